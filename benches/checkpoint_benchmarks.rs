@@ -49,6 +49,7 @@ fn create_test_checkpoint(num_variables: usize, num_completed_steps: usize) -> W
             duration: Duration::from_secs(1),
             completed_at: chrono::Utc::now(),
             retry_state: None,
+            changes: None,
         })
         .collect();
 
@@ -77,6 +78,9 @@ fn create_test_checkpoint(num_variables: usize, num_completed_steps: usize) -> W
         error_recovery_state: None,
         retry_checkpoint_state: None,
         variable_checkpoint_state: None,
+        failure_chains: Vec::new(),
+        repo: None,
+        step_selection: None,
     }
 }
 
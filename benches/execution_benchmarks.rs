@@ -280,6 +280,9 @@ steps:
                     error_recovery_state: None,
                     retry_checkpoint_state: None,
                     variable_checkpoint_state: None,
+                    failure_chains: Vec::new(),
+                    repo: None,
+                    step_selection: None,
                 };
 
                 checkpoint_manager
@@ -328,6 +331,7 @@ fn bench_workflow_resume_operation(c: &mut Criterion) {
                             duration: Duration::from_secs(1),
                             completed_at: chrono::Utc::now(),
                             retry_state: None,
+                            changes: None,
                         })
                         .collect(),
                     variable_state: create_variable_context(100),
@@ -341,6 +345,9 @@ fn bench_workflow_resume_operation(c: &mut Criterion) {
                     error_recovery_state: None,
                     retry_checkpoint_state: None,
                     variable_checkpoint_state: None,
+                    failure_chains: Vec::new(),
+                    repo: None,
+                    step_selection: None,
                 };
 
                 let rt_local = Runtime::new().unwrap();
@@ -490,6 +490,7 @@ fn bench_checkpoint_save_performance(c: &mut Criterion) {
                                 branch_name: Some(format!("branch_{}", i)),
                                 worktree_session_id: Some(format!("session_{}", i)),
                                 files_modified: vec![format!("file_{}.rs", i)],
+                                no_changes: None,
                             });
                         }
 
@@ -561,6 +562,7 @@ fn bench_checkpoint_load_performance(c: &mut Criterion) {
                                 branch_name: None,
                                 worktree_session_id: None,
                                 files_modified: vec![],
+                                no_changes: None,
                             });
                         }
 
@@ -646,6 +648,7 @@ fn bench_job_state_manager_with_checkpointing(c: &mut Criterion) {
                                         branch_name: None,
                                         worktree_session_id: None,
                                         files_modified: vec![],
+                                        no_changes: None,
                                     },
                                 )
                                 .await
@@ -343,6 +343,7 @@ fn create_large_checkpoint(
             duration: Duration::from_secs(1),
             completed_at: chrono::Utc::now(),
             retry_state: None,
+            changes: None,
         })
         .collect();
 
@@ -369,6 +370,9 @@ fn create_large_checkpoint(
         error_recovery_state: None,
         retry_checkpoint_state: None,
         variable_checkpoint_state: None,
+        failure_chains: Vec::new(),
+        repo: None,
+        step_selection: None,
     }
 }
 
@@ -29,6 +29,7 @@ fn create_test_env(temp_dir: &TempDir) -> ExecutionEnvironment {
         project_dir: Arc::new(temp_dir.path().to_path_buf()),
         worktree_name: Some(Arc::from("bench-worktree")),
         session_id: Arc::from("bench-session"),
+        package: None,
     }
 }
 
@@ -64,6 +65,8 @@ fn bench_setup_phase_scaling(c: &mut Criterion) {
                         commands,
                         timeout: Some(60),
                         capture_outputs: HashMap::new(),
+                        artifacts: Vec::new(),
+                        shared_context: None,
                     };
 
                     let executor =
@@ -128,6 +131,24 @@ fn bench_map_phase_work_items(c: &mut Criterion) {
                         distinct: None,
                         timeout_config: None,
                         workflow_env: std::collections::HashMap::new(),
+                        rebase_onto: None,
+                        speculative: None,
+                        item_key: None,
+                        affinity: Vec::new(),
+                        build_cache: Vec::new(),
+                        network: Default::default(),
+                        strict_variables: false,
+                        setup_artifacts: None,
+                        chaos: None,
+                        junit: None,
+                        idempotency: None,
+                        notifications: None,
+                        claude_permissions: None,
+                        changed_since: None,
+                        changed_path: None,
+                        protected_paths: None,
+                        seed: None,
+                        failures: None,
                     };
 
                     let executor =
@@ -174,6 +195,8 @@ fn bench_reduce_phase_aggregation(c: &mut Criterion) {
                     let reduce_phase = ReducePhase {
                         commands,
                         timeout_secs: Some(60),
+                        results_file: None,
+                        claude_permissions: None,
                     };
 
                     let executor =
@@ -218,6 +241,8 @@ fn bench_full_workflow_execution(c: &mut Criterion) {
                 ],
                 timeout: Some(30),
                 capture_outputs: HashMap::new(),
+                artifacts: Vec::new(),
+                shared_context: None,
             };
 
             // Map phase: minimal processing
@@ -235,6 +260,24 @@ fn bench_full_workflow_execution(c: &mut Criterion) {
                 distinct: None,
                 timeout_config: None,
                 workflow_env: std::collections::HashMap::new(),
+                rebase_onto: None,
+                speculative: None,
+                item_key: None,
+                affinity: Vec::new(),
+                build_cache: Vec::new(),
+                network: Default::default(),
+                strict_variables: false,
+                setup_artifacts: None,
+                chaos: None,
+                junit: None,
+                idempotency: None,
+                notifications: None,
+                claude_permissions: None,
+                changed_since: None,
+                changed_path: None,
+                protected_paths: None,
+                seed: None,
+                failures: None,
             };
 
             // Reduce phase: aggregate results
@@ -244,6 +287,8 @@ fn bench_full_workflow_execution(c: &mut Criterion) {
                     ..Default::default()
                 }],
                 timeout_secs: Some(30),
+                results_file: None,
+                claude_permissions: None,
             };
 
             let coordinator = PhaseCoordinator::new(
@@ -302,6 +347,24 @@ fn bench_phase_transitions(c: &mut Criterion) {
                 distinct: None,
                 timeout_config: None,
                 workflow_env: std::collections::HashMap::new(),
+                rebase_onto: None,
+                speculative: None,
+                item_key: None,
+                affinity: Vec::new(),
+                build_cache: Vec::new(),
+                network: Default::default(),
+                strict_variables: false,
+                setup_artifacts: None,
+                chaos: None,
+                junit: None,
+                idempotency: None,
+                notifications: None,
+                claude_permissions: None,
+                changed_since: None,
+                changed_path: None,
+                protected_paths: None,
+                seed: None,
+                failures: None,
             };
 
             let coordinator = PhaseCoordinator::new(None, map_phase, None, subprocess.clone());
@@ -344,6 +407,24 @@ fn bench_phase_scaling(c: &mut Criterion) {
                         distinct: None,
                         timeout_config: None,
                         workflow_env: std::collections::HashMap::new(),
+                        rebase_onto: None,
+                        speculative: None,
+                        item_key: None,
+                        affinity: Vec::new(),
+                        build_cache: Vec::new(),
+                        network: Default::default(),
+                        strict_variables: false,
+                        setup_artifacts: None,
+                        chaos: None,
+                        junit: None,
+                        idempotency: None,
+                        notifications: None,
+                        claude_permissions: None,
+                        changed_since: None,
+                        changed_path: None,
+                        protected_paths: None,
+                        seed: None,
+                        failures: None,
                     };
 
                     let coordinator =
@@ -376,6 +457,8 @@ fn bench_executor_trait_overhead(c: &mut Criterion) {
                     }],
                     timeout: Some(30),
                     capture_outputs: HashMap::new(),
+                    artifacts: Vec::new(),
+                    shared_context: None,
                 };
 
                 let executor =
@@ -213,6 +213,7 @@ fn bench_display_formatting(c: &mut Criterion) {
                 None
             },
             total_items: if i % 3 == 0 { Some(1000) } else { None },
+            disk_usage_bytes: None,
         })
         .collect();
 
@@ -224,6 +225,7 @@ fn bench_display_formatting(c: &mut Criterion) {
             interrupted: 25,
             failed: 25,
             completed: 25,
+            disk_usage_bytes: None,
         },
     };
 
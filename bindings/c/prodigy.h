@@ -0,0 +1,57 @@
+/*
+ * C interface to Prodigy's workflow runner.
+ *
+ * Build the shared library with:
+ *
+ *     cargo rustc --release --lib --features prodigy-ffi --crate-type cdylib
+ *
+ * See src/ffi/mod.rs for the full documentation of each function.
+ */
+
+#ifndef PRODIGY_H
+#define PRODIGY_H
+
+#include <stdbool.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef enum ProdigyJobStatus {
+    PRODIGY_JOB_RUNNING = 0,
+    PRODIGY_JOB_SUCCEEDED = 1,
+    PRODIGY_JOB_FAILED = 2,
+    PRODIGY_JOB_CANCELLED = 3,
+} ProdigyJobStatus;
+
+typedef struct ProdigyJobProgress {
+    ProdigyJobStatus status;
+    uint64_t elapsed_ms;
+} ProdigyJobProgress;
+
+typedef struct ProdigyJob ProdigyJob;
+
+/* Start a workflow; repo_path may be NULL. Returns NULL on invalid input. */
+ProdigyJob *prodigy_job_start(const char *workflow_path,
+                              const char *repo_path,
+                              bool auto_accept);
+
+/* Report job status and elapsed time without blocking. */
+ProdigyJobProgress prodigy_job_poll(const ProdigyJob *job);
+
+/* Request cancellation of a running job. It reports PRODIGY_JOB_RUNNING until
+   running steps finish and the session is saved as interrupted. */
+void prodigy_job_cancel(const ProdigyJob *job);
+
+/* Error message of a failed job, or NULL. Valid until prodigy_job_free. */
+const char *prodigy_job_error(const ProdigyJob *job);
+
+/* Release the handle, cancelling and joining the job if still running. */
+void prodigy_job_free(ProdigyJob *job);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* PRODIGY_H */
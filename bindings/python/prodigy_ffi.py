@@ -0,0 +1,108 @@
+"""ctypes bindings for Prodigy's C interface (see bindings/c/prodigy.h).
+
+Build the shared library first:
+
+    cargo rustc --release --lib --features prodigy-ffi --crate-type cdylib
+
+Then point ``PRODIGY_LIB`` at it (``target/release/libprodigy.so`` on Linux,
+``libprodigy.dylib`` on macOS) or pass the path to ``load()``.
+
+Example::
+
+    import time
+    import prodigy_ffi
+
+    with prodigy_ffi.Job("workflows/debtmap.yml", repo="/path/to/repo",
+                         auto_accept=True) as job:
+        while job.poll().status is prodigy_ffi.Status.RUNNING:
+            time.sleep(1)
+        if job.poll().status is prodigy_ffi.Status.FAILED:
+            raise RuntimeError(job.error())
+"""
+
+import ctypes
+import enum
+import os
+from typing import NamedTuple, Optional
+
+
+class Status(enum.IntEnum):
+    RUNNING = 0
+    SUCCEEDED = 1
+    FAILED = 2
+    CANCELLED = 3
+
+
+class Progress(NamedTuple):
+    status: Status
+    elapsed_ms: int
+
+
+class _Progress(ctypes.Structure):
+    _fields_ = [("status", ctypes.c_int), ("elapsed_ms", ctypes.c_uint64)]
+
+
+_lib: Optional[ctypes.CDLL] = None
+
+
+def load(path: Optional[str] = None) -> ctypes.CDLL:
+    """Load the Prodigy shared library and declare its function signatures."""
+    global _lib
+    if _lib is not None:
+        return _lib
+
+    lib = ctypes.CDLL(path or os.environ.get("PRODIGY_LIB", "libprodigy.so"))
+    lib.prodigy_job_start.argtypes = [ctypes.c_char_p, ctypes.c_char_p, ctypes.c_bool]
+    lib.prodigy_job_start.restype = ctypes.c_void_p
+    lib.prodigy_job_poll.argtypes = [ctypes.c_void_p]
+    lib.prodigy_job_poll.restype = _Progress
+    lib.prodigy_job_cancel.argtypes = [ctypes.c_void_p]
+    lib.prodigy_job_cancel.restype = None
+    lib.prodigy_job_error.argtypes = [ctypes.c_void_p]
+    lib.prodigy_job_error.restype = ctypes.c_char_p
+    lib.prodigy_job_free.argtypes = [ctypes.c_void_p]
+    lib.prodigy_job_free.restype = None
+    _lib = lib
+    return lib
+
+
+class Job:
+    """A workflow running in-process on a background thread."""
+
+    def __init__(self, workflow: str, repo: Optional[str] = None, auto_accept: bool = False):
+        self._handle = None
+        self._lib = load()
+        handle = self._lib.prodigy_job_start(
+            os.fsencode(workflow),
+            os.fsencode(repo) if repo is not None else None,
+            auto_accept,
+        )
+        if not handle:
+            raise ValueError(f"Failed to start Prodigy workflow {workflow!r}")
+        self._handle = handle
+
+    def poll(self) -> Progress:
+        progress = self._lib.prodigy_job_poll(self._handle)
+        return Progress(Status(progress.status), progress.elapsed_ms)
+
+    def cancel(self) -> None:
+        self._lib.prodigy_job_cancel(self._handle)
+
+    def error(self) -> Optional[str]:
+        message = self._lib.prodigy_job_error(self._handle)
+        return message.decode("utf-8", "replace") if message is not None else None
+
+    def close(self) -> None:
+        """Free the job, cancelling it if it is still running."""
+        if self._handle:
+            self._lib.prodigy_job_free(self._handle)
+            self._handle = None
+
+    def __enter__(self) -> "Job":
+        return self
+
+    def __exit__(self, *exc) -> None:
+        self.close()
+
+    def __del__(self) -> None:
+        self.close()
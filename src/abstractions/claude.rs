@@ -8,6 +8,7 @@ use crate::subprocess::{
     ClaudeRunner as SubprocessClaudeRunner, ProcessCommand, ProcessCommandBuilder,
     SubprocessManager,
 };
+use crate::{styled_eprintln, styled_println};
 use anyhow::Result;
 use async_trait::async_trait;
 use std::collections::HashMap;
@@ -460,7 +461,7 @@ impl RealClaudeClient {
             OutputClassification::TransientFailure(ref stderr) => {
                 if Self::should_continue_retry(&classification, attempt, max_retries) {
                     if verbose {
-                        eprintln!(
+                        styled_eprintln!(
                             "⚠️  Transient error detected: {}",
                             stderr.lines().next().unwrap_or("Unknown error")
                         );
@@ -494,7 +495,7 @@ impl RealClaudeClient {
 
         if Self::should_retry_error(&error_type, attempt, max_retries) {
             if verbose {
-                eprintln!("⚠️  IO error: {error}");
+                styled_eprintln!("⚠️  IO error: {error}");
             }
             Ok((None, Some(error.to_string())))
         } else {
@@ -543,7 +544,7 @@ impl ClaudeClient for RealClaudeClient {
             if attempt > 0 {
                 let delay = Self::calculate_retry_delay(attempt);
                 if verbose {
-                    println!(
+                    styled_println!(
                         "⏳ Retrying {command} after {delay:?} (attempt {attempt}/{max_retries})"
                     );
                 }
@@ -616,7 +617,7 @@ impl ClaudeClient for RealClaudeClient {
     }
 
     async fn code_review(&self, verbose: bool) -> Result<bool> {
-        println!("🤖 Running /prodigy-code-review...");
+        styled_println!("🤖 Running /prodigy-code-review...");
 
         let mut env_vars = HashMap::new();
         if std::env::var("PRODIGY_AUTOMATION").unwrap_or_default() == "true" {
@@ -644,7 +645,7 @@ impl ClaudeClient for RealClaudeClient {
     }
 
     async fn implement_spec(&self, spec_id: &str, verbose: bool) -> Result<bool> {
-        println!("🔧 Running /prodigy-implement-spec {spec_id}...");
+        styled_println!("🔧 Running /prodigy-implement-spec {spec_id}...");
 
         let mut env_vars = HashMap::new();
         if std::env::var("PRODIGY_AUTOMATION").unwrap_or_default() == "true" {
@@ -679,7 +680,7 @@ impl ClaudeClient for RealClaudeClient {
     }
 
     async fn lint(&self, verbose: bool) -> Result<bool> {
-        println!("🧹 Running /prodigy-lint...");
+        styled_println!("🧹 Running /prodigy-lint...");
 
         let mut env_vars = HashMap::new();
         if std::env::var("PRODIGY_AUTOMATION").unwrap_or_default() == "true" {
@@ -3,7 +3,7 @@
 //! Provides trait-based abstraction for git commands to enable
 //! testing without actual git repository access.
 
-use crate::subprocess::{ProcessCommandBuilder, SubprocessManager};
+use crate::subprocess::{ProcessCommand, ProcessCommandBuilder, SubprocessManager};
 use anyhow::Result;
 use async_trait::async_trait;
 use std::path::Path;
@@ -24,6 +24,18 @@ pub trait GitOperations: Send + Sync {
         working_dir: &Path,
     ) -> Result<std::process::Output>;
 
+    /// Execute a git command in a specific directory with its own index file
+    ///
+    /// The command sees `index_file` as `GIT_INDEX_FILE`, so it can stage files
+    /// and write trees without touching the repository's index.
+    async fn git_command_with_index(
+        &self,
+        args: &[&str],
+        description: &str,
+        working_dir: &Path,
+        index_file: &Path,
+    ) -> Result<std::process::Output>;
+
     /// Get the last commit message
     async fn get_last_commit_message(&self) -> Result<String>;
 
@@ -75,6 +87,38 @@ impl RealGitOperations {
             subprocess,
         }
     }
+
+    /// Run a git command in a directory with exclusive access
+    async fn run_exclusive(
+        &self,
+        command: ProcessCommand,
+        description: &str,
+    ) -> Result<std::process::Output> {
+        // Acquire the mutex to ensure exclusive access
+        let _guard = self.git_mutex.lock().await;
+
+        let output = self
+            .subprocess
+            .runner()
+            .run(command)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to execute git {}: {}", description, e))?;
+
+        if !output.status.success() {
+            let stderr = &output.stderr;
+            return Err(anyhow::anyhow!(
+                "Git {} failed: {}",
+                description,
+                stderr.trim()
+            ));
+        }
+
+        Ok(std::process::Output {
+            status: std::process::ExitStatus::from_raw(output.status.code().unwrap_or(0)),
+            stdout: output.stdout.into_bytes(),
+            stderr: output.stderr.into_bytes(),
+        })
+    }
 }
 
 impl Default for RealGitOperations {
@@ -165,35 +209,26 @@ impl GitOperations for RealGitOperations {
         description: &str,
         working_dir: &Path,
     ) -> Result<std::process::Output> {
-        // Acquire the mutex to ensure exclusive access
-        let _guard = self.git_mutex.lock().await;
-
         let command = ProcessCommandBuilder::new("git")
             .args(args)
             .current_dir(working_dir)
             .build();
+        self.run_exclusive(command, description).await
+    }
 
-        let output = self
-            .subprocess
-            .runner()
-            .run(command)
-            .await
-            .map_err(|e| anyhow::anyhow!("Failed to execute git {}: {}", description, e))?;
-
-        if !output.status.success() {
-            let stderr = &output.stderr;
-            return Err(anyhow::anyhow!(
-                "Git {} failed: {}",
-                description,
-                stderr.trim()
-            ));
-        }
-
-        Ok(std::process::Output {
-            status: std::process::ExitStatus::from_raw(output.status.code().unwrap_or(0)),
-            stdout: output.stdout.into_bytes(),
-            stderr: output.stderr.into_bytes(),
-        })
+    async fn git_command_with_index(
+        &self,
+        args: &[&str],
+        description: &str,
+        working_dir: &Path,
+        index_file: &Path,
+    ) -> Result<std::process::Output> {
+        let command = ProcessCommandBuilder::new("git")
+            .args(args)
+            .current_dir(working_dir)
+            .env("GIT_INDEX_FILE", &index_file.to_string_lossy())
+            .build();
+        self.run_exclusive(command, description).await
     }
 
     async fn create_worktree(&self, name: &str, path: &Path) -> Result<()> {
@@ -348,6 +383,16 @@ impl GitOperations for MockGitOperations {
         // For mocks, just delegate to git_command since we don't actually run commands
         self.git_command(args, description).await
     }
+
+    async fn git_command_with_index(
+        &self,
+        args: &[&str],
+        description: &str,
+        _working_dir: &Path,
+        _index_file: &Path,
+    ) -> Result<std::process::Output> {
+        self.git_command(args, description).await
+    }
 }
 
 #[cfg(test)]
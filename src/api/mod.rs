@@ -0,0 +1,73 @@
+//! Stable library API for embedding Prodigy in other Rust tools
+//!
+//! The rest of the crate is organized around the CLI and its internal
+//! architecture, and module paths there change as the code evolves. This
+//! module is the supported entry point for library consumers: it exposes
+//! builder-style runners for the main operations and re-exports the types
+//! those runners accept and return.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use prodigy::api::WorkflowRunner;
+//!
+//! # async fn example() -> prodigy::api::Result<()> {
+//! WorkflowRunner::builder()
+//!     .workflow("workflows/debtmap.yml")
+//!     .path("/path/to/repo")
+//!     .worktree(true)
+//!     .auto_accept(true)
+//!     .run()
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Operations
+//!
+//! - [`WorkflowRunner`] - run a workflow file (standard or MapReduce)
+//! - [`ResumeRunner`] - resume an interrupted session or MapReduce job
+//! - [`BatchRunner`] - run one command over every file matching a pattern
+//!
+//! Workflows can also be constructed in code with [`WorkflowBuilder`] and
+//! passed to [`WorkflowRunnerBuilder::workflow_config`].
+//!
+//! # Stability
+//!
+//! Items in `prodigy::api` follow semantic versioning:
+//!
+//! - Names and signatures of everything exported from this module only change
+//!   in a major release (or a minor release while the crate is `0.x`).
+//! - New builder methods and new re-exports may be added in any minor release.
+//! - Builders never gain new required settings; new settings always default to
+//!   the previous behavior.
+//!
+//! Everything outside `prodigy::api` is considered internal and may change in
+//! any release.
+
+mod runner;
+
+pub use runner::{
+    BatchRunner, BatchRunnerBuilder, ResumeRunner, ResumeRunnerBuilder, WorkflowRunner,
+    WorkflowRunnerBuilder,
+};
+
+// Result and error types
+pub use crate::cook::execution::{AgentResult, AgentStatus, ResumeResult};
+pub use crate::error::{ErrorCode, ProdigyError};
+
+// Workflow configuration types
+pub use crate::config::{
+    BuiltWorkflow, MapPhaseBuilder, MapReduceWorkflowConfig, Step, WorkflowBuilder, WorkflowConfig,
+};
+
+// Record/replay fixtures for deterministic workflow tests
+pub use crate::cook::execution::{CommandFixture, FixtureKind, ReplaySession};
+
+// Chaos testing for checkpoint, resume, and DLQ handling
+pub use crate::cook::execution::chaos::{
+    ChaosAction, ChaosConfig, ChaosMonkey, ChaosStats, ChaosTarget,
+};
+
+/// Result type returned by the runners in this module
+pub type Result<T> = anyhow::Result<T>;
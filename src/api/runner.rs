@@ -0,0 +1,624 @@
+//! Builder-style runners for workflow, resume, and batch operations
+
+use crate::config::BuiltWorkflow;
+use crate::cook::command::{CookCommand, ExplicitFlags};
+use crate::cook::execution::chaos::ChaosConfig;
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Runs a workflow file
+///
+/// Created through [`WorkflowRunner::builder`].
+#[derive(Debug, Clone)]
+pub struct WorkflowRunner {
+    command: CookCommand,
+    config: Option<BuiltWorkflow>,
+}
+
+impl WorkflowRunner {
+    /// Start building a workflow run
+    pub fn builder() -> WorkflowRunnerBuilder {
+        WorkflowRunnerBuilder::default()
+    }
+
+    /// Run the workflow to completion
+    pub async fn run(self) -> Result<()> {
+        match self.config {
+            Some(config) => {
+                let (workflow, mapreduce) = config.into_parts();
+                crate::cook::cook_with_workflow(self.command, workflow, mapreduce).await
+            }
+            None => crate::cook::cook(self.command).await,
+        }
+    }
+}
+
+/// Builder for [`WorkflowRunner`]
+#[derive(Debug, Clone, Default)]
+pub struct WorkflowRunnerBuilder {
+    workflow: Option<PathBuf>,
+    config: Option<BuiltWorkflow>,
+    path: Option<PathBuf>,
+    package: Option<PathBuf>,
+    worktree: Option<bool>,
+    max_iterations: Option<u32>,
+    map: Vec<String>,
+    changed_only: Option<String>,
+    args: Vec<String>,
+    fail_fast: Option<bool>,
+    auto_accept: bool,
+    verbosity: u8,
+    dry_run: bool,
+    read_only: bool,
+    record: Option<PathBuf>,
+    junit_output: Option<PathBuf>,
+    profile_steps: bool,
+    keep_going: bool,
+    replay: Option<PathBuf>,
+    chaos: Option<ChaosConfig>,
+    seed: Option<u64>,
+    answers_file: Option<PathBuf>,
+    environment: Option<String>,
+    params: HashMap<String, Value>,
+}
+
+impl WorkflowRunnerBuilder {
+    /// Workflow file to execute (required)
+    pub fn workflow(mut self, path: impl Into<PathBuf>) -> Self {
+        self.workflow = Some(path.into());
+        self
+    }
+
+    /// Run a workflow built with [`WorkflowBuilder`](crate::config::WorkflowBuilder)
+    /// instead of loading a workflow file
+    pub fn workflow_config(mut self, config: BuiltWorkflow) -> Self {
+        self.config = Some(config);
+        self
+    }
+
+    /// Repository to run in (defaults to the current directory)
+    pub fn path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    /// Monorepo package to run in, relative to the repository root
+    pub fn package(mut self, package: impl Into<PathBuf>) -> Self {
+        self.package = Some(package.into());
+        self
+    }
+
+    /// Run in an isolated git worktree (default: `true`, or the workflow's `defaults`)
+    ///
+    /// When disabled, commands run directly in the repository.
+    pub fn worktree(mut self, enabled: bool) -> Self {
+        self.worktree = Some(enabled);
+        self
+    }
+
+    /// Maximum number of iterations (default: 1, or the workflow's `defaults`)
+    pub fn max_iterations(mut self, iterations: u32) -> Self {
+        self.max_iterations = Some(iterations);
+        self
+    }
+
+    /// Add a file pattern to map the workflow over
+    pub fn map(mut self, pattern: impl Into<String>) -> Self {
+        self.map.push(pattern.into());
+        self
+    }
+
+    /// Only process map items referring to files changed since `base`,
+    /// overriding the workflow's `map.changed_since`
+    pub fn changed_only(mut self, base: impl Into<String>) -> Self {
+        self.changed_only = Some(base.into());
+        self
+    }
+
+    /// Add an argument available to commands as `$ARG`
+    pub fn arg(mut self, arg: impl Into<String>) -> Self {
+        self.args.push(arg.into());
+        self
+    }
+
+    /// Stop on the first failure when processing multiple inputs
+    /// (default: `false`, or the workflow's `defaults`)
+    pub fn fail_fast(mut self, fail_fast: bool) -> Self {
+        self.fail_fast = Some(fail_fast);
+        self
+    }
+
+    /// Automatically answer yes to all prompts
+    pub fn auto_accept(mut self, auto_accept: bool) -> Self {
+        self.auto_accept = auto_accept;
+        self
+    }
+
+    /// Output verbosity (0 = normal, 1 = verbose, 2 = debug, 3 = trace)
+    pub fn verbosity(mut self, verbosity: u8) -> Self {
+        self.verbosity = verbosity;
+        self
+    }
+
+    /// Preview commands without executing them
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Forbid commits and changes to tracked files
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Record Claude and shell command outputs to a fixture file
+    pub fn record(mut self, path: impl Into<PathBuf>) -> Self {
+        self.record = Some(path.into());
+        self
+    }
+
+    /// Write step and work item results as JUnit XML
+    pub fn junit_output(mut self, path: impl Into<PathBuf>) -> Self {
+        self.junit_output = Some(path.into());
+        self
+    }
+
+    /// Record wall time, CPU time and peak memory of each step
+    pub fn profile_steps(mut self, profile_steps: bool) -> Self {
+        self.profile_steps = profile_steps;
+        self
+    }
+
+    /// Run every step even if some fail; only failed `required: true` steps fail the run
+    pub fn keep_going(mut self, keep_going: bool) -> Self {
+        self.keep_going = keep_going;
+        self
+    }
+
+    /// Replay command outputs from a fixture file
+    pub fn replay(mut self, path: impl Into<PathBuf>) -> Self {
+        self.replay = Some(path.into());
+        self
+    }
+
+    /// Randomly fail, delay, or kill commands to test checkpoint, resume, and DLQ handling
+    pub fn chaos(mut self, config: ChaosConfig) -> Self {
+        self.chaos = Some(config);
+        self
+    }
+
+    /// Make retry jitter, chaos decisions and agent scheduling repeatable
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Answer prompts from a file written by `prodigy sessions answers`
+    pub fn answers_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.answers_file = Some(path.into());
+        self
+    }
+
+    /// Merge the workflow's `overrides:` entry for this environment
+    pub fn environment(mut self, name: impl Into<String>) -> Self {
+        self.environment = Some(name.into());
+        self
+    }
+
+    /// Set a workflow template parameter
+    pub fn param(mut self, name: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.params.insert(name.into(), value.into());
+        self
+    }
+
+    /// Validate the settings and build the runner
+    pub fn build(self) -> Result<WorkflowRunner> {
+        // Built workflows are identified by name in checkpoints and session metadata
+        let playbook = match (&self.workflow, &self.config) {
+            (Some(path), _) => path.clone(),
+            (None, Some(config)) => PathBuf::from(format!("{}.yml", built_workflow_name(config))),
+            (None, None) => {
+                return Err(anyhow!(
+                    "A workflow file or built workflow configuration is required"
+                ))
+            }
+        };
+
+        if self.record.is_some() && self.replay.is_some() {
+            return Err(anyhow!("record and replay cannot be used together"));
+        }
+
+        Ok(WorkflowRunner {
+            command: CookCommand {
+                playbook,
+                path: self.path,
+                package: self.package,
+                max_iterations: self.max_iterations.unwrap_or(1),
+                map: self.map,
+                changed_only: self.changed_only,
+                args: self.args,
+                fail_fast: self.fail_fast.unwrap_or(false),
+                auto_accept: self.auto_accept,
+                resume: None,
+                verbosity: self.verbosity,
+                quiet: false,
+                dry_run: self.dry_run,
+                read_only: self.read_only,
+                record: self.record,
+                junit_output: self.junit_output,
+                profile_steps: self.profile_steps,
+                replay: self.replay,
+                chaos: self.chaos,
+                seed: self.seed,
+                answers_file: self.answers_file,
+                environment: self.environment,
+                no_worktree: !self.worktree.unwrap_or(true),
+                explicit_flags: ExplicitFlags {
+                    worktree: self.worktree.is_some(),
+                    max_iterations: self.max_iterations.is_some(),
+                    fail_fast: self.fail_fast.is_some(),
+                },
+                params: self.params,
+                explain_failure: false,
+                keep_going: self.keep_going,
+                steps: Default::default(),
+            },
+            config: self.config,
+        })
+    }
+
+    /// Build the runner and run the workflow
+    pub async fn run(self) -> Result<()> {
+        self.build()?.run().await
+    }
+}
+
+/// Name used to identify a built workflow
+fn built_workflow_name(config: &BuiltWorkflow) -> &str {
+    match config {
+        BuiltWorkflow::Standard(workflow) => workflow.name.as_deref().unwrap_or("workflow"),
+        BuiltWorkflow::MapReduce(workflow) => &workflow.name,
+    }
+}
+
+/// Resumes an interrupted workflow session or MapReduce job
+///
+/// Created through [`ResumeRunner::builder`].
+#[derive(Debug, Clone)]
+pub struct ResumeRunner {
+    session_id: String,
+    from_checkpoint: Option<String>,
+    path: Option<PathBuf>,
+    relocate: Option<PathBuf>,
+}
+
+impl ResumeRunner {
+    /// Start building a resume operation
+    pub fn builder() -> ResumeRunnerBuilder {
+        ResumeRunnerBuilder::default()
+    }
+
+    /// Resume the session to completion
+    pub async fn run(self) -> Result<()> {
+        crate::cli::commands::run_resume_workflow(
+            Some(self.session_id),
+            false,
+            self.from_checkpoint,
+            self.path,
+            self.relocate,
+        )
+        .await
+    }
+}
+
+/// Builder for [`ResumeRunner`]
+#[derive(Debug, Clone, Default)]
+pub struct ResumeRunnerBuilder {
+    session_id: Option<String>,
+    from_checkpoint: Option<String>,
+    path: Option<PathBuf>,
+    relocate: Option<PathBuf>,
+}
+
+impl ResumeRunnerBuilder {
+    /// Session ID or MapReduce job ID to resume (required)
+    pub fn session(mut self, session_id: impl Into<String>) -> Self {
+        self.session_id = Some(session_id.into());
+        self
+    }
+
+    /// Resume from a specific checkpoint instead of the latest one
+    pub fn from_checkpoint(mut self, checkpoint_id: impl Into<String>) -> Self {
+        self.from_checkpoint = Some(checkpoint_id.into());
+        self
+    }
+
+    /// Repository the session belongs to
+    pub fn path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    /// Resume from the checkout now at `new_path` after it was moved or copied
+    pub fn relocate(mut self, new_path: impl Into<PathBuf>) -> Self {
+        self.relocate = Some(new_path.into());
+        self
+    }
+
+    /// Validate the settings and build the runner
+    pub fn build(self) -> Result<ResumeRunner> {
+        let session_id = self
+            .session_id
+            .ok_or_else(|| anyhow!("A session ID is required to resume"))?;
+        Ok(ResumeRunner {
+            session_id,
+            from_checkpoint: self.from_checkpoint,
+            path: self.path,
+            relocate: self.relocate,
+        })
+    }
+
+    /// Build the runner and resume the session
+    pub async fn run(self) -> Result<()> {
+        self.build()?.run().await
+    }
+}
+
+/// Runs a single command over every file matching a pattern
+///
+/// Created through [`BatchRunner::builder`].
+#[derive(Debug, Clone)]
+pub struct BatchRunner {
+    pattern: String,
+    command: String,
+    parallel: usize,
+    retry: Option<u32>,
+    timeout: Option<u64>,
+    path: Option<PathBuf>,
+}
+
+impl BatchRunner {
+    /// Start building a batch run
+    pub fn builder() -> BatchRunnerBuilder {
+        BatchRunnerBuilder::default()
+    }
+
+    /// Run the batch to completion
+    pub async fn run(self) -> Result<()> {
+        crate::cli::commands::run_batch_command(
+            Some(self.pattern),
+            false,
+            self.command,
+            self.parallel,
+            self.retry,
+            self.timeout,
+            self.path,
+        )
+        .await
+    }
+}
+
+/// Builder for [`BatchRunner`]
+#[derive(Debug, Clone)]
+pub struct BatchRunnerBuilder {
+    pattern: Option<String>,
+    command: Option<String>,
+    parallel: usize,
+    retry: Option<u32>,
+    timeout: Option<u64>,
+    path: Option<PathBuf>,
+}
+
+impl Default for BatchRunnerBuilder {
+    fn default() -> Self {
+        Self {
+            pattern: None,
+            command: None,
+            parallel: 5,
+            retry: None,
+            timeout: None,
+            path: None,
+        }
+    }
+}
+
+impl BatchRunnerBuilder {
+    /// File pattern to match, e.g. `"src/**/*.rs"` (required)
+    pub fn pattern(mut self, pattern: impl Into<String>) -> Self {
+        self.pattern = Some(pattern.into());
+        self
+    }
+
+    /// Command to run for each file, e.g. `"claude: /refactor"` (required)
+    pub fn command(mut self, command: impl Into<String>) -> Self {
+        self.command = Some(command.into());
+        self
+    }
+
+    /// Number of parallel workers (default: 5)
+    pub fn parallel(mut self, parallel: usize) -> Self {
+        self.parallel = parallel;
+        self
+    }
+
+    /// Number of retry attempts per file
+    pub fn retry(mut self, attempts: u32) -> Self {
+        self.retry = Some(attempts);
+        self
+    }
+
+    /// Timeout per file in seconds
+    pub fn timeout(mut self, seconds: u64) -> Self {
+        self.timeout = Some(seconds);
+        self
+    }
+
+    /// Repository to run in (defaults to the current directory)
+    pub fn path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    /// Validate the settings and build the runner
+    pub fn build(self) -> Result<BatchRunner> {
+        let pattern = self
+            .pattern
+            .ok_or_else(|| anyhow!("A file pattern is required"))?;
+        let command = self
+            .command
+            .ok_or_else(|| anyhow!("A command is required"))?;
+        if self.parallel == 0 {
+            return Err(anyhow!("parallel must be at least 1"));
+        }
+        Ok(BatchRunner {
+            pattern,
+            command,
+            parallel: self.parallel,
+            retry: self.retry,
+            timeout: self.timeout,
+            path: self.path,
+        })
+    }
+
+    /// Build the runner and run the batch
+    pub async fn run(self) -> Result<()> {
+        self.build()?.run().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_workflow_runner_requires_workflow() {
+        let err = WorkflowRunner::builder().build().unwrap_err();
+        assert!(err.to_string().contains("workflow file or built workflow"));
+    }
+
+    #[test]
+    fn test_workflow_runner_maps_settings() {
+        let runner = WorkflowRunner::builder()
+            .workflow("workflow.yml")
+            .path("/repo")
+            .worktree(false)
+            .arg("42")
+            .param("target", "src")
+            .read_only(true)
+            .build()
+            .unwrap();
+
+        let cmd = runner.command;
+        assert_eq!(cmd.playbook, PathBuf::from("workflow.yml"));
+        assert_eq!(cmd.path, Some(PathBuf::from("/repo")));
+        assert!(cmd.no_worktree);
+        assert!(cmd.explicit_flags.worktree);
+        assert!(!cmd.explicit_flags.max_iterations);
+        assert!(cmd.read_only);
+        assert_eq!(cmd.args, vec!["42"]);
+        assert_eq!(cmd.params.get("target"), Some(&Value::from("src")));
+        assert_eq!(cmd.max_iterations, 1);
+    }
+
+    #[test]
+    fn test_workflow_runner_accepts_built_workflow() {
+        let config = crate::config::WorkflowBuilder::new()
+            .name("generated")
+            .shell("cargo test")
+            .build()
+            .unwrap();
+        let runner = WorkflowRunner::builder()
+            .workflow_config(config)
+            .build()
+            .unwrap();
+        assert_eq!(runner.command.playbook, PathBuf::from("generated.yml"));
+        assert!(runner.config.is_some());
+    }
+
+    #[test]
+    fn test_workflow_runner_defaults_to_worktree() {
+        let runner = WorkflowRunner::builder()
+            .workflow("workflow.yml")
+            .build()
+            .unwrap();
+        assert!(!runner.command.no_worktree);
+    }
+
+    #[test]
+    fn test_workflow_runner_rejects_record_with_replay() {
+        let result = WorkflowRunner::builder()
+            .workflow("workflow.yml")
+            .record("a.json")
+            .replay("b.json")
+            .build();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_workflow_runner_passes_chaos_config() {
+        let config = ChaosConfig::default().fail_percent(10.0).seed(7);
+        let runner = WorkflowRunner::builder()
+            .workflow("workflow.yml")
+            .chaos(config.clone())
+            .build()
+            .unwrap();
+        assert_eq!(runner.command.chaos, Some(config));
+    }
+
+    #[test]
+    fn test_workflow_runner_passes_seed() {
+        let runner = WorkflowRunner::builder()
+            .workflow("workflow.yml")
+            .seed(1234)
+            .build()
+            .unwrap();
+        assert_eq!(runner.command.seed, Some(1234));
+    }
+
+    #[test]
+    fn test_workflow_runner_passes_answers_file() {
+        let runner = WorkflowRunner::builder()
+            .workflow("workflow.yml")
+            .answers_file("answers.yml")
+            .build()
+            .unwrap();
+        assert_eq!(
+            runner.command.answers_file,
+            Some(PathBuf::from("answers.yml"))
+        );
+    }
+
+    #[test]
+    fn test_resume_runner_requires_session() {
+        assert!(ResumeRunner::builder().build().is_err());
+        let runner = ResumeRunner::builder()
+            .session("session-123")
+            .build()
+            .unwrap();
+        assert_eq!(runner.session_id, "session-123");
+    }
+
+    #[test]
+    fn test_batch_runner_validation() {
+        assert!(BatchRunner::builder()
+            .command("shell: true")
+            .build()
+            .is_err());
+        assert!(BatchRunner::builder()
+            .pattern("*.rs")
+            .command("shell: true")
+            .parallel(0)
+            .build()
+            .is_err());
+
+        let runner = BatchRunner::builder()
+            .pattern("*.rs")
+            .command("shell: true")
+            .build()
+            .unwrap();
+        assert_eq!(runner.parallel, 5);
+    }
+}
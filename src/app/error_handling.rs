@@ -2,6 +2,7 @@
 //!
 //! This module provides centralized error handling for the application.
 
+use crate::styled_eprintln;
 use tracing::error;
 
 /// Handle fatal errors and exit with appropriate status code
@@ -18,26 +19,32 @@ pub fn handle_fatal_error(error: anyhow::Error, verbose: u8) -> ! {
 
     error!("Fatal error: {}", error);
 
+    // A run stopped by SIGTERM or SIGHUP has already saved its checkpoint
+    if let Some(signal) = crate::cook::signal_handler::shutdown_requested() {
+        styled_eprintln!("Stopped after {}: {error}", signal.name());
+        std::process::exit(crate::cook::signal_handler::GRACEFUL_SHUTDOWN_EXIT_CODE);
+    }
+
     // Check if it's a ProdigyError for better handling
     let exit_code = if let Some(prodigy_err) = error.downcast_ref::<ProdigyError>() {
         // Use the user-friendly message for ProdigyError
-        eprintln!("{}", prodigy_err.user_message());
+        styled_eprintln!("{}", prodigy_err.user_message());
 
         // Show developer message with full context chain in verbose mode
         if verbose >= 1 {
-            eprintln!("\nContext Chain:\n{}", prodigy_err.developer_message());
+            styled_eprintln!("\nContext Chain:\n{}", prodigy_err.developer_message());
         }
 
         prodigy_err.exit_code()
     } else {
         // Fallback for non-ProdigyError errors
-        eprintln!("Error: {error}");
+        styled_eprintln!("Error: {error}");
 
         // Show chain in verbose mode for non-ProdigyError errors
         if verbose >= 1 {
-            eprintln!("\nError chain:");
+            styled_eprintln!("\nError chain:");
             for (i, cause) in error.chain().enumerate() {
-                eprintln!("  {}: {}", i, cause);
+                styled_eprintln!("  {}: {}", i, cause);
             }
         }
 
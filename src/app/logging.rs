@@ -3,6 +3,7 @@
 //! This module handles all logging setup and configuration for the application.
 
 use crate::app::config::AppConfig;
+use crate::output::{self, StyledWriter};
 use tracing::{debug, trace};
 
 /// Initialize tracing/logging for the application
@@ -14,6 +15,8 @@ pub fn init_logging(config: &AppConfig) {
         .with_target(config.verbose >= 2) // Show target module for -vv and above
         .with_thread_ids(config.verbose >= 3) // Show thread IDs for -vvv
         .with_line_number(config.verbose >= 3) // Show line numbers for -vvv
+        .with_ansi(output::current().color)
+        .with_writer(|| StyledWriter::new(std::io::stdout()))
         .init();
 
     debug!("Prodigy started with verbosity level: {}", config.verbose);
@@ -3,6 +3,8 @@
 //! This module defines all command-line interface structures used by Prodigy.
 //! It includes the main CLI structure and all subcommand definitions.
 
+use crate::cli::events::parse_rfc3339;
+use chrono::{DateTime, Utc};
 use clap::{Parser, Subcommand};
 use std::path::PathBuf;
 
@@ -16,11 +18,20 @@ pub struct Cli {
     #[arg(short, long, action = clap::ArgAction::Count, global = true)]
     pub verbose: u8,
 
+    /// Print plain ASCII output without colors or emoji
+    #[arg(long, visible_alias = "no-color", global = true)]
+    pub plain: bool,
+
+    /// Directory for sessions, events, DLQ and worktrees (overrides PRODIGY_STORAGE_DIR and config)
+    #[arg(long, value_name = "DIR")]
+    pub storage_dir: Option<PathBuf>,
+
     #[command(subcommand)]
     pub command: Option<Commands>,
 }
 
 #[derive(Subcommand)]
+#[allow(clippy::large_enum_variant)]
 pub enum Commands {
     /// Run a workflow file
     #[command(name = "run")]
@@ -32,22 +43,46 @@ pub enum Commands {
         #[arg(short = 'p', long)]
         path: Option<PathBuf>,
 
-        /// Maximum number of iterations
-        #[arg(short = 'n', long, default_value = "1")]
-        max_iterations: u32,
+        /// Monorepo package to run in, relative to the repository root (e.g. crates/foo)
+        #[arg(long, value_name = "DIR")]
+        package: Option<PathBuf>,
+
+        /// File listing repositories to run the workflow in, one path or git URL per line
+        #[arg(long, value_name = "FILE", conflicts_with_all = ["path", "package", "resume", "watch"])]
+        repos: Option<PathBuf>,
+
+        /// Maximum number of iterations [default: 1, or the workflow's `defaults`]
+        #[arg(short = 'n', long)]
+        max_iterations: Option<u32>,
+
+        /// Run in an isolated worktree (the default unless the workflow's `defaults` say otherwise)
+        #[arg(long, conflicts_with = "no_worktree")]
+        worktree: bool,
+
+        /// Run directly in the repository instead of an isolated worktree
+        #[arg(long)]
+        no_worktree: bool,
 
         /// File patterns to map over
         #[arg(long, value_name = "PATTERN")]
         map: Vec<String>,
 
+        /// Only process map items referring to files changed since a git ref (default: origin/main)
+        #[arg(long, value_name = "REF", num_args = 0..=1, default_missing_value = "origin/main")]
+        changed_only: Option<String>,
+
         /// Direct arguments to pass to commands
         #[arg(long, value_name = "VALUE")]
         args: Vec<String>,
 
         /// Stop on first failure when processing multiple files
-        #[arg(long)]
+        #[arg(long, conflicts_with = "no_fail_fast")]
         fail_fast: bool,
 
+        /// Keep going after failures even if the workflow's `defaults` enable fail_fast
+        #[arg(long)]
+        no_fail_fast: bool,
+
         /// Automatically answer yes to all prompts
         #[arg(short = 'y', long = "yes")]
         auto_accept: bool,
@@ -60,6 +95,58 @@ pub enum Commands {
         #[arg(long, help = "Preview commands without executing them")]
         dry_run: bool,
 
+        /// Read-only mode - forbid commits and changes to tracked files
+        #[arg(long)]
+        read_only: bool,
+
+        /// Record Claude and shell command outputs to a fixture file
+        #[arg(long, value_name = "FILE", conflicts_with = "replay")]
+        record: Option<PathBuf>,
+
+        /// Replay command outputs from a fixture file recorded with --record
+        #[arg(long, value_name = "FILE")]
+        replay: Option<PathBuf>,
+
+        /// Write step and MapReduce item results as JUnit XML for CI test reports
+        #[arg(long, value_name = "FILE")]
+        junit_output: Option<PathBuf>,
+
+        /// Print and store the wall time, CPU time and peak memory of each step
+        #[arg(long)]
+        profile_steps: bool,
+
+        /// On failure, print the failing step, its output tail and how to resume
+        #[arg(long)]
+        explain_failure: bool,
+
+        /// Run every step even if some fail; only failed `required: true` steps fail the run
+        #[arg(long)]
+        keep_going: bool,
+
+        /// Run only these steps, by 1-based number or step id (e.g. 3,5-7 or lint)
+        #[arg(long, value_name = "STEPS", conflicts_with = "resume")]
+        only_steps: Option<String>,
+
+        /// Skip these steps, by 1-based number or step id (e.g. 2 or lint)
+        #[arg(long, value_name = "STEPS", conflicts_with = "resume")]
+        skip_steps: Option<String>,
+
+        /// Chaos testing: randomly fail, delay, or kill commands (e.g. fail=10,delay=20,kill=5,seed=42)
+        #[arg(long, value_name = "SPEC")]
+        chaos: Option<crate::cook::execution::chaos::ChaosConfig>,
+
+        /// Make retry jitter, chaos decisions and agent start order repeatable (recorded in the session)
+        #[arg(long, value_name = "N")]
+        seed: Option<u64>,
+
+        /// Answer prompts from a file written by `prodigy sessions answers` instead of asking
+        #[arg(long, value_name = "PATH")]
+        answers_file: Option<PathBuf>,
+
+        /// Merge the workflow's `overrides:` entry for this environment (e.g. ci)
+        #[arg(long = "env", value_name = "NAME")]
+        environment: Option<String>,
+
         /// Template parameters (key=value)
         #[arg(long = "param", value_name = "KEY=VALUE")]
         params: Vec<String>,
@@ -67,6 +154,10 @@ pub enum Commands {
         /// Parameter file (JSON or YAML)
         #[arg(long = "param-file")]
         param_file: Option<PathBuf>,
+
+        /// Keep running and re-run the workflow when it or the config changes
+        #[arg(long, conflicts_with = "resume")]
+        watch: bool,
     },
 
     /// Execute a single command with retry support
@@ -92,7 +183,12 @@ pub enum Commands {
     #[command(name = "batch")]
     Batch {
         /// File pattern to match (e.g., "*.py", "src/**/*.ts")
-        pattern: String,
+        #[arg(required_unless_present = "stdin")]
+        pattern: Option<String>,
+
+        /// Read work items from stdin: one file path or JSON item per line, or a JSON array
+        #[arg(long, conflicts_with = "pattern")]
+        stdin: bool,
 
         /// Command to execute for each file
         #[arg(long)]
@@ -132,6 +228,10 @@ pub enum Commands {
         /// Working directory
         #[arg(short = 'p', long)]
         path: Option<PathBuf>,
+
+        /// Resume from the checkout now at this path, after moving or copying it
+        #[arg(long, value_name = "NEW_PATH")]
+        relocate: Option<PathBuf>,
     },
 
     /// List available workflow checkpoints
@@ -141,6 +241,77 @@ pub enum Commands {
         command: CheckpointCommands,
     },
 
+    /// Record a progress mark in the active session from inside a workflow step
+    #[command(name = "checkpoint-mark")]
+    CheckpointMark {
+        /// Label identifying the completed sub-phase
+        #[arg(long)]
+        label: String,
+
+        /// Exit successfully only if the label was already marked
+        #[arg(long)]
+        check: bool,
+
+        /// Session ID (defaults to PRODIGY_SESSION_ID, set for workflow steps)
+        #[arg(long)]
+        session: Option<String>,
+    },
+
+    /// Generate markdown documentation for a workflow file
+    Docs {
+        /// Workflow file to document
+        workflow: PathBuf,
+
+        /// Write the documentation to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Draw a workflow file as a Mermaid or Graphviz diagram
+    Graph {
+        /// Workflow file to draw
+        workflow: PathBuf,
+
+        /// Diagram format (mermaid, dot)
+        #[arg(long, default_value = "mermaid")]
+        format: String,
+
+        /// Write the diagram to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Run a MapReduce workflow's input through its data pipeline and show the items
+    #[command(name = "inspect-input")]
+    InspectInput {
+        /// MapReduce workflow file
+        workflow: PathBuf,
+
+        /// Input to use instead of `map.input`, e.g. a sample of a file the setup phase creates
+        #[arg(long)]
+        input: Option<String>,
+
+        /// Number of resulting items to print
+        #[arg(long, default_value = "20")]
+        show: usize,
+
+        /// Output format (table, json)
+        #[arg(long, default_value = "table")]
+        output_format: String,
+    },
+
+    /// Restore the working tree snapshot taken before a `snapshot: true` step
+    #[command(name = "rollback")]
+    Rollback {
+        /// Step number whose snapshot to restore, as shown in progress output
+        #[arg(long = "to-step")]
+        to_step: usize,
+
+        /// Session ID (defaults to PRODIGY_SESSION_ID)
+        #[arg(long)]
+        session: Option<String>,
+    },
+
     /// Manage git worktrees for parallel Prodigy sessions
     Worktree {
         #[command(subcommand)]
@@ -160,6 +331,34 @@ pub enum Commands {
         #[arg(short, long)]
         path: Option<PathBuf>,
     },
+    /// Guided first-run setup: commands, storage, parallelism and Claude CLI check
+    Setup {
+        /// Accept the defaults without prompting
+        #[arg(short, long)]
+        yes: bool,
+
+        /// Directory for sessions, events and worktrees
+        #[arg(long)]
+        storage_dir: Option<PathBuf>,
+
+        /// Default max_parallel for MapReduce workflows (1-100)
+        #[arg(long)]
+        max_parallel: Option<usize>,
+
+        /// Skip the Claude CLI test prompt
+        #[arg(long)]
+        skip_claude_check: bool,
+
+        /// Project to install commands into (defaults to current)
+        #[arg(short, long)]
+        path: Option<PathBuf>,
+    },
+    /// Manage the storage directory
+    #[command(name = "storage")]
+    Storage {
+        #[command(subcommand)]
+        command: StorageCommands,
+    },
     /// Migrate workflow YAML files to simplified syntax
     #[command(name = "migrate-yaml")]
     MigrateYaml {
@@ -211,9 +410,25 @@ pub enum Commands {
         #[arg(long, default_value = "2")]
         max_retries: u32,
 
+        /// Run at most this many agents at once (overrides the workflow's max_parallel)
+        #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+        max_parallel: Option<u64>,
+
+        /// Process at most this many of the remaining items
+        #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+        max_items: Option<u64>,
+
+        /// Timeout in seconds for each remaining item (overrides agent_timeout_secs)
+        #[arg(long, value_name = "SECS", value_parser = clap::value_parser!(u64).range(1..))]
+        item_timeout: Option<u64>,
+
         /// Path to the repository (defaults to current directory)
         #[arg(short = 'p', long)]
         path: Option<PathBuf>,
+
+        /// Resume from the checkout now at this path, after moving or copying it
+        #[arg(long, value_name = "NEW_PATH")]
+        relocate: Option<PathBuf>,
     },
     /// View and search MapReduce events
     #[command(name = "events")]
@@ -221,12 +436,24 @@ pub enum Commands {
         #[command(subcommand)]
         command: EventCommands,
     },
+    /// Start workflows automatically when matching events occur
+    #[command(name = "triggers")]
+    Triggers {
+        #[command(subcommand)]
+        command: TriggerCommands,
+    },
     /// Manage Dead Letter Queue for failed MapReduce items
     #[command(name = "dlq")]
     Dlq {
         #[command(subcommand)]
         command: DlqCommands,
     },
+    /// Inspect and compare MapReduce job runs
+    #[command(name = "jobs")]
+    Jobs {
+        #[command(subcommand)]
+        command: JobCommands,
+    },
     /// Manage workflow sessions
     Sessions {
         #[command(subcommand)]
@@ -251,8 +478,11 @@ pub enum Commands {
         web: Option<u16>,
     },
     /// Manage and view Claude JSON logs
-    #[command(name = "logs")]
+    #[command(name = "logs", args_conflicts_with_subcommands = true)]
     Logs {
+        #[command(subcommand)]
+        command: Option<LogsCommands>,
+
         /// Session ID to view logs for
         session_id: Option<String>,
 
@@ -274,6 +504,22 @@ pub enum Commands {
         #[command(subcommand)]
         command: CleanCommands,
     },
+    /// Run the test cases in a workflow's `tests:` block with Claude mocked
+    #[command(name = "test-workflow")]
+    TestWorkflow {
+        /// Workflow file with a `tests:` block
+        workflow: PathBuf,
+
+        /// Only run test cases whose name contains this text
+        #[arg(long)]
+        filter: Option<String>,
+    },
+    /// Discover the Claude commands available to workflows
+    #[command(name = "commands")]
+    ClaudeCommands {
+        #[command(subcommand)]
+        command: ClaudeCommandsCommand,
+    },
     /// Manage workflow templates
     #[command(name = "template")]
     Template {
@@ -292,11 +538,229 @@ pub enum Commands {
         #[command(subcommand)]
         command: ChangelogCommands,
     },
+    /// Convert workflows from other automation systems
+    #[command(name = "import")]
+    Import {
+        #[command(subcommand)]
+        command: ImportCommands,
+    },
+    /// Report which Claude sessions produced which commits
+    #[command(name = "analytics")]
+    Analytics {
+        #[command(subcommand)]
+        command: AnalyticsCommands,
+    },
+    /// Generate a shareable report for a MapReduce job
+    #[command(name = "report")]
+    Report {
+        /// Job ID to report on
+        job_id: String,
+
+        /// Report format (html, json)
+        #[arg(long, default_value = "html")]
+        format: String,
+
+        /// File to write (defaults to <job_id>-report.<format>)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Price of input tokens in USD per million, for the cost estimate
+        #[arg(long, default_value = "3.0")]
+        input_cost: f64,
+
+        /// Price of output tokens in USD per million, for the cost estimate
+        #[arg(long, default_value = "15.0")]
+        output_cost: f64,
+
+        /// Price of cached tokens in USD per million, for the cost estimate
+        #[arg(long, default_value = "0.3")]
+        cache_cost: f64,
+    },
+    /// Export workflow findings as SARIF for code scanning
+    #[command(name = "sarif")]
+    Sarif {
+        /// Export the work items of this MapReduce job
+        #[arg(long, conflicts_with = "input", required_unless_present = "input")]
+        job: Option<String>,
+
+        /// Export findings from a JSON file, such as a captured artifact
+        #[arg(long)]
+        input: Option<PathBuf>,
+
+        /// Path to the findings array inside --input (e.g. "items")
+        #[arg(long, requires = "input")]
+        json_path: Option<String>,
+
+        /// File to write (defaults to <job>.sarif or findings.sarif)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Field holding the rule ID (e.g. "debt_type")
+        #[arg(long)]
+        rule_field: Option<String>,
+
+        /// Field holding the file path (e.g. "location.file")
+        #[arg(long)]
+        file_field: Option<String>,
+
+        /// Field holding the line number (e.g. "location.line")
+        #[arg(long)]
+        line_field: Option<String>,
+
+        /// Field holding the result message
+        #[arg(long)]
+        message_field: Option<String>,
+
+        /// Field holding the severity (string or numeric score)
+        #[arg(long)]
+        level_field: Option<String>,
+
+        /// Tool name reported in the SARIF driver
+        #[arg(long, default_value = "prodigy")]
+        tool_name: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum LogsCommands {
+    /// Pretty-print the Claude streaming logs of a workflow run
+    Claude {
+        /// Workflow session ID or MapReduce job ID
+        session_id: String,
+
+        /// Keep printing new messages as they are streamed
+        #[arg(short, long)]
+        follow: bool,
+
+        /// Only show logs of this MapReduce agent
+        #[arg(long)]
+        agent: Option<String>,
+
+        /// Only show logs of this MapReduce work item
+        #[arg(long)]
+        item: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum AnalyticsCommands {
+    /// Link Claude sessions to the Prodigy runs and commits they produced
+    Sessions {
+        /// Only runs of this workflow session or MapReduce job
+        #[arg(long)]
+        session: Option<String>,
+
+        /// Only this Claude session ID (or a prefix of it)
+        #[arg(long)]
+        claude_session: Option<String>,
+
+        /// Only the run that produced this commit SHA (or a prefix of it)
+        #[arg(long)]
+        commit: Option<String>,
+
+        /// Only runs that made commits
+        #[arg(long)]
+        with_commits: bool,
+
+        /// Show at most this many of the most recent runs
+        #[arg(long, default_value = "50")]
+        limit: usize,
+
+        /// Output format (table, json)
+        #[arg(long, default_value = "table")]
+        output_format: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum StorageCommands {
+    /// Move events, DLQ items, checkpoints and session state to a new directory
+    Migrate {
+        /// New storage directory (must be empty or not exist)
+        new_path: PathBuf,
+
+        /// Show what would move without moving anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum TriggerCommands {
+    /// Run a workflow whenever an event matches a rule
+    Add {
+        /// Rule over event fields, e.g. 'JobFailed && job.name == "nightly-debt"'
+        #[arg(long, value_name = "RULE")]
+        on: String,
+
+        /// Workflow file to run when the rule matches
+        #[arg(long, value_name = "WORKFLOW")]
+        run: PathBuf,
+    },
+
+    /// List the repository's triggers
+    #[command(name = "ls", alias = "list")]
+    Ls,
+
+    /// Remove a trigger
+    #[command(name = "rm", alias = "remove")]
+    Rm {
+        /// Trigger ID
+        id: String,
+    },
+
+    /// Watch the event stream and run the workflows of matching triggers
+    Daemon {
+        /// Seconds between checks for new events
+        #[arg(long, default_value = "2")]
+        interval: u64,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ImportCommands {
+    /// Convert a GitHub Actions workflow into a Prodigy workflow
+    Gha {
+        /// GitHub Actions workflow file, e.g. .github/workflows/ci.yml
+        workflow: PathBuf,
+
+        /// Only convert this job
+        #[arg(long)]
+        job: Option<String>,
+
+        /// Write the Prodigy workflow to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum JobCommands {
+    /// Compare item results between two runs of a workflow
+    Compare {
+        /// Baseline job ID
+        job_a: String,
+
+        /// Job ID to compare against the baseline
+        job_b: String,
+
+        /// Field or item_key expression matching items across runs (defaults to the whole item)
+        #[arg(long)]
+        key: Option<String>,
+
+        /// Output format (human, json)
+        #[arg(long, default_value = "human")]
+        output_format: String,
+
+        /// Exit with an error if any item regressed
+        #[arg(long)]
+        fail_on_regression: bool,
+    },
 }
 
 #[derive(Subcommand)]
 pub enum SessionCommands {
-    /// List resumable sessions
+    /// List sessions with their status and staleness
     #[command(name = "ls", alias = "list")]
     List,
     /// Show details about a specific session
@@ -313,6 +777,30 @@ pub enum SessionCommands {
         #[arg(short, long)]
         force: bool,
     },
+    /// Export a session's checkpoints, events, DLQ items and logs to a bundle
+    Export {
+        /// Session ID to export
+        session_id: String,
+        /// Bundle file to write (defaults to <session_id>.tar.zst)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Unpack a session bundle for inspection
+    Import {
+        /// Bundle file created by `sessions export`
+        bundle: PathBuf,
+        /// Directory to unpack into (defaults to ~/.prodigy/imported/<session_id>)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Write the prompts answered in a session to an answers file for `run --answers-file`
+    Answers {
+        /// Session ID whose answers to write
+        session_id: String,
+        /// Answers file to write (defaults to stdout)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -364,6 +852,21 @@ pub enum CheckpointCommands {
         path: Option<PathBuf>,
     },
 
+    /// Show retry attempts and failure history per step
+    #[command(name = "retries")]
+    Retries {
+        /// Workflow ID
+        workflow_id: String,
+
+        /// Clear the retry counters of a step (index or command) before resuming
+        #[arg(long, value_name = "STEP")]
+        reset_retries: Option<String>,
+
+        /// Working directory
+        #[arg(short = 'p', long)]
+        path: Option<PathBuf>,
+    },
+
     /// Validate checkpoint integrity
     #[command(name = "validate")]
     Validate {
@@ -431,13 +934,37 @@ pub enum EventCommands {
         #[arg(long)]
         since: Option<u64>,
 
-        /// Limit number of events shown
+        /// Show only events at or after this RFC 3339 time
+        #[arg(long, value_parser = parse_rfc3339)]
+        from: Option<DateTime<Utc>>,
+
+        /// Show only events at or before this RFC 3339 time
+        #[arg(long, value_parser = parse_rfc3339)]
+        to: Option<DateTime<Utc>>,
+
+        /// Condition on event fields, e.g. 'duration > 60s' (repeatable)
+        #[arg(long = "where", value_name = "CONDITION")]
+        conditions: Vec<String>,
+
+        /// Number of events per page
         #[arg(long, default_value = "100")]
         limit: usize,
 
+        /// Page of events to show, 1 for the most recent
+        #[arg(long, default_value = "1")]
+        page: usize,
+
+        /// Continue from a cursor printed by a previous listing
+        #[arg(long)]
+        cursor: Option<u64>,
+
         /// Path to events file
         #[arg(long, default_value = ".prodigy/events/mapreduce_events.jsonl")]
         file: PathBuf,
+
+        /// Output format (human, json, yaml, table)
+        #[arg(long, default_value = "human")]
+        output_format: String,
     },
     /// Show event statistics
     Stats {
@@ -448,6 +975,18 @@ pub enum EventCommands {
         /// Group statistics by field (job_id, event_type, agent_id)
         #[arg(long, default_value = "event_type")]
         group_by: String,
+
+        /// Output format (human, json, yaml)
+        #[arg(long, default_value = "human")]
+        output_format: String,
+
+        /// Show duration percentiles, events over time, and failure rate
+        #[arg(long)]
+        metrics: bool,
+
+        /// Time bucket width in minutes for --metrics
+        #[arg(long, default_value = "1")]
+        bucket_minutes: i64,
     },
     /// Search events by pattern
     Search {
@@ -628,6 +1167,38 @@ pub enum DlqCommands {
         #[arg(long)]
         yes: bool,
     },
+    /// Mark matching items for reprocessing on the next resume
+    Requeue {
+        /// Job ID containing the items
+        #[arg(long)]
+        job_id: String,
+
+        /// Filter expression over DLQ item fields (e.g. 'error_type == "timeout"')
+        #[arg(long = "where", value_name = "EXPR")]
+        where_expr: String,
+
+        /// Show matching items without changing them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Permanently remove matching items from the DLQ
+    Drop {
+        /// Job ID containing the items
+        #[arg(long)]
+        job_id: String,
+
+        /// Filter expression over DLQ item fields (e.g. 'failure_count >= 5')
+        #[arg(long = "where", value_name = "EXPR")]
+        where_expr: String,
+
+        /// Show matching items without removing them
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Confirm removal without prompting
+        #[arg(long)]
+        yes: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -638,7 +1209,7 @@ pub enum WorktreeCommands {
         /// Output in JSON format
         #[arg(long)]
         json: bool,
-        /// Show detailed information for each session
+        /// Show detailed information for each session, including disk usage
         #[arg(short = 'd', long)]
         detailed: bool,
     },
@@ -649,6 +1220,9 @@ pub enum WorktreeCommands {
         /// Merge all Prodigy worktrees
         #[arg(long)]
         all: bool,
+        /// Command to run after each merge; merges that fail it are rolled back and skipped
+        #[arg(long, value_name = "COMMAND")]
+        verify: Option<String>,
     },
     /// Clean up completed or abandoned worktrees
     Clean {
@@ -687,6 +1261,11 @@ pub enum WorktreeCommands {
         #[arg(short = 'f', long)]
         force: bool,
     },
+    /// Resume an interrupted session in its existing worktree
+    Recover {
+        /// Name of the worktree session to recover (e.g., session-abc123)
+        name: String,
+    },
 }
 
 #[derive(Subcommand)]
@@ -783,6 +1362,25 @@ pub enum CleanCommands {
     },
 }
 
+#[derive(Subcommand, Debug)]
+pub enum ClaudeCommandsCommand {
+    /// List commands in .claude/commands and bundled templates not yet installed
+    #[command(name = "ls", alias = "list")]
+    Ls {
+        /// Project directory (defaults to the current directory)
+        #[arg(short = 'p', long)]
+        path: Option<PathBuf>,
+
+        /// Only list commands installed in the project
+        #[arg(long)]
+        installed: bool,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
 #[derive(Subcommand, Debug)]
 pub enum TemplateCommand {
     /// Register a new workflow template
@@ -0,0 +1,179 @@
+//! Analytics command implementation
+//!
+//! `prodigy analytics sessions` links Claude conversations to the Prodigy
+//! sessions that ran them and the commits they produced.
+
+use crate::cli::args::AnalyticsCommands;
+use crate::cook::execution::claude_logs;
+use crate::cook::execution::claude_runs::{self, ClaudeRun, RunFilter};
+use anyhow::{bail, Result};
+
+/// Execute an analytics command
+pub async fn run_analytics_command(command: AnalyticsCommands) -> Result<()> {
+    match command {
+        AnalyticsCommands::Sessions {
+            session,
+            claude_session,
+            commit,
+            with_commits,
+            limit,
+            output_format,
+        } => {
+            if !matches!(output_format.as_str(), "table" | "json") {
+                bail!(
+                    "Unsupported output format '{}' (expected table or json)",
+                    output_format
+                );
+            }
+            let filter = RunFilter {
+                run_id: session,
+                claude_session,
+                commit,
+                with_commits,
+            };
+            let runs = claude_runs::load_runs(&claude_logs::streaming_log_dir()?)?;
+            let runs = select_runs(runs, &filter, limit);
+
+            if output_format == "json" {
+                println!("{}", serde_json::to_string_pretty(&runs)?);
+            } else if runs.is_empty() {
+                println!("No recorded Claude runs match");
+            } else {
+                print!("{}", format_table(&runs));
+            }
+            Ok(())
+        }
+    }
+}
+
+/// The most recent `limit` matching runs, oldest first (pure function)
+fn select_runs(runs: Vec<ClaudeRun>, filter: &RunFilter, limit: usize) -> Vec<ClaudeRun> {
+    let matching: Vec<ClaudeRun> = runs.into_iter().filter(|run| filter.matches(run)).collect();
+    let skip = matching.len().saturating_sub(limit);
+    matching.into_iter().skip(skip).collect()
+}
+
+/// Table with one row per run and its commits (pure function)
+fn format_table(runs: &[ClaudeRun]) -> String {
+    let rows: Vec<[String; 6]> = runs
+        .iter()
+        .map(|run| {
+            let run_id = match (&run.job_id, &run.agent_id) {
+                (Some(job), Some(agent)) => format!("{} / {}", job, agent),
+                (Some(job), None) => job.clone(),
+                _ => run.session_id.clone().unwrap_or_else(|| "-".to_string()),
+            };
+            let commits = if run.commits.is_empty() {
+                "-".to_string()
+            } else {
+                run.commits
+                    .iter()
+                    .map(|sha| sha.chars().take(8).collect::<String>())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            };
+            [
+                run.started_at.format("%Y-%m-%d %H:%M").to_string(),
+                run.claude_session_id
+                    .clone()
+                    .unwrap_or_else(|| "-".to_string()),
+                run_id,
+                run.branch.clone().unwrap_or_else(|| "-".to_string()),
+                run.command
+                    .split_whitespace()
+                    .next()
+                    .unwrap_or("")
+                    .to_string(),
+                commits,
+            ]
+        })
+        .collect();
+
+    let headers = [
+        "STARTED",
+        "CLAUDE SESSION",
+        "PRODIGY RUN",
+        "BRANCH",
+        "COMMAND",
+        "COMMITS",
+    ];
+    let mut widths = headers.map(str::len);
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let line = |cells: &[String]| -> String {
+        let padded: Vec<String> = cells
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect();
+        format!("{}\n", padded.join("  ").trim_end())
+    };
+    let mut out = line(&headers.map(String::from));
+    for row in &rows {
+        out.push_str(&line(row));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use std::path::PathBuf;
+
+    fn run(session: &str, commits: &[&str]) -> ClaudeRun {
+        ClaudeRun {
+            claude_session_id: Some(format!("claude-{}", session)),
+            command: "/prodigy-fix src/lib.rs".to_string(),
+            started_at: Utc::now(),
+            finished_at: Utc::now(),
+            success: true,
+            session_id: Some(session.to_string()),
+            job_id: None,
+            agent_id: None,
+            item_id: None,
+            branch: Some(format!("prodigy-{}", session)),
+            commits: commits.iter().map(|c| c.to_string()).collect(),
+            log: PathBuf::from("/logs/a.jsonl"),
+        }
+    }
+
+    #[test]
+    fn test_select_runs_keeps_most_recent() {
+        let runs = vec![
+            run("session-1", &[]),
+            run("session-2", &["abc"]),
+            run("session-3", &["def"]),
+        ];
+        let filter = RunFilter {
+            with_commits: true,
+            ..Default::default()
+        };
+        let selected = select_runs(runs, &filter, 1);
+        assert_eq!(selected.len(), 1);
+        assert_eq!(selected[0].session_id.as_deref(), Some("session-3"));
+    }
+
+    #[test]
+    fn test_format_table() {
+        let table = format_table(&[run("session-1", &["0123456789abcdef", "fedcba9876543210"])]);
+        let lines: Vec<&str> = table.lines().collect();
+        assert!(lines[0].starts_with("STARTED"));
+        let cells: Vec<&str> = lines[1].split_whitespace().skip(2).collect();
+        assert_eq!(
+            cells,
+            vec![
+                "claude-session-1",
+                "session-1",
+                "prodigy-session-1",
+                "/prodigy-fix",
+                "01234567",
+                "fedcba98"
+            ]
+        );
+    }
+}
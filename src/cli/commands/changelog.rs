@@ -4,6 +4,7 @@
 //! following the Keep a Changelog format and Semantic Versioning.
 
 use crate::cli::args::ChangelogCommands;
+use crate::styled_println;
 use anyhow::{Context, Result};
 use chrono::Local;
 use git2::Repository;
@@ -92,7 +93,7 @@ async fn generate_changelog(
         println!("{}", markdown);
     } else {
         fs::write(output, markdown).context("Failed to write changelog file")?;
-        println!("✓ Changelog generated: {}", output.display());
+        styled_println!("✓ Changelog generated: {}", output.display());
     }
 
     Ok(())
@@ -354,9 +355,9 @@ async fn validate_changelog(file: &Path, strict: bool, json: bool) -> Result<()>
         });
         println!("{}", serde_json::to_string_pretty(&result)?);
     } else if issues.is_empty() {
-        println!("✓ Changelog is valid");
+        styled_println!("✓ Changelog is valid");
     } else {
-        println!("✗ Changelog validation failed:");
+        styled_println!("✗ Changelog validation failed:");
         for issue in &issues {
             println!("  - {}", issue);
         }
@@ -400,7 +401,7 @@ async fn prepare_release(
             println!("{}", content);
         } else {
             fs::write("CHANGELOG.md", content)?;
-            println!("✓ Release {} prepared in CHANGELOG.md", version);
+            styled_println!("✓ Release {} prepared in CHANGELOG.md", version);
         }
     } else {
         anyhow::bail!("Could not find Unreleased section in CHANGELOG.md");
@@ -440,7 +441,7 @@ async fn export_changelog(
         _ => anyhow::bail!("Unsupported format: {}", format),
     }
 
-    println!("✓ Exported to {}", output.display());
+    styled_println!("✓ Exported to {}", output.display());
     Ok(())
 }
 
@@ -652,7 +653,7 @@ async fn add_changelog_entry(
     }
 
     fs::write("CHANGELOG.md", content)?;
-    println!("✓ Added entry to {} section", section);
+    styled_println!("✓ Added entry to {} section", section);
 
     Ok(())
 }
@@ -5,6 +5,7 @@
 
 use crate::cli::args::CheckpointCommands;
 use crate::storage::{extract_repo_name, GlobalStorage};
+use crate::styled_println;
 use anyhow::{Context, Result};
 use std::path::{Path, PathBuf};
 
@@ -199,7 +200,7 @@ pub async fn run_checkpoints_command(command: CheckpointCommands, verbose: u8) -
                 initialize_checkpoint_storage(&working_dir).await?;
 
             if !checkpoint_dir.exists() {
-                println!("No checkpoints found.");
+                styled_println!("No checkpoints found.");
                 return Ok(());
             }
 
@@ -222,7 +223,7 @@ pub async fn run_checkpoints_command(command: CheckpointCommands, verbose: u8) -
                 initialize_checkpoint_storage(&working_dir).await?;
 
             if !checkpoint_dir.exists() {
-                println!("No checkpoints to clean.");
+                styled_println!("No checkpoints to clean.");
                 return Ok(());
             }
 
@@ -232,7 +233,7 @@ pub async fn run_checkpoints_command(command: CheckpointCommands, verbose: u8) -
                 }
                 CleanOperation::CleanAll => clean_all_checkpoints(&checkpoint_dir, force).await,
                 CleanOperation::InvalidRequest => {
-                    println!("Please specify --workflow-id or --all");
+                    styled_println!("Please specify --workflow-id or --all");
                     Ok(())
                 }
             }
@@ -250,6 +251,25 @@ pub async fn run_checkpoints_command(command: CheckpointCommands, verbose: u8) -
 
             show_checkpoint_details(&checkpoint_manager, &workflow_id).await
         }
+        CheckpointCommands::Retries {
+            workflow_id,
+            reset_retries,
+            path,
+        } => {
+            let working_dir = resolve_working_directory(path)?;
+            let (checkpoint_dir, checkpoint_id) =
+                resolve_retry_checkpoint(&working_dir, &workflow_id).await?;
+
+            let checkpoint_manager = create_checkpoint_manager(checkpoint_dir);
+
+            show_or_reset_retries(
+                &checkpoint_manager,
+                &checkpoint_id,
+                &workflow_id,
+                reset_retries.as_deref(),
+            )
+            .await
+        }
         CheckpointCommands::Validate {
             checkpoint_id,
             repair,
@@ -280,6 +300,108 @@ pub async fn run_checkpoints_command(command: CheckpointCommands, verbose: u8) -
     }
 }
 
+/// Record or check a progress mark in the active session
+///
+/// With `--check`, exits with status 1 when the label has not been marked,
+/// so scripts can guard sub-phases with
+/// `prodigy checkpoint-mark --check --label X || { ...; prodigy checkpoint-mark --label X; }`.
+pub async fn run_checkpoint_mark_command(
+    label: &str,
+    check: bool,
+    session: Option<String>,
+) -> Result<()> {
+    use crate::cook::workflow::checkpoint_marks::{self, SESSION_ID_ENV};
+
+    let session_id = session
+        .or_else(|| std::env::var(SESSION_ID_ENV).ok())
+        .filter(|id| !id.is_empty())
+        .with_context(|| {
+            format!(
+                "No active session: run inside a workflow step or pass --session (reads {})",
+                SESSION_ID_ENV
+            )
+        })?;
+    let path = checkpoint_marks::marks_file_path(&session_id)?;
+
+    if check {
+        let marks = checkpoint_marks::load_marks(&path).await?;
+        if checkpoint_marks::is_marked(&marks, label) {
+            styled_println!("Checkpoint '{}' already completed", label);
+            return Ok(());
+        }
+        std::process::exit(1);
+    }
+
+    checkpoint_marks::append_mark(&path, label).await?;
+    styled_println!("Checkpoint marked: {}", label);
+    Ok(())
+}
+
+/// Restore the snapshot taken before step `to_step` of a session
+///
+/// Resets the session's worktree to the commit the step started from and
+/// brings back the uncommitted and untracked files it had then.
+pub async fn run_rollback_command(to_step: usize, session: Option<String>) -> Result<()> {
+    use crate::abstractions::git::RealGitOperations;
+    use crate::cook::workflow::checkpoint_marks::SESSION_ID_ENV;
+    use crate::cook::workflow::snapshots;
+
+    let session_id = session
+        .or_else(|| std::env::var(SESSION_ID_ENV).ok())
+        .filter(|id| !id.is_empty())
+        .with_context(|| {
+            format!(
+                "No session given: pass --session (reads {})",
+                SESSION_ID_ENV
+            )
+        })?;
+    let all = snapshots::load_snapshots(&snapshots::snapshots_file_path(&session_id)?).await?;
+    let snapshot = snapshots::find_snapshot(&all, to_step).with_context(|| {
+        let steps: std::collections::BTreeSet<usize> = all.iter().map(|s| s.step).collect();
+        let steps: Vec<String> = steps.iter().map(usize::to_string).collect();
+        if steps.is_empty() {
+            format!("Session {} has no snapshots", session_id)
+        } else {
+            format!(
+                "No snapshot before step {} in session {} (snapshots exist for steps: {})",
+                to_step,
+                session_id,
+                steps.join(", ")
+            )
+        }
+    })?;
+
+    snapshots::restore_snapshot(&RealGitOperations::new(), snapshot).await?;
+    styled_println!(
+        "Rolled back {} to before step {} ({})",
+        snapshot.working_dir.display(),
+        snapshot.step,
+        snapshot.step_name
+    );
+    Ok(())
+}
+
+/// Print progress marks recorded for a session, if any
+async fn print_checkpoint_marks(session_id: &str) -> Result<()> {
+    use crate::cook::workflow::checkpoint_marks;
+
+    let path = checkpoint_marks::marks_file_path(session_id)?;
+    let marks = checkpoint_marks::load_marks(&path).await?;
+    if marks.is_empty() {
+        return Ok(());
+    }
+
+    styled_println!(
+        "
+Checkpoint Marks: {}",
+        marks.len()
+    );
+    for mark in &marks {
+        styled_println!("  {} - {}", mark.created_at.to_rfc3339(), mark.label);
+    }
+    Ok(())
+}
+
 /// List checkpoints for a specific workflow ID
 async fn list_specific_checkpoint(
     checkpoint_manager: &crate::cook::workflow::CheckpointManager,
@@ -288,35 +410,36 @@ async fn list_specific_checkpoint(
 ) -> Result<()> {
     match checkpoint_manager.load_checkpoint(workflow_id).await {
         Ok(checkpoint) => {
-            println!("📋 Checkpoint for workflow: {}", workflow_id);
-            println!("   Status: {:?}", checkpoint.execution_state.status);
-            println!(
+            styled_println!("📋 Checkpoint for workflow: {}", workflow_id);
+            styled_println!("   Status: {:?}", checkpoint.execution_state.status);
+            styled_println!(
                 "   Step: {}/{}",
                 checkpoint.execution_state.current_step_index,
                 checkpoint.execution_state.total_steps
             );
-            println!("   Created: {}", checkpoint.timestamp);
+            styled_println!("   Created: {}", checkpoint.timestamp);
 
             if verbose {
-                println!("\n   Completed Steps:");
+                styled_println!("\n   Completed Steps:");
                 for step in &checkpoint.completed_steps {
-                    println!(
+                    styled_println!(
                         "     {} - {} ({})",
                         step.step_index,
                         step.command,
                         if step.success { "✓" } else { "✗" }
                     );
                     if let Some(ref retry) = step.retry_state {
-                        println!(
+                        styled_println!(
                             "       Retry: {}/{}",
-                            retry.current_attempt, retry.max_attempts
+                            retry.current_attempt,
+                            retry.max_attempts
                         );
                     }
                 }
             }
         }
         Err(e) => {
-            println!("Error loading checkpoint for {}: {}", workflow_id, e);
+            styled_println!("Error loading checkpoint for {}: {}", workflow_id, e);
         }
     }
     Ok(())
@@ -328,7 +451,7 @@ async fn list_all_checkpoints(
     checkpoint_dir: &PathBuf,
     verbose: bool,
 ) -> Result<()> {
-    println!("📋 Available checkpoints:");
+    styled_println!("📋 Available checkpoints:");
 
     let mut entries = tokio::fs::read_dir(checkpoint_dir).await?;
     let mut checkpoints = Vec::new();
@@ -346,24 +469,25 @@ async fn list_all_checkpoints(
     }
 
     if checkpoints.is_empty() {
-        println!("  No checkpoints found.");
+        styled_println!("  No checkpoints found.");
     } else {
         for (id, checkpoint) in checkpoints {
-            println!(
+            styled_println!(
                 "\n  {} - Status: {:?}",
-                id, checkpoint.execution_state.status
+                id,
+                checkpoint.execution_state.status
             );
-            println!(
+            styled_println!(
                 "    Step: {}/{}",
                 checkpoint.execution_state.current_step_index,
                 checkpoint.execution_state.total_steps
             );
-            println!("    Created: {}", checkpoint.timestamp);
+            styled_println!("    Created: {}", checkpoint.timestamp);
 
             if verbose && !checkpoint.completed_steps.is_empty() {
-                println!("    Completed Steps:");
+                styled_println!("    Completed Steps:");
                 for step in &checkpoint.completed_steps {
-                    println!(
+                    styled_println!(
                         "      {} - {} ({})",
                         step.step_index,
                         step.command,
@@ -391,14 +515,14 @@ async fn clean_specific_checkpoint(
             let mut input = String::new();
             io::stdin().read_line(&mut input)?;
             if !input.trim().eq_ignore_ascii_case("y") {
-                println!("Cancelled.");
+                styled_println!("Cancelled.");
                 return Ok(());
             }
         }
         tokio::fs::remove_file(&checkpoint_path).await?;
-        println!("✅ Deleted checkpoint for {}", workflow_id);
+        styled_println!("✅ Deleted checkpoint for {}", workflow_id);
     } else {
-        println!("No checkpoint found for {}", workflow_id);
+        styled_println!("No checkpoint found for {}", workflow_id);
     }
     Ok(())
 }
@@ -473,7 +597,7 @@ async fn clean_all_checkpoints(checkpoint_dir: &PathBuf, force: bool) -> Result<
                     // Use pure predicate to check if checkpoint is completed
                     if is_completed_checkpoint(&checkpoint) {
                         if !force {
-                            println!("Delete completed checkpoint for {}?", workflow_id);
+                            styled_println!("Delete completed checkpoint for {}?", workflow_id);
                         }
                         tokio::fs::remove_file(&path).await?;
                         deleted += 1;
@@ -483,7 +607,7 @@ async fn clean_all_checkpoints(checkpoint_dir: &PathBuf, force: bool) -> Result<
         }
     }
 
-    println!("✅ Deleted {} completed checkpoints", deleted);
+    styled_println!("✅ Deleted {} completed checkpoints", deleted);
     Ok(())
 }
 
@@ -494,33 +618,33 @@ async fn show_checkpoint_details(
 ) -> Result<()> {
     match checkpoint_manager.load_checkpoint(workflow_id).await {
         Ok(checkpoint) => {
-            println!("📋 Checkpoint Details for: {}", workflow_id);
-            println!("\nExecution State:");
-            println!("  Status: {:?}", checkpoint.execution_state.status);
-            println!(
+            styled_println!("📋 Checkpoint Details for: {}", workflow_id);
+            styled_println!("\nExecution State:");
+            styled_println!("  Status: {:?}", checkpoint.execution_state.status);
+            styled_println!(
                 "  Current Step: {}/{}",
                 checkpoint.execution_state.current_step_index,
                 checkpoint.execution_state.total_steps
             );
-            println!("  Start Time: {}", checkpoint.execution_state.start_time);
-            println!(
+            styled_println!("  Start Time: {}", checkpoint.execution_state.start_time);
+            styled_println!(
                 "  Last Checkpoint: {}",
                 checkpoint.execution_state.last_checkpoint
             );
 
-            println!("\nWorkflow Info:");
+            styled_println!("\nWorkflow Info:");
             if let Some(ref name) = checkpoint.workflow_name {
-                println!("  Name: {}", name);
+                styled_println!("  Name: {}", name);
             }
             if let Some(ref path) = checkpoint.workflow_path {
-                println!("  Path: {}", path.display());
+                styled_println!("  Path: {}", path.display());
             }
-            println!("  Version: {}", checkpoint.version);
-            println!("  Hash: {}", checkpoint.workflow_hash);
+            styled_println!("  Version: {}", checkpoint.version);
+            styled_println!("  Hash: {}", checkpoint.workflow_hash);
 
-            println!("\nCompleted Steps: {}", checkpoint.completed_steps.len());
+            styled_println!("\nCompleted Steps: {}", checkpoint.completed_steps.len());
             for step in &checkpoint.completed_steps {
-                println!(
+                styled_println!(
                     "  [{}] {} - {} (Duration: {:?})",
                     step.step_index,
                     step.command,
@@ -533,17 +657,22 @@ async fn show_checkpoint_details(
                 );
 
                 if let Some(ref retry) = step.retry_state {
-                    println!(
+                    styled_println!(
                         "      Retry: {}/{} attempts",
-                        retry.current_attempt, retry.max_attempts
+                        retry.current_attempt,
+                        retry.max_attempts
                     );
                     if !retry.failure_history.is_empty() {
-                        println!("      Failures: {:?}", retry.failure_history);
+                        styled_println!("      Failures: {:?}", retry.failure_history);
                     }
                 }
 
+                if let Some(ref changes) = step.changes {
+                    styled_println!("      Changes: {}", changes.diff_stat());
+                }
+
                 if !step.captured_variables.is_empty() {
-                    println!(
+                    styled_println!(
                         "      Variables: {:?}",
                         step.captured_variables.keys().collect::<Vec<_>>()
                     );
@@ -551,24 +680,230 @@ async fn show_checkpoint_details(
             }
 
             if !checkpoint.variable_state.is_empty() {
-                println!("\nVariable State:");
+                styled_println!("\nVariable State:");
                 for key in checkpoint.variable_state.keys() {
-                    println!("  {}", key);
+                    styled_println!("  {}", key);
                 }
             }
 
             if let Some(ref mapreduce) = checkpoint.mapreduce_state {
-                println!("\nMapReduce State:");
-                println!("  Completed Items: {}", mapreduce.completed_items.len());
-                println!("  Failed Items: {}", mapreduce.failed_items.len());
-                println!("  In Progress: {}", mapreduce.in_progress_items.len());
-                println!("  Reduce Completed: {}", mapreduce.reduce_completed);
+                styled_println!("\nMapReduce State:");
+                styled_println!("  Completed Items: {}", mapreduce.completed_items.len());
+                styled_println!("  Failed Items: {}", mapreduce.failed_items.len());
+                styled_println!("  In Progress: {}", mapreduce.in_progress_items.len());
+                styled_println!("  Reduce Completed: {}", mapreduce.reduce_completed);
             }
         }
         Err(e) => {
-            println!("Error loading checkpoint for {}: {}", workflow_id, e);
+            styled_println!("Error loading checkpoint for {}: {}", workflow_id, e);
+        }
+    }
+
+    print_checkpoint_marks(workflow_id).await
+}
+
+// ============================================================================
+// Pure Functions: Retry State
+// ============================================================================
+
+/// Retry attempts recorded for one step
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct StepRetries {
+    step: String,
+    step_index: Option<usize>,
+    attempts: u32,
+    max_attempts: u32,
+    failures: Vec<String>,
+    circuit_open: bool,
+}
+
+/// Retry state of every step in a checkpoint (pure function)
+///
+/// Combines the retry state kept with completed steps and the per-command
+/// state of the retry manager, which is keyed by the step's command.
+fn collect_step_retries(
+    checkpoint: &crate::cook::workflow::checkpoint::WorkflowCheckpoint,
+) -> Vec<StepRetries> {
+    use crate::cook::retry_state::CircuitState;
+
+    let mut retries: Vec<StepRetries> = checkpoint
+        .completed_steps
+        .iter()
+        .filter_map(|step| {
+            step.retry_state.as_ref().map(|retry| StepRetries {
+                step: step.command.clone(),
+                step_index: Some(step.step_index),
+                attempts: retry.current_attempt as u32,
+                max_attempts: retry.max_attempts as u32,
+                failures: retry.failure_history.clone(),
+                circuit_open: false,
+            })
+        })
+        .collect();
+
+    if let Some(state) = &checkpoint.retry_checkpoint_state {
+        let mut commands: Vec<_> = state.command_retry_states.values().collect();
+        commands.sort_by(|a, b| a.command_id.cmp(&b.command_id));
+        for command in commands {
+            let failures = command
+                .retry_history
+                .iter()
+                .filter(|attempt| !attempt.success)
+                .map(|attempt| {
+                    let error = attempt.error.as_deref().unwrap_or("failed");
+                    format!(
+                        "attempt {}: {}",
+                        attempt.attempt_number,
+                        error.lines().next().unwrap_or("")
+                    )
+                })
+                .collect();
+            let circuit_open = command.is_circuit_broken
+                || state
+                    .circuit_breaker_states
+                    .get(&command.command_id)
+                    .is_some_and(|cb| cb.state == CircuitState::Open);
+            let step_index = checkpoint
+                .completed_steps
+                .iter()
+                .find(|step| step.command == command.command_id)
+                .map(|step| step.step_index);
+            let entry = StepRetries {
+                step: command.command_id.clone(),
+                step_index,
+                attempts: command.attempt_count,
+                max_attempts: command.max_attempts,
+                failures,
+                circuit_open,
+            };
+            match retries.iter_mut().find(|r| r.step == entry.step) {
+                Some(existing) => *existing = entry,
+                None => retries.push(entry),
+            }
         }
     }
+    retries
+}
+
+/// Clear the retry state of the steps matching `selector` (pure function)
+///
+/// `selector` is a step index or the step's command as shown by
+/// `prodigy checkpoints retries`. Returns the commands that were reset.
+fn reset_step_retries(
+    checkpoint: &mut crate::cook::workflow::checkpoint::WorkflowCheckpoint,
+    selector: &str,
+) -> Vec<String> {
+    let index = selector.parse::<usize>().ok();
+    let mut commands: Vec<String> = checkpoint
+        .completed_steps
+        .iter()
+        .filter(|step| Some(step.step_index) == index || step.command == selector)
+        .map(|step| step.command.clone())
+        .collect();
+    if index.is_none() && !commands.iter().any(|c| c == selector) {
+        commands.push(selector.to_string());
+    }
+
+    let mut reset = Vec::new();
+    for command in commands {
+        let mut cleared = false;
+        for step in checkpoint
+            .completed_steps
+            .iter_mut()
+            .filter(|step| step.command == command)
+        {
+            cleared |= step.retry_state.take().is_some();
+        }
+        if let Some(state) = checkpoint.retry_checkpoint_state.as_mut() {
+            cleared |= state.command_retry_states.remove(&command).is_some();
+            cleared |= state.circuit_breaker_states.remove(&command).is_some();
+            let before = state.retry_execution_history.len();
+            state
+                .retry_execution_history
+                .retain(|execution| execution.command_id != command);
+            cleared |= state.retry_execution_history.len() != before;
+        }
+        if cleared {
+            reset.push(command);
+        }
+    }
+    reset
+}
+
+/// Lines describing the retry state of each step (pure function)
+fn format_step_retries(retries: &[StepRetries]) -> Vec<String> {
+    let mut lines = Vec::new();
+    for retry in retries {
+        let index = retry
+            .step_index
+            .map(|i| format!("[{}] ", i))
+            .unwrap_or_default();
+        let circuit = if retry.circuit_open {
+            " (circuit open)"
+        } else {
+            ""
+        };
+        lines.push(format!(
+            "{}{}: {}/{} attempts{}",
+            index, retry.step, retry.attempts, retry.max_attempts, circuit
+        ));
+        lines.extend(retry.failures.iter().map(|f| format!("    {}", f)));
+    }
+    lines
+}
+
+/// Checkpoint directory and ID for a session or workflow ID
+///
+/// Resumable sessions keep their checkpoints under
+/// `~/.prodigy/state/{session_id}/checkpoints/`; the latest one is used.
+/// Other IDs are looked up in the repository's checkpoint directory.
+async fn resolve_retry_checkpoint(working_dir: &Path, id: &str) -> Result<(PathBuf, String)> {
+    let session_dir = crate::storage::get_default_storage_dir()?
+        .join("state")
+        .join(id)
+        .join("checkpoints");
+    if let Some(latest) = find_latest_checkpoint(&session_dir).await {
+        let checkpoint_id = latest.trim_end_matches(".checkpoint").to_string();
+        return Ok((session_dir, checkpoint_id));
+    }
+    let (_storage, _repo_name, checkpoint_dir) = initialize_checkpoint_storage(working_dir).await?;
+    Ok((checkpoint_dir, id.to_string()))
+}
+
+/// Print the retry state of a workflow's steps, or reset one step's counters
+async fn show_or_reset_retries(
+    checkpoint_manager: &crate::cook::workflow::CheckpointManager,
+    checkpoint_id: &str,
+    workflow_id: &str,
+    reset: Option<&str>,
+) -> Result<()> {
+    let mut checkpoint = checkpoint_manager
+        .load_checkpoint(checkpoint_id)
+        .await
+        .with_context(|| format!("Failed to load checkpoint for {}", workflow_id))?;
+
+    if let Some(selector) = reset {
+        let reset = reset_step_retries(&mut checkpoint, selector);
+        if reset.is_empty() {
+            anyhow::bail!("No retry state recorded for step '{}'", selector);
+        }
+        checkpoint_manager.save_checkpoint(&checkpoint).await?;
+        for command in reset {
+            styled_println!("✓ Reset retry counters for {}", command);
+        }
+        styled_println!("Resume with: prodigy resume {}", workflow_id);
+        return Ok(());
+    }
+
+    let retries = collect_step_retries(&checkpoint);
+    if retries.is_empty() {
+        styled_println!("No retries recorded for {}", workflow_id);
+        return Ok(());
+    }
+    styled_println!("🔁 Retries for: {}", workflow_id);
+    for line in format_step_retries(&retries) {
+        styled_println!("  {}", line);
+    }
     Ok(())
 }
 
@@ -594,17 +929,17 @@ async fn validate_checkpoint(working_dir: &Path, checkpoint_id: &str, repair: bo
 
     match manager.resume_from_checkpoint(Some(cp_id)).await {
         Ok(_) => {
-            println!("✓ Checkpoint {} is valid", checkpoint_id);
+            styled_println!("✓ Checkpoint {} is valid", checkpoint_id);
             Ok(())
         }
         Err(e) => {
-            println!("✗ Checkpoint {} validation failed: {}", checkpoint_id, e);
+            styled_println!("✗ Checkpoint {} validation failed: {}", checkpoint_id, e);
 
             if repair {
-                println!("Attempting repair...");
+                styled_println!("Attempting repair...");
                 let cp_id_repair = CheckpointId::from_string(checkpoint_id.to_string());
                 if let Err(repair_err) = repair_checkpoint(&manager, &cp_id_repair).await {
-                    println!("❌ Repair failed: {}", repair_err);
+                    styled_println!("❌ Repair failed: {}", repair_err);
                     return Err(e);
                 }
 
@@ -612,11 +947,11 @@ async fn validate_checkpoint(working_dir: &Path, checkpoint_id: &str, repair: bo
                 let cp_id_retry = CheckpointId::from_string(checkpoint_id.to_string());
                 match manager.resume_from_checkpoint(Some(cp_id_retry)).await {
                     Ok(_) => {
-                        println!("✓ Checkpoint repaired and validated successfully");
+                        styled_println!("✓ Checkpoint repaired and validated successfully");
                         return Ok(());
                     }
                     Err(new_err) => {
-                        println!("❌ Checkpoint still invalid after repair: {}", new_err);
+                        styled_println!("❌ Checkpoint still invalid after repair: {}", new_err);
                         return Err(new_err);
                     }
                 }
@@ -648,7 +983,7 @@ async fn list_mapreduce_checkpoints(
         .join("checkpoints");
 
     if !checkpoint_dir.exists() {
-        println!("No MapReduce checkpoints found for job {}", job_id);
+        styled_println!("No MapReduce checkpoints found for job {}", job_id);
         return Ok(());
     }
 
@@ -659,34 +994,35 @@ async fn list_mapreduce_checkpoints(
     let checkpoints = manager.list_checkpoints().await?;
 
     if checkpoints.is_empty() {
-        println!("No checkpoints found for job {}", job_id);
+        styled_println!("No checkpoints found for job {}", job_id);
         return Ok(());
     }
 
-    println!("MapReduce Checkpoints for job {}:", job_id);
-    println!("{:-<80}", "");
+    styled_println!("MapReduce Checkpoints for job {}:", job_id);
+    styled_println!("{:-<80}", "");
 
     for checkpoint in checkpoints {
         if detailed {
-            println!("\nCheckpoint ID: {}", checkpoint.id);
-            println!(
+            styled_println!("\nCheckpoint ID: {}", checkpoint.id);
+            styled_println!(
                 "  Created: {}",
                 checkpoint.created_at.format("%Y-%m-%d %H:%M:%S")
             );
-            println!("  Phase: {:?}", checkpoint.phase);
-            println!(
+            styled_println!("  Phase: {:?}", checkpoint.phase);
+            styled_println!(
                 "  Progress: {}/{} items",
-                checkpoint.completed_items, checkpoint.total_items
+                checkpoint.completed_items,
+                checkpoint.total_items
             );
 
             if checkpoint.total_items > 0 {
                 let percentage =
                     (checkpoint.completed_items as f64 / checkpoint.total_items as f64) * 100.0;
-                println!("  Completion: {:.1}%", percentage);
+                styled_println!("  Completion: {:.1}%", percentage);
             }
 
             if checkpoint.is_final {
-                println!("  Status: FINAL");
+                styled_println!("  Status: FINAL");
             }
         } else {
             let percentage = if checkpoint.total_items > 0 {
@@ -695,7 +1031,7 @@ async fn list_mapreduce_checkpoints(
                 0.0
             };
 
-            println!(
+            styled_println!(
                 "{} - Phase: {:?}, Progress: {}/{} ({:.1}%){}",
                 checkpoint.created_at.format("%Y-%m-%d %H:%M:%S"),
                 checkpoint.phase,
@@ -717,7 +1053,7 @@ async fn repair_checkpoint(
 ) -> Result<()> {
     // For now, basic repair is limited since we can't access private storage
     // This would require adding a public repair method to CheckpointManager
-    println!("Attempting basic checkpoint repair for {}", checkpoint_id);
+    styled_println!("Attempting basic checkpoint repair for {}", checkpoint_id);
 
     // In a complete implementation, this would:
     // 1. Fix missing or corrupted work item state
@@ -727,7 +1063,7 @@ async fn repair_checkpoint(
     // 5. Ensure checkpoint ID consistency
     // 6. Save repaired checkpoint
 
-    println!("✓ Applied basic repairs to checkpoint");
+    styled_println!("✓ Applied basic repairs to checkpoint");
     Ok(())
 }
 
@@ -750,7 +1086,7 @@ async fn delete_checkpoint(working_dir: &Path, checkpoint_id: &str, force: bool)
         io::stdin().read_line(&mut input)?;
 
         if !input.trim().eq_ignore_ascii_case("y") {
-            println!("Cancelled");
+            styled_println!("Cancelled");
             return Ok(());
         }
     }
@@ -769,7 +1105,7 @@ async fn delete_checkpoint(working_dir: &Path, checkpoint_id: &str, force: bool)
     let cp_id = CheckpointId::from_string(checkpoint_id.to_string());
 
     manager.delete_checkpoint(&cp_id).await?;
-    println!("✓ Deleted checkpoint {}", checkpoint_id);
+    styled_println!("✓ Deleted checkpoint {}", checkpoint_id);
 
     Ok(())
 }
@@ -808,6 +1144,9 @@ mod tests {
             error_recovery_state: None,
             retry_checkpoint_state: None,
             variable_checkpoint_state: None,
+            failure_chains: Vec::new(),
+            repo: None,
+            step_selection: None,
         }
     }
 
@@ -892,6 +1231,50 @@ mod tests {
         assert_eq!(result, super::CleanOperation::InvalidRequest);
     }
 
+    // Tests for retry state
+
+    #[tokio::test]
+    async fn test_collect_and_reset_step_retries() {
+        use crate::cook::retry_state::{RetryAttempt, RetryStateManager};
+        use crate::cook::retry_v2::RetryConfig;
+
+        let manager = RetryStateManager::new();
+        for (attempt_number, success) in [(1, false), (2, false)] {
+            let attempt = RetryAttempt {
+                attempt_number,
+                executed_at: Utc::now(),
+                duration: std::time::Duration::from_secs(1),
+                success,
+                error: Some("tests failed\nmore output".to_string()),
+                backoff_applied: std::time::Duration::from_secs(0),
+                exit_code: Some(1),
+            };
+            manager
+                .update_retry_state("shell: cargo test", attempt, &RetryConfig::default())
+                .await
+                .unwrap();
+        }
+        let mut checkpoint = create_test_checkpoint(WorkflowStatus::Failed);
+        checkpoint.retry_checkpoint_state = Some(manager.create_checkpoint_state().await.unwrap());
+
+        let retries = collect_step_retries(&checkpoint);
+        assert_eq!(
+            format_step_retries(&retries),
+            vec![
+                "shell: cargo test: 2/3 attempts",
+                "    attempt 1: tests failed",
+                "    attempt 2: tests failed",
+            ]
+        );
+
+        assert!(reset_step_retries(&mut checkpoint, "shell: make").is_empty());
+        assert_eq!(
+            reset_step_retries(&mut checkpoint, "shell: cargo test"),
+            vec!["shell: cargo test"]
+        );
+        assert!(collect_step_retries(&checkpoint).is_empty());
+    }
+
     // Tests for create_checkpoint_manager
 
     #[test]
@@ -0,0 +1,112 @@
+//! Claude command discovery
+//!
+//! Lists the slash commands workflow steps can call, with the description and
+//! arguments declared in each command file.
+
+use crate::cli::args::ClaudeCommandsCommand;
+use crate::config::command_catalog::{self, CatalogEntry, CommandSource};
+use anyhow::Result;
+
+/// Execute a `prodigy commands` subcommand
+pub async fn run_claude_commands_command(command: ClaudeCommandsCommand) -> Result<()> {
+    match command {
+        ClaudeCommandsCommand::Ls {
+            path,
+            installed,
+            json,
+        } => {
+            let commands_dir = path.unwrap_or_default().join(".claude").join("commands");
+            let entries = if installed {
+                command_catalog::scan_project_commands(&commands_dir).await?
+            } else {
+                command_catalog::catalog(&commands_dir).await?
+            };
+            if json {
+                println!("{}", serde_json::to_string_pretty(&entries)?);
+            } else {
+                for line in format_catalog(&entries) {
+                    println!("{}", line);
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Commands grouped by source, each with its usage and description (pure function)
+fn format_catalog(entries: &[CatalogEntry]) -> Vec<String> {
+    let mut lines = Vec::new();
+    for (source, heading) in [
+        (
+            CommandSource::Project,
+            "Project commands (.claude/commands):",
+        ),
+        (
+            CommandSource::Template,
+            "Bundled templates (install with `prodigy init`):",
+        ),
+    ] {
+        let group: Vec<&CatalogEntry> = entries.iter().filter(|e| e.source == source).collect();
+        if group.is_empty() {
+            continue;
+        }
+        if !lines.is_empty() {
+            lines.push(String::new());
+        }
+        lines.push(heading.to_string());
+        for entry in group {
+            let usage = if entry.arguments.is_empty() {
+                format!("/{}", entry.name)
+            } else {
+                format!("/{} {}", entry.name, entry.arguments)
+            };
+            lines.push(format!("  {}", usage));
+            if !entry.description.is_empty() {
+                lines.push(format!("      {}", entry.description));
+            }
+        }
+    }
+    if lines.is_empty() {
+        lines.push("No Claude commands found in .claude/commands".to_string());
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, arguments: &str, source: CommandSource) -> CatalogEntry {
+        CatalogEntry {
+            name: name.to_string(),
+            description: format!("Run {}", name),
+            arguments: arguments.to_string(),
+            source,
+            path: None,
+        }
+    }
+
+    #[test]
+    fn test_format_catalog_groups_by_source() {
+        let lines = format_catalog(&[
+            entry("prodigy-fix", "<finding>", CommandSource::Project),
+            entry("prodigy-lint", "", CommandSource::Template),
+        ]);
+        assert_eq!(
+            lines,
+            vec![
+                "Project commands (.claude/commands):",
+                "  /prodigy-fix <finding>",
+                "      Run prodigy-fix",
+                "",
+                "Bundled templates (install with `prodigy init`):",
+                "  /prodigy-lint",
+                "      Run prodigy-lint",
+            ]
+        );
+        assert_eq!(
+            format_catalog(&[]),
+            vec!["No Claude commands found in .claude/commands"]
+        );
+    }
+}
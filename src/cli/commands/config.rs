@@ -8,6 +8,7 @@ use anyhow::Result;
 use crate::cli::args::ConfigCommands;
 use crate::config::diagnostics::{detect_issues, format_issues, format_issues_json};
 use crate::config::tracing::{trace_config, TracedProdigyConfig};
+use crate::styled_println;
 
 /// Execute a config command.
 pub async fn run_config_command(command: ConfigCommands) -> Result<()> {
@@ -48,7 +49,7 @@ async fn handle_trace(
         if json {
             println!("{}", format_issues_json(&issues));
         } else {
-            println!("{}", format_issues(&issues));
+            styled_println!("{}", format_issues(&issues));
         }
         return Ok(());
     }
@@ -168,7 +169,7 @@ fn print_single_trace(traced: &TracedProdigyConfig, path: &str, json: bool) {
                     println!("{}", json_str);
                 }
             } else {
-                println!("{}", trace.explain(path));
+                styled_println!("{}", trace.explain(path));
             }
         }
         None => {
@@ -199,7 +200,7 @@ fn print_overrides(traced: &TracedProdigyConfig, json: bool) {
         println!("Overridden configuration values:\n");
         for path in overridden {
             if let Some(trace) = traced.trace(&path) {
-                println!("{}", trace.explain(&path));
+                styled_println!("{}", trace.explain(&path));
                 println!();
             }
         }
@@ -215,7 +216,7 @@ fn print_all_traces(traced: &TracedProdigyConfig, json: bool) {
     } else {
         println!("Configuration values:\n");
         for (path, trace) in traced.all_traces() {
-            println!("{}", trace.explain(&path));
+            styled_println!("{}", trace.explain(&path));
             println!();
         }
     }
@@ -3,7 +3,10 @@
 //! This module handles DLQ management for failed MapReduce items.
 
 use crate::cli::args::DlqCommands;
-use anyhow::Result;
+use crate::cook::execution::dlq::{DeadLetterQueue, DeadLetteredItem, FailureAnalysis};
+use crate::cook::interaction::prompts::{UserPrompter, UserPrompterImpl};
+use anyhow::{Context, Result};
+use std::path::PathBuf;
 
 /// Execute DLQ-related commands
 pub async fn run_dlq_command(command: DlqCommands) -> Result<()> {
@@ -23,13 +26,7 @@ pub async fn run_dlq_command(command: DlqCommands) -> Result<()> {
             println!("Inspecting DLQ item...");
             Ok(())
         }
-        DlqCommands::Analyze {
-            job_id: _job_id,
-            export: _export,
-        } => {
-            println!("Analyzing DLQ failure patterns...");
-            Ok(())
-        }
+        DlqCommands::Analyze { job_id, export } => analyze_dlq(job_id, export).await,
         DlqCommands::Export {
             output: _output,
             job_id: _job_id,
@@ -69,5 +66,185 @@ pub async fn run_dlq_command(command: DlqCommands) -> Result<()> {
             println!("Clearing processed DLQ items...");
             Ok(())
         }
+        DlqCommands::Requeue {
+            job_id,
+            where_expr,
+            dry_run,
+        } => requeue_matching(&job_id, &where_expr, dry_run).await,
+        DlqCommands::Drop {
+            job_id,
+            where_expr,
+            dry_run,
+            yes,
+        } => drop_matching(&job_id, &where_expr, dry_run, yes).await,
+    }
+}
+
+/// Open a job's DLQ in global storage for the current repository
+async fn open_job_dlq(job_id: &str) -> Result<DeadLetterQueue> {
+    let repo_path = std::env::current_dir()?;
+    crate::storage::create_global_dlq(&repo_path, job_id, None).await
+}
+
+/// Print the items selected by a `--where` expression
+fn print_selection(items: &[DeadLetteredItem], where_expr: &str) {
+    println!("{} items match '{}'", items.len(), where_expr);
+    for item in items {
+        let error = item
+            .failure_history
+            .last()
+            .map(|f| f.error_message.as_str())
+            .unwrap_or("");
+        println!(
+            "  {} ({} failures): {}",
+            item.item_id, item.failure_count, error
+        );
+    }
+}
+
+/// Requeue DLQ items matching a filter expression
+async fn requeue_matching(job_id: &str, where_expr: &str, dry_run: bool) -> Result<()> {
+    let dlq = open_job_dlq(job_id).await?;
+    let selected = dlq.select_where(where_expr).await?;
+    print_selection(&selected, where_expr);
+
+    if dry_run || selected.is_empty() {
+        return Ok(());
+    }
+
+    let ids: Vec<String> = selected.into_iter().map(|item| item.item_id).collect();
+    let requeued = dlq.requeue(&ids).await?;
+    println!(
+        "Requeued {} items; run 'prodigy resume-job {}' to reprocess them",
+        requeued, job_id
+    );
+    Ok(())
+}
+
+/// Remove DLQ items matching a filter expression
+async fn drop_matching(job_id: &str, where_expr: &str, dry_run: bool, yes: bool) -> Result<()> {
+    let dlq = open_job_dlq(job_id).await?;
+    let selected = dlq.select_where(where_expr).await?;
+    print_selection(&selected, where_expr);
+
+    if dry_run || selected.is_empty() {
+        return Ok(());
+    }
+
+    if !yes && std::env::var("PRODIGY_AUTOMATION").unwrap_or_default() != "true" {
+        let confirm = UserPrompterImpl::new()
+            .prompt_yes_no(&format!(
+                "This will permanently remove {} DLQ items. Continue?",
+                selected.len()
+            ))
+            .await?;
+        if !confirm {
+            println!("Drop cancelled.");
+            return Ok(());
+        }
+    }
+
+    for item in &selected {
+        dlq.remove(&item.item_id).await?;
+    }
+    println!("Dropped {} items", selected.len());
+    Ok(())
+}
+
+/// Analyze a job's DLQ and print failure clusters
+async fn analyze_dlq(job_id: Option<String>, export: Option<PathBuf>) -> Result<()> {
+    let job_id = job_id.context("--job-id is required to analyze a DLQ")?;
+    let dlq = open_job_dlq(&job_id).await?;
+    let analysis = dlq.analyze_patterns().await?;
+
+    for line in format_failure_clusters(&analysis) {
+        println!("{}", line);
+    }
+
+    if let Some(path) = export {
+        let json = serde_json::to_string_pretty(&analysis)?;
+        tokio::fs::write(&path, json)
+            .await
+            .with_context(|| format!("Failed to write analysis to {}", path.display()))?;
+        println!("\nAnalysis exported to {}", path.display());
+    }
+
+    Ok(())
+}
+
+/// Lines describing each failure cluster with samples and a suggested action
+fn format_failure_clusters(analysis: &FailureAnalysis) -> Vec<String> {
+    if analysis.total_items == 0 {
+        return vec!["DLQ is empty".to_string()];
+    }
+
+    let mut lines = vec![format!(
+        "{} failed items in {} clusters",
+        analysis.total_items,
+        analysis.clusters.len()
+    )];
+    for (index, cluster) in analysis.clusters.iter().enumerate() {
+        lines.push(String::new());
+        lines.push(format!(
+            "[{}] {} items, step: {}, type: {:?}",
+            index + 1,
+            cluster.count,
+            cluster.step_failed,
+            cluster.error_type
+        ));
+        lines.push(format!("    error: {}", cluster.normalized_error));
+        for sample in &cluster.samples {
+            lines.push(format!(
+                "    sample {}: {}",
+                sample.item_id, sample.error_message
+            ));
+        }
+        lines.push(format!(
+            "    suggested: {} ({})",
+            cluster.suggested_action.label(),
+            cluster.reason
+        ));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cook::execution::dlq::ErrorType;
+    use crate::cook::execution::dlq_clustering::{ClusterSample, FailureCluster, SuggestedAction};
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_format_failure_clusters() {
+        let analysis = FailureAnalysis {
+            total_items: 2,
+            pattern_groups: Vec::new(),
+            error_distribution: HashMap::new(),
+            temporal_distribution: Vec::new(),
+            clusters: vec![FailureCluster {
+                normalized_error: "timed out after <n>s".to_string(),
+                step_failed: "claude: /fix".to_string(),
+                error_type: ErrorType::Timeout,
+                count: 2,
+                item_ids: vec!["a".to_string(), "b".to_string()],
+                samples: vec![ClusterSample {
+                    item_id: "a".to_string(),
+                    error_message: "timed out after 300s".to_string(),
+                }],
+                suggested_action: SuggestedAction::RetryCluster,
+                reason: "Timeout errors are usually transient".to_string(),
+            }],
+        };
+
+        let lines = format_failure_clusters(&analysis);
+
+        assert_eq!(lines[0], "2 failed items in 1 clusters");
+        assert_eq!(lines[2], "[1] 2 items, step: claude: /fix, type: Timeout");
+        assert_eq!(lines[4], "    sample a: timed out after 300s");
+        assert_eq!(
+            lines[5],
+            "    suggested: retry cluster (Timeout errors are usually transient)"
+        );
     }
 }
@@ -0,0 +1,114 @@
+//! Workflow documentation command
+//!
+//! Renders a markdown page describing a workflow file, printed or written to
+//! a file so it can be committed next to the workflow.
+
+use crate::config::WorkflowConfig;
+use crate::cook::workflow::composition::ParameterDefinitions;
+use crate::cook::workflow::docs::{self, WorkflowDoc};
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+/// Execute `prodigy docs`
+pub async fn run_docs_command(workflow: PathBuf, output: Option<PathBuf>) -> Result<()> {
+    let content = tokio::fs::read_to_string(&workflow)
+        .await
+        .with_context(|| format!("Failed to read workflow file: {}", workflow.display()))?;
+    let doc = build_doc(&workflow, &content)?;
+    let markdown = docs::render_markdown(&doc, &workflow.display().to_string());
+
+    match output {
+        Some(path) => {
+            tokio::fs::write(&path, &markdown)
+                .await
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            println!("Wrote documentation to {}", path.display());
+        }
+        None => print!("{}", markdown),
+    }
+    Ok(())
+}
+
+/// Parse a workflow file into its documentation model
+///
+/// `${env.VAR}` references are only resolved from the workflow's own `env:`
+/// block so the output does not depend on the caller's environment.
+pub(super) fn build_doc(path: &Path, content: &str) -> Result<WorkflowDoc> {
+    let is_json = path.extension().and_then(|e| e.to_str()) == Some("json");
+    let content = if is_json {
+        content.to_string()
+    } else {
+        crate::config::yaml_loader::preprocess_workflow_yaml_with_env(content, |_| None)
+            .with_context(|| format!("Failed to load workflow: {}", path.display()))?
+    };
+
+    // Workflows written as a bare list of steps declare no parameters
+    let root: serde_yaml::Value = serde_yaml::from_str(&content)
+        .with_context(|| format!("Invalid workflow: {}", path.display()))?;
+    let parameters: Option<ParameterDefinitions> = root
+        .get("parameters")
+        .map(|value| serde_yaml::from_value(value.clone()))
+        .transpose()
+        .context("Failed to parse workflow parameters")?;
+    let parameters = parameters.as_ref();
+
+    if content.contains("mode: mapreduce") || content.contains("mode: \"mapreduce\"") {
+        let config = crate::config::parse_mapreduce_workflow(&content)
+            .with_context(|| format!("Failed to parse MapReduce workflow: {}", path.display()))?;
+        return Ok(docs::mapreduce_doc(&config, parameters));
+    }
+
+    let config: WorkflowConfig = if is_json {
+        serde_json::from_str(&content)?
+    } else {
+        serde_yaml::from_str(&content)?
+    };
+    let fallback_name = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("workflow");
+    Ok(docs::standard_doc(fallback_name, &config, parameters))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_doc_with_parameters() {
+        let yaml = r#"
+parameters:
+  required:
+    - name: target
+      type: string
+      description: "File to refactor"
+  optional:
+    - name: style
+      type: string
+      default: functional
+commands:
+  - claude: "/prodigy-refactor ${target} --style ${style}"
+"#;
+        let doc = build_doc(Path::new("workflows/refactor.yml"), yaml).unwrap();
+        assert_eq!(doc.name, "refactor");
+        assert_eq!(doc.parameters.len(), 2);
+        assert!(doc.parameters[0].required);
+        assert_eq!(doc.parameters[1].default.as_deref(), Some("functional"));
+
+        let markdown = docs::render_markdown(&doc, "workflows/refactor.yml");
+        assert!(markdown.contains("| `target` | string | yes |  | File to refactor |"));
+        assert!(markdown.contains("| `style` | parameter | step 1 |"));
+    }
+
+    #[test]
+    fn test_build_doc_keeps_unresolved_env_references() {
+        let yaml = r#"
+commands:
+  - shell: "deploy --region ${env.DEPLOY_REGION}"
+"#;
+        let doc = build_doc(Path::new("deploy.yml"), yaml).unwrap();
+        let step = &doc.sections[0].steps[0];
+        assert_eq!(step.reads, vec!["env.DEPLOY_REGION"]);
+        assert_eq!(step.tools, vec!["deploy"]);
+    }
+}
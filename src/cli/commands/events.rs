@@ -3,6 +3,7 @@
 //! This module handles event viewing and management for MapReduce operations.
 
 use crate::cli::args::EventCommands;
+use crate::cli::events::{self, EventsArgs, EventsCommand};
 use anyhow::Result;
 
 /// Validate duration string format (e.g., "7d", "24h", "365d")
@@ -23,22 +24,54 @@ fn is_valid_duration(duration: &str) -> bool {
 pub async fn run_events_command(command: EventCommands) -> Result<()> {
     match command {
         EventCommands::Ls {
-            job_id: _job_id,
-            event_type: _event_type,
-            agent_id: _agent_id,
-            since: _since,
-            limit: _limit,
-            file: _file,
+            job_id,
+            event_type,
+            agent_id,
+            since,
+            from,
+            to,
+            conditions,
+            limit,
+            page,
+            cursor,
+            file,
+            output_format,
         } => {
-            println!("Listing events...");
-            Ok(())
+            events::execute(EventsArgs {
+                command: EventsCommand::Ls {
+                    job_id,
+                    event_type,
+                    agent_id,
+                    since,
+                    from,
+                    to,
+                    conditions,
+                    limit,
+                    page,
+                    cursor,
+                    file,
+                    output_format,
+                },
+            })
+            .await
         }
         EventCommands::Stats {
-            file: _file,
-            group_by: _group_by,
+            file,
+            group_by,
+            output_format,
+            metrics,
+            bucket_minutes,
         } => {
-            println!("Showing event statistics...");
-            Ok(())
+            events::execute(EventsArgs {
+                command: EventsCommand::Stats {
+                    file,
+                    group_by,
+                    output_format,
+                    metrics,
+                    bucket_minutes,
+                },
+            })
+            .await
         }
         EventCommands::Search {
             pattern: _pattern,
@@ -2,7 +2,8 @@
 //!
 //! This module handles the execution of single commands with retry support.
 
-use anyhow::Result;
+use crate::styled_println;
+use anyhow::{anyhow, Context, Result};
 use std::path::PathBuf;
 
 /// Execute a single command with retry support
@@ -19,7 +20,7 @@ pub async fn run_exec_command(
         std::env::set_current_dir(&p)?;
     }
 
-    println!("🚀 Executing command: {}", command);
+    styled_println!("🚀 Executing command: {}", command);
     if retry > 1 {
         println!("   Retry attempts: {}", retry);
     }
@@ -46,7 +47,23 @@ pub async fn run_exec_command(
         quiet: false,
         verbosity: 0,
         dry_run: false,
+        read_only: false,
+        record: None,
+        junit_output: None,
+        profile_steps: false,
+        replay: None,
+        chaos: None,
+        seed: None,
+        answers_file: None,
+        environment: None,
+        no_worktree: false,
         params: std::collections::HashMap::new(),
+        package: None,
+        changed_only: None,
+        explain_failure: false,
+        keep_going: false,
+        steps: Default::default(),
+        explicit_flags: Default::default(),
     };
 
     crate::cook::cook(cook_cmd).await
@@ -54,22 +71,43 @@ pub async fn run_exec_command(
 
 /// Execute a batch of commands on multiple files
 pub async fn run_batch_command(
-    pattern: String,
+    pattern: Option<String>,
+    stdin: bool,
     command: String,
     parallel: usize,
     retry: Option<u32>,
     timeout: Option<u64>,
     path: Option<PathBuf>,
 ) -> Result<()> {
-    use crate::cli::workflow_generator::{generate_batch_workflow, TemporaryWorkflow};
+    use crate::cli::workflow_generator::{
+        create_batch_manifest, generate_batch_workflow, BatchInput, TemporaryWorkflow,
+    };
+
+    // Work items piped on stdin go to a temporary manifest the map phase reads
+    let mut _temp_manifest = None;
+    let input = if stdin {
+        let items = read_stdin_items()?;
+        let manifest = create_batch_manifest(&items)?;
+        _temp_manifest = Some(TemporaryWorkflow {
+            path: manifest.clone(),
+        });
+        styled_println!("📥 Read {} work items from stdin", items.len());
+        BatchInput::Manifest(manifest)
+    } else {
+        BatchInput::Pattern(
+            pattern.ok_or_else(|| anyhow!("A file pattern or --stdin is required"))?,
+        )
+    };
 
     // Change to specified directory if provided
     if let Some(p) = path.clone() {
         std::env::set_current_dir(&p)?;
     }
 
-    println!("📦 Starting batch processing");
-    println!("   Pattern: {}", pattern);
+    styled_println!("📦 Starting batch processing");
+    if let BatchInput::Pattern(pattern) = &input {
+        println!("   Pattern: {}", pattern);
+    }
     println!("   Command: {}", command);
     println!("   Parallel workers: {}", parallel);
     if let Some(r) = retry {
@@ -81,7 +119,7 @@ pub async fn run_batch_command(
 
     // Generate temporary workflow
     let (_workflow, temp_path) =
-        generate_batch_workflow(&pattern, &command, parallel, retry, timeout)?;
+        generate_batch_workflow(&input, &command, parallel, retry, timeout)?;
     let _temp_workflow = TemporaryWorkflow {
         path: temp_path.clone(),
     };
@@ -99,8 +137,46 @@ pub async fn run_batch_command(
         quiet: false,
         verbosity: 0,
         dry_run: false,
+        read_only: false,
+        record: None,
+        junit_output: None,
+        profile_steps: false,
+        replay: None,
+        chaos: None,
+        seed: None,
+        answers_file: None,
+        environment: None,
+        no_worktree: false,
         params: std::collections::HashMap::new(),
+        package: None,
+        changed_only: None,
+        explain_failure: false,
+        keep_going: false,
+        steps: Default::default(),
+        explicit_flags: Default::default(),
     };
 
     crate::cook::cook(cook_cmd).await
 }
+
+/// Read batch work items piped on stdin
+fn read_stdin_items() -> Result<Vec<serde_json::Value>> {
+    use std::io::{IsTerminal, Read};
+
+    let mut stdin = std::io::stdin();
+    if stdin.is_terminal() {
+        return Err(anyhow!(
+            "--stdin expects work items piped in, e.g. `rg -l TODO | prodigy batch --stdin ...`"
+        ));
+    }
+    let mut text = String::new();
+    stdin
+        .read_to_string(&mut text)
+        .context("Failed to read work items from stdin")?;
+
+    let items = crate::cook::execution::input_source::InputSource::parse_items(&text);
+    if items.is_empty() {
+        return Err(anyhow!("No work items received on stdin"));
+    }
+    Ok(items)
+}
@@ -0,0 +1,34 @@
+//! Workflow diagram command
+//!
+//! Draws a workflow file as a Mermaid or Graphviz diagram, printed or written
+//! to a file for embedding in documentation.
+
+use super::docs::build_doc;
+use crate::cook::workflow::graph::{self, GraphFormat};
+use anyhow::{Context, Result};
+use std::path::PathBuf;
+
+/// Execute `prodigy graph`
+pub async fn run_graph_command(
+    workflow: PathBuf,
+    format: &str,
+    output: Option<PathBuf>,
+) -> Result<()> {
+    let format: GraphFormat = format.parse()?;
+    let content = tokio::fs::read_to_string(&workflow)
+        .await
+        .with_context(|| format!("Failed to read workflow file: {}", workflow.display()))?;
+    let doc = build_doc(&workflow, &content)?;
+    let diagram = graph::render(&graph::build_graph(&doc), format);
+
+    match output {
+        Some(path) => {
+            tokio::fs::write(&path, &diagram)
+                .await
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            println!("Wrote diagram to {}", path.display());
+        }
+        None => print!("{}", diagram),
+    }
+    Ok(())
+}
@@ -0,0 +1,38 @@
+//! Workflow import command
+//!
+//! Converts workflows written for other automation systems into Prodigy
+//! workflows, reporting what could not be converted.
+
+use crate::cli::args::ImportCommands;
+use crate::cook::workflow::gha_import;
+use anyhow::{Context, Result};
+
+/// Execute an import command
+pub async fn run_import_command(command: ImportCommands) -> Result<()> {
+    match command {
+        ImportCommands::Gha {
+            workflow,
+            job,
+            output,
+        } => {
+            let content = tokio::fs::read_to_string(&workflow)
+                .await
+                .with_context(|| format!("Failed to read workflow file: {}", workflow.display()))?;
+            let conversion = gha_import::convert(&content, job.as_deref())?;
+
+            match output {
+                Some(path) => {
+                    tokio::fs::write(&path, &conversion.workflow)
+                        .await
+                        .with_context(|| format!("Failed to write {}", path.display()))?;
+                    println!("Wrote Prodigy workflow to {}", path.display());
+                }
+                None => print!("{}", conversion.workflow),
+            }
+            for warning in &conversion.warnings {
+                eprintln!("warning: {}", warning);
+            }
+            Ok(())
+        }
+    }
+}
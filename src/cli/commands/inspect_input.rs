@@ -0,0 +1,290 @@
+//! Input inspection command
+//!
+//! `prodigy inspect-input` loads a MapReduce workflow's `map.input` and runs
+//! it through the same data pipeline a job would (json_path, filter,
+//! changed_since, sort_by, distinct, offset, max_items), printing how many
+//! items each stage kept and dropped and the resulting work items. No
+//! setup phase or agents run.
+
+use crate::config::mapreduce::{parse_mapreduce_workflow, MapPhaseYaml};
+use crate::cook::execution::data_pipeline::{ChangedFiles, DataPipeline, PipelineRun};
+use crate::cook::execution::github_input::{fetch_items, GhApi, GitHubInput};
+use crate::cook::execution::input_source::InputSource;
+use crate::subprocess::SubprocessManager;
+use anyhow::{anyhow, bail, Context, Result};
+use serde_json::{json, Value};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Execute `prodigy inspect-input`
+pub async fn run_inspect_input_command(
+    workflow: PathBuf,
+    input: Option<String>,
+    show: usize,
+    output_format: &str,
+) -> Result<()> {
+    if !matches!(output_format, "table" | "json") {
+        bail!(
+            "Unsupported output format '{}' (expected table or json)",
+            output_format
+        );
+    }
+    let content = tokio::fs::read_to_string(&workflow)
+        .await
+        .with_context(|| format!("Failed to read workflow file: {}", workflow.display()))?;
+    let config = parse_mapreduce_workflow(&content).map_err(|e| {
+        anyhow!(
+            "Failed to parse {} as a MapReduce workflow: {}",
+            workflow.display(),
+            e
+        )
+    })?;
+    let map = &config.map;
+    let input = input.unwrap_or_else(|| map.input.clone());
+
+    let project_dir = std::env::current_dir()?;
+    let data = load_input(&input, &project_dir).await?;
+    let mut pipeline = DataPipeline::from_full_config(
+        Some(map.json_path.clone()),
+        map.filter.clone(),
+        map.sort_by.clone(),
+        map.max_items,
+        map.offset,
+        map.distinct.clone(),
+    )?;
+    if let Some(base) = &map.changed_since {
+        let files = changed_files(base, &project_dir).await?;
+        pipeline = pipeline.with_changed_files(ChangedFiles::new(files, map.changed_path.clone()));
+    }
+    let run = pipeline.process_with_stats(&data)?;
+
+    if output_format == "json" {
+        let report = json!({
+            "input": input,
+            "extracted": run.extracted,
+            "stages": run.stages,
+            "items": run.items,
+        });
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        let first_extracted = DataPipeline::from_full_config(
+            Some(map.json_path.clone()),
+            None,
+            None,
+            Some(1),
+            None,
+            None,
+        )?
+        .process(&data)?
+        .into_iter()
+        .next();
+        print!(
+            "{}",
+            format_report(&input, map, &run, first_extracted.as_ref(), show)
+        );
+    }
+    Ok(())
+}
+
+/// Raw input data, loaded the way a MapReduce job loads it
+async fn load_input(input: &str, project_dir: &Path) -> Result<Value> {
+    match InputSource::detect_with_base(input, project_dir) {
+        InputSource::JsonFile(path) => Ok(InputSource::load_json_file(&path, project_dir).await?),
+        InputSource::Command(_) if input.ends_with(".json") => bail!(
+            "Input file {} does not exist. If the setup phase creates it, run setup first \
+             or pass a sample file with --input",
+            input
+        ),
+        InputSource::Command(command) => {
+            let subprocess = SubprocessManager::production();
+            let items =
+                InputSource::execute_command(&command, Duration::from_secs(300), &subprocess)
+                    .await?;
+            Ok(Value::Array(items))
+        }
+        InputSource::GitHub(input) => {
+            let query = GitHubInput::parse(&input)?;
+            let api = GhApi::new(
+                std::sync::Arc::new(SubprocessManager::production()),
+                project_dir,
+            );
+            Ok(Value::Array(fetch_items(&api, &query).await?))
+        }
+    }
+}
+
+/// Files changed on HEAD since it diverged from `base`
+async fn changed_files(base: &str, dir: &Path) -> Result<Vec<PathBuf>> {
+    use crate::git::{GitCommandRunner, GitReader};
+
+    GitCommandRunner::new(SubprocessManager::production().runner())
+        .get_changed_files(dir, base)
+        .await
+        .map_err(|e| anyhow!("Failed to list files changed since {}: {}", base, e))
+}
+
+/// The configured value of a pipeline stage (pure function)
+fn stage_setting(map: &MapPhaseYaml, stage: &str) -> String {
+    let setting = match stage {
+        "filter" => map.filter.clone(),
+        "changed_since" => map.changed_since.clone(),
+        "sort_by" => map.sort_by.clone(),
+        "distinct" => map.distinct.clone(),
+        "offset" => map.offset.map(|n| n.to_string()),
+        "max_items" => map.max_items.map(|n| n.to_string()),
+        _ => None,
+    };
+    setting.unwrap_or_default()
+}
+
+/// Per-stage counts followed by the first `show` items (pure function)
+fn format_report(
+    input: &str,
+    map: &MapPhaseYaml,
+    run: &PipelineRun,
+    first_extracted: Option<&Value>,
+    show: usize,
+) -> String {
+    let mut out = String::new();
+    let _ = write!(out, "Input: {}", input);
+    if !map.json_path.is_empty() {
+        let _ = write!(out, " (json_path {})", map.json_path);
+    }
+    let _ = writeln!(out, "\n  {:<14} {:>6}", "extracted", run.extracted);
+
+    let settings: Vec<String> = run
+        .stages
+        .iter()
+        .map(|stage| stage_setting(map, stage.stage))
+        .collect();
+    let width = settings.iter().map(String::len).max().unwrap_or(0);
+    for (stage, setting) in run.stages.iter().zip(&settings) {
+        let _ = write!(
+            out,
+            "  {:<14} {:>6}  {:<width$}",
+            stage.stage,
+            stage.kept,
+            setting,
+            width = width
+        );
+        if stage.dropped > 0 {
+            let _ = write!(out, "  ({} dropped)", stage.dropped);
+        }
+        out = out.trim_end().to_string();
+        out.push('\n');
+    }
+
+    if let Some(empty_at) = run
+        .stages
+        .iter()
+        .find(|stage| stage.kept == 0 && stage.dropped > 0)
+    {
+        let _ = writeln!(out, "\nNo items are left after `{}`.", empty_at.stage);
+        if let Some(Value::Object(fields)) = first_extracted {
+            let names: Vec<&str> = fields.keys().map(String::as_str).collect();
+            let _ = writeln!(
+                out,
+                "Fields of the first extracted item: {}",
+                names.join(", ")
+            );
+        }
+        return out;
+    }
+    if run.extracted == 0 {
+        let _ = writeln!(out, "\nThe input has no items at this json_path.");
+        return out;
+    }
+
+    let _ = writeln!(out, "\n{} work items:", run.items.len());
+    for (index, item) in run.items.iter().take(show).enumerate() {
+        let _ = writeln!(out, "  {}. {}", index + 1, item);
+    }
+    if run.items.len() > show {
+        let _ = writeln!(
+            out,
+            "  ... {} more (use --show to list more)",
+            run.items.len() - show
+        );
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn map_phase(yaml: &str) -> MapPhaseYaml {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn test_report_lists_stage_counts_and_items() {
+        let map = map_phase(
+            r#"
+input: items.json
+json_path: "$.items[*]"
+filter: "severity == 'high'"
+distinct: file
+agent_template: []
+"#,
+        );
+        let data = json!({"items": [
+            {"file": "a.rs", "severity": "high"},
+            {"file": "a.rs", "severity": "high"},
+            {"file": "b.rs", "severity": "high"},
+            {"file": "c.rs", "severity": "low"}
+        ]});
+        let run = DataPipeline::from_full_config(
+            Some(map.json_path.clone()),
+            map.filter.clone(),
+            None,
+            None,
+            None,
+            map.distinct.clone(),
+        )
+        .unwrap()
+        .process_with_stats(&data)
+        .unwrap();
+
+        let report = format_report("items.json", &map, &run, None, 1);
+        let lines: Vec<&str> = report.lines().collect();
+        assert_eq!(lines[0], "Input: items.json (json_path $.items[*])");
+        assert_eq!(lines[1], "  extracted           4");
+        assert_eq!(
+            lines[2],
+            "  filter              3  severity == 'high'  (1 dropped)"
+        );
+        assert_eq!(
+            lines[3],
+            "  distinct            2  file                (1 dropped)"
+        );
+        assert_eq!(lines[5], "2 work items:");
+        assert_eq!(lines[6], r#"  1. {"file":"a.rs","severity":"high"}"#);
+        assert!(lines[7].contains("1 more"));
+    }
+
+    #[test]
+    fn test_report_names_stage_that_drops_everything() {
+        let map = map_phase(
+            r#"
+input: items.json
+filter: "priority > 5"
+agent_template: []
+"#,
+        );
+        let data = json!([{"score": 9, "file": "a.rs"}]);
+        let run = DataPipeline::from_full_config(None, map.filter.clone(), None, None, None, None)
+            .unwrap()
+            .process_with_stats(&data)
+            .unwrap();
+
+        let report = format_report("items.json", &map, &run, data.get(0), 10);
+        assert!(
+            report.contains("No items are left after `filter`."),
+            "{}",
+            report
+        );
+        assert!(report.contains("Fields of the first extracted item: file, score"));
+    }
+}
@@ -0,0 +1,296 @@
+//! Jobs command implementation
+//!
+//! Compares two runs of a MapReduce workflow item by item.
+
+use crate::cli::args::JobCommands;
+use crate::cook::execution::job_compare::{
+    compare_jobs, ItemChange, ItemComparison, ItemOutcome, ItemRun, JobComparison,
+};
+use crate::cook::execution::mapreduce::item_key::ItemKeyExpr;
+use crate::cook::execution::state::{CheckpointManager, MapReduceJobState};
+use crate::styled_println;
+use anyhow::{anyhow, Context, Result};
+
+/// Execute job-related commands
+pub async fn run_jobs_command(command: JobCommands) -> Result<()> {
+    match command {
+        JobCommands::Compare {
+            job_a,
+            job_b,
+            key,
+            output_format,
+            fail_on_regression,
+        } => {
+            let before = load_job_state(&job_a).await?;
+            let after = load_job_state(&job_b).await?;
+            let key = key
+                .as_deref()
+                .map(parse_compare_key)
+                .transpose()
+                .map_err(anyhow::Error::msg)?;
+            let comparison = compare_jobs(&before, &after, key.as_ref());
+
+            match output_format.as_str() {
+                "json" => println!("{}", serde_json::to_string_pretty(&comparison)?),
+                "human" => {
+                    for line in format_comparison(&comparison) {
+                        styled_println!("{}", line);
+                    }
+                }
+                other => {
+                    return Err(anyhow!(
+                        "Unsupported output format '{}' (expected human or json)",
+                        other
+                    ))
+                }
+            }
+
+            if fail_on_regression && comparison.regressions() > 0 {
+                return Err(anyhow!(
+                    "{} item(s) regressed between {} and {}",
+                    comparison.regressions(),
+                    job_a,
+                    job_b
+                ));
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Parse `--key`, accepting a bare field name as shorthand for `$.field`
+fn parse_compare_key(key: &str) -> Result<ItemKeyExpr, String> {
+    if key.starts_with('$') || key.contains('+') {
+        ItemKeyExpr::parse(key)
+    } else {
+        ItemKeyExpr::parse(&format!("$.{}", key))
+    }
+}
+
+/// Load the latest checkpoint of a MapReduce job from global storage
+pub(crate) async fn load_job_state(job_id: &str) -> Result<MapReduceJobState> {
+    let state_dir = crate::storage::get_default_storage_dir()
+        .context("Failed to determine Prodigy storage directory")?
+        .join("state");
+
+    if let Ok(mut entries) = tokio::fs::read_dir(&state_dir).await {
+        while let Ok(Some(repo_entry)) = entries.next_entry().await {
+            let mapreduce_dir = repo_entry.path().join("mapreduce");
+            if mapreduce_dir.join("jobs").join(job_id).is_dir() {
+                return CheckpointManager::new(mapreduce_dir)
+                    .load_checkpoint(job_id)
+                    .await
+                    .with_context(|| format!("Failed to load checkpoint for job {}", job_id));
+            }
+        }
+    }
+
+    Err(anyhow!(
+        "MapReduce job not found: {}\nSearched in: {}",
+        job_id,
+        state_dir.display()
+    ))
+}
+
+/// Format a comparison for the terminal (pure function)
+fn format_comparison(comparison: &JobComparison) -> Vec<String> {
+    let (before, after) = (&comparison.before, &comparison.after);
+    let mut lines = vec![
+        format!("Comparing {} → {}", before.job_id, after.job_id),
+        String::new(),
+        format!("  {:<12} {:>12} {:>12}", "", "before", "after"),
+        format!(
+            "  {:<12} {:>12} {:>12}",
+            "Items", before.total_items, after.total_items
+        ),
+        format!(
+            "  {:<12} {:>12} {:>12}",
+            "Succeeded", before.succeeded, after.succeeded
+        ),
+        format!(
+            "  {:<12} {:>12} {:>12}",
+            "Failed", before.failed, after.failed
+        ),
+        format!(
+            "  {:<12} {:>12} {:>12}",
+            "Total time",
+            format_secs(before.total_duration_secs),
+            format_secs(after.total_duration_secs)
+        ),
+        String::new(),
+    ];
+
+    if comparison.items.is_empty() {
+        lines.push(format!(
+            "No differences in {} item(s)",
+            comparison.unchanged
+        ));
+        return lines;
+    }
+
+    let counts: Vec<String> = [
+        ItemChange::Regressed,
+        ItemChange::Slower,
+        ItemChange::Fixed,
+        ItemChange::Faster,
+        ItemChange::ArtifactsChanged,
+        ItemChange::Added,
+        ItemChange::Removed,
+    ]
+    .iter()
+    .map(|change| (change, comparison.count(*change)))
+    .filter(|(_, count)| *count > 0)
+    .map(|(change, count)| format!("{} {}", count, change.label()))
+    .collect();
+    lines.push(format!(
+        "{} {} ({} unchanged)",
+        if comparison.regressions() > 0 {
+            "❌"
+        } else {
+            "✅"
+        },
+        counts.join(", "),
+        comparison.unchanged
+    ));
+    lines.push(String::new());
+
+    for item in &comparison.items {
+        lines.push(format_item(item));
+    }
+    lines
+}
+
+/// Format one changed item (pure function)
+fn format_item(item: &ItemComparison) -> String {
+    let icon = match item.changes.first() {
+        Some(ItemChange::Regressed) => "❌",
+        Some(ItemChange::Slower) => "🐢",
+        Some(ItemChange::Fixed) => "✅",
+        Some(ItemChange::Faster) => "⚡",
+        Some(ItemChange::ArtifactsChanged) => "📝",
+        Some(ItemChange::Added) => "➕",
+        Some(ItemChange::Removed) | None => "➖",
+    };
+
+    let details: Vec<String> = item
+        .changes
+        .iter()
+        .map(|change| match change {
+            ItemChange::Regressed | ItemChange::Fixed => {
+                format!(
+                    "{} → {}",
+                    outcome(item.before.as_ref()),
+                    outcome(item.after.as_ref())
+                )
+            }
+            ItemChange::Slower | ItemChange::Faster => format!(
+                "{} → {}",
+                duration(item.before.as_ref()),
+                duration(item.after.as_ref())
+            ),
+            ItemChange::ArtifactsChanged => {
+                let files: Vec<String> = item
+                    .files_added
+                    .iter()
+                    .map(|f| format!("+{}", f))
+                    .chain(item.files_removed.iter().map(|f| format!("-{}", f)))
+                    .collect();
+                format!("files {}", files.join(" "))
+            }
+            ItemChange::Added => outcome(item.after.as_ref()),
+            ItemChange::Removed => outcome(item.before.as_ref()),
+        })
+        .collect();
+
+    let labels: Vec<&str> = item.changes.iter().map(ItemChange::label).collect();
+    format!(
+        "  {} {}: {} ({})",
+        icon,
+        item.key,
+        labels.join(", "),
+        details.join("; ")
+    )
+}
+
+fn outcome(run: Option<&ItemRun>) -> String {
+    match run {
+        Some(run) => match &run.error {
+            Some(error) if run.outcome != ItemOutcome::Succeeded => {
+                format!("{}: {}", run.outcome.label(), error)
+            }
+            _ => run.outcome.label().to_string(),
+        },
+        None => "missing".to_string(),
+    }
+}
+
+fn duration(run: Option<&ItemRun>) -> String {
+    run.and_then(|r| r.duration_secs)
+        .map(format_secs)
+        .unwrap_or_else(|| "-".to_string())
+}
+
+fn format_secs(secs: f64) -> String {
+    if secs >= 60.0 {
+        format!(
+            "{}m {:02}s",
+            (secs / 60.0).floor() as u64,
+            (secs % 60.0).round() as u64
+        )
+    } else {
+        format!("{:.1}s", secs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cook::execution::job_compare::compare_runs;
+    use std::collections::BTreeMap;
+
+    fn run(outcome: ItemOutcome, duration_secs: f64, error: Option<&str>) -> ItemRun {
+        ItemRun {
+            item_id: "item_0".to_string(),
+            outcome,
+            duration_secs: Some(duration_secs),
+            error: error.map(str::to_string),
+            commits: 0,
+            files_modified: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_format_comparison_highlights_regressions() {
+        let before = BTreeMap::from([
+            ("a".to_string(), run(ItemOutcome::Succeeded, 12.0, None)),
+            ("b".to_string(), run(ItemOutcome::Succeeded, 5.0, None)),
+        ]);
+        let after = BTreeMap::from([
+            (
+                "a".to_string(),
+                run(ItemOutcome::Failed, 3.0, Some("tests failed")),
+            ),
+            ("b".to_string(), run(ItemOutcome::Succeeded, 5.0, None)),
+        ]);
+
+        let lines = format_comparison(&compare_runs("job-a", &before, "job-b", &after));
+        assert_eq!(lines[0], "Comparing job-a → job-b");
+        assert!(lines.contains(&"❌ 1 regressed (1 unchanged)".to_string()));
+        assert!(lines.contains(&"  ❌ a: regressed (succeeded → failed: tests failed)".to_string()));
+    }
+
+    #[test]
+    fn test_parse_compare_key() {
+        let item = serde_json::json!({"file": "a.rs", "fn": "main"});
+        let bare = parse_compare_key("file").unwrap();
+        assert_eq!(bare.evaluate(&item).unwrap(), "a.rs");
+        let expr = parse_compare_key("$.file + :: + $.fn").unwrap();
+        assert_eq!(expr.evaluate(&item).unwrap(), "a.rs::main");
+    }
+
+    #[test]
+    fn test_format_secs() {
+        assert_eq!(format_secs(4.0), "4.0s");
+        assert_eq!(format_secs(125.0), "2m 05s");
+    }
+}
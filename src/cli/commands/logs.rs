@@ -2,9 +2,12 @@
 //!
 //! Provides functionality to view, search, and analyze Claude JSON logs.
 
+use crate::cook::execution::claude_logs;
+use crate::styled_println;
 use anyhow::{Context, Result};
+use std::collections::HashMap;
 use std::fs;
-use std::io::{BufRead, BufReader};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::time::SystemTime;
@@ -29,6 +32,104 @@ pub async fn run_logs_command(
     Ok(())
 }
 
+/// Run `prodigy logs claude`, printing the streamed Claude messages of a run
+pub async fn run_claude_logs_command(
+    run_id: &str,
+    follow: bool,
+    agent: Option<String>,
+    item: Option<String>,
+) -> Result<()> {
+    let log_dir = claude_logs::streaming_log_dir()?;
+    let mut viewer = ClaudeLogViewer::default();
+
+    let found = viewer.print_new_messages(&log_dir, run_id, agent.as_deref(), item.as_deref())?;
+    if !found && !follow {
+        anyhow::bail!(
+            "No Claude streaming logs found for '{}' in {}. Logs are recorded for Claude \
+             commands run with streaming enabled (the default).",
+            run_id,
+            log_dir.display()
+        );
+    }
+
+    if follow {
+        println!("\nFollowing Claude logs (Ctrl+C to exit)...\n");
+        loop {
+            tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+            viewer.print_new_messages(&log_dir, run_id, agent.as_deref(), item.as_deref())?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Tracks how far each streaming log has been printed
+#[derive(Default)]
+struct ClaudeLogViewer {
+    offsets: HashMap<PathBuf, u64>,
+    current: Option<PathBuf>,
+}
+
+impl ClaudeLogViewer {
+    /// Print messages appended since the last call, returning whether the run has any logs
+    fn print_new_messages(
+        &mut self,
+        log_dir: &Path,
+        run_id: &str,
+        agent: Option<&str>,
+        item: Option<&str>,
+    ) -> Result<bool> {
+        let entries = claude_logs::load_entries(log_dir)?;
+        let selected = claude_logs::select_entries(&entries, run_id, agent, item);
+
+        for entry in &selected {
+            let offset = self.offsets.entry(entry.log.clone()).or_insert(0);
+            let lines = read_new_lines(&entry.log, offset)?;
+            let rendered: Vec<String> = lines
+                .iter()
+                .flat_map(|line| claude_logs::render_event(line))
+                .collect();
+            if rendered.is_empty() {
+                continue;
+            }
+            if self.current.as_ref() != Some(&entry.log) {
+                styled_println!("\n{}", entry.header());
+                self.current = Some(entry.log.clone());
+            }
+            for line in rendered {
+                println!("  {}", line);
+            }
+        }
+
+        Ok(!selected.is_empty())
+    }
+}
+
+/// Read the complete lines written to `path` after `offset`, advancing it
+///
+/// A trailing line without a newline is still being written and is left for
+/// the next read. Missing files yield no lines.
+fn read_new_lines(path: &Path, offset: &mut u64) -> Result<Vec<String>> {
+    let mut file = match fs::File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e).with_context(|| format!("Failed to open {}", path.display())),
+    };
+    file.seek(SeekFrom::Start(*offset))?;
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf)?;
+
+    let complete = match buf.iter().rposition(|b| *b == b'\n') {
+        Some(pos) => pos + 1,
+        None => return Ok(Vec::new()),
+    };
+    *offset += complete as u64;
+    Ok(String::from_utf8_lossy(&buf[..complete])
+        .lines()
+        .map(str::to_string)
+        .collect())
+}
+
 /// Get the Claude log directory
 fn get_claude_log_dir() -> Result<PathBuf> {
     let home = dirs::home_dir().context("Could not determine home directory")?;
@@ -39,7 +140,7 @@ fn get_claude_log_dir() -> Result<PathBuf> {
 fn handle_latest_log(log_dir: &Path, tail: bool, summary: bool) -> Result<()> {
     let latest_log = find_latest_log(log_dir)?;
 
-    println!("📋 Latest Claude log: {}", latest_log.display());
+    styled_println!("📋 Latest Claude log: {}", latest_log.display());
 
     if tail {
         tail_file(&latest_log)?;
@@ -68,7 +169,7 @@ fn handle_specific_session(
     // Try to find a log file matching the session ID
     let log_file = find_log_for_session(log_dir, session_id)?;
 
-    println!("📋 Claude log: {}", log_file.display());
+    styled_println!("📋 Claude log: {}", log_file.display());
 
     if tail {
         tail_file(&log_file)?;
@@ -329,6 +430,24 @@ fn tail_file(log_path: &Path) -> Result<()> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_read_new_lines() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("stream.jsonl");
+        let mut offset = 0;
+        assert!(read_new_lines(&path, &mut offset).unwrap().is_empty());
+
+        fs::write(&path, "first\nsecond\npart").unwrap();
+        assert_eq!(
+            read_new_lines(&path, &mut offset).unwrap(),
+            vec!["first", "second"]
+        );
+
+        fs::write(&path, "first\nsecond\npartial\n").unwrap();
+        assert_eq!(read_new_lines(&path, &mut offset).unwrap(), vec!["partial"]);
+        assert!(read_new_lines(&path, &mut offset).unwrap().is_empty());
+    }
+
     #[test]
     fn test_is_log_file() {
         assert!(is_log_file(Path::new("session-abc.jsonl")));
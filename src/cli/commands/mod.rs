@@ -3,28 +3,52 @@
 //! This module contains the implementation of each CLI command.
 //! Each command is implemented as a separate module for better organization.
 
+pub mod analytics;
 pub mod changelog;
 pub mod checkpoints;
+pub mod claude_commands;
 pub mod clean;
 pub mod config;
 pub mod dlq;
+pub mod docs;
 pub mod events;
 pub mod exec;
+pub mod graph;
+pub mod import;
+pub mod inspect_input;
+pub mod jobs;
 pub mod logs;
 pub mod progress;
+pub mod report;
 pub mod resume;
+pub mod sarif;
 pub mod sessions;
+pub mod setup;
+pub mod storage;
+pub mod triggers;
 pub mod worktree;
 
 // Re-export command execution functions
+pub use analytics::run_analytics_command;
 pub use changelog::run_changelog_command;
-pub use checkpoints::run_checkpoints_command;
+pub use checkpoints::{run_checkpoint_mark_command, run_checkpoints_command, run_rollback_command};
+pub use claude_commands::run_claude_commands_command;
 pub use config::run_config_command;
 pub use dlq::run_dlq_command;
+pub use docs::run_docs_command;
 pub use events::run_events_command;
 pub use exec::{run_batch_command, run_exec_command};
-pub use logs::run_logs_command;
+pub use graph::run_graph_command;
+pub use import::run_import_command;
+pub use inspect_input::run_inspect_input_command;
+pub use jobs::run_jobs_command;
+pub use logs::{run_claude_logs_command, run_logs_command};
 pub use progress::run_progress_command;
-pub use resume::{run_resume_job_command, run_resume_workflow};
+pub use report::run_report_command;
+pub use resume::{run_resume_job_command, run_resume_workflow, ResumeOverrides};
+pub use sarif::run_sarif_command;
 pub use sessions::run_sessions_command;
-pub use worktree::run_worktree_command;
+pub use setup::{run_setup_command, SetupOptions};
+pub use storage::run_storage_command;
+pub use triggers::run_triggers_command;
+pub use worktree::{report_orphaned_sessions_in, run_worktree_command};
@@ -1,17 +1,291 @@
 //! Progress command implementation
 //!
-//! This module handles MapReduce job progress viewing and monitoring.
+//! Shows the steps of a session or MapReduce job with their status and elapsed
+//! time, along with the progress that shell steps report with
+//! `::prodigy-progress` lines and the estimated time left in the map phase.
+//! The progress can also be exported or served on the web dashboard.
 
-use anyhow::Result;
-use std::path::PathBuf;
+use crate::cook::execution::progress::format_duration;
+use crate::cook::execution::progress_dashboard::DashboardServer;
+use crate::cook::execution::progress_tracker::{
+    AgentProgress, AgentStatus, PhaseProgress, PhaseType, ProgressRenderer, ProgressTracker,
+    WorkflowProgress,
+};
+use crate::cook::execution::step_progress::{
+    load_latest_progress, progress_file_path, StepProgressRecord, StepStatus,
+};
+use crate::styled_println;
+use anyhow::{anyhow, Result};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-/// View MapReduce job progress
+const STEPS_PHASE: &str = "steps";
+
+/// View step progress for a session or MapReduce job
 pub async fn run_progress_command(
-    _job_id: String,
-    _export: Option<PathBuf>,
-    _format: String,
-    _web: Option<u16>,
+    job_id: String,
+    export: Option<PathBuf>,
+    format: String,
+    web: Option<u16>,
 ) -> Result<()> {
-    println!("Viewing MapReduce job progress...");
+    let path = progress_file_path(&job_id)?;
+
+    if let Some(port) = web {
+        return serve_dashboard(&job_id, path, port).await;
+    }
+
+    let records = load_latest_progress(&path)?;
+
+    if let Some(export_path) = export {
+        let content = export_records(&records, &format)?;
+        tokio::fs::write(&export_path, content).await?;
+        println!(
+            "Exported progress for {} step(s) to {}",
+            records.len(),
+            export_path.display()
+        );
+        return Ok(());
+    }
+
+    if records.is_empty() {
+        println!("No step progress reported for {}", job_id);
+        println!("Shell steps report progress by printing lines such as:");
+        println!("  ::prodigy-progress 45/100 \"building\"");
+        return Ok(());
+    }
+
+    println!("Progress for {}:", job_id);
+    for record in &records {
+        styled_println!("  {}", format_record(record));
+    }
     Ok(())
 }
+
+/// Format one record as a progress bar line (pure function)
+fn format_record(record: &StepProgressRecord) -> String {
+    format!("{} {}", record.source, describe_record(record))
+}
+
+/// Progress, status and timing of a record (pure function)
+fn describe_record(record: &StepProgressRecord) -> String {
+    let mut parts = Vec::new();
+    if record.progress.total > 0 {
+        let width = 20;
+        let filled = ((record.progress.percentage() / 100.0) * width as f32).round() as usize;
+        parts.push(format!(
+            "[{}{}]",
+            "█".repeat(filled),
+            "░".repeat(width - filled)
+        ));
+    }
+    let progress = record.progress.to_string();
+    if !progress.is_empty() {
+        parts.push(progress);
+    }
+    parts.push(match record.elapsed() {
+        Some(elapsed) => {
+            let elapsed = format_duration(elapsed);
+            match record.status {
+                StepStatus::Running => format!("(running for {})", elapsed),
+                StepStatus::Completed => format!("(completed in {})", elapsed),
+                StepStatus::Failed => format!("(failed after {})", elapsed),
+            }
+        }
+        None => format!("(updated {})", record.updated_at.format("%H:%M:%S")),
+    });
+    if let (StepStatus::Running, Some(eta)) = (record.status, record.eta_secs) {
+        parts.push(format!("ETA {}", format_duration(Duration::from_secs(eta))));
+    }
+    parts.join(" ")
+}
+
+/// Serialize records in the requested export format (pure function)
+fn export_records(records: &[StepProgressRecord], format: &str) -> Result<String> {
+    match format {
+        "json" => Ok(serde_json::to_string_pretty(records)?),
+        "csv" => {
+            let mut csv = String::from("source,step,current,total,percentage,message,updated_at\n");
+            for record in records {
+                csv.push_str(&format!(
+                    "{},{},{},{},{:.1},{},{}\n",
+                    csv_field(&record.source),
+                    csv_field(&record.step),
+                    record.progress.current,
+                    record.progress.total,
+                    record.progress.percentage(),
+                    csv_field(record.progress.message.as_deref().unwrap_or_default()),
+                    record.updated_at.to_rfc3339()
+                ));
+            }
+            Ok(csv)
+        }
+        other => Err(anyhow!(
+            "Unsupported progress export format '{}' (expected json or csv)",
+            other
+        )),
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Renderer for a tracker that is only read by the dashboard
+struct DashboardOnlyRenderer;
+
+#[async_trait::async_trait]
+impl ProgressRenderer for DashboardOnlyRenderer {
+    async fn update_display(
+        &self,
+        _workflow: &WorkflowProgress,
+        _phases: &HashMap<String, PhaseProgress>,
+    ) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Serve the web dashboard, refreshing it from the progress file
+async fn serve_dashboard(job_id: &str, path: PathBuf, port: u16) -> Result<()> {
+    let tracker = Arc::new(ProgressTracker::new(
+        job_id.to_string(),
+        job_id.to_string(),
+        Box::new(DashboardOnlyRenderer),
+    ));
+    tracker.start_workflow(0).await?;
+    tracker
+        .start_phase(STEPS_PHASE.to_string(), PhaseType::Sequential, 0)
+        .await?;
+
+    let server = Arc::new(DashboardServer::new(tracker.clone(), port));
+    let refresher = server.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(1));
+        loop {
+            interval.tick().await;
+            if let Err(e) = refresh_dashboard(&tracker, &refresher, &path).await {
+                tracing::warn!("Failed to refresh progress dashboard: {}", e);
+            }
+        }
+    });
+
+    server.start().await
+}
+
+async fn refresh_dashboard(
+    tracker: &ProgressTracker,
+    server: &DashboardServer,
+    path: &Path,
+) -> Result<()> {
+    for record in load_latest_progress(path)? {
+        let now = Instant::now();
+        // Progress-only records finish when they reach their total
+        let status = match record.status {
+            StepStatus::Completed => AgentStatus::Completed,
+            StepStatus::Failed => AgentStatus::Failed,
+            StepStatus::Running
+                if record.started_at.is_none()
+                    && record.progress.total > 0
+                    && record.progress.current >= record.progress.total =>
+            {
+                AgentStatus::Completed
+            }
+            StepStatus::Running => AgentStatus::Working,
+        };
+        let start_time = record
+            .elapsed()
+            .and_then(|elapsed| now.checked_sub(elapsed))
+            .unwrap_or(now);
+        tracker
+            .update_agent(
+                STEPS_PHASE,
+                AgentProgress {
+                    id: record.source.clone(),
+                    worktree: record.step.clone(),
+                    current_item: None,
+                    status,
+                    items_processed: 0,
+                    start_time,
+                    last_update: now,
+                    current_step: Some(describe_record(&record)),
+                    memory_usage: 0,
+                    cpu_usage: 0.0,
+                },
+            )
+            .await?;
+    }
+    server
+        .broadcast_update(tracker.serializable_snapshot().await)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cook::execution::step_progress::StepProgress;
+    use chrono::Utc;
+
+    fn record(source: &str, current: u64, message: Option<&str>) -> StepProgressRecord {
+        StepProgressRecord {
+            source: source.to_string(),
+            step: "./build.sh".to_string(),
+            phase: None,
+            status: StepStatus::Running,
+            progress: StepProgress {
+                current,
+                total: 100,
+                message: message.map(str::to_string),
+            },
+            started_at: None,
+            eta_secs: None,
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_format_record_shows_bar_and_message() {
+        let line = format_record(&record("shell: ./build.sh", 50, Some("compiling")));
+        assert!(line.starts_with("shell: ./build.sh [██████████░░░░░░░░░░] 50/100 (50%) compiling"));
+    }
+
+    #[test]
+    fn test_format_record_shows_step_timing() {
+        let now = Utc::now();
+        let mut step = record("setup: cargo build", 0, None);
+        step.progress.total = 0;
+        step.started_at = Some(now - chrono::Duration::seconds(75));
+        step.updated_at = now;
+        step.status = StepStatus::Completed;
+        assert_eq!(
+            format_record(&step),
+            "setup: cargo build (completed in 1m 15s)"
+        );
+
+        step.status = StepStatus::Failed;
+        step.progress.total = 10;
+        step.progress.current = 4;
+        assert!(format_record(&step).ends_with("4/10 (40%) (failed after 1m 15s)"));
+
+        step.status = StepStatus::Running;
+        step.eta_secs = Some(200);
+        assert!(format_record(&step).ends_with("ETA 3m 20s"));
+    }
+
+    #[test]
+    fn test_export_records() {
+        let records = vec![record("agent-1", 45, Some("a, b"))];
+
+        let csv = export_records(&records, "csv").unwrap();
+        assert!(csv.contains("agent-1,./build.sh,45,100,45.0,\"a, b\","));
+
+        let json = export_records(&records, "json").unwrap();
+        assert!(json.contains("\"current\": 45"));
+
+        assert!(export_records(&records, "html").is_err());
+    }
+}
@@ -0,0 +1,85 @@
+//! Report command implementation
+//!
+//! Renders a MapReduce job's checkpoint and events as an HTML or JSON report.
+
+use super::jobs::load_job_state;
+use crate::cli::events::io::find_event_files;
+use crate::cook::execution::events::EventRecord;
+use crate::cook::execution::job_report::{
+    build_job_report, commit_url_base, render_html, CostRates,
+};
+use anyhow::{anyhow, Context, Result};
+use std::io::BufRead;
+use std::path::PathBuf;
+
+/// Generate a report for a MapReduce job
+pub async fn run_report_command(
+    job_id: &str,
+    format: &str,
+    output: Option<PathBuf>,
+    rates: CostRates,
+) -> Result<()> {
+    if !matches!(format, "html" | "json") {
+        return Err(anyhow!(
+            "Unsupported report format '{}' (expected html or json)",
+            format
+        ));
+    }
+
+    let state = load_job_state(job_id).await?;
+    let events = load_job_events(job_id)?;
+    let report = build_job_report(&state, &events, &rates);
+
+    let content = if format == "json" {
+        serde_json::to_string_pretty(&report)?
+    } else {
+        render_html(&report, origin_commit_url().as_deref())
+    };
+    let output = output.unwrap_or_else(|| PathBuf::from(format!("{}-report.{}", job_id, format)));
+    tokio::fs::write(&output, content)
+        .await
+        .with_context(|| format!("Failed to write {}", output.display()))?;
+
+    println!(
+        "Wrote {} report for {} to {} ({} items, {} failed)",
+        format,
+        job_id,
+        output.display(),
+        report.total_items,
+        report.failed
+    );
+    Ok(())
+}
+
+/// Read every event recorded for a job in global storage
+fn load_job_events(job_id: &str) -> Result<Vec<EventRecord>> {
+    let events_dir = crate::storage::get_default_storage_dir()
+        .context("Failed to determine Prodigy storage directory")?
+        .join("events");
+
+    let mut events = Vec::new();
+    let repo_dirs = match std::fs::read_dir(&events_dir) {
+        Ok(entries) => entries,
+        Err(_) => return Ok(events),
+    };
+    for repo_dir in repo_dirs.filter_map(|e| e.ok()) {
+        for file in find_event_files(&repo_dir.path().join(job_id))? {
+            let reader = std::io::BufReader::new(std::fs::File::open(&file)?);
+            events.extend(
+                reader
+                    .lines()
+                    .map_while(|line| line.ok())
+                    .filter_map(|line| serde_json::from_str::<EventRecord>(&line).ok()),
+            );
+        }
+    }
+    events.sort_by_key(|record| record.timestamp);
+    Ok(events)
+}
+
+/// Commit URL base of the current repository's `origin` remote
+fn origin_commit_url() -> Option<String> {
+    let repo = git2::Repository::discover(".").ok()?;
+    let remote = repo.find_remote("origin").ok()?;
+    commit_url_base(remote.url()?)
+}
@@ -7,6 +7,7 @@
 //! - Small focused async functions for I/O operations
 //! - Main functions compose these smaller units
 
+use crate::{styled_eprintln, styled_println};
 use anyhow::{anyhow, Context, Result};
 use std::path::{Path, PathBuf};
 use tokio::fs;
@@ -95,7 +96,23 @@ fn build_cook_command(
         verbosity: 0,
         quiet: false,
         dry_run: false,
+        read_only: false,
+        record: None,
+        junit_output: None,
+        profile_steps: false,
+        replay: None,
+        chaos: None,
+        seed: None,
+        answers_file: None,
+        environment: None,
+        no_worktree: false,
         params: std::collections::HashMap::new(),
+        package: None,
+        changed_only: None,
+        explain_failure: false,
+        keep_going: false,
+        steps: Default::default(),
+        explicit_flags: Default::default(),
     }
 }
 
@@ -103,18 +120,14 @@ fn build_cook_command(
 // Small Focused Async Functions - Single responsibility I/O operations
 // ============================================================================
 
-/// Acquire a resume lock for the session
+/// Claim the session so no other process can run it while it is resumed
 async fn acquire_resume_lock(
     prodigy_home: &Path,
     session_id: &str,
-) -> Result<crate::cook::execution::ResumeLock> {
-    let lock_manager = crate::cook::execution::ResumeLockManager::new(prodigy_home.to_path_buf())
-        .context("Failed to create resume lock manager")?;
+) -> Result<crate::unified_session::heartbeat::SessionLock> {
+    use crate::unified_session::heartbeat::{session_locks_dir, SessionLock};
 
-    lock_manager
-        .acquire_lock(session_id)
-        .await
-        .context("Failed to acquire resume lock")
+    SessionLock::acquire(session_locks_dir(prodigy_home), session_id).await
 }
 
 /// Load session and validate it's resumable, returns session data if found
@@ -146,6 +159,25 @@ async fn load_and_validate_session(
     }
 }
 
+/// Warn when tool versions or the platform changed since the session started
+async fn warn_on_environment_change(session: &crate::unified_session::UnifiedSession) {
+    use crate::unified_session::EnvironmentSnapshot;
+
+    let Some(recorded) = EnvironmentSnapshot::from_metadata(&session.metadata) else {
+        return;
+    };
+    let differences = recorded.differences(&EnvironmentSnapshot::capture().await);
+    if differences.is_empty() {
+        return;
+    }
+
+    styled_eprintln!("⚠️  Environment changed since this session started:");
+    for difference in differences {
+        styled_eprintln!("   {}", difference);
+    }
+    styled_eprintln!();
+}
+
 /// Check if checkpoint directory exists and contains checkpoint files
 fn validate_checkpoint_directory(checkpoint_dir: &Path) -> Result<bool> {
     if !checkpoint_dir.exists() {
@@ -244,6 +276,27 @@ async fn read_workflow_path_from_checkpoint(
         })
 }
 
+/// Move a session to the checkout at `new_path` and report what changed
+async fn relocate_session(
+    worktree_path: &Path,
+    checkpoint_dir: &Path,
+    new_path: &Path,
+) -> Result<()> {
+    let relocation =
+        crate::cook::workflow::relocate::relocate_session(worktree_path, checkpoint_dir, new_path)
+            .await
+            .context("Failed to relocate session")?;
+    styled_println!(
+        "Relocated session from {} to {} ({} paths in {} files)",
+        relocation.old_root.display(),
+        relocation.new_root.display(),
+        relocation.paths,
+        relocation.files
+    );
+    styled_println!();
+    Ok(())
+}
+
 /// Print resume status information
 fn print_resume_status(
     session_id: &str,
@@ -252,22 +305,42 @@ fn print_resume_status(
     worktree_path: &Path,
     from_checkpoint: Option<&str>,
 ) {
-    println!("Resuming session: {}", session_id);
-    println!("Workflow: {}", workflow_path);
-    println!(
+    styled_println!("Resuming session: {}", session_id);
+    styled_println!("Workflow: {}", workflow_path);
+    styled_println!(
         "Checkpoint: {}",
         checkpoint_file.file_name().unwrap().to_string_lossy()
     );
 
-    println!();
-    println!("Note: Resuming from worktree: {}", worktree_path.display());
+    styled_println!();
+    styled_println!("Note: Resuming from worktree: {}", worktree_path.display());
     if let Some(cp) = from_checkpoint {
-        println!("      Using specific checkpoint: {}", cp);
+        styled_println!("      Using specific checkpoint: {}", cp);
     } else {
-        println!("      Using latest checkpoint");
+        styled_println!("      Using latest checkpoint");
+    }
+    styled_println!("      Project root: {}", worktree_path.display());
+    styled_println!();
+}
+
+/// Report progress marks that steps can skip on resume
+async fn print_checkpoint_marks(session_id: &str) {
+    use crate::cook::workflow::checkpoint_marks;
+
+    let marks = match checkpoint_marks::marks_file_path(session_id) {
+        Ok(path) => checkpoint_marks::load_marks(&path)
+            .await
+            .unwrap_or_default(),
+        Err(_) => return,
+    };
+    if let Some(latest) = marks.last() {
+        styled_println!(
+            "Checkpoint marks: {} recorded (latest: {})",
+            marks.len(),
+            latest.label
+        );
+        styled_println!();
     }
-    println!("      Project root: {}", worktree_path.display());
-    println!();
 }
 
 /// Resume an interrupted workflow or MapReduce job
@@ -282,6 +355,7 @@ pub async fn run_resume_workflow(
     _force: bool,
     from_checkpoint: Option<String>,
     _path: Option<PathBuf>,
+    relocate: Option<PathBuf>,
 ) -> Result<()> {
     // If no session ID provided, try to find the most recent interrupted session
     let session_id = if let Some(id) = session_id {
@@ -295,7 +369,23 @@ pub async fn run_resume_workflow(
     };
 
     // Try to detect the type of ID and resume appropriately
-    let resume_result = try_unified_resume(&session_id, from_checkpoint).await;
+    let resume_result = match relocate {
+        Some(new_path) if matches!(detect_id_type(&session_id), IdType::MapReduceJobId) => {
+            run_resume_job_command(
+                session_id.clone(),
+                false,
+                0,
+                ResumeOverrides::default(),
+                None,
+                Some(new_path),
+            )
+            .await
+        }
+        Some(new_path) => {
+            try_resume_regular_workflow(&session_id, from_checkpoint, Some(&new_path)).await
+        }
+        None => try_unified_resume(&session_id, from_checkpoint).await,
+    };
 
     match resume_result {
         Ok(()) => Ok(()),
@@ -351,7 +441,7 @@ async fn try_unified_resume(id: &str, from_checkpoint: Option<String>) -> Result
     match id_type {
         IdType::SessionId => {
             // First try regular workflow resume
-            match try_resume_regular_workflow(id, from_checkpoint.clone()).await {
+            match try_resume_regular_workflow(id, from_checkpoint.clone(), None).await {
                 Ok(()) => Ok(()),
                 Err(e) => {
                     // If that fails, maybe it's a MapReduce job with session ID
@@ -369,7 +459,7 @@ async fn try_unified_resume(id: &str, from_checkpoint: Option<String>) -> Result
             match check_session_type(id).await {
                 Ok(SessionType::Workflow) => {
                     // It's a workflow session, use workflow resume
-                    try_resume_regular_workflow(id, from_checkpoint.clone()).await
+                    try_resume_regular_workflow(id, from_checkpoint.clone(), None).await
                 }
                 Ok(SessionType::MapReduce) => {
                     // It's a MapReduce session, use MapReduce resume
@@ -377,7 +467,7 @@ async fn try_unified_resume(id: &str, from_checkpoint: Option<String>) -> Result
                 }
                 Err(_) => {
                     // Session not found in UnifiedSessionManager, try workflow first
-                    match try_resume_regular_workflow(id, from_checkpoint.clone()).await {
+                    match try_resume_regular_workflow(id, from_checkpoint.clone(), None).await {
                         Ok(()) => Ok(()),
                         Err(e) => {
                             // Check if the error is about a completed/cancelled session
@@ -466,6 +556,7 @@ fn detect_id_type(id: &str) -> IdType {
 async fn try_resume_regular_workflow(
     session_id: &str,
     from_checkpoint: Option<String>,
+    relocate: Option<&Path>,
 ) -> Result<()> {
     let prodigy_home = crate::storage::get_default_storage_dir()
         .context("Failed to determine Prodigy storage directory")?;
@@ -475,6 +566,9 @@ async fn try_resume_regular_workflow(
 
     // Load and validate session status
     let session_data = load_and_validate_session(session_id).await?;
+    if let Some(session) = &session_data {
+        warn_on_environment_change(session).await;
+    }
 
     // Validate checkpoint directory and files exist
     let checkpoint_dir = prodigy_home
@@ -496,13 +590,18 @@ async fn try_resume_regular_workflow(
     // Find the checkpoint file
     let checkpoint_file = find_checkpoint_file(&checkpoint_dir, from_checkpoint.as_deref()).await?;
 
-    // Extract workflow path from checkpoint
-    let workflow_path = read_workflow_path_from_checkpoint(&checkpoint_file, session_id).await?;
-
     // Find the worktree for this session
     let worktrees_dir = prodigy_home.join("worktrees");
     let worktree_path = find_worktree_for_session(&worktrees_dir, session_id).await?;
 
+    // Point the worktree and checkpoints at the moved checkout
+    if let Some(new_path) = relocate {
+        relocate_session(&worktree_path, &checkpoint_dir, new_path).await?;
+    }
+
+    // Extract workflow path from checkpoint
+    let workflow_path = read_workflow_path_from_checkpoint(&checkpoint_file, session_id).await?;
+
     // Print status and execute
     print_resume_status(
         session_id,
@@ -511,19 +610,38 @@ async fn try_resume_regular_workflow(
         &worktree_path,
         from_checkpoint.as_deref(),
     );
+    print_checkpoint_marks(session_id).await;
 
-    let cook_cmd = build_cook_command(&workflow_path, worktree_path, session_id);
+    let mut cook_cmd = build_cook_command(&workflow_path, worktree_path, session_id);
+    // A deterministic run stays deterministic when resumed
+    cook_cmd.seed = session_data
+        .as_ref()
+        .and_then(|session| crate::cook::execution::determinism::from_metadata(&session.metadata));
     crate::cook::cook(cook_cmd).await
 }
 
 /// Try to resume a MapReduce job by job ID
 async fn try_resume_mapreduce_job(job_id: &str) -> Result<()> {
     // Delegate to the existing MapReduce job resume command
-    run_resume_job_command(job_id.to_string(), false, 0, None).await
+    run_resume_job_command(
+        job_id.to_string(),
+        false,
+        0,
+        ResumeOverrides::default(),
+        None,
+        None,
+    )
+    .await
 }
 
 /// Try to find and resume a MapReduce job associated with a session ID
 async fn try_resume_mapreduce_from_session(session_id: &str) -> Result<()> {
+    let prodigy_home = crate::storage::get_default_storage_dir()
+        .context("Failed to determine Prodigy storage directory")?;
+
+    // Hold the session while its job resumes
+    let _session_lock = acquire_resume_lock(&prodigy_home, session_id).await?;
+
     // Check if session exists and is resumable by loading session metadata
     let storage =
         crate::storage::GlobalStorage::new().context("Failed to create global storage")?;
@@ -558,8 +676,6 @@ async fn try_resume_mapreduce_from_session(session_id: &str) -> Result<()> {
     }
 
     // Look for MapReduce jobs in the global storage
-    let prodigy_home = crate::storage::get_default_storage_dir()
-        .context("Failed to determine Prodigy storage directory")?;
 
     // Try to find a MapReduce job for this session
     // MapReduce jobs are stored at: ~/.prodigy/state/{repo}/mapreduce/jobs/{job-id}/
@@ -605,7 +721,7 @@ async fn try_resume_mapreduce_from_session(session_id: &str) -> Result<()> {
     }
 
     if let Some(job_id) = found_job_id {
-        println!("Found MapReduce job: {}", job_id);
+        styled_println!("Found MapReduce job: {}", job_id);
         try_resume_mapreduce_job(&job_id).await
     } else {
         Err(anyhow!(
@@ -615,14 +731,27 @@ async fn try_resume_mapreduce_from_session(session_id: &str) -> Result<()> {
     }
 }
 
+/// Settings that replace the job's original configuration for the remaining work
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ResumeOverrides {
+    /// Maximum parallel agents
+    pub max_parallel: Option<usize>,
+    /// Maximum number of remaining items to process
+    pub max_items: Option<usize>,
+    /// Per-agent timeout in seconds
+    pub agent_timeout_secs: Option<u64>,
+}
+
 /// Resume a MapReduce job from its checkpoint
 pub async fn run_resume_job_command(
     job_id: String,
     _force: bool,
     _max_retries: u32,
+    overrides: ResumeOverrides,
     _path: Option<PathBuf>,
+    relocate: Option<PathBuf>,
 ) -> Result<()> {
-    println!("🔄 Resuming MapReduce job: {}", job_id);
+    styled_println!("🔄 Resuming MapReduce job: {}", job_id);
 
     // Find the MapReduce job checkpoint
     let prodigy_home = crate::storage::get_default_storage_dir()
@@ -678,28 +807,36 @@ pub async fn run_resume_job_command(
         )
     })?;
 
-    println!("📂 Found job at: {}", job_dir.display());
+    styled_println!("📂 Found job at: {}", job_dir.display());
 
     // Check for checkpoint files
     if let Ok(mut entries) = fs::read_dir(&job_dir).await {
-        println!("\n📋 Available checkpoints:");
+        styled_println!("\n📋 Available checkpoints:");
         while let Ok(Some(entry)) = entries.next_entry().await {
             let name = entry.file_name();
             if let Some(name_str) = name.to_str() {
                 if name_str.contains("checkpoint") {
-                    println!("  - {}", name_str);
+                    styled_println!("  - {}", name_str);
                 }
             }
         }
     }
 
-    println!(
+    styled_println!(
         "\n🔍 Loading checkpoint and resuming execution for job: {}",
         job_id
     );
 
     // Execute the actual resume logic
-    execute_mapreduce_resume(&job_id, _force, _max_retries, job_dir).await
+    execute_mapreduce_resume(
+        &job_id,
+        _force,
+        _max_retries,
+        overrides,
+        job_dir,
+        relocate.as_deref(),
+    )
+    .await
 }
 
 /// Execute MapReduce resume with full checkpoint loading and execution
@@ -707,7 +844,9 @@ async fn execute_mapreduce_resume(
     job_id: &str,
     force: bool,
     max_retries: u32,
+    overrides: ResumeOverrides,
     job_dir: PathBuf,
+    relocate: Option<&Path>,
 ) -> Result<()> {
     use crate::cook::execution::events::{EventLogger, JsonlEventWriter};
     use crate::cook::execution::mapreduce_resume::{EnhancedResumeOptions, MapReduceResumeManager};
@@ -731,12 +870,40 @@ async fn execute_mapreduce_resume(
 
     // Load the checkpoint to get the parent worktree path
     let state_manager = Arc::new(DefaultJobStateManager::new(state_dir.to_path_buf()));
-    let checkpoint = state_manager
+    let mut checkpoint = state_manager
         .checkpoint_manager
         .load_checkpoint(job_id)
         .await
         .context("Failed to load checkpoint")?;
 
+    if let Some(new_path) = relocate {
+        let parent_worktree = checkpoint.parent_worktree.as_deref().ok_or_else(|| {
+            anyhow!(
+                "Job {} records no parent worktree, so it cannot be relocated",
+                job_id
+            )
+        })?;
+        let relocation = crate::cook::workflow::relocate::relocate_job(
+            Path::new(parent_worktree),
+            &job_dir,
+            new_path,
+        )
+        .await
+        .context("Failed to relocate job")?;
+        styled_println!(
+            "Relocated job from {} to {} ({} paths in {} files)",
+            relocation.old_root.display(),
+            relocation.new_root.display(),
+            relocation.paths,
+            relocation.files
+        );
+        checkpoint = state_manager
+            .checkpoint_manager
+            .load_checkpoint(job_id)
+            .await
+            .context("Failed to load relocated checkpoint")?;
+    }
+
     // Determine the working directory from the checkpoint's parent worktree
     let working_dir = if let Some(parent_worktree) = &checkpoint.parent_worktree {
         PathBuf::from(parent_worktree)
@@ -781,14 +948,23 @@ async fn execute_mapreduce_resume(
         })?
     };
 
-    println!("📂 Working directory: {}", working_dir.display());
-    println!("📊 Job has {} total items", checkpoint.total_items);
-    println!("✅ Completed: {}", checkpoint.successful_count);
-    println!("❌ Failed: {}", checkpoint.failed_count);
-    println!(
+    styled_println!("📂 Working directory: {}", working_dir.display());
+    styled_println!("📊 Job has {} total items", checkpoint.total_items);
+    styled_println!("✅ Completed: {}", checkpoint.successful_count);
+    styled_println!("❌ Failed: {}", checkpoint.failed_count);
+    styled_println!(
         "⏳ Remaining: {}",
         checkpoint.total_items - checkpoint.successful_count - checkpoint.failed_count
     );
+    if let Some(max_parallel) = overrides.max_parallel {
+        styled_println!("⚙️  Max parallel: {}", max_parallel);
+    }
+    if let Some(max_items) = overrides.max_items {
+        styled_println!("⚙️  Max items this run: {}", max_items);
+    }
+    if let Some(timeout) = overrides.agent_timeout_secs {
+        styled_println!("⚙️  Item timeout: {}s", timeout);
+    }
 
     // Create event logger
     let events_dir = job_dir.join("events");
@@ -819,7 +995,9 @@ async fn execute_mapreduce_resume(
         max_additional_retries: max_retries,
         skip_validation: false,
         from_checkpoint: None,
-        max_parallel: None,
+        max_parallel: overrides.max_parallel,
+        max_items: overrides.max_items,
+        agent_timeout_secs: overrides.agent_timeout_secs,
         force_recreation: false,
         include_dlq_items: true,
         validate_environment: true,
@@ -832,9 +1010,10 @@ async fn execute_mapreduce_resume(
         project_dir: Arc::new(working_dir.clone()),
         worktree_name: None,
         session_id: Arc::from(job_id),
+        package: None,
     };
 
-    println!("\n🚀 Starting resume execution...\n");
+    styled_println!("\n🚀 Starting resume execution...\n");
 
     // Resume the job
     let result = resume_manager
@@ -854,38 +1033,38 @@ fn display_resume_summary(
 ) -> Result<()> {
     use crate::cook::execution::mapreduce_resume::EnhancedResumeResult;
 
-    println!("\n");
-    println!("═══════════════════════════════════════════════");
-    println!("           MapReduce Resume Summary");
-    println!("═══════════════════════════════════════════════");
+    styled_println!("\n");
+    styled_println!("═══════════════════════════════════════════════");
+    styled_println!("           MapReduce Resume Summary");
+    styled_println!("═══════════════════════════════════════════════");
 
     match result {
         EnhancedResumeResult::FullWorkflowCompleted(full_result) => {
-            println!("\n✅ Workflow completed successfully!");
-            println!("\nMap Phase:");
-            println!("  • Total items: {}", full_result.map_result.total);
-            println!("  • Successful: {}", full_result.map_result.successful);
-            println!("  • Failed: {}", full_result.map_result.failed);
+            styled_println!("\n✅ Workflow completed successfully!");
+            styled_println!("\nMap Phase:");
+            styled_println!("  • Total items: {}", full_result.map_result.total);
+            styled_println!("  • Successful: {}", full_result.map_result.successful);
+            styled_println!("  • Failed: {}", full_result.map_result.failed);
 
             if let Some(reduce_result) = &full_result.reduce_result {
-                println!("\nReduce Phase:");
-                println!("  • Output: {}", reduce_result);
+                styled_println!("\nReduce Phase:");
+                styled_println!("  • Output: {}", reduce_result);
             }
         }
         EnhancedResumeResult::MapOnlyCompleted(map_result) => {
-            println!("\n✅ Map phase completed!");
-            println!("\nResults:");
-            println!("  • Total items: {}", map_result.total);
-            println!("  • Successful: {}", map_result.successful);
-            println!("  • Failed: {}", map_result.failed);
-            println!("\n⚠️  Note: No reduce phase defined in workflow");
+            styled_println!("\n✅ Map phase completed!");
+            styled_println!("\nResults:");
+            styled_println!("  • Total items: {}", map_result.total);
+            styled_println!("  • Successful: {}", map_result.successful);
+            styled_println!("  • Failed: {}", map_result.failed);
+            styled_println!("\n⚠️  Note: No reduce phase defined in workflow");
         }
         EnhancedResumeResult::PartialResume { phase, progress } => {
-            println!("\n⚠️  Partial resume (interrupted)");
-            println!("\nStatus:");
-            println!("  • Phase: {:?}", phase);
-            println!("  • Progress: {:.1}%", progress * 100.0);
-            println!("\n💡 Run 'prodigy resume-job <job_id>' again to continue");
+            styled_println!("\n⚠️  Partial resume (interrupted)");
+            styled_println!("\nStatus:");
+            styled_println!("  • Phase: {:?}", phase);
+            styled_println!("  • Progress: {:.1}%", progress * 100.0);
+            styled_println!("\n💡 Run 'prodigy resume-job <job_id>' again to continue");
         }
         EnhancedResumeResult::ReadyToExecute {
             phase,
@@ -893,17 +1072,17 @@ fn display_resume_summary(
             state,
             ..
         } => {
-            println!("\n⚠️  Resume prepared but not executed");
-            println!("\nStatus:");
-            println!("  • Phase: {:?}", phase);
-            println!("  • Remaining items: {}", remaining_items.len());
-            println!("  • Completed: {}", state.completed_agents.len());
-            println!("\n💡 Note: This indicates the resume manager prepared the state but did not execute");
-            println!("         This may occur if execution was not triggered properly");
+            styled_println!("\n⚠️  Resume prepared but not executed");
+            styled_println!("\nStatus:");
+            styled_println!("  • Phase: {:?}", phase);
+            styled_println!("  • Remaining items: {}", remaining_items.len());
+            styled_println!("  • Completed: {}", state.completed_agents.len());
+            styled_println!("\n💡 Note: This indicates the resume manager prepared the state but did not execute");
+            styled_println!("         This may occur if execution was not triggered properly");
         }
     }
 
-    println!("\n═══════════════════════════════════════════════\n");
+    styled_println!("\n═══════════════════════════════════════════════\n");
 
     Ok(())
 }
@@ -0,0 +1,130 @@
+//! SARIF command implementation
+//!
+//! Exports findings from a MapReduce job's work items or a captured JSON
+//! artifact as SARIF for code scanning upload.
+
+use super::jobs::load_job_state;
+use crate::cook::execution::job_compare::collect_item_runs;
+use crate::cook::execution::mapreduce::item_key::positional_item_id;
+use crate::cook::execution::sarif::{build_sarif_log, extract_finding, Finding, SarifFieldMap};
+use crate::cook::execution::variable_capture::extract_json_path;
+use anyhow::{anyhow, Context, Result};
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+
+/// Where findings are read from
+pub enum FindingSource {
+    /// Work items of a MapReduce job
+    Job(String),
+    /// JSON file, optionally with a path to the findings array
+    File {
+        path: PathBuf,
+        json_path: Option<String>,
+    },
+}
+
+/// Export findings as a SARIF log
+pub async fn run_sarif_command(
+    source: FindingSource,
+    fields: SarifFieldMap,
+    output: Option<PathBuf>,
+    tool_name: &str,
+) -> Result<()> {
+    let (findings, skipped, default_output) = match &source {
+        FindingSource::Job(job_id) => {
+            let (findings, skipped) = job_findings(job_id, &fields).await?;
+            (findings, skipped, format!("{}.sarif", job_id))
+        }
+        FindingSource::File { path, json_path } => {
+            let items = read_items(path, json_path.as_deref()).await?;
+            let findings: Vec<Finding> = items
+                .iter()
+                .filter_map(|item| extract_finding(item, &fields))
+                .collect();
+            let skipped = items.len() - findings.len();
+            (findings, skipped, "findings.sarif".to_string())
+        }
+    };
+
+    let repo_root = git2::Repository::discover(".")
+        .ok()
+        .and_then(|repo| repo.workdir().map(|dir| dir.to_path_buf()));
+    let log = build_sarif_log(
+        &findings,
+        tool_name,
+        env!("CARGO_PKG_VERSION"),
+        repo_root.as_deref(),
+    );
+
+    let output = output.unwrap_or_else(|| PathBuf::from(default_output));
+    tokio::fs::write(&output, serde_json::to_string_pretty(&log)?)
+        .await
+        .with_context(|| format!("Failed to write {}", output.display()))?;
+
+    println!(
+        "Wrote {} findings to {}{}",
+        findings.len(),
+        output.display(),
+        if skipped > 0 {
+            format!(" ({} items without a file location skipped)", skipped)
+        } else {
+            String::new()
+        }
+    );
+    Ok(())
+}
+
+/// Findings from a job's work items, tagged with each item's outcome
+async fn job_findings(job_id: &str, fields: &SarifFieldMap) -> Result<(Vec<Finding>, usize)> {
+    let state = load_job_state(job_id).await?;
+    let runs = collect_item_runs(&state, None);
+
+    let mut findings = Vec::new();
+    for (index, item) in state.work_items.iter().enumerate() {
+        let Some(mut finding) = extract_finding(item, fields) else {
+            continue;
+        };
+        let run = runs
+            .get(&positional_item_id(index))
+            .or_else(|| runs.get(&item.to_string()));
+        finding
+            .properties
+            .insert("prodigyJobId".to_string(), json!(job_id));
+        if let Some(run) = run {
+            finding
+                .properties
+                .insert("prodigyItemId".to_string(), json!(run.item_id));
+            finding
+                .properties
+                .insert("prodigyOutcome".to_string(), json!(run.outcome.label()));
+        }
+        findings.push(finding);
+    }
+    let skipped = state.work_items.len() - findings.len();
+    Ok((findings, skipped))
+}
+
+/// Read the findings array from a JSON file
+async fn read_items(path: &Path, json_path: Option<&str>) -> Result<Vec<Value>> {
+    let content = tokio::fs::read_to_string(path)
+        .await
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let value: Value = serde_json::from_str(&content)
+        .with_context(|| format!("{} is not valid JSON", path.display()))?;
+    let value = match json_path {
+        Some(json_path) => extract_json_path(&value, json_path.trim_start_matches("$."))
+            .ok_or_else(|| anyhow!("Path '{}' not found in {}", json_path, path.display()))?,
+        None => value,
+    };
+    match value {
+        Value::Array(items) => Ok(items),
+        Value::Object(_) => Ok(vec![value]),
+        _ => Err(anyhow!(
+            "Expected an array of findings in {}{}",
+            path.display(),
+            json_path
+                .map(|p| format!(" at '{}'", p))
+                .unwrap_or_default()
+        )),
+    }
+}
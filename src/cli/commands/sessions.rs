@@ -3,21 +3,22 @@
 //! This module handles session management commands.
 
 use crate::cli::args::SessionCommands;
-use anyhow::Result;
+use crate::cook::execution::{determinism, step_profile};
+use crate::cook::interaction::answers;
+use crate::cook::workflow::{failure_report, score_gate, step_audit};
+use crate::unified_session::heartbeat::{self, Heartbeat};
+use crate::unified_session::{
+    bundle, EnvironmentSnapshot, SessionId, SessionManager, SessionSummary, UnifiedSession,
+};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use std::path::{Path, PathBuf};
 
 /// Execute session-related commands
 pub async fn run_sessions_command(command: SessionCommands) -> Result<()> {
     match command {
-        SessionCommands::List => {
-            println!("Listing resumable sessions...");
-            Ok(())
-        }
-        SessionCommands::Show {
-            session_id: _session_id,
-        } => {
-            println!("Showing session details...");
-            Ok(())
-        }
+        SessionCommands::List => list_sessions().await,
+        SessionCommands::Show { session_id } => show_session(&session_id).await,
         SessionCommands::Clean {
             all: _all,
             force: _force,
@@ -25,5 +26,472 @@ pub async fn run_sessions_command(command: SessionCommands) -> Result<()> {
             println!("Cleaning up old sessions...");
             Ok(())
         }
+        SessionCommands::Export { session_id, output } => export_session(&session_id, output).await,
+        SessionCommands::Import { bundle, output } => import_session(&bundle, output).await,
+        SessionCommands::Answers { session_id, output } => {
+            write_session_answers(&session_id, output).await
+        }
+    }
+}
+
+/// Write a redacted bundle of everything stored for a session
+async fn export_session(session_id: &str, output: Option<PathBuf>) -> Result<()> {
+    let storage =
+        crate::storage::GlobalStorage::new().context("Failed to create global storage")?;
+    let base_dir = storage.base_dir().to_path_buf();
+    let output = output.unwrap_or_else(|| PathBuf::from(format!("{}.tar.zst", session_id)));
+    let session_id = session_id.to_string();
+
+    let (manifest, output) = tokio::task::spawn_blocking(move || {
+        bundle::export_bundle(
+            &base_dir,
+            &session_id,
+            &output,
+            &bundle::Redactor::from_env(),
+        )
+        .map(|manifest| (manifest, output))
+    })
+    .await??;
+
+    println!(
+        "Exported session {} to {}",
+        manifest.session_id,
+        output.display()
+    );
+    println!(
+        "  {} files, {} values redacted",
+        manifest.files.len(),
+        manifest.redactions
+    );
+    for skipped in &manifest.skipped {
+        println!("  skipped {}", skipped);
+    }
+    Ok(())
+}
+
+/// Unpack a session bundle and summarize its contents
+async fn import_session(bundle_path: &Path, output: Option<PathBuf>) -> Result<()> {
+    let manifest = bundle::read_manifest(bundle_path)?;
+    let dest = match output {
+        Some(dest) => dest,
+        None => crate::storage::GlobalStorage::new()
+            .context("Failed to create global storage")?
+            .base_dir()
+            .join("imported")
+            .join(&manifest.session_id),
+    };
+    let bundle_path = bundle_path.to_path_buf();
+    let unpack_dest = dest.clone();
+    tokio::task::spawn_blocking(move || bundle::import_bundle(&bundle_path, &unpack_dest))
+        .await??;
+
+    for line in format_bundle_summary(&manifest, &dest) {
+        println!("{}", line);
+    }
+    let session_file = dest
+        .join("sessions")
+        .join(format!("{}.json", manifest.session_id));
+    if let Ok(content) = tokio::fs::read_to_string(&session_file).await {
+        if let Ok(session) = serde_json::from_str::<UnifiedSession>(&content) {
+            println!();
+            for line in format_session_details(&session) {
+                println!("{}", line);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Lines describing an imported bundle
+fn format_bundle_summary(manifest: &bundle::BundleManifest, dest: &Path) -> Vec<String> {
+    let count = |prefix: &str| {
+        manifest
+            .files
+            .iter()
+            .filter(|f| f.starts_with(prefix))
+            .count()
+    };
+    let mut lines = vec![
+        format!(
+            "Imported session {} to {}",
+            manifest.session_id,
+            dest.display()
+        ),
+        format!(
+            "Exported: {} by prodigy {}",
+            manifest.created_at.format("%Y-%m-%d %H:%M:%S UTC"),
+            manifest.prodigy_version
+        ),
+    ];
+    if !manifest.job_ids.is_empty() {
+        lines.push(format!("Jobs: {}", manifest.job_ids.join(", ")));
+    }
+    lines.push(format!(
+        "Files: {} ({} state, {} events, {} DLQ, {} logs)",
+        manifest.files.len(),
+        count("state/") + count("worktrees/"),
+        count("events/"),
+        count("dlq/"),
+        count("logs/") + count("claude-logs/")
+    ));
+    lines.push(format!("Redacted values: {}", manifest.redactions));
+    lines
+}
+
+/// List sessions, most recently updated first
+async fn list_sessions() -> Result<()> {
+    let storage =
+        crate::storage::GlobalStorage::new().context("Failed to create global storage")?;
+    let manager = SessionManager::new(storage)
+        .await
+        .context("Failed to create session manager")?;
+    let mut sessions = manager.list_sessions(None).await?;
+    sessions.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+
+    for line in format_session_list(&sessions, Utc::now()) {
+        println!("{}", line);
+    }
+    Ok(())
+}
+
+/// Table of sessions with running sessions' staleness
+fn format_session_list(sessions: &[SessionSummary], now: DateTime<Utc>) -> Vec<String> {
+    if sessions.is_empty() {
+        return vec!["No sessions found.".to_string()];
+    }
+
+    let mut lines = vec![format!(
+        "{:<45} {:<10} {:<22} {:<20}",
+        "Session", "Type", "Status", "Updated"
+    )];
+    lines.extend(sessions.iter().map(|session| {
+        let liveness =
+            heartbeat::liveness(&session.status, &session.metadata, session.updated_at, now);
+        format!(
+            "{:<45} {:<10} {:<22} {:<20}",
+            session.id.as_str(),
+            format!("{:?}", session.session_type),
+            heartbeat::format_status(&session.status, &liveness),
+            session.updated_at.format("%Y-%m-%d %H:%M:%S")
+        )
+    }));
+    lines
+}
+
+/// Load a session from global storage
+async fn load_session(session_id: &str) -> Result<UnifiedSession> {
+    let storage =
+        crate::storage::GlobalStorage::new().context("Failed to create global storage")?;
+    let manager = SessionManager::new(storage)
+        .await
+        .context("Failed to create session manager")?;
+    manager
+        .load_session(&SessionId::from_string(session_id.to_string()))
+        .await
+        .with_context(|| format!("Session not found: {}", session_id))
+}
+
+/// Display details for a single session
+async fn show_session(session_id: &str) -> Result<()> {
+    let session = load_session(session_id).await?;
+    for line in format_session_details(&session) {
+        println!("{}", line);
+    }
+    Ok(())
+}
+
+/// Write the prompts answered in a session as an answers file
+async fn write_session_answers(session_id: &str, output: Option<PathBuf>) -> Result<()> {
+    let session = load_session(session_id).await?;
+    let recorded = answers::from_metadata(&session.metadata).unwrap_or_default();
+    if recorded.is_empty() {
+        anyhow::bail!("Session {} has no recorded prompt answers", session_id);
+    }
+    let content = answers::render_answers_file(&recorded)?;
+    match output {
+        Some(path) => {
+            std::fs::write(&path, content)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            println!(
+                "Wrote {} answer(s) from session {} to {}",
+                recorded.len(),
+                session_id,
+                path.display()
+            );
+        }
+        None => print!("{}", content),
+    }
+    Ok(())
+}
+
+/// Lines describing a session and its environment snapshot
+fn format_session_details(session: &UnifiedSession) -> Vec<String> {
+    let mut lines = vec![
+        format!("Session: {}", session.id.as_str()),
+        format!("Type: {:?}", session.session_type),
+        format!("Status: {:?}", session.status),
+        format!(
+            "Started: {}",
+            session.started_at.format("%Y-%m-%d %H:%M:%S UTC")
+        ),
+    ];
+    if let Some(completed_at) = session.completed_at {
+        lines.push(format!(
+            "Completed: {}",
+            completed_at.format("%Y-%m-%d %H:%M:%S UTC")
+        ));
+    }
+    if let Some(beat) = Heartbeat::from_metadata(&session.metadata) {
+        lines.push(format!(
+            "Last heartbeat: {} (process {} on {})",
+            beat.at.format("%Y-%m-%d %H:%M:%S UTC"),
+            beat.pid,
+            beat.hostname
+        ));
+    }
+    if let Some(seed) = determinism::from_metadata(&session.metadata) {
+        lines.push(format!("Seed: {} (rerun with --seed {})", seed, seed));
+    }
+    if let Some(error) = &session.error {
+        lines.push(format!("Error: {}", error));
+    }
+
+    lines.push(String::new());
+    match EnvironmentSnapshot::from_metadata(&session.metadata) {
+        Some(snapshot) => {
+            lines.push("Environment:".to_string());
+            lines.extend(
+                snapshot
+                    .display_lines()
+                    .into_iter()
+                    .map(|l| format!("  {}", l)),
+            );
+        }
+        None => lines.push("Environment: not recorded".to_string()),
+    }
+    if let Some(steps) = step_profile::from_metadata(&session.metadata) {
+        lines.push(String::new());
+        lines.push("Step profile (slowest first):".to_string());
+        lines.extend(
+            step_profile::format_profile(&steps)
+                .into_iter()
+                .map(|l| format!("  {}", l)),
+        );
+    }
+    if let Some(steps) = step_audit::from_metadata(&session.metadata) {
+        lines.push(String::new());
+        lines.push("Step changes:".to_string());
+        lines.extend(
+            step_audit::format_audit(&steps)
+                .into_iter()
+                .map(|l| format!("  {}", l)),
+        );
+    }
+    if let Some(recorded) = answers::from_metadata(&session.metadata) {
+        lines.push(String::new());
+        lines.push(format!(
+            "Prompts answered (export with `prodigy sessions answers {}`):",
+            session.id.as_str()
+        ));
+        lines.extend(
+            answers::format_answers(&recorded)
+                .into_iter()
+                .map(|l| format!("  {}", l)),
+        );
+    }
+    if let Some(scores) = score_gate::from_metadata(&session.metadata) {
+        lines.push(String::new());
+        lines.push("Health scores:".to_string());
+        lines.extend(
+            score_gate::format_scores(&scores)
+                .into_iter()
+                .map(|l| format!("  {}", l)),
+        );
+    }
+    if let Some(report) = failure_report::from_metadata(&session.metadata) {
+        lines.push(String::new());
+        lines.push("Failure:".to_string());
+        lines.extend(
+            failure_report::format_report(&report)
+                .into_iter()
+                .map(|l| format!("  {}", l)),
+        );
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::unified_session::environment::ENVIRONMENT_METADATA_KEY;
+
+    #[test]
+    fn test_format_session_details_includes_environment() {
+        let mut session = UnifiedSession::new_workflow("wf-1".to_string(), "demo".to_string());
+        let mut snapshot = EnvironmentSnapshot {
+            os: "linux".to_string(),
+            arch: "x86_64".to_string(),
+            ..Default::default()
+        };
+        snapshot
+            .tools
+            .insert("rustc".to_string(), "rustc 1.80.0".to_string());
+        session.metadata.insert(
+            ENVIRONMENT_METADATA_KEY.to_string(),
+            snapshot.to_metadata_value(),
+        );
+
+        let lines = format_session_details(&session);
+        assert!(lines.contains(&"Environment:".to_string()));
+        assert!(lines.contains(&"  Platform: linux/x86_64".to_string()));
+        assert!(lines.contains(&"  rustc: rustc 1.80.0".to_string()));
+        assert!(lines.contains(&"  node: not installed".to_string()));
+    }
+
+    #[test]
+    fn test_format_bundle_summary_counts_files() {
+        let manifest = bundle::BundleManifest {
+            format_version: bundle::BUNDLE_FORMAT_VERSION,
+            session_id: "session-1".to_string(),
+            job_ids: vec!["job-1".to_string()],
+            created_at: chrono::Utc::now(),
+            prodigy_version: "0.4.4".to_string(),
+            files: vec![
+                "sessions/session-1.json".to_string(),
+                "events/repo/job-1/events.jsonl".to_string(),
+                "dlq/repo/job-1/items/a.json".to_string(),
+                "claude-logs/run.jsonl".to_string(),
+            ],
+            skipped: vec![],
+            redactions: 2,
+        };
+        let lines = format_bundle_summary(&manifest, Path::new("/tmp/imported"));
+        assert!(lines.contains(&"Jobs: job-1".to_string()));
+        assert!(lines.contains(&"Files: 4 (0 state, 1 events, 1 DLQ, 1 logs)".to_string()));
+        assert!(lines.contains(&"Redacted values: 2".to_string()));
+    }
+
+    #[test]
+    fn test_format_session_details_includes_step_profile() {
+        let mut session = UnifiedSession::new_workflow("wf-1".to_string(), "demo".to_string());
+        let steps = vec![step_profile::StepProfile {
+            step: "shell: cargo test".to_string(),
+            wall_secs: 90.0,
+            cpu_secs: 240.0,
+            peak_rss_bytes: 0,
+            success: true,
+        }];
+        session.metadata.insert(
+            step_profile::STEP_PROFILE_METADATA_KEY.to_string(),
+            step_profile::to_metadata_value(&steps),
+        );
+
+        let lines = format_session_details(&session);
+        assert!(lines.contains(&"Step profile (slowest first):".to_string()));
+        let row = lines.last().unwrap();
+        assert!(row.contains("1m 30s"));
+        assert!(row.contains("4m 0s"));
+        assert!(row.ends_with("100.0%  shell: cargo test"));
+    }
+
+    #[test]
+    fn test_format_session_details_includes_prompt_answers() {
+        let mut session = UnifiedSession::new_workflow("wf-1".to_string(), "demo".to_string());
+        let recorded = vec![answers::RecordedAnswer {
+            kind: crate::cook::interaction::PromptKind::MergeConfirmation,
+            prompt: "Merge session-1 to main".to_string(),
+            answer: answers::Answer::Confirm(false),
+        }];
+        session.metadata.insert(
+            answers::ANSWERS_METADATA_KEY.to_string(),
+            answers::to_metadata_value(&recorded),
+        );
+
+        let lines = format_session_details(&session);
+        assert!(lines
+            .iter()
+            .any(|l| l.starts_with("Prompts answered (export with `prodigy sessions answers")));
+        assert_eq!(lines.last().unwrap(), "  Merge session-1 to main -> no");
+    }
+
+    #[test]
+    fn test_format_session_details_includes_step_changes() {
+        let mut session = UnifiedSession::new_workflow("wf-1".to_string(), "demo".to_string());
+        let steps = vec![step_audit::StepAudit {
+            step_index: 1,
+            step: "claude: /fix".to_string(),
+            changes: crate::cook::workflow::StepChanges {
+                files_added: vec!["src/new.rs".to_string()],
+                commits: vec!["abc123".to_string()],
+                insertions: 40,
+                ..Default::default()
+            },
+        }];
+        session.metadata.insert(
+            step_audit::STEP_AUDIT_METADATA_KEY.to_string(),
+            step_audit::to_metadata_value(&steps),
+        );
+
+        let lines = format_session_details(&session);
+        assert!(lines.contains(&"Step changes:".to_string()));
+        assert_eq!(
+            lines.last().unwrap(),
+            "  [1] claude: /fix: 1 file changed (+1 ~0 -0), +40 -0 lines, 1 commit"
+        );
+    }
+
+    #[test]
+    fn test_format_session_details_includes_failure() {
+        let mut session = UnifiedSession::new_workflow("wf-1".to_string(), "demo".to_string());
+        let mut report = failure_report::FailureReport::new(
+            "wf-1",
+            &anyhow::anyhow!("Step 'shell: make' failed with exit code 2"),
+            None,
+        );
+        report.resume_command = Some("prodigy resume wf-1".to_string());
+        session.metadata.insert(
+            failure_report::FAILURE_METADATA_KEY.to_string(),
+            failure_report::to_metadata_value(&report),
+        );
+
+        let lines = format_session_details(&session);
+        assert!(lines.contains(&"Failure:".to_string()));
+        assert_eq!(lines.last().unwrap(), "  Resume with: prodigy resume wf-1");
+    }
+
+    #[test]
+    fn test_format_session_details_without_environment() {
+        let session = UnifiedSession::new_workflow("wf-1".to_string(), "demo".to_string());
+        let lines = format_session_details(&session);
+        assert_eq!(lines.last().unwrap(), "Environment: not recorded");
+    }
+
+    #[test]
+    fn test_format_session_list_shows_staleness() {
+        let now = Utc::now();
+        let mut stale = UnifiedSession::new_workflow("wf-stale".to_string(), "demo".to_string());
+        stale.status = crate::unified_session::SessionStatus::Running;
+        stale.metadata.insert(
+            heartbeat::HEARTBEAT_METADATA_KEY.to_string(),
+            Heartbeat {
+                at: now - chrono::Duration::hours(2),
+                pid: 1,
+                hostname: "host".to_string(),
+            }
+            .to_metadata_value(),
+        );
+        let mut done = UnifiedSession::new_workflow("wf-done".to_string(), "demo".to_string());
+        done.status = crate::unified_session::SessionStatus::Completed;
+
+        let lines = format_session_list(&[stale.to_summary(), done.to_summary()], now);
+
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].starts_with("wf-stale"));
+        assert!(lines[1].contains("running (stale 2h)"));
+        assert!(lines[2].contains("completed"));
+        assert_eq!(
+            format_session_list(&[], now),
+            vec!["No sessions found.".to_string()]
+        );
     }
 }
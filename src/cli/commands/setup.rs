@@ -0,0 +1,424 @@
+//! First-run setup wizard.
+//!
+//! `prodigy setup` walks through onboarding in one command: it installs the
+//! Prodigy Claude commands into the project, chooses the storage directory and
+//! the default MapReduce parallelism, checks that the Claude CLI answers a
+//! prompt, and writes the answers to the global config (`~/.prodigy/config.yml`).
+
+use crate::{styled_print, styled_println};
+use anyhow::{anyhow, Context, Result};
+use serde_yaml::{Mapping, Value};
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::config::{global_config_path, load_prodigy_config, ProdigyConfig};
+use crate::cook::execution::ClaudeCapabilities;
+use crate::init::command::InitCommand;
+use crate::init::templates;
+use crate::subprocess::{ProcessCommandBuilder, SubprocessManager};
+
+/// Parallelism used by MapReduce workflows when neither they nor the config set one
+const BUILTIN_MAX_PARALLEL: usize = 10;
+
+/// Prompt sent to check that the Claude CLI is logged in and responding
+const CLAUDE_TEST_PROMPT: &str = "Reply with the single word OK.";
+
+/// How long the test prompt may take
+const CLAUDE_TEST_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Options for `prodigy setup`
+#[derive(Debug, Clone, Default)]
+pub struct SetupOptions {
+    /// Accept defaults instead of prompting
+    pub yes: bool,
+    /// Storage directory, skipping the prompt
+    pub storage_dir: Option<PathBuf>,
+    /// Default MapReduce parallelism, skipping the prompt
+    pub max_parallel: Option<usize>,
+    /// Skip the Claude CLI test prompt
+    pub skip_claude_check: bool,
+    /// Project to install commands into (defaults to the current directory)
+    pub path: Option<PathBuf>,
+}
+
+/// Execute `prodigy setup`.
+pub async fn run_setup_command(options: SetupOptions) -> Result<()> {
+    let interactive = !options.yes && io::stdin().is_terminal();
+    let project = match &options.path {
+        Some(path) => path.clone(),
+        None => std::env::current_dir()?,
+    };
+    let current = load_prodigy_config()
+        .map(|config| config.into_inner())
+        .unwrap_or_default();
+
+    styled_println!("🚀 Prodigy setup");
+
+    println!("\n[1/4] Claude commands");
+    setup_commands(&project, interactive).await;
+
+    println!("\n[2/4] Storage directory");
+    let builtin_storage = builtin_storage_dir()?;
+    let storage_dir = match options.storage_dir {
+        Some(dir) => expand_home(&dir)?,
+        None => {
+            let default = current
+                .storage
+                .base_path
+                .clone()
+                .unwrap_or_else(|| builtin_storage.clone());
+            prompt_until(
+                interactive,
+                "Where should Prodigy keep sessions, events and worktrees?",
+                &default.display().to_string(),
+                |answer| expand_home(Path::new(answer)),
+            )?
+        }
+    };
+    styled_println!("  📁 {}", storage_dir.display());
+    if std::env::var_os("PRODIGY_HOME").is_some() {
+        styled_println!("  ⚠️  PRODIGY_HOME is set and takes precedence over this setting.");
+    }
+
+    println!("\n[3/4] Default parallelism");
+    let max_parallel = match options.max_parallel {
+        Some(value) => validate_max_parallel(value)?,
+        None => prompt_until(
+            interactive,
+            "How many agents should MapReduce workflows run at once by default?",
+            &current
+                .default_max_parallel
+                .unwrap_or(BUILTIN_MAX_PARALLEL)
+                .to_string(),
+            |answer| {
+                answer
+                    .parse::<usize>()
+                    .map_err(|_| anyhow!("'{}' is not a number", answer))
+                    .and_then(validate_max_parallel)
+            },
+        )?,
+    };
+    styled_println!("  ⚙️  {} parallel agents", max_parallel);
+
+    println!("\n[4/4] Claude CLI");
+    let claude_ok = if options.skip_claude_check {
+        styled_println!("  ⏭️  Skipped");
+        true
+    } else {
+        check_claude_cli(&SubprocessManager::production()).await
+    };
+
+    let config_path = global_config_path();
+    let existing = match std::fs::read_to_string(&config_path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(e) => {
+            return Err(e).with_context(|| format!("Failed to read {}", config_path.display()))
+        }
+    };
+    let storage_setting = (storage_dir != builtin_storage).then_some(storage_dir.as_path());
+    let updated = update_global_config(&existing, storage_setting, max_parallel)?;
+    if let Some(parent) = config_path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    std::fs::write(&config_path, updated)
+        .with_context(|| format!("Failed to write {}", config_path.display()))?;
+    styled_println!("\n✅ Saved settings to {}", config_path.display());
+
+    if claude_ok {
+        println!("\nNext steps:");
+        println!("  prodigy run workflows/<workflow>.yml   Run a workflow");
+        println!("  prodigy config show                    Review the effective configuration");
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "Setup saved your settings, but the Claude CLI check failed. \
+             Fix the problem above and run `prodigy setup` again."
+        ))
+    }
+}
+
+/// Offer to install the Prodigy commands missing from the project
+async fn setup_commands(project: &Path, interactive: bool) {
+    let commands_dir = project.join(".claude").join("commands");
+    let missing: Vec<String> = templates::get_all_templates()
+        .iter()
+        .map(|template| template.name.to_string())
+        .filter(|name| !commands_dir.join(format!("{}.md", name)).exists())
+        .collect();
+
+    if missing.is_empty() {
+        styled_println!(
+            "  ✅ All Prodigy commands are installed in {}",
+            commands_dir.display()
+        );
+        return;
+    }
+
+    let question = format!(
+        "Install {} Prodigy command(s) into {}?",
+        missing.len(),
+        commands_dir.display()
+    );
+    match confirm(interactive, &question, true) {
+        Ok(true) => {}
+        Ok(false) => {
+            styled_println!("  ⏭️  Skipped. Run `prodigy init` later to install them.");
+            return;
+        }
+        Err(e) => {
+            styled_println!("  ⚠️  {}", e);
+            return;
+        }
+    }
+
+    let init = InitCommand {
+        force: false,
+        commands: Some(missing),
+        path: Some(project.to_path_buf()),
+    };
+    if let Err(e) = crate::init::run(init).await {
+        styled_println!("  ⚠️  Could not install commands: {}", e);
+        println!("     Run `prodigy init` in your project once the problem is fixed.");
+    }
+}
+
+/// Check that the Claude CLI is installed, supported and answering prompts
+async fn check_claude_cli(subprocess: &SubprocessManager) -> bool {
+    let version = ProcessCommandBuilder::new("claude")
+        .arg("--version")
+        .timeout(Duration::from_secs(30))
+        .build();
+    let version = match subprocess.runner().run(version).await {
+        Ok(output) if output.status.success() => output.stdout.trim().to_string(),
+        _ => {
+            styled_println!("  ❌ Claude CLI not found");
+            println!("     Install it with: npm install -g @anthropic-ai/claude-code");
+            return false;
+        }
+    };
+    if let Err(e) = ClaudeCapabilities::from_version_output(&version) {
+        styled_println!("  ❌ {}", e);
+        return false;
+    }
+    styled_println!("  ✅ Claude CLI {}", version);
+
+    styled_print!("  ⏳ Sending a test prompt... ");
+    let _ = io::stdout().flush();
+    let prompt = ProcessCommandBuilder::new("claude")
+        .args(["--print", CLAUDE_TEST_PROMPT])
+        .timeout(CLAUDE_TEST_TIMEOUT)
+        .build();
+    match subprocess.runner().run(prompt).await {
+        Ok(output) if output.status.success() && !output.stdout.trim().is_empty() => {
+            styled_println!("✅ Claude responded");
+            true
+        }
+        Ok(output) => {
+            styled_println!("❌ Claude did not respond");
+            let detail = if output.stderr.trim().is_empty() {
+                output.stdout.trim()
+            } else {
+                output.stderr.trim()
+            };
+            if !detail.is_empty() {
+                println!("     {}", detail.lines().last().unwrap_or(detail));
+            }
+            println!("     Run `claude` once to log in, then try again.");
+            false
+        }
+        Err(e) => {
+            styled_println!("❌ {}", e);
+            false
+        }
+    }
+}
+
+/// Merge the wizard's answers into the global config file contents
+///
+/// Other settings in the file are kept. A `storage_dir` of `None` removes
+/// `storage.base_path` so the built-in location is used.
+pub fn update_global_config(
+    existing: &str,
+    storage_dir: Option<&Path>,
+    max_parallel: usize,
+) -> Result<String> {
+    let mut root = parse_config_mapping(existing)?;
+    root.insert(
+        Value::from("default_max_parallel"),
+        Value::from(max_parallel as u64),
+    );
+    set_storage_base_path(&mut root, storage_dir);
+    render_config(root)
+}
+
+/// Set or remove `storage.base_path` in a config file's contents, keeping
+/// every other setting
+pub fn update_storage_base_path(existing: &str, storage_dir: Option<&Path>) -> Result<String> {
+    let mut root = parse_config_mapping(existing)?;
+    set_storage_base_path(&mut root, storage_dir);
+    render_config(root)
+}
+
+fn parse_config_mapping(existing: &str) -> Result<Mapping> {
+    match serde_yaml::from_str::<Value>(existing)
+        .context("The existing config is not valid YAML")?
+    {
+        Value::Mapping(mapping) => Ok(mapping),
+        Value::Null => Ok(Mapping::new()),
+        _ => Err(anyhow!("The existing config is not a YAML mapping")),
+    }
+}
+
+fn set_storage_base_path(root: &mut Mapping, storage_dir: Option<&Path>) {
+    let storage_key = Value::from("storage");
+    let mut storage = match root.remove(&storage_key) {
+        Some(Value::Mapping(mapping)) => mapping,
+        _ => Mapping::new(),
+    };
+    match storage_dir {
+        Some(dir) => {
+            storage.insert(
+                Value::from("base_path"),
+                Value::from(dir.display().to_string()),
+            );
+        }
+        None => {
+            storage.remove(Value::from("base_path"));
+        }
+    }
+    if !storage.is_empty() {
+        root.insert(storage_key, Value::Mapping(storage));
+    }
+}
+
+fn render_config(root: Mapping) -> Result<String> {
+    let updated = serde_yaml::to_string(&Value::Mapping(root))?;
+    serde_yaml::from_str::<ProdigyConfig>(&updated)
+        .context("The updated config is not a valid Prodigy configuration")?;
+    Ok(updated)
+}
+
+fn builtin_storage_dir() -> Result<PathBuf> {
+    dirs::home_dir()
+        .map(|home| home.join(".prodigy"))
+        .ok_or_else(|| anyhow!("Could not determine home directory"))
+}
+
+/// Expand a leading `~` and require an absolute path
+fn expand_home(path: &Path) -> Result<PathBuf> {
+    let expanded = match path.strip_prefix("~") {
+        Ok(rest) => dirs::home_dir()
+            .ok_or_else(|| anyhow!("Could not determine home directory"))?
+            .join(rest),
+        Err(_) => path.to_path_buf(),
+    };
+    if !expanded.is_absolute() {
+        return Err(anyhow!(
+            "Storage directory must be an absolute path: {}",
+            path.display()
+        ));
+    }
+    Ok(expanded)
+}
+
+fn validate_max_parallel(value: usize) -> Result<usize> {
+    if (1..=100).contains(&value) {
+        Ok(value)
+    } else {
+        Err(anyhow!("Parallelism must be between 1 and 100"))
+    }
+}
+
+/// Ask a yes/no question, answering `default` when not interactive
+fn confirm(interactive: bool, question: &str, default: bool) -> Result<bool> {
+    if !interactive {
+        return Ok(default);
+    }
+    let hint = if default { "Y/n" } else { "y/N" };
+    print!("  {} [{}]: ", question, hint);
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    Ok(match input.trim().to_lowercase().as_str() {
+        "" => default,
+        answer => answer == "y" || answer == "yes",
+    })
+}
+
+/// Ask for a value until `parse` accepts it, using `default` when not interactive
+fn prompt_until<T>(
+    interactive: bool,
+    question: &str,
+    default: &str,
+    parse: impl Fn(&str) -> Result<T>,
+) -> Result<T> {
+    if !interactive {
+        return parse(default);
+    }
+    loop {
+        print!("  {} [{}]: ", question, default);
+        io::stdout().flush()?;
+
+        let mut input = String::new();
+        if io::stdin().read_line(&mut input)? == 0 {
+            return parse(default);
+        }
+        let answer = match input.trim() {
+            "" => default,
+            answer => answer,
+        };
+        match parse(answer) {
+            Ok(value) => return Ok(value),
+            Err(e) => styled_println!("  ❌ {}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_global_config_keeps_other_settings() {
+        let existing = "log_level: debug\nstorage:\n  compression_level: 3\n";
+        let updated = update_global_config(existing, Some(Path::new("/data/prodigy")), 6).unwrap();
+        let config: ProdigyConfig = serde_yaml::from_str(&updated).unwrap();
+
+        assert_eq!(config.log_level, "debug");
+        assert_eq!(config.storage.compression_level, 3);
+        assert_eq!(
+            config.storage.base_path,
+            Some(PathBuf::from("/data/prodigy"))
+        );
+        assert_eq!(config.default_max_parallel, Some(6));
+
+        // Choosing the built-in location removes the override
+        let reset = update_global_config(&updated, None, 6).unwrap();
+        let config: ProdigyConfig = serde_yaml::from_str(&reset).unwrap();
+        assert!(config.storage.base_path.is_none());
+        assert_eq!(config.storage.compression_level, 3);
+    }
+
+    #[test]
+    fn test_update_global_config_rejects_invalid_files() {
+        assert!(update_global_config("", None, 4).is_ok());
+        assert!(update_global_config("- not\n- a mapping\n", None, 4).is_err());
+        assert!(update_global_config("log_level: [unclosed", None, 4).is_err());
+    }
+
+    #[test]
+    fn test_non_interactive_prompts_use_defaults() {
+        assert!(confirm(false, "Install?", true).unwrap());
+        let value = prompt_until(false, "How many?", "8", |answer| {
+            answer.parse::<usize>().map_err(Into::into)
+        })
+        .unwrap();
+        assert_eq!(value, 8);
+        assert!(expand_home(Path::new("relative/dir")).is_err());
+        assert!(validate_max_parallel(0).is_err());
+    }
+}
@@ -0,0 +1,93 @@
+//! Storage command implementation
+//!
+//! `prodigy storage migrate <new_path>` moves the storage directory and points
+//! the configuration at its new location.
+
+use crate::cli::args::StorageCommands;
+use crate::cli::commands::setup::update_storage_base_path;
+use crate::config::{global_config_path, project_config_path};
+use crate::storage::migrate::{execute_migration, plan_migration};
+use crate::storage::{get_default_storage_dir, STORAGE_DIR_ENV};
+use crate::styled_println;
+use anyhow::{Context, Result};
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Execute a storage command
+pub async fn run_storage_command(command: StorageCommands) -> Result<()> {
+    match command {
+        StorageCommands::Migrate { new_path, dry_run } => migrate(&new_path, dry_run),
+    }
+}
+
+fn migrate(new_path: &Path, dry_run: bool) -> Result<()> {
+    let current = get_default_storage_dir()?;
+    let plan = plan_migration(&current, new_path)?;
+
+    println!(
+        "Moving storage from {} to {}",
+        plan.from.display(),
+        plan.to.display()
+    );
+    for entry in &plan.entries {
+        println!("  {}", entry);
+    }
+    if dry_run {
+        println!("Dry run: nothing was moved");
+        return Ok(());
+    }
+    if plan.entries.is_empty() {
+        println!("  (nothing to move)");
+    }
+
+    let report = execute_migration(&plan)?;
+    styled_println!("✅ Moved {} file(s), {} bytes", report.files, report.bytes);
+
+    if std::env::var_os("PRODIGY_HOME").is_some() {
+        styled_println!(
+            "⚠️  PRODIGY_HOME is set and takes precedence; point it at {}",
+            plan.to.display()
+        );
+    } else if std::env::var_os(STORAGE_DIR_ENV).is_some() {
+        println!(
+            "Use --storage-dir {0} or {1}={0} from now on",
+            plan.to.display(),
+            STORAGE_DIR_ENV
+        );
+    } else {
+        let config_path = config_to_update();
+        let existing = read_config(&config_path)?;
+        let updated = update_storage_base_path(&existing, Some(&plan.to))?;
+        if let Some(parent) = config_path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        std::fs::write(&config_path, updated)
+            .with_context(|| format!("Failed to write {}", config_path.display()))?;
+        println!("Set storage.base_path in {}", config_path.display());
+    }
+    Ok(())
+}
+
+/// The project config if it already chooses the storage directory, otherwise
+/// the global config
+fn config_to_update() -> PathBuf {
+    let project = project_config_path();
+    let sets_base_path = std::fs::read_to_string(&project)
+        .ok()
+        .and_then(|content| serde_yaml::from_str::<serde_yaml::Value>(&content).ok())
+        .is_some_and(|config| config["storage"]["base_path"].is_string());
+    if sets_base_path {
+        project
+    } else {
+        global_config_path()
+    }
+}
+
+fn read_config(path: &Path) -> Result<String> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => Ok(content),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(String::new()),
+        Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    }
+}
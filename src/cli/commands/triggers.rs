@@ -0,0 +1,53 @@
+//! Trigger command implementation
+//!
+//! `prodigy triggers` manages rules that start workflows when matching events
+//! appear in the repository's global event stream.
+
+use crate::cli::args::TriggerCommands;
+use crate::cook::triggers::{self, Trigger, TriggerStore};
+use anyhow::{bail, Context, Result};
+use std::time::Duration;
+
+/// Execute a triggers command
+pub async fn run_triggers_command(command: TriggerCommands) -> Result<()> {
+    let project_path = std::env::current_dir()?;
+    let store = TriggerStore::for_repo(&project_path).await?;
+
+    match command {
+        TriggerCommands::Add { on, run } => {
+            let run = project_path.join(run);
+            if !run.is_file() {
+                bail!("Workflow file not found: {}", run.display());
+            }
+            let run = run
+                .canonicalize()
+                .with_context(|| format!("Failed to resolve {}", run.display()))?;
+            let trigger = Trigger::new(&on, run)?;
+            store.add(trigger.clone())?;
+            println!("Added trigger {}", trigger.id);
+            println!("Start `prodigy triggers daemon` to run it when events match");
+            Ok(())
+        }
+        TriggerCommands::Ls => {
+            let triggers = store.load()?;
+            if triggers.is_empty() {
+                println!("No triggers");
+            }
+            for trigger in triggers {
+                println!("{}  on: {}", trigger.id, trigger.on);
+                println!("    run: {}", trigger.run.display());
+            }
+            Ok(())
+        }
+        TriggerCommands::Rm { id } => {
+            if !store.remove(&id)? {
+                bail!("No trigger with ID {}", id);
+            }
+            println!("Removed trigger {}", id);
+            Ok(())
+        }
+        TriggerCommands::Daemon { interval } => {
+            triggers::run_daemon(&project_path, Duration::from_secs(interval.max(1))).await
+        }
+    }
+}
@@ -3,22 +3,29 @@
 //! This module handles git worktree management for parallel sessions.
 
 use crate::cli::args::WorktreeCommands;
+use crate::{styled_eprintln, styled_println};
 use anyhow::Result;
 
 use super::age_cleanup::cleanup_old_worktrees;
 use super::mapreduce_cleanup::run_mapreduce_cleanup;
 use super::operations::{
-    list_sessions_operation, merge_all_sessions_operation, merge_session_operation,
+    list_sessions_operation, merge_all_sessions_operation, merge_queue_operation,
+    merge_session_operation, merge_session_verified_operation,
 };
 use super::orphaned_cleanup::run_worktree_clean_orphaned;
-use super::presentation::{format_batch_merge_summary, format_merge_result, format_sessions_table};
+use super::presentation::{
+    format_batch_merge_summary, format_merge_result, format_orphaned_sessions,
+    format_sessions_table,
+};
 use super::utils::parse_duration;
 
 /// Execute worktree-related commands
 pub async fn run_worktree_command(command: WorktreeCommands) -> Result<()> {
     match command {
         WorktreeCommands::Ls { json, detailed } => run_worktree_ls(json, detailed).await,
-        WorktreeCommands::Merge { name, all } => run_worktree_merge(name, all).await,
+        WorktreeCommands::Merge { name, all, verify } => {
+            run_worktree_merge(name, all, verify).await
+        }
         WorktreeCommands::Clean {
             all,
             name,
@@ -46,18 +53,82 @@ pub async fn run_worktree_command(command: WorktreeCommands) -> Result<()> {
             dry_run,
             force,
         } => run_worktree_clean_orphaned(job_id, dry_run, force).await,
+        WorktreeCommands::Recover { name } => run_worktree_recover(name).await,
+    }
+}
+
+/// Mark sessions orphaned by a crash as interrupted and tell the user
+///
+/// Best effort: detection problems are logged and never fail the command.
+pub fn report_orphaned_sessions(manager: &crate::worktree::WorktreeManager) {
+    match manager.mark_orphaned_sessions() {
+        Ok(states) if !states.is_empty() => {
+            let names: Vec<String> = states.into_iter().map(|s| s.worktree_name).collect();
+            styled_eprintln!("{}", format_orphaned_sessions(&names));
+        }
+        Ok(_) => {}
+        Err(e) => tracing::debug!("Failed to check for orphaned worktree sessions: {}", e),
+    }
+}
+
+/// Detect orphaned sessions for the repository at `repo_path`, if it is one
+pub fn report_orphaned_sessions_in(repo_path: &std::path::Path) {
+    use crate::subprocess::SubprocessManager;
+    use crate::worktree::manager::WorktreeManager;
+
+    if !repo_path.join(".git").exists() {
+        return;
+    }
+    if let Ok(manager) =
+        WorktreeManager::new(repo_path.to_path_buf(), SubprocessManager::production())
+    {
+        report_orphaned_sessions(&manager);
     }
 }
 
 /// List active worktrees
-async fn run_worktree_ls(_json: bool, _detailed: bool) -> Result<()> {
+///
+/// `--detailed` adds per-worktree disk usage, the total, and the configured
+/// quota; `--json` prints the detailed list as JSON.
+async fn run_worktree_ls(json: bool, detailed: bool) -> Result<()> {
     use crate::subprocess::SubprocessManager;
+    use crate::worktree::disk_usage::{
+        apply_disk_usage, directory_size, format_size, load_disk_quota, quota_exceeded_message,
+    };
     use crate::worktree::manager::WorktreeManager;
+    use crate::worktree::SessionDisplay;
 
     // Initialize dependencies
     let repo_path = std::env::current_dir()?;
     let subprocess = SubprocessManager::production();
     let manager = WorktreeManager::new(repo_path, subprocess)?;
+    report_orphaned_sessions(&manager);
+
+    if detailed || json {
+        let mut list = manager.list_detailed().await?;
+        if detailed {
+            apply_disk_usage(&mut list);
+        }
+
+        if json {
+            println!("{}", serde_json::to_string_pretty(&list.format_json())?);
+            return Ok(());
+        }
+
+        styled_println!("{}", list.format_verbose());
+        if let Some(quota) = load_disk_quota() {
+            let used = directory_size(manager.worktrees_root());
+            println!(
+                "Quota: {} of {} used across all repositories",
+                format_size(used),
+                format_size(quota.limit_bytes)
+            );
+            if quota.is_exceeded(used) {
+                styled_println!("⚠️  {}", quota_exceeded_message(used, &quota));
+            }
+        }
+        return Ok(());
+    }
 
     // Execute operation
     let result = list_sessions_operation(&manager).await?;
@@ -69,8 +140,42 @@ async fn run_worktree_ls(_json: bool, _detailed: bool) -> Result<()> {
     Ok(())
 }
 
+/// Resume an interrupted session in its existing worktree
+///
+/// Sessions left in progress by a crashed process are accepted as well. The
+/// session is claimed by this process for the resumed run and returned to
+/// interrupted if the resume fails.
+async fn run_worktree_recover(name: String) -> Result<()> {
+    use crate::subprocess::SubprocessManager;
+    use crate::worktree::manager::WorktreeManager;
+
+    let repo_path = std::env::current_dir()?;
+    let manager = WorktreeManager::new(repo_path, SubprocessManager::production())?;
+    let state = manager.claim_for_recovery(&name)?;
+
+    styled_println!(
+        "🔄 Recovering session {} on branch {}",
+        state.worktree_name,
+        state.branch
+    );
+    let result = crate::cli::commands::resume::run_resume_workflow(
+        Some(state.session_id),
+        false,
+        None,
+        None,
+        None,
+    )
+    .await;
+    if result.is_err() {
+        if let Err(e) = manager.release_recovery(&name) {
+            tracing::warn!("Failed to mark session {} interrupted: {}", name, e);
+        }
+    }
+    result
+}
+
 /// Merge worktree changes
-async fn run_worktree_merge(name: Option<String>, all: bool) -> Result<()> {
+async fn run_worktree_merge(name: Option<String>, all: bool, verify: Option<String>) -> Result<()> {
     use crate::subprocess::SubprocessManager;
     use crate::worktree::manager::WorktreeManager;
 
@@ -81,31 +186,44 @@ async fn run_worktree_merge(name: Option<String>, all: bool) -> Result<()> {
 
     if all {
         // Merge all active worktrees
-        println!("Merging all worktrees...");
-        let result = merge_all_sessions_operation(&manager).await?;
+        let result = match verify.as_deref() {
+            Some(command) => {
+                println!("Merging all worktrees, verifying each with: {}", command);
+                merge_queue_operation(&manager, command).await?
+            }
+            None => {
+                println!("Merging all worktrees...");
+                merge_all_sessions_operation(&manager).await?
+            }
+        };
 
         // Display results using presentation layer
         for merge_result in &result.results {
             let message = format_merge_result(merge_result);
             if merge_result.success {
-                println!("{}", message);
+                styled_println!("{}", message);
             } else {
-                eprintln!("{}", message);
+                styled_eprintln!("{}", message);
             }
         }
 
         let summary = format_batch_merge_summary(&result);
         if !summary.is_empty() {
-            println!("{}", summary);
+            styled_println!("{}", summary);
         }
         Ok(())
     } else if let Some(name) = name {
         println!("Merging worktree '{}'...", name);
-        let result = merge_session_operation(&manager, &name).await;
+        let result = match verify.as_deref() {
+            Some(command) => merge_session_verified_operation(&manager, &name, command).await,
+            None => merge_session_operation(&manager, &name).await,
+        };
 
         if result.success {
-            println!("{}", format_merge_result(&result));
+            styled_println!("{}", format_merge_result(&result));
             Ok(())
+        } else if result.verification_failed {
+            Err(anyhow::anyhow!("{}", format_merge_result(&result)))
         } else {
             let error_msg = result.error.unwrap_or_else(|| "Unknown error".to_string());
             Err(anyhow::anyhow!(
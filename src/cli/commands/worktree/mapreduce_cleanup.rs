@@ -4,7 +4,7 @@
 //! separating pure logic from I/O operations.
 
 use anyhow::{Context, Result};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 use crate::cook::execution::mapreduce::cleanup::{
@@ -13,13 +13,10 @@ use crate::cook::execution::mapreduce::cleanup::{
 
 /// Pure function to resolve worktree base path
 ///
-/// Constructs the worktree base path from home directory and repository name.
+/// Constructs the worktree base path from storage directory and repository name.
 /// This is a pure function that can be tested without I/O.
-pub fn resolve_worktree_base_path(home_dir: &str, repo_name: &str) -> PathBuf {
-    PathBuf::from(home_dir)
-        .join(".prodigy")
-        .join("worktrees")
-        .join(repo_name)
+pub fn resolve_worktree_base_path(storage_dir: &Path, repo_name: &str) -> PathBuf {
+    storage_dir.join("worktrees").join(repo_name)
 }
 
 /// Pure function to build cleanup configuration
@@ -33,11 +30,6 @@ pub fn build_cleanup_config(force: bool) -> WorktreeCleanupConfig {
     }
 }
 
-/// Get home directory with fallback
-fn get_home_dir() -> String {
-    std::env::var("HOME").unwrap_or_else(|_| "/tmp".to_string())
-}
-
 /// Get repository name from current directory
 fn get_repo_name() -> Result<String> {
     let current_dir = std::env::current_dir().context("Failed to get current directory")?;
@@ -63,9 +55,9 @@ pub async fn run_mapreduce_cleanup(
     force: bool,
 ) -> Result<()> {
     // Resolve paths
-    let home_dir = get_home_dir();
+    let storage_dir = crate::storage::get_default_storage_dir()?;
     let repo_name = get_repo_name()?;
-    let worktree_base_path = resolve_worktree_base_path(&home_dir, &repo_name);
+    let worktree_base_path = resolve_worktree_base_path(&storage_dir, &repo_name);
 
     // Build configuration
     let config = build_cleanup_config(force);
@@ -145,7 +137,7 @@ mod tests {
 
     #[test]
     fn test_resolve_worktree_base_path() {
-        let path = resolve_worktree_base_path("/home/user", "myrepo");
+        let path = resolve_worktree_base_path(Path::new("/home/user/.prodigy"), "myrepo");
         assert_eq!(path, PathBuf::from("/home/user/.prodigy/worktrees/myrepo"));
     }
 
@@ -146,5 +146,5 @@ mod orphaned_cleanup;
 mod presentation;
 mod utils;
 
-pub use cli::run_worktree_command;
+pub use cli::{report_orphaned_sessions_in, run_worktree_command};
 pub use utils::parse_duration;
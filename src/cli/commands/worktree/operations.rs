@@ -20,6 +20,8 @@ pub struct MergeResult {
     pub session_name: String,
     pub success: bool,
     pub error: Option<String>,
+    /// Merged but rolled back because verification failed
+    pub verification_failed: bool,
 }
 
 /// Result of a batch merge operation
@@ -31,6 +33,19 @@ pub struct BatchMergeResult {
     pub failed_count: usize,
 }
 
+impl BatchMergeResult {
+    /// Aggregate individual merge results
+    pub fn from_results(results: Vec<MergeResult>) -> Self {
+        let merged_count = results.iter().filter(|r| r.success).count();
+        let failed_count = results.len() - merged_count;
+        Self {
+            results,
+            merged_count,
+            failed_count,
+        }
+    }
+}
+
 /// List all active worktree sessions
 ///
 /// This is a pure orchestration function that wraps the manager's list_sessions
@@ -49,11 +64,13 @@ pub async fn merge_session_operation(manager: &WorktreeManager, session_name: &s
             session_name: session_name.to_string(),
             success: true,
             error: None,
+            verification_failed: false,
         },
         Err(e) => MergeResult {
             session_name: session_name.to_string(),
             success: false,
             error: Some(e.to_string()),
+            verification_failed: false,
         },
     }
 }
@@ -70,14 +87,65 @@ pub async fn merge_all_sessions_operation(manager: &WorktreeManager) -> Result<B
         results.push(result);
     }
 
-    let merged_count = results.iter().filter(|r| r.success).count();
-    let failed_count = results.len() - merged_count;
+    Ok(BatchMergeResult::from_results(results))
+}
 
-    Ok(BatchMergeResult {
-        results,
-        merged_count,
-        failed_count,
-    })
+/// Merge a single worktree session, keeping it only if verification passes
+///
+/// Returns a MergeResult with `verification_failed` set when the merge was
+/// rolled back.
+pub async fn merge_session_verified_operation(
+    manager: &WorktreeManager,
+    session_name: &str,
+    verify_command: &str,
+) -> MergeResult {
+    match manager
+        .merge_session_verified(session_name, verify_command)
+        .await
+    {
+        Ok(None) => MergeResult {
+            session_name: session_name.to_string(),
+            success: true,
+            error: None,
+            verification_failed: false,
+        },
+        Ok(Some(output)) => MergeResult {
+            session_name: session_name.to_string(),
+            success: false,
+            error: Some(output),
+            verification_failed: true,
+        },
+        Err(e) => MergeResult {
+            session_name: session_name.to_string(),
+            success: false,
+            error: Some(e.to_string()),
+            verification_failed: false,
+        },
+    }
+}
+
+/// Merge all active worktree sessions through a local merge queue
+///
+/// Sessions are merged one at a time and `verify_command` runs on the
+/// accumulated result after each merge. Sessions that fail verification are
+/// rolled back and skipped, so every later merge builds on verified state.
+pub async fn merge_queue_operation(
+    manager: &WorktreeManager,
+    verify_command: &str,
+) -> Result<BatchMergeResult> {
+    if manager.has_uncommitted_changes().await? {
+        anyhow::bail!("Commit or stash uncommitted changes before merging with --verify");
+    }
+
+    let sessions = manager.list_sessions().await?;
+    let mut results = Vec::new();
+
+    for session in sessions {
+        let result = merge_session_verified_operation(manager, &session.name, verify_command).await;
+        results.push(result);
+    }
+
+    Ok(BatchMergeResult::from_results(results))
 }
 
 /// Clean up a single worktree session
@@ -175,6 +243,7 @@ mod tests {
             session_name: "test-session".to_string(),
             success: true,
             error: None,
+            verification_failed: false,
         };
         assert!(result.success);
         assert!(result.error.is_none());
@@ -186,6 +255,7 @@ mod tests {
             session_name: "test-session".to_string(),
             success: false,
             error: Some("merge failed".to_string()),
+            verification_failed: false,
         };
         assert!(!result.success);
         assert!(result.error.is_some());
@@ -199,16 +269,19 @@ mod tests {
                 session_name: "session1".to_string(),
                 success: true,
                 error: None,
+                verification_failed: false,
             },
             MergeResult {
                 session_name: "session2".to_string(),
                 success: false,
                 error: Some("error".to_string()),
+                verification_failed: false,
             },
             MergeResult {
                 session_name: "session3".to_string(),
                 success: true,
                 error: None,
+                verification_failed: false,
             },
         ];
 
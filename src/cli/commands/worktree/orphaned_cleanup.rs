@@ -3,6 +3,7 @@
 //! This module handles cleanup of orphaned worktrees that failed during cleanup,
 //! separating pure logic from I/O operations.
 
+use crate::{styled_eprintln, styled_println};
 use anyhow::{Context, Result};
 use std::path::{Path, PathBuf};
 
@@ -11,11 +12,8 @@ use crate::cook::execution::mapreduce::coordination::executor::OrphanedWorktree;
 /// Pure function to resolve registry base path
 ///
 /// Constructs the registry path for orphaned worktrees.
-pub fn resolve_registry_base_path(home_dir: &Path, repo_name: &str) -> PathBuf {
-    home_dir
-        .join(".prodigy")
-        .join("orphaned_worktrees")
-        .join(repo_name)
+pub fn resolve_registry_base_path(storage_dir: &Path, repo_name: &str) -> PathBuf {
+    storage_dir.join("orphaned_worktrees").join(repo_name)
 }
 
 /// Pure function to find registry files
@@ -60,8 +58,7 @@ pub async fn run_worktree_clean_orphaned(
     dry_run: bool,
     force: bool,
 ) -> Result<()> {
-    let home_dir =
-        dirs::home_dir().ok_or_else(|| anyhow::anyhow!("Unable to determine home directory"))?;
+    let storage_dir = crate::storage::get_default_storage_dir()?;
     let repo_path = std::env::current_dir()?;
     let repo_name = repo_path
         .file_name()
@@ -69,7 +66,7 @@ pub async fn run_worktree_clean_orphaned(
         .unwrap_or("unknown");
 
     // Resolve registry path
-    let registry_path = resolve_registry_base_path(&home_dir, repo_name);
+    let registry_path = resolve_registry_base_path(&storage_dir, repo_name);
 
     if !registry_path.exists() {
         println!("No orphaned worktrees registry found.");
@@ -116,9 +113,9 @@ pub async fn run_worktree_clean_orphaned(
     // Remove registry file if all cleaned successfully
     if failed == 0 {
         std::fs::remove_file(&registry_file)?;
-        println!("\n✅ Cleaned {} orphaned worktree(s)", cleaned);
+        styled_println!("\n✅ Cleaned {} orphaned worktree(s)", cleaned);
     } else {
-        println!("\n⚠️  Cleaned {} worktree(s), {} failed", cleaned, failed);
+        styled_println!("\n⚠️  Cleaned {} worktree(s), {} failed", cleaned, failed);
     }
 
     Ok(())
@@ -192,16 +189,16 @@ async fn cleanup_orphaned_worktrees(orphaned_worktrees: &[OrphanedWorktree]) ->
         if orphaned.path.exists() {
             match std::fs::remove_dir_all(&orphaned.path) {
                 Ok(_) => {
-                    println!("✅ Cleaned: {}", orphaned.path.display());
+                    styled_println!("✅ Cleaned: {}", orphaned.path.display());
                     cleaned += 1;
                 }
                 Err(e) => {
-                    eprintln!("❌ Failed to clean {}: {}", orphaned.path.display(), e);
+                    styled_eprintln!("❌ Failed to clean {}: {}", orphaned.path.display(), e);
                     failed += 1;
                 }
             }
         } else {
-            println!("⚠️  Already removed: {}", orphaned.path.display());
+            styled_println!("⚠️  Already removed: {}", orphaned.path.display());
             cleaned += 1;
         }
     }
@@ -215,8 +212,8 @@ mod tests {
 
     #[test]
     fn test_resolve_registry_base_path() {
-        let home = PathBuf::from("/home/user");
-        let path = resolve_registry_base_path(&home, "myrepo");
+        let storage = PathBuf::from("/home/user/.prodigy");
+        let path = resolve_registry_base_path(&storage, "myrepo");
         assert_eq!(
             path,
             PathBuf::from("/home/user/.prodigy/orphaned_worktrees/myrepo")
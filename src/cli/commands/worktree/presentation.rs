@@ -40,6 +40,12 @@ pub fn format_sessions_table(sessions: &[WorktreeSession]) -> String {
 pub fn format_merge_result(result: &MergeResult) -> String {
     if result.success {
         format!("✅ Successfully merged worktree '{}'", result.session_name)
+    } else if result.verification_failed {
+        format!(
+            "⏭️  Skipped worktree '{}': verification failed after merging, merge rolled back\n{}",
+            result.session_name,
+            result.error.as_deref().unwrap_or_default()
+        )
     } else {
         format!(
             "❌ Failed to merge worktree '{}': {}",
@@ -55,10 +61,27 @@ pub fn format_merge_result(result: &MergeResult) -> String {
 /// Format batch merge summary
 #[allow(dead_code)] // Used in Phase 5
 pub fn format_batch_merge_summary(result: &BatchMergeResult) -> String {
-    if result.merged_count > 0 {
+    let summary = if result.merged_count > 0 {
         format!("Successfully merged {} worktree(s)", result.merged_count)
     } else {
         "No worktrees were merged.".to_string()
+    };
+
+    let skipped: Vec<&str> = result
+        .results
+        .iter()
+        .filter(|r| r.verification_failed)
+        .map(|r| r.session_name.as_str())
+        .collect();
+    if skipped.is_empty() {
+        summary
+    } else {
+        format!(
+            "{}\nSkipped {} worktree(s) that failed verification: {}",
+            summary,
+            skipped.len(),
+            skipped.join(", ")
+        )
     }
 }
 
@@ -98,6 +121,19 @@ pub fn format_old_worktree_removal(session_name: &str, age_hours: i64, dry_run:
     }
 }
 
+/// Format a notice about sessions found orphaned and marked interrupted
+pub fn format_orphaned_sessions(session_names: &[String]) -> String {
+    let mut output = format!(
+        "⚠️  {} worktree session(s) were left in progress by a process that is no longer running and have been marked interrupted:\n",
+        session_names.len()
+    );
+    for name in session_names {
+        output.push_str(&format!("  - {name}\n"));
+    }
+    output.push_str("Resume in the existing worktree with: prodigy worktree recover <name>");
+    output
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -157,6 +193,7 @@ mod tests {
             session_name: "test-session".to_string(),
             success: true,
             error: None,
+            verification_failed: false,
         };
 
         let output = format_merge_result(&result);
@@ -171,6 +208,7 @@ mod tests {
             session_name: "test-session".to_string(),
             success: false,
             error: Some("merge conflict".to_string()),
+            verification_failed: false,
         };
 
         let output = format_merge_result(&result);
@@ -208,6 +246,34 @@ mod tests {
         assert!(output.contains("No worktrees were merged"));
     }
 
+    #[test]
+    fn test_format_batch_merge_summary_reports_verification_failures() {
+        use super::super::operations::BatchMergeResult;
+
+        let result = BatchMergeResult::from_results(vec![
+            MergeResult {
+                session_name: "session-1".to_string(),
+                success: true,
+                error: None,
+                verification_failed: false,
+            },
+            MergeResult {
+                session_name: "session-2".to_string(),
+                success: false,
+                error: Some("test result: FAILED".to_string()),
+                verification_failed: true,
+            },
+        ]);
+
+        let output = format_batch_merge_summary(&result);
+        assert!(output.contains("Successfully merged 1"));
+        assert!(output.contains("Skipped 1 worktree(s) that failed verification: session-2"));
+
+        let message = format_merge_result(&result.results[1]);
+        assert!(message.contains("Skipped worktree 'session-2'"));
+        assert!(message.contains("test result: FAILED"));
+    }
+
     #[test]
     fn test_format_cleanup_summary_dry_run() {
         let output = format_cleanup_summary(5, true);
@@ -253,4 +319,12 @@ mod tests {
         assert!(output.contains("24 hours"));
         assert!(!output.contains("DRY RUN"));
     }
+
+    #[test]
+    fn test_format_orphaned_sessions() {
+        let output = format_orphaned_sessions(&["session-a".to_string(), "session-b".to_string()]);
+        assert!(output.contains("2 worktree session(s)"));
+        assert!(output.contains("  - session-a\n  - session-b\n"));
+        assert!(output.ends_with("prodigy worktree recover <name>"));
+    }
 }
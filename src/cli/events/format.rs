@@ -3,6 +3,7 @@
 //! This module contains all functions for formatting and displaying events in various
 //! formats (human-readable, JSON, YAML, table, CSV, markdown).
 
+use crate::styled_println;
 use anyhow::Result;
 use chrono::{DateTime, Local};
 use serde::Serialize;
@@ -258,7 +259,7 @@ pub fn display_statistics_with_format(
         }
         _ => {
             let title_suffix = if is_aggregated { " - All Jobs" } else { "" };
-            println!("Event Statistics (grouped by {}){}", group_by, title_suffix);
+            styled_println!("Event Statistics (grouped by {}){}", group_by, title_suffix);
             let human_output = format_statistics_human(sorted_stats, total, group_by);
             print!("{}", human_output);
         }
@@ -284,7 +285,7 @@ pub fn display_events_with_format(events: &[Value], output_format: &str) -> Resu
             for event in events {
                 display_event(event);
             }
-            println!("\nDisplayed {} events", events.len());
+            styled_println!("\nDisplayed {} events", events.len());
         }
     }
     Ok(())
@@ -301,7 +302,7 @@ pub fn display_search_results(matching_events: &[Value], is_aggregated: bool) ->
     } else {
         ""
     };
-    println!(
+    styled_println!(
         "\nFound {} matching events{}",
         matching_events.len(),
         suffix
@@ -316,9 +317,12 @@ pub fn display_job_started(event: &Value, time_str: &str, event_type: &str, job_
             .get("total_items")
             .and_then(|v| v.as_u64())
             .unwrap_or(0);
-        println!(
+        styled_println!(
             "[{}] {} - Job: {} - Started with {} items",
-            time_str, event_type, job_id, total_items
+            time_str,
+            event_type,
+            job_id,
+            total_items
         );
     }
 }
@@ -334,9 +338,13 @@ pub fn display_job_completed(event: &Value, time_str: &str, event_type: &str, jo
             .get("failure_count")
             .and_then(|v| v.as_u64())
             .unwrap_or(0);
-        println!(
+        styled_println!(
             "[{}] {} - Job: {} - Success: {}, Failures: {}",
-            time_str, event_type, job_id, success, failure
+            time_str,
+            event_type,
+            job_id,
+            success,
+            failure
         );
     }
 }
@@ -353,9 +361,14 @@ pub fn display_agent_progress(event: &Value, time_str: &str, event_type: &str, j
             .get("progress_pct")
             .and_then(|v| v.as_f64())
             .unwrap_or(0.0);
-        println!(
+        styled_println!(
             "[{}] {} - Job: {} - Agent: {} - Step: {} ({:.1}%)",
-            time_str, event_type, job_id, agent_id, step, progress
+            time_str,
+            event_type,
+            job_id,
+            agent_id,
+            step,
+            progress
         );
     }
 }
@@ -451,11 +464,15 @@ pub fn export_as_markdown(events: &[Value]) -> Result<String> {
 
 /// Print table header for events display
 pub fn print_table_header() {
-    println!(
+    styled_println!(
         "{:<20} {:<15} {:<20} {:<15} {:<30}",
-        "Timestamp", "Event Type", "Job ID", "Agent ID", "Details"
+        "Timestamp",
+        "Event Type",
+        "Job ID",
+        "Agent ID",
+        "Details"
     );
-    println!("{}", "-".repeat(100));
+    styled_println!("{}", "-".repeat(100));
 }
 
 /// Truncate string to fit in table column
@@ -482,7 +499,7 @@ pub fn extract_table_row_data(event: &Value) -> (String, String, String, String,
 pub fn print_event_row(event: &Value) {
     let (timestamp, event_type, job_id, agent_id, details) = extract_table_row_data(event);
 
-    println!(
+    styled_println!(
         "{:<20} {:<15} {:<20} {:<15} {:<30}",
         truncate_field(&timestamp, 19),
         truncate_field(&event_type, 14),
@@ -495,7 +512,7 @@ pub fn print_event_row(event: &Value) {
 /// Display events in a table format
 pub fn display_events_as_table(events: &[Value]) -> Result<()> {
     if events.is_empty() {
-        println!("No events to display.");
+        styled_println!("No events to display.");
         return Ok(());
     }
 
@@ -505,7 +522,7 @@ pub fn display_events_as_table(events: &[Value]) -> Result<()> {
         print_event_row(event);
     }
 
-    println!("\nTotal events: {}", events.len());
+    styled_println!("\nTotal events: {}", events.len());
     Ok(())
 }
 
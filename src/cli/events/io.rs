@@ -8,7 +8,7 @@ use super::EventFilter;
 use anyhow::Result;
 use serde_json::Value;
 use std::fs;
-use std::io::{BufRead, BufReader};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
 use std::path::{Path, PathBuf};
 use tracing::info;
 
@@ -346,6 +346,163 @@ pub fn read_and_filter_events(
     Ok(events)
 }
 
+// =============================================================================
+// Paginated Reading
+// =============================================================================
+
+/// Bytes read per step when scanning a file backwards
+const REVERSE_CHUNK_SIZE: u64 = 64 * 1024;
+
+/// Lines of a file from last to first, each with the byte offset it starts at
+///
+/// Reads fixed-size chunks from the end of the file, so only the lines
+/// consumed are ever in memory.
+pub struct ReverseLines {
+    file: fs::File,
+    /// File offset of the first byte in `pending`
+    pos: u64,
+    /// Bytes between `pos` and the start of the last line returned
+    pending: Vec<u8>,
+}
+
+impl ReverseLines {
+    /// Read the lines of `path` that end before byte offset `end`
+    ///
+    /// `end` defaults to the end of the file and is clamped to its length.
+    pub fn open(path: &Path, end: Option<u64>) -> Result<Self> {
+        let file = fs::File::open(path)?;
+        let len = file.metadata()?.len();
+        Ok(Self {
+            file,
+            pos: end.map(|end| end.min(len)).unwrap_or(len),
+            pending: Vec::new(),
+        })
+    }
+
+    fn read_previous_chunk(&mut self) -> std::io::Result<()> {
+        let size = REVERSE_CHUNK_SIZE.min(self.pos);
+        self.pos -= size;
+        let mut chunk = vec![0; size as usize];
+        self.file.seek(SeekFrom::Start(self.pos))?;
+        self.file.read_exact(&mut chunk)?;
+        chunk.append(&mut self.pending);
+        self.pending = chunk;
+        Ok(())
+    }
+}
+
+impl Iterator for ReverseLines {
+    type Item = Result<(u64, String)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(newline) = self.pending.iter().rposition(|b| *b == b'\n') {
+                let line = self.pending.split_off(newline + 1);
+                self.pending.truncate(newline);
+                if line.is_empty() {
+                    continue;
+                }
+                let offset = self.pos + newline as u64 + 1;
+                return Some(Ok((offset, String::from_utf8_lossy(&line).into_owned())));
+            }
+            if self.pos == 0 {
+                if self.pending.is_empty() {
+                    return None;
+                }
+                let line = std::mem::take(&mut self.pending);
+                return Some(Ok((0, String::from_utf8_lossy(&line).into_owned())));
+            }
+            if let Err(e) = self.read_previous_chunk() {
+                return Some(Err(e.into()));
+            }
+        }
+    }
+}
+
+/// One page of events, newest last
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventPage {
+    pub events: Vec<Value>,
+    /// Cursor for the page of older events, if there are any
+    pub next_cursor: Option<u64>,
+}
+
+/// Read a page of the most recent events matching a filter
+///
+/// Pages are counted from 1, newest first, each holding up to `limit`
+/// events. A cursor from a previous page's `next_cursor` starts reading at
+/// the events just before that page, and `page` then counts from there.
+/// The file is read backwards and only until the page is filled or the
+/// events predate the filter's time range.
+///
+/// # Arguments
+/// * `file` - Path to the file to read
+/// * `filter` - Filter to apply to events
+/// * `limit` - Maximum number of events per page
+/// * `page` - Page number, 1 for the newest events
+/// * `cursor` - Byte offset to read backwards from
+///
+/// # Example
+/// ```no_run
+/// use prodigy::cli::events::io::read_events_page;
+/// use prodigy::cli::events::EventFilter;
+/// use std::path::PathBuf;
+///
+/// # fn example() -> Result<(), anyhow::Error> {
+/// let file = PathBuf::from("events.jsonl");
+/// let filter = EventFilter::new(None, None, None, None);
+/// let latest = read_events_page(&file, &filter, 100, 1, None)?;
+/// if let Some(cursor) = latest.next_cursor {
+///     let older = read_events_page(&file, &filter, 100, 1, Some(cursor))?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn read_events_page(
+    file: &Path,
+    filter: &EventFilter,
+    limit: usize,
+    page: usize,
+    cursor: Option<u64>,
+) -> Result<EventPage> {
+    let skip = page.saturating_sub(1).saturating_mul(limit);
+    let mut events = Vec::with_capacity(limit);
+    let mut oldest_offset = None;
+    let mut matched = 0;
+
+    for line in ReverseLines::open(file, cursor)? {
+        let (offset, line) = line?;
+        let Some(event) = transform::parse_event_line(&line) else {
+            continue;
+        };
+        if filter.precedes_range(&event) {
+            break;
+        }
+        if !filter.matches_event(&event) {
+            continue;
+        }
+        if events.len() == limit {
+            // An older match exists, so the page has a successor
+            events.reverse();
+            return Ok(EventPage {
+                events,
+                next_cursor: oldest_offset,
+            });
+        }
+        matched += 1;
+        if matched > skip {
+            events.push(event);
+            oldest_offset = Some(offset);
+        }
+    }
+
+    events.reverse();
+    Ok(EventPage {
+        events,
+        next_cursor: None,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -425,6 +582,69 @@ mod tests {
         assert_eq!(events.len(), 2);
     }
 
+    #[test]
+    fn test_reverse_lines_across_chunks() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("events.jsonl");
+        let lines: Vec<String> = (0..5000)
+            .map(|i| format!(r#"{{"seq":{},"pad":"{}"}}"#, i, "x".repeat(i % 40)))
+            .collect();
+        fs::write(&file_path, lines.join("\n") + "\n\n").unwrap();
+
+        let read: Vec<(u64, String)> = ReverseLines::open(&file_path, None)
+            .unwrap()
+            .map(Result::unwrap)
+            .collect();
+        let expected: Vec<&String> = lines.iter().rev().collect();
+        assert_eq!(read.iter().map(|(_, l)| l).collect::<Vec<_>>(), expected);
+
+        // Offsets point at line starts, so a cursor resumes before that line
+        let (offset, _) = &read[10];
+        let next = ReverseLines::open(&file_path, Some(*offset))
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap();
+        assert_eq!(next.1, read[11].1);
+    }
+
+    #[test]
+    fn test_read_events_page_newest_first() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("events.jsonl");
+        let mut file = fs::File::create(&file_path).unwrap();
+        for i in 0..10 {
+            let job = if i % 2 == 0 { "even" } else { "odd" };
+            writeln!(file, r#"{{"job_id":"{}","seq":{}}}"#, job, i).unwrap();
+        }
+        writeln!(file, "not json").unwrap();
+
+        let seqs = |page: &EventPage| -> Vec<u64> {
+            page.events
+                .iter()
+                .map(|e| e["seq"].as_u64().unwrap())
+                .collect()
+        };
+        let all = EventFilter::new(None, None, None, None);
+
+        let latest = read_events_page(&file_path, &all, 3, 1, None).unwrap();
+        assert_eq!(seqs(&latest), vec![7, 8, 9]);
+        let second = read_events_page(&file_path, &all, 3, 2, None).unwrap();
+        assert_eq!(seqs(&second), vec![4, 5, 6]);
+
+        let older = read_events_page(&file_path, &all, 3, 1, latest.next_cursor).unwrap();
+        assert_eq!(older, second);
+
+        let last = read_events_page(&file_path, &all, 3, 4, None).unwrap();
+        assert_eq!(seqs(&last), vec![0]);
+        assert_eq!(last.next_cursor, None);
+
+        let odd = EventFilter::new(Some("odd".to_string()), None, None, None);
+        let page = read_events_page(&file_path, &odd, 5, 1, None).unwrap();
+        assert_eq!(seqs(&page), vec![1, 3, 5, 7, 9]);
+        assert_eq!(page.next_cursor, None);
+    }
+
     #[test]
     fn test_build_global_events_path() {
         let path = build_global_events_path("test-repo").unwrap();
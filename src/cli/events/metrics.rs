@@ -0,0 +1,414 @@
+//! Time-bucketed metrics for event analysis
+//!
+//! Pure functions that turn raw events into duration percentiles, an
+//! events-per-bucket time series, and failure rate over time, plus renderers
+//! for ASCII charts and JSON export.
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, DurationRound, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+use super::transform;
+
+/// Upper bound on time buckets; wider spans get wider buckets
+const MAX_BUCKETS: i64 = 60;
+
+/// Width of the bars in ASCII charts
+const BAR_WIDTH: usize = 40;
+
+/// Duration percentiles for one event type, in milliseconds
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DurationPercentiles {
+    pub count: usize,
+    pub p50_ms: u64,
+    pub p90_ms: u64,
+    pub p99_ms: u64,
+    pub max_ms: u64,
+}
+
+/// Event counts within one time bucket
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TimeBucket {
+    pub start: DateTime<Utc>,
+    pub events: usize,
+    pub completed: usize,
+    pub failed: usize,
+}
+
+impl TimeBucket {
+    /// Failed share of finished agents and jobs, if any finished
+    pub fn failure_rate(&self) -> Option<f64> {
+        let finished = self.completed + self.failed;
+        (finished > 0).then(|| self.failed as f64 / finished as f64)
+    }
+}
+
+/// Metrics computed from a set of events
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EventMetrics {
+    pub total_events: usize,
+    /// Width of each time bucket in minutes
+    pub bucket_minutes: i64,
+    /// Percentiles keyed by event type, for events that carry a duration
+    pub durations: BTreeMap<String, DurationPercentiles>,
+    /// Contiguous buckets from the first to the last timestamped event
+    pub buckets: Vec<TimeBucket>,
+}
+
+/// Compute duration percentiles and time-bucketed counts (pure function)
+///
+/// `bucket_minutes` is widened when the events span more than
+/// `MAX_BUCKETS` buckets, so the chart stays readable.
+pub fn compute_event_metrics(events: &[Value], bucket_minutes: i64) -> EventMetrics {
+    let mut durations: BTreeMap<String, Vec<u64>> = BTreeMap::new();
+    let mut timestamped = Vec::new();
+
+    for event in events {
+        let kind = event_kind(event);
+        if let Some(ms) = extract_duration_ms(event) {
+            durations.entry(kind.clone()).or_default().push(ms);
+        }
+        if let Some(timestamp) = transform::extract_timestamp(event) {
+            timestamped.push((timestamp, kind));
+        }
+    }
+
+    let durations = durations
+        .into_iter()
+        .map(|(kind, mut values)| {
+            values.sort_unstable();
+            (kind, summarize_durations(&values))
+        })
+        .collect();
+
+    let (bucket_minutes, buckets) = bucket_events(&timestamped, bucket_minutes.max(1));
+
+    EventMetrics {
+        total_events: events.len(),
+        bucket_minutes,
+        durations,
+        buckets,
+    }
+}
+
+/// Nearest-rank percentile of sorted values (pure function)
+pub fn percentile(sorted: &[u64], pct: f64) -> u64 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let rank = ((pct / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+fn summarize_durations(sorted: &[u64]) -> DurationPercentiles {
+    DurationPercentiles {
+        count: sorted.len(),
+        p50_ms: percentile(sorted, 50.0),
+        p90_ms: percentile(sorted, 90.0),
+        p99_ms: percentile(sorted, 99.0),
+        max_ms: sorted.last().copied().unwrap_or(0),
+    }
+}
+
+/// Snake-case event type of an event record or legacy event
+pub fn event_kind(event: &Value) -> String {
+    let tagged = event
+        .get("event")
+        .and_then(|inner| inner.get("event_type"))
+        .or_else(|| event.get("event_type"))
+        .and_then(Value::as_str);
+
+    match tagged {
+        Some(kind) => kind.to_string(),
+        None => to_snake_case(&transform::get_event_type(event)),
+    }
+}
+
+fn to_snake_case(name: &str) -> String {
+    let mut out = String::with_capacity(name.len() + 4);
+    for (i, ch) in name.chars().enumerate() {
+        if ch.is_ascii_uppercase() {
+            if i > 0 {
+                out.push('_');
+            }
+            out.push(ch.to_ascii_lowercase());
+        } else {
+            out.push(ch);
+        }
+    }
+    out
+}
+
+/// Duration carried by an event, in milliseconds
+///
+/// Accepts `duration` as a `[secs, nanos]` pair or `{secs, nanos}` object,
+/// and `duration_ms` as a number.
+pub fn extract_duration_ms(event: &Value) -> Option<u64> {
+    let payload = event
+        .get("event")
+        .filter(|inner| inner.is_object())
+        .unwrap_or(event);
+
+    if let Some(ms) = payload
+        .get("duration_ms")
+        .or_else(|| transform::extract_nested_field(payload, "duration_ms"))
+        .and_then(Value::as_u64)
+    {
+        return Some(ms);
+    }
+
+    let duration = payload
+        .get("duration")
+        .or_else(|| transform::extract_nested_field(payload, "duration"))?;
+    let (secs, nanos) = match duration {
+        Value::Array(parts) => (parts.first()?.as_u64()?, parts.get(1)?.as_u64()?),
+        Value::Object(fields) => (
+            fields.get("secs")?.as_u64()?,
+            fields.get("nanos")?.as_u64()?,
+        ),
+        _ => return None,
+    };
+    Some(secs * 1000 + nanos / 1_000_000)
+}
+
+fn bucket_events(
+    timestamped: &[(DateTime<Utc>, String)],
+    requested_minutes: i64,
+) -> (i64, Vec<TimeBucket>) {
+    let Some(first) = timestamped.iter().map(|(ts, _)| *ts).min() else {
+        return (requested_minutes, Vec::new());
+    };
+    let last = timestamped.iter().map(|(ts, _)| *ts).max().unwrap_or(first);
+
+    let span_minutes = (last - first).num_minutes() + 1;
+    let minutes = requested_minutes.max((span_minutes + MAX_BUCKETS - 1) / MAX_BUCKETS);
+    let width = Duration::minutes(minutes);
+    let origin = first.duration_trunc(width).unwrap_or(first);
+
+    let count = ((last - origin).num_minutes() / minutes + 1) as usize;
+    let mut buckets: Vec<TimeBucket> = (0..count)
+        .map(|i| TimeBucket {
+            start: origin + width * i as i32,
+            events: 0,
+            completed: 0,
+            failed: 0,
+        })
+        .collect();
+
+    for (timestamp, kind) in timestamped {
+        let index = ((*timestamp - origin).num_minutes() / minutes) as usize;
+        if let Some(bucket) = buckets.get_mut(index) {
+            bucket.events += 1;
+            match kind.as_str() {
+                "agent_completed" | "job_completed" => bucket.completed += 1,
+                "agent_failed" | "job_failed" => bucket.failed += 1,
+                _ => {}
+            }
+        }
+    }
+
+    (minutes, buckets)
+}
+
+/// Render metrics as ASCII tables and bar charts (pure function)
+pub fn format_metrics_ascii(metrics: &EventMetrics) -> String {
+    let mut out = String::new();
+
+    out.push_str("Duration percentiles (ms)\n");
+    if metrics.durations.is_empty() {
+        out.push_str("  No events with durations\n");
+    } else {
+        out.push_str(&format!(
+            "  {:<20} {:>7} {:>10} {:>10} {:>10} {:>10}\n",
+            "Event type", "Count", "p50", "p90", "p99", "max"
+        ));
+        for (kind, stats) in &metrics.durations {
+            out.push_str(&format!(
+                "  {:<20} {:>7} {:>10} {:>10} {:>10} {:>10}\n",
+                kind, stats.count, stats.p50_ms, stats.p90_ms, stats.p99_ms, stats.max_ms
+            ));
+        }
+    }
+
+    out.push_str(&format!(
+        "\nEvents per {}m (total: {})\n",
+        metrics.bucket_minutes, metrics.total_events
+    ));
+    if metrics.buckets.is_empty() {
+        out.push_str("  No timestamped events\n");
+        return out;
+    }
+    let peak = metrics.buckets.iter().map(|b| b.events).max().unwrap_or(0);
+    for bucket in &metrics.buckets {
+        out.push_str(&format!(
+            "  {}  {:<width$} {}\n",
+            bucket_label(bucket),
+            bar(bucket.events as f64, peak as f64),
+            bucket.events,
+            width = BAR_WIDTH
+        ));
+    }
+
+    out.push_str("\nFailure rate\n");
+    let finished: Vec<&TimeBucket> = metrics
+        .buckets
+        .iter()
+        .filter(|b| b.failure_rate().is_some())
+        .collect();
+    if finished.is_empty() {
+        out.push_str("  No completed or failed agents\n");
+    }
+    for bucket in finished {
+        let rate = bucket.failure_rate().unwrap_or(0.0);
+        out.push_str(&format!(
+            "  {}  {:<width$} {:.1}% ({}/{})\n",
+            bucket_label(bucket),
+            bar(rate, 1.0),
+            rate * 100.0,
+            bucket.failed,
+            bucket.completed + bucket.failed,
+            width = BAR_WIDTH
+        ));
+    }
+
+    out
+}
+
+/// Render metrics as pretty-printed JSON
+pub fn format_metrics_json(metrics: &EventMetrics) -> Result<String> {
+    Ok(serde_json::to_string_pretty(metrics)?)
+}
+
+fn bucket_label(bucket: &TimeBucket) -> String {
+    bucket.start.format("%Y-%m-%d %H:%M").to_string()
+}
+
+fn bar(value: f64, max: f64) -> String {
+    if max <= 0.0 {
+        return String::new();
+    }
+    let len = ((value / max) * BAR_WIDTH as f64).round() as usize;
+    "█".repeat(len.min(BAR_WIDTH))
+}
+
+/// Print metrics in the requested output format
+pub fn display_metrics_with_format(
+    events: &[Value],
+    bucket_minutes: i64,
+    output_format: &str,
+) -> Result<()> {
+    let metrics = compute_event_metrics(events, bucket_minutes);
+    match output_format {
+        "json" => println!("{}", format_metrics_json(&metrics)?),
+        _ => print!("{}", format_metrics_ascii(&metrics)),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn record(timestamp: &str, event: Value) -> Value {
+        json!({ "id": "x", "timestamp": timestamp, "correlation_id": "c", "event": event })
+    }
+
+    #[test]
+    fn test_percentile_nearest_rank() {
+        let values: Vec<u64> = (1..=100).collect();
+        assert_eq!(percentile(&values, 50.0), 50);
+        assert_eq!(percentile(&values, 90.0), 90);
+        assert_eq!(percentile(&values, 99.0), 99);
+        assert_eq!(percentile(&[7], 99.0), 7);
+        assert_eq!(percentile(&[], 50.0), 0);
+    }
+
+    #[test]
+    fn test_extract_duration_formats() {
+        let chrono_pair = record(
+            "2026-01-01T10:00:00Z",
+            json!({"event_type": "agent_completed", "duration": [2, 500_000_000]}),
+        );
+        assert_eq!(extract_duration_ms(&chrono_pair), Some(2500));
+        assert_eq!(event_kind(&chrono_pair), "agent_completed");
+
+        let legacy = json!({"AgentCompleted": {"duration": {"secs": 1, "nanos": 0}}});
+        assert_eq!(extract_duration_ms(&legacy), Some(1000));
+        assert_eq!(event_kind(&legacy), "agent_completed");
+
+        assert_eq!(extract_duration_ms(&json!({"duration_ms": 42})), Some(42));
+        assert_eq!(
+            extract_duration_ms(&json!({"event_type": "agent_started"})),
+            None
+        );
+    }
+
+    #[test]
+    fn test_compute_metrics_buckets_and_failure_rate() {
+        let events = vec![
+            record(
+                "2026-01-01T10:00:10Z",
+                json!({"event_type": "agent_started"}),
+            ),
+            record(
+                "2026-01-01T10:00:40Z",
+                json!({"event_type": "agent_completed", "duration": [3, 0]}),
+            ),
+            record(
+                "2026-01-01T10:02:05Z",
+                json!({"event_type": "agent_failed"}),
+            ),
+            record(
+                "2026-01-01T10:02:30Z",
+                json!({"event_type": "agent_completed", "duration": [1, 0]}),
+            ),
+        ];
+
+        let metrics = compute_event_metrics(&events, 1);
+
+        assert_eq!(metrics.bucket_minutes, 1);
+        assert_eq!(
+            metrics.buckets.iter().map(|b| b.events).collect::<Vec<_>>(),
+            vec![2, 0, 2]
+        );
+        assert_eq!(metrics.buckets[0].failure_rate(), Some(0.0));
+        assert_eq!(metrics.buckets[1].failure_rate(), None);
+        assert_eq!(metrics.buckets[2].failure_rate(), Some(0.5));
+
+        let agent = &metrics.durations["agent_completed"];
+        assert_eq!((agent.count, agent.p50_ms, agent.max_ms), (2, 1000, 3000));
+
+        let ascii = format_metrics_ascii(&metrics);
+        assert!(ascii.contains("agent_completed"));
+        let failure_line = ascii
+            .lines()
+            .rev()
+            .find(|line| line.contains("2026-01-01 10:02"))
+            .unwrap();
+        assert_eq!(failure_line.matches('█').count(), BAR_WIDTH / 2);
+        assert!(failure_line.ends_with("50.0% (1/2)"));
+    }
+
+    #[test]
+    fn test_long_spans_widen_buckets() {
+        let events = vec![
+            record(
+                "2026-01-01T00:00:00Z",
+                json!({"event_type": "agent_started"}),
+            ),
+            record(
+                "2026-01-01T23:59:00Z",
+                json!({"event_type": "agent_started"}),
+            ),
+        ];
+
+        let metrics = compute_event_metrics(&events, 1);
+
+        assert_eq!(metrics.bucket_minutes, 24);
+        assert!(metrics.buckets.len() as i64 <= MAX_BUCKETS);
+        assert_eq!(metrics.buckets.iter().map(|b| b.events).sum::<usize>(), 2);
+    }
+}
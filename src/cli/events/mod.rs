@@ -3,10 +3,13 @@
 pub mod analysis;
 pub mod format;
 pub mod io;
+pub mod metrics;
 pub mod transform;
 
 use crate::cook::interaction::prompts::{UserPrompter, UserPrompterImpl};
+use crate::styled_println;
 use anyhow::Result;
+use chrono::{DateTime, Utc};
 use clap::{Args, Subcommand};
 use serde_json::Value;
 use std::fs;
@@ -41,10 +44,30 @@ pub enum EventsCommand {
         #[arg(long)]
         since: Option<u64>,
 
-        /// Limit number of events shown
+        /// Show only events at or after this RFC 3339 time
+        #[arg(long, value_parser = parse_rfc3339)]
+        from: Option<DateTime<Utc>>,
+
+        /// Show only events at or before this RFC 3339 time
+        #[arg(long, value_parser = parse_rfc3339)]
+        to: Option<DateTime<Utc>>,
+
+        /// Condition on event fields, e.g. 'duration > 60s' (repeatable)
+        #[arg(long = "where", value_name = "CONDITION")]
+        conditions: Vec<String>,
+
+        /// Number of events per page
         #[arg(long, default_value = "100")]
         limit: usize,
 
+        /// Page of events to show, 1 for the most recent
+        #[arg(long, default_value = "1")]
+        page: usize,
+
+        /// Continue from a cursor printed by a previous listing
+        #[arg(long)]
+        cursor: Option<u64>,
+
         /// Path to events file
         #[arg(long, default_value = ".prodigy/events/mapreduce_events.jsonl")]
         file: PathBuf,
@@ -67,6 +90,14 @@ pub enum EventsCommand {
         /// Output format (human, json, yaml, table)
         #[arg(long, default_value = "human")]
         output_format: String,
+
+        /// Show duration percentiles, events over time, and failure rate
+        #[arg(long)]
+        metrics: bool,
+
+        /// Time bucket width in minutes for --metrics
+        #[arg(long, default_value = "1")]
+        bucket_minutes: i64,
     },
 
     /// Search events by pattern
@@ -168,6 +199,13 @@ pub use transform::EventFilter;
 // Command Execution Functions
 // =============================================================================
 
+/// Parse an RFC 3339 timestamp argument such as `2024-05-01T12:00:00Z`
+pub fn parse_rfc3339(value: &str) -> std::result::Result<DateTime<Utc>, String> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|time| time.with_timezone(&Utc))
+        .map_err(|e| format!("expected an RFC 3339 time like 2024-05-01T12:00:00Z: {}", e))
+}
+
 /// Execute event viewer commands
 pub async fn execute(args: EventsArgs) -> Result<()> {
     match args.command {
@@ -176,7 +214,12 @@ pub async fn execute(args: EventsArgs) -> Result<()> {
             event_type,
             agent_id,
             since,
+            from,
+            to,
+            conditions,
             limit,
+            page,
+            cursor,
             file,
             output_format,
         } => {
@@ -187,13 +230,17 @@ pub async fn execute(args: EventsArgs) -> Result<()> {
             } else {
                 // Resolve the event file and list events
                 let resolved_file = io::resolve_event_file_with_fallback(file, job_id.as_deref())?;
+                let filter = EventFilter::new(job_id, event_type, agent_id, since)
+                    .with_time_range(from, to)
+                    .with_conditions(&conditions)?;
                 list_events(
                     resolved_file,
-                    job_id,
-                    event_type,
-                    agent_id,
-                    since,
-                    limit,
+                    filter,
+                    EventPageRequest {
+                        limit,
+                        page,
+                        cursor,
+                    },
                     output_format,
                 )
                 .await
@@ -204,9 +251,13 @@ pub async fn execute(args: EventsArgs) -> Result<()> {
             file,
             group_by,
             output_format,
+            metrics,
+            bucket_minutes,
         } => {
-            // If no explicit file, aggregate all events from global storage
-            if !file.exists() {
+            if metrics {
+                show_metrics(file, bucket_minutes, output_format).await
+            } else if !file.exists() {
+                // If no explicit file, aggregate all events from global storage
                 analysis::show_aggregated_stats(group_by, output_format).await
             } else {
                 let resolved_file = io::resolve_event_file_with_fallback(file, None)?;
@@ -294,7 +345,7 @@ fn display_available_jobs() -> Result<()> {
 
     for job in jobs {
         let job_info = format::create_job_display_info(&job);
-        println!("  • {}", job_info);
+        styled_println!("  • {}", job_info);
     }
 
     println!("{}", "=".repeat(50));
@@ -306,14 +357,18 @@ fn display_available_jobs() -> Result<()> {
     Ok(())
 }
 
+/// Which page of events `ls` shows
+struct EventPageRequest {
+    limit: usize,
+    page: usize,
+    cursor: Option<u64>,
+}
+
 /// List events with optional filters (refactored to separate I/O from logic)
 async fn list_events(
     file: PathBuf,
-    job_id: Option<String>,
-    event_type: Option<String>,
-    agent_id: Option<String>,
-    since: Option<u64>,
-    limit: usize,
+    filter: EventFilter,
+    request: EventPageRequest,
     output_format: String,
 ) -> Result<()> {
     if !file.exists() {
@@ -321,14 +376,17 @@ async fn list_events(
         return Ok(());
     }
 
-    // Create filter using pure function
-    let filter = EventFilter::new(job_id, event_type, agent_id, since);
-
-    // Read events from file
-    let events = io::read_and_filter_events(&file, &filter, limit)?;
+    // Read only the requested page, from the end of the file
+    let page = io::read_events_page(&file, &filter, request.limit, request.page, request.cursor)?;
 
     // Display events using pure functions
-    format::display_events_with_format(&events, &output_format)
+    format::display_events_with_format(&page.events, &output_format)?;
+
+    // Written to stderr so JSON and YAML output stay parseable
+    if let Some(cursor) = page.next_cursor {
+        eprintln!("Older events: --cursor {}", cursor);
+    }
+    Ok(())
 }
 
 /// Show event statistics (refactored to use pure functions)
@@ -347,6 +405,22 @@ async fn show_stats(file: PathBuf, group_by: String, output_format: String) -> R
     format::display_statistics_with_format(&sorted_stats, total, &group_by, &output_format, false)
 }
 
+/// Show time-bucketed metrics for one file, or for all jobs if it does not exist
+async fn show_metrics(file: PathBuf, bucket_minutes: i64, output_format: String) -> Result<()> {
+    let events = if file.exists() {
+        io::read_events_from_single_file(&file)?
+    } else {
+        io::read_events_from_files(&io::get_all_event_files()?)?
+    };
+
+    if events.is_empty() {
+        println!("No events found.");
+        return Ok(());
+    }
+
+    metrics::display_metrics_with_format(&events, bucket_minutes, &output_format)
+}
+
 /// Search events by pattern (refactored to use pure functions)
 async fn search_events(file: PathBuf, pattern: String, fields: Option<Vec<String>>) -> Result<()> {
     if !file.exists() {
@@ -1319,6 +1393,60 @@ mod tests {
         assert!(!event_matches_type(&event, "Unknown"));
     }
 
+    fn agent_completed(timestamp: &str, secs: u64, commits: usize) -> Value {
+        json!({
+            "id": "6d1f",
+            "timestamp": timestamp,
+            "correlation_id": "c1",
+            "event": {
+                "event_type": "agent_completed",
+                "job_id": "job-1",
+                "agent_id": "agent-1",
+                "duration": {"secs": secs, "nanos": 0},
+                "commits": vec!["abc"; commits],
+                "commit_count": commits,
+            }
+        })
+    }
+
+    #[test]
+    fn test_event_filter_time_range() {
+        let from = parse_rfc3339("2024-05-01T10:00:00Z").unwrap();
+        let to = parse_rfc3339("2024-05-01T11:00:00+00:00").unwrap();
+        let filter = EventFilter::new(None, None, None, None).with_time_range(Some(from), Some(to));
+
+        assert!(filter.matches_event(&agent_completed("2024-05-01T10:30:00Z", 5, 1)));
+        assert!(!filter.matches_event(&agent_completed("2024-05-01T09:59:59Z", 5, 1)));
+        assert!(!filter.matches_event(&agent_completed("2024-05-01T11:00:01Z", 5, 1)));
+
+        // Reads from the end stop only once events are clearly older than the range
+        assert!(!filter.precedes_range(&agent_completed("2024-05-01T09:59:30Z", 5, 1)));
+        assert!(filter.precedes_range(&agent_completed("2024-05-01T09:58:00Z", 5, 1)));
+
+        assert!(parse_rfc3339("yesterday").is_err());
+    }
+
+    #[test]
+    fn test_event_filter_conditions() {
+        let filter = EventFilter::new(None, None, None, None)
+            .with_conditions(&[
+                "duration > 60s".to_string(),
+                "commit_count >= 2".to_string(),
+            ])
+            .unwrap();
+
+        assert!(filter.matches_event(&agent_completed("2024-05-01T10:00:00Z", 90, 2)));
+        assert!(!filter.matches_event(&agent_completed("2024-05-01T10:00:00Z", 30, 2)));
+        assert!(!filter.matches_event(&agent_completed("2024-05-01T10:00:00Z", 90, 1)));
+        assert_eq!(
+            filter_view(&agent_completed("2024-05-01T10:00:00Z", 90, 2))["duration"],
+            json!(90.0)
+        );
+
+        let invalid = EventFilter::new(None, None, None, None).with_conditions(&["&&".to_string()]);
+        assert!(invalid.is_err());
+    }
+
     // ===========================================================================
     // Tests for Pure Decision Functions
     // ===========================================================================
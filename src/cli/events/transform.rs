@@ -3,10 +3,17 @@
 //! This module contains all pure functions for transforming, filtering, and
 //! analyzing event data. These functions have no side effects and are highly testable.
 
+use crate::cook::execution::data_pipeline::FilterExpression;
 use chrono::{DateTime, Utc};
 use serde_json::Value;
 use std::collections::HashMap;
 
+/// How far an event may precede the time range before a backwards read stops
+///
+/// Events are appended roughly in time order; buffered writers can put a
+/// slightly older event after a newer one.
+const OUT_OF_ORDER_SLACK_SECS: i64 = 60;
+
 /// Pure function to build event filter criteria
 #[derive(Debug, Clone)]
 pub struct EventFilter {
@@ -14,6 +21,10 @@ pub struct EventFilter {
     pub event_type: Option<String>,
     pub agent_id: Option<String>,
     pub since_time: Option<DateTime<Utc>>,
+    /// Latest timestamp to include
+    pub until_time: Option<DateTime<Utc>>,
+    /// Conditions on event fields, all of which must hold
+    pub conditions: Vec<FilterExpression>,
 }
 
 impl EventFilter {
@@ -30,7 +41,46 @@ impl EventFilter {
             event_type,
             agent_id,
             since_time,
+            until_time: None,
+            conditions: Vec::new(),
+        }
+    }
+
+    /// Restrict events to `from..=to`, combined with any `since` window
+    pub fn with_time_range(
+        mut self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Self {
+        self.since_time = self.since_time.max(from);
+        self.until_time = to;
+        self
+    }
+
+    /// Require every condition, e.g. `duration > 60s` or `success_count >= 10`
+    ///
+    /// Conditions use the work item filter syntax and are evaluated against
+    /// [`filter_view`] of each event.
+    pub fn with_conditions(mut self, conditions: &[String]) -> anyhow::Result<Self> {
+        for condition in conditions {
+            let expression = FilterExpression::parse(condition)
+                .map_err(|e| anyhow::anyhow!("Invalid --where condition '{}': {}", condition, e))?;
+            self.conditions.push(expression);
         }
+        Ok(self)
+    }
+
+    /// Whether an event is old enough that earlier events cannot match
+    ///
+    /// Lets a read from the end of a file stop once it has passed the start
+    /// of the time range.
+    pub fn precedes_range(&self, event: &Value) -> bool {
+        let Some(since) = self.since_time else {
+            return false;
+        };
+        event_timestamp(event).is_some_and(|timestamp| {
+            timestamp < since - chrono::Duration::seconds(OUT_OF_ORDER_SLACK_SECS)
+        })
     }
 
     pub fn matches_event(&self, event: &Value) -> bool {
@@ -58,10 +108,56 @@ impl EventFilter {
             }
         }
 
+        if let Some(until_time) = self.until_time {
+            if event_timestamp(event).is_none_or(|timestamp| timestamp > until_time) {
+                return false;
+            }
+        }
+
+        if !self.conditions.is_empty() {
+            let view = filter_view(event);
+            if !self
+                .conditions
+                .iter()
+                .all(|condition| condition.evaluate(&view))
+            {
+                return false;
+            }
+        }
+
         true
     }
 }
 
+/// Event flattened for `--where` conditions (pure function)
+///
+/// Payload fields are lifted next to the record's own fields, so conditions
+/// can say `success_count` rather than `event.success_count`, and `duration`
+/// becomes a number of seconds.
+pub fn filter_view(event: &Value) -> Value {
+    let mut view = event.as_object().cloned().unwrap_or_default();
+    let payload = event
+        .get("event")
+        .filter(|inner| inner.is_object())
+        .or_else(|| {
+            event
+                .as_object()
+                .and_then(|fields| fields.values().find(|value| value.get("job_id").is_some()))
+        });
+    if let Some(payload) = payload.and_then(Value::as_object) {
+        for (key, value) in payload {
+            view.entry(key.clone()).or_insert_with(|| value.clone());
+        }
+    }
+    if let Some(ms) = super::metrics::extract_duration_ms(event) {
+        view.insert(
+            "duration".to_string(),
+            serde_json::json!(ms as f64 / 1000.0),
+        );
+    }
+    Value::Object(view)
+}
+
 /// Pure function to transform events into statistics
 pub fn calculate_event_statistics(
     events: impl Iterator<Item = Value>,
@@ -327,20 +423,21 @@ pub fn get_event_type(event: &Value) -> String {
 }
 
 pub fn event_is_recent(event: &Value, since_time: DateTime<Utc>) -> bool {
+    event_timestamp(event).is_some_and(|event_time| event_time >= since_time)
+}
+
+/// Pure function to read an event's timestamp
+pub fn event_timestamp(event: &Value) -> Option<DateTime<Utc>> {
     // Look for timestamp in various possible locations
     let timestamp_str = event
         .get("timestamp")
         .or_else(|| event.get("JobStarted").and_then(|v| v.get("timestamp")))
         .or_else(|| event.get("time"))
-        .and_then(|v| v.as_str());
+        .and_then(|v| v.as_str())?;
 
-    if let Some(ts) = timestamp_str {
-        if let Ok(event_time) = DateTime::parse_from_rfc3339(ts) {
-            return event_time.with_timezone(&Utc) >= since_time;
-        }
-    }
-
-    false
+    DateTime::parse_from_rfc3339(timestamp_str)
+        .ok()
+        .map(|event_time| event_time.with_timezone(&Utc))
 }
 
 /// Extract event metadata for display
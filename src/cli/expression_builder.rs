@@ -1,5 +1,6 @@
 //! Expression builder CLI tool for testing and validating expressions
 
+use crate::styled_println;
 use anyhow::Result;
 use clap::{Parser, Subcommand};
 use serde_json::{json, Value};
@@ -272,12 +273,12 @@ impl ExpressionBuilderCommand {
         match expr_type {
             "filter" => {
                 let filter = FilterExpression::parse(expression)?;
-                println!("✓ Valid filter expression");
+                styled_println!("✓ Valid filter expression");
                 println!("Expression: {:?}", filter);
             }
             "sort" => {
                 let sorter = Sorter::parse(expression)?;
-                println!("✓ Valid sort expression");
+                styled_println!("✓ Valid sort expression");
                 println!("Sort keys: {:?}", sorter.fields);
             }
             _ => {
@@ -2,9 +2,10 @@
 //!
 //! This module handles routing CLI commands to their respective implementations.
 
-use crate::cli::args::{Commands, TemplateCommand};
+use crate::cli::args::{Commands, LogsCommands, TemplateCommand};
 use crate::cli::commands::*;
 use crate::cli::params::{load_param_file, merge_params, parse_cli_params};
+use crate::{styled_eprintln, styled_println};
 use anyhow::Result;
 use std::path::PathBuf;
 
@@ -14,15 +15,35 @@ pub async fn execute_command(command: Option<Commands>, verbose: u8) -> Result<(
         Some(Commands::Run {
             workflow,
             path,
+            package,
+            repos,
             max_iterations,
+            worktree,
+            no_worktree,
             map,
+            changed_only,
             args,
             fail_fast,
+            no_fail_fast,
             auto_accept,
             resume,
             dry_run,
+            read_only,
+            record,
+            replay,
+            junit_output,
+            profile_steps,
+            explain_failure,
+            keep_going,
+            only_steps,
+            skip_steps,
+            chaos,
+            seed,
+            answers_file,
+            environment,
             params,
             param_file,
+            watch,
         }) => {
             // Parse CLI parameters
             let cli_params = parse_cli_params(params)?;
@@ -37,12 +58,19 @@ pub async fn execute_command(command: Option<Commands>, verbose: u8) -> Result<(
             // Merge parameters (CLI takes precedence)
             let merged_params = merge_params(cli_params, file_params);
 
+            let steps = crate::cook::workflow::step_selection::StepSelection::parse(
+                only_steps.as_deref(),
+                skip_steps.as_deref(),
+            )?;
+
             // Run is the primary command for workflow execution
             let cook_cmd = crate::cook::command::CookCommand {
                 playbook: workflow,
                 path,
-                max_iterations,
+                package,
+                max_iterations: max_iterations.unwrap_or(1),
                 map,
+                changed_only,
                 args,
                 fail_fast,
                 auto_accept,
@@ -50,9 +78,39 @@ pub async fn execute_command(command: Option<Commands>, verbose: u8) -> Result<(
                 quiet: false,
                 verbosity: verbose,
                 dry_run,
+                read_only,
+                record,
+                replay,
+                junit_output,
+                profile_steps,
+                explain_failure,
+                keep_going,
+                steps,
+                chaos,
+                seed,
+                answers_file,
+                environment,
+                no_worktree,
+                explicit_flags: crate::cook::command::ExplicitFlags {
+                    worktree: worktree || no_worktree,
+                    max_iterations: max_iterations.is_some(),
+                    fail_fast: fail_fast || no_fail_fast,
+                },
                 params: merged_params,
             };
-            crate::cook::cook(cook_cmd).await
+            // Sessions left behind by a crash are marked interrupted before starting
+            if let Ok(cwd) = std::env::current_dir() {
+                report_orphaned_sessions_in(
+                    &cook_cmd.path.as_ref().map(|p| cwd.join(p)).unwrap_or(cwd),
+                );
+            }
+            if let Some(repos) = repos {
+                crate::cook::multi_repo::run_across_repos(cook_cmd, &repos).await
+            } else if watch {
+                crate::cook::watch::watch(cook_cmd).await
+            } else {
+                crate::cook::cook(cook_cmd).await
+            }
         }
         Some(Commands::Exec {
             command,
@@ -62,19 +120,41 @@ pub async fn execute_command(command: Option<Commands>, verbose: u8) -> Result<(
         }) => run_exec_command(command, retry, timeout, path).await,
         Some(Commands::Batch {
             pattern,
+            stdin,
             command,
             parallel,
             retry,
             timeout,
             path,
-        }) => run_batch_command(pattern, command, parallel, retry, timeout, path).await,
+        }) => run_batch_command(pattern, stdin, command, parallel, retry, timeout, path).await,
         Some(Commands::Resume {
             session_id,
             force,
             from_checkpoint,
             path,
-        }) => run_resume_workflow(session_id, force, from_checkpoint, path).await,
+            relocate,
+        }) => run_resume_workflow(session_id, force, from_checkpoint, path, relocate).await,
         Some(Commands::Checkpoints { command }) => run_checkpoints_command(command, verbose).await,
+        Some(Commands::CheckpointMark {
+            label,
+            check,
+            session,
+        }) => run_checkpoint_mark_command(&label, check, session).await,
+        Some(Commands::Docs { workflow, output }) => run_docs_command(workflow, output).await,
+        Some(Commands::Graph {
+            workflow,
+            format,
+            output,
+        }) => run_graph_command(workflow, &format, output).await,
+        Some(Commands::InspectInput {
+            workflow,
+            input,
+            show,
+            output_format,
+        }) => run_inspect_input_command(workflow, input, show, &output_format).await,
+        Some(Commands::Rollback { to_step, session }) => {
+            run_rollback_command(to_step, session).await
+        }
         Some(Commands::Worktree { command }) => run_worktree_command(command).await,
         Some(Commands::Init {
             force,
@@ -88,6 +168,23 @@ pub async fn execute_command(command: Option<Commands>, verbose: u8) -> Result<(
             };
             crate::init::run(init_cmd).await
         }
+        Some(Commands::Setup {
+            yes,
+            storage_dir,
+            max_parallel,
+            skip_claude_check,
+            path,
+        }) => {
+            run_setup_command(SetupOptions {
+                yes,
+                storage_dir,
+                max_parallel,
+                skip_claude_check,
+                path,
+            })
+            .await
+        }
+        Some(Commands::Storage { command }) => run_storage_command(command).await,
         Some(Commands::MigrateYaml {
             path,
             backup,
@@ -107,9 +204,9 @@ pub async fn execute_command(command: Option<Commands>, verbose: u8) -> Result<(
             // Print results
             for result in results {
                 if result.was_migrated {
-                    println!("✓ Migrated: {}", result.file.display());
+                    styled_println!("✓ Migrated: {}", result.file.display());
                 } else if let Some(error) = result.error {
-                    eprintln!("✗ Failed: {} - {}", result.file.display(), error);
+                    styled_eprintln!("✗ Failed: {} - {}", result.file.display(), error);
                 }
             }
 
@@ -127,9 +224,9 @@ pub async fn execute_command(command: Option<Commands>, verbose: u8) -> Result<(
 
             // Print validation results
             if result.is_valid {
-                println!("✓ Workflow is valid: {}", workflow.display());
+                styled_println!("✓ Workflow is valid: {}", workflow.display());
             } else {
-                eprintln!("✗ Workflow has issues:");
+                styled_eprintln!("✗ Workflow has issues:");
                 for issue in &result.issues {
                     eprintln!("  - {}", issue);
                 }
@@ -151,10 +248,23 @@ pub async fn execute_command(command: Option<Commands>, verbose: u8) -> Result<(
             job_id,
             force,
             max_retries,
+            max_parallel,
+            max_items,
+            item_timeout,
             path,
-        }) => run_resume_job_command(job_id, force, max_retries, path).await,
+            relocate,
+        }) => {
+            let overrides = ResumeOverrides {
+                max_parallel: max_parallel.map(|n| n as usize),
+                max_items: max_items.map(|n| n as usize),
+                agent_timeout_secs: item_timeout,
+            };
+            run_resume_job_command(job_id, force, max_retries, overrides, path, relocate).await
+        }
         Some(Commands::Events { command }) => run_events_command(command).await,
+        Some(Commands::Triggers { command }) => run_triggers_command(command).await,
         Some(Commands::Dlq { command }) => run_dlq_command(command).await,
+        Some(Commands::Jobs { command }) => run_jobs_command(command).await,
         Some(Commands::Sessions { command }) => run_sessions_command(command).await,
         Some(Commands::Progress {
             job_id,
@@ -163,6 +273,17 @@ pub async fn execute_command(command: Option<Commands>, verbose: u8) -> Result<(
             web,
         }) => run_progress_command(job_id, export, format, web).await,
         Some(Commands::Logs {
+            command:
+                Some(LogsCommands::Claude {
+                    session_id,
+                    follow,
+                    agent,
+                    item,
+                }),
+            ..
+        }) => run_claude_logs_command(&session_id, follow, agent, item).await,
+        Some(Commands::Logs {
+            command: None,
             session_id,
             latest,
             tail,
@@ -172,9 +293,57 @@ pub async fn execute_command(command: Option<Commands>, verbose: u8) -> Result<(
             let repo_path = std::env::current_dir()?;
             clean::execute(command, &repo_path).await
         }
+        Some(Commands::TestWorkflow { workflow, filter }) => {
+            crate::cook::workflow_test::run_workflow_tests(&workflow, filter.as_deref()).await
+        }
+        Some(Commands::ClaudeCommands { command }) => run_claude_commands_command(command).await,
         Some(Commands::Template { action }) => execute_template_command(action).await,
         Some(Commands::Config { command }) => run_config_command(command).await,
         Some(Commands::Changelog { command }) => run_changelog_command(command).await,
+        Some(Commands::Import { command }) => run_import_command(command).await,
+        Some(Commands::Analytics { command }) => run_analytics_command(command).await,
+        Some(Commands::Report {
+            job_id,
+            format,
+            output,
+            input_cost,
+            output_cost,
+            cache_cost,
+        }) => {
+            let rates = crate::cook::execution::job_report::CostRates {
+                input_per_mtok: input_cost,
+                output_per_mtok: output_cost,
+                cache_per_mtok: cache_cost,
+            };
+            run_report_command(&job_id, &format, output, rates).await
+        }
+        Some(Commands::Sarif {
+            job,
+            input,
+            json_path,
+            output,
+            rule_field,
+            file_field,
+            line_field,
+            message_field,
+            level_field,
+            tool_name,
+        }) => {
+            use crate::cli::commands::sarif::FindingSource;
+            let source = match (job, input) {
+                (Some(job_id), _) => FindingSource::Job(job_id),
+                (None, Some(path)) => FindingSource::File { path, json_path },
+                (None, None) => anyhow::bail!("Either --job or --input is required"),
+            };
+            let fields = crate::cook::execution::sarif::SarifFieldMap {
+                rule: rule_field,
+                file: file_field,
+                line: line_field,
+                message: message_field,
+                level: level_field,
+            };
+            run_sarif_command(source, fields, output, &tool_name).await
+        }
         None => {
             // No command provided, show help
             use crate::cli::help::generate_help;
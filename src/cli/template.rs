@@ -6,6 +6,7 @@ use crate::cook::workflow::composition::registry::{
     FileTemplateStorage, TemplateMetadata, TemplateRegistry,
 };
 use crate::cook::workflow::composition::ComposableWorkflow;
+use crate::styled_println;
 use anyhow::{Context, Result};
 use std::io::{self, Write};
 use std::path::PathBuf;
@@ -67,7 +68,7 @@ impl TemplateManager {
             .await
             .with_context(|| format!("Failed to register template '{}'", template_name))?;
 
-        println!("✅ Template '{}' registered successfully", template_name);
+        styled_println!("✅ Template '{}' registered successfully", template_name);
 
         Ok(())
     }
@@ -89,7 +90,7 @@ impl TemplateManager {
 
         if long {
             for template in templates {
-                println!("📦 {}", template.name);
+                styled_println!("📦 {}", template.name);
                 if let Some(desc) = &template.description {
                     println!("   Description: {}", desc);
                 }
@@ -117,7 +118,7 @@ impl TemplateManager {
             .await
             .with_context(|| format!("Template '{}' not found", name))?;
 
-        println!("📦 Template: {}\n", entry.name);
+        styled_println!("📦 Template: {}\n", entry.name);
 
         if let Some(desc) = &entry.metadata.description {
             println!("Description: {}", desc);
@@ -186,7 +187,7 @@ impl TemplateManager {
             .await
             .with_context(|| format!("Failed to delete template '{}'", name))?;
 
-        println!("✅ Template '{}' deleted", name);
+        styled_println!("✅ Template '{}' deleted", name);
 
         Ok(())
     }
@@ -235,22 +236,22 @@ impl TemplateManager {
         let template: ComposableWorkflow =
             serde_yaml::from_str(&content).with_context(|| "Failed to parse template YAML")?;
 
-        println!("✅ Template file is valid\n");
+        styled_println!("✅ Template file is valid\n");
 
         // Show validation details
         if template.uses_composition() {
             println!("Composition features used:");
             if template.imports.is_some() {
-                println!("  ✓ Imports");
+                styled_println!("  ✓ Imports");
             }
             if template.extends.is_some() {
-                println!("  ✓ Inheritance");
+                styled_println!("  ✓ Inheritance");
             }
             if template.template.is_some() {
-                println!("  ✓ Template reference");
+                styled_println!("  ✓ Template reference");
             }
             if template.workflows.is_some() {
-                println!("  ✓ Sub-workflows");
+                styled_println!("  ✓ Sub-workflows");
             }
         }
 
@@ -274,7 +275,7 @@ impl TemplateManager {
             .await
             .with_context(|| format!("Failed to create directory: {}", path.display()))?;
 
-        println!("✅ Created template directory: {}", path.display());
+        styled_println!("✅ Created template directory: {}", path.display());
 
         // Create example template
         let example_template = r#"# Example Refactoring Template
@@ -318,7 +319,7 @@ commands:
             .await
             .with_context(|| "Failed to write example template")?;
 
-        println!("✅ Created example template: {}", example_path.display());
+        styled_println!("✅ Created example template: {}", example_path.display());
 
         // Create README
         let readme = r#"# Workflow Templates
@@ -359,7 +360,7 @@ See refactor-example.yml for a complete example.
             .await
             .with_context(|| "Failed to write README")?;
 
-        println!("✅ Created README: {}", readme_path.display());
+        styled_println!("✅ Created README: {}", readme_path.display());
 
         Ok(())
     }
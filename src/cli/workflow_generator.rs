@@ -25,9 +25,29 @@ pub fn generate_exec_workflow(
     Ok((workflow_steps, temp_file))
 }
 
+/// Where a batch command gets its work items
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BatchInput {
+    /// Files matching a glob pattern
+    Pattern(String),
+    /// JSON array of work items, e.g. written from `--stdin`
+    Manifest(PathBuf),
+}
+
+impl BatchInput {
+    /// The map phase `input` for this source (pure function)
+    fn map_input(&self) -> String {
+        match self {
+            // Use find command to generate file list as input
+            BatchInput::Pattern(pattern) => format!("find . -name '{}'", pattern),
+            BatchInput::Manifest(path) => path.to_string_lossy().to_string(),
+        }
+    }
+}
+
 /// Generate a workflow configuration for batch command
 pub fn generate_batch_workflow(
-    pattern: &str,
+    input: &BatchInput,
     command: &str,
     parallel: usize,
     retry: Option<u32>,
@@ -37,7 +57,7 @@ pub fn generate_batch_workflow(
     let agent_step = create_workflow_step(command, retry, timeout);
 
     // Build the MapReduce configuration functionally
-    let map_config = build_map_config(pattern, parallel, agent_step)?;
+    let map_config = build_map_config(input, parallel, agent_step)?;
     let reduce_config = build_reduce_config();
     let workflow = build_mapreduce_workflow(map_config, reduce_config);
 
@@ -56,17 +76,15 @@ fn create_workflow_step(command: &str, retry: Option<u32>, timeout: Option<u64>)
 
 /// Build the map configuration for MapReduce
 fn build_map_config(
-    pattern: &str,
+    input: &BatchInput,
     parallel: usize,
     agent_step: WorkflowStep,
 ) -> Result<serde_yaml::Mapping> {
     let mut map_config = serde_yaml::Mapping::new();
 
-    // Use find command to generate file list as input
-    let find_command = format!("find . -name '{}'", pattern);
     map_config.insert(
         Value::String("input".to_string()),
-        Value::String(find_command),
+        Value::String(input.map_input()),
     );
     // Ensure max_parallel is at least 1 (0 means use default of 5)
     let effective_parallel = if parallel == 0 { 5 } else { parallel };
@@ -189,6 +207,17 @@ fn create_temp_workflow_yaml(workflow: &Value) -> Result<PathBuf> {
     Ok(path)
 }
 
+/// Write batch work items to a temporary JSON manifest
+pub fn create_batch_manifest(items: &[serde_json::Value]) -> Result<PathBuf> {
+    let mut temp_file = NamedTempFile::with_suffix(".json")?;
+    use std::io::Write;
+    temp_file.write_all(serde_json::to_string(items)?.as_bytes())?;
+
+    // Convert to a persistent temporary file that we manage
+    let (_, path) = temp_file.keep()?;
+    Ok(path)
+}
+
 /// Workflow step structure matching Prodigy's actual format
 #[derive(Debug, Serialize, Deserialize, Default)]
 pub struct WorkflowStep {
@@ -268,8 +297,14 @@ mod tests {
 
     #[test]
     fn test_generate_batch_workflow() {
-        let (workflow_value, _path) =
-            generate_batch_workflow("*.py", "claude: /add-types", 5, Some(2), Some(60)).unwrap();
+        let (workflow_value, _path) = generate_batch_workflow(
+            &BatchInput::Pattern("*.py".to_string()),
+            "claude: /add-types",
+            5,
+            Some(2),
+            Some(60),
+        )
+        .unwrap();
         // Test that it's a mapreduce workflow
         if let Value::Mapping(ref map) = workflow_value {
             assert!(map.contains_key(Value::String("name".to_string())));
@@ -292,4 +327,32 @@ mod tests {
             panic!("Expected a mapping for batch workflow");
         }
     }
+
+    #[test]
+    fn test_generate_batch_workflow_from_manifest() {
+        let items = vec![
+            serde_json::json!({"item": "src/a.rs"}),
+            serde_json::json!({"item": "src/b.rs"}),
+        ];
+        let manifest = create_batch_manifest(&items).unwrap();
+        let _cleanup = TemporaryWorkflow {
+            path: manifest.clone(),
+        };
+        let written: Vec<serde_json::Value> =
+            serde_json::from_str(&std::fs::read_to_string(&manifest).unwrap()).unwrap();
+        assert_eq!(written, items);
+
+        let (workflow_value, _path) = generate_batch_workflow(
+            &BatchInput::Manifest(manifest.clone()),
+            "shell: wc -l ${item}",
+            2,
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            workflow_value["map"]["input"],
+            Value::String(manifest.to_string_lossy().to_string())
+        );
+    }
 }
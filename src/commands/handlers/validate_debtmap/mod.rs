@@ -6,6 +6,7 @@
 use crate::commands::{
     AttributeSchema, AttributeValue, CommandHandler, CommandResult, ExecutionContext,
 };
+use crate::styled_println;
 use async_trait::async_trait;
 use serde_json::{json, Value};
 use std::collections::HashMap;
@@ -180,7 +181,7 @@ impl ValidateDebtmapHandler {
         }
         println!("{}", header);
         for item in items {
-            println!("  • {}", item);
+            styled_println!("  • {}", item);
         }
     }
 
@@ -191,9 +192,11 @@ impl ValidateDebtmapHandler {
         }
         println!("\nGaps to Address ({}):", gaps.len());
         for gap in gaps.values() {
-            println!(
+            styled_println!(
                 "  • [{}] {} at {}",
-                gap.severity, gap.description, gap.location
+                gap.severity,
+                gap.description,
+                gap.location
             );
         }
     }
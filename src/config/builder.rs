@@ -40,6 +40,7 @@
 use super::prodigy_config::{global_config_path, project_config_path, ProdigyConfig};
 use premortem::config::Config;
 use premortem::prelude::*;
+use std::path::Path;
 
 /// Load Prodigy configuration from all sources using real I/O.
 ///
@@ -107,9 +108,24 @@ pub fn load_prodigy_config() -> Result<Config<ProdigyConfig>, ConfigErrors> {
 /// ```
 pub fn load_prodigy_config_with<E: ConfigEnv>(
     env: &E,
+) -> Result<Config<ProdigyConfig>, ConfigErrors> {
+    load_prodigy_config_from(env, &project_config_path())
+}
+
+/// Load Prodigy configuration with the project config file at `project_path`.
+///
+/// Runs outside the current directory, such as in a monorepo package or in
+/// one repository of a multi-repo run, pass their own project config here
+/// (see [`project_config_path_in`](super::project_config_path_in)).
+pub fn load_prodigy_config_at(project_path: &Path) -> Result<Config<ProdigyConfig>, ConfigErrors> {
+    load_prodigy_config_from(&RealEnv, project_path)
+}
+
+fn load_prodigy_config_from<E: ConfigEnv>(
+    env: &E,
+    project_path: &Path,
 ) -> Result<Config<ProdigyConfig>, ConfigErrors> {
     let global_path = global_config_path();
-    let project_path = project_config_path();
 
     // First, build with file sources and structured env vars
     let mut builder = Config::<ProdigyConfig>::builder()
@@ -345,6 +361,61 @@ max_concurrent_specs: 8
         assert_eq!(config.max_concurrent_specs, 8);
     }
 
+    #[test]
+    fn test_load_prompt_policies() {
+        use crate::cook::interaction::{PromptKind, PromptPolicy};
+
+        let global_path = global_config_path();
+        let env = MockEnv::new().with_file(
+            global_path.to_string_lossy().to_string(),
+            r#"
+prompts:
+  merge_confirmation: decline
+  text: fail
+"#,
+        );
+
+        let config = load_prodigy_config_with(&env).unwrap();
+
+        assert_eq!(
+            config.prompts.get(&PromptKind::MergeConfirmation),
+            Some(&PromptPolicy::Decline)
+        );
+        assert_eq!(
+            config.prompts.get(&PromptKind::Text),
+            Some(&PromptPolicy::Fail)
+        );
+        assert_eq!(config.prompts.get(&PromptKind::Choice), None);
+    }
+
+    #[test]
+    fn test_load_output_settings() {
+        use crate::config::TruncationStrategy;
+
+        let config = load_prodigy_config_with(&MockEnv::new()).unwrap();
+        assert_eq!(config.output.max_capture_bytes, Some(64 * 1024));
+        assert_eq!(config.output.truncation, TruncationStrategy::HeadTail);
+
+        let global_path = global_config_path();
+        let env = MockEnv::new().with_file(
+            global_path.to_string_lossy().to_string(),
+            r#"
+output:
+  max_capture_bytes: 4096
+  truncation: tail
+"#,
+        );
+        let config = load_prodigy_config_with(&env).unwrap();
+        assert_eq!(config.output.max_capture_bytes, Some(4096));
+        assert_eq!(config.output.truncation, TruncationStrategy::Tail);
+
+        let env = MockEnv::new().with_file(
+            global_path.to_string_lossy().to_string(),
+            "output:\n  max_capture_bytes: 10\n",
+        );
+        assert!(load_prodigy_config_with(&env).is_err());
+    }
+
     #[test]
     fn test_load_with_env_override() {
         let global_path = global_config_path();
@@ -382,6 +453,26 @@ project:
         );
     }
 
+    #[test]
+    fn test_load_with_project_config_at_path() {
+        let package_config = Path::new("/repo/crates/foo/.prodigy/config.yml");
+        let env = MockEnv::new()
+            .with_file(
+                project_config_path().to_string_lossy().to_string(),
+                "log_level: warn",
+            )
+            .with_file(
+                package_config.to_string_lossy().to_string(),
+                "log_level: debug",
+            );
+
+        let config = load_prodigy_config_from(&env, package_config).unwrap();
+        assert_eq!(config.log_level, "debug");
+
+        let config = load_prodigy_config_with(&env).unwrap();
+        assert_eq!(config.log_level, "warn");
+    }
+
     #[test]
     fn test_load_with_options_skip_global() {
         let global_path = global_config_path();
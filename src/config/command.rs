@@ -166,7 +166,8 @@ pub struct OutputDeclaration {
 /// Configuration for test debugging on failure
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct TestDebugConfig {
-    /// Claude command to run on test failure
+    /// Claude command to run on test failure (may be empty when only escalating)
+    #[serde(default)]
     pub claude: String,
 
     /// Maximum number of retry attempts
@@ -180,6 +181,17 @@ pub struct TestDebugConfig {
     /// Whether the debug command should create commits
     #[serde(default = "default_true")]
     pub commit_required: bool,
+
+    /// Model to retry a failed Claude step with before giving up
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub escalate_model: Option<String>,
+}
+
+impl TestDebugConfig {
+    /// Debug command to run on failure, if one is configured
+    pub fn debug_command(&self) -> Option<String> {
+        Some(self.claude.clone()).filter(|c| !c.trim().is_empty())
+    }
 }
 
 fn default_max_attempts() -> u32 {
@@ -317,12 +329,24 @@ fn default_file_mode() -> String {
 }
 
 /// New workflow step command format supporting claude:, shell:, analyze:, and test: syntax
-#[derive(Debug, Clone, Serialize, PartialEq)]
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
 pub struct WorkflowStepCommand {
     /// Claude CLI command with args
     #[serde(skip_serializing_if = "Option::is_none")]
     pub claude: Option<String>,
 
+    /// Model passed to the Claude CLI for this step
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+
+    /// Repository files injected into the Claude command
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context: Option<crate::cook::workflow::context_pack::ContextPackConfig>,
+
+    /// Contract the Claude response must satisfy, re-prompting on failure
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub validate_output: Option<crate::cook::workflow::output_validation::OutputValidationConfig>,
+
     /// Shell command to execute
     #[serde(skip_serializing_if = "Option::is_none")]
     pub shell: Option<String>,
@@ -343,6 +367,30 @@ pub struct WorkflowStepCommand {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub write_file: Option<WriteFileConfig>,
 
+    /// Git and filesystem state assertions
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assert: Option<crate::cook::workflow::assertions::AssertConfig>,
+
+    /// Summary of the commits made during the workflow
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summarize_commits: Option<crate::cook::workflow::commit_summary::SummarizeCommitsConfig>,
+
+    /// Messages sent to Claude within one conversation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub claude_chat: Option<crate::cook::workflow::claude_chat::ClaudeChatConfig>,
+
+    /// Inline script run with the selected interpreter
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub script: Option<crate::cook::workflow::script::ScriptConfig>,
+
+    /// Record a snapshot of the working tree before the step runs
+    #[serde(default)]
+    pub snapshot: bool,
+
+    /// Fail a `--keep-going` run if this step fails
+    #[serde(default)]
+    pub required: bool,
+
     /// Command ID for referencing outputs
     #[serde(skip_serializing_if = "Option::is_none")]
     pub id: Option<String>,
@@ -351,6 +399,10 @@ pub struct WorkflowStepCommand {
     #[serde(default)]
     pub commit_required: bool,
 
+    /// Commit counts, paths and messages checked when `commit_required` is a policy
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit_policy: Option<crate::cook::commit_tracker::CommitPolicy>,
+
     /// Analysis configuration
     #[serde(skip_serializing_if = "Option::is_none")]
     pub analysis: Option<AnalysisConfig>,
@@ -379,6 +431,14 @@ pub struct WorkflowStepCommand {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub timeout: Option<u64>,
 
+    /// Working directory for this step, relative to the worktree
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<String>,
+
+    /// Environment variables set for this step only
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub env: HashMap<String, String>,
+
     /// Conditional execution expression
     #[serde(skip_serializing_if = "Option::is_none")]
     pub when: Option<String>,
@@ -406,6 +466,16 @@ pub enum CaptureOutputConfig {
     Variable(String),
 }
 
+/// Value of a step's `commit_required` field
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub enum CommitRequirement {
+    /// Whether at least one commit is required
+    Required(bool),
+    /// Commits verified against a policy
+    Policy(crate::cook::commit_tracker::CommitPolicy),
+}
+
 impl<'de> Deserialize<'de> for WorkflowStepCommand {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -414,14 +484,26 @@ impl<'de> Deserialize<'de> for WorkflowStepCommand {
         #[derive(Deserialize)]
         struct Helper {
             claude: Option<String>,
+            model: Option<String>,
+            context: Option<crate::cook::workflow::context_pack::ContextPackConfig>,
+            validate_output:
+                Option<crate::cook::workflow::output_validation::OutputValidationConfig>,
             shell: Option<String>,
             analyze: Option<HashMap<String, serde_json::Value>>,
             test: Option<TestCommand>,
             foreach: Option<ForeachConfig>,
             write_file: Option<WriteFileConfig>,
-            id: Option<String>,
+            assert: Option<crate::cook::workflow::assertions::AssertConfig>,
+            summarize_commits:
+                Option<crate::cook::workflow::commit_summary::SummarizeCommitsConfig>,
+            claude_chat: Option<crate::cook::workflow::claude_chat::ClaudeChatConfig>,
+            script: Option<crate::cook::workflow::script::ScriptConfig>,
+            #[serde(default)]
+            snapshot: bool,
             #[serde(default)]
-            commit_required: bool,
+            required: bool,
+            id: Option<String>,
+            commit_required: Option<CommitRequirement>,
             analysis: Option<AnalysisConfig>,
             outputs: Option<HashMap<String, OutputDeclaration>>,
             #[serde(default)]
@@ -430,6 +512,10 @@ impl<'de> Deserialize<'de> for WorkflowStepCommand {
             on_success: Option<Box<WorkflowStepCommand>>,
             validate: Option<crate::cook::workflow::validation::ValidationConfig>,
             timeout: Option<u64>,
+            #[serde(alias = "working_dir")]
+            cwd: Option<String>,
+            #[serde(default)]
+            env: HashMap<String, String>,
             when: Option<String>,
             capture_format: Option<String>,
             capture_streams: Option<String>,
@@ -438,6 +524,12 @@ impl<'de> Deserialize<'de> for WorkflowStepCommand {
 
         let helper = Helper::deserialize(deserializer)?;
 
+        let (commit_required, commit_policy) = match helper.commit_required {
+            Some(CommitRequirement::Required(required)) => (required, None),
+            Some(CommitRequirement::Policy(policy)) => (true, Some(policy)),
+            None => (false, None),
+        };
+
         // Handle deprecated test command - convert to shell with on_failure
         let (shell, test, on_failure) = if let Some(test_cmd) = helper.test {
             // Show deprecation warning
@@ -463,21 +555,35 @@ impl<'de> Deserialize<'de> for WorkflowStepCommand {
             && helper.analyze.is_none()
             && helper.foreach.is_none()
             && helper.write_file.is_none()
+            && helper.assert.is_none()
+            && helper.summarize_commits.is_none()
+            && helper.claude_chat.is_none()
+            && helper.script.is_none()
         {
             return Err(serde::de::Error::custom(
-                "WorkflowStepCommand must have 'claude', 'shell', 'analyze', 'foreach', or 'write_file' field",
+                "WorkflowStepCommand must have 'claude', 'shell', 'analyze', 'foreach', 'write_file', 'assert', 'summarize_commits', 'claude_chat', or 'script' field",
             ));
         }
 
         Ok(WorkflowStepCommand {
             claude: helper.claude,
+            model: helper.model,
+            context: helper.context,
+            validate_output: helper.validate_output,
             shell,
             analyze: helper.analyze,
             test,
             foreach: helper.foreach,
             write_file: helper.write_file,
+            assert: helper.assert,
+            summarize_commits: helper.summarize_commits,
+            claude_chat: helper.claude_chat,
+            script: helper.script,
+            snapshot: helper.snapshot,
+            required: helper.required,
             id: helper.id,
-            commit_required: helper.commit_required,
+            commit_required,
+            commit_policy,
             analysis: helper.analysis,
             outputs: helper.outputs,
             capture_output: helper.capture_output,
@@ -485,6 +591,8 @@ impl<'de> Deserialize<'de> for WorkflowStepCommand {
             on_success: helper.on_success,
             validate: helper.validate,
             timeout: helper.timeout,
+            cwd: helper.cwd,
+            env: helper.env,
             when: helper.when,
             capture_format: helper.capture_format,
             capture_streams: helper.capture_streams,
@@ -551,6 +659,14 @@ fn extract_command_string(step: &WorkflowStepCommand) -> String {
     } else if let Some(write_file_config) = &step.write_file {
         // For write_file commands
         format!("write_file {}", write_file_config.path)
+    } else if step.assert.is_some() {
+        "assert".to_string()
+    } else if step.summarize_commits.is_some() {
+        "summarize_commits".to_string()
+    } else if step.claude_chat.is_some() {
+        "claude_chat".to_string()
+    } else if let Some(script) = &step.script {
+        format!("script: {}", script.lang)
     } else {
         // No command specified
         String::new()
@@ -840,6 +956,46 @@ mod tests {
         assert!(!cmd.metadata.commit_required);
     }
 
+    #[test]
+    fn test_commit_required_policy() {
+        let step: WorkflowStepCommand =
+            serde_yaml::from_str("claude: \"/fix\"\ncommit_required: true\n").unwrap();
+        assert!(step.commit_required);
+        assert!(step.commit_policy.is_none());
+
+        let yaml = r#"
+claude: "/fix"
+commit_required:
+  max_commits: 2
+  paths: ["src/"]
+  message_pattern: "^fix:"
+"#;
+        let step: WorkflowStepCommand = serde_yaml::from_str(yaml).unwrap();
+        assert!(step.commit_required);
+        let policy = step.commit_policy.unwrap();
+        assert_eq!(policy.min_commits, None);
+        assert_eq!(policy.max_commits, Some(2));
+        assert_eq!(policy.paths, vec!["src/"]);
+        assert_eq!(policy.message_pattern.as_deref(), Some("^fix:"));
+    }
+
+    #[test]
+    fn test_step_cwd_and_env() {
+        let yaml = r#"
+shell: "npm test"
+cwd: frontend
+env:
+  NODE_ENV: test
+"#;
+        let step: WorkflowStepCommand = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(step.cwd.as_deref(), Some("frontend"));
+        assert_eq!(step.env.get("NODE_ENV").map(String::as_str), Some("test"));
+
+        let step: WorkflowStepCommand =
+            serde_yaml::from_str("shell: \"ls\"\nworking_dir: backend\n").unwrap();
+        assert_eq!(step.cwd.as_deref(), Some("backend"));
+    }
+
     #[test]
     fn test_commit_required_serialization() {
         // Test serialization and deserialization of SimpleCommand with commit_required
@@ -963,11 +1119,95 @@ when: "${build.success} == true"
         assert_eq!(step.when, Some("${build.success} == true".to_string()));
     }
 
+    #[test]
+    fn test_workflow_step_command_with_context() {
+        let yaml = r#"
+claude: "/prodigy-implement"
+context:
+  files: ["ARCHITECTURE.md", "src/config/*.rs"]
+  max_bytes: 4000
+"#;
+
+        let step: WorkflowStepCommand = serde_yaml::from_str(yaml).unwrap();
+        let spec = step.context.expect("context should parse").spec();
+        assert_eq!(spec.files, vec!["ARCHITECTURE.md", "src/config/*.rs"]);
+        assert_eq!(spec.max_bytes, 4000);
+    }
+
+    #[test]
+    fn test_workflow_step_command_with_assert() {
+        let yaml = r#"
+assert:
+  branch_clean: true
+  commits_since: "origin/main >= 1"
+"#;
+
+        let step: WorkflowStepCommand = serde_yaml::from_str(yaml).unwrap();
+        let assert_config = step.assert.clone().expect("assert should parse");
+        assert!(assert_config.branch_clean);
+        assert_eq!(
+            assert_config.commits_since.as_deref(),
+            Some("origin/main >= 1")
+        );
+        assert_eq!(extract_command_string(&step), "assert");
+    }
+
+    #[test]
+    fn test_workflow_step_command_with_summarize_commits() {
+        let yaml = r#"
+summarize_commits:
+  variable: changelog
+  claude: "/prodigy-polish-changelog"
+"#;
+
+        let step: WorkflowStepCommand = serde_yaml::from_str(yaml).unwrap();
+        let config = step
+            .summarize_commits
+            .clone()
+            .expect("summarize_commits should parse");
+        assert_eq!(config.variable_name(), "changelog");
+        assert_eq!(config.claude.as_deref(), Some("/prodigy-polish-changelog"));
+        assert_eq!(extract_command_string(&step), "summarize_commits");
+    }
+
+    #[test]
+    fn test_workflow_step_command_with_claude_chat() {
+        let yaml = r#"
+claude_chat:
+  messages:
+    - "/prodigy-review"
+    - "Fix what you found"
+"#;
+
+        let step: WorkflowStepCommand = serde_yaml::from_str(yaml).unwrap();
+        let config = step.claude_chat.clone().expect("claude_chat should parse");
+        assert_eq!(config.messages.len(), 2);
+        assert_eq!(extract_command_string(&step), "claude_chat");
+    }
+
+    #[test]
+    fn test_workflow_step_command_with_script() {
+        let yaml = r#"
+script:
+  lang: python
+  code: |
+    print("hello")
+"#;
+
+        let step: WorkflowStepCommand = serde_yaml::from_str(yaml).unwrap();
+        let config = step.script.clone().expect("script should parse");
+        assert_eq!(config.code, "print(\"hello\")\n");
+        assert_eq!(extract_command_string(&step), "script: python");
+    }
+
     #[test]
     fn test_conditional_workflow_serialization() {
         // Test serialization and deserialization of when clauses
         let step = WorkflowStepCommand {
             claude: Some("/prodigy-test".to_string()),
+            model: None,
+            context: None,
+            validate_output: None,
             shell: None,
             analyze: None,
             test: None,
@@ -986,6 +1226,15 @@ when: "${build.success} == true"
             capture_format: None,
             capture_streams: None,
             output_file: None,
+            assert: None,
+            summarize_commits: None,
+            claude_chat: None,
+            script: None,
+            snapshot: false,
+            required: false,
+            commit_policy: None,
+            cwd: None,
+            env: HashMap::new(),
         };
 
         let yaml = serde_yaml::to_string(&step).unwrap();
@@ -0,0 +1,276 @@
+//! Catalog of available Claude commands
+//!
+//! `prodigy commands ls` lists the slash commands a workflow can call: every
+//! markdown file in `.claude/commands/` plus the command templates bundled
+//! with prodigy that `prodigy init` has not installed yet. Descriptions and
+//! arguments come from the file's frontmatter (`description`, `arguments`,
+//! `options`, `argument-hint`), falling back to the `## Variables` section and
+//! the first paragraph of the file.
+
+use super::command_discovery::CommandFile;
+use super::command_validator::CommandDefinition;
+use super::metadata_parser::MetadataParser;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Where a listed command comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CommandSource {
+    /// Installed in the project's `.claude/commands/`
+    Project,
+    /// Bundled template, installed with `prodigy init`
+    Template,
+}
+
+/// One available command
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CatalogEntry {
+    /// Name used to invoke the command, without the leading `/`
+    pub name: String,
+    pub description: String,
+    /// Expected arguments, e.g. `<spec_id> [notes] [--dry-run]`
+    pub arguments: String,
+    pub source: CommandSource,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+}
+
+/// Frontmatter keys the command definition does not carry
+#[derive(Debug, Default, Deserialize)]
+struct CatalogFrontmatter {
+    #[serde(rename = "argument-hint")]
+    argument_hint: Option<String>,
+}
+
+/// Build the catalog entry of a command file (pure function)
+pub fn describe(
+    parser: &MetadataParser,
+    file: &CommandFile,
+    source: CommandSource,
+) -> CatalogEntry {
+    let definition = parser
+        .parse_command_file(file)
+        .unwrap_or_else(|_| parser.create_minimal_definition(file));
+    let description = if definition.description.is_empty() {
+        first_paragraph(&file.content)
+    } else {
+        definition.description.clone()
+    };
+    let arguments = split_frontmatter(&file.content)
+        .0
+        .and_then(|yaml| serde_yaml::from_str::<CatalogFrontmatter>(yaml).ok())
+        .and_then(|front| front.argument_hint)
+        .unwrap_or_else(|| format_arguments(&definition));
+
+    CatalogEntry {
+        name: file.name.clone(),
+        description,
+        arguments,
+        source,
+        path: (source == CommandSource::Project).then(|| file.path.clone()),
+    }
+}
+
+/// Usage string of a command's arguments and options (pure function)
+pub fn format_arguments(definition: &CommandDefinition) -> String {
+    let required = definition
+        .required_args
+        .iter()
+        .map(|arg| format!("<{}>", arg.name));
+    let optional = definition
+        .optional_args
+        .iter()
+        .map(|arg| format!("[{}]", arg.name));
+    let options = definition
+        .options
+        .iter()
+        .map(|opt| format!("[--{}]", opt.name));
+    required
+        .chain(optional)
+        .chain(options)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Split a command file into its frontmatter YAML and body (pure function)
+fn split_frontmatter(content: &str) -> (Option<&str>, &str) {
+    let Some(rest) = content.strip_prefix("---\n") else {
+        return (None, content);
+    };
+    match rest.find("\n---") {
+        Some(end) => (Some(&rest[..end]), &rest[end + 4..]),
+        None => (None, content),
+    }
+}
+
+/// First paragraph of prose after the frontmatter and title (pure function)
+fn first_paragraph(content: &str) -> String {
+    split_frontmatter(content)
+        .1
+        .lines()
+        .map(str::trim)
+        .skip_while(|line| line.is_empty() || line.starts_with('#'))
+        .take_while(|line| !line.is_empty() && !line.starts_with('#'))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Commands installed in a `.claude/commands` directory
+pub async fn scan_project_commands(commands_dir: &Path) -> Result<Vec<CatalogEntry>> {
+    if !commands_dir.exists() {
+        return Ok(vec![]);
+    }
+    let parser = MetadataParser::new();
+    let mut entries = Vec::new();
+    let mut dir = tokio::fs::read_dir(commands_dir).await.with_context(|| {
+        format!(
+            "Failed to read commands directory: {}",
+            commands_dir.display()
+        )
+    })?;
+    while let Some(entry) = dir.next_entry().await? {
+        let path = entry.path();
+        if path.extension().is_none_or(|ext| ext != "md") {
+            continue;
+        }
+        let Some(name) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        let content = tokio::fs::read_to_string(&path)
+            .await
+            .with_context(|| format!("Failed to read command file: {}", path.display()))?;
+        let file = CommandFile {
+            name: name.to_string(),
+            path,
+            content,
+            modified: SystemTime::UNIX_EPOCH,
+        };
+        entries.push(describe(&parser, &file, CommandSource::Project));
+    }
+    entries.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(entries)
+}
+
+/// Project commands followed by the bundled templates not installed yet
+pub async fn catalog(commands_dir: &Path) -> Result<Vec<CatalogEntry>> {
+    let mut entries = scan_project_commands(commands_dir).await?;
+    let parser = MetadataParser::new();
+    let mut templates: Vec<CatalogEntry> = crate::init::templates::get_all_templates()
+        .into_iter()
+        .filter(|template| !entries.iter().any(|e| e.name == template.name))
+        .map(|template| {
+            let file = CommandFile {
+                path: PathBuf::new(),
+                name: template.name.to_string(),
+                content: template.content.to_string(),
+                modified: SystemTime::UNIX_EPOCH,
+            };
+            describe(&parser, &file, CommandSource::Template)
+        })
+        .collect();
+    templates.sort_by(|a, b| a.name.cmp(&b.name));
+    entries.extend(templates);
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn file(name: &str, content: &str) -> CommandFile {
+        CommandFile {
+            path: PathBuf::from(format!(".claude/commands/{}.md", name)),
+            name: name.to_string(),
+            content: content.to_string(),
+            modified: SystemTime::UNIX_EPOCH,
+        }
+    }
+
+    #[test]
+    fn test_describe_frontmatter_arguments() {
+        let entry = describe(
+            &MetadataParser::new(),
+            &file(
+                "prodigy-fix",
+                "---\ndescription: Fix a finding\narguments:\n  - name: finding\n    required: true\n  - name: notes\noptions:\n  - name: dry-run\n---\n\n# Fix\n",
+            ),
+            CommandSource::Project,
+        );
+        assert_eq!(entry.description, "Fix a finding");
+        assert_eq!(entry.arguments, "<finding> [notes] [--dry-run]");
+        assert_eq!(
+            entry.path,
+            Some(PathBuf::from(".claude/commands/prodigy-fix.md"))
+        );
+    }
+
+    #[test]
+    fn test_describe_argument_hint_and_args_alias() {
+        let parser = MetadataParser::new();
+        let hinted = describe(
+            &parser,
+            &file(
+                "review",
+                "---\ndescription: Review a PR\nargument-hint: <pr-number> [focus]\n---\n",
+            ),
+            CommandSource::Project,
+        );
+        assert_eq!(hinted.arguments, "<pr-number> [focus]");
+
+        let aliased = describe(
+            &parser,
+            &file(
+                "compare",
+                "---\ndescription: Compare results\nargs:\n  - name: before\n    required: true\n---\n",
+            ),
+            CommandSource::Project,
+        );
+        assert_eq!(aliased.arguments, "<before>");
+    }
+
+    #[test]
+    fn test_describe_without_frontmatter_uses_first_paragraph() {
+        let entry = describe(
+            &MetadataParser::new(),
+            &file(
+                "prodigy-lint",
+                "# PRODIGY Lint Command\n\nFormat and lint\nthe code.\n\n## Role\nLinter\n",
+            ),
+            CommandSource::Template,
+        );
+        assert_eq!(entry.description, "Format and lint the code.");
+        assert_eq!(entry.arguments, "");
+        assert_eq!(entry.path, None);
+    }
+
+    #[tokio::test]
+    async fn test_catalog_lists_project_commands_then_templates() {
+        let temp = TempDir::new().unwrap();
+        std::fs::write(
+            temp.path().join("prodigy-lint.md"),
+            "---\ndescription: Project lint\n---\n",
+        )
+        .unwrap();
+        std::fs::write(temp.path().join("notes.txt"), "not a command").unwrap();
+        std::fs::write(temp.path().join("deploy.md"), "# Deploy\n\nShip it.\n").unwrap();
+
+        let entries = catalog(temp.path()).await.unwrap();
+        let project: Vec<_> = entries
+            .iter()
+            .take_while(|e| e.source == CommandSource::Project)
+            .map(|e| e.name.as_str())
+            .collect();
+        assert_eq!(project, vec!["deploy", "prodigy-lint"]);
+        assert!(entries
+            .iter()
+            .any(|e| e.source == CommandSource::Template && e.name == "prodigy-code-review"));
+        assert_eq!(
+            entries.iter().filter(|e| e.name == "prodigy-lint").count(),
+            1
+        );
+    }
+}
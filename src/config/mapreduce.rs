@@ -2,10 +2,22 @@
 //!
 //! Handles parsing of MapReduce workflow YAML files.
 
+use crate::config::RunDefaults;
+use crate::cook::concurrency::ConcurrencyConfig;
 use crate::cook::environment::{EnvProfile, SecretValue};
+use crate::cook::execution::claude_permissions::{ClaudePermissions, PermissionProfile};
+use crate::cook::execution::mapreduce::affinity;
+use crate::cook::execution::mapreduce::idempotency::{deserialize_idempotency, IdempotencyConfig};
+use crate::cook::execution::mapreduce::issue_tracker::NotificationsConfig;
+use crate::cook::execution::mapreduce::item_key::ItemKeyExpr;
+use crate::cook::execution::mapreduce::network_isolation::AgentNetwork;
+use crate::cook::execution::mapreduce::setup_artifacts::validate_artifact_pattern;
+use crate::cook::execution::mapreduce::shared_context::SharedContextConfig;
 use crate::cook::execution::variable_capture::CaptureConfig;
 use crate::cook::execution::{MapPhase, MapReduceConfig, ReducePhase, SetupPhase};
+use crate::cook::protected_paths::ProtectedPaths;
 use crate::cook::workflow::{WorkflowErrorPolicy, WorkflowStep};
+use crate::worktree::build_cache::BuildCacheEntry;
 use anyhow::Context as _;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -79,6 +91,35 @@ pub struct MapReduceWorkflowConfig {
     /// Optional custom merge workflow for worktree integration
     #[serde(skip_serializing_if = "Option::is_none")]
     pub merge: Option<MergeWorkflow>,
+
+    /// Notifications sent when the job ends
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notifications: Option<NotificationsConfig>,
+
+    /// Concurrency group that queues overlapping runs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub concurrency: Option<ConcurrencyConfig>,
+
+    /// Tool permissions for Claude commands, and for agents unless `map` overrides them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub claude_permissions: Option<ClaudePermissions>,
+
+    /// Cleanup steps that run after the job even when it fails or is cancelled
+    #[serde(default, alias = "finally", skip_serializing_if = "Vec::is_empty")]
+    pub always: Vec<WorkflowStep>,
+
+    /// Defaults for `prodigy run` flags that the command line has not set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub defaults: Option<RunDefaults>,
+
+    /// Paths steps, including agent steps, may not change
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub protected_paths: Option<ProtectedPaths>,
+
+    /// Fail steps, including agent and reduce steps, that reference undefined
+    /// `${...}` variables, overriding the global default
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strict_variables: Option<bool>,
 }
 
 /// Custom merge workflow configuration
@@ -136,6 +177,7 @@ fn default_mode() -> String {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SetupPhaseConfig {
     /// Commands to execute during setup
+    #[serde(default)]
     pub commands: Vec<WorkflowStep>,
 
     /// Timeout for the entire setup phase (in seconds)
@@ -155,6 +197,15 @@ pub struct SetupPhaseConfig {
         deserialize_with = "deserialize_capture_outputs"
     )]
     pub capture_outputs: HashMap<String, CaptureConfig>,
+
+    /// Files generated by setup to copy into every agent worktree,
+    /// e.g. `debtmap.json` or `coverage/**/*.json`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub artifacts: Vec<String>,
+
+    /// Context computed once per commit and shared with every agent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shared_context: Option<SharedContextConfig>,
 }
 
 /// Custom deserializer for capture_outputs that supports both legacy and new format
@@ -214,6 +265,8 @@ where
                 commands,
                 timeout: None,
                 capture_outputs: HashMap::new(),
+                artifacts: Vec::new(),
+                shared_context: None,
             }))
         }
         Some(SetupValue::Config(config)) => Ok(Some(config)),
@@ -234,11 +287,14 @@ pub struct MapPhaseYaml {
     pub agent_template: AgentTemplate,
 
     /// Maximum parallel agents (can be a number or environment variable reference)
+    ///
+    /// Defaults to `default_max_parallel` from the configuration, or 10.
     #[serde(
-        default = "default_max_parallel_string",
-        deserialize_with = "deserialize_usize_or_string"
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_optional_usize_or_string"
     )]
-    pub max_parallel: String,
+    pub max_parallel: Option<String>,
 
     /// Optional filter expression
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -260,6 +316,16 @@ pub struct MapPhaseYaml {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub distinct: Option<String>,
 
+    /// Git ref (e.g. `origin/main`); only items referring to files changed
+    /// on HEAD since it diverged from this ref are processed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub changed_since: Option<String>,
+
+    /// Field holding each item's file path for `changed_since`
+    /// (defaults to any string field of the item)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub changed_path: Option<String>,
+
     /// Agent timeout in seconds (can be a number or environment variable reference)
     #[serde(
         default,
@@ -271,17 +337,59 @@ pub struct MapPhaseYaml {
     /// Timeout configuration
     #[serde(skip_serializing_if = "Option::is_none")]
     pub timeout_config: Option<crate::cook::execution::mapreduce::timeout::TimeoutConfig>,
-}
 
-fn default_max_parallel_string() -> String {
-    "10".to_string()
+    /// Branch to rebase each agent's commits onto before merging, so late
+    /// merges pick up commits made there during a long map phase
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rebase_onto: Option<String>,
+
+    /// Launch backup agents for items running much longer than the median
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "crate::cook::execution::mapreduce::speculative::deserialize_speculative"
+    )]
+    pub speculative: Option<crate::cook::execution::mapreduce::speculative::SpeculativeConfig>,
+
+    /// Expression deriving a stable item ID from each work item,
+    /// e.g. `$.location.file + :: + $.location.function`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub item_key: Option<String>,
+
+    /// Number of consecutive work items each agent processes together
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub batch_size: Option<usize>,
+
+    /// Expressions such as `$.location.file`; items with the same value
+    /// never run at the same time
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub affinity: Vec<String>,
+
+    /// Directories such as `target` shared across agent worktrees
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub build_cache: Vec<BuildCacheEntry>,
+
+    /// Tool permissions for agent Claude commands
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub claude_permissions: Option<ClaudePermissions>,
+
+    /// Network access for agent shell commands, `allow` or `deny`
+    #[serde(default, skip_serializing_if = "AgentNetwork::is_allow")]
+    pub network: AgentNetwork,
 }
 
+/// Agents run in parallel when neither the workflow nor the configuration sets a limit
+pub const DEFAULT_MAX_PARALLEL: usize = 10;
+
 /// Agent template configuration
 #[derive(Debug, Clone, Serialize)]
 pub struct AgentTemplate {
     /// Commands to execute for each work item
     pub commands: Vec<WorkflowStep>,
+
+    /// Record a marker per applied item and skip marked items on later runs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub idempotency: Option<IdempotencyConfig>,
 }
 
 impl<'de> Deserialize<'de> for AgentTemplate {
@@ -294,8 +402,12 @@ impl<'de> Deserialize<'de> for AgentTemplate {
         enum AgentTemplateValue {
             // New simplified format: direct array of steps
             Commands(Vec<WorkflowStep>),
-            // Old nested format with 'commands' key
-            Nested { commands: Vec<WorkflowStep> },
+            // Nested format with 'commands' key, required for template options
+            Nested {
+                commands: Vec<WorkflowStep>,
+                #[serde(default, deserialize_with = "deserialize_idempotency")]
+                idempotency: Option<IdempotencyConfig>,
+            },
         }
 
         let value = AgentTemplateValue::deserialize(deserializer)?;
@@ -303,12 +415,23 @@ impl<'de> Deserialize<'de> for AgentTemplate {
         match value {
             AgentTemplateValue::Commands(commands) => {
                 // Using the new simplified format - this is preferred
-                Ok(AgentTemplate { commands })
+                Ok(AgentTemplate {
+                    commands,
+                    idempotency: None,
+                })
             }
-            AgentTemplateValue::Nested { commands } => {
-                // Using deprecated nested format
-                tracing::warn!("Using deprecated nested 'commands' syntax in agent_template. Consider using the simplified array format directly under 'agent_template'.");
-                Ok(AgentTemplate { commands })
+            AgentTemplateValue::Nested {
+                commands,
+                idempotency,
+            } => {
+                // The nested format is only deprecated when no options need it
+                if idempotency.is_none() {
+                    tracing::warn!("Using deprecated nested 'commands' syntax in agent_template. Consider using the simplified array format directly under 'agent_template'.");
+                }
+                Ok(AgentTemplate {
+                    commands,
+                    idempotency,
+                })
             }
         }
     }
@@ -319,6 +442,10 @@ impl<'de> Deserialize<'de> for AgentTemplate {
 pub struct ReducePhaseYaml {
     /// Commands to execute in reduce phase
     pub commands: Vec<WorkflowStep>,
+
+    /// File, relative to the parent worktree, to write the full map results to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub results_file: Option<String>,
 }
 
 impl<'de> Deserialize<'de> for ReducePhaseYaml {
@@ -331,8 +458,12 @@ impl<'de> Deserialize<'de> for ReducePhaseYaml {
         enum ReduceValue {
             // New simplified format: direct array of steps
             Commands(Vec<WorkflowStep>),
-            // Old nested format with 'commands' key
-            Nested { commands: Vec<WorkflowStep> },
+            // Nested format with 'commands' key, required for reduce options
+            Nested {
+                commands: Vec<WorkflowStep>,
+                #[serde(default)]
+                results_file: Option<String>,
+            },
         }
 
         let value = ReduceValue::deserialize(deserializer)?;
@@ -340,19 +471,30 @@ impl<'de> Deserialize<'de> for ReducePhaseYaml {
         match value {
             ReduceValue::Commands(commands) => {
                 // Using the new simplified format - this is preferred
-                Ok(ReducePhaseYaml { commands })
+                Ok(ReducePhaseYaml {
+                    commands,
+                    results_file: None,
+                })
             }
-            ReduceValue::Nested { commands } => {
-                // Using deprecated nested format
-                tracing::warn!("Using deprecated nested 'commands' syntax in reduce. Consider using the simplified array format directly under 'reduce'.");
-                Ok(ReducePhaseYaml { commands })
+            ReduceValue::Nested {
+                commands,
+                results_file,
+            } => {
+                // The nested format is only deprecated when no options need it
+                if results_file.is_none() {
+                    tracing::warn!("Using deprecated nested 'commands' syntax in reduce. Consider using the simplified array format directly under 'reduce'.");
+                }
+                Ok(ReducePhaseYaml {
+                    commands,
+                    results_file,
+                })
             }
         }
     }
 }
 
-/// Custom deserializer for usize values that can also be environment variable references
-fn deserialize_usize_or_string<'de, D>(deserializer: D) -> Result<String, D::Error>
+/// Custom deserializer for optional usize values that can also be environment variable references
+fn deserialize_optional_usize_or_string<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
 where
     D: serde::Deserializer<'de>,
 {
@@ -363,12 +505,12 @@ where
         String(String),
     }
 
-    let value = UsizeOrString::deserialize(deserializer)?;
+    let value = Option::<UsizeOrString>::deserialize(deserializer)?;
 
-    match value {
-        UsizeOrString::Number(n) => Ok(n.to_string()),
-        UsizeOrString::String(s) => Ok(s),
-    }
+    Ok(value.map(|value| match value {
+        UsizeOrString::Number(n) => n.to_string(),
+        UsizeOrString::String(s) => s,
+    }))
 }
 
 /// Custom deserializer for optional u64 values that can also be environment variable references
@@ -512,23 +654,61 @@ impl MapReduceWorkflowConfig {
                 None
             };
 
+            for pattern in &s.artifacts {
+                validate_artifact_pattern(pattern)?;
+            }
+            if let Some(shared) = &s.shared_context {
+                shared.validate()?;
+            }
+
             Ok(Some(SetupPhase {
                 commands: s.commands.clone(),
                 timeout,
                 capture_outputs: s.capture_outputs.clone(),
+                artifacts: s.artifacts.clone(),
+                shared_context: s.shared_context.clone(),
             }))
         } else {
             Ok(None)
         }
     }
 
+    /// Fill in settings the workflow leaves to the global or project configuration
+    pub fn apply_settings(&mut self, settings: &super::ProdigyConfig) {
+        self.strict_variables
+            .get_or_insert(settings.strict_variables);
+        if self.map.max_parallel.is_none() {
+            self.map.max_parallel = settings.default_max_parallel.map(|n| n.to_string());
+        }
+    }
+
+    /// Whether undefined variables fail a step
+    pub fn strict_variables_enabled(&self) -> bool {
+        self.strict_variables.unwrap_or_default()
+    }
+
+    /// Tool permissions for map agents
+    ///
+    /// `map.claude_permissions` wins over the workflow's; agents without
+    /// either use the restrictive `agent` profile.
+    pub fn agent_permissions(&self) -> ClaudePermissions {
+        self.map
+            .claude_permissions
+            .clone()
+            .or_else(|| self.claude_permissions.clone())
+            .unwrap_or_else(|| ClaudePermissions::profile(PermissionProfile::Agent))
+    }
+
     /// Convert to execution-ready MapPhase
     /// Returns an error if environment variable resolution or numeric parsing fails
     pub fn to_map_phase(&self) -> Result<MapPhase, anyhow::Error> {
         // Resolve max_parallel from string (may be a number or env var reference)
-        let max_parallel = self
-            .resolve_env_or_parse::<usize>(&self.map.max_parallel)
-            .context("Failed to resolve max_parallel")?;
+        let max_parallel = match &self.map.max_parallel {
+            Some(max_parallel) => self
+                .resolve_env_or_parse::<usize>(max_parallel)
+                .context("Failed to resolve max_parallel")?,
+            None => DEFAULT_MAX_PARALLEL,
+        };
 
         // Resolve agent_timeout_secs if present
         let agent_timeout_secs = if let Some(ref timeout_str) = self.map.agent_timeout_secs {
@@ -540,6 +720,20 @@ impl MapReduceWorkflowConfig {
             None
         };
 
+        if let Some(item_key) = &self.map.item_key {
+            ItemKeyExpr::parse(item_key).map_err(anyhow::Error::msg)?;
+        }
+        if self.map.batch_size == Some(0) {
+            return Err(anyhow::anyhow!("map.batch_size must be at least 1"));
+        }
+        affinity::parse_rules(&self.map.affinity).map_err(anyhow::Error::msg)?;
+        for entry in &self.map.build_cache {
+            entry.validate()?;
+        }
+        if let Some(issues) = self.notifications.as_ref().and_then(|n| n.issues.as_ref()) {
+            issues.validate()?;
+        }
+
         Ok(MapPhase {
             config: MapReduceConfig {
                 input: self.map.input.clone(),
@@ -547,7 +741,7 @@ impl MapReduceWorkflowConfig {
                 max_parallel,
                 agent_timeout_secs,
                 continue_on_failure: false,
-                batch_size: None,
+                batch_size: self.map.batch_size,
                 enable_checkpoints: true,
                 max_items: self.map.max_items,
                 offset: self.map.offset,
@@ -558,8 +752,26 @@ impl MapReduceWorkflowConfig {
             sort_by: self.map.sort_by.clone(),
             max_items: self.map.max_items,
             distinct: self.map.distinct.clone(),
+            changed_since: self.map.changed_since.clone(),
+            changed_path: self.map.changed_path.clone(),
             timeout_config: self.map.timeout_config.clone(),
+            rebase_onto: self.map.rebase_onto.clone(),
+            speculative: self.map.speculative.clone(),
+            item_key: self.map.item_key.clone(),
+            affinity: self.map.affinity.clone(),
+            build_cache: self.map.build_cache.clone(),
+            idempotency: self.map.agent_template.idempotency.clone(),
+            notifications: self.notifications.clone(),
+            claude_permissions: Some(self.agent_permissions()),
+            network: self.map.network,
             workflow_env: self.env.clone().unwrap_or_default(),
+            strict_variables: self.strict_variables_enabled(),
+            setup_artifacts: None,
+            chaos: None,
+            junit: None,
+            protected_paths: None,
+            seed: None,
+            failures: None,
         })
     }
 
@@ -568,6 +780,8 @@ impl MapReduceWorkflowConfig {
         self.reduce.as_ref().map(|r| ReducePhase {
             commands: r.commands.clone(),
             timeout_secs: None,
+            results_file: r.results_file.clone(),
+            claude_permissions: self.claude_permissions.clone(),
         })
     }
 
@@ -613,10 +827,449 @@ reduce:
         let config = parse_mapreduce_workflow(yaml).unwrap();
         assert_eq!(config.name, "parallel-debt-elimination");
         assert_eq!(config.mode, "mapreduce");
-        assert_eq!(config.map.max_parallel, "10");
+        assert_eq!(config.map.max_parallel.as_deref(), Some("10"));
         assert_eq!(config.map.agent_template.commands.len(), 2);
     }
 
+    #[test]
+    fn test_parse_rebase_onto() {
+        let yaml = r#"
+name: rebasing
+mode: mapreduce
+
+map:
+  input: items.json
+  rebase_onto: main
+  agent_template:
+    - shell: "echo ${item}"
+"#;
+
+        let config = parse_mapreduce_workflow(yaml).unwrap();
+        assert_eq!(config.map.rebase_onto.as_deref(), Some("main"));
+        let map_phase = config.to_map_phase().unwrap();
+        assert_eq!(map_phase.rebase_onto.as_deref(), Some("main"));
+    }
+
+    #[test]
+    fn test_parse_speculative() {
+        let yaml = r#"
+name: speculating
+mode: mapreduce
+
+map:
+  input: items.json
+  speculative:
+    slowdown: 3.0
+  agent_template:
+    - shell: "echo ${item}"
+"#;
+
+        let config = parse_mapreduce_workflow(yaml).unwrap();
+        let speculative = config.to_map_phase().unwrap().speculative.unwrap();
+        assert_eq!(speculative.slowdown, 3.0);
+        assert_eq!(speculative.min_completed, 3);
+    }
+
+    #[test]
+    fn test_parse_item_key() {
+        let yaml = r#"
+name: keyed
+mode: mapreduce
+
+map:
+  input: items.json
+  item_key: "$.location.file + :: + $.location.function"
+  agent_template:
+    - shell: "echo ${item_id}"
+"#;
+
+        let config = parse_mapreduce_workflow(yaml).unwrap();
+        let map_phase = config.to_map_phase().unwrap();
+        assert_eq!(
+            map_phase.item_key.as_deref(),
+            Some("$.location.file + :: + $.location.function")
+        );
+
+        let invalid = yaml.replace(":: +", ":: + +");
+        let config = parse_mapreduce_workflow(&invalid).unwrap();
+        assert!(config.to_map_phase().is_err());
+    }
+
+    #[test]
+    fn test_parse_changed_since() {
+        let yaml = r#"
+name: pr-scoped
+mode: mapreduce
+
+map:
+  input: items.json
+  changed_since: origin/main
+  changed_path: location.file
+  agent_template:
+    - shell: "echo ${item.location.file}"
+"#;
+
+        let config = parse_mapreduce_workflow(yaml).unwrap();
+        let map_phase = config.to_map_phase().unwrap();
+        assert_eq!(map_phase.changed_since.as_deref(), Some("origin/main"));
+        assert_eq!(map_phase.changed_path.as_deref(), Some("location.file"));
+    }
+
+    #[test]
+    fn test_parse_build_cache() {
+        use crate::worktree::build_cache::BuildCacheMode;
+
+        let yaml = r#"
+name: cached
+mode: mapreduce
+
+map:
+  input: items.json
+  build_cache:
+    - path: target
+      key: [Cargo.lock]
+    - path: node_modules
+      mode: symlink
+  agent_template:
+    - shell: "cargo test"
+"#;
+
+        let config = parse_mapreduce_workflow(yaml).unwrap();
+        let map_phase = config.to_map_phase().unwrap();
+        assert_eq!(map_phase.build_cache.len(), 2);
+        assert_eq!(map_phase.build_cache[0].mode, BuildCacheMode::Slot);
+        assert_eq!(map_phase.build_cache[0].key, vec!["Cargo.lock"]);
+        assert_eq!(map_phase.build_cache[1].mode, BuildCacheMode::Symlink);
+
+        let escaping = yaml.replace("path: target", "path: ../target");
+        let config = parse_mapreduce_workflow(&escaping).unwrap();
+        assert!(config.to_map_phase().is_err());
+    }
+
+    #[test]
+    fn test_parse_batch_size() {
+        let yaml = r#"
+name: batched
+mode: mapreduce
+
+map:
+  input: findings.json
+  batch_size: 20
+  agent_template:
+    - claude: "/fix-lints '${items}'"
+"#;
+
+        let config = parse_mapreduce_workflow(yaml).unwrap();
+        assert_eq!(config.to_map_phase().unwrap().config.batch_size, Some(20));
+
+        let zero = yaml.replace("batch_size: 20", "batch_size: 0");
+        let config = parse_mapreduce_workflow(&zero).unwrap();
+        assert!(config.to_map_phase().is_err());
+    }
+
+    #[test]
+    fn test_parse_affinity() {
+        let yaml = r#"
+name: affinity
+mode: mapreduce
+
+map:
+  input: debt.json
+  affinity:
+    - "$.location.file"
+  agent_template:
+    - claude: "/fix '${item}'"
+"#;
+
+        let config = parse_mapreduce_workflow(yaml).unwrap();
+        assert_eq!(
+            config.to_map_phase().unwrap().affinity,
+            vec!["$.location.file".to_string()]
+        );
+
+        let literal = yaml.replace("\"$.location.file\"", "location.file");
+        let config = parse_mapreduce_workflow(&literal).unwrap();
+        assert!(config.to_map_phase().is_err());
+    }
+
+    #[test]
+    fn test_parse_agent_template_idempotency() {
+        let yaml = r#"
+name: idempotent
+mode: mapreduce
+
+map:
+  input: items.json
+  item_key: "$.id"
+  agent_template:
+    idempotency:
+      scope: fix-debt
+    commands:
+      - shell: "echo ${item.id}"
+"#;
+
+        let config = parse_mapreduce_workflow(yaml).unwrap();
+        let map_phase = config.to_map_phase().unwrap();
+        assert_eq!(map_phase.agent_template.len(), 1);
+        assert_eq!(
+            map_phase.idempotency.unwrap().scope.as_deref(),
+            Some("fix-debt")
+        );
+
+        let enabled = yaml.replace(
+            "    idempotency:\n      scope: fix-debt\n",
+            "    idempotency: true\n",
+        );
+        let config = parse_mapreduce_workflow(&enabled).unwrap();
+        assert!(config.to_map_phase().unwrap().idempotency.is_some());
+    }
+
+    #[test]
+    fn test_parse_issue_notifications() {
+        let yaml = r#"
+name: notify
+mode: mapreduce
+
+map:
+  input: items.json
+  agent_template:
+    - shell: "echo ${item.id}"
+
+notifications:
+  issues:
+    provider: jira
+    url: https://example.atlassian.net
+    project: OPS
+"#;
+
+        let config = parse_mapreduce_workflow(yaml).unwrap();
+        let issues = config
+            .to_map_phase()
+            .unwrap()
+            .notifications
+            .unwrap()
+            .issues
+            .unwrap();
+        assert_eq!(issues.project.as_deref(), Some("OPS"));
+        assert_eq!(issues.issue_type, "Bug");
+
+        let missing_project = yaml.replace("    project: OPS\n", "");
+        let config = parse_mapreduce_workflow(&missing_project).unwrap();
+        assert!(config.to_map_phase().is_err());
+    }
+
+    #[test]
+    fn test_agent_claude_permissions() {
+        let yaml = r#"
+name: permissions
+mode: mapreduce
+
+map:
+  input: items.json
+  agent_template:
+    - claude: "/fix ${item.id}"
+
+claude_permissions:
+  profile: no_shell
+"#;
+
+        // Agents inherit the workflow's permissions
+        let config = parse_mapreduce_workflow(yaml).unwrap();
+        assert_eq!(
+            config.to_map_phase().unwrap().claude_permissions,
+            Some(ClaudePermissions::profile(PermissionProfile::NoShell))
+        );
+
+        // Reduce steps use the workflow's permissions
+        let with_reduce = format!("{yaml}\nreduce:\n  - claude: \"/summarize\"\n");
+        let config = parse_mapreduce_workflow(&with_reduce).unwrap();
+        assert_eq!(
+            config.to_reduce_phase().unwrap().claude_permissions,
+            Some(ClaudePermissions::profile(PermissionProfile::NoShell))
+        );
+
+        // The map block overrides them
+        let map_override = yaml.replace(
+            "    - claude: \"/fix ${item.id}\"\n",
+            "    - claude: \"/fix ${item.id}\"\n  claude_permissions:\n    profile: read_only\n",
+        );
+        let config = parse_mapreduce_workflow(&map_override).unwrap();
+        assert_eq!(
+            config.agent_permissions(),
+            ClaudePermissions::profile(PermissionProfile::ReadOnly)
+        );
+
+        // Without either, agents get the restrictive default
+        let unset = yaml.replace("claude_permissions:\n  profile: no_shell\n", "");
+        let config = parse_mapreduce_workflow(&unset).unwrap();
+        assert!(config.claude_permissions.is_none());
+        assert_eq!(
+            config.agent_permissions(),
+            ClaudePermissions::profile(PermissionProfile::Agent)
+        );
+    }
+
+    #[test]
+    fn test_strict_variables_reach_map_phase() {
+        let yaml = r#"
+name: strict
+mode: mapreduce
+strict_variables: true
+
+map:
+  input: items.json
+  agent_template:
+    - shell: "echo ${item.id}"
+"#;
+        let config = parse_mapreduce_workflow(yaml).unwrap();
+        assert!(config.to_map_phase().unwrap().strict_variables);
+
+        let config =
+            parse_mapreduce_workflow(&yaml.replace("strict_variables: true\n", "")).unwrap();
+        assert!(!config.to_map_phase().unwrap().strict_variables);
+    }
+
+    #[test]
+    fn test_settings_fill_unset_fields() {
+        let yaml = r#"
+name: defaults
+mode: mapreduce
+
+map:
+  input: items.json
+  agent_template:
+    - shell: "echo ${item.id}"
+"#;
+        let settings = crate::config::ProdigyConfig {
+            default_max_parallel: Some(3),
+            strict_variables: true,
+            ..Default::default()
+        };
+
+        let mut config = parse_mapreduce_workflow(yaml).unwrap();
+        assert_eq!(
+            config.to_map_phase().unwrap().config.max_parallel,
+            DEFAULT_MAX_PARALLEL
+        );
+        config.apply_settings(&settings);
+        let map_phase = config.to_map_phase().unwrap();
+        assert_eq!(map_phase.config.max_parallel, 3);
+        assert!(map_phase.strict_variables);
+
+        // The workflow's own settings win
+        let mut config = parse_mapreduce_workflow(&yaml.replace(
+            "mode: mapreduce\n",
+            "mode: mapreduce\nstrict_variables: false\n",
+        ))
+        .unwrap();
+        config.map.max_parallel = Some("5".to_string());
+        config.apply_settings(&settings);
+        let map_phase = config.to_map_phase().unwrap();
+        assert_eq!(map_phase.config.max_parallel, 5);
+        assert!(!map_phase.strict_variables);
+    }
+
+    #[test]
+    fn test_map_network_setting() {
+        let yaml = r#"
+name: sandboxed
+mode: mapreduce
+
+map:
+  input: items.json
+  network: deny
+  agent_template:
+    - shell: "cargo test"
+"#;
+        let config = parse_mapreduce_workflow(yaml).unwrap();
+        assert_eq!(config.to_map_phase().unwrap().network, AgentNetwork::Deny);
+
+        let config = parse_mapreduce_workflow(&yaml.replace("  network: deny\n", "")).unwrap();
+        assert!(config.to_map_phase().unwrap().network.is_allow());
+    }
+
+    #[test]
+    fn test_parse_setup_artifacts() {
+        let yaml = r#"
+name: artifacts
+mode: mapreduce
+
+setup:
+  commands:
+    - shell: "debtmap analyze . --output debtmap.json"
+  artifacts:
+    - debtmap.json
+    - coverage/**/*.json
+
+map:
+  input: debtmap.json
+  agent_template:
+    - shell: "cat debtmap.json"
+"#;
+
+        let config = parse_mapreduce_workflow(yaml).unwrap();
+        let setup = config.to_setup_phase().unwrap().unwrap();
+        assert_eq!(setup.artifacts, vec!["debtmap.json", "coverage/**/*.json"]);
+
+        let escaping = yaml.replace("- debtmap.json\n", "- ../debtmap.json\n");
+        let config = parse_mapreduce_workflow(&escaping).unwrap();
+        assert!(config.to_setup_phase().is_err());
+    }
+
+    #[test]
+    fn test_parse_setup_shared_context() {
+        let yaml = r#"
+name: shared-context
+mode: mapreduce
+
+setup:
+  shared_context:
+    commands:
+      - claude: "/summarize-repo --output .prodigy/repo-summary.md"
+    file: .prodigy/repo-summary.md
+  artifacts:
+    - items.json
+
+map:
+  input: items.json
+  agent_template:
+    - claude: "/fix ${item.id} --context .prodigy/repo-summary.md"
+"#;
+
+        let config = parse_mapreduce_workflow(yaml).unwrap();
+        let setup = config.to_setup_phase().unwrap().unwrap();
+        assert!(setup.commands.is_empty());
+        assert_eq!(setup.shared_context.as_ref().unwrap().commands.len(), 1);
+        assert_eq!(
+            setup.artifact_patterns(),
+            vec!["items.json", ".prodigy/repo-summary.md"]
+        );
+
+        let escaping = yaml.replace("file: .prodigy", "file: ../.prodigy");
+        let config = parse_mapreduce_workflow(&escaping).unwrap();
+        assert!(config.to_setup_phase().is_err());
+    }
+
+    #[test]
+    fn test_parse_always_steps() {
+        let yaml = r#"
+name: cleanup
+mode: mapreduce
+
+map:
+  input: items.json
+  agent_template:
+    - shell: "echo ${item}"
+
+finally:
+  - shell: "rm -f items.json"
+"#;
+
+        let config = parse_mapreduce_workflow(yaml).unwrap();
+        assert_eq!(config.always.len(), 1);
+        assert_eq!(config.always[0].shell.as_deref(), Some("rm -f items.json"));
+    }
+
     #[test]
     fn test_simplified_agent_template_syntax() {
         // Test new simplified format (preferred)
@@ -718,6 +1371,32 @@ reduce:
         assert_eq!(config.reduce.as_ref().unwrap().commands.len(), 2);
     }
 
+    #[test]
+    fn test_parse_reduce_results_file() {
+        let yaml = r#"
+name: test-reduce-results-file
+mode: mapreduce
+
+map:
+  input: items.json
+  agent_template:
+    - shell: "echo processing"
+
+reduce:
+  results_file: .prodigy/map-results.json
+  commands:
+    - shell: "jq length .prodigy/map-results.json"
+"#;
+
+        let config = parse_mapreduce_workflow(yaml).unwrap();
+        let reduce = config.to_reduce_phase().unwrap();
+        assert_eq!(
+            reduce.results_file.as_deref(),
+            Some(".prodigy/map-results.json")
+        );
+        assert_eq!(reduce.commands.len(), 1);
+    }
+
     #[test]
     fn test_mixed_simplified_and_nested_syntax() {
         // Test workflow with mixed syntax (not recommended but should work)
@@ -261,6 +261,7 @@ impl Default for MetadataParser {
 struct FrontmatterMetadata {
     name: Option<String>,
     description: Option<String>,
+    #[serde(alias = "args")]
     arguments: Option<Vec<FrontmatterArgument>>,
     options: Option<Vec<FrontmatterOption>>,
     metadata: Option<CommandMetadata>,
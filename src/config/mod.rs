@@ -5,6 +5,7 @@ use std::path::PathBuf;
 
 pub mod builder;
 pub mod command;
+pub mod command_catalog;
 pub mod command_discovery;
 pub mod command_parser;
 pub mod command_validator;
@@ -16,10 +17,12 @@ pub mod metadata_parser;
 pub mod prodigy_config;
 pub mod tracing;
 pub mod workflow;
+pub mod workflow_builder;
+pub mod yaml_loader;
 
 pub use builder::{
-    load_prodigy_config, load_prodigy_config_traced, load_prodigy_config_traced_with,
-    load_prodigy_config_with, load_prodigy_config_with_options,
+    load_prodigy_config, load_prodigy_config_at, load_prodigy_config_traced,
+    load_prodigy_config_traced_with, load_prodigy_config_with, load_prodigy_config_with_options,
     load_prodigy_config_with_options_and_env, LoadOptions,
 };
 pub use command::{
@@ -31,10 +34,12 @@ pub use dynamic_registry::DynamicCommandRegistry;
 pub use loader::ConfigLoader;
 pub use mapreduce::{parse_mapreduce_workflow, MapReduceWorkflowConfig};
 pub use prodigy_config::{
-    global_config_path, project_config_path, BackendType, PluginConfig, ProdigyConfig,
-    ProjectSettings, StorageSettings, VALID_LOG_LEVELS,
+    global_config_path, project_config_path, project_config_path_in, BackendType, OutputSettings,
+    PluginConfig, ProdigyConfig, ProjectSettings, StorageSettings, TruncationStrategy,
+    WorktreeSettings, VALID_LOG_LEVELS,
 };
-pub use workflow::WorkflowConfig;
+pub use workflow::{RunDefaults, WorkflowConfig};
+pub use workflow_builder::{BuiltWorkflow, MapPhaseBuilder, Step, WorkflowBuilder};
 
 /// Get the global Prodigy directory for storing configuration and data
 pub fn get_global_prodigy_dir() -> Result<PathBuf> {
@@ -308,6 +313,82 @@ commands:
         assert!(matches!(&config.commands[2], WorkflowCommand::Simple(_)));
     }
 
+    #[test]
+    fn test_workflow_always_steps() {
+        let yaml_str = r#"
+commands:
+  - shell: "cargo test"
+always:
+  - shell: "rm -rf tmp/"
+  - shell: "docker compose down"
+"#;
+
+        let config: WorkflowConfig = serde_yaml::from_str(yaml_str).unwrap();
+        assert_eq!(config.commands.len(), 1);
+        assert_eq!(config.always.len(), 2);
+
+        // `finally:` is accepted as an alias
+        let config: WorkflowConfig =
+            serde_yaml::from_str(&yaml_str.replace("always:", "finally:")).unwrap();
+        assert_eq!(config.always.len(), 2);
+    }
+
+    #[test]
+    fn test_workflow_strict_variables() {
+        let config: WorkflowConfig =
+            serde_yaml::from_str("commands:\n  - shell: \"make\"\nstrict_variables: true\n")
+                .unwrap();
+        assert_eq!(config.strict_variables, Some(true));
+        assert!(config.strict_variables_enabled());
+
+        // Without an override the global default applies
+        let mut config: WorkflowConfig =
+            serde_yaml::from_str("commands:\n  - shell: \"make\"\n").unwrap();
+        assert!(!config.strict_variables_enabled());
+        config.apply_settings(&ProdigyConfig {
+            strict_variables: true,
+            ..Default::default()
+        });
+        assert!(config.strict_variables_enabled());
+    }
+
+    #[test]
+    fn test_workflow_run_defaults() {
+        use crate::cook::command::{CookCommand, ExplicitFlags};
+        use clap::Parser;
+
+        #[derive(Parser)]
+        struct Cli {
+            #[command(flatten)]
+            cook: CookCommand,
+        }
+
+        let yaml = "commands:\n  - shell: \"make\"\ndefaults:\n  worktree: false\n  max_iterations: 3\n  fail_fast: true\n";
+        let config: WorkflowConfig = serde_yaml::from_str(yaml).unwrap();
+        let defaults = config.defaults.unwrap();
+
+        let mut cmd = Cli::parse_from(["prodigy", "workflow.yml"]).cook;
+        defaults.apply(&mut cmd);
+        assert!(cmd.no_worktree);
+        assert_eq!(cmd.max_iterations, 3);
+        assert!(cmd.fail_fast);
+
+        // Flags set on the command line win
+        let mut cmd = Cli::parse_from(["prodigy", "workflow.yml", "-n", "5"]).cook;
+        cmd.explicit_flags = ExplicitFlags {
+            worktree: true,
+            max_iterations: true,
+            fail_fast: true,
+        };
+        defaults.apply(&mut cmd);
+        assert!(!cmd.no_worktree);
+        assert_eq!(cmd.max_iterations, 5);
+        assert!(!cmd.fail_fast);
+
+        // Unknown defaults are rejected rather than silently ignored
+        assert!(serde_yaml::from_str::<RunDefaults>("fail_fasst: true").is_err());
+    }
+
     #[test]
     fn test_command_string_parsing() {
         // Test various command string formats
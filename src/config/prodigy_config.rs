@@ -26,10 +26,11 @@
 //! assert_eq!(config.log_level, "debug");
 //! ```
 
+use crate::cook::interaction::{PromptKind, PromptPolicy};
 use premortem::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Valid log levels for configuration validation.
 pub const VALID_LOG_LEVELS: &[&str] = &["trace", "debug", "info", "warn", "error"];
@@ -86,6 +87,30 @@ pub struct ProdigyConfig {
     /// Plugin configuration.
     #[serde(default)]
     pub plugins: PluginConfig,
+
+    /// Worktree disk usage settings.
+    #[serde(default)]
+    pub worktree: WorktreeSettings,
+
+    /// Default `map.max_parallel` for MapReduce workflows that don't set one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_max_parallel: Option<usize>,
+
+    /// How to answer each kind of prompt when stdin is not a terminal.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub prompts: HashMap<PromptKind, PromptPolicy>,
+
+    /// Size limits for step output kept in variables and checkpoints.
+    #[serde(default)]
+    pub output: OutputSettings,
+
+    /// Fail steps that reference undefined `${...}` variables unless a workflow opts out.
+    #[serde(default)]
+    pub strict_variables: bool,
+
+    /// Paths no workflow step may change, added to each workflow's `protected_paths`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub protected_paths: Vec<String>,
 }
 
 /// Project-specific configuration settings.
@@ -138,6 +163,79 @@ pub struct StorageSettings {
     pub compression_level: u8,
 }
 
+/// Worktree disk usage and checkout configuration.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WorktreeSettings {
+    /// Maximum total size of all Prodigy worktrees (e.g. "50GB").
+    #[serde(default)]
+    pub disk_quota: Option<String>,
+
+    /// Refuse to create worktrees while over the quota instead of warning.
+    #[serde(default)]
+    pub enforce_disk_quota: bool,
+
+    /// Initialize and update submodules in new worktrees.
+    #[serde(default = "default_true")]
+    pub submodules: bool,
+
+    /// Check out Git LFS objects in new worktrees.
+    #[serde(default = "default_true")]
+    pub lfs: bool,
+}
+
+impl Default for WorktreeSettings {
+    fn default() -> Self {
+        Self {
+            disk_quota: None,
+            enforce_disk_quota: false,
+            submodules: true,
+            lfs: true,
+        }
+    }
+}
+
+/// Step output size limits.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OutputSettings {
+    /// Largest stdout or stderr kept for a step, in bytes (`null` keeps everything).
+    #[serde(default = "default_max_capture_bytes")]
+    pub max_capture_bytes: Option<usize>,
+
+    /// Which part of an oversized output to keep.
+    #[serde(default)]
+    pub truncation: TruncationStrategy,
+
+    /// Directory for full outputs (defaults to `~/.prodigy/logs/<repo>/outputs`).
+    #[serde(default)]
+    pub artifact_dir: Option<PathBuf>,
+}
+
+impl Default for OutputSettings {
+    fn default() -> Self {
+        Self {
+            max_capture_bytes: default_max_capture_bytes(),
+            truncation: TruncationStrategy::default(),
+            artifact_dir: None,
+        }
+    }
+}
+
+/// Part of an oversized output to keep.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TruncationStrategy {
+    /// Keep the beginning.
+    Head,
+    /// Keep the end, where test failures and errors usually are.
+    Tail,
+    /// Keep a quarter from the beginning and the rest from the end.
+    #[default]
+    HeadTail,
+}
+
+/// Smallest accepted `output.max_capture_bytes`.
+pub const MIN_CAPTURE_BYTES: usize = 1024;
+
 /// Plugin configuration for extending Prodigy functionality.
 ///
 /// Plugins are loaded from a directory and can provide custom commands
@@ -180,6 +278,12 @@ impl Default for ProdigyConfig {
             project: None,
             storage: StorageSettings::default(),
             plugins: PluginConfig::default(),
+            worktree: WorktreeSettings::default(),
+            default_max_parallel: None,
+            prompts: HashMap::new(),
+            output: OutputSettings::default(),
+            strict_variables: false,
+            protected_paths: Vec::new(),
         }
     }
 }
@@ -197,6 +301,14 @@ fn default_auto_commit() -> bool {
     true
 }
 
+fn default_true() -> bool {
+    true
+}
+
+fn default_max_capture_bytes() -> Option<usize> {
+    Some(64 * 1024)
+}
+
 impl ProdigyConfig {
     /// Get the effective Claude API key (project overrides global).
     ///
@@ -313,6 +425,33 @@ impl Validate for ProdigyConfig {
             });
         }
 
+        // Validate default_max_parallel is in range 1..=100
+        if let Some(max_parallel) = self.default_max_parallel {
+            if max_parallel == 0 || max_parallel > 100 {
+                errors.push(ConfigError::ValidationError {
+                    path: "default_max_parallel".to_string(),
+                    source_location: None,
+                    value: Some(max_parallel.to_string()),
+                    message: "default_max_parallel must be between 1 and 100".to_string(),
+                });
+            }
+        }
+
+        // Validate output.max_capture_bytes leaves room for useful output
+        if let Some(max_bytes) = self.output.max_capture_bytes {
+            if max_bytes < MIN_CAPTURE_BYTES {
+                errors.push(ConfigError::ValidationError {
+                    path: "output.max_capture_bytes".to_string(),
+                    source_location: None,
+                    value: Some(max_bytes.to_string()),
+                    message: format!(
+                        "output.max_capture_bytes must be at least {}",
+                        MIN_CAPTURE_BYTES
+                    ),
+                });
+            }
+        }
+
         // Validate storage.compression_level is in range 0..=9
         if self.storage.compression_level > 9 {
             errors.push(ConfigError::ValidationError {
@@ -323,6 +462,18 @@ impl Validate for ProdigyConfig {
             });
         }
 
+        // Validate worktree.disk_quota is a size like "50GB"
+        if let Some(ref quota) = self.worktree.disk_quota {
+            if let Err(e) = crate::worktree::disk_usage::parse_size(quota) {
+                errors.push(ConfigError::ValidationError {
+                    path: "worktree.disk_quota".to_string(),
+                    source_location: None,
+                    value: Some(quota.clone()),
+                    message: e.to_string(),
+                });
+            }
+        }
+
         // Validate project settings if present
         if let Some(ref project) = self.project {
             // Validate project.name is non-empty when provided
@@ -373,6 +524,17 @@ pub fn project_config_path() -> PathBuf {
     PathBuf::from(".prodigy/config.yml")
 }
 
+/// Returns the project config file of a run in `project_dir`.
+///
+/// This is `.prodigy/config.yml` in the monorepo package directory if it has
+/// one, otherwise in `project_dir`.
+pub fn project_config_path_in(project_dir: &Path, package_dir: Option<&Path>) -> PathBuf {
+    package_dir
+        .map(|dir| dir.join(project_config_path()))
+        .filter(|path| path.exists())
+        .unwrap_or_else(|| project_dir.join(project_config_path()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -476,6 +638,40 @@ storage:
         assert!(matches!(result, Validation::Failure(_)));
     }
 
+    #[test]
+    fn test_worktree_disk_quota_validation() {
+        let config = ProdigyConfig {
+            worktree: WorktreeSettings {
+                disk_quota: Some("50GB".to_string()),
+                enforce_disk_quota: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert!(matches!(config.validate(), Validation::Success(_)));
+
+        let invalid_config = ProdigyConfig {
+            worktree: WorktreeSettings {
+                disk_quota: Some("fifty gigs".to_string()),
+                enforce_disk_quota: false,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert!(matches!(invalid_config.validate(), Validation::Failure(_)));
+    }
+
+    #[test]
+    fn test_worktree_checkout_settings_default_on() {
+        let settings: WorktreeSettings = serde_yaml::from_str("lfs: false\n").unwrap();
+        assert!(settings.submodules);
+        assert!(!settings.lfs);
+        assert_eq!(
+            serde_yaml::from_str::<WorktreeSettings>("{}").unwrap(),
+            WorktreeSettings::default()
+        );
+    }
+
     #[test]
     fn test_backend_type_serialization() {
         assert_eq!(
@@ -712,4 +908,25 @@ plugins:
         );
         assert_eq!(config.plugins.auto_load, vec!["plugin1", "plugin2"]);
     }
+
+    #[test]
+    fn test_project_config_path_prefers_package_config() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let repo = Path::new("/repo");
+        let default = PathBuf::from("/repo/.prodigy/config.yml");
+
+        assert_eq!(project_config_path_in(repo, None), default);
+        assert_eq!(project_config_path_in(repo, Some(temp.path())), default);
+
+        std::fs::create_dir_all(temp.path().join(".prodigy")).unwrap();
+        std::fs::write(
+            temp.path().join(".prodigy/config.yml"),
+            "log_level: debug\n",
+        )
+        .unwrap();
+        assert_eq!(
+            project_config_path_in(repo, Some(temp.path())),
+            temp.path().join(".prodigy/config.yml")
+        );
+    }
 }
@@ -1,6 +1,11 @@
 use super::command::WorkflowCommand;
 use super::mapreduce::MergeWorkflow;
+use crate::cook::command::CookCommand;
+use crate::cook::concurrency::ConcurrencyConfig;
 use crate::cook::environment::{EnvProfile, SecretValue};
+use crate::cook::execution::claude_permissions::ClaudePermissions;
+use crate::cook::protected_paths::ProtectedPaths;
+use crate::cook::workflow::score_gate::ScoreGate;
 use serde::{Deserialize, Deserializer, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -36,6 +41,89 @@ pub struct WorkflowConfig {
     /// Optional custom merge workflow for worktree integration
     #[serde(skip_serializing_if = "Option::is_none")]
     pub merge: Option<MergeWorkflow>,
+
+    /// Concurrency group that queues overlapping runs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub concurrency: Option<ConcurrencyConfig>,
+
+    /// Tool permissions for Claude commands
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub claude_permissions: Option<ClaudePermissions>,
+
+    /// Cleanup steps that run after the commands even when they fail or are cancelled
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub always: Vec<WorkflowCommand>,
+
+    /// Fail steps that reference undefined `${...}` variables, overriding the global default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strict_variables: Option<bool>,
+
+    /// Defaults for `prodigy run` flags that the command line has not set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub defaults: Option<RunDefaults>,
+
+    /// Paths steps may not change
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protected_paths: Option<ProtectedPaths>,
+
+    /// Health score check that stops iterating when the score drops
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score_gate: Option<ScoreGate>,
+}
+
+/// Defaults for run flags declared by a workflow
+///
+/// ```yaml
+/// defaults:
+///   worktree: true
+///   max_iterations: 3
+///   fail_fast: true
+/// ```
+///
+/// Flags given on the command line still win.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RunDefaults {
+    /// Run in an isolated worktree (`--worktree` / `--no-worktree`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub worktree: Option<bool>,
+
+    /// Maximum number of iterations (`--max-iterations`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_iterations: Option<u32>,
+
+    /// Stop on the first failure (`--fail-fast` / `--no-fail-fast`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fail_fast: Option<bool>,
+}
+
+impl RunDefaults {
+    /// Fill in the flags of `cmd` that were not set explicitly
+    pub fn apply(&self, cmd: &mut CookCommand) {
+        let explicit = cmd.explicit_flags;
+        if let Some(worktree) = self.worktree.filter(|_| !explicit.worktree) {
+            cmd.no_worktree = !worktree;
+        }
+        if let Some(iterations) = self.max_iterations.filter(|_| !explicit.max_iterations) {
+            cmd.max_iterations = iterations;
+        }
+        if let Some(fail_fast) = self.fail_fast.filter(|_| !explicit.fail_fast) {
+            cmd.fail_fast = fail_fast;
+        }
+    }
+}
+
+impl WorkflowConfig {
+    /// Fill in settings the workflow leaves to the global or project configuration
+    pub fn apply_settings(&mut self, settings: &super::ProdigyConfig) {
+        self.strict_variables
+            .get_or_insert(settings.strict_variables);
+    }
+
+    /// Whether undefined variables fail a step
+    pub fn strict_variables_enabled(&self) -> bool {
+        self.strict_variables.unwrap_or_default()
+    }
 }
 
 impl<'de> Deserialize<'de> for WorkflowConfig {
@@ -64,6 +152,20 @@ impl<'de> Deserialize<'de> for WorkflowConfig {
                 profiles: Option<HashMap<String, EnvProfile>>,
                 #[serde(default)]
                 merge: Option<MergeWorkflow>,
+                #[serde(default)]
+                concurrency: Option<ConcurrencyConfig>,
+                #[serde(default)]
+                claude_permissions: Option<ClaudePermissions>,
+                #[serde(default, alias = "finally")]
+                always: Vec<WorkflowCommand>,
+                #[serde(default)]
+                strict_variables: Option<bool>,
+                #[serde(default)]
+                defaults: Option<RunDefaults>,
+                #[serde(default)]
+                protected_paths: Option<ProtectedPaths>,
+                #[serde(default)]
+                score_gate: Option<ScoreGate>,
             },
             // Old format: object with commands field only
             WithCommandsField {
@@ -83,6 +185,13 @@ impl<'de> Deserialize<'de> for WorkflowConfig {
                 env_files: None,
                 profiles: None,
                 merge: None,
+                concurrency: None,
+                claude_permissions: None,
+                always: Vec::new(),
+                strict_variables: None,
+                defaults: None,
+                protected_paths: None,
+                score_gate: None,
             }),
             WorkflowConfigHelper::Full {
                 name,
@@ -92,6 +201,13 @@ impl<'de> Deserialize<'de> for WorkflowConfig {
                 env_files,
                 profiles,
                 merge,
+                concurrency,
+                claude_permissions,
+                always,
+                strict_variables,
+                defaults,
+                protected_paths,
+                score_gate,
             } => Ok(WorkflowConfig {
                 name,
                 commands,
@@ -100,6 +216,13 @@ impl<'de> Deserialize<'de> for WorkflowConfig {
                 env_files,
                 profiles,
                 merge,
+                concurrency,
+                claude_permissions,
+                always,
+                strict_variables,
+                defaults,
+                protected_paths,
+                score_gate,
             }),
             WorkflowConfigHelper::WithCommandsField { name, commands } => Ok(WorkflowConfig {
                 name,
@@ -109,6 +232,13 @@ impl<'de> Deserialize<'de> for WorkflowConfig {
                 env_files: None,
                 profiles: None,
                 merge: None,
+                concurrency: None,
+                claude_permissions: None,
+                always: Vec::new(),
+                strict_variables: None,
+                defaults: None,
+                protected_paths: None,
+                score_gate: None,
             }),
         }
     }
@@ -0,0 +1,598 @@
+//! Typed builder for constructing workflows programmatically
+//!
+//! [`WorkflowBuilder`] produces the same [`WorkflowConfig`] and
+//! [`MapReduceWorkflowConfig`] values the YAML loader does, so tools can
+//! generate and execute workflows without serializing them to YAML first.
+//!
+//! Steps added before [`WorkflowBuilder::map`] form the setup phase of a
+//! MapReduce workflow, and steps added after it form the reduce phase. A
+//! builder without a map phase produces a standard workflow.
+//!
+//! ```
+//! use prodigy::config::{MapPhaseBuilder, WorkflowBuilder};
+//!
+//! // Standard workflow
+//! let workflow = WorkflowBuilder::new()
+//!     .claude("/lint")
+//!     .shell("cargo test")
+//!     .build()
+//!     .unwrap();
+//! assert!(!workflow.is_mapreduce());
+//!
+//! // MapReduce workflow
+//! let workflow = WorkflowBuilder::new()
+//!     .name("fix-debt")
+//!     .shell("debtmap analyze . --output items.json")
+//!     .map(
+//!         MapPhaseBuilder::new("items.json")
+//!             .json_path("$.items[*]")
+//!             .claude("/fix ${item.location}")
+//!             .max_parallel(5),
+//!     )
+//!     .shell("cargo test")
+//!     .build()
+//!     .unwrap();
+//! assert!(workflow.is_mapreduce());
+//! ```
+
+use super::command::{WorkflowCommand, WorkflowStepCommand};
+use super::mapreduce::{
+    AgentTemplate, MapPhaseYaml, MapReduceWorkflowConfig, ReducePhaseYaml, SetupPhaseConfig,
+};
+use super::workflow::WorkflowConfig;
+use crate::cook::execution::mapreduce::speculative::SpeculativeConfig;
+use crate::cook::workflow::context_pack::ContextPackConfig;
+use crate::cook::workflow::{WorkflowErrorPolicy, WorkflowStep};
+use crate::worktree::build_cache::BuildCacheEntry;
+use anyhow::{anyhow, Result};
+use std::collections::HashMap;
+
+/// A single workflow step
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Step {
+    claude: Option<String>,
+    model: Option<String>,
+    context: Option<ContextPackConfig>,
+    shell: Option<String>,
+    commit_required: bool,
+    timeout: Option<u64>,
+    when: Option<String>,
+}
+
+impl Step {
+    /// Step that runs a Claude command
+    pub fn claude(command: impl Into<String>) -> Self {
+        Self {
+            claude: Some(command.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Step that runs a shell command
+    pub fn shell(command: impl Into<String>) -> Self {
+        Self {
+            shell: Some(command.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Model passed to the Claude CLI for this step
+    pub fn model(mut self, model: impl Into<String>) -> Self {
+        self.model = Some(model.into());
+        self
+    }
+
+    /// Files or glob patterns injected into the Claude command
+    pub fn context<I, S>(mut self, files: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.context = Some(ContextPackConfig::Files(
+            files.into_iter().map(Into::into).collect(),
+        ));
+        self
+    }
+
+    /// Require the step to create at least one commit
+    pub fn commit_required(mut self, required: bool) -> Self {
+        self.commit_required = required;
+        self
+    }
+
+    /// Timeout in seconds
+    pub fn timeout(mut self, seconds: u64) -> Self {
+        self.timeout = Some(seconds);
+        self
+    }
+
+    /// Only run the step when the expression evaluates to true
+    pub fn when(mut self, expression: impl Into<String>) -> Self {
+        self.when = Some(expression.into());
+        self
+    }
+
+    fn to_step_command(&self) -> WorkflowStepCommand {
+        WorkflowStepCommand {
+            claude: self.claude.clone(),
+            model: self.model.clone(),
+            context: self.context.clone(),
+            shell: self.shell.clone(),
+            commit_required: self.commit_required,
+            timeout: self.timeout,
+            when: self.when.clone(),
+            ..WorkflowStepCommand::default()
+        }
+    }
+
+    fn to_workflow_step(&self) -> WorkflowStep {
+        WorkflowStep {
+            claude: self.claude.clone(),
+            model: self.model.clone(),
+            context: self.context.clone(),
+            shell: self.shell.clone(),
+            commit_required: self.commit_required,
+            timeout: self.timeout,
+            when: self.when.clone(),
+            ..WorkflowStep::default()
+        }
+    }
+}
+
+/// Builder for the map phase of a MapReduce workflow
+#[derive(Debug, Clone)]
+pub struct MapPhaseBuilder {
+    input: String,
+    json_path: String,
+    agent_steps: Vec<Step>,
+    max_parallel: usize,
+    filter: Option<String>,
+    sort_by: Option<String>,
+    max_items: Option<usize>,
+    offset: Option<usize>,
+    distinct: Option<String>,
+    agent_timeout_secs: Option<u64>,
+    rebase_onto: Option<String>,
+    speculative: Option<SpeculativeConfig>,
+    item_key: Option<String>,
+    batch_size: Option<usize>,
+    affinity: Vec<String>,
+    build_cache: Vec<BuildCacheEntry>,
+}
+
+impl MapPhaseBuilder {
+    /// Map over the work items in `input` (a file path or command)
+    pub fn new(input: impl Into<String>) -> Self {
+        Self {
+            input: input.into(),
+            json_path: String::new(),
+            agent_steps: Vec::new(),
+            max_parallel: 10,
+            filter: None,
+            sort_by: None,
+            max_items: None,
+            offset: None,
+            distinct: None,
+            agent_timeout_secs: None,
+            rebase_onto: None,
+            speculative: None,
+            item_key: None,
+            affinity: Vec::new(),
+            batch_size: None,
+            build_cache: Vec::new(),
+        }
+    }
+
+    /// JSONPath expression selecting the work items
+    pub fn json_path(mut self, path: impl Into<String>) -> Self {
+        self.json_path = path.into();
+        self
+    }
+
+    /// Add a Claude command to the agent template
+    pub fn claude(self, command: impl Into<String>) -> Self {
+        self.step(Step::claude(command))
+    }
+
+    /// Add a shell command to the agent template
+    pub fn shell(self, command: impl Into<String>) -> Self {
+        self.step(Step::shell(command))
+    }
+
+    /// Add a step to the agent template
+    pub fn step(mut self, step: Step) -> Self {
+        self.agent_steps.push(step);
+        self
+    }
+
+    /// Maximum number of parallel agents (default: 10)
+    pub fn max_parallel(mut self, max_parallel: usize) -> Self {
+        self.max_parallel = max_parallel;
+        self
+    }
+
+    /// Only process items matching the filter expression
+    pub fn filter(mut self, expression: impl Into<String>) -> Self {
+        self.filter = Some(expression.into());
+        self
+    }
+
+    /// Sort items by a field (e.g. `"priority DESC"`)
+    pub fn sort_by(mut self, field: impl Into<String>) -> Self {
+        self.sort_by = Some(field.into());
+        self
+    }
+
+    /// Maximum number of items to process
+    pub fn max_items(mut self, max_items: usize) -> Self {
+        self.max_items = Some(max_items);
+        self
+    }
+
+    /// Number of items to skip
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// Deduplicate items by a field
+    pub fn distinct(mut self, field: impl Into<String>) -> Self {
+        self.distinct = Some(field.into());
+        self
+    }
+
+    /// Per-agent timeout in seconds
+    pub fn agent_timeout_secs(mut self, seconds: u64) -> Self {
+        self.agent_timeout_secs = Some(seconds);
+        self
+    }
+
+    /// Rebase each agent's commits onto a branch before merging
+    pub fn rebase_onto(mut self, branch: impl Into<String>) -> Self {
+        self.rebase_onto = Some(branch.into());
+        self
+    }
+
+    /// Launch backup agents for items running much longer than the median
+    pub fn speculative(mut self, config: SpeculativeConfig) -> Self {
+        self.speculative = Some(config);
+        self
+    }
+
+    /// Derive stable item IDs from work items, e.g. `$.file + :: + $.function`
+    pub fn item_key(mut self, expr: impl Into<String>) -> Self {
+        self.item_key = Some(expr.into());
+        self
+    }
+
+    /// Give each agent up to `batch_size` items at once, exposed as `${items}`
+    pub fn batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = Some(batch_size);
+        self
+    }
+
+    /// Never run two items with the same value for `expr` at the same time
+    pub fn affinity(mut self, expr: impl Into<String>) -> Self {
+        self.affinity.push(expr.into());
+        self
+    }
+
+    /// Share a directory such as `target` across agent worktrees
+    pub fn build_cache(mut self, entry: BuildCacheEntry) -> Self {
+        self.build_cache.push(entry);
+        self
+    }
+
+    fn build(self) -> Result<MapPhaseYaml> {
+        if self.input.trim().is_empty() {
+            return Err(anyhow!("Map phase requires an input"));
+        }
+        if self.agent_steps.is_empty() {
+            return Err(anyhow!("Map phase requires at least one agent step"));
+        }
+        if self.max_parallel == 0 {
+            return Err(anyhow!("Map phase max_parallel must be at least 1"));
+        }
+
+        Ok(MapPhaseYaml {
+            input: self.input,
+            json_path: self.json_path,
+            agent_template: AgentTemplate {
+                commands: self
+                    .agent_steps
+                    .iter()
+                    .map(Step::to_workflow_step)
+                    .collect(),
+                idempotency: None,
+            },
+            max_parallel: Some(self.max_parallel.to_string()),
+            filter: self.filter,
+            sort_by: self.sort_by,
+            max_items: self.max_items,
+            offset: self.offset,
+            distinct: self.distinct,
+            agent_timeout_secs: self.agent_timeout_secs.map(|secs| secs.to_string()),
+            timeout_config: None,
+            rebase_onto: self.rebase_onto,
+            speculative: self.speculative,
+            item_key: self.item_key,
+            build_cache: self.build_cache,
+            claude_permissions: None,
+            network: Default::default(),
+            changed_since: None,
+            changed_path: None,
+            batch_size: self.batch_size,
+            affinity: self.affinity,
+        })
+    }
+}
+
+/// A workflow produced by [`WorkflowBuilder`]
+#[derive(Debug, Clone)]
+#[allow(clippy::large_enum_variant)]
+pub enum BuiltWorkflow {
+    /// Standard sequential workflow
+    Standard(WorkflowConfig),
+    /// MapReduce workflow
+    MapReduce(MapReduceWorkflowConfig),
+}
+
+impl BuiltWorkflow {
+    /// Whether this is a MapReduce workflow
+    pub fn is_mapreduce(&self) -> bool {
+        matches!(self, BuiltWorkflow::MapReduce(_))
+    }
+
+    /// Split into the configuration pair used by the cook orchestrator
+    ///
+    /// MapReduce workflows carry their environment and merge settings in a
+    /// step-less `WorkflowConfig`, matching what the YAML loader returns.
+    pub fn into_parts(self) -> (WorkflowConfig, Option<MapReduceWorkflowConfig>) {
+        match self {
+            BuiltWorkflow::Standard(config) => (config, None),
+            BuiltWorkflow::MapReduce(config) => (
+                WorkflowConfig {
+                    name: Some(config.name.clone()),
+                    commands: vec![],
+                    env: config.env.clone(),
+                    secrets: config.secrets.clone(),
+                    env_files: config.env_files.clone(),
+                    profiles: config.profiles.clone(),
+                    merge: config.merge.clone(),
+                    concurrency: config.concurrency.clone(),
+                    claude_permissions: config.claude_permissions.clone(),
+                    always: Vec::new(),
+                    strict_variables: None,
+                    defaults: None,
+                    protected_paths: None,
+                    score_gate: None,
+                },
+                Some(config),
+            ),
+        }
+    }
+}
+
+/// Builder for standard and MapReduce workflows
+#[derive(Debug, Clone, Default)]
+pub struct WorkflowBuilder {
+    name: Option<String>,
+    env: HashMap<String, String>,
+    steps: Vec<Step>,
+    map: Option<MapPhaseBuilder>,
+    reduce_steps: Vec<Step>,
+}
+
+impl WorkflowBuilder {
+    /// Create an empty workflow builder
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Workflow name
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Set a global environment variable
+    pub fn env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.env.insert(key.into(), value.into());
+        self
+    }
+
+    /// Add a Claude command step
+    pub fn claude(self, command: impl Into<String>) -> Self {
+        self.step(Step::claude(command))
+    }
+
+    /// Add a shell command step
+    pub fn shell(self, command: impl Into<String>) -> Self {
+        self.step(Step::shell(command))
+    }
+
+    /// Add a step
+    ///
+    /// Before [`map`](Self::map) is called the step belongs to the workflow
+    /// (or the setup phase of a MapReduce workflow); afterwards it belongs to
+    /// the reduce phase.
+    pub fn step(mut self, step: Step) -> Self {
+        if self.map.is_some() {
+            self.reduce_steps.push(step);
+        } else {
+            self.steps.push(step);
+        }
+        self
+    }
+
+    /// Turn the workflow into a MapReduce workflow with the given map phase
+    pub fn map(mut self, map: MapPhaseBuilder) -> Self {
+        self.map = Some(map);
+        self
+    }
+
+    /// Validate the workflow and build its configuration
+    pub fn build(self) -> Result<BuiltWorkflow> {
+        let env = (!self.env.is_empty()).then_some(self.env);
+
+        let Some(map) = self.map else {
+            if self.steps.is_empty() {
+                return Err(anyhow!("Workflow requires at least one step"));
+            }
+            return Ok(BuiltWorkflow::Standard(WorkflowConfig {
+                name: self.name,
+                commands: self
+                    .steps
+                    .iter()
+                    .map(|step| WorkflowCommand::WorkflowStep(Box::new(step.to_step_command())))
+                    .collect(),
+                env,
+                secrets: None,
+                env_files: None,
+                profiles: None,
+                merge: None,
+                concurrency: None,
+                claude_permissions: None,
+                always: Vec::new(),
+                strict_variables: None,
+                defaults: None,
+                protected_paths: None,
+                score_gate: None,
+            }));
+        };
+
+        let setup = (!self.steps.is_empty()).then(|| SetupPhaseConfig {
+            commands: self.steps.iter().map(Step::to_workflow_step).collect(),
+            timeout: None,
+            capture_outputs: HashMap::new(),
+            artifacts: Vec::new(),
+            shared_context: None,
+        });
+        let reduce = (!self.reduce_steps.is_empty()).then(|| ReducePhaseYaml {
+            commands: self
+                .reduce_steps
+                .iter()
+                .map(Step::to_workflow_step)
+                .collect(),
+            results_file: None,
+        });
+
+        Ok(BuiltWorkflow::MapReduce(MapReduceWorkflowConfig {
+            name: self.name.unwrap_or_else(|| "mapreduce".to_string()),
+            mode: "mapreduce".to_string(),
+            env,
+            secrets: None,
+            env_files: None,
+            profiles: None,
+            setup,
+            map: map.build()?,
+            reduce,
+            error_policy: WorkflowErrorPolicy::default(),
+            on_item_failure: None,
+            continue_on_failure: None,
+            max_failures: None,
+            failure_threshold: None,
+            error_collection: None,
+            merge: None,
+            notifications: None,
+            concurrency: None,
+            claude_permissions: None,
+            always: Vec::new(),
+            defaults: None,
+            protected_paths: None,
+            strict_variables: None,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_standard_workflow_matches_yaml() {
+        let built = WorkflowBuilder::new()
+            .step(Step::claude("/lint").model("opus"))
+            .step(Step::shell("cargo test").timeout(300))
+            .build()
+            .unwrap();
+        let BuiltWorkflow::Standard(config) = built else {
+            panic!("expected a standard workflow");
+        };
+
+        let yaml: WorkflowConfig = serde_yaml::from_str(
+            r#"
+commands:
+  - claude: "/lint"
+    model: opus
+  - shell: "cargo test"
+    timeout: 300
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.commands, yaml.commands);
+    }
+
+    #[test]
+    fn test_mapreduce_workflow_matches_yaml() {
+        let built = WorkflowBuilder::new()
+            .name("fix-items")
+            .shell("generate-items > items.json")
+            .map(
+                MapPhaseBuilder::new("items.json")
+                    .json_path("$.items[*]")
+                    .step(Step::claude("/fix ${item.id}").commit_required(true))
+                    .max_parallel(4),
+            )
+            .shell("cargo test")
+            .build()
+            .unwrap();
+        assert!(built.is_mapreduce());
+        let (workflow, mapreduce) = built.into_parts();
+        let config = mapreduce.unwrap();
+        assert_eq!(workflow.name.as_deref(), Some("fix-items"));
+        assert!(workflow.commands.is_empty());
+
+        let yaml = crate::config::parse_mapreduce_workflow(
+            r#"
+name: fix-items
+mode: mapreduce
+setup:
+  - shell: "generate-items > items.json"
+map:
+  input: items.json
+  json_path: "$.items[*]"
+  agent_template:
+    - claude: "/fix ${item.id}"
+      commit_required: true
+  max_parallel: 4
+reduce:
+  - shell: "cargo test"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&config).unwrap(),
+            serde_json::to_value(&yaml).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_build_validation() {
+        assert!(WorkflowBuilder::new().build().is_err());
+        assert!(WorkflowBuilder::new()
+            .map(MapPhaseBuilder::new("items.json"))
+            .build()
+            .is_err());
+        assert!(WorkflowBuilder::new()
+            .map(
+                MapPhaseBuilder::new("items.json")
+                    .claude("/fix")
+                    .max_parallel(0)
+            )
+            .build()
+            .is_err());
+    }
+}
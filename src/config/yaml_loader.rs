@@ -0,0 +1,506 @@
+//! Workflow YAML preprocessing
+//!
+//! Runs before a workflow file is deserialized into its config type:
+//!
+//! - Anchors and aliases are resolved, including `<<: *base` merge keys, so
+//!   shared step or map settings can be declared once. Top-level keys starting
+//!   with `x-` are a conventional place to declare anchors and are ignored.
+//! - `${env.VAR}` and `${env.VAR:-default}` are expanded at load time, so
+//!   structural fields such as `max_parallel` or `map.input` can use them.
+//! - The `overrides:` entry for the environment selected with
+//!   `prodigy run --env <name>` is merged onto the workflow.
+//!
+//! Load-time `${env.VAR}` resolution uses, in order:
+//!
+//! 1. The workflow's top-level `env:` block
+//! 2. The environment of the prodigy process
+//! 3. The inline `:-default`
+//!
+//! Names declared under `secrets:` are never expanded, so secret values are
+//! not baked into the parsed config. Unresolved references are left as-is and
+//! resolved when the command runs.
+
+use anyhow::{anyhow, Result};
+use regex::Regex;
+use serde_yaml::{Mapping, Value};
+use std::collections::{HashMap, HashSet};
+use std::sync::OnceLock;
+
+/// Matches `${env.NAME}` and `${env.NAME:-default}`
+fn env_reference_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| {
+        Regex::new(r"\$\{env\.([A-Za-z_][A-Za-z0-9_]*)(?::-([^}]*))?\}")
+            .expect("valid env reference regex")
+    })
+}
+
+/// Preprocess workflow YAML using the process environment
+pub fn preprocess_workflow_yaml(content: &str) -> Result<String> {
+    preprocess_workflow_yaml_with_env(content, |name| std::env::var(name).ok())
+}
+
+/// Preprocess workflow YAML with a custom environment lookup
+///
+/// Returns `content` unchanged when it needs no rewriting, so parse errors
+/// keep pointing at the user's original line numbers.
+pub fn preprocess_workflow_yaml_with_env<F>(content: &str, lookup: F) -> Result<String>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    // Other syntax errors are reported by the config parser with more context
+    let mut value: Value = match serde_yaml::from_str(content) {
+        Ok(value) => value,
+        Err(e) => {
+            return match describe_undefined_alias(&e, content) {
+                Some(message) => Err(anyhow!(message)),
+                None => Ok(content.to_string()),
+            }
+        }
+    };
+
+    let mut changed = false;
+    if has_merge_keys(&value) {
+        value
+            .apply_merge()
+            .map_err(|e| anyhow!("Invalid YAML merge key: {}", e))?;
+        changed = true;
+    }
+
+    if let Value::Mapping(root) = &mut value {
+        let extension_keys: Vec<Value> = root
+            .keys()
+            .filter(|key| key.as_str().is_some_and(|k| k.starts_with("x-")))
+            .cloned()
+            .collect();
+        for key in extension_keys {
+            root.remove(&key);
+            changed = true;
+        }
+    }
+
+    if env_reference_regex().is_match(content) {
+        changed |= expand_env_references(&mut value, &lookup);
+    }
+
+    if !changed {
+        return Ok(content.to_string());
+    }
+    serde_yaml::to_string(&value).map_err(|e| anyhow!("Failed to normalize workflow YAML: {}", e))
+}
+
+/// Merge the `overrides:` entry for `environment` onto the workflow
+///
+/// A workflow can adjust its settings per environment, selected with
+/// `prodigy run --env <name>`:
+///
+/// ```yaml
+/// overrides:
+///   ci:
+///     map:
+///       max_parallel: 20
+///   laptop:
+///     map:
+///       max_parallel: 4
+/// ```
+///
+/// Mappings are merged key by key; any other value, including a list,
+/// replaces the base value. The `overrides` block is removed from the result.
+/// Every entry must be a mapping, and selecting an environment the workflow
+/// does not define is an error. Returns `content` unchanged when there is
+/// nothing to merge or remove.
+pub fn apply_environment_overrides(content: &str, environment: Option<&str>) -> Result<String> {
+    let Ok(mut value) = serde_yaml::from_str::<Value>(content) else {
+        return Ok(content.to_string());
+    };
+    let overrides = match &mut value {
+        Value::Mapping(root) => root.remove("overrides"),
+        _ => None,
+    };
+    let Some(overrides) = overrides else {
+        return match environment {
+            Some(name) => Err(anyhow!(
+                "--env {} was given but the workflow defines no overrides",
+                name
+            )),
+            None => Ok(content.to_string()),
+        };
+    };
+
+    let Value::Mapping(overrides) = overrides else {
+        return Err(anyhow!("overrides must map environment names to settings"));
+    };
+    for (name, settings) in &overrides {
+        if !settings.is_mapping() {
+            return Err(anyhow!(
+                "overrides.{} must be a mapping of settings",
+                display_key(name)
+            ));
+        }
+    }
+
+    if let Some(name) = environment {
+        let Some(settings) = overrides.get(name) else {
+            let mut available: Vec<String> = overrides.keys().map(display_key).collect();
+            available.sort();
+            return Err(anyhow!(
+                "Unknown environment '{}' (the workflow defines: {})",
+                name,
+                available.join(", ")
+            ));
+        };
+        merge_value(&mut value, settings.clone());
+    }
+
+    serde_yaml::to_string(&value).map_err(|e| anyhow!("Failed to normalize workflow YAML: {}", e))
+}
+
+/// Merge `overlay` onto `base`, recursing into mappings
+fn merge_value(base: &mut Value, overlay: Value) {
+    match (base, overlay) {
+        (Value::Mapping(base), Value::Mapping(overlay)) => {
+            for (key, entry) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_value(existing, entry),
+                    None => {
+                        base.insert(key, entry);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+fn display_key(key: &Value) -> String {
+    match key {
+        Value::String(s) => s.clone(),
+        other => serde_yaml::to_string(other)
+            .map(|s| s.trim_end().to_string())
+            .unwrap_or_default(),
+    }
+}
+
+/// Whether any mapping uses a `<<` merge key
+fn has_merge_keys(value: &Value) -> bool {
+    match value {
+        Value::Mapping(mapping) => mapping
+            .iter()
+            .any(|(key, entry)| key.as_str() == Some("<<") || has_merge_keys(entry)),
+        Value::Sequence(items) => items.iter().any(has_merge_keys),
+        Value::Tagged(tagged) => has_merge_keys(&tagged.value),
+        _ => false,
+    }
+}
+
+/// Expand `${env.*}` references throughout the document
+fn expand_env_references<F>(value: &mut Value, lookup: &F) -> bool
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let root = match value {
+        Value::Mapping(root) => root,
+        // Plain step lists have no env or secrets blocks
+        other => return expand_value(other, &HashMap::new(), &HashSet::new(), lookup),
+    };
+
+    let secrets: HashSet<String> = root
+        .get("secrets")
+        .and_then(Value::as_mapping)
+        .map(|secrets| {
+            secrets
+                .keys()
+                .filter_map(|k| k.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    // The env block itself only resolves against the process environment
+    let mut changed = false;
+    if let Some(env) = root.get_mut("env") {
+        changed |= expand_value(env, &HashMap::new(), &secrets, lookup);
+    }
+    let workflow_env = root
+        .get("env")
+        .and_then(Value::as_mapping)
+        .map(string_entries)
+        .unwrap_or_default();
+
+    for (key, entry) in root.iter_mut() {
+        if matches!(key.as_str(), Some("env" | "secrets")) {
+            continue;
+        }
+        changed |= expand_value(entry, &workflow_env, &secrets, lookup);
+    }
+    changed
+}
+
+/// String-valued entries of a mapping
+fn string_entries(mapping: &Mapping) -> HashMap<String, String> {
+    mapping
+        .iter()
+        .filter_map(|(k, v)| Some((k.as_str()?.to_string(), v.as_str()?.to_string())))
+        .collect()
+}
+
+fn expand_value<F>(
+    value: &mut Value,
+    workflow_env: &HashMap<String, String>,
+    secrets: &HashSet<String>,
+    lookup: &F,
+) -> bool
+where
+    F: Fn(&str) -> Option<String>,
+{
+    match value {
+        Value::String(text) => match expand_env_string(text, workflow_env, secrets, lookup) {
+            Some(expanded) => {
+                *text = expanded;
+                true
+            }
+            None => false,
+        },
+        Value::Sequence(items) => items.iter_mut().fold(false, |changed, item| {
+            expand_value(item, workflow_env, secrets, lookup) | changed
+        }),
+        Value::Mapping(mapping) => mapping.values_mut().fold(false, |changed, item| {
+            expand_value(item, workflow_env, secrets, lookup) | changed
+        }),
+        Value::Tagged(tagged) => expand_value(&mut tagged.value, workflow_env, secrets, lookup),
+        _ => false,
+    }
+}
+
+/// Expand env references in one string, or `None` if nothing was resolved
+fn expand_env_string<F>(
+    text: &str,
+    workflow_env: &HashMap<String, String>,
+    secrets: &HashSet<String>,
+    lookup: &F,
+) -> Option<String>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let mut changed = false;
+    let expanded = env_reference_regex().replace_all(text, |caps: &regex::Captures| {
+        let name = &caps[1];
+        let resolved = if secrets.contains(name) {
+            None
+        } else {
+            workflow_env
+                .get(name)
+                .cloned()
+                .or_else(|| lookup(name))
+                .or_else(|| caps.get(2).map(|d| d.as_str().to_string()))
+        };
+        match resolved {
+            Some(value) => {
+                changed = true;
+                value
+            }
+            None => caps[0].to_string(),
+        }
+    });
+    changed.then(|| expanded.into_owned())
+}
+
+/// Explain an undefined-anchor error, naming the alias and the defined anchors
+pub fn describe_undefined_alias(error: &serde_yaml::Error, content: &str) -> Option<String> {
+    if !error.to_string().contains("unknown anchor") {
+        return None;
+    }
+    let location = error.location()?;
+    let line = content
+        .lines()
+        .nth(location.line().saturating_sub(1))
+        .unwrap_or_default();
+    let alias = line
+        .get(location.column().saturating_sub(1)..)
+        .and_then(|rest| rest.trim_start().strip_prefix('*'))
+        .map(|rest| {
+            rest.chars()
+                .take_while(|c| !c.is_whitespace() && !matches!(c, ',' | ']' | '}'))
+                .collect::<String>()
+        })
+        .unwrap_or_default();
+
+    let defined = defined_anchors(content);
+    let hint = if defined.is_empty() {
+        "No anchors are defined; declare one with '&name' before using '*name'".to_string()
+    } else {
+        format!(
+            "Defined anchors: {} (an anchor must appear before its alias)",
+            defined.join(", ")
+        )
+    };
+    Some(format!(
+        "Undefined YAML alias '*{}' at line {}, column {}\n  {} | {}\n{}",
+        alias,
+        location.line(),
+        location.column(),
+        location.line(),
+        line,
+        hint
+    ))
+}
+
+/// Anchor names declared with `&name`, in order of appearance
+fn defined_anchors(content: &str) -> Vec<String> {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = REGEX.get_or_init(|| {
+        Regex::new(r"(?:^|[\s:\-\[,])&([^\s,\[\]{}]+)").expect("valid anchor regex")
+    });
+    let mut names: Vec<String> = Vec::new();
+    for caps in regex.captures_iter(content) {
+        let name = caps[1].to_string();
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lookup(vars: &'static [(&'static str, &'static str)]) -> impl Fn(&str) -> Option<String> {
+        move |name| {
+            vars.iter()
+                .find(|(k, _)| *k == name)
+                .map(|(_, v)| v.to_string())
+        }
+    }
+
+    #[test]
+    fn test_unchanged_content_is_returned_verbatim() {
+        let yaml = "# comment\nname: plain\ncommands:\n  - shell: cat <<EOF\n";
+        let result = preprocess_workflow_yaml_with_env(yaml, lookup(&[])).unwrap();
+        assert_eq!(result, yaml);
+    }
+
+    #[test]
+    fn test_merge_keys_and_extension_fields() {
+        let yaml = r#"
+x-defaults: &defaults
+  timeout: 300
+  commit_required: true
+name: anchors
+commands:
+  - <<: *defaults
+    shell: "cargo test"
+  - <<: *defaults
+    shell: "cargo clippy"
+    timeout: 60
+"#;
+        let result = preprocess_workflow_yaml_with_env(yaml, lookup(&[])).unwrap();
+        let value: Value = serde_yaml::from_str(&result).unwrap();
+        assert!(value.get("x-defaults").is_none());
+        let commands = value["commands"].as_sequence().unwrap();
+        assert_eq!(commands[0]["timeout"], Value::from(300));
+        assert_eq!(commands[0]["commit_required"], Value::from(true));
+        assert_eq!(commands[1]["timeout"], Value::from(60));
+    }
+
+    #[test]
+    fn test_env_precedence() {
+        let yaml = r#"
+name: env
+env:
+  OUTPUT_DIR: "${env.HOME}/out"
+  MAX: "4"
+secrets:
+  TOKEN: "${env.TOKEN}"
+map:
+  input: "${env.OUTPUT_DIR}/items.json"
+  max_parallel: "${env.MAX}"
+  agent_template:
+    - shell: "echo ${env.MODE:-fast} ${env.TOKEN} ${env.UNSET}"
+"#;
+        let vars = lookup(&[("HOME", "/home/dev"), ("MAX", "99"), ("TOKEN", "s3cret")]);
+        let result = preprocess_workflow_yaml_with_env(yaml, vars).unwrap();
+        let value: Value = serde_yaml::from_str(&result).unwrap();
+
+        assert_eq!(value["env"]["OUTPUT_DIR"], Value::from("/home/dev/out"));
+        // The env block wins over the process environment
+        assert_eq!(value["map"]["max_parallel"], Value::from("4"));
+        // Env block values are expanded before being used
+        assert_eq!(
+            value["map"]["input"],
+            Value::from("/home/dev/out/items.json")
+        );
+        // Secrets and unresolved names are left for runtime
+        assert_eq!(
+            value["map"]["agent_template"][0]["shell"],
+            Value::from("echo fast ${env.TOKEN} ${env.UNSET}")
+        );
+        assert_eq!(value["secrets"]["TOKEN"], Value::from("${env.TOKEN}"));
+    }
+
+    #[test]
+    fn test_undefined_alias_error_names_alias() {
+        let yaml = "x-base: &base\n  timeout: 10\ncommands:\n  - *missing\n";
+        let err = preprocess_workflow_yaml_with_env(yaml, lookup(&[]))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("'*missing'"), "{}", err);
+        assert!(err.contains("line 4"), "{}", err);
+        assert!(err.contains("Defined anchors: base"), "{}", err);
+
+        // Other syntax errors are left to the config parser
+        let invalid = "commands:\n- shell: echo\n  bad\ninvalid_line\n";
+        let result = preprocess_workflow_yaml_with_env(invalid, lookup(&[])).unwrap();
+        assert_eq!(result, invalid);
+    }
+
+    #[test]
+    fn test_environment_overrides() {
+        let yaml = r#"
+name: overrides
+mode: mapreduce
+map:
+  input: items.json
+  max_parallel: 2
+  agent_template:
+    - shell: "cargo test"
+overrides:
+  ci:
+    map:
+      max_parallel: 20
+      agent_template:
+        - shell: "cargo test --release"
+  laptop:
+    map:
+      max_parallel: 4
+"#;
+        let ci = apply_environment_overrides(yaml, Some("ci")).unwrap();
+        let value: Value = serde_yaml::from_str(&ci).unwrap();
+        assert!(value.get("overrides").is_none());
+        assert_eq!(value["map"]["max_parallel"], Value::from(20));
+        assert_eq!(value["map"]["input"], Value::from("items.json"));
+        // Lists are replaced rather than merged
+        let template = value["map"]["agent_template"].as_sequence().unwrap();
+        assert_eq!(template.len(), 1);
+        assert_eq!(template[0]["shell"], Value::from("cargo test --release"));
+
+        let base = apply_environment_overrides(yaml, None).unwrap();
+        let value: Value = serde_yaml::from_str(&base).unwrap();
+        assert!(value.get("overrides").is_none());
+        assert_eq!(value["map"]["max_parallel"], Value::from(2));
+
+        let err = apply_environment_overrides(yaml, Some("staging"))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("the workflow defines: ci, laptop"), "{}", err);
+
+        let plain = "name: plain\ncommands:\n  - shell: echo\n";
+        assert_eq!(apply_environment_overrides(plain, None).unwrap(), plain);
+        assert!(apply_environment_overrides(plain, Some("ci")).is_err());
+
+        let invalid = "overrides:\n  ci: 20\n";
+        let err = apply_environment_overrides(invalid, None)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("overrides.ci must be a mapping"), "{}", err);
+    }
+}
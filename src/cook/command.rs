@@ -1,3 +1,4 @@
+use crate::cook::execution::chaos::ChaosConfig;
 use clap::Args;
 use serde_json::Value;
 use std::collections::HashMap;
@@ -22,6 +23,13 @@ pub struct CookCommand {
     )]
     pub path: Option<PathBuf>,
 
+    /// Monorepo package to run in, relative to the repository root
+    ///
+    /// Relative workflow paths and `.prodigy` config are resolved from the
+    /// package directory, and commands and map inputs are scoped to it.
+    #[arg(long, value_name = "DIR")]
+    pub package: Option<PathBuf>,
+
     /// Maximum number of iterations to run (default: 1)
     ///
     /// This limits how many improvement cycles will be executed.
@@ -35,6 +43,18 @@ pub struct CookCommand {
     #[arg(long, value_name = "PATTERN")]
     pub map: Vec<String>,
 
+    /// Only process map items referring to files changed since a git ref
+    ///
+    /// Overrides the workflow's `map.changed_since`. Without a value, compares
+    /// against `origin/main`.
+    #[arg(
+        long,
+        value_name = "REF",
+        num_args = 0..=1,
+        default_missing_value = "origin/main"
+    )]
+    pub changed_only: Option<String>,
+
     /// Direct arguments to pass to commands
     ///
     /// Arguments that will be passed to workflow commands via $ARG variable.
@@ -75,7 +95,112 @@ pub struct CookCommand {
     #[arg(long, help = "Preview commands without executing them")]
     pub dry_run: bool,
 
+    /// Read-only analysis mode
+    ///
+    /// Runs the workflow in a detached throwaway worktree, rejects steps that
+    /// require commits, and fails if any tracked file changed by the end of the run.
+    #[arg(long)]
+    pub read_only: bool,
+
+    /// Record Claude and shell command outputs to a fixture file
+    ///
+    /// The recorded fixtures can be replayed later with `--replay` to re-run
+    /// the workflow deterministically without invoking Claude. Not supported
+    /// for MapReduce workflows.
+    #[arg(long, value_name = "FILE", conflicts_with = "replay")]
+    pub record: Option<PathBuf>,
+
+    /// Replay command outputs from a fixture file instead of executing commands
+    #[arg(long, value_name = "FILE")]
+    pub replay: Option<PathBuf>,
+
+    /// Write step and work item results as JUnit XML
+    ///
+    /// Each step, and each MapReduce setup step, work item and reduce step,
+    /// becomes a test case so CI systems can show the run in their test reports.
+    #[arg(long, value_name = "FILE")]
+    pub junit_output: Option<PathBuf>,
+
+    /// Record wall time, CPU time and peak memory of each step
+    ///
+    /// The profile is printed at the end of the run, slowest step first, and
+    /// stored with the session.
+    #[arg(long)]
+    pub profile_steps: bool,
+
+    /// Print a summary of a failed run
+    ///
+    /// Shows the failing step, its exit code, the last lines of its output,
+    /// related dead-lettered items and the command to resume. The summary is
+    /// stored with the session either way.
+    #[arg(long)]
+    pub explain_failure: bool,
+
+    /// Run every step even if some fail
+    ///
+    /// A failing step is reported as a warning and the remaining steps still
+    /// run. A table of step outcomes is printed at the end, and the run fails
+    /// only if a step marked `required: true` failed.
+    #[arg(long)]
+    pub keep_going: bool,
+
+    /// Chaos testing: randomly fail, delay, or kill commands
+    ///
+    /// Takes a spec such as `fail=10,delay=20,kill=5,seed=42` (percentages of
+    /// commands) to verify checkpoint, resume, and DLQ handling. A bare number
+    /// is the failure percentage.
+    #[arg(long, value_name = "SPEC")]
+    pub chaos: Option<ChaosConfig>,
+
+    /// Seed for a deterministic run
+    ///
+    /// Retry jitter, chaos decisions without their own seed, and generated
+    /// inputs are drawn from it, and MapReduce agents start in work-item
+    /// order. The seed is recorded in the session metadata.
+    #[arg(long, value_name = "N")]
+    pub seed: Option<u64>,
+
+    /// Answers file replayed instead of prompting
+    ///
+    /// Written by `prodigy sessions answers` from a session whose prompts were
+    /// answered interactively. A prompt with no answer left in the file fails.
+    #[arg(long, value_name = "PATH")]
+    pub answers_file: Option<PathBuf>,
+
+    /// Environment whose `overrides:` entry is merged onto the workflow
+    ///
+    /// Lets one workflow file carry settings for several machines, for example
+    /// a higher `map.max_parallel` in CI than on a laptop.
+    #[arg(long = "env", value_name = "NAME")]
+    pub environment: Option<String>,
+
+    /// Run directly in the project directory instead of an isolated worktree
+    /// (not a CLI argument, set by `prodigy run --no-worktree` or the `prodigy::api` facade)
+    #[arg(skip)]
+    pub no_worktree: bool,
+
+    /// Steps to run, from `prodigy run --only-steps` and `--skip-steps`
+    /// (not a CLI argument; resolved against the workflow before the run starts)
+    #[arg(skip)]
+    pub steps: crate::cook::workflow::step_selection::StepSelection,
+
+    /// Flags set explicitly by the caller, which win over the workflow's `defaults`
+    /// (not a CLI argument)
+    #[arg(skip)]
+    pub explicit_flags: ExplicitFlags,
+
     /// Template parameters (not a CLI argument, populated from --param and --param-file)
     #[arg(skip)]
     pub params: HashMap<String, Value>,
 }
+
+/// Run flags the caller set explicitly rather than leaving at their defaults
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ExplicitFlags {
+    /// `--worktree` or `--no-worktree`
+    pub worktree: bool,
+    /// `--max-iterations`
+    pub max_iterations: bool,
+    /// `--fail-fast` or `--no-fail-fast`
+    pub fail_fast: bool,
+}
@@ -45,6 +45,106 @@ pub struct CommitConfig {
     pub squash: bool,
 }
 
+/// Commits a step is expected to create, checked after the step runs
+///
+/// Written in place of the boolean form of `commit_required`:
+///
+/// ```yaml
+/// commit_required:
+///   min_commits: 1
+///   max_commits: 3
+///   paths: ["src/", "tests/"]
+///   message_pattern: "^(feat|fix|refactor):"
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CommitPolicy {
+    /// Fewest commits the step must create (defaults to 1)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_commits: Option<usize>,
+
+    /// Most commits the step may create
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_commits: Option<usize>,
+
+    /// Path prefixes every committed file must fall under
+    #[serde(
+        default,
+        alias = "allowed_paths",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub paths: Vec<String>,
+
+    /// Regex every commit message must match
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message_pattern: Option<String>,
+}
+
+impl CommitPolicy {
+    /// Ways in which `commits` break the policy (pure function)
+    ///
+    /// Returns an error only when `message_pattern` is not a valid regex.
+    pub fn violations(&self, commits: &[TrackedCommit]) -> Result<Vec<String>> {
+        let mut violations = Vec::new();
+
+        let min = self.min_commits.unwrap_or(1);
+        if commits.len() < min {
+            violations.push(format!(
+                "expected at least {} commit(s), found {}",
+                min,
+                commits.len()
+            ));
+        }
+        if let Some(max) = self.max_commits.filter(|max| commits.len() > *max) {
+            violations.push(format!(
+                "expected at most {} commit(s), found {}",
+                max,
+                commits.len()
+            ));
+        }
+
+        if !self.paths.is_empty() {
+            for commit in commits {
+                for file in &commit.files_changed {
+                    let allowed = self
+                        .paths
+                        .iter()
+                        .any(|prefix| file.starts_with(prefix.trim_end_matches('/')));
+                    if !allowed {
+                        violations.push(format!(
+                            "commit {} changes {} outside of allowed paths [{}]",
+                            short_hash(&commit.hash),
+                            file.display(),
+                            self.paths.join(", ")
+                        ));
+                    }
+                }
+            }
+        }
+
+        if let Some(pattern) = &self.message_pattern {
+            let re = regex::Regex::new(pattern)
+                .map_err(|e| anyhow!("Invalid commit_required message_pattern: {e}"))?;
+            for commit in commits {
+                let subject = commit.message.lines().next().unwrap_or("");
+                if !re.is_match(&commit.message) {
+                    violations.push(format!(
+                        "commit {} message '{}' does not match '{}'",
+                        short_hash(&commit.hash),
+                        subject,
+                        pattern
+                    ));
+                }
+            }
+        }
+
+        Ok(violations)
+    }
+}
+
+fn short_hash(hash: &str) -> &str {
+    &hash[..hash.len().min(8)]
+}
+
 /// Metadata for a tracked commit
 #[derive(Debug, Clone, Serialize)]
 pub struct TrackedCommit {
@@ -940,4 +1040,55 @@ mod tests {
         assert!(json_str.contains("insertions"));
         assert!(json_str.contains("deletions"));
     }
+
+    #[test]
+    fn test_commit_policy_violations() {
+        let commit = |hash: &str, message: &str, files: &[&str]| TrackedCommit {
+            hash: hash.to_string(),
+            message: message.to_string(),
+            author: "Test".to_string(),
+            timestamp: Utc::now(),
+            files_changed: files.iter().map(PathBuf::from).collect(),
+            insertions: 1,
+            deletions: 0,
+            step_name: "implement".to_string(),
+            agent_id: None,
+        };
+        let policy = CommitPolicy {
+            min_commits: None,
+            max_commits: Some(1),
+            paths: vec!["src/".to_string()],
+            message_pattern: Some("^fix:".to_string()),
+        };
+
+        assert_eq!(
+            policy.violations(&[]).unwrap(),
+            vec!["expected at least 1 commit(s), found 0"]
+        );
+        assert!(policy
+            .violations(&[commit("abc123def456", "fix: bug", &["src/lib.rs"])])
+            .unwrap()
+            .is_empty());
+
+        let violations = policy
+            .violations(&[
+                commit("abc123def456", "fix: bug", &["src/lib.rs"]),
+                commit("def456abc123", "wip", &["Cargo.toml"]),
+            ])
+            .unwrap();
+        assert_eq!(
+            violations,
+            vec![
+                "expected at most 1 commit(s), found 2",
+                "commit def456ab changes Cargo.toml outside of allowed paths [src/]",
+                "commit def456ab message 'wip' does not match '^fix:'",
+            ]
+        );
+
+        let invalid = CommitPolicy {
+            message_pattern: Some("(".to_string()),
+            ..Default::default()
+        };
+        assert!(invalid.violations(&[commit("abc", "x", &[])]).is_err());
+    }
 }
@@ -0,0 +1,429 @@
+//! Workflow concurrency groups
+//!
+//! Runs of workflows that declare the same group execute one at a time, with
+//! the same semantics as GitHub Actions `concurrency`:
+//!
+//! ```yaml
+//! concurrency:
+//!   group: deploy-${env.BRANCH}
+//!   cancel_in_progress: false
+//! ```
+//!
+//! - At most one run holds the group. A new run waits until it is released.
+//! - At most one run waits. A newer run replaces the waiting one, which is
+//!   cancelled before it starts.
+//! - With `cancel_in_progress: true`, a new run also cancels the run holding
+//!   the group, which stops as if it had received SIGTERM.
+//!
+//! Groups are scoped to the repository and held through the global storage
+//! lock subsystem, so they apply across processes sharing `~/.prodigy`. The
+//! holder refreshes its lock while running; a crashed holder's lock expires
+//! after [`LOCK_TTL`].
+
+use crate::storage::lock::{FileLockBackend, LockBackend, StorageLockGuard};
+use anyhow::{anyhow, Context, Result};
+use chrono::Utc;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+/// How long a lock survives without a heartbeat
+pub const LOCK_TTL: Duration = Duration::from_secs(30);
+
+/// How often the holder extends its lock
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(10);
+
+/// How often waiting runs retry and holders check for cancellation
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Concurrency group settings for a workflow
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ConcurrencyConfig {
+    /// Name shared by runs that must not overlap
+    pub group: String,
+    /// Cancel the running workflow instead of waiting for it
+    #[serde(default)]
+    pub cancel_in_progress: bool,
+}
+
+impl<'de> Deserialize<'de> for ConcurrencyConfig {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum ConcurrencyHelper {
+            // Shorthand: `concurrency: deploy`
+            Group(String),
+            Full {
+                group: String,
+                #[serde(default)]
+                cancel_in_progress: bool,
+            },
+        }
+
+        let (group, cancel_in_progress) = match ConcurrencyHelper::deserialize(deserializer)? {
+            ConcurrencyHelper::Group(group) => (group, false),
+            ConcurrencyHelper::Full {
+                group,
+                cancel_in_progress,
+            } => (group, cancel_in_progress),
+        };
+        if group.trim().is_empty() {
+            return Err(serde::de::Error::custom(
+                "concurrency.group must not be empty",
+            ));
+        }
+        Ok(Self {
+            group,
+            cancel_in_progress,
+        })
+    }
+}
+
+/// Membership of a concurrency group, held for the duration of a run
+pub struct ConcurrencySlot {
+    group: String,
+    guard: Arc<Mutex<Option<Box<dyn StorageLockGuard>>>>,
+    heartbeat: JoinHandle<()>,
+}
+
+impl ConcurrencySlot {
+    /// Wait for the group and take it
+    ///
+    /// `on_cancel` runs if a newer run with `cancel_in_progress` cancels this
+    /// one; the group is already released at that point. Returns an error if
+    /// a newer run replaced this one while it was waiting.
+    pub async fn acquire(
+        config: &ConcurrencyConfig,
+        locks_dir: PathBuf,
+        on_cancel: impl Fn() + Send + Sync + 'static,
+    ) -> Result<Self> {
+        let backend = FileLockBackend::new(locks_dir)?;
+        let group = config.group.clone();
+        let run_id = uuid::Uuid::new_v4().simple().to_string()[..8].to_string();
+        let holder = format!(
+            "run {} (pid {} on {})",
+            run_id,
+            std::process::id(),
+            hostname::get()
+                .ok()
+                .and_then(|h| h.into_string().ok())
+                .unwrap_or_else(|| "unknown".to_string())
+        );
+        let lock_path = backend.lock_path(&group);
+        let pending_path = lock_path.with_extension("pending");
+        let cancel_path = lock_path.with_extension("cancel");
+
+        let mut queued = false;
+        let mut cancelled_token = None;
+        let guard = loop {
+            match backend.try_acquire(&group, &holder, LOCK_TTL).await {
+                Ok(guard) => break guard,
+                Err(e) if e.is_conflict() => {}
+                Err(e) => return Err(e.into()),
+            }
+
+            if !queued {
+                write_marker(&pending_path, &run_id).await?;
+                queued = true;
+            } else if read_marker(&pending_path).await.as_deref() != Some(run_id.as_str()) {
+                return Err(anyhow!(
+                    "Cancelled: a newer run is waiting in concurrency group '{}'",
+                    group
+                ));
+            }
+
+            if let Some(current) = backend.current(&group).await? {
+                if cancelled_token.as_ref() != Some(&current.token) {
+                    if config.cancel_in_progress {
+                        info!(
+                            "Cancelling {} in concurrency group '{}'",
+                            current.holder, group
+                        );
+                        write_marker(&cancel_path, &current.token).await?;
+                    } else {
+                        info!(
+                            "Waiting for {} in concurrency group '{}'",
+                            current.holder, group
+                        );
+                    }
+                    cancelled_token = Some(current.token);
+                }
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        };
+
+        if queued && read_marker(&pending_path).await.as_deref() == Some(run_id.as_str()) {
+            let _ = tokio::fs::remove_file(&pending_path).await;
+        }
+        info!("Acquired concurrency group '{}'", group);
+
+        let token = guard.lock_info().token.clone();
+        let guard = Arc::new(Mutex::new(Some(guard)));
+        let heartbeat = tokio::spawn(heartbeat(
+            group.clone(),
+            guard.clone(),
+            token,
+            cancel_path,
+            on_cancel,
+        ));
+        Ok(Self {
+            group,
+            guard,
+            heartbeat,
+        })
+    }
+
+    /// Name of the held group
+    pub fn group(&self) -> &str {
+        &self.group
+    }
+
+    /// Give up the group so the next run can start
+    pub async fn release(self) {
+        self.heartbeat.abort();
+        if let Some(guard) = self.guard.lock().await.take() {
+            if let Err(e) = guard.release().await {
+                warn!(
+                    "Failed to release concurrency group '{}': {}",
+                    self.group, e
+                );
+            }
+        }
+    }
+}
+
+/// Keep the lock alive and watch for cancellation requests
+async fn heartbeat(
+    group: String,
+    guard: Arc<Mutex<Option<Box<dyn StorageLockGuard>>>>,
+    token: String,
+    cancel_path: PathBuf,
+    on_cancel: impl Fn() + Send + Sync + 'static,
+) {
+    let mut last_beat = tokio::time::Instant::now();
+    loop {
+        tokio::time::sleep(POLL_INTERVAL).await;
+
+        if read_marker(&cancel_path).await.as_deref() == Some(token.as_str()) {
+            let _ = tokio::fs::remove_file(&cancel_path).await;
+            if let Some(guard) = guard.lock().await.take() {
+                let _ = guard.release().await;
+            }
+            warn!("Cancelled by a newer run in concurrency group '{}'", group);
+            on_cancel();
+            return;
+        }
+
+        if last_beat.elapsed() >= HEARTBEAT_INTERVAL {
+            last_beat = tokio::time::Instant::now();
+            let mut held = guard.lock().await;
+            if let Some(current) = held.as_mut() {
+                let renewal = current.lock_info().renewal(LOCK_TTL, Utc::now());
+                if let Err(e) = current.extend(renewal).await {
+                    // Another run may take the group once the lock expires,
+                    // so this one must not keep running outside it
+                    held.take();
+                    warn!("Lost concurrency group '{}': {}", group, e);
+                    on_cancel();
+                    return;
+                }
+            }
+        }
+    }
+}
+
+async fn read_marker(path: &Path) -> Option<String> {
+    tokio::fs::read_to_string(path)
+        .await
+        .ok()
+        .map(|content| content.trim().to_string())
+}
+
+/// Replace a marker file atomically so readers never see partial contents
+async fn write_marker(path: &Path, value: &str) -> Result<()> {
+    let temp = path.with_extension(format!("{}.tmp", value));
+    tokio::fs::write(&temp, value)
+        .await
+        .with_context(|| format!("Failed to write {}", temp.display()))?;
+    tokio::fs::rename(&temp, path)
+        .await
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::error::{StorageError, StorageResult};
+    use crate::storage::lock::StorageLock;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use tempfile::TempDir;
+
+    fn config(group: &str, cancel_in_progress: bool) -> ConcurrencyConfig {
+        ConcurrencyConfig {
+            group: group.to_string(),
+            cancel_in_progress,
+        }
+    }
+
+    /// Guard whose lock can no longer be extended, as after it expired
+    struct ExpiredGuard(StorageLock);
+
+    #[async_trait::async_trait]
+    impl StorageLockGuard for ExpiredGuard {
+        fn lock_info(&self) -> &StorageLock {
+            &self.0
+        }
+
+        async fn release(self: Box<Self>) -> StorageResult<()> {
+            Ok(())
+        }
+
+        async fn extend(&mut self, _additional_ttl: Duration) -> StorageResult<()> {
+            Err(StorageError::lock("lock held by another run"))
+        }
+
+        async fn is_valid(&self) -> StorageResult<bool> {
+            Ok(false)
+        }
+    }
+
+    #[test]
+    fn test_parse_config() {
+        let full: ConcurrencyConfig =
+            serde_yaml::from_str("group: deploy\ncancel_in_progress: true\n").unwrap();
+        assert_eq!(full, config("deploy", true));
+
+        let shorthand: ConcurrencyConfig = serde_yaml::from_str("deploy").unwrap();
+        assert_eq!(shorthand, config("deploy", false));
+
+        assert!(serde_yaml::from_str::<ConcurrencyConfig>("group: ''").is_err());
+    }
+
+    #[test]
+    fn test_late_heartbeats_never_let_the_lock_expire() {
+        let mut lock = StorageLock::new("deploy".into(), "run".into(), LOCK_TTL);
+        let start = lock.acquired_at;
+        // Every beat lands 3s late, as on a busy runtime
+        for beat in 1..=1000 {
+            let now = start + chrono::Duration::seconds(beat * 13);
+            let expiry = lock.acquired_at + chrono::Duration::from_std(lock.ttl).unwrap();
+            assert!(expiry > now, "lock expired before heartbeat {}", beat);
+
+            lock.ttl += lock.renewal(LOCK_TTL, now);
+            let expiry = lock.acquired_at + chrono::Duration::from_std(lock.ttl).unwrap();
+            assert_eq!(expiry, now + chrono::Duration::from_std(LOCK_TTL).unwrap());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_runs_queue_behind_holder() {
+        let dir = TempDir::new().unwrap();
+        let first = ConcurrencySlot::acquire(&config("deploy", false), dir.path().into(), || {})
+            .await
+            .unwrap();
+
+        let path = dir.path().to_path_buf();
+        let second = tokio::spawn(async move {
+            ConcurrencySlot::acquire(&config("deploy", false), path, || {}).await
+        });
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+        assert!(!second.is_finished());
+
+        first.release().await;
+        let second = tokio::time::timeout(Duration::from_secs(5), second)
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert_eq!(second.group(), "deploy");
+        second.release().await;
+    }
+
+    #[tokio::test]
+    async fn test_newer_waiting_run_replaces_older() {
+        let dir = TempDir::new().unwrap();
+        let holder = ConcurrencySlot::acquire(&config("deploy", false), dir.path().into(), || {})
+            .await
+            .unwrap();
+
+        let path = dir.path().to_path_buf();
+        let older = tokio::spawn(async move {
+            ConcurrencySlot::acquire(&config("deploy", false), path, || {}).await
+        });
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        let path = dir.path().to_path_buf();
+        let newer = tokio::spawn(async move {
+            ConcurrencySlot::acquire(&config("deploy", false), path, || {}).await
+        });
+
+        let older = tokio::time::timeout(Duration::from_secs(5), older)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(older.err().unwrap().to_string().contains("newer run"));
+
+        holder.release().await;
+        let newer = tokio::time::timeout(Duration::from_secs(5), newer)
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        newer.release().await;
+    }
+
+    #[tokio::test]
+    async fn test_cancel_in_progress() {
+        let dir = TempDir::new().unwrap();
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let flag = cancelled.clone();
+        let _holder =
+            ConcurrencySlot::acquire(&config("deploy", false), dir.path().into(), move || {
+                flag.store(true, Ordering::SeqCst)
+            })
+            .await
+            .unwrap();
+
+        let newer = tokio::time::timeout(
+            Duration::from_secs(5),
+            ConcurrencySlot::acquire(&config("deploy", true), dir.path().into(), || {}),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert!(cancelled.load(Ordering::SeqCst));
+        newer.release().await;
+    }
+
+    #[tokio::test]
+    async fn test_failed_heartbeat_cancels_run() {
+        let dir = TempDir::new().unwrap();
+        let lock = StorageLock::new("deploy".into(), "run".into(), LOCK_TTL);
+        let token = lock.token.clone();
+        let guard: Box<dyn StorageLockGuard> = Box::new(ExpiredGuard(lock));
+        let guard = Arc::new(Mutex::new(Some(guard)));
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let flag = cancelled.clone();
+
+        tokio::time::timeout(
+            HEARTBEAT_INTERVAL + Duration::from_secs(5),
+            heartbeat(
+                "deploy".into(),
+                guard.clone(),
+                token,
+                dir.path().join("cancel"),
+                move || flag.store(true, Ordering::SeqCst),
+            ),
+        )
+        .await
+        .unwrap();
+        assert!(cancelled.load(Ordering::SeqCst));
+        assert!(guard.lock().await.is_none());
+    }
+}
@@ -102,6 +102,9 @@ impl DefaultWorkflowCoordinator {
             name: None,
             command,
             claude: None,
+            model: None,
+            context: None,
+            validate_output: None,
             shell: None,
             test: None,
             foreach: None,
@@ -128,6 +131,13 @@ impl DefaultWorkflowCoordinator {
             validation_timeout: None,
             ignore_validation_failure: false,
             when: None,
+            assert: None,
+            summarize_commits: None,
+            claude_chat: None,
+            script: None,
+            snapshot: false,
+            required: false,
+            commit_policy: None,
         }
     }
 
@@ -337,6 +347,9 @@ mod tests {
     fn test_extract_workflow_step_command_claude() {
         let step = WorkflowStepCommand {
             claude: Some("claude-command".to_string()),
+            model: None,
+            context: None,
+            validate_output: None,
             shell: None,
             analyze: None,
             test: None,
@@ -355,6 +368,15 @@ mod tests {
             capture_format: None,
             capture_streams: None,
             output_file: None,
+            assert: None,
+            summarize_commits: None,
+            claude_chat: None,
+            script: None,
+            snapshot: false,
+            required: false,
+            commit_policy: None,
+            cwd: None,
+            env: HashMap::new(),
         };
         let result = DefaultWorkflowCoordinator::extract_workflow_step_command(&step);
         assert_eq!(result, "claude-command");
@@ -364,6 +386,9 @@ mod tests {
     fn test_extract_workflow_step_command_shell() {
         let step = WorkflowStepCommand {
             claude: None,
+            model: None,
+            context: None,
+            validate_output: None,
             shell: Some("ls -la".to_string()),
             analyze: None,
             test: None,
@@ -382,6 +407,15 @@ mod tests {
             capture_format: None,
             capture_streams: None,
             output_file: None,
+            assert: None,
+            summarize_commits: None,
+            claude_chat: None,
+            script: None,
+            snapshot: false,
+            required: false,
+            commit_policy: None,
+            cwd: None,
+            env: HashMap::new(),
         };
         let result = DefaultWorkflowCoordinator::extract_workflow_step_command(&step);
         assert_eq!(result, "shell ls -la");
@@ -392,6 +426,9 @@ mod tests {
         use crate::config::command::TestCommand;
         let step = WorkflowStepCommand {
             claude: None,
+            model: None,
+            context: None,
+            validate_output: None,
             shell: None,
             analyze: None,
             test: Some(TestCommand {
@@ -413,6 +450,15 @@ mod tests {
             capture_format: None,
             capture_streams: None,
             output_file: None,
+            assert: None,
+            summarize_commits: None,
+            claude_chat: None,
+            script: None,
+            snapshot: false,
+            required: false,
+            commit_policy: None,
+            cwd: None,
+            env: HashMap::new(),
         };
         let result = DefaultWorkflowCoordinator::extract_workflow_step_command(&step);
         assert_eq!(result, "test cargo test");
@@ -422,6 +468,9 @@ mod tests {
     fn test_extract_workflow_step_command_empty() {
         let step = WorkflowStepCommand {
             claude: None,
+            model: None,
+            context: None,
+            validate_output: None,
             shell: None,
             analyze: None,
             test: None,
@@ -440,6 +489,15 @@ mod tests {
             capture_format: None,
             capture_streams: None,
             output_file: None,
+            assert: None,
+            summarize_commits: None,
+            claude_chat: None,
+            script: None,
+            snapshot: false,
+            required: false,
+            commit_policy: None,
+            cwd: None,
+            env: HashMap::new(),
         };
         let result = DefaultWorkflowCoordinator::extract_workflow_step_command(&step);
         assert_eq!(result, "");
@@ -495,6 +553,9 @@ mod tests {
         // Test priority: claude > shell > test
         let step = WorkflowStepCommand {
             claude: Some("claude".to_string()),
+            model: None,
+            context: None,
+            validate_output: None,
             shell: Some("shell".to_string()),
             analyze: None,
             test: Some(crate::config::command::TestCommand {
@@ -516,6 +577,15 @@ mod tests {
             capture_format: None,
             capture_streams: None,
             output_file: None,
+            assert: None,
+            summarize_commits: None,
+            claude_chat: None,
+            script: None,
+            snapshot: false,
+            required: false,
+            commit_policy: None,
+            cwd: None,
+            env: HashMap::new(),
         };
         let result = DefaultWorkflowCoordinator::extract_workflow_step_command(&step);
         assert_eq!(
@@ -441,6 +441,9 @@ mod tests {
             WorkflowCommand::WorkflowStep(Box::new(WorkflowStepCommand {
                 analyze: None,
                 claude: Some("/prodigy-analyze".to_string()),
+                model: None,
+                context: None,
+                validate_output: None,
                 shell: None,
                 test: None,
                 foreach: None,
@@ -458,10 +461,22 @@ mod tests {
                 capture_format: None,
                 capture_streams: None,
                 output_file: None,
+                assert: None,
+                summarize_commits: None,
+                claude_chat: None,
+                script: None,
+                snapshot: false,
+                required: false,
+                commit_policy: None,
+                cwd: None,
+                env: HashMap::new(),
             })),
             WorkflowCommand::WorkflowStep(Box::new(WorkflowStepCommand {
                 analyze: None,
                 claude: None,
+                model: None,
+                context: None,
+                validate_output: None,
                 shell: Some("cargo test".to_string()),
                 test: None,
                 foreach: None,
@@ -479,10 +494,22 @@ mod tests {
                 capture_format: None,
                 capture_streams: None,
                 output_file: None,
+                assert: None,
+                summarize_commits: None,
+                claude_chat: None,
+                script: None,
+                snapshot: false,
+                required: false,
+                commit_policy: None,
+                cwd: None,
+                env: HashMap::new(),
             })),
             WorkflowCommand::WorkflowStep(Box::new(WorkflowStepCommand {
                 analyze: None,
                 claude: None,
+                model: None,
+                context: None,
+                validate_output: None,
                 shell: None,
                 test: Some(TestCommand {
                     command: "cargo build".to_string(),
@@ -503,6 +530,15 @@ mod tests {
                 capture_format: None,
                 capture_streams: None,
                 output_file: None,
+                assert: None,
+                summarize_commits: None,
+                claude_chat: None,
+                script: None,
+                snapshot: false,
+                required: false,
+                commit_policy: None,
+                cwd: None,
+                env: HashMap::new(),
             })),
         ];
 
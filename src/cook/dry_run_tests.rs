@@ -102,6 +102,13 @@ mod tests {
             env_files: None,
             profiles: None,
             merge: None,
+            concurrency: None,
+            claude_permissions: None,
+            always: Vec::new(),
+            strict_variables: None,
+            defaults: None,
+            protected_paths: None,
+            score_gate: None,
         };
 
         assert!(workflow.commands.is_empty());
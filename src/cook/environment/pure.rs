@@ -59,6 +59,7 @@ use std::path::PathBuf;
 ///     project_dir: Arc::new(PathBuf::from("/project")),
 ///     worktree_name: None,
 ///     session_id: Arc::from("test"),
+///     package: None,
 /// };
 ///
 /// let working_dir = resolve_working_directory(&step, &env, &context);
@@ -262,6 +263,7 @@ mod tests {
             project_dir: Arc::new(PathBuf::from("/project")),
             worktree_name: None,
             session_id: Arc::from("test"),
+            package: None,
         }
     }
 
@@ -0,0 +1,509 @@
+//! Failure injection for testing workflow resilience
+//!
+//! Chaos mode randomly fails, delays, or kills Claude and shell commands so
+//! that checkpoint, resume, and DLQ handling can be exercised before a long
+//! job depends on them. A [`ChaosMonkey`] decides what happens to each
+//! command; [`ChaosMonkey::run`] applies that decision around the command's
+//! future:
+//!
+//! - **fail** - the command is not run and returns exit code 1
+//! - **delay** - the command runs after a random pause
+//! - **kill** - the command is started and dropped (killing the child
+//!   process) after a random time, returning exit code 137 as if the agent
+//!   had been killed
+//!
+//! `--chaos` creates one monkey per run. The workflow executor holds it, and
+//! the map phase hands it to every MapReduce agent's command executor, so
+//! all commands of the run draw from the same fault sequence.
+//!
+//! # Spec format
+//!
+//! `--chaos` takes a comma-separated spec, e.g.
+//! `fail=10,delay=20,max-delay=5,kill=5,kill-after=30,target=shell,seed=42`.
+//! Rates are percentages of commands; durations are seconds. A bare number
+//! such as `--chaos 10` is shorthand for `fail=10`.
+
+use crate::cook::workflow::StepResult;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::fmt;
+use std::future::Future;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Exit code reported for commands killed by chaos mode (128 + SIGKILL)
+pub const CHAOS_KILL_EXIT_CODE: i32 = 137;
+
+const DEFAULT_MAX_DELAY: Duration = Duration::from_secs(5);
+const DEFAULT_KILL_AFTER: Duration = Duration::from_secs(10);
+
+/// Kind of command chaos mode applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChaosTarget {
+    Claude,
+    Shell,
+}
+
+impl fmt::Display for ChaosTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChaosTarget::Claude => write!(f, "claude"),
+            ChaosTarget::Shell => write!(f, "shell"),
+        }
+    }
+}
+
+/// Failure injection settings
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChaosConfig {
+    /// Fraction of commands (0.0-1.0) that fail without running
+    pub fail_rate: f64,
+    /// Fraction of commands delayed before running
+    pub delay_rate: f64,
+    /// Upper bound for injected delays
+    pub max_delay: Duration,
+    /// Fraction of commands killed while running
+    pub kill_rate: f64,
+    /// Upper bound for how long a command runs before it is killed
+    pub kill_after: Duration,
+    /// Command kinds affected (empty means all)
+    pub targets: Vec<ChaosTarget>,
+    /// Seed for reproducible runs
+    pub seed: Option<u64>,
+}
+
+impl Default for ChaosConfig {
+    fn default() -> Self {
+        Self {
+            fail_rate: 0.0,
+            delay_rate: 0.0,
+            max_delay: DEFAULT_MAX_DELAY,
+            kill_rate: 0.0,
+            kill_after: DEFAULT_KILL_AFTER,
+            targets: Vec::new(),
+            seed: None,
+        }
+    }
+}
+
+impl ChaosConfig {
+    /// Fail `percent`% of commands
+    pub fn fail_percent(mut self, percent: f64) -> Self {
+        self.fail_rate = percent / 100.0;
+        self
+    }
+
+    /// Delay `percent`% of commands by up to `max_delay`
+    pub fn delay_percent(mut self, percent: f64, max_delay: Duration) -> Self {
+        self.delay_rate = percent / 100.0;
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Kill `percent`% of commands after running for up to `kill_after`
+    pub fn kill_percent(mut self, percent: f64, kill_after: Duration) -> Self {
+        self.kill_rate = percent / 100.0;
+        self.kill_after = kill_after;
+        self
+    }
+
+    /// Only affect commands of `target` kind (may be called repeatedly)
+    pub fn target(mut self, target: ChaosTarget) -> Self {
+        self.targets.push(target);
+        self
+    }
+
+    /// Use a fixed seed so the same commands are affected on every run
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    fn applies_to(&self, target: ChaosTarget) -> bool {
+        self.targets.is_empty() || self.targets.contains(&target)
+    }
+}
+
+impl fmt::Display for ChaosConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "fail {:.0}%, delay {:.0}% (up to {}s), kill {:.0}% (within {}s)",
+            self.fail_rate * 100.0,
+            self.delay_rate * 100.0,
+            self.max_delay.as_secs(),
+            self.kill_rate * 100.0,
+            self.kill_after.as_secs()
+        )?;
+        if !self.targets.is_empty() {
+            let targets: Vec<String> = self.targets.iter().map(|t| t.to_string()).collect();
+            write!(f, ", targets: {}", targets.join("+"))?;
+        }
+        if let Some(seed) = self.seed {
+            write!(f, ", seed {}", seed)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for ChaosConfig {
+    type Err = String;
+
+    fn from_str(spec: &str) -> Result<Self, Self::Err> {
+        let spec = spec.trim();
+        if let Ok(percent) = spec.parse::<f64>() {
+            return Ok(Self::default().fail_percent(parse_percent("fail", percent)?));
+        }
+
+        let mut config = Self::default();
+        for entry in spec.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let (key, value) = entry
+                .split_once('=')
+                .ok_or_else(|| format!("Invalid chaos setting '{}', expected key=value", entry))?;
+            let number = || {
+                value
+                    .trim_end_matches(['%', 's'])
+                    .parse::<f64>()
+                    .map_err(|_| format!("Invalid number for chaos setting '{}': {}", key, value))
+            };
+            match key {
+                "fail" => config.fail_rate = parse_percent(key, number()?)? / 100.0,
+                "delay" => config.delay_rate = parse_percent(key, number()?)? / 100.0,
+                "kill" => config.kill_rate = parse_percent(key, number()?)? / 100.0,
+                "max-delay" => config.max_delay = parse_seconds(key, number()?)?,
+                "kill-after" => config.kill_after = parse_seconds(key, number()?)?,
+                "seed" => {
+                    config.seed = Some(
+                        value
+                            .parse()
+                            .map_err(|_| format!("Invalid chaos seed: {}", value))?,
+                    )
+                }
+                "target" => {
+                    for target in value.split('+') {
+                        match target {
+                            "claude" => config.targets.push(ChaosTarget::Claude),
+                            "shell" => config.targets.push(ChaosTarget::Shell),
+                            "all" => config.targets.clear(),
+                            other => {
+                                return Err(format!(
+                                    "Invalid chaos target '{}', expected claude, shell, or all",
+                                    other
+                                ))
+                            }
+                        }
+                    }
+                }
+                other => return Err(format!("Unknown chaos setting '{}'", other)),
+            }
+        }
+
+        if config.fail_rate + config.kill_rate > 1.0 {
+            return Err("Chaos fail and kill rates add up to more than 100%".to_string());
+        }
+        Ok(config)
+    }
+}
+
+fn parse_percent(key: &str, percent: f64) -> Result<f64, String> {
+    if (0.0..=100.0).contains(&percent) {
+        Ok(percent)
+    } else {
+        Err(format!(
+            "Chaos setting '{}' must be between 0 and 100, got {}",
+            key, percent
+        ))
+    }
+}
+
+fn parse_seconds(key: &str, seconds: f64) -> Result<Duration, String> {
+    if seconds.is_finite() && seconds >= 0.0 {
+        Ok(Duration::from_secs_f64(seconds))
+    } else {
+        Err(format!(
+            "Chaos setting '{}' must be a non-negative number of seconds",
+            key
+        ))
+    }
+}
+
+/// What chaos mode does to a single command
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChaosAction {
+    /// Run the command normally
+    Proceed,
+    /// Fail without running the command
+    Fail,
+    /// Wait, then run the command
+    Delay(Duration),
+    /// Run the command and kill it after the given time
+    Kill(Duration),
+}
+
+/// Counts of injected faults
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ChaosStats {
+    pub failures: usize,
+    pub delays: usize,
+    pub kills: usize,
+}
+
+/// Decides and applies injected faults
+pub struct ChaosMonkey {
+    config: ChaosConfig,
+    rng: Mutex<StdRng>,
+    failures: AtomicUsize,
+    delays: AtomicUsize,
+    kills: AtomicUsize,
+}
+
+impl fmt::Debug for ChaosMonkey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChaosMonkey")
+            .field("config", &self.config)
+            .field("stats", &self.stats())
+            .finish()
+    }
+}
+
+impl ChaosMonkey {
+    /// Faults without a seed of their own are drawn from the run's `seed`, if any
+    pub fn new(config: ChaosConfig, run_seed: Option<u64>) -> Self {
+        let rng = match config.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => super::determinism::rng(run_seed, "chaos", 0),
+        };
+        Self {
+            config,
+            rng: Mutex::new(rng),
+            failures: AtomicUsize::new(0),
+            delays: AtomicUsize::new(0),
+            kills: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn config(&self) -> &ChaosConfig {
+        &self.config
+    }
+
+    /// Faults injected so far
+    pub fn stats(&self) -> ChaosStats {
+        ChaosStats {
+            failures: self.failures.load(Ordering::Relaxed),
+            delays: self.delays.load(Ordering::Relaxed),
+            kills: self.kills.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Decide what happens to the next command of `target` kind
+    pub fn decide(&self, target: ChaosTarget) -> ChaosAction {
+        if !self.config.applies_to(target) {
+            return ChaosAction::Proceed;
+        }
+
+        let mut rng = match self.rng.lock() {
+            Ok(rng) => rng,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let roll: f64 = rng.random();
+        let config = &self.config;
+
+        if roll < config.fail_rate {
+            ChaosAction::Fail
+        } else if roll < config.fail_rate + config.kill_rate {
+            ChaosAction::Kill(random_duration(&mut rng, config.kill_after))
+        } else if rng.random::<f64>() < config.delay_rate {
+            ChaosAction::Delay(random_duration(&mut rng, config.max_delay))
+        } else {
+            ChaosAction::Proceed
+        }
+    }
+
+    /// Run `execute` with an injected fault, if one is chosen
+    pub async fn run<F, E>(
+        &self,
+        target: ChaosTarget,
+        command: &str,
+        execute: F,
+    ) -> Result<StepResult, E>
+    where
+        F: Future<Output = Result<StepResult, E>>,
+    {
+        match self.decide(target) {
+            ChaosAction::Proceed => execute.await,
+            ChaosAction::Fail => {
+                self.failures.fetch_add(1, Ordering::Relaxed);
+                tracing::warn!(
+                    "chaos: injected failure into {} command: {}",
+                    target,
+                    command
+                );
+                Ok(StepResult {
+                    success: false,
+                    exit_code: Some(1),
+                    stderr: format!("chaos: injected failure into {} command", target),
+                    ..Default::default()
+                })
+            }
+            ChaosAction::Delay(delay) => {
+                self.delays.fetch_add(1, Ordering::Relaxed);
+                tracing::warn!(
+                    "chaos: delaying {} command by {:.1}s: {}",
+                    target,
+                    delay.as_secs_f64(),
+                    command
+                );
+                tokio::time::sleep(delay).await;
+                execute.await
+            }
+            ChaosAction::Kill(after) => match tokio::time::timeout(after, execute).await {
+                Ok(result) => result,
+                Err(_) => {
+                    self.kills.fetch_add(1, Ordering::Relaxed);
+                    tracing::warn!(
+                        "chaos: killed {} command after {:.1}s: {}",
+                        target,
+                        after.as_secs_f64(),
+                        command
+                    );
+                    Ok(StepResult {
+                        success: false,
+                        exit_code: Some(CHAOS_KILL_EXIT_CODE),
+                        stderr: format!(
+                            "chaos: killed {} command after {:.1}s",
+                            target,
+                            after.as_secs_f64()
+                        ),
+                        ..Default::default()
+                    })
+                }
+            },
+        }
+    }
+}
+
+fn random_duration(rng: &mut StdRng, max: Duration) -> Duration {
+    if max.is_zero() {
+        return Duration::ZERO;
+    }
+    Duration::from_secs_f64(rng.random_range(0.0..max.as_secs_f64()))
+}
+
+/// Run `execute` under `monkey`, or directly when chaos is off
+pub async fn run_with_chaos<F, E>(
+    monkey: Option<&ChaosMonkey>,
+    target: ChaosTarget,
+    command: &str,
+    execute: F,
+) -> Result<StepResult, E>
+where
+    F: Future<Output = Result<StepResult, E>>,
+{
+    match monkey {
+        Some(monkey) => monkey.run(target, command, execute).await,
+        None => execute.await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ok() -> Result<StepResult, String> {
+        Ok(StepResult {
+            success: true,
+            exit_code: Some(0),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_parse_spec() {
+        let config: ChaosConfig =
+            "fail=10,delay=20%,max-delay=2s,kill=5,kill-after=30,target=shell,seed=7"
+                .parse()
+                .unwrap();
+        assert_eq!(config.fail_rate, 0.1);
+        assert_eq!(config.delay_rate, 0.2);
+        assert_eq!(config.max_delay, Duration::from_secs(2));
+        assert_eq!(config.kill_rate, 0.05);
+        assert_eq!(config.kill_after, Duration::from_secs(30));
+        assert_eq!(config.targets, vec![ChaosTarget::Shell]);
+        assert_eq!(config.seed, Some(7));
+
+        let shorthand: ChaosConfig = "25".parse().unwrap();
+        assert_eq!(shorthand.fail_rate, 0.25);
+
+        assert!("fail=150".parse::<ChaosConfig>().is_err());
+        assert!("fail=60,kill=60".parse::<ChaosConfig>().is_err());
+        assert!("explode=1".parse::<ChaosConfig>().is_err());
+        assert!("target=git".parse::<ChaosConfig>().is_err());
+    }
+
+    #[test]
+    fn test_seeded_decisions_are_reproducible_and_respect_rates() {
+        let config = ChaosConfig::default().fail_percent(30.0).seed(42);
+        let first = ChaosMonkey::new(config.clone(), None);
+        let second = ChaosMonkey::new(config, None);
+
+        let decisions: Vec<ChaosAction> =
+            (0..200).map(|_| first.decide(ChaosTarget::Shell)).collect();
+        let repeated: Vec<ChaosAction> = (0..200)
+            .map(|_| second.decide(ChaosTarget::Shell))
+            .collect();
+        assert_eq!(decisions, repeated);
+
+        let failures = decisions
+            .iter()
+            .filter(|a| **a == ChaosAction::Fail)
+            .count();
+        assert!((30..=90).contains(&failures), "got {failures} failures");
+    }
+
+    #[test]
+    fn test_targets_limit_affected_commands() {
+        let monkey = ChaosMonkey::new(
+            ChaosConfig::default()
+                .fail_percent(100.0)
+                .target(ChaosTarget::Claude),
+            None,
+        );
+        assert_eq!(monkey.decide(ChaosTarget::Shell), ChaosAction::Proceed);
+        assert_eq!(monkey.decide(ChaosTarget::Claude), ChaosAction::Fail);
+    }
+
+    #[tokio::test]
+    async fn test_run_injects_failures_and_kills() {
+        let failing = ChaosMonkey::new(ChaosConfig::default().fail_percent(100.0), None);
+        let result = failing
+            .run(ChaosTarget::Shell, "true", async { ok() })
+            .await;
+        let result = result.unwrap();
+        assert!(!result.success);
+        assert_eq!(failing.stats().failures, 1);
+
+        let killing = ChaosMonkey::new(
+            ChaosConfig::default().kill_percent(100.0, Duration::from_millis(10)),
+            None,
+        );
+        let slow = async {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            ok()
+        };
+        let result = killing.run(ChaosTarget::Claude, "/slow", slow).await;
+        let result = result.unwrap();
+        assert_eq!(result.exit_code, Some(CHAOS_KILL_EXIT_CODE));
+        assert_eq!(killing.stats().kills, 1);
+
+        let idle = ChaosMonkey::new(ChaosConfig::default(), None);
+        assert!(
+            idle.run(ChaosTarget::Shell, "true", async { ok() })
+                .await
+                .unwrap()
+                .success
+        );
+        assert_eq!(idle.stats(), ChaosStats::default());
+    }
+}
@@ -1,5 +1,8 @@
 //! Claude CLI execution implementation
 
+use super::claude_capabilities::{self, ClaudeCapabilities, PERMISSION_MODE_ENV};
+use super::claude_permissions::{self, ClaudePermissions};
+use super::claude_sessions::{self, WarmSessions};
 use super::{CommandExecutor, CommandRunner, ExecutionContext, ExecutionResult};
 use crate::cook::execution::events::EventLogger;
 use crate::testing::config::TestConfiguration;
@@ -9,6 +12,11 @@ use std::collections::HashMap;
 use std::path::Path;
 use std::sync::Arc;
 
+/// Environment variable selecting the model passed to `claude --model`
+///
+/// Set by the workflow executor from a step's `model:` field.
+pub const CLAUDE_MODEL_ENV: &str = "PRODIGY_CLAUDE_MODEL";
+
 /// Trait for executing Claude commands
 #[async_trait]
 pub trait ClaudeExecutor: Send + Sync {
@@ -25,6 +33,14 @@ pub trait ClaudeExecutor: Send + Sync {
 
     /// Get Claude CLI version
     async fn get_claude_version(&self) -> Result<String>;
+
+    /// Detect the installed Claude CLI's capabilities
+    ///
+    /// Fails if the installed version is unsupported. The default
+    /// implementation assumes a current CLI.
+    async fn probe_capabilities(&self) -> Result<ClaudeCapabilities> {
+        Ok(ClaudeCapabilities::assume_latest())
+    }
 }
 
 /// Implementation of Claude executor
@@ -33,6 +49,7 @@ pub struct ClaudeExecutorImpl<R: CommandRunner> {
     test_config: Option<Arc<TestConfiguration>>,
     event_logger: Option<Arc<EventLogger>>,
     verbosity: u8,
+    warm_sessions: WarmSessions,
 }
 
 impl<R: CommandRunner> ClaudeExecutorImpl<R> {
@@ -43,6 +60,7 @@ impl<R: CommandRunner> ClaudeExecutorImpl<R> {
             test_config: None,
             event_logger: None,
             verbosity: 0,
+            warm_sessions: WarmSessions::default(),
         }
     }
 
@@ -53,6 +71,7 @@ impl<R: CommandRunner> ClaudeExecutorImpl<R> {
             test_config: Some(test_config),
             event_logger: None,
             verbosity: 0,
+            warm_sessions: WarmSessions::default(),
         }
     }
 
@@ -88,10 +107,21 @@ impl<R: CommandRunner + 'static> ClaudeExecutor for ClaudeExecutorImpl<R> {
         }
 
         // Streaming is enabled by default for auditability
-        // Only disabled if explicitly set to "false"
-        let streaming_disabled = env_vars
-            .get("PRODIGY_CLAUDE_STREAMING")
-            .is_some_and(|v| v == "false");
+        // Only disabled if explicitly set to "false" or unsupported by the CLI
+        let capabilities = claude_capabilities::detected_capabilities();
+        let streaming_disabled = !capabilities.stream_json
+            || env_vars
+                .get("PRODIGY_CLAUDE_STREAMING")
+                .is_some_and(|v| v == "false");
+        let mut cli_flags = match env_vars.get(claude_permissions::PERMISSIONS_ENV) {
+            Some(value) => ClaudePermissions::from_env_value(value)?.cli_args(&capabilities)?,
+            None => capabilities
+                .permission_args(env_vars.get(PERMISSION_MODE_ENV).map(String::as_str))?,
+        };
+        cli_flags.extend(build_model_args(&env_vars));
+        let explicit_resume = claude_sessions::explicit_resume_args(&env_vars);
+        let resuming = !explicit_resume.is_empty();
+        cli_flags.extend(explicit_resume);
 
         tracing::debug!(
             "Claude execution mode: streaming={}, env_var={:?}",
@@ -99,15 +129,31 @@ impl<R: CommandRunner + 'static> ClaudeExecutor for ClaudeExecutorImpl<R> {
             env_vars.get("PRODIGY_CLAUDE_STREAMING")
         );
 
-        if !streaming_disabled {
+        // Session IDs are only reported in streaming mode
+        let reuse_scope = (!streaming_disabled && claude_sessions::reuse_enabled(&env_vars))
+            .then(|| claude_sessions::scope_key(project_path, &env_vars));
+
+        if let Some(scope) = reuse_scope {
+            if !resuming {
+                cli_flags.extend(self.warm_sessions.resume_args(&scope));
+            }
+            let result = self
+                .execute_with_streaming(command, project_path, env_vars, &cli_flags)
+                .await;
+            match &result {
+                Ok(r) => self.warm_sessions.record(&scope, r.success, &r.stdout),
+                Err(_) => self.warm_sessions.record(&scope, false, ""),
+            }
+            result
+        } else if !streaming_disabled {
             // Default: streaming mode for audit trail and debugging
             tracing::debug!("Using streaming mode for Claude command");
-            self.execute_with_streaming(command, project_path, env_vars)
+            self.execute_with_streaming(command, project_path, env_vars, &cli_flags)
                 .await
         } else {
             // Explicit opt-out: print mode for resource-constrained environments
             tracing::debug!("Using print mode for Claude command");
-            self.execute_with_print(command, project_path, env_vars)
+            self.execute_with_print(command, project_path, env_vars, &cli_flags)
                 .await
         }
     }
@@ -145,6 +191,23 @@ impl<R: CommandRunner + 'static> ClaudeExecutor for ClaudeExecutorImpl<R> {
             anyhow::bail!("Failed to get Claude version")
         }
     }
+
+    async fn probe_capabilities(&self) -> Result<ClaudeCapabilities> {
+        let test_mode = self
+            .test_config
+            .as_ref()
+            .map(|c| c.test_mode)
+            .unwrap_or(false);
+        if test_mode {
+            return Ok(ClaudeCapabilities::assume_latest());
+        }
+
+        let version = self.get_claude_version().await?;
+        let capabilities = ClaudeCapabilities::from_version_output(&version)?;
+        tracing::debug!("Detected Claude CLI capabilities: {:?}", capabilities);
+        claude_capabilities::record_detected_capabilities(capabilities);
+        Ok(capabilities)
+    }
 }
 
 impl<R: CommandRunner> ClaudeExecutorImpl<R> {
@@ -154,6 +217,7 @@ impl<R: CommandRunner> ClaudeExecutorImpl<R> {
         command: &str,
         project_path: &Path,
         env_vars: HashMap<String, String>,
+        cli_flags: &[String],
     ) -> Result<ExecutionResult> {
         // Note: --print mode doesn't stream JSON, so no log file is created
 
@@ -175,11 +239,9 @@ impl<R: CommandRunner> ClaudeExecutorImpl<R> {
         // Claude requires some input on stdin to work properly
         context.stdin = Some("".to_string());
 
-        let args = vec![
-            "--print".to_string(),
-            "--dangerously-skip-permissions".to_string(),
-            command.to_string(),
-        ];
+        let mut args = vec!["--print".to_string()];
+        args.extend_from_slice(cli_flags);
+        args.push(command.to_string());
         tracing::debug!("Executing claude command with args: {:?}", args);
 
         let result = self
@@ -222,6 +284,7 @@ impl<R: CommandRunner> ClaudeExecutorImpl<R> {
         command: &str,
         project_path: &Path,
         env_vars: HashMap<String, String>,
+        cli_flags: &[String],
     ) -> Result<ExecutionResult> {
         // Generate log file path that Prodigy will save the streaming JSON to
         // Use temp directory in test environments (when RUST_TEST_THREADS is set or in cfg(test))
@@ -246,7 +309,7 @@ impl<R: CommandRunner> ClaudeExecutorImpl<R> {
         }
 
         // Build command args using pure helper function
-        let args = build_streaming_claude_args(command);
+        let args = build_streaming_claude_args(command, cli_flags);
 
         tracing::debug!(
             "Executing claude command in streaming mode with args: {:?}",
@@ -263,6 +326,29 @@ impl<R: CommandRunner> ClaudeExecutorImpl<R> {
             print_to_console,
         );
 
+        // Write the log as Claude streams so it can be followed while running,
+        // and index it under the session, job, agent and item running the command
+        let (processor, streamed_to_log) = match std::fs::File::create(&log_path) {
+            Ok(file) => {
+                let processor: Box<dyn crate::subprocess::streaming::StreamProcessor> =
+                    Box::new(super::claude_logs::LogFileProcessor::new(file, processor));
+                (processor, true)
+            }
+            Err(e) => {
+                tracing::warn!("Failed to create streaming JSON log: {}", e);
+                (processor, false)
+            }
+        };
+        let log_entry = log_path.parent().map(|log_dir| {
+            let entry =
+                super::claude_logs::ClaudeLogEntry::from_env(log_path.clone(), command, &env_vars);
+            if let Err(e) = super::claude_logs::record(log_dir, &entry) {
+                tracing::warn!("Failed to index streaming JSON log: {}", e);
+            }
+            (log_dir.to_path_buf(), entry)
+        });
+        let start = super::claude_runs::GitPosition::read(project_path).await;
+
         // Use the streaming interface
         let result = self
             .runner
@@ -279,16 +365,32 @@ impl<R: CommandRunner> ClaudeExecutorImpl<R> {
                 );
                 // Fall back to print mode
                 return self
-                    .execute_with_print(command, project_path, env_vars)
+                    .execute_with_print(command, project_path, env_vars, cli_flags)
                     .await;
             }
             other => other,
         };
 
+        // Link the Claude session to the commits it made for `prodigy analytics sessions`
+        if let (Ok(execution_result), Some((log_dir, entry))) = (&result, &log_entry) {
+            let run = super::claude_runs::ClaudeRun::new(
+                entry,
+                &start,
+                super::claude_sessions::parse_session_id(&execution_result.stdout),
+                execution_result.success,
+                start.commits_since(project_path).await,
+            );
+            if let Err(e) = super::claude_runs::record(log_dir, &run) {
+                tracing::warn!("Failed to record Claude run: {}", e);
+            }
+        }
+
         match result {
             Ok(mut execution_result) => {
-                // Save the streaming JSON output to the log file
-                if let Err(e) =
+                // Save the streaming JSON output to the log file unless it was streamed there
+                if streamed_to_log {
+                    execution_result = execution_result.with_json_log_location(log_path);
+                } else if let Err(e) =
                     save_streaming_output_to_file(&execution_result.stdout, &log_path).await
                 {
                     tracing::warn!("Failed to save streaming JSON log: {}", e);
@@ -365,16 +467,13 @@ impl<R: CommandRunner> ClaudeExecutorImpl<R> {
 
 // Pure helper functions for configuration parsing
 
-/// Generate a unique log file path for saving Claude streaming JSON in home directory
-/// Path format: ~/.prodigy/logs/claude-streaming/{timestamp}-{uuid}.jsonl
+/// Generate a unique log file path for saving Claude streaming JSON in global storage
+/// Path format: ~/.prodigy/logs/claude-streaming/{timestamp}-{uuid}.jsonl (by default)
 fn generate_streaming_log_path_home() -> Result<std::path::PathBuf> {
     use chrono::Utc;
     use uuid::Uuid;
 
-    let home =
-        dirs::home_dir().ok_or_else(|| anyhow::anyhow!("Could not determine home directory"))?;
-
-    let log_dir = home.join(".prodigy/logs/claude-streaming");
+    let log_dir = super::claude_logs::streaming_log_dir()?;
 
     // Create directory if it doesn't exist
     std::fs::create_dir_all(&log_dir)
@@ -499,14 +598,27 @@ fn format_error_with_log_location(
 
 /// Build command arguments for streaming Claude execution
 /// Pure function that constructs the required args for --output-format stream-json mode
-fn build_streaming_claude_args(command: &str) -> Vec<String> {
-    vec![
+fn build_streaming_claude_args(command: &str, cli_flags: &[String]) -> Vec<String> {
+    let mut args = vec![
         "--output-format".to_string(),
         "stream-json".to_string(),
         "--verbose".to_string(),
-        "--dangerously-skip-permissions".to_string(),
-        command.to_string(),
-    ]
+    ];
+    args.extend_from_slice(cli_flags);
+    args.push(command.to_string());
+    args
+}
+
+/// Build the `--model` arguments requested through [`CLAUDE_MODEL_ENV`]
+fn build_model_args(env_vars: &HashMap<String, String>) -> Vec<String> {
+    match env_vars
+        .get(CLAUDE_MODEL_ENV)
+        .map(|m| m.trim())
+        .filter(|m| !m.is_empty())
+    {
+        Some(model) => vec!["--model".to_string(), model.to_string()],
+        None => Vec::new(),
+    }
 }
 
 /// Build execution context for streaming Claude command
@@ -724,9 +836,13 @@ mod tests {
 
     // Phase 4: Tests for command args builder
 
+    fn skip_permissions() -> Vec<String> {
+        vec!["--dangerously-skip-permissions".to_string()]
+    }
+
     #[test]
     fn test_build_streaming_claude_args() {
-        let args = build_streaming_claude_args("/test-command");
+        let args = build_streaming_claude_args("/test-command", &skip_permissions());
 
         assert_eq!(args.len(), 5);
         assert_eq!(args[0], "--output-format");
@@ -738,16 +854,16 @@ mod tests {
 
     #[test]
     fn test_build_streaming_claude_args_different_commands() {
-        let args1 = build_streaming_claude_args("/prodigy-lint");
+        let args1 = build_streaming_claude_args("/prodigy-lint", &skip_permissions());
         assert_eq!(args1[4], "/prodigy-lint");
 
-        let args2 = build_streaming_claude_args("/fix-issue");
+        let args2 = build_streaming_claude_args("/fix-issue", &skip_permissions());
         assert_eq!(args2[4], "/fix-issue");
     }
 
     #[test]
     fn test_build_streaming_claude_args_required_flags() {
-        let args = build_streaming_claude_args("/any-command");
+        let args = build_streaming_claude_args("/any-command", &skip_permissions());
 
         // Verify all required flags are present
         assert!(args.contains(&"--output-format".to_string()));
@@ -756,6 +872,28 @@ mod tests {
         assert!(args.contains(&"--dangerously-skip-permissions".to_string()));
     }
 
+    #[test]
+    fn test_build_streaming_claude_args_permission_mode() {
+        let permission_args = vec!["--permission-mode".to_string(), "acceptEdits".to_string()];
+        let args = build_streaming_claude_args("/any-command", &permission_args);
+
+        assert_eq!(&args[3..5], permission_args.as_slice());
+        assert!(!args.contains(&"--dangerously-skip-permissions".to_string()));
+        assert_eq!(args.last().map(String::as_str), Some("/any-command"));
+    }
+
+    #[test]
+    fn test_build_model_args() {
+        let mut env_vars = HashMap::new();
+        assert!(build_model_args(&env_vars).is_empty());
+
+        env_vars.insert(CLAUDE_MODEL_ENV.to_string(), " ".to_string());
+        assert!(build_model_args(&env_vars).is_empty());
+
+        env_vars.insert(CLAUDE_MODEL_ENV.to_string(), "opus".to_string());
+        assert_eq!(build_model_args(&env_vars), vec!["--model", "opus"]);
+    }
+
     #[tokio::test]
     async fn test_claude_verbosity_streaming() {
         // Test that verbosity level controls streaming output
@@ -835,6 +973,24 @@ mod tests {
         assert_eq!(version, "claude version 1.0.0");
     }
 
+    #[tokio::test]
+    async fn test_probe_capabilities_rejects_unsupported_version() {
+        let mock_runner = MockCommandRunner::new();
+        mock_runner.add_response(ExecutionResult {
+            success: true,
+            stdout: "0.1.0 (Claude Code)\n".to_string(),
+            stderr: String::new(),
+            exit_code: Some(0),
+            metadata: HashMap::new(),
+        });
+
+        let executor = ClaudeExecutorImpl::new(mock_runner);
+        let err = executor.probe_capabilities().await.unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Claude CLI 0.1.0 is not supported"));
+    }
+
     #[tokio::test]
     async fn test_claude_command_execution() {
         let mock_runner = MockCommandRunner::new();
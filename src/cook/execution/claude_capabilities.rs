@@ -0,0 +1,255 @@
+//! Claude CLI version detection and capability negotiation
+//!
+//! Prodigy depends on specific Claude CLI flags (`--print`,
+//! `--output-format stream-json`, `--dangerously-skip-permissions`,
+//! `--permission-mode`). Older CLI releases lack some of them, and running a
+//! workflow against such a release used to fail mid-workflow with an
+//! unhelpful argument error.
+//!
+//! The installed version is probed once from `claude --version` during the
+//! prerequisite checks. Versions older than [`MIN_SUPPORTED_CLAUDE_VERSION`]
+//! are rejected up front; for supported versions the detected
+//! [`ClaudeCapabilities`] decide which flags each Claude invocation uses.
+//! When the version output cannot be parsed, Prodigy assumes a current CLI
+//! and keeps its default flags.
+
+use anyhow::{anyhow, Result};
+use std::fmt;
+use std::sync::OnceLock;
+
+/// Environment variable selecting a Claude permission mode
+///
+/// When unset Prodigy passes `--dangerously-skip-permissions`; when set its
+/// value is passed as `--permission-mode <value>`.
+pub const PERMISSION_MODE_ENV: &str = "PRODIGY_CLAUDE_PERMISSION_MODE";
+
+/// Oldest Claude CLI release Prodigy can drive
+pub const MIN_SUPPORTED_CLAUDE_VERSION: ClaudeCliVersion = ClaudeCliVersion::new(0, 2, 50);
+
+/// First Claude CLI release with `--output-format stream-json`
+pub const STREAM_JSON_MIN_VERSION: ClaudeCliVersion = ClaudeCliVersion::new(0, 2, 66);
+
+/// First Claude CLI release with `--permission-mode`
+pub const PERMISSION_MODE_MIN_VERSION: ClaudeCliVersion = ClaudeCliVersion::new(1, 0, 0);
+
+/// Semantic version of the installed Claude CLI
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ClaudeCliVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl ClaudeCliVersion {
+    /// Create a version from its components
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    /// Extract the version from `claude --version` output
+    ///
+    /// Accepts output such as `1.0.51 (Claude Code)` or
+    /// `claude version 1.0.0`: the first whitespace-separated token of the
+    /// form `MAJOR.MINOR[.PATCH]` is used, ignoring a leading `v` and any
+    /// pre-release suffix.
+    pub fn parse(output: &str) -> Option<Self> {
+        output.split_whitespace().find_map(parse_version_token)
+    }
+}
+
+impl fmt::Display for ClaudeCliVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+fn parse_version_token(token: &str) -> Option<ClaudeCliVersion> {
+    let token = token.strip_prefix('v').unwrap_or(token);
+    let core = token.split(['-', '+']).next().unwrap_or(token);
+    let mut parts = core.split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    let patch = match parts.next() {
+        Some(patch) => patch.parse().ok()?,
+        None => 0,
+    };
+    if parts.next().is_some() {
+        return None;
+    }
+    Some(ClaudeCliVersion::new(major, minor, patch))
+}
+
+/// Flags supported by the installed Claude CLI
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClaudeCapabilities {
+    /// Detected version, or `None` if it could not be determined
+    pub version: Option<ClaudeCliVersion>,
+    /// Whether `--output-format stream-json` is available
+    pub stream_json: bool,
+    /// Whether `--permission-mode` is available
+    pub permission_mode: bool,
+}
+
+impl ClaudeCapabilities {
+    /// Capabilities of a current Claude CLI, used when no version was detected
+    pub const fn assume_latest() -> Self {
+        Self {
+            version: None,
+            stream_json: true,
+            permission_mode: true,
+        }
+    }
+
+    /// Capabilities of a specific Claude CLI version
+    pub fn for_version(version: ClaudeCliVersion) -> Self {
+        Self {
+            version: Some(version),
+            stream_json: version >= STREAM_JSON_MIN_VERSION,
+            permission_mode: version >= PERMISSION_MODE_MIN_VERSION,
+        }
+    }
+
+    /// Negotiate capabilities from `claude --version` output
+    ///
+    /// Fails if the version is older than [`MIN_SUPPORTED_CLAUDE_VERSION`].
+    pub fn from_version_output(output: &str) -> Result<Self> {
+        let Some(version) = ClaudeCliVersion::parse(output) else {
+            tracing::warn!(
+                "Could not determine Claude CLI version from '{}', assuming a current release",
+                output.trim()
+            );
+            return Ok(Self::assume_latest());
+        };
+
+        if version < MIN_SUPPORTED_CLAUDE_VERSION {
+            return Err(anyhow!(
+                "Claude CLI {} is not supported; Prodigy requires Claude CLI {} or newer.\n\
+                 Upgrade with: npm install -g @anthropic-ai/claude-code",
+                version,
+                MIN_SUPPORTED_CLAUDE_VERSION
+            ));
+        }
+
+        Ok(Self::for_version(version))
+    }
+
+    /// Arguments granting Claude permission to run tools unattended
+    ///
+    /// Without a requested `mode` this is `--dangerously-skip-permissions`;
+    /// with one it is `--permission-mode <mode>`, which fails for CLI
+    /// versions that predate the flag.
+    pub fn permission_args(&self, mode: Option<&str>) -> Result<Vec<String>> {
+        match mode.map(str::trim).filter(|m| !m.is_empty()) {
+            None => Ok(vec!["--dangerously-skip-permissions".to_string()]),
+            Some(mode) if self.permission_mode => {
+                Ok(vec!["--permission-mode".to_string(), mode.to_string()])
+            }
+            Some(mode) => Err(anyhow!(
+                "{}={} requires Claude CLI {} or newer (installed: {})",
+                PERMISSION_MODE_ENV,
+                mode,
+                PERMISSION_MODE_MIN_VERSION,
+                self.version
+                    .map(|v| v.to_string())
+                    .unwrap_or_else(|| "unknown".to_string())
+            )),
+        }
+    }
+}
+
+impl Default for ClaudeCapabilities {
+    fn default() -> Self {
+        Self::assume_latest()
+    }
+}
+
+static DETECTED_CAPABILITIES: OnceLock<ClaudeCapabilities> = OnceLock::new();
+
+/// Remember the capabilities probed for this process
+///
+/// Only the first probe is kept; the installed CLI does not change mid-run.
+pub fn record_detected_capabilities(capabilities: ClaudeCapabilities) {
+    let _ = DETECTED_CAPABILITIES.set(capabilities);
+}
+
+/// Capabilities probed for this process, or [`ClaudeCapabilities::assume_latest`]
+pub fn detected_capabilities() -> ClaudeCapabilities {
+    DETECTED_CAPABILITIES.get().copied().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_version_formats() {
+        assert_eq!(
+            ClaudeCliVersion::parse("1.0.51 (Claude Code)"),
+            Some(ClaudeCliVersion::new(1, 0, 51))
+        );
+        assert_eq!(
+            ClaudeCliVersion::parse("claude version 1.0.0\n"),
+            Some(ClaudeCliVersion::new(1, 0, 0))
+        );
+        assert_eq!(
+            ClaudeCliVersion::parse("v2.1-beta.3"),
+            Some(ClaudeCliVersion::new(2, 1, 0))
+        );
+        assert_eq!(ClaudeCliVersion::parse("Claude Code"), None);
+        assert_eq!(ClaudeCliVersion::parse("1.2.3.4"), None);
+    }
+
+    #[test]
+    fn test_unsupported_version_fails_fast() {
+        let err = ClaudeCapabilities::from_version_output("0.2.10 (Claude Code)").unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("Claude CLI 0.2.10 is not supported"));
+        assert!(message.contains(&MIN_SUPPORTED_CLAUDE_VERSION.to_string()));
+    }
+
+    #[test]
+    fn test_capabilities_follow_version_thresholds() {
+        let old = ClaudeCapabilities::from_version_output("0.2.60").unwrap();
+        assert!(!old.stream_json);
+        assert!(!old.permission_mode);
+
+        let streaming = ClaudeCapabilities::from_version_output("0.2.66").unwrap();
+        assert!(streaming.stream_json);
+        assert!(!streaming.permission_mode);
+
+        let current = ClaudeCapabilities::from_version_output("1.0.51 (Claude Code)").unwrap();
+        assert!(current.stream_json);
+        assert!(current.permission_mode);
+    }
+
+    #[test]
+    fn test_unparseable_version_assumes_latest() {
+        let caps = ClaudeCapabilities::from_version_output("unknown build").unwrap();
+        assert_eq!(caps, ClaudeCapabilities::assume_latest());
+    }
+
+    #[test]
+    fn test_permission_args() {
+        let current = ClaudeCapabilities::assume_latest();
+        assert_eq!(
+            current.permission_args(None).unwrap(),
+            vec!["--dangerously-skip-permissions"]
+        );
+        assert_eq!(
+            current.permission_args(Some("  ")).unwrap(),
+            vec!["--dangerously-skip-permissions"]
+        );
+        assert_eq!(
+            current.permission_args(Some("acceptEdits")).unwrap(),
+            vec!["--permission-mode", "acceptEdits"]
+        );
+
+        let old = ClaudeCapabilities::for_version(ClaudeCliVersion::new(0, 2, 70));
+        let err = old.permission_args(Some("acceptEdits")).unwrap_err();
+        assert!(err.to_string().contains("installed: 0.2.70"));
+    }
+}
@@ -147,7 +147,8 @@ mod tests {
             log_path
         );
 
+        // Lines are appended as they stream, each newline-terminated
         let contents = fs::read_to_string(log_path).unwrap();
-        assert_eq!(contents, test_json);
+        assert_eq!(contents, format!("{}\n", test_json));
     }
 }
@@ -0,0 +1,380 @@
+//! Claude streaming log index and rendering
+//!
+//! Streaming Claude runs write their raw JSON events to
+//! `logs/claude-streaming/{timestamp}-{uuid}.jsonl` in the global storage
+//! directory (`~/.prodigy` by default). The file name says nothing about
+//! which run produced it, so every log is also recorded in an `index.jsonl`
+//! next to it with the session, MapReduce job, agent and work item taken from
+//! the Claude command's environment. `prodigy logs claude` reads the index to
+//! find a run's logs and renders them with [`render_event`].
+
+use crate::subprocess::streaming::{StreamProcessor, StreamSource};
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Environment variable naming the MapReduce job running a Claude command
+pub use crate::subprocess::metadata::JOB_ID_ENV;
+
+/// Environment variable naming the MapReduce agent running a Claude command
+pub const AGENT_ID_ENV: &str = "PRODIGY_AGENT_ID";
+
+/// Environment variable naming the work item a Claude command processes
+pub use crate::subprocess::metadata::ITEM_ID_ENV;
+
+/// Index file listing every streaming log
+pub const INDEX_FILE: &str = "index.jsonl";
+
+/// Longest tool input or result shown on one rendered line
+const MAX_RENDERED_CHARS: usize = 160;
+
+/// One streaming log and the run that produced it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClaudeLogEntry {
+    pub log: PathBuf,
+    pub started_at: DateTime<Utc>,
+    pub command: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub job_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub item_id: Option<String>,
+}
+
+impl ClaudeLogEntry {
+    /// Describe a log from the Claude command's environment (pure function)
+    pub fn from_env(log: PathBuf, command: &str, env_vars: &HashMap<String, String>) -> Self {
+        let var = |key: &str| env_vars.get(key).filter(|v| !v.is_empty()).cloned();
+        Self {
+            log,
+            started_at: Utc::now(),
+            command: command.to_string(),
+            session_id: var(crate::cook::workflow::checkpoint_marks::SESSION_ID_ENV),
+            job_id: var(JOB_ID_ENV),
+            agent_id: var(AGENT_ID_ENV),
+            item_id: var(ITEM_ID_ENV),
+        }
+    }
+
+    /// Whether the log belongs to `run_id`, a session or MapReduce job ID (pure function)
+    pub fn belongs_to(&self, run_id: &str) -> bool {
+        self.session_id.as_deref() == Some(run_id) || self.job_id.as_deref() == Some(run_id)
+    }
+
+    /// One-line header shown above the log's events (pure function)
+    pub fn header(&self) -> String {
+        let mut labels = Vec::new();
+        if let Some(agent) = &self.agent_id {
+            labels.push(format!("agent {}", agent));
+        }
+        if let Some(item) = &self.item_id {
+            labels.push(format!("item {}", item));
+        }
+        let labels = if labels.is_empty() {
+            String::new()
+        } else {
+            format!(" ({})", labels.join(", "))
+        };
+        format!(
+            "── {} {}{} ──",
+            self.started_at.format("%H:%M:%S"),
+            first_line(&self.command, MAX_RENDERED_CHARS),
+            labels
+        )
+    }
+}
+
+/// Directory holding Claude streaming logs, inside the global storage directory
+pub fn streaming_log_dir() -> Result<PathBuf> {
+    Ok(crate::storage::get_default_storage_dir()?.join("logs/claude-streaming"))
+}
+
+/// Append an entry to the index in `log_dir`
+pub fn record(log_dir: &Path, entry: &ClaudeLogEntry) -> Result<()> {
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(log_dir.join(INDEX_FILE))?;
+    writeln!(file, "{}", serde_json::to_string(entry)?)?;
+    Ok(())
+}
+
+/// Entries recorded in the index in `log_dir`, oldest first
+///
+/// Lines that do not parse are skipped.
+pub fn load_entries(log_dir: &Path) -> Result<Vec<ClaudeLogEntry>> {
+    let path = log_dir.join(INDEX_FILE);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    Ok(content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+/// Entries of a run, optionally narrowed to one agent or work item (pure function)
+pub fn select_entries<'a>(
+    entries: &'a [ClaudeLogEntry],
+    run_id: &str,
+    agent: Option<&str>,
+    item: Option<&str>,
+) -> Vec<&'a ClaudeLogEntry> {
+    entries
+        .iter()
+        .filter(|entry| entry.belongs_to(run_id))
+        .filter(|entry| agent.is_none() || entry.agent_id.as_deref() == agent)
+        .filter(|entry| item.is_none() || entry.item_id.as_deref() == item)
+        .collect()
+}
+
+/// Render one line of Claude's stream JSON for reading (pure function)
+///
+/// Returns one output line per assistant text, tool call and tool result, and
+/// nothing for bookkeeping events such as thinking or token estimates. Lines
+/// that are not JSON are shown as they are.
+pub fn render_event(line: &str) -> Vec<String> {
+    let line = line.trim();
+    if line.is_empty() {
+        return Vec::new();
+    }
+    let Ok(event) = serde_json::from_str::<Value>(line) else {
+        return vec![line.to_string()];
+    };
+
+    match event.get("type").and_then(Value::as_str) {
+        Some("system") if event.get("subtype").and_then(Value::as_str) == Some("init") => {
+            let model = event.get("model").and_then(Value::as_str).unwrap_or("?");
+            vec![format!("session started (model {})", model)]
+        }
+        Some("assistant") => content_items(&event)
+            .iter()
+            .filter_map(|item| match item.get("type").and_then(Value::as_str) {
+                Some("text") => item
+                    .get("text")
+                    .and_then(Value::as_str)
+                    .filter(|text| !text.trim().is_empty())
+                    .map(|text| format!("assistant: {}", text.trim())),
+                Some("tool_use") => {
+                    let name = item.get("name").and_then(Value::as_str).unwrap_or("?");
+                    let input = item.get("input").map(Value::to_string).unwrap_or_default();
+                    Some(format!(
+                        "tool {}: {}",
+                        name,
+                        first_line(&input, MAX_RENDERED_CHARS)
+                    ))
+                }
+                _ => None,
+            })
+            .collect(),
+        Some("user") => content_items(&event)
+            .iter()
+            .filter(|item| item.get("type").and_then(Value::as_str) == Some("tool_result"))
+            .map(|item| {
+                let text = match item.get("content") {
+                    Some(Value::String(text)) => text.clone(),
+                    Some(Value::Array(parts)) => parts
+                        .iter()
+                        .filter_map(|part| part.get("text").and_then(Value::as_str))
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                    _ => String::new(),
+                };
+                let label = if item.get("is_error").and_then(Value::as_bool) == Some(true) {
+                    "tool error"
+                } else {
+                    "tool result"
+                };
+                format!("{}: {}", label, first_line(&text, MAX_RENDERED_CHARS))
+            })
+            .collect(),
+        Some("result") => {
+            let outcome = if event.get("is_error").and_then(Value::as_bool) == Some(true) {
+                "failed"
+            } else {
+                "finished"
+            };
+            let mut details = Vec::new();
+            if let Some(turns) = event.get("num_turns").and_then(Value::as_u64) {
+                details.push(format!("{} turns", turns));
+            }
+            if let Some(ms) = event.get("duration_ms").and_then(Value::as_u64) {
+                details.push(format!("{:.1}s", ms as f64 / 1000.0));
+            }
+            if let Some(cost) = event.get("total_cost_usd").and_then(Value::as_f64) {
+                details.push(format!("${:.4}", cost));
+            }
+            if details.is_empty() {
+                vec![outcome.to_string()]
+            } else {
+                vec![format!("{} ({})", outcome, details.join(", "))]
+            }
+        }
+        _ => Vec::new(),
+    }
+}
+
+fn content_items(event: &Value) -> Vec<Value> {
+    event
+        .get("message")
+        .and_then(|message| message.get("content"))
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// First line of `text`, cut to `max` characters (pure function)
+fn first_line(text: &str, max: usize) -> String {
+    let line = text.trim().lines().next().unwrap_or("");
+    if line.chars().count() > max {
+        format!("{}…", line.chars().take(max).collect::<String>())
+    } else {
+        line.to_string()
+    }
+}
+
+/// Stream processor that appends stdout lines to a log file as they arrive
+///
+/// Writing while Claude runs, instead of once it finishes, lets
+/// `prodigy logs claude --follow` show a run in progress.
+pub struct LogFileProcessor {
+    inner: Box<dyn StreamProcessor>,
+    file: Mutex<std::fs::File>,
+}
+
+impl LogFileProcessor {
+    /// Wrap `inner`, writing stdout lines to `file`
+    pub fn new(file: std::fs::File, inner: Box<dyn StreamProcessor>) -> Self {
+        Self {
+            inner,
+            file: Mutex::new(file),
+        }
+    }
+}
+
+#[async_trait]
+impl StreamProcessor for LogFileProcessor {
+    async fn process_line(&self, line: &str, source: StreamSource) -> Result<()> {
+        if matches!(source, StreamSource::Stdout) {
+            let mut file = match self.file.lock() {
+                Ok(file) => file,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+            if let Err(e) = writeln!(file, "{}", line) {
+                tracing::warn!("Failed to write Claude streaming log: {}", e);
+            }
+        }
+        self.inner.process_line(line, source).await
+    }
+
+    async fn on_complete(&self, exit_code: Option<i32>) -> Result<()> {
+        self.inner.on_complete(exit_code).await
+    }
+
+    async fn on_error(&self, error: &anyhow::Error) -> Result<()> {
+        self.inner.on_error(error).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(session: Option<&str>, job: Option<&str>, agent: Option<&str>) -> ClaudeLogEntry {
+        ClaudeLogEntry {
+            log: PathBuf::from("/logs/a.jsonl"),
+            started_at: Utc::now(),
+            command: "/prodigy-fix".to_string(),
+            session_id: session.map(String::from),
+            job_id: job.map(String::from),
+            agent_id: agent.map(String::from),
+            item_id: agent.map(|a| format!("item-for-{}", a)),
+        }
+    }
+
+    #[test]
+    fn test_index_round_trip_and_selection() {
+        let dir = tempfile::tempdir().unwrap();
+        let env = HashMap::from([
+            (JOB_ID_ENV.to_string(), "mapreduce-1".to_string()),
+            (AGENT_ID_ENV.to_string(), "agent-0".to_string()),
+            (ITEM_ID_ENV.to_string(), "item-0".to_string()),
+        ]);
+        let recorded = ClaudeLogEntry::from_env(dir.path().join("a.jsonl"), "/fix", &env);
+        record(dir.path(), &recorded).unwrap();
+        assert_eq!(load_entries(dir.path()).unwrap(), vec![recorded]);
+
+        let entries = vec![
+            entry(Some("session-1"), None, None),
+            entry(Some("session-1"), Some("mapreduce-1"), Some("agent-0")),
+            entry(None, Some("mapreduce-1"), Some("agent-1")),
+            entry(Some("session-2"), None, None),
+        ];
+        assert_eq!(select_entries(&entries, "session-1", None, None).len(), 2);
+        assert_eq!(select_entries(&entries, "mapreduce-1", None, None).len(), 2);
+        let agent = select_entries(&entries, "mapreduce-1", Some("agent-1"), None);
+        assert_eq!(agent.len(), 1);
+        assert_eq!(
+            agent[0]
+                .header()
+                .split_once(' ')
+                .unwrap()
+                .1
+                .split_once(' ')
+                .unwrap()
+                .1,
+            "/prodigy-fix (agent agent-1, item item-for-agent-1) ──"
+        );
+        assert_eq!(
+            select_entries(&entries, "mapreduce-1", None, Some("item-for-agent-0")).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_render_event() {
+        assert_eq!(
+            render_event(r#"{"type":"system","subtype":"init","model":"opus"}"#),
+            vec!["session started (model opus)"]
+        );
+        assert!(render_event(r#"{"type":"system","subtype":"thinking_tokens"}"#).is_empty());
+        assert_eq!(
+            render_event(
+                r#"{"type":"assistant","message":{"content":[
+                    {"type":"thinking","thinking":"hmm"},
+                    {"type":"text","text":"Fixing the test\n"},
+                    {"type":"tool_use","name":"Bash","input":{"command":"cargo test"}}]}}"#
+            ),
+            vec![
+                "assistant: Fixing the test",
+                r#"tool Bash: {"command":"cargo test"}"#
+            ]
+        );
+        assert_eq!(
+            render_event(
+                r#"{"type":"user","message":{"content":[
+                    {"type":"tool_result","content":"test result: ok\nmore","is_error":false},
+                    {"type":"tool_result","content":[{"type":"text","text":"boom"}],"is_error":true}]}}"#
+            ),
+            vec!["tool result: test result: ok", "tool error: boom"]
+        );
+        assert_eq!(
+            render_event(
+                r#"{"type":"result","is_error":false,"num_turns":4,"duration_ms":12500,"total_cost_usd":0.25}"#
+            ),
+            vec!["finished (4 turns, 12.5s, $0.2500)"]
+        );
+        assert_eq!(render_event("plain output"), vec!["plain output"]);
+    }
+}
@@ -0,0 +1,225 @@
+//! Mocked Claude commands
+//!
+//! `prodigy test-workflow` runs workflows without calling Claude. Each test
+//! declares how the Claude commands it expects should behave: the output they
+//! print, whether they succeed and an optional shell script that stands in
+//! for Claude's edits, such as writing a file and committing it.
+//!
+//! [`ClaudeMocks`] stands in for the Claude executor of a run. Every Claude
+//! invocation is served by the first matching mock; a command without a mock
+//! fails so tests cannot silently skip a step.
+
+use super::claude::ClaudeExecutor;
+use super::claude_capabilities::ClaudeCapabilities;
+use super::ExecutionResult;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Mutex;
+
+fn default_success() -> bool {
+    true
+}
+
+/// Behavior of one mocked Claude command
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ClaudeMock {
+    /// Command to mock, e.g. `/prodigy-lint`. Matches the command with any arguments.
+    pub command: String,
+
+    /// Output the command prints
+    #[serde(default)]
+    pub stdout: String,
+
+    /// Whether the command succeeds
+    #[serde(default = "default_success")]
+    pub success: bool,
+
+    /// Shell script run in the working directory in place of Claude's edits
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub run: Option<String>,
+}
+
+impl ClaudeMock {
+    /// Whether this mock serves `command` (pure function)
+    pub fn matches(&self, command: &str) -> bool {
+        let command = command.trim();
+        let mocked = self.command.trim();
+        command == mocked
+            || command
+                .strip_prefix(mocked)
+                .is_some_and(|rest| rest.starts_with(char::is_whitespace))
+    }
+}
+
+/// Mocks serving a run and the Claude commands it invoked
+#[derive(Debug, Default)]
+pub struct ClaudeMocks {
+    mocks: Vec<ClaudeMock>,
+    calls: Mutex<Vec<String>>,
+}
+
+impl ClaudeMocks {
+    pub fn new(mocks: Vec<ClaudeMock>) -> Self {
+        Self {
+            mocks,
+            calls: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Claude commands invoked so far, in order
+    pub fn calls(&self) -> Vec<String> {
+        match self.calls.lock() {
+            Ok(calls) => calls.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+
+    /// Serve a Claude command from its mock
+    pub async fn execute(
+        &self,
+        command: &str,
+        working_dir: &Path,
+        env_vars: &HashMap<String, String>,
+    ) -> Result<ExecutionResult> {
+        match self.calls.lock() {
+            Ok(mut calls) => calls.push(command.to_string()),
+            Err(poisoned) => poisoned.into_inner().push(command.to_string()),
+        }
+
+        let Some(mock) = self.mocks.iter().find(|mock| mock.matches(command)) else {
+            return Ok(ExecutionResult {
+                success: false,
+                stdout: String::new(),
+                stderr: format!("No mock for Claude command '{}'", command),
+                exit_code: Some(1),
+                metadata: HashMap::new(),
+            });
+        };
+
+        let mut stderr = String::new();
+        if let Some(script) = &mock.run {
+            let output = tokio::process::Command::new("sh")
+                .arg("-c")
+                .arg(script)
+                .current_dir(working_dir)
+                .envs(env_vars)
+                .output()
+                .await
+                .with_context(|| format!("Failed to run mock script for '{}'", command))?;
+            if !output.status.success() {
+                return Ok(ExecutionResult {
+                    success: false,
+                    stdout: String::from_utf8_lossy(&output.stdout).to_string(),
+                    stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+                    exit_code: output.status.code(),
+                    metadata: HashMap::new(),
+                });
+            }
+            stderr = String::from_utf8_lossy(&output.stderr).to_string();
+        }
+
+        Ok(ExecutionResult {
+            success: mock.success,
+            stdout: mock.stdout.clone(),
+            stderr,
+            exit_code: Some(if mock.success { 0 } else { 1 }),
+            metadata: HashMap::new(),
+        })
+    }
+}
+
+#[async_trait]
+impl ClaudeExecutor for ClaudeMocks {
+    async fn execute_claude_command(
+        &self,
+        command: &str,
+        project_path: &Path,
+        env_vars: HashMap<String, String>,
+    ) -> Result<ExecutionResult> {
+        self.execute(command, project_path, &env_vars).await
+    }
+
+    async fn check_claude_cli(&self) -> Result<bool> {
+        Ok(true)
+    }
+
+    async fn get_claude_version(&self) -> Result<String> {
+        Ok("mocked".to_string())
+    }
+
+    async fn probe_capabilities(&self) -> Result<ClaudeCapabilities> {
+        Ok(ClaudeCapabilities::assume_latest())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn mock(command: &str, run: Option<&str>) -> ClaudeMock {
+        ClaudeMock {
+            command: command.to_string(),
+            stdout: "done".to_string(),
+            success: true,
+            run: run.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_matches_command_with_arguments() {
+        let lint = mock("/prodigy-lint", None);
+        assert!(lint.matches("/prodigy-lint"));
+        assert!(lint.matches("/prodigy-lint src/lib.rs"));
+        assert!(!lint.matches("/prodigy-lint-all"));
+        assert!(!lint.matches("/prodigy-fix"));
+    }
+
+    #[tokio::test]
+    async fn test_execute_runs_script_and_records_calls() {
+        let temp = TempDir::new().unwrap();
+        let mocks = ClaudeMocks::new(vec![mock(
+            "/prodigy-fix",
+            Some("echo \"$FIX_NOTE\" > fixed.txt"),
+        )]);
+        let env = HashMap::from([("FIX_NOTE".to_string(), "patched".to_string())]);
+
+        let result = mocks
+            .execute("/prodigy-fix 42", temp.path(), &env)
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert_eq!(result.stdout, "done");
+        assert_eq!(
+            std::fs::read_to_string(temp.path().join("fixed.txt")).unwrap(),
+            "patched\n"
+        );
+
+        let unmocked = mocks
+            .execute("/prodigy-review", temp.path(), &env)
+            .await
+            .unwrap();
+        assert!(!unmocked.success);
+        assert!(unmocked.stderr.contains("No mock for Claude command"));
+        assert_eq!(mocks.calls(), vec!["/prodigy-fix 42", "/prodigy-review"]);
+    }
+
+    #[tokio::test]
+    async fn test_mocks_serve_as_claude_executor() {
+        let temp = TempDir::new().unwrap();
+        let executor: std::sync::Arc<dyn ClaudeExecutor> =
+            std::sync::Arc::new(ClaudeMocks::new(vec![mock("/prodigy-lint", None)]));
+
+        assert!(executor.check_claude_cli().await.unwrap());
+        let result = executor
+            .execute_claude_command("/prodigy-lint", temp.path(), HashMap::new())
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert_eq!(result.stdout, "done");
+    }
+}
@@ -0,0 +1,315 @@
+//! Claude tool permission profiles
+//!
+//! A workflow's `claude_permissions:` block decides which tools Claude may use
+//! in each invocation:
+//!
+//! ```yaml
+//! claude_permissions:
+//!   profile: no_shell
+//!   allowed_tools: ["Bash(cargo test:*)"]
+//!   disallowed_tools: [WebFetch]
+//! ```
+//!
+//! The settings become `--permission-mode`, `--allowedTools` and
+//! `--disallowedTools` flags. Executors pass them to each invocation through
+//! [`PERMISSIONS_ENV`]: the workflow executor and the reduce phase use the
+//! workflow-level settings, and MapReduce agents use their own, defaulting to
+//! the restrictive [`PermissionProfile::Agent`] profile. Without any settings,
+//! Claude runs with `--dangerously-skip-permissions` or the mode from
+//! `PRODIGY_CLAUDE_PERMISSION_MODE`, as before.
+
+use super::claude_capabilities::{ClaudeCapabilities, PERMISSION_MODE_MIN_VERSION};
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Environment variable carrying per-invocation permissions as JSON
+pub const PERMISSIONS_ENV: &str = "PRODIGY_CLAUDE_PERMISSIONS";
+
+/// Tools that run shell commands
+const SHELL_TOOLS: &[&str] = &["Bash", "BashOutput", "KillShell"];
+
+/// Tools that modify files
+const EDIT_TOOLS: &[&str] = &["Edit", "MultiEdit", "Write", "NotebookEdit"];
+
+/// Tools that reach the network
+const WEB_TOOLS: &[&str] = &["WebFetch", "WebSearch"];
+
+/// Tools MapReduce agents may use by default
+const AGENT_ALLOWED_TOOLS: &[&str] = &[
+    "Read",
+    "Glob",
+    "Grep",
+    "LS",
+    "Edit",
+    "MultiEdit",
+    "Write",
+    "NotebookEdit",
+    "TodoWrite",
+    "Bash(git:*)",
+];
+
+/// Named set of tool permissions
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PermissionProfile {
+    /// Every tool, without prompts
+    #[default]
+    Unrestricted,
+    /// Every tool except shell commands
+    NoShell,
+    /// Reading and searching only
+    ReadOnly,
+    /// File edits and git, without network access (MapReduce agent default)
+    Agent,
+}
+
+impl fmt::Display for PermissionProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            PermissionProfile::Unrestricted => "unrestricted",
+            PermissionProfile::NoShell => "no_shell",
+            PermissionProfile::ReadOnly => "read_only",
+            PermissionProfile::Agent => "agent",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Claude CLI permission mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PermissionMode {
+    /// Tools not explicitly allowed are denied in non-interactive runs
+    Default,
+    /// File edits are approved automatically
+    AcceptEdits,
+    /// Claude may only plan, not act
+    Plan,
+    /// Every tool is approved
+    BypassPermissions,
+}
+
+impl fmt::Display for PermissionMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            PermissionMode::Default => "default",
+            PermissionMode::AcceptEdits => "acceptEdits",
+            PermissionMode::Plan => "plan",
+            PermissionMode::BypassPermissions => "bypassPermissions",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Tool permissions for Claude invocations
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClaudePermissions {
+    /// Base profile the other settings extend
+    #[serde(default)]
+    pub profile: PermissionProfile,
+    /// Permission mode, replacing the profile's
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mode: Option<PermissionMode>,
+    /// Extra tools to approve, e.g. `Bash(cargo test:*)`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_tools: Vec<String>,
+    /// Extra tools to remove
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub disallowed_tools: Vec<String>,
+}
+
+impl ClaudePermissions {
+    /// Permissions of a bare profile
+    pub fn profile(profile: PermissionProfile) -> Self {
+        Self {
+            profile,
+            ..Self::default()
+        }
+    }
+
+    /// Permission mode after applying the profile
+    ///
+    /// `None` means `--dangerously-skip-permissions`.
+    pub fn effective_mode(&self) -> Option<PermissionMode> {
+        self.mode.or(match self.profile {
+            PermissionProfile::Agent => Some(PermissionMode::AcceptEdits),
+            _ => None,
+        })
+    }
+
+    /// Approved tools after applying the profile
+    pub fn effective_allowed_tools(&self) -> Vec<String> {
+        let base: &[&str] = match self.profile {
+            PermissionProfile::Agent => AGENT_ALLOWED_TOOLS,
+            _ => &[],
+        };
+        merge_tools(base, &self.allowed_tools)
+    }
+
+    /// Removed tools after applying the profile
+    pub fn effective_disallowed_tools(&self) -> Vec<String> {
+        let base: Vec<&str> = match self.profile {
+            PermissionProfile::Unrestricted => Vec::new(),
+            PermissionProfile::NoShell => SHELL_TOOLS.to_vec(),
+            PermissionProfile::ReadOnly => [SHELL_TOOLS, EDIT_TOOLS].concat(),
+            PermissionProfile::Agent => WEB_TOOLS.to_vec(),
+        };
+        merge_tools(&base, &self.disallowed_tools)
+    }
+
+    /// Claude CLI arguments enforcing these permissions
+    ///
+    /// CLIs without `--permission-mode` reject an explicit `mode`; a mode
+    /// implied by the profile falls back to skipping permission prompts, which
+    /// still removes the profile's disallowed tools.
+    pub fn cli_args(&self, capabilities: &ClaudeCapabilities) -> Result<Vec<String>> {
+        let mut args = match self.effective_mode() {
+            None => vec!["--dangerously-skip-permissions".to_string()],
+            Some(mode) if capabilities.permission_mode => {
+                vec!["--permission-mode".to_string(), mode.to_string()]
+            }
+            Some(_) if self.mode.is_none() => {
+                return Ok(self.skip_permissions_args());
+            }
+            Some(mode) => {
+                return Err(anyhow!(
+                    "claude_permissions mode '{}' requires Claude CLI {} or newer (installed: {})",
+                    mode,
+                    PERMISSION_MODE_MIN_VERSION,
+                    capabilities
+                        .version
+                        .map(|v| v.to_string())
+                        .unwrap_or_else(|| "unknown".to_string())
+                ))
+            }
+        };
+
+        let allowed = self.effective_allowed_tools();
+        if !allowed.is_empty() {
+            args.push("--allowedTools".to_string());
+            args.push(allowed.join(","));
+        }
+        let disallowed = self.effective_disallowed_tools();
+        if !disallowed.is_empty() {
+            args.push("--disallowedTools".to_string());
+            args.push(disallowed.join(","));
+        }
+        Ok(args)
+    }
+
+    fn skip_permissions_args(&self) -> Vec<String> {
+        let mut args = vec!["--dangerously-skip-permissions".to_string()];
+        let disallowed = self.effective_disallowed_tools();
+        if !disallowed.is_empty() {
+            args.push("--disallowedTools".to_string());
+            args.push(disallowed.join(","));
+        }
+        args
+    }
+
+    /// Encode for [`PERMISSIONS_ENV`]
+    pub fn to_env_value(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    /// Decode a [`PERMISSIONS_ENV`] value
+    pub fn from_env_value(value: &str) -> Result<Self> {
+        serde_json::from_str(value).map_err(|e| anyhow!("Invalid {}: {}", PERMISSIONS_ENV, e))
+    }
+}
+
+fn merge_tools(base: &[&str], extra: &[String]) -> Vec<String> {
+    let mut tools: Vec<String> = base.iter().map(|tool| tool.to_string()).collect();
+    for tool in extra.iter().map(|t| t.trim()).filter(|t| !t.is_empty()) {
+        if !tools.iter().any(|existing| existing == tool) {
+            tools.push(tool.to_string());
+        }
+    }
+    tools
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cook::execution::claude_capabilities::ClaudeCliVersion;
+
+    #[test]
+    fn test_profiles_translate_to_flags() {
+        let caps = ClaudeCapabilities::assume_latest();
+
+        assert_eq!(
+            ClaudePermissions::default().cli_args(&caps).unwrap(),
+            vec!["--dangerously-skip-permissions"]
+        );
+        assert_eq!(
+            ClaudePermissions::profile(PermissionProfile::NoShell)
+                .cli_args(&caps)
+                .unwrap(),
+            vec![
+                "--dangerously-skip-permissions",
+                "--disallowedTools",
+                "Bash,BashOutput,KillShell"
+            ]
+        );
+
+        let agent = ClaudePermissions::profile(PermissionProfile::Agent)
+            .cli_args(&caps)
+            .unwrap();
+        assert_eq!(agent[..2], ["--permission-mode", "acceptEdits"]);
+        assert_eq!(agent[2], "--allowedTools");
+        assert!(agent[3].ends_with(",Bash(git:*)"));
+        assert_eq!(agent[4..], ["--disallowedTools", "WebFetch,WebSearch"]);
+    }
+
+    #[test]
+    fn test_config_extends_profile() {
+        let permissions: ClaudePermissions = serde_yaml::from_str(
+            "profile: read_only\nmode: default\nallowed_tools: ['Bash(cargo test:*)']\ndisallowed_tools: [WebFetch, Bash]\n",
+        )
+        .unwrap();
+        let args = permissions
+            .cli_args(&ClaudeCapabilities::assume_latest())
+            .unwrap();
+        assert_eq!(
+            args,
+            vec![
+                "--permission-mode",
+                "default",
+                "--allowedTools",
+                "Bash(cargo test:*)",
+                "--disallowedTools",
+                "Bash,BashOutput,KillShell,Edit,MultiEdit,Write,NotebookEdit,WebFetch"
+            ]
+        );
+
+        let decoded = ClaudePermissions::from_env_value(&permissions.to_env_value()).unwrap();
+        assert_eq!(decoded, permissions);
+
+        assert!(serde_yaml::from_str::<ClaudePermissions>("profile: yolo").is_err());
+        assert!(serde_yaml::from_str::<ClaudePermissions>("mode: acceptAll").is_err());
+    }
+
+    #[test]
+    fn test_mode_requires_supported_cli() {
+        let old = ClaudeCapabilities::for_version(ClaudeCliVersion::new(0, 2, 70));
+        let explicit = ClaudePermissions {
+            mode: Some(PermissionMode::Plan),
+            ..ClaudePermissions::default()
+        };
+        let err = explicit.cli_args(&old).unwrap_err();
+        assert!(err.to_string().contains("installed: 0.2.70"));
+
+        // The agent default degrades to removing tools on older CLIs
+        assert_eq!(
+            ClaudePermissions::profile(PermissionProfile::Agent)
+                .cli_args(&old)
+                .unwrap(),
+            vec![
+                "--dangerously-skip-permissions",
+                "--disallowedTools",
+                "WebFetch,WebSearch"
+            ]
+        );
+    }
+}
@@ -0,0 +1,252 @@
+//! Claude runs linked to the commits they produced
+//!
+//! When a streaming Claude command finishes, a record is appended to
+//! `runs.jsonl` next to the streaming log index. It ties together the Claude
+//! session ID reported in the stream, the Prodigy session, MapReduce job,
+//! agent and work item running the command, the branch it ran on and the
+//! commits made while it ran. `prodigy analytics sessions` reads the records
+//! to answer which conversation produced a commit.
+//!
+//! Commits are those reachable from the working directory's HEAD after the
+//! command but not before it, so a command that fails after committing still
+//! has its commits recorded.
+
+use super::claude_logs::ClaudeLogEntry;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// File listing finished Claude runs, next to the streaming log index
+pub const RUNS_FILE: &str = "runs.jsonl";
+
+/// A finished Claude command and what it produced
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClaudeRun {
+    /// Claude's own session ID, as accepted by `claude --resume`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub claude_session_id: Option<String>,
+    pub command: String,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    pub success: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub job_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub item_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+    /// Commits made while the command ran, oldest first
+    #[serde(default)]
+    pub commits: Vec<String>,
+    pub log: PathBuf,
+}
+
+impl ClaudeRun {
+    /// Describe a finished run from its log index entry (pure function)
+    pub fn new(
+        entry: &ClaudeLogEntry,
+        start: &GitPosition,
+        claude_session_id: Option<String>,
+        success: bool,
+        commits: Vec<String>,
+    ) -> Self {
+        Self {
+            claude_session_id,
+            command: entry.command.clone(),
+            started_at: entry.started_at,
+            finished_at: Utc::now(),
+            success,
+            session_id: entry.session_id.clone(),
+            job_id: entry.job_id.clone(),
+            agent_id: entry.agent_id.clone(),
+            item_id: entry.item_id.clone(),
+            branch: start.branch.clone(),
+            commits,
+            log: entry.log.clone(),
+        }
+    }
+}
+
+/// Git HEAD and branch of a working directory
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GitPosition {
+    pub head: Option<String>,
+    pub branch: Option<String>,
+}
+
+impl GitPosition {
+    /// Read the position of `dir`, or nothing if it is not a git repository
+    pub async fn read(dir: &Path) -> Self {
+        Self {
+            head: git_output(dir, &["rev-parse", "HEAD"]).await,
+            branch: git_output(dir, &["rev-parse", "--abbrev-ref", "HEAD"])
+                .await
+                .filter(|branch| branch != "HEAD"),
+        }
+    }
+
+    /// Commits made in `dir` since this position, oldest first
+    pub async fn commits_since(&self, dir: &Path) -> Vec<String> {
+        let Some(head) = &self.head else {
+            return Vec::new();
+        };
+        git_output(dir, &["rev-list", "--reverse", &format!("{}..HEAD", head)])
+            .await
+            .map(|output| output.lines().map(str::to_string).collect())
+            .unwrap_or_default()
+    }
+}
+
+async fn git_output(dir: &Path, args: &[&str]) -> Option<String> {
+    let output = tokio::process::Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .await
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Append a run to the records in `log_dir`
+pub fn record(log_dir: &Path, run: &ClaudeRun) -> Result<()> {
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(log_dir.join(RUNS_FILE))?;
+    writeln!(file, "{}", serde_json::to_string(run)?)?;
+    Ok(())
+}
+
+/// Runs recorded in `log_dir`, oldest first
+///
+/// Lines that do not parse are skipped.
+pub fn load_runs(log_dir: &Path) -> Result<Vec<ClaudeRun>> {
+    let path = log_dir.join(RUNS_FILE);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    Ok(content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+/// Which runs a report includes
+#[derive(Debug, Clone, Default)]
+pub struct RunFilter {
+    /// Prodigy session or MapReduce job ID
+    pub run_id: Option<String>,
+    /// Claude session ID, or a prefix of it
+    pub claude_session: Option<String>,
+    /// Commit SHA, or a prefix of it
+    pub commit: Option<String>,
+    /// Only runs that made commits
+    pub with_commits: bool,
+}
+
+impl RunFilter {
+    /// Whether a run matches every criterion given (pure function)
+    pub fn matches(&self, run: &ClaudeRun) -> bool {
+        let run_id = self.run_id.as_deref().is_none_or(|id| {
+            run.session_id.as_deref() == Some(id) || run.job_id.as_deref() == Some(id)
+        });
+        let claude_session = self.claude_session.as_deref().is_none_or(|prefix| {
+            run.claude_session_id
+                .as_deref()
+                .is_some_and(|id| id.starts_with(prefix))
+        });
+        let commit = self
+            .commit
+            .as_deref()
+            .is_none_or(|prefix| run.commits.iter().any(|sha| sha.starts_with(prefix)));
+        run_id && claude_session && commit && (!self.with_commits || !run.commits.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = std::process::Command::new("git")
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success(), "git {:?}", args);
+    }
+
+    #[tokio::test]
+    async fn test_run_records_commits_made_during_command() {
+        let repo = tempfile::tempdir().unwrap();
+        git(repo.path(), &["init", "-q", "-b", "prodigy-session-1"]);
+        git(
+            repo.path(),
+            &["commit", "-q", "--allow-empty", "-m", "base"],
+        );
+
+        let start = GitPosition::read(repo.path()).await;
+        assert_eq!(start.branch.as_deref(), Some("prodigy-session-1"));
+        git(
+            repo.path(),
+            &["commit", "-q", "--allow-empty", "-m", "first"],
+        );
+        git(
+            repo.path(),
+            &["commit", "-q", "--allow-empty", "-m", "second"],
+        );
+        let commits = start.commits_since(repo.path()).await;
+        assert_eq!(commits.len(), 2);
+
+        let env = HashMap::from([(
+            crate::cook::workflow::checkpoint_marks::SESSION_ID_ENV.to_string(),
+            "session-1".to_string(),
+        )]);
+        let entry = ClaudeLogEntry::from_env(repo.path().join("a.jsonl"), "/fix", &env);
+        let run = ClaudeRun::new(&entry, &start, Some("c0ffee-1".to_string()), true, commits);
+
+        let log_dir = tempfile::tempdir().unwrap();
+        record(log_dir.path(), &run).unwrap();
+        assert_eq!(load_runs(log_dir.path()).unwrap(), vec![run.clone()]);
+
+        let by_commit = RunFilter {
+            commit: Some(run.commits[1][..8].to_string()),
+            ..Default::default()
+        };
+        assert!(by_commit.matches(&run));
+        let by_session = RunFilter {
+            run_id: Some("session-1".to_string()),
+            claude_session: Some("c0ffee".to_string()),
+            with_commits: true,
+            ..Default::default()
+        };
+        assert!(by_session.matches(&run));
+        let other = RunFilter {
+            run_id: Some("session-2".to_string()),
+            ..Default::default()
+        };
+        assert!(!other.matches(&run));
+    }
+
+    #[tokio::test]
+    async fn test_position_outside_repository_is_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        let position = GitPosition::read(dir.path()).await;
+        assert_eq!(position, GitPosition::default());
+        assert!(position.commits_since(dir.path()).await.is_empty());
+    }
+}
@@ -0,0 +1,168 @@
+//! Warm Claude session reuse
+//!
+//! Every Claude step starts a fresh `claude` process, which loads the
+//! project, its CLAUDE.md and the tool definitions before doing any work.
+//! Agent templates with several small Claude steps pay that cold start on
+//! every step. With [`SESSION_REUSE_ENV`] set to `true`, the executor
+//! remembers the session ID each streaming run reports and passes
+//! `--resume <id>` to the next Claude command in the same scope, so the CLI
+//! continues the loaded conversation and reuses its prompt cache.
+//!
+//! A scope is a MapReduce agent, or otherwise the command's working
+//! directory. Commands in one scope run one after another, so a session is
+//! never resumed twice at once. A failed command forgets its scope's session
+//! and the next command starts fresh.
+
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Mutex;
+
+/// Environment variable enabling warm session reuse (`true` to enable)
+pub const SESSION_REUSE_ENV: &str = "PRODIGY_CLAUDE_SESSION_REUSE";
+
+/// Environment variable naming a session the command must resume
+///
+/// Set by `claude_chat` steps; takes precedence over warm session reuse.
+pub const RESUME_SESSION_ENV: &str = "PRODIGY_CLAUDE_RESUME_SESSION";
+
+/// Whether the Claude command's environment enables session reuse (pure function)
+pub fn reuse_enabled(env_vars: &HashMap<String, String>) -> bool {
+    env_vars
+        .get(SESSION_REUSE_ENV)
+        .is_some_and(|v| v.trim().eq_ignore_ascii_case("true"))
+}
+
+/// Arguments resuming the session the environment names (pure function)
+pub fn explicit_resume_args(env_vars: &HashMap<String, String>) -> Vec<String> {
+    match env_vars.get(RESUME_SESSION_ENV).filter(|id| !id.is_empty()) {
+        Some(id) => vec!["--resume".to_string(), id.clone()],
+        None => Vec::new(),
+    }
+}
+
+/// Scope whose commands share a Claude session (pure function)
+///
+/// The MapReduce agent when there is one, otherwise the working directory.
+pub fn scope_key(project_path: &Path, env_vars: &HashMap<String, String>) -> String {
+    match env_vars
+        .get(super::claude_logs::AGENT_ID_ENV)
+        .filter(|id| !id.is_empty())
+    {
+        Some(agent_id) => format!("agent:{}", agent_id),
+        None => format!("dir:{}", project_path.display()),
+    }
+}
+
+/// Last session ID reported in Claude's stream-json output (pure function)
+///
+/// The `init` event opens the session and the final `result` event repeats
+/// its ID; the last one wins because a resumed run may report a new ID.
+pub fn parse_session_id(stream_output: &str) -> Option<String> {
+    stream_output
+        .lines()
+        .rev()
+        .filter_map(|line| serde_json::from_str::<Value>(line.trim()).ok())
+        .filter_map(|event| {
+            event
+                .get("session_id")
+                .and_then(Value::as_str)
+                .filter(|id| !id.is_empty() && *id != "unknown")
+                .map(String::from)
+        })
+        .next()
+}
+
+/// Claude sessions kept warm per scope for one executor
+#[derive(Debug, Default)]
+pub struct WarmSessions {
+    sessions: Mutex<HashMap<String, String>>,
+}
+
+impl WarmSessions {
+    /// Arguments resuming the scope's session, empty if it has none
+    pub fn resume_args(&self, scope: &str) -> Vec<String> {
+        match self.sessions.lock().unwrap().get(scope) {
+            Some(id) => vec!["--resume".to_string(), id.clone()],
+            None => Vec::new(),
+        }
+    }
+
+    /// Record the outcome of a Claude command run in `scope`
+    ///
+    /// A successful run keeps the session it reported; a failed run, or one
+    /// that reported none, forgets the scope's session.
+    pub fn record(&self, scope: &str, success: bool, stream_output: &str) {
+        let mut sessions = self.sessions.lock().unwrap();
+        match parse_session_id(stream_output).filter(|_| success) {
+            Some(id) => {
+                tracing::debug!("Keeping Claude session {} warm for {}", id, scope);
+                sessions.insert(scope.to_string(), id);
+            }
+            None => {
+                sessions.remove(scope);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reuse_enabled() {
+        let mut env = HashMap::new();
+        assert!(!reuse_enabled(&env));
+        env.insert(SESSION_REUSE_ENV.to_string(), "false".to_string());
+        assert!(!reuse_enabled(&env));
+        env.insert(SESSION_REUSE_ENV.to_string(), "TRUE".to_string());
+        assert!(reuse_enabled(&env));
+    }
+
+    #[test]
+    fn test_explicit_resume_args() {
+        let mut env = HashMap::new();
+        assert!(explicit_resume_args(&env).is_empty());
+        env.insert(RESUME_SESSION_ENV.to_string(), "abc".to_string());
+        assert_eq!(explicit_resume_args(&env), vec!["--resume", "abc"]);
+    }
+
+    #[test]
+    fn test_scope_key_prefers_agent() {
+        let dir = Path::new("/tmp/worktree");
+        let mut env = HashMap::new();
+        assert_eq!(scope_key(dir, &env), "dir:/tmp/worktree");
+        env.insert(
+            super::super::claude_logs::AGENT_ID_ENV.to_string(),
+            "job_agent_3".to_string(),
+        );
+        assert_eq!(scope_key(dir, &env), "agent:job_agent_3");
+    }
+
+    #[test]
+    fn test_parse_session_id_takes_last() {
+        let output = [
+            r#"{"type":"system","subtype":"init","session_id":"first"}"#,
+            "not json",
+            r#"{"type":"assistant","message":{}}"#,
+            r#"{"type":"result","session_id":"second"}"#,
+        ]
+        .join("\n");
+        assert_eq!(parse_session_id(&output), Some("second".to_string()));
+        assert_eq!(parse_session_id("plain text output"), None);
+    }
+
+    #[test]
+    fn test_warm_sessions_resume_and_forget() {
+        let sessions = WarmSessions::default();
+        assert!(sessions.resume_args("agent:a").is_empty());
+
+        sessions.record("agent:a", true, r#"{"session_id":"abc"}"#);
+        assert_eq!(sessions.resume_args("agent:a"), vec!["--resume", "abc"]);
+        assert!(sessions.resume_args("agent:b").is_empty());
+
+        sessions.record("agent:a", false, r#"{"session_id":"def"}"#);
+        assert!(sessions.resume_args("agent:a").is_empty());
+    }
+}
@@ -1,6 +1,7 @@
 //! Claude stream handler for integrating with event logging
 
 use crate::cook::execution::events::{EventLogger, MapReduceEvent};
+use crate::styled_println;
 use crate::subprocess::streaming::{ClaudeStreamHandler, StreamSource};
 use anyhow::Result;
 use async_trait::async_trait;
@@ -35,7 +36,7 @@ impl ClaudeStreamHandler for EventLoggingClaudeHandler {
         parameters: &Value,
     ) -> Result<()> {
         if self.print_to_console {
-            println!("🔧 Tool invoked: {}", tool_name);
+            styled_println!("🔧 Tool invoked: {}", tool_name);
         }
 
         let event = MapReduceEvent::ClaudeToolInvoked {
@@ -54,9 +55,11 @@ impl ClaudeStreamHandler for EventLoggingClaudeHandler {
 
     async fn on_token_usage(&self, input: u64, output: u64, cache: u64) -> Result<()> {
         if self.print_to_console {
-            println!(
+            styled_println!(
                 "📊 Tokens - Input: {}, Output: {}, Cache: {}",
-                input, output, cache
+                input,
+                output,
+                cache
             );
         }
 
@@ -99,7 +102,7 @@ impl ClaudeStreamHandler for EventLoggingClaudeHandler {
         tools: Vec<String>,
     ) -> Result<()> {
         if self.print_to_console {
-            println!("🚀 Claude session started - Model: {}", model);
+            styled_println!("🚀 Claude session started - Model: {}", model);
         }
 
         let event = MapReduceEvent::ClaudeSessionStarted {
@@ -153,14 +156,17 @@ impl ClaudeStreamHandler for ConsoleClaudeHandler {
         _tool_id: &str,
         _parameters: &Value,
     ) -> Result<()> {
-        println!("[{}] 🔧 Tool invoked: {}", self.agent_id, tool_name);
+        styled_println!("[{}] 🔧 Tool invoked: {}", self.agent_id, tool_name);
         Ok(())
     }
 
     async fn on_token_usage(&self, input: u64, output: u64, cache: u64) -> Result<()> {
-        println!(
+        styled_println!(
             "[{}] 📊 Tokens - Input: {}, Output: {}, Cache: {}",
-            self.agent_id, input, output, cache
+            self.agent_id,
+            input,
+            output,
+            cache
         );
         Ok(())
     }
@@ -178,9 +184,10 @@ impl ClaudeStreamHandler for ConsoleClaudeHandler {
         model: &str,
         _tools: Vec<String>,
     ) -> Result<()> {
-        println!(
+        styled_println!(
             "[{}] 🚀 Claude session started - Model: {}",
-            self.agent_id, model
+            self.agent_id,
+            model
         );
         Ok(())
     }
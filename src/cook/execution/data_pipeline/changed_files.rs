@@ -0,0 +1,112 @@
+//! Changed-file scoping for work items
+//!
+//! Keeps only work items that refer to a file changed relative to a git ref,
+//! so PR-scoped runs skip items the change cannot affect. An item refers to
+//! a changed file when one of its paths names that file or a directory
+//! containing it.
+
+use super::filter::FilterExpression;
+use serde_json::Value;
+use std::path::{Component, Path, PathBuf};
+
+/// Files changed relative to a git ref, and how to find an item's paths
+#[derive(Debug, Clone, Default)]
+pub struct ChangedFiles {
+    /// Changed files, relative to the directory items are resolved against
+    pub files: Vec<PathBuf>,
+    /// Field holding each item's path (e.g. `location.file`); any string
+    /// value of the item is considered when unset
+    pub field: Option<String>,
+}
+
+impl ChangedFiles {
+    /// Create a changed-file scope
+    pub fn new(files: Vec<PathBuf>, field: Option<String>) -> Self {
+        Self { files, field }
+    }
+
+    /// Whether `item` refers to a changed file
+    pub fn matches(&self, item: &Value) -> bool {
+        let selected = match &self.field {
+            Some(field) => match FilterExpression::get_nested_field_with_array(item, field) {
+                Some(value) => value,
+                None => return false,
+            },
+            None => item.clone(),
+        };
+        let mut paths = Vec::new();
+        collect_strings(&selected, &mut paths);
+
+        paths.iter().any(|path| {
+            let path = normalize(Path::new(path));
+            !path.as_os_str().is_empty()
+                && self
+                    .files
+                    .iter()
+                    .any(|changed| normalize(changed).starts_with(&path))
+        })
+    }
+}
+
+/// Collect every string in a JSON value (pure function)
+fn collect_strings<'a>(value: &'a Value, out: &mut Vec<&'a str>) {
+    match value {
+        Value::String(s) => out.push(s),
+        Value::Array(values) => {
+            for v in values {
+                collect_strings(v, out);
+            }
+        }
+        Value::Object(map) => {
+            for v in map.values() {
+                collect_strings(v, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Drop `.` components and trailing separators so paths compare by component (pure function)
+fn normalize(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|component| !matches!(component, Component::CurDir))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn changed(field: Option<&str>) -> ChangedFiles {
+        ChangedFiles::new(
+            vec![PathBuf::from("src/lib.rs"), PathBuf::from("docs/guide.md")],
+            field.map(str::to_string),
+        )
+    }
+
+    #[test]
+    fn test_matches_any_string_field() {
+        let scope = changed(None);
+        assert!(scope.matches(&json!({"file": "src/lib.rs", "line": 3})));
+        assert!(scope.matches(&json!({"files": ["README.md", "./docs/guide.md"]})));
+        assert!(!scope.matches(&json!({"file": "src/main.rs"})));
+    }
+
+    #[test]
+    fn test_matches_directories_by_component() {
+        let scope = changed(None);
+        assert!(scope.matches(&json!({"module": "src"})));
+        assert!(scope.matches(&json!({"module": "docs/"})));
+        assert!(!scope.matches(&json!({"module": "sr"})));
+        assert!(!scope.matches(&json!({"module": ""})));
+    }
+
+    #[test]
+    fn test_matches_configured_field_only() {
+        let scope = changed(Some("location.file"));
+        assert!(scope.matches(&json!({"location": {"file": "src/lib.rs"}})));
+        assert!(!scope.matches(&json!({"location": {"file": "src/main.rs"}, "note": "src/lib.rs"})));
+        assert!(!scope.matches(&json!({"file": "src/lib.rs"})));
+    }
+}
@@ -2,7 +2,9 @@
 //!
 //! Provides a filter expression language for selecting items from data.
 //! Supports comparison operators, logical operators (AND/OR/NOT), IN expressions,
-//! and functions like is_null, is_empty, matches_regex, etc.
+//! and functions like is_null, is_empty, matches_regex, etc. Durations such as
+//! `90s` or `5m` on the right of `<`, `>`, `<=` or `>=` compare as a number of
+//! seconds.
 
 use anyhow::{anyhow, Context, Result};
 use regex::Regex;
@@ -318,8 +320,16 @@ impl FilterExpression {
     ) -> Result<Self> {
         let field = expr[..op_pos].trim().to_string();
         let value_str = expr[op_pos + op_str.len()..].trim();
-        let value = Self::parse_value(value_str)?;
         let op = Self::string_to_comparison_op(op_str)?;
+        // Durations are only bounds: `field == 5m` still compares strings
+        let value = if Self::is_ordering_op(&op) && !Self::is_quoted(value_str) {
+            match Self::try_parse_duration(value_str) {
+                Some(seconds) => seconds,
+                None => Self::parse_value(value_str)?,
+            }
+        } else {
+            Self::parse_value(value_str)?
+        };
 
         Ok(FilterExpression::Comparison { field, op, value })
     }
@@ -337,6 +347,17 @@ impl FilterExpression {
         }
     }
 
+    /// Pure function: Whether the operator orders values (`<`, `>`, `<=`, `>=`)
+    pub(crate) fn is_ordering_op(op: &ComparisonOp) -> bool {
+        matches!(
+            op,
+            ComparisonOp::Greater
+                | ComparisonOp::Less
+                | ComparisonOp::GreaterEqual
+                | ComparisonOp::LessEqual
+        )
+    }
+
     /// Parse a value string into a JSON value
     pub(crate) fn parse_value(value_str: &str) -> Result<Value> {
         let trimmed = value_str.trim();
@@ -386,6 +407,22 @@ impl FilterExpression {
             .and_then(|num| serde_json::Number::from_f64(num).map(Value::Number))
     }
 
+    /// Pure function: Try to parse a duration such as `90s`, `5m` or `250ms` as seconds
+    pub(crate) fn try_parse_duration(s: &str) -> Option<Value> {
+        let unit_start = s.find(|c: char| !c.is_ascii_digit() && c != '.')?;
+        let (number, unit) = s.split_at(unit_start);
+        let number: f64 = number.parse().ok()?;
+        let seconds = match unit {
+            "ms" => number / 1000.0,
+            "s" => number,
+            "m" => number * 60.0,
+            "h" => number * 3600.0,
+            "d" => number * 86400.0,
+            _ => return None,
+        };
+        serde_json::Number::from_f64(seconds).map(Value::Number)
+    }
+
     /// Evaluate the filter expression against a JSON value
     pub fn evaluate(&self, item: &Value) -> bool {
         match self {
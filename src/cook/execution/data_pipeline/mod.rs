@@ -3,17 +3,20 @@
 //! Provides JSON path extraction, filtering, sorting, and data transformation
 //! capabilities for processing work items in MapReduce workflows.
 
+mod changed_files;
 mod filter;
 mod json_path;
 mod sorter;
 pub mod validation;
 
+pub use changed_files::ChangedFiles;
 pub use filter::{ComparisonOp, FilterExpression, LogicalOp, PathPart};
 pub use json_path::JsonPath;
 pub use sorter::{NullPosition, SortField, SortOrder, Sorter};
 pub use validation::{ValidWorkItem, WorkItemValidationError};
 
 use anyhow::{anyhow, Result};
+use serde::Serialize;
 use serde_json::Value;
 use std::collections::HashMap;
 use std::io::Read;
@@ -26,6 +29,8 @@ pub struct DataPipeline {
     pub json_path: Option<JsonPath>,
     /// Filter expression for selecting items
     pub filter: Option<FilterExpression>,
+    /// Files changed relative to a git ref that items must refer to
+    pub changed_files: Option<ChangedFiles>,
     /// Sorting configuration
     pub sorter: Option<Sorter>,
     /// Maximum number of items to process
@@ -40,6 +45,25 @@ pub struct DataPipeline {
     pub preview_mode: bool,
 }
 
+/// Items kept and dropped by one configured pipeline stage
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StageStats {
+    /// Workflow field of the stage, e.g. `filter` or `max_items`
+    pub stage: &'static str,
+    pub kept: usize,
+    pub dropped: usize,
+}
+
+/// Items produced by the pipeline with the counts of each stage
+#[derive(Debug, Clone, PartialEq)]
+pub struct PipelineRun {
+    pub items: Vec<Value>,
+    /// Items selected by the JSON path, before any other stage
+    pub extracted: usize,
+    /// Configured stages in the order they ran
+    pub stages: Vec<StageStats>,
+}
+
 impl DataPipeline {
     /// Create a new data pipeline from configuration
     pub fn from_config(
@@ -73,6 +97,7 @@ impl DataPipeline {
         Ok(Self {
             json_path,
             filter,
+            changed_files: None,
             sorter,
             limit: max_items,
             offset: None,
@@ -116,6 +141,7 @@ impl DataPipeline {
         Ok(Self {
             json_path,
             filter,
+            changed_files: None,
             sorter,
             limit: max_items,
             offset,
@@ -125,8 +151,19 @@ impl DataPipeline {
         })
     }
 
+    /// Keep only items that refer to the given changed files
+    pub fn with_changed_files(mut self, changed_files: ChangedFiles) -> Self {
+        self.changed_files = Some(changed_files);
+        self
+    }
+
     /// Process input data through the pipeline
     pub fn process(&self, input: &Value) -> Result<Vec<Value>> {
+        Ok(self.process_with_stats(input)?.items)
+    }
+
+    /// Process input data, counting the items each configured stage kept and dropped
+    pub fn process_with_stats(&self, input: &Value) -> Result<PipelineRun> {
         debug!("Processing data through pipeline");
 
         // Step 1: Extract items using JSON path
@@ -151,6 +188,15 @@ impl DataPipeline {
         };
 
         debug!("Extracted {} items from JSON path", items.len());
+        let extracted = items.len();
+        let mut stages = Vec::new();
+        let mut record = |stage: &'static str, before: usize, after: usize| {
+            stages.push(StageStats {
+                stage,
+                kept: after,
+                dropped: before - after,
+            })
+        };
 
         // Step 2: Apply filter
         if let Some(ref filter) = self.filter {
@@ -162,34 +208,54 @@ impl DataPipeline {
                 items.len(),
                 before_count - items.len()
             );
+            record("filter", before_count, items.len());
+        }
+
+        // Step 2b: Keep items that refer to changed files
+        if let Some(ref changed) = self.changed_files {
+            let before_count = items.len();
+            items.retain(|item| changed.matches(item));
+            debug!(
+                "After changed-file scoping: {} items (skipped {})",
+                items.len(),
+                before_count - items.len()
+            );
+            record("changed_since", before_count, items.len());
         }
 
         // Step 3: Sort items
         if let Some(ref sorter) = self.sorter {
             sorter.sort(&mut items);
             debug!("Sorted {} items", items.len());
+            record("sort_by", items.len(), items.len());
         }
 
         // Step 4: Apply distinct (deduplication)
         if let Some(ref distinct_field) = self.distinct {
+            let before_count = items.len();
             items = self.deduplicate(items, distinct_field)?;
             debug!("Deduplicated to {} items", items.len());
+            record("distinct", before_count, items.len());
         }
 
         // Step 5: Apply offset
         if let Some(offset) = self.offset {
+            let before_count = items.len();
             if offset < items.len() {
                 items = items[offset..].to_vec();
                 debug!("Applied offset {}, {} items remaining", offset, items.len());
             } else {
                 items.clear();
             }
+            record("offset", before_count, items.len());
         }
 
         // Step 6: Apply limit
         if let Some(limit) = self.limit {
+            let before_count = items.len();
             items.truncate(limit);
             debug!("Limited to {} items", items.len());
+            record("max_items", before_count, items.len());
         }
 
         // Step 7: Apply field mapping
@@ -200,7 +266,11 @@ impl DataPipeline {
                 .collect();
         }
 
-        Ok(items)
+        Ok(PipelineRun {
+            items,
+            extracted,
+            stages,
+        })
     }
 
     /// Process streaming JSON input
@@ -365,6 +435,49 @@ mod tests {
         assert_eq!(results[1]["priority"], 5);
     }
 
+    #[test]
+    fn test_pipeline_stats_count_each_stage() {
+        let pipeline = DataPipeline::from_full_config(
+            Some("$.items[*]".to_string()),
+            Some("priority > 3".to_string()),
+            Some("priority DESC".to_string()),
+            Some(1),
+            None,
+            Some("group".to_string()),
+        )
+        .unwrap();
+
+        let data = json!({
+            "items": [
+                {"id": 1, "priority": 5, "group": "a"},
+                {"id": 2, "priority": 2, "group": "a"},
+                {"id": 3, "priority": 8, "group": "a"},
+                {"id": 4, "priority": 4, "group": "b"},
+            ]
+        });
+
+        let run = pipeline.process_with_stats(&data).unwrap();
+        assert_eq!(run.extracted, 4);
+        let counts: Vec<(&str, usize, usize)> = run
+            .stages
+            .iter()
+            .map(|stage| (stage.stage, stage.kept, stage.dropped))
+            .collect();
+        assert_eq!(
+            counts,
+            vec![
+                ("filter", 3, 1),
+                ("sort_by", 3, 0),
+                ("distinct", 2, 1),
+                ("max_items", 1, 1)
+            ]
+        );
+        assert_eq!(
+            run.items,
+            vec![json!({"id": 3, "priority": 8, "group": "a"})]
+        );
+    }
+
     #[test]
     fn test_mapreduce_debtmap_scenario() {
         // Test the exact scenario from the debtmap MapReduce workflow
@@ -429,6 +542,25 @@ mod tests {
         assert_eq!(results_4[3]["unified_score"]["final_score"], 5.1);
     }
 
+    #[test]
+    fn test_changed_files_scoping_before_limit() {
+        let pipeline = DataPipeline::from_config(None, None, None, Some(1))
+            .unwrap()
+            .with_changed_files(ChangedFiles::new(
+                vec![std::path::PathBuf::from("src/b.rs")],
+                Some("file".to_string()),
+            ));
+
+        let input = json!([
+            {"file": "src/a.rs"},
+            {"file": "src/b.rs"},
+            {"file": "src/c.rs"}
+        ]);
+
+        let result = pipeline.process(&input).unwrap();
+        assert_eq!(result, vec![json!({"file": "src/b.rs"})]);
+    }
+
     #[test]
     fn test_distinct_deduplication() {
         // Test deduplication based on distinct field
@@ -1461,6 +1593,25 @@ mod tests {
             assert!(result.is_ok());
         }
 
+        #[test]
+        fn test_parse_duration_values() {
+            let expr = FilterExpression::parse("duration > 1.5m").unwrap();
+            assert!(expr.evaluate(&json!({"duration": 120})));
+            assert!(!expr.evaluate(&json!({"duration": 60})));
+
+            let expr = FilterExpression::parse("elapsed <= 250ms").unwrap();
+            assert!(expr.evaluate(&json!({"elapsed": 0.25})));
+
+            // Other unquoted words stay strings
+            let expr = FilterExpression::parse("size == 5xl").unwrap();
+            assert!(expr.evaluate(&json!({"size": "5xl"})));
+
+            // Equality keeps string semantics, even for duration-like tokens
+            let expr = FilterExpression::parse("window == 5m").unwrap();
+            assert!(expr.evaluate(&json!({"window": "5m"})));
+            assert!(!expr.evaluate(&json!({"window": 300})));
+        }
+
         #[test]
         fn test_parse_field_paths() {
             // Test simple field
@@ -0,0 +1,146 @@
+//! Deterministic mode for reproducing runs
+//!
+//! `prodigy run --seed N` makes Prodigy's own randomized decisions repeatable:
+//! retry jitter, chaos mode faults without their own seed, and generated
+//! inputs are drawn from generators seeded from `N`, and MapReduce agents take
+//! their slots strictly in work-item order instead of whichever spawned task
+//! the runtime polls first. The seed is recorded in the session metadata, so a
+//! problematic run can be repeated with the same seed.
+//!
+//! The seed travels from the run's command to the executors that make these
+//! decisions. Without a seed, generators are seeded from system entropy.
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::collections::HashMap;
+use tokio::sync::watch;
+
+/// Session metadata key holding the seed of a deterministic run
+pub const SEED_METADATA_KEY: &str = "seed";
+
+/// Random number generator for one decision
+///
+/// In a seeded run the generator depends only on `seed`, the `stream`
+/// naming the kind of decision, and `key` (e.g. the retry delay), so the
+/// same decision comes out the same regardless of task interleaving.
+/// Otherwise it is freshly seeded from system entropy.
+pub fn rng(seed: Option<u64>, stream: &str, key: u64) -> StdRng {
+    match seed {
+        Some(seed) => StdRng::seed_from_u64(derive_seed(seed, stream, key)),
+        None => StdRng::seed_from_u64(rand::random()),
+    }
+}
+
+/// Seed recorded in a session's metadata
+pub fn from_metadata(metadata: &HashMap<String, serde_json::Value>) -> Option<u64> {
+    metadata.get(SEED_METADATA_KEY).and_then(|v| v.as_u64())
+}
+
+/// Mix the run seed with a stream name and key (FNV-1a, then SplitMix64)
+fn derive_seed(seed: u64, stream: &str, key: u64) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in stream.bytes() {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    let mut z = seed ^ hash.rotate_left(17) ^ key.wrapping_mul(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// Hands out turns in a fixed order
+///
+/// Agents of a seeded run wait for their turn before taking a slot, so slots
+/// go to work items in order even though every agent task is spawned at once.
+#[derive(Debug)]
+pub struct TurnOrder {
+    next: watch::Sender<usize>,
+}
+
+impl Default for TurnOrder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TurnOrder {
+    pub fn new() -> Self {
+        Self {
+            next: watch::channel(0).0,
+        }
+    }
+
+    /// Wait until every earlier position has finished its turn
+    ///
+    /// The turn ends when the returned guard is dropped.
+    pub async fn turn(&self, position: usize) -> Turn<'_> {
+        let mut next = self.next.subscribe();
+        // The sender lives as long as `self`, so waiting cannot fail
+        let _ = next.wait_for(|next| *next >= position).await;
+        Turn { order: self }
+    }
+}
+
+/// A held turn; the next position may proceed once this is dropped
+pub struct Turn<'a> {
+    order: &'a TurnOrder,
+}
+
+impl Drop for Turn<'_> {
+    fn drop(&mut self) {
+        self.order.next.send_modify(|next| *next += 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_derived_generators_depend_on_seed_stream_and_key() {
+        let draw = |seed, stream, key| {
+            StdRng::seed_from_u64(derive_seed(seed, stream, key)).random::<u64>()
+        };
+        assert_eq!(draw(42, "retry", 1), draw(42, "retry", 1));
+        assert_ne!(draw(42, "retry", 1), draw(43, "retry", 1));
+        assert_ne!(draw(42, "retry", 1), draw(42, "chaos", 1));
+        assert_ne!(draw(42, "retry", 1), draw(42, "retry", 2));
+        assert_eq!(
+            rng(Some(42), "retry", 1).random::<u64>(),
+            draw(42, "retry", 1)
+        );
+    }
+
+    #[test]
+    fn test_seed_from_metadata() {
+        let metadata = HashMap::from([(SEED_METADATA_KEY.to_string(), serde_json::json!(7))]);
+        assert_eq!(from_metadata(&metadata), Some(7));
+        assert_eq!(from_metadata(&HashMap::new()), None);
+    }
+
+    #[tokio::test]
+    async fn test_turns_are_taken_in_order() {
+        let order = Arc::new(TurnOrder::new());
+        let taken = Arc::new(Mutex::new(Vec::new()));
+
+        let tasks: Vec<_> = (0..8)
+            .rev()
+            .map(|position| {
+                let order = order.clone();
+                let taken = taken.clone();
+                tokio::spawn(async move {
+                    let _turn = order.turn(position).await;
+                    taken.lock().unwrap().push(position);
+                })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap();
+        }
+
+        assert_eq!(*taken.lock().unwrap(), (0..8).collect::<Vec<_>>());
+    }
+}
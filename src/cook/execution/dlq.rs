@@ -14,7 +14,9 @@ use tokio::fs;
 use tokio::sync::RwLock;
 use tracing::{debug, error, info, warn};
 
+use super::dlq_clustering::{cluster_failures, FailureCluster};
 use super::events::EventLogger;
+use super::expression::ExpressionEngine;
 
 /// Dead Letter Queue for handling failed items
 pub struct DeadLetterQueue {
@@ -77,6 +79,51 @@ pub enum ErrorType {
     Unknown,
 }
 
+impl ErrorType {
+    /// Snake-case name used in filter expressions
+    pub fn name(&self) -> &'static str {
+        match self {
+            ErrorType::Timeout => "timeout",
+            ErrorType::CommandFailed { .. } => "command_failed",
+            ErrorType::CommitValidationFailed => "commit_validation_failed",
+            ErrorType::WorktreeError => "worktree_error",
+            ErrorType::MergeConflict => "merge_conflict",
+            ErrorType::ValidationFailed => "validation_failed",
+            ErrorType::ResourceExhausted => "resource_exhausted",
+            ErrorType::Unknown => "unknown",
+        }
+    }
+}
+
+impl DeadLetteredItem {
+    /// Fields exposed to `--where` filter expressions
+    ///
+    /// Error details come from the latest failure; the original work item is
+    /// available under `item`.
+    pub fn filter_fields(&self) -> Value {
+        let latest = self.failure_history.iter().max_by_key(|f| f.attempt_number);
+        let exit_code = latest.and_then(|f| match f.error_type {
+            ErrorType::CommandFailed { exit_code } => Some(exit_code),
+            _ => None,
+        });
+
+        serde_json::json!({
+            "item_id": self.item_id,
+            "item": self.item_data,
+            "error_type": latest.map(|f| f.error_type.name()),
+            "exit_code": exit_code,
+            "error": latest.map(|f| f.error_message.as_str()),
+            "step_failed": latest.map(|f| f.step_failed.as_str()),
+            "failure_count": self.failure_count,
+            "error_signature": self.error_signature,
+            "reprocess_eligible": self.reprocess_eligible,
+            "manual_review_required": self.manual_review_required,
+            "first_attempt": self.first_attempt.to_rfc3339(),
+            "last_attempt": self.last_attempt.to_rfc3339(),
+        })
+    }
+}
+
 /// Artifacts from the worktree where the failure occurred
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorktreeArtifacts {
@@ -100,6 +147,9 @@ pub struct FailureAnalysis {
     pub pattern_groups: Vec<PatternGroup>,
     pub error_distribution: HashMap<ErrorType, usize>,
     pub temporal_distribution: Vec<(DateTime<Utc>, usize)>,
+    /// Items clustered by normalized error message and failing step
+    #[serde(default)]
+    pub clusters: Vec<FailureCluster>,
 }
 
 /// A group of failures with similar patterns
@@ -321,6 +371,46 @@ impl DeadLetterQueue {
         Ok(items.get(item_id).cloned())
     }
 
+    /// Select items matching a filter expression
+    ///
+    /// Uses the MapReduce filter syntax against
+    /// [`DeadLetteredItem::filter_fields`], for example
+    /// `error_type == "timeout" && failure_count < 3`.
+    pub async fn select_where(&self, expression: &str) -> Result<Vec<DeadLetteredItem>> {
+        let filter = ExpressionEngine::new()
+            .compile_filter(expression)
+            .with_context(|| format!("Invalid filter expression: {}", expression))?;
+
+        let items = self.items.read().await;
+        let mut selected = Vec::new();
+        for item in items.values() {
+            if filter.evaluate(&item.filter_fields())? {
+                selected.push(item.clone());
+            }
+        }
+        selected.sort_by(|a, b| a.item_id.cmp(&b.item_id));
+
+        Ok(selected)
+    }
+
+    /// Mark items eligible for reprocessing so the next resume picks them up
+    pub async fn requeue(&self, item_ids: &[String]) -> Result<usize> {
+        let mut items = self.items.write().await;
+        let mut requeued = 0;
+
+        for item_id in item_ids {
+            if let Some(item) = items.get_mut(item_id) {
+                item.reprocess_eligible = true;
+                item.manual_review_required = false;
+                self.storage.persist(&self.job_id, item).await?;
+                requeued += 1;
+            }
+        }
+
+        info!("Requeued {} DLQ items for job {}", requeued, self.job_id);
+        Ok(requeued)
+    }
+
     /// Analyze failure patterns in the DLQ
     pub async fn analyze_patterns(&self) -> Result<FailureAnalysis> {
         let items = self.items.read().await;
@@ -388,6 +478,7 @@ impl DeadLetterQueue {
             pattern_groups,
             error_distribution,
             temporal_distribution,
+            clusters: cluster_failures(items.values()),
         })
     }
 
@@ -0,0 +1,317 @@
+//! Similarity clustering for Dead Letter Queue items
+//!
+//! Error signatures group only identical messages, so failures that differ in
+//! a path, commit hash, or line number end up in separate groups. These pure
+//! functions normalize those variable parts away and cluster items by the
+//! normalized message and failing step, with a suggested bulk action per
+//! cluster.
+
+use super::dlq::{DeadLetteredItem, ErrorType, FailureDetail};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+
+/// Number of representative samples kept per cluster
+const SAMPLES_PER_CLUSTER: usize = 3;
+
+static UUID_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\b[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}\b")
+        .expect("Valid regex pattern")
+});
+
+static PATH_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?:[A-Za-z]:)?[\w.~-]*(?:[/\\][\w.-]+)+(?::\d+)*").expect("Valid regex pattern")
+});
+
+static HASH_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)\b[0-9a-f]{7,64}\b").expect("Valid regex pattern"));
+
+static NUMBER_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\d+").expect("Valid regex pattern"));
+
+/// Bulk action suggested for a cluster
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SuggestedAction {
+    /// Errors look transient; reprocess the whole cluster
+    RetryCluster,
+    /// Errors repeat deterministically; retrying will not help
+    DropCluster,
+    /// At least one item was flagged for manual review
+    ManualReview,
+}
+
+impl SuggestedAction {
+    /// Short human-readable label
+    pub fn label(&self) -> &'static str {
+        match self {
+            SuggestedAction::RetryCluster => "retry cluster",
+            SuggestedAction::DropCluster => "drop cluster",
+            SuggestedAction::ManualReview => "manual review",
+        }
+    }
+}
+
+/// A representative failure within a cluster
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClusterSample {
+    pub item_id: String,
+    pub error_message: String,
+}
+
+/// DLQ items sharing a normalized error message and failing step
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FailureCluster {
+    /// Error message with paths, hashes, and numbers replaced by placeholders
+    pub normalized_error: String,
+    /// Step that failed for every item in the cluster
+    pub step_failed: String,
+    pub error_type: ErrorType,
+    pub count: usize,
+    pub item_ids: Vec<String>,
+    pub samples: Vec<ClusterSample>,
+    pub suggested_action: SuggestedAction,
+    /// Why the action was suggested
+    pub reason: String,
+}
+
+/// Replace variable parts of an error message with placeholders
+///
+/// UUIDs, file paths (with optional `:line:col`), hex hashes, and numbers
+/// become `<uuid>`, `<path>`, `<hash>`, and `<n>`; whitespace is collapsed.
+pub fn normalize_error_message(message: &str) -> String {
+    let normalized = UUID_REGEX.replace_all(message, "<uuid>");
+    let normalized = PATH_REGEX.replace_all(&normalized, "<path>");
+    let normalized = HASH_REGEX.replace_all(&normalized, "<hash>");
+    let normalized = NUMBER_REGEX.replace_all(&normalized, "<n>");
+    normalized.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Cluster items by normalized latest error and failing step
+///
+/// Clusters are sorted by size, largest first.
+pub fn cluster_failures<'a>(
+    items: impl IntoIterator<Item = &'a DeadLetteredItem>,
+) -> Vec<FailureCluster> {
+    let mut groups: HashMap<(String, String), Vec<&DeadLetteredItem>> = HashMap::new();
+    for item in items {
+        let Some(latest) = latest_failure(item) else {
+            continue;
+        };
+        let key = (
+            normalize_error_message(&latest.error_message),
+            latest.step_failed.clone(),
+        );
+        groups.entry(key).or_default().push(item);
+    }
+
+    let mut clusters: Vec<FailureCluster> = groups
+        .into_iter()
+        .map(|((normalized_error, step_failed), mut members)| {
+            members.sort_by(|a, b| a.item_id.cmp(&b.item_id));
+            let error_type = latest_failure(members[0])
+                .map(|f| f.error_type.clone())
+                .unwrap_or(ErrorType::Unknown);
+            let (suggested_action, reason) = suggest_action(&members, &error_type);
+            FailureCluster {
+                normalized_error,
+                step_failed,
+                error_type,
+                count: members.len(),
+                item_ids: members.iter().map(|i| i.item_id.clone()).collect(),
+                samples: members
+                    .iter()
+                    .take(SAMPLES_PER_CLUSTER)
+                    .filter_map(|item| {
+                        latest_failure(item).map(|f| ClusterSample {
+                            item_id: item.item_id.clone(),
+                            error_message: f.error_message.clone(),
+                        })
+                    })
+                    .collect(),
+                suggested_action,
+                reason,
+            }
+        })
+        .collect();
+
+    clusters.sort_by(|a, b| {
+        b.count
+            .cmp(&a.count)
+            .then_with(|| a.normalized_error.cmp(&b.normalized_error))
+    });
+    clusters
+}
+
+fn latest_failure(item: &DeadLetteredItem) -> Option<&FailureDetail> {
+    item.failure_history.iter().max_by_key(|f| f.attempt_number)
+}
+
+fn is_transient(error_type: &ErrorType) -> bool {
+    matches!(
+        error_type,
+        ErrorType::Timeout
+            | ErrorType::ResourceExhausted
+            | ErrorType::MergeConflict
+            | ErrorType::WorktreeError
+    )
+}
+
+/// Whether every attempt of the item failed with the same normalized error
+fn fails_deterministically(item: &DeadLetteredItem) -> bool {
+    let messages: BTreeSet<String> = item
+        .failure_history
+        .iter()
+        .map(|f| normalize_error_message(&f.error_message))
+        .collect();
+    item.failure_history.len() >= 2 && messages.len() == 1
+}
+
+fn suggest_action(
+    members: &[&DeadLetteredItem],
+    error_type: &ErrorType,
+) -> (SuggestedAction, String) {
+    if members.iter().any(|i| i.manual_review_required) {
+        return (
+            SuggestedAction::ManualReview,
+            "items are flagged for manual review".to_string(),
+        );
+    }
+    if !members.iter().any(|i| i.reprocess_eligible) {
+        return (
+            SuggestedAction::DropCluster,
+            "no items are eligible for reprocessing".to_string(),
+        );
+    }
+    if is_transient(error_type) {
+        return (
+            SuggestedAction::RetryCluster,
+            format!("{:?} errors are usually transient", error_type),
+        );
+    }
+    if members.iter().all(|i| fails_deterministically(i)) {
+        return (
+            SuggestedAction::DropCluster,
+            "every attempt failed with the same error".to_string(),
+        );
+    }
+    (
+        SuggestedAction::RetryCluster,
+        "errors varied between attempts".to_string(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use serde_json::json;
+
+    fn item(id: &str, error_type: ErrorType, step: &str, messages: &[&str]) -> DeadLetteredItem {
+        DeadLetteredItem {
+            item_id: id.to_string(),
+            item_data: json!({}),
+            first_attempt: Utc::now(),
+            last_attempt: Utc::now(),
+            failure_count: messages.len() as u32,
+            failure_history: messages
+                .iter()
+                .enumerate()
+                .map(|(i, message)| FailureDetail {
+                    attempt_number: i as u32 + 1,
+                    timestamp: Utc::now(),
+                    error_type: error_type.clone(),
+                    error_message: message.to_string(),
+                    error_context: None,
+                    stack_trace: None,
+                    agent_id: format!("agent-{id}"),
+                    step_failed: step.to_string(),
+                    duration_ms: 10,
+                    json_log_location: None,
+                })
+                .collect(),
+            error_signature: String::new(),
+            worktree_artifacts: None,
+            reprocess_eligible: true,
+            manual_review_required: false,
+        }
+    }
+
+    #[test]
+    fn test_normalize_strips_variable_parts() {
+        assert_eq!(
+            normalize_error_message(
+                "error[E0308] at src/lib/parser.rs:42:7 in commit 3f9a2c1d  (agent 550e8400-e29b-41d4-a716-446655440000)"
+            ),
+            "error[E<n>] at <path> in commit <hash> (agent <uuid>)"
+        );
+        assert_eq!(
+            normalize_error_message("Command timed out after 300s"),
+            normalize_error_message("Command timed out after 600s")
+        );
+    }
+
+    #[test]
+    fn test_cluster_by_message_and_step() {
+        let items = [
+            item(
+                "a",
+                ErrorType::Timeout,
+                "claude: /fix",
+                &["timed out after 300s"],
+            ),
+            item(
+                "b",
+                ErrorType::Timeout,
+                "claude: /fix",
+                &["timed out after 310s"],
+            ),
+            item(
+                "c",
+                ErrorType::Timeout,
+                "shell: test",
+                &["timed out after 300s"],
+            ),
+            item(
+                "d",
+                ErrorType::ValidationFailed,
+                "shell: test",
+                &[
+                    "assertion failed in tests/a.rs:1",
+                    "assertion failed in tests/a.rs:9",
+                ],
+            ),
+        ];
+
+        let clusters = cluster_failures(&items);
+
+        assert_eq!(clusters.len(), 3);
+        assert_eq!(clusters[0].item_ids, vec!["a", "b"]);
+        assert_eq!(clusters[0].step_failed, "claude: /fix");
+        assert_eq!(clusters[0].samples.len(), 2);
+        assert_eq!(clusters[0].suggested_action, SuggestedAction::RetryCluster);
+
+        let validation = clusters.iter().find(|c| c.item_ids == vec!["d"]).unwrap();
+        assert_eq!(validation.normalized_error, "assertion failed in <path>");
+        assert_eq!(validation.suggested_action, SuggestedAction::DropCluster);
+    }
+
+    #[test]
+    fn test_manual_review_and_ineligible_items() {
+        let mut flagged = item("a", ErrorType::Timeout, "s", &["boom"]);
+        flagged.manual_review_required = true;
+        let mut ineligible = item("b", ErrorType::Timeout, "s", &["other"]);
+        ineligible.reprocess_eligible = false;
+
+        let clusters = cluster_failures(&[flagged, ineligible]);
+        let action = |id: &str| {
+            clusters
+                .iter()
+                .find(|c| c.item_ids == vec![id.to_string()])
+                .map(|c| c.suggested_action)
+        };
+
+        assert_eq!(action("a"), Some(SuggestedAction::ManualReview));
+        assert_eq!(action("b"), Some(SuggestedAction::DropCluster));
+    }
+}
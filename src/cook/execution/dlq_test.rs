@@ -124,6 +124,9 @@ mod tests {
         assert_eq!(analysis.pattern_groups.len(), 1);
         assert_eq!(analysis.pattern_groups[0].count, 3);
         assert_eq!(analysis.pattern_groups[0].signature, error_signature);
+        assert_eq!(analysis.clusters.len(), 1);
+        assert_eq!(analysis.clusters[0].count, 3);
+        assert_eq!(analysis.clusters[0].normalized_error, "Test error");
 
         Ok(())
     }
@@ -400,4 +403,66 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_select_where_and_requeue() -> Result<()> {
+        let temp_dir = tempdir()?;
+        let dlq = DeadLetterQueue::new(
+            "test-where".to_string(),
+            temp_dir.path().into(),
+            100,
+            30,
+            None,
+        )
+        .await?;
+
+        for (id, error_type, failures) in [
+            ("a", ErrorType::Timeout, 1),
+            ("b", ErrorType::Timeout, 5),
+            ("c", ErrorType::CommandFailed { exit_code: 2 }, 1),
+        ] {
+            let mut item = create_test_item(
+                id,
+                serde_json::json!({"priority": failures}),
+                failures,
+                "sig",
+                false,
+            );
+            item.failure_history = vec![create_test_failure_detail(1, error_type, "boom")];
+            dlq.add(item).await?;
+        }
+
+        let selected = dlq
+            .select_where(r#"error_type == "timeout" && failure_count < 3"#)
+            .await?;
+        assert_eq!(
+            selected
+                .iter()
+                .map(|i| i.item_id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["a"]
+        );
+
+        let by_exit_code = dlq
+            .select_where("exit_code == 2 || item.priority >= 5")
+            .await?;
+        assert_eq!(by_exit_code.len(), 2);
+
+        assert!(dlq.select_where("error_type ==").await.is_err());
+
+        let ids: Vec<String> = selected.into_iter().map(|i| i.item_id).collect();
+        assert_eq!(dlq.requeue(&ids).await?, 1);
+        let reloaded = DeadLetterQueue::new(
+            "test-where".to_string(),
+            temp_dir.path().into(),
+            100,
+            30,
+            None,
+        )
+        .await?;
+        assert!(reloaded.get_item("a").await?.unwrap().reprocess_eligible);
+        assert!(!reloaded.get_item("b").await?.unwrap().reprocess_eligible);
+
+        Ok(())
+    }
 }
@@ -256,6 +256,14 @@ impl EventLogger {
             metadata.insert("thread".to_string(), Value::String(name.to_string()));
         }
 
+        // Mark events of triggered runs so they do not fire triggers again
+        if let Ok(trigger_id) = std::env::var(crate::cook::triggers::TRIGGER_ID_ENV) {
+            metadata.insert(
+                crate::cook::triggers::TRIGGER_ID_METADATA.to_string(),
+                Value::String(trigger_id),
+            );
+        }
+
         metadata
     }
 }
@@ -36,6 +36,12 @@ pub enum MapReduceEvent {
         checkpoint_version: u32,
         pending_items: usize,
     },
+    ConfigReloaded {
+        job_id: String,
+        previous_version: String,
+        new_version: String,
+        changed_files: Vec<String>,
+    },
 
     // Agent lifecycle events
     AgentStarted {
@@ -179,6 +185,7 @@ impl MapReduceEvent {
             | JobFailed { job_id, .. }
             | JobPaused { job_id, .. }
             | JobResumed { job_id, .. }
+            | ConfigReloaded { job_id, .. }
             | AgentStarted { job_id, .. }
             | AgentProgress { job_id, .. }
             | AgentCompleted { job_id, .. }
@@ -234,6 +241,7 @@ impl MapReduceEvent {
             JobFailed { .. } => "job_failed",
             JobPaused { .. } => "job_paused",
             JobResumed { .. } => "job_resumed",
+            ConfigReloaded { .. } => "config_reloaded",
             AgentStarted { .. } => "agent_started",
             AgentProgress { .. } => "agent_progress",
             AgentCompleted { .. } => "agent_completed",
@@ -57,6 +57,7 @@ impl MapReduceEvent {
             JobStarted { .. }
             | JobCompleted { .. }
             | AgentCompleted { .. }
+            | ConfigReloaded { .. }
             | DLQItemAdded { .. } => EventSeverity::Info,
             _ => EventSeverity::Debug,
         }
@@ -70,7 +71,8 @@ impl MapReduceEvent {
             | JobCompleted { .. }
             | JobFailed { .. }
             | JobPaused { .. }
-            | JobResumed { .. } => EventCategory::JobLifecycle,
+            | JobResumed { .. }
+            | ConfigReloaded { .. } => EventCategory::JobLifecycle,
             AgentStarted { .. }
             | AgentProgress { .. }
             | AgentCompleted { .. }
@@ -1,5 +1,6 @@
 //! Event retention policy management
 
+use crate::styled_println;
 use anyhow::Result;
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
@@ -560,7 +561,7 @@ impl RetentionAnalysis {
         if !self.warnings.is_empty() {
             println!("\nWarnings:");
             for warning in &self.warnings {
-                println!("  ⚠️  {}", warning);
+                styled_println!("  ⚠️  {}", warning);
             }
         }
     }
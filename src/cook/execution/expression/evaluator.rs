@@ -289,10 +289,12 @@ impl ExpressionEvaluator {
 
             // Comparison operators - already extracted as helpers
             Expression::Equal(left, right) => {
-                self.evaluate_binary_comparison(left, right, item, |l, r| l == r)
+                self.evaluate_binary_comparison(left, right, item, Self::json_values_equal)
             }
             Expression::NotEqual(left, right) => {
-                self.evaluate_binary_comparison(left, right, item, |l, r| l != r)
+                self.evaluate_binary_comparison(left, right, item, |l, r| {
+                    !Self::json_values_equal(l, r)
+                })
             }
             Expression::GreaterThan(left, right) => self.evaluate_comparison_gt(left, right, item),
             Expression::LessThan(left, right) => self.evaluate_comparison_lt(left, right, item),
@@ -357,6 +359,14 @@ impl ExpressionEvaluator {
         }
     }
 
+    /// Pure function: Equality that compares numbers by value, so `2 == 2.0`
+    fn json_values_equal(a: &Value, b: &Value) -> bool {
+        match (a, b) {
+            (Value::Number(a), Value::Number(b)) => a.as_f64() == b.as_f64(),
+            _ => a == b,
+        }
+    }
+
     /// Pure function: Evaluate a literal number
     fn evaluate_literal_number(n: f64) -> Value {
         Value::Number(
@@ -163,6 +163,19 @@ mod tests {
         assert!(!filter.evaluate(&item3).unwrap());
     }
 
+    #[test]
+    fn test_expression_engine_equality_compares_numbers_by_value() {
+        let mut engine = ExpressionEngine::new();
+        let equal = engine.compile_filter("score == 2").unwrap();
+        let not_equal = engine.compile_filter("score != 2").unwrap();
+
+        for item in [json!({"score": 2}), json!({"score": 2.0})] {
+            assert!(equal.evaluate(&item).unwrap());
+            assert!(!not_equal.evaluate(&item).unwrap());
+        }
+        assert!(!equal.evaluate(&json!({"score": 2.5})).unwrap());
+    }
+
     #[test]
     fn test_expression_engine_sort() {
         let mut engine = ExpressionEngine::new();
@@ -401,6 +401,9 @@ mod tests {
             do_block: vec![Box::new(WorkflowStepCommand {
                 shell: Some("echo Processing ${item}".to_string()),
                 claude: None,
+                model: None,
+                context: None,
+                validate_output: None,
                 analyze: None,
                 test: None,
                 foreach: None,
@@ -418,6 +421,15 @@ mod tests {
                 capture_output: None,
                 on_failure: None,
                 on_success: None,
+                assert: None,
+                summarize_commits: None,
+                claude_chat: None,
+                script: None,
+                snapshot: false,
+                required: false,
+                commit_policy: None,
+                cwd: None,
+                env: HashMap::new(),
             })],
             continue_on_error: false,
             max_items: None,
@@ -440,6 +452,9 @@ mod tests {
             do_block: vec![Box::new(WorkflowStepCommand {
                 shell: Some("echo Item ${item} at index ${index} of ${total}".to_string()),
                 claude: None,
+                model: None,
+                context: None,
+                validate_output: None,
                 analyze: None,
                 test: None,
                 foreach: None,
@@ -457,6 +472,15 @@ mod tests {
                 capture_output: None,
                 on_failure: None,
                 on_success: None,
+                assert: None,
+                summarize_commits: None,
+                claude_chat: None,
+                script: None,
+                snapshot: false,
+                required: false,
+                commit_policy: None,
+                cwd: None,
+                env: HashMap::new(),
             })],
             continue_on_error: false,
             max_items: None,
@@ -478,6 +502,9 @@ mod tests {
             do_block: vec![Box::new(WorkflowStepCommand {
                 shell: Some("echo Parallel ${item}".to_string()),
                 claude: None,
+                model: None,
+                context: None,
+                validate_output: None,
                 analyze: None,
                 test: None,
                 foreach: None,
@@ -495,6 +522,15 @@ mod tests {
                 capture_output: None,
                 on_failure: None,
                 on_success: None,
+                assert: None,
+                summarize_commits: None,
+                claude_chat: None,
+                script: None,
+                snapshot: false,
+                required: false,
+                commit_policy: None,
+                cwd: None,
+                env: HashMap::new(),
             })],
             continue_on_error: false,
             max_items: None,
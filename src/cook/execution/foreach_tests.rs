@@ -21,6 +21,9 @@ mod foreach_execution_tests {
             do_block: vec![Box::new(WorkflowStepCommand {
                 shell: Some("echo Processing ${item}".to_string()),
                 claude: None,
+                model: None,
+                context: None,
+                validate_output: None,
                 analyze: None,
                 test: None,
                 foreach: None,
@@ -38,6 +41,15 @@ mod foreach_execution_tests {
                 capture_output: None,
                 on_failure: None,
                 on_success: None,
+                assert: None,
+                summarize_commits: None,
+                claude_chat: None,
+                script: None,
+                snapshot: false,
+                required: false,
+                commit_policy: None,
+                cwd: None,
+                env: std::collections::HashMap::new(),
             })],
             continue_on_error: false,
             max_items: None,
@@ -63,6 +75,9 @@ mod foreach_execution_tests {
             do_block: vec![Box::new(WorkflowStepCommand {
                 shell: Some("echo Processing ${item}".to_string()),
                 claude: None,
+                model: None,
+                context: None,
+                validate_output: None,
                 analyze: None,
                 test: None,
                 foreach: None,
@@ -80,6 +95,15 @@ mod foreach_execution_tests {
                 capture_output: None,
                 on_failure: None,
                 on_success: None,
+                assert: None,
+                summarize_commits: None,
+                claude_chat: None,
+                script: None,
+                snapshot: false,
+                required: false,
+                commit_policy: None,
+                cwd: None,
+                env: std::collections::HashMap::new(),
             })],
             continue_on_error: false,
             max_items: None,
@@ -114,6 +138,9 @@ mod foreach_execution_tests {
                 // This command will help us test parallelism
                 shell: Some("sleep 0.1 && echo Processing ${item}".to_string()),
                 claude: None,
+                model: None,
+                context: None,
+                validate_output: None,
                 analyze: None,
                 test: None,
                 foreach: None,
@@ -131,6 +158,15 @@ mod foreach_execution_tests {
                 capture_output: None,
                 on_failure: None,
                 on_success: None,
+                assert: None,
+                summarize_commits: None,
+                claude_chat: None,
+                script: None,
+                snapshot: false,
+                required: false,
+                commit_policy: None,
+                cwd: None,
+                env: std::collections::HashMap::new(),
             })],
             continue_on_error: false,
             max_items: None,
@@ -171,6 +207,9 @@ mod foreach_execution_tests {
             do_block: vec![Box::new(WorkflowStepCommand {
                 shell: Some("echo Processing ${item}".to_string()),
                 claude: None,
+                model: None,
+                context: None,
+                validate_output: None,
                 analyze: None,
                 test: None,
                 foreach: None,
@@ -188,6 +227,15 @@ mod foreach_execution_tests {
                 capture_output: None,
                 on_failure: None,
                 on_success: None,
+                assert: None,
+                summarize_commits: None,
+                claude_chat: None,
+                script: None,
+                snapshot: false,
+                required: false,
+                commit_policy: None,
+                cwd: None,
+                env: std::collections::HashMap::new(),
             })],
             continue_on_error: false,
             max_items: Some(3), // Limit to 3 items
@@ -217,6 +265,9 @@ mod foreach_execution_tests {
                 // This will fail for "fail" item
                 shell: Some("test \"${item}\" != \"fail\" && echo Success || exit 1".to_string()),
                 claude: None,
+                model: None,
+                context: None,
+                validate_output: None,
                 analyze: None,
                 test: None,
                 foreach: None,
@@ -234,6 +285,15 @@ mod foreach_execution_tests {
                 capture_output: None,
                 on_failure: None,
                 on_success: None,
+                assert: None,
+                summarize_commits: None,
+                claude_chat: None,
+                script: None,
+                snapshot: false,
+                required: false,
+                commit_policy: None,
+                cwd: None,
+                env: std::collections::HashMap::new(),
             })],
             continue_on_error: true, // Continue despite failures
             max_items: None,
@@ -264,6 +324,9 @@ mod foreach_execution_tests {
                 // This will fail for "fail" item
                 shell: Some("test \"${item}\" != \"fail\" && echo Success || exit 1".to_string()),
                 claude: None,
+                model: None,
+                context: None,
+                validate_output: None,
                 analyze: None,
                 test: None,
                 foreach: None,
@@ -281,6 +344,15 @@ mod foreach_execution_tests {
                 capture_output: None,
                 on_failure: None,
                 on_success: None,
+                assert: None,
+                summarize_commits: None,
+                claude_chat: None,
+                script: None,
+                snapshot: false,
+                required: false,
+                commit_policy: None,
+                cwd: None,
+                env: std::collections::HashMap::new(),
             })],
             continue_on_error: false, // Stop on first error
             max_items: None,
@@ -303,6 +375,9 @@ mod foreach_execution_tests {
             do_block: vec![Box::new(WorkflowStepCommand {
                 shell: Some("echo 'Processing ${item} (${index}/${total})'".to_string()),
                 claude: None,
+                model: None,
+                context: None,
+                validate_output: None,
                 analyze: None,
                 test: None,
                 foreach: None,
@@ -320,6 +395,15 @@ mod foreach_execution_tests {
                 capture_output: None,
                 on_failure: None,
                 on_success: None,
+                assert: None,
+                summarize_commits: None,
+                claude_chat: None,
+                script: None,
+                snapshot: false,
+                required: false,
+                commit_policy: None,
+                cwd: None,
+                env: std::collections::HashMap::new(),
             })],
             continue_on_error: false,
             max_items: None,
@@ -344,6 +428,9 @@ mod foreach_execution_tests {
             do_block: vec![Box::new(WorkflowStepCommand {
                 shell: Some("echo Processing ${item}".to_string()),
                 claude: None,
+                model: None,
+                context: None,
+                validate_output: None,
                 analyze: None,
                 test: None,
                 foreach: None,
@@ -361,6 +448,15 @@ mod foreach_execution_tests {
                 capture_output: None,
                 on_failure: None,
                 on_success: None,
+                assert: None,
+                summarize_commits: None,
+                claude_chat: None,
+                script: None,
+                snapshot: false,
+                required: false,
+                commit_policy: None,
+                cwd: None,
+                env: std::collections::HashMap::new(),
             })],
             continue_on_error: false,
             max_items: None,
@@ -387,6 +483,9 @@ mod foreach_execution_tests {
                 Box::new(WorkflowStepCommand {
                     shell: Some("echo 'Starting ${item}'".to_string()),
                     claude: None,
+                    model: None,
+                    context: None,
+                    validate_output: None,
                     analyze: None,
                     test: None,
                     foreach: None,
@@ -404,10 +503,22 @@ mod foreach_execution_tests {
                     capture_output: None,
                     on_failure: None,
                     on_success: None,
+                    assert: None,
+                    summarize_commits: None,
+                    claude_chat: None,
+                    script: None,
+                    snapshot: false,
+                    required: false,
+                    commit_policy: None,
+                    cwd: None,
+                    env: std::collections::HashMap::new(),
                 }),
                 Box::new(WorkflowStepCommand {
                     shell: Some("echo 'Processing ${item}'".to_string()),
                     claude: None,
+                    model: None,
+                    context: None,
+                    validate_output: None,
                     analyze: None,
                     test: None,
                     foreach: None,
@@ -425,10 +536,22 @@ mod foreach_execution_tests {
                     capture_output: None,
                     on_failure: None,
                     on_success: None,
+                    assert: None,
+                    summarize_commits: None,
+                    claude_chat: None,
+                    script: None,
+                    snapshot: false,
+                    required: false,
+                    commit_policy: None,
+                    cwd: None,
+                    env: std::collections::HashMap::new(),
                 }),
                 Box::new(WorkflowStepCommand {
                     shell: Some("echo 'Finished ${item}'".to_string()),
                     claude: None,
+                    model: None,
+                    context: None,
+                    validate_output: None,
                     analyze: None,
                     test: None,
                     foreach: None,
@@ -446,6 +569,15 @@ mod foreach_execution_tests {
                     capture_output: None,
                     on_failure: None,
                     on_success: None,
+                    assert: None,
+                    summarize_commits: None,
+                    claude_chat: None,
+                    script: None,
+                    snapshot: false,
+                    required: false,
+                    commit_policy: None,
+                    cwd: None,
+                    env: std::collections::HashMap::new(),
                 }),
             ],
             continue_on_error: false,
@@ -475,6 +607,9 @@ mod foreach_execution_tests {
                 }),
                 shell: None,
                 claude: None,
+                model: None,
+                context: None,
+                validate_output: None,
                 analyze: None,
                 foreach: None,
                 write_file: None,
@@ -491,6 +626,15 @@ mod foreach_execution_tests {
                 capture_output: None,
                 on_failure: None,
                 on_success: None,
+                assert: None,
+                summarize_commits: None,
+                claude_chat: None,
+                script: None,
+                snapshot: false,
+                required: false,
+                commit_policy: None,
+                cwd: None,
+                env: std::collections::HashMap::new(),
             })],
             continue_on_error: false,
             max_items: None,
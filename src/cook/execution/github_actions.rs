@@ -0,0 +1,407 @@
+//! GitHub Actions annotations and step summaries
+//!
+//! When Prodigy runs inside GitHub Actions (`GITHUB_ACTIONS=true`), failed
+//! steps and work items are reported as `::error` workflow commands so they
+//! show up inline on the pull request, and a Markdown summary of the run is
+//! appended to `$GITHUB_STEP_SUMMARY`. Set `PRODIGY_GITHUB_ANNOTATIONS` to
+//! `false` to turn this off, or to `true` to force it outside GitHub Actions.
+//!
+//! The results come from the same recorder that backs `--junit-output`.
+//! Annotations point at the first `path:line` location in a failure's output
+//! that names an existing file, so compiler and test errors land on the line
+//! that caused them.
+
+use super::junit::{CaseOutcome, TestCase};
+use anyhow::{Context, Result};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::io::Write;
+use std::path::Path;
+
+/// Most annotations GitHub shows per step; later ones are dropped
+const MAX_ANNOTATIONS: usize = 10;
+
+static LOCATION_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?:^|[\s(\[`'])(\.{0,2}/?[\w./-]*\w\.\w+):(\d+)(?::(\d+))?").expect("valid regex")
+});
+
+/// Severity of an annotation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnnotationLevel {
+    Error,
+    Warning,
+    Notice,
+}
+
+impl AnnotationLevel {
+    fn command(&self) -> &'static str {
+        match self {
+            AnnotationLevel::Error => "error",
+            AnnotationLevel::Warning => "warning",
+            AnnotationLevel::Notice => "notice",
+        }
+    }
+}
+
+/// A workflow command annotation
+#[derive(Debug, Clone, PartialEq)]
+pub struct Annotation {
+    pub level: AnnotationLevel,
+    pub title: String,
+    pub message: String,
+    pub file: Option<String>,
+    pub line: Option<u64>,
+    pub col: Option<u64>,
+}
+
+impl std::fmt::Display for Annotation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut properties = Vec::new();
+        if let Some(file) = &self.file {
+            properties.push(format!("file={}", escape_property(file)));
+            if let Some(line) = self.line {
+                properties.push(format!("line={}", line));
+            }
+            if let Some(col) = self.col {
+                properties.push(format!("col={}", col));
+            }
+        }
+        properties.push(format!("title={}", escape_property(&self.title)));
+        write!(
+            f,
+            "::{} {}::{}",
+            self.level.command(),
+            properties.join(","),
+            escape_data(&self.message)
+        )
+    }
+}
+
+/// Whether annotations and step summaries should be written
+pub fn enabled() -> bool {
+    enabled_from(
+        std::env::var("PRODIGY_GITHUB_ANNOTATIONS").ok().as_deref(),
+        std::env::var("GITHUB_ACTIONS").ok().as_deref(),
+    )
+}
+
+fn enabled_from(setting: Option<&str>, github_actions: Option<&str>) -> bool {
+    match setting.map(|s| s.trim().to_lowercase()) {
+        Some(s) if matches!(s.as_str(), "0" | "false" | "no" | "off") => false,
+        Some(s) if matches!(s.as_str(), "1" | "true" | "yes" | "on") => true,
+        _ => github_actions == Some("true"),
+    }
+}
+
+/// First `path:line[:col]` in `text` whose file exists under `root`
+pub fn find_location(text: &str, root: &Path) -> Option<(String, u64, Option<u64>)> {
+    LOCATION_RE.captures_iter(text).find_map(|caps| {
+        let path = caps[1].trim_start_matches("./");
+        let file = Path::new(path);
+        let relative = if file.is_absolute() {
+            file.strip_prefix(root).ok()?
+        } else {
+            file
+        };
+        if !root.join(relative).is_file() {
+            return None;
+        }
+        let line = caps[2].parse().ok().filter(|l| *l > 0)?;
+        let col = caps.get(3).and_then(|c| c.as_str().parse().ok());
+        Some((relative.to_string_lossy().replace('\\', "/"), line, col))
+    })
+}
+
+/// Annotations for the failed cases of a run
+///
+/// `run_error` is annotated on its own when the run failed without any
+/// failed case, such as an invalid workflow.
+pub fn annotations(
+    cases: &[TestCase],
+    run_error: Option<&anyhow::Error>,
+    root: &Path,
+) -> Vec<Annotation> {
+    let mut annotations: Vec<Annotation> = cases
+        .iter()
+        .filter_map(|case| match &case.outcome {
+            CaseOutcome::Failed { message, details } => {
+                let location =
+                    find_location(details, root).or_else(|| find_location(message, root));
+                let (file, line, col) = match location {
+                    Some((file, line, col)) => (Some(file), Some(line), col),
+                    None => (None, None, None),
+                };
+                let message = if details.trim().is_empty() {
+                    message.clone()
+                } else if details.starts_with(message.as_str()) {
+                    last_lines(details, 20)
+                } else {
+                    format!("{}\n{}", message, last_lines(details, 20))
+                };
+                Some(Annotation {
+                    level: AnnotationLevel::Error,
+                    title: format!("Prodigy {}: {}", case.suite, case.name),
+                    message,
+                    file,
+                    line,
+                    col,
+                })
+            }
+            _ => None,
+        })
+        .collect();
+
+    if annotations.is_empty() {
+        if let Some(error) = run_error {
+            annotations.push(Annotation {
+                level: AnnotationLevel::Error,
+                title: "Prodigy run failed".to_string(),
+                message: format!("{:#}", error),
+                file: None,
+                line: None,
+                col: None,
+            });
+        }
+    }
+
+    if annotations.len() > MAX_ANNOTATIONS {
+        let dropped = annotations.len() - MAX_ANNOTATIONS + 1;
+        annotations.truncate(MAX_ANNOTATIONS - 1);
+        annotations.push(Annotation {
+            level: AnnotationLevel::Warning,
+            title: "More Prodigy failures".to_string(),
+            message: format!(
+                "{} more failures are not annotated; see the step summary",
+                dropped
+            ),
+            file: None,
+            line: None,
+            col: None,
+        });
+    }
+    annotations
+}
+
+/// Markdown summary of a run for `$GITHUB_STEP_SUMMARY`
+pub fn step_summary(name: &str, cases: &[TestCase], run_error: Option<&anyhow::Error>) -> String {
+    let failed = cases
+        .iter()
+        .filter(|c| matches!(c.outcome, CaseOutcome::Failed { .. }))
+        .count();
+    let skipped = cases
+        .iter()
+        .filter(|c| matches!(c.outcome, CaseOutcome::Skipped { .. }))
+        .count();
+    let status = if run_error.is_some() || failed > 0 {
+        "❌ failed"
+    } else {
+        "✅ succeeded"
+    };
+
+    let mut md = format!("## Prodigy: {} {}\n\n", name, status);
+    md.push_str(&format!(
+        "{} steps and items, {} passed, {} failed, {} skipped\n\n",
+        cases.len(),
+        cases.len() - failed - skipped,
+        failed,
+        skipped
+    ));
+    if let Some(error) = run_error {
+        let error = error.to_string();
+        md.push_str(&format!("> {}\n\n", error.lines().next().unwrap_or("")));
+    }
+
+    if !cases.is_empty() {
+        md.push_str("| Phase | Step | Result | Duration |\n|---|---|---|---|\n");
+        for case in cases {
+            let result = match &case.outcome {
+                CaseOutcome::Passed => "✅ passed".to_string(),
+                CaseOutcome::Failed { message, .. } => {
+                    format!("❌ {}", escape_cell(message.lines().next().unwrap_or("")))
+                }
+                CaseOutcome::Skipped { message } => format!("⏭️ {}", escape_cell(message)),
+            };
+            md.push_str(&format!(
+                "| {} | `{}` | {} | {:.1}s |\n",
+                escape_cell(&case.suite),
+                case.name.replace('`', "'").replace('|', "\\|"),
+                result,
+                case.duration.as_secs_f64()
+            ));
+        }
+        md.push('\n');
+    }
+
+    for case in cases {
+        if let CaseOutcome::Failed { details, .. } = &case.outcome {
+            if details.trim().is_empty() {
+                continue;
+            }
+            md.push_str(&format!(
+                "<details><summary>{}: {}</summary>\n\n```\n{}\n```\n\n</details>\n\n",
+                html_escape(&case.suite),
+                html_escape(&case.name),
+                last_lines(details, 50).replace("```", "'''")
+            ));
+        }
+    }
+    md
+}
+
+/// Print annotations and append the step summary for a finished run
+pub fn report(
+    name: &str,
+    cases: &[TestCase],
+    run_error: Option<&anyhow::Error>,
+    root: &Path,
+) -> Result<()> {
+    for annotation in annotations(cases, run_error, root) {
+        println!("{}", annotation);
+    }
+
+    if let Some(path) = std::env::var_os("GITHUB_STEP_SUMMARY") {
+        let path = Path::new(&path);
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("Failed to open {}", path.display()))?;
+        file.write_all(step_summary(name, cases, run_error).as_bytes())
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+    Ok(())
+}
+
+fn last_lines(text: &str, count: usize) -> String {
+    let lines: Vec<&str> = text.trim_end().lines().collect();
+    lines[lines.len().saturating_sub(count)..].join("\n")
+}
+
+fn escape_data(text: &str) -> String {
+    text.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+fn escape_property(text: &str) -> String {
+    escape_data(text).replace(':', "%3A").replace(',', "%2C")
+}
+
+fn escape_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    fn failed(name: &str, details: &str) -> TestCase {
+        TestCase {
+            suite: "map".to_string(),
+            name: name.to_string(),
+            duration: Duration::from_secs(2),
+            outcome: CaseOutcome::Failed {
+                message: "Step failed with exit code 101".to_string(),
+                details: details.to_string(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_enabled_detection() {
+        assert!(enabled_from(None, Some("true")));
+        assert!(!enabled_from(None, None));
+        assert!(!enabled_from(Some("false"), Some("true")));
+        assert!(enabled_from(Some("1"), None));
+    }
+
+    #[test]
+    fn test_annotation_format_escapes_values() {
+        let annotation = Annotation {
+            level: AnnotationLevel::Error,
+            title: "Prodigy map: a,b:c".to_string(),
+            message: "100% broken\nsecond line".to_string(),
+            file: Some("src/lib.rs".to_string()),
+            line: Some(7),
+            col: Some(3),
+        };
+        assert_eq!(
+            annotation.to_string(),
+            "::error file=src/lib.rs,line=7,col=3,title=Prodigy map%3A a%2Cb%3Ac::100%25 broken%0Asecond line"
+        );
+    }
+
+    #[test]
+    fn test_annotations_locate_existing_files() {
+        let root = TempDir::new().unwrap();
+        std::fs::create_dir_all(root.path().join("src")).unwrap();
+        std::fs::write(root.path().join("src/parser.rs"), "fn main() {}\n").unwrap();
+
+        let absolute = root.path().join("src/parser.rs");
+        let cases = vec![
+            failed(
+                "item_0",
+                "error[E0308]: mismatched types\n  --> src/parser.rs:12:5\n",
+            ),
+            failed(
+                "item_1",
+                &format!("thread panicked at {}:40:9", absolute.display()),
+            ),
+            failed("item_2", "see missing/file.rs:3 for details"),
+        ];
+        let annotations = annotations(&cases, None, root.path());
+
+        assert_eq!(annotations.len(), 3);
+        assert_eq!(annotations[0].file.as_deref(), Some("src/parser.rs"));
+        assert_eq!(annotations[0].line, Some(12));
+        assert_eq!(annotations[0].col, Some(5));
+        assert_eq!(annotations[1].file.as_deref(), Some("src/parser.rs"));
+        assert_eq!(annotations[1].line, Some(40));
+        assert_eq!(annotations[2].file, None);
+        assert_eq!(annotations[2].title, "Prodigy map: item_2");
+    }
+
+    #[test]
+    fn test_annotations_cap_and_run_error() {
+        let root = TempDir::new().unwrap();
+        let cases: Vec<TestCase> = (0..15)
+            .map(|i| failed(&format!("item_{}", i), ""))
+            .collect();
+        let capped = annotations(&cases, None, root.path());
+        assert_eq!(capped.len(), MAX_ANNOTATIONS);
+        assert_eq!(capped[9].level, AnnotationLevel::Warning);
+        assert!(capped[9].message.starts_with("6 more failures"));
+
+        let error = anyhow::anyhow!("Invalid workflow");
+        let only_error = annotations(&[], Some(&error), root.path());
+        assert_eq!(only_error.len(), 1);
+        assert_eq!(only_error[0].message, "Invalid workflow");
+    }
+
+    #[test]
+    fn test_step_summary() {
+        let cases = vec![
+            TestCase {
+                suite: "setup".to_string(),
+                name: "shell: make | tee log".to_string(),
+                duration: Duration::from_millis(1500),
+                outcome: CaseOutcome::Passed,
+            },
+            failed("item_0", "assertion failed"),
+        ];
+        let summary = step_summary("fix-debt", &cases, None);
+        assert!(summary.starts_with("## Prodigy: fix-debt ❌ failed"));
+        assert!(summary.contains("2 steps and items, 1 passed, 1 failed, 0 skipped"));
+        assert!(summary.contains("| setup | `shell: make \\| tee log` | ✅ passed | 1.5s |"));
+        assert!(summary.contains("<summary>map: item_0</summary>"));
+        assert!(summary.contains("assertion failed"));
+    }
+}
@@ -0,0 +1,479 @@
+//! GitHub issues and pull request review comments as map work items
+//!
+//! A map `input` starting with `github:` reads work items from the GitHub API
+//! instead of a file or command:
+//!
+//! ```yaml
+//! map:
+//!   input: "github:issues?label=tech-debt"
+//!   # or review comments on a pull request
+//!   # input: "github:pr-comments?pr=123"
+//! ```
+//!
+//! Issue queries accept `label` (repeatable or comma-separated), `state`
+//! (`open`, `closed` or `all`), `assignee` and `limit`; review comment queries
+//! require `pr`. Both accept `repo=owner/name`, which otherwise defaults to
+//! the repository of the workflow's working directory.
+//!
+//! Requests go through `gh api`, so `gh` must be installed and authenticated
+//! (`gh auth login`, or `GH_TOKEN`/`GITHUB_TOKEN` in CI). Each result is
+//! mapped to a flat item (see [`issue_item`] and [`review_comment_item`])
+//! whose `id` makes a stable `item_key`.
+
+use crate::subprocess::{ProcessCommandBuilder, SubprocessManager};
+use anyhow::{anyhow, bail, Context, Result};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Prefix that selects the GitHub input provider
+pub const GITHUB_INPUT_PREFIX: &str = "github:";
+
+/// Results requested per API page (the GitHub maximum)
+const PAGE_SIZE: usize = 100;
+
+/// Issues returned when the query sets no `limit`
+const DEFAULT_ISSUE_LIMIT: usize = 500;
+
+/// What a `github:` input reads
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GitHubQuery {
+    /// Issues, excluding pull requests
+    Issues {
+        labels: Vec<String>,
+        state: String,
+        assignee: Option<String>,
+        limit: usize,
+    },
+    /// Top-level review comments on a pull request
+    ReviewComments { pr: u64 },
+}
+
+/// A parsed `github:` map input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitHubInput {
+    /// Repository as `owner/name`; the working directory's repository if unset
+    pub repo: Option<String>,
+    pub query: GitHubQuery,
+}
+
+impl GitHubInput {
+    /// Whether a map input selects the GitHub provider (pure function)
+    pub fn is_github_input(input: &str) -> bool {
+        input.trim_start().starts_with(GITHUB_INPUT_PREFIX)
+    }
+
+    /// Parse `github:<kind>?key=value&...` (pure function)
+    pub fn parse(input: &str) -> Result<Self> {
+        let spec = input
+            .trim()
+            .strip_prefix(GITHUB_INPUT_PREFIX)
+            .ok_or_else(|| anyhow!("GitHub input must start with '{}'", GITHUB_INPUT_PREFIX))?;
+        let (kind, params) = spec.split_once('?').unwrap_or((spec, ""));
+
+        let mut repo = None;
+        let mut labels = Vec::new();
+        let mut state = "open".to_string();
+        let mut assignee = None;
+        let mut limit = None;
+        let mut pr = None;
+        for pair in params.split('&').filter(|pair| !pair.is_empty()) {
+            let (key, value) = pair
+                .split_once('=')
+                .ok_or_else(|| anyhow!("Expected key=value in GitHub input, got '{}'", pair))?;
+            match key {
+                "repo" => {
+                    if value.split('/').filter(|part| !part.is_empty()).count() != 2 {
+                        bail!("GitHub repo must be owner/name, got '{}'", value);
+                    }
+                    repo = Some(value.to_string());
+                }
+                "label" | "labels" => labels.extend(
+                    value
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|label| !label.is_empty())
+                        .map(str::to_string),
+                ),
+                "state" => match value {
+                    "open" | "closed" | "all" => state = value.to_string(),
+                    _ => bail!(
+                        "GitHub issue state must be open, closed or all, got '{}'",
+                        value
+                    ),
+                },
+                "assignee" => assignee = Some(value.to_string()),
+                "limit" => {
+                    limit = Some(
+                        value
+                            .parse::<usize>()
+                            .ok()
+                            .filter(|limit| *limit > 0)
+                            .ok_or_else(|| anyhow!("Invalid GitHub input limit '{}'", value))?,
+                    )
+                }
+                "pr" => {
+                    pr = Some(
+                        value
+                            .trim_start_matches('#')
+                            .parse::<u64>()
+                            .map_err(|_| anyhow!("Invalid pull request number '{}'", value))?,
+                    )
+                }
+                _ => bail!("Unknown GitHub input parameter '{}'", key),
+            }
+        }
+
+        let query = match kind {
+            "issues" => {
+                if pr.is_some() {
+                    bail!("'pr' only applies to github:pr-comments");
+                }
+                GitHubQuery::Issues {
+                    labels,
+                    state,
+                    assignee,
+                    limit: limit.unwrap_or(DEFAULT_ISSUE_LIMIT),
+                }
+            }
+            "pr-comments" => {
+                if !labels.is_empty() || assignee.is_some() || limit.is_some() {
+                    bail!("github:pr-comments only accepts 'pr' and 'repo'");
+                }
+                GitHubQuery::ReviewComments {
+                    pr: pr.ok_or_else(|| anyhow!("github:pr-comments requires pr=<number>"))?,
+                }
+            }
+            _ => bail!(
+                "Unknown GitHub input '{}', expected github:issues or github:pr-comments",
+                kind
+            ),
+        };
+        Ok(Self { repo, query })
+    }
+
+    /// API path of one result page (pure function)
+    ///
+    /// `{owner}/{repo}` is filled in by `gh` from the working directory.
+    pub fn page_path(&self, page: usize) -> String {
+        let repo = self.repo.as_deref().unwrap_or("{owner}/{repo}");
+        match &self.query {
+            GitHubQuery::Issues {
+                labels,
+                state,
+                assignee,
+                ..
+            } => {
+                let mut path = format!(
+                    "repos/{}/issues?state={}&per_page={}&page={}",
+                    repo, state, PAGE_SIZE, page
+                );
+                if !labels.is_empty() {
+                    path.push_str(&format!("&labels={}", encode_query(&labels.join(","))));
+                }
+                if let Some(assignee) = assignee {
+                    path.push_str(&format!("&assignee={}", encode_query(assignee)));
+                }
+                path
+            }
+            GitHubQuery::ReviewComments { pr } => format!(
+                "repos/{}/pulls/{}/comments?per_page={}&page={}",
+                repo, pr, PAGE_SIZE, page
+            ),
+        }
+    }
+
+    /// Map one API result to a work item, or `None` if it is not one (pure function)
+    pub fn to_item(&self, value: &Value) -> Option<Value> {
+        match &self.query {
+            GitHubQuery::Issues { .. } => issue_item(value),
+            GitHubQuery::ReviewComments { pr } => review_comment_item(*pr, value),
+        }
+    }
+
+    fn limit(&self) -> Option<usize> {
+        match &self.query {
+            GitHubQuery::Issues { limit, .. } => Some(*limit),
+            GitHubQuery::ReviewComments { .. } => None,
+        }
+    }
+}
+
+/// Percent-encode a query parameter value (pure function)
+fn encode_query(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b',' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+fn login(value: &Value) -> Value {
+    value.get("login").cloned().unwrap_or(Value::Null)
+}
+
+fn text(value: &Value, field: &str) -> Value {
+    json!(value.get(field).and_then(Value::as_str).unwrap_or(""))
+}
+
+/// Work item for an issue; pull requests listed by the issues API are skipped (pure function)
+pub fn issue_item(issue: &Value) -> Option<Value> {
+    if issue.get("pull_request").is_some() {
+        return None;
+    }
+    let number = issue.get("number")?.as_u64()?;
+    let names = |field: &str, key: &str| -> Vec<Value> {
+        issue
+            .get(field)
+            .and_then(Value::as_array)
+            .map(|values| values.iter().filter_map(|v| v.get(key).cloned()).collect())
+            .unwrap_or_default()
+    };
+    Some(json!({
+        "id": format!("issue-{}", number),
+        "number": number,
+        "title": text(issue, "title"),
+        "body": text(issue, "body"),
+        "labels": names("labels", "name"),
+        "assignees": names("assignees", "login"),
+        "state": text(issue, "state"),
+        "author": issue.get("user").map(login).unwrap_or(Value::Null),
+        "url": text(issue, "html_url"),
+        "created_at": text(issue, "created_at"),
+        "updated_at": text(issue, "updated_at"),
+    }))
+}
+
+/// Work item for a review comment; replies are folded into their thread (pure function)
+pub fn review_comment_item(pr: u64, comment: &Value) -> Option<Value> {
+    if comment
+        .get("in_reply_to_id")
+        .is_some_and(|id| !id.is_null())
+    {
+        return None;
+    }
+    let id = comment.get("id")?.as_u64()?;
+    let line = comment
+        .get("line")
+        .filter(|line| !line.is_null())
+        .or_else(|| comment.get("original_line"))
+        .cloned()
+        .unwrap_or(Value::Null);
+    Some(json!({
+        "id": format!("comment-{}", id),
+        "pr": pr,
+        "path": text(comment, "path"),
+        "line": line,
+        "body": text(comment, "body"),
+        "diff_hunk": text(comment, "diff_hunk"),
+        "commit_id": text(comment, "commit_id"),
+        "author": comment.get("user").map(login).unwrap_or(Value::Null),
+        "url": text(comment, "html_url"),
+    }))
+}
+
+/// Authenticated access to the GitHub REST API
+#[async_trait]
+pub trait GitHubApi: Send + Sync {
+    /// GET an API path such as `repos/owner/name/issues?page=1`
+    async fn get(&self, path: &str) -> Result<Value>;
+}
+
+/// GitHub API through `gh api`, which supplies the credentials
+pub struct GhApi {
+    subprocess: Arc<SubprocessManager>,
+    working_dir: PathBuf,
+}
+
+impl GhApi {
+    /// Create a client that resolves `{owner}/{repo}` from `working_dir`
+    pub fn new(subprocess: Arc<SubprocessManager>, working_dir: &Path) -> Self {
+        Self {
+            subprocess,
+            working_dir: working_dir.to_path_buf(),
+        }
+    }
+}
+
+#[async_trait]
+impl GitHubApi for GhApi {
+    async fn get(&self, path: &str) -> Result<Value> {
+        let command = ProcessCommandBuilder::new("gh")
+            .args(["api", "-H", "Accept: application/vnd.github+json", path])
+            .current_dir(&self.working_dir)
+            .build();
+        let output = self
+            .subprocess
+            .runner()
+            .run(command)
+            .await
+            .context("Failed to run gh")?;
+        if !output.status.success() {
+            bail!("gh api {} failed: {}", path, output.stderr.trim());
+        }
+        serde_json::from_str(output.stdout.trim())
+            .with_context(|| format!("Unexpected gh api {} output", path))
+    }
+}
+
+/// Fetch every page of a query and map the results to work items
+pub async fn fetch_items(api: &dyn GitHubApi, input: &GitHubInput) -> Result<Vec<Value>> {
+    let limit = input.limit();
+    let mut items = Vec::new();
+    for page in 1.. {
+        let path = input.page_path(page);
+        let results = match api.get(&path).await? {
+            Value::Array(results) => results,
+            other => bail!("Expected a JSON array from {}, got {}", path, other),
+        };
+        items.extend(results.iter().filter_map(|value| input.to_item(value)));
+
+        if let Some(limit) = limit.filter(|limit| items.len() >= *limit) {
+            items.truncate(limit);
+            break;
+        }
+        if results.len() < PAGE_SIZE {
+            break;
+        }
+    }
+    Ok(items)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    struct MockApi {
+        pages: Vec<Value>,
+        requested: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl GitHubApi for MockApi {
+        async fn get(&self, path: &str) -> Result<Value> {
+            let mut requested = self.requested.lock().unwrap();
+            requested.push(path.to_string());
+            Ok(self
+                .pages
+                .get(requested.len() - 1)
+                .cloned()
+                .unwrap_or_else(|| json!([])))
+        }
+    }
+
+    fn issue(number: u64) -> Value {
+        json!({
+            "number": number,
+            "title": format!("Issue {}", number),
+            "body": null,
+            "labels": [{"name": "tech-debt"}],
+            "assignees": [],
+            "state": "open",
+            "user": {"login": "octocat"},
+            "html_url": format!("https://github.com/o/r/issues/{}", number),
+        })
+    }
+
+    #[test]
+    fn test_parse_issues() {
+        let input =
+            GitHubInput::parse("github:issues?label=tech-debt,good first issue&repo=o/r&limit=5")
+                .unwrap();
+        assert_eq!(input.repo.as_deref(), Some("o/r"));
+        assert_eq!(
+            input.query,
+            GitHubQuery::Issues {
+                labels: vec!["tech-debt".to_string(), "good first issue".to_string()],
+                state: "open".to_string(),
+                assignee: None,
+                limit: 5,
+            }
+        );
+        assert_eq!(
+            input.page_path(2),
+            "repos/o/r/issues?state=open&per_page=100&page=2&labels=tech-debt,good%20first%20issue"
+        );
+    }
+
+    #[test]
+    fn test_parse_pr_comments() {
+        let input = GitHubInput::parse("github:pr-comments?pr=#42").unwrap();
+        assert_eq!(input.query, GitHubQuery::ReviewComments { pr: 42 });
+        assert_eq!(
+            input.page_path(1),
+            "repos/{owner}/{repo}/pulls/42/comments?per_page=100&page=1"
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_input() {
+        assert!(GitHubInput::parse("github:pulls").is_err());
+        assert!(GitHubInput::parse("github:pr-comments").is_err());
+        assert!(GitHubInput::parse("github:pr-comments?pr=1&label=x").is_err());
+        assert!(GitHubInput::parse("github:issues?state=stale").is_err());
+        assert!(GitHubInput::parse("github:issues?repo=just-a-name").is_err());
+        assert!(GitHubInput::parse("github:issues?limit=0").is_err());
+        assert!(GitHubInput::parse("github:issues?milestone=1").is_err());
+    }
+
+    #[test]
+    fn test_issue_item_mapping() {
+        let item = issue_item(&issue(7)).unwrap();
+        assert_eq!(item["id"], "issue-7");
+        assert_eq!(item["body"], "");
+        assert_eq!(item["labels"], json!(["tech-debt"]));
+        assert_eq!(item["author"], "octocat");
+
+        let mut pull = issue(8);
+        pull["pull_request"] = json!({});
+        assert_eq!(issue_item(&pull), None);
+    }
+
+    #[test]
+    fn test_review_comment_item_mapping() {
+        let comment = json!({
+            "id": 99,
+            "path": "src/lib.rs",
+            "line": null,
+            "original_line": 12,
+            "body": "Use a constant here",
+            "user": {"login": "reviewer"},
+        });
+        let item = review_comment_item(3, &comment).unwrap();
+        assert_eq!(item["id"], "comment-99");
+        assert_eq!(item["pr"], 3);
+        assert_eq!(item["path"], "src/lib.rs");
+        assert_eq!(item["line"], 12);
+
+        let reply = json!({"id": 100, "in_reply_to_id": 99});
+        assert_eq!(review_comment_item(3, &reply), None);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_items_paginates_up_to_limit() {
+        let first: Vec<Value> = (1..=100).map(issue).collect();
+        let second: Vec<Value> = (101..=150).map(issue).collect();
+        let api = MockApi {
+            pages: vec![Value::Array(first), Value::Array(second)],
+            requested: Mutex::new(Vec::new()),
+        };
+
+        let input = GitHubInput::parse("github:issues?label=tech-debt").unwrap();
+        let items = fetch_items(&api, &input).await.unwrap();
+        assert_eq!(items.len(), 150);
+        assert_eq!(api.requested.lock().unwrap().len(), 2);
+
+        api.requested.lock().unwrap().clear();
+        let input = GitHubInput::parse("github:issues?limit=3").unwrap();
+        let items = fetch_items(&api, &input).await.unwrap();
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[2]["number"], 3);
+        assert_eq!(api.requested.lock().unwrap().len(), 1);
+    }
+}
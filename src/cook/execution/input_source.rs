@@ -1,8 +1,9 @@
 //! Input source handling for MapReduce workflows
 //!
-//! Supports both command execution and JSON file input sources.
+//! Supports command execution, JSON file and GitHub (`github:`) input sources.
 
 use crate::cook::execution::errors::{MapReduceError, MapReduceResult};
+use crate::cook::execution::github_input::GitHubInput;
 use crate::subprocess::SubprocessManager;
 use serde_json::Value;
 use std::path::Path;
@@ -16,6 +17,8 @@ pub enum InputSource {
     Command(String),
     /// Path to JSON file
     JsonFile(String),
+    /// GitHub issues or review comments (see [`crate::cook::execution::github_input`])
+    GitHub(String),
 }
 
 impl InputSource {
@@ -29,9 +32,15 @@ impl InputSource {
 
     /// Detect the input source type from a string with a base path for resolution
     ///
-    /// If the input is a path to an existing .json file, it's treated as a JSON file.
-    /// Otherwise, it's treated as a command to execute.
+    /// Inputs starting with `github:` read from GitHub. If the input is a path
+    /// to an existing .json file, it's treated as a JSON file. Otherwise, it's
+    /// treated as a command to execute.
     pub fn detect_with_base(input: &str, base_path: &Path) -> Self {
+        if GitHubInput::is_github_input(input) {
+            debug!("Detected GitHub input: {}", input);
+            return InputSource::GitHub(input.to_string());
+        }
+
         let path = Path::new(input);
 
         // Resolve the path relative to the base if it's not absolute
@@ -91,43 +100,40 @@ impl InputSource {
             });
         }
 
-        // Try to parse the output as JSON first
-        let trimmed = output.stdout.trim();
+        let items = Self::parse_items(&output.stdout);
+        info!("Command produced {} work items", items.len());
+        Ok(items)
+    }
+
+    /// Work items from command output or piped text (pure function)
+    ///
+    /// A JSON array yields its elements and any other JSON document a single
+    /// item. Otherwise every non-empty line is an item: JSON lines are used
+    /// as-is and other lines become `{"item": line}`.
+    pub fn parse_items(text: &str) -> Vec<Value> {
+        let trimmed = text.trim();
 
         // Check if the entire output is valid JSON
         if let Ok(json_value) = serde_json::from_str::<Value>(trimmed) {
-            // If it's already an array, return it as-is
-            if let Value::Array(arr) = json_value {
-                info!("Command produced JSON array with {} items", arr.len());
-                return Ok(arr);
-            } else {
-                // If it's a single JSON object/value, wrap it in an array
-                info!("Command produced single JSON value");
-                return Ok(vec![json_value]);
-            }
+            return match json_value {
+                Value::Array(arr) => arr,
+                single => vec![single],
+            };
         }
 
         // Fall back to line-based parsing if not JSON
-        let items: Vec<Value> = output
-            .stdout
-            .lines()
+        text.lines()
             .filter(|line| !line.trim().is_empty())
             .map(|line| {
                 // Try to parse each line as JSON
-                if let Ok(json) = serde_json::from_str::<Value>(line.trim()) {
-                    json
-                } else {
+                serde_json::from_str::<Value>(line.trim()).unwrap_or_else(|_| {
                     // Each line becomes a work item with the line as the "item" field
                     serde_json::json!({
                         "item": line.trim()
                     })
-                }
+                })
             })
-            .collect();
-
-        info!("Command produced {} work items", items.len());
-
-        Ok(items)
+            .collect()
     }
 
     /// Load work items from a JSON file
@@ -175,6 +181,22 @@ mod tests {
     use std::fs;
     use tempfile::TempDir;
 
+    #[test]
+    fn test_parse_items() {
+        assert_eq!(
+            InputSource::parse_items("src/a.rs\n\n{\"id\": 2}\n"),
+            vec![
+                serde_json::json!({"item": "src/a.rs"}),
+                serde_json::json!({"id": 2})
+            ]
+        );
+        assert_eq!(
+            InputSource::parse_items("[{\"id\": 1}, {\"id\": 2}]"),
+            vec![serde_json::json!({"id": 1}), serde_json::json!({"id": 2})]
+        );
+        assert!(InputSource::parse_items("  \n").is_empty());
+    }
+
     #[test]
     fn test_detect_json_file() {
         let temp_dir = TempDir::new().unwrap();
@@ -206,6 +228,15 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_detect_github() {
+        let source = InputSource::detect("github:issues?label=tech-debt");
+        match source {
+            InputSource::GitHub(_) => {} // Expected
+            _ => panic!("Expected GitHub detection"),
+        }
+    }
+
     #[tokio::test]
     async fn test_execute_command() {
         use crate::subprocess::SubprocessManager;
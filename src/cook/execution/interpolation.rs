@@ -1,9 +1,14 @@
 //! Variable interpolation engine for MapReduce workflows
 //!
 //! Provides template parsing and variable resolution for dynamic command generation
-//! in MapReduce workflows. Supports nested property access, default values, and
-//! multiple variable contexts.
+//! in MapReduce workflows. Supports nested property access, default values,
+//! value filters and multiple variable contexts.
+//!
+//! Filters are chained after the variable path with `|`, for example
+//! `${map.results | filter 'status == "failed"' | json}`. Available filters are
+//! `filter '<expr>'`, `json`, `length` and `shell`.
 
+use crate::cook::execution::data_pipeline::FilterExpression;
 use anyhow::{anyhow, Result};
 use regex::Regex;
 use serde_json::Value;
@@ -76,6 +81,35 @@ impl InterpolationEngine {
         }
     }
 
+    /// Braced references in a template that are undefined and have no default
+    ///
+    /// Unbraced `$VAR` references are left out, since in shell commands they
+    /// usually name shell variables rather than workflow variables. Writing
+    /// `${var:-}` opts a reference out by giving it an empty default.
+    pub fn undefined_references(
+        &mut self,
+        template_str: &str,
+        context: &InterpolationContext,
+    ) -> Result<Vec<String>> {
+        let template = self.get_or_parse_template(template_str)?;
+        let mut undefined = Vec::new();
+        for segment in &template.segments {
+            if let Segment::Variable {
+                path,
+                default: None,
+                braced: true,
+                ..
+            } = segment
+            {
+                let name = path.join(".");
+                if context.resolve_path(path).is_err() && !undefined.contains(&name) {
+                    undefined.push(name);
+                }
+            }
+        }
+        Ok(undefined)
+    }
+
     /// Resolve all template segments with enhanced error reporting
     fn resolve_template_segments(
         &self,
@@ -89,9 +123,19 @@ impl InterpolationEngine {
         for segment in &template.segments {
             match segment {
                 Segment::Literal(text) => result.push_str(text),
-                Segment::Variable { path, default } => {
+                Segment::Variable {
+                    path,
+                    default,
+                    filters,
+                    ..
+                } => {
                     match self.resolve_variable_with_context(path, context, debug_mode) {
-                        Ok(value) => result.push_str(&value_to_string(&value)),
+                        Ok(value) => {
+                            let value = apply_filters(value, filters).map_err(|e| {
+                                anyhow!("Failed to filter variable '{}': {}", path.join("."), e)
+                            })?;
+                            result.push_str(&value_to_string(&value));
+                        }
                         Err(resolution_error) => {
                             if let Some(default_value) = default {
                                 if debug_mode {
@@ -238,12 +282,12 @@ impl InterpolationEngine {
                 .ok_or_else(|| anyhow!("Failed to get regex match group"))?;
 
             // Determine which capture group matched: ${...} or $VAR
-            let var_expr = if let Some(braced_match) = cap.get(1) {
+            let (var_expr, braced) = if let Some(braced_match) = cap.get(1) {
                 // ${variable} pattern
-                braced_match.as_str()
+                (braced_match.as_str(), true)
             } else if let Some(unbraced_match) = cap.get(2) {
                 // $VAR pattern
-                unbraced_match.as_str()
+                (unbraced_match.as_str(), false)
             } else {
                 continue; // Should never happen
             };
@@ -256,8 +300,13 @@ impl InterpolationEngine {
             }
 
             // Parse variable expression
-            let (path, default) = self.parse_variable_expression(var_expr)?;
-            segments.push(Segment::Variable { path, default });
+            let (path, default, filters) = self.parse_variable_expression(var_expr)?;
+            segments.push(Segment::Variable {
+                path,
+                default,
+                filters,
+                braced,
+            });
 
             last_end = full_match.end();
         }
@@ -273,8 +322,16 @@ impl InterpolationEngine {
         })
     }
 
-    /// Parse a variable expression like "item.name" or "timeout:-600"
-    fn parse_variable_expression(&self, expr: &str) -> Result<(Vec<String>, Option<String>)> {
+    /// Parse a variable expression like "item.name", "timeout:-600" or "items | json"
+    fn parse_variable_expression(
+        &self,
+        expr: &str,
+    ) -> Result<(Vec<String>, Option<String>, Vec<ValueFilter>)> {
+        let (expr, filters) = match split_filters(expr)? {
+            Some((head, filters)) => (head, filters),
+            None => (expr, Vec::new()),
+        };
+
         // Check for default value syntax (:-default)
         let (path_str, default) = if let Some(idx) = expr.find(":-") {
             let path = &expr[..idx];
@@ -285,8 +342,8 @@ impl InterpolationEngine {
         };
 
         // Parse path segments
-        let path = self.parse_path(path_str)?;
-        Ok((path, default))
+        let path = self.parse_path(path_str.trim_end())?;
+        Ok((path, default, filters))
     }
 
     /// Classify a character in the context of path parsing
@@ -370,9 +427,142 @@ pub enum Segment {
         path: Vec<String>,
         /// Default value if variable is undefined
         default: Option<String>,
+        /// Filters applied to the resolved value, in order
+        filters: Vec<ValueFilter>,
+        /// Written as `${...}` rather than `$VAR`
+        braced: bool,
     },
 }
 
+/// Transformation applied to a resolved variable, written `${var | name}`
+#[derive(Debug, Clone)]
+pub enum ValueFilter {
+    /// Keep the array elements matching a filter expression
+    Filter(FilterExpression),
+    /// Render the value as compact JSON
+    Json,
+    /// Number of array elements, object keys or string characters
+    Length,
+    /// Quote the rendered value as a single shell word
+    Shell,
+}
+
+impl ValueFilter {
+    /// Parse a filter such as `json` or `filter 'score > 5'`
+    ///
+    /// Returns `None` when `spec` does not name a known filter.
+    fn parse(spec: &str) -> Option<Result<Self>> {
+        let spec = spec.trim();
+        let (name, arg) = spec.split_once(char::is_whitespace).unwrap_or((spec, ""));
+        let arg = arg.trim();
+        let filter = match name {
+            "filter" => {
+                if !FilterExpression::is_quoted(arg) || arg.len() < 2 {
+                    return Some(Err(anyhow!(
+                        "filter expects a quoted expression, e.g. filter 'status == \"failed\"'"
+                    )));
+                }
+                FilterExpression::parse(&FilterExpression::unquote(arg)).map(Self::Filter)
+            }
+            "json" => Ok(Self::Json),
+            "length" => Ok(Self::Length),
+            "shell" => Ok(Self::Shell),
+            _ => return None,
+        };
+        if !matches!(name, "filter") && !arg.is_empty() {
+            return Some(Err(anyhow!("{} filter takes no arguments", name)));
+        }
+        Some(filter)
+    }
+
+    /// Apply the filter to a resolved value
+    fn apply(&self, value: Value) -> Result<Value> {
+        match self {
+            Self::Filter(expression) => match value {
+                Value::Array(items) => Ok(Value::Array(
+                    items
+                        .into_iter()
+                        .filter(|item| expression.evaluate(item))
+                        .collect(),
+                )),
+                other => Err(anyhow!(
+                    "filter requires an array, got {}",
+                    value_type_name(&other)
+                )),
+            },
+            Self::Json => Ok(Value::String(serde_json::to_string(&value)?)),
+            Self::Length => match &value {
+                Value::Array(items) => Ok(Value::from(items.len())),
+                Value::Object(fields) => Ok(Value::from(fields.len())),
+                Value::String(text) => Ok(Value::from(text.chars().count())),
+                Value::Null => Ok(Value::from(0)),
+                other => Err(anyhow!(
+                    "length is undefined for {}",
+                    value_type_name(other)
+                )),
+            },
+            Self::Shell => Ok(Value::String(shell_escape(&value_to_string(&value)))),
+        }
+    }
+}
+
+/// Split `path | filter | ...` into the path part and its filters
+///
+/// Returns `None` when the expression has no filters, including when text
+/// after a `|` is not a known filter, so defaults such as `${x:-a|b}` keep
+/// working.
+fn split_filters(expr: &str) -> Result<Option<(&str, Vec<ValueFilter>)>> {
+    let mut parts = Vec::new();
+    let mut quote = None;
+    let mut start = 0;
+    for (index, ch) in expr.char_indices() {
+        match (quote, ch) {
+            (None, '\'' | '"') => quote = Some(ch),
+            (Some(open), _) if open == ch => quote = None,
+            (None, '|') => {
+                parts.push(&expr[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&expr[start..]);
+
+    let Some((head, specs)) = parts.split_first() else {
+        return Ok(None);
+    };
+    if specs.is_empty() {
+        return Ok(None);
+    }
+    let mut filters = Vec::with_capacity(specs.len());
+    for spec in specs {
+        match ValueFilter::parse(spec) {
+            Some(filter) => filters.push(filter?),
+            None => return Ok(None),
+        }
+    }
+    Ok(Some((head, filters)))
+}
+
+/// Apply filters to a value in order
+fn apply_filters(value: Value, filters: &[ValueFilter]) -> Result<Value> {
+    filters
+        .iter()
+        .try_fold(value, |value, filter| filter.apply(value))
+}
+
+/// Name of a JSON value's type for error messages
+fn value_type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "an array",
+        Value::Object(_) => "an object",
+    }
+}
+
 /// Interpolation context containing variables
 #[derive(Debug, Clone, Default)]
 pub struct InterpolationContext {
@@ -739,6 +929,75 @@ Failed items: ${map.failed}""#;
         let result = engine.interpolate(template, &context).unwrap();
         assert_eq!(result, "Item: only item");
     }
+
+    /// Test filtering structured map results down to failed items
+    #[test]
+    fn test_filter_and_json_filters() {
+        let mut engine = InterpolationEngine::new(false);
+        let mut context = InterpolationContext::new();
+        context.set(
+            "map.results",
+            json!([
+                {"item_id": "a", "status": "success"},
+                {"item_id": "b", "status": "failed"},
+                {"item_id": "c", "status": "failed"}
+            ]),
+        );
+
+        let result = engine
+            .interpolate(
+                r#"${map.results | filter 'status == "failed"' | json}"#,
+                &context,
+            )
+            .unwrap();
+        assert_eq!(
+            result,
+            r#"[{"item_id":"b","status":"failed"},{"item_id":"c","status":"failed"}]"#
+        );
+
+        let count = engine
+            .interpolate(
+                r#"${map.results | filter 'status == "failed"' | length}"#,
+                &context,
+            )
+            .unwrap();
+        assert_eq!(count, "2");
+    }
+
+    /// Test shell filter quotes JSON containing single quotes
+    #[test]
+    fn test_shell_filter_quotes_value() {
+        let mut engine = InterpolationEngine::new(false);
+        let mut context = InterpolationContext::new();
+        context.set("results", json!([{"output": "it's done"}]));
+
+        let result = engine
+            .interpolate("echo ${results | json | shell}", &context)
+            .unwrap();
+        assert_eq!(result, r#"echo '[{"output":"it'\''s done"}]'"#);
+    }
+
+    /// Test filter errors and defaults containing pipes
+    #[test]
+    fn test_filter_edge_cases() {
+        let mut engine = InterpolationEngine::new(false);
+        let mut context = InterpolationContext::new();
+        context.set("name", json!("prodigy"));
+
+        let err = engine
+            .interpolate("${name | filter 'x == 1'}", &context)
+            .unwrap_err();
+        assert!(err.to_string().contains("Failed to filter variable 'name'"));
+
+        assert!(engine.interpolate("${name | filter x}", &context).is_err());
+        assert!(engine
+            .interpolate("${name | json extra}", &context)
+            .is_err());
+
+        // Unknown filters leave the expression to the default-value syntax
+        let result = engine.interpolate("${missing:-a|b}", &context).unwrap();
+        assert_eq!(result, "a|b");
+    }
 }
 
 #[cfg(test)]
@@ -821,6 +1080,21 @@ mod tests {
         assert_eq!(result, "Value: ${undefined}");
     }
 
+    #[test]
+    fn test_undefined_references() {
+        let mut engine = InterpolationEngine::new(false);
+        let mut context = InterpolationContext::new();
+        context.set("build", json!({"dir": "target"}));
+
+        let undefined = engine
+            .undefined_references(
+                "rm -rf ${build.dir}/${cache_dir} ${cache_dir} ${opt:-} $HOME ${build.missing}",
+                &context,
+            )
+            .unwrap();
+        assert_eq!(undefined, vec!["cache_dir", "build.missing"]);
+    }
+
     #[test]
     fn test_context_inheritance() {
         let mut engine = InterpolationEngine::new(false);
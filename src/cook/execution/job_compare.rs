@@ -0,0 +1,426 @@
+//! Comparison of two MapReduce job runs
+//!
+//! Iterating on agent prompts means running the same workflow over the same
+//! items again and again. These pure functions line up the items of two job
+//! checkpoints and report which items were added or removed, which changed
+//! outcome, which got much slower, and which modified different files,
+//! marking changes for the worse as regressions.
+
+use super::mapreduce::agent::AgentStatus;
+use super::mapreduce::item_key::{positional_item_id, ItemKeyExpr};
+use super::state::MapReduceJobState;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// An item must take this many times longer to count as slower
+pub const SLOWDOWN_FACTOR: f64 = 1.5;
+
+/// An item must also take this many seconds longer, so short items stay quiet
+pub const MIN_SLOWDOWN_SECS: f64 = 10.0;
+
+/// Final outcome of one item in a job
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ItemOutcome {
+    Succeeded,
+    Failed,
+    NotRun,
+}
+
+impl ItemOutcome {
+    /// Short human-readable label
+    pub fn label(&self) -> &'static str {
+        match self {
+            ItemOutcome::Succeeded => "succeeded",
+            ItemOutcome::Failed => "failed",
+            ItemOutcome::NotRun => "not run",
+        }
+    }
+}
+
+/// What one job recorded for an item
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ItemRun {
+    pub item_id: String,
+    pub outcome: ItemOutcome,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_secs: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub commits: usize,
+    pub files_modified: BTreeSet<String>,
+}
+
+/// How an item changed between the two runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ItemChange {
+    /// Succeeded before, failed or did not run after
+    Regressed,
+    /// Took much longer than before
+    Slower,
+    /// Failed or did not run before, succeeded after
+    Fixed,
+    /// Took much less time than before
+    Faster,
+    /// Modified a different set of files
+    ArtifactsChanged,
+    /// Only present in the second run
+    Added,
+    /// Only present in the first run
+    Removed,
+}
+
+impl ItemChange {
+    /// Whether the change is for the worse
+    pub fn is_regression(&self) -> bool {
+        matches!(self, ItemChange::Regressed | ItemChange::Slower)
+    }
+
+    /// Short human-readable label
+    pub fn label(&self) -> &'static str {
+        match self {
+            ItemChange::Regressed => "regressed",
+            ItemChange::Slower => "slower",
+            ItemChange::Fixed => "fixed",
+            ItemChange::Faster => "faster",
+            ItemChange::ArtifactsChanged => "artifacts changed",
+            ItemChange::Added => "added",
+            ItemChange::Removed => "removed",
+        }
+    }
+}
+
+/// An item that differs between the two runs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ItemComparison {
+    /// Key that matched the item across runs
+    pub key: String,
+    pub changes: Vec<ItemChange>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<ItemRun>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<ItemRun>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub files_added: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub files_removed: Vec<String>,
+}
+
+impl ItemComparison {
+    /// Whether any change for this item is a regression
+    pub fn is_regression(&self) -> bool {
+        self.changes.iter().any(ItemChange::is_regression)
+    }
+}
+
+/// Totals for one job
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JobSummary {
+    pub job_id: String,
+    pub total_items: usize,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub total_duration_secs: f64,
+}
+
+/// Differences between two runs of the same workflow
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JobComparison {
+    pub before: JobSummary,
+    pub after: JobSummary,
+    /// Items that changed, regressions first
+    pub items: Vec<ItemComparison>,
+    /// Number of items present in both runs with no changes
+    pub unchanged: usize,
+}
+
+impl JobComparison {
+    /// Number of items with a change for the worse
+    pub fn regressions(&self) -> usize {
+        self.items.iter().filter(|i| i.is_regression()).count()
+    }
+
+    /// Number of items with the given change
+    pub fn count(&self, change: ItemChange) -> usize {
+        self.items
+            .iter()
+            .filter(|i| i.changes.contains(&change))
+            .count()
+    }
+}
+
+/// Key identifying a work item across runs
+///
+/// Items are matched by `key` when given and it can be evaluated for the
+/// item, and by their full JSON otherwise.
+pub fn item_key(item: &Value, key: Option<&ItemKeyExpr>) -> String {
+    key.and_then(|key| key.evaluate(item).ok())
+        .unwrap_or_else(|| item.to_string())
+}
+
+/// Collect the recorded result of every work item in a job
+pub fn collect_item_runs(
+    state: &MapReduceJobState,
+    key: Option<&ItemKeyExpr>,
+) -> BTreeMap<String, ItemRun> {
+    state
+        .work_items
+        .iter()
+        .enumerate()
+        .map(|(index, item)| {
+            let key = item_key(item, key);
+            // Jobs run with an item_key record results under the key itself
+            let item_id = [positional_item_id(index), key.clone()]
+                .into_iter()
+                .find(|id| {
+                    state.agent_results.contains_key(id) || state.failed_agents.contains_key(id)
+                })
+                .unwrap_or_else(|| positional_item_id(index));
+            let run = match state.agent_results.get(&item_id) {
+                Some(result) => ItemRun {
+                    item_id: item_id.clone(),
+                    outcome: match &result.status {
+                        AgentStatus::Success => ItemOutcome::Succeeded,
+                        AgentStatus::Failed(_) | AgentStatus::Timeout => ItemOutcome::Failed,
+                        _ => ItemOutcome::NotRun,
+                    },
+                    duration_secs: Some(result.duration.as_secs_f64()),
+                    error: match &result.status {
+                        AgentStatus::Failed(e) => Some(e.clone()),
+                        AgentStatus::Timeout => Some("timed out".to_string()),
+                        _ => result.error.clone(),
+                    },
+                    commits: result.commits.len(),
+                    files_modified: result.files_modified.iter().cloned().collect(),
+                },
+                None => ItemRun {
+                    item_id: item_id.clone(),
+                    outcome: if state.failed_agents.contains_key(&item_id) {
+                        ItemOutcome::Failed
+                    } else {
+                        ItemOutcome::NotRun
+                    },
+                    duration_secs: None,
+                    error: state
+                        .failed_agents
+                        .get(&item_id)
+                        .map(|f| f.last_error.clone()),
+                    commits: 0,
+                    files_modified: BTreeSet::new(),
+                },
+            };
+            (key, run)
+        })
+        .collect()
+}
+
+/// Summarize a job's item runs
+fn summarize(job_id: &str, runs: &BTreeMap<String, ItemRun>) -> JobSummary {
+    JobSummary {
+        job_id: job_id.to_string(),
+        total_items: runs.len(),
+        succeeded: runs
+            .values()
+            .filter(|r| r.outcome == ItemOutcome::Succeeded)
+            .count(),
+        failed: runs
+            .values()
+            .filter(|r| r.outcome == ItemOutcome::Failed)
+            .count(),
+        total_duration_secs: runs.values().filter_map(|r| r.duration_secs).sum(),
+    }
+}
+
+/// Classify how an item present in both runs changed
+fn classify(before: &ItemRun, after: &ItemRun) -> Vec<ItemChange> {
+    let mut changes = Vec::new();
+    let succeeded = |run: &ItemRun| run.outcome == ItemOutcome::Succeeded;
+
+    if succeeded(before) && !succeeded(after) {
+        changes.push(ItemChange::Regressed);
+    } else if !succeeded(before) && succeeded(after) {
+        changes.push(ItemChange::Fixed);
+    }
+
+    if succeeded(before) && succeeded(after) {
+        if let (Some(b), Some(a)) = (before.duration_secs, after.duration_secs) {
+            if a > b * SLOWDOWN_FACTOR && a - b >= MIN_SLOWDOWN_SECS {
+                changes.push(ItemChange::Slower);
+            } else if b > a * SLOWDOWN_FACTOR && b - a >= MIN_SLOWDOWN_SECS {
+                changes.push(ItemChange::Faster);
+            }
+        }
+        if before.files_modified != after.files_modified {
+            changes.push(ItemChange::ArtifactsChanged);
+        }
+    }
+
+    changes
+}
+
+/// Compare the item runs of two jobs
+pub fn compare_runs(
+    before_id: &str,
+    before: &BTreeMap<String, ItemRun>,
+    after_id: &str,
+    after: &BTreeMap<String, ItemRun>,
+) -> JobComparison {
+    let keys: BTreeSet<&String> = before.keys().chain(after.keys()).collect();
+    let mut items = Vec::new();
+    let mut unchanged = 0;
+
+    for key in keys {
+        let (b, a) = (before.get(key), after.get(key));
+        let changes = match (b, a) {
+            (Some(b), Some(a)) => classify(b, a),
+            (Some(_), None) => vec![ItemChange::Removed],
+            (None, _) => vec![ItemChange::Added],
+        };
+        if changes.is_empty() {
+            unchanged += 1;
+            continue;
+        }
+
+        let files =
+            |run: Option<&ItemRun>| run.map(|r| r.files_modified.clone()).unwrap_or_default();
+        let (before_files, after_files) = (files(b), files(a));
+        let has_artifact_change = changes.contains(&ItemChange::ArtifactsChanged);
+
+        items.push(ItemComparison {
+            key: key.clone(),
+            changes,
+            before: b.cloned(),
+            after: a.cloned(),
+            files_added: if has_artifact_change {
+                after_files.difference(&before_files).cloned().collect()
+            } else {
+                Vec::new()
+            },
+            files_removed: if has_artifact_change {
+                before_files.difference(&after_files).cloned().collect()
+            } else {
+                Vec::new()
+            },
+        });
+    }
+
+    // Regressions first, then by most significant change, then by key
+    items.sort_by(|x, y| {
+        y.is_regression()
+            .cmp(&x.is_regression())
+            .then_with(|| x.changes.cmp(&y.changes))
+            .then_with(|| x.key.cmp(&y.key))
+    });
+
+    JobComparison {
+        before: summarize(before_id, before),
+        after: summarize(after_id, after),
+        items,
+        unchanged,
+    }
+}
+
+/// Compare two job checkpoints
+pub fn compare_jobs(
+    before: &MapReduceJobState,
+    after: &MapReduceJobState,
+    key: Option<&ItemKeyExpr>,
+) -> JobComparison {
+    compare_runs(
+        &before.job_id,
+        &collect_item_runs(before, key),
+        &after.job_id,
+        &collect_item_runs(after, key),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn run(outcome: ItemOutcome, duration_secs: f64, files: &[&str]) -> ItemRun {
+        ItemRun {
+            item_id: "item_0".to_string(),
+            outcome,
+            duration_secs: Some(duration_secs),
+            error: None,
+            commits: 1,
+            files_modified: files.iter().map(|f| f.to_string()).collect(),
+        }
+    }
+
+    fn runs(entries: Vec<(&str, ItemRun)>) -> BTreeMap<String, ItemRun> {
+        entries
+            .into_iter()
+            .map(|(key, run)| (key.to_string(), run))
+            .collect()
+    }
+
+    #[test]
+    fn test_item_key() {
+        let item = json!({"path": "src/lib.rs", "id": 7});
+        let path = ItemKeyExpr::parse("$.path").unwrap();
+        let missing = ItemKeyExpr::parse("$.missing").unwrap();
+        assert_eq!(item_key(&item, Some(&path)), "src/lib.rs");
+        assert_eq!(item_key(&item, Some(&missing)), item.to_string());
+        assert_eq!(item_key(&json!("plain"), None), "\"plain\"");
+    }
+
+    #[test]
+    fn test_compare_runs_classifies_changes() {
+        use ItemOutcome::*;
+        let before = runs(vec![
+            ("a", run(Succeeded, 10.0, &["a.rs"])),
+            ("b", run(Failed, 10.0, &[])),
+            ("c", run(Succeeded, 10.0, &["c.rs"])),
+            ("d", run(Succeeded, 10.0, &["d.rs"])),
+            ("e", run(Succeeded, 10.0, &["e.rs"])),
+            ("gone", run(Succeeded, 10.0, &[])),
+        ]);
+        let after = runs(vec![
+            ("a", run(Failed, 10.0, &[])),
+            ("b", run(Succeeded, 10.0, &[])),
+            ("c", run(Succeeded, 40.0, &["c.rs"])),
+            ("d", run(Succeeded, 12.0, &["d.rs", "d_test.rs"])),
+            ("e", run(Succeeded, 11.0, &["e.rs"])),
+            ("new", run(Succeeded, 10.0, &[])),
+        ]);
+
+        let comparison = compare_runs("job-a", &before, "job-b", &after);
+        let changes: BTreeMap<&str, &[ItemChange]> = comparison
+            .items
+            .iter()
+            .map(|i| (i.key.as_str(), i.changes.as_slice()))
+            .collect();
+
+        assert_eq!(changes["a"], [ItemChange::Regressed]);
+        assert_eq!(changes["b"], [ItemChange::Fixed]);
+        assert_eq!(changes["c"], [ItemChange::Slower]);
+        assert_eq!(changes["d"], [ItemChange::ArtifactsChanged]);
+        assert_eq!(changes["gone"], [ItemChange::Removed]);
+        assert_eq!(changes["new"], [ItemChange::Added]);
+        assert_eq!(comparison.unchanged, 1);
+        assert_eq!(comparison.regressions(), 2);
+        assert!(comparison.items[0].is_regression());
+        assert!(comparison.items[1].is_regression());
+
+        let d = comparison.items.iter().find(|i| i.key == "d").unwrap();
+        assert_eq!(d.files_added, vec!["d_test.rs".to_string()]);
+        assert!(d.files_removed.is_empty());
+
+        assert_eq!(comparison.before.succeeded, 5);
+        assert_eq!(comparison.after.failed, 1);
+    }
+
+    #[test]
+    fn test_short_items_are_not_slower() {
+        let before = runs(vec![("a", run(ItemOutcome::Succeeded, 2.0, &[]))]);
+        let after = runs(vec![("a", run(ItemOutcome::Succeeded, 8.0, &[]))]);
+        let comparison = compare_runs("job-a", &before, "job-b", &after);
+        assert!(comparison.items.is_empty());
+        assert_eq!(comparison.unchanged, 1);
+    }
+}
@@ -0,0 +1,693 @@
+//! Shareable reports for completed MapReduce jobs
+//!
+//! After a large automated cleanup, the job checkpoint and event log hold
+//! everything needed to explain what happened. These pure functions combine
+//! them into a [`JobReport`] with summary statistics, a per-item table,
+//! clustered failures, token usage with an estimated cost, and a timeline, and
+//! render it as a self-contained HTML page that can be attached to a PR.
+
+use super::dlq_clustering::normalize_error_message;
+use super::events::{EventRecord, MapReduceEvent};
+use super::job_compare::{collect_item_runs, ItemOutcome};
+use super::state::MapReduceJobState;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
+
+/// Timeline rows drawn before the chart is truncated
+const MAX_TIMELINE_ROWS: usize = 500;
+
+/// Prices used to estimate the cost of a job, in USD per million tokens
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CostRates {
+    pub input_per_mtok: f64,
+    pub output_per_mtok: f64,
+    pub cache_per_mtok: f64,
+}
+
+impl Default for CostRates {
+    fn default() -> Self {
+        Self {
+            input_per_mtok: 3.0,
+            output_per_mtok: 15.0,
+            cache_per_mtok: 0.3,
+        }
+    }
+}
+
+/// Tokens reported by Claude for a job or item
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenTotals {
+    pub input: u64,
+    pub output: u64,
+    pub cache: u64,
+}
+
+impl TokenTotals {
+    fn add(&mut self, input: u64, output: u64, cache: u64) {
+        self.input += input;
+        self.output += output;
+        self.cache += cache;
+    }
+
+    /// Estimated cost in USD
+    pub fn cost(&self, rates: &CostRates) -> f64 {
+        (self.input as f64 * rates.input_per_mtok
+            + self.output as f64 * rates.output_per_mtok
+            + self.cache as f64 * rates.cache_per_mtok)
+            / 1_000_000.0
+    }
+}
+
+/// One work item in the report
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ItemReport {
+    pub item_id: String,
+    pub outcome: ItemOutcome,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_secs: Option<f64>,
+    pub attempts: u32,
+    pub commits: Vec<String>,
+    pub files_modified: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub tokens: TokenTotals,
+    /// Seconds from job start until the item's first attempt started
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub started_offset_secs: Option<f64>,
+    /// Seconds from job start until the item's last attempt ended
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finished_offset_secs: Option<f64>,
+}
+
+/// Failed items sharing a normalized error message
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FailureGroup {
+    pub message: String,
+    pub items: Vec<String>,
+}
+
+/// Everything shown in a job report
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JobReport {
+    pub job_id: String,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    pub duration_secs: f64,
+    pub is_complete: bool,
+    pub total_items: usize,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub not_run: usize,
+    pub total_commits: usize,
+    pub tokens: TokenTotals,
+    pub rates: CostRates,
+    pub estimated_cost_usd: f64,
+    pub items: Vec<ItemReport>,
+    pub failure_groups: Vec<FailureGroup>,
+}
+
+impl JobReport {
+    /// Share of items that succeeded, as a percentage
+    pub fn success_rate(&self) -> f64 {
+        if self.total_items == 0 {
+            0.0
+        } else {
+            self.succeeded as f64 * 100.0 / self.total_items as f64
+        }
+    }
+
+    /// Mean duration of items that ran
+    pub fn mean_item_secs(&self) -> Option<f64> {
+        let durations: Vec<f64> = self.items.iter().filter_map(|i| i.duration_secs).collect();
+        (!durations.is_empty()).then(|| durations.iter().sum::<f64>() / durations.len() as f64)
+    }
+}
+
+/// Per-item data gathered from the event log
+#[derive(Default)]
+struct ItemEvents {
+    started: Option<DateTime<Utc>>,
+    finished: Option<DateTime<Utc>>,
+    attempts: u32,
+    tokens: TokenTotals,
+}
+
+/// Build a report from a job checkpoint and its events (pure function)
+pub fn build_job_report(
+    state: &MapReduceJobState,
+    events: &[EventRecord],
+    rates: &CostRates,
+) -> JobReport {
+    let mut agent_items: HashMap<&str, &str> = HashMap::new();
+    let mut per_item: HashMap<String, ItemEvents> = HashMap::new();
+    let mut tokens = TokenTotals::default();
+    let mut job_finished = None;
+
+    for record in events {
+        match &record.event {
+            MapReduceEvent::AgentStarted {
+                agent_id,
+                item_id,
+                attempt,
+                ..
+            } => {
+                agent_items.insert(agent_id, item_id);
+                let entry = per_item.entry(item_id.clone()).or_default();
+                entry.started = entry.started.or(Some(record.timestamp));
+                entry.attempts = entry.attempts.max(*attempt);
+            }
+            MapReduceEvent::AgentCompleted { agent_id, .. }
+            | MapReduceEvent::AgentFailed { agent_id, .. } => {
+                if let Some(item_id) = agent_items.get(agent_id.as_str()) {
+                    per_item.entry(item_id.to_string()).or_default().finished =
+                        Some(record.timestamp);
+                }
+            }
+            MapReduceEvent::ClaudeTokenUsage {
+                agent_id,
+                input_tokens,
+                output_tokens,
+                cache_tokens,
+            } => {
+                tokens.add(*input_tokens, *output_tokens, *cache_tokens);
+                if let Some(item_id) = agent_items.get(agent_id.as_str()) {
+                    per_item.entry(item_id.to_string()).or_default().tokens.add(
+                        *input_tokens,
+                        *output_tokens,
+                        *cache_tokens,
+                    );
+                }
+            }
+            MapReduceEvent::JobCompleted { .. } | MapReduceEvent::JobFailed { .. } => {
+                job_finished = Some(record.timestamp);
+            }
+            _ => {}
+        }
+    }
+
+    let started_at = state.started_at;
+    let offset = |time: Option<DateTime<Utc>>| {
+        time.map(|t| (t - started_at).num_milliseconds().max(0) as f64 / 1000.0)
+    };
+
+    let mut items: Vec<ItemReport> = collect_item_runs(state, None)
+        .into_values()
+        .map(|run| {
+            let item_events = per_item.remove(&run.item_id).unwrap_or_default();
+            let result = state.agent_results.get(&run.item_id);
+            let attempts = state
+                .failed_agents
+                .get(&run.item_id)
+                .map(|f| f.attempts)
+                .unwrap_or(0)
+                .max(item_events.attempts)
+                .max(u32::from(run.outcome != ItemOutcome::NotRun));
+            ItemReport {
+                outcome: run.outcome,
+                duration_secs: run.duration_secs,
+                attempts,
+                commits: result.map(|r| r.commits.clone()).unwrap_or_default(),
+                files_modified: run.files_modified.len(),
+                error: run.error,
+                tokens: item_events.tokens,
+                started_offset_secs: offset(item_events.started),
+                finished_offset_secs: offset(item_events.finished),
+                item_id: run.item_id,
+            }
+        })
+        .collect();
+    items.sort_by(|a, b| {
+        let start = |i: &ItemReport| i.started_offset_secs.unwrap_or(f64::MAX);
+        start(a)
+            .total_cmp(&start(b))
+            .then_with(|| a.item_id.cmp(&b.item_id))
+    });
+
+    let finished_at = job_finished
+        .or_else(|| events.iter().map(|r| r.timestamp).max())
+        .unwrap_or(state.updated_at)
+        .max(started_at);
+    let count = |outcome: ItemOutcome| items.iter().filter(|i| i.outcome == outcome).count();
+
+    JobReport {
+        job_id: state.job_id.clone(),
+        started_at,
+        finished_at,
+        duration_secs: (finished_at - started_at).num_milliseconds() as f64 / 1000.0,
+        is_complete: state.is_complete,
+        total_items: items.len(),
+        succeeded: count(ItemOutcome::Succeeded),
+        failed: count(ItemOutcome::Failed),
+        not_run: count(ItemOutcome::NotRun),
+        total_commits: items.iter().map(|i| i.commits.len()).sum(),
+        estimated_cost_usd: tokens.cost(rates),
+        tokens,
+        rates: *rates,
+        failure_groups: group_failures(&items),
+        items,
+    }
+}
+
+/// Group failed items by normalized error, largest group first
+fn group_failures(items: &[ItemReport]) -> Vec<FailureGroup> {
+    let mut groups: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for item in items.iter().filter(|i| i.outcome == ItemOutcome::Failed) {
+        let first_line = item
+            .error
+            .as_deref()
+            .and_then(|e| e.lines().find(|l| !l.trim().is_empty()))
+            .unwrap_or("unknown error");
+        groups
+            .entry(normalize_error_message(first_line))
+            .or_default()
+            .push(item.item_id.clone());
+    }
+    let mut groups: Vec<FailureGroup> = groups
+        .into_iter()
+        .map(|(message, items)| FailureGroup { message, items })
+        .collect();
+    groups.sort_by(|a, b| b.items.len().cmp(&a.items.len()));
+    groups
+}
+
+/// Web URL for commits of a git remote, if it is hosted on a known forge
+///
+/// Handles `git@host:owner/repo.git` and `https://host/owner/repo(.git)`.
+pub fn commit_url_base(remote_url: &str) -> Option<String> {
+    let remote = remote_url.trim().trim_end_matches('/');
+    let remote = remote.strip_suffix(".git").unwrap_or(remote);
+    let (host, path) = if let Some(rest) = remote.strip_prefix("git@") {
+        rest.split_once(':')?
+    } else {
+        let rest = remote
+            .strip_prefix("https://")
+            .or_else(|| remote.strip_prefix("http://"))
+            .or_else(|| remote.strip_prefix("ssh://git@"))?;
+        let rest = rest.rsplit_once('@').map(|(_, r)| r).unwrap_or(rest);
+        rest.split_once('/')?
+    };
+    if !matches!(host, "github.com" | "gitlab.com" | "bitbucket.org") || path.is_empty() {
+        return None;
+    }
+    let commit_path = if host == "bitbucket.org" {
+        "commits"
+    } else {
+        "commit"
+    };
+    Some(format!("https://{}/{}/{}", host, path, commit_path))
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn format_secs(secs: f64) -> String {
+    if secs >= 3600.0 {
+        format!(
+            "{}h {}m",
+            (secs / 3600.0) as u64,
+            ((secs % 3600.0) / 60.0) as u64
+        )
+    } else if secs >= 60.0 {
+        format!("{}m {}s", (secs / 60.0) as u64, (secs % 60.0) as u64)
+    } else {
+        format!("{:.1}s", secs)
+    }
+}
+
+fn format_tokens(count: u64) -> String {
+    if count >= 1_000_000 {
+        format!("{:.2}M", count as f64 / 1_000_000.0)
+    } else if count >= 1_000 {
+        format!("{:.1}k", count as f64 / 1_000.0)
+    } else {
+        count.to_string()
+    }
+}
+
+const STYLE: &str = "body{font-family:-apple-system,Segoe UI,Helvetica,Arial,sans-serif;margin:2em auto;max-width:1100px;color:#1f2328;padding:0 1em}\
+h1{font-size:1.6em}h2{font-size:1.2em;margin-top:2em;border-bottom:1px solid #d0d7de;padding-bottom:.3em}\
+.cards{display:flex;flex-wrap:wrap;gap:12px}.card{border:1px solid #d0d7de;border-radius:6px;padding:10px 16px;min-width:120px}\
+.card .value{font-size:1.4em;font-weight:600}.card .label{color:#59636e;font-size:.85em}\
+table{border-collapse:collapse;width:100%;font-size:.9em}th,td{border-bottom:1px solid #d0d7de;padding:6px 8px;text-align:left;vertical-align:top}\
+th{background:#f6f8fa}code{font-size:.9em}.succeeded{color:#1a7f37}.failed{color:#cf222e}.not_run{color:#59636e}\
+.error{color:#59636e;font-size:.85em;white-space:pre-wrap}footer{margin-top:3em;color:#59636e;font-size:.8em}";
+
+/// Render a report as a self-contained HTML page
+///
+/// Commits link to `{commit_url}/{sha}` when a commit URL base is given.
+pub fn render_html(report: &JobReport, commit_url: Option<&str>) -> String {
+    let mut html = String::new();
+    let title = format!("Prodigy report: {}", escape_html(&report.job_id));
+    let _ = write!(
+        html,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>{}</style>\n</head>\n<body>\n<h1>{}</h1>\n",
+        title, STYLE, title
+    );
+    let _ = writeln!(
+        html,
+        "<p>Started {} &middot; {} &middot; {}</p>",
+        report.started_at.format("%Y-%m-%d %H:%M:%S UTC"),
+        format_secs(report.duration_secs),
+        if report.is_complete {
+            "complete"
+        } else {
+            "incomplete"
+        }
+    );
+
+    html.push_str("<div class=\"cards\">\n");
+    let mean = report
+        .mean_item_secs()
+        .map(format_secs)
+        .unwrap_or_else(|| "-".to_string());
+    let cards = [
+        ("Items", report.total_items.to_string()),
+        ("Succeeded", report.succeeded.to_string()),
+        ("Failed", report.failed.to_string()),
+        ("Not run", report.not_run.to_string()),
+        ("Success rate", format!("{:.1}%", report.success_rate())),
+        ("Commits", report.total_commits.to_string()),
+        ("Mean item time", mean),
+        ("Est. cost", format!("${:.2}", report.estimated_cost_usd)),
+    ];
+    for (label, value) in cards {
+        let _ = writeln!(
+            html,
+            "<div class=\"card\"><div class=\"value\">{}</div><div class=\"label\">{}</div></div>",
+            escape_html(&value),
+            label
+        );
+    }
+    html.push_str("</div>\n");
+
+    html.push_str("<h2>Cost</h2>\n");
+    let _ = writeln!(
+        html,
+        "<p>{} input, {} output and {} cache tokens. Estimated at ${}/${}/${} per million tokens: <strong>${:.2}</strong>.</p>",
+        format_tokens(report.tokens.input),
+        format_tokens(report.tokens.output),
+        format_tokens(report.tokens.cache),
+        report.rates.input_per_mtok,
+        report.rates.output_per_mtok,
+        report.rates.cache_per_mtok,
+        report.estimated_cost_usd
+    );
+
+    html.push_str("<h2>Timeline</h2>\n");
+    html.push_str(&render_timeline(report));
+
+    if !report.failure_groups.is_empty() {
+        html.push_str("<h2>Failure clusters</h2>\n<table>\n<tr><th>Items</th><th>Error</th><th>Affected items</th></tr>\n");
+        for group in &report.failure_groups {
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td><code>{}</code></td><td>{}</td></tr>",
+                group.items.len(),
+                escape_html(&group.message),
+                escape_html(&group.items.join(", "))
+            );
+        }
+        html.push_str("</table>\n");
+    }
+
+    html.push_str("<h2>Items</h2>\n<table>\n<tr><th>Item</th><th>Status</th><th>Duration</th><th>Attempts</th><th>Tokens</th><th>Commits</th></tr>\n");
+    for item in &report.items {
+        let commits: Vec<String> = item
+            .commits
+            .iter()
+            .map(|sha| {
+                let short = escape_html(&sha.chars().take(8).collect::<String>());
+                match commit_url {
+                    Some(base) => format!(
+                        "<a href=\"{}/{}\"><code>{}</code></a>",
+                        escape_html(base),
+                        escape_html(sha),
+                        short
+                    ),
+                    None => format!("<code>{}</code>", short),
+                }
+            })
+            .collect();
+        let error = item
+            .error
+            .as_deref()
+            .map(|e| format!("<div class=\"error\">{}</div>", escape_html(e)))
+            .unwrap_or_default();
+        let outcome = serde_json::to_value(item.outcome)
+            .ok()
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_default();
+        let _ = writeln!(
+            html,
+            "<tr><td><code>{}</code>{}</td><td class=\"{}\">{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            escape_html(&item.item_id),
+            error,
+            outcome,
+            item.outcome.label(),
+            item.duration_secs
+                .map(format_secs)
+                .unwrap_or_else(|| "-".to_string()),
+            item.attempts,
+            format_tokens(item.tokens.input + item.tokens.output),
+            commits.join(" ")
+        );
+    }
+    html.push_str("</table>\n");
+
+    let _ = writeln!(
+        html,
+        "<footer>Generated by prodigy {} on {}</footer>\n</body>\n</html>",
+        env!("CARGO_PKG_VERSION"),
+        Utc::now().format("%Y-%m-%d %H:%M:%S UTC")
+    );
+    html
+}
+
+/// Inline SVG with one bar per item, from first start to last finish
+fn render_timeline(report: &JobReport) -> String {
+    let rows: Vec<&ItemReport> = report
+        .items
+        .iter()
+        .filter(|i| i.started_offset_secs.is_some())
+        .take(MAX_TIMELINE_ROWS)
+        .collect();
+    if rows.is_empty() {
+        return "<p>No agent events were recorded for this job.</p>\n".to_string();
+    }
+
+    const WIDTH: f64 = 1000.0;
+    const LABEL: f64 = 200.0;
+    const ROW: f64 = 16.0;
+    let span = report.duration_secs.max(
+        rows.iter()
+            .filter_map(|i| i.finished_offset_secs.or(i.started_offset_secs))
+            .fold(1.0, f64::max),
+    );
+    let scale = (WIDTH - LABEL - 10.0) / span;
+    let height = rows.len() as f64 * ROW + 20.0;
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {} {}\" width=\"100%\" font-size=\"11\">\n",
+        WIDTH, height
+    );
+    for (index, item) in rows.iter().enumerate() {
+        let y = index as f64 * ROW;
+        let start = item.started_offset_secs.unwrap_or(0.0);
+        let end = item.finished_offset_secs.unwrap_or(span).max(start);
+        let color = match item.outcome {
+            ItemOutcome::Succeeded => "#2da44e",
+            ItemOutcome::Failed => "#cf222e",
+            ItemOutcome::NotRun => "#8c959f",
+        };
+        let label: String = item.item_id.chars().take(32).collect();
+        let _ = writeln!(
+            svg,
+            "<text x=\"0\" y=\"{:.1}\">{}</text><rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" fill=\"{}\"><title>{} ({})</title></rect>",
+            y + ROW - 4.0,
+            escape_html(&label),
+            LABEL + start * scale,
+            y + 2.0,
+            ((end - start) * scale).max(1.0),
+            ROW - 4.0,
+            color,
+            escape_html(&item.item_id),
+            format_secs(end - start)
+        );
+    }
+    let _ = writeln!(
+        svg,
+        "<text x=\"{}\" y=\"{:.1}\">0s</text><text x=\"{}\" y=\"{:.1}\" text-anchor=\"end\">{}</text>\n</svg>",
+        LABEL,
+        height - 4.0,
+        WIDTH - 10.0,
+        height - 4.0,
+        format_secs(span)
+    );
+    if report.items.len() > rows.len() && rows.len() == MAX_TIMELINE_ROWS {
+        let _ = writeln!(svg, "<p>Showing the first {} items.</p>", MAX_TIMELINE_ROWS);
+    }
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cook::execution::mapreduce::agent::AgentResult;
+    use crate::cook::execution::mapreduce::MapReduceConfig;
+    use crate::cook::execution::state::FailureRecord;
+    use serde_json::json;
+    use std::time::Duration;
+
+    fn record(offset_secs: i64, event: MapReduceEvent, start: DateTime<Utc>) -> EventRecord {
+        EventRecord {
+            id: uuid::Uuid::new_v4(),
+            timestamp: start + chrono::Duration::seconds(offset_secs),
+            correlation_id: "c".to_string(),
+            event,
+            metadata: HashMap::new(),
+        }
+    }
+
+    fn started(agent: &str, item: &str) -> MapReduceEvent {
+        MapReduceEvent::AgentStarted {
+            job_id: "job-1".to_string(),
+            agent_id: agent.to_string(),
+            item_id: item.to_string(),
+            worktree: "wt".to_string(),
+            attempt: 1,
+        }
+    }
+
+    fn sample_state() -> MapReduceJobState {
+        let items = vec![
+            json!({"file": "a.rs"}),
+            json!({"file": "b.rs"}),
+            json!({"file": "c.rs"}),
+        ];
+        let mut state =
+            MapReduceJobState::new("job-1".to_string(), MapReduceConfig::default(), items);
+        let mut success = AgentResult::success("item_0".to_string(), None, Duration::from_secs(30));
+        success.commits = vec!["0123456789abcdef".to_string()];
+        state.agent_results.insert("item_0".to_string(), success);
+        state.agent_results.insert(
+            "item_1".to_string(),
+            AgentResult::failed(
+                "item_1".to_string(),
+                "Test failed at src/b.rs:42".to_string(),
+                Duration::from_secs(10),
+            ),
+        );
+        state.failed_agents.insert(
+            "item_1".to_string(),
+            FailureRecord {
+                item_id: "item_1".to_string(),
+                attempts: 2,
+                last_error: "Test failed at src/b.rs:42".to_string(),
+                last_attempt: Utc::now(),
+                worktree_info: None,
+            },
+        );
+        state
+    }
+
+    #[test]
+    fn test_build_job_report() {
+        let state = sample_state();
+        let start = state.started_at;
+        let events = vec![
+            record(1, started("agent-0", "item_0"), start),
+            record(2, started("agent-1", "item_1"), start),
+            record(
+                20,
+                MapReduceEvent::ClaudeTokenUsage {
+                    agent_id: "agent-0".to_string(),
+                    input_tokens: 1_000_000,
+                    output_tokens: 100_000,
+                    cache_tokens: 0,
+                },
+                start,
+            ),
+            record(
+                31,
+                MapReduceEvent::AgentCompleted {
+                    job_id: "job-1".to_string(),
+                    agent_id: "agent-0".to_string(),
+                    duration: chrono::Duration::seconds(30),
+                    commits: vec![],
+                    json_log_location: None,
+                },
+                start,
+            ),
+            record(
+                60,
+                MapReduceEvent::JobCompleted {
+                    job_id: "job-1".to_string(),
+                    duration: chrono::Duration::seconds(60),
+                    success_count: 1,
+                    failure_count: 1,
+                },
+                start,
+            ),
+        ];
+
+        let report = build_job_report(&state, &events, &CostRates::default());
+        assert_eq!(report.total_items, 3);
+        assert_eq!((report.succeeded, report.failed, report.not_run), (1, 1, 1));
+        assert_eq!(report.total_commits, 1);
+        assert_eq!(report.duration_secs, 60.0);
+        // 1M input at $3 plus 100k output at $15
+        assert!((report.estimated_cost_usd - 4.5).abs() < 1e-9);
+
+        let first = &report.items[0];
+        assert_eq!(first.item_id, "item_0");
+        assert_eq!(first.started_offset_secs, Some(1.0));
+        assert_eq!(first.finished_offset_secs, Some(31.0));
+        assert_eq!(first.tokens.input, 1_000_000);
+        assert_eq!(report.items[1].attempts, 2);
+        assert_eq!(report.items[2].attempts, 0);
+
+        assert_eq!(report.failure_groups.len(), 1);
+        assert_eq!(report.failure_groups[0].message, "Test failed at <path>");
+    }
+
+    #[test]
+    fn test_render_html_is_self_contained() {
+        let mut state = sample_state();
+        state.job_id = "job-<1>".to_string();
+        let report = build_job_report(&state, &[], &CostRates::default());
+        let html = render_html(&report, Some("https://github.com/o/r/commit"));
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("job-&lt;1&gt;"));
+        assert!(!html.contains("job-<1>"));
+        assert!(html.contains("https://github.com/o/r/commit/0123456789abcdef"));
+        assert!(html.contains("Failure clusters"));
+        assert!(html.contains("No agent events were recorded"));
+        assert!(!html.contains("<script"));
+        assert!(!html.contains("<link"));
+    }
+
+    #[test]
+    fn test_commit_url_base() {
+        assert_eq!(
+            commit_url_base("git@github.com:iepathos/prodigy.git").as_deref(),
+            Some("https://github.com/iepathos/prodigy/commit")
+        );
+        assert_eq!(
+            commit_url_base("https://token@github.com/iepathos/prodigy").as_deref(),
+            Some("https://github.com/iepathos/prodigy/commit")
+        );
+        assert_eq!(
+            commit_url_base("https://bitbucket.org/team/repo.git").as_deref(),
+            Some("https://bitbucket.org/team/repo/commits")
+        );
+        assert_eq!(commit_url_base("/srv/git/repo.git"), None);
+        assert_eq!(commit_url_base("https://git.internal/repo"), None);
+    }
+}
@@ -47,6 +47,7 @@ mod json_log_location_tests {
             worktree_session_id: Some("session-123".to_string()),
             json_log_location: log_location.clone(),
             cleanup_status: None,
+            no_changes: None,
         };
 
         assert_eq!(agent_result.json_log_location, log_location);
@@ -69,6 +70,7 @@ mod json_log_location_tests {
             worktree_session_id: Some("session-123".to_string()),
             json_log_location: None,
             cleanup_status: None,
+            no_changes: None,
         };
 
         assert!(agent_result.json_log_location.is_none());
@@ -0,0 +1,374 @@
+//! JUnit XML reports of workflow runs
+//!
+//! `prodigy run --junit-output results.xml` records every workflow step, and
+//! every MapReduce setup step, work item and reduce step, as a JUnit test case
+//! so CI systems such as Jenkins and GitLab can show a run in their test report
+//! views. Each phase becomes a test suite.
+//!
+//! A run creates one [`JunitRecorder`] and hands it to the workflow executor
+//! and, through the map phase, to the MapReduce coordinator. Executors without
+//! a recorder record nothing.
+
+use crate::cook::execution::mapreduce::{AgentResult, AgentStatus};
+use crate::cook::workflow::StepResult;
+use anyhow::{Context, Result};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Longest captured output kept for a failure, in characters
+const MAX_DETAIL_CHARS: usize = 8 * 1024;
+
+/// Result of one test case
+#[derive(Debug, Clone, PartialEq)]
+pub enum CaseOutcome {
+    Passed,
+    Failed { message: String, details: String },
+    Skipped { message: String },
+}
+
+/// A step or work item recorded as a test case
+#[derive(Debug, Clone, PartialEq)]
+pub struct TestCase {
+    /// Suite the case belongs to, such as the workflow name or `map`
+    pub suite: String,
+    pub name: String,
+    pub duration: Duration,
+    pub outcome: CaseOutcome,
+}
+
+impl TestCase {
+    /// Test case for an executed step
+    pub fn from_step(
+        suite: impl Into<String>,
+        name: impl Into<String>,
+        duration: Duration,
+        result: &StepResult,
+    ) -> Self {
+        let outcome = if result.success {
+            CaseOutcome::Passed
+        } else {
+            let message = match result.exit_code {
+                Some(code) => format!("Step failed with exit code {}", code),
+                None => "Step failed".to_string(),
+            };
+            let details = if result.stderr.trim().is_empty() {
+                &result.stdout
+            } else {
+                &result.stderr
+            };
+            CaseOutcome::Failed {
+                message,
+                details: details.clone(),
+            }
+        };
+        Self {
+            suite: suite.into(),
+            name: name.into(),
+            duration,
+            outcome,
+        }
+    }
+
+    /// Test case for a MapReduce work item
+    pub fn from_agent_result(result: &AgentResult) -> Self {
+        let details = || {
+            result
+                .error
+                .clone()
+                .or_else(|| result.output.clone())
+                .unwrap_or_default()
+        };
+        let outcome = match &result.status {
+            AgentStatus::Success => CaseOutcome::Passed,
+            AgentStatus::Failed(message) => CaseOutcome::Failed {
+                message: message.clone(),
+                details: details(),
+            },
+            AgentStatus::Timeout => CaseOutcome::Failed {
+                message: "Agent timed out".to_string(),
+                details: details(),
+            },
+            status => CaseOutcome::Skipped {
+                message: format!("Agent did not finish ({:?})", status),
+            },
+        };
+        Self {
+            suite: "map".to_string(),
+            name: result.item_id.clone(),
+            duration: result.duration,
+            outcome,
+        }
+    }
+
+    /// Test case for a step that returned an error instead of a result
+    pub fn from_error(
+        suite: impl Into<String>,
+        name: impl Into<String>,
+        duration: Duration,
+        error: &impl std::fmt::Display,
+    ) -> Self {
+        Self {
+            suite: suite.into(),
+            name: name.into(),
+            duration,
+            outcome: CaseOutcome::Failed {
+                message: error.to_string().lines().next().unwrap_or("").to_string(),
+                details: format!("{:#}", error),
+            },
+        }
+    }
+}
+
+/// Collects test cases during a run
+#[derive(Debug, Default)]
+pub struct JunitRecorder {
+    cases: Mutex<Vec<TestCase>>,
+}
+
+impl JunitRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&self, case: TestCase) {
+        match self.cases.lock() {
+            Ok(mut cases) => cases.push(case),
+            Err(poisoned) => poisoned.into_inner().push(case),
+        }
+    }
+
+    /// Cases recorded so far, in recording order
+    pub fn cases(&self) -> Vec<TestCase> {
+        match self.cases.lock() {
+            Ok(cases) => cases.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+
+    /// Write the recorded cases to `path` as JUnit XML
+    pub fn write(&self, path: &Path, name: &str) -> Result<()> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        std::fs::write(path, render_junit_xml(name, &self.cases()))
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+/// Record a case with `recorder`, if JUnit output is enabled
+pub fn record(recorder: Option<&JunitRecorder>, case: TestCase) {
+    if let Some(recorder) = recorder {
+        recorder.record(case);
+    }
+}
+
+/// Render test cases as a JUnit XML document, one suite per distinct suite name
+pub fn render_junit_xml(name: &str, cases: &[TestCase]) -> String {
+    let mut suites: Vec<(&str, Vec<&TestCase>)> = Vec::new();
+    for case in cases {
+        match suites.iter_mut().find(|(suite, _)| *suite == case.suite) {
+            Some((_, members)) => members.push(case),
+            None => suites.push((&case.suite, vec![case])),
+        }
+    }
+
+    let failures = |cases: &[&TestCase]| {
+        cases
+            .iter()
+            .filter(|c| matches!(c.outcome, CaseOutcome::Failed { .. }))
+            .count()
+    };
+    let skipped = |cases: &[&TestCase]| {
+        cases
+            .iter()
+            .filter(|c| matches!(c.outcome, CaseOutcome::Skipped { .. }))
+            .count()
+    };
+    let total_time =
+        |cases: &[&TestCase]| -> f64 { cases.iter().map(|c| c.duration.as_secs_f64()).sum() };
+
+    let all: Vec<&TestCase> = cases.iter().collect();
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!(
+        "<testsuites name=\"{}\" tests=\"{}\" failures=\"{}\" skipped=\"{}\" time=\"{:.3}\">\n",
+        escape(name),
+        all.len(),
+        failures(&all),
+        skipped(&all),
+        total_time(&all)
+    ));
+
+    for (suite, members) in &suites {
+        xml.push_str(&format!(
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" skipped=\"{}\" time=\"{:.3}\">\n",
+            escape(suite),
+            members.len(),
+            failures(members),
+            skipped(members),
+            total_time(members)
+        ));
+        for case in members {
+            let open = format!(
+                "    <testcase name=\"{}\" classname=\"{}\" time=\"{:.3}\"",
+                escape(&case.name),
+                escape(&format!("{}.{}", name, suite)),
+                case.duration.as_secs_f64()
+            );
+            match &case.outcome {
+                CaseOutcome::Passed => xml.push_str(&format!("{}/>\n", open)),
+                CaseOutcome::Failed { message, details } => xml.push_str(&format!(
+                    "{}>\n      <failure message=\"{}\">{}</failure>\n    </testcase>\n",
+                    open,
+                    escape(first_line(message)),
+                    escape(&truncate_tail(details))
+                )),
+                CaseOutcome::Skipped { message } => xml.push_str(&format!(
+                    "{}>\n      <skipped message=\"{}\"/>\n    </testcase>\n",
+                    open,
+                    escape(message)
+                )),
+            }
+        }
+        xml.push_str("  </testsuite>\n");
+    }
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+fn first_line(text: &str) -> &str {
+    text.lines().next().unwrap_or("")
+}
+
+/// Keep the end of long output, where errors usually are
+fn truncate_tail(text: &str) -> String {
+    let count = text.chars().count();
+    if count <= MAX_DETAIL_CHARS {
+        return text.to_string();
+    }
+    let tail: String = text.chars().skip(count - MAX_DETAIL_CHARS).collect();
+    format!(
+        "[... {} characters truncated]\n{}",
+        count - MAX_DETAIL_CHARS,
+        tail
+    )
+}
+
+/// Escape text for XML attributes and content, dropping invalid control characters
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\n' | '\r' | '\t' => escaped.push(c),
+            c if c.is_control() => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(success: bool, stderr: &str) -> StepResult {
+        StepResult {
+            success,
+            exit_code: Some(if success { 0 } else { 2 }),
+            stderr: stderr.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_render_groups_cases_into_suites() {
+        let cases = vec![
+            TestCase::from_step(
+                "setup",
+                "shell: make",
+                Duration::from_secs(2),
+                &step(true, ""),
+            ),
+            TestCase::from_step(
+                "map",
+                "item_0",
+                Duration::from_millis(1500),
+                &step(false, "error: <bad> & \"worse\"\u{1b}[0m"),
+            ),
+            TestCase::from_step("map", "item_1", Duration::from_secs(1), &step(true, "")),
+            TestCase {
+                suite: "map".to_string(),
+                name: "item_2".to_string(),
+                duration: Duration::ZERO,
+                outcome: CaseOutcome::Skipped {
+                    message: "already applied".to_string(),
+                },
+            },
+        ];
+        let xml = render_junit_xml("fix-debt", &cases);
+
+        assert!(xml.contains(
+            "<testsuites name=\"fix-debt\" tests=\"4\" failures=\"1\" skipped=\"1\" time=\"4.500\">"
+        ));
+        assert!(xml.contains(
+            "<testsuite name=\"setup\" tests=\"1\" failures=\"0\" skipped=\"0\" time=\"2.000\">"
+        ));
+        assert!(xml.contains("<testsuite name=\"map\" tests=\"3\" failures=\"1\" skipped=\"1\""));
+        assert!(xml.contains(
+            "<testcase name=\"shell: make\" classname=\"fix-debt.setup\" time=\"2.000\"/>"
+        ));
+        assert!(xml.contains("<failure message=\"Step failed with exit code 2\">error: &lt;bad&gt; &amp; &quot;worse&quot;[0m</failure>"));
+        assert!(xml.contains("<skipped message=\"already applied\"/>"));
+        assert!(xml.find("name=\"setup\"").unwrap() < xml.find("name=\"map\"").unwrap());
+    }
+
+    #[test]
+    fn test_failure_details_keep_the_tail() {
+        let long = format!("{}END", "x".repeat(MAX_DETAIL_CHARS + 10));
+        let truncated = truncate_tail(&long);
+        assert!(truncated.starts_with("[... 13 characters truncated]"));
+        assert!(truncated.ends_with("END"));
+
+        let case = TestCase::from_error(
+            "steps",
+            "claude: /fix",
+            Duration::ZERO,
+            &anyhow::anyhow!("timed out\nafter 60s"),
+        );
+        let xml = render_junit_xml("wf", &[case]);
+        assert!(xml.contains("<failure message=\"timed out\">"));
+    }
+
+    #[test]
+    fn test_recorder_collects_cases() {
+        let recorder = JunitRecorder::new();
+        recorder.record(TestCase::from_step(
+            "s",
+            "a",
+            Duration::ZERO,
+            &step(true, ""),
+        ));
+        recorder.record(TestCase::from_step(
+            "s",
+            "b",
+            Duration::ZERO,
+            &step(false, ""),
+        ));
+        let cases = recorder.cases();
+        assert_eq!(cases.len(), 2);
+        assert_eq!(cases[1].name, "b");
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("reports").join("results.xml");
+        recorder.write(&path, "wf").unwrap();
+        assert!(std::fs::read_to_string(path)
+            .unwrap()
+            .contains("failures=\"1\""));
+    }
+}
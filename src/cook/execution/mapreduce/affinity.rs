@@ -0,0 +1,177 @@
+//! Agent affinity rules for map phase work items
+//!
+//! Two agents editing the same file at once produce a merge conflict when
+//! their worktrees are merged back. `affinity` lists expressions over work
+//! item fields; items with the same value for an expression never run at the
+//! same time:
+//!
+//! ```yaml
+//! map:
+//!   input: debt.json
+//!   json_path: "$.items[*]"
+//!   affinity:
+//!     - "$.location.file"
+//! ```
+//!
+//! Expressions use the `item_key` syntax. Items without a value for an
+//! expression are not constrained by it. A `batch_size` batch belongs to the
+//! groups of all its items. An agent waiting for another item of its group
+//! does not hold a `max_parallel` slot.
+
+use super::batching::batch_contents;
+use super::item_key::ItemKeyExpr;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::sync::{Mutex as AsyncMutex, OwnedMutexGuard};
+
+/// Parse `map.affinity` expressions, rejecting ones without a field reference
+pub fn parse_rules(rules: &[String]) -> Result<Vec<ItemKeyExpr>, String> {
+    rules
+        .iter()
+        .map(|rule| {
+            let expr = ItemKeyExpr::parse(rule)?;
+            if !expr.has_fields() {
+                return Err(format!(
+                    "affinity rule '{}' must reference an item field, e.g. $.location.file",
+                    rule
+                ));
+            }
+            Ok(expr)
+        })
+        .collect()
+}
+
+/// Affinity groups of a work item, as (rule index, value), sorted (pure function)
+pub fn item_groups(rules: &[ItemKeyExpr], item: &Value) -> Vec<Group> {
+    let items = match batch_contents(item) {
+        Some(items) => items.iter().collect(),
+        None => vec![item],
+    };
+    let mut groups: Vec<Group> = items
+        .into_iter()
+        .flat_map(|item| {
+            rules
+                .iter()
+                .enumerate()
+                .filter_map(move |(index, rule)| rule.evaluate(item).ok().map(|v| (index, v)))
+        })
+        .collect();
+    groups.sort();
+    groups.dedup();
+    groups
+}
+
+/// Held affinity groups; released when dropped
+pub type AffinityGuard = Vec<OwnedMutexGuard<()>>;
+
+/// An affinity group: (rule index, value)
+type Group = (usize, String);
+
+/// Serializes items that share an affinity group
+#[derive(Debug)]
+pub struct AffinityLocks {
+    rules: Vec<ItemKeyExpr>,
+    locks: Mutex<HashMap<Group, Arc<AsyncMutex<()>>>>,
+}
+
+impl AffinityLocks {
+    pub fn new(rules: Vec<ItemKeyExpr>) -> Self {
+        Self {
+            rules,
+            locks: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Wait until no other item in any of `item`'s groups is running
+    ///
+    /// Groups are always taken in sorted order, so items in several groups
+    /// cannot deadlock.
+    pub async fn acquire(&self, item: &Value) -> AffinityGuard {
+        let mut guard = Vec::new();
+        for group in item_groups(&self.rules, item) {
+            let lock = {
+                let mut locks = match self.locks.lock() {
+                    Ok(locks) => locks,
+                    Err(poisoned) => poisoned.into_inner(),
+                };
+                locks.entry(group).or_default().clone()
+            };
+            guard.push(lock.lock_owned().await);
+        }
+        guard
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[test]
+    fn test_parse_rules_requires_fields() {
+        assert!(parse_rules(&["$.location.file".to_string()]).is_ok());
+        let err = parse_rules(&["location.file".to_string()]).unwrap_err();
+        assert!(err.contains("must reference an item field"));
+    }
+
+    #[test]
+    fn test_item_groups_skip_missing_values() {
+        let rules = parse_rules(&["$.location.file".to_string(), "$.module".to_string()]).unwrap();
+        let item = json!({"location": {"file": "src/lib.rs"}});
+        assert_eq!(
+            item_groups(&rules, &item),
+            vec![(0, "src/lib.rs".to_string())]
+        );
+        assert!(item_groups(&rules, &json!({"other": 1})).is_empty());
+
+        let batch = json!({
+            "batch": {"index": 0, "size": 3},
+            "items": [{"module": "b"}, {"module": "a"}, {"module": "b"}],
+        });
+        assert_eq!(
+            item_groups(&rules, &batch),
+            vec![(1, "a".to_string()), (1, "b".to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_items_in_the_same_group_run_one_at_a_time() {
+        let locks = Arc::new(AffinityLocks::new(
+            parse_rules(&["$.file".to_string()]).unwrap(),
+        ));
+        let running = Arc::new(AtomicUsize::new(0));
+        let overlapped = Arc::new(AtomicBool::new(false));
+
+        let tasks: Vec<_> = (0..4)
+            .map(|_| {
+                let locks = locks.clone();
+                let running = running.clone();
+                let overlapped = overlapped.clone();
+                tokio::spawn(async move {
+                    let _guard = locks.acquire(&json!({"file": "a.rs"})).await;
+                    if running.fetch_add(1, Ordering::SeqCst) > 0 {
+                        overlapped.store(true, Ordering::SeqCst);
+                    }
+                    tokio::time::sleep(Duration::from_millis(5)).await;
+                    running.fetch_sub(1, Ordering::SeqCst);
+                })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap();
+        }
+        assert!(!overlapped.load(Ordering::SeqCst));
+
+        // Items in different groups do not wait for each other
+        let _a = locks.acquire(&json!({"file": "a.rs"})).await;
+        let b = tokio::time::timeout(
+            Duration::from_millis(100),
+            locks.acquire(&json!({"file": "b.rs"})),
+        )
+        .await;
+        assert!(b.is_ok());
+    }
+}
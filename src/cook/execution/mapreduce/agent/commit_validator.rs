@@ -214,6 +214,16 @@ mod tests {
             }
         }
 
+        async fn git_command_with_index(
+            &self,
+            args: &[&str],
+            desc: &str,
+            dir: &Path,
+            _index_file: &Path,
+        ) -> Result<Output> {
+            self.git_command_in_dir(args, desc, dir).await
+        }
+
         async fn get_last_commit_message(&self) -> Result<String> {
             Ok("test commit".to_string())
         }
@@ -22,6 +22,8 @@ pub enum LifecycleError {
     BranchCreation(String),
     #[error("Failed to merge branch: {0}")]
     MergeError(String),
+    #[error("Rebase conflict: {0}")]
+    RebaseConflict(String),
     #[error("Git operation failed: {0}")]
     GitError(String),
     #[error("Cleanup failed: {0}")]
@@ -49,6 +51,9 @@ pub trait AgentLifecycleManager: Send + Sync {
         branch_name: &str,
     ) -> LifecycleResult<()>;
 
+    /// Rebase an agent's commits onto another branch, aborting on conflict
+    async fn rebase_agent_branch(&self, worktree_path: &Path, onto: &str) -> LifecycleResult<()>;
+
     /// Merge an agent's changes back to the parent
     async fn merge_agent_to_parent(
         &self,
@@ -138,6 +143,35 @@ impl AgentLifecycleManager for DefaultLifecycleManager {
         Ok(())
     }
 
+    async fn rebase_agent_branch(&self, worktree_path: &Path, onto: &str) -> LifecycleResult<()> {
+        use tokio::process::Command;
+
+        let output = Command::new("git")
+            .args(["rebase", onto])
+            .current_dir(worktree_path)
+            .output()
+            .await
+            .map_err(|e| LifecycleError::GitError(e.to_string()))?;
+
+        if !output.status.success() {
+            // Leave the worktree as it was before the rebase
+            let _ = Command::new("git")
+                .args(["rebase", "--abort"])
+                .current_dir(worktree_path)
+                .output()
+                .await;
+
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(LifecycleError::RebaseConflict(format!(
+                "Failed to rebase onto {}: {}",
+                onto,
+                stderr.trim()
+            )));
+        }
+
+        Ok(())
+    }
+
     async fn merge_agent_to_parent(
         &self,
         agent_branch: &str,
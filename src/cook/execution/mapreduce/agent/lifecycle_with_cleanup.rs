@@ -114,6 +114,12 @@ impl AgentLifecycleManager for CleanupAwareLifecycleManager {
             .await
     }
 
+    async fn rebase_agent_branch(&self, worktree_path: &Path, onto: &str) -> LifecycleResult<()> {
+        self.base_manager
+            .rebase_agent_branch(worktree_path, onto)
+            .await
+    }
+
     async fn merge_agent_to_parent(
         &self,
         agent_branch: &str,
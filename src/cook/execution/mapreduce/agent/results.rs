@@ -267,6 +267,7 @@ impl AgentResultAggregator for DefaultResultAggregator {
             worktree_session_id: Some(worktree_session_id),
             json_log_location: None,
             cleanup_status: None,
+            no_changes: None,
         })
     }
 
@@ -170,6 +170,7 @@ pub fn state_to_result(state: &AgentLifecycleState) -> Option<AgentResult> {
             worktree_session_id: None,
             json_log_location: None,
             cleanup_status: None,
+            no_changes: None,
         }),
 
         AgentLifecycleState::Failed {
@@ -190,6 +191,7 @@ pub fn state_to_result(state: &AgentLifecycleState) -> Option<AgentResult> {
             worktree_session_id: None,
             json_log_location: json_log_location.clone(),
             cleanup_status: None,
+            no_changes: None,
         }),
 
         // Non-terminal states
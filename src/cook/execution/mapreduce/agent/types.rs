@@ -4,6 +4,7 @@
 //! managing agent lifecycle, execution, and results in the MapReduce framework.
 
 use crate::cook::workflow::WorkflowStep;
+use crate::worktree::build_cache::BuildCacheMount;
 use crate::worktree::WorktreeSession;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -76,6 +77,9 @@ pub struct AgentResult {
     /// Status of cleanup operations
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cleanup_status: Option<CleanupStatus>,
+    /// Set when a `commit_required` step reported that no changes were needed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub no_changes: Option<crate::cook::no_changes::NoChanges>,
 }
 
 impl AgentResult {
@@ -94,6 +98,7 @@ impl AgentResult {
             worktree_session_id: None,
             json_log_location: None,
             cleanup_status: None,
+            no_changes: None,
         }
     }
 
@@ -112,6 +117,7 @@ impl AgentResult {
             worktree_session_id: None,
             json_log_location: None,
             cleanup_status: None,
+            no_changes: None,
         }
     }
 
@@ -179,6 +185,8 @@ pub struct AgentHandle {
     pub state: Arc<RwLock<AgentState>>,
     /// Commands to be executed by the agent
     pub commands: Vec<WorkflowStep>,
+    /// Build caches mounted into the worktree, released when the handle drops
+    pub build_cache: Option<BuildCacheMount>,
 }
 
 impl AgentHandle {
@@ -193,6 +201,7 @@ impl AgentHandle {
             worktree_session,
             state: Arc::new(RwLock::new(AgentState::default())),
             commands,
+            build_cache: None,
         }
     }
 
@@ -91,6 +91,7 @@ impl AgentCommandExecutor {
             worktree_session_id: None,
             json_log_location: None,
             cleanup_status: None,
+            no_changes: None,
         };
 
         // Execute commands
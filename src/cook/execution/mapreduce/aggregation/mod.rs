@@ -22,6 +22,9 @@ pub struct AggregationSummary {
     pub successful: usize,
     /// Number of failed operations
     pub failed: usize,
+    /// Successful operations that reported no changes were needed
+    #[serde(default)]
+    pub no_changes: usize,
     /// Total number of operations
     pub total: usize,
     /// Average duration in seconds
@@ -43,6 +46,11 @@ impl AggregationSummary {
             .filter(|r| matches!(r.status, AgentStatus::Failed(_) | AgentStatus::Timeout))
             .count();
 
+        let no_changes = results
+            .iter()
+            .filter(|r| matches!(r.status, AgentStatus::Success) && r.no_changes.is_some())
+            .count();
+
         let total_duration: f64 = results.iter().map(|r| r.duration.as_secs_f64()).sum();
 
         let avg_duration = if results.is_empty() {
@@ -54,6 +62,7 @@ impl AggregationSummary {
         Self {
             successful,
             failed,
+            no_changes,
             total: results.len(),
             avg_duration_secs: avg_duration,
             total_duration_secs: total_duration,
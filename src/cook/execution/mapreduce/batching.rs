@@ -0,0 +1,80 @@
+//! Batching of small map phase work items
+//!
+//! Each work item normally gets its own agent, worktree and Claude session.
+//! For thousands of tiny items, such as lint findings, that startup cost
+//! dominates. `batch_size` groups consecutive items into one work item:
+//!
+//! ```yaml
+//! map:
+//!   input: findings.json
+//!   json_path: "$.findings[*]"
+//!   batch_size: 20
+//!   agent_template:
+//!     - claude: "/fix-lints '${items}'"
+//! ```
+//!
+//! Batching happens after filtering, sorting, deduplication and `max_items`,
+//! so those still apply to single items. A batch is the work item
+//! `{"batch": {"index": 0, "size": 20}, "items": [...]}`, and agents see its
+//! items as the JSON array `${items}`. Results, retries and the DLQ are per
+//! batch.
+
+use serde_json::{json, Value};
+
+/// Group `items` into batches of up to `batch_size` (pure function)
+///
+/// A batch size of 0 or 1 leaves the items unchanged.
+pub fn batch_items(items: Vec<Value>, batch_size: usize) -> Vec<Value> {
+    if batch_size <= 1 {
+        return items;
+    }
+    items
+        .chunks(batch_size)
+        .enumerate()
+        .map(|(index, chunk)| {
+            json!({
+                "batch": {"index": index, "size": chunk.len()},
+                "items": chunk,
+            })
+        })
+        .collect()
+}
+
+/// The items of a batch work item, or `None` for a single item (pure function)
+pub fn batch_contents(item: &Value) -> Option<&Vec<Value>> {
+    let batch = item.as_object()?;
+    if !batch.get("batch")?.is_object() {
+        return None;
+    }
+    batch.get("items")?.as_array()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_batch_items() {
+        let items: Vec<Value> = (0..5).map(|n| json!({"id": n})).collect();
+
+        let batches = batch_items(items.clone(), 2);
+        assert_eq!(batches.len(), 3);
+        assert_eq!(
+            batches[0],
+            json!({"batch": {"index": 0, "size": 2}, "items": [{"id": 0}, {"id": 1}]})
+        );
+        assert_eq!(batches[2]["batch"]["size"], json!(1));
+        assert_eq!(
+            batch_contents(&batches[2]).unwrap(),
+            &vec![json!({"id": 4})]
+        );
+
+        assert_eq!(batch_items(items.clone(), 1), items);
+        assert_eq!(batch_contents(&items[0]), None);
+    }
+
+    #[test]
+    fn test_items_field_alone_is_not_a_batch() {
+        assert_eq!(batch_contents(&json!({"items": [1, 2]})), None);
+    }
+}
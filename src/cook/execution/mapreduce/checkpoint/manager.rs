@@ -683,6 +683,7 @@ mod tests {
                     worktree_session_id: None,
                     json_log_location: None,
                     cleanup_status: None,
+                    no_changes: None,
                 },
                 completed_at: Utc::now(),
             })
@@ -1090,6 +1091,7 @@ mod tests {
                         files_modified: vec![],
                         json_log_location: None,
                         cleanup_status: None,
+                        no_changes: None,
                     },
                     completed_at: Utc::now(),
                 }],
@@ -258,6 +258,7 @@ mod tests {
             files_modified: vec![],
             json_log_location: None,
             cleanup_status: None,
+            no_changes: None,
         }
     }
 
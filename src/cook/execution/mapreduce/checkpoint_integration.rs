@@ -654,6 +654,7 @@ fn create_success_result(item: &WorkItem) -> AgentResult {
         files_modified: vec![],
         json_log_location: None,
         cleanup_status: None,
+        no_changes: None,
     }
 }
 
@@ -929,6 +930,7 @@ fn process_work_batch(batch: Vec<WorkItem>, base_duration_secs: u64) -> Vec<Agen
             files_modified: vec![],
             json_log_location: None,
             cleanup_status: None,
+            no_changes: None,
         })
         .collect()
 }
@@ -1694,6 +1696,7 @@ mod tests {
                 files_modified: vec![],
                 json_log_location: None,
                 cleanup_status: None,
+                no_changes: None,
             },
             AgentResult {
                 item_id: "item_2".to_string(),
@@ -1710,6 +1713,7 @@ mod tests {
                 files_modified: vec![],
                 json_log_location: None,
                 cleanup_status: None,
+                no_changes: None,
             },
             AgentResult {
                 item_id: "item_3".to_string(),
@@ -1724,6 +1728,7 @@ mod tests {
                 files_modified: vec![],
                 json_log_location: None,
                 cleanup_status: None,
+                no_changes: None,
             },
         ];
 
@@ -1755,6 +1760,7 @@ mod tests {
                 files_modified: vec![],
                 json_log_location: None,
                 cleanup_status: None,
+                no_changes: None,
             },
             AgentResult {
                 item_id: "item_2".to_string(),
@@ -1771,6 +1777,7 @@ mod tests {
                 files_modified: vec![],
                 json_log_location: None,
                 cleanup_status: None,
+                no_changes: None,
             },
             AgentResult {
                 item_id: "item_3".to_string(),
@@ -1785,6 +1792,7 @@ mod tests {
                 files_modified: vec![],
                 json_log_location: None,
                 cleanup_status: None,
+                no_changes: None,
             },
         ];
 
@@ -1808,6 +1816,7 @@ mod tests {
             files_modified: vec![],
             json_log_location: None,
             cleanup_status: None,
+            no_changes: None,
         }];
 
         let no_change = update_checkpoint_progress(10, &failed_results);
@@ -167,6 +167,8 @@ mod types_tests {
         let step = WorkflowStep {
             shell: Some("echo test".to_string()),
             claude: Some("claude command".to_string()),
+            context: None,
+            validate_output: None,
             ..Default::default()
         };
 
@@ -182,6 +184,8 @@ mod types_tests {
         let step = WorkflowStep {
             shell: Some("shell cmd".to_string()),
             claude: Some("claude cmd".to_string()),
+            context: None,
+            validate_output: None,
             ..Default::default()
         };
 
@@ -3,22 +3,40 @@
 //! This module handles executing different types of commands (Claude, shell, write_file)
 //! within agent worktrees with variable interpolation support.
 
+use crate::cook::execution::chaos::{run_with_chaos, ChaosMonkey, ChaosTarget};
+use crate::cook::execution::claude_logs;
+use crate::cook::execution::claude_permissions::{ClaudePermissions, PERMISSIONS_ENV};
+use crate::cook::execution::claude_sessions::SESSION_REUSE_ENV;
 use crate::cook::execution::errors::{MapReduceError, MapReduceResult};
 use crate::cook::execution::interpolation::{InterpolationContext, InterpolationEngine};
-use crate::cook::execution::ClaudeExecutor;
+use crate::cook::execution::mapreduce::network_isolation::{self, AgentNetwork};
+use crate::cook::execution::step_progress::StepProgressReporter;
+use crate::cook::execution::{ClaudeExecutor, ExecutionResult, CLAUDE_MODEL_ENV};
 use crate::cook::orchestrator::ExecutionEnvironment;
-use crate::cook::workflow::{StepResult, WorkflowStep};
+use crate::cook::workflow::context_pack::inject_context;
+use crate::cook::workflow::output_validation::{
+    build_reprompt, response_text, OutputValidationConfig, CLAUDE_OUTPUT_ENV,
+};
+use crate::cook::workflow::script::{self, ScriptConfig};
+use crate::cook::workflow::{
+    format_undefined_variables, without_environment_variables, StepResult, WorkflowStep,
+};
 use crate::subprocess::{ProcessCommandBuilder, SubprocessManager};
 use std::collections::HashMap;
 use std::path::Path;
 use std::sync::Arc;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
 /// Executor for workflow commands in agent worktrees
 #[derive(Clone)]
 pub struct CommandExecutor {
     claude_executor: Arc<dyn ClaudeExecutor>,
     subprocess: Arc<SubprocessManager>,
+    progress_id: Option<String>,
+    claude_permissions: Option<ClaudePermissions>,
+    network: AgentNetwork,
+    strict_variables: bool,
+    chaos: Option<Arc<ChaosMonkey>>,
 }
 
 impl CommandExecutor {
@@ -30,9 +48,58 @@ impl CommandExecutor {
         Self {
             claude_executor,
             subprocess,
+            progress_id: None,
+            claude_permissions: None,
+            network: AgentNetwork::Allow,
+            strict_variables: false,
+            chaos: None,
         }
     }
 
+    /// Record `::prodigy-progress` lines from shell steps under `job_id`
+    pub fn with_progress_id(mut self, job_id: impl Into<String>) -> Self {
+        self.progress_id = Some(job_id.into());
+        self
+    }
+
+    /// The MapReduce job these commands run for, if known
+    pub fn job_id(&self) -> Option<&str> {
+        self.progress_id.as_deref()
+    }
+
+    /// Run Claude commands with `permissions` instead of the workflow's
+    pub fn with_claude_permissions(mut self, permissions: Option<ClaudePermissions>) -> Self {
+        self.claude_permissions = permissions;
+        self
+    }
+
+    /// Run shell commands with `network` access
+    pub fn with_network(mut self, network: AgentNetwork) -> Self {
+        self.network = network;
+        self
+    }
+
+    /// Inject faults into Claude and shell commands (`--chaos`)
+    pub fn with_chaos(mut self, chaos: Option<Arc<ChaosMonkey>>) -> Self {
+        self.chaos = chaos;
+        self
+    }
+
+    /// Fail steps that reference undefined `${...}` variables
+    pub fn with_strict_variables(mut self, strict_variables: bool) -> Self {
+        self.strict_variables = strict_variables;
+        self
+    }
+
+    /// Command builder running `shell_cmd`, isolated when network is denied
+    async fn shell_command(&self, shell_cmd: &str) -> ProcessCommandBuilder {
+        if self.network == AgentNetwork::Deny && network_isolation::isolation_available().await {
+            let (program, args) = network_isolation::isolated_shell_command(shell_cmd);
+            return ProcessCommandBuilder::new(&program).args(args);
+        }
+        ProcessCommandBuilder::new("sh").args(["-c", shell_cmd])
+    }
+
     /// Get a displayable name for a workflow step
     pub fn get_step_display_name(step: &WorkflowStep) -> String {
         if let Some(claude_cmd) = &step.claude {
@@ -46,6 +113,10 @@ impl CommandExecutor {
             }
         } else if let Some(write_file) = &step.write_file {
             format!("write_file: {}", write_file.path)
+        } else if step.assert.is_some() {
+            "assert".to_string()
+        } else if let Some(script) = &step.script {
+            format!("script: {}", script.lang)
         } else {
             "unknown step".to_string()
         }
@@ -59,39 +130,20 @@ impl CommandExecutor {
         env_vars: HashMap<String, String>,
     ) -> MapReduceResult<StepResult> {
         if let Some(shell_cmd) = &step.shell {
-            info!("Executing shell command: {}", shell_cmd);
-            info!("Working directory: {}", env.working_dir.display());
-
-            let command = ProcessCommandBuilder::new("sh")
-                .args(["-c", shell_cmd])
-                .current_dir(&env.working_dir)
-                .envs(env_vars)
-                .build();
-
-            let output = self.subprocess.runner().run(command).await.map_err(|e| {
-                MapReduceError::ProcessingError(format!("Shell command failed: {}", e))
-            })?;
-
-            let exit_code = match output.status {
-                crate::subprocess::runner::ExitStatus::Success => 0,
-                crate::subprocess::runner::ExitStatus::Error(code) => code,
-                crate::subprocess::runner::ExitStatus::Timeout => -1,
-                crate::subprocess::runner::ExitStatus::Signal(sig) => -sig,
-            };
-
-            Ok(StepResult {
-                success: exit_code == 0,
-                exit_code: Some(exit_code),
-                stdout: output.stdout,
-                stderr: output.stderr,
-                json_log_location: None,
-            })
+            self.execute_setup_shell(shell_cmd, env, env_vars).await
+        } else if let Some(script_cfg) = &step.script {
+            let file = Self::write_script(script_cfg, &script_cfg.code)?;
+            let command = script::interpreter_command(script_cfg.lang, file.path());
+            self.execute_setup_shell(&command, env, env_vars).await
         } else if let Some(claude_cmd) = &step.claude {
             info!("Executing Claude command: {}", claude_cmd);
 
+            let command = match &step.context {
+                Some(pack) => inject_context(claude_cmd, &pack.spec(), &env.working_dir)?,
+                None => claude_cmd.clone(),
+            };
             let result = self
-                .claude_executor
-                .execute_claude_command(claude_cmd, &env.working_dir, env_vars)
+                .run_claude_for_step(&command, step, &env.working_dir, env_vars)
                 .await?;
 
             let json_log_location = result.json_log_location().map(|s| s.to_string());
@@ -120,16 +172,74 @@ impl CommandExecutor {
                 stderr: result.stderr,
                 json_log_location: result.json_log_location,
             })
+        } else if let Some(assert_cfg) = &step.assert {
+            Self::execute_assert(assert_cfg, &env.working_dir).await
         } else {
             Err(MapReduceError::InvalidConfiguration {
-                reason: "Step must have either 'claude', 'shell', or 'write_file' command"
-                    .to_string(),
+                reason:
+                    "Step must have either 'claude', 'shell', 'write_file', 'assert', or 'script' command"
+                        .to_string(),
                 field: "step".to_string(),
                 value: format!("{:?}", step),
             })
         }
     }
 
+    /// Run a setup shell command in the environment's working directory
+    async fn execute_setup_shell(
+        &self,
+        shell_cmd: &str,
+        env: &ExecutionEnvironment,
+        env_vars: HashMap<String, String>,
+    ) -> MapReduceResult<StepResult> {
+        info!("Executing shell command: {}", shell_cmd);
+        info!("Working directory: {}", env.working_dir.display());
+
+        let command = ProcessCommandBuilder::new("sh")
+            .args(["-c", shell_cmd])
+            .current_dir(&env.working_dir)
+            .envs(env_vars)
+            .build();
+
+        let progress = self
+            .progress_id
+            .as_ref()
+            .map(|job_id| StepProgressReporter::new(job_id, "setup", shell_cmd.to_string()));
+        let on_line = |line: &str| {
+            if let Some(progress) = &progress {
+                progress.observe_line(line);
+            }
+        };
+
+        let output = self
+            .subprocess
+            .runner()
+            .run_with_line_handler(command, &on_line)
+            .await
+            .map_err(|e| MapReduceError::ProcessingError(format!("Shell command failed: {}", e)))?;
+
+        let exit_code = match output.status {
+            crate::subprocess::runner::ExitStatus::Success => 0,
+            crate::subprocess::runner::ExitStatus::Error(code) => code,
+            crate::subprocess::runner::ExitStatus::Timeout => -1,
+            crate::subprocess::runner::ExitStatus::Signal(sig) => -sig,
+        };
+
+        Ok(StepResult {
+            success: exit_code == 0,
+            exit_code: Some(exit_code),
+            stdout: output.stdout,
+            stderr: output.stderr,
+            json_log_location: None,
+        })
+    }
+
+    /// Write a script step's interpolated `code` to a temporary file
+    fn write_script(config: &ScriptConfig, code: &str) -> MapReduceResult<tempfile::NamedTempFile> {
+        script::write_script(config.lang, code)
+            .map_err(|e| MapReduceError::ProcessingError(format!("Script step failed: {:#}", e)))
+    }
+
     /// Execute a step in an agent's worktree with variable interpolation
     ///
     /// # Arguments
@@ -161,17 +271,38 @@ impl CommandExecutor {
             Self::build_context_from_variables(variables)
         };
 
+        if self.strict_variables {
+            Self::ensure_variables_defined(step, &mut engine, &interp_context, variables)?;
+        }
+
         // Execute based on step type
         if let Some(claude_cmd) = &step.claude {
-            self.execute_claude_in_worktree(claude_cmd, worktree_path, &mut engine, &interp_context)
-                .await
+            run_with_chaos(
+                self.chaos.as_deref(),
+                ChaosTarget::Claude,
+                claude_cmd,
+                self.execute_claude_in_worktree(
+                    claude_cmd,
+                    step,
+                    worktree_path,
+                    &mut engine,
+                    &interp_context,
+                    variables,
+                ),
+            )
+            .await
         } else if let Some(shell_cmd) = &step.shell {
-            self.execute_shell_in_worktree(
+            run_with_chaos(
+                self.chaos.as_deref(),
+                ChaosTarget::Shell,
                 shell_cmd,
-                worktree_path,
-                variables,
-                &mut engine,
-                &interp_context,
+                self.execute_shell_in_worktree(
+                    shell_cmd,
+                    worktree_path,
+                    variables,
+                    &mut engine,
+                    &interp_context,
+                ),
             )
             .await
         } else if let Some(write_file_cfg) = &step.write_file {
@@ -182,16 +313,85 @@ impl CommandExecutor {
                 &interp_context,
             )
             .await
+        } else if let Some(assert_cfg) = &step.assert {
+            let mut interpolation_error = None;
+            let interpolated = assert_cfg.interpolated(|s| {
+                engine.interpolate(s, &interp_context).unwrap_or_else(|e| {
+                    interpolation_error.get_or_insert(e.to_string());
+                    s.to_string()
+                })
+            });
+            if let Some(e) = interpolation_error {
+                return Err(MapReduceError::ProcessingError(format!(
+                    "Variable interpolation failed for assert: {}",
+                    e
+                )));
+            }
+            Self::execute_assert(&interpolated, worktree_path).await
+        } else if let Some(script_cfg) = &step.script {
+            let code = engine
+                .interpolate(&script_cfg.code, &interp_context)
+                .map_err(|e| {
+                    MapReduceError::ProcessingError(format!(
+                        "Variable interpolation failed for script: {}",
+                        e
+                    ))
+                })?;
+            let file = Self::write_script(script_cfg, &code)?;
+            let command = script::interpreter_command(script_cfg.lang, file.path());
+            run_with_chaos(
+                self.chaos.as_deref(),
+                ChaosTarget::Shell,
+                &command,
+                self.execute_shell_in_worktree(
+                    &command,
+                    worktree_path,
+                    variables,
+                    &mut engine,
+                    &interp_context,
+                ),
+            )
+            .await
         } else {
             Err(MapReduceError::InvalidConfiguration {
-                reason: "Step must have either 'claude', 'shell', or 'write_file' command"
-                    .to_string(),
+                reason:
+                    "Step must have either 'claude', 'shell', 'write_file', 'assert', or 'script' command"
+                        .to_string(),
                 field: "step".to_string(),
                 value: format!("{:?}", step),
             })
         }
     }
 
+    /// Fail a step whose templates reference undefined variables
+    ///
+    /// Names in `variables`, which become the step's environment, and in the
+    /// process environment count as defined.
+    fn ensure_variables_defined(
+        step: &WorkflowStep,
+        engine: &mut InterpolationEngine,
+        context: &InterpolationContext,
+        variables: &HashMap<String, String>,
+    ) -> MapReduceResult<()> {
+        let templates = [
+            step.claude.as_deref(),
+            step.shell.as_deref(),
+            step.write_file.as_ref().map(|cfg| cfg.path.as_str()),
+            step.write_file.as_ref().map(|cfg| cfg.content.as_str()),
+            step.script.as_ref().map(|cfg| cfg.code.as_str()),
+        ];
+        for template in templates.into_iter().flatten() {
+            let undefined = engine
+                .undefined_references(template, context)
+                .map_err(|e| MapReduceError::ProcessingError(e.to_string()))?;
+            let undefined = without_environment_variables(undefined, variables);
+            if let Some(message) = format_undefined_variables(&undefined, template) {
+                return Err(MapReduceError::ProcessingError(message));
+            }
+        }
+        Ok(())
+    }
+
     /// Build interpolation context from variables HashMap
     fn build_context_from_variables(variables: &HashMap<String, String>) -> InterpolationContext {
         let mut ctx = InterpolationContext::new();
@@ -224,27 +424,71 @@ impl CommandExecutor {
     async fn execute_claude_in_worktree(
         &self,
         claude_cmd: &str,
+        step: &WorkflowStep,
         worktree_path: &Path,
         engine: &mut InterpolationEngine,
         context: &InterpolationContext,
+        variables: &HashMap<String, String>,
     ) -> MapReduceResult<StepResult> {
         let interpolated_cmd = engine.interpolate(claude_cmd, context).map_err(|e| {
             MapReduceError::ProcessingError(format!("Variable interpolation failed: {}", e))
         })?;
 
+        let interpolated_cmd = match &step.context {
+            Some(pack) => {
+                let mut spec = pack.spec();
+                spec.files = spec
+                    .files
+                    .iter()
+                    .map(|pattern| engine.interpolate(pattern, context))
+                    .collect::<Result<_, _>>()
+                    .map_err(|e| {
+                        MapReduceError::ProcessingError(format!(
+                            "Variable interpolation failed: {}",
+                            e
+                        ))
+                    })?;
+                inject_context(&interpolated_cmd, &spec, worktree_path).map_err(|e| {
+                    MapReduceError::ProcessingError(format!("Failed to build context pack: {}", e))
+                })?
+            }
+            None => interpolated_cmd,
+        };
+
         info!("Executing Claude command in worktree: {}", interpolated_cmd);
 
         let mut env_vars = HashMap::new();
         env_vars.insert("PRODIGY_AUTOMATION".to_string(), "true".to_string());
-        env_vars.insert("PRODIGY_CLAUDE_STREAMING".to_string(), "false".to_string());
+        if let Ok(streaming) = std::env::var("PRODIGY_CLAUDE_STREAMING") {
+            env_vars.insert("PRODIGY_CLAUDE_STREAMING".to_string(), streaming);
+        }
+        if let Ok(reuse) = std::env::var(SESSION_REUSE_ENV) {
+            env_vars.insert(SESSION_REUSE_ENV.to_string(), reuse);
+        }
+        env_vars.extend(claude_log_env(self.progress_id.as_deref(), variables));
+        if let Some(permissions) = &self.claude_permissions {
+            env_vars.insert(PERMISSIONS_ENV.to_string(), permissions.to_env_value());
+        }
 
-        let result = self
-            .claude_executor
-            .execute_claude_command(&interpolated_cmd, worktree_path, env_vars)
-            .await
-            .map_err(|e| {
-                MapReduceError::ProcessingError(format!("Failed to execute Claude command: {}", e))
-            })?;
+        let result = match &step.validate_output {
+            Some(contract) => {
+                self.run_claude_validated(
+                    &interpolated_cmd,
+                    step,
+                    contract,
+                    worktree_path,
+                    env_vars,
+                )
+                .await
+            }
+            None => {
+                self.run_claude_for_step(&interpolated_cmd, step, worktree_path, env_vars)
+                    .await
+            }
+        }
+        .map_err(|e| {
+            MapReduceError::ProcessingError(format!("Failed to execute Claude command: {}", e))
+        })?;
 
         let json_log_location = result.json_log_location().map(|s| s.to_string());
 
@@ -257,6 +501,142 @@ impl CommandExecutor {
         })
     }
 
+    /// Run a Claude command until its output meets the step's `validate_output`
+    async fn run_claude_validated(
+        &self,
+        command: &str,
+        step: &WorkflowStep,
+        contract: &OutputValidationConfig,
+        working_dir: &Path,
+        env_vars: HashMap<String, String>,
+    ) -> anyhow::Result<ExecutionResult> {
+        let max_attempts = contract.max_attempts.max(1);
+        let mut prompt = command.to_string();
+        let mut attempt = 1;
+
+        loop {
+            let mut result = self
+                .run_claude_for_step(&prompt, step, working_dir, env_vars.clone())
+                .await?;
+            if !result.success {
+                return Ok(result);
+            }
+
+            let response = response_text(&result.stdout);
+            let mut errors = contract.check_response(&response);
+            if let Some(validator) = &contract.shell {
+                if let Some(error) = self
+                    .run_output_validator(validator, &response, working_dir, &env_vars)
+                    .await?
+                {
+                    errors.push(error);
+                }
+            }
+            if errors.is_empty() {
+                return Ok(result);
+            }
+
+            if attempt >= max_attempts {
+                result.success = false;
+                result.stderr = format!(
+                    "Output validation failed after {} attempt(s):\n{}",
+                    max_attempts,
+                    errors.join("\n")
+                );
+                return Ok(result);
+            }
+
+            warn!(
+                "Output validation failed (attempt {}/{}), re-prompting: {}",
+                attempt,
+                max_attempts,
+                errors.join("; ")
+            );
+            prompt = build_reprompt(command, &errors);
+            attempt += 1;
+        }
+    }
+
+    /// Run a `validate_output` shell validator, returning its error if it fails
+    async fn run_output_validator(
+        &self,
+        validator: &str,
+        response: &str,
+        working_dir: &Path,
+        env_vars: &HashMap<String, String>,
+    ) -> anyhow::Result<Option<String>> {
+        let output_file = tempfile::NamedTempFile::new()?;
+        std::fs::write(output_file.path(), response)?;
+
+        let mut validator_env = env_vars.clone();
+        validator_env.insert(
+            CLAUDE_OUTPUT_ENV.to_string(),
+            output_file.path().to_string_lossy().to_string(),
+        );
+        let command = ProcessCommandBuilder::new("sh")
+            .args(["-c", validator])
+            .current_dir(working_dir)
+            .envs(validator_env)
+            .build();
+        let output = self.subprocess.runner().run(command).await?;
+
+        if matches!(
+            output.status,
+            crate::subprocess::runner::ExitStatus::Success
+        ) {
+            return Ok(None);
+        }
+        let detail = [output.stderr.trim(), output.stdout.trim()]
+            .into_iter()
+            .find(|s| !s.is_empty())
+            .map(String::from)
+            .unwrap_or_else(|| format!("{:?}", output.status));
+        Ok(Some(format!("Validator `{validator}` failed: {detail}")))
+    }
+
+    /// Run a Claude command with the step's model, escalating once on failure
+    ///
+    /// When the step sets `on_failure.escalate_model`, a failed run is retried
+    /// with that model before the failure is reported.
+    async fn run_claude_for_step(
+        &self,
+        command: &str,
+        step: &WorkflowStep,
+        working_dir: &Path,
+        mut env_vars: HashMap<String, String>,
+    ) -> anyhow::Result<ExecutionResult> {
+        if let Some(model) = &step.model {
+            env_vars.insert(CLAUDE_MODEL_ENV.to_string(), model.clone());
+        }
+
+        let escalate_model = step
+            .on_failure
+            .as_ref()
+            .and_then(|config| config.escalate_model());
+        let Some(model) = escalate_model else {
+            return self
+                .claude_executor
+                .execute_claude_command(command, working_dir, env_vars)
+                .await;
+        };
+
+        match self
+            .claude_executor
+            .execute_claude_command(command, working_dir, env_vars.clone())
+            .await
+        {
+            Ok(result) if result.success => return Ok(result),
+            Ok(result) => warn!("Claude command '{}' failed: {}", command, result.stderr),
+            Err(e) => warn!("Claude command '{}' failed: {:#}", command, e),
+        }
+
+        info!("Retrying Claude command with model '{}'", model);
+        env_vars.insert(CLAUDE_MODEL_ENV.to_string(), model.to_string());
+        self.claude_executor
+            .execute_claude_command(command, working_dir, env_vars)
+            .await
+    }
+
     /// Execute shell command in worktree
     async fn execute_shell_in_worktree(
         &self,
@@ -275,15 +655,40 @@ impl CommandExecutor {
 
         info!("Executing shell command in worktree: {}", interpolated_cmd);
 
-        let command = ProcessCommandBuilder::new("sh")
-            .args(["-c", &interpolated_cmd])
+        let command = self
+            .shell_command(&interpolated_cmd)
+            .await
             .current_dir(worktree_path)
             .envs(variables.clone())
             .build();
 
-        let output = self.subprocess.runner().run(command).await.map_err(|e| {
-            MapReduceError::ProcessingError(format!("Failed to execute shell command: {}", e))
-        })?;
+        let progress = self.progress_id.as_ref().map(|job_id| {
+            // Map agents report under their item ID; other steps under the worktree
+            let source = variables
+                .get("item_id")
+                .cloned()
+                .or_else(|| {
+                    worktree_path
+                        .file_name()
+                        .map(|name| name.to_string_lossy().to_string())
+                })
+                .unwrap_or_else(|| "reduce".to_string());
+            StepProgressReporter::new(job_id, source, interpolated_cmd.clone())
+        });
+        let on_line = |line: &str| {
+            if let Some(progress) = &progress {
+                progress.observe_line(line);
+            }
+        };
+
+        let output = self
+            .subprocess
+            .runner()
+            .run_with_line_handler(command, &on_line)
+            .await
+            .map_err(|e| {
+                MapReduceError::ProcessingError(format!("Failed to execute shell command: {}", e))
+            })?;
 
         let exit_code = match output.status {
             crate::subprocess::runner::ExitStatus::Success => 0,
@@ -302,6 +707,17 @@ impl CommandExecutor {
     }
 
     /// Execute write_file command in worktree
+    /// Run an assert step's checks in `dir`
+    async fn execute_assert(
+        config: &crate::cook::workflow::assertions::AssertConfig,
+        dir: &Path,
+    ) -> MapReduceResult<StepResult> {
+        let git = crate::abstractions::git::RealGitOperations::new();
+        crate::cook::workflow::execute_assert_command(config, dir, &git)
+            .await
+            .map_err(|e| MapReduceError::ProcessingError(format!("Assert command failed: {}", e)))
+    }
+
     async fn execute_write_file_in_worktree(
         &self,
         write_file_cfg: &crate::config::command::WriteFileConfig,
@@ -359,3 +775,23 @@ impl CommandExecutor {
         })
     }
 }
+
+/// Environment labelling an agent's Claude streaming log (pure function)
+///
+/// `prodigy logs claude` uses these to find the logs of a job, agent or item.
+fn claude_log_env(
+    job_id: Option<&str>,
+    variables: &HashMap<String, String>,
+) -> HashMap<String, String> {
+    let mut env = HashMap::new();
+    if let Some(job_id) = job_id {
+        env.insert(claude_logs::JOB_ID_ENV.to_string(), job_id.to_string());
+    }
+    if let Some(agent_id) = variables.get("agent_id") {
+        env.insert(claude_logs::AGENT_ID_ENV.to_string(), agent_id.clone());
+    }
+    if let Some(item_id) = variables.get("item_id") {
+        env.insert(claude_logs::ITEM_ID_ENV.to_string(), item_id.clone());
+    }
+    env
+}
@@ -5,29 +5,47 @@
 
 use super::command_executor::CommandExecutor;
 use crate::cook::execution::claude::ClaudeExecutorImpl;
-use crate::cook::execution::data_pipeline::DataPipeline;
-use crate::cook::execution::dlq::DeadLetterQueue;
+use crate::cook::execution::data_pipeline::{ChangedFiles, DataPipeline};
+use crate::cook::execution::determinism::TurnOrder;
+use crate::cook::execution::dlq::{DLQFilter, DeadLetterQueue};
 use crate::cook::execution::errors::{MapReduceError, MapReduceResult};
 use crate::cook::execution::input_source::InputSource;
+use crate::cook::execution::junit::{self, JunitRecorder, TestCase};
 use crate::cook::execution::mapreduce::{
+    affinity::{self, AffinityLocks},
     agent::{AgentConfig, AgentLifecycleManager, AgentResult, AgentStatus},
     aggregation::{AggregationSummary, CollectionStrategy, ResultCollector},
-    dlq_integration,
+    batching, dlq_integration,
+    eta::EtaEstimator,
     event::{EventLogger, MapReduceEvent},
+    idempotency::{self, AppliedMarker, IdempotencyStore},
+    issue_tracker::{self, IssueAction, IssueNotificationConfig},
+    item_key::{assign_item_ids, branch_safe, ItemKeyExpr},
     merge_queue::MergeQueue,
+    pure::conflict_analysis::{predict_conflicts, ConflictReport},
+    pure::formatting::format_commit_requirement_error,
     resources::git::GitOperations,
     retry_tracking,
+    setup_artifacts::SetupArtifacts,
+    shared_context::{self, SharedContextCache, SharedContextConfig},
+    speculative::{SpeculationTracker, SpeculativeWinner, STRAGGLER_CHECK_INTERVAL},
     state::StateManager,
     timeout::{TimeoutConfig, TimeoutEnforcer},
     types::{MapPhase, ReducePhase, SetupPhase},
 };
+use crate::cook::execution::progress::format_duration;
 use crate::cook::execution::runner::RealCommandRunner;
+use crate::cook::execution::step_progress::{
+    self, StepProgress, StepProgressRecord, StepStatus, StepTracker,
+};
 use crate::cook::execution::ClaudeExecutor;
 use crate::cook::interaction::UserInteraction;
 use crate::cook::orchestrator::ExecutionEnvironment;
 use crate::cook::session::SessionManager;
+use crate::cook::signal_handler;
 use crate::cook::workflow::{OnFailureConfig, WorkflowStep};
-use crate::subprocess::SubprocessManager;
+use crate::subprocess::{metadata, ProcessMetadata, SubprocessManager};
+use crate::worktree::build_cache::{mount_build_cache, BuildCacheMount};
 use chrono::{DateTime, Utc};
 use serde_json::Value;
 use std::collections::HashMap;
@@ -100,11 +118,15 @@ impl MapReduceCoordinator {
         subprocess: Arc<SubprocessManager>,
         project_root: PathBuf,
     ) -> Self {
+        // Create claude executor using the real implementation
+        let claude_executor: Arc<dyn ClaudeExecutor> =
+            Arc::new(ClaudeExecutorImpl::new(RealCommandRunner::new()));
         Self::with_mode(
             agent_manager,
             state_manager,
             user_interaction,
             subprocess,
+            claude_executor,
             project_root,
             crate::cook::execution::mapreduce::dry_run::ExecutionMode::Normal,
             0, // Default verbosity
@@ -112,11 +134,13 @@ impl MapReduceCoordinator {
     }
 
     /// Create a new coordinator with execution mode
+    #[allow(clippy::too_many_arguments)]
     pub fn with_mode(
         agent_manager: Arc<dyn AgentLifecycleManager>,
         state_manager: Arc<StateManager>,
         user_interaction: Arc<dyn UserInteraction>,
         subprocess: Arc<SubprocessManager>,
+        claude_executor: Arc<dyn ClaudeExecutor>,
         project_root: PathBuf,
         execution_mode: crate::cook::execution::mapreduce::dry_run::ExecutionMode,
         verbosity: u8,
@@ -130,11 +154,6 @@ impl MapReduceCoordinator {
             verbosity,
         ));
 
-        // Create claude executor using the real implementation
-        let command_runner = RealCommandRunner::new();
-        let claude_executor: Arc<dyn ClaudeExecutor> =
-            Arc::new(ClaudeExecutorImpl::new(command_runner));
-
         // Create session manager - not used but required for struct
         let session_manager = Arc::new(DummySessionManager);
 
@@ -167,7 +186,8 @@ impl MapReduceCoordinator {
         });
 
         // Create command executor
-        let command_executor = CommandExecutor::new(claude_executor.clone(), subprocess.clone());
+        let command_executor = CommandExecutor::new(claude_executor.clone(), subprocess.clone())
+            .with_progress_id(job_id.clone());
 
         Self {
             agent_manager,
@@ -211,7 +231,7 @@ impl MapReduceCoordinator {
     pub async fn execute_job(
         &self,
         setup: Option<SetupPhase>,
-        map_phase: MapPhase,
+        mut map_phase: MapPhase,
         reduce: Option<ReducePhase>,
         env: &ExecutionEnvironment,
     ) -> MapReduceResult<Vec<AgentResult>> {
@@ -245,14 +265,43 @@ impl MapReduceCoordinator {
                 .await;
         }
 
+        // Lets a failure report point at this job's dead-lettered items
+        if let Some(failures) = &map_phase.failures {
+            failures.record_mapreduce_job(&self.job_id);
+        }
+
         // Execute setup phase if present
         if let Some(setup_phase) = setup {
-            self.execute_setup_phase(setup_phase, env, &map_phase.workflow_env)
+            let artifact_patterns = setup_phase.artifact_patterns();
+            if let Some(shared) = setup_phase.shared_context.as_ref() {
+                self.prepare_shared_context(
+                    shared,
+                    env,
+                    &map_phase.workflow_env,
+                    map_phase.junit.as_deref(),
+                )
                 .await?;
+            }
+            self.execute_setup_phase(
+                setup_phase,
+                env,
+                &map_phase.workflow_env,
+                map_phase.junit.as_deref(),
+            )
+            .await?;
+
+            // Snapshot declared setup outputs before agents start merging
+            if !artifact_patterns.is_empty() {
+                let artifacts = SetupArtifacts::capture(&artifact_patterns, &env.working_dir)
+                    .map_err(|e| MapReduceError::ProcessingError(e.to_string()))?;
+                map_phase.setup_artifacts = Some(Arc::new(artifacts));
+            }
         }
 
+        self.stop_if_shutdown_requested("before the map phase")?;
+
         // Load work items
-        let work_items = self.load_work_items(&map_phase).await?;
+        let work_items = self.load_work_items(&map_phase, &env.working_dir).await?;
 
         if work_items.is_empty() {
             warn!("No work items to process");
@@ -261,16 +310,62 @@ impl MapReduceCoordinator {
 
         info!("Processing {} work items", work_items.len());
 
+        let issue_config = map_phase
+            .notifications
+            .as_ref()
+            .and_then(|n| n.issues.clone());
+        let workflow_env = map_phase.workflow_env.clone();
+        let junit = map_phase.junit.clone();
+        // Reduce steps run Claude with the workflow's permissions, and with the
+        // map phase's strictness and fault injection
+        let reduce_executor = self
+            .command_executor
+            .clone()
+            .with_claude_permissions(reduce.as_ref().and_then(|r| r.claude_permissions.clone()))
+            .with_strict_variables(map_phase.strict_variables)
+            .with_chaos(map_phase.chaos.clone());
+
         // Execute map phase
-        let map_results = self
+        let map_results = match self
             .execute_map_phase_internal(map_phase, work_items, env)
-            .await?;
+            .await
+        {
+            Ok(results) => results,
+            Err(e) => {
+                if let Some(config) = &issue_config {
+                    self.notify_failure_issues(config).await;
+                }
+                return Err(e);
+            }
+        };
+
+        self.display_conflict_report(&predict_conflicts(&map_results));
 
         // Execute reduce phase if present
-        if let Some(reduce_phase) = reduce {
-            self.execute_reduce_phase(reduce_phase, &map_results, env)
-                .await?;
+        let reduce_result = match reduce {
+            Some(reduce_phase) => {
+                match self.stop_if_shutdown_requested("before the reduce phase") {
+                    Ok(()) => {
+                        self.execute_reduce_phase(
+                            reduce_phase,
+                            &map_results,
+                            env,
+                            &workflow_env,
+                            reduce_executor,
+                            junit.as_deref(),
+                        )
+                        .await
+                    }
+                    Err(e) => Err(e),
+                }
+            }
+            None => Ok(()),
+        };
+
+        if let Some(config) = &issue_config {
+            self.notify_failure_issues(config).await;
         }
+        reduce_result?;
 
         // SPEC 134: Merge to original branch is handled by orchestrator cleanup with user confirmation
         // No automatic merge happens here. The orchestrator will prompt the user to merge the parent
@@ -280,6 +375,22 @@ impl MapReduceCoordinator {
         Ok(map_results)
     }
 
+    /// Stop the job once SIGTERM or SIGHUP requested a graceful shutdown
+    fn stop_if_shutdown_requested(&self, stage: &str) -> MapReduceResult<()> {
+        let Some(signal) = signal_handler::shutdown_requested() else {
+            return Ok(());
+        };
+        Err(MapReduceError::General {
+            message: format!(
+                "MapReduce job {} stopped by {} {}",
+                self.job_id,
+                signal.name(),
+                stage
+            ),
+            source: None,
+        })
+    }
+
     /// Execute in dry-run mode
     async fn execute_dry_run(
         &self,
@@ -397,6 +508,14 @@ impl MapReduceCoordinator {
         CommandExecutor::get_step_display_name(step)
     }
 
+    /// Subprocess metadata for a setup or reduce step of this job (pure function)
+    fn phase_metadata(env: &ExecutionEnvironment, job_id: &str, index: usize) -> ProcessMetadata {
+        ProcessMetadata::new(env.session_id.to_string())
+            .with_job_id(Some(job_id))
+            .with_worktree(env.worktree_name.as_deref())
+            .with_step_index(index)
+    }
+
     /// Interpolate a workflow step with environment variables
     fn interpolate_step_with_env(
         &self,
@@ -435,9 +554,56 @@ impl MapReduceCoordinator {
             })?);
         }
 
+        if let Some(script) = &mut interpolated.script {
+            script.code = engine.interpolate(&script.code, &context).map_err(|e| {
+                MapReduceError::ProcessingError(format!("Failed to interpolate script: {}", e))
+            })?;
+        }
+
         Ok(interpolated)
     }
 
+    /// Restore the shared context from cache or compute it for the current commit
+    async fn prepare_shared_context(
+        &self,
+        shared: &SharedContextConfig,
+        env: &ExecutionEnvironment,
+        workflow_env: &HashMap<String, String>,
+        junit: Option<&JunitRecorder>,
+    ) -> MapReduceResult<()> {
+        let to_error = |e: anyhow::Error| MapReduceError::ProcessingError(e.to_string());
+        let cache = SharedContextCache::open(
+            shared,
+            shared_context::cache_dir(&env.project_dir).map_err(to_error)?,
+            &env.working_dir,
+        )
+        .map_err(to_error)?;
+        if cache
+            .restore(&env.working_dir, &shared.file)
+            .map_err(to_error)?
+        {
+            info!(
+                "Reusing shared context {} cached for {}",
+                shared.file,
+                cache.head()
+            );
+            return Ok(());
+        }
+
+        let phase = SetupPhase {
+            commands: shared.commands.clone(),
+            timeout: None,
+            capture_outputs: HashMap::new(),
+            artifacts: Vec::new(),
+            shared_context: None,
+        };
+        self.execute_setup_phase(phase, env, workflow_env, junit)
+            .await?;
+        cache
+            .store(&env.working_dir, &shared.file)
+            .map_err(to_error)
+    }
+
     /// Execute the setup phase
     #[cfg_attr(test, allow(dead_code))]
     pub(crate) async fn execute_setup_phase(
@@ -445,6 +611,7 @@ impl MapReduceCoordinator {
         setup_phase: SetupPhase,
         env: &ExecutionEnvironment,
         workflow_env: &HashMap<String, String>,
+        junit: Option<&JunitRecorder>,
     ) -> MapReduceResult<()> {
         info!("Executing setup phase");
         info!(
@@ -505,26 +672,42 @@ impl MapReduceCoordinator {
             );
 
             // Execute the interpolated step
-            let result = self
-                .command_executor
-                .execute_setup_step(&interpolated_step, env, env_vars)
-                .await
-                .map_err(|e| {
-                    MapReduceError::ProcessingError(format!(
-                        "Setup step {} ({}) failed: {}",
-                        index + 1,
-                        step_name,
-                        e
-                    ))
-                })?;
+            let step_start = Instant::now();
+            let tracker = StepTracker::start(&self.job_id, "setup", &step_name);
+            let result = metadata::scope(
+                Self::phase_metadata(env, &self.job_id, index),
+                self.command_executor
+                    .execute_setup_step(&interpolated_step, env, env_vars),
+            )
+            .await
+            .map_err(|e| {
+                let error = MapReduceError::ProcessingError(format!(
+                    "Setup step {} ({}) failed: {}",
+                    index + 1,
+                    step_name,
+                    e
+                ));
+                junit::record(
+                    junit,
+                    TestCase::from_error("setup", &step_name, step_start.elapsed(), &error),
+                );
+                error
+            })?;
+            junit::record(
+                junit,
+                TestCase::from_step("setup", &step_name, step_start.elapsed(), &result),
+            );
+
+            let elapsed = tracker.finish(result.success);
 
             // Display completion
             if result.success {
                 self.user_interaction.display_success(&format!(
-                    "✓ Setup [{}/{}]: {} completed",
+                    "✓ Setup [{}/{}]: {} completed in {}",
                     index + 1,
                     setup_phase.commands.len(),
-                    step_name
+                    step_name,
+                    format_duration(elapsed)
                 ));
             }
 
@@ -539,12 +722,15 @@ impl MapReduceCoordinator {
                     // Create empty variables for on_failure (setup phase has no item context)
                     let variables = HashMap::new();
 
-                    let handler_result = Self::handle_on_failure(
-                        on_failure,
-                        &env.working_dir,
-                        &variables,
-                        &self.command_executor,
-                        &self.user_interaction,
+                    let handler_result = metadata::scope(
+                        Self::phase_metadata(env, &self.job_id, index),
+                        Self::handle_on_failure(
+                            on_failure,
+                            &env.working_dir,
+                            &variables,
+                            &self.command_executor,
+                            &self.user_interaction,
+                        ),
                     )
                     .await?;
 
@@ -599,33 +785,39 @@ impl MapReduceCoordinator {
         Ok(())
     }
 
-    /// Load work items from input source
-    async fn load_work_items(&self, map_phase: &MapPhase) -> MapReduceResult<Vec<Value>> {
+    /// Load work items from input source, resolving input files against `base_dir`
+    async fn load_work_items(
+        &self,
+        map_phase: &MapPhase,
+        base_dir: &Path,
+    ) -> MapReduceResult<Vec<Value>> {
         info!("Loading work items from: {}", map_phase.config.input);
 
         // Create input source
-        let input_source =
-            InputSource::detect_with_base(&map_phase.config.input, &self.project_root);
+        let input_source = InputSource::detect_with_base(&map_phase.config.input, base_dir);
 
         // Load data based on input source type
         let json_data = match input_source {
-            InputSource::JsonFile(path) => {
-                InputSource::load_json_file(&path, &self.project_root).await?
-            }
+            InputSource::JsonFile(path) => InputSource::load_json_file(&path, base_dir).await?,
             InputSource::Command(cmd) => {
                 let items =
                     InputSource::execute_command(&cmd, Duration::from_secs(300), &self.subprocess)
                         .await?;
                 serde_json::Value::Array(items)
             }
+            InputSource::GitHub(input) => {
+                serde_json::Value::Array(self.github_items(&input, base_dir).await?)
+            }
         };
 
         // Create data pipeline from configuration
-        let pipeline = DataPipeline::from_config(
+        let pipeline = DataPipeline::from_full_config(
             map_phase.json_path.clone(),
             map_phase.filter.clone(),
             map_phase.sort_by.clone(),
             map_phase.max_items,
+            map_phase.config.offset,
+            map_phase.distinct.clone(),
         )
         .map_err(|e| MapReduceError::InvalidConfiguration {
             reason: format!("Failed to build data pipeline: {}", e),
@@ -633,6 +825,17 @@ impl MapReduceCoordinator {
             value: "configuration".to_string(),
         })?;
 
+        // Scope items to files changed since a git ref (PR-scoped runs)
+        let pipeline = match &map_phase.changed_since {
+            Some(base) => {
+                let files = self.changed_files(base, base_dir).await?;
+                info!("{} files changed since {}", files.len(), base);
+                pipeline
+                    .with_changed_files(ChangedFiles::new(files, map_phase.changed_path.clone()))
+            }
+            None => pipeline,
+        };
+
         // Process the data through the pipeline
         let items =
             pipeline
@@ -644,9 +847,60 @@ impl MapReduceCoordinator {
                 })?;
 
         debug!("Loaded {} work items", items.len());
+
+        // Group small items so each agent processes a batch of them
+        match map_phase.config.batch_size {
+            Some(batch_size) if batch_size > 1 => {
+                let count = items.len();
+                let batches = batching::batch_items(items, batch_size);
+                info!(
+                    "Grouped {} work items into {} batches of up to {}",
+                    count,
+                    batches.len(),
+                    batch_size
+                );
+                Ok(batches)
+            }
+            _ => Ok(items),
+        }
+    }
+
+    /// Work items read from GitHub issues or review comments
+    async fn github_items(&self, input: &str, dir: &Path) -> MapReduceResult<Vec<Value>> {
+        use crate::cook::execution::github_input::{fetch_items, GhApi, GitHubInput};
+
+        let query =
+            GitHubInput::parse(input).map_err(|e| MapReduceError::InvalidConfiguration {
+                reason: e.to_string(),
+                field: "input".to_string(),
+                value: input.to_string(),
+            })?;
+        let api = GhApi::new(self.subprocess.clone(), dir);
+        let items = fetch_items(&api, &query).await.map_err(|e| {
+            MapReduceError::CommandExecutionFailed {
+                command: input.to_string(),
+                reason: format!("Failed to read work items from GitHub: {:#}", e),
+                source: None,
+            }
+        })?;
+        info!("Read {} work items from {}", items.len(), input);
         Ok(items)
     }
 
+    /// Files changed on HEAD since it diverged from `base`, relative to `dir`
+    async fn changed_files(&self, base: &str, dir: &Path) -> MapReduceResult<Vec<PathBuf>> {
+        use crate::git::{GitCommandRunner, GitReader};
+
+        GitCommandRunner::new(self.subprocess.runner())
+            .get_changed_files(dir, base)
+            .await
+            .map_err(|e| MapReduceError::InvalidConfiguration {
+                reason: format!("Failed to list files changed since {}: {}", base, e),
+                field: "changed_since".to_string(),
+                value: base.to_string(),
+            })
+    }
+
     /// Convert execution result to AgentResult with appropriate event logging
     ///
     /// This helper function handles the conversion of agent execution results,
@@ -704,6 +958,7 @@ impl MapReduceCoordinator {
                     files_modified: vec![],
                     json_log_location: None,
                     cleanup_status: None,
+                    no_changes: None,
                 })
             }
         }
@@ -754,6 +1009,7 @@ impl MapReduceCoordinator {
     #[allow(clippy::too_many_arguments)]
     async fn process_single_work_item(
         index: usize,
+        item_id: String,
         item: Value,
         job_id: String,
         map_phase: MapPhase,
@@ -768,15 +1024,63 @@ impl MapReduceCoordinator {
         dlq: Arc<DeadLetterQueue>,
         retry_counts: Arc<tokio::sync::RwLock<HashMap<String, u32>>>,
         timeout_enforcer: Option<Arc<TimeoutEnforcer>>,
+        speculation: Option<Arc<SpeculationTracker>>,
+        idempotency: Option<Arc<IdempotencyStore>>,
+        eta: Arc<EtaEstimator>,
+        turns: Option<Arc<TurnOrder>>,
+        affinity: Option<Arc<AffinityLocks>>,
         total_items: usize,
     ) -> MapReduceResult<AgentResult> {
+        let turn = match turns.as_deref() {
+            Some(turns) => Some(turns.turn(index).await),
+            None => None,
+        };
+
+        // Skip items an earlier run already applied, without taking a slot
+        if let Some(store) = idempotency.as_deref() {
+            if let Some(marker) = store.applied(&item_id).await {
+                info!(
+                    "Skipping item {}: already applied by job {}",
+                    item_id, marker.job_id
+                );
+                let agent_result = idempotency::skipped_result(&item_id, &marker);
+                result_collector.add_result(agent_result.clone()).await;
+                Self::report_map_progress(
+                    &eta,
+                    &job_id,
+                    &event_logger,
+                    user_interaction.as_ref(),
+                    &agent_result,
+                )
+                .await?;
+                return Ok(agent_result);
+            }
+        }
+
+        // Wait for running items of the same affinity group, without a slot
+        let _affinity = match affinity.as_deref() {
+            Some(locks) => Some(locks.acquire(&item).await),
+            None => None,
+        };
+
         // Acquire semaphore permit
         let _permit = semaphore.acquire().await.map_err(|e| {
             MapReduceError::ProcessingError(format!("Failed to acquire semaphore: {}", e))
         })?;
+        drop(turn);
+
+        // Leave items that have not started for resume once shutdown is requested
+        if let Some(signal) = signal_handler::shutdown_requested() {
+            return Err(MapReduceError::ProcessingError(format!(
+                "Item {} not started: {} received",
+                item_id,
+                signal.name()
+            )));
+        }
 
-        let item_id = format!("item_{}", index);
         let agent_id = format!("{}_agent_{}", job_id, index);
+        let _in_flight =
+            signal_handler::track_work(format!("agent {} (item {})", agent_id, item_id));
 
         // Log agent start
         event_logger
@@ -793,21 +1097,44 @@ impl MapReduceCoordinator {
         let item_for_dlq = item.clone();
 
         // Execute agent with item
-        let result = Self::execute_agent_for_item(
-            &agent_manager,
-            &merge_queue,
-            &agent_id,
-            &item_id,
-            item,
-            &map_phase,
-            &env,
-            &user_interaction,
-            &command_executor,
-            timeout_enforcer.as_ref(),
-            index,
-            total_items,
-        )
-        .await;
+        let result = match speculation.as_deref() {
+            Some(speculation) => {
+                Self::execute_speculative_agent_for_item(
+                    &agent_manager,
+                    &merge_queue,
+                    &agent_id,
+                    &item_id,
+                    item,
+                    &map_phase,
+                    &env,
+                    &user_interaction,
+                    &command_executor,
+                    timeout_enforcer.as_ref(),
+                    &semaphore,
+                    speculation,
+                    index,
+                    total_items,
+                )
+                .await
+            }
+            None => {
+                Self::execute_agent_for_item(
+                    &agent_manager,
+                    &merge_queue,
+                    &agent_id,
+                    &item_id,
+                    item,
+                    &map_phase,
+                    &env,
+                    &user_interaction,
+                    &command_executor,
+                    timeout_enforcer.as_ref(),
+                    index,
+                    total_items,
+                )
+                .await
+            }
+        };
 
         let duration = start_time.elapsed();
 
@@ -821,9 +1148,38 @@ impl MapReduceCoordinator {
         )
         .await?;
 
+        // Record the item as applied so retries and re-runs skip it
+        if let Some(store) = idempotency
+            .as_deref()
+            .filter(|_| idempotency::should_record(&agent_result))
+        {
+            let marker = AppliedMarker {
+                job_id: job_id.clone(),
+                commits: agent_result.commits.clone(),
+                applied_at: Utc::now(),
+            };
+            if let Err(e) = store.record(&item_id, marker).await {
+                warn!("Failed to record idempotency marker for {}: {}", item_id, e);
+            }
+        }
+
+        junit::record(
+            map_phase.junit.as_deref(),
+            TestCase::from_agent_result(&agent_result),
+        );
+
         // Add result to collector
         result_collector.add_result(agent_result.clone()).await;
 
+        Self::report_map_progress(
+            &eta,
+            &job_id,
+            &event_logger,
+            user_interaction.as_ref(),
+            &agent_result,
+        )
+        .await?;
+
         // Add failed items to DLQ (graceful failure - don't break workflow)
         Self::handle_dlq_for_failed_item(
             &agent_result,
@@ -837,6 +1193,66 @@ impl MapReduceCoordinator {
         Ok(agent_result)
     }
 
+    /// Update the map phase estimate once an item finishes
+    ///
+    /// The estimate is shown to the user, logged as a progress event and
+    /// recorded under the job so `prodigy progress` can show it.
+    async fn report_map_progress(
+        eta: &EtaEstimator,
+        job_id: &str,
+        event_logger: &EventLogger,
+        user_interaction: &dyn UserInteraction,
+        agent_result: &AgentResult,
+    ) -> MapReduceResult<()> {
+        let snapshot = eta.finish_item(
+            &agent_result.item_id,
+            agent_result.duration,
+            matches!(agent_result.status, AgentStatus::Success),
+        );
+
+        event_logger
+            .log_event(MapReduceEvent::map_phase_progress(
+                snapshot.completed,
+                snapshot.total,
+                snapshot.remaining,
+            ))
+            .await
+            .map_err(|e| MapReduceError::ProcessingError(e.to_string()))?;
+
+        let done = snapshot.completed >= snapshot.total;
+        if let Some(remaining) = snapshot.remaining.filter(|_| !done) {
+            user_interaction.display_progress(&format!(
+                "Map phase: {}/{} items done, ETA {}",
+                snapshot.completed,
+                snapshot.total,
+                format_duration(remaining)
+            ));
+        }
+
+        let record = StepProgressRecord {
+            source: format!("map: {} items", snapshot.total),
+            step: "map phase".to_string(),
+            phase: Some("map".to_string()),
+            status: if done {
+                StepStatus::Completed
+            } else {
+                StepStatus::Running
+            },
+            progress: StepProgress {
+                current: snapshot.completed as u64,
+                total: snapshot.total as u64,
+                message: None,
+            },
+            started_at: Some(eta.started_at()),
+            eta_secs: snapshot.remaining.map(|eta| eta.as_secs()),
+            updated_at: Utc::now(),
+        };
+        if let Err(e) = step_progress::append_progress(job_id, &record) {
+            debug!("Failed to record map phase progress: {}", e);
+        }
+        Ok(())
+    }
+
     /// Collect results from agent futures, handling errors gracefully
     ///
     /// This helper function waits for all agent futures to complete,
@@ -896,33 +1312,132 @@ impl MapReduceCoordinator {
             .await
             .map_err(|e| MapReduceError::ProcessingError(e.to_string()))?;
 
+        // Derive item IDs up front so a bad item_key fails before any agent starts
+        let item_ids = map_phase
+            .item_key
+            .as_deref()
+            .map(ItemKeyExpr::parse)
+            .transpose()
+            .and_then(|key| assign_item_ids(&work_items, key.as_ref()))
+            .map_err(|reason| MapReduceError::InvalidConfiguration {
+                reason,
+                field: "item_key".to_string(),
+                value: map_phase.item_key.clone().unwrap_or_default(),
+            })?;
+
+        // Estimate the time left from earlier runs of the same agent template
+        let eta = Arc::new(
+            EtaEstimator::open_global(
+                &env.project_dir,
+                &idempotency::template_scope(&map_phase.agent_template),
+                &item_ids,
+                max_parallel,
+            )
+            .await,
+        );
+        if let Some(remaining) = eta.snapshot().remaining {
+            self.user_interaction.display_info(&format!(
+                "Estimated map phase time: {} (based on {} earlier item run(s))",
+                format_duration(remaining),
+                eta.history_len()
+            ));
+        }
+
+        // Load markers of items applied by earlier runs
+        let idempotency = match &map_phase.idempotency {
+            Some(config) => {
+                if map_phase.item_key.is_none() {
+                    warn!("idempotency is enabled without item_key; positional item IDs change when the input is reordered");
+                }
+                let scope = config.resolve_scope(&map_phase.agent_template);
+                let store = IdempotencyStore::open_global(&self.project_root, &scope)
+                    .await
+                    .map_err(|e| {
+                        MapReduceError::ProcessingError(format!(
+                            "Failed to load idempotency markers: {}",
+                            e
+                        ))
+                    })?;
+                let applied = store.len().await;
+                if applied > 0 {
+                    self.user_interaction.display_info(&format!(
+                        "Idempotency scope '{}': items applied by earlier runs will be skipped ({} marker(s))",
+                        store.scope(),
+                        applied
+                    ));
+                }
+                Some(Arc::new(store))
+            }
+            None => None,
+        };
+
         // Create semaphore for parallel control
         let semaphore = Arc::new(Semaphore::new(max_parallel));
 
+        // In a seeded run, agents take slots strictly in item order
+        let turns = map_phase.seed.map(|_| Arc::new(TurnOrder::new()));
+
+        // Items sharing an affinity group run one at a time
+        let affinity = if map_phase.affinity.is_empty() {
+            None
+        } else {
+            let rules = affinity::parse_rules(&map_phase.affinity).map_err(|reason| {
+                MapReduceError::InvalidConfiguration {
+                    reason,
+                    field: "affinity".to_string(),
+                    value: map_phase.affinity.join(", "),
+                }
+            })?;
+            Some(Arc::new(AffinityLocks::new(rules)))
+        };
+
         // Get the timeout enforcer if configured
         let timeout_enforcer = self.timeout_enforcer.lock().await.clone();
 
+        // Track item runtimes when backup agents are enabled
+        let speculation = map_phase
+            .speculative
+            .clone()
+            .map(|config| Arc::new(SpeculationTracker::new(config)));
+
+        // Agents run Claude with the map phase's permissions and shell
+        // commands with its network access
+        let agent_executor = self
+            .command_executor
+            .clone()
+            .with_claude_permissions(map_phase.claude_permissions.clone())
+            .with_network(map_phase.network)
+            .with_strict_variables(map_phase.strict_variables)
+            .with_chaos(map_phase.chaos.clone());
+
         // Spawn parallel agents for each work item
         let agent_futures: Vec<_> = work_items
             .into_iter()
+            .zip(item_ids)
             .enumerate()
-            .map(|(index, item)| {
+            .map(|(index, (item, item_id))| {
                 let sem = Arc::clone(&semaphore);
                 let agent_manager = Arc::clone(&self.agent_manager);
                 let merge_queue = Arc::clone(&self.merge_queue);
                 let event_logger = Arc::clone(&self.event_logger);
                 let result_collector = Arc::clone(&self.result_collector);
                 let user_interaction = Arc::clone(&self.user_interaction);
-                let command_executor = self.command_executor.clone();
+                let command_executor = agent_executor.clone();
                 let dlq = Arc::clone(&self.dlq);
                 let retry_counts = Arc::clone(&self.retry_counts);
                 let map_phase = map_phase.clone();
                 let env = env.clone();
                 let job_id = self.job_id.clone();
                 let timeout_enforcer = timeout_enforcer.clone();
+                let speculation = speculation.clone();
+                let idempotency = idempotency.clone();
+                let eta = eta.clone();
+                let turns = turns.clone();
+                let affinity = affinity.clone();
 
                 tokio::spawn(Self::process_single_work_item(
                     index,
+                    item_id,
                     item,
                     job_id,
                     map_phase,
@@ -937,6 +1452,11 @@ impl MapReduceCoordinator {
                     dlq,
                     retry_counts,
                     timeout_enforcer,
+                    speculation,
+                    idempotency,
+                    eta,
+                    turns,
+                    affinity,
                     total_items,
                 ))
             })
@@ -945,6 +1465,27 @@ impl MapReduceCoordinator {
         // Wait for all agents to complete
         let results = Self::collect_agent_results(agent_futures).await;
 
+        if let Err(e) = eta.save().await {
+            warn!("Failed to save item durations: {}", e);
+        }
+
+        if signal_handler::shutdown_requested().is_some() {
+            self.user_interaction.display_warning(&format!(
+                "Shutdown requested: {} of {} items finished, the rest were not started",
+                results.len(),
+                total_items
+            ));
+            self.stop_if_shutdown_requested("during the map phase")?;
+        }
+
+        if let Some(speculation) = speculation.filter(|s| s.launched() > 0) {
+            self.user_interaction.display_info(&format!(
+                "Speculative execution: {} backup agent(s) started, {} finished first",
+                speculation.launched(),
+                speculation.backup_wins()
+            ));
+        }
+
         // Log map phase completion
         let summary = AggregationSummary::from_results(&results);
         self.event_logger
@@ -1150,7 +1691,7 @@ impl MapReduceCoordinator {
     /// * `user_interaction` - User interaction handler
     ///
     /// # Returns
-    /// Tuple of (output, commits, files_modified)
+    /// Tuple of (output, commits, files_modified, no_changes)
     #[allow(clippy::too_many_arguments)]
     async fn execute_agent_commands(
         handle: &crate::cook::execution::mapreduce::agent::AgentHandle,
@@ -1158,15 +1699,24 @@ impl MapReduceCoordinator {
         item: &Value,
         item_id: &str,
         agent_id: &str,
-        _env: &ExecutionEnvironment,
+        env: &ExecutionEnvironment,
         command_executor: &CommandExecutor,
         timeout_enforcer: Option<&Arc<TimeoutEnforcer>>,
         user_interaction: &Arc<dyn UserInteraction>,
         workflow_env: &HashMap<String, String>,
-    ) -> MapReduceResult<(String, Vec<String>, Vec<String>)> {
+        protected: Option<&crate::cook::protected_paths::ProtectedPaths>,
+    ) -> MapReduceResult<(
+        String,
+        Vec<String>,
+        Vec<String>,
+        Option<crate::cook::no_changes::NoChanges>,
+    )> {
+        // Commands run in the same package subdirectory as the parent workflow
+        let command_dir = env.scoped_dir(handle.worktree_path());
         let mut output = String::new();
         let mut all_commits = Vec::new();
         let mut all_files_modified = Vec::new();
+        let mut no_changes = None;
 
         for (index, step) in commands.iter().enumerate() {
             user_interaction.display_progress(&format!(
@@ -1182,17 +1732,56 @@ impl MapReduceCoordinator {
             let cmd_start = Instant::now();
 
             // Create variables for interpolation (includes workflow env)
-            let variables = Self::build_item_variables(item, item_id, workflow_env);
+            let mut variables = Self::build_item_variables(item, item_id, workflow_env);
+            variables.insert("agent_id".to_string(), agent_id.to_string());
+
+            // Record HEAD so protected path changes and missing commits can be
+            // detected after the step
+            let before_head = if protected.is_some() || step.commit_required {
+                Some(
+                    crate::cook::protected_paths::head(handle.worktree_path())
+                        .await
+                        .map_err(|e| MapReduceError::ProcessingError(e.to_string()))?,
+                )
+            } else {
+                None
+            };
 
             // Execute the step in the agent's worktree
-            let step_result = command_executor
-                .execute_step_in_worktree(
-                    handle.worktree_path(),
+            let step_metadata = ProcessMetadata::new(env.session_id.to_string())
+                .with_job_id(command_executor.job_id())
+                .with_item_id(item_id)
+                .with_worktree(Some(handle.worktree_session.name.as_str()))
+                .with_step_index(index);
+            let mut step_result = metadata::scope(
+                step_metadata.clone(),
+                command_executor.execute_step_in_worktree(
+                    &command_dir,
                     step,
                     &variables,
                     None, // Map phase doesn't need full context
+                ),
+            )
+            .await?;
+
+            if let (Some(protected), Some(before_head)) = (protected, &before_head) {
+                let violation = crate::cook::protected_paths::enforce(
+                    protected,
+                    handle.worktree_path(),
+                    before_head,
                 )
-                .await?;
+                .await
+                .map_err(|e| MapReduceError::ProcessingError(e.to_string()))?;
+                if let Some(violation) = violation {
+                    let message =
+                        violation.describe(&format!("agent {} step {}", agent_id, index + 1));
+                    user_interaction.display_warning(&message);
+                    if !violation.reverted && step_result.success {
+                        step_result.success = false;
+                        step_result.stderr.push_str(&message);
+                    }
+                }
+            }
 
             // Notify timeout enforcer of command completion
             Self::register_command_lifecycle(
@@ -1213,15 +1802,20 @@ impl MapReduceCoordinator {
                     ));
 
                     // Rebuild variables for on_failure handler (should include workflow env)
-                    let failure_variables = Self::build_item_variables(item, item_id, workflow_env);
+                    let mut failure_variables =
+                        Self::build_item_variables(item, item_id, workflow_env);
+                    failure_variables.insert("agent_id".to_string(), agent_id.to_string());
 
                     // Execute on_failure handler
-                    let handler_result = Self::handle_on_failure(
-                        on_failure,
-                        handle.worktree_path(),
-                        &failure_variables,
-                        command_executor,
-                        user_interaction,
+                    let handler_result = metadata::scope(
+                        step_metadata.clone(),
+                        Self::handle_on_failure(
+                            on_failure,
+                            &command_dir,
+                            &failure_variables,
+                            command_executor,
+                            user_interaction,
+                        ),
                     )
                     .await?;
 
@@ -1241,6 +1835,39 @@ impl MapReduceCoordinator {
                 output.push('\n');
             }
 
+            // Fail the item if a commit_required step made no commit, unless
+            // the agent reported that no changes were needed
+            if let (true, true, Some(before_head)) =
+                (step.commit_required, step_result.success, &before_head)
+            {
+                let report =
+                    crate::cook::no_changes::take(handle.worktree_path(), &step_result.stdout);
+                let after_head = crate::cook::protected_paths::head(handle.worktree_path())
+                    .await
+                    .map_err(|e| MapReduceError::ProcessingError(e.to_string()))?;
+                if &after_head == before_head {
+                    let Some(report) = report else {
+                        return Err(MapReduceError::ProcessingError(format!(
+                            "Commit validation failed for agent {} step {}: {}\nCommand: {}",
+                            agent_id,
+                            index + 1,
+                            format_commit_requirement_error(
+                                &Self::get_step_display_name(step),
+                                step_result.json_log_location.as_deref(),
+                            ),
+                            Self::get_step_display_name(step),
+                        )));
+                    };
+                    user_interaction.display_info(&format!(
+                        "Agent {}: step {} {}",
+                        agent_id,
+                        index + 1,
+                        report.describe()
+                    ));
+                    no_changes = Some(report);
+                }
+            }
+
             // Track commits if required
             if step.commit_required {
                 // Get actual commits from git in the worktree
@@ -1253,7 +1880,7 @@ impl MapReduceCoordinator {
             }
         }
 
-        Ok((output, all_commits, all_files_modified))
+        Ok((output, all_commits, all_files_modified, no_changes))
     }
 
     /// Build variables for item interpolation
@@ -1307,6 +1934,14 @@ impl MapReduceCoordinator {
         );
         variables.insert("item_id".to_string(), item_id.to_string());
 
+        // A batch's items as one JSON array
+        if let Some(items) = batching::batch_contents(item) {
+            variables.insert(
+                "items".to_string(),
+                serde_json::to_string(items).unwrap_or_default(),
+            );
+        }
+
         // Log final variable set for agent (debug level)
         debug!(
             item_id = %item_id,
@@ -1333,82 +1968,412 @@ impl MapReduceCoordinator {
         agent_index: usize,
         total_items: usize,
     ) -> MapReduceResult<AgentResult> {
+        let (config, handle) = Self::start_agent(
+            agent_manager,
+            agent_id,
+            item_id,
+            map_phase,
+            env,
+            agent_index,
+            total_items,
+        )
+        .await?;
+
+        let agent_result = Self::run_agent(
+            &handle,
+            &config,
+            &item,
+            map_phase,
+            env,
+            user_interaction,
+            command_executor,
+            timeout_enforcer,
+        )
+        .await?;
+
+        Self::finish_agent_for_item(
+            agent_manager,
+            merge_queue,
+            handle,
+            &config,
+            agent_result,
+            map_phase,
+            env,
+        )
+        .await
+    }
+
+    /// Execute a work item, racing a backup agent against a straggling primary
+    ///
+    /// The primary runs alone until it takes much longer than the median of
+    /// completed items and a parallel slot is free. A backup agent then runs
+    /// the same item in its own worktree; the first to succeed is merged and
+    /// the other is cancelled and cleaned up.
+    #[allow(clippy::too_many_arguments)]
+    async fn execute_speculative_agent_for_item(
+        agent_manager: &Arc<dyn AgentLifecycleManager>,
+        merge_queue: &Arc<MergeQueue>,
+        agent_id: &str,
+        item_id: &str,
+        item: Value,
+        map_phase: &MapPhase,
+        env: &ExecutionEnvironment,
+        user_interaction: &Arc<dyn UserInteraction>,
+        command_executor: &CommandExecutor,
+        timeout_enforcer: Option<&Arc<TimeoutEnforcer>>,
+        semaphore: &Arc<Semaphore>,
+        speculation: &SpeculationTracker,
+        agent_index: usize,
+        total_items: usize,
+    ) -> MapReduceResult<AgentResult> {
+        enum PrimaryProgress {
+            Finished(Box<MapReduceResult<AgentResult>>),
+            Straggling(tokio::sync::OwnedSemaphorePermit),
+        }
+
+        let started = Instant::now();
+        let (primary_config, primary) = Self::start_agent(
+            agent_manager,
+            agent_id,
+            item_id,
+            map_phase,
+            env,
+            agent_index,
+            total_items,
+        )
+        .await?;
+
+        let mut primary_run = Box::pin(Self::run_agent(
+            &primary,
+            &primary_config,
+            &item,
+            map_phase,
+            env,
+            user_interaction,
+            command_executor,
+            timeout_enforcer,
+        ));
+
+        let progress = loop {
+            tokio::select! {
+                result = &mut primary_run => break PrimaryProgress::Finished(Box::new(result)),
+                _ = tokio::time::sleep(STRAGGLER_CHECK_INTERVAL) => {
+                    if speculation.is_straggler(started.elapsed()) {
+                        // Only speculate with spare capacity so queued items are not delayed
+                        if let Ok(permit) = semaphore.clone().try_acquire_owned() {
+                            break PrimaryProgress::Straggling(permit);
+                        }
+                    }
+                }
+            }
+        };
+
+        let permit = match progress {
+            PrimaryProgress::Finished(result) => {
+                drop(primary_run);
+                let agent_result = (*result)?;
+                speculation.record_completion(started.elapsed());
+                return Self::finish_agent_for_item(
+                    agent_manager,
+                    merge_queue,
+                    primary,
+                    &primary_config,
+                    agent_result,
+                    map_phase,
+                    env,
+                )
+                .await;
+            }
+            PrimaryProgress::Straggling(permit) => permit,
+        };
+
+        let backup_id = format!("{}_backup", agent_id);
+        let (backup_config, backup) = match Self::start_agent(
+            agent_manager,
+            &backup_id,
+            item_id,
+            map_phase,
+            env,
+            agent_index,
+            total_items,
+        )
+        .await
+        {
+            Ok(started_backup) => started_backup,
+            Err(e) => {
+                warn!("Failed to start backup agent for item {}: {}", item_id, e);
+                drop(permit);
+                let result = (&mut primary_run).await;
+                drop(primary_run);
+                return Self::finish_agent_for_item(
+                    agent_manager,
+                    merge_queue,
+                    primary,
+                    &primary_config,
+                    result?,
+                    map_phase,
+                    env,
+                )
+                .await;
+            }
+        };
+
+        speculation.record_launch();
+        user_interaction.display_progress(&format!(
+            "Item {} has been running for {}s (median {}s), starting backup agent",
+            item_id,
+            started.elapsed().as_secs(),
+            speculation.median().unwrap_or_default().as_secs()
+        ));
+
+        let mut backup_run = Box::pin(Self::run_agent(
+            &backup,
+            &backup_config,
+            &item,
+            map_phase,
+            env,
+            user_interaction,
+            command_executor,
+            timeout_enforcer,
+        ));
+
+        let first = tokio::select! {
+            result = &mut primary_run => (SpeculativeWinner::Primary, result),
+            result = &mut backup_run => (SpeculativeWinner::Backup, result),
+        };
+
+        // A failed run does not decide the race while the other may still succeed
+        let (winner, result) = match first {
+            (SpeculativeWinner::Primary, Err(e)) => match (&mut backup_run).await {
+                Ok(agent_result) => (SpeculativeWinner::Backup, Ok(agent_result)),
+                Err(_) => (SpeculativeWinner::Primary, Err(e)),
+            },
+            (SpeculativeWinner::Backup, Err(_)) => {
+                (SpeculativeWinner::Primary, (&mut primary_run).await)
+            }
+            decided => decided,
+        };
+
+        // Dropping the runs cancels the loser's commands
+        drop(primary_run);
+        drop(backup_run);
+        drop(permit);
+
+        let ((winner_config, winner_handle), (loser_config, loser_handle)) = match winner {
+            SpeculativeWinner::Primary => ((primary_config, primary), (backup_config, backup)),
+            SpeculativeWinner::Backup => ((backup_config, backup), (primary_config, primary)),
+        };
+
+        Self::unregister_agent_timeout(timeout_enforcer, &loser_config.id).await?;
+        if let Err(e) = agent_manager.cleanup_agent(loser_handle).await {
+            warn!(
+                "Failed to cleanup cancelled agent {}: {}. Worktree may need manual cleanup.",
+                loser_config.id, e
+            );
+        }
+        speculation.record_winner(winner);
+
+        let agent_result = result?;
+        info!(
+            "Agent {} finished item {} first, cancelled {}",
+            winner_config.id, item_id, loser_config.id
+        );
+
+        Self::finish_agent_for_item(
+            agent_manager,
+            merge_queue,
+            winner_handle,
+            &winner_config,
+            agent_result,
+            map_phase,
+            env,
+        )
+        .await
+    }
+
+    /// Create an agent and its worktree for a work item
+    async fn start_agent(
+        agent_manager: &Arc<dyn AgentLifecycleManager>,
+        agent_id: &str,
+        item_id: &str,
+        map_phase: &MapPhase,
+        env: &ExecutionEnvironment,
+        agent_index: usize,
+        total_items: usize,
+    ) -> MapReduceResult<(
+        AgentConfig,
+        crate::cook::execution::mapreduce::agent::AgentHandle,
+    )> {
         info!("Starting agent {} for item {}", agent_id, item_id);
 
         // Create agent config
         let config = AgentConfig {
             id: agent_id.to_string(),
             item_id: item_id.to_string(),
-            branch_name: format!("agent-{}-{}", agent_id, item_id),
+            branch_name: format!("agent-{}-{}", agent_id, branch_safe(item_id)),
             max_retries: 3,
             timeout: Duration::from_secs(600),
             agent_index,
             total_items,
         };
 
-        // Convert agent template to WorkflowSteps
-        let commands = map_phase.agent_template.clone();
-
         // Create the agent with its worktree
-        let handle = agent_manager
-            .create_agent(config.clone(), commands.clone())
+        let mut handle = agent_manager
+            .create_agent(config.clone(), map_phase.agent_template.clone())
             .await
             .map_err(|e| {
                 MapReduceError::ProcessingError(format!("Failed to create agent: {}", e))
             })?;
 
+        if !map_phase.build_cache.is_empty() {
+            handle.build_cache =
+                Self::mount_build_cache(map_phase, env, handle.worktree_path()).await;
+        }
+
+        if let Some(artifacts) = &map_phase.setup_artifacts {
+            if let Err(e) = artifacts.materialize(handle.worktree_path()) {
+                let _ = agent_manager.cleanup_agent(handle).await;
+                return Err(MapReduceError::ProcessingError(format!(
+                    "Failed to copy setup artifacts: {}",
+                    e
+                )));
+            }
+        }
+
+        Ok((config, handle))
+    }
+
+    /// Mount shared build caches into an agent worktree
+    ///
+    /// Returns `None` if the cache directory cannot be determined; individual
+    /// entries that cannot be mounted are skipped inside the mount.
+    async fn mount_build_cache(
+        map_phase: &MapPhase,
+        env: &ExecutionEnvironment,
+        worktree_path: &Path,
+    ) -> Option<BuildCacheMount> {
+        let repo_name = env
+            .project_dir
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| "default".to_string());
+        let cache_root = match crate::storage::get_default_storage_dir() {
+            Ok(dir) => dir.join("cache").join(repo_name),
+            Err(e) => {
+                warn!("Skipping build cache: {}", e);
+                return None;
+            }
+        };
+
+        let entries = map_phase.build_cache.clone();
+        let parent = env.checkout_root();
+        let worktree = worktree_path.to_path_buf();
+        tokio::task::spawn_blocking(move || {
+            mount_build_cache(&entries, &parent, &worktree, &cache_root)
+        })
+        .await
+        .map_err(|e| warn!("Skipping build cache: {}", e))
+        .ok()
+    }
+
+    /// Run an agent's commands with timeout monitoring
+    #[allow(clippy::too_many_arguments)]
+    async fn run_agent(
+        handle: &crate::cook::execution::mapreduce::agent::AgentHandle,
+        config: &AgentConfig,
+        item: &Value,
+        map_phase: &MapPhase,
+        env: &ExecutionEnvironment,
+        user_interaction: &Arc<dyn UserInteraction>,
+        command_executor: &CommandExecutor,
+        timeout_enforcer: Option<&Arc<TimeoutEnforcer>>,
+    ) -> MapReduceResult<AgentResult> {
+        let agent_id = config.id.as_str();
+        let item_id = config.item_id.as_str();
+        let commands = &map_phase.agent_template;
+
         // Register timeout if enforcer is available
         let _timeout_handle =
-            Self::register_agent_timeout(timeout_enforcer, agent_id, item_id, &commands).await;
+            Self::register_agent_timeout(timeout_enforcer, agent_id, item_id, commands).await;
 
-        // Execute commands with timeout monitoring
-        let agent_result = {
-            let start_time = Instant::now();
+        let start_time = Instant::now();
 
-            // Execute all commands
-            let (output, all_commits, all_files_modified) = Self::execute_agent_commands(
-                &handle,
-                &commands,
-                &item,
-                item_id,
-                agent_id,
-                env,
-                command_executor,
-                timeout_enforcer,
-                user_interaction,
-                &map_phase.workflow_env,
-            )
-            .await?;
+        // Execute all commands
+        let (output, all_commits, all_files_modified, no_changes) = Self::execute_agent_commands(
+            handle,
+            commands,
+            item,
+            item_id,
+            agent_id,
+            env,
+            command_executor,
+            timeout_enforcer,
+            user_interaction,
+            &map_phase.workflow_env,
+            map_phase.protected_paths.as_deref(),
+        )
+        .await?;
 
-            // Calculate total duration
-            let total_duration = start_time.elapsed();
-
-            // Build the result
-            AgentResult {
-                item_id: item_id.to_string(),
-                status: AgentStatus::Success,
-                output: Some(output),
-                commits: all_commits.clone(),
-                duration: total_duration,
-                error: None,
-                worktree_path: Some(handle.worktree_path().to_path_buf()),
-                branch_name: Some(handle.worktree_session.branch.clone()),
-                worktree_session_id: Some(agent_id.to_string()),
-                files_modified: all_files_modified.clone(),
-                json_log_location: None,
-                cleanup_status: None,
-            }
+        // Build the result
+        let agent_result = AgentResult {
+            item_id: item_id.to_string(),
+            status: AgentStatus::Success,
+            output: Some(output),
+            commits: all_commits,
+            duration: start_time.elapsed(),
+            error: None,
+            worktree_path: Some(handle.worktree_path().to_path_buf()),
+            branch_name: Some(handle.worktree_session.branch.clone()),
+            worktree_session_id: Some(agent_id.to_string()),
+            files_modified: all_files_modified,
+            json_log_location: None,
+            cleanup_status: None,
+            no_changes,
         };
 
         // Unregister timeout (agent completed)
         Self::unregister_agent_timeout(timeout_enforcer, agent_id).await?;
 
+        Ok(agent_result)
+    }
+
+    /// Rebase and merge a finished agent's work, then clean up its worktree
+    async fn finish_agent_for_item(
+        agent_manager: &Arc<dyn AgentLifecycleManager>,
+        merge_queue: &Arc<MergeQueue>,
+        handle: crate::cook::execution::mapreduce::agent::AgentHandle,
+        config: &AgentConfig,
+        agent_result: AgentResult,
+        map_phase: &MapPhase,
+        env: &ExecutionEnvironment,
+    ) -> MapReduceResult<AgentResult> {
+        let agent_id = config.id.as_str();
+        let item_id = config.item_id.as_str();
+
+        // Pick up commits made on the rebase target since the agent started.
+        // A conflicting rebase fails the item so it lands in the DLQ.
+        if let Some(onto) = map_phase.rebase_onto.as_deref() {
+            if !agent_result.commits.is_empty() {
+                if let Err(e) = agent_manager
+                    .rebase_agent_branch(handle.worktree_path(), onto)
+                    .await
+                {
+                    warn!("Agent {} (item {}): {}", agent_id, item_id, e);
+                    let _ = agent_manager.cleanup_agent(handle).await;
+                    let mut final_result = agent_result;
+                    final_result.status = AgentStatus::Failed(e.to_string());
+                    final_result.error = Some(e.to_string());
+                    return Ok(final_result);
+                }
+            }
+        }
+
         // Merge and cleanup agent if successful
         let merge_successful = Self::merge_and_cleanup_agent(
             agent_manager,
             merge_queue,
             handle,
-            &config,
+            config,
             &agent_result,
             env,
             agent_id,
@@ -1464,6 +2429,8 @@ impl MapReduceCoordinator {
 
             let step = WorkflowStep {
                 claude: Some(cmd.to_string()),
+                context: None,
+                validate_output: None,
                 ..Default::default()
             };
 
@@ -1539,6 +2506,7 @@ impl MapReduceCoordinator {
     /// Creates an InterpolationContext with all reduce phase variables including:
     /// - Scalar summary values (map.successful, map.failed, map.total)
     /// - Full map.results array (for write_file commands)
+    /// - Predicted merge conflicts (map.conflicts, map.conflict_clusters)
     #[cfg_attr(test, allow(dead_code))]
     pub(crate) fn build_reduce_interpolation_context(
         map_results: &[AgentResult],
@@ -1551,25 +2519,102 @@ impl MapReduceCoordinator {
         // Add scalar summary values
         context.set("map.successful", serde_json::json!(summary.successful));
         context.set("map.failed", serde_json::json!(summary.failed));
+        context.set("map.no_changes", serde_json::json!(summary.no_changes));
         context.set("map.total", serde_json::json!(summary.total));
 
         // Add full results as JSON value (for write_file interpolation)
         // This can be >1MB with many agents, so it's excluded from env vars
         // but available for interpolation in write_file commands
-        let results_value = serde_json::to_value(map_results).map_err(|e| {
-            MapReduceError::ProcessingError(format!("Failed to serialize map results: {}", e))
+        context.set("map.results", Self::map_results_value(map_results)?);
+
+        // Add clusters of items whose branches modified the same files
+        let conflicts = predict_conflicts(map_results);
+        context.set(
+            "map.conflict_clusters",
+            serde_json::json!(conflicts.clusters.len()),
+        );
+        let conflicts_value = serde_json::to_value(&conflicts.clusters).map_err(|e| {
+            MapReduceError::ProcessingError(format!("Failed to serialize conflict report: {}", e))
         })?;
-        context.set("map.results", results_value);
+        context.set("map.conflicts", conflicts_value);
 
         Ok(context)
     }
 
+    /// Serialize map results with `status` as a plain lowercase string
+    ///
+    /// Keeps `${map.results | filter 'status == "failed"'}` and the results file
+    /// queryable without knowing the internal enum encoding.
+    pub(crate) fn map_results_value(map_results: &[AgentResult]) -> MapReduceResult<Value> {
+        let mut value = serde_json::to_value(map_results).map_err(|e| {
+            MapReduceError::ProcessingError(format!("Failed to serialize map results: {}", e))
+        })?;
+
+        if let Value::Array(entries) = &mut value {
+            for (entry, result) in entries.iter_mut().zip(map_results) {
+                if let Value::Object(fields) = entry {
+                    let status = match &result.status {
+                        AgentStatus::Success => "success",
+                        AgentStatus::Failed(_) => "failed",
+                        AgentStatus::Timeout => "timeout",
+                        AgentStatus::Pending => "pending",
+                        AgentStatus::Running => "running",
+                        AgentStatus::Retrying(_) => "retrying",
+                    };
+                    fields.insert("status".to_string(), Value::String(status.to_string()));
+                }
+            }
+        }
+
+        Ok(value)
+    }
+
+    /// Write map results as pretty JSON to `results_file` under the working directory
+    pub(crate) async fn write_reduce_results_file(
+        working_dir: &Path,
+        results_file: &str,
+        map_results: &[AgentResult],
+    ) -> MapReduceResult<PathBuf> {
+        let path = working_dir.join(results_file);
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await.map_err(|e| {
+                MapReduceError::ProcessingError(format!(
+                    "Failed to create directory for results file {}: {}",
+                    path.display(),
+                    e
+                ))
+            })?;
+        }
+
+        let json =
+            serde_json::to_string_pretty(&Self::map_results_value(map_results)?).map_err(|e| {
+                MapReduceError::ProcessingError(format!("Failed to serialize map results: {}", e))
+            })?;
+        tokio::fs::write(&path, json).await.map_err(|e| {
+            MapReduceError::ProcessingError(format!(
+                "Failed to write results file {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+
+        info!(
+            "Wrote {} map results to {}",
+            map_results.len(),
+            path.display()
+        );
+        Ok(path)
+    }
+
     /// Execute the reduce phase
     async fn execute_reduce_phase(
         &self,
         reduce: ReducePhase,
         map_results: &[AgentResult],
         env: &ExecutionEnvironment,
+        workflow_env: &HashMap<String, String>,
+        command_executor: CommandExecutor,
+        junit: Option<&JunitRecorder>,
     ) -> MapReduceResult<()> {
         info!("Executing reduce phase");
 
@@ -1586,15 +2631,30 @@ impl MapReduceCoordinator {
             .map_err(|e| MapReduceError::ProcessingError(e.to_string()))?;
 
         // Create LIMITED variables for environment (prevent E2BIG errors)
-        // map.results excluded because it can be >1MB with many agents
-        let mut variables = HashMap::new();
+        // map.results excluded because it can be >1MB with many agents.
+        // Workflow env is included, as it is for setup and map steps
+        let mut variables = workflow_env.clone();
         variables.insert("map.successful".to_string(), summary.successful.to_string());
         variables.insert("map.failed".to_string(), summary.failed.to_string());
+        variables.insert("map.no_changes".to_string(), summary.no_changes.to_string());
         variables.insert("map.total".to_string(), summary.total.to_string());
+        variables.insert(
+            "map.conflict_clusters".to_string(),
+            predict_conflicts(map_results).clusters.len().to_string(),
+        );
 
         // Create FULL interpolation context for write_file commands
         // Includes map.results since interpolation doesn't use env vars
-        let full_context = Self::build_reduce_interpolation_context(map_results, &summary)?;
+        let mut full_context = Self::build_reduce_interpolation_context(map_results, &summary)?;
+
+        // Write full results to a file so shell steps can read them without quoting issues
+        if let Some(results_file) = &reduce.results_file {
+            let path = Self::write_reduce_results_file(&env.working_dir, results_file, map_results)
+                .await?;
+            let path = path.to_string_lossy().to_string();
+            variables.insert("map.results_file".to_string(), path.clone());
+            full_context.set("map.results_file", Value::String(path));
+        }
 
         // Execute reduce commands
         for (index, step) in reduce.commands.iter().enumerate() {
@@ -1604,15 +2664,44 @@ impl MapReduceCoordinator {
                 reduce.commands.len()
             ));
 
-            let step_result = self
-                .command_executor
-                .execute_step_in_worktree(
+            let step_name = Self::get_step_display_name(step);
+            let step_start = Instant::now();
+            let tracker = StepTracker::start(&self.job_id, "reduce", &step_name);
+            let step_result = match metadata::scope(
+                Self::phase_metadata(env, &self.job_id, index),
+                command_executor.execute_step_in_worktree(
                     &env.working_dir,
                     step,
                     &variables,
                     Some(&full_context), // Reduce phase provides full context
-                )
-                .await?;
+                ),
+            )
+            .await
+            {
+                Ok(result) => result,
+                Err(e) => {
+                    junit::record(
+                        junit,
+                        TestCase::from_error("reduce", &step_name, step_start.elapsed(), &e),
+                    );
+                    return Err(e);
+                }
+            };
+            junit::record(
+                junit,
+                TestCase::from_step("reduce", &step_name, step_start.elapsed(), &step_result),
+            );
+
+            let elapsed = tracker.finish(step_result.success);
+            if step_result.success {
+                self.user_interaction.display_success(&format!(
+                    "Reduce [{}/{}]: {} completed in {}",
+                    index + 1,
+                    reduce.commands.len(),
+                    step_name,
+                    format_duration(elapsed)
+                ));
+            }
 
             if !step_result.success {
                 // Handle on_failure if configured
@@ -1622,12 +2711,15 @@ impl MapReduceCoordinator {
                         index + 1
                     ));
 
-                    let handler_result = Self::handle_on_failure(
-                        on_failure,
-                        &env.working_dir,
-                        &variables,
-                        &self.command_executor,
-                        &self.user_interaction,
+                    let handler_result = metadata::scope(
+                        Self::phase_metadata(env, &self.job_id, index),
+                        Self::handle_on_failure(
+                            on_failure,
+                            &env.working_dir,
+                            &variables,
+                            &command_executor,
+                            &self.user_interaction,
+                        ),
                     )
                     .await?;
 
@@ -1654,9 +2746,13 @@ impl MapReduceCoordinator {
 
     /// Display map phase summary
     fn display_map_summary(&self, summary: &AggregationSummary) {
+        let no_changes = match summary.no_changes {
+            0 => String::new(),
+            n => format!(" ({} needed no changes)", n),
+        };
         let message = format!(
-            "Map phase completed: {} successful, {} failed (total: {})",
-            summary.successful, summary.failed, summary.total
+            "Map phase completed: {} successful{}, {} failed (total: {})",
+            summary.successful, no_changes, summary.failed, summary.total
         );
 
         if summary.failed > 0 {
@@ -1666,6 +2762,70 @@ impl MapReduceCoordinator {
         }
     }
 
+    /// Display merge conflicts predicted from overlapping file changes
+    fn display_conflict_report(&self, report: &ConflictReport) {
+        if report.is_empty() {
+            return;
+        }
+
+        self.user_interaction.display_warning(&format!(
+            "Predicted merge conflicts: {} items in {} clusters modified the same files",
+            report.conflicting_items(),
+            report.clusters.len()
+        ));
+        for line in report.summary_lines() {
+            self.user_interaction.display_info(&format!("  {}", line));
+        }
+    }
+
+    /// Open or update a ticket for each DLQ failure cluster
+    ///
+    /// Tracker problems are shown as warnings and never fail the job.
+    async fn notify_failure_issues(&self, config: &IssueNotificationConfig) {
+        let items = match self.dlq.list_items(DLQFilter::default()).await {
+            Ok(items) => items,
+            Err(e) => {
+                warn!("Failed to read DLQ for issue notifications: {}", e);
+                return;
+            }
+        };
+        if items.is_empty() {
+            return;
+        }
+        let tracker = match issue_tracker::create_tracker(config, self.subprocess.clone()) {
+            Ok(tracker) => tracker,
+            Err(e) => {
+                self.user_interaction
+                    .display_warning(&format!("Issue notifications disabled: {}", e));
+                return;
+            }
+        };
+
+        let items: HashMap<_, _> = items
+            .into_iter()
+            .map(|item| (item.item_id.clone(), item))
+            .collect();
+        let results =
+            issue_tracker::sync_failure_issues(tracker.as_ref(), config, &self.job_id, &items)
+                .await;
+        for result in results {
+            match result.action {
+                IssueAction::Created(issue) => self.user_interaction.display_info(&format!(
+                    "Opened issue {} for {} failed items",
+                    issue, result.item_count
+                )),
+                IssueAction::Updated(issue) => self.user_interaction.display_info(&format!(
+                    "Updated issue {} with {} failed items",
+                    issue, result.item_count
+                )),
+                IssueAction::Failed(error) => self.user_interaction.display_warning(&format!(
+                    "Failed to file issue for {}: {}",
+                    result.marker, error
+                )),
+            }
+        }
+    }
+
     /// Display reduce phase summary
     fn display_reduce_summary(&self, summary: &AggregationSummary) {
         self.user_interaction.display_info(&format!(
@@ -127,6 +127,7 @@ mod handle_on_failure_tests {
             project_dir: Arc::new(PathBuf::from("/tmp/test")),
             worktree_name: None,
             session_id: Arc::from("test-session"),
+            package: None,
         }
     }
 
@@ -136,6 +137,7 @@ mod handle_on_failure_tests {
             claude: Some("/test-command".to_string()),
             shell: None,
             max_retries: 1,
+            escalate_model: None,
             fail_workflow: false,
             retry_original: false,
         };
@@ -172,6 +174,7 @@ mod handle_on_failure_tests {
             claude: Some("/test-command".to_string()),
             shell: None,
             max_retries: 1,
+            escalate_model: None,
             fail_workflow: false,
             retry_original: false,
         };
@@ -208,6 +211,7 @@ mod handle_on_failure_tests {
             claude: None,
             shell: Some("echo test".to_string()),
             max_retries: 1,
+            escalate_model: None,
             fail_workflow: false,
             retry_original: false,
         };
@@ -244,6 +248,7 @@ mod handle_on_failure_tests {
             claude: None,
             shell: Some("echo test".to_string()),
             max_retries: 1,
+            escalate_model: None,
             fail_workflow: false,
             retry_original: false,
         };
@@ -436,6 +441,82 @@ mod handle_on_failure_tests {
         // The variable remains unchanged when not found in non-strict mode
         assert!(executed[0].contains("missing_var") || executed[0] == "/test ");
     }
+
+    #[tokio::test]
+    async fn test_strict_variables_fail_agent_steps() {
+        let mock_executor = MockClaudeExecutor::new(true);
+        let claude_executor: Arc<dyn ClaudeExecutor> = Arc::new(mock_executor.clone());
+        let command_executor =
+            crate::cook::execution::mapreduce::coordination::CommandExecutor::new(
+                claude_executor,
+                create_mock_subprocess(true),
+            )
+            .with_strict_variables(true);
+        let worktree_path = PathBuf::from("/tmp/test");
+        // Agents get item fields and the workflow env, including secret parameters
+        let variables = HashMap::from([
+            ("item.name".to_string(), "api".to_string()),
+            ("PRODIGY_PARAM_TOKEN".to_string(), "secret".to_string()),
+        ]);
+
+        let step = crate::cook::workflow::WorkflowStep {
+            claude: Some("/deploy ${item.name} ${PRODIGY_PARAM_TOKEN} ${HOME}".to_string()),
+            ..Default::default()
+        };
+        let result = command_executor
+            .execute_step_in_worktree(&worktree_path, &step, &variables, None)
+            .await;
+        assert!(result.unwrap().success);
+
+        let step = crate::cook::workflow::WorkflowStep {
+            claude: Some("/deploy ${item.name} ${item.region}".to_string()),
+            ..Default::default()
+        };
+        let err = command_executor
+            .execute_step_in_worktree(&worktree_path, &step, &variables, None)
+            .await
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Undefined variable ${item.region}"));
+        assert_eq!(mock_executor.get_executed_commands().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_chaos_applies_only_to_executors_given_the_monkey() {
+        use crate::cook::execution::chaos::{ChaosConfig, ChaosMonkey};
+
+        let mock_executor = MockClaudeExecutor::new(true);
+        let claude_executor: Arc<dyn ClaudeExecutor> = Arc::new(mock_executor.clone());
+        let command_executor =
+            crate::cook::execution::mapreduce::coordination::CommandExecutor::new(
+                claude_executor,
+                create_mock_subprocess(true),
+            );
+        let monkey = Arc::new(ChaosMonkey::new(
+            ChaosConfig::default().fail_percent(100.0),
+            None,
+        ));
+        let chaotic = command_executor.clone().with_chaos(Some(monkey.clone()));
+        let worktree_path = PathBuf::from("/tmp/test");
+        let step = crate::cook::workflow::WorkflowStep {
+            claude: Some("/deploy".to_string()),
+            ..Default::default()
+        };
+
+        let result = chaotic
+            .execute_step_in_worktree(&worktree_path, &step, &HashMap::new(), None)
+            .await;
+        assert!(!result.unwrap().success);
+        assert_eq!(monkey.stats().failures, 1);
+
+        let result = command_executor
+            .execute_step_in_worktree(&worktree_path, &step, &HashMap::new(), None)
+            .await;
+        assert!(result.unwrap().success);
+        assert_eq!(monkey.stats().failures, 1);
+        assert_eq!(mock_executor.get_executed_commands().len(), 1);
+    }
 }
 
 #[cfg(test)]
@@ -561,6 +642,14 @@ mod execute_setup_phase_tests {
                 unimplemented!("Not used in these tests")
             }
 
+            async fn rebase_agent_branch(
+                &self,
+                _worktree_path: &Path,
+                _onto: &str,
+            ) -> Result<(), LifecycleError> {
+                unimplemented!("Not used in these tests")
+            }
+
             async fn merge_agent_to_parent(
                 &self,
                 _agent_branch: &str,
@@ -656,6 +745,7 @@ mod execute_setup_phase_tests {
             project_dir: Arc::new(PathBuf::from("/tmp/test")),
             worktree_name: None,
             session_id: Arc::from("test-session"),
+            package: None,
         }
     }
 
@@ -677,10 +767,12 @@ mod execute_setup_phase_tests {
             ],
             timeout: None,
             capture_outputs: HashMap::new(),
+            artifacts: Vec::new(),
+            shared_context: None,
         };
 
         let result = coordinator
-            .execute_setup_phase(setup, &env, &HashMap::new())
+            .execute_setup_phase(setup, &env, &HashMap::new(), None)
             .await;
         assert!(
             result.is_ok(),
@@ -700,10 +792,12 @@ mod execute_setup_phase_tests {
             }],
             timeout: None,
             capture_outputs: HashMap::new(),
+            artifacts: Vec::new(),
+            shared_context: None,
         };
 
         let result = coordinator
-            .execute_setup_phase(setup, &env, &HashMap::new())
+            .execute_setup_phase(setup, &env, &HashMap::new(), None)
             .await;
         assert!(
             result.is_err(),
@@ -735,10 +829,12 @@ mod execute_setup_phase_tests {
             }],
             timeout: None,
             capture_outputs: HashMap::new(),
+            artifacts: Vec::new(),
+            shared_context: None,
         };
 
         let result = coordinator
-            .execute_setup_phase(setup, &env, &HashMap::new())
+            .execute_setup_phase(setup, &env, &HashMap::new(), None)
             .await;
         assert!(
             result.is_err(),
@@ -764,10 +860,12 @@ mod execute_setup_phase_tests {
             }],
             timeout: None,
             capture_outputs: HashMap::new(),
+            artifacts: Vec::new(),
+            shared_context: None,
         };
 
         let result = coordinator
-            .execute_setup_phase(setup, &env, &HashMap::new())
+            .execute_setup_phase(setup, &env, &HashMap::new(), None)
             .await;
         assert!(result.is_err(), "Setup phase should fail");
 
@@ -787,14 +885,18 @@ mod execute_setup_phase_tests {
         let setup = SetupPhase {
             commands: vec![WorkflowStep {
                 claude: Some("/test-command".to_string()),
+                context: None,
+                validate_output: None,
                 ..Default::default()
             }],
             timeout: None,
             capture_outputs: HashMap::new(),
+            artifacts: Vec::new(),
+            shared_context: None,
         };
 
         let result = coordinator
-            .execute_setup_phase(setup, &env, &HashMap::new())
+            .execute_setup_phase(setup, &env, &HashMap::new(), None)
             .await;
         assert!(
             result.is_err(),
@@ -832,10 +934,12 @@ mod execute_setup_phase_tests {
             ],
             timeout: None,
             capture_outputs: HashMap::new(),
+            artifacts: Vec::new(),
+            shared_context: None,
         };
 
         let result = coordinator
-            .execute_setup_phase(setup, &env, &HashMap::new())
+            .execute_setup_phase(setup, &env, &HashMap::new(), None)
             .await;
         assert!(
             result.is_err(),
@@ -862,10 +966,12 @@ mod execute_setup_phase_tests {
             }],
             timeout: None,
             capture_outputs: HashMap::new(),
+            artifacts: Vec::new(),
+            shared_context: None,
         };
 
         let result = coordinator
-            .execute_setup_phase(setup, &env, &HashMap::new())
+            .execute_setup_phase(setup, &env, &HashMap::new(), None)
             .await;
         // Should succeed - this test verifies env vars are set
         // (actual verification would require checking the subprocess call)
@@ -884,12 +990,14 @@ mod execute_setup_phase_tests {
             }],
             timeout: None,
             capture_outputs: HashMap::new(),
+            artifacts: Vec::new(),
+            shared_context: None,
         };
 
         // This test verifies the function runs without panicking
         // Debug logs are checked via tracing (would need tracing subscriber in real test)
         let result = coordinator
-            .execute_setup_phase(setup, &env, &HashMap::new())
+            .execute_setup_phase(setup, &env, &HashMap::new(), None)
             .await;
         assert!(
             result.is_ok(),
@@ -922,6 +1030,7 @@ mod reduce_interpolation_context_tests {
                 worktree_session_id: None,
                 json_log_location: None,
                 cleanup_status: None,
+                no_changes: None,
             },
             AgentResult {
                 item_id: "item-2".to_string(),
@@ -936,6 +1045,7 @@ mod reduce_interpolation_context_tests {
                 worktree_session_id: None,
                 json_log_location: None,
                 cleanup_status: None,
+                no_changes: None,
             },
         ];
 
@@ -972,6 +1082,59 @@ mod reduce_interpolation_context_tests {
         );
     }
 
+    #[test]
+    fn test_build_reduce_interpolation_context_includes_predicted_conflicts() {
+        let results: Vec<AgentResult> = [("item-1", "src/lib.rs"), ("item-2", "src/lib.rs")]
+            .into_iter()
+            .map(|(item_id, file)| {
+                let mut result =
+                    AgentResult::success(item_id.to_string(), None, Duration::from_secs(1));
+                result.commits = vec![format!("commit-{item_id}")];
+                result.files_modified = vec![file.to_string()];
+                result
+            })
+            .collect();
+        let summary = AggregationSummary::from_results(&results);
+
+        let context =
+            MapReduceCoordinator::build_reduce_interpolation_context(&results, &summary).unwrap();
+
+        assert_eq!(
+            context.variables.get("map.conflict_clusters").unwrap(),
+            &serde_json::json!(1)
+        );
+        assert_eq!(
+            context.variables.get("map.conflicts").unwrap(),
+            &serde_json::json!([{"items": ["item-1", "item-2"], "files": ["src/lib.rs"]}])
+        );
+    }
+
+    #[test]
+    fn test_build_reduce_interpolation_context_counts_no_changes_items() {
+        let mut fixed = AgentResult::success("item-1".to_string(), None, Duration::from_secs(1));
+        fixed.commits = vec!["commit-1".to_string()];
+        let mut unchanged =
+            AgentResult::success("item-2".to_string(), None, Duration::from_secs(1));
+        unchanged.no_changes = Some(crate::cook::no_changes::NoChanges {
+            reason: Some("already covered".to_string()),
+        });
+        let results = vec![fixed, unchanged];
+        let summary = AggregationSummary::from_results(&results);
+        assert_eq!((summary.successful, summary.no_changes), (2, 1));
+
+        let context =
+            MapReduceCoordinator::build_reduce_interpolation_context(&results, &summary).unwrap();
+
+        assert_eq!(
+            context.variables.get("map.no_changes").unwrap(),
+            &serde_json::json!(1)
+        );
+        assert_eq!(
+            context.variables.get("map.results").unwrap()[1]["no_changes"],
+            serde_json::json!({"reason": "already covered"})
+        );
+    }
+
     #[test]
     fn test_build_reduce_interpolation_context_with_empty_results() {
         let results: Vec<AgentResult> = vec![];
@@ -1031,6 +1194,7 @@ mod reduce_interpolation_context_tests {
                 worktree_session_id: None,
                 json_log_location: None,
                 cleanup_status: None,
+                no_changes: None,
             },
             AgentResult {
                 item_id: "item-2".to_string(),
@@ -1045,6 +1209,7 @@ mod reduce_interpolation_context_tests {
                 worktree_session_id: None,
                 json_log_location: Some("/path/to/log.json".to_string()),
                 cleanup_status: None,
+                no_changes: None,
             },
         ];
 
@@ -1116,6 +1281,7 @@ mod reduce_interpolation_context_tests {
             worktree_session_id: None,
             json_log_location: None,
             cleanup_status: None,
+            no_changes: None,
         }];
 
         let summary = AggregationSummary::from_results(&results);
@@ -1127,4 +1293,56 @@ mod reduce_interpolation_context_tests {
             "Valid AgentResult data should serialize successfully"
         );
     }
+
+    #[tokio::test]
+    async fn test_write_reduce_results_file_normalizes_status() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let results = vec![
+            AgentResult {
+                item_id: "item-1".to_string(),
+                status: AgentStatus::Success,
+                output: None,
+                commits: vec![],
+                files_modified: vec![],
+                duration: Duration::from_secs(1),
+                error: None,
+                worktree_path: None,
+                branch_name: None,
+                worktree_session_id: None,
+                json_log_location: None,
+                cleanup_status: None,
+                no_changes: None,
+            },
+            AgentResult {
+                item_id: "item-2".to_string(),
+                status: AgentStatus::Failed("boom".to_string()),
+                output: None,
+                commits: vec![],
+                files_modified: vec![],
+                duration: Duration::from_secs(1),
+                error: Some("boom".to_string()),
+                worktree_path: None,
+                branch_name: None,
+                worktree_session_id: None,
+                json_log_location: None,
+                cleanup_status: None,
+                no_changes: None,
+            },
+        ];
+
+        let path = MapReduceCoordinator::write_reduce_results_file(
+            temp_dir.path(),
+            ".prodigy/results.json",
+            &results,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(path, temp_dir.path().join(".prodigy/results.json"));
+        let written: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(written[0]["status"], "success");
+        assert_eq!(written[1]["status"], "failed");
+        assert_eq!(written[1]["error"], "boom");
+    }
 }
@@ -65,6 +65,7 @@ use std::collections::HashMap;
 ///     worktree_session_id: None,
 ///     json_log_location: Some("/path/to/log.json".to_string()),
 ///     cleanup_status: None,
+///     no_changes: None,
 /// };
 /// let work_item = json!({"id": 1, "data": "test"});
 ///
@@ -445,6 +446,7 @@ mod tests {
             worktree_session_id: Some("session-123".to_string()),
             json_log_location: Some("/path/to/log.json".to_string()),
             cleanup_status: Some(CleanupStatus::Success),
+            no_changes: None,
         }
     }
 
@@ -462,6 +464,7 @@ mod tests {
             worktree_session_id: Some("session-456".to_string()),
             json_log_location: Some("/path/to/log2.json".to_string()),
             cleanup_status: Some(CleanupStatus::Success),
+            no_changes: None,
         }
     }
 
@@ -479,6 +482,7 @@ mod tests {
             worktree_session_id: Some("session-789".to_string()),
             json_log_location: Some("/path/to/log3.json".to_string()),
             cleanup_status: Some(CleanupStatus::Success),
+            no_changes: None,
         }
     }
 
@@ -555,6 +559,14 @@ mod tests {
         assert_eq!(error_type, ErrorType::MergeConflict);
     }
 
+    #[test]
+    fn test_classify_error_rebase_conflict() {
+        let error = "Rebase conflict: Failed to rebase onto main: CONFLICT (content)";
+        let status = AgentStatus::Failed(error.to_string());
+
+        assert_eq!(classify_error(&status, error), ErrorType::MergeConflict);
+    }
+
     #[test]
     fn test_classify_error_worktree() {
         let status = AgentStatus::Failed("worktree error".to_string());
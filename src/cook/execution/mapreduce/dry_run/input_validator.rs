@@ -3,6 +3,7 @@
 //! Validates input sources and JSONPath expressions without executing commands.
 
 use super::types::{DryRunError, InputValidation, JsonPathValidation};
+use crate::cook::execution::github_input::GitHubInput;
 use serde_json::Value;
 use std::collections::HashMap;
 use std::path::Path;
@@ -24,6 +25,15 @@ impl InputValidator {
 
         if input.starts_with("shell:") {
             self.validate_command_input(input).await
+        } else if GitHubInput::is_github_input(input) {
+            // Checked for syntax only; dry runs don't call the GitHub API
+            Ok(InputValidation {
+                source: input.to_string(),
+                valid: GitHubInput::parse(input).is_ok(),
+                size_bytes: 0,
+                item_count_estimate: 0,
+                data_structure: "github".to_string(),
+            })
         } else if Path::new(input).exists() {
             self.validate_file_input(input).await
         } else {
@@ -106,6 +116,10 @@ impl InputValidator {
             warn!("Command input in dry-run mode, returning empty work items");
             return Ok(Vec::new());
         }
+        if GitHubInput::is_github_input(input) {
+            warn!("GitHub input in dry-run mode, returning empty work items");
+            return Ok(Vec::new());
+        }
 
         // Load from file
         if !Path::new(input).exists() {
@@ -19,6 +19,9 @@ fn create_workflow_step() -> WorkflowStep {
     WorkflowStep {
         name: None,
         claude: None,
+        model: None,
+        context: None,
+        validate_output: None,
         shell: None,
         test: None,
         foreach: None,
@@ -46,6 +49,13 @@ fn create_workflow_step() -> WorkflowStep {
         validation_timeout: None,
         ignore_validation_failure: false,
         when: None,
+        assert: None,
+        summarize_commits: None,
+        claude_chat: None,
+        script: None,
+        snapshot: false,
+        required: false,
+        commit_policy: None,
     }
 }
 
@@ -83,6 +93,24 @@ fn create_test_map_phase() -> MapPhase {
         distinct: None,
         timeout_config: None,
         workflow_env: std::collections::HashMap::new(),
+        rebase_onto: None,
+        speculative: None,
+        item_key: None,
+        affinity: Vec::new(),
+        build_cache: Vec::new(),
+        network: Default::default(),
+        strict_variables: false,
+        setup_artifacts: None,
+        chaos: None,
+        junit: None,
+        protected_paths: None,
+        seed: None,
+        failures: None,
+        idempotency: None,
+        notifications: None,
+        claude_permissions: None,
+        changed_since: None,
+        changed_path: None,
     }
 }
 
@@ -104,6 +132,8 @@ fn create_test_setup_phase() -> SetupPhase {
         ],
         timeout: Some(60),
         capture_outputs: std::collections::HashMap::new(),
+        artifacts: Vec::new(),
+        shared_context: None,
     }
 }
 
@@ -117,6 +147,8 @@ fn create_test_reduce_phase() -> ReducePhase {
             step
         }],
         timeout_secs: Some(120),
+        results_file: None,
+        claude_permissions: None,
     }
 }
 
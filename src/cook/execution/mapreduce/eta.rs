@@ -0,0 +1,371 @@
+//! Remaining-time estimates for the map phase
+//!
+//! The durations of successful map items are kept per agent template under
+//! `~/.prodigy/state/{repo}/item_durations/{scope}.json`, where the scope is a
+//! digest of the template commands, so re-running the same workflow reuses
+//! the timings of earlier runs and editing the template starts over.
+//!
+//! While the map phase runs, each remaining item is expected to take as long
+//! as it took last time, or the median item duration when it has never run.
+//! Historical durations are scaled by how much faster or slower the items
+//! completed so far ran compared to their history, and the remaining work is
+//! spread over the parallel agents.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Recent durations kept for the median of items without their own history
+pub const HISTORY_LIMIT: usize = 500;
+
+/// Items this run must complete before its own median replaces the history
+pub const MIN_CURRENT_SAMPLES: usize = 3;
+
+/// Durations recorded by earlier runs of an agent template
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DurationHistory {
+    /// Last successful duration of each item, in seconds
+    #[serde(default)]
+    pub items: HashMap<String, f64>,
+    /// Most recent successful durations, oldest first, in seconds
+    #[serde(default)]
+    pub recent: Vec<f64>,
+}
+
+impl DurationHistory {
+    /// Add a successful item duration, keeping at most [`HISTORY_LIMIT`] recent ones
+    pub fn record(&mut self, item_id: &str, secs: f64) {
+        self.items.insert(item_id.to_string(), secs);
+        self.recent.push(secs);
+        if self.recent.len() > HISTORY_LIMIT {
+            let excess = self.recent.len() - HISTORY_LIMIT;
+            self.recent.drain(..excess);
+        }
+    }
+
+    /// Whether no durations are recorded
+    pub fn is_empty(&self) -> bool {
+        self.recent.is_empty() && self.items.is_empty()
+    }
+}
+
+/// Median of a set of durations (pure function)
+pub fn median(values: &[f64]) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let mid = sorted.len() / 2;
+    Some(if sorted.len() % 2 == 0 {
+        (sorted[mid - 1] + sorted[mid]) / 2.0
+    } else {
+        sorted[mid]
+    })
+}
+
+/// Estimate the time left for `remaining` items (pure function)
+///
+/// `completed` holds the successful items of this run with their durations.
+/// Returns `None` when neither the history nor this run has any timings.
+pub fn estimate_remaining(
+    history: &DurationHistory,
+    completed: &[(String, f64)],
+    remaining: &[&str],
+    max_parallel: usize,
+) -> Option<Duration> {
+    if remaining.is_empty() {
+        return Some(Duration::ZERO);
+    }
+
+    let current: Vec<f64> = completed.iter().map(|(_, secs)| *secs).collect();
+    let fallback = if current.len() >= MIN_CURRENT_SAMPLES {
+        median(&current)
+    } else {
+        median(&history.recent)
+            .or_else(|| median(&history.items.values().copied().collect::<Vec<_>>()))
+            .or_else(|| median(&current))
+    };
+
+    // How this run compares to earlier runs on the items both have timed
+    let ratios: Vec<f64> = completed
+        .iter()
+        .filter_map(|(id, secs)| {
+            let before = *history.items.get(id)?;
+            (before > 0.0).then(|| secs / before)
+        })
+        .collect();
+    let speed = median(&ratios).unwrap_or(1.0);
+
+    let mut total = 0.0;
+    let mut longest: f64 = 0.0;
+    for id in remaining {
+        let expected = match history.items.get(*id) {
+            Some(secs) => secs * speed,
+            None => fallback?,
+        };
+        total += expected;
+        longest = longest.max(expected);
+    }
+
+    let lanes = max_parallel.clamp(1, remaining.len()) as f64;
+    Some(Duration::from_secs_f64((total / lanes).max(longest)))
+}
+
+/// Map phase progress with the estimated time left
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EtaSnapshot {
+    pub completed: usize,
+    pub total: usize,
+    pub remaining: Option<Duration>,
+}
+
+#[derive(Debug, Default)]
+struct RunTimings {
+    pending: HashSet<String>,
+    completed: Vec<(String, f64)>,
+    finished: usize,
+}
+
+/// Tracks the items of a running map phase and estimates the time left
+#[derive(Debug)]
+pub struct EtaEstimator {
+    path: Option<PathBuf>,
+    started_at: DateTime<Utc>,
+    history: DurationHistory,
+    max_parallel: usize,
+    total: usize,
+    run: Mutex<RunTimings>,
+}
+
+impl EtaEstimator {
+    /// Estimator for `item_ids`, without history
+    pub fn new(item_ids: &[String], max_parallel: usize) -> Self {
+        Self {
+            path: None,
+            started_at: Utc::now(),
+            history: DurationHistory::default(),
+            max_parallel,
+            total: item_ids.len(),
+            run: Mutex::new(RunTimings {
+                pending: item_ids.iter().cloned().collect(),
+                ..RunTimings::default()
+            }),
+        }
+    }
+
+    /// Estimator using the history of `scope` in the global state of `repo_path`
+    ///
+    /// A missing or unreadable history leaves the estimate to this run's timings.
+    pub async fn open_global(
+        repo_path: &Path,
+        scope: &str,
+        item_ids: &[String],
+        max_parallel: usize,
+    ) -> Self {
+        let path = match history_path(repo_path, scope).await {
+            Ok(path) => path,
+            Err(e) => {
+                tracing::debug!("Item duration history unavailable: {}", e);
+                return Self::new(item_ids, max_parallel);
+            }
+        };
+        Self::open(path, item_ids, max_parallel).await
+    }
+
+    /// Estimator using the history stored at `path`
+    pub async fn open(path: PathBuf, item_ids: &[String], max_parallel: usize) -> Self {
+        let history = match tokio::fs::read_to_string(&path).await {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                tracing::warn!("Ignoring unreadable {}: {}", path.display(), e);
+                DurationHistory::default()
+            }),
+            Err(_) => DurationHistory::default(),
+        };
+        Self {
+            path: Some(path),
+            history,
+            ..Self::new(item_ids, max_parallel)
+        }
+    }
+
+    /// When the estimator was created, at the start of the map phase
+    pub fn started_at(&self) -> DateTime<Utc> {
+        self.started_at
+    }
+
+    /// Number of historical durations the estimate starts from
+    pub fn history_len(&self) -> usize {
+        self.history.recent.len()
+    }
+
+    /// Current progress and estimate
+    pub fn snapshot(&self) -> EtaSnapshot {
+        let run = self.lock();
+        let remaining: Vec<&str> = run.pending.iter().map(String::as_str).collect();
+        EtaSnapshot {
+            completed: run.finished,
+            total: self.total,
+            remaining: estimate_remaining(
+                &self.history,
+                &run.completed,
+                &remaining,
+                self.max_parallel,
+            ),
+        }
+    }
+
+    /// Record that an item finished, returning the updated estimate
+    ///
+    /// Only successful items with a measured duration feed the estimate.
+    pub fn finish_item(&self, item_id: &str, duration: Duration, success: bool) -> EtaSnapshot {
+        {
+            let mut run = self.lock();
+            if run.pending.remove(item_id) {
+                run.finished += 1;
+            }
+            if success && !duration.is_zero() {
+                run.completed
+                    .push((item_id.to_string(), duration.as_secs_f64()));
+            }
+        }
+        self.snapshot()
+    }
+
+    /// Add this run's successful durations to the stored history
+    pub async fn save(&self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let completed = self.lock().completed.clone();
+        if completed.is_empty() {
+            return Ok(());
+        }
+
+        let mut history = self.history.clone();
+        for (item_id, secs) in &completed {
+            history.record(item_id, *secs);
+        }
+
+        let content = serde_json::to_string_pretty(&history)?;
+        let temp_path = path.with_extension("json.tmp");
+        tokio::fs::write(&temp_path, content)
+            .await
+            .with_context(|| format!("Failed to write {}", temp_path.display()))?;
+        tokio::fs::rename(&temp_path, path)
+            .await
+            .with_context(|| format!("Failed to replace {}", path.display()))?;
+        Ok(())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, RunTimings> {
+        self.run.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+async fn history_path(repo_path: &Path, scope: &str) -> Result<PathBuf> {
+    let storage = crate::storage::GlobalStorage::new()?;
+    let repo_name = crate::storage::extract_repo_name(repo_path)?;
+    let dir = storage
+        .get_state_base_dir(&repo_name)
+        .await?
+        .join("item_durations");
+    tokio::fs::create_dir_all(&dir)
+        .await
+        .with_context(|| format!("Failed to create {}", dir.display()))?;
+    Ok(dir.join(format!("{}.json", scope)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn history(items: &[(&str, f64)]) -> DurationHistory {
+        let mut history = DurationHistory::default();
+        for (id, secs) in items {
+            history.record(id, *secs);
+        }
+        history
+    }
+
+    #[test]
+    fn test_estimate_remaining() {
+        let history = history(&[("a", 10.0), ("b", 20.0), ("c", 30.0)]);
+
+        // Known items use their own duration, unknown ones the median
+        assert_eq!(
+            estimate_remaining(&history, &[], &["a", "b", "new"], 1),
+            Some(Duration::from_secs(50))
+        );
+        // Work is spread over the agents, but never below the longest item
+        assert_eq!(
+            estimate_remaining(&history, &[], &["a", "b", "c"], 2),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            estimate_remaining(&history, &[], &["a", "b", "c", "new"], 4),
+            Some(Duration::from_secs(30))
+        );
+        // Items finished this run twice as slowly as before
+        assert_eq!(
+            estimate_remaining(&history, &[("a".to_string(), 20.0)], &["b", "c"], 1),
+            Some(Duration::from_secs(100))
+        );
+        assert_eq!(
+            estimate_remaining(&history, &[], &[], 4),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(
+            estimate_remaining(&DurationHistory::default(), &[], &["a"], 1),
+            None
+        );
+        // Without history, this run's own timings are used
+        assert_eq!(
+            estimate_remaining(
+                &DurationHistory::default(),
+                &[("x".to_string(), 8.0)],
+                &["a", "b"],
+                1
+            ),
+            Some(Duration::from_secs(16))
+        );
+    }
+
+    #[test]
+    fn test_history_keeps_recent_durations() {
+        let mut history = DurationHistory::default();
+        for i in 0..HISTORY_LIMIT + 10 {
+            history.record("same", i as f64);
+        }
+        assert_eq!(history.recent.len(), HISTORY_LIMIT);
+        assert_eq!(history.recent[0], 10.0);
+        assert_eq!(history.items["same"], (HISTORY_LIMIT + 9) as f64);
+        assert_eq!(median(&[3.0, 1.0, 2.0, 4.0]), Some(2.5));
+    }
+
+    #[tokio::test]
+    async fn test_estimator_uses_saved_history() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("template-abc.json");
+        let ids: Vec<String> = ["a", "b"].iter().map(|s| s.to_string()).collect();
+
+        let first = EtaEstimator::open(path.clone(), &ids, 1).await;
+        assert_eq!(first.snapshot().remaining, None);
+        let snapshot = first.finish_item("a", Duration::from_secs(12), true);
+        assert_eq!(snapshot.completed, 1);
+        assert_eq!(snapshot.remaining, Some(Duration::from_secs(12)));
+        first.finish_item("b", Duration::from_secs(4), false);
+        first.save().await.unwrap();
+
+        let second = EtaEstimator::open(path, &ids, 1).await;
+        assert_eq!(second.history_len(), 1);
+        let snapshot = second.snapshot();
+        assert_eq!((snapshot.completed, snapshot.total), (0, 2));
+        assert_eq!(snapshot.remaining, Some(Duration::from_secs(24)));
+    }
+}
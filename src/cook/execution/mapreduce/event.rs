@@ -103,9 +103,10 @@ impl EventLogger {
                 | MapReduceEvent::ReducePhaseCompleted { .. } => {
                     tracing::info!("MapReduce event: {:?}", event);
                 }
-                // Suppress AgentStarted, AgentCompleted, and AgentFailed in default mode
+                // Suppress progress, AgentStarted, AgentCompleted, and AgentFailed in default mode
                 // (AgentFailed is already handled above and always shown)
-                MapReduceEvent::AgentStarted { .. }
+                MapReduceEvent::MapPhaseProgress { .. }
+                | MapReduceEvent::AgentStarted { .. }
                 | MapReduceEvent::AgentCompleted { .. }
                 | MapReduceEvent::AgentFailed { .. } => {}
             }
@@ -129,6 +130,13 @@ pub enum MapReduceEvent {
         failed: usize,
         timestamp: DateTime<Utc>,
     },
+    /// An item finished, with the estimated time left in the map phase
+    MapPhaseProgress {
+        completed: usize,
+        total: usize,
+        eta_secs: Option<u64>,
+        timestamp: DateTime<Utc>,
+    },
     /// Agent started processing an item
     AgentStarted {
         agent_id: String,
@@ -178,6 +186,20 @@ impl MapReduceEvent {
         }
     }
 
+    /// Create map phase progress event
+    pub fn map_phase_progress(
+        completed: usize,
+        total: usize,
+        eta: Option<std::time::Duration>,
+    ) -> Self {
+        Self::MapPhaseProgress {
+            completed,
+            total,
+            eta_secs: eta.map(|eta| eta.as_secs()),
+            timestamp: Utc::now(),
+        }
+    }
+
     /// Create agent started event
     pub fn agent_started(agent_id: String, item_id: String) -> Self {
         Self::AgentStarted {
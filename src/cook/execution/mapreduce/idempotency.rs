@@ -0,0 +1,307 @@
+//! Idempotency guards for map phase agents
+//!
+//! Retrying or re-running a job normally executes every item again, so an item
+//! whose fix was already merged gets a second, duplicate commit. With
+//! `idempotency` enabled, a marker is recorded for each item once its agent
+//! succeeds and merges, and items with a marker are skipped on later runs:
+//!
+//! ```yaml
+//! map:
+//!   item_key: "$.location.file + :: + $.location.function"
+//!   agent_template:
+//!     idempotency:
+//!       scope: fix-debt   # markers are shared by runs using the same scope
+//!     commands:
+//!       - claude: "/fix ${item.location.file}"
+//! ```
+//!
+//! `idempotency: true` enables markers with a scope derived from the agent
+//! template commands, so editing the template starts with a clean slate.
+//! Markers are keyed by item ID, so they need a stable `item_key`.
+//! They live under `~/.prodigy/state/{repo}/idempotency/{scope}.json`; delete
+//! that file to apply every item again.
+
+use super::agent::{AgentResult, AgentStatus};
+use crate::cook::workflow::WorkflowStep;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tokio::sync::Mutex;
+
+/// Settings for recording and honoring applied-item markers
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct IdempotencyConfig {
+    /// Name shared by runs whose markers should apply to each other
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+}
+
+impl IdempotencyConfig {
+    /// Scope to store markers under, derived from the commands when unset
+    pub fn resolve_scope(&self, commands: &[WorkflowStep]) -> String {
+        if let Some(scope) = self.scope.as_deref().filter(|s| !s.trim().is_empty()) {
+            return scope.trim().to_string();
+        }
+        template_scope(commands)
+    }
+}
+
+/// Scope identifying an agent template by a digest of its commands
+pub fn template_scope(commands: &[WorkflowStep]) -> String {
+    let serialized = serde_json::to_string(commands).unwrap_or_default();
+    let digest = Sha256::digest(serialized.as_bytes());
+    let hex: String = digest
+        .iter()
+        .take(6)
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    format!("template-{}", hex)
+}
+
+/// Deserialize `idempotency: true|false` or a settings map
+pub fn deserialize_idempotency<'de, D>(
+    deserializer: D,
+) -> Result<Option<IdempotencyConfig>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum IdempotencyValue {
+        Enabled(bool),
+        Config(IdempotencyConfig),
+    }
+
+    Ok(
+        match Option::<IdempotencyValue>::deserialize(deserializer)? {
+            None | Some(IdempotencyValue::Enabled(false)) => None,
+            Some(IdempotencyValue::Enabled(true)) => Some(IdempotencyConfig::default()),
+            Some(IdempotencyValue::Config(config)) => Some(config),
+        },
+    )
+}
+
+/// Record that an item was applied
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AppliedMarker {
+    /// Job that applied the item
+    pub job_id: String,
+    /// Commits merged for the item
+    #[serde(default)]
+    pub commits: Vec<String>,
+    /// When the marker was recorded
+    pub applied_at: DateTime<Utc>,
+}
+
+/// Whether a result should leave a marker (pure function)
+pub fn should_record(result: &AgentResult) -> bool {
+    matches!(result.status, AgentStatus::Success)
+}
+
+/// Result for an item skipped because it already has a marker
+pub fn skipped_result(item_id: &str, marker: &AppliedMarker) -> AgentResult {
+    let mut result = AgentResult::success(
+        item_id.to_string(),
+        Some(format!(
+            "Skipped: already applied by job {} at {}",
+            marker.job_id,
+            marker.applied_at.to_rfc3339()
+        )),
+        std::time::Duration::ZERO,
+    );
+    result.commits = Vec::new();
+    result
+}
+
+/// Make a scope safe to use as a file name
+fn scope_file_name(scope: &str) -> String {
+    let safe: String = scope
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("{}.json", safe)
+}
+
+/// Persistent applied-item markers for one scope
+#[derive(Debug)]
+pub struct IdempotencyStore {
+    path: PathBuf,
+    scope: String,
+    markers: Mutex<HashMap<String, AppliedMarker>>,
+}
+
+impl IdempotencyStore {
+    /// Open the store for `scope` in the global state directory of `repo_path`
+    pub async fn open_global(repo_path: &Path, scope: &str) -> Result<Self> {
+        let storage = crate::storage::GlobalStorage::new()?;
+        let repo_name = crate::storage::extract_repo_name(repo_path)?;
+        let dir = storage
+            .get_state_base_dir(&repo_name)
+            .await?
+            .join("idempotency");
+        Self::open(&dir, scope).await
+    }
+
+    /// Open the store for `scope` in `dir`, loading existing markers
+    pub async fn open(dir: &Path, scope: &str) -> Result<Self> {
+        tokio::fs::create_dir_all(dir)
+            .await
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+        let path = dir.join(scope_file_name(scope));
+
+        let markers = match tokio::fs::read_to_string(&path).await {
+            Ok(content) => serde_json::from_str(&content)
+                .with_context(|| format!("Failed to parse {}", path.display()))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => HashMap::new(),
+            Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        };
+
+        Ok(Self {
+            path,
+            scope: scope.to_string(),
+            markers: Mutex::new(markers),
+        })
+    }
+
+    /// Scope the markers belong to
+    pub fn scope(&self) -> &str {
+        &self.scope
+    }
+
+    /// Marker for an item, if it was already applied
+    pub async fn applied(&self, item_id: &str) -> Option<AppliedMarker> {
+        self.markers.lock().await.get(item_id).cloned()
+    }
+
+    /// Number of recorded markers
+    pub async fn len(&self) -> usize {
+        self.markers.lock().await.len()
+    }
+
+    /// Whether no markers are recorded
+    pub async fn is_empty(&self) -> bool {
+        self.markers.lock().await.is_empty()
+    }
+
+    /// Record a marker for an item and persist the store
+    pub async fn record(&self, item_id: &str, marker: AppliedMarker) -> Result<()> {
+        let mut markers = self.markers.lock().await;
+        markers.insert(item_id.to_string(), marker);
+
+        let content = serde_json::to_string_pretty(&*markers)?;
+        let temp_path = self.path.with_extension("json.tmp");
+        tokio::fs::write(&temp_path, content)
+            .await
+            .with_context(|| format!("Failed to write {}", temp_path.display()))?;
+        tokio::fs::rename(&temp_path, &self.path)
+            .await
+            .with_context(|| format!("Failed to replace {}", self.path.display()))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    fn marker(job_id: &str) -> AppliedMarker {
+        AppliedMarker {
+            job_id: job_id.to_string(),
+            commits: vec!["abc123".to_string()],
+            applied_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_deserialize_idempotency() {
+        #[derive(Deserialize)]
+        struct Wrapper {
+            #[serde(default, deserialize_with = "deserialize_idempotency")]
+            idempotency: Option<IdempotencyConfig>,
+        }
+
+        let parse = |yaml: &str| serde_yaml::from_str::<Wrapper>(yaml).unwrap().idempotency;
+        assert_eq!(
+            parse("idempotency: true"),
+            Some(IdempotencyConfig::default())
+        );
+        assert_eq!(parse("idempotency: false"), None);
+        assert_eq!(parse("{}"), None);
+        assert_eq!(
+            parse("idempotency:\n  scope: fix-debt"),
+            Some(IdempotencyConfig {
+                scope: Some("fix-debt".to_string())
+            })
+        );
+    }
+
+    #[test]
+    fn test_resolve_scope() {
+        let commands = vec![WorkflowStep {
+            shell: Some("echo fix".to_string()),
+            ..Default::default()
+        }];
+        let other = vec![WorkflowStep {
+            shell: Some("echo other".to_string()),
+            ..Default::default()
+        }];
+
+        let derived = IdempotencyConfig::default().resolve_scope(&commands);
+        assert!(derived.starts_with("template-"));
+        assert_eq!(
+            derived,
+            IdempotencyConfig::default().resolve_scope(&commands)
+        );
+        assert_ne!(derived, IdempotencyConfig::default().resolve_scope(&other));
+
+        let named = IdempotencyConfig {
+            scope: Some("fix-debt".to_string()),
+        };
+        assert_eq!(named.resolve_scope(&commands), "fix-debt");
+    }
+
+    #[test]
+    fn test_should_record_and_skipped_result() {
+        let success = AgentResult::success("a".to_string(), None, Duration::from_secs(1));
+        let failed = AgentResult::failed("a".to_string(), "boom".to_string(), Duration::ZERO);
+        assert!(should_record(&success));
+        assert!(!should_record(&failed));
+
+        let skipped = skipped_result("a", &marker("job-1"));
+        assert_eq!(skipped.status, AgentStatus::Success);
+        assert!(skipped.commits.is_empty());
+        assert!(skipped.output.unwrap().contains("job-1"));
+    }
+
+    #[tokio::test]
+    async fn test_store_persists_markers() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = IdempotencyStore::open(temp_dir.path(), "fix/debt")
+            .await
+            .unwrap();
+        assert!(store.is_empty().await);
+
+        store.record("item-1", marker("job-1")).await.unwrap();
+        assert!(temp_dir.path().join("fix_debt.json").exists());
+
+        let reopened = IdempotencyStore::open(temp_dir.path(), "fix/debt")
+            .await
+            .unwrap();
+        assert_eq!(reopened.applied("item-1").await.unwrap().job_id, "job-1");
+        assert!(reopened.applied("item-2").await.is_none());
+
+        reopened.record("item-2", marker("job-2")).await.unwrap();
+        assert_eq!(reopened.len().await, 2);
+    }
+}
@@ -0,0 +1,792 @@
+//! Issue tracker tickets for DLQ failure clusters
+//!
+//! After a MapReduce job ends, failed items in its Dead Letter Queue are
+//! clustered by normalized error (see [`cluster_failures`]) and each cluster
+//! can open a ticket, or comment on the open ticket for the same cluster from
+//! an earlier run:
+//!
+//! ```yaml
+//! notifications:
+//!   issues:
+//!     provider: github          # or jira
+//!     repo: owner/name          # defaults to the repository gh detects
+//!     labels: [prodigy]
+//!     min_items: 2              # skip clusters with fewer failed items
+//!     report_url: "https://ci.example.com/reports/${job_id}.html"
+//! ```
+//!
+//! GitHub tickets are managed with the `gh` CLI, so it must be installed and
+//! authenticated. Jira tickets use the REST API through `curl`, with `url` and
+//! `project` set in the config and credentials read from `JIRA_EMAIL` and
+//! `JIRA_API_TOKEN`. Each ticket carries a `prodigy-cluster-<fingerprint>`
+//! marker derived from the normalized error and failing step, which is how
+//! later runs find it again.
+
+use crate::cook::execution::dlq::DeadLetteredItem;
+use crate::cook::execution::dlq_clustering::{cluster_failures, FailureCluster};
+use crate::subprocess::{ProcessCommandBuilder, SubprocessManager};
+use anyhow::{anyhow, bail, Context, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Items listed in a ticket body before the rest are summarized
+const MAX_LISTED_ITEMS: usize = 20;
+
+/// Characters of item data shown per sample
+const MAX_ITEM_DATA_CHARS: usize = 2000;
+
+/// Notification settings for a MapReduce workflow
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NotificationsConfig {
+    /// Open or update tickets for failure clusters when a job ends
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issues: Option<IssueNotificationConfig>,
+}
+
+/// Issue tracker that receives failure tickets
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueProvider {
+    #[default]
+    Github,
+    Jira,
+}
+
+/// Settings for failure-cluster tickets
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IssueNotificationConfig {
+    #[serde(default)]
+    pub provider: IssueProvider,
+    /// GitHub repository as `owner/name`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repo: Option<String>,
+    /// Jira base URL, e.g. `https://example.atlassian.net`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Jira project key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    /// Jira issue type for new tickets
+    #[serde(default = "default_issue_type")]
+    pub issue_type: String,
+    /// Labels added to new tickets (GitHub labels must already exist)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<String>,
+    /// Smallest cluster that gets a ticket
+    #[serde(default = "default_min_items")]
+    pub min_items: usize,
+    /// Most tickets opened or updated per job, largest clusters first
+    #[serde(default = "default_max_issues")]
+    pub max_issues: usize,
+    /// Link to the job report; `${job_id}` is replaced with the job ID
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub report_url: Option<String>,
+}
+
+fn default_issue_type() -> String {
+    "Bug".to_string()
+}
+
+fn default_min_items() -> usize {
+    1
+}
+
+fn default_max_issues() -> usize {
+    10
+}
+
+impl IssueNotificationConfig {
+    /// Check that the provider has the settings it needs
+    pub fn validate(&self) -> Result<()> {
+        if self.provider == IssueProvider::Jira {
+            if self.url.as_deref().is_none_or(|u| u.trim().is_empty()) {
+                bail!("notifications.issues.url is required for the jira provider");
+            }
+            if self.project.as_deref().is_none_or(|p| p.trim().is_empty()) {
+                bail!("notifications.issues.project is required for the jira provider");
+            }
+        }
+        if self.max_issues == 0 {
+            bail!("notifications.issues.max_issues must be at least 1");
+        }
+        Ok(())
+    }
+
+    fn report_link(&self, job_id: &str) -> Option<String> {
+        self.report_url
+            .as_ref()
+            .map(|url| url.replace("${job_id}", job_id))
+    }
+}
+
+/// Markup language of ticket text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Markup {
+    Markdown,
+    /// Jira wiki markup, used by the v2 REST API
+    JiraWiki,
+}
+
+/// Opens and updates tickets in an issue tracker
+#[async_trait]
+pub trait IssueTracker: Send + Sync {
+    /// Markup used for ticket bodies and comments
+    fn markup(&self) -> Markup;
+    /// Open ticket containing `marker`, if any
+    async fn find_open(&self, marker: &str) -> Result<Option<String>>;
+    /// Open a ticket, returning its reference
+    async fn create(&self, title: &str, body: &str) -> Result<String>;
+    /// Comment on an existing ticket
+    async fn comment(&self, issue: &str, body: &str) -> Result<()>;
+}
+
+/// What happened to a cluster's ticket
+#[derive(Debug, Clone, PartialEq)]
+pub enum IssueAction {
+    Created(String),
+    Updated(String),
+    Failed(String),
+}
+
+/// Ticket outcome for one failure cluster
+#[derive(Debug, Clone, PartialEq)]
+pub struct IssueSyncResult {
+    pub marker: String,
+    pub item_count: usize,
+    pub action: IssueAction,
+}
+
+/// Marker that identifies a cluster's ticket across runs
+pub fn cluster_marker(cluster: &FailureCluster) -> String {
+    let digest = Sha256::digest(format!(
+        "{}\0{}",
+        cluster.normalized_error, cluster.step_failed
+    ));
+    let hex: String = digest[..6].iter().map(|b| format!("{:02x}", b)).collect();
+    format!("prodigy-cluster-{}", hex)
+}
+
+/// Ticket title for a cluster
+pub fn issue_title(cluster: &FailureCluster) -> String {
+    let error = cluster.normalized_error.lines().next().unwrap_or("");
+    let error = if error.chars().count() > 80 {
+        format!("{}...", error.chars().take(77).collect::<String>())
+    } else {
+        error.to_string()
+    };
+    format!(
+        "[prodigy] {} item{} failed: {}",
+        cluster.count,
+        if cluster.count == 1 { "" } else { "s" },
+        error
+    )
+}
+
+/// Ticket body describing a cluster and its items
+pub fn issue_body(
+    job_id: &str,
+    cluster: &FailureCluster,
+    items: &HashMap<String, DeadLetteredItem>,
+    report_link: Option<&str>,
+    markup: Markup,
+) -> String {
+    let md = markup == Markup::Markdown;
+    let code = |text: &str| {
+        if md {
+            format!("`{}`", text.replace('`', "'"))
+        } else {
+            format!("{{{{{}}}}}", text)
+        }
+    };
+    let heading = |text: &str| {
+        if md {
+            format!("### {}\n\n", text)
+        } else {
+            format!("h3. {}\n\n", text)
+        }
+    };
+    let block = |text: &str, lang: &str| {
+        if md {
+            format!("```{}\n{}\n```\n\n", lang, text.replace("```", "'''"))
+        } else {
+            format!(
+                "{{code}}\n{}\n{{code}}\n\n",
+                text.replace("{code}", "{ code}")
+            )
+        }
+    };
+    let bold = |text: &str| {
+        if md {
+            format!("**{}**", text)
+        } else {
+            format!("*{}*", text)
+        }
+    };
+
+    let mut body = format!(
+        "Prodigy job {} finished with {} failed work item{} sharing this error.\n\n",
+        code(job_id),
+        cluster.count,
+        if cluster.count == 1 { "" } else { "s" }
+    );
+    body.push_str(&format!(
+        "{} {}\n{} {}\n{} {} ({})\n\n",
+        bold("Failing step:"),
+        code(&cluster.step_failed),
+        bold("Error type:"),
+        cluster.error_type.name(),
+        bold("Suggested action:"),
+        cluster.suggested_action.label(),
+        cluster.reason
+    ));
+
+    body.push_str(&heading("Error"));
+    body.push_str(&block(&cluster.normalized_error, ""));
+
+    body.push_str(&heading("Items"));
+    body.push_str(if md {
+        "| Item | Attempts | Last error |\n|---|---|---|\n"
+    } else {
+        "||Item||Attempts||Last error||\n"
+    });
+    for item_id in cluster.item_ids.iter().take(MAX_LISTED_ITEMS) {
+        let (attempts, error) = items
+            .get(item_id)
+            .map(|item| {
+                let error = item
+                    .failure_history
+                    .last()
+                    .map(|f| f.error_message.lines().next().unwrap_or("").to_string())
+                    .unwrap_or_default();
+                (item.failure_count.to_string(), error)
+            })
+            .unwrap_or_default();
+        let error: String = error.chars().take(120).collect();
+        let error = error.replace('|', "\\|");
+        body.push_str(&format!(
+            "| {} | {} | {} |\n",
+            code(item_id),
+            attempts,
+            error
+        ));
+    }
+    if cluster.item_ids.len() > MAX_LISTED_ITEMS {
+        body.push_str(&format!(
+            "\n...and {} more\n",
+            cluster.item_ids.len() - MAX_LISTED_ITEMS
+        ));
+    }
+    body.push('\n');
+
+    if let Some(sample) = cluster.item_ids.first().and_then(|id| items.get(id)) {
+        body.push_str(&heading(&format!("Sample item ({})", sample.item_id)));
+        let data = serde_json::to_string_pretty(&sample.item_data).unwrap_or_default();
+        let data = if data.chars().count() > MAX_ITEM_DATA_CHARS {
+            format!(
+                "{}\n...",
+                data.chars().take(MAX_ITEM_DATA_CHARS).collect::<String>()
+            )
+        } else {
+            data
+        };
+        body.push_str(&block(&data, "json"));
+    }
+
+    body.push_str(&next_steps(job_id, report_link, markup));
+    body.push_str(&format!("\n\n{}\n", cluster_marker(cluster)));
+    body
+}
+
+/// Comment added to an open ticket when a later job hits the same cluster
+pub fn update_comment(
+    job_id: &str,
+    cluster: &FailureCluster,
+    report_link: Option<&str>,
+    markup: Markup,
+) -> String {
+    let ids: Vec<&str> = cluster
+        .item_ids
+        .iter()
+        .take(MAX_LISTED_ITEMS)
+        .map(String::as_str)
+        .collect();
+    let more = cluster.item_ids.len().saturating_sub(MAX_LISTED_ITEMS);
+    format!(
+        "Prodigy job {} hit this failure again for {} item{}: {}{}\n\n{}",
+        job_id,
+        cluster.count,
+        if cluster.count == 1 { "" } else { "s" },
+        ids.join(", "),
+        if more > 0 {
+            format!(" and {} more", more)
+        } else {
+            String::new()
+        },
+        next_steps(job_id, report_link, markup)
+    )
+}
+
+fn next_steps(job_id: &str, report_link: Option<&str>, markup: Markup) -> String {
+    let commands = match markup {
+        Markup::Markdown => format!(
+            "Inspect with `prodigy dlq analyze --job-id {0}` or `prodigy report {0}`, and retry with `prodigy dlq retry {0}`.",
+            job_id
+        ),
+        Markup::JiraWiki => format!(
+            "Inspect with {{{{prodigy dlq analyze --job-id {0}}}}} or {{{{prodigy report {0}}}}}, and retry with {{{{prodigy dlq retry {0}}}}}.",
+            job_id
+        ),
+    };
+    match report_link {
+        Some(link) => match markup {
+            Markup::Markdown => format!("Report: [{}]({})\n\n{}", job_id, link, commands),
+            Markup::JiraWiki => format!("Report: [{}|{}]\n\n{}", job_id, link, commands),
+        },
+        None => commands,
+    }
+}
+
+/// Open or update a ticket for each qualifying failure cluster
+///
+/// Tracker errors are reported per cluster instead of stopping the sync.
+pub async fn sync_failure_issues(
+    tracker: &dyn IssueTracker,
+    config: &IssueNotificationConfig,
+    job_id: &str,
+    items: &HashMap<String, DeadLetteredItem>,
+) -> Vec<IssueSyncResult> {
+    let report_link = config.report_link(job_id);
+    let markup = tracker.markup();
+    let clusters: Vec<FailureCluster> = cluster_failures(items.values())
+        .into_iter()
+        .filter(|cluster| cluster.count >= config.min_items)
+        .take(config.max_issues)
+        .collect();
+
+    let mut results = Vec::new();
+    for cluster in &clusters {
+        let marker = cluster_marker(cluster);
+        let action = match tracker.find_open(&marker).await {
+            Ok(Some(issue)) => {
+                let comment = update_comment(job_id, cluster, report_link.as_deref(), markup);
+                match tracker.comment(&issue, &comment).await {
+                    Ok(()) => IssueAction::Updated(issue),
+                    Err(e) => IssueAction::Failed(format!("{:#}", e)),
+                }
+            }
+            Ok(None) => {
+                let body = issue_body(job_id, cluster, items, report_link.as_deref(), markup);
+                match tracker.create(&issue_title(cluster), &body).await {
+                    Ok(issue) => IssueAction::Created(issue),
+                    Err(e) => IssueAction::Failed(format!("{:#}", e)),
+                }
+            }
+            Err(e) => IssueAction::Failed(format!("{:#}", e)),
+        };
+        results.push(IssueSyncResult {
+            marker,
+            item_count: cluster.count,
+            action,
+        });
+    }
+    results
+}
+
+/// Build the tracker for a config
+pub fn create_tracker(
+    config: &IssueNotificationConfig,
+    subprocess: Arc<SubprocessManager>,
+) -> Result<Box<dyn IssueTracker>> {
+    config.validate()?;
+    Ok(match config.provider {
+        IssueProvider::Github => Box::new(GitHubIssues {
+            subprocess,
+            repo: config.repo.clone(),
+            labels: config.labels.clone(),
+        }),
+        IssueProvider::Jira => Box::new(JiraIssues {
+            subprocess,
+            url: config
+                .url
+                .clone()
+                .unwrap_or_default()
+                .trim_end_matches('/')
+                .to_string(),
+            project: config.project.clone().unwrap_or_default(),
+            issue_type: config.issue_type.clone(),
+            labels: config.labels.clone(),
+        }),
+    })
+}
+
+/// GitHub Issues through the `gh` CLI
+struct GitHubIssues {
+    subprocess: Arc<SubprocessManager>,
+    repo: Option<String>,
+    labels: Vec<String>,
+}
+
+impl GitHubIssues {
+    async fn gh(&self, mut args: Vec<String>) -> Result<String> {
+        if let Some(repo) = &self.repo {
+            args.push("--repo".to_string());
+            args.push(repo.clone());
+        }
+        let command = ProcessCommandBuilder::new("gh").args(&args).build();
+        let output = self
+            .subprocess
+            .runner()
+            .run(command)
+            .await
+            .context("Failed to run gh")?;
+        if !output.status.success() {
+            bail!(
+                "gh {} failed: {}",
+                args[..2].join(" "),
+                output.stderr.trim()
+            );
+        }
+        Ok(output.stdout)
+    }
+}
+
+#[async_trait]
+impl IssueTracker for GitHubIssues {
+    fn markup(&self) -> Markup {
+        Markup::Markdown
+    }
+
+    async fn find_open(&self, marker: &str) -> Result<Option<String>> {
+        let stdout = self
+            .gh(vec![
+                "issue".to_string(),
+                "list".to_string(),
+                "--state".to_string(),
+                "open".to_string(),
+                "--search".to_string(),
+                format!("\"{}\" in:body", marker),
+                "--json".to_string(),
+                "number".to_string(),
+                "--limit".to_string(),
+                "1".to_string(),
+            ])
+            .await?;
+        let issues: Vec<Value> =
+            serde_json::from_str(stdout.trim()).context("Unexpected gh issue list output")?;
+        Ok(issues
+            .first()
+            .and_then(|issue| issue.get("number"))
+            .map(|number| number.to_string()))
+    }
+
+    async fn create(&self, title: &str, body: &str) -> Result<String> {
+        let mut args = vec![
+            "issue".to_string(),
+            "create".to_string(),
+            "--title".to_string(),
+            title.to_string(),
+            "--body".to_string(),
+            body.to_string(),
+        ];
+        for label in &self.labels {
+            args.push("--label".to_string());
+            args.push(label.clone());
+        }
+        Ok(self.gh(args).await?.trim().to_string())
+    }
+
+    async fn comment(&self, issue: &str, body: &str) -> Result<()> {
+        self.gh(vec![
+            "issue".to_string(),
+            "comment".to_string(),
+            issue.to_string(),
+            "--body".to_string(),
+            body.to_string(),
+        ])
+        .await?;
+        Ok(())
+    }
+}
+
+/// Jira through its v2 REST API, called with `curl`
+struct JiraIssues {
+    subprocess: Arc<SubprocessManager>,
+    url: String,
+    project: String,
+    issue_type: String,
+    labels: Vec<String>,
+}
+
+impl JiraIssues {
+    /// Run curl with credentials passed on stdin so they stay out of `ps`
+    async fn curl(&self, args: Vec<String>) -> Result<Value> {
+        let email = std::env::var("JIRA_EMAIL").context("JIRA_EMAIL is not set")?;
+        let token = std::env::var("JIRA_API_TOKEN").context("JIRA_API_TOKEN is not set")?;
+        let config = format!(
+            "user = \"{}:{}\"\n",
+            email.replace('"', "\\\""),
+            token.replace('"', "\\\"")
+        );
+
+        let mut all_args = vec![
+            "--silent".to_string(),
+            "--show-error".to_string(),
+            "--fail-with-body".to_string(),
+            "--config".to_string(),
+            "-".to_string(),
+            "--header".to_string(),
+            "Accept: application/json".to_string(),
+        ];
+        all_args.extend(args);
+        let command = ProcessCommandBuilder::new("curl")
+            .args(&all_args)
+            .stdin(config)
+            .build();
+        let output = self
+            .subprocess
+            .runner()
+            .run(command)
+            .await
+            .context("Failed to run curl")?;
+        if !output.status.success() {
+            bail!(
+                "Jira request failed: {} {}",
+                output.stderr.trim(),
+                output.stdout.trim()
+            );
+        }
+        serde_json::from_str(&output.stdout).context("Unexpected Jira response")
+    }
+
+    async fn post(&self, path: &str, payload: Value) -> Result<Value> {
+        self.curl(vec![
+            "--request".to_string(),
+            "POST".to_string(),
+            "--header".to_string(),
+            "Content-Type: application/json".to_string(),
+            "--data".to_string(),
+            payload.to_string(),
+            format!("{}{}", self.url, path),
+        ])
+        .await
+    }
+}
+
+#[async_trait]
+impl IssueTracker for JiraIssues {
+    fn markup(&self) -> Markup {
+        Markup::JiraWiki
+    }
+
+    async fn find_open(&self, marker: &str) -> Result<Option<String>> {
+        let jql = format!(
+            "project = \"{}\" AND statusCategory != Done AND text ~ \"\\\"{}\\\"\"",
+            self.project, marker
+        );
+        let response = self
+            .curl(vec![
+                "--get".to_string(),
+                "--data-urlencode".to_string(),
+                format!("jql={}", jql),
+                "--data".to_string(),
+                "maxResults=1&fields=key".to_string(),
+                format!("{}/rest/api/2/search", self.url),
+            ])
+            .await?;
+        Ok(response["issues"]
+            .as_array()
+            .and_then(|issues| issues.first())
+            .and_then(|issue| issue["key"].as_str())
+            .map(str::to_string))
+    }
+
+    async fn create(&self, title: &str, body: &str) -> Result<String> {
+        let payload = json!({
+            "fields": {
+                "project": { "key": self.project },
+                "summary": title,
+                "description": body,
+                "issuetype": { "name": self.issue_type },
+                "labels": self.labels,
+            }
+        });
+        let response = self.post("/rest/api/2/issue", payload).await?;
+        let key = response["key"]
+            .as_str()
+            .ok_or_else(|| anyhow!("Jira did not return an issue key"))?;
+        Ok(format!("{}/browse/{}", self.url, key))
+    }
+
+    async fn comment(&self, issue: &str, body: &str) -> Result<()> {
+        self.post(
+            &format!("/rest/api/2/issue/{}/comment", issue),
+            json!({ "body": body }),
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cook::execution::dlq::{ErrorType, FailureDetail};
+    use chrono::Utc;
+    use std::sync::Mutex;
+
+    fn dlq_item(id: &str, error: &str) -> DeadLetteredItem {
+        DeadLetteredItem {
+            item_id: id.to_string(),
+            item_data: json!({"file": format!("src/{}.rs", id)}),
+            first_attempt: Utc::now(),
+            last_attempt: Utc::now(),
+            failure_count: 2,
+            failure_history: vec![FailureDetail {
+                attempt_number: 2,
+                timestamp: Utc::now(),
+                error_type: ErrorType::CommandFailed { exit_code: 101 },
+                error_message: error.to_string(),
+                error_context: None,
+                stack_trace: None,
+                agent_id: format!("agent-{}", id),
+                step_failed: "shell: cargo test".to_string(),
+                duration_ms: 1000,
+                json_log_location: None,
+            }],
+            error_signature: "sig".to_string(),
+            worktree_artifacts: None,
+            reprocess_eligible: true,
+            manual_review_required: false,
+        }
+    }
+
+    fn config() -> IssueNotificationConfig {
+        serde_yaml::from_str("provider: github\nreport_url: https://ci/${job_id}.html\n").unwrap()
+    }
+
+    /// Tracker that stores tickets in memory
+    #[derive(Default)]
+    struct FakeTracker {
+        open: Mutex<Vec<(String, String)>>,
+        comments: Mutex<Vec<(String, String)>>,
+    }
+
+    #[async_trait]
+    impl IssueTracker for FakeTracker {
+        fn markup(&self) -> Markup {
+            Markup::Markdown
+        }
+
+        async fn find_open(&self, marker: &str) -> Result<Option<String>> {
+            let open = self.open.lock().unwrap();
+            Ok(open
+                .iter()
+                .position(|(_, body)| body.contains(marker))
+                .map(|index| format!("#{}", index + 1)))
+        }
+
+        async fn create(&self, title: &str, body: &str) -> Result<String> {
+            let mut open = self.open.lock().unwrap();
+            open.push((title.to_string(), body.to_string()));
+            Ok(format!("#{}", open.len()))
+        }
+
+        async fn comment(&self, issue: &str, body: &str) -> Result<()> {
+            self.comments
+                .lock()
+                .unwrap()
+                .push((issue.to_string(), body.to_string()));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_config_defaults_and_validation() {
+        let config = config();
+        assert_eq!(config.provider, IssueProvider::Github);
+        assert_eq!(config.min_items, 1);
+        assert_eq!(config.max_issues, 10);
+        assert!(config.validate().is_ok());
+        assert_eq!(
+            config.report_link("job-1").as_deref(),
+            Some("https://ci/job-1.html")
+        );
+
+        let jira: IssueNotificationConfig =
+            serde_yaml::from_str("provider: jira\nproject: OPS\n").unwrap();
+        assert!(jira.validate().unwrap_err().to_string().contains("url"));
+    }
+
+    #[test]
+    fn test_issue_text() {
+        let items: HashMap<String, DeadLetteredItem> = ["item_3", "item_7"]
+            .iter()
+            .map(|id| {
+                let error = format!("test failed at src/{}.rs:12", id);
+                (id.to_string(), dlq_item(id, &error))
+            })
+            .collect();
+        let cluster = cluster_failures(items.values()).remove(0);
+        let marker = cluster_marker(&cluster);
+        assert!(marker.starts_with("prodigy-cluster-"));
+        assert_eq!(marker.len(), "prodigy-cluster-".len() + 12);
+
+        assert_eq!(
+            issue_title(&cluster),
+            "[prodigy] 2 items failed: test failed at <path>"
+        );
+
+        let body = issue_body("job-1", &cluster, &items, None, Markup::Markdown);
+        assert!(body.contains("**Failing step:** `shell: cargo test`"));
+        assert!(body.contains("| `item_3` | 2 | test failed at src/item_3.rs:12 |"));
+        assert!(body.contains("### Sample item (item_3)"));
+        assert!(body.contains("`prodigy dlq analyze --job-id job-1`"));
+        assert!(body.trim_end().ends_with(&marker));
+
+        let wiki = issue_body(
+            "job-1",
+            &cluster,
+            &items,
+            Some("https://ci/r"),
+            Markup::JiraWiki,
+        );
+        assert!(wiki.contains("*Failing step:* {{shell: cargo test}}"));
+        assert!(wiki.contains("||Item||Attempts||Last error||"));
+        assert!(wiki.contains("{code}"));
+        assert!(wiki.contains("Report: [job-1|https://ci/r]"));
+    }
+
+    #[tokio::test]
+    async fn test_sync_creates_then_updates() {
+        let items: HashMap<String, DeadLetteredItem> = vec![
+            dlq_item("a", "timeout after 30s"),
+            dlq_item("b", "timeout after 45s"),
+            dlq_item("c", "permission denied"),
+        ]
+        .into_iter()
+        .map(|item| (item.item_id.clone(), item))
+        .collect();
+        let tracker = FakeTracker::default();
+        let mut config = config();
+        config.min_items = 2;
+
+        let first = sync_failure_issues(&tracker, &config, "job-1", &items).await;
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0].item_count, 2);
+        assert_eq!(first[0].action, IssueAction::Created("#1".to_string()));
+
+        let second = sync_failure_issues(&tracker, &config, "job-2", &items).await;
+        assert_eq!(second[0].action, IssueAction::Updated("#1".to_string()));
+        assert_eq!(second[0].marker, first[0].marker);
+        let comments = tracker.comments.lock().unwrap();
+        assert_eq!(comments.len(), 1);
+        assert!(comments[0]
+            .1
+            .starts_with("Prodigy job job-2 hit this failure again for 2 items: a, b"));
+        assert!(comments[0].1.contains("[job-2](https://ci/job-2.html)"));
+    }
+}
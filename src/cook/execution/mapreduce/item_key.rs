@@ -0,0 +1,229 @@
+//! Stable identity for map phase work items
+//!
+//! Without configuration, items are identified by their position in the
+//! input (`item_0`, `item_1`, ...), so reordering the input changes every
+//! ID. An `item_key` expression derives the ID from the item itself:
+//!
+//! ```yaml
+//! map:
+//!   input: debt.json
+//!   json_path: "$.items[*]"
+//!   item_key: "$.location.file + :: + $.location.function"
+//! ```
+//!
+//! The expression joins `+`-separated parts: JSONPath field references
+//! starting with `$`, and literal text (optionally quoted to include `+`).
+//! The key becomes the item ID used by results, events, the DLQ, progress
+//! reporting, and `${item_id}`.
+
+use crate::cook::execution::data_pipeline::FilterExpression;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt;
+
+/// One part of an item key expression
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum KeyPart {
+    /// Field path relative to the item, without the leading `$.`
+    Field(String),
+    Literal(String),
+}
+
+/// A parsed `item_key` expression
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ItemKeyExpr {
+    source: String,
+    parts: Vec<KeyPart>,
+}
+
+impl ItemKeyExpr {
+    /// Parse an expression such as `$.location.file + :: + $.location.function`
+    pub fn parse(expr: &str) -> Result<Self, String> {
+        let mut raw_parts = Vec::new();
+        let mut current = String::new();
+        let mut quote: Option<char> = None;
+
+        for c in expr.chars() {
+            match (quote, c) {
+                (Some(q), c) if c == q => {
+                    quote = None;
+                    current.push(c);
+                }
+                (None, '"' | '\'') => {
+                    quote = Some(c);
+                    current.push(c);
+                }
+                (None, '+') => raw_parts.push(std::mem::take(&mut current)),
+                _ => current.push(c),
+            }
+        }
+        if quote.is_some() {
+            return Err(format!("Unterminated quote in item_key '{}'", expr));
+        }
+        raw_parts.push(current);
+
+        let parts = raw_parts
+            .iter()
+            .map(|raw| Self::parse_part(raw.trim(), expr))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            source: expr.to_string(),
+            parts,
+        })
+    }
+
+    fn parse_part(raw: &str, expr: &str) -> Result<KeyPart, String> {
+        if raw.is_empty() {
+            return Err(format!("Empty part in item_key '{}'", expr));
+        }
+        if let Some(path) = raw.strip_prefix('$') {
+            let path = path.strip_prefix('.').unwrap_or(path);
+            if path.is_empty() {
+                return Err(format!(
+                    "item_key field '{}' must name a field, e.g. $.id",
+                    raw
+                ));
+            }
+            return Ok(KeyPart::Field(path.to_string()));
+        }
+        let unquoted = raw
+            .strip_prefix('"')
+            .and_then(|r| r.strip_suffix('"'))
+            .or_else(|| raw.strip_prefix('\'').and_then(|r| r.strip_suffix('\'')))
+            .unwrap_or(raw);
+        Ok(KeyPart::Literal(unquoted.to_string()))
+    }
+
+    /// Whether the expression references at least one item field
+    pub fn has_fields(&self) -> bool {
+        self.parts
+            .iter()
+            .any(|part| matches!(part, KeyPart::Field(_)))
+    }
+
+    /// Build the key for one item (pure function)
+    pub fn evaluate(&self, item: &Value) -> Result<String, String> {
+        let mut key = String::new();
+        for part in &self.parts {
+            match part {
+                KeyPart::Literal(text) => key.push_str(text),
+                KeyPart::Field(path) => {
+                    match FilterExpression::get_nested_field_with_array(item, path) {
+                        Some(Value::String(s)) => key.push_str(&s),
+                        Some(Value::Null) | None => return Err(format!("no value for $.{}", path)),
+                        Some(value) => key.push_str(&value.to_string()),
+                    }
+                }
+            }
+        }
+        Ok(key)
+    }
+}
+
+impl fmt::Display for ItemKeyExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+/// Positional ID used when no `item_key` is configured
+pub fn positional_item_id(index: usize) -> String {
+    format!("item_{}", index)
+}
+
+/// Assign an ID to every work item (pure function)
+///
+/// Fails if any item lacks a key field or two items share a key, since
+/// either would make results for different items indistinguishable.
+pub fn assign_item_ids(items: &[Value], key: Option<&ItemKeyExpr>) -> Result<Vec<String>, String> {
+    let Some(key) = key else {
+        return Ok((0..items.len()).map(positional_item_id).collect());
+    };
+
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    let mut ids = Vec::with_capacity(items.len());
+    for (index, item) in items.iter().enumerate() {
+        let id = key
+            .evaluate(item)
+            .map_err(|e| format!("item_key '{}': item {}: {}", key, index, e))?;
+        if let Some(first) = seen.insert(id.clone(), index) {
+            return Err(format!(
+                "item_key '{}' is not unique: items {} and {} both have key '{}' \
+                 (use `distinct` to drop duplicates)",
+                key, first, index, id
+            ));
+        }
+        ids.push(id);
+    }
+    Ok(ids)
+}
+
+/// Make an item ID safe to embed in a git branch name (pure function)
+pub fn branch_safe(item_id: &str) -> String {
+    let mut safe = String::with_capacity(item_id.len());
+    for c in item_id.chars() {
+        if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+            safe.push(c);
+        } else if !safe.ends_with('-') {
+            safe.push('-');
+        }
+    }
+    let safe = safe
+        .trim_matches(|c| c == '-' || c == '.')
+        .replace("..", ".");
+    if safe.is_empty() {
+        "item".to_string()
+    } else {
+        safe
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_evaluate_item_key() {
+        let key = ItemKeyExpr::parse("$.location.file + :: + $.location.function").unwrap();
+        let item = json!({"location": {"file": "src/lib.rs", "function": "parse"}});
+        assert_eq!(key.evaluate(&item).unwrap(), "src/lib.rs::parse");
+
+        let key = ItemKeyExpr::parse("'a+b' + $.tags[1] + \"-\" + $.id").unwrap();
+        let item = json!({"tags": ["x", "y"], "id": 7});
+        assert_eq!(key.evaluate(&item).unwrap(), "a+by-7");
+
+        assert!(key.evaluate(&json!({"id": 7})).is_err());
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_expressions() {
+        assert!(ItemKeyExpr::parse("$.a + + $.b").is_err());
+        assert!(ItemKeyExpr::parse("$.a + 'open").is_err());
+        assert!(ItemKeyExpr::parse("$").is_err());
+    }
+
+    #[test]
+    fn test_assign_item_ids() {
+        let items = vec![json!({"id": "b"}), json!({"id": "a"})];
+        assert_eq!(
+            assign_item_ids(&items, None).unwrap(),
+            vec!["item_0", "item_1"]
+        );
+
+        let key = ItemKeyExpr::parse("$.id").unwrap();
+        assert_eq!(assign_item_ids(&items, Some(&key)).unwrap(), vec!["b", "a"]);
+
+        let duplicates = vec![json!({"id": "a"}), json!({"id": "a"})];
+        let err = assign_item_ids(&duplicates, Some(&key)).unwrap_err();
+        assert!(err.contains("not unique"));
+    }
+
+    #[test]
+    fn test_branch_safe() {
+        assert_eq!(branch_safe("src/lib.rs::parse"), "src-lib.rs-parse");
+        assert_eq!(branch_safe("item_3"), "item_3");
+        assert_eq!(branch_safe("../::"), "item");
+    }
+}
@@ -179,6 +179,7 @@ fn create_agent_context(
             project_dir: Arc::new(std::path::PathBuf::from(".")),
             worktree_name: Some(agent_id.to_string().into()),
             session_id: format!("agent-session-{}", agent_id).into(),
+            package: None,
         },
     );
 
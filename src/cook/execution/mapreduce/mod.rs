@@ -7,9 +7,11 @@
 //! programming principles, with each module under 500 lines for maintainability.
 
 // Sub-modules for organized functionality
+pub mod affinity;
 pub mod agent;
 pub mod agent_command_executor;
 pub mod aggregation;
+pub mod batching;
 pub mod checkpoint;
 pub mod checkpoint_integration;
 pub mod cleanup;
@@ -21,10 +23,15 @@ pub mod effects;
 pub mod env_interpolation;
 pub mod environment;
 pub mod environment_helpers;
+pub mod eta;
 pub mod event;
+pub mod idempotency;
+pub mod issue_tracker;
+pub mod item_key;
 pub mod map_phase;
 pub mod merge_queue;
 pub mod mock_environment;
+pub mod network_isolation;
 pub mod noop_writer;
 pub mod phases;
 pub mod progress;
@@ -34,6 +41,9 @@ pub mod resources;
 pub mod resume_collection;
 pub mod resume_deduplication;
 pub mod retry_tracking;
+pub mod setup_artifacts;
+pub mod shared_context;
+pub mod speculative;
 pub mod state;
 pub mod timeout;
 #[cfg(test)]
@@ -366,6 +376,7 @@ impl MapReduceExecutor {
             project_dir: Arc::new(self.project_root.clone()),
             worktree_name: None,
             session_id: Arc::from(self.correlation_id.as_str()),
+            package: None,
         };
         self.execute_with_context(setup, map_phase, reduce, env)
             .await
@@ -386,6 +397,7 @@ impl MapReduceExecutor {
                 self.enhanced_state_manager.clone(),
                 self.user_interaction.clone(),
                 self.subprocess.clone(),
+                self.claude_executor.clone(),
                 self.project_root.clone(),
                 self.execution_mode.clone(),
                 self.verbosity,
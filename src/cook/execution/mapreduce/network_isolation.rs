@@ -0,0 +1,140 @@
+//! Network isolation for map agent shell commands
+//!
+//! Generated code runs unreviewed in every agent, so a test suite that pipes
+//! `curl` into `sh` or phones home runs on the host network. With
+//! `network: deny` the agent's shell commands run without network access:
+//!
+//! ```yaml
+//! map:
+//!   network: deny
+//!   agent_template:
+//!     - claude: "/fix ${item.path}"
+//!     - shell: "cargo test"   # runs with loopback only
+//! ```
+//!
+//! Only shell commands are isolated; Claude commands still reach the Claude
+//! API, and the tools Claude runs itself are governed by `claude_permissions`.
+//!
+//! On Linux each command runs in a fresh network namespace created with
+//! `unshare`, which has a loopback interface and nothing else. The namespace
+//! needs unprivileged user namespaces, and inside it commands run as a
+//! mapped root user. Isolation is best-effort: on other platforms, or when
+//! namespaces are unavailable, a warning is logged once and commands keep
+//! their network access.
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::OnceCell;
+use tracing::warn;
+
+/// Network access for agent shell commands
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AgentNetwork {
+    /// Commands use the host network
+    #[default]
+    Allow,
+    /// Commands run without network access where the platform supports it
+    Deny,
+}
+
+impl AgentNetwork {
+    /// Whether this is the default, unrestricted setting
+    pub fn is_allow(&self) -> bool {
+        *self == AgentNetwork::Allow
+    }
+}
+
+/// `unshare` options creating an unprivileged network namespace
+const UNSHARE_ARGS: &[&str] = &["--user", "--map-root-user", "--net", "--"];
+
+/// Brings loopback up, then runs the command passed as `$1`
+const LOOPBACK_WRAPPER: &str = "ip link set lo up 2>/dev/null; exec sh -c \"$1\"";
+
+/// Program and arguments running `shell_cmd` without network access (pure function)
+///
+/// The command is passed as a positional argument rather than spliced into
+/// the wrapper script, so it needs no extra quoting.
+pub fn isolated_shell_command(shell_cmd: &str) -> (String, Vec<String>) {
+    let args = UNSHARE_ARGS
+        .iter()
+        .copied()
+        .chain(["sh", "-c", LOOPBACK_WRAPPER, "sh", shell_cmd])
+        .map(String::from)
+        .collect();
+    ("unshare".to_string(), args)
+}
+
+/// Whether shell commands can be isolated on this host
+///
+/// Probed once per process; a warning is logged when isolation is requested
+/// but unavailable.
+pub async fn isolation_available() -> bool {
+    static AVAILABLE: OnceCell<bool> = OnceCell::const_new();
+    *AVAILABLE
+        .get_or_init(|| async {
+            if !cfg!(target_os = "linux") {
+                warn!(
+                    "map.network: deny is only enforced on Linux; agent shell commands keep network access"
+                );
+                return false;
+            }
+
+            let available = tokio::process::Command::new("unshare")
+                .args(UNSHARE_ARGS)
+                .arg("true")
+                .stdout(std::process::Stdio::null())
+                .stderr(std::process::Stdio::null())
+                .status()
+                .await
+                .is_ok_and(|status| status.success());
+            if !available {
+                warn!(
+                    "map.network: deny needs `unshare` and unprivileged user namespaces; \
+                     agent shell commands keep network access"
+                );
+            }
+            available
+        })
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_network_setting() {
+        let network: AgentNetwork = serde_yaml::from_str("deny").unwrap();
+        assert_eq!(network, AgentNetwork::Deny);
+        assert!(AgentNetwork::default().is_allow());
+        assert!(serde_yaml::from_str::<AgentNetwork>("none").is_err());
+    }
+
+    #[test]
+    fn test_isolated_command_passes_command_as_argument() {
+        let (program, args) = isolated_shell_command("echo \"$HOME\" | wc -c");
+        assert_eq!(program, "unshare");
+        assert_eq!(&args[..4], UNSHARE_ARGS);
+        assert_eq!(args.last().unwrap(), "echo \"$HOME\" | wc -c");
+    }
+
+    #[tokio::test]
+    async fn test_isolated_command_has_no_network() {
+        if !isolation_available().await {
+            return;
+        }
+        let (program, args) = isolated_shell_command("cat /proc/net/dev");
+        let output = tokio::process::Command::new(program)
+            .args(args)
+            .output()
+            .await
+            .unwrap();
+        assert!(output.status.success());
+        let interfaces: Vec<String> = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .skip(2)
+            .filter_map(|line| line.split(':').next().map(|name| name.trim().to_string()))
+            .collect();
+        assert_eq!(interfaces, vec!["lo"]);
+    }
+}
@@ -17,6 +17,7 @@ fn create_test_environment() -> ExecutionEnvironment {
         project_dir: Arc::new(PathBuf::from("/tmp/test")),
         worktree_name: Some(Arc::from("test-worktree")),
         session_id: Arc::from("test-session"),
+        package: None,
     }
 }
 
@@ -28,6 +29,8 @@ fn create_test_setup_phase() -> SetupPhase {
         }],
         timeout: Some(60),
         capture_outputs: HashMap::new(),
+        artifacts: Vec::new(),
+        shared_context: None,
     }
 }
 
@@ -52,6 +55,24 @@ fn create_test_map_phase() -> MapPhase {
         distinct: None,
         timeout_config: None,
         workflow_env: HashMap::new(),
+        rebase_onto: None,
+        speculative: None,
+        item_key: None,
+        affinity: Vec::new(),
+        build_cache: Vec::new(),
+        network: Default::default(),
+        strict_variables: false,
+        setup_artifacts: None,
+        chaos: None,
+        junit: None,
+        protected_paths: None,
+        seed: None,
+        failures: None,
+        idempotency: None,
+        notifications: None,
+        claude_permissions: None,
+        changed_since: None,
+        changed_path: None,
     }
 }
 
@@ -62,6 +83,8 @@ fn create_test_reduce_phase() -> ReducePhase {
             ..Default::default()
         }],
         timeout_secs: None,
+        results_file: None,
+        claude_permissions: None,
     }
 }
 
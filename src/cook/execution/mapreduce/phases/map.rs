@@ -90,6 +90,7 @@ impl MapPhaseExecutor {
                 worktree_session_id: None,
                 json_log_location: None,
                 cleanup_status: None,
+                no_changes: None,
             });
         }
 
@@ -289,6 +289,9 @@ impl ReducePhaseExecutor {
                     command: Some(cmd.clone()),
                     name: None,
                     claude: None,
+                    model: None,
+                    context: None,
+                    validate_output: None,
                     shell: None,
                     test: None,
                     foreach: None,
@@ -315,6 +318,13 @@ impl ReducePhaseExecutor {
                     validation_timeout: None,
                     ignore_validation_failure: false,
                     when: None,
+                    assert: None,
+                    summarize_commits: None,
+                    claude_chat: None,
+                    script: None,
+                    snapshot: false,
+                    required: false,
+                    commit_policy: None,
                 };
                 let result = self.execute_single_step(&handler_step, context).await?;
                 Ok(result.success)
@@ -326,6 +336,9 @@ impl ReducePhaseExecutor {
                         command: Some(cmd.clone()),
                         name: None,
                         claude: None,
+                        model: None,
+                        context: None,
+                        validate_output: None,
                         shell: None,
                         test: None,
                         foreach: None,
@@ -352,6 +365,13 @@ impl ReducePhaseExecutor {
                         validation_timeout: None,
                         ignore_validation_failure: false,
                         when: None,
+                        assert: None,
+                        summarize_commits: None,
+                        claude_chat: None,
+                        script: None,
+                        snapshot: false,
+                        required: false,
+                        commit_policy: None,
                     };
                     let result = self.execute_single_step(&handler_step, context).await?;
                     if !result.success {
@@ -15,6 +15,7 @@ fn create_test_environment() -> ExecutionEnvironment {
         project_dir: Arc::new(PathBuf::from("/tmp/test")),
         worktree_name: Some(Arc::from("test-worktree")),
         session_id: Arc::from("test-session"),
+        package: None,
     }
 }
 
@@ -25,6 +26,8 @@ fn create_test_reduce_phase() -> ReducePhase {
             ..Default::default()
         }],
         timeout_secs: None,
+        results_file: None,
+        claude_permissions: None,
     }
 }
 
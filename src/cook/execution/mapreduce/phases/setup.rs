@@ -211,6 +211,7 @@ mod execute_step_tests {
             project_dir: Arc::new(PathBuf::from("/tmp")),
             worktree_name: Some(Arc::from("test-worktree")),
             session_id: Arc::from("test-session"),
+            package: None,
         }
     }
 
@@ -222,6 +223,8 @@ mod execute_step_tests {
             }],
             timeout: Some(60),
             capture_outputs: HashMap::new(),
+            artifacts: Vec::new(),
+            shared_context: None,
         }
     }
 
@@ -297,6 +300,8 @@ mod execute_step_tests {
         let step = WorkflowStep {
             shell: None,
             claude: Some("/analyze-project".to_string()),
+            context: None,
+            validate_output: None,
             ..Default::default()
         };
 
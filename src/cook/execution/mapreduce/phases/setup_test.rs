@@ -15,6 +15,7 @@ fn create_test_environment() -> ExecutionEnvironment {
         project_dir: Arc::new(PathBuf::from("/tmp/test")),
         worktree_name: Some(Arc::from("test-worktree")),
         session_id: Arc::from("test-session"),
+        package: None,
     }
 }
 
@@ -32,6 +33,8 @@ fn create_test_setup_phase() -> SetupPhase {
         ],
         timeout: Some(60),
         capture_outputs: HashMap::new(),
+        artifacts: Vec::new(),
+        shared_context: None,
     }
 }
 
@@ -143,6 +146,8 @@ fn test_setup_phase_with_complex_commands() {
             },
             WorkflowStep {
                 claude: Some("/analyze-project".to_string()),
+                context: None,
+                validate_output: None,
                 timeout: Some(120),
                 commit_required: true,
                 ..Default::default()
@@ -150,6 +155,8 @@ fn test_setup_phase_with_complex_commands() {
         ],
         timeout: Some(60),
         capture_outputs: HashMap::new(),
+        artifacts: Vec::new(),
+        shared_context: None,
     };
 
     let _executor = SetupPhaseExecutor::new(setup_phase.clone());
@@ -49,6 +49,7 @@ pub struct AggregationStats {
 ///         files_modified: vec![],
 ///         json_log_location: None,
 ///         cleanup_status: None,
+///         no_changes: None,
 ///     },
 ///     AgentResult {
 ///         item_id: "2".to_string(),
@@ -63,6 +64,7 @@ pub struct AggregationStats {
 ///         files_modified: vec![],
 ///         json_log_location: None,
 ///         cleanup_status: None,
+///         no_changes: None,
 ///     },
 /// ];
 /// assert_eq!(calculate_success_rate(&results), 50.0);
@@ -232,6 +234,7 @@ mod tests {
             files_modified: vec![],
             json_log_location: None,
             cleanup_status: None,
+            no_changes: None,
         }
     }
 
@@ -249,6 +252,7 @@ mod tests {
             files_modified: vec![],
             json_log_location: None,
             cleanup_status: None,
+            no_changes: None,
         }
     }
 
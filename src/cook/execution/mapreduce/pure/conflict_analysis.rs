@@ -0,0 +1,177 @@
+//! Pure functions for predicting merge conflicts between agent branches
+//!
+//! Agents that modify the same files are likely to conflict when their
+//! branches are merged into the parent worktree. These functions group such
+//! agents into clusters after the map phase, so conflicts can be reported
+//! together instead of being discovered one merge at a time.
+
+use crate::cook::execution::mapreduce::agent::{AgentResult, AgentStatus};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Work items whose branches modify overlapping files
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ConflictCluster {
+    /// Items in the cluster, in map order
+    pub items: Vec<String>,
+    /// Files modified by more than one item in the cluster
+    pub files: Vec<String>,
+}
+
+/// Predicted merge conflicts for a map phase
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ConflictReport {
+    /// Clusters of items sharing modified files
+    pub clusters: Vec<ConflictCluster>,
+}
+
+impl ConflictReport {
+    /// Whether any conflicts are predicted
+    pub fn is_empty(&self) -> bool {
+        self.clusters.is_empty()
+    }
+
+    /// Number of items involved in predicted conflicts
+    pub fn conflicting_items(&self) -> usize {
+        self.clusters.iter().map(|c| c.items.len()).sum()
+    }
+
+    /// One-line description of each cluster
+    pub fn summary_lines(&self) -> Vec<String> {
+        self.clusters
+            .iter()
+            .map(|cluster| {
+                format!(
+                    "items [{}] overlap on {}",
+                    cluster.items.join(", "),
+                    cluster.files.join(", ")
+                )
+            })
+            .collect()
+    }
+}
+
+/// Group successful agents with commits by overlapping modified files
+///
+/// Two items are in the same cluster when they modify a common file, directly
+/// or through other items in the cluster.
+pub fn predict_conflicts(results: &[AgentResult]) -> ConflictReport {
+    let candidates: Vec<&AgentResult> = results
+        .iter()
+        .filter(|r| matches!(r.status, AgentStatus::Success) && !r.commits.is_empty())
+        .collect();
+
+    // Which candidates touch each file
+    let mut touched_by: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+    for (index, result) in candidates.iter().enumerate() {
+        let files: BTreeSet<&str> = result.files_modified.iter().map(String::as_str).collect();
+        for file in files {
+            touched_by.entry(file).or_default().push(index);
+        }
+    }
+
+    let mut parent: Vec<usize> = (0..candidates.len()).collect();
+    for indices in touched_by.values() {
+        for pair in indices.windows(2) {
+            union(&mut parent, pair[0], pair[1]);
+        }
+    }
+
+    let mut clusters: BTreeMap<usize, (BTreeSet<usize>, BTreeSet<&str>)> = BTreeMap::new();
+    for (file, indices) in &touched_by {
+        if indices.len() < 2 {
+            continue;
+        }
+        let root = find(&mut parent, indices[0]);
+        let entry = clusters.entry(root).or_default();
+        entry.0.extend(indices.iter().copied());
+        entry.1.insert(file);
+    }
+
+    let mut clusters: Vec<ConflictCluster> = clusters
+        .into_values()
+        .map(|(items, files)| ConflictCluster {
+            items: items
+                .into_iter()
+                .map(|i| candidates[i].item_id.clone())
+                .collect(),
+            files: files.into_iter().map(String::from).collect(),
+        })
+        .collect();
+    clusters.sort_by_key(|cluster| std::cmp::Reverse(cluster.items.len()));
+
+    ConflictReport { clusters }
+}
+
+fn find(parent: &mut [usize], mut node: usize) -> usize {
+    while parent[node] != node {
+        parent[node] = parent[parent[node]];
+        node = parent[node];
+    }
+    node
+}
+
+fn union(parent: &mut [usize], a: usize, b: usize) {
+    let (root_a, root_b) = (find(parent, a), find(parent, b));
+    if root_a != root_b {
+        parent[root_b.max(root_a)] = root_a.min(root_b);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn result(item_id: &str, files: &[&str]) -> AgentResult {
+        let mut result = AgentResult::success(item_id.to_string(), None, Duration::from_secs(1));
+        result.commits = vec![format!("commit-{item_id}")];
+        result.files_modified = files.iter().map(|f| f.to_string()).collect();
+        result
+    }
+
+    #[test]
+    fn test_no_overlap_predicts_nothing() {
+        let report = predict_conflicts(&[result("a", &["a.rs"]), result("b", &["b.rs"])]);
+        assert!(report.is_empty());
+    }
+
+    #[test]
+    fn test_transitive_overlap_forms_one_cluster() {
+        let report = predict_conflicts(&[
+            result("a", &["shared.rs", "a.rs"]),
+            result("b", &["shared.rs", "common.rs"]),
+            result("c", &["common.rs"]),
+            result("d", &["d.rs"]),
+            result("e", &["x.rs"]),
+            result("f", &["x.rs"]),
+        ]);
+
+        assert_eq!(
+            report.clusters,
+            vec![
+                ConflictCluster {
+                    items: vec!["a".into(), "b".into(), "c".into()],
+                    files: vec!["common.rs".into(), "shared.rs".into()],
+                },
+                ConflictCluster {
+                    items: vec!["e".into(), "f".into()],
+                    files: vec!["x.rs".into()],
+                },
+            ]
+        );
+        assert_eq!(report.conflicting_items(), 5);
+        assert_eq!(report.summary_lines()[1], "items [e, f] overlap on x.rs");
+    }
+
+    #[test]
+    fn test_failed_and_empty_agents_are_ignored() {
+        let mut failed = result("failed", &["shared.rs"]);
+        failed.status = AgentStatus::Failed("boom".into());
+        let mut no_commits = result("idle", &["shared.rs"]);
+        no_commits.commits.clear();
+
+        let report = predict_conflicts(&[result("a", &["shared.rs"]), failed, no_commits]);
+        assert!(report.is_empty());
+    }
+}
@@ -4,6 +4,7 @@
 //! to improve testability, reusability, and maintainability.
 
 pub mod aggregation;
+pub mod conflict_analysis;
 pub mod dependency_analysis;
 pub mod formatting;
 pub mod interpolation;
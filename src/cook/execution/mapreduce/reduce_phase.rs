@@ -98,6 +98,7 @@ pub fn create_reduce_context(
             project_dir: config.parent_worktree_path.clone().into(),
             worktree_name: Some(config.parent_worktree_name.clone().into()),
             session_id: format!("reduce-session-{}", config.job_id).into(),
+            package: None,
         },
     );
 
@@ -280,6 +281,7 @@ mod tests {
             worktree_session_id: None,
             json_log_location: None,
             cleanup_status: None,
+            no_changes: None,
         }
     }
 }
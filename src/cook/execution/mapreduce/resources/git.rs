@@ -499,6 +499,7 @@ mod tests {
             project_dir: Arc::new(std::path::PathBuf::from("/tmp/project")),
             worktree_name: Some(Arc::from("test-worktree")),
             session_id: Arc::from("test-session"),
+            package: None,
         };
 
         let result = GitOperations::validate_worktree_context(&env);
@@ -513,6 +514,7 @@ mod tests {
             project_dir: Arc::new(std::path::PathBuf::from("/tmp/project")),
             worktree_name: None,
             session_id: Arc::from("test-session"),
+            package: None,
         };
 
         let result = GitOperations::validate_worktree_context(&env);
@@ -568,6 +570,7 @@ mod tests {
             project_dir: Arc::new(std::path::PathBuf::from("/tmp")),
             worktree_name: None,
             session_id: Arc::from("test-session"),
+            package: None,
         };
 
         // Should fail because we're not in a worktree context
@@ -597,6 +600,7 @@ mod tests {
             project_dir: Arc::new(parent_path.clone()),
             worktree_name: Some(Arc::from("agent-worktree")),
             session_id: Arc::from("test-session"),
+            package: None,
         };
 
         let git_ops = GitOperations::new();
@@ -641,6 +645,7 @@ mod tests {
             project_dir: Arc::new(parent_path.clone()),
             worktree_name: Some(Arc::from("agent-worktree")),
             session_id: Arc::from("test-session"),
+            package: None,
         };
 
         let git_ops = GitOperations::new();
@@ -670,6 +675,7 @@ mod tests {
             project_dir: Arc::new(parent_path.clone()),
             worktree_name: Some(Arc::from("agent-worktree")),
             session_id: Arc::from("test-session"),
+            package: None,
         };
 
         let git_ops = GitOperations::new();
@@ -692,6 +698,7 @@ mod tests {
             project_dir: Arc::new(parent_path.clone()),
             worktree_name: Some(Arc::from("test-worktree")),
             session_id: Arc::from("test-session"),
+            package: None,
         };
 
         let git_ops = GitOperations::new();
@@ -758,6 +765,7 @@ mod tests {
             project_dir: Arc::new(parent_path.clone()),
             worktree_name: Some(Arc::from("agent-1")),
             session_id: Arc::from("test-session"),
+            package: None,
         };
 
         let git_ops = GitOperations::new();
@@ -770,6 +778,7 @@ mod tests {
             project_dir: Arc::new(parent_path.clone()),
             worktree_name: Some(Arc::from("agent-2")),
             session_id: Arc::from("test-session"),
+            package: None,
         };
 
         let result2 = git_ops.merge_agent_to_parent("agent-2", &env2).await;
@@ -0,0 +1,201 @@
+//! Setup-phase artifacts materialized into agent worktrees
+//!
+//! Files generated during setup, such as `debtmap.json` or coverage data, only
+//! exist in the parent worktree unless they are committed. Files declared under
+//! `setup.artifacts` are copied into every agent worktree instead:
+//!
+//! ```yaml
+//! setup:
+//!   commands:
+//!     - shell: "debtmap analyze . --output debtmap.json"
+//!   artifacts:
+//!     - debtmap.json
+//!     - coverage/**/*.json
+//! ```
+//!
+//! Artifacts are snapshotted as soon as setup finishes, so merges into the
+//! parent worktree during the map phase cannot change what later agents
+//! receive. The reduce phase runs in the parent worktree, where the original
+//! files still are.
+
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::process::{Command, Stdio};
+use tempfile::TempDir;
+use tracing::{info, warn};
+use walkdir::WalkDir;
+
+/// Check that an artifact pattern stays inside the worktree
+pub fn validate_artifact_pattern(pattern: &str) -> Result<()> {
+    if pattern.trim().is_empty() {
+        bail!("setup artifact pattern cannot be empty");
+    }
+    if !Path::new(pattern)
+        .components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+    {
+        bail!(
+            "setup artifact '{}' must be a relative path inside the worktree",
+            pattern
+        );
+    }
+    Ok(())
+}
+
+/// Snapshot of the files declared in `setup.artifacts`
+#[derive(Debug)]
+pub struct SetupArtifacts {
+    snapshot: TempDir,
+    paths: Vec<PathBuf>,
+}
+
+impl SetupArtifacts {
+    /// Copy every file matching `patterns` under `root` into a snapshot
+    ///
+    /// # Errors
+    /// Returns error if a pattern is invalid or matches nothing, or if copying fails
+    pub fn capture(patterns: &[String], root: &Path) -> Result<Self> {
+        let snapshot = TempDir::new().context("Failed to create setup artifact snapshot")?;
+        let mut paths = Vec::new();
+
+        for pattern in patterns {
+            validate_artifact_pattern(pattern)?;
+            let full_pattern = root.join(pattern);
+            let matches = glob::glob(&full_pattern.to_string_lossy())
+                .with_context(|| format!("Invalid setup artifact pattern '{}'", pattern))?
+                .filter_map(|entry| entry.ok())
+                .collect::<Vec<_>>();
+            if matches.is_empty() {
+                bail!("Setup artifact '{}' was not created by setup", pattern);
+            }
+
+            for source in matches {
+                let relative = source.strip_prefix(root)?.to_path_buf();
+                if paths.contains(&relative) {
+                    continue;
+                }
+                copy_path(&source, &snapshot.path().join(&relative))?;
+                if !is_git_ignored(root, &relative) {
+                    warn!(
+                        "Setup artifact {} is not ignored by git; agents that commit it will conflict when merging",
+                        relative.display()
+                    );
+                }
+                paths.push(relative);
+            }
+        }
+
+        info!("Captured {} setup artifact(s)", paths.len());
+        Ok(Self { snapshot, paths })
+    }
+
+    /// Captured paths, relative to the worktree root
+    pub fn paths(&self) -> &[PathBuf] {
+        &self.paths
+    }
+
+    /// Copy the snapshot into a worktree, replacing any existing files
+    ///
+    /// # Errors
+    /// Returns error if a file cannot be copied
+    pub fn materialize(&self, worktree: &Path) -> Result<()> {
+        for path in &self.paths {
+            copy_path(&self.snapshot.path().join(path), &worktree.join(path))?;
+        }
+        Ok(())
+    }
+}
+
+/// Copy a file or directory tree to `target`, creating parent directories
+fn copy_path(source: &Path, target: &Path) -> Result<()> {
+    for entry in WalkDir::new(source) {
+        let entry = entry?;
+        let relative = entry.path().strip_prefix(source)?;
+        let destination = if relative.as_os_str().is_empty() {
+            target.to_path_buf()
+        } else {
+            target.join(relative)
+        };
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(&destination)?;
+        } else {
+            if let Some(parent) = destination.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::copy(entry.path(), &destination).with_context(|| {
+                format!("Failed to copy setup artifact {}", entry.path().display())
+            })?;
+        }
+    }
+    Ok(())
+}
+
+/// Whether git ignores `path` in the repository at `root`
+fn is_git_ignored(root: &Path, path: &Path) -> bool {
+    Command::new("git")
+        .arg("check-ignore")
+        .arg("-q")
+        .arg(path)
+        .current_dir(root)
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_artifact_pattern() {
+        assert!(validate_artifact_pattern("debtmap.json").is_ok());
+        assert!(validate_artifact_pattern("./coverage/**/*.json").is_ok());
+        assert!(validate_artifact_pattern("").is_err());
+        assert!(validate_artifact_pattern("../secrets.json").is_err());
+        assert!(validate_artifact_pattern("/etc/passwd").is_err());
+    }
+
+    #[test]
+    fn test_capture_and_materialize() {
+        let parent = TempDir::new().unwrap();
+        let agent = TempDir::new().unwrap();
+        fs::write(parent.path().join("debtmap.json"), "{}").unwrap();
+        fs::create_dir_all(parent.path().join("coverage/unit")).unwrap();
+        fs::write(parent.path().join("coverage/unit/lcov.info"), "TN:").unwrap();
+        fs::write(agent.path().join("debtmap.json"), "stale").unwrap();
+
+        let artifacts = SetupArtifacts::capture(
+            &["debtmap.json".to_string(), "coverage".to_string()],
+            parent.path(),
+        )
+        .unwrap();
+        // Later changes in the parent do not reach agents
+        fs::write(parent.path().join("debtmap.json"), "changed").unwrap();
+        artifacts.materialize(agent.path()).unwrap();
+
+        assert_eq!(artifacts.paths().len(), 2);
+        assert_eq!(
+            fs::read_to_string(agent.path().join("debtmap.json")).unwrap(),
+            "{}"
+        );
+        assert_eq!(
+            fs::read_to_string(agent.path().join("coverage/unit/lcov.info")).unwrap(),
+            "TN:"
+        );
+    }
+
+    #[test]
+    fn test_capture_fails_for_missing_artifact() {
+        let parent = TempDir::new().unwrap();
+        fs::write(parent.path().join("a.json"), "{}").unwrap();
+
+        let globbed = SetupArtifacts::capture(&["*.json".to_string()], parent.path()).unwrap();
+        assert_eq!(globbed.paths(), &[PathBuf::from("a.json")]);
+
+        let err = SetupArtifacts::capture(&["missing.json".to_string()], parent.path())
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("missing.json"));
+    }
+}
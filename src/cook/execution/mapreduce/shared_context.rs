@@ -0,0 +1,256 @@
+//! Shared context computed once for all MapReduce agents
+//!
+//! Agents running the same template on many items each spend tokens
+//! re-deriving the same background, such as a summary of the repository.
+//! `setup.shared_context` computes it once and hands every agent the file:
+//!
+//! ```yaml
+//! setup:
+//!   shared_context:
+//!     commands:
+//!       - claude: "/summarize-repo --output .prodigy/repo-summary.md"
+//!     file: .prodigy/repo-summary.md
+//!   commands:
+//!     - shell: "debtmap analyze . --output debtmap.json"
+//! ```
+//!
+//! The commands run in the parent worktree before the other setup commands
+//! and must create `file`. The result is cached under `~/.prodigy/cache`,
+//! keyed by the commands and the commit checked out, so later jobs on the
+//! same commit skip the commands. A new commit invalidates the cache and
+//! replaces the cached file of the previous one. The file reaches agent
+//! worktrees like a setup artifact.
+
+use crate::cook::workflow::WorkflowStep;
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tracing::{info, warn};
+
+use super::setup_artifacts::validate_artifact_pattern;
+
+/// Context precomputed during setup and shared with every agent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SharedContextConfig {
+    /// Commands that write `file` in the parent worktree
+    pub commands: Vec<WorkflowStep>,
+
+    /// File holding the context, relative to the worktree root
+    pub file: String,
+}
+
+impl SharedContextConfig {
+    /// Check the configuration before running anything
+    pub fn validate(&self) -> Result<()> {
+        if self.commands.is_empty() {
+            bail!("setup.shared_context needs at least one command");
+        }
+        validate_artifact_pattern(&self.file).context("Invalid setup.shared_context.file")?;
+        if self.file.contains(['*', '?', '[']) {
+            bail!(
+                "setup.shared_context.file '{}' must name a single file",
+                self.file
+            );
+        }
+        Ok(())
+    }
+
+    /// Short hash identifying the commands and file (pure function)
+    pub fn fingerprint(&self) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(serde_json::to_string(&self.commands).unwrap_or_default());
+        hasher.update([0]);
+        hasher.update(self.file.as_bytes());
+        format!("{:x}", hasher.finalize())[..16].to_string()
+    }
+}
+
+/// Cached shared context for one configuration at one commit
+#[derive(Debug, Clone)]
+pub struct SharedContextCache {
+    dir: PathBuf,
+    fingerprint: String,
+    head: String,
+}
+
+impl SharedContextCache {
+    /// Cache for `config` at the commit checked out in `worktree`
+    ///
+    /// # Errors
+    /// Returns error if the checked-out commit cannot be determined
+    pub fn open(config: &SharedContextConfig, cache_dir: PathBuf, worktree: &Path) -> Result<Self> {
+        Ok(Self {
+            dir: cache_dir,
+            fingerprint: config.fingerprint(),
+            head: head_commit(worktree)?,
+        })
+    }
+
+    /// Commit the cache entry belongs to
+    pub fn head(&self) -> &str {
+        &self.head
+    }
+
+    /// Path of the cached file (pure function)
+    pub fn entry_path(&self) -> PathBuf {
+        self.dir.join(format!("{}-{}", self.fingerprint, self.head))
+    }
+
+    /// Write the cached context to `file` in `worktree`, returning whether there was one
+    ///
+    /// # Errors
+    /// Returns error if the cached file cannot be copied
+    pub fn restore(&self, worktree: &Path, file: &str) -> Result<bool> {
+        let entry = self.entry_path();
+        if !entry.is_file() {
+            return Ok(false);
+        }
+        let target = worktree.join(file);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::copy(&entry, &target)
+            .with_context(|| format!("Failed to restore shared context to {}", target.display()))?;
+        info!("Restored shared context {} from {}", file, entry.display());
+        Ok(true)
+    }
+
+    /// Cache `file` from `worktree`, dropping entries cached for other commits
+    ///
+    /// # Errors
+    /// Returns error if the commands did not create `file` or it cannot be cached
+    pub fn store(&self, worktree: &Path, file: &str) -> Result<()> {
+        let source = worktree.join(file);
+        if !source.is_file() {
+            bail!("Shared context commands did not create {}", file);
+        }
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create {}", self.dir.display()))?;
+        let entry = self.entry_path();
+        fs::copy(&source, &entry)
+            .with_context(|| format!("Failed to cache shared context at {}", entry.display()))?;
+
+        let prefix = format!("{}-", self.fingerprint);
+        for stale in fs::read_dir(&self.dir)?.filter_map(|e| e.ok()) {
+            let path = stale.path();
+            let is_stale = path != entry
+                && stale
+                    .file_name()
+                    .to_str()
+                    .is_some_and(|name| name.starts_with(&prefix));
+            if is_stale {
+                if let Err(e) = fs::remove_file(&path) {
+                    warn!(
+                        "Failed to remove stale shared context {}: {}",
+                        path.display(),
+                        e
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Directory caching shared context for the project at `project_dir`
+pub fn cache_dir(project_dir: &Path) -> Result<PathBuf> {
+    let repo_name = project_dir
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "default".to_string());
+    Ok(crate::storage::get_default_storage_dir()?
+        .join("cache")
+        .join(repo_name)
+        .join("shared-context"))
+}
+
+/// Commit checked out in `worktree`
+fn head_commit(worktree: &Path) -> Result<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .current_dir(worktree)
+        .output()
+        .context("Failed to run git rev-parse")?;
+    if !output.status.success() {
+        bail!(
+            "Failed to determine HEAD in {}: {}",
+            worktree.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn config(command: &str) -> SharedContextConfig {
+        SharedContextConfig {
+            commands: vec![WorkflowStep {
+                shell: Some(command.to_string()),
+                ..Default::default()
+            }],
+            file: "context/summary.md".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_validate_and_fingerprint() {
+        assert!(config("make summary").validate().is_ok());
+        assert_eq!(
+            config("make summary").fingerprint(),
+            config("make summary").fingerprint()
+        );
+        assert_ne!(
+            config("make summary").fingerprint(),
+            config("make other").fingerprint()
+        );
+
+        let mut invalid = config("make summary");
+        invalid.file = "../summary.md".to_string();
+        assert!(invalid.validate().is_err());
+        invalid.file = "*.md".to_string();
+        assert!(invalid.validate().is_err());
+        invalid.file = "summary.md".to_string();
+        invalid.commands.clear();
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn test_store_restore_and_invalidate() {
+        let cache_dir = TempDir::new().unwrap();
+        let parent = TempDir::new().unwrap();
+        let agent = TempDir::new().unwrap();
+        let config = config("make summary");
+        let cache_at = |head: &str| SharedContextCache {
+            dir: cache_dir.path().to_path_buf(),
+            fingerprint: config.fingerprint(),
+            head: head.to_string(),
+        };
+
+        let first = cache_at("aaa");
+        assert!(!first.restore(agent.path(), &config.file).unwrap());
+        assert!(first.store(parent.path(), &config.file).is_err());
+
+        fs::create_dir_all(parent.path().join("context")).unwrap();
+        fs::write(parent.path().join(&config.file), "summary at aaa").unwrap();
+        first.store(parent.path(), &config.file).unwrap();
+        assert!(first.restore(agent.path(), &config.file).unwrap());
+        assert_eq!(
+            fs::read_to_string(agent.path().join(&config.file)).unwrap(),
+            "summary at aaa"
+        );
+
+        // A new commit misses the cache and replaces the old entry once stored
+        let second = cache_at("bbb");
+        assert!(!second.restore(agent.path(), &config.file).unwrap());
+        second.store(parent.path(), &config.file).unwrap();
+        assert!(!first.entry_path().exists());
+        assert!(second.entry_path().exists());
+    }
+}
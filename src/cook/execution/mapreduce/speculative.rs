@@ -0,0 +1,274 @@
+//! Speculative execution of straggling map items
+//!
+//! When an item runs much longer than the median of the items completed so
+//! far, a backup agent is started on a fresh worktree. Whichever agent
+//! finishes first is merged and the other is cancelled and cleaned up.
+//! Backups only start when a parallel slot is free, so speculation uses idle
+//! capacity at the tail of a job instead of delaying queued items.
+//!
+//! ```yaml
+//! map:
+//!   speculative:
+//!     slowdown: 2.0        # straggler = running longer than 2x the median
+//!     min_completed: 3     # items that must finish before speculating
+//!     min_runtime_secs: 60 # never speculate on items shorter than this
+//! ```
+//!
+//! `speculative: true` enables speculation with these defaults.
+
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// How often running items are checked for stragglers
+pub const STRAGGLER_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Settings for launching backup agents
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpeculativeConfig {
+    /// Multiple of the median runtime after which an item is a straggler
+    #[serde(default = "default_slowdown")]
+    pub slowdown: f64,
+    /// Completed items needed before the median is trusted
+    #[serde(default = "default_min_completed")]
+    pub min_completed: usize,
+    /// Minimum runtime before an item can get a backup
+    #[serde(default = "default_min_runtime_secs")]
+    pub min_runtime_secs: u64,
+}
+
+fn default_slowdown() -> f64 {
+    2.0
+}
+
+fn default_min_completed() -> usize {
+    3
+}
+
+fn default_min_runtime_secs() -> u64 {
+    60
+}
+
+impl Default for SpeculativeConfig {
+    fn default() -> Self {
+        Self {
+            slowdown: default_slowdown(),
+            min_completed: default_min_completed(),
+            min_runtime_secs: default_min_runtime_secs(),
+        }
+    }
+}
+
+/// Deserialize `speculative: true|false` or a settings map
+pub fn deserialize_speculative<'de, D>(
+    deserializer: D,
+) -> Result<Option<SpeculativeConfig>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum SpeculativeValue {
+        Enabled(bool),
+        Config(SpeculativeConfig),
+    }
+
+    Ok(
+        match Option::<SpeculativeValue>::deserialize(deserializer)? {
+            None | Some(SpeculativeValue::Enabled(false)) => None,
+            Some(SpeculativeValue::Enabled(true)) => Some(SpeculativeConfig::default()),
+            Some(SpeculativeValue::Config(config)) => Some(config),
+        },
+    )
+}
+
+/// Median of a set of durations
+pub fn median_duration(durations: &[Duration]) -> Option<Duration> {
+    if durations.is_empty() {
+        return None;
+    }
+    let mut sorted = durations.to_vec();
+    sorted.sort();
+    let mid = sorted.len() / 2;
+    Some(if sorted.len() % 2 == 0 {
+        (sorted[mid - 1] + sorted[mid]) / 2
+    } else {
+        sorted[mid]
+    })
+}
+
+/// Whether an item running for `elapsed` deserves a backup (pure function)
+pub fn is_straggler(config: &SpeculativeConfig, elapsed: Duration, completed: &[Duration]) -> bool {
+    if elapsed < Duration::from_secs(config.min_runtime_secs)
+        || completed.len() < config.min_completed.max(1)
+    {
+        return false;
+    }
+    median_duration(completed)
+        .is_some_and(|median| elapsed.as_secs_f64() > median.as_secs_f64() * config.slowdown)
+}
+
+/// Which agent produced an item's result
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpeculativeWinner {
+    Primary,
+    Backup,
+}
+
+/// Shared speculation state for one map phase
+#[derive(Debug)]
+pub struct SpeculationTracker {
+    config: SpeculativeConfig,
+    completed: Mutex<Vec<Duration>>,
+    launched: AtomicUsize,
+    backup_wins: AtomicUsize,
+}
+
+impl SpeculationTracker {
+    pub fn new(config: SpeculativeConfig) -> Self {
+        Self {
+            config,
+            completed: Mutex::new(Vec::new()),
+            launched: AtomicUsize::new(0),
+            backup_wins: AtomicUsize::new(0),
+        }
+    }
+
+    /// Record the runtime of an item that finished without a backup
+    pub fn record_completion(&self, duration: Duration) {
+        let mut completed = match self.completed.lock() {
+            Ok(completed) => completed,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        completed.push(duration);
+    }
+
+    /// Median runtime of completed items
+    pub fn median(&self) -> Option<Duration> {
+        match self.completed.lock() {
+            Ok(completed) => median_duration(&completed),
+            Err(poisoned) => median_duration(&poisoned.into_inner()),
+        }
+    }
+
+    /// Whether an item running for `elapsed` should get a backup agent
+    pub fn is_straggler(&self, elapsed: Duration) -> bool {
+        match self.completed.lock() {
+            Ok(completed) => is_straggler(&self.config, elapsed, &completed),
+            Err(poisoned) => is_straggler(&self.config, elapsed, &poisoned.into_inner()),
+        }
+    }
+
+    /// Record that a backup agent was started
+    pub fn record_launch(&self) {
+        self.launched.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record which agent finished first for a speculated item
+    pub fn record_winner(&self, winner: SpeculativeWinner) {
+        if winner == SpeculativeWinner::Backup {
+            self.backup_wins.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Number of backup agents started
+    pub fn launched(&self) -> usize {
+        self.launched.load(Ordering::Relaxed)
+    }
+
+    /// Number of items whose backup finished first
+    pub fn backup_wins(&self) -> usize {
+        self.backup_wins.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secs(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|s| Duration::from_secs(*s)).collect()
+    }
+
+    #[test]
+    fn test_median_duration() {
+        assert_eq!(median_duration(&[]), None);
+        assert_eq!(
+            median_duration(&secs(&[30, 10, 20])),
+            Some(Duration::from_secs(20))
+        );
+        assert_eq!(
+            median_duration(&secs(&[10, 20, 30, 40])),
+            Some(Duration::from_secs(25))
+        );
+    }
+
+    #[test]
+    fn test_is_straggler() {
+        let config = SpeculativeConfig {
+            slowdown: 2.0,
+            min_completed: 3,
+            min_runtime_secs: 30,
+        };
+        let completed = secs(&[20, 25, 30]);
+
+        assert!(is_straggler(&config, Duration::from_secs(51), &completed));
+        assert!(!is_straggler(&config, Duration::from_secs(49), &completed));
+        // Too few completed items to trust the median
+        assert!(!is_straggler(
+            &config,
+            Duration::from_secs(500),
+            &secs(&[20, 25])
+        ));
+        // Short items never get backups
+        assert!(!is_straggler(
+            &config,
+            Duration::from_secs(29),
+            &secs(&[1, 1, 1])
+        ));
+    }
+
+    #[test]
+    fn test_deserialize_speculative() {
+        #[derive(Deserialize)]
+        struct Map {
+            #[serde(default, deserialize_with = "deserialize_speculative")]
+            speculative: Option<SpeculativeConfig>,
+        }
+
+        let parse = |yaml: &str| serde_yaml::from_str::<Map>(yaml).unwrap().speculative;
+        assert_eq!(parse("{}"), None);
+        assert_eq!(parse("speculative: false"), None);
+        assert_eq!(
+            parse("speculative: true"),
+            Some(SpeculativeConfig::default())
+        );
+        assert_eq!(
+            parse("speculative:\n  slowdown: 3.0"),
+            Some(SpeculativeConfig {
+                slowdown: 3.0,
+                ..Default::default()
+            })
+        );
+    }
+
+    #[test]
+    fn test_tracker_counts_launches_and_backup_wins() {
+        let tracker = SpeculationTracker::new(SpeculativeConfig {
+            min_runtime_secs: 0,
+            min_completed: 1,
+            ..Default::default()
+        });
+        assert!(!tracker.is_straggler(Duration::from_secs(100)));
+
+        tracker.record_completion(Duration::from_secs(10));
+        assert!(tracker.is_straggler(Duration::from_secs(21)));
+
+        tracker.record_launch();
+        tracker.record_winner(SpeculativeWinner::Backup);
+        tracker.record_winner(SpeculativeWinner::Primary);
+        assert_eq!(tracker.launched(), 1);
+        assert_eq!(tracker.backup_wins(), 1);
+    }
+}
@@ -257,6 +257,7 @@ mod tests {
                         files_modified: vec![],
                         json_log_location: None,
                         cleanup_status: None,
+                        no_changes: None,
                     },
                 );
 
@@ -275,6 +276,7 @@ mod tests {
                         files_modified: vec![],
                         json_log_location: None,
                         cleanup_status: None,
+                        no_changes: None,
                     },
                 );
 
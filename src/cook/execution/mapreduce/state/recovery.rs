@@ -296,6 +296,7 @@ mod tests {
                         files_modified: vec![],
                         json_log_location: None,
                         cleanup_status: None,
+                        no_changes: None,
                     },
                 );
 
@@ -314,6 +315,7 @@ mod tests {
                         files_modified: vec![],
                         json_log_location: None,
                         cleanup_status: None,
+                        no_changes: None,
                     },
                 );
 
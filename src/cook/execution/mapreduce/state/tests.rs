@@ -45,6 +45,7 @@ mod state_tests {
                             files_modified: vec![],
                             json_log_location: None,
                             cleanup_status: None,
+                            no_changes: None,
                         },
                     );
                 }
@@ -279,6 +280,7 @@ mod state_tests {
                             files_modified: vec![],
                             json_log_location: None,
                             cleanup_status: None,
+                            no_changes: None,
                         },
                     );
                 }
@@ -38,6 +38,8 @@ async fn test_register_agent_timeout() {
 
     let commands = vec![WorkflowStep {
         claude: Some("test command".to_string()),
+        context: None,
+        validate_output: None,
         ..WorkflowStep::default()
     }];
 
@@ -62,6 +64,8 @@ async fn test_command_timeout_calculation() {
     let commands = vec![
         WorkflowStep {
             claude: Some("claude command".to_string()),
+            context: None,
+            validate_output: None,
             ..WorkflowStep::default()
         },
         WorkflowStep {
@@ -154,6 +158,8 @@ async fn test_per_command_timeout_policy() {
     let commands = vec![
         WorkflowStep {
             claude: Some("command 1".to_string()),
+            context: None,
+            validate_output: None,
             ..WorkflowStep::default()
         },
         WorkflowStep {
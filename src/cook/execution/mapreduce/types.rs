@@ -28,7 +28,8 @@ pub struct MapReduceConfig {
     pub agent_timeout_secs: Option<u64>,
     /// Whether to continue on agent failures
     pub continue_on_failure: bool,
-    /// Batch size for processing work items
+    /// Number of consecutive work items each agent processes together
+    #[serde(default)]
     pub batch_size: Option<usize>,
     /// Enable checkpoint saving
     pub enable_checkpoints: bool,
@@ -69,6 +70,25 @@ pub struct SetupPhase {
     /// Variables to capture from setup commands
     #[serde(default)]
     pub capture_outputs: HashMap<String, CaptureConfig>,
+    /// Files generated by setup to copy into each agent worktree
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub artifacts: Vec<String>,
+    /// Context computed once, cached per commit, and copied into each agent worktree
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shared_context: Option<super::shared_context::SharedContextConfig>,
+}
+
+impl SetupPhase {
+    /// Files to copy into agent worktrees, including the shared context file
+    pub fn artifact_patterns(&self) -> Vec<String> {
+        let mut patterns = self.artifacts.clone();
+        if let Some(shared) = &self.shared_context {
+            if !patterns.contains(&shared.file) {
+                patterns.push(shared.file.clone());
+            }
+        }
+        patterns
+    }
 }
 
 /// Map phase configuration
@@ -90,12 +110,69 @@ pub struct MapPhase {
     /// Optional distinct field for deduplication
     #[serde(skip_serializing_if = "Option::is_none")]
     pub distinct: Option<String>,
+    /// Git ref whose changed files work items must refer to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub changed_since: Option<String>,
+    /// Field holding each item's file path for `changed_since`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub changed_path: Option<String>,
     /// Timeout configuration
     #[serde(skip_serializing_if = "Option::is_none")]
     pub timeout_config: Option<crate::cook::execution::mapreduce::timeout::TimeoutConfig>,
+    /// Branch to rebase agent commits onto before merging
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rebase_onto: Option<String>,
+    /// Backup agents for straggling items
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speculative: Option<super::speculative::SpeculativeConfig>,
+    /// Expression deriving stable item IDs from work items
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub item_key: Option<String>,
+    /// Expressions whose equal values keep items from running concurrently
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub affinity: Vec<String>,
+    /// Directories shared across agent worktrees
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub build_cache: Vec<crate::worktree::build_cache::BuildCacheEntry>,
+    /// Markers that skip items already applied by an earlier run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idempotency: Option<super::idempotency::IdempotencyConfig>,
+    /// Tickets opened for failure clusters when the job ends
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notifications: Option<super::issue_tracker::NotificationsConfig>,
+    /// Tool permissions for agent Claude commands
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub claude_permissions: Option<crate::cook::execution::claude_permissions::ClaudePermissions>,
+    /// Network access for agent shell commands
+    #[serde(
+        default,
+        skip_serializing_if = "super::network_isolation::AgentNetwork::is_allow"
+    )]
+    pub network: super::network_isolation::AgentNetwork,
     /// Workflow environment variables (resolved from env section and command-line args)
     #[serde(skip)]
     pub workflow_env: std::collections::HashMap<String, String>,
+    /// Fail agent and reduce steps that reference undefined `${...}` variables
+    #[serde(default)]
+    pub strict_variables: bool,
+    /// Setup artifacts captured after the setup phase, copied into each agent worktree
+    #[serde(skip)]
+    pub setup_artifacts: Option<std::sync::Arc<super::setup_artifacts::SetupArtifacts>>,
+    /// Fault injector shared with the run's other commands (`--chaos`)
+    #[serde(skip)]
+    pub chaos: Option<std::sync::Arc<crate::cook::execution::chaos::ChaosMonkey>>,
+    /// Recorder for JUnit XML output, shared with the run's other steps
+    #[serde(skip)]
+    pub junit: Option<std::sync::Arc<crate::cook::execution::junit::JunitRecorder>>,
+    /// Paths agent steps may not change
+    #[serde(skip)]
+    pub protected_paths: Option<std::sync::Arc<crate::cook::protected_paths::ProtectedPaths>>,
+    /// Seed of a deterministic run; agents then take slots in work-item order
+    #[serde(skip)]
+    pub seed: Option<u64>,
+    /// Receives the job ID, so a failure report can list its dead-lettered items
+    #[serde(skip)]
+    pub failures: Option<std::sync::Arc<crate::cook::workflow::failure_report::FailureRecorder>>,
 }
 
 /// Reduce phase configuration
@@ -105,6 +182,12 @@ pub struct ReducePhase {
     pub commands: Vec<crate::cook::workflow::WorkflowStep>,
     /// Timeout for reduce phase (in seconds)
     pub timeout_secs: Option<u64>,
+    /// File, relative to the parent worktree, to write the full map results to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub results_file: Option<String>,
+    /// Tool permissions for Claude commands, from the workflow's `claude_permissions:`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub claude_permissions: Option<crate::cook::execution::claude_permissions::ClaudePermissions>,
 }
 
 /// Options for resuming MapReduce jobs
@@ -265,7 +265,7 @@ reduce:
         // Verify map phase
         assert_eq!(config.map.input, "debtmap.json");
         assert_eq!(config.map.json_path, "$.items[*]");
-        assert_eq!(config.map.max_parallel, "5");
+        assert_eq!(config.map.max_parallel.as_deref(), Some("5"));
         assert_eq!(config.map.max_items, Some(10));
         assert_eq!(
             config.map.filter,
@@ -402,6 +402,8 @@ reduce:
             iterate: false,
             retry_defaults: None,
             environment: None,
+            always_steps: Vec::new(),
+            strict_variables: false,
         };
 
         assert_eq!(extended_workflow.name, "test-conversion");
@@ -7,7 +7,7 @@ use super::dlq::DeadLetterQueue;
 use super::errors::MapReduceError;
 use super::errors::MapReduceResult as MRResult;
 use super::events::{EventLogger, MapReduceEvent};
-use super::mapreduce::{AgentResult, MapPhase, MapReduceExecutor, ReducePhase};
+use super::mapreduce::{AgentResult, MapPhase, MapReduceConfig, MapReduceExecutor, ReducePhase};
 use super::state::{JobStateManager, MapReduceJobState};
 use crate::cook::orchestrator::ExecutionEnvironment;
 // Removed unused imports
@@ -32,6 +32,12 @@ pub struct EnhancedResumeOptions {
     pub from_checkpoint: Option<u32>,
     /// Maximum parallel agents (None uses original config)
     pub max_parallel: Option<usize>,
+    /// Maximum number of remaining items to process (None processes all)
+    #[serde(default)]
+    pub max_items: Option<usize>,
+    /// Per-agent timeout in seconds (None uses original config)
+    #[serde(default)]
+    pub agent_timeout_secs: Option<u64>,
     /// Force recreation of worktrees
     pub force_recreation: bool,
     /// Include DLQ items in resume
@@ -50,6 +56,8 @@ impl Default for EnhancedResumeOptions {
             skip_validation: false,
             from_checkpoint: None,
             max_parallel: None,
+            max_items: None,
+            agent_timeout_secs: None,
             force_recreation: false,
             include_dlq_items: true,
             validate_environment: true,
@@ -58,6 +66,30 @@ impl Default for EnhancedResumeOptions {
     }
 }
 
+impl EnhancedResumeOptions {
+    /// Apply the parallelism and timeout overrides to a job's map config (pure function)
+    ///
+    /// The checkpointed config is left untouched, so overrides only last for this resume.
+    pub fn override_config(&self, config: &MapReduceConfig) -> MapReduceConfig {
+        let mut config = config.clone();
+        if let Some(max_parallel) = self.max_parallel {
+            config.max_parallel = max_parallel;
+        }
+        if let Some(timeout) = self.agent_timeout_secs {
+            config.agent_timeout_secs = Some(timeout);
+        }
+        config
+    }
+
+    /// Limit the remaining items to `max_items` (pure function)
+    pub fn limit_items(&self, mut items: Vec<Value>) -> Vec<Value> {
+        if let Some(max_items) = self.max_items {
+            items.truncate(max_items);
+        }
+        items
+    }
+}
+
 /// Current phase of MapReduce execution
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum MapReducePhase {
@@ -549,6 +581,7 @@ impl MapReduceResumeManager {
         env: &ExecutionEnvironment,
         options: &EnhancedResumeOptions,
     ) -> MRResult<EnhancedResumeResult> {
+        let remaining_items = options.limit_items(remaining_items);
         info!(
             "Resuming map phase with {} remaining items",
             remaining_items.len()
@@ -575,7 +608,7 @@ impl MapReduceResumeManager {
         // NOTE: workflow_env is not preserved in resume state, so we use empty HashMap
         // This is acceptable as environment variables should be consistent across resume
         let map_phase = MapPhase {
-            config: state.config.clone(),
+            config: options.override_config(&state.config),
             json_path: None,
             agent_template: state.agent_template.clone(),
             filter: None,
@@ -584,6 +617,24 @@ impl MapReduceResumeManager {
             distinct: None,
             timeout_config: None,
             workflow_env: std::collections::HashMap::new(),
+            rebase_onto: None,
+            speculative: None,
+            item_key: None,
+            affinity: Vec::new(),
+            build_cache: Vec::new(),
+            network: Default::default(),
+            strict_variables: false,
+            setup_artifacts: None,
+            chaos: None,
+            junit: None,
+            protected_paths: None,
+            seed: None,
+            failures: None,
+            idempotency: None,
+            notifications: None,
+            claude_permissions: None,
+            changed_since: None,
+            changed_path: None,
         };
 
         // Return execution context so the caller can execute with a mutable executor
@@ -594,6 +645,8 @@ impl MapReduceResumeManager {
                 Box::new(ReducePhase {
                     commands: commands.clone(),
                     timeout_secs: None,
+                    results_file: None,
+                    claude_permissions: None,
                 })
             }),
             remaining_items: Box::new(remaining_items),
@@ -646,6 +699,8 @@ impl MapReduceResumeManager {
                     Box::new(ReducePhase {
                         commands: commands.clone(),
                         timeout_secs: None,
+                        results_file: None,
+                        claude_permissions: None,
                     })
                 }),
                 remaining_items: Box::new(Vec::new()), // No remaining items for reduce phase
@@ -679,6 +734,45 @@ mod tests {
         assert!(options.validate_environment);
     }
 
+    #[test]
+    fn test_resume_overrides_apply_to_remaining_work() {
+        let config = MapReduceConfig {
+            input: "items.json".to_string(),
+            json_path: "$[*]".to_string(),
+            max_parallel: 10,
+            agent_timeout_secs: Some(600),
+            continue_on_failure: false,
+            batch_size: None,
+            enable_checkpoints: true,
+            max_items: None,
+            offset: None,
+        };
+        let items: Vec<Value> = (0..5).map(|i| serde_json::json!({ "id": i })).collect();
+
+        let defaults = EnhancedResumeOptions::default();
+        assert_eq!(defaults.override_config(&config).max_parallel, 10);
+        assert_eq!(defaults.limit_items(items.clone()).len(), 5);
+
+        let options = EnhancedResumeOptions {
+            max_parallel: Some(2),
+            max_items: Some(3),
+            agent_timeout_secs: Some(120),
+            ..Default::default()
+        };
+        let overridden = options.override_config(&config);
+        assert_eq!(overridden.max_parallel, 2);
+        assert_eq!(overridden.agent_timeout_secs, Some(120));
+        assert_eq!(overridden.input, config.input);
+        assert_eq!(
+            options.limit_items(items),
+            vec![
+                serde_json::json!({ "id": 0 }),
+                serde_json::json!({ "id": 1 }),
+                serde_json::json!({ "id": 2 })
+            ]
+        );
+    }
+
     #[test]
     fn test_work_item_serialization() {
         let item = WorkItem {
@@ -738,6 +832,7 @@ mod tests {
                     worktree_path: Some(std::path::PathBuf::from("<test-worktree-path>")),
                     json_log_location: None,
                     cleanup_status: None,
+                    no_changes: None,
                 },
             );
         }
@@ -830,6 +925,7 @@ mod tests {
             project_dir: Arc::new(std::path::PathBuf::from("/tmp")),
             worktree_name: None,
             session_id: Arc::from("test-session"),
+            package: None,
         };
         let options = EnhancedResumeOptions::default();
 
@@ -873,6 +969,7 @@ mod tests {
             project_dir: Arc::new(std::path::PathBuf::from("/tmp")),
             worktree_name: None,
             session_id: Arc::from("test-session"),
+            package: None,
         };
         let options = EnhancedResumeOptions::default();
 
@@ -934,6 +1031,7 @@ mod tests {
             project_dir: Arc::new(std::path::PathBuf::from("/tmp")),
             worktree_name: None,
             session_id: Arc::from("test-session"),
+            package: None,
         };
         let options = EnhancedResumeOptions::default();
 
@@ -68,6 +68,7 @@ fn test_resume_result_serialization() {
             files_modified: vec![],
             json_log_location: None,
             cleanup_status: None,
+            no_changes: None,
         }],
     };
 
@@ -109,6 +110,7 @@ fn test_agent_context_creation() {
         project_dir: Arc::new(PathBuf::from("/test/project")),
         worktree_name: Some(Arc::from("test-worktree")),
         session_id: Arc::from("test-session"),
+        package: None,
     };
 
     let context = AgentContext::new(
@@ -133,6 +135,7 @@ fn test_agent_context_update_with_output() {
         project_dir: Arc::new(PathBuf::from("/test/project")),
         worktree_name: Some(Arc::from("test-worktree")),
         session_id: Arc::from("test-session"),
+        package: None,
     };
 
     let mut context = AgentContext::new(
@@ -163,6 +166,7 @@ fn test_agent_context_to_interpolation_context() {
         project_dir: Arc::new(PathBuf::from("/test/project")),
         worktree_name: Some(Arc::from("test-worktree")),
         session_id: Arc::from("test-session"),
+        package: None,
     };
 
     let mut context = AgentContext::new(
@@ -223,6 +227,7 @@ fn test_agent_result_serialization() {
         files_modified: vec!["src/main.rs".to_string()],
         json_log_location: None,
         cleanup_status: None,
+        no_changes: None,
     };
 
     let json = serde_json::to_string(&result).unwrap();
@@ -256,6 +261,9 @@ fn test_map_phase_configuration() {
         agent_template: vec![WorkflowStep {
             name: None,
             claude: Some("/fix-issue ${item.description}".to_string()),
+            model: None,
+            context: None,
+            validate_output: None,
             shell: None,
             test: None,
             foreach: None,
@@ -283,6 +291,13 @@ fn test_map_phase_configuration() {
             validation_timeout: None,
             ignore_validation_failure: false,
             when: None,
+            assert: None,
+            summarize_commits: None,
+            claude_chat: None,
+            script: None,
+            snapshot: false,
+            required: false,
+            commit_policy: None,
         }],
         filter: Some("severity == 'high'".to_string()),
         sort_by: Some("priority".to_string()),
@@ -290,6 +305,24 @@ fn test_map_phase_configuration() {
         distinct: None,
         timeout_config: None,
         workflow_env: HashMap::new(),
+        rebase_onto: None,
+        speculative: None,
+        item_key: None,
+        affinity: Vec::new(),
+        build_cache: Vec::new(),
+        network: Default::default(),
+        strict_variables: false,
+        setup_artifacts: None,
+        chaos: None,
+        junit: None,
+        protected_paths: None,
+        seed: None,
+        failures: None,
+        idempotency: None,
+        notifications: None,
+        claude_permissions: None,
+        changed_since: None,
+        changed_path: None,
     };
 
     assert_eq!(map_phase.config.max_parallel, 20);
@@ -307,6 +340,9 @@ fn test_reduce_phase_configuration() {
             WorkflowStep {
                 name: None,
                 claude: Some("/summarize-results".to_string()),
+                model: None,
+                context: None,
+                validate_output: None,
                 shell: None,
                 test: None,
                 foreach: None,
@@ -334,10 +370,20 @@ fn test_reduce_phase_configuration() {
                 validation_timeout: None,
                 ignore_validation_failure: false,
                 when: None,
+                assert: None,
+                summarize_commits: None,
+                claude_chat: None,
+                script: None,
+                snapshot: false,
+                required: false,
+                commit_policy: None,
             },
             WorkflowStep {
                 name: None,
                 claude: None,
+                model: None,
+                context: None,
+                validate_output: None,
                 shell: Some("git merge --no-ff agent-*".to_string()),
                 test: None,
                 foreach: None,
@@ -365,9 +411,18 @@ fn test_reduce_phase_configuration() {
                 validation_timeout: None,
                 ignore_validation_failure: false,
                 when: None,
+                assert: None,
+                summarize_commits: None,
+                claude_chat: None,
+                script: None,
+                snapshot: false,
+                required: false,
+                commit_policy: None,
             },
         ],
         timeout_secs: None,
+        results_file: None,
+        claude_permissions: None,
     };
 
     assert_eq!(reduce_phase.commands.len(), 2);
@@ -383,6 +438,7 @@ fn test_reduce_phase_variable_substitution() {
         project_dir: Arc::new(PathBuf::from("/test/project")),
         worktree_name: Some(Arc::from("test-worktree")),
         session_id: Arc::from("test-session"),
+        package: None,
     };
 
     let mut reduce_context = AgentContext::new(
@@ -438,6 +494,7 @@ fn test_reduce_phase_complex_variable_substitution() {
         project_dir: Arc::new(PathBuf::from("/test/project")),
         worktree_name: Some(Arc::from("test-worktree")),
         session_id: Arc::from("test-session"),
+        package: None,
     };
 
     let mut reduce_context = AgentContext::new(
@@ -514,6 +571,7 @@ fn test_reduce_context_has_map_variables() {
         project_dir: Arc::new(PathBuf::from("/test/project")),
         worktree_name: Some(Arc::from("test-worktree")),
         session_id: Arc::from("test-session"),
+        package: None,
     };
 
     // Simulate what execute_reduce_phase does after our fix
@@ -774,6 +832,9 @@ mod command_type_tests {
     fn test_collect_command_types_single_claude() {
         let step = WorkflowStep {
             claude: Some("/test-command".to_string()),
+            model: None,
+            context: None,
+            validate_output: None,
             name: None,
             shell: None,
             test: None,
@@ -802,6 +863,13 @@ mod command_type_tests {
             validation_timeout: None,
             ignore_validation_failure: false,
             when: None,
+            assert: None,
+            summarize_commits: None,
+            claude_chat: None,
+            script: None,
+            snapshot: false,
+            required: false,
+            commit_policy: None,
         };
 
         let commands = crate::cook::execution::mapreduce::command::collect_command_types(&step);
@@ -815,6 +883,9 @@ mod command_type_tests {
             shell: Some("echo test".to_string()),
             name: None,
             claude: None,
+            model: None,
+            context: None,
+            validate_output: None,
             test: None,
             foreach: None,
             write_file: None,
@@ -841,6 +912,13 @@ mod command_type_tests {
             validation_timeout: None,
             ignore_validation_failure: false,
             when: None,
+            assert: None,
+            summarize_commits: None,
+            claude_chat: None,
+            script: None,
+            snapshot: false,
+            required: false,
+            commit_policy: None,
         };
 
         let commands = crate::cook::execution::mapreduce::command::collect_command_types(&step);
@@ -852,6 +930,9 @@ mod command_type_tests {
     fn test_collect_command_types_multiple() {
         let step = WorkflowStep {
             claude: Some("/test-command".to_string()),
+            model: None,
+            context: None,
+            validate_output: None,
             shell: Some("echo test".to_string()),
             name: None,
             test: None,
@@ -880,6 +961,13 @@ mod command_type_tests {
             validation_timeout: None,
             ignore_validation_failure: false,
             when: None,
+            assert: None,
+            summarize_commits: None,
+            claude_chat: None,
+            script: None,
+            snapshot: false,
+            required: false,
+            commit_policy: None,
         };
 
         let commands = crate::cook::execution::mapreduce::command::collect_command_types(&step);
@@ -891,6 +979,9 @@ mod command_type_tests {
         let step = WorkflowStep {
             name: Some("/legacy-command".to_string()),
             claude: None,
+            model: None,
+            context: None,
+            validate_output: None,
             shell: None,
             test: None,
             foreach: None,
@@ -918,6 +1009,13 @@ mod command_type_tests {
             validation_timeout: None,
             ignore_validation_failure: false,
             when: None,
+            assert: None,
+            summarize_commits: None,
+            claude_chat: None,
+            script: None,
+            snapshot: false,
+            required: false,
+            commit_policy: None,
         };
 
         let commands = crate::cook::execution::mapreduce::command::collect_command_types(&step);
@@ -934,6 +1032,9 @@ mod command_type_tests {
         let step = WorkflowStep {
             name: Some("legacy-command".to_string()),
             claude: None,
+            model: None,
+            context: None,
+            validate_output: None,
             shell: None,
             test: None,
             foreach: None,
@@ -961,6 +1062,13 @@ mod command_type_tests {
             validation_timeout: None,
             ignore_validation_failure: false,
             when: None,
+            assert: None,
+            summarize_commits: None,
+            claude_chat: None,
+            script: None,
+            snapshot: false,
+            required: false,
+            commit_policy: None,
         };
 
         let commands = crate::cook::execution::mapreduce::command::collect_command_types(&step);
@@ -1467,6 +1575,7 @@ mod concurrency_tests {
                     files_modified: vec![],
                     json_log_location: None,
                     cleanup_status: None,
+                    no_changes: None,
                 };
 
                 res.lock().await.push(result);
@@ -1723,6 +1832,9 @@ mod additional_coverage_tests {
             commands: vec![WorkflowStep {
                 shell: Some("echo 'setup'".to_string()),
                 claude: None,
+                model: None,
+                context: None,
+                validate_output: None,
                 name: None,
                 test: None,
                 foreach: None,
@@ -1750,12 +1862,21 @@ mod additional_coverage_tests {
                 validation_timeout: None,
                 ignore_validation_failure: false,
                 when: None,
+                assert: None,
+                summarize_commits: None,
+                claude_chat: None,
+                script: None,
+                snapshot: false,
+                required: false,
+                commit_policy: None,
             }],
             timeout: Some(60),
             capture_outputs: HashMap::from([(
                 "setup_output".to_string(),
                 crate::cook::execution::variable_capture::CaptureConfig::Simple(0),
             )]),
+            artifacts: Vec::new(),
+            shared_context: None,
         };
 
         // Test serialization
@@ -1783,6 +1904,9 @@ mod additional_coverage_tests {
             },
             agent_template: vec![WorkflowStep {
                 claude: Some("/process ${item}".to_string()),
+                model: None,
+                context: None,
+                validate_output: None,
                 shell: None,
                 name: None,
                 test: None,
@@ -1811,6 +1935,13 @@ mod additional_coverage_tests {
                 validation_timeout: None,
                 ignore_validation_failure: false,
                 when: None,
+                assert: None,
+                summarize_commits: None,
+                claude_chat: None,
+                script: None,
+                snapshot: false,
+                required: false,
+                commit_policy: None,
             }],
             json_path: Some("$.items[*]".to_string()),
             filter: Some("item.priority == 'high'".to_string()),
@@ -1819,6 +1950,24 @@ mod additional_coverage_tests {
             distinct: Some("item.id".to_string()),
             timeout_config: None,
             workflow_env: HashMap::new(),
+            rebase_onto: None,
+            speculative: None,
+            item_key: None,
+            affinity: Vec::new(),
+            build_cache: Vec::new(),
+            network: Default::default(),
+            strict_variables: false,
+            setup_artifacts: None,
+            chaos: None,
+            junit: None,
+            protected_paths: None,
+            seed: None,
+            failures: None,
+            idempotency: None,
+            notifications: None,
+            claude_permissions: None,
+            changed_since: None,
+            changed_path: None,
         };
 
         assert_eq!(map_phase.config.max_parallel, 50);
@@ -1877,6 +2026,7 @@ mod additional_coverage_tests {
             files_modified: vec![],
             json_log_location: None,
             cleanup_status: None,
+            no_changes: None,
         };
 
         assert_eq!(minimal_result.item_id, "minimal");
@@ -1898,6 +2048,7 @@ mod additional_coverage_tests {
             files_modified: vec![],
             json_log_location: None,
             cleanup_status: None,
+            no_changes: None,
         };
 
         assert!(matches!(error_result.status, AgentStatus::Failed(_)));
@@ -1943,6 +2094,7 @@ mod additional_coverage_tests {
                 files_modified: vec![],
                 json_log_location: None,
                 cleanup_status: None,
+                no_changes: None,
             });
         }
 
@@ -5,10 +5,17 @@
 pub mod bridge;
 #[cfg(test)]
 pub mod bridge_tests;
+pub mod chaos;
 pub mod claude;
+pub mod claude_capabilities;
 pub mod claude_log_detection;
 #[cfg(test)]
 pub mod claude_log_path_test;
+pub mod claude_logs;
+pub mod claude_mock;
+pub mod claude_permissions;
+pub mod claude_runs;
+pub mod claude_sessions;
 pub mod claude_stream_handler;
 #[cfg(test)]
 pub mod claude_streaming_test;
@@ -16,7 +23,9 @@ pub mod command;
 #[cfg(test)]
 pub mod command_tests;
 pub mod data_pipeline;
+pub mod determinism;
 pub mod dlq;
+pub mod dlq_clustering;
 pub mod dlq_reprocessor;
 #[cfg(test)]
 pub mod dlq_reprocessor_test;
@@ -33,8 +42,13 @@ pub mod expression;
 pub mod foreach;
 #[cfg(test)]
 pub mod foreach_tests;
+pub mod github_actions;
+pub mod github_input;
 pub mod input_source;
 pub mod interpolation;
+pub mod job_compare;
+pub mod job_report;
+pub mod junit;
 pub mod mapreduce;
 #[cfg(test)]
 pub mod mapreduce_integration_tests;
@@ -60,10 +74,12 @@ pub mod progress_display_tests;
 #[cfg(test)]
 pub mod progress_tests;
 pub mod progress_tracker;
+pub mod replay;
 pub mod resume_lock;
 #[cfg(test)]
 pub mod resume_lock_tests;
 pub mod runner;
+pub mod sarif;
 pub mod setup_executor;
 #[cfg(test)]
 pub mod shell_failure_tests;
@@ -71,6 +87,8 @@ pub mod state;
 pub mod state_pure;
 #[cfg(test)]
 pub mod state_tests;
+pub mod step_profile;
+pub mod step_progress;
 pub mod variable_capture;
 #[cfg(test)]
 pub mod variable_capture_test;
@@ -79,13 +97,15 @@ pub mod variables;
 pub mod variables_test;
 
 pub use bridge::{create_legacy_executor, LegacyExecutorBridge};
-pub use claude::{ClaudeExecutor, ClaudeExecutorImpl};
+pub use claude::{ClaudeExecutor, ClaudeExecutorImpl, CLAUDE_MODEL_ENV};
+pub use claude_capabilities::{ClaudeCapabilities, ClaudeCliVersion};
 pub use command::{CommandRequest, CommandSpec, CommandType, ExecutionConfig, OutputFormat};
 pub use executor::{CommandExecutor as UnifiedExecutor, UnifiedCommandExecutor};
 pub use mapreduce::{
     AgentResult, AgentStatus, MapPhase, MapReduceConfig, MapReduceExecutor, ReducePhase,
     ResumeOptions, ResumeResult, SetupPhase,
 };
+pub use replay::{CommandFixture, FixtureKind, ReplayMode, ReplaySession};
 pub use resume_lock::{is_process_running, ResumeLock, ResumeLockData, ResumeLockManager};
 pub use runner::{CommandRunner, RealCommandRunner};
 
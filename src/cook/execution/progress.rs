@@ -4,6 +4,7 @@
 //! for parallel job execution.
 
 use crate::cook::execution::errors::MapReduceResult;
+use crate::styled_println;
 use axum::{
     extract::{
         ws::{Message, WebSocket, WebSocketUpgrade},
@@ -1071,7 +1072,7 @@ impl CLIProgressViewer {
             // Check if job is complete
             let metrics = self.tracker.metrics.read().await;
             if Self::is_job_complete(&metrics) {
-                println!("\n✅ Job completed!");
+                styled_println!("\n✅ Job completed!");
                 break;
             }
         }
@@ -1089,10 +1090,10 @@ impl CLIProgressViewer {
         };
         let elapsed = self.tracker.start_time.elapsed();
 
-        println!("╔════════════════════════════════════════════════════════════════╗");
-        println!("║  MapReduce Job: {}  ║", self.tracker.job_id);
-        println!("║  Progress: {:.1}% | Elapsed: {:?}  ║", progress, elapsed);
-        println!("╚════════════════════════════════════════════════════════════════╝");
+        styled_println!("╔════════════════════════════════════════════════════════════════╗");
+        styled_println!("║  MapReduce Job: {}  ║", self.tracker.job_id);
+        styled_println!("║  Progress: {:.1}% | Elapsed: {:?}  ║", progress, elapsed);
+        styled_println!("╚════════════════════════════════════════════════════════════════╝");
 
         Ok(())
     }
@@ -1101,8 +1102,8 @@ impl CLIProgressViewer {
     async fn render_cached_agents(&self) -> MapReduceResult<()> {
         if let Some(ref sampler) = self.sampler {
             if let Some((snapshot, _)) = sampler.get_cached().await {
-                println!("\n👥 Agent Status (cached):");
-                println!("{}", "─".repeat(60));
+                styled_println!("\n👥 Agent Status (cached):");
+                styled_println!("{}", "─".repeat(60));
 
                 for (id, state) in snapshot.agent_states.iter().take(10) {
                     let state_str = match state {
@@ -1112,11 +1113,11 @@ impl CLIProgressViewer {
                         _ => format!("{:?}", state),
                     };
 
-                    println!("  {}: {}", &id[..8.min(id.len())], state_str);
+                    styled_println!("  {}: {}", &id[..8.min(id.len())], state_str);
                 }
 
                 if snapshot.agent_states.len() > 10 {
-                    println!("  ... and {} more agents", snapshot.agent_states.len() - 10);
+                    styled_println!("  ... and {} more agents", snapshot.agent_states.len() - 10);
                 }
             }
         }
@@ -1133,10 +1134,10 @@ impl CLIProgressViewer {
         let progress = self.tracker.get_overall_progress().await;
         let elapsed = self.tracker.start_time.elapsed();
 
-        println!("╔════════════════════════════════════════════════════════════════╗");
-        println!("║  MapReduce Job: {}  ║", self.tracker.job_id);
-        println!("║  Progress: {:.1}% | Elapsed: {:?}  ║", progress, elapsed);
-        println!("╚════════════════════════════════════════════════════════════════╝");
+        styled_println!("╔════════════════════════════════════════════════════════════════╗");
+        styled_println!("║  MapReduce Job: {}  ║", self.tracker.job_id);
+        styled_println!("║  Progress: {:.1}% | Elapsed: {:?}  ║", progress, elapsed);
+        styled_println!("╚════════════════════════════════════════════════════════════════╝");
 
         Ok(())
     }
@@ -1145,24 +1146,27 @@ impl CLIProgressViewer {
     async fn render_metrics(&self) -> MapReduceResult<()> {
         let metrics = self.tracker.metrics.read().await;
 
-        println!("\n📊 Metrics:");
-        println!("{}", "─".repeat(60));
-        println!(
+        styled_println!("\n📊 Metrics:");
+        styled_println!("{}", "─".repeat(60));
+        styled_println!(
             "  Completed: {} | Failed: {} | Pending: {}",
-            metrics.completed_items, metrics.failed_items, metrics.pending_items
+            metrics.completed_items,
+            metrics.failed_items,
+            metrics.pending_items
         );
-        println!(
+        styled_println!(
             "  Active Agents: {} | Success Rate: {:.1}%",
-            metrics.active_agents, metrics.success_rate
+            metrics.active_agents,
+            metrics.success_rate
         );
-        println!(
+        styled_println!(
             "  Throughput: {:.2} items/sec (avg)",
             metrics.throughput_average
         );
 
         if let Some(etc) = metrics.estimated_completion {
             let remaining = etc.signed_duration_since(Utc::now());
-            println!(
+            styled_println!(
                 "  ETC: {} ({} remaining)",
                 etc.format("%H:%M:%S"),
                 format_duration(remaining.to_std().unwrap_or_default())
@@ -1176,8 +1180,8 @@ impl CLIProgressViewer {
     async fn render_agents(&self) -> MapReduceResult<()> {
         let agents = self.tracker.agents.read().await;
 
-        println!("\n👥 Agent Status:");
-        println!("{}", "─".repeat(60));
+        styled_println!("\n👥 Agent Status:");
+        styled_println!("{}", "─".repeat(60));
 
         for (id, progress) in agents.iter().take(10) {
             let bar = self.create_progress_bar(progress.progress_percentage);
@@ -1188,7 +1192,7 @@ impl CLIProgressViewer {
                 _ => format!("{:?}", progress.state),
             };
 
-            println!(
+            styled_println!(
                 "  {}: {} [{}] {:.1}%",
                 &id[..8],
                 state_str,
@@ -1198,7 +1202,7 @@ impl CLIProgressViewer {
         }
 
         if agents.len() > 10 {
-            println!("  ... and {} more agents", agents.len() - 10);
+            styled_println!("  ... and {} more agents", agents.len() - 10);
         }
 
         Ok(())
@@ -0,0 +1,336 @@
+//! Record-and-replay support for workflow command outputs
+//!
+//! A [`ReplaySession`] either records the outputs of Claude and shell commands
+//! executed during a real run into a JSON fixture file, or serves those
+//! recorded outputs back in place of executing the commands. Replaying a
+//! recorded run makes it possible to write deterministic integration tests for
+//! complex workflows without invoking Claude.
+//!
+//! Fixtures are matched by command kind and the fully interpolated command
+//! text. When the same command runs several times, its fixtures are consumed
+//! in the order they were recorded.
+
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Current fixture file format version
+pub const FIXTURE_FORMAT_VERSION: u32 = 1;
+
+/// Kind of command a fixture was recorded for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FixtureKind {
+    /// Claude CLI command
+    Claude,
+    /// Shell command
+    Shell,
+}
+
+impl std::fmt::Display for FixtureKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FixtureKind::Claude => write!(f, "claude"),
+            FixtureKind::Shell => write!(f, "shell"),
+        }
+    }
+}
+
+/// Recorded output of a single command execution
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommandFixture {
+    /// Kind of command
+    pub kind: FixtureKind,
+    /// Interpolated command text
+    pub command: String,
+    /// Whether the command succeeded
+    pub success: bool,
+    /// Exit code, if any
+    #[serde(default)]
+    pub exit_code: Option<i32>,
+    /// Captured standard output
+    #[serde(default)]
+    pub stdout: String,
+    /// Captured standard error
+    #[serde(default)]
+    pub stderr: String,
+}
+
+/// On-disk fixture file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FixtureFile {
+    /// Format version
+    pub version: u32,
+    /// Recorded fixtures in execution order
+    pub fixtures: Vec<CommandFixture>,
+}
+
+impl Default for FixtureFile {
+    fn default() -> Self {
+        Self {
+            version: FIXTURE_FORMAT_VERSION,
+            fixtures: Vec::new(),
+        }
+    }
+}
+
+impl FixtureFile {
+    /// Load a fixture file from disk
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read fixture file: {}", path.display()))?;
+        let file: FixtureFile = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse fixture file: {}", path.display()))?;
+        if file.version > FIXTURE_FORMAT_VERSION {
+            return Err(anyhow!(
+                "Fixture file {} uses unsupported format version {} (max {})",
+                path.display(),
+                file.version,
+                FIXTURE_FORMAT_VERSION
+            ));
+        }
+        Ok(file)
+    }
+
+    /// Write the fixture file to disk, creating parent directories as needed
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent).with_context(|| {
+                format!("Failed to create fixture directory: {}", parent.display())
+            })?;
+        }
+        let content = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, content)
+            .with_context(|| format!("Failed to write fixture file: {}", path.display()))
+    }
+}
+
+/// Whether a session records or replays fixtures
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplayMode {
+    /// Execute commands normally and record their outputs
+    Record,
+    /// Serve recorded outputs instead of executing commands
+    Replay,
+}
+
+#[derive(Debug, Default)]
+struct SessionState {
+    fixtures: Vec<CommandFixture>,
+    consumed: Vec<bool>,
+}
+
+/// Shared record/replay state for a single workflow run
+#[derive(Debug)]
+pub struct ReplaySession {
+    mode: ReplayMode,
+    path: PathBuf,
+    state: Mutex<SessionState>,
+}
+
+impl ReplaySession {
+    /// Start recording command outputs to `path`
+    ///
+    /// The file is written after every recorded command so fixtures survive
+    /// a failed or interrupted run.
+    pub fn record(path: impl Into<PathBuf>) -> Self {
+        Self {
+            mode: ReplayMode::Record,
+            path: path.into(),
+            state: Mutex::new(SessionState::default()),
+        }
+    }
+
+    /// Replay command outputs recorded in the fixture file at `path`
+    pub fn replay(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let file = FixtureFile::load(&path)?;
+        Ok(Self::from_fixtures(path, file.fixtures))
+    }
+
+    /// Replay an in-memory set of fixtures (useful for tests)
+    pub fn from_fixtures(path: impl Into<PathBuf>, fixtures: Vec<CommandFixture>) -> Self {
+        let consumed = vec![false; fixtures.len()];
+        Self {
+            mode: ReplayMode::Replay,
+            path: path.into(),
+            state: Mutex::new(SessionState { fixtures, consumed }),
+        }
+    }
+
+    /// Session mode
+    pub fn mode(&self) -> ReplayMode {
+        self.mode
+    }
+
+    /// Whether recorded outputs are served instead of executing commands
+    pub fn is_replaying(&self) -> bool {
+        self.mode == ReplayMode::Replay
+    }
+
+    /// Fixture file path
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Record a command output and persist the fixture file
+    ///
+    /// Does nothing when replaying.
+    pub fn record_fixture(&self, fixture: CommandFixture) -> Result<()> {
+        if self.is_replaying() {
+            return Ok(());
+        }
+
+        let mut state = self
+            .state
+            .lock()
+            .map_err(|_| anyhow!("Replay session state lock poisoned"))?;
+        state.fixtures.push(fixture);
+        state.consumed.push(false);
+
+        FixtureFile {
+            version: FIXTURE_FORMAT_VERSION,
+            fixtures: state.fixtures.clone(),
+        }
+        .save(&self.path)
+    }
+
+    /// Take the next unconsumed fixture recorded for `command`
+    pub fn next_fixture(&self, kind: FixtureKind, command: &str) -> Result<CommandFixture> {
+        let mut state = self
+            .state
+            .lock()
+            .map_err(|_| anyhow!("Replay session state lock poisoned"))?;
+
+        let index = find_next_fixture(&state.fixtures, &state.consumed, kind, command).ok_or_else(
+            || {
+                anyhow!(
+                    "No recorded fixture for {} command '{}' in {}",
+                    kind,
+                    command,
+                    self.path.display()
+                )
+            },
+        )?;
+        state.consumed[index] = true;
+        Ok(state.fixtures[index].clone())
+    }
+
+    /// Number of fixtures that have not been replayed yet
+    pub fn remaining(&self) -> usize {
+        self.state
+            .lock()
+            .map(|state| state.consumed.iter().filter(|c| !**c).count())
+            .unwrap_or(0)
+    }
+}
+
+/// Find the index of the first unconsumed fixture matching `kind` and `command`
+fn find_next_fixture(
+    fixtures: &[CommandFixture],
+    consumed: &[bool],
+    kind: FixtureKind,
+    command: &str,
+) -> Option<usize> {
+    fixtures
+        .iter()
+        .zip(consumed)
+        .position(|(fixture, used)| !used && fixture.kind == kind && fixture.command == command)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn fixture(kind: FixtureKind, command: &str, stdout: &str) -> CommandFixture {
+        CommandFixture {
+            kind,
+            command: command.to_string(),
+            success: true,
+            exit_code: Some(0),
+            stdout: stdout.to_string(),
+            stderr: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_record_then_replay_roundtrip() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("fixtures/run.json");
+
+        let recorder = ReplaySession::record(&path);
+        recorder
+            .record_fixture(fixture(FixtureKind::Claude, "/analyze", "done"))
+            .unwrap();
+        recorder
+            .record_fixture(fixture(FixtureKind::Shell, "cargo test", "ok"))
+            .unwrap();
+
+        let replay = ReplaySession::replay(&path).unwrap();
+        assert!(replay.is_replaying());
+        assert_eq!(replay.remaining(), 2);
+
+        let shell = replay
+            .next_fixture(FixtureKind::Shell, "cargo test")
+            .unwrap();
+        assert_eq!(shell.stdout, "ok");
+        let claude = replay
+            .next_fixture(FixtureKind::Claude, "/analyze")
+            .unwrap();
+        assert_eq!(claude.stdout, "done");
+        assert_eq!(replay.remaining(), 0);
+    }
+
+    #[test]
+    fn test_repeated_commands_replay_in_order() {
+        let replay = ReplaySession::from_fixtures(
+            "fixtures.json",
+            vec![
+                fixture(FixtureKind::Shell, "echo", "first"),
+                fixture(FixtureKind::Shell, "echo", "second"),
+            ],
+        );
+
+        assert_eq!(
+            replay
+                .next_fixture(FixtureKind::Shell, "echo")
+                .unwrap()
+                .stdout,
+            "first"
+        );
+        assert_eq!(
+            replay
+                .next_fixture(FixtureKind::Shell, "echo")
+                .unwrap()
+                .stdout,
+            "second"
+        );
+        assert!(replay.next_fixture(FixtureKind::Shell, "echo").is_err());
+    }
+
+    #[test]
+    fn test_missing_fixture_reports_command() {
+        let replay = ReplaySession::from_fixtures(
+            "fixtures.json",
+            vec![fixture(FixtureKind::Shell, "/analyze", "")],
+        );
+
+        // Kind must match as well as the command text
+        let err = replay
+            .next_fixture(FixtureKind::Claude, "/analyze")
+            .unwrap_err();
+        assert!(err.to_string().contains("claude command '/analyze'"));
+    }
+
+    #[test]
+    fn test_load_rejects_newer_format() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("fixtures.json");
+        std::fs::write(&path, r#"{"version": 99, "fixtures": []}"#).unwrap();
+
+        let err = ReplaySession::replay(&path).unwrap_err();
+        assert!(err.to_string().contains("unsupported format version"));
+    }
+}
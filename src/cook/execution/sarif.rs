@@ -0,0 +1,346 @@
+//! SARIF export of workflow findings
+//!
+//! Workflows often process structured findings, such as debtmap items or lint
+//! results, as MapReduce work items or captured JSON artifacts. These pure
+//! functions map such items to [SARIF 2.1.0] results, so they can be uploaded
+//! to GitHub code scanning and other tools that read SARIF.
+//!
+//! Each finding needs a file; items without one are skipped. The rule, line,
+//! message and level are taken from configurable dot-separated field paths, or
+//! detected from common field names when no path is given. Items wrapped in a
+//! single-key object, like debtmap's `{"Function": {...}}`, are unwrapped and
+//! the key is used as the rule when no other rule is found.
+//!
+//! [SARIF 2.1.0]: https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html
+
+use super::variable_capture::extract_json_path;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// SARIF schema referenced by exported logs
+pub const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+const FILE_FIELDS: &[&str] = &[
+    "location.file",
+    "file",
+    "path",
+    "file_path",
+    "filename",
+    "metrics.path",
+];
+const LINE_FIELDS: &[&str] = &[
+    "location.line",
+    "line",
+    "start_line",
+    "line_number",
+    "location.start_line",
+];
+const RULE_FIELDS: &[&str] = &["rule_id", "rule", "check", "code", "category", "debt_type"];
+const MESSAGE_FIELDS: &[&str] = &[
+    "message",
+    "description",
+    "title",
+    "recommendation.primary_action",
+    "summary",
+];
+const LEVEL_FIELDS: &[&str] = &["level", "severity", "priority"];
+
+/// Field paths used to read findings from items
+///
+/// Unset paths fall back to common field names.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SarifFieldMap {
+    pub rule: Option<String>,
+    pub file: Option<String>,
+    pub line: Option<String>,
+    pub message: Option<String>,
+    pub level: Option<String>,
+}
+
+/// One finding mapped from an item
+#[derive(Debug, Clone, PartialEq)]
+pub struct Finding {
+    pub rule_id: String,
+    pub file: String,
+    pub line: Option<u64>,
+    pub message: String,
+    pub level: &'static str,
+    /// Extra properties attached to the SARIF result
+    pub properties: Map<String, Value>,
+}
+
+/// Unwrap `{"Variant": {...}}` enum wrappers, returning the variant name
+fn unwrap_variant(item: &Value) -> (&Value, Option<&str>) {
+    match item.as_object() {
+        Some(map) if map.len() == 1 => {
+            let (key, inner) = map.iter().next().expect("one entry");
+            if inner.is_object() {
+                (inner, Some(key.as_str()))
+            } else {
+                (item, None)
+            }
+        }
+        _ => (item, None),
+    }
+}
+
+fn lookup(item: &Value, path: Option<&str>, defaults: &[&str]) -> Option<Value> {
+    match path {
+        Some(path) => extract_json_path(item, path.trim_start_matches("$.")),
+        None => defaults
+            .iter()
+            .find_map(|path| extract_json_path(item, path).filter(|v| !v.is_null())),
+    }
+    .filter(|v| !v.is_null())
+}
+
+/// Text for a rule or message value; enum-like objects use their variant name
+fn value_text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) if !s.trim().is_empty() => Some(s.trim().to_string()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        Value::Object(map) => map.keys().next().cloned(),
+        _ => None,
+    }
+}
+
+/// SARIF level for a severity string or score
+pub fn sarif_level(value: Option<&Value>) -> &'static str {
+    match value {
+        Some(Value::String(s)) => match s.to_lowercase().as_str() {
+            "error" | "critical" | "high" | "blocker" | "major" => "error",
+            "note" | "info" | "low" | "minor" | "hint" => "note",
+            "none" => "none",
+            _ => "warning",
+        },
+        Some(Value::Number(n)) => match n.as_f64() {
+            Some(score) if score >= 7.0 => "error",
+            Some(score) if score >= 4.0 => "warning",
+            Some(_) => "note",
+            None => "warning",
+        },
+        _ => "warning",
+    }
+}
+
+/// Map an item to a finding, or `None` when it has no file
+pub fn extract_finding(item: &Value, fields: &SarifFieldMap) -> Option<Finding> {
+    let (inner, variant) = unwrap_variant(item);
+    let file = lookup(inner, fields.file.as_deref(), FILE_FIELDS).and_then(|v| value_text(&v))?;
+    let line = lookup(inner, fields.line.as_deref(), LINE_FIELDS).and_then(|v| match v {
+        Value::Number(n) => n.as_u64(),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    });
+    let rule_id = lookup(inner, fields.rule.as_deref(), RULE_FIELDS)
+        .and_then(|v| value_text(&v))
+        .or_else(|| variant.map(str::to_string))
+        .unwrap_or_else(|| "finding".to_string());
+    let message = lookup(inner, fields.message.as_deref(), MESSAGE_FIELDS)
+        .and_then(|v| value_text(&v))
+        .unwrap_or_else(|| format!("{} in {}", rule_id, file));
+    let level = sarif_level(lookup(inner, fields.level.as_deref(), LEVEL_FIELDS).as_ref());
+
+    Some(Finding {
+        rule_id,
+        file,
+        line: line.filter(|l| *l > 0),
+        message,
+        level,
+        properties: Map::new(),
+    })
+}
+
+/// Repository-relative URI for a finding's file
+fn artifact_uri(file: &str, repo_root: Option<&Path>) -> String {
+    let path = Path::new(file);
+    let relative = repo_root
+        .and_then(|root| path.strip_prefix(root).ok())
+        .unwrap_or(path);
+    let uri = relative.to_string_lossy().replace('\\', "/");
+    uri.trim_start_matches("./").to_string()
+}
+
+/// Stable fingerprint so code scanning can track a finding across runs
+fn fingerprint(finding: &Finding, uri: &str) -> String {
+    let digest = Sha256::digest(format!("{}\0{}\0{}", finding.rule_id, uri, finding.message));
+    digest[..16].iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Build a SARIF log with one run from `findings`
+pub fn build_sarif_log(
+    findings: &[Finding],
+    tool_name: &str,
+    tool_version: &str,
+    repo_root: Option<&Path>,
+) -> Value {
+    // Rules in order of first appearance, each with its most severe level
+    let mut rule_index: BTreeMap<&str, usize> = BTreeMap::new();
+    let mut rules: Vec<(&str, &'static str)> = Vec::new();
+    for finding in findings {
+        let index = *rule_index.entry(&finding.rule_id).or_insert_with(|| {
+            rules.push((&finding.rule_id, finding.level));
+            rules.len() - 1
+        });
+        if level_rank(finding.level) > level_rank(rules[index].1) {
+            rules[index].1 = finding.level;
+        }
+    }
+
+    let results: Vec<Value> = findings
+        .iter()
+        .map(|finding| {
+            let uri = artifact_uri(&finding.file, repo_root);
+            let mut physical = json!({
+                "artifactLocation": { "uri": uri, "uriBaseId": "%SRCROOT%" }
+            });
+            if let Some(line) = finding.line {
+                physical["region"] = json!({ "startLine": line });
+            }
+            let mut result = json!({
+                "ruleId": finding.rule_id,
+                "ruleIndex": rule_index[finding.rule_id.as_str()],
+                "level": finding.level,
+                "message": { "text": finding.message },
+                "locations": [{ "physicalLocation": physical }],
+                "partialFingerprints": { "prodigyFindingHash/v1": fingerprint(finding, &uri) },
+            });
+            if !finding.properties.is_empty() {
+                result["properties"] = Value::Object(finding.properties.clone());
+            }
+            result
+        })
+        .collect();
+
+    let rules: Vec<Value> = rules
+        .iter()
+        .map(|(id, level)| {
+            json!({
+                "id": id,
+                "name": id,
+                "shortDescription": { "text": id },
+                "defaultConfiguration": { "level": level },
+            })
+        })
+        .collect();
+
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": tool_name,
+                    "version": tool_version,
+                    "informationUri": "https://github.com/iepathos/prodigy",
+                    "rules": rules,
+                }
+            },
+            "results": results,
+        }]
+    })
+}
+
+fn level_rank(level: &str) -> u8 {
+    match level {
+        "error" => 3,
+        "warning" => 2,
+        "note" => 1,
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_finding_detects_common_fields() {
+        let lint = json!({
+            "file": "src/lib.rs",
+            "line": "12",
+            "code": "clippy::unwrap_used",
+            "message": "used unwrap",
+            "severity": "high"
+        });
+        let finding = extract_finding(&lint, &SarifFieldMap::default()).unwrap();
+        assert_eq!(finding.rule_id, "clippy::unwrap_used");
+        assert_eq!(finding.file, "src/lib.rs");
+        assert_eq!(finding.line, Some(12));
+        assert_eq!(finding.message, "used unwrap");
+        assert_eq!(finding.level, "error");
+
+        // Debtmap-style enum wrapper with a nested location
+        let debt = json!({"Function": {
+            "location": {"file": "src/parser.rs", "function": "parse", "line": 40},
+            "debt_type": {"ComplexityHotspot": {"cyclomatic": 30}},
+            "recommendation": {"primary_action": "Extract helper functions"}
+        }});
+        let finding = extract_finding(&debt, &SarifFieldMap::default()).unwrap();
+        assert_eq!(finding.rule_id, "ComplexityHotspot");
+        assert_eq!(finding.line, Some(40));
+        assert_eq!(finding.message, "Extract helper functions");
+        assert_eq!(finding.level, "warning");
+
+        assert!(
+            extract_finding(&json!({"message": "no file"}), &SarifFieldMap::default()).is_none()
+        );
+    }
+
+    #[test]
+    fn test_extract_finding_with_explicit_fields() {
+        let item = json!({"loc": {"p": "a.py", "l": 3}, "kind": "E501", "score": 8.5});
+        let fields = SarifFieldMap {
+            rule: Some("kind".to_string()),
+            file: Some("$.loc.p".to_string()),
+            line: Some("loc.l".to_string()),
+            message: None,
+            level: Some("score".to_string()),
+        };
+        let finding = extract_finding(&item, &fields).unwrap();
+        assert_eq!(finding.rule_id, "E501");
+        assert_eq!(finding.file, "a.py");
+        assert_eq!(finding.line, Some(3));
+        assert_eq!(finding.message, "E501 in a.py");
+        assert_eq!(finding.level, "error");
+    }
+
+    #[test]
+    fn test_build_sarif_log() {
+        let items = [
+            json!({"file": "/repo/src/a.rs", "line": 1, "rule": "R1", "severity": "low"}),
+            json!({"file": "/repo/src/b.rs", "rule": "R1", "severity": "critical"}),
+            json!({"file": "src/c.rs", "line": 5, "rule": "R2"}),
+        ];
+        let findings: Vec<Finding> = items
+            .iter()
+            .filter_map(|i| extract_finding(i, &SarifFieldMap::default()))
+            .collect();
+        let log = build_sarif_log(&findings, "prodigy", "1.0.0", Some(Path::new("/repo")));
+
+        assert_eq!(log["version"], "2.1.0");
+        let run = &log["runs"][0];
+        let rules = run["tool"]["driver"]["rules"].as_array().unwrap();
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0]["id"], "R1");
+        assert_eq!(rules[0]["defaultConfiguration"]["level"], "error");
+
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(results.len(), 3);
+        let location = &results[0]["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "src/a.rs");
+        assert_eq!(location["region"]["startLine"], 1);
+        assert!(results[1]["locations"][0]["physicalLocation"]
+            .get("region")
+            .is_none());
+        assert_eq!(results[2]["ruleIndex"], 1);
+        assert_ne!(
+            results[0]["partialFingerprints"],
+            results[1]["partialFingerprints"]
+        );
+    }
+}
@@ -2,6 +2,8 @@
 //!
 //! Handles execution of setup commands with output capture and timeout management.
 
+use crate::cook::execution::progress::format_duration;
+use crate::cook::execution::step_progress::StepTracker;
 use crate::cook::execution::variable_capture::{CommandResult, VariableCaptureEngine};
 use crate::cook::execution::SetupPhase;
 use crate::cook::workflow::{WorkflowContext, WorkflowStep};
@@ -167,7 +169,15 @@ impl SetupPhaseExecutor {
                 };
 
                 // Execute the step
+                let tracker = StepTracker::start(&env.session_id, "setup", &step_name);
                 let step_result = executor.execute_step(step, env, context).await?;
+                let elapsed = tracker.finish(step_result.success);
+                info!(
+                    "Setup [{}/{}] finished in {}",
+                    index + 1,
+                    commands.len(),
+                    format_duration(elapsed)
+                );
 
                 // Validate commit requirement if specified
                 if step.commit_required {
@@ -176,19 +186,31 @@ impl SetupPhaseExecutor {
                         .await
                         .unwrap_or_else(|_| "unknown".to_string());
 
-                    // Check if commits were created (HEAD changed)
+                    // Check if commits were created (HEAD changed), unless the
+                    // step reported that no changes were needed
+                    let no_changes =
+                        crate::cook::no_changes::take(&env.working_dir, &step_result.stdout);
                     if head_before == head_after {
-                        let step_name = get_step_display_name(step);
-                        let mut error_msg = format!(
-                            "Step '{}' has commit_required=true but no commits were created",
-                            step_name
-                        );
-
-                        if let Some(log_path) = &step_result.json_log_location {
-                            error_msg.push_str(&format!("\n📝 Claude log: {}", log_path));
+                        if let Some(no_changes) = no_changes {
+                            info!(
+                                "Setup [{}/{}]: {}",
+                                index + 1,
+                                commands.len(),
+                                no_changes.describe()
+                            );
+                        } else {
+                            let step_name = get_step_display_name(step);
+                            let mut error_msg = format!(
+                                "Step '{}' has commit_required=true but no commits were created",
+                                step_name
+                            );
+
+                            if let Some(log_path) = &step_result.json_log_location {
+                                error_msg.push_str(&format!("\n📝 Claude log: {}", log_path));
+                            }
+
+                            return Err(anyhow!(error_msg));
                         }
-
-                        return Err(anyhow!(error_msg));
                     }
                 }
 
@@ -390,6 +412,8 @@ mod tests {
             commands: vec![WorkflowStep::default(), WorkflowStep::default()],
             timeout: Some(60),
             capture_outputs,
+            artifacts: Vec::new(),
+            shared_context: None,
         };
 
         let mut executor_impl = SetupPhaseExecutor::new(&setup_phase);
@@ -419,6 +443,7 @@ mod tests {
             project_dir: Arc::new(PathBuf::from(".")),
             session_id: Arc::from("test-session"),
             worktree_name: Some(Arc::from("test-worktree")),
+            package: None,
         };
 
         let mut context = WorkflowContext::default();
@@ -445,6 +470,8 @@ mod tests {
             commands: vec![WorkflowStep::default()],
             timeout: Some(0), // Immediate timeout
             capture_outputs: HashMap::new(),
+            artifacts: Vec::new(),
+            shared_context: None,
         };
 
         let mut executor_impl = SetupPhaseExecutor::new(&setup_phase);
@@ -471,6 +498,7 @@ mod tests {
             project_dir: Arc::new(PathBuf::from(".")),
             session_id: Arc::from("test-session"),
             worktree_name: Some(Arc::from("test-worktree")),
+            package: None,
         };
         let mut context = WorkflowContext::default();
 
@@ -77,6 +77,8 @@ async fn test_setup_phase_uses_worktree_directory() {
         }],
         timeout: None,
         capture_outputs: Default::default(),
+        artifacts: Vec::new(),
+        shared_context: None,
     };
 
     // Create setup executor
@@ -92,6 +94,7 @@ async fn test_setup_phase_uses_worktree_directory() {
         project_dir: Arc::new(main_repo.clone()),
         session_id: Arc::from("test-session"),
         worktree_name: Some(Arc::from("test-worktree")),
+        package: None,
     };
 
     let mut context = WorkflowContext::default();
@@ -141,6 +144,8 @@ async fn test_setup_phase_preserves_environment_working_dir() {
         }],
         timeout: None,
         capture_outputs: Default::default(),
+        artifacts: Vec::new(),
+        shared_context: None,
     };
 
     let mut setup_executor = SetupPhaseExecutor::new(&setup_phase);
@@ -152,6 +157,7 @@ async fn test_setup_phase_preserves_environment_working_dir() {
         project_dir: Arc::new(main_repo),
         session_id: Arc::from("test"),
         worktree_name: Some(Arc::from("test-worktree")),
+        package: None,
     };
 
     let mut context = WorkflowContext::default();
@@ -230,6 +230,9 @@ map:
             name: Some("failing-command".to_string()),
             shell: Some("exit 42".to_string()),
             claude: None,
+            model: None,
+            context: None,
+            validate_output: None,
             test: None,
             foreach: None,
             write_file: None,
@@ -256,6 +259,13 @@ map:
             working_dir: None,
             env: Default::default(),
             when: None,
+            assert: None,
+            summarize_commits: None,
+            claude_chat: None,
+            script: None,
+            snapshot: false,
+            required: false,
+            commit_policy: None,
         };
 
         // Create a minimal execution environment
@@ -265,6 +275,7 @@ map:
             project_dir: Arc::new(temp_dir.path().to_path_buf()),
             worktree_name: None,
             session_id: Arc::from("test-session"),
+            package: None,
         };
 
         // TODO: Create executor and run the step
@@ -1105,6 +1105,7 @@ mod tests {
             files_modified: vec![],
             json_log_location: None,
             cleanup_status: None,
+            no_changes: None,
         });
 
         // Save checkpoint
@@ -1260,6 +1261,7 @@ mod tests {
             files_modified: vec![],
             json_log_location: None,
             cleanup_status: None,
+            no_changes: None,
         };
 
         manager.update_agent_result(&job_id, result).await.unwrap();
@@ -1718,6 +1720,7 @@ mod tests {
                     files_modified: vec![],
                     json_log_location: None,
                     cleanup_status: None,
+                    no_changes: None,
                 },
             )
             .await
@@ -1739,6 +1742,7 @@ mod tests {
                     files_modified: vec![],
                     json_log_location: None,
                     cleanup_status: None,
+                    no_changes: None,
                 },
             )
             .await
@@ -304,6 +304,7 @@ mod tests {
             worktree_session_id: None,
             json_log_location: None,
             cleanup_status: None,
+            no_changes: None,
         }
     }
 
@@ -135,6 +135,7 @@ mod tests {
                     files_modified: vec![],
                     json_log_location: None,
                     cleanup_status: None,
+                    no_changes: None,
                 },
             )
             .await
@@ -156,6 +157,7 @@ mod tests {
                     files_modified: vec![],
                     json_log_location: None,
                     cleanup_status: None,
+                    no_changes: None,
                 },
             )
             .await
@@ -208,6 +210,7 @@ mod tests {
             files_modified: vec!["file1.rs".to_string()],
             json_log_location: None,
             cleanup_status: None,
+            no_changes: None,
         };
 
         state.update_agent_result(success_result.clone());
@@ -233,6 +236,7 @@ mod tests {
             files_modified: vec![],
             json_log_location: None,
             cleanup_status: None,
+            no_changes: None,
         };
 
         state.update_agent_result(failed_result);
@@ -285,6 +289,7 @@ mod tests {
             files_modified: vec![],
             json_log_location: None,
             cleanup_status: None,
+            no_changes: None,
         });
 
         state.update_agent_result(AgentResult {
@@ -300,6 +305,7 @@ mod tests {
             files_modified: vec![],
             json_log_location: None,
             cleanup_status: None,
+            no_changes: None,
         });
 
         // Save checkpoint
@@ -407,6 +413,7 @@ mod tests {
             files_modified: vec![],
             json_log_location: None,
             cleanup_status: None,
+            no_changes: None,
         };
 
         manager.update_agent_result(&job_id, result1).await.unwrap();
@@ -487,6 +494,7 @@ mod tests {
                     files_modified: vec![],
                     json_log_location: None,
                     cleanup_status: None,
+                    no_changes: None,
                 },
             )
             .await
@@ -508,6 +516,7 @@ mod tests {
                     files_modified: vec![],
                     json_log_location: None,
                     cleanup_status: None,
+                    no_changes: None,
                 },
             )
             .await
@@ -603,6 +612,7 @@ mod tests {
             files_modified: vec![],
             json_log_location: None,
             cleanup_status: None,
+            no_changes: None,
         });
 
         state.update_agent_result(AgentResult {
@@ -618,6 +628,7 @@ mod tests {
             files_modified: vec![],
             json_log_location: None,
             cleanup_status: None,
+            no_changes: None,
         });
 
         // Now map phase should be complete
@@ -778,6 +789,7 @@ mod tests {
                     files_modified: vec![],
                     json_log_location: None,
                     cleanup_status: None,
+                    no_changes: None,
                 },
             )
             .await
@@ -841,6 +853,7 @@ mod tests {
                     files_modified: vec![],
                     json_log_location: None,
                     cleanup_status: None,
+                    no_changes: None,
                 },
             )
             .await
@@ -911,6 +924,7 @@ mod tests {
                     files_modified: vec!["file1.rs".to_string()],
                     json_log_location: None,
                     cleanup_status: None,
+                    no_changes: None,
                 },
             )
             .await
@@ -932,6 +946,7 @@ mod tests {
                     files_modified: vec![],
                     json_log_location: None,
                     cleanup_status: None,
+                    no_changes: None,
                 },
             )
             .await
@@ -961,6 +976,7 @@ mod tests {
                     files_modified: vec!["file2.rs".to_string()],
                     json_log_location: None,
                     cleanup_status: None,
+                    no_changes: None,
                 },
             )
             .await
@@ -983,6 +999,7 @@ mod tests {
                     files_modified: vec!["file1_fixed.rs".to_string()],
                     json_log_location: None,
                     cleanup_status: None,
+                    no_changes: None,
                 },
             )
             .await
@@ -1013,6 +1030,7 @@ mod tests {
                     files_modified: vec!["file3.rs".to_string()],
                     json_log_location: None,
                     cleanup_status: None,
+                    no_changes: None,
                 },
             )
             .await
@@ -1034,6 +1052,7 @@ mod tests {
                     files_modified: vec!["file4.rs".to_string()],
                     json_log_location: None,
                     cleanup_status: None,
+                    no_changes: None,
                 },
             )
             .await
@@ -1109,6 +1128,7 @@ mod tests {
                     files_modified: vec!["file1.rs".to_string()],
                     json_log_location: None,
                     cleanup_status: None,
+                    no_changes: None,
                 },
             )
             .await
@@ -1130,6 +1150,7 @@ mod tests {
                     files_modified: vec![],
                     json_log_location: None,
                     cleanup_status: None,
+                    no_changes: None,
                 },
             )
             .await
@@ -1163,6 +1184,7 @@ mod tests {
                     files_modified: vec!["file3.rs".to_string()],
                     json_log_location: None,
                     cleanup_status: None,
+                    no_changes: None,
                 },
             )
             .await
@@ -1185,6 +1207,7 @@ mod tests {
                     files_modified: vec!["file2.rs".to_string()],
                     json_log_location: None,
                     cleanup_status: None,
+                    no_changes: None,
                 },
             )
             .await
@@ -1212,6 +1235,7 @@ mod tests {
                     files_modified: vec!["file4.rs".to_string()],
                     json_log_location: None,
                     cleanup_status: None,
+                    no_changes: None,
                 },
             )
             .await
@@ -1233,6 +1257,7 @@ mod tests {
                     files_modified: vec!["file5.rs".to_string()],
                     json_log_location: None,
                     cleanup_status: None,
+                    no_changes: None,
                 },
             )
             .await
@@ -1330,6 +1355,7 @@ mod tests {
                     files_modified: vec![],
                     json_log_location: None,
                     cleanup_status: None,
+                    no_changes: None,
                 },
             )
             .await
@@ -1352,6 +1378,7 @@ mod tests {
                     files_modified: vec![],
                     json_log_location: None,
                     cleanup_status: None,
+                    no_changes: None,
                 },
             )
             .await
@@ -1374,6 +1401,7 @@ mod tests {
                     files_modified: vec![],
                     json_log_location: None,
                     cleanup_status: None,
+                    no_changes: None,
                 },
             )
             .await
@@ -1419,6 +1447,7 @@ mod tests {
                     files_modified: vec![],
                     json_log_location: None,
                     cleanup_status: None,
+                    no_changes: None,
                 },
             )
             .await
@@ -1473,6 +1502,7 @@ mod tests {
                     files_modified: vec!["file1.rs".to_string()],
                     json_log_location: None,
                     cleanup_status: None,
+                    no_changes: None,
                 },
             )
             .await
@@ -1500,6 +1530,7 @@ mod tests {
                     files_modified: vec!["file2.rs".to_string()],
                     json_log_location: None,
                     cleanup_status: None,
+                    no_changes: None,
                 },
             )
             .await
@@ -1532,6 +1563,7 @@ mod tests {
                     files_modified: vec![],
                     json_log_location: None,
                     cleanup_status: None,
+                    no_changes: None,
                 },
             )
             .await
@@ -1553,6 +1585,7 @@ mod tests {
                     files_modified: vec![],
                     json_log_location: None,
                     cleanup_status: None,
+                    no_changes: None,
                 },
             )
             .await
@@ -1613,6 +1646,7 @@ mod tests {
                     files_modified: vec![],
                     json_log_location: None,
                     cleanup_status: None,
+                    no_changes: None,
                 },
             )
             .await
@@ -1634,6 +1668,7 @@ mod tests {
                     files_modified: vec![],
                     json_log_location: None,
                     cleanup_status: None,
+                    no_changes: None,
                 },
             )
             .await
@@ -1662,6 +1697,7 @@ mod tests {
                     files_modified: vec![],
                     json_log_location: None,
                     cleanup_status: None,
+                    no_changes: None,
                 },
             )
             .await
@@ -1684,6 +1720,7 @@ mod tests {
                     files_modified: vec![],
                     json_log_location: None,
                     cleanup_status: None,
+                    no_changes: None,
                 },
             )
             .await
@@ -1717,6 +1754,7 @@ mod tests {
                     files_modified: vec![],
                     json_log_location: None,
                     cleanup_status: None,
+                    no_changes: None,
                 },
             )
             .await
@@ -1738,6 +1776,7 @@ mod tests {
                     files_modified: vec![],
                     json_log_location: None,
                     cleanup_status: None,
+                    no_changes: None,
                 },
             )
             .await
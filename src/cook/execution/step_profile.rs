@@ -0,0 +1,203 @@
+//! Step profiling
+//!
+//! `prodigy run --profile-steps` measures every workflow step: its wall time,
+//! the CPU time of the commands it ran and their peak resident memory, taken
+//! from the subprocess layer's [`UsageMonitor`]. The profile is printed at the
+//! end of the run, slowest step first, and stored in the session metadata so
+//! `prodigy sessions show` can display it later.
+//!
+//! A run creates one [`StepProfiler`] and hands it to the workflow executor.
+//! Executors without a profiler measure nothing.
+
+use crate::cook::execution::progress::format_duration;
+use crate::subprocess::usage::UsageMonitor;
+use crate::worktree::disk_usage::format_size;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Session metadata key holding the recorded profile
+pub const STEP_PROFILE_METADATA_KEY: &str = "step_profile";
+
+/// Measurements of one executed step
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StepProfile {
+    pub step: String,
+    pub wall_secs: f64,
+    pub cpu_secs: f64,
+    pub peak_rss_bytes: u64,
+    pub success: bool,
+}
+
+/// Collects step profiles during a run
+#[derive(Debug, Default)]
+pub struct StepProfiler {
+    steps: Mutex<Vec<StepProfile>>,
+}
+
+impl StepProfiler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&self, profile: StepProfile) {
+        match self.steps.lock() {
+            Ok(mut steps) => steps.push(profile),
+            Err(poisoned) => poisoned.into_inner().push(profile),
+        }
+    }
+
+    /// Profiles recorded so far, in execution order
+    pub fn steps(&self) -> Vec<StepProfile> {
+        match self.steps.lock() {
+            Ok(steps) => steps.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+}
+
+/// A step being measured
+pub struct StepMeasurement {
+    profiler: Arc<StepProfiler>,
+    step: String,
+    started: Instant,
+    usage: UsageMonitor,
+}
+
+/// Start measuring `step` with `profiler`, if step profiling is enabled
+pub fn start(profiler: Option<&Arc<StepProfiler>>, step: &str) -> Option<StepMeasurement> {
+    profiler.map(|profiler| StepMeasurement {
+        profiler: Arc::clone(profiler),
+        step: step.to_string(),
+        started: Instant::now(),
+        usage: UsageMonitor::start(),
+    })
+}
+
+impl StepMeasurement {
+    /// Stop measuring and record the step
+    pub fn finish(self, success: bool) {
+        let wall_time = self.started.elapsed();
+        let usage = self.usage.finish();
+        self.profiler.record(StepProfile {
+            step: self.step,
+            wall_secs: wall_time.as_secs_f64(),
+            cpu_secs: usage.cpu_time.as_secs_f64(),
+            peak_rss_bytes: usage.peak_rss_bytes,
+            success,
+        });
+    }
+}
+
+/// Profile as a session metadata value
+pub fn to_metadata_value(steps: &[StepProfile]) -> serde_json::Value {
+    serde_json::to_value(steps).unwrap_or_default()
+}
+
+/// Profile recorded in session metadata, if any
+pub fn from_metadata(metadata: &HashMap<String, serde_json::Value>) -> Option<Vec<StepProfile>> {
+    metadata
+        .get(STEP_PROFILE_METADATA_KEY)
+        .and_then(|value| serde_json::from_value(value.clone()).ok())
+}
+
+/// Table of steps, slowest first, with their share of the total wall time (pure function)
+pub fn format_profile(steps: &[StepProfile]) -> Vec<String> {
+    let mut sorted: Vec<&StepProfile> = steps.iter().collect();
+    sorted.sort_by(|a, b| b.wall_secs.total_cmp(&a.wall_secs));
+    let total: f64 = steps.iter().map(|s| s.wall_secs).sum();
+
+    let mut lines = vec![format!(
+        "{:>10} {:>10} {:>10} {:>6}  Step",
+        "Wall", "CPU", "Peak RSS", "Share"
+    )];
+    for profile in sorted {
+        let share = if total > 0.0 {
+            profile.wall_secs / total * 100.0
+        } else {
+            0.0
+        };
+        let peak = if profile.peak_rss_bytes > 0 {
+            format_size(profile.peak_rss_bytes)
+        } else {
+            "-".to_string()
+        };
+        let failed = if profile.success { "" } else { " (failed)" };
+        lines.push(format!(
+            "{:>10} {:>10} {:>10} {:>5.1}%  {}{}",
+            format_secs(profile.wall_secs),
+            format_secs(profile.cpu_secs),
+            peak,
+            share,
+            profile.step,
+            failed
+        ));
+    }
+    lines
+}
+
+fn format_secs(secs: f64) -> String {
+    if secs < 60.0 {
+        format!("{:.1}s", secs)
+    } else {
+        format_duration(Duration::from_secs_f64(secs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(step: &str, wall_secs: f64, success: bool) -> StepProfile {
+        StepProfile {
+            step: step.to_string(),
+            wall_secs,
+            cpu_secs: wall_secs / 2.0,
+            peak_rss_bytes: 512 * 1024 * 1024,
+            success,
+        }
+    }
+
+    #[test]
+    fn test_format_profile_sorts_by_wall_time() {
+        let steps = vec![
+            profile("shell: cargo fmt", 2.0, true),
+            profile("claude: /implement", 5400.0, true),
+            profile("shell: cargo test", 1798.0, false),
+        ];
+        let lines = format_profile(&steps);
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].ends_with("Share  Step"));
+        assert!(lines[1].ends_with("75.0%  claude: /implement"));
+        assert!(lines[1].contains("1h 30m 0s"));
+        assert!(lines[1].contains("512.0 MB"));
+        assert!(lines[2].ends_with("shell: cargo test (failed)"));
+        assert!(lines[3].contains("2.0s"));
+    }
+
+    #[test]
+    fn test_profile_metadata_round_trip() {
+        let steps = vec![profile("shell: make", 3.5, true)];
+        let mut metadata = HashMap::new();
+        assert_eq!(from_metadata(&metadata), None);
+        metadata.insert(
+            STEP_PROFILE_METADATA_KEY.to_string(),
+            to_metadata_value(&steps),
+        );
+        assert_eq!(from_metadata(&metadata), Some(steps));
+    }
+
+    #[tokio::test]
+    async fn test_start_records_only_with_a_profiler() {
+        assert!(start(None, "shell: true").is_none());
+
+        let profiler = Arc::new(StepProfiler::new());
+        start(Some(&profiler), "shell: true").unwrap().finish(true);
+
+        let steps = profiler.steps();
+        assert_eq!(steps.len(), 1);
+        assert_eq!(steps[0].step, "shell: true");
+        assert!(steps[0].success);
+    }
+}
@@ -0,0 +1,605 @@
+//! Progress reported by shell steps through a line protocol
+//!
+//! Long-running scripts can print progress lines to stdout:
+//!
+//! ```text
+//! ::prodigy-progress 45/100 "building"
+//! ::prodigy-progress 80%
+//! ```
+//!
+//! The executor parses these lines while the command runs, logs them, and
+//! appends them to a JSON Lines file under `~/.prodigy/progress/` keyed by the
+//! session (standard workflows) or job (MapReduce). `prodigy progress <id>`
+//! and its web dashboard read that file.
+//!
+//! Workflow steps and MapReduce setup and reduce steps are also tracked
+//! while they run, whether or not they print progress lines. A
+//! [`StepTracker`] records when each step starts and finishes and shows a
+//! spinner with the elapsed time, which turns into a progress bar once the
+//! step reports progress.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use indicatif::{ProgressBar, ProgressStyle};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Steps running in the foreground, one per progress file, which progress
+/// lines written to that file are attributed to
+static ACTIVE_STEPS: Mutex<Vec<ActiveStep>> = Mutex::new(Vec::new());
+
+/// Prefix that marks a progress line in command output
+pub const PROGRESS_PREFIX: &str = "::prodigy-progress";
+
+/// Progress parsed from a single protocol line
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StepProgress {
+    pub current: u64,
+    pub total: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+impl StepProgress {
+    /// Completion percentage, capped at 100
+    pub fn percentage(&self) -> f32 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        ((self.current as f64 / self.total as f64) * 100.0).min(100.0) as f32
+    }
+}
+
+impl std::fmt::Display for StepProgress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Steps that never reported progress have no amount to show
+        if self.total == 0 {
+            return write!(f, "{}", self.message.as_deref().unwrap_or_default());
+        }
+        write!(
+            f,
+            "{}/{} ({:.0}%)",
+            self.current,
+            self.total,
+            self.percentage()
+        )?;
+        if let Some(message) = &self.message {
+            write!(f, " {}", message)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parse a `::prodigy-progress` line
+///
+/// Accepts `N/TOTAL` or `N%`, optionally followed by a message that may be
+/// quoted. Returns `None` for any other line.
+pub fn parse_progress_line(line: &str) -> Option<StepProgress> {
+    let rest = line.trim().strip_prefix(PROGRESS_PREFIX)?;
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+
+    let rest = rest.trim_start();
+    let (amount, message) = match rest.split_once(char::is_whitespace) {
+        Some((amount, message)) => (amount, message.trim()),
+        None => (rest, ""),
+    };
+
+    let (current, total) = if let Some(percent) = amount.strip_suffix('%') {
+        (percent.parse().ok()?, 100)
+    } else {
+        let (current, total) = amount.split_once('/')?;
+        (current.parse().ok()?, total.parse().ok()?)
+    };
+
+    let message = message
+        .strip_prefix('"')
+        .and_then(|m| m.strip_suffix('"'))
+        .unwrap_or(message);
+
+    Some(StepProgress {
+        current,
+        total,
+        message: (!message.is_empty()).then(|| message.to_string()),
+    })
+}
+
+/// Lifecycle of a tracked step
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StepStatus {
+    #[default]
+    Running,
+    Completed,
+    Failed,
+}
+
+impl std::fmt::Display for StepStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            StepStatus::Running => "running",
+            StepStatus::Completed => "completed",
+            StepStatus::Failed => "failed",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A progress update as stored in the progress file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StepProgressRecord {
+    /// What reported the progress (a step for standard workflows, an agent
+    /// worktree for MapReduce)
+    pub source: String,
+    /// The command that printed the progress line
+    pub step: String,
+    /// Workflow phase of a tracked step (`steps`, `setup` or `reduce`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub phase: Option<String>,
+    #[serde(default)]
+    pub status: StepStatus,
+    #[serde(flatten)]
+    pub progress: StepProgress,
+    /// When a tracked step started
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<DateTime<Utc>>,
+    /// Estimated seconds until the step finishes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub eta_secs: Option<u64>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl StepProgressRecord {
+    /// Time the step has been running, or ran for once finished
+    pub fn elapsed(&self) -> Option<Duration> {
+        let end = match self.status {
+            StepStatus::Running => Utc::now(),
+            _ => self.updated_at,
+        };
+        (end - self.started_at?).to_std().ok()
+    }
+}
+
+/// Location of the progress file for a session or job
+pub fn progress_file_path(id: &str) -> Result<PathBuf> {
+    Ok(crate::storage::get_default_storage_dir()?
+        .join("progress")
+        .join(format!("{}.jsonl", id)))
+}
+
+/// Latest progress per source, in the order the sources first reported
+///
+/// A missing file means nothing has been reported; unreadable lines are
+/// skipped.
+pub fn load_latest_progress(path: &Path) -> Result<Vec<StepProgressRecord>> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    };
+
+    Ok(latest_per_source(&content))
+}
+
+/// Keep the last record for each source (pure function)
+fn latest_per_source(content: &str) -> Vec<StepProgressRecord> {
+    let mut latest: Vec<StepProgressRecord> = Vec::new();
+    let mut positions = HashMap::new();
+    for line in content.lines().filter(|line| !line.trim().is_empty()) {
+        if let Ok(record) = serde_json::from_str::<StepProgressRecord>(line) {
+            match positions.get(&record.source) {
+                Some(&index) => latest[index] = record,
+                None => {
+                    positions.insert(record.source.clone(), latest.len());
+                    latest.push(record);
+                }
+            }
+        }
+    }
+    latest
+}
+
+/// Watches a command's output for progress lines
+#[derive(Debug, Clone)]
+pub struct StepProgressReporter {
+    path: Option<PathBuf>,
+    source: String,
+    step: String,
+}
+
+impl StepProgressReporter {
+    /// Report progress for `step` under the session or job `id`
+    pub fn new(id: &str, source: impl Into<String>, step: impl Into<String>) -> Self {
+        let path = progress_file_path(id)
+            .map_err(|e| tracing::debug!("Step progress will not be persisted: {}", e))
+            .ok();
+        Self {
+            path,
+            source: source.into(),
+            step: step.into(),
+        }
+    }
+
+    /// Reporter that writes to an explicit file
+    pub fn with_path(path: PathBuf, source: impl Into<String>, step: impl Into<String>) -> Self {
+        Self {
+            path: Some(path),
+            source: source.into(),
+            step: step.into(),
+        }
+    }
+
+    /// Handle one line of command output
+    pub fn observe_line(&self, line: &str) {
+        let Some(progress) = parse_progress_line(line) else {
+            return;
+        };
+
+        let mut record = StepProgressRecord {
+            source: self.source.clone(),
+            step: self.step.clone(),
+            phase: None,
+            status: StepStatus::Running,
+            progress,
+            started_at: None,
+            eta_secs: None,
+            updated_at: Utc::now(),
+        };
+
+        // Progress of the foreground step belongs to that step's entry
+        if let Some(active) = lock_active_steps()
+            .iter()
+            .find(|active| self.path.is_some() && active.path == self.path)
+        {
+            active.show_progress(&record.progress);
+            record.source = active.source.clone();
+            record.phase = Some(active.phase.clone());
+            record.started_at = Some(active.started_at);
+        }
+
+        tracing::info!("[{}] {}", record.source, record.progress);
+
+        if let Some(path) = &self.path {
+            if let Err(e) = append_record(path, &record) {
+                tracing::warn!("Failed to record step progress: {}", e);
+            }
+        }
+    }
+}
+
+/// The step currently tracked in the foreground
+struct ActiveStep {
+    path: Option<PathBuf>,
+    source: String,
+    phase: String,
+    started_at: DateTime<Utc>,
+    bar: ProgressBar,
+}
+
+impl ActiveStep {
+    /// Turn the spinner into a progress bar
+    fn show_progress(&self, progress: &StepProgress) {
+        if progress.total == 0 {
+            return;
+        }
+        if self.bar.length().is_none() {
+            self.bar.set_style(bar_style());
+        }
+        self.bar.set_length(progress.total);
+        self.bar.set_position(progress.current.min(progress.total));
+        self.bar
+            .set_message(progress.message.clone().unwrap_or_default());
+    }
+}
+
+fn lock_active_steps() -> std::sync::MutexGuard<'static, Vec<ActiveStep>> {
+    match ACTIVE_STEPS.lock() {
+        Ok(active) => active,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+fn spinner_style() -> ProgressStyle {
+    let style = ProgressStyle::with_template("{spinner} {prefix} [{elapsed}] {wide_msg}")
+        .unwrap_or_else(|_| ProgressStyle::default_spinner());
+    if crate::output::current().unicode {
+        style
+    } else {
+        style.tick_chars("|/-\\ ")
+    }
+}
+
+fn bar_style() -> ProgressStyle {
+    let style = ProgressStyle::with_template(
+        "{spinner} {prefix} [{elapsed}] [{bar:30}] {pos}/{len} ({percent}%) {wide_msg}",
+    )
+    .unwrap_or_else(|_| ProgressStyle::default_bar());
+    if crate::output::current().unicode {
+        style.progress_chars("█▓░")
+    } else {
+        style.progress_chars("#>-").tick_chars("|/-\\ ")
+    }
+}
+
+/// Tracks one workflow, setup or reduce step from start to finish
+///
+/// While the step runs, a spinner with the elapsed time is shown on the
+/// terminal and progress lines printed by its commands are attributed to
+/// it. Steps started while another step of the same session or job is
+/// tracked (such as steps of a nested workflow) are recorded but leave the
+/// foreground step in place. Dropping
+/// the tracker without calling [`StepTracker::finish`] records the step as
+/// failed.
+pub struct StepTracker {
+    path: Option<PathBuf>,
+    source: String,
+    name: String,
+    phase: String,
+    started_at: DateTime<Utc>,
+    started: Instant,
+    foreground: bool,
+    finished: bool,
+}
+
+impl StepTracker {
+    /// Start tracking step `name` of `phase` in the session or job `id`
+    pub fn start(id: &str, phase: &str, name: &str) -> Self {
+        let path = progress_file_path(id)
+            .map_err(|e| tracing::debug!("Step progress will not be persisted: {}", e))
+            .ok();
+        Self::start_with_path(path, phase, name)
+    }
+
+    /// Start tracking a step recorded in an explicit file
+    pub fn start_with_path(path: Option<PathBuf>, phase: &str, name: &str) -> Self {
+        let mut tracker = Self {
+            path,
+            source: format!("{}: {}", phase, truncate_label(name)),
+            name: name.to_string(),
+            phase: phase.to_string(),
+            started_at: Utc::now(),
+            started: Instant::now(),
+            foreground: false,
+            finished: false,
+        };
+        tracker.record(StepStatus::Running);
+
+        let mut active = lock_active_steps();
+        if !active.iter().any(|step| step.path == tracker.path) {
+            let bar = ProgressBar::new_spinner()
+                .with_style(spinner_style())
+                .with_prefix(tracker.source.clone());
+            bar.enable_steady_tick(Duration::from_millis(120));
+            crate::output::set_live_bar(Some(bar.clone()));
+            active.push(ActiveStep {
+                path: tracker.path.clone(),
+                source: tracker.source.clone(),
+                phase: tracker.phase.clone(),
+                started_at: tracker.started_at,
+                bar,
+            });
+            tracker.foreground = true;
+        }
+        drop(active);
+        tracker
+    }
+
+    /// Entry name of the step in progress views
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Stop tracking and record the outcome, returning the elapsed time
+    pub fn finish(mut self, success: bool) -> Duration {
+        self.complete(success)
+    }
+
+    fn complete(&mut self, success: bool) -> Duration {
+        self.finished = true;
+        let elapsed = self.started.elapsed();
+        if self.foreground {
+            let mut active = lock_active_steps();
+            if let Some(index) = active.iter().position(|step| step.path == self.path) {
+                active.remove(index).bar.finish_and_clear();
+            }
+            crate::output::set_live_bar(active.last().map(|step| step.bar.clone()));
+        }
+
+        let status = if success {
+            StepStatus::Completed
+        } else {
+            StepStatus::Failed
+        };
+        self.record(status);
+        tracing::debug!("{} {} after {:?}", self.source, status, elapsed);
+        elapsed
+    }
+
+    fn record(&self, status: StepStatus) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        // Keep the last reported amount so finished steps still show it
+        let progress = match status {
+            StepStatus::Running => None,
+            _ => load_latest_progress(path).ok().and_then(|records| {
+                records
+                    .into_iter()
+                    .find(|record| record.source == self.source)
+                    .map(|record| record.progress)
+            }),
+        };
+        let record = StepProgressRecord {
+            source: self.source.clone(),
+            step: self.name.clone(),
+            phase: Some(self.phase.clone()),
+            status,
+            progress: progress.unwrap_or(StepProgress {
+                current: 0,
+                total: 0,
+                message: None,
+            }),
+            started_at: Some(self.started_at),
+            eta_secs: None,
+            updated_at: Utc::now(),
+        };
+        if let Err(e) = append_record(path, &record) {
+            tracing::warn!("Failed to record step progress: {}", e);
+        }
+    }
+}
+
+/// Shorten a step name to fit on one line of the progress views
+fn truncate_label(name: &str) -> String {
+    let name = name.lines().next().unwrap_or_default();
+    match name.char_indices().nth(57) {
+        Some((end, _)) => format!("{}...", &name[..end]),
+        None => name.to_string(),
+    }
+}
+
+impl Drop for StepTracker {
+    fn drop(&mut self) {
+        if !self.finished {
+            self.complete(false);
+        }
+    }
+}
+
+/// Record progress that is not reported by a command, such as the map phase
+pub fn append_progress(id: &str, record: &StepProgressRecord) -> Result<()> {
+    append_record(&progress_file_path(id)?, record)
+}
+
+fn append_record(path: &Path, record: &StepProgressRecord) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+
+    let mut line = serde_json::to_string(record)?;
+    line.push('\n');
+
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    file.write_all(line.as_bytes())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_progress_line() {
+        assert_eq!(
+            parse_progress_line("::prodigy-progress 45/100 \"building\""),
+            Some(StepProgress {
+                current: 45,
+                total: 100,
+                message: Some("building".to_string()),
+            })
+        );
+        assert_eq!(
+            parse_progress_line("  ::prodigy-progress 80% linking objects"),
+            Some(StepProgress {
+                current: 80,
+                total: 100,
+                message: Some("linking objects".to_string()),
+            })
+        );
+        assert_eq!(
+            parse_progress_line("::prodigy-progress 3/7").map(|p| p.message),
+            Some(None)
+        );
+
+        assert_eq!(parse_progress_line("building 45/100"), None);
+        assert_eq!(parse_progress_line("::prodigy-progressive 1/2"), None);
+        assert_eq!(parse_progress_line("::prodigy-progress lots"), None);
+    }
+
+    #[test]
+    fn test_percentage_and_display() {
+        let progress = parse_progress_line("::prodigy-progress 1/3 \"tests\"").unwrap();
+        assert_eq!(progress.to_string(), "1/3 (33%) tests");
+        assert_eq!(
+            StepProgress {
+                current: 5,
+                total: 0,
+                message: None
+            }
+            .percentage(),
+            0.0
+        );
+    }
+
+    #[test]
+    fn test_reporter_keeps_latest_progress_per_source() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("progress").join("session-1.jsonl");
+
+        let build = StepProgressReporter::with_path(path.clone(), "build", "make all");
+        let tests = StepProgressReporter::with_path(path.clone(), "tests", "cargo test");
+        build.observe_line("compiling");
+        build.observe_line("::prodigy-progress 10/100");
+        tests.observe_line("::prodigy-progress 1/2 \"unit\"");
+        build.observe_line("::prodigy-progress 90/100 \"linking\"");
+
+        let records = load_latest_progress(&path).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].source, "build");
+        assert_eq!(records[0].progress.current, 90);
+        assert_eq!(records[0].step, "make all");
+        assert_eq!(records[1].source, "tests");
+
+        let missing = temp_dir.path().join("missing.jsonl");
+        assert!(load_latest_progress(&missing).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_tracker_records_step_lifecycle() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("job-1.jsonl");
+
+        let setup = StepTracker::start_with_path(Some(path.clone()), "setup", "cargo build");
+        let running = load_latest_progress(&path).unwrap();
+        assert_eq!(running[0].source, "setup: cargo build");
+        assert_eq!(running[0].status, StepStatus::Running);
+
+        // Progress lines of the step's commands land on the step's entry
+        let reporter = StepProgressReporter::with_path(path.clone(), "setup", "cargo build");
+        reporter.observe_line("::prodigy-progress 3/4 \"linking\"");
+        setup.finish(true);
+
+        let nested_parent = StepTracker::start_with_path(Some(path.clone()), "reduce", "merge");
+        drop(StepTracker::start_with_path(
+            Some(path.clone()),
+            "steps",
+            "nested",
+        ));
+        drop(nested_parent);
+
+        let records = load_latest_progress(&path).unwrap();
+        let sources: Vec<_> = records.iter().map(|r| r.source.as_str()).collect();
+        assert_eq!(
+            sources,
+            vec!["setup: cargo build", "reduce: merge", "steps: nested"]
+        );
+        assert_eq!(records[0].status, StepStatus::Completed);
+        assert_eq!(records[0].progress.current, 3);
+        assert_eq!(records[0].phase.as_deref(), Some("setup"));
+        assert!(records[0].elapsed().is_some());
+        // Dropped without finishing
+        assert_eq!(records[1].status, StepStatus::Failed);
+        assert_eq!(records[2].status, StepStatus::Failed);
+    }
+}
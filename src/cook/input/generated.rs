@@ -5,7 +5,11 @@ use async_trait::async_trait;
 use chrono::Utc;
 use rand::Rng;
 
-pub struct GeneratedInputProvider;
+#[derive(Default)]
+pub struct GeneratedInputProvider {
+    /// Seed of a deterministic run, for repeatable random values
+    seed: Option<u64>,
+}
 
 #[async_trait]
 impl InputProvider for GeneratedInputProvider {
@@ -209,6 +213,11 @@ impl InputProvider for GeneratedInputProvider {
 }
 
 impl GeneratedInputProvider {
+    /// Draw random values from the run's seed (`--seed`)
+    pub fn with_seed(seed: Option<u64>) -> Self {
+        Self { seed }
+    }
+
     fn generate_sequence(&self, config: &InputConfig) -> Result<Vec<ExecutionInput>> {
         let start = config
             .get_string("start")
@@ -275,7 +284,7 @@ impl GeneratedInputProvider {
             .and_then(|s| s.parse::<i64>().ok())
             .unwrap_or(100);
 
-        let mut rng = rand::rng();
+        let mut rng = crate::cook::execution::determinism::rng(self.seed, "generated-input", 0);
         let mut inputs = Vec::new();
 
         for i in 0..count {
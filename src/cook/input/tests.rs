@@ -115,7 +115,23 @@ fn test_legacy_adapter() {
         verbosity: 0,
         quiet: false,
         dry_run: false,
+        read_only: false,
+        record: None,
+        junit_output: None,
+        profile_steps: false,
+        replay: None,
+        chaos: None,
+        seed: None,
+        answers_file: None,
+        environment: None,
+        no_worktree: false,
         params: std::collections::HashMap::new(),
+        package: None,
+        changed_only: None,
+        explain_failure: false,
+        keep_going: false,
+        steps: Default::default(),
+        explicit_flags: Default::default(),
     };
 
     let config = LegacyInputAdapter::from_cook_command(&cmd).unwrap();
@@ -1189,7 +1205,7 @@ async fn test_environment_provider_supports() {
 async fn test_generated_sequence() {
     use generated::GeneratedInputProvider;
 
-    let provider = GeneratedInputProvider;
+    let provider = GeneratedInputProvider::default();
     let mut config = provider::InputConfig::new();
 
     config.set("generator".to_string(), json!("sequence"));
@@ -1233,7 +1249,7 @@ async fn test_generated_sequence() {
 async fn test_generated_random() {
     use generated::GeneratedInputProvider;
 
-    let provider = GeneratedInputProvider;
+    let provider = GeneratedInputProvider::default();
     let mut config = provider::InputConfig::new();
 
     config.set("generator".to_string(), json!("random"));
@@ -1259,7 +1275,7 @@ async fn test_generated_random() {
 async fn test_generated_uuid() {
     use generated::GeneratedInputProvider;
 
-    let provider = GeneratedInputProvider;
+    let provider = GeneratedInputProvider::default();
     let mut config = provider::InputConfig::new();
 
     config.set("generator".to_string(), json!("uuid"));
@@ -1279,7 +1295,7 @@ async fn test_generated_uuid() {
 async fn test_generated_timestamp() {
     use generated::GeneratedInputProvider;
 
-    let provider = GeneratedInputProvider;
+    let provider = GeneratedInputProvider::default();
     let mut config = provider::InputConfig::new();
 
     config.set("generator".to_string(), json!("timestamp"));
@@ -1316,7 +1332,7 @@ async fn test_generated_timestamp() {
 async fn test_generated_range() {
     use generated::GeneratedInputProvider;
 
-    let provider = GeneratedInputProvider;
+    let provider = GeneratedInputProvider::default();
     let mut config = provider::InputConfig::new();
 
     config.set("generator".to_string(), json!("range"));
@@ -1345,7 +1361,7 @@ async fn test_generated_range() {
 async fn test_generated_grid() {
     use generated::GeneratedInputProvider;
 
-    let provider = GeneratedInputProvider;
+    let provider = GeneratedInputProvider::default();
     let mut config = provider::InputConfig::new();
 
     config.set("generator".to_string(), json!("grid"));
@@ -1379,7 +1395,7 @@ async fn test_generated_grid() {
 async fn test_generated_fibonacci() {
     use generated::GeneratedInputProvider;
 
-    let provider = GeneratedInputProvider;
+    let provider = GeneratedInputProvider::default();
     let mut config = provider::InputConfig::new();
 
     config.set("generator".to_string(), json!("fibonacci"));
@@ -1406,7 +1422,7 @@ async fn test_generated_fibonacci() {
 async fn test_generated_factorial() {
     use generated::GeneratedInputProvider;
 
-    let provider = GeneratedInputProvider;
+    let provider = GeneratedInputProvider::default();
     let mut config = provider::InputConfig::new();
 
     config.set("generator".to_string(), json!("factorial"));
@@ -1433,7 +1449,7 @@ async fn test_generated_factorial() {
 async fn test_generated_prime() {
     use generated::GeneratedInputProvider;
 
-    let provider = GeneratedInputProvider;
+    let provider = GeneratedInputProvider::default();
     let mut config = provider::InputConfig::new();
 
     config.set("generator".to_string(), json!("prime"));
@@ -1460,7 +1476,7 @@ async fn test_generated_prime() {
 async fn test_generated_validation() {
     use generated::GeneratedInputProvider;
 
-    let provider = GeneratedInputProvider;
+    let provider = GeneratedInputProvider::default();
 
     // Test invalid generator type
     let mut config = provider::InputConfig::new();
@@ -1494,7 +1510,7 @@ async fn test_generated_validation() {
 async fn test_generated_available_variables() {
     use generated::GeneratedInputProvider;
 
-    let provider = GeneratedInputProvider;
+    let provider = GeneratedInputProvider::default();
 
     // Test sequence generator variables
     let mut config = provider::InputConfig::new();
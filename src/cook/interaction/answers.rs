@@ -0,0 +1,244 @@
+//! Recorded prompt answers
+//!
+//! Every prompt answered during a run, whether by the user or by a
+//! non-interactive policy, is recorded with its answer and stored in the
+//! session metadata. `prodigy sessions answers <session>` writes them to an
+//! answers file, and `prodigy run --answers-file answers.yml` answers the
+//! prompts of a later run from it, so a workflow certified interactively
+//! once can run unattended with the same decisions:
+//!
+//! ```yaml
+//! answers:
+//!   - kind: confirmation
+//!     prompt: "Apply the migration?"
+//!     answer: true
+//!   - kind: merge_confirmation
+//!     prompt: "Merge session-1234 to main"
+//!     answer: false
+//! ```
+//!
+//! Prompt texts often contain session or worktree names that change between
+//! runs, so answers are consumed in order per prompt kind; a differing prompt
+//! text is only logged. A prompt with no answer left fails instead of waiting
+//! for input that never comes.
+//!
+//! A run creates one [`InteractionLog`] and hands it to the prompt broker of
+//! its user interaction; the recorded answers are stored with the session when
+//! the run ends.
+
+use super::broker::PromptKind;
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Mutex;
+
+/// Session metadata key holding the recorded answers
+pub const ANSWERS_METADATA_KEY: &str = "answers";
+
+/// Answer given to a prompt
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Answer {
+    /// Yes/no answer
+    Confirm(bool),
+    /// Index of the selected choice
+    Choice(usize),
+    /// Text input
+    Text(String),
+}
+
+/// One answered prompt
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedAnswer {
+    pub kind: PromptKind,
+    pub prompt: String,
+    pub answer: Answer,
+}
+
+/// Contents of an answers file
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AnswersFile {
+    pub answers: Vec<RecordedAnswer>,
+}
+
+/// Records the answers of a run and replays those of an answers file
+#[derive(Debug, Default)]
+pub struct InteractionLog {
+    recorded: Mutex<Vec<RecordedAnswer>>,
+    replay: Option<Mutex<Vec<Option<RecordedAnswer>>>>,
+}
+
+impl InteractionLog {
+    /// Log recording answers as they are given
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Log answering prompts from `answers` instead of asking
+    pub fn replaying(answers: Vec<RecordedAnswer>) -> Self {
+        Self {
+            recorded: Mutex::new(Vec::new()),
+            replay: Some(Mutex::new(answers.into_iter().map(Some).collect())),
+        }
+    }
+
+    /// Next recorded answer for a prompt of `kind`, when replaying
+    pub fn replayed(&self, kind: PromptKind, prompt: &str) -> Result<Option<Answer>> {
+        let Some(replay) = &self.replay else {
+            return Ok(None);
+        };
+        let mut remaining = match replay.lock() {
+            Ok(remaining) => remaining,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let recorded = remaining
+            .iter_mut()
+            .find(|answer| matches!(answer, Some(answer) if answer.kind == kind))
+            .and_then(Option::take)
+            .ok_or_else(|| {
+                anyhow!(
+                    "The answers file has no answer left for {:?} prompt: {}",
+                    kind,
+                    prompt
+                )
+            })?;
+        if recorded.prompt != prompt {
+            tracing::warn!(
+                "Answering '{}' with the answer recorded for '{}'",
+                prompt,
+                recorded.prompt
+            );
+        }
+        Ok(Some(recorded.answer))
+    }
+
+    pub fn record(&self, kind: PromptKind, prompt: &str, answer: Answer) {
+        let answer = RecordedAnswer {
+            kind,
+            prompt: prompt.to_string(),
+            answer,
+        };
+        match self.recorded.lock() {
+            Ok(mut recorded) => recorded.push(answer),
+            Err(poisoned) => poisoned.into_inner().push(answer),
+        }
+    }
+
+    /// Answers recorded so far, in the order they were given
+    pub fn recorded(&self) -> Vec<RecordedAnswer> {
+        match self.recorded.lock() {
+            Ok(recorded) => recorded.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+}
+
+/// Read the answers of an answers file
+pub fn load_answers_file(path: &Path) -> Result<Vec<RecordedAnswer>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read answers file {}", path.display()))?;
+    let file: AnswersFile = serde_yaml::from_str(&content)
+        .with_context(|| format!("Invalid answers file {}", path.display()))?;
+    Ok(file.answers)
+}
+
+/// Answers file content for `answers` (pure function)
+pub fn render_answers_file(answers: &[RecordedAnswer]) -> Result<String> {
+    serde_yaml::to_string(&AnswersFile {
+        answers: answers.to_vec(),
+    })
+    .context("Failed to serialize answers")
+}
+
+/// Answers as a session metadata value
+pub fn to_metadata_value(answers: &[RecordedAnswer]) -> serde_json::Value {
+    serde_json::to_value(answers).unwrap_or_default()
+}
+
+/// Answers recorded in session metadata, if any
+pub fn from_metadata(metadata: &HashMap<String, serde_json::Value>) -> Option<Vec<RecordedAnswer>> {
+    metadata
+        .get(ANSWERS_METADATA_KEY)
+        .and_then(|value| serde_json::from_value(value.clone()).ok())
+}
+
+/// One line per answered prompt (pure function)
+pub fn format_answers(answers: &[RecordedAnswer]) -> Vec<String> {
+    answers
+        .iter()
+        .map(|recorded| {
+            let answer = match &recorded.answer {
+                Answer::Confirm(true) => "yes".to_string(),
+                Answer::Confirm(false) => "no".to_string(),
+                Answer::Choice(index) => format!("choice {}", index + 1),
+                Answer::Text(text) => format!("{:?}", text),
+            };
+            format!("{} -> {}", recorded.prompt, answer)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn answer(kind: PromptKind, prompt: &str, answer: Answer) -> RecordedAnswer {
+        RecordedAnswer {
+            kind,
+            prompt: prompt.to_string(),
+            answer,
+        }
+    }
+
+    #[test]
+    fn test_answers_file_round_trip() {
+        let answers = vec![
+            answer(PromptKind::Confirmation, "Continue?", Answer::Confirm(true)),
+            answer(PromptKind::Text, "Name", Answer::Text("42".to_string())),
+            answer(PromptKind::Choice, "Pick", Answer::Choice(2)),
+        ];
+        let rendered = render_answers_file(&answers).unwrap();
+        let parsed: AnswersFile = serde_yaml::from_str(&rendered).unwrap();
+        assert_eq!(parsed.answers, answers);
+    }
+
+    #[test]
+    fn test_replay_consumes_answers_in_order_per_kind() {
+        let log = InteractionLog::replaying(vec![
+            answer(
+                PromptKind::MergeConfirmation,
+                "Merge a",
+                Answer::Confirm(false),
+            ),
+            answer(PromptKind::Confirmation, "First?", Answer::Confirm(true)),
+            answer(PromptKind::Confirmation, "Second?", Answer::Confirm(false)),
+        ]);
+
+        assert_eq!(
+            log.replayed(PromptKind::Confirmation, "First?").unwrap(),
+            Some(Answer::Confirm(true))
+        );
+        assert_eq!(
+            log.replayed(PromptKind::MergeConfirmation, "Merge b")
+                .unwrap(),
+            Some(Answer::Confirm(false))
+        );
+        assert_eq!(
+            log.replayed(PromptKind::Confirmation, "Second?").unwrap(),
+            Some(Answer::Confirm(false))
+        );
+        let err = log
+            .replayed(PromptKind::Confirmation, "Third?")
+            .unwrap_err();
+        assert!(err.to_string().contains("Third?"));
+
+        assert_eq!(
+            InteractionLog::new()
+                .replayed(PromptKind::Text, "Name")
+                .unwrap(),
+            None
+        );
+    }
+}
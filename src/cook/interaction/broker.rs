@@ -0,0 +1,369 @@
+//! Prompt broker
+//!
+//! Parallel steps and MapReduce agents share one terminal, so prompts asked
+//! at the same time interleave and garble each other. The [`PromptBroker`]
+//! shows one prompt at a time across the whole process, labels each with
+//! its source (e.g. `[agent-3]`), and decides per prompt kind what to do when
+//! stdin is not a terminal:
+//!
+//! ```yaml
+//! prompts:
+//!   merge_confirmation: decline
+//!   text: fail
+//! ```
+//!
+//! Kinds without a policy keep reading the answer from stdin, so piped
+//! answers continue to work. Every answer is recorded in the run's
+//! [`InteractionLog`], which answers prompts itself when replaying an answers
+//! file.
+
+use super::answers::{Answer, InteractionLog};
+use super::prompts::UserPrompter;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::IsTerminal;
+use std::sync::Arc;
+
+/// Held while a prompt is on screen, shared by every broker in the process
+static PROMPT_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+/// Kinds of prompts, each with its own non-interactive policy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PromptKind {
+    /// Whether to merge a worktree back into its target branch
+    MergeConfirmation,
+    /// Any other yes/no question
+    Confirmation,
+    /// Free text input
+    Text,
+    /// Selection from a list
+    Choice,
+}
+
+/// What to answer when stdin is not a terminal
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PromptPolicy {
+    /// Read the answer from stdin anyway
+    #[default]
+    Ask,
+    /// Answer yes, or take the default text or first choice
+    Accept,
+    /// Answer no; text and choice prompts fail
+    Decline,
+    /// Fail the prompt
+    Fail,
+}
+
+/// Serializes prompts and applies non-interactive policies
+pub struct PromptBroker {
+    prompter: Arc<dyn UserPrompter>,
+    policies: HashMap<PromptKind, PromptPolicy>,
+    interactive: bool,
+    log: Option<Arc<InteractionLog>>,
+}
+
+impl PromptBroker {
+    pub fn new(
+        prompter: Arc<dyn UserPrompter>,
+        policies: HashMap<PromptKind, PromptPolicy>,
+        interactive: bool,
+    ) -> Self {
+        Self {
+            prompter,
+            policies,
+            interactive,
+            log: None,
+        }
+    }
+
+    /// Record and replay answers with `log`
+    pub fn with_interaction_log(mut self, log: Arc<InteractionLog>) -> Self {
+        self.log = Some(log);
+        self
+    }
+
+    /// Broker that asks every prompt unless stdin is not a terminal
+    pub fn for_stdin(prompter: Arc<dyn UserPrompter>) -> Self {
+        Self::new(prompter, HashMap::new(), std::io::stdin().is_terminal())
+    }
+
+    /// Answer prompts by `policies` when stdin is not a terminal
+    pub fn with_policies(mut self, policies: HashMap<PromptKind, PromptPolicy>) -> Self {
+        self.policies = policies;
+        self
+    }
+
+    /// Policy applying to `kind` right now
+    fn policy(&self, kind: PromptKind) -> PromptPolicy {
+        if self.interactive {
+            PromptPolicy::Ask
+        } else {
+            self.policies.get(&kind).copied().unwrap_or_default()
+        }
+    }
+
+    /// Ask a yes/no question of `kind` on behalf of `source`
+    pub async fn confirm(
+        &self,
+        kind: PromptKind,
+        source: Option<&str>,
+        message: &str,
+    ) -> Result<bool> {
+        let message = label(source, message);
+        let answer = match replayed(self.log.as_deref(), kind, &message)? {
+            Some(Answer::Confirm(answer)) => answer,
+            Some(other) => return Err(mismatched(kind, &message, &other)),
+            None => match self.policy(kind) {
+                PromptPolicy::Ask => {
+                    let _guard = PROMPT_LOCK.lock().await;
+                    self.prompter.prompt_yes_no(&message).await?
+                }
+                PromptPolicy::Accept => true,
+                PromptPolicy::Decline => false,
+                PromptPolicy::Fail => return Err(unanswered(kind, &message)),
+            },
+        };
+        record(self.log.as_deref(), kind, &message, Answer::Confirm(answer));
+        Ok(answer)
+    }
+
+    /// Ask for text on behalf of `source`
+    pub async fn text(
+        &self,
+        source: Option<&str>,
+        message: &str,
+        default: Option<&str>,
+    ) -> Result<String> {
+        let message = label(source, message);
+        let answer = match replayed(self.log.as_deref(), PromptKind::Text, &message)? {
+            Some(Answer::Text(answer)) => answer,
+            Some(other) => return Err(mismatched(PromptKind::Text, &message, &other)),
+            None => match (self.policy(PromptKind::Text), default) {
+                (PromptPolicy::Ask, _) => {
+                    let _guard = PROMPT_LOCK.lock().await;
+                    self.prompter.prompt_text(&message, default).await?
+                }
+                (PromptPolicy::Accept, Some(default)) => default.to_string(),
+                _ => return Err(unanswered(PromptKind::Text, &message)),
+            },
+        };
+        record(
+            self.log.as_deref(),
+            PromptKind::Text,
+            &message,
+            Answer::Text(answer.clone()),
+        );
+        Ok(answer)
+    }
+
+    /// Ask for a choice from `choices` on behalf of `source`
+    pub async fn choice(
+        &self,
+        source: Option<&str>,
+        message: &str,
+        choices: &[String],
+    ) -> Result<usize> {
+        let message = label(source, message);
+        let answer = match replayed(self.log.as_deref(), PromptKind::Choice, &message)? {
+            Some(Answer::Choice(index)) if index < choices.len() => index,
+            Some(other) => return Err(mismatched(PromptKind::Choice, &message, &other)),
+            None => match self.policy(PromptKind::Choice) {
+                PromptPolicy::Ask => {
+                    let _guard = PROMPT_LOCK.lock().await;
+                    self.prompter.prompt_choice(&message, choices).await?
+                }
+                PromptPolicy::Accept if !choices.is_empty() => 0,
+                _ => return Err(unanswered(PromptKind::Choice, &message)),
+            },
+        };
+        record(
+            self.log.as_deref(),
+            PromptKind::Choice,
+            &message,
+            Answer::Choice(answer),
+        );
+        Ok(answer)
+    }
+}
+
+/// Prefix a prompt with its source (pure function)
+pub fn label(source: Option<&str>, message: &str) -> String {
+    match source {
+        Some(source) => format!("[{}] {}", source, message),
+        None => message.to_string(),
+    }
+}
+
+/// Answer from the answers file being replayed, if any
+fn replayed(
+    log: Option<&InteractionLog>,
+    kind: PromptKind,
+    message: &str,
+) -> Result<Option<Answer>> {
+    match log {
+        Some(log) => log.replayed(kind, message),
+        None => Ok(None),
+    }
+}
+
+fn record(log: Option<&InteractionLog>, kind: PromptKind, message: &str, answer: Answer) {
+    if let Some(log) = log {
+        log.record(kind, message, answer);
+    }
+}
+
+fn mismatched(kind: PromptKind, message: &str, answer: &Answer) -> anyhow::Error {
+    anyhow!(
+        "The answers file answers {:?} prompt '{}' with {:?}",
+        kind,
+        message,
+        answer
+    )
+}
+
+fn unanswered(kind: PromptKind, message: &str) -> anyhow::Error {
+    anyhow!(
+        "Cannot answer {:?} prompt without a terminal: {}",
+        kind,
+        message
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    /// Answers yes after a delay, recording the prompts and their overlap
+    #[derive(Default)]
+    struct SlowPrompter {
+        asked: Mutex<Vec<String>>,
+        active: AtomicUsize,
+        max_active: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl UserPrompter for SlowPrompter {
+        async fn prompt_yes_no(&self, message: &str) -> Result<bool> {
+            let active = self.active.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_active.fetch_max(active, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            self.asked.lock().unwrap().push(message.to_string());
+            self.active.fetch_sub(1, Ordering::SeqCst);
+            Ok(true)
+        }
+
+        async fn prompt_text(&self, message: &str, _default: Option<&str>) -> Result<String> {
+            self.asked.lock().unwrap().push(message.to_string());
+            Ok("typed".to_string())
+        }
+
+        async fn prompt_choice(&self, message: &str, _choices: &[String]) -> Result<usize> {
+            self.asked.lock().unwrap().push(message.to_string());
+            Ok(1)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_prompts_are_serialized_and_labeled() {
+        let prompter = Arc::new(SlowPrompter::default());
+        let broker = Arc::new(PromptBroker::new(prompter.clone(), HashMap::new(), true));
+
+        let handles: Vec<_> = (0..4)
+            .map(|i| {
+                let broker = Arc::clone(&broker);
+                tokio::spawn(async move {
+                    let source = format!("agent-{}", i);
+                    broker
+                        .confirm(PromptKind::Confirmation, Some(&source), "Continue?")
+                        .await
+                })
+            })
+            .collect();
+        for handle in handles {
+            assert!(handle.await.unwrap().unwrap());
+        }
+
+        assert_eq!(prompter.max_active.load(Ordering::SeqCst), 1);
+        let mut asked = prompter.asked.lock().unwrap().clone();
+        asked.sort();
+        assert_eq!(asked[0], "[agent-0] Continue?");
+        assert_eq!(asked.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_non_interactive_policies() {
+        let prompter = Arc::new(SlowPrompter::default());
+        let policies = HashMap::from([
+            (PromptKind::MergeConfirmation, PromptPolicy::Decline),
+            (PromptKind::Confirmation, PromptPolicy::Accept),
+            (PromptKind::Text, PromptPolicy::Accept),
+            (PromptKind::Choice, PromptPolicy::Fail),
+        ]);
+        let broker = PromptBroker::new(prompter.clone(), policies, false);
+
+        assert!(!broker
+            .confirm(PromptKind::MergeConfirmation, None, "Merge?")
+            .await
+            .unwrap());
+        assert!(broker
+            .confirm(PromptKind::Confirmation, None, "Continue?")
+            .await
+            .unwrap());
+        assert_eq!(
+            broker.text(None, "Name", Some("demo")).await.unwrap(),
+            "demo"
+        );
+        assert!(broker.text(None, "Name", None).await.is_err());
+        let err = broker
+            .choice(Some("agent-1"), "Pick", &["a".to_string()])
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("[agent-1] Pick"));
+        assert!(prompter.asked.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_answers_are_recorded_and_replayed() {
+        let prompter = Arc::new(SlowPrompter::default());
+        let log = Arc::new(InteractionLog::new());
+        let broker = PromptBroker::new(prompter.clone(), HashMap::new(), true)
+            .with_interaction_log(log.clone());
+
+        assert!(broker
+            .confirm(PromptKind::Confirmation, Some("agent-1"), "Continue?")
+            .await
+            .unwrap());
+        assert_eq!(broker.text(None, "Name", None).await.unwrap(), "typed");
+        let recorded = log.recorded();
+        assert_eq!(recorded.len(), 2);
+        assert_eq!(recorded[0].prompt, "[agent-1] Continue?");
+
+        // Replaying answers every prompt without asking, even without a terminal
+        let replay = PromptBroker::new(prompter.clone(), HashMap::new(), false)
+            .with_interaction_log(Arc::new(InteractionLog::replaying(recorded)));
+        prompter.asked.lock().unwrap().clear();
+        assert!(replay
+            .confirm(PromptKind::Confirmation, Some("agent-2"), "Continue?")
+            .await
+            .unwrap());
+        assert_eq!(replay.text(None, "Name", None).await.unwrap(), "typed");
+        assert!(replay.text(None, "Name", None).await.is_err());
+        assert!(prompter.asked.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_unconfigured_kinds_still_ask_without_terminal() {
+        let prompter = Arc::new(SlowPrompter::default());
+        let broker = PromptBroker::new(prompter.clone(), HashMap::new(), false);
+
+        assert_eq!(broker.text(None, "Name", None).await.unwrap(), "typed");
+        assert_eq!(prompter.asked.lock().unwrap().as_slice(), ["Name"]);
+    }
+}
@@ -33,6 +33,7 @@
 //! ```
 
 use super::SpinnerHandle;
+use crate::{styled_eprintln, styled_println};
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
@@ -79,6 +80,23 @@ impl Default for IconConfig {
     }
 }
 
+impl IconConfig {
+    /// Text labels for plain output
+    pub fn ascii() -> Self {
+        Self {
+            info: "[info]",
+            warning: "[warn]",
+            error: "[error]",
+            progress: "-",
+            success: "[ok]",
+            action: ">",
+            metric: "-",
+            status: "-",
+            debug: "[debug]",
+        }
+    }
+}
+
 /// Verbosity level for output control
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum VerbosityLevel {
@@ -171,13 +189,16 @@ impl Default for ProgressDisplayImpl {
 
 impl ProgressDisplayImpl {
     pub fn new(verbosity: VerbosityLevel) -> Self {
-        // Detect terminal capabilities
-        let use_unicode = Self::supports_unicode();
+        let use_unicode = crate::output::current().unicode;
 
         Self {
             verbosity,
             use_unicode,
-            icons: IconConfig::default(),
+            icons: if use_unicode {
+                IconConfig::default()
+            } else {
+                IconConfig::ascii()
+            },
         }
     }
 
@@ -187,23 +208,6 @@ impl ProgressDisplayImpl {
         Self::new(verbosity)
     }
 
-    /// Check if terminal supports Unicode
-    fn supports_unicode() -> bool {
-        // Check LANG/LC_ALL environment variables
-        if let Ok(lang) = std::env::var("LANG") {
-            if lang.contains("UTF-8") || lang.contains("utf8") {
-                return true;
-            }
-        }
-        if let Ok(lc_all) = std::env::var("LC_ALL") {
-            if lc_all.contains("UTF-8") || lc_all.contains("utf8") {
-                return true;
-            }
-        }
-        // Default to ASCII on Windows, Unicode elsewhere
-        !cfg!(windows)
-    }
-
     /// Get box drawing characters based on Unicode support
     fn box_chars(&self) -> BoxChars {
         if self.use_unicode {
@@ -267,54 +271,54 @@ impl BoxChars {
 impl ProgressDisplay for ProgressDisplayImpl {
     fn info(&self, message: &str) {
         if self.verbosity >= VerbosityLevel::Normal {
-            println!("{} {message}", self.icons.info);
+            styled_println!("{} {message}", self.icons.info);
         }
     }
 
     fn warning(&self, message: &str) {
         if self.verbosity >= VerbosityLevel::Normal {
-            eprintln!("{} {message}", self.icons.warning);
+            styled_eprintln!("{} {message}", self.icons.warning);
         }
     }
 
     fn error(&self, message: &str) {
         // Always show errors, even in quiet mode
-        eprintln!("{} {message}", self.icons.error);
+        styled_eprintln!("{} {message}", self.icons.error);
     }
 
     fn progress(&self, message: &str) {
         if self.verbosity >= VerbosityLevel::Normal {
-            println!("{} {message}", self.icons.progress);
+            styled_println!("{} {message}", self.icons.progress);
         }
     }
 
     fn success(&self, message: &str) {
         if self.verbosity >= VerbosityLevel::Normal {
-            println!("{} {message}", self.icons.success);
+            styled_println!("{} {message}", self.icons.success);
         }
     }
 
     fn action(&self, message: &str) {
         if self.verbosity >= VerbosityLevel::Normal {
-            println!("{} {message}", self.icons.action);
+            styled_println!("{} {message}", self.icons.action);
         }
     }
 
     fn metric(&self, label: &str, value: &str) {
         if self.verbosity >= VerbosityLevel::Normal {
-            println!("{} {label}: {value}", self.icons.metric);
+            styled_println!("{} {label}: {value}", self.icons.metric);
         }
     }
 
     fn status(&self, message: &str) {
         if self.verbosity >= VerbosityLevel::Normal {
-            println!("{} {message}", self.icons.status);
+            styled_println!("{} {message}", self.icons.status);
         }
     }
 
     fn start_spinner(&self, message: &str) -> Box<dyn SpinnerHandle> {
         if self.verbosity >= VerbosityLevel::Normal {
-            println!("⏳ {message}");
+            styled_println!("⏳ {message}");
         }
         Box::new(SimpleSpinnerHandle::new(self.verbosity, self.icons))
     }
@@ -326,14 +330,14 @@ impl ProgressDisplay for ProgressDisplayImpl {
             let title = format!(" ITERATION {current}/{total} ");
             let padding = (width - title.len()) / 2;
 
-            println!();
-            println!(
+            styled_println!();
+            styled_println!(
                 "{}{}{}",
                 chars.top_left,
                 std::iter::repeat_n(chars.horizontal, width).collect::<String>(),
                 chars.top_right
             );
-            println!(
+            styled_println!(
                 "{}{:padding$}{}{:padding$}{}",
                 chars.vertical,
                 "",
@@ -342,13 +346,13 @@ impl ProgressDisplay for ProgressDisplayImpl {
                 chars.vertical,
                 padding = padding
             );
-            println!(
+            styled_println!(
                 "{}{}{}",
                 chars.bottom_left,
                 std::iter::repeat_n(chars.horizontal, width).collect::<String>(),
                 chars.bottom_right
             );
-            println!();
+            styled_println!();
         }
     }
 
@@ -361,18 +365,20 @@ impl ProgressDisplay for ProgressDisplayImpl {
                 format!("{} Failed", self.icons.error)
             };
 
-            println!();
-            println!("┌─ Iteration {current} Summary ──────────────────────────────────────┐");
-            println!("│ Duration: {:<49}│", duration_str);
-            println!("│ Status: {:<51}│", status);
-            println!("└────────────────────────────────────────────────────────────┘");
-            println!();
+            styled_println!();
+            styled_println!(
+                "┌─ Iteration {current} Summary ──────────────────────────────────────┐"
+            );
+            styled_println!("│ Duration: {:<49}│", duration_str);
+            styled_println!("│ Status: {:<51}│", status);
+            styled_println!("└────────────────────────────────────────────────────────────┘");
+            styled_println!();
         }
     }
 
     fn step_start(&self, step: u32, total: u32, description: &str) {
         if self.verbosity >= VerbosityLevel::Verbose {
-            println!("[Step {step}/{total}] {description}");
+            styled_println!("[Step {step}/{total}] {description}");
         }
     }
 
@@ -383,7 +389,7 @@ impl ProgressDisplay for ProgressDisplayImpl {
             } else {
                 self.icons.error
             };
-            println!("[Step {step}] {status}");
+            styled_println!("[Step {step}] {status}");
         }
     }
 
@@ -395,7 +401,7 @@ impl ProgressDisplay for ProgressDisplayImpl {
 
     fn debug_output(&self, message: &str, min_verbosity: VerbosityLevel) {
         if self.verbosity >= min_verbosity {
-            println!("{} {message}", self.icons.debug);
+            styled_println!("{} {message}", self.icons.debug);
         }
     }
 
@@ -424,21 +430,21 @@ impl SimpleSpinnerHandle {
 impl SpinnerHandle for SimpleSpinnerHandle {
     fn update_message(&mut self, message: &str) {
         if *self.active.lock().unwrap() && self.verbosity >= VerbosityLevel::Normal {
-            println!("⏳ {message}");
+            styled_println!("⏳ {message}");
         }
     }
 
     fn success(&mut self, message: &str) {
         *self.active.lock().unwrap() = false;
         if self.verbosity >= VerbosityLevel::Normal {
-            println!("{} {message}", self.icons.success);
+            styled_println!("{} {message}", self.icons.success);
         }
     }
 
     fn fail(&mut self, message: &str) {
         *self.active.lock().unwrap() = false;
         if self.verbosity >= VerbosityLevel::Normal {
-            println!("{} {message}", self.icons.error);
+            styled_println!("{} {message}", self.icons.error);
         }
     }
 }
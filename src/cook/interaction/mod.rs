@@ -2,9 +2,12 @@
 //!
 //! Provides abstractions for prompts, progress display, and user input.
 
+pub mod answers;
+pub mod broker;
 pub mod display;
 pub mod prompts;
 
+pub use broker::{PromptBroker, PromptKind, PromptPolicy};
 pub use display::{DisplayMessageType, ProgressDisplay, ProgressDisplayImpl, VerbosityLevel};
 pub use prompts::{UserPrompter, UserPrompterImpl};
 #[cfg(test)]
@@ -12,6 +15,8 @@ pub use tests::MockUserInteraction;
 
 use anyhow::Result;
 use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::Duration;
 
 /// Trait for user interaction
@@ -25,6 +30,16 @@ pub trait UserInteraction: Send + Sync {
         self.prompt_yes_no(message).await
     }
 
+    /// Prompt user for yes/no confirmation of `kind`, labelled with its source
+    async fn prompt_yes_no_from(
+        &self,
+        _kind: PromptKind,
+        _source: Option<&str>,
+        message: &str,
+    ) -> Result<bool> {
+        self.prompt_yes_no(message).await
+    }
+
     /// Prompt user for text input
     async fn prompt_text(&self, message: &str, default: Option<&str>) -> Result<String>;
 
@@ -91,7 +106,7 @@ pub trait SpinnerHandle: Send + Sync {
 
 /// Default implementation of user interaction
 pub struct DefaultUserInteraction {
-    prompter: UserPrompterImpl,
+    broker: PromptBroker,
     display: ProgressDisplayImpl,
 }
 
@@ -104,23 +119,46 @@ impl Default for DefaultUserInteraction {
 impl DefaultUserInteraction {
     pub fn new() -> Self {
         Self {
-            prompter: UserPrompterImpl::new(),
+            broker: PromptBroker::for_stdin(Arc::new(UserPrompterImpl::new())),
             display: ProgressDisplayImpl::new(VerbosityLevel::Normal),
         }
     }
 
     pub fn with_verbosity(verbosity: VerbosityLevel) -> Self {
         Self {
-            prompter: UserPrompterImpl::new(),
+            broker: PromptBroker::for_stdin(Arc::new(UserPrompterImpl::new())),
             display: ProgressDisplayImpl::new(verbosity),
         }
     }
+
+    /// Record prompt answers in `log`, answering from it when it replays an answers file
+    pub fn with_interaction_log(mut self, log: Arc<answers::InteractionLog>) -> Self {
+        self.broker = self.broker.with_interaction_log(log);
+        self
+    }
+
+    /// Answer prompts by the configured `prompts` policies when stdin is not a terminal
+    pub fn with_prompt_policies(mut self, policies: HashMap<PromptKind, PromptPolicy>) -> Self {
+        self.broker = self.broker.with_policies(policies);
+        self
+    }
 }
 
 #[async_trait]
 impl UserInteraction for DefaultUserInteraction {
     async fn prompt_yes_no(&self, message: &str) -> Result<bool> {
-        self.prompter.prompt_yes_no(message).await
+        self.broker
+            .confirm(PromptKind::Confirmation, None, message)
+            .await
+    }
+
+    async fn prompt_yes_no_from(
+        &self,
+        kind: PromptKind,
+        source: Option<&str>,
+        message: &str,
+    ) -> Result<bool> {
+        self.broker.confirm(kind, source, message).await
     }
 
     async fn prompt_confirmation(&self, message: &str) -> Result<bool> {
@@ -128,7 +166,7 @@ impl UserInteraction for DefaultUserInteraction {
     }
 
     async fn prompt_text(&self, message: &str, default: Option<&str>) -> Result<String> {
-        self.prompter.prompt_text(message, default).await
+        self.broker.text(None, message, default).await
     }
 
     fn display_info(&self, message: &str) {
@@ -6,6 +6,7 @@
 pub mod command;
 pub mod commit_tracker;
 pub mod common_strings;
+pub mod concurrency;
 pub mod coordinators;
 pub mod environment;
 pub mod error;
@@ -14,13 +15,20 @@ pub mod expression;
 pub mod git_ops;
 pub mod input;
 pub mod interaction;
+pub mod multi_repo;
+pub mod no_changes;
 pub mod orchestrator;
+pub mod package;
+pub mod protected_paths;
 pub mod retry;
 pub mod retry_state;
 pub mod retry_v2;
 pub mod session;
 pub mod signal_handler;
+pub mod triggers;
+pub mod watch;
 pub mod workflow;
+pub mod workflow_test;
 
 #[cfg(test)]
 mod tests;
@@ -36,9 +44,10 @@ mod dry_run_tests;
 
 use crate::abstractions::git::RealGitOperations;
 use crate::config::{workflow::WorkflowConfig, ConfigLoader};
+use crate::styled_println;
 use crate::unified_session::SessionId;
 use anyhow::{anyhow, Context as _, Result};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 // Re-export key types
@@ -47,7 +56,62 @@ pub use environment::{PathResolver, Platform};
 pub use orchestrator::{CookConfig, CookOrchestrator, DefaultCookOrchestrator};
 
 /// Main entry point for cook operations
-pub async fn cook(mut cmd: CookCommand) -> Result<()> {
+pub async fn cook(cmd: CookCommand) -> Result<()> {
+    run_cook(cmd, None, None).await
+}
+
+/// Run a workflow with every Claude command served by `claude_executor`
+///
+/// Used by `prodigy test-workflow` to run workflows against mocked Claude commands.
+pub async fn cook_with_claude(
+    cmd: CookCommand,
+    claude_executor: Arc<dyn execution::ClaudeExecutor>,
+) -> Result<()> {
+    run_cook(cmd, None, Some(claude_executor)).await
+}
+
+/// Run a workflow that was constructed in memory instead of loaded from the playbook
+///
+/// `cmd.playbook` is still used to identify the workflow in checkpoints and
+/// session metadata, but it does not need to exist on disk.
+pub async fn cook_with_workflow(
+    cmd: CookCommand,
+    workflow: WorkflowConfig,
+    mapreduce_config: Option<crate::config::MapReduceWorkflowConfig>,
+) -> Result<()> {
+    run_cook(cmd, Some((workflow, mapreduce_config)), None).await
+}
+
+/// Changes the current directory, changing back when dropped
+struct CurrentDirGuard {
+    previous: PathBuf,
+}
+
+impl CurrentDirGuard {
+    fn enter(dir: &Path) -> Result<Self> {
+        let previous = std::env::current_dir()?;
+        std::env::set_current_dir(dir)
+            .with_context(|| format!("Failed to change to directory: {}", dir.display()))?;
+        Ok(Self { previous })
+    }
+}
+
+impl Drop for CurrentDirGuard {
+    fn drop(&mut self) {
+        if let Err(e) = std::env::set_current_dir(&self.previous) {
+            tracing::warn!("Failed to return to {}: {}", self.previous.display(), e);
+        }
+    }
+}
+
+async fn run_cook(
+    mut cmd: CookCommand,
+    preloaded: Option<(
+        WorkflowConfig,
+        Option<crate::config::MapReduceWorkflowConfig>,
+    )>,
+    claude_executor: Option<Arc<dyn execution::ClaudeExecutor>>,
+) -> Result<()> {
     // Save the original directory before any path changes
     let original_dir = std::env::current_dir()?;
 
@@ -98,40 +162,204 @@ pub async fn cook(mut cmd: CookCommand) -> Result<()> {
             return Err(anyhow!("Not a git repository: {}", absolute_path.display()));
         }
 
-        // Change to the specified directory
-        std::env::set_current_dir(&absolute_path).with_context(|| {
-            format!("Failed to change to directory: {}", absolute_path.display())
-        })?;
-
         absolute_path
     } else {
         original_dir.clone()
     };
 
-    // Make playbook path absolute if it's relative (based on original directory)
-    if !cmd.playbook.is_absolute() {
-        cmd.playbook = original_dir.join(&cmd.playbook);
+    // Run in the project directory, returning to the original one when the run
+    // ends, even if it fails or panics
+    let _project_dir = match &cmd.path {
+        Some(_) => Some(CurrentDirGuard::enter(&project_path)?),
+        None => None,
+    };
+
+    // Resolve the monorepo package the run is scoped to
+    let package_dir = match cmd.package.take() {
+        Some(package) => {
+            let (relative, dir) = package::resolve_package(&project_path, &package)?;
+            cmd.package = Some(relative);
+            Some(dir)
+        }
+        None => None,
+    };
+
+    // Make playbook path absolute if it's relative (based on the package or original directory)
+    cmd.playbook =
+        package::resolve_workflow_path(&cmd.playbook, &original_dir, package_dir.as_deref());
+
+    // Set up command output recording or replay
+    let replay = create_replay_session(&cmd, &original_dir)?;
+
+    // Record every prompt answer, answering from the answers file when one is given
+    let interaction_log = match &cmd.answers_file {
+        Some(path) => {
+            let answers = interaction::answers::load_answers_file(&original_dir.join(path))?;
+            tracing::info!("Answering prompts from {}", path.display());
+            interaction::answers::InteractionLog::replaying(answers)
+        }
+        None => interaction::answers::InteractionLog::new(),
+    };
+    let interaction_log = Arc::new(interaction_log);
+
+    // Make this run's randomized decisions repeatable when a seed is given
+    if let Some(seed) = cmd.seed {
+        tracing::info!("Deterministic mode: seed {}", seed);
     }
 
+    // Create the command fault injector for this run
+    let chaos = cmd.chaos.clone().map(|config| {
+        tracing::warn!("Chaos mode enabled: {}", config);
+        Arc::new(execution::chaos::ChaosMonkey::new(config, cmd.seed))
+    });
+
+    // Collect step results for JUnit XML output and GitHub Actions annotations
+    let github_annotations = execution::github_actions::enabled();
+    let run_name = cmd
+        .playbook
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| "prodigy".to_string());
+    let junit_path = cmd
+        .junit_output
+        .as_ref()
+        .map(|path| original_dir.join(path));
+    let results = (junit_path.is_some() || github_annotations)
+        .then(|| Arc::new(execution::junit::JunitRecorder::new()));
+
+    // Measure each step when profiling is requested
+    let profiler = cmd
+        .profile_steps
+        .then(|| Arc::new(execution::step_profile::StepProfiler::new()));
+
+    // Load the global and project configuration once for this run
+    let settings = match crate::config::load_prodigy_config_at(
+        &crate::config::project_config_path_in(&project_path, package_dir.as_deref()),
+    ) {
+        Ok(config) => config.into_inner(),
+        Err(e) => {
+            tracing::warn!("Using default configuration: {}", e);
+            crate::config::ProdigyConfig::default()
+        }
+    };
+
     // Load configuration
     let config_loader = ConfigLoader::new().await?;
     config_loader
-        .load_with_explicit_path(&project_path, None)
+        .load_with_explicit_path(
+            package::config_root(&project_path, package_dir.as_deref()),
+            None,
+        )
         .await?;
     let _config = config_loader.get_config();
 
     // Load workflow - this handles both regular and MapReduce workflows
-    let (workflow, mapreduce_config) = load_workflow_with_mapreduce(&cmd).await?;
+    let (mut workflow, mut mapreduce_config) = match preloaded {
+        Some(preloaded) => preloaded,
+        None => load_workflow_with_mapreduce(&cmd).await?,
+    };
+    workflow.apply_settings(&settings);
+    if let Some(mapreduce_config) = mapreduce_config.as_mut() {
+        mapreduce_config.apply_settings(&settings);
+    }
+
+    // Workflow defaults fill in run flags not given on the command line
+    if let Some(defaults) = workflow.defaults.as_ref() {
+        defaults.apply(&mut cmd);
+    }
+
+    // MapReduce agents run their commands without the record/replay session
+    if mapreduce_config.is_some() && (cmd.record.is_some() || cmd.replay.is_some()) {
+        return Err(anyhow!(
+            "--record and --replay are not supported for MapReduce workflows"
+        ));
+    }
+
+    // Check the step selection against the workflow before the run starts
+    if !cmd.steps.is_empty() {
+        if mapreduce_config.is_some() {
+            return Err(anyhow!(
+                "--only-steps and --skip-steps are not supported for MapReduce workflows"
+            ));
+        }
+        cmd.steps = cmd
+            .steps
+            .resolve(&workflow::step_selection::step_ids(&workflow.commands))?;
+        tracing::info!("Step selection: {}", cmd.steps);
+    }
+
+    // Reject invalid protected path patterns before the run starts
+    let protected = protected_paths::ProtectedPaths::resolve(
+        workflow.protected_paths.as_ref(),
+        &settings.protected_paths,
+    );
+    if let Some(protected) = protected.as_ref() {
+        protected.validate()?;
+    }
+    if let Some(gate) = workflow.score_gate.as_ref() {
+        gate.validate()?;
+    }
+
+    // Fail before the first step if a Claude command the workflow calls is missing.
+    // Resumed runs were checked when they started, replayed and mocked runs never
+    // invoke Claude, and dry runs only report the problem.
+    if cmd.replay.is_none() && cmd.resume.is_none() && claude_executor.is_none() {
+        let project_dirs: Vec<&Path> = std::iter::once(project_path.as_path())
+            .chain(package_dir.as_deref())
+            .collect();
+        workflow::command_check::check_claude_commands(
+            &workflow,
+            mapreduce_config.as_ref(),
+            &project_dirs,
+            cmd.dry_run,
+        )?;
+    }
+
+    // Wait for other runs in the workflow's concurrency group
+    let concurrency_slot = match workflow.concurrency.as_ref().filter(|_| !cmd.dry_run) {
+        Some(concurrency) => {
+            let locks_dir = crate::storage::GlobalStorage::new()?
+                .get_locks_dir(&crate::storage::extract_repo_name(&project_path)?)
+                .await?;
+            Some(
+                concurrency::ConcurrencySlot::acquire(
+                    concurrency,
+                    locks_dir,
+                    terminate_cancelled_run,
+                )
+                .await?,
+            )
+        }
+        None => None,
+    };
 
     // Create orchestrator with all dependencies
-    let orchestrator = create_orchestrator(&project_path, &cmd).await?;
+    let orchestrator = create_orchestrator(
+        &project_path,
+        &cmd,
+        claude_executor,
+        interaction_log.clone(),
+        &settings,
+    )
+    .await?;
+    let job_name = workflow.name.clone().unwrap_or_else(|| run_name.clone());
+    let publish_outcome = !cmd.dry_run;
 
     // Create cook configuration
+    let project_root = project_path.clone();
     let mut cook_config = CookConfig {
         command: cmd,
         project_path: Arc::new(project_path),
         workflow: Arc::new(workflow),
         mapreduce_config: None,
+        replay,
+        chaos: chaos.clone(),
+        junit: results.clone(),
+        profiler: profiler.clone(),
+        protected_paths: protected.map(Arc::new),
+        answers: Some(interaction_log),
+        failures: Some(Arc::new(workflow::failure_report::FailureRecorder::new())),
+        settings: Arc::new(settings),
     };
 
     // If this is a MapReduce workflow, we need special handling
@@ -141,7 +369,139 @@ pub async fn cook(mut cmd: CookCommand) -> Result<()> {
     }
 
     // Run the orchestrator
-    orchestrator.run(cook_config).await
+    let started = std::time::Instant::now();
+    let result = orchestrator.run(cook_config).await;
+    if publish_outcome {
+        log_run_outcome(&project_root, &job_name, started.elapsed(), &result).await;
+    }
+
+    if let Some(slot) = concurrency_slot {
+        slot.release().await;
+    }
+
+    if let Some(monkey) = chaos {
+        let stats = monkey.stats();
+        tracing::warn!(
+            "Chaos mode injected {} failure(s), {} delay(s), {} kill(s)",
+            stats.failures,
+            stats.delays,
+            stats.kills
+        );
+    }
+
+    if let Some(profiler) = profiler {
+        let steps = profiler.steps();
+        if !steps.is_empty() {
+            styled_println!("\nStep profile (slowest first):");
+            for line in execution::step_profile::format_profile(&steps) {
+                styled_println!("  {}", line);
+            }
+        }
+    }
+
+    if let Some(recorder) = results {
+        if let Some(path) = junit_path {
+            match recorder.write(&path, &run_name) {
+                Ok(()) => tracing::info!("Wrote JUnit report to {}", path.display()),
+                Err(e) => tracing::warn!("Failed to write JUnit report: {:#}", e),
+            }
+        }
+        if github_annotations {
+            if let Err(e) = execution::github_actions::report(
+                &run_name,
+                &recorder.cases(),
+                result.as_ref().err(),
+                &project_root,
+            ) {
+                tracing::warn!("Failed to write GitHub Actions step summary: {:#}", e);
+            }
+        }
+    }
+
+    result
+}
+
+/// Log the run's outcome to the global event stream
+///
+/// The record names the workflow in its `job_name` metadata, so `prodigy
+/// triggers` rules can react to a particular workflow completing or failing.
+async fn log_run_outcome(
+    project_path: &Path,
+    job_name: &str,
+    duration: std::time::Duration,
+    result: &Result<()>,
+) {
+    use execution::events::MapReduceEvent;
+
+    let job_id = format!("run-{}", chrono::Utc::now().format("%Y%m%d_%H%M%S"));
+    let logger = match crate::storage::create_global_event_logger(project_path, &job_id).await {
+        Ok(logger) => logger,
+        Err(e) => {
+            tracing::warn!("Failed to create event logger for {}: {}", job_id, e);
+            return;
+        }
+    };
+
+    // A run has no work items, so only the outcome is reported
+    let event = match result {
+        Ok(()) => MapReduceEvent::JobCompleted {
+            job_id,
+            duration: chrono::Duration::from_std(duration).unwrap_or_default(),
+            success_count: 0,
+            failure_count: 0,
+        },
+        Err(e) => MapReduceEvent::JobFailed {
+            job_id,
+            error: format!("{:#}", e),
+            partial_results: 0,
+        },
+    };
+    let metadata = std::collections::HashMap::from([(
+        triggers::JOB_NAME_METADATA.to_string(),
+        serde_json::Value::String(job_name.to_string()),
+    )]);
+    if let Err(e) = logger.log_with_metadata(event, metadata).await {
+        tracing::warn!("Failed to log run outcome: {}", e);
+    } else if let Err(e) = logger.flush().await {
+        tracing::warn!("Failed to flush run outcome: {}", e);
+    }
+}
+
+/// Stop a run cancelled by a newer run in its concurrency group
+///
+/// Raises SIGTERM so the interrupt handlers mark the session resumable.
+fn terminate_cancelled_run() {
+    #[cfg(unix)]
+    {
+        let _ = nix::sys::signal::raise(nix::sys::signal::Signal::SIGTERM);
+    }
+    #[cfg(not(unix))]
+    {
+        std::process::exit(143);
+    }
+}
+
+/// Create the record/replay session requested by `--record` or `--replay`
+///
+/// Relative fixture paths are resolved against the directory prodigy was invoked from.
+fn create_replay_session(
+    cmd: &CookCommand,
+    original_dir: &Path,
+) -> Result<Option<Arc<execution::ReplaySession>>> {
+    if let Some(path) = &cmd.replay {
+        let session = execution::ReplaySession::replay(original_dir.join(path))?;
+        tracing::info!(
+            "Replaying command outputs from {}",
+            session.path().display()
+        );
+        return Ok(Some(Arc::new(session)));
+    }
+
+    Ok(cmd.record.as_ref().map(|path| {
+        let session = execution::ReplaySession::record(original_dir.join(path));
+        tracing::info!("Recording command outputs to {}", session.path().display());
+        Arc::new(session)
+    }))
 }
 
 /// Create the orchestrator with all dependencies
@@ -185,6 +545,9 @@ async fn create_event_logger(
 async fn create_orchestrator(
     project_path: &Path,
     cmd: &CookCommand,
+    claude_executor: Option<Arc<dyn execution::ClaudeExecutor>>,
+    interaction_log: Arc<interaction::answers::InteractionLog>,
+    settings: &crate::config::ProdigyConfig,
 ) -> Result<Arc<dyn CookOrchestrator>> {
     // Create shared dependencies
     let git_operations = Arc::new(RealGitOperations::new());
@@ -207,9 +570,11 @@ async fn create_orchestrator(
 
     // Create user interaction with verbosity from command args
     let verbosity = interaction::VerbosityLevel::from_args(cmd.verbosity, cmd.quiet);
-    let user_interaction = Arc::new(interaction::DefaultUserInteraction::with_verbosity(
-        verbosity,
-    ));
+    let user_interaction = Arc::new(
+        interaction::DefaultUserInteraction::with_verbosity(verbosity)
+            .with_interaction_log(interaction_log)
+            .with_prompt_policies(settings.prompts.clone()),
+    );
 
     // Create executors
     let command_executor = Arc::new(command_runner1);
@@ -217,13 +582,13 @@ async fn create_orchestrator(
     // Create event logger for Claude streaming logs
     let event_logger = create_event_logger(project_path, &session_id.to_string()).await;
 
-    let claude_executor = Arc::new({
+    let claude_executor = claude_executor.unwrap_or_else(|| {
         let mut executor = execution::claude::ClaudeExecutorImpl::new(command_runner2)
             .with_verbosity(cmd.verbosity);
         if let Some(logger) = event_logger {
             executor = executor.with_event_logger(logger);
         }
-        executor
+        Arc::new(executor)
     });
 
     // Create environment coordinator
@@ -281,7 +646,7 @@ async fn load_workflow_with_mapreduce(
     Option<crate::config::MapReduceWorkflowConfig>,
 )> {
     // Always load from playbook since it's required
-    load_playbook_with_mapreduce(&cmd.playbook, &cmd.params).await
+    load_playbook_with_mapreduce(&cmd.playbook, &cmd.params, cmd.environment.as_deref()).await
 }
 
 /// Load workflow configuration (backward compatibility)
@@ -396,10 +761,17 @@ fn format_json_parse_error(e: &serde_json::Error, path: &Path) -> String {
     error_msg
 }
 
+/// Merge the `--env` overrides, keeping the cause in the message shown to the user
+fn apply_overrides(content: &str, environment: Option<&str>, path: &Path) -> Result<String> {
+    crate::config::yaml_loader::apply_environment_overrides(content, environment)
+        .map_err(|e| anyhow!("Failed to load workflow {}: {}", path.display(), e))
+}
+
 /// Load workflow configuration from a playbook file with MapReduce support
 async fn load_playbook_with_mapreduce(
     path: &Path,
     params: &std::collections::HashMap<String, serde_json::Value>,
+    environment: Option<&str>,
 ) -> Result<(
     WorkflowConfig,
     Option<crate::config::MapReduceWorkflowConfig>,
@@ -413,8 +785,12 @@ async fn load_playbook_with_mapreduce(
     match file_format {
         FileFormat::Yaml => {
             if is_mapreduce_content(&content) {
+                let content = workflow::apply_parameters_to_content(&content, params)?;
+                let content = crate::config::yaml_loader::preprocess_workflow_yaml(&content)
+                    .with_context(|| format!("Failed to load workflow: {}", path.display()))?;
+                let content = apply_overrides(&content, environment, path)?;
                 // Try to parse as MapReduce workflow
-                match crate::config::parse_mapreduce_workflow(&content) {
+                return match crate::config::parse_mapreduce_workflow(&content) {
                     Ok(mapreduce_config) => {
                         // Return workflow config with environment variables and merge workflow from MapReduce config
                         Ok((
@@ -426,6 +802,13 @@ async fn load_playbook_with_mapreduce(
                                 env_files: mapreduce_config.env_files.clone(),
                                 profiles: mapreduce_config.profiles.clone(),
                                 merge: mapreduce_config.merge.clone(),
+                                concurrency: mapreduce_config.concurrency.clone(),
+                                claude_permissions: mapreduce_config.claude_permissions.clone(),
+                                always: Vec::new(),
+                                strict_variables: mapreduce_config.strict_variables,
+                                defaults: mapreduce_config.defaults.clone(),
+                                protected_paths: mapreduce_config.protected_paths.clone(),
+                                score_gate: None,
                             },
                             Some(mapreduce_config),
                         ))
@@ -434,8 +817,13 @@ async fn load_playbook_with_mapreduce(
                         let error_msg = format_mapreduce_parse_error(&e, path);
                         Err(anyhow!(error_msg))
                     }
-                }
-            } else if workflow::is_composable_workflow(&content) {
+                };
+            }
+
+            let content = crate::config::yaml_loader::preprocess_workflow_yaml(&content)
+                .with_context(|| format!("Failed to load workflow: {}", path.display()))?;
+            let content = apply_overrides(&content, environment, path)?;
+            if workflow::is_composable_workflow(&content) {
                 // Check if it's a composable workflow (templates, imports, etc.)
                 workflow::parse_composable_workflow(path, &content, params.clone())
                     .await
@@ -452,6 +840,12 @@ async fn load_playbook_with_mapreduce(
             }
         }
         FileFormat::Json => {
+            if let Some(name) = environment {
+                return Err(anyhow!(
+                    "--env {} needs a YAML workflow; JSON workflows have no overrides",
+                    name
+                ));
+            }
             // Parse as JSON
             match serde_json::from_str::<WorkflowConfig>(&content) {
                 Ok(config) => Ok((config, None)),
@@ -586,9 +980,33 @@ mod cook_tests {
             verbosity: 0,
             quiet: false,
             dry_run: false,
+            read_only: false,
+            record: None,
+            junit_output: None,
+            profile_steps: false,
+            replay: None,
+            chaos: None,
+            seed: None,
+            answers_file: None,
+            environment: None,
+            no_worktree: false,
             params: std::collections::HashMap::new(),
+            package: None,
+            changed_only: None,
+            explain_failure: false,
+            keep_going: false,
+            steps: Default::default(),
+            explicit_flags: Default::default(),
         };
-        let orchestrator = create_orchestrator(temp_dir.path(), &cmd).await.unwrap();
+        let orchestrator = create_orchestrator(
+            temp_dir.path(),
+            &cmd,
+            None,
+            Arc::default(),
+            &Default::default(),
+        )
+        .await
+        .unwrap();
 
         // Should create orchestrator successfully - just check it exists by trying to drop it
         drop(orchestrator);
@@ -621,7 +1039,23 @@ mod cook_tests {
             verbosity: 0,
             quiet: false,
             dry_run: false,
+            read_only: false,
+            record: None,
+            junit_output: None,
+            profile_steps: false,
+            replay: None,
+            chaos: None,
+            seed: None,
+            answers_file: None,
+            environment: None,
+            no_worktree: false,
             params: std::collections::HashMap::new(),
+            package: None,
+            changed_only: None,
+            explain_failure: false,
+            keep_going: false,
+            steps: Default::default(),
+            explicit_flags: Default::default(),
         };
 
         let config = crate::config::Config::default();
@@ -664,7 +1098,8 @@ reduce:
 
         // Try to load the MapReduce workflow
         let result =
-            load_playbook_with_mapreduce(&playbook_path, &std::collections::HashMap::new()).await;
+            load_playbook_with_mapreduce(&playbook_path, &std::collections::HashMap::new(), None)
+                .await;
 
         // Debug the error if it fails
         match &result {
@@ -774,7 +1209,8 @@ reduce:
 
         // Try to load the MapReduce workflow
         let result =
-            load_playbook_with_mapreduce(&playbook_path, &std::collections::HashMap::new()).await;
+            load_playbook_with_mapreduce(&playbook_path, &std::collections::HashMap::new(), None)
+                .await;
 
         // Debug the error if it fails
         match &result {
@@ -859,7 +1295,23 @@ reduce:
             verbosity: 0,
             quiet: false,
             dry_run: false,
+            read_only: false,
+            record: None,
+            junit_output: None,
+            profile_steps: false,
+            replay: None,
+            chaos: None,
+            seed: None,
+            answers_file: None,
+            environment: None,
+            no_worktree: false,
             params: std::collections::HashMap::new(),
+            package: None,
+            changed_only: None,
+            explain_failure: false,
+            keep_going: false,
+            steps: Default::default(),
+            explicit_flags: Default::default(),
         };
 
         // Create dummy session and worktree manager (not used in the function)
@@ -903,7 +1355,8 @@ map:
             .unwrap();
 
         let result =
-            load_playbook_with_mapreduce(&playbook_path, &std::collections::HashMap::new()).await;
+            load_playbook_with_mapreduce(&playbook_path, &std::collections::HashMap::new(), None)
+                .await;
         assert!(result.is_ok(), "Should parse MapReduce YAML successfully");
 
         let (workflow, mapreduce_config) = result.unwrap();
@@ -914,6 +1367,56 @@ map:
         assert_eq!(mr_config.mode, "mapreduce");
     }
 
+    #[tokio::test]
+    async fn test_load_playbook_with_environment_overrides() {
+        let temp_dir = TempDir::new().unwrap();
+        let playbook_path = temp_dir.path().join("overrides.yml");
+        let params = std::collections::HashMap::new();
+
+        tokio::fs::write(
+            &playbook_path,
+            r#"name: test-overrides
+mode: mapreduce
+map:
+  input: test.json
+  agent_template:
+    - claude: "/process ${item.id}"
+  max_parallel: 2
+overrides:
+  ci:
+    map:
+      max_parallel: 20
+  broken:
+    map:
+      agent_template: "not a list"
+"#,
+        )
+        .await
+        .unwrap();
+
+        let (_, config) = load_playbook_with_mapreduce(&playbook_path, &params, Some("ci"))
+            .await
+            .unwrap();
+        assert_eq!(config.unwrap().map.max_parallel.as_deref(), Some("20"));
+
+        let (_, config) = load_playbook_with_mapreduce(&playbook_path, &params, None)
+            .await
+            .unwrap();
+        assert_eq!(config.unwrap().map.max_parallel.as_deref(), Some("2"));
+
+        // The merged workflow is validated like any other
+        assert!(
+            load_playbook_with_mapreduce(&playbook_path, &params, Some("broken"))
+                .await
+                .is_err()
+        );
+        assert!(
+            load_playbook_with_mapreduce(&playbook_path, &params, Some("staging"))
+                .await
+                .is_err()
+        );
+    }
+
     #[tokio::test]
     async fn test_load_playbook_with_mapreduce_yaml_regular() {
         let temp_dir = TempDir::new().unwrap();
@@ -928,7 +1431,8 @@ map:
             .unwrap();
 
         let result =
-            load_playbook_with_mapreduce(&playbook_path, &std::collections::HashMap::new()).await;
+            load_playbook_with_mapreduce(&playbook_path, &std::collections::HashMap::new(), None)
+                .await;
         assert!(result.is_ok(), "Should parse regular YAML successfully");
 
         let (workflow, mapreduce_config) = result.unwrap();
@@ -952,7 +1456,8 @@ map:
             .unwrap();
 
         let result =
-            load_playbook_with_mapreduce(&playbook_path, &std::collections::HashMap::new()).await;
+            load_playbook_with_mapreduce(&playbook_path, &std::collections::HashMap::new(), None)
+                .await;
         assert!(result.is_ok(), "Should parse JSON successfully");
 
         let (workflow, mapreduce_config) = result.unwrap();
@@ -971,7 +1476,7 @@ map:
 "#;
         tokio::fs::write(&yml_path, workflow_content).await.unwrap();
         let result =
-            load_playbook_with_mapreduce(&yml_path, &std::collections::HashMap::new()).await;
+            load_playbook_with_mapreduce(&yml_path, &std::collections::HashMap::new(), None).await;
         assert!(result.is_ok(), "Should handle .yml extension");
 
         // Test .yaml extension
@@ -980,7 +1485,7 @@ map:
             .await
             .unwrap();
         let result =
-            load_playbook_with_mapreduce(&yaml_path, &std::collections::HashMap::new()).await;
+            load_playbook_with_mapreduce(&yaml_path, &std::collections::HashMap::new(), None).await;
         assert!(result.is_ok(), "Should handle .yaml extension");
 
         // Test .json extension
@@ -988,10 +1493,40 @@ map:
         let json_content = r#"{"commands": [{"shell": "echo test"}]}"#;
         tokio::fs::write(&json_path, json_content).await.unwrap();
         let result =
-            load_playbook_with_mapreduce(&json_path, &std::collections::HashMap::new()).await;
+            load_playbook_with_mapreduce(&json_path, &std::collections::HashMap::new(), None).await;
         assert!(result.is_ok(), "Should handle .json extension");
     }
 
+    #[tokio::test]
+    async fn test_load_playbook_with_mapreduce_anchors_and_env() {
+        let temp_dir = TempDir::new().unwrap();
+        let playbook_path = temp_dir.path().join("anchors.yml");
+        let workflow_content = r#"name: anchors
+mode: mapreduce
+env:
+  WORKERS: "3"
+x-step: &step
+  timeout: 120
+map:
+  input: items.json
+  max_parallel: "${env.WORKERS}"
+  agent_template:
+    - <<: *step
+      shell: "echo ${item.id}"
+"#;
+        tokio::fs::write(&playbook_path, workflow_content)
+            .await
+            .unwrap();
+
+        let (_, mapreduce) =
+            load_playbook_with_mapreduce(&playbook_path, &std::collections::HashMap::new(), None)
+                .await
+                .unwrap();
+        let map_phase = mapreduce.unwrap().to_map_phase().unwrap();
+        assert_eq!(map_phase.config.max_parallel, 3);
+        assert_eq!(map_phase.agent_template[0].timeout, Some(120));
+    }
+
     // Phase 2 Tests: Error Handling Paths
 
     #[tokio::test]
@@ -1009,7 +1544,8 @@ mode: mapreduce
             .unwrap();
 
         let result =
-            load_playbook_with_mapreduce(&playbook_path, &std::collections::HashMap::new()).await;
+            load_playbook_with_mapreduce(&playbook_path, &std::collections::HashMap::new(), None)
+                .await;
         assert!(
             result.is_err(),
             "Should fail on invalid MapReduce structure"
@@ -1039,7 +1575,8 @@ invalid_line
             .unwrap();
 
         let result =
-            load_playbook_with_mapreduce(&playbook_path, &std::collections::HashMap::new()).await;
+            load_playbook_with_mapreduce(&playbook_path, &std::collections::HashMap::new(), None)
+                .await;
         assert!(result.is_err(), "Should fail on invalid YAML");
 
         let err = result.unwrap_err();
@@ -1062,7 +1599,8 @@ invalid_line
             .unwrap();
 
         let result =
-            load_playbook_with_mapreduce(&playbook_path, &std::collections::HashMap::new()).await;
+            load_playbook_with_mapreduce(&playbook_path, &std::collections::HashMap::new(), None)
+                .await;
         assert!(result.is_err(), "Should fail on invalid JSON");
 
         let err = result.unwrap_err();
@@ -1079,7 +1617,8 @@ invalid_line
         let playbook_path = temp_dir.path().join("nonexistent.yml");
 
         let result =
-            load_playbook_with_mapreduce(&playbook_path, &std::collections::HashMap::new()).await;
+            load_playbook_with_mapreduce(&playbook_path, &std::collections::HashMap::new(), None)
+                .await;
         assert!(result.is_err(), "Should fail on missing file");
 
         let err = result.unwrap_err();
@@ -1105,7 +1644,8 @@ mode: 123
             .unwrap();
 
         let result =
-            load_playbook_with_mapreduce(&playbook_path, &std::collections::HashMap::new()).await;
+            load_playbook_with_mapreduce(&playbook_path, &std::collections::HashMap::new(), None)
+                .await;
         // This might succeed or fail depending on how the parser handles it
         // The main goal is to ensure the function handles it gracefully
         if result.is_err() {
@@ -0,0 +1,304 @@
+//! Running one workflow across many repositories
+//!
+//! `prodigy run workflow.yml --repos repos.txt` runs the same workflow in every
+//! repository listed in `repos.txt`, one path or git URL per line. Local paths
+//! are used in place; URLs are cloned once into `~/.prodigy/repos/{name}` and
+//! fast-forwarded on later runs. Each run creates its own worktree as usual.
+//! Repositories run one after another, a failure does not stop the others,
+//! and a combined report is printed at the end.
+
+use super::command::CookCommand;
+use crate::styled_println;
+use crate::subprocess::{ProcessCommandBuilder, SubprocessManager};
+use anyhow::{anyhow, Context, Result};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Where a listed repository comes from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RepoSource {
+    /// An existing checkout
+    Local(PathBuf),
+    /// A git URL to clone
+    Remote(String),
+}
+
+/// One entry of a repository list
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepoEntry {
+    /// Short name used in progress output and for the clone directory
+    pub name: String,
+    pub source: RepoSource,
+}
+
+/// Result of running the workflow in one repository
+#[derive(Debug, Clone)]
+pub struct RepoOutcome {
+    pub name: String,
+    pub duration: Duration,
+    /// Why the run failed, `None` on success
+    pub error: Option<String>,
+}
+
+/// Whether a list entry is a git URL rather than a path (pure function)
+fn is_git_url(entry: &str) -> bool {
+    entry.contains("://") || entry.starts_with("git@")
+}
+
+/// Repository name from a path or URL, without a trailing `.git` (pure function)
+fn repo_name(entry: &str) -> Option<String> {
+    let last = entry
+        .trim_end_matches('/')
+        .rsplit(['/', ':'])
+        .next()
+        .unwrap_or_default();
+    let name = last.strip_suffix(".git").unwrap_or(last);
+    (!name.is_empty() && name != "." && name != "..").then(|| name.to_string())
+}
+
+/// Parse a repository list (pure function)
+///
+/// Blank lines and lines starting with `#` are skipped. Relative paths are
+/// resolved against `base_dir`, the directory containing the list.
+pub fn parse_repo_list(content: &str, base_dir: &Path) -> Result<Vec<RepoEntry>> {
+    let mut names = HashSet::new();
+    let mut entries = Vec::new();
+
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let name = repo_name(line).ok_or_else(|| {
+            anyhow!(
+                "Line {}: cannot determine a repository name from '{}'",
+                index + 1,
+                line
+            )
+        })?;
+        if !names.insert(name.clone()) {
+            return Err(anyhow!(
+                "Line {}: repository name '{}' is listed more than once",
+                index + 1,
+                name
+            ));
+        }
+
+        let source = if is_git_url(line) {
+            RepoSource::Remote(line.to_string())
+        } else {
+            RepoSource::Local(base_dir.join(line))
+        };
+        entries.push(RepoEntry { name, source });
+    }
+
+    if entries.is_empty() {
+        return Err(anyhow!("Repository list is empty"));
+    }
+    Ok(entries)
+}
+
+/// Format the combined report (pure function)
+pub fn format_report(outcomes: &[RepoOutcome]) -> Vec<String> {
+    let failed = outcomes.iter().filter(|o| o.error.is_some()).count();
+    let mut lines = vec![format!(
+        "Multi-repo run: {} succeeded, {} failed, {} total",
+        outcomes.len() - failed,
+        failed,
+        outcomes.len()
+    )];
+    for outcome in outcomes {
+        let status = match &outcome.error {
+            None => "✅".to_string(),
+            Some(error) => format!("❌ {}", error.lines().next().unwrap_or_default()),
+        };
+        lines.push(format!(
+            "  {:<30} {:>8}  {}",
+            outcome.name,
+            crate::unified_session::format_duration(outcome.duration),
+            status
+        ));
+    }
+    lines
+}
+
+/// Get a checkout for a repository, cloning or updating remote ones
+async fn prepare_checkout(
+    entry: &RepoEntry,
+    clones_dir: &Path,
+    subprocess: &SubprocessManager,
+) -> Result<PathBuf> {
+    let url = match &entry.source {
+        RepoSource::Local(path) => {
+            if !path.join(".git").exists() {
+                return Err(anyhow!("Not a git repository: {}", path.display()));
+            }
+            return Ok(path.clone());
+        }
+        RepoSource::Remote(url) => url,
+    };
+
+    let dir = clones_dir.join(&entry.name);
+    let command = if dir.join(".git").exists() {
+        ProcessCommandBuilder::new("git")
+            .args(["pull", "--ff-only"])
+            .current_dir(&dir)
+            .build()
+    } else {
+        tokio::fs::create_dir_all(clones_dir).await?;
+        ProcessCommandBuilder::new("git")
+            .args(["clone", url.as_str(), &dir.to_string_lossy()])
+            .build()
+    };
+
+    let output = subprocess
+        .runner()
+        .run(command)
+        .await
+        .with_context(|| format!("Failed to run git for {}", url))?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "Failed to update checkout of {}: {}",
+            url,
+            output.stderr.trim()
+        ));
+    }
+    Ok(dir)
+}
+
+/// Run the workflow in every repository listed in `repos_file`
+pub async fn run_across_repos(cmd: CookCommand, repos_file: &Path) -> Result<()> {
+    let original_dir = std::env::current_dir()?;
+    let content = tokio::fs::read_to_string(repos_file)
+        .await
+        .with_context(|| format!("Failed to read repository list {}", repos_file.display()))?;
+    let list_dir = original_dir.join(repos_file.parent().unwrap_or(Path::new("")));
+    let entries = parse_repo_list(&content, &list_dir)?;
+
+    let clones_dir = crate::storage::get_default_storage_dir()?.join("repos");
+    let subprocess = SubprocessManager::production();
+    let total = entries.len();
+    let mut outcomes = Vec::with_capacity(total);
+
+    for (index, entry) in entries.iter().enumerate() {
+        let failed = outcomes
+            .iter()
+            .filter(|o: &&RepoOutcome| o.error.is_some())
+            .count();
+        println!(
+            "\n[{}/{}] {} ({} succeeded, {} failed so far)",
+            index + 1,
+            total,
+            entry.name,
+            index - failed,
+            failed
+        );
+
+        let started = Instant::now();
+        let result = match prepare_checkout(entry, &clones_dir, &subprocess).await {
+            Ok(path) => {
+                // The run loads the repository's own `.prodigy/config.yml`
+                let mut repo_cmd = cmd.clone();
+                repo_cmd.path = Some(path);
+                super::cook(repo_cmd).await
+            }
+            Err(e) => Err(e),
+        };
+
+        if let Err(e) = &result {
+            eprintln!("{}: {:#}", entry.name, e);
+        }
+        outcomes.push(RepoOutcome {
+            name: entry.name.clone(),
+            duration: started.elapsed(),
+            error: result.err().map(|e| format!("{:#}", e)),
+        });
+    }
+
+    println!();
+    for line in format_report(&outcomes) {
+        styled_println!("{}", line);
+    }
+
+    let failed = outcomes.iter().filter(|o| o.error.is_some()).count();
+    if failed > 0 {
+        return Err(anyhow!("{} of {} repositories failed", failed, total));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_repo_list() {
+        let content = "\
+# services
+../payments
+/srv/checkout/
+
+https://github.com/acme/billing.git
+git@github.com:acme/search.git
+";
+        let entries = parse_repo_list(content, Path::new("/work")).unwrap();
+        assert_eq!(
+            entries,
+            vec![
+                RepoEntry {
+                    name: "payments".to_string(),
+                    source: RepoSource::Local(PathBuf::from("/work/../payments")),
+                },
+                RepoEntry {
+                    name: "checkout".to_string(),
+                    source: RepoSource::Local(PathBuf::from("/srv/checkout/")),
+                },
+                RepoEntry {
+                    name: "billing".to_string(),
+                    source: RepoSource::Remote("https://github.com/acme/billing.git".to_string()),
+                },
+                RepoEntry {
+                    name: "search".to_string(),
+                    source: RepoSource::Remote("git@github.com:acme/search.git".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_repo_list_errors() {
+        let duplicate = parse_repo_list("a/api\nb/api.git\n", Path::new("/work"));
+        assert!(duplicate
+            .unwrap_err()
+            .to_string()
+            .contains("Line 2: repository name 'api' is listed more than once"));
+
+        let empty = parse_repo_list("# nothing here\n\n", Path::new("/work"));
+        assert!(empty.unwrap_err().to_string().contains("empty"));
+
+        assert!(parse_repo_list("..\n", Path::new("/work")).is_err());
+    }
+
+    #[test]
+    fn test_format_report() {
+        let outcomes = vec![
+            RepoOutcome {
+                name: "payments".to_string(),
+                duration: Duration::from_secs(65),
+                error: None,
+            },
+            RepoOutcome {
+                name: "billing".to_string(),
+                duration: Duration::from_secs(3),
+                error: Some("Workflow failed\nmore detail".to_string()),
+            },
+        ];
+        let lines = format_report(&outcomes);
+        assert_eq!(lines[0], "Multi-repo run: 1 succeeded, 1 failed, 2 total");
+        assert!(lines[1].starts_with("  payments"));
+        assert!(lines[1].ends_with("✅"));
+        assert!(lines[2].ends_with("❌ Workflow failed"));
+    }
+}
@@ -0,0 +1,145 @@
+//! "No changes needed" outcomes of `commit_required` steps
+//!
+//! A step with `commit_required: true` fails when it creates no commit. An
+//! agent that decides its item needs no change can report that instead, and
+//! the step then succeeds without a commit. Either write
+//! `.prodigy/no-changes.json` in the working directory:
+//!
+//! ```json
+//! {"reason": "parse_config is already covered by tests"}
+//! ```
+//!
+//! or print a line starting with `PRODIGY_NO_CHANGES`, optionally followed by
+//! `:` and the reason. The file is removed once read, so it never ends up in a
+//! commit or carries over to the next step. MapReduce counts these items
+//! separately in its map statistics.
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// File an agent writes, relative to its working directory
+pub const NO_CHANGES_FILE: &str = ".prodigy/no-changes.json";
+
+/// Marker an agent prints at the start of a line
+pub const NO_CHANGES_MARKER: &str = "PRODIGY_NO_CHANGES";
+
+/// A step's report that its item needed no changes
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NoChanges {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl NoChanges {
+    /// One-line description for progress output
+    pub fn describe(&self) -> String {
+        match &self.reason {
+            Some(reason) => format!("no changes needed: {}", reason),
+            None => "no changes needed".to_string(),
+        }
+    }
+}
+
+/// Take the report a step left in `working_dir` or printed to `output`
+///
+/// The report file is removed whether or not it parses.
+pub fn take(working_dir: &Path, output: &str) -> Option<NoChanges> {
+    let path = working_dir.join(NO_CHANGES_FILE);
+    if let Ok(content) = std::fs::read_to_string(&path) {
+        if let Err(e) = std::fs::remove_file(&path) {
+            tracing::warn!("Failed to remove {}: {}", path.display(), e);
+        }
+        return Some(parse_file(&content));
+    }
+    find_marker(output)
+}
+
+/// Report file content; anything other than the JSON object is taken as the reason (pure function)
+fn parse_file(content: &str) -> NoChanges {
+    serde_json::from_str(content).unwrap_or_else(|_| {
+        let reason = content.trim();
+        NoChanges {
+            reason: (!reason.is_empty()).then(|| reason.to_string()),
+        }
+    })
+}
+
+/// The last marker line in a step's output (pure function)
+///
+/// Claude's streaming output is JSON, so a line may also start after an
+/// escaped newline or the opening quote of a string.
+pub fn find_marker(output: &str) -> Option<NoChanges> {
+    let (start, _) = output
+        .match_indices(NO_CHANGES_MARKER)
+        .filter(|(index, _)| {
+            let before = &output[..*index];
+            before.is_empty()
+                || before.ends_with('\n')
+                || before.ends_with("\\n")
+                || before.ends_with('"')
+        })
+        .last()?;
+    let rest = &output[start + NO_CHANGES_MARKER.len()..];
+    let reason = rest.strip_prefix(':').map(|reason| {
+        let end = reason.find(['\n', '"']).unwrap_or(reason.len());
+        let reason = &reason[..end];
+        reason.split("\\n").next().unwrap_or(reason).trim()
+    });
+    Some(NoChanges {
+        reason: reason
+            .filter(|reason| !reason.is_empty())
+            .map(str::to_string),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_find_marker() {
+        assert_eq!(find_marker("Checked the function\nall good"), None);
+        assert_eq!(
+            find_marker("Checked the function\nPRODIGY_NO_CHANGES: already tested\n"),
+            Some(NoChanges {
+                reason: Some("already tested".to_string())
+            })
+        );
+        assert_eq!(
+            find_marker("PRODIGY_NO_CHANGES"),
+            Some(NoChanges { reason: None })
+        );
+        // Instructions that mention the marker mid-line are not a report
+        assert_eq!(
+            find_marker("If nothing needs fixing, print `PRODIGY_NO_CHANGES`"),
+            None
+        );
+        // Streaming JSON output
+        let streamed =
+            r#"{"type":"result","result":"Done.\nPRODIGY_NO_CHANGES: covered by test_parse\nBye"}"#;
+        assert_eq!(
+            find_marker(streamed).and_then(|report| report.reason),
+            Some("covered by test_parse".to_string())
+        );
+    }
+
+    #[test]
+    fn test_take_consumes_report_file() {
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(dir.path().join(".prodigy")).unwrap();
+        std::fs::write(
+            dir.path().join(NO_CHANGES_FILE),
+            r#"{"reason": "already fixed upstream"}"#,
+        )
+        .unwrap();
+
+        let report = take(dir.path(), "").unwrap();
+        assert_eq!(
+            report.describe(),
+            "no changes needed: already fixed upstream"
+        );
+        assert!(!dir.path().join(NO_CHANGES_FILE).exists());
+        assert_eq!(take(dir.path(), "no marker here"), None);
+    }
+}
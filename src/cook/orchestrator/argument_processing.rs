@@ -154,6 +154,13 @@ impl ArgumentProcessor {
             iterate: false,
             retry_defaults: None,
             environment: None,
+            always_steps: config
+                .workflow
+                .always
+                .iter()
+                .map(Self::convert_command_to_step)
+                .collect(),
+            strict_variables: config.workflow.strict_variables_enabled(),
         };
 
         // Create workflow context with variables
@@ -166,15 +173,18 @@ impl ArgumentProcessor {
             validation_results: HashMap::new(),
             variable_store: std::sync::Arc::new(crate::cook::workflow::VariableStore::new()),
             git_tracker: None,
+            strict_variables: false,
         };
 
         // Create workflow executor with checkpoint support using session storage
         let checkpoint_storage = crate::cook::workflow::CheckpointStorage::Session {
             session_id: env.session_id.to_string(),
         };
-        let checkpoint_manager = Arc::new(crate::cook::workflow::CheckpointManager::with_storage(
-            checkpoint_storage,
-        ));
+        let checkpoint_manager = Arc::new(
+            crate::cook::workflow::CheckpointManager::with_storage(checkpoint_storage).with_repo(
+                crate::cook::workflow::relocate::RepoIdentity::detect(&config.project_path).await,
+            ),
+        );
         let workflow_id = format!("workflow-{}", chrono::Utc::now().timestamp_millis());
 
         let mut executor = self
@@ -199,6 +209,19 @@ impl ArgumentProcessor {
             .with_checkpoint_manager(checkpoint_manager, workflow_id)
             // Re-apply dry-run mode after creating test executor
             .with_dry_run(config.command.dry_run)
+            .with_read_only(config.command.read_only)
+            .with_keep_going(config.command.keep_going)
+            .with_step_selection(config.command.steps.clone())
+            .with_replay_session(config.replay.clone())
+            .with_chaos(config.chaos.clone())
+            .with_junit(config.junit.clone())
+            .with_step_profiler(config.profiler.clone())
+            .with_protected_paths(config.protected_paths.clone())
+            .with_seed(config.command.seed)
+            .with_score_gate(config.workflow.score_gate.clone())
+            .with_failure_recorder(config.failures.clone())
+            .with_output_settings(config.settings.output.clone())
+            .with_claude_permissions(config.workflow.claude_permissions.clone())
             // Re-apply positional args after creating test executor
             .with_positional_args(vec![input.to_string()]);
         }
@@ -338,6 +361,19 @@ impl ArgumentProcessor {
         )
         .with_workflow_path(config.command.playbook.clone())
         .with_dry_run(config.command.dry_run)
+        .with_read_only(config.command.read_only)
+        .with_keep_going(config.command.keep_going)
+        .with_step_selection(config.command.steps.clone())
+        .with_replay_session(config.replay.clone())
+        .with_chaos(config.chaos.clone())
+        .with_junit(config.junit.clone())
+        .with_step_profiler(config.profiler.clone())
+        .with_protected_paths(config.protected_paths.clone())
+        .with_seed(config.command.seed)
+        .with_score_gate(config.workflow.score_gate.clone())
+        .with_failure_recorder(config.failures.clone())
+        .with_output_settings(config.settings.output.clone())
+        .with_claude_permissions(config.workflow.claude_permissions.clone())
         .with_positional_args(config.command.args.clone())
     }
 }
@@ -252,6 +252,13 @@ mod tests {
             env_files: None,
             profiles: None,
             merge: None,
+            concurrency: None,
+            claude_permissions: None,
+            always: Vec::new(),
+            strict_variables: None,
+            defaults: None,
+            protected_paths: None,
+            score_gate: None,
         };
 
         let env_config = create_env_config(&workflow);
@@ -277,6 +284,13 @@ mod tests {
             env_files: None,
             profiles: None,
             merge: None,
+            concurrency: None,
+            claude_permissions: None,
+            always: Vec::new(),
+            strict_variables: None,
+            defaults: None,
+            protected_paths: None,
+            score_gate: None,
         };
 
         let env_config = create_env_config(&workflow);
@@ -300,7 +314,23 @@ mod tests {
             verbosity: 0,
             quiet: false,
             dry_run: false,
+            read_only: false,
+            record: None,
+            junit_output: None,
+            profile_steps: false,
+            replay: None,
+            chaos: None,
+            seed: None,
+            answers_file: None,
+            environment: None,
+            no_worktree: false,
             params: std::collections::HashMap::new(),
+            package: None,
+            changed_only: None,
+            explain_failure: false,
+            keep_going: false,
+            steps: Default::default(),
+            explicit_flags: Default::default(),
         };
 
         let (playbook, args, map) = create_workflow_state_base(&command);
@@ -392,6 +422,13 @@ mod tests {
                 commands: vec![],
                 timeout: Some(600),
             }),
+            concurrency: None,
+            claude_permissions: None,
+            always: Vec::new(),
+            strict_variables: None,
+            defaults: None,
+            protected_paths: None,
+            score_gate: None,
         };
 
         let result = extract_merge_config(&workflow, &None);
@@ -412,6 +449,13 @@ mod tests {
             env_files: None,
             profiles: None,
             merge: None,
+            concurrency: None,
+            claude_permissions: None,
+            always: Vec::new(),
+            strict_variables: None,
+            defaults: None,
+            protected_paths: None,
+            score_gate: None,
         };
 
         let result = extract_workflow_env(&workflow);
@@ -21,12 +21,12 @@ use crate::testing::config::TestConfiguration;
 use crate::worktree::WorktreeManager;
 use anyhow::{anyhow, Result};
 use async_trait::async_trait;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use crate::cook::command::CookCommand;
 use crate::cook::execution::{ClaudeExecutor, CommandExecutor};
-use crate::cook::interaction::UserInteraction;
+use crate::cook::interaction::{PromptKind, UserInteraction};
 use crate::cook::session::SessionManager;
 // Re-export WorkflowType from workflow_classifier for backwards compatibility
 pub(crate) use super::workflow_classifier::WorkflowType;
@@ -42,6 +42,22 @@ pub struct CookConfig {
     pub workflow: Arc<WorkflowConfig>,
     /// MapReduce configuration (if this is a MapReduce workflow)
     pub mapreduce_config: Option<Arc<crate::config::MapReduceWorkflowConfig>>,
+    /// Record/replay session for command outputs (from `--record` or `--replay`)
+    pub replay: Option<Arc<crate::cook::execution::ReplaySession>>,
+    /// Fault injector for Claude and shell commands (from `--chaos`)
+    pub chaos: Option<Arc<crate::cook::execution::chaos::ChaosMonkey>>,
+    /// Step results for JUnit XML output and GitHub Actions annotations
+    pub junit: Option<Arc<crate::cook::execution::junit::JunitRecorder>>,
+    /// Step measurements for `--profile-steps`
+    pub profiler: Option<Arc<crate::cook::execution::step_profile::StepProfiler>>,
+    /// Paths no step may change, from the workflow and the Prodigy config
+    pub protected_paths: Option<Arc<crate::cook::protected_paths::ProtectedPaths>>,
+    /// Prompt answers given during the run (from `--answers-file` when replaying)
+    pub answers: Option<Arc<crate::cook::interaction::answers::InteractionLog>>,
+    /// Details of the step that ends a failed run, for its failure report
+    pub failures: Option<Arc<crate::cook::workflow::failure_report::FailureRecorder>>,
+    /// Global and project configuration loaded for the run
+    pub settings: Arc<crate::config::ProdigyConfig>,
 }
 
 /// Trait for orchestrating cook operations
@@ -75,6 +91,8 @@ pub struct ExecutionEnvironment {
     pub worktree_name: Option<Arc<str>>,
     /// Session ID
     pub session_id: Arc<str>,
+    /// Monorepo package `working_dir` is scoped to, relative to the checkout root
+    pub package: Option<Arc<Path>>,
 }
 
 impl Clone for ExecutionEnvironment {
@@ -84,6 +102,31 @@ impl Clone for ExecutionEnvironment {
             project_dir: Arc::clone(&self.project_dir),
             worktree_name: self.worktree_name.as_ref().map(Arc::clone),
             session_id: Arc::clone(&self.session_id),
+            package: self.package.as_ref().map(Arc::clone),
+        }
+    }
+}
+
+impl ExecutionEnvironment {
+    /// Root of the checkout (worktree or project) `working_dir` lives in
+    pub fn checkout_root(&self) -> PathBuf {
+        let depth = self
+            .package
+            .as_ref()
+            .map(|package| package.components().count())
+            .unwrap_or(0);
+        self.working_dir
+            .ancestors()
+            .nth(depth)
+            .unwrap_or(&self.working_dir)
+            .to_path_buf()
+    }
+
+    /// Directory to run commands in for another checkout of the same repository
+    pub fn scoped_dir(&self, checkout_root: &Path) -> PathBuf {
+        match &self.package {
+            Some(package) => checkout_root.join(package),
+            None => checkout_root.to_path_buf(),
         }
     }
 }
@@ -97,7 +140,6 @@ pub struct DefaultCookOrchestrator {
     command_executor: Arc<dyn CommandExecutor>,
     claude_executor: Arc<dyn ClaudeExecutor>,
     user_interaction: Arc<dyn UserInteraction>,
-    #[allow(dead_code)]
     git_operations: Arc<dyn GitOperations>,
     subprocess: crate::subprocess::SubprocessManager,
     #[allow(dead_code)]
@@ -222,6 +264,19 @@ impl DefaultCookOrchestrator {
             Arc::clone(&self.user_interaction),
             config.command.playbook.clone(),
         )
+        .with_read_only(config.command.read_only)
+        .with_keep_going(config.command.keep_going)
+        .with_step_selection(config.command.steps.clone())
+        .with_replay_session(config.replay.clone())
+        .with_chaos(config.chaos.clone())
+        .with_junit(config.junit.clone())
+        .with_step_profiler(config.profiler.clone())
+        .with_protected_paths(config.protected_paths.clone())
+        .with_seed(config.command.seed)
+        .with_score_gate(config.workflow.score_gate.clone())
+        .with_failure_recorder(config.failures.clone())
+        .with_output_settings(config.settings.output.clone())
+        .with_claude_permissions(config.workflow.claude_permissions.clone())
     }
 
     /// Classify workflow type using pure function
@@ -281,7 +336,11 @@ impl DefaultCookOrchestrator {
                         .await
                         .unwrap_or_else(|_| "master".to_string());
                     self.user_interaction
-                        .prompt_yes_no(&format!("Merge {} to {}", worktree, target))
+                        .prompt_yes_no_from(
+                            PromptKind::MergeConfirmation,
+                            None,
+                            &format!("Merge {} to {}", worktree, target),
+                        )
                         .await?
                 }
             };
@@ -294,6 +353,66 @@ impl DefaultCookOrchestrator {
         Ok(())
     }
 
+    async fn create_read_only_worktree(
+        &self,
+        config: &CookConfig,
+        session_id: &str,
+    ) -> Result<(Arc<PathBuf>, Option<Arc<str>>)> {
+        let manager =
+            WorktreeManager::new(config.project_path.to_path_buf(), self.subprocess.clone())?;
+        let path = manager.create_detached_worktree(session_id).await?;
+        self.user_interaction.display_info(&format!(
+            "Created read-only worktree at: {}",
+            path.display()
+        ));
+        // No worktree name: detached worktrees are never merged
+        Ok((Arc::new(path), None))
+    }
+
+    /// Verify that neither the worktree nor the project changed during a read-only run
+    async fn verify_read_only_run(
+        &self,
+        env: &ExecutionEnvironment,
+        project_state: &super::read_only::TrackedState,
+    ) -> Result<()> {
+        // The worktree starts as a clean checkout of the project's HEAD
+        let worktree_state = super::read_only::TrackedState {
+            head: project_state.head.clone(),
+            status: Vec::new(),
+        };
+        let checkout_root = env.checkout_root();
+        super::read_only::verify_unchanged(
+            self.git_operations.as_ref(),
+            &checkout_root,
+            &worktree_state,
+        )
+        .await?;
+        if checkout_root != *env.project_dir {
+            super::read_only::verify_unchanged(
+                self.git_operations.as_ref(),
+                &env.project_dir,
+                project_state,
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    async fn remove_read_only_worktree(&self, env: &ExecutionEnvironment) {
+        let removal =
+            match WorktreeManager::new(env.project_dir.to_path_buf(), self.subprocess.clone()) {
+                Ok(manager) => manager.remove_detached_worktree(&env.checkout_root()).await,
+                Err(e) => Err(e),
+            };
+        if let Err(e) = removal {
+            log::warn!(
+                "Failed to remove read-only worktree {}: {}",
+                env.working_dir.display(),
+                e
+            );
+        }
+    }
+
     // --- Execution Dispatch (Uses Pure Plan) ---
 
     async fn execute_by_mode(
@@ -353,14 +472,21 @@ impl DefaultCookOrchestrator {
 
         let extended = super::workflow_execution::build_standard_workflow_config(
             &config.workflow.commands,
+            &config.workflow.always,
             config.command.max_iterations,
+            config.workflow.strict_variables_enabled(),
         );
 
-        let checkpoint_mgr = Arc::new(crate::cook::workflow::CheckpointManager::with_storage(
-            crate::cook::workflow::CheckpointStorage::Session {
-                session_id: env.session_id.to_string(),
-            },
-        ));
+        let checkpoint_mgr = Arc::new(
+            crate::cook::workflow::CheckpointManager::with_storage(
+                crate::cook::workflow::CheckpointStorage::Session {
+                    session_id: env.session_id.to_string(),
+                },
+            )
+            .with_repo(
+                crate::cook::workflow::relocate::RepoIdentity::detect(&config.project_path).await,
+            ),
+        );
         let mut executor = self
             .create_workflow_executor_internal(config)
             .with_checkpoint_manager(
@@ -403,11 +529,29 @@ impl CookOrchestrator for DefaultCookOrchestrator {
             plan.phase_count()
         );
 
+        // Reject workflows that cannot run without committing
+        if config.command.read_only {
+            super::read_only::validate_read_only(&config)?;
+        }
+
         // Check prerequisites
         self.session_ops
             .check_prerequisites_with_config(&config)
             .await?;
 
+        // Snapshot the project so read-only runs can prove it was left untouched
+        let project_state = if config.command.read_only {
+            Some(
+                super::read_only::capture_tracked_state(
+                    self.git_operations.as_ref(),
+                    &config.project_path,
+                )
+                .await?,
+            )
+        } else {
+            None
+        };
+
         // Setup environment (I/O)
         let env = self.setup_environment(&config).await?;
 
@@ -424,19 +568,39 @@ impl CookOrchestrator for DefaultCookOrchestrator {
         )?;
 
         // Execute by mode (determined by pure plan)
-        let execution_result = self.execute_by_mode(&env, &config, &plan).await;
+        let mut execution_result = self.execute_by_mode(&env, &config, &plan).await;
 
         interrupt_handler.abort();
 
+        if let Some(ref project_state) = project_state {
+            let verification = self.verify_read_only_run(&env, project_state).await;
+            execution_result = execution_result.and(verification);
+        }
+
         // Update session status
+        self.session_ops
+            .record_step_profile(config.profiler.as_deref())
+            .await;
         self.session_ops
             .update_unified_session_status(&env.session_id, execution_result.is_ok())
             .await;
 
         // Finalize
-        self.execution_pipeline
+        let result = self
+            .execution_pipeline
             .finalize_session(&env, &config, execution_result, self.cleanup(&env, &config))
-            .await
+            .await;
+
+        // After finalizing, so the merge confirmation is included
+        self.session_ops
+            .record_prompt_answers(config.answers.as_deref())
+            .await;
+
+        if config.command.read_only && !config.command.dry_run {
+            self.remove_read_only_worktree(&env).await;
+        }
+
+        result
     }
 
     async fn check_prerequisites(&self) -> Result<()> {
@@ -458,12 +622,34 @@ impl CookOrchestrator for DefaultCookOrchestrator {
             );
         }
 
-        let (working_dir, worktree_name) = if !config.command.dry_run {
-            self.create_worktree(config, &session_id).await?
-        } else {
+        let (working_dir, worktree_name) = if config.command.dry_run {
             self.user_interaction
                 .display_info("[DRY RUN] Would create worktree for isolated execution");
             (Arc::clone(&config.project_path), None)
+        } else if config.command.read_only {
+            self.create_read_only_worktree(config, &session_id).await?
+        } else if config.command.no_worktree {
+            self.user_interaction
+                .display_info("Running directly in the project directory (no worktree)");
+            (Arc::clone(&config.project_path), None)
+        } else {
+            self.create_worktree(config, &session_id).await?
+        };
+
+        let package = config.command.package.as_deref().map(Arc::<Path>::from);
+        let working_dir = match &package {
+            Some(package) => {
+                let package_dir = working_dir.join(package);
+                if !package_dir.is_dir() {
+                    return Err(anyhow!(
+                        "Package directory {} does not exist in {} (is it committed?)",
+                        package.display(),
+                        working_dir.display()
+                    ));
+                }
+                Arc::new(package_dir)
+            }
+            None => working_dir,
         };
 
         Ok(ExecutionEnvironment {
@@ -471,6 +657,7 @@ impl CookOrchestrator for DefaultCookOrchestrator {
             project_dir: Arc::clone(&config.project_path),
             worktree_name,
             session_id,
+            package,
         })
     }
 
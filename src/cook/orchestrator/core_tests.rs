@@ -22,7 +22,23 @@ fn create_test_cook_command() -> CookCommand {
         verbosity: 0,
         quiet: false,
         dry_run: false,
+        read_only: false,
+        record: None,
+        junit_output: None,
+        profile_steps: false,
+        replay: None,
+        chaos: None,
+        seed: None,
+        answers_file: None,
+        environment: None,
+        no_worktree: false,
         params: std::collections::HashMap::new(),
+        package: None,
+        changed_only: None,
+        explain_failure: false,
+        keep_going: false,
+        steps: Default::default(),
+        explicit_flags: Default::default(),
     }
 }
 
@@ -39,8 +55,23 @@ fn test_classify_workflow_type_standard() {
             env_files: None,
             profiles: None,
             merge: None,
+            concurrency: None,
+            claude_permissions: None,
+            always: Vec::new(),
+            strict_variables: None,
+            defaults: None,
+            protected_paths: None,
+            score_gate: None,
         }),
         mapreduce_config: None,
+        replay: None,
+        chaos: None,
+        junit: None,
+        profiler: None,
+        protected_paths: None,
+        answers: None,
+        failures: None,
+        settings: Default::default(),
     };
 
     assert_eq!(
@@ -65,8 +96,23 @@ fn test_classify_workflow_type_with_arguments() {
             env_files: None,
             profiles: None,
             merge: None,
+            concurrency: None,
+            claude_permissions: None,
+            always: Vec::new(),
+            strict_variables: None,
+            defaults: None,
+            protected_paths: None,
+            score_gate: None,
         }),
         mapreduce_config: None,
+        replay: None,
+        chaos: None,
+        junit: None,
+        profiler: None,
+        protected_paths: None,
+        answers: None,
+        failures: None,
+        settings: Default::default(),
     };
 
     assert_eq!(
@@ -91,8 +137,23 @@ fn test_classify_workflow_type_with_map_patterns() {
             env_files: None,
             profiles: None,
             merge: None,
+            concurrency: None,
+            claude_permissions: None,
+            always: Vec::new(),
+            strict_variables: None,
+            defaults: None,
+            protected_paths: None,
+            score_gate: None,
         }),
         mapreduce_config: None,
+        replay: None,
+        chaos: None,
+        junit: None,
+        profiler: None,
+        protected_paths: None,
+        answers: None,
+        failures: None,
+        settings: Default::default(),
     };
 
     assert_eq!(
@@ -100,3 +161,35 @@ fn test_classify_workflow_type_with_map_patterns() {
         WorkflowType::WithArguments
     );
 }
+
+#[test]
+fn test_execution_environment_package_scoping() {
+    use super::core::ExecutionEnvironment;
+    use std::path::Path;
+
+    let env = ExecutionEnvironment {
+        working_dir: Arc::new(PathBuf::from("/worktrees/session-1/crates/foo")),
+        project_dir: Arc::new(PathBuf::from("/repo")),
+        worktree_name: Some(Arc::from("session-1")),
+        session_id: Arc::from("session-1"),
+        package: Some(Arc::from(Path::new("crates/foo"))),
+    };
+    assert_eq!(env.checkout_root(), PathBuf::from("/worktrees/session-1"));
+    assert_eq!(
+        env.scoped_dir(Path::new("/worktrees/agent-1")),
+        PathBuf::from("/worktrees/agent-1/crates/foo")
+    );
+
+    let unscoped = ExecutionEnvironment {
+        package: None,
+        ..env.clone()
+    };
+    assert_eq!(
+        unscoped.checkout_root(),
+        PathBuf::from("/worktrees/session-1/crates/foo")
+    );
+    assert_eq!(
+        unscoped.scoped_dir(Path::new("/worktrees/agent-1")),
+        PathBuf::from("/worktrees/agent-1")
+    );
+}
@@ -163,6 +163,24 @@ impl ExecutionPipeline {
             .await?;
         debug!("Workflow type updated");
 
+        if let Some(package) = &config.command.package {
+            self.session_manager
+                .update_session(SessionUpdate::SetMetadata(
+                    crate::cook::package::PACKAGE_METADATA_KEY.to_string(),
+                    serde_json::Value::String(package.display().to_string()),
+                ))
+                .await?;
+        }
+
+        if let Some(seed) = config.command.seed {
+            self.session_manager
+                .update_session(SessionUpdate::SetMetadata(
+                    crate::cook::execution::determinism::SEED_METADATA_KEY.to_string(),
+                    serde_json::Value::from(seed),
+                ))
+                .await?;
+        }
+
         Ok(())
     }
 
@@ -201,6 +219,32 @@ impl ExecutionPipeline {
         })
     }
 
+    /// Record how the session's worktree run ended
+    ///
+    /// Best effort: a missing worktree state (e.g. already cleaned up) is not
+    /// an error. Finished sessions are no longer in progress, so they are not
+    /// mistaken for sessions orphaned by a crash.
+    fn update_worktree_finished_state(
+        &self,
+        config: &CookConfig,
+        env: &ExecutionEnvironment,
+        status: WorktreeStatus,
+        error: Option<String>,
+    ) {
+        let Some(ref name) = env.worktree_name else {
+            return;
+        };
+        let result = self.create_worktree_manager(config).and_then(|manager| {
+            manager.update_session_state(name.as_ref(), |state| {
+                state.status = status;
+                state.error = error;
+            })
+        });
+        if let Err(e) = result {
+            log::debug!("Could not update worktree state for {}: {}", name, e);
+        }
+    }
+
     /// Handle successful session completion
     async fn handle_session_success(&self) -> Result<()> {
         self.session_manager
@@ -290,6 +334,65 @@ impl ExecutionPipeline {
         Ok(())
     }
 
+    /// Store a failure report with the session and print it if requested
+    async fn report_failure(
+        &self,
+        error: &anyhow::Error,
+        env: &ExecutionEnvironment,
+        config: &CookConfig,
+        pending: crate::cook::workflow::failure_report::PendingFailure,
+    ) {
+        use crate::cook::workflow::failure_report::{self, FailureReport};
+
+        let mut report = FailureReport::new(&env.session_id, error, pending.step);
+        let resumable = self
+            .session_manager
+            .get_state()
+            .map(|state| state.workflow_state.is_some())
+            .unwrap_or(false);
+        if resumable {
+            report.resume_command = Some(format!("prodigy resume {}", env.session_id));
+        }
+        if let Some(job_id) = pending.mapreduce_job {
+            match crate::storage::create_global_dlq(&config.project_path, &job_id, None).await {
+                Ok(dlq) => {
+                    if let Ok(items) = dlq.list_items(Default::default()).await {
+                        report.dlq_total = items.len();
+                        report.dlq_items = items
+                            .into_iter()
+                            .take(failure_report::MAX_DLQ_ITEMS)
+                            .map(|item| item.item_id)
+                            .collect();
+                    }
+                }
+                Err(e) => tracing::warn!("Failed to open DLQ for {}: {}", job_id, e),
+            }
+            report
+                .resume_command
+                .get_or_insert_with(|| format!("prodigy resume-job {}", job_id));
+            report.dlq_job_id = Some(job_id);
+        }
+
+        if let Err(e) = self
+            .session_manager
+            .update_session(SessionUpdate::SetMetadata(
+                failure_report::FAILURE_METADATA_KEY.to_string(),
+                failure_report::to_metadata_value(&report),
+            ))
+            .await
+        {
+            tracing::warn!("Failed to store failure report: {}", e);
+        }
+
+        if config.command.explain_failure {
+            let lines = failure_report::format_report(&report);
+            self.user_interaction.display_info(&format!(
+                "\n── Failure summary ──\n  {}",
+                lines.join("\n  ")
+            ));
+        }
+    }
+
     /// Execute cleanup and complete the session
     async fn execute_cleanup_and_completion(
         &self,
@@ -377,6 +480,21 @@ impl ExecutionPipeline {
         execution_result: Result<(), anyhow::Error>,
         cleanup_fn: impl std::future::Future<Output = Result<()>>,
     ) -> Result<()> {
+        let pending_failure = config
+            .failures
+            .as_ref()
+            .map(|failures| failures.take())
+            .unwrap_or_default();
+
+        // A run stopped by SIGTERM or SIGHUP is resumable, not failed
+        if execution_result.is_err() && crate::cook::signal_handler::shutdown_requested().is_some()
+        {
+            self.session_manager
+                .update_session(SessionUpdate::MarkInterrupted)
+                .await
+                .ok();
+        }
+
         // Classify the execution outcome
         let session_status = if execution_result.is_err() {
             self.session_manager
@@ -393,6 +511,7 @@ impl ExecutionPipeline {
         match outcome {
             ExecutionOutcome::Success => {
                 self.handle_session_success().await?;
+                self.update_worktree_finished_state(config, env, WorktreeStatus::Completed, None);
                 let summary = self.execute_cleanup_and_completion(cleanup_fn).await?;
                 self.display_completion_summary(&summary, config).await?;
                 Ok(())
@@ -403,12 +522,25 @@ impl ExecutionPipeline {
                 execution_result
             }
             ExecutionOutcome::Failed(_) => {
+                self.update_worktree_finished_state(
+                    config,
+                    env,
+                    WorktreeStatus::Failed,
+                    execution_result.as_ref().err().map(|e| e.to_string()),
+                );
                 self.handle_session_failure(
                     execution_result.as_ref().unwrap_err(),
                     &env.session_id,
                     env,
                 )
                 .await?;
+                self.report_failure(
+                    execution_result.as_ref().unwrap_err(),
+                    env,
+                    config,
+                    pending_failure,
+                )
+                .await;
                 execution_result
             }
         }
@@ -988,6 +1120,9 @@ impl ExecutionPipeline {
         )?;
         // Override the workflow_env with interpolated values
         map_phase.workflow_env = workflow_env_plain;
+        if let Some(base) = &config.command.changed_only {
+            map_phase.changed_since = Some(base.clone());
+        }
 
         let extended_workflow = ExtendedWorkflowConfig {
             name: mapreduce_config.name.clone(),
@@ -1002,7 +1137,9 @@ impl ExecutionPipeline {
             iterate: false,
             retry_defaults: None,
             environment: None,
+            always_steps: mapreduce_config.always.clone(),
             // collect_metrics removed - MMM focuses on orchestration
+            strict_variables: false,
         };
 
         // Set global environment configuration if present in MapReduce workflow
@@ -21,6 +21,7 @@ pub mod environment;
 mod execution_pipeline;
 mod normalization;
 pub mod pure;
+mod read_only;
 mod session_ops;
 pub mod workflow_classifier;
 mod workflow_execution;
@@ -30,6 +31,8 @@ mod core_tests;
 
 // Re-export public types and traits from core
 pub use core::{CookConfig, CookOrchestrator, DefaultCookOrchestrator, ExecutionEnvironment};
+// Re-export step conversion for tools that inspect workflows
+pub(crate) use normalization::convert_command_to_step;
 // Re-export builder
 pub use builder::OrchestratorBuilder;
 // Re-export construction helpers
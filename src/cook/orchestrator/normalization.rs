@@ -6,7 +6,7 @@ use crate::config::command::{TestCommand, WorkflowCommand, WorkflowStepCommand};
 use crate::cook::workflow::{CaptureOutput, OnFailureConfig, WorkflowStep};
 
 /// Convert a WorkflowCommand to a WorkflowStep
-pub(super) fn convert_command_to_step(cmd: &WorkflowCommand) -> WorkflowStep {
+pub(crate) fn convert_command_to_step(cmd: &WorkflowCommand) -> WorkflowStep {
     match cmd {
         WorkflowCommand::WorkflowStep(step) => {
             // Handle new workflow step format directly
@@ -17,10 +17,19 @@ pub(super) fn convert_command_to_step(cmd: &WorkflowCommand) -> WorkflowStep {
                 name: None,
                 command: None,
                 claude: step.claude.clone(),
+                model: step.model.clone(),
+                context: step.context.clone(),
+                validate_output: step.validate_output.clone(),
                 shell,
                 test, // Contains retry logic for shell commands
                 foreach: step.foreach.clone(),
                 write_file: None,
+                assert: step.assert.clone(),
+                summarize_commits: step.summarize_commits.clone(),
+                claude_chat: step.claude_chat.clone(),
+                script: step.script.clone(),
+                snapshot: step.snapshot,
+                required: step.required,
                 handler: None,
                 capture: None,
                 auto_commit: false,
@@ -71,8 +80,8 @@ pub(super) fn convert_command_to_step(cmd: &WorkflowCommand) -> WorkflowStep {
                     None => CaptureOutput::Disabled,
                 },
                 timeout: None,
-                working_dir: None,
-                env: std::collections::HashMap::new(),
+                working_dir: step.cwd.as_ref().map(std::path::PathBuf::from),
+                env: step.env.clone(),
                 on_failure,
                 retry: None,
                 on_success: None,
@@ -85,6 +94,7 @@ pub(super) fn convert_command_to_step(cmd: &WorkflowCommand) -> WorkflowStep {
                 validation_timeout: None,
                 ignore_validation_failure: false,
                 when: None,
+                commit_policy: step.commit_policy.clone(),
             }
         }
         _ => {
@@ -103,6 +113,9 @@ pub(super) fn convert_command_to_step(cmd: &WorkflowCommand) -> WorkflowStep {
                     format!("/{command_str}")
                 }),
                 claude: None,
+                model: None,
+                context: None,
+                validate_output: None,
                 shell: None,
                 test: None,
                 foreach: None,
@@ -129,6 +142,13 @@ pub(super) fn convert_command_to_step(cmd: &WorkflowCommand) -> WorkflowStep {
                 validation_timeout: None,
                 ignore_validation_failure: false,
                 when: None,
+                assert: None,
+                summarize_commits: None,
+                claude_chat: None,
+                script: None,
+                snapshot: false,
+                required: false,
+                commit_policy: None,
             }
         }
     }
@@ -138,7 +158,9 @@ pub(super) fn convert_command_to_step(cmd: &WorkflowCommand) -> WorkflowStep {
 pub(super) fn process_step_failure_config(
     step: &WorkflowStepCommand,
 ) -> (Option<String>, Option<TestCommand>, Option<OnFailureConfig>) {
-    if step.shell.is_some() && step.on_failure.is_some() {
+    // Commit policy violations are detected after the step, so a shell step with
+    // a policy keeps on_failure on the step instead of inside the test retry loop
+    if step.shell.is_some() && step.on_failure.is_some() && step.commit_policy.is_none() {
         // Convert shell command with on_failure to test command for retry logic
         // Safe to use unwrap here as we just checked is_some() above
         let test_cmd = step.shell.as_ref().map(|shell_cmd| TestCommand {
@@ -153,10 +175,11 @@ pub(super) fn process_step_failure_config(
             // Use Advanced config with claude command
             OnFailureConfig::Advanced {
                 shell: None,
-                claude: Some(debug_config.claude.clone()),
+                claude: debug_config.debug_command(),
                 fail_workflow: debug_config.fail_workflow,
                 retry_original: false,
                 max_retries: debug_config.max_attempts - 1, // max_attempts includes first try
+                escalate_model: debug_config.escalate_model.clone(),
             }
         });
         (step.shell.clone(), step.test.clone(), on_failure)
@@ -181,6 +204,9 @@ mod tests {
     fn empty_workflow_step() -> WorkflowStepCommand {
         WorkflowStepCommand {
             claude: None,
+            model: None,
+            context: None,
+            validate_output: None,
             shell: None,
             analyze: None,
             test: None,
@@ -199,6 +225,15 @@ mod tests {
             capture_format: None,
             capture_streams: None,
             output_file: None,
+            assert: None,
+            summarize_commits: None,
+            claude_chat: None,
+            script: None,
+            snapshot: false,
+            required: false,
+            commit_policy: None,
+            cwd: None,
+            env: std::collections::HashMap::new(),
         }
     }
 
@@ -211,6 +246,7 @@ mod tests {
                 max_attempts: 3,
                 fail_workflow: false,
                 commit_required: true,
+                escalate_model: None,
             }),
             ..empty_workflow_step()
         };
@@ -226,15 +262,48 @@ mod tests {
         assert!(on_failure.is_none());
     }
 
+    #[test]
+    fn test_process_step_failure_config_shell_with_commit_policy() {
+        let step = WorkflowStepCommand {
+            shell: Some("echo test".to_string()),
+            commit_required: true,
+            commit_policy: Some(crate::cook::commit_tracker::CommitPolicy::default()),
+            on_failure: Some(TestDebugConfig {
+                claude: "/debug".to_string(),
+                max_attempts: 2,
+                fail_workflow: true,
+                commit_required: true,
+                escalate_model: None,
+            }),
+            ..empty_workflow_step()
+        };
+
+        let (shell, test, on_failure) = process_step_failure_config(&step);
+
+        // The step keeps its shell command and on_failure handler
+        assert_eq!(shell.as_deref(), Some("echo test"));
+        assert!(test.is_none());
+        assert!(matches!(
+            on_failure,
+            Some(OnFailureConfig::Advanced {
+                fail_workflow: true,
+                ..
+            })
+        ));
+    }
+
     #[test]
     fn test_process_step_failure_config_non_shell_with_on_failure() {
         let step = WorkflowStepCommand {
             claude: Some("/test".to_string()),
+            context: None,
+            validate_output: None,
             on_failure: Some(TestDebugConfig {
                 claude: "/debug".to_string(),
                 max_attempts: 3,
                 fail_workflow: true,
                 commit_required: true,
+                escalate_model: None,
             }),
             ..empty_workflow_step()
         };
@@ -249,6 +318,7 @@ mod tests {
             claude,
             fail_workflow,
             max_retries,
+            escalate_model: None,
             ..
         }) = on_failure
         {
@@ -290,6 +360,8 @@ mod tests {
     fn test_convert_command_to_step_workflow_step() {
         let cmd = WorkflowCommand::WorkflowStep(Box::new(WorkflowStepCommand {
             claude: Some("/lint".to_string()),
+            context: None,
+            validate_output: None,
             commit_required: true,
             capture_output: Some(CaptureOutputConfig::Variable("result".to_string())),
             ..empty_workflow_step()
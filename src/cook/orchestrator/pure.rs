@@ -454,6 +454,13 @@ mod tests {
             env_files: None,
             profiles: None,
             merge: None,
+            concurrency: None,
+            claude_permissions: None,
+            always: Vec::new(),
+            strict_variables: None,
+            defaults: None,
+            protected_paths: None,
+            score_gate: None,
         }
     }
 
@@ -466,6 +473,13 @@ mod tests {
             env_files: None,
             profiles: None,
             merge: None,
+            concurrency: None,
+            claude_permissions: None,
+            always: Vec::new(),
+            strict_variables: None,
+            defaults: None,
+            protected_paths: None,
+            score_gate: None,
         }
     }
 
@@ -480,6 +494,13 @@ mod tests {
             env_files: None,
             profiles: None,
             merge: None,
+            concurrency: None,
+            claude_permissions: None,
+            always: Vec::new(),
+            strict_variables: None,
+            defaults: None,
+            protected_paths: None,
+            score_gate: None,
         }
     }
 
@@ -494,6 +515,13 @@ mod tests {
             env_files: None,
             profiles: None,
             merge: None,
+            concurrency: None,
+            claude_permissions: None,
+            always: Vec::new(),
+            strict_variables: None,
+            defaults: None,
+            protected_paths: None,
+            score_gate: None,
         }
     }
 
@@ -508,6 +536,13 @@ mod tests {
             env_files: None,
             profiles: None,
             merge: None,
+            concurrency: None,
+            claude_permissions: None,
+            always: Vec::new(),
+            strict_variables: None,
+            defaults: None,
+            protected_paths: None,
+            score_gate: None,
         }
     }
 
@@ -641,6 +676,13 @@ mod tests {
             env_files: None,
             profiles: None,
             merge: None,
+            concurrency: None,
+            claude_permissions: None,
+            always: Vec::new(),
+            strict_variables: None,
+            defaults: None,
+            protected_paths: None,
+            score_gate: None,
         };
 
         let result = validate_workflow(&config);
@@ -719,6 +761,13 @@ mod tests {
             env_files: None,
             profiles: None,
             merge: None,
+            concurrency: None,
+            claude_permissions: None,
+            always: Vec::new(),
+            strict_variables: None,
+            defaults: None,
+            protected_paths: None,
+            score_gate: None,
         };
 
         let result = validate_workflow(&config);
@@ -818,6 +867,13 @@ mod tests {
             env_files: None,
             profiles: None,
             merge: None,
+            concurrency: None,
+            claude_permissions: None,
+            always: Vec::new(),
+            strict_variables: None,
+            defaults: None,
+            protected_paths: None,
+            score_gate: None,
         };
 
         let result = validate_workflow(&config);
@@ -838,6 +894,13 @@ mod tests {
             env_files: None,
             profiles: None,
             merge: None,
+            concurrency: None,
+            claude_permissions: None,
+            always: Vec::new(),
+            strict_variables: None,
+            defaults: None,
+            protected_paths: None,
+            score_gate: None,
         };
 
         let result = validate_workflow(&config);
@@ -0,0 +1,231 @@
+//! Read-only analysis mode
+//!
+//! Read-only runs execute a workflow in a detached, throwaway worktree and
+//! guarantee that the repository is left untouched. Validation happens in two
+//! places:
+//!
+//! - **Before execution**: workflows containing steps that require or
+//!   auto-create commits are rejected, as are MapReduce workflows (agents merge
+//!   their results back into the parent worktree).
+//! - **After execution**: the tracked state of the worktree is compared with the
+//!   state captured before the run, and any new commits or modified tracked
+//!   files fail the run.
+
+use crate::abstractions::git::GitOperations;
+use crate::cook::workflow::WorkflowStep;
+use anyhow::{anyhow, Result};
+use std::path::Path;
+
+use super::core::CookConfig;
+
+/// Snapshot of the tracked state of a git working tree
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct TrackedState {
+    /// Commit SHA of HEAD
+    pub head: String,
+    /// Porcelain status lines for tracked files (untracked files are ignored)
+    pub status: Vec<String>,
+}
+
+/// Get a short label describing a step for error messages
+fn step_label(step: &WorkflowStep) -> String {
+    if let Some(name) = &step.name {
+        name.clone()
+    } else if let Some(cmd) = &step.claude {
+        format!("claude: {cmd}")
+    } else if let Some(cmd) = &step.shell {
+        format!("shell: {cmd}")
+    } else if let Some(cmd) = &step.command {
+        format!("command: {cmd}")
+    } else {
+        "unknown step".to_string()
+    }
+}
+
+/// Find steps that cannot run in read-only mode
+///
+/// Returns a label for every step with `commit_required` or `auto_commit` set.
+pub(super) fn find_commit_required_steps(steps: &[WorkflowStep]) -> Vec<String> {
+    steps
+        .iter()
+        .filter(|step| step.commit_required || step.auto_commit)
+        .map(step_label)
+        .collect()
+}
+
+/// Validate that a workflow can be executed in read-only mode
+pub(super) fn validate_read_only(config: &CookConfig) -> Result<()> {
+    if config.mapreduce_config.is_some() {
+        return Err(anyhow!(
+            "MapReduce workflows cannot run in read-only mode: agents merge their changes into the parent worktree"
+        ));
+    }
+
+    let steps: Vec<WorkflowStep> = config
+        .workflow
+        .commands
+        .iter()
+        .map(super::normalization::convert_command_to_step)
+        .collect();
+
+    let offending = find_commit_required_steps(&steps);
+    if offending.is_empty() {
+        return Ok(());
+    }
+
+    Err(anyhow!(
+        "Workflow cannot run in read-only mode; the following steps require commits:\n  - {}",
+        offending.join("\n  - ")
+    ))
+}
+
+/// Parse `git status --porcelain` output into sorted, non-empty lines
+fn parse_status_lines(output: &str) -> Vec<String> {
+    let mut lines: Vec<String> = output
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(str::to_string)
+        .collect();
+    lines.sort();
+    lines
+}
+
+/// Describe every difference between two tracked states
+///
+/// An empty result means the working tree is unchanged.
+pub(super) fn detect_tracked_changes(before: &TrackedState, after: &TrackedState) -> Vec<String> {
+    let mut changes = Vec::new();
+
+    if before.head != after.head {
+        changes.push(format!("HEAD moved from {} to {}", before.head, after.head));
+    }
+
+    changes.extend(
+        after
+            .status
+            .iter()
+            .filter(|line| !before.status.contains(line))
+            .map(|line| format!("modified: {}", line.trim())),
+    );
+
+    changes
+}
+
+/// Capture the tracked state of the working tree at `dir`
+pub(super) async fn capture_tracked_state(
+    git: &dyn GitOperations,
+    dir: &Path,
+) -> Result<TrackedState> {
+    let head = git
+        .git_command_in_dir(&["rev-parse", "HEAD"], "get HEAD", dir)
+        .await?;
+    let status = git
+        .git_command_in_dir(
+            &["status", "--porcelain", "--untracked-files=no"],
+            "check tracked files",
+            dir,
+        )
+        .await?;
+
+    Ok(TrackedState {
+        head: String::from_utf8_lossy(&head.stdout).trim().to_string(),
+        status: parse_status_lines(&String::from_utf8_lossy(&status.stdout)),
+    })
+}
+
+/// Verify that the working tree at `dir` still matches `before`
+pub(super) async fn verify_unchanged(
+    git: &dyn GitOperations,
+    dir: &Path,
+    before: &TrackedState,
+) -> Result<()> {
+    let after = capture_tracked_state(git, dir).await?;
+    let changes = detect_tracked_changes(before, &after);
+    if changes.is_empty() {
+        return Ok(());
+    }
+
+    Err(anyhow!(
+        "Read-only run modified the repository at {}:\n  - {}",
+        dir.display(),
+        changes.join("\n  - ")
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(head: &str, status: &[&str]) -> TrackedState {
+        TrackedState {
+            head: head.to_string(),
+            status: status.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_find_commit_required_steps() {
+        let steps = vec![
+            WorkflowStep {
+                shell: Some("cargo test".to_string()),
+                ..Default::default()
+            },
+            WorkflowStep {
+                claude: Some("/fix".to_string()),
+                context: None,
+                validate_output: None,
+                commit_required: true,
+                ..Default::default()
+            },
+            WorkflowStep {
+                shell: Some("cargo fmt".to_string()),
+                auto_commit: true,
+                ..Default::default()
+            },
+        ];
+
+        assert_eq!(
+            find_commit_required_steps(&steps),
+            vec!["claude: /fix".to_string(), "shell: cargo fmt".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_find_commit_required_steps_none() {
+        let steps = vec![WorkflowStep {
+            claude: Some("/analyze".to_string()),
+            context: None,
+            validate_output: None,
+            ..Default::default()
+        }];
+        assert!(find_commit_required_steps(&steps).is_empty());
+    }
+
+    #[test]
+    fn test_parse_status_lines() {
+        let lines = parse_status_lines(" M src/lib.rs\n\nM  README.md\n");
+        assert_eq!(lines, vec![" M src/lib.rs", "M  README.md"]);
+    }
+
+    #[test]
+    fn test_detect_tracked_changes_unchanged() {
+        let before = state("abc", &[" M src/lib.rs"]);
+        assert!(detect_tracked_changes(&before, &before.clone()).is_empty());
+    }
+
+    #[test]
+    fn test_detect_tracked_changes_new_commit() {
+        let changes = detect_tracked_changes(&state("abc", &[]), &state("def", &[]));
+        assert_eq!(changes, vec!["HEAD moved from abc to def"]);
+    }
+
+    #[test]
+    fn test_detect_tracked_changes_modified_file() {
+        let before = state("abc", &[" M src/lib.rs"]);
+        let after = state("abc", &[" M src/lib.rs", " M src/main.rs"]);
+        assert_eq!(
+            detect_tracked_changes(&before, &after),
+            vec!["modified: M src/main.rs"]
+        );
+    }
+}
@@ -5,9 +5,9 @@
 use crate::abstractions::git::GitOperations;
 use crate::config::WorkflowConfig;
 use crate::cook::execution::ClaudeExecutor;
-use crate::cook::interaction::UserInteraction;
+use crate::cook::interaction::{answers, UserInteraction};
 use crate::cook::orchestrator::core::{CookConfig, ExecutionEnvironment};
-use crate::cook::session::{SessionManager, SessionState};
+use crate::cook::session::{SessionManager, SessionState, SessionUpdate};
 use crate::worktree::WorktreeManager;
 use anyhow::{anyhow, Context, Result};
 use sha2::{Digest, Sha256};
@@ -75,19 +75,71 @@ impl SessionOperations {
             return;
         };
         let id = crate::unified_session::SessionId::from_string(session_id.to_string());
-        if manager.load_session(&id).await.is_ok() {
+        if manager.load_session(&id).await.is_err() {
+            return;
+        }
+        if !success && crate::cook::signal_handler::shutdown_requested().is_some() {
+            let _ = manager.pause_session(&id).await;
+        } else {
             let _ = manager.complete_session(&id, success).await;
         }
     }
 
+    /// Store the step profile of this run, if profiling is enabled, with the session
+    pub async fn record_step_profile(
+        &self,
+        profiler: Option<&crate::cook::execution::step_profile::StepProfiler>,
+    ) {
+        use crate::cook::execution::step_profile;
+
+        let Some(steps) = profiler
+            .map(|profiler| profiler.steps())
+            .filter(|steps| !steps.is_empty())
+        else {
+            return;
+        };
+        let update = SessionUpdate::SetMetadata(
+            step_profile::STEP_PROFILE_METADATA_KEY.to_string(),
+            step_profile::to_metadata_value(&steps),
+        );
+        if let Err(e) = self.session_manager.update_session(update).await {
+            log::warn!("Failed to store step profile: {}", e);
+        }
+    }
+
+    /// Store the prompts answered during this run with the session
+    pub async fn record_prompt_answers(&self, log: Option<&answers::InteractionLog>) {
+        let Some(recorded) = log
+            .map(|log| log.recorded())
+            .filter(|recorded| !recorded.is_empty())
+        else {
+            return;
+        };
+        let update = SessionUpdate::SetMetadata(
+            answers::ANSWERS_METADATA_KEY.to_string(),
+            answers::to_metadata_value(&recorded),
+        );
+        if let Err(e) = self.session_manager.update_session(update).await {
+            log::warn!("Failed to store prompt answers: {}", e);
+        }
+    }
+
     /// Create a new unified session
     pub async fn create_unified_session(&self, config: &CookConfig) -> Result<String> {
         let manager = self.get_unified_session_manager().await?;
         let wf_id = super::construction::generate_workflow_id();
+        let mut metadata =
+            super::construction::create_session_metadata(config.workflow.commands.len());
+        metadata.insert(
+            crate::unified_session::environment::ENVIRONMENT_METADATA_KEY.to_string(),
+            crate::unified_session::EnvironmentSnapshot::capture()
+                .await
+                .to_metadata_value(),
+        );
         let cfg = super::construction::build_session_config(
             wf_id.clone(),
             config.workflow.name.clone(),
-            super::construction::create_session_metadata(config.workflow.commands.len()),
+            metadata,
         );
         let id = manager.create_session(cfg).await?;
         manager.start_session(&id).await?;
@@ -120,6 +172,7 @@ impl SessionOperations {
         if !self.claude_executor.check_claude_cli().await? {
             anyhow::bail!("Claude CLI is not available. Please install it first.");
         }
+        self.claude_executor.probe_capabilities().await?;
 
         // Check git repository
         if !self.git_operations.is_git_repo().await {
@@ -141,6 +194,7 @@ impl SessionOperations {
         if !self.claude_executor.check_claude_cli().await? {
             anyhow::bail!("Claude CLI is not available. Please install it first.");
         }
+        self.claude_executor.probe_capabilities().await?;
 
         // Check if this is a temporary workflow (batch/exec commands)
         let is_temp_workflow = config
@@ -167,6 +221,11 @@ impl SessionOperations {
         let mut working_dir = Arc::new(state.working_directory.clone());
         let mut worktree_name: Option<Arc<str>> =
             state.worktree_name.as_ref().map(|s| Arc::from(s.as_str()));
+        // Worktree paths are checkout roots; runs scoped to a package work below them
+        let scoped = |root: &std::path::Path| match &config.command.package {
+            Some(package) => Arc::new(root.join(package)),
+            None => Arc::new(root.to_path_buf()),
+        };
 
         // If using a worktree, verify it still exists
         if let Some(ref name) = worktree_name {
@@ -196,13 +255,13 @@ impl SessionOperations {
                 self.user_interaction
                     .display_warning(&format!("Worktree {} was deleted, recreating...", name));
                 let session = worktree_manager.create_session().await?;
-                working_dir = Arc::new(session.path.clone());
+                working_dir = scoped(&session.path);
                 worktree_name = Some(Arc::from(session.name.as_ref()));
             } else {
                 // Get the existing worktree path
                 let sessions = worktree_manager.list_sessions().await?;
                 if let Some(session) = sessions.iter().find(|s| s.name.as_str() == name.as_ref()) {
-                    working_dir = Arc::new(session.path.clone());
+                    working_dir = scoped(&session.path);
                 }
             }
         }
@@ -212,6 +271,7 @@ impl SessionOperations {
             project_dir: Arc::clone(&config.project_path),
             worktree_name,
             session_id: Arc::from(state.session_id.as_str()),
+            package: config.command.package.as_deref().map(Arc::from),
         })
     }
 }
@@ -234,6 +294,13 @@ mod tests {
             env_files: None,
             profiles: None,
             merge: None,
+            concurrency: None,
+            claude_permissions: None,
+            always: Vec::new(),
+            strict_variables: None,
+            defaults: None,
+            protected_paths: None,
+            score_gate: None,
         };
 
         let hash1 = SessionOperations::calculate_workflow_hash(&workflow);
@@ -260,6 +327,13 @@ mod tests {
             env_files: None,
             profiles: None,
             merge: None,
+            concurrency: None,
+            claude_permissions: None,
+            always: Vec::new(),
+            strict_variables: None,
+            defaults: None,
+            protected_paths: None,
+            score_gate: None,
         };
 
         let workflow2 = WorkflowConfig {
@@ -270,6 +344,13 @@ mod tests {
             env_files: None,
             profiles: None,
             merge: None,
+            concurrency: None,
+            claude_permissions: None,
+            always: Vec::new(),
+            strict_variables: None,
+            defaults: None,
+            protected_paths: None,
+            score_gate: None,
         };
 
         let hash1 = SessionOperations::calculate_workflow_hash(&workflow1);
@@ -135,7 +135,23 @@ mod tests {
                 verbosity: 0,
                 quiet: false,
                 dry_run: false,
+                read_only: false,
+                record: None,
+                junit_output: None,
+                profile_steps: false,
+                replay: None,
+                chaos: None,
+                seed: None,
+                answers_file: None,
+                environment: None,
+                no_worktree: false,
                 params: std::collections::HashMap::new(),
+                package: None,
+                changed_only: None,
+                explain_failure: false,
+                keep_going: false,
+                steps: Default::default(),
+                explicit_flags: Default::default(),
             },
             project_path: Arc::new(PathBuf::from("/test")),
             workflow: Arc::new(WorkflowConfig {
@@ -146,8 +162,23 @@ mod tests {
                 env_files: None,
                 profiles: None,
                 merge: None,
+                concurrency: None,
+                claude_permissions: None,
+                always: Vec::new(),
+                strict_variables: None,
+                defaults: None,
+                protected_paths: None,
+                score_gate: None,
             }),
             mapreduce_config: None,
+            replay: None,
+            chaos: None,
+            junit: None,
+            profiler: None,
+            protected_paths: None,
+            answers: None,
+            failures: None,
+            settings: Default::default(),
         }
     }
 
@@ -91,6 +91,7 @@ impl WorkflowExecutor {
                     started_at: chrono::Utc::now(),
                     completed_at: chrono::Utc::now(),
                     exit_code: Some(0),
+                    model: None,
                 });
 
             // Save checkpoint after successful execution
@@ -232,12 +233,18 @@ impl WorkflowExecutor {
 /// suitable for execution by the workflow executor.
 pub fn build_standard_workflow_config(
     commands: &[WorkflowCommand],
+    always: &[WorkflowCommand],
     max_iterations: u32,
+    strict_variables: bool,
 ) -> crate::cook::workflow::ExtendedWorkflowConfig {
     let steps: Vec<WorkflowStep> = commands
         .iter()
         .map(WorkflowExecutor::convert_command_to_step)
         .collect();
+    let always_steps: Vec<WorkflowStep> = always
+        .iter()
+        .map(WorkflowExecutor::convert_command_to_step)
+        .collect();
 
     crate::cook::workflow::ExtendedWorkflowConfig {
         name: "default".to_string(),
@@ -250,6 +257,8 @@ pub fn build_standard_workflow_config(
         iterate: max_iterations > 1,
         retry_defaults: None,
         environment: None,
+        always_steps,
+        strict_variables,
     }
 }
 
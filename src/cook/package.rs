@@ -0,0 +1,155 @@
+//! Monorepo package selection
+//!
+//! `prodigy run --package crates/foo` runs a workflow against one package of
+//! a monorepo. The package is a directory relative to the repository root:
+//! relative workflow paths and `.prodigy` config are looked up there first,
+//! commands and map inputs are scoped to the same subdirectory of the
+//! worktree, and the package is recorded in the session metadata.
+
+use anyhow::{anyhow, Result};
+use std::path::{Component, Path, PathBuf};
+
+/// Session metadata key holding the package a run was scoped to
+pub const PACKAGE_METADATA_KEY: &str = "package";
+
+/// Normalize a package path to a clean path relative to the repository root (pure function)
+///
+/// Absolute paths are accepted when they point inside `project_path`.
+pub fn normalize_package(project_path: &Path, package: &Path) -> Result<PathBuf> {
+    let relative = if package.is_absolute() {
+        package.strip_prefix(project_path).map_err(|_| {
+            anyhow!(
+                "Package {} is not inside the repository {}",
+                package.display(),
+                project_path.display()
+            )
+        })?
+    } else {
+        package
+    };
+
+    let mut normalized = PathBuf::new();
+    for component in relative.components() {
+        match component {
+            Component::CurDir => {}
+            Component::Normal(part) => normalized.push(part),
+            _ => {
+                return Err(anyhow!(
+                    "Package {} must be a directory inside the repository",
+                    package.display()
+                ))
+            }
+        }
+    }
+
+    if normalized.as_os_str().is_empty() {
+        return Err(anyhow!("Package path must not be the repository root"));
+    }
+    Ok(normalized)
+}
+
+/// Resolve and validate the package directory of a run
+///
+/// Returns the normalized relative package path and its absolute directory.
+pub fn resolve_package(project_path: &Path, package: &Path) -> Result<(PathBuf, PathBuf)> {
+    let relative = normalize_package(project_path, package)?;
+    let dir = project_path.join(&relative);
+    if !dir.is_dir() {
+        return Err(anyhow!("Package directory not found: {}", dir.display()));
+    }
+    Ok((relative, dir))
+}
+
+/// Resolve a relative workflow path, preferring the package directory
+///
+/// Falls back to `original_dir` when the package has no such file, so paths
+/// typed relative to the current directory keep working.
+pub fn resolve_workflow_path(
+    playbook: &Path,
+    original_dir: &Path,
+    package_dir: Option<&Path>,
+) -> PathBuf {
+    if playbook.is_absolute() {
+        return playbook.to_path_buf();
+    }
+    package_dir
+        .map(|dir| dir.join(playbook))
+        .filter(|path| path.exists())
+        .unwrap_or_else(|| original_dir.join(playbook))
+}
+
+/// Directory whose `.prodigy` config applies to a run
+///
+/// A package with its own `.prodigy` directory overrides the repository's.
+pub fn config_root<'a>(project_path: &'a Path, package_dir: Option<&'a Path>) -> &'a Path {
+    package_dir
+        .filter(|dir| dir.join(".prodigy").is_dir())
+        .unwrap_or(project_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_normalize_package() {
+        let root = Path::new("/repo");
+        assert_eq!(
+            normalize_package(root, Path::new("./crates/foo/")).unwrap(),
+            PathBuf::from("crates/foo")
+        );
+        assert_eq!(
+            normalize_package(root, Path::new("/repo/crates/foo")).unwrap(),
+            PathBuf::from("crates/foo")
+        );
+        assert!(normalize_package(root, Path::new("../other")).is_err());
+        assert!(normalize_package(root, Path::new("/elsewhere/foo")).is_err());
+        assert!(normalize_package(root, Path::new(".")).is_err());
+    }
+
+    #[test]
+    fn test_resolve_package_requires_directory() {
+        let temp = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp.path().join("crates/foo")).unwrap();
+
+        let (relative, dir) = resolve_package(temp.path(), Path::new("crates/foo")).unwrap();
+        assert_eq!(relative, PathBuf::from("crates/foo"));
+        assert_eq!(dir, temp.path().join("crates/foo"));
+        assert!(resolve_package(temp.path(), Path::new("crates/missing")).is_err());
+    }
+
+    #[test]
+    fn test_resolve_workflow_path_prefers_package() {
+        let temp = TempDir::new().unwrap();
+        let package_dir = temp.path().join("crates/foo");
+        std::fs::create_dir_all(&package_dir).unwrap();
+        std::fs::write(package_dir.join("ci.yml"), "").unwrap();
+        let original_dir = temp.path().join("elsewhere");
+
+        assert_eq!(
+            resolve_workflow_path(Path::new("ci.yml"), &original_dir, Some(&package_dir)),
+            package_dir.join("ci.yml")
+        );
+        assert_eq!(
+            resolve_workflow_path(Path::new("other.yml"), &original_dir, Some(&package_dir)),
+            original_dir.join("other.yml")
+        );
+        assert_eq!(
+            resolve_workflow_path(Path::new("/abs/ci.yml"), &original_dir, Some(&package_dir)),
+            PathBuf::from("/abs/ci.yml")
+        );
+    }
+
+    #[test]
+    fn test_config_root_uses_package_prodigy_dir() {
+        let temp = TempDir::new().unwrap();
+        let package_dir = temp.path().join("crates/foo");
+        std::fs::create_dir_all(&package_dir).unwrap();
+
+        assert_eq!(config_root(temp.path(), Some(&package_dir)), temp.path());
+        std::fs::create_dir_all(package_dir.join(".prodigy")).unwrap();
+        assert_eq!(config_root(temp.path(), Some(&package_dir)), package_dir);
+        assert_eq!(config_root(temp.path(), None), temp.path());
+    }
+}
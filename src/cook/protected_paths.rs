@@ -0,0 +1,386 @@
+//! Protected paths that automated steps may not change
+//!
+//! A workflow, or the Prodigy config, can list paths no step is allowed to
+//! touch:
+//!
+//! ```yaml
+//! protected_paths:
+//!   - migrations/**
+//!   - .github/workflows/**
+//! ```
+//!
+//! After each step, including MapReduce agent steps, the files changed since
+//! the step started are matched against the list. By default changes to
+//! protected paths are reverted, with a commit if the step committed them.
+//! `on_violation: fail` fails the step instead and leaves the changes for its
+//! `on_failure` handler:
+//!
+//! ```yaml
+//! protected_paths:
+//!   paths: [migrations/**]
+//!   on_violation: fail
+//! ```
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::path::{Path, PathBuf};
+use tokio::process::Command;
+
+/// Message of the commit that reverts committed changes to protected paths
+pub const REVERT_COMMIT_MESSAGE: &str = "Revert changes to protected paths";
+
+/// What to do when a step changes a protected path
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProtectedPathAction {
+    /// Restore the protected files to their state before the step
+    #[default]
+    Revert,
+    /// Fail the step and leave the changes in place
+    Fail,
+}
+
+/// Paths steps may not change, as glob patterns relative to the repository root
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
+pub struct ProtectedPaths {
+    pub paths: Vec<String>,
+    #[serde(default)]
+    pub on_violation: ProtectedPathAction,
+}
+
+impl<'de> Deserialize<'de> for ProtectedPaths {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum ProtectedPathsValue {
+            Paths(Vec<String>),
+            Config {
+                paths: Vec<String>,
+                #[serde(default)]
+                on_violation: ProtectedPathAction,
+            },
+        }
+
+        Ok(match ProtectedPathsValue::deserialize(deserializer)? {
+            ProtectedPathsValue::Paths(paths) => ProtectedPaths {
+                paths,
+                on_violation: ProtectedPathAction::default(),
+            },
+            ProtectedPathsValue::Config {
+                paths,
+                on_violation,
+            } => ProtectedPaths {
+                paths,
+                on_violation,
+            },
+        })
+    }
+}
+
+impl ProtectedPaths {
+    /// Combine a workflow's protected paths with those from the Prodigy config
+    ///
+    /// Returns `None` when neither protects anything.
+    pub fn resolve(workflow: Option<&ProtectedPaths>, configured: &[String]) -> Option<Self> {
+        let mut protected = workflow.cloned().unwrap_or_default();
+        for path in configured {
+            if !protected.paths.contains(path) {
+                protected.paths.push(path.clone());
+            }
+        }
+        (!protected.paths.is_empty()).then_some(protected)
+    }
+
+    /// Check that every pattern is a valid glob
+    pub fn validate(&self) -> Result<()> {
+        for path in &self.paths {
+            glob::Pattern::new(path)
+                .with_context(|| format!("Invalid protected path pattern '{}'", path))?;
+        }
+        Ok(())
+    }
+
+    /// Whether `file`, relative to the repository root, is protected
+    ///
+    /// A pattern without wildcards also protects everything below it when it
+    /// names a directory.
+    pub fn protects(&self, file: &str) -> bool {
+        let options = glob::MatchOptions {
+            require_literal_separator: true,
+            ..Default::default()
+        };
+        self.paths.iter().any(|path| {
+            let path = path.trim_end_matches('/');
+            file == path
+                || file.starts_with(&format!("{}/", path))
+                || glob::Pattern::new(path).is_ok_and(|pattern| pattern.matches_with(file, options))
+        })
+    }
+
+    /// Files among `files` that are protected (pure function)
+    pub fn matching(&self, files: &[String]) -> Vec<String> {
+        files
+            .iter()
+            .filter(|file| self.protects(file))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Protected files a step changed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// Changed files, relative to the repository root
+    pub files: Vec<String>,
+    /// Whether the changes were reverted
+    pub reverted: bool,
+}
+
+impl Violation {
+    /// Describe the violation for the step named `step_name` (pure function)
+    pub fn describe(&self, step_name: &str) -> String {
+        let action = if self.reverted {
+            "Reverted changes"
+        } else {
+            "Step failed after changes"
+        };
+        format!(
+            "{} to protected paths by '{}':\n  - {}",
+            action,
+            step_name,
+            self.files.join("\n  - ")
+        )
+    }
+}
+
+/// Check the changes made in `dir` since `before_head` against `protected`
+///
+/// Reverts the changes to protected files when the action is `revert`.
+///
+/// # Errors
+/// Returns error if git fails while inspecting or reverting the changes
+pub async fn enforce(
+    protected: &ProtectedPaths,
+    dir: &Path,
+    before_head: &str,
+) -> Result<Option<Violation>> {
+    let root = PathBuf::from(git(dir, &["rev-parse", "--show-toplevel"]).await?.trim());
+    let mut changed =
+        lines(&git(&root, &["diff", "--name-only", "--no-renames", before_head]).await?);
+    changed.extend(lines(
+        &git(&root, &["ls-files", "--others", "--exclude-standard"]).await?,
+    ));
+
+    let files = protected.matching(&changed);
+    if files.is_empty() {
+        return Ok(None);
+    }
+    if protected.on_violation == ProtectedPathAction::Fail {
+        return Ok(Some(Violation {
+            files,
+            reverted: false,
+        }));
+    }
+
+    revert(&root, before_head, &files).await?;
+    Ok(Some(Violation {
+        files,
+        reverted: true,
+    }))
+}
+
+/// Restore `files` to their state at `before_head`, committing if HEAD changed them
+async fn revert(root: &Path, before_head: &str, files: &[String]) -> Result<()> {
+    for file in files {
+        let existed = git(
+            root,
+            &["cat-file", "-e", &format!("{}:{}", before_head, file)],
+        )
+        .await
+        .is_ok();
+        if existed {
+            git(root, &["checkout", before_head, "--", file]).await?;
+        } else {
+            git(
+                root,
+                &["rm", "-q", "-f", "--cached", "--ignore-unmatch", "--", file],
+            )
+            .await?;
+            let path = root.join(file);
+            if path.exists() {
+                tokio::fs::remove_file(&path)
+                    .await
+                    .with_context(|| format!("Failed to remove {}", path.display()))?;
+            }
+        }
+    }
+
+    // Only files the step committed differ from HEAD once restored
+    let mut diff_args = vec![
+        "diff",
+        "--cached",
+        "--name-only",
+        "--no-renames",
+        "HEAD",
+        "--",
+    ];
+    diff_args.extend(files.iter().map(String::as_str));
+    let committed = lines(&git(root, &diff_args).await?);
+    if !committed.is_empty() {
+        let mut commit_args = vec!["commit", "-q", "-m", REVERT_COMMIT_MESSAGE, "--"];
+        commit_args.extend(committed.iter().map(String::as_str));
+        git(root, &commit_args).await?;
+    }
+    Ok(())
+}
+
+/// Current HEAD commit of the repository containing `dir`
+///
+/// # Errors
+/// Returns error if `dir` is not inside a git repository with commits
+pub async fn head(dir: &Path) -> Result<String> {
+    Ok(git(dir, &["rev-parse", "HEAD"]).await?.trim().to_string())
+}
+
+/// Run git in `dir`, returning stdout
+async fn git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .await
+        .map_err(|e| anyhow!("Failed to run git {}: {}", args.join(" "), e))?;
+    if !output.status.success() {
+        bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Non-empty lines of `output`
+fn lines(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command as StdCommand;
+    use tempfile::TempDir;
+
+    fn run_git(dir: &Path, args: &[&str]) -> String {
+        let output = StdCommand::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {:?} failed", args);
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    }
+
+    fn repo() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        run_git(dir.path(), &["init", "-q"]);
+        run_git(dir.path(), &["config", "user.email", "test@example.com"]);
+        run_git(dir.path(), &["config", "user.name", "Test"]);
+        std::fs::create_dir_all(dir.path().join("migrations")).unwrap();
+        std::fs::write(dir.path().join("migrations/001.sql"), "create").unwrap();
+        std::fs::write(dir.path().join("src.rs"), "fn main() {}").unwrap();
+        run_git(dir.path(), &["add", "-A"]);
+        run_git(dir.path(), &["commit", "-q", "-m", "init"]);
+        dir
+    }
+
+    #[test]
+    fn test_parse_and_match() {
+        let protected: ProtectedPaths =
+            serde_yaml::from_str("[\"migrations/**\", .github/workflows, \"*.lock\"]").unwrap();
+        assert_eq!(protected.on_violation, ProtectedPathAction::Revert);
+        assert!(protected.validate().is_ok());
+        assert!(protected.protects("migrations/001.sql"));
+        assert!(protected.protects("migrations/nested/002.sql"));
+        assert!(protected.protects(".github/workflows/ci.yml"));
+        assert!(protected.protects("Cargo.lock"));
+        assert!(!protected.protects("crates/a/Cargo.lock"));
+        assert!(!protected.protects("src/migrations.rs"));
+
+        let protected: ProtectedPaths =
+            serde_yaml::from_str("paths: [migrations]\non_violation: fail\n").unwrap();
+        assert_eq!(protected.on_violation, ProtectedPathAction::Fail);
+
+        let merged = ProtectedPaths::resolve(Some(&protected), &["secrets/**".to_string()]);
+        assert_eq!(merged.unwrap().paths, vec!["migrations", "secrets/**"]);
+        assert!(ProtectedPaths::resolve(None, &[]).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_enforce_reverts_committed_and_uncommitted_changes() {
+        let dir = repo();
+        let root = dir.path();
+        let before = run_git(root, &["rev-parse", "HEAD"]);
+
+        std::fs::write(root.join("migrations/001.sql"), "drop").unwrap();
+        std::fs::write(root.join("src.rs"), "fn main() { run() }").unwrap();
+        run_git(root, &["commit", "-q", "-am", "agent change"]);
+        std::fs::write(root.join("migrations/002.sql"), "new").unwrap();
+
+        let protected = ProtectedPaths {
+            paths: vec!["migrations/**".to_string()],
+            on_violation: ProtectedPathAction::Revert,
+        };
+        let violation = enforce(&protected, root, &before).await.unwrap().unwrap();
+        assert!(violation.reverted);
+        assert_eq!(
+            violation.files,
+            vec!["migrations/001.sql", "migrations/002.sql"]
+        );
+
+        assert_eq!(
+            std::fs::read_to_string(root.join("migrations/001.sql")).unwrap(),
+            "create"
+        );
+        assert!(!root.join("migrations/002.sql").exists());
+        assert_eq!(
+            std::fs::read_to_string(root.join("src.rs")).unwrap(),
+            "fn main() { run() }"
+        );
+        assert_eq!(
+            run_git(root, &["log", "-1", "--format=%s"]),
+            REVERT_COMMIT_MESSAGE
+        );
+        assert!(run_git(root, &["status", "--porcelain"]).is_empty());
+        assert!(enforce(&protected, root, &before).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_enforce_fail_leaves_changes() {
+        let dir = repo();
+        let root = dir.path();
+        let before = run_git(root, &["rev-parse", "HEAD"]);
+        std::fs::write(root.join("migrations/001.sql"), "drop").unwrap();
+
+        let protected = ProtectedPaths {
+            paths: vec!["migrations".to_string()],
+            on_violation: ProtectedPathAction::Fail,
+        };
+        let violation = enforce(&protected, root, &before).await.unwrap().unwrap();
+        assert!(!violation.reverted);
+        assert_eq!(
+            std::fs::read_to_string(root.join("migrations/001.sql")).unwrap(),
+            "drop"
+        );
+        assert!(violation
+            .describe("shell: migrate")
+            .contains("migrations/001.sql"));
+    }
+}
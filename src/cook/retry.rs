@@ -7,6 +7,7 @@
 //! abstraction for better testability while maintaining the existing API.
 
 use crate::abstractions::{ClaudeClient, RealClaudeClient};
+use crate::{styled_eprintln, styled_println};
 use anyhow::{Context, Result};
 use once_cell::sync::Lazy;
 use std::sync::Arc;
@@ -45,7 +46,7 @@ pub async fn execute_with_retry(
             Ok(output) => {
                 if let Some(retry_reason) = should_retry_output(&output, attempt, max_retries) {
                     if verbose {
-                        eprintln!("⚠️  Transient error detected: {retry_reason}");
+                        styled_eprintln!("⚠️  Transient error detected: {retry_reason}");
                     }
                     last_error = Some(retry_reason);
                     attempt += 1;
@@ -76,7 +77,9 @@ pub async fn execute_with_retry(
 async fn await_retry_delay(attempt: u32, description: &str, max_retries: u32, verbose: bool) {
     let delay = Duration::from_secs(2u64.pow(attempt.min(3))); // Exponential backoff, max 8s
     if verbose {
-        println!("⏳ Retrying {description} after {delay:?} (attempt {attempt}/{max_retries})");
+        styled_println!(
+            "⏳ Retrying {description} after {delay:?} (attempt {attempt}/{max_retries})"
+        );
     }
     sleep(delay).await;
 }
@@ -110,7 +113,7 @@ fn handle_command_error(
     // Other IO errors might be transient
     if attempt < max_retries {
         if verbose {
-            eprintln!("⚠️  IO error: {error}");
+            styled_eprintln!("⚠️  IO error: {error}");
         }
         return Ok(Some(error.to_string()));
     }
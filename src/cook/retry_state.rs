@@ -5,6 +5,7 @@
 
 use anyhow::{anyhow, Result};
 use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -12,6 +13,7 @@ use std::time::Duration;
 use tokio::sync::RwLock;
 use tracing::{debug, info, warn};
 
+use crate::cook::execution::determinism;
 use crate::cook::retry_v2::{BackoffStrategy, RetryConfig};
 
 /// Enhanced retry checkpoint state for comprehensive persistence
@@ -162,6 +164,8 @@ pub struct RetryStateManager {
     command_states: Arc<RwLock<HashMap<String, CommandRetryState>>>,
     /// Circuit breakers
     circuit_breakers: Arc<RwLock<HashMap<String, CircuitBreakerState>>>,
+    /// Seed of a deterministic run, for repeatable jitter
+    seed: Option<u64>,
 }
 
 impl Default for RetryStateManager {
@@ -187,9 +191,16 @@ impl RetryStateManager {
             checkpoint_state: Arc::new(RwLock::new(None)),
             command_states: Arc::new(RwLock::new(HashMap::new())),
             circuit_breakers: Arc::new(RwLock::new(HashMap::new())),
+            seed: None,
         }
     }
 
+    /// Draw jitter from the run's seed (`--seed`)
+    pub fn with_seed(mut self, seed: Option<u64>) -> Self {
+        self.seed = seed;
+        self
+    }
+
     /// Create checkpoint state for persistence
     pub async fn create_checkpoint_state(&self) -> Result<RetryCheckpointState> {
         let command_states = self.command_states.read().await;
@@ -399,7 +410,12 @@ impl RetryStateManager {
         // Apply jitter if enabled
         let delay = if backoff_state.jitter_enabled {
             let jitter_range = base_delay.as_millis() as f64 * backoff_state.jitter_factor;
-            let jitter = rand::random::<f64>() * jitter_range - (jitter_range / 2.0);
+            let mut rng = determinism::rng(
+                self.seed,
+                "retry-state-jitter",
+                base_delay.as_millis() as u64,
+            );
+            let jitter = rng.random::<f64>() * jitter_range - (jitter_range / 2.0);
             let millis = (base_delay.as_millis() as f64 + jitter).max(0.0) as u64;
             Duration::from_millis(millis)
         } else {
@@ -386,6 +386,7 @@ async fn test_checkpoint_with_retry_state_integration() {
             duration: Duration::from_secs(5),
             completed_at: Utc::now(),
             retry_state: None,
+            changes: None,
         }],
         variable_state: HashMap::new(),
         mapreduce_state: None,
@@ -398,6 +399,9 @@ async fn test_checkpoint_with_retry_state_integration() {
         error_recovery_state: None,
         retry_checkpoint_state: Some(retry_state.clone()),
         variable_checkpoint_state: None,
+        failure_chains: Vec::new(),
+        repo: None,
+        step_selection: None,
     };
 
     // Save checkpoint
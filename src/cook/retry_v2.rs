@@ -3,6 +3,7 @@
 //! This module provides comprehensive retry mechanisms with multiple backoff strategies,
 //! jitter support, circuit breakers, and fine-grained control over retry behavior.
 
+use crate::cook::execution::determinism;
 use anyhow::{anyhow, Result};
 use rand::Rng;
 use serde::{Deserialize, Serialize};
@@ -169,6 +170,7 @@ pub struct RetryExecutor {
     config: RetryConfig,
     metrics: Arc<RwLock<RetryMetrics>>,
     circuit_breaker: Option<Arc<CircuitBreaker>>,
+    seed: Option<u64>,
 }
 
 impl RetryExecutor {
@@ -178,9 +180,16 @@ impl RetryExecutor {
             config,
             metrics: Arc::new(RwLock::new(RetryMetrics::default())),
             circuit_breaker: None,
+            seed: None,
         }
     }
 
+    /// Draw jitter from the run's seed (`--seed`)
+    pub fn with_seed(mut self, seed: Option<u64>) -> Self {
+        self.seed = seed;
+        self
+    }
+
     /// Create with circuit breaker enabled
     pub fn with_circuit_breaker(mut self, threshold: u32, recovery_timeout: Duration) -> Self {
         self.circuit_breaker = Some(Arc::new(CircuitBreaker::new(threshold, recovery_timeout)));
@@ -310,7 +319,7 @@ impl RetryExecutor {
             return delay;
         }
 
-        let mut rng = rand::rng();
+        let mut rng = determinism::rng(self.seed, "retry-jitter", delay.as_nanos() as u64);
         let jitter_range = delay.as_secs_f64() * self.config.jitter_factor;
         let jitter = rng.random_range(-jitter_range / 2.0..=jitter_range / 2.0);
         Duration::from_secs_f64((delay.as_secs_f64() + jitter).max(0.0))
@@ -91,4 +91,6 @@ pub enum SessionUpdate {
     SetWorkflowType(state::WorkflowType),
     /// Update execution context
     UpdateExecutionContext(state::ExecutionContext),
+    /// Store a value in the session metadata
+    SetMetadata(String, serde_json::Value),
 }
@@ -123,6 +123,9 @@ pub struct StepResult {
     pub completed_at: DateTime<Utc>,
     /// Exit code from the command
     pub exit_code: Option<i32>,
+    /// Claude model the step ran with, if one was selected
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
 }
 
 /// Execution context for variable interpolation and outputs
@@ -1,23 +1,181 @@
 use crate::worktree::{InterruptionType, WorktreeManager};
 use anyhow::Result;
 use chrono::Utc;
-use signal_hook::consts::{SIGINT, SIGTERM};
+use signal_hook::consts::{SIGHUP, SIGINT, SIGTERM};
 use signal_hook::iterator::Signals;
-use std::sync::Arc;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
+use std::sync::{Arc, Mutex};
 use std::thread;
+use std::time::Duration;
+
+/// Exit code of a run that stopped cleanly after SIGTERM or SIGHUP and can be resumed
+///
+/// This is `EX_TEMPFAIL` from sysexits.h, distinct from the 128+signal codes
+/// used when the process is killed before the shutdown completes.
+pub const GRACEFUL_SHUTDOWN_EXIT_CODE: i32 = 75;
+
+/// How long a graceful shutdown may take before the process exits anyway
+pub const DEFAULT_SHUTDOWN_GRACE_PERIOD: Duration = Duration::from_secs(60);
+
+/// Environment variable overriding the grace period, in seconds (`0` waits indefinitely)
+pub const SHUTDOWN_GRACE_ENV: &str = "PRODIGY_SHUTDOWN_GRACE_SECS";
+
+/// Signal that requested a graceful shutdown, if any (0 = none)
+static SHUTDOWN: AtomicU8 = AtomicU8::new(0);
+
+/// Steps and agents currently running, reported when a shutdown is forced
+static IN_FLIGHT: Mutex<BTreeMap<u64, String>> = Mutex::new(BTreeMap::new());
+
+static NEXT_WORK_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Signals that stop a run gracefully instead of killing it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShutdownSignal {
+    /// SIGTERM, sent by CI runners, systemd, or a newer run in the same concurrency group
+    Terminate,
+    /// SIGHUP, sent when the controlling terminal goes away
+    Hangup,
+}
+
+impl ShutdownSignal {
+    /// Signal name for messages
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Terminate => "SIGTERM",
+            Self::Hangup => "SIGHUP",
+        }
+    }
+
+    /// Exit code when the process is stopped before the shutdown completes
+    pub fn forced_exit_code(self) -> i32 {
+        match self {
+            Self::Terminate => 143,
+            Self::Hangup => 129,
+        }
+    }
+
+    fn from_raw(raw: u8) -> Option<Self> {
+        match raw {
+            1 => Some(Self::Terminate),
+            2 => Some(Self::Hangup),
+            _ => None,
+        }
+    }
+
+    fn to_raw(self) -> u8 {
+        match self {
+            Self::Terminate => 1,
+            Self::Hangup => 2,
+        }
+    }
+}
+
+/// Signal that requested a graceful shutdown of this process, if any
+///
+/// Executors check this before starting each step or agent.
+pub fn shutdown_requested() -> Option<ShutdownSignal> {
+    ShutdownSignal::from_raw(SHUTDOWN.load(Ordering::Acquire))
+}
+
+/// Request a graceful shutdown; returns false if one was already requested
+pub fn request_shutdown(signal: ShutdownSignal) -> bool {
+    SHUTDOWN
+        .compare_exchange(0, signal.to_raw(), Ordering::AcqRel, Ordering::Acquire)
+        .is_ok()
+}
+
+/// Forget an earlier shutdown request so the next run in this process starts
+///
+/// For embedders that run several workflows in one process; the CLI exits
+/// after a shutdown instead.
+pub fn clear_shutdown_request() {
+    SHUTDOWN.store(0, Ordering::Release);
+}
+
+/// Grace period for a value of [`SHUTDOWN_GRACE_ENV`] (pure function)
+///
+/// `None` means the shutdown waits for running work however long it takes.
+pub fn parse_grace_period(value: Option<&str>) -> Option<Duration> {
+    let Some(value) = value.map(str::trim) else {
+        return Some(DEFAULT_SHUTDOWN_GRACE_PERIOD);
+    };
+    match value.parse::<u64>() {
+        Ok(0) => None,
+        Ok(secs) => Some(Duration::from_secs(secs)),
+        Err(_) => {
+            tracing::warn!(
+                "Ignoring invalid {}={:?}, using {}s",
+                SHUTDOWN_GRACE_ENV,
+                value,
+                DEFAULT_SHUTDOWN_GRACE_PERIOD.as_secs()
+            );
+            Some(DEFAULT_SHUTDOWN_GRACE_PERIOD)
+        }
+    }
+}
+
+/// Grace period configured for this process
+pub fn shutdown_grace_period() -> Option<Duration> {
+    parse_grace_period(std::env::var(SHUTDOWN_GRACE_ENV).ok().as_deref())
+}
+
+/// Running step or agent, listed as abandoned if the process is forced to exit
+pub struct InFlightWork {
+    id: u64,
+}
+
+impl Drop for InFlightWork {
+    fn drop(&mut self) {
+        lock_in_flight().remove(&self.id);
+    }
+}
+
+/// Register work that a forced shutdown would abandon until the guard drops
+pub fn track_work(description: impl Into<String>) -> InFlightWork {
+    let id = NEXT_WORK_ID.fetch_add(1, Ordering::Relaxed);
+    lock_in_flight().insert(id, description.into());
+    InFlightWork { id }
+}
+
+/// Descriptions of the work running right now, oldest first
+pub fn in_flight_work() -> Vec<String> {
+    lock_in_flight().values().cloned().collect()
+}
+
+fn lock_in_flight() -> std::sync::MutexGuard<'static, BTreeMap<u64, String>> {
+    match IN_FLIGHT.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+/// Report abandoned work and exit with the signal's conventional exit code
+fn force_exit(signal: ShutdownSignal, reason: &str) -> ! {
+    eprintln!("{}", reason);
+    for work in in_flight_work() {
+        eprintln!("  abandoned: {}", work);
+        tracing::warn!("Abandoned by forced shutdown: {}", work);
+    }
+    std::process::exit(signal.forced_exit_code());
+}
 
 /// Set up interrupt handlers for graceful shutdown
 ///
-/// This function installs signal handlers for SIGINT (Ctrl-C) and SIGTERM
-/// that will update the worktree state to mark it as interrupted before exit.
+/// SIGINT (Ctrl-C) marks the worktree state as interrupted and exits at once.
+/// SIGTERM and SIGHUP mark it interrupted and request a graceful shutdown:
+/// no new steps or agents start, running ones finish, checkpoints and events
+/// are flushed, and the process exits with [`GRACEFUL_SHUTDOWN_EXIT_CODE`].
+/// A second signal, or a shutdown exceeding the grace period (see
+/// [`shutdown_grace_period`]), exits immediately with 143 (SIGTERM) or 129
+/// (SIGHUP) after listing the steps and agents it abandoned.
 pub fn setup_interrupt_handlers(
     worktree_manager: Arc<WorktreeManager>,
     session_name: String,
 ) -> Result<()> {
-    let mut signals = Signals::new([SIGINT, SIGTERM])?;
+    let mut signals = Signals::new([SIGINT, SIGTERM, SIGHUP])?;
 
     thread::spawn(move || {
-        #[allow(clippy::never_loop)]
         for sig in signals.forever() {
             match sig {
                 SIGINT => {
@@ -28,13 +186,39 @@ pub fn setup_interrupt_handlers(
                     );
                     std::process::exit(130); // Standard exit code for SIGINT
                 }
-                SIGTERM => {
+                SIGTERM | SIGHUP => {
+                    let signal = if sig == SIGTERM {
+                        ShutdownSignal::Terminate
+                    } else {
+                        ShutdownSignal::Hangup
+                    };
+                    if !request_shutdown(signal) {
+                        force_exit(
+                            signal,
+                            &format!("\nReceived {} again, exiting now", signal.name()),
+                        );
+                    }
                     update_interrupted_state(
                         &worktree_manager,
                         &session_name,
                         InterruptionType::Termination,
                     );
-                    std::process::exit(143); // Standard exit code for SIGTERM
+                    eprintln!(
+                        "\nReceived {}, finishing running work and saving checkpoints (send again to exit now)",
+                        signal.name()
+                    );
+                    if let Some(grace_period) = shutdown_grace_period() {
+                        thread::spawn(move || {
+                            thread::sleep(grace_period);
+                            force_exit(
+                                signal,
+                                &format!(
+                                    "Graceful shutdown timed out after {}s, exiting",
+                                    grace_period.as_secs()
+                                ),
+                            );
+                        });
+                    }
                 }
                 _ => unreachable!(),
             }
@@ -138,6 +322,7 @@ mod tests {
             interruption_type: None,
             last_checkpoint: None,
             resumable: false,
+            owner: None,
         };
 
         let state_file = metadata_dir.join(format!("{session_name}.json"));
@@ -203,6 +388,17 @@ mod tests {
 
         assert_eq!(state.interruption_type, Some(InterruptionType::Termination));
     }
+
+    #[test]
+    fn test_shutdown_signal_codes() {
+        for signal in [ShutdownSignal::Terminate, ShutdownSignal::Hangup] {
+            assert_eq!(ShutdownSignal::from_raw(signal.to_raw()), Some(signal));
+            assert_ne!(signal.forced_exit_code(), GRACEFUL_SHUTDOWN_EXIT_CODE);
+        }
+        assert_eq!(ShutdownSignal::from_raw(0), None);
+        assert_eq!(ShutdownSignal::Terminate.forced_exit_code(), 143);
+        assert_eq!(ShutdownSignal::Hangup.forced_exit_code(), 129);
+    }
 }
 
 #[cfg(test)]
@@ -236,6 +432,31 @@ mod signal_tests {
             InterruptionType::UserInterrupt,
         );
     }
+
+    #[test]
+    fn test_parse_grace_period() {
+        assert_eq!(
+            parse_grace_period(None),
+            Some(DEFAULT_SHUTDOWN_GRACE_PERIOD)
+        );
+        assert_eq!(
+            parse_grace_period(Some("600")),
+            Some(Duration::from_secs(600))
+        );
+        assert_eq!(parse_grace_period(Some("0")), None);
+        assert_eq!(
+            parse_grace_period(Some("ten")),
+            Some(DEFAULT_SHUTDOWN_GRACE_PERIOD)
+        );
+    }
+
+    #[test]
+    fn test_track_work_lists_running_work_until_dropped() {
+        let step = track_work("step 2: claude /implement (test)");
+        assert!(in_flight_work().contains(&"step 2: claude /implement (test)".to_string()));
+        drop(step);
+        assert!(!in_flight_work().contains(&"step 2: claude /implement (test)".to_string()));
+    }
 }
 
 // Include the comprehensive test suite
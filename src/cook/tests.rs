@@ -37,7 +37,23 @@ mod cook_tests {
             verbosity: 0,
             quiet: false,
             dry_run: false,
+            read_only: false,
+            record: None,
+            junit_output: None,
+            profile_steps: false,
+            replay: None,
+            chaos: None,
+            seed: None,
+            answers_file: None,
+            environment: None,
+            no_worktree: false,
             params: std::collections::HashMap::new(),
+            package: None,
+            changed_only: None,
+            explain_failure: false,
+            keep_going: false,
+            steps: Default::default(),
+            explicit_flags: Default::default(),
         };
 
         // Run the command (this would require refactoring cook::run to accept injected dependencies)
@@ -0,0 +1,635 @@
+//! Event-driven triggers that start workflows
+//!
+//! A trigger pairs a rule over the global event stream with a workflow to run
+//! when an event matches it:
+//!
+//! ```text
+//! prodigy triggers add --on 'JobFailed && job.name == "nightly-debt"' --run workflows/alert.yml
+//! ```
+//!
+//! `prodigy triggers daemon` tails the repository's event files and runs the
+//! workflow of every trigger whose rule matches a new event. Rules use the
+//! work item filter syntax; a bare event name such as `JobFailed` matches
+//! events of that type. Each event is evaluated with its payload fields at the
+//! top level (`error`, `job_id`, ...) plus `job.id` and `job.name`.
+
+use crate::cook::execution::data_pipeline::FilterExpression;
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::fs;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+/// Environment variable naming the trigger that started a workflow
+pub const TRIGGER_ID_ENV: &str = "PRODIGY_TRIGGER_ID";
+
+/// Environment variable holding the event record that fired the trigger, as JSON
+pub const TRIGGER_EVENT_ENV: &str = "PRODIGY_TRIGGER_EVENT";
+
+/// Event record metadata naming the workflow that logged the event
+pub const JOB_NAME_METADATA: &str = "job_name";
+
+/// Event record metadata naming the trigger that started the logging run
+pub const TRIGGER_ID_METADATA: &str = "trigger_id";
+
+/// A rule and the workflow it starts
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Trigger {
+    pub id: String,
+    /// Rule the event must satisfy
+    pub on: String,
+    /// Workflow file to run
+    pub run: PathBuf,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Trigger {
+    /// Create a trigger, rejecting rules that do not parse
+    pub fn new(on: &str, run: PathBuf) -> Result<Self> {
+        parse_rule(on)?;
+        let id = uuid::Uuid::new_v4().simple().to_string();
+        Ok(Self {
+            id: format!("trigger-{}", &id[..8]),
+            on: on.trim().to_string(),
+            run,
+            created_at: Utc::now(),
+        })
+    }
+}
+
+/// Parse a trigger rule into a filter expression
+pub fn parse_rule(on: &str) -> Result<FilterExpression> {
+    let rule = rewrite_event_names(on.trim());
+    if rule.is_empty() {
+        return Err(anyhow!("Trigger rule is empty"));
+    }
+    FilterExpression::parse(&rule).map_err(|e| anyhow!("Invalid trigger rule '{}': {}", on, e))
+}
+
+/// Replace bare event names with `event_type` comparisons (pure function)
+///
+/// `JobFailed && job.name == "x"` becomes
+/// `event_type == 'job_failed' && job.name == "x"`. Quoted text, dotted
+/// paths and function names are left alone.
+fn rewrite_event_names(rule: &str) -> String {
+    let chars: Vec<char> = rule.chars().collect();
+    let mut output = String::with_capacity(rule.len());
+    let mut quote: Option<char> = None;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if let Some(q) = quote {
+            output.push(c);
+            if c == q {
+                quote = None;
+            }
+            i += 1;
+            continue;
+        }
+        if c == '\'' || c == '"' {
+            quote = Some(c);
+            output.push(c);
+            i += 1;
+            continue;
+        }
+        if c.is_ascii_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len()
+                && (chars[i].is_ascii_alphanumeric() || chars[i] == '_' || chars[i] == '.')
+            {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+            let next = chars[i..].iter().find(|c| !c.is_whitespace());
+            if c.is_ascii_uppercase() && !word.contains('.') && next != Some(&'(') {
+                output.push_str(&format!("event_type == '{}'", event_type_name(&word)));
+            } else {
+                output.push_str(&word);
+            }
+            continue;
+        }
+        output.push(c);
+        i += 1;
+    }
+
+    output
+}
+
+/// The serialized `event_type` of an event variant name (pure function)
+///
+/// Matches serde's `snake_case` renaming, so `DLQItemAdded` becomes
+/// `d_l_q_item_added`.
+fn event_type_name(variant: &str) -> String {
+    let mut name = String::with_capacity(variant.len() + 4);
+    for (i, c) in variant.chars().enumerate() {
+        if c.is_ascii_uppercase() && i > 0 {
+            name.push('_');
+        }
+        name.push(c.to_ascii_lowercase());
+    }
+    name
+}
+
+/// The fields a trigger rule is evaluated against (pure function)
+///
+/// `job_name` is used when the record itself does not name its workflow.
+pub fn rule_view(record: &Value, job_name: Option<&str>) -> Value {
+    let mut view = record.as_object().cloned().unwrap_or_default();
+    if let Some(payload) = record.get("event").and_then(Value::as_object) {
+        for (key, value) in payload {
+            view.entry(key.clone()).or_insert_with(|| value.clone());
+        }
+    }
+    let name = record_job_name(record).or(job_name);
+    let id = view.get("job_id").cloned().unwrap_or(Value::Null);
+    view.insert("job".to_string(), json!({ "id": id, "name": name }));
+    Value::Object(view)
+}
+
+/// The trigger that started the run which logged an event
+fn record_trigger_id(record: &Value) -> Option<&str> {
+    record
+        .get("metadata")
+        .and_then(|metadata| metadata.get(TRIGGER_ID_METADATA))
+        .and_then(Value::as_str)
+}
+
+/// Whether an event ends its job, so no further events of the job follow
+fn is_terminal_job_event(record: &Value) -> bool {
+    matches!(
+        record
+            .get("event")
+            .and_then(|event| event.get("event_type"))
+            .and_then(Value::as_str),
+        Some("job_completed" | "job_failed")
+    )
+}
+
+/// The workflow name recorded in an event's metadata
+fn record_job_name(record: &Value) -> Option<&str> {
+    record
+        .get("metadata")
+        .and_then(|metadata| metadata.get(JOB_NAME_METADATA))
+        .and_then(Value::as_str)
+}
+
+/// Triggers of a repository, stored as JSON in global storage
+pub struct TriggerStore {
+    path: PathBuf,
+}
+
+impl TriggerStore {
+    /// Store backed by `path`
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Store for the repository at `project_path`
+    pub async fn for_repo(project_path: &Path) -> Result<Self> {
+        let storage = crate::storage::GlobalStorage::new()?;
+        let repo_name = crate::storage::extract_repo_name(project_path)?;
+        let dir = storage.get_triggers_dir(&repo_name).await?;
+        Ok(Self::new(dir.join("triggers.json")))
+    }
+
+    pub fn load(&self) -> Result<Vec<Trigger>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        let content = fs::read_to_string(&self.path)
+            .with_context(|| format!("Failed to read {}", self.path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", self.path.display()))
+    }
+
+    pub fn add(&self, trigger: Trigger) -> Result<()> {
+        let mut triggers = self.load()?;
+        triggers.push(trigger);
+        self.save(&triggers)
+    }
+
+    /// Remove a trigger, returning whether it existed
+    pub fn remove(&self, id: &str) -> Result<bool> {
+        let mut triggers = self.load()?;
+        let count = triggers.len();
+        triggers.retain(|trigger| trigger.id != id);
+        if triggers.len() == count {
+            return Ok(false);
+        }
+        self.save(&triggers)?;
+        Ok(true)
+    }
+
+    fn save(&self, triggers: &[Trigger]) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let temp = self.path.with_extension("json.tmp");
+        fs::write(&temp, serde_json::to_string_pretty(triggers)?)?;
+        fs::rename(&temp, &self.path)
+            .with_context(|| format!("Failed to write {}", self.path.display()))
+    }
+}
+
+/// Reads event records appended to the `*.jsonl` files of an events directory
+///
+/// The events directory holds one subdirectory per job. Only complete lines
+/// are consumed, so a record being written is read on a later poll.
+pub struct EventTail {
+    dir: PathBuf,
+    offsets: HashMap<PathBuf, u64>,
+}
+
+impl EventTail {
+    /// Tail `dir`, skipping the events already written
+    pub fn from_end(dir: PathBuf) -> Result<Self> {
+        let mut offsets = HashMap::new();
+        for file in event_files(&dir)? {
+            let len = fs::metadata(&file)?.len();
+            offsets.insert(file, len);
+        }
+        Ok(Self { dir, offsets })
+    }
+
+    /// Records appended since the last poll, in file order
+    pub fn poll(&mut self) -> Result<Vec<Value>> {
+        let mut records = Vec::new();
+        for file in event_files(&self.dir)? {
+            let offset = self.offsets.get(&file).copied().unwrap_or(0);
+            let (lines, consumed) = read_complete_lines(&file, offset)?;
+            self.offsets.insert(file, offset + consumed);
+            records.extend(
+                lines
+                    .iter()
+                    .filter_map(|line| serde_json::from_str::<Value>(line).ok()),
+            );
+        }
+        Ok(records)
+    }
+}
+
+/// Event files under the job directories of `dir`, sorted by path
+fn event_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    if !dir.exists() {
+        return Ok(files);
+    }
+    for job_dir in fs::read_dir(dir)? {
+        let job_dir = job_dir?.path();
+        if !job_dir.is_dir() {
+            continue;
+        }
+        for entry in fs::read_dir(&job_dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "jsonl") {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Lines after `offset` that end in a newline, and the bytes they span
+fn read_complete_lines(file: &Path, offset: u64) -> Result<(Vec<String>, u64)> {
+    let mut handle = fs::File::open(file)?;
+    if handle.metadata()?.len() <= offset {
+        return Ok((Vec::new(), 0));
+    }
+    handle.seek(SeekFrom::Start(offset))?;
+    let mut buffer = Vec::new();
+    handle.read_to_end(&mut buffer)?;
+
+    let Some(end) = buffer.iter().rposition(|&b| b == b'\n') else {
+        return Ok((Vec::new(), 0));
+    };
+    let text = String::from_utf8_lossy(&buffer[..end]);
+    let lines = text
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(str::to_string)
+        .collect();
+    Ok((lines, end as u64 + 1))
+}
+
+/// Triggers whose rules match an event
+pub struct TriggerMatcher {
+    rules: Vec<(Trigger, FilterExpression)>,
+    job_names: HashMap<String, String>,
+}
+
+impl TriggerMatcher {
+    pub fn new(triggers: Vec<Trigger>) -> Self {
+        let rules = triggers
+            .into_iter()
+            .filter_map(|trigger| match parse_rule(&trigger.on) {
+                Ok(rule) => Some((trigger, rule)),
+                Err(e) => {
+                    tracing::warn!("Skipping trigger {}: {}", trigger.id, e);
+                    None
+                }
+            })
+            .collect();
+        Self {
+            rules,
+            job_names: HashMap::new(),
+        }
+    }
+
+    /// Replace the triggers, keeping the job names seen so far
+    pub fn reload(&mut self, triggers: Vec<Trigger>) {
+        let job_names = std::mem::take(&mut self.job_names);
+        *self = Self::new(triggers);
+        self.job_names = job_names;
+    }
+
+    /// Replace the triggers with those in `store`
+    ///
+    /// If the file cannot be loaded, for example while it is being rewritten,
+    /// the current triggers are kept.
+    pub fn reload_from(&mut self, store: &TriggerStore) -> Result<()> {
+        self.reload(store.load()?);
+        Ok(())
+    }
+
+    /// The triggers matching `record`
+    ///
+    /// A job's name is remembered from the first of its events that carries
+    /// one, so later events of the job can be matched on `job.name` too, and
+    /// forgotten once the job completes or fails. Events logged by triggered runs match nothing, so a workflow cannot
+    /// keep triggering itself.
+    pub fn matching(&mut self, record: &Value) -> Vec<&Trigger> {
+        let job_id = record
+            .get("event")
+            .and_then(|event| event.get("job_id"))
+            .and_then(Value::as_str);
+        if let (Some(job_id), Some(name)) = (job_id, record_job_name(record)) {
+            self.job_names
+                .entry(job_id.to_string())
+                .or_insert_with(|| name.to_string());
+        }
+        let job_name = job_id.and_then(|id| self.job_names.get(id)).cloned();
+        if let Some(job_id) = job_id.filter(|_| is_terminal_job_event(record)) {
+            self.job_names.remove(job_id);
+        }
+        if record_trigger_id(record).is_some() {
+            return Vec::new();
+        }
+        let view = rule_view(record, job_name.as_deref());
+
+        self.rules
+            .iter()
+            .filter(|(_, rule)| rule.evaluate(&view))
+            .map(|(trigger, _)| trigger)
+            .collect()
+    }
+}
+
+/// Tail the repository's event stream and run matching triggers until interrupted
+///
+/// Triggers are reloaded on every poll, so `triggers add` and `triggers rm`
+/// take effect without restarting the daemon. If the triggers file cannot be
+/// loaded, the previous triggers stay in effect. Workflows run in the
+/// background so the daemon keeps tailing events; a trigger whose previous
+/// run is still going is skipped.
+pub async fn run_daemon(project_path: &Path, interval: std::time::Duration) -> Result<()> {
+    let store = TriggerStore::for_repo(project_path).await?;
+    let storage = crate::storage::GlobalStorage::new()?;
+    let repo_name = crate::storage::extract_repo_name(project_path)?;
+    let events_dir = storage.get_events_base_dir(&repo_name).await?;
+
+    let mut tail = EventTail::from_end(events_dir.clone())?;
+    let mut matcher = TriggerMatcher::new(store.load()?);
+    let mut running: HashMap<String, TriggeredRun> = HashMap::new();
+    let mut load_failed = false;
+    println!(
+        "Watching {} with {} trigger(s) (Ctrl+C to stop)",
+        events_dir.display(),
+        matcher.rules.len()
+    );
+
+    loop {
+        tokio::time::sleep(interval).await;
+        running.retain(|_, run| !run.finished());
+        match matcher.reload_from(&store) {
+            Ok(()) => load_failed = false,
+            Err(e) => {
+                // Warn once per failure rather than on every poll
+                if !load_failed {
+                    tracing::warn!("Keeping the previous triggers: {:#}", e);
+                }
+                load_failed = true;
+            }
+        }
+        for record in tail.poll()? {
+            let fired: Vec<Trigger> = matcher.matching(&record).into_iter().cloned().collect();
+            for trigger in fired {
+                if running.contains_key(&trigger.id) {
+                    println!(
+                        "Trigger {} matched again while its workflow is running; skipping",
+                        trigger.id
+                    );
+                    continue;
+                }
+                match fire(&trigger, &record, project_path) {
+                    Ok(run) => {
+                        running.insert(trigger.id.clone(), run);
+                    }
+                    Err(e) => eprintln!("Trigger {} failed: {:#}", trigger.id, e),
+                }
+            }
+        }
+    }
+}
+
+/// A workflow started by a trigger
+struct TriggeredRun {
+    trigger_id: String,
+    workflow: PathBuf,
+    child: tokio::process::Child,
+}
+
+impl TriggeredRun {
+    /// Whether the workflow has exited, reporting a failure if it did
+    fn finished(&mut self) -> bool {
+        match self.child.try_wait() {
+            Ok(None) => false,
+            Ok(Some(status)) => {
+                if !status.success() {
+                    eprintln!(
+                        "Trigger {} failed: {} exited with {}",
+                        self.trigger_id,
+                        self.workflow.display(),
+                        status
+                    );
+                }
+                true
+            }
+            Err(e) => {
+                eprintln!("Trigger {} failed: {}", self.trigger_id, e);
+                true
+            }
+        }
+    }
+}
+
+/// Start a trigger's workflow for the event that fired it
+fn fire(trigger: &Trigger, record: &Value, project_path: &Path) -> Result<TriggeredRun> {
+    let event_type = record
+        .get("event")
+        .and_then(|event| event.get("event_type"))
+        .and_then(Value::as_str)
+        .unwrap_or("event");
+    println!(
+        "Trigger {} matched {}, running {}",
+        trigger.id,
+        event_type,
+        trigger.run.display()
+    );
+
+    let child = tokio::process::Command::new(std::env::current_exe()?)
+        .arg("run")
+        .arg(&trigger.run)
+        .arg("--path")
+        .arg(project_path)
+        .arg("--yes")
+        .env(TRIGGER_ID_ENV, &trigger.id)
+        .env(TRIGGER_EVENT_ENV, record.to_string())
+        .spawn()
+        .context("Failed to start triggered workflow")?;
+    Ok(TriggeredRun {
+        trigger_id: trigger.id.clone(),
+        workflow: trigger.run.clone(),
+        child,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn job_failed(job_id: &str, job_name: Option<&str>) -> Value {
+        let mut record = json!({
+            "id": "00000000-0000-0000-0000-000000000000",
+            "timestamp": "2024-01-01T00:00:00Z",
+            "correlation_id": "c",
+            "event": {"event_type": "job_failed", "job_id": job_id, "error": "boom", "partial_results": 0},
+        });
+        if let Some(name) = job_name {
+            record["metadata"] = json!({ JOB_NAME_METADATA: name });
+        }
+        record
+    }
+
+    #[test]
+    fn test_rewrite_event_names() {
+        assert_eq!(
+            rewrite_event_names(r#"JobFailed && job.name == "NightlyDebt""#),
+            r#"event_type == 'job_failed' && job.name == "NightlyDebt""#
+        );
+        assert_eq!(
+            rewrite_event_names("DLQItemAdded || !is_null(error)"),
+            "event_type == 'd_l_q_item_added' || !is_null(error)"
+        );
+    }
+
+    #[test]
+    fn test_rule_matches_event_type_and_job_name() {
+        let rule = parse_rule(r#"JobFailed && job.name == "nightly-debt""#).unwrap();
+
+        assert!(rule.evaluate(&rule_view(&job_failed("run-1", Some("nightly-debt")), None)));
+        assert!(!rule.evaluate(&rule_view(&job_failed("run-1", Some("other")), None)));
+        assert!(!rule.evaluate(&rule_view(&job_failed("run-1", None), None)));
+        assert!(parse_rule("   ").is_err());
+    }
+
+    #[test]
+    fn test_matcher_remembers_job_names() {
+        let trigger =
+            Trigger::new(r#"JobFailed && job.name == "nightly""#, "alert.yml".into()).unwrap();
+        let mut matcher = TriggerMatcher::new(vec![trigger]);
+
+        let mut started = job_failed("run-1", Some("nightly"));
+        started["event"]["event_type"] = json!("job_resumed");
+        assert!(matcher.matching(&started).is_empty());
+        assert_eq!(matcher.matching(&job_failed("run-1", None)).len(), 1);
+        assert!(matcher.matching(&job_failed("run-2", None)).is_empty());
+
+        // The failure ended run-1, so its name is no longer kept
+        assert!(matcher.job_names.is_empty());
+        assert!(matcher.matching(&job_failed("run-1", None)).is_empty());
+    }
+
+    #[test]
+    fn test_reload_keeps_triggers_when_file_is_malformed() {
+        let temp = TempDir::new().unwrap();
+        let store = TriggerStore::new(temp.path().join("triggers.json"));
+        store
+            .add(Trigger::new("JobFailed", "alert.yml".into()).unwrap())
+            .unwrap();
+        let mut matcher = TriggerMatcher::new(Vec::new());
+        matcher.reload_from(&store).unwrap();
+
+        // As if caught while another process writes the file
+        fs::write(temp.path().join("triggers.json"), "[{\"id\": ").unwrap();
+        assert!(matcher.reload_from(&store).is_err());
+        assert_eq!(matcher.matching(&job_failed("run-1", None)).len(), 1);
+    }
+
+    #[test]
+    fn test_events_from_triggered_runs_do_not_fire() {
+        let trigger = Trigger::new("JobFailed", "alert.yml".into()).unwrap();
+        let mut matcher = TriggerMatcher::new(vec![trigger.clone()]);
+
+        // The alert workflow fails too, logging a job_failed event of its own
+        let mut own_failure = job_failed("run-2", Some("alert"));
+        own_failure["metadata"][TRIGGER_ID_METADATA] = json!(trigger.id);
+        assert!(matcher.matching(&own_failure).is_empty());
+
+        assert_eq!(matcher.matching(&job_failed("run-3", None)).len(), 1);
+    }
+
+    #[test]
+    fn test_store_add_and_remove() {
+        let temp = TempDir::new().unwrap();
+        let store = TriggerStore::new(temp.path().join("triggers.json"));
+        let trigger = Trigger::new("JobFailed", "alert.yml".into()).unwrap();
+
+        store.add(trigger.clone()).unwrap();
+        assert_eq!(store.load().unwrap(), vec![trigger.clone()]);
+        assert!(store.remove(&trigger.id).unwrap());
+        assert!(!store.remove(&trigger.id).unwrap());
+        assert!(store.load().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_tail_reads_only_new_complete_lines() {
+        let temp = TempDir::new().unwrap();
+        let job_dir = temp.path().join("run-1");
+        fs::create_dir_all(&job_dir).unwrap();
+        let file = job_dir.join("events-1.jsonl");
+        fs::write(&file, format!("{}\n", job_failed("old", None))).unwrap();
+
+        let mut tail = EventTail::from_end(temp.path().to_path_buf()).unwrap();
+        assert!(tail.poll().unwrap().is_empty());
+
+        let new = job_failed("new", None).to_string();
+        let (complete, partial) = new.split_at(10);
+        let mut content = fs::read_to_string(&file).unwrap();
+        content.push_str(complete);
+        fs::write(&file, &content).unwrap();
+        assert!(tail.poll().unwrap().is_empty());
+
+        content.push_str(partial);
+        content.push('\n');
+        fs::write(&file, &content).unwrap();
+        let records = tail.poll().unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0]["event"]["job_id"], "new");
+    }
+}
@@ -0,0 +1,279 @@
+//! Watch mode for long-running workflow execution
+//!
+//! `prodigy run workflow.yml --watch` keeps the process alive after a run and
+//! re-runs the workflow whenever the workflow file or the Prodigy config
+//! (`~/.prodigy/config.yml` and `.prodigy/config.yml`) changes. Each change is
+//! re-validated before it is applied: an invalid edit is reported and the
+//! watcher keeps waiting, so a typo never stops the process.
+//!
+//! Every applied change gets a new config version, a short digest of the
+//! watched files, and a `config_reloaded` event is written to the watch
+//! session's event log with the previous and new versions.
+
+use super::command::CookCommand;
+use crate::cook::execution::events::MapReduceEvent;
+use anyhow::{anyhow, Context as _, Result};
+use notify::{RecursiveMode, Watcher};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// Time to wait for an editor to finish writing before reloading
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Files whose changes trigger a reload
+pub fn watched_files(playbook: &Path, project_path: &Path) -> Vec<PathBuf> {
+    vec![
+        playbook.to_path_buf(),
+        project_path.join(".prodigy").join("config.yml"),
+        crate::config::global_config_path(),
+    ]
+}
+
+/// Contents digest of each watched file at one point in time
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigSnapshot {
+    /// Per-file digest, `None` when the file does not exist
+    files: BTreeMap<PathBuf, Option<String>>,
+}
+
+impl ConfigSnapshot {
+    /// Read the current contents of `paths`
+    pub fn capture(paths: &[PathBuf]) -> Self {
+        let files = paths
+            .iter()
+            .map(|path| {
+                let digest = std::fs::read(path).ok().map(|content| hex_digest(&content));
+                (path.clone(), digest)
+            })
+            .collect();
+        Self { files }
+    }
+
+    /// Short version identifier covering every watched file
+    pub fn version(&self) -> String {
+        let mut hasher = Sha256::new();
+        for (path, digest) in &self.files {
+            hasher.update(path.to_string_lossy().as_bytes());
+            hasher.update([0]);
+            hasher.update(digest.as_deref().unwrap_or("-").as_bytes());
+            hasher.update([0]);
+        }
+        hasher.finalize()[..6]
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+
+    /// Files whose contents differ from `previous`
+    pub fn changed_files(&self, previous: &ConfigSnapshot) -> Vec<PathBuf> {
+        self.files
+            .iter()
+            .filter(|(path, digest)| previous.files.get(*path) != Some(*digest))
+            .map(|(path, _)| path.clone())
+            .collect()
+    }
+}
+
+fn hex_digest(content: &[u8]) -> String {
+    Sha256::digest(content)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Run the workflow, then re-run it whenever its workflow file or config changes
+pub async fn watch(mut cmd: CookCommand) -> Result<()> {
+    // Pin paths up front, since a run may change the current directory
+    let original_dir = std::env::current_dir()?;
+    let project_path = match &cmd.path {
+        Some(path) if path.is_absolute() => path.clone(),
+        Some(path) => original_dir.join(path),
+        None => original_dir.clone(),
+    };
+    let package_dir = cmd
+        .package
+        .as_ref()
+        .map(|package| project_path.join(package));
+    cmd.playbook =
+        super::package::resolve_workflow_path(&cmd.playbook, &original_dir, package_dir.as_deref());
+    cmd.path = Some(project_path.clone());
+
+    let files = watched_files(
+        &cmd.playbook,
+        super::package::config_root(&project_path, package_dir.as_deref()),
+    );
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let _watcher = start_watcher(&files, tx)?;
+
+    let watch_id = format!("watch-{}", chrono::Utc::now().format("%Y%m%d_%H%M%S"));
+    let event_logger =
+        match crate::storage::create_global_event_logger(&project_path, &watch_id).await {
+            Ok(logger) => Some(logger),
+            Err(e) => {
+                tracing::warn!("Failed to create event logger for {}: {}", watch_id, e);
+                None
+            }
+        };
+
+    let mut applied = ConfigSnapshot::capture(&files);
+    println!(
+        "Watching {} for changes (config version {}, Ctrl+C to stop)",
+        cmd.playbook.display(),
+        applied.version()
+    );
+
+    loop {
+        if let Err(e) = super::cook(cmd.clone()).await {
+            eprintln!("Workflow run failed: {:#}", e);
+        }
+
+        // Wait for a valid change before running again
+        let mut last_seen = applied.version();
+        loop {
+            tokio::select! {
+                changed = rx.recv() => {
+                    if changed.is_none() {
+                        return Err(anyhow!("File watcher stopped unexpectedly"));
+                    }
+                }
+                _ = tokio::signal::ctrl_c() => {
+                    println!("Stopped watching {}", cmd.playbook.display());
+                    return Ok(());
+                }
+            }
+            tokio::time::sleep(DEBOUNCE).await;
+            while rx.try_recv().is_ok() {}
+
+            let next = ConfigSnapshot::capture(&files);
+            let version = next.version();
+            if version == last_seen {
+                continue;
+            }
+            last_seen = version.clone();
+
+            if let Err(e) = validate(&cmd, &project_path).await {
+                eprintln!(
+                    "Ignoring config version {}: {:#}\nStill using config version {}",
+                    version,
+                    e,
+                    applied.version()
+                );
+                continue;
+            }
+
+            let changed_files: Vec<String> = next
+                .changed_files(&applied)
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect();
+            println!(
+                "Config changed ({}), reloading: version {} -> {}",
+                changed_files.join(", "),
+                applied.version(),
+                version
+            );
+            if let Some(logger) = &event_logger {
+                let event = MapReduceEvent::ConfigReloaded {
+                    job_id: watch_id.clone(),
+                    previous_version: applied.version(),
+                    new_version: version,
+                    changed_files,
+                };
+                if let Err(e) = logger.log(event).await {
+                    tracing::warn!("Failed to log config reload event: {}", e);
+                } else if let Err(e) = logger.flush().await {
+                    tracing::warn!("Failed to flush config reload event: {}", e);
+                }
+            }
+            applied = next;
+            break;
+        }
+    }
+}
+
+/// Check that the workflow and config files still load
+async fn validate(cmd: &CookCommand, project_path: &Path) -> Result<()> {
+    super::load_playbook_with_mapreduce(&cmd.playbook, &cmd.params, cmd.environment.as_deref())
+        .await?;
+
+    let project_config = project_path.join(".prodigy").join("config.yml");
+    let options = crate::config::LoadOptions {
+        skip_project: !project_config.exists(),
+        config_path: project_config.exists().then_some(project_config),
+        ..Default::default()
+    };
+    crate::config::load_prodigy_config_with_options(&options)
+        .map_err(|errors| anyhow!("Invalid Prodigy config: {}", errors))?;
+    Ok(())
+}
+
+/// Watch the directories containing `files`
+///
+/// Directories are watched instead of the files themselves because editors
+/// often save by replacing the file, which would end a watch on the file.
+fn start_watcher(
+    files: &[PathBuf],
+    tx: mpsc::UnboundedSender<()>,
+) -> Result<notify::RecommendedWatcher> {
+    let targets: HashSet<PathBuf> = files.iter().cloned().collect();
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+        if let Ok(event) = res {
+            if event.paths.iter().any(|path| targets.contains(path)) {
+                let _ = tx.send(());
+            }
+        }
+    })?;
+
+    let mut dirs: Vec<&Path> = files.iter().filter_map(|file| file.parent()).collect();
+    dirs.sort();
+    dirs.dedup();
+    for dir in dirs {
+        if dir.is_dir() {
+            watcher
+                .watch(dir, RecursiveMode::NonRecursive)
+                .with_context(|| format!("Failed to watch {}", dir.display()))?;
+        }
+    }
+    Ok(watcher)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_snapshot_version_tracks_contents() {
+        let temp_dir = TempDir::new().unwrap();
+        let workflow = temp_dir.path().join("workflow.yml");
+        let config = temp_dir.path().join("config.yml");
+        let files = vec![workflow.clone(), config.clone()];
+        std::fs::write(&workflow, "commands: []\n").unwrap();
+
+        let first = ConfigSnapshot::capture(&files);
+        assert_eq!(first.version(), ConfigSnapshot::capture(&files).version());
+        assert_eq!(first.version().len(), 12);
+
+        std::fs::write(&config, "log_level: debug\n").unwrap();
+        let second = ConfigSnapshot::capture(&files);
+        assert_ne!(first.version(), second.version());
+        assert_eq!(second.changed_files(&first), vec![config.clone()]);
+
+        std::fs::write(&workflow, "commands:\n  - shell: echo hi\n").unwrap();
+        std::fs::remove_file(&config).unwrap();
+        let third = ConfigSnapshot::capture(&files);
+        assert_eq!(third.changed_files(&second), vec![config, workflow]);
+        assert_eq!(third.changed_files(&third), Vec::<PathBuf>::new());
+    }
+
+    #[test]
+    fn test_watched_files_include_configs() {
+        let files = watched_files(Path::new("/repo/workflow.yml"), Path::new("/repo"));
+        assert_eq!(files[0], PathBuf::from("/repo/workflow.yml"));
+        assert_eq!(files[1], PathBuf::from("/repo/.prodigy/config.yml"));
+        assert_eq!(files[2], crate::config::global_config_path());
+    }
+}
@@ -0,0 +1,494 @@
+//! `assert:` steps
+//!
+//! An assert step checks the state a workflow should have left behind and
+//! fails the workflow, listing every failed check, when it does not hold:
+//!
+//! ```yaml
+//! - assert:
+//!     no_uncommitted_changes: true
+//!     commits_since: "origin/main >= 1"
+//!     file_exists: [target/report.json]
+//!     json_field:
+//!       file: target/report.json
+//!       field: summary.failed
+//!       equals: 0
+//!     message: "Implementation did not finish"
+//! ```
+//!
+//! `no_uncommitted_changes` ignores untracked files; `branch_clean` also
+//! requires there to be none. `commits_since` counts commits on HEAD that are
+//! not on the ref and compares them with `>=`, `>`, `==`, `!=`, `<=` or `<`
+//! (a bare ref means `>= 1`). Paths are relative to the working directory and
+//! all strings support variable interpolation.
+
+use crate::abstractions::git::GitOperations;
+use crate::cook::execution::data_pipeline::FilterExpression;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+use std::path::Path;
+
+/// Uncommitted files listed in a failure message before the rest are counted
+const MAX_LISTED_FILES: usize = 10;
+
+/// Checks made by an `assert:` step
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AssertConfig {
+    /// Tracked files have no staged or unstaged changes
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub no_uncommitted_changes: bool,
+
+    /// No uncommitted changes and no untracked files
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub branch_clean: bool,
+
+    /// Commit count since a ref, e.g. `origin/main >= 1`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commits_since: Option<String>,
+
+    /// Files or directories that must exist
+    #[serde(
+        default,
+        deserialize_with = "one_or_many",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub file_exists: Vec<String>,
+
+    /// JSON fields that must hold a value
+    #[serde(
+        default,
+        deserialize_with = "one_or_many",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub json_field: Vec<JsonFieldAssertion>,
+
+    /// Explanation shown above the failed checks
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// A JSON file field that must equal a value
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct JsonFieldAssertion {
+    /// JSON file, relative to the working directory
+    pub file: String,
+    /// Field path such as `summary.failed` or `results[0].status`
+    pub field: String,
+    /// Expected value
+    pub equals: Value,
+}
+
+fn one_or_many<'de, D, T>(deserializer: D) -> std::result::Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany<T> {
+        One(T),
+        Many(Vec<T>),
+    }
+
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(value) => vec![value],
+        OneOrMany::Many(values) => values,
+    })
+}
+
+/// Comparison used by `commits_since`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CountComparison {
+    AtLeast,
+    MoreThan,
+    Exactly,
+    Not,
+    AtMost,
+    LessThan,
+}
+
+impl CountComparison {
+    fn holds(self, actual: u64, expected: u64) -> bool {
+        match self {
+            CountComparison::AtLeast => actual >= expected,
+            CountComparison::MoreThan => actual > expected,
+            CountComparison::Exactly => actual == expected,
+            CountComparison::Not => actual != expected,
+            CountComparison::AtMost => actual <= expected,
+            CountComparison::LessThan => actual < expected,
+        }
+    }
+
+    fn symbol(self) -> &'static str {
+        match self {
+            CountComparison::AtLeast => ">=",
+            CountComparison::MoreThan => ">",
+            CountComparison::Exactly => "==",
+            CountComparison::Not => "!=",
+            CountComparison::AtMost => "<=",
+            CountComparison::LessThan => "<",
+        }
+    }
+}
+
+/// A parsed `commits_since` expression
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitsSince {
+    pub reference: String,
+    pub comparison: CountComparison,
+    pub count: u64,
+}
+
+impl CommitsSince {
+    /// Parse `<ref> <op> <count>`, or a bare `<ref>` meaning `>= 1` (pure function)
+    pub fn parse(expr: &str) -> Result<Self> {
+        let parts: Vec<&str> = expr.split_whitespace().collect();
+        let (reference, comparison, count) = match parts.as_slice() {
+            [reference] => (*reference, CountComparison::AtLeast, 1),
+            [reference, op, count] => {
+                let comparison = match *op {
+                    ">=" => CountComparison::AtLeast,
+                    ">" => CountComparison::MoreThan,
+                    "==" | "=" => CountComparison::Exactly,
+                    "!=" => CountComparison::Not,
+                    "<=" => CountComparison::AtMost,
+                    "<" => CountComparison::LessThan,
+                    _ => return Err(anyhow!("Unknown comparison '{}' in commits_since", op)),
+                };
+                let count = count
+                    .parse()
+                    .map_err(|_| anyhow!("Invalid commit count '{}' in commits_since", count))?;
+                (*reference, comparison, count)
+            }
+            _ => {
+                return Err(anyhow!(
+                    "commits_since must be '<ref> <op> <count>', e.g. 'origin/main >= 1', got '{}'",
+                    expr
+                ))
+            }
+        };
+        Ok(Self {
+            reference: reference.to_string(),
+            comparison,
+            count,
+        })
+    }
+}
+
+impl AssertConfig {
+    /// Number of checks configured (pure function)
+    pub fn check_count(&self) -> usize {
+        usize::from(self.no_uncommitted_changes)
+            + usize::from(self.branch_clean)
+            + usize::from(self.commits_since.is_some())
+            + self.file_exists.len()
+            + self.json_field.len()
+    }
+
+    /// Copy with every string passed through `interpolate`
+    pub fn interpolated(&self, mut interpolate: impl FnMut(&str) -> String) -> Self {
+        let json_field = self
+            .json_field
+            .iter()
+            .map(|check| JsonFieldAssertion {
+                file: interpolate(&check.file),
+                field: interpolate(&check.field),
+                equals: match &check.equals {
+                    Value::String(s) => Value::String(interpolate(s)),
+                    other => other.clone(),
+                },
+            })
+            .collect();
+        Self {
+            no_uncommitted_changes: self.no_uncommitted_changes,
+            branch_clean: self.branch_clean,
+            commits_since: self.commits_since.as_deref().map(&mut interpolate),
+            file_exists: self.file_exists.iter().map(|f| interpolate(f)).collect(),
+            json_field,
+            message: self.message.as_deref().map(&mut interpolate),
+        }
+    }
+}
+
+/// Whether two JSON values are equal, comparing numbers by value (pure function)
+fn json_equals(actual: &Value, expected: &Value) -> bool {
+    match (actual.as_f64(), expected.as_f64()) {
+        (Some(a), Some(b)) if actual.is_number() && expected.is_number() => a == b,
+        _ => actual == expected,
+    }
+}
+
+/// Check a JSON field assertion against a parsed document (pure function)
+pub fn check_json_field(document: &Value, check: &JsonFieldAssertion) -> Option<String> {
+    match FilterExpression::get_nested_field_with_array(document, &check.field) {
+        Some(actual) if json_equals(&actual, &check.equals) => None,
+        Some(actual) => Some(format!(
+            "json_field: {} in {} is {}, expected {}",
+            check.field, check.file, actual, check.equals
+        )),
+        None => Some(format!(
+            "json_field: {} not found in {}",
+            check.field, check.file
+        )),
+    }
+}
+
+/// Describe uncommitted files from `git status --porcelain` output (pure function)
+fn describe_status(status: &str) -> String {
+    let lines: Vec<&str> = status.lines().filter(|l| !l.trim().is_empty()).collect();
+    let mut listed = lines
+        .iter()
+        .take(MAX_LISTED_FILES)
+        .map(|l| l.trim())
+        .collect::<Vec<_>>()
+        .join(", ");
+    if lines.len() > MAX_LISTED_FILES {
+        listed.push_str(&format!(" and {} more", lines.len() - MAX_LISTED_FILES));
+    }
+    format!(
+        "{} file{} changed ({})",
+        lines.len(),
+        if lines.len() == 1 { "" } else { "s" },
+        listed
+    )
+}
+
+async fn git_output(git: &dyn GitOperations, args: &[&str], dir: &Path) -> Result<String> {
+    let output = git.git_command_in_dir(args, args[0], dir).await?;
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Run every check, returning one message per failed check
+///
+/// Errors are reserved for configuration problems, such as an invalid
+/// `commits_since` expression.
+pub async fn run_assertions(
+    config: &AssertConfig,
+    working_dir: &Path,
+    git: &dyn GitOperations,
+) -> Result<Vec<String>> {
+    let commits_since = config
+        .commits_since
+        .as_deref()
+        .map(CommitsSince::parse)
+        .transpose()?;
+    let mut failures = Vec::new();
+
+    if config.no_uncommitted_changes {
+        match git_output(
+            git,
+            &["status", "--porcelain", "--untracked-files=no"],
+            working_dir,
+        )
+        .await
+        {
+            Ok(status) if status.trim().is_empty() => {}
+            Ok(status) => failures.push(format!(
+                "no_uncommitted_changes: {}",
+                describe_status(&status)
+            )),
+            Err(e) => failures.push(format!("no_uncommitted_changes: {}", e)),
+        }
+    }
+
+    if config.branch_clean {
+        match git_output(git, &["status", "--porcelain"], working_dir).await {
+            Ok(status) if status.trim().is_empty() => {}
+            Ok(status) => failures.push(format!("branch_clean: {}", describe_status(&status))),
+            Err(e) => failures.push(format!("branch_clean: {}", e)),
+        }
+    }
+
+    if let Some(expected) = commits_since {
+        let range = format!("{}..HEAD", expected.reference);
+        match git_output(git, &["rev-list", "--count", &range], working_dir).await {
+            Ok(count) => match count.trim().parse::<u64>() {
+                Ok(count) if expected.comparison.holds(count, expected.count) => {}
+                Ok(count) => failures.push(format!(
+                    "commits_since: {} commit{} since {}, expected {} {}",
+                    count,
+                    if count == 1 { "" } else { "s" },
+                    expected.reference,
+                    expected.comparison.symbol(),
+                    expected.count
+                )),
+                Err(_) => failures.push(format!(
+                    "commits_since: unexpected git rev-list output '{}'",
+                    count.trim()
+                )),
+            },
+            Err(e) => failures.push(format!("commits_since: {}", e)),
+        }
+    }
+
+    for file in &config.file_exists {
+        if !working_dir.join(file).exists() {
+            failures.push(format!("file_exists: {} does not exist", file));
+        }
+    }
+
+    for check in &config.json_field {
+        let path = working_dir.join(&check.file);
+        let document = tokio::fs::read_to_string(&path)
+            .await
+            .map_err(|e| format!("cannot read {}: {}", check.file, e))
+            .and_then(|content| {
+                serde_json::from_str::<Value>(&content)
+                    .map_err(|e| format!("{} is not valid JSON: {}", check.file, e))
+            });
+        match document {
+            Ok(document) => failures.extend(check_json_field(&document, check)),
+            Err(e) => failures.push(format!("json_field: {}", e)),
+        }
+    }
+
+    Ok(failures)
+}
+
+/// Failure report for an assert step (pure function)
+pub fn format_failures(config: &AssertConfig, failures: &[String]) -> String {
+    let mut report = format!(
+        "Assertion failed: {} of {} check{} failed",
+        failures.len(),
+        config.check_count(),
+        if config.check_count() == 1 { "" } else { "s" }
+    );
+    if let Some(message) = &config.message {
+        report = format!("{}\n{}", message, report);
+    }
+    for failure in failures {
+        report.push_str(&format!("\n  - {}", failure));
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::abstractions::git::MockGitOperations;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_assert_config() {
+        let yaml = r#"
+no_uncommitted_changes: true
+commits_since: "origin/main >= 1"
+file_exists: Cargo.lock
+json_field:
+  - file: report.json
+    field: summary.failed
+    equals: 0
+"#;
+        let config: AssertConfig = serde_yaml::from_str(yaml).unwrap();
+        assert!(config.no_uncommitted_changes);
+        assert!(!config.branch_clean);
+        assert_eq!(config.file_exists, vec!["Cargo.lock"]);
+        assert_eq!(config.json_field[0].equals, json!(0));
+        assert_eq!(config.check_count(), 4);
+
+        assert!(serde_yaml::from_str::<AssertConfig>("no_uncommited_changes: true").is_err());
+    }
+
+    #[test]
+    fn test_parse_commits_since() {
+        assert_eq!(
+            CommitsSince::parse("origin/main >= 2").unwrap(),
+            CommitsSince {
+                reference: "origin/main".to_string(),
+                comparison: CountComparison::AtLeast,
+                count: 2,
+            }
+        );
+        let bare = CommitsSince::parse("HEAD~3").unwrap();
+        assert_eq!(bare.comparison, CountComparison::AtLeast);
+        assert_eq!(bare.count, 1);
+        assert!(CommitsSince::parse("main ~ 1").is_err());
+        assert!(CommitsSince::parse("main >= many").is_err());
+        assert!(CommitsSince::parse("").is_err());
+    }
+
+    #[test]
+    fn test_check_json_field() {
+        let document = json!({"summary": {"failed": 0.0, "status": "ok"}, "items": [{"id": 7}]});
+        let check = |field: &str, equals: Value| JsonFieldAssertion {
+            file: "report.json".to_string(),
+            field: field.to_string(),
+            equals,
+        };
+        assert_eq!(
+            check_json_field(&document, &check("summary.failed", json!(0))),
+            None
+        );
+        assert_eq!(
+            check_json_field(&document, &check("items[0].id", json!(7))),
+            None
+        );
+        assert_eq!(
+            check_json_field(&document, &check("summary.status", json!("failed"))).unwrap(),
+            "json_field: summary.status in report.json is \"ok\", expected \"failed\""
+        );
+        assert_eq!(
+            check_json_field(&document, &check("summary.missing", json!(1))).unwrap(),
+            "json_field: summary.missing not found in report.json"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_assertions_reports_every_failure() {
+        let temp = TempDir::new().unwrap();
+        std::fs::write(temp.path().join("report.json"), r#"{"failed": 2}"#).unwrap();
+        let git = MockGitOperations::new();
+        git.add_success_response(" M src/lib.rs\n").await;
+        git.add_success_response("0\n").await;
+
+        let config = AssertConfig {
+            no_uncommitted_changes: true,
+            commits_since: Some("origin/main".to_string()),
+            file_exists: vec!["report.json".to_string(), "missing.txt".to_string()],
+            json_field: vec![JsonFieldAssertion {
+                file: "report.json".to_string(),
+                field: "failed".to_string(),
+                equals: json!(0),
+            }],
+            ..Default::default()
+        };
+        let failures = run_assertions(&config, temp.path(), &git).await.unwrap();
+        assert_eq!(
+            failures,
+            vec![
+                "no_uncommitted_changes: 1 file changed (M src/lib.rs)",
+                "commits_since: 0 commits since origin/main, expected >= 1",
+                "file_exists: missing.txt does not exist",
+                "json_field: failed in report.json is 2, expected 0",
+            ]
+        );
+        assert_eq!(
+            git.get_called_commands().await,
+            vec![
+                vec!["status", "--porcelain", "--untracked-files=no"],
+                vec!["rev-list", "--count", "origin/main..HEAD"],
+            ]
+        );
+
+        let report = format_failures(&config, &failures);
+        assert!(report.starts_with("Assertion failed: 4 of 5 checks failed\n  - "));
+    }
+
+    #[tokio::test]
+    async fn test_run_assertions_passes_clean_state() {
+        let git = MockGitOperations::new();
+        git.add_success_response("").await;
+        git.add_success_response("3\n").await;
+
+        let config = AssertConfig {
+            branch_clean: true,
+            commits_since: Some("main > 2".to_string()),
+            ..Default::default()
+        };
+        let failures = run_assertions(&config, Path::new("."), &git).await.unwrap();
+        assert!(failures.is_empty());
+    }
+}
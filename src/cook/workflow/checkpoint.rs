@@ -5,7 +5,9 @@
 use crate::cook::workflow::checkpoint_errors::CheckpointError;
 use crate::cook::workflow::checkpoint_path::CheckpointStorage;
 use crate::cook::workflow::executor::WorkflowContext;
+use crate::cook::workflow::git_context::StepChanges;
 use crate::cook::workflow::normalized::NormalizedWorkflow;
+use crate::cook::workflow::relocate::RepoIdentity;
 use crate::cook::workflow::variable_checkpoint::VariableCheckpointState;
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
@@ -55,6 +57,15 @@ pub struct WorkflowCheckpoint {
     pub retry_checkpoint_state: Option<crate::cook::retry_state::RetryCheckpointState>,
     /// Enhanced variable checkpoint state for comprehensive variable persistence
     pub variable_checkpoint_state: Option<VariableCheckpointState>,
+    /// `on_failure` chains in flight, outermost first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub failure_chains: Vec<FailureChainState>,
+    /// Checkout the session runs from, for `prodigy resume --relocate`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repo: Option<RepoIdentity>,
+    /// Steps selected with `--only-steps`/`--skip-steps`, skipped again on resume
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub step_selection: Option<crate::cook::workflow::step_selection::StepSelection>,
 }
 
 /// Current state of workflow execution
@@ -111,6 +122,9 @@ pub struct CompletedStep {
     /// Retry state if this step is being retried
     #[serde(skip_serializing_if = "Option::is_none")]
     pub retry_state: Option<RetryState>,
+    /// Git changes made by this step
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub changes: Option<StepChanges>,
 }
 
 /// State of a step being retried
@@ -126,6 +140,20 @@ pub struct RetryState {
     pub in_retry_loop: bool,
 }
 
+/// Progress of an `on_failure` handler chain
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FailureChainState {
+    /// Display name of the step whose failure is being handled
+    pub step: String,
+    /// Index of the handler running (or about to run)
+    pub handler_index: usize,
+    /// Attempts used so far against `max_total_attempts`
+    pub attempts: u32,
+    /// How the chain ended, `None` while it is still running
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub outcome: Option<crate::cook::workflow::on_failure::FailureOutcome>,
+}
+
 /// MapReduce job checkpoint state
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MapReduceCheckpoint {
@@ -214,6 +242,8 @@ pub struct CheckpointManager {
     checkpoint_interval: Duration,
     /// Whether checkpointing is enabled (immutable)
     enabled: bool,
+    /// Checkout recorded in saved checkpoints (immutable)
+    repo: Option<RepoIdentity>,
 }
 
 impl CheckpointManager {
@@ -229,6 +259,7 @@ impl CheckpointManager {
             storage,
             checkpoint_interval: DEFAULT_CHECKPOINT_INTERVAL,
             enabled: true,
+            repo: None,
         }
     }
 
@@ -263,6 +294,15 @@ impl CheckpointManager {
         self
     }
 
+    /// Record the checkout the session runs from in saved checkpoints (builder pattern)
+    ///
+    /// Checkpoints keep the workflow path relative to the checkout so
+    /// `prodigy resume --relocate` can move the session to another clone.
+    pub fn with_repo(mut self, repo: Option<RepoIdentity>) -> Self {
+        self.repo = repo;
+        self
+    }
+
     /// Create a new checkpoint manager (deprecated - use with_storage)
     ///
     /// This constructor is maintained for backwards compatibility but is deprecated.
@@ -296,6 +336,7 @@ impl CheckpointManager {
             storage: CheckpointStorage::Local(storage_path),
             checkpoint_interval: DEFAULT_CHECKPOINT_INTERVAL,
             enabled: true,
+            repo: None,
         }
     }
 
@@ -345,6 +386,18 @@ impl CheckpointManager {
             .context("Failed to resolve checkpoint path")?;
         let temp_path = checkpoint_path.with_extension("tmp");
 
+        // Pure: record the checkout and the workflow path relative to it
+        let recorded;
+        let checkpoint = match (&self.repo, &checkpoint.repo) {
+            (Some(repo), None) => {
+                let mut with_repo = checkpoint.clone();
+                with_repo.repo = Some(repo.with_workflow(checkpoint.workflow_path.as_deref()));
+                recorded = with_repo;
+                &recorded
+            }
+            _ => checkpoint,
+        };
+
         // I/O: ensure directory exists
         ensure_checkpoint_dir_exists(&checkpoint_path).await?;
 
@@ -605,6 +658,9 @@ pub fn create_checkpoint_with_total_steps(
         error_recovery_state: None,   // Will be set if error handlers are present
         retry_checkpoint_state: None, // Will be set by the executor if retry state exists
         variable_checkpoint_state,
+        failure_chains: Vec::new(),
+        repo: None,
+        step_selection: None,
     }
 }
 
@@ -0,0 +1,140 @@
+//! Intermediate checkpoint marks written from inside shell steps
+//!
+//! Long-running scripts call `prodigy checkpoint-mark --label "..."` to record
+//! progress in the active session. Marks are appended to a JSON Lines file next
+//! to the session's workflow checkpoints and are shown by
+//! `prodigy checkpoints show`. They survive resume, so a script can skip
+//! sub-phases it already finished by testing for a mark with
+//! `prodigy checkpoint-mark --check --label "..."`.
+
+use super::checkpoint_path::CheckpointStorage;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tokio::io::AsyncWriteExt;
+
+/// Environment variable carrying the active session ID into workflow steps
+pub use crate::subprocess::metadata::SESSION_ID_ENV;
+
+const MARKS_FILE: &str = "marks.jsonl";
+
+/// A progress mark recorded by a running step
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CheckpointMark {
+    pub label: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Location of the marks file for a session
+pub fn marks_file_path(session_id: &str) -> Result<PathBuf> {
+    let storage = CheckpointStorage::Session {
+        session_id: session_id.to_string(),
+    };
+    Ok(storage.resolve_base_dir()?.join(MARKS_FILE))
+}
+
+/// Append a mark to the marks file
+///
+/// Each mark is written as a single line so concurrent writers cannot
+/// interleave partial records.
+pub async fn append_mark(path: &Path, label: &str) -> Result<CheckpointMark> {
+    let mark = CheckpointMark {
+        label: label.to_string(),
+        created_at: Utc::now(),
+    };
+
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+
+    let mut line = serde_json::to_string(&mark)?;
+    line.push('\n');
+
+    let mut file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .await
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    file.write_all(line.as_bytes()).await?;
+    file.flush().await?;
+
+    Ok(mark)
+}
+
+/// Load all marks in the order they were recorded
+///
+/// A missing file means no marks; unreadable lines are skipped.
+pub async fn load_marks(path: &Path) -> Result<Vec<CheckpointMark>> {
+    let content = match tokio::fs::read_to_string(path).await {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => {
+            return Err(e).with_context(|| format!("Failed to read {}", path.display()));
+        }
+    };
+
+    Ok(parse_marks(&content))
+}
+
+/// Parse marks from JSON Lines content (pure function)
+fn parse_marks(content: &str) -> Vec<CheckpointMark> {
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| match serde_json::from_str(line) {
+            Ok(mark) => Some(mark),
+            Err(e) => {
+                tracing::warn!("Skipping malformed checkpoint mark: {}", e);
+                None
+            }
+        })
+        .collect()
+}
+
+/// Whether a mark with `label` has been recorded
+pub fn is_marked(marks: &[CheckpointMark], label: &str) -> bool {
+    marks.iter().any(|mark| mark.label == label)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_append_and_load_marks() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("checkpoints").join(MARKS_FILE);
+
+        assert!(load_marks(&path).await.unwrap().is_empty());
+
+        append_mark(&path, "migrated 50%").await.unwrap();
+        append_mark(&path, "migrated 100%").await.unwrap();
+
+        let marks = load_marks(&path).await.unwrap();
+        let labels: Vec<&str> = marks.iter().map(|m| m.label.as_str()).collect();
+        assert_eq!(labels, vec!["migrated 50%", "migrated 100%"]);
+        assert!(is_marked(&marks, "migrated 50%"));
+        assert!(!is_marked(&marks, "migrated 75%"));
+    }
+
+    #[test]
+    fn test_parse_marks_skips_malformed_lines() {
+        let content = "{\"label\":\"a\",\"created_at\":\"2026-01-01T00:00:00Z\"}\nnot json\n\n";
+        let marks = parse_marks(content);
+        assert_eq!(marks.len(), 1);
+        assert_eq!(marks[0].label, "a");
+    }
+
+    #[test]
+    fn test_marks_file_is_in_session_checkpoint_dir() {
+        let path = marks_file_path("session-123").unwrap();
+        assert!(path
+            .to_string_lossy()
+            .ends_with("state/session-123/checkpoints/marks.jsonl"));
+    }
+}
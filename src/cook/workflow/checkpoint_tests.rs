@@ -35,6 +35,7 @@ mod tests {
                     outputs: None,
                     commit_required: false,
                     when: None,
+                    commit_policy: None,
                 },
                 NormalizedStep {
                     id: Arc::from("step-2"),
@@ -47,6 +48,7 @@ mod tests {
                     outputs: None,
                     commit_required: false,
                     when: None,
+                    commit_policy: None,
                 },
                 NormalizedStep {
                     id: Arc::from("step-3"),
@@ -59,6 +61,7 @@ mod tests {
                     outputs: None,
                     commit_required: false,
                     when: None,
+                    commit_policy: None,
                 },
             ]),
             execution_mode: crate::cook::workflow::normalized::ExecutionMode::Sequential,
@@ -89,6 +92,7 @@ mod tests {
                 duration: std::time::Duration::from_secs(1),
                 completed_at: chrono::Utc::now(),
                 retry_state: None,
+                changes: None,
             }],
             1, // Current step
             "workflow_hash_123".to_string(),
@@ -226,6 +230,7 @@ mod tests {
             duration: std::time::Duration::from_secs(1),
             completed_at: chrono::Utc::now(),
             retry_state: None,
+            changes: None,
         }];
 
         let checkpoint = create_checkpoint(
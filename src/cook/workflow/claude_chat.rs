@@ -0,0 +1,110 @@
+//! `claude_chat:` steps
+//!
+//! A claude_chat step sends several messages to Claude within one
+//! conversation, so related prompts keep the context of the earlier ones
+//! instead of starting from scratch:
+//!
+//! ```yaml
+//! - claude_chat:
+//!     variable: review
+//!     messages:
+//!       - "/prodigy-review-module src/parser.rs"
+//!       - "Fix the most important issue you found and commit the fix"
+//!       - "Describe the fix in one sentence"
+//! ```
+//!
+//! Every message after the first resumes the session the previous reply
+//! reported, which requires Claude's JSON streaming. Replies are stored as
+//! `${review.1}`, `${review.2}`, ... as they arrive, so later messages can
+//! reference earlier replies; `${review}` holds the last reply and
+//! `${review.session_id}` the conversation's session. The step stops at the
+//! first failing message.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Variable the replies are stored under unless configured otherwise
+pub const DEFAULT_CHAT_VARIABLE: &str = "claude_chat";
+
+/// Configuration of a `claude_chat:` step
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ClaudeChatConfig {
+    /// Messages sent in order, each a Claude command or prompt
+    pub messages: Vec<String>,
+
+    /// Variable the replies are stored under, defaults to `claude_chat`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub variable: Option<String>,
+}
+
+impl ClaudeChatConfig {
+    /// Variable the replies are stored under
+    pub fn variable_name(&self) -> &str {
+        self.variable.as_deref().unwrap_or(DEFAULT_CHAT_VARIABLE)
+    }
+}
+
+/// Variable holding the reply to the message at `index` (pure function)
+///
+/// Messages are numbered from 1 in variable names.
+pub fn reply_variable(variable: &str, index: usize) -> String {
+    format!("{}.{}", variable, index + 1)
+}
+
+/// Text of Claude's reply in a command's output (pure function)
+///
+/// Streaming output carries the reply in its final `result` event; print
+/// mode output is the reply itself.
+pub fn reply_text(stdout: &str) -> String {
+    stdout
+        .lines()
+        .rev()
+        .filter_map(|line| serde_json::from_str::<Value>(line.trim()).ok())
+        .find(|event| event.get("type").and_then(Value::as_str) == Some("result"))
+        .and_then(|event| {
+            event
+                .get("result")
+                .and_then(Value::as_str)
+                .map(String::from)
+        })
+        .unwrap_or_else(|| stdout.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_claude_chat_config() {
+        let yaml = r#"
+variable: review
+messages:
+  - "/prodigy-review src/lib.rs"
+  - "Fix what you found"
+"#;
+        let config: ClaudeChatConfig = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(config.messages.len(), 2);
+        assert_eq!(config.variable_name(), "review");
+        assert_eq!(reply_variable(config.variable_name(), 0), "review.1");
+
+        let config: ClaudeChatConfig = serde_yaml::from_str("messages: [hi]").unwrap();
+        assert_eq!(config.variable_name(), DEFAULT_CHAT_VARIABLE);
+    }
+
+    #[test]
+    fn test_reply_text_from_stream() {
+        let output = [
+            r#"{"type":"system","subtype":"init","session_id":"abc"}"#,
+            r#"{"type":"assistant","message":{"content":[]}}"#,
+            r#"{"type":"result","subtype":"success","result":"Found 2 issues","session_id":"abc"}"#,
+        ]
+        .join("\n");
+        assert_eq!(reply_text(&output), "Found 2 issues");
+    }
+
+    #[test]
+    fn test_reply_text_from_print_mode() {
+        assert_eq!(reply_text("  Found 2 issues\n"), "Found 2 issues");
+    }
+}
@@ -0,0 +1,250 @@
+//! Check that a workflow's Claude commands are installed
+//!
+//! Claude rejects an unknown slash command only when the step runs, which for
+//! a long workflow or a MapReduce job can be well into the run. Before the
+//! first step, every `claude:` command the workflow calls, including failure
+//! handlers and merge steps, is looked up in `.claude/commands/` of the
+//! project and of the user's home directory. Missing commands stop the run,
+//! with a `prodigy init --commands` line for the ones prodigy bundles.
+//!
+//! Commands that cannot be checked are skipped: names built from `${...}`
+//! variables, plugin commands (`/plugin:command`) and Claude's built-in
+//! commands.
+
+use super::docs::{self, WorkflowDoc};
+use crate::config::mapreduce::MapReduceWorkflowConfig;
+use crate::config::WorkflowConfig;
+use anyhow::{anyhow, Result};
+use std::collections::{BTreeSet, HashSet};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+/// Slash commands built into Claude Code
+const BUILTIN_COMMANDS: &[&str] = &[
+    "add-dir",
+    "agents",
+    "bug",
+    "clear",
+    "compact",
+    "config",
+    "cost",
+    "doctor",
+    "help",
+    "init",
+    "mcp",
+    "memory",
+    "model",
+    "permissions",
+    "pr-comments",
+    "review",
+    "security-review",
+    "status",
+];
+
+/// Commands a workflow calls that are not installed
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MissingCommands {
+    /// Bundled with prodigy and installable with `prodigy init --commands`
+    pub installable: Vec<String>,
+    /// Neither installed nor bundled
+    pub unknown: Vec<String>,
+}
+
+impl MissingCommands {
+    pub fn is_empty(&self) -> bool {
+        self.installable.is_empty() && self.unknown.is_empty()
+    }
+
+    /// Error message listing the missing commands and how to add them
+    pub fn message(&self) -> String {
+        let names: Vec<String> = self
+            .installable
+            .iter()
+            .chain(&self.unknown)
+            .map(|name| format!("/{}", name))
+            .collect();
+        let mut message = format!(
+            "Workflow calls Claude commands that are not installed: {}",
+            names.join(", ")
+        );
+        if !self.installable.is_empty() {
+            let _ = write!(
+                message,
+                "\n  Install the bundled commands with: prodigy init --commands {}",
+                self.installable.join(",")
+            );
+        }
+        if !self.unknown.is_empty() {
+            let files: Vec<String> = self
+                .unknown
+                .iter()
+                .map(|name| format!("{}.md", name))
+                .collect();
+            let _ = write!(
+                message,
+                "\n  Create the others in .claude/commands/: {}",
+                files.join(", ")
+            );
+        }
+        message
+    }
+}
+
+/// Names of the slash commands a workflow calls, without the `/` (pure function)
+///
+/// Names that cannot be checked before the run are left out.
+pub fn required_commands(
+    workflow: &WorkflowConfig,
+    mapreduce: Option<&MapReduceWorkflowConfig>,
+) -> BTreeSet<String> {
+    let doc: WorkflowDoc = match mapreduce {
+        Some(config) => docs::mapreduce_doc(config, None),
+        None => docs::standard_doc("workflow", workflow, None),
+    };
+    let merge = mapreduce
+        .and_then(|config| config.merge.as_ref())
+        .or(workflow.merge.as_ref());
+
+    doc.sections
+        .iter()
+        .flat_map(|section| &section.steps)
+        .cloned()
+        .chain(
+            merge
+                .into_iter()
+                .flat_map(|m| m.commands.iter().map(docs::step_doc)),
+        )
+        .flat_map(|step| step.claude_commands)
+        .filter_map(|command| checkable_name(&command))
+        .collect()
+}
+
+/// Command name to look up, or `None` if it cannot be checked (pure function)
+fn checkable_name(command: &str) -> Option<String> {
+    let name = command.strip_prefix('/')?;
+    let checkable = !name.is_empty()
+        && !name.contains('$')
+        && !name.contains(':')
+        && !BUILTIN_COMMANDS.contains(&name);
+    checkable.then(|| name.to_string())
+}
+
+/// Split required commands into installed, bundled and unknown (pure function)
+pub fn find_missing(
+    required: &BTreeSet<String>,
+    installed: &HashSet<String>,
+    bundled: &HashSet<String>,
+) -> MissingCommands {
+    let mut missing = MissingCommands::default();
+    for name in required.iter().filter(|name| !installed.contains(*name)) {
+        if bundled.contains(name) {
+            missing.installable.push(name.clone());
+        } else {
+            missing.unknown.push(name.clone());
+        }
+    }
+    missing
+}
+
+/// Names of the commands in `.claude/commands` under each directory, including subdirectories
+pub fn installed_commands(roots: &[PathBuf]) -> HashSet<String> {
+    roots
+        .iter()
+        .map(|root| root.join(".claude").join("commands"))
+        .filter(|dir| dir.is_dir())
+        .flat_map(|dir| walkdir::WalkDir::new(dir).follow_links(true))
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "md"))
+        .filter_map(|entry| {
+            entry
+                .path()
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .map(str::to_string)
+        })
+        .collect()
+}
+
+/// Fail if the workflow calls Claude commands that are not installed
+///
+/// `project_dirs` are searched along with the user's home directory. With
+/// `warn_only`, as in a dry run, missing commands are reported as a warning.
+pub fn check_claude_commands(
+    workflow: &WorkflowConfig,
+    mapreduce: Option<&MapReduceWorkflowConfig>,
+    project_dirs: &[&Path],
+    warn_only: bool,
+) -> Result<()> {
+    let required = required_commands(workflow, mapreduce);
+    if required.is_empty() {
+        return Ok(());
+    }
+
+    let mut roots: Vec<PathBuf> = project_dirs.iter().map(|dir| dir.to_path_buf()).collect();
+    roots.extend(directories::BaseDirs::new().map(|dirs| dirs.home_dir().to_path_buf()));
+    let bundled: HashSet<String> = crate::init::templates::get_all_templates()
+        .into_iter()
+        .map(|template| template.name.to_string())
+        .collect();
+
+    let missing = find_missing(&required, &installed_commands(&roots), &bundled);
+    if missing.is_empty() {
+        Ok(())
+    } else if warn_only {
+        tracing::warn!("{}", missing.message());
+        Ok(())
+    } else {
+        Err(anyhow!(missing.message()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_required_commands_include_handlers_and_skip_unverifiable() {
+        let yaml = r#"
+commands:
+  - claude: "/prodigy-implement-spec $ARG"
+  - shell: "just test"
+    on_failure:
+      claude: "/prodigy-debug-test-failure --output ${shell.output}"
+  - claude: "/compact"
+  - claude: "/${command_name} --fast"
+  - claude: "/tools:lint"
+  - claude: "Explain the failure in plain words"
+"#;
+        let workflow: WorkflowConfig = serde_yaml::from_str(yaml).unwrap();
+        let required: Vec<String> = required_commands(&workflow, None).into_iter().collect();
+        assert_eq!(
+            required,
+            vec!["prodigy-debug-test-failure", "prodigy-implement-spec"]
+        );
+    }
+
+    #[test]
+    fn test_find_missing_suggests_bundled_commands() {
+        let temp = TempDir::new().unwrap();
+        let nested = temp.path().join(".claude/commands/team");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::write(nested.join("triage.md"), "# Triage").unwrap();
+
+        let installed = installed_commands(&[temp.path().to_path_buf()]);
+        let required: BTreeSet<String> = ["triage", "prodigy-lint", "my-review"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        let bundled: HashSet<String> = ["prodigy-lint".to_string()].into_iter().collect();
+
+        let missing = find_missing(&required, &installed, &bundled);
+        assert_eq!(missing.installable, vec!["prodigy-lint"]);
+        assert_eq!(missing.unknown, vec!["my-review"]);
+
+        let message = missing.message();
+        assert!(message.contains("/prodigy-lint, /my-review"), "{}", message);
+        assert!(message.contains("prodigy init --commands prodigy-lint"));
+        assert!(message.contains(".claude/commands/: my-review.md"));
+    }
+}
@@ -0,0 +1,345 @@
+//! `summarize_commits:` steps
+//!
+//! A summarize_commits step collects the commits made since the workflow
+//! started, groups them by conventional-commit type, writes the summary to an
+//! artifact and stores it in a variable for later steps:
+//!
+//! ```yaml
+//! - summarize_commits:
+//!     variable: changelog
+//!     output: .prodigy/changelog.md
+//!     claude: "/prodigy-polish-changelog"
+//! ```
+//!
+//! `since` overrides the starting ref, which defaults to the commit the
+//! workflow started from. When `claude` is set, the command runs with the
+//! artifact path appended as its last argument and may rewrite the file; the
+//! variable holds the file's final content. All strings support variable
+//! interpolation.
+
+use crate::abstractions::git::GitOperations;
+use crate::cook::commit_tracker::{CommitTracker, TrackedCommit};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Variable the summary is stored in unless configured otherwise
+pub const DEFAULT_SUMMARY_VARIABLE: &str = "commit_summary";
+
+/// Conventional-commit types and their section titles, in display order
+const SECTIONS: &[(&str, &str)] = &[
+    ("feat", "Features"),
+    ("fix", "Bug Fixes"),
+    ("perf", "Performance"),
+    ("refactor", "Refactoring"),
+    ("docs", "Documentation"),
+    ("test", "Tests"),
+    ("build", "Build"),
+    ("ci", "CI"),
+    ("chore", "Chores"),
+];
+
+/// Section title for commits without a recognised type
+const OTHER_SECTION: &str = "Other Changes";
+
+/// Configuration of a `summarize_commits:` step
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SummarizeCommitsConfig {
+    /// Ref to summarize from, defaults to the workflow's starting commit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub since: Option<String>,
+
+    /// Variable the summary is stored in, defaults to `commit_summary`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub variable: Option<String>,
+
+    /// Artifact path relative to the working directory
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<String>,
+
+    /// Output format of the summary
+    #[serde(default)]
+    pub format: SummaryFormat,
+
+    /// Claude command that refines the generated summary
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub claude: Option<String>,
+}
+
+/// Output format of a commit summary
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SummaryFormat {
+    #[default]
+    Markdown,
+    Json,
+}
+
+impl SummarizeCommitsConfig {
+    /// Copy of the config with every string interpolated
+    pub fn interpolated(&self, mut interpolate: impl FnMut(&str) -> String) -> Self {
+        Self {
+            since: self.since.as_deref().map(&mut interpolate),
+            variable: self.variable.as_deref().map(&mut interpolate),
+            output: self.output.as_deref().map(&mut interpolate),
+            format: self.format,
+            claude: self.claude.as_deref().map(&mut interpolate),
+        }
+    }
+
+    /// Variable the summary is stored in
+    pub fn variable_name(&self) -> &str {
+        self.variable.as_deref().unwrap_or(DEFAULT_SUMMARY_VARIABLE)
+    }
+
+    /// Artifact path relative to the working directory
+    pub fn output_path(&self) -> String {
+        self.output.clone().unwrap_or_else(|| match self.format {
+            SummaryFormat::Markdown => ".prodigy/commit-summary.md".to_string(),
+            SummaryFormat::Json => ".prodigy/commit-summary.json".to_string(),
+        })
+    }
+}
+
+/// Structured summary of a range of commits
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct CommitSummary {
+    pub commit_count: usize,
+    pub files_changed: usize,
+    pub insertions: usize,
+    pub deletions: usize,
+    pub sections: Vec<SummarySection>,
+}
+
+/// Commits of one type
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SummarySection {
+    pub title: String,
+    pub entries: Vec<SummaryEntry>,
+}
+
+/// One summarized commit
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SummaryEntry {
+    pub hash: String,
+    /// Commit subject without its type prefix
+    pub description: String,
+    /// Scope from `type(scope): ...`, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    pub author: String,
+}
+
+/// Split a conventional-commit subject into type, scope and description (pure function)
+fn parse_subject(subject: &str) -> (Option<&str>, Option<&str>, &str) {
+    let Some((prefix, description)) = subject.split_once(": ") else {
+        return (None, None, subject);
+    };
+    let prefix = prefix.trim_end_matches('!');
+    let (kind, scope) = match prefix.split_once('(') {
+        Some((kind, scope)) => (kind, scope.strip_suffix(')')),
+        None => (prefix, None),
+    };
+    if kind.is_empty() || !kind.chars().all(|c| c.is_ascii_alphabetic()) {
+        return (None, None, subject);
+    }
+    (Some(kind), scope, description.trim())
+}
+
+/// Group commits by type, keeping their order within each group (pure function)
+pub fn summarize(commits: &[TrackedCommit]) -> CommitSummary {
+    let mut files: Vec<&PathBuf> = commits.iter().flat_map(|c| &c.files_changed).collect();
+    files.sort();
+    files.dedup();
+
+    let mut grouped: Vec<(&str, Vec<SummaryEntry>)> = Vec::new();
+    for commit in commits {
+        let (kind, scope, description) = parse_subject(&commit.message);
+        let title = kind
+            .map(|k| k.to_ascii_lowercase())
+            .and_then(|k| SECTIONS.iter().find(|(t, _)| *t == k))
+            .map(|(_, title)| *title)
+            .unwrap_or(OTHER_SECTION);
+        let entry = SummaryEntry {
+            hash: commit.hash.chars().take(7).collect(),
+            description: description.to_string(),
+            scope: scope.map(str::to_string),
+            author: commit.author.clone(),
+        };
+        match grouped.iter_mut().find(|(t, _)| *t == title) {
+            Some((_, entries)) => entries.push(entry),
+            None => grouped.push((title, vec![entry])),
+        }
+    }
+
+    let order = |title: &str| {
+        SECTIONS
+            .iter()
+            .position(|(_, t)| *t == title)
+            .unwrap_or(SECTIONS.len())
+    };
+    grouped.sort_by_key(|(title, _)| order(title));
+
+    CommitSummary {
+        commit_count: commits.len(),
+        files_changed: files.len(),
+        insertions: commits.iter().map(|c| c.insertions).sum(),
+        deletions: commits.iter().map(|c| c.deletions).sum(),
+        sections: grouped
+            .into_iter()
+            .map(|(title, entries)| SummarySection {
+                title: title.to_string(),
+                entries,
+            })
+            .collect(),
+    }
+}
+
+/// Render a summary as markdown (pure function)
+pub fn render_markdown(summary: &CommitSummary) -> String {
+    let mut out = format!(
+        "## Summary\n\n{} commit{}, {} file{} changed, +{} -{}\n",
+        summary.commit_count,
+        if summary.commit_count == 1 { "" } else { "s" },
+        summary.files_changed,
+        if summary.files_changed == 1 { "" } else { "s" },
+        summary.insertions,
+        summary.deletions
+    );
+    if summary.sections.is_empty() {
+        out.push_str("\nNo commits were made.\n");
+    }
+    for section in &summary.sections {
+        out.push_str(&format!("\n### {}\n\n", section.title));
+        for entry in &section.entries {
+            let scope = entry
+                .scope
+                .as_ref()
+                .map(|s| format!("**{}:** ", s))
+                .unwrap_or_default();
+            out.push_str(&format!(
+                "- {}{} ({})\n",
+                scope, entry.description, entry.hash
+            ));
+        }
+    }
+    out
+}
+
+/// Render a summary in the configured format (pure function)
+pub fn render(summary: &CommitSummary, format: SummaryFormat) -> String {
+    match format {
+        SummaryFormat::Markdown => render_markdown(summary),
+        SummaryFormat::Json => {
+            serde_json::to_string_pretty(summary).unwrap_or_else(|_| "{}".to_string())
+        }
+    }
+}
+
+/// Commits made on HEAD since `since`, oldest first
+pub async fn collect_commits(
+    git: Arc<dyn GitOperations>,
+    working_dir: PathBuf,
+    since: &str,
+) -> anyhow::Result<Vec<TrackedCommit>> {
+    let tracker = CommitTracker::new(git, working_dir);
+    let mut commits = tracker.get_commits_between(since, "HEAD").await?;
+    commits.reverse();
+    Ok(commits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn commit(hash: &str, message: &str, files: &[&str]) -> TrackedCommit {
+        TrackedCommit {
+            hash: hash.to_string(),
+            message: message.to_string(),
+            author: "Ada".to_string(),
+            timestamp: Utc::now(),
+            files_changed: files.iter().map(PathBuf::from).collect(),
+            insertions: 10,
+            deletions: 2,
+            step_name: String::new(),
+            agent_id: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_config() {
+        let config: SummarizeCommitsConfig =
+            serde_yaml::from_str("variable: changelog\nformat: json\n").unwrap();
+        assert_eq!(config.variable_name(), "changelog");
+        assert_eq!(config.output_path(), ".prodigy/commit-summary.json");
+
+        let defaults = SummarizeCommitsConfig::default();
+        assert_eq!(defaults.variable_name(), DEFAULT_SUMMARY_VARIABLE);
+        assert_eq!(defaults.output_path(), ".prodigy/commit-summary.md");
+
+        assert!(serde_yaml::from_str::<SummarizeCommitsConfig>("varible: x").is_err());
+    }
+
+    #[test]
+    fn test_parse_subject() {
+        assert_eq!(
+            parse_subject("feat(cli): add --repos"),
+            (Some("feat"), Some("cli"), "add --repos")
+        );
+        assert_eq!(
+            parse_subject("fix!: drop legacy flag"),
+            (Some("fix"), None, "drop legacy flag")
+        );
+        assert_eq!(
+            parse_subject("Merge branch 'main'"),
+            (None, None, "Merge branch 'main'")
+        );
+        assert_eq!(
+            parse_subject("see docs/a b: notes"),
+            (None, None, "see docs/a b: notes")
+        );
+    }
+
+    #[test]
+    fn test_summarize_groups_by_type() {
+        let commits = vec![
+            commit("aaaaaaaaaa", "fix(parser): handle tabs", &["src/parser.rs"]),
+            commit("bbbbbbbbbb", "Update README", &["README.md"]),
+            commit(
+                "cccccccccc",
+                "feat: add summaries",
+                &["src/parser.rs", "src/summary.rs"],
+            ),
+            commit("dddddddddd", "fix: typo", &[]),
+        ];
+        let summary = summarize(&commits);
+
+        assert_eq!(summary.commit_count, 4);
+        assert_eq!(summary.files_changed, 3);
+        assert_eq!(summary.insertions, 40);
+        let titles: Vec<_> = summary.sections.iter().map(|s| s.title.as_str()).collect();
+        assert_eq!(titles, vec!["Features", "Bug Fixes", "Other Changes"]);
+        assert_eq!(summary.sections[1].entries[0].hash, "aaaaaaa");
+        assert_eq!(summary.sections[1].entries[1].description, "typo");
+
+        let markdown = render_markdown(&summary);
+        assert!(markdown.starts_with("## Summary\n\n4 commits, 3 files changed, +40 -8\n"));
+        assert!(markdown.contains("### Bug Fixes\n\n- **parser:** handle tabs (aaaaaaa)\n"));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&render(&summary, SummaryFormat::Json)).unwrap();
+        assert_eq!(
+            json["sections"][0]["entries"][0]["description"],
+            "add summaries"
+        );
+    }
+
+    #[test]
+    fn test_render_without_commits() {
+        let markdown = render_markdown(&summarize(&[]));
+        assert!(markdown.contains("0 commits, 0 files changed"));
+        assert!(markdown.contains("No commits were made."));
+    }
+}
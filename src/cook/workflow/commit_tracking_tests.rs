@@ -38,6 +38,8 @@ mod tests {
         // Create workflow step with auto_commit enabled
         let step = WorkflowStep {
             claude: Some("/test-command".to_string()),
+            context: None,
+            validate_output: None,
             auto_commit: true,
             commit_config: Some(CommitConfig {
                 message_template: Some("chore: auto commit for ${command}".to_string()),
@@ -93,6 +95,8 @@ mod tests {
         // Create workflow step that requires commits
         let step = WorkflowStep {
             claude: Some("/implement-feature".to_string()),
+            context: None,
+            validate_output: None,
             commit_required: true,
             commit_config: Some(CommitConfig {
                 message_template: None,
@@ -156,6 +160,8 @@ mod tests {
         // Create workflow step with minimal config
         let step = WorkflowStep {
             claude: Some("/test".to_string()),
+            context: None,
+            validate_output: None,
             auto_commit: true,
             commit_config: None, // Use defaults
             ..Default::default()
@@ -6,9 +6,12 @@
 
 use crate::config::WorkflowConfig;
 use crate::cook::workflow::composition::{
-    ComposableWorkflow, ComposedWorkflow, TemplateRegistry, WorkflowComposer,
+    prompt_secret_from_terminal, resolve_secret_parameters, ComposableWorkflow, ComposedWorkflow,
+    ParameterDefinitions, SecretParameters, TemplateRegistry, WorkflowComposer,
 };
 use anyhow::{Context, Result};
+use once_cell::sync::Lazy;
+use regex::Regex;
 use serde_json::Value;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
@@ -62,14 +65,28 @@ pub async fn parse_composable_workflow(
     let composable: ComposableWorkflow = serde_yaml::from_str(content)
         .with_context(|| format!("Failed to parse composable workflow: {}", path.display()))?;
 
+    let mut cli_params = cli_params;
+    let mut secrets = SecretParameters::default();
+    if let Some(definitions) = &composable.parameters {
+        reject_unknown_parameters(definitions, &cli_params)?;
+        secrets = resolve_secret_parameters(
+            definitions,
+            &mut cli_params,
+            |name| std::env::var(name).ok(),
+            prompt_secret_from_terminal,
+        )?;
+    }
+
     // Extract parameters from workflow defaults
     let workflow_params = extract_workflow_parameters(&composable)?;
 
-    // Merge CLI parameters with workflow defaults (CLI takes precedence)
+    // Merge CLI parameters with workflow defaults (CLI takes precedence);
+    // secret parameters only ever contribute their placeholders
     let mut params = workflow_params;
     for (key, value) in cli_params {
         params.insert(key, value);
     }
+    params.extend(secrets.placeholders);
 
     // Initialize template registry
     let registry = Arc::new(create_template_registry()?);
@@ -84,7 +101,15 @@ pub async fn parse_composable_workflow(
         .context("Failed to compose workflow")?;
 
     // Convert to WorkflowConfig
-    let workflow_config = convert_composed_to_config(composed)?;
+    let mut workflow_config = convert_composed_to_config(composed)?;
+
+    // Secret values reach steps only through their environment
+    if !secrets.env.is_empty() {
+        workflow_config
+            .env
+            .get_or_insert_with(HashMap::new)
+            .extend(secrets.env);
+    }
 
     Ok((workflow_config, None))
 }
@@ -155,15 +180,104 @@ fn extract_workflow_parameters(composable: &ComposableWorkflow) -> Result<HashMa
         }
     }
 
-    // NOTE: CLI parameter overrides will be implemented in Phase 2
-    // Phase 1 uses only defaults from workflow file
+    // Required parameters are validated by the composer once CLI values
+    // have been merged in
+    Ok(params)
+}
 
-    // Validate required parameters
-    composable
-        .validate_parameters(&params)
-        .context("Parameter validation failed")?;
+/// Fail on CLI parameters the workflow does not declare
+fn reject_unknown_parameters(
+    definitions: &ParameterDefinitions,
+    provided: &HashMap<String, Value>,
+) -> Result<()> {
+    let unknown = definitions.unknown_parameters(provided.keys());
+    if unknown.is_empty() {
+        return Ok(());
+    }
 
-    Ok(params)
+    let names: Vec<String> = unknown.iter().map(|name| format!("'{}'", name)).collect();
+    anyhow::bail!(
+        "Unknown parameter {}\n\nExpected parameters:\n{}",
+        names.join(", "),
+        definitions.describe()
+    )
+}
+
+static PARAMS_REFERENCE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\$\{params\.([A-Za-z0-9_-]+)\}").expect("Valid regex pattern"));
+
+/// Validate declared parameters and substitute `${params.name}` in raw workflow YAML
+///
+/// MapReduce workflows are not composed, so parameter references are
+/// replaced in the file content before it is parsed.
+pub fn apply_parameters_to_content(
+    content: &str,
+    cli_params: &HashMap<String, Value>,
+) -> Result<String> {
+    #[derive(serde::Deserialize)]
+    struct Declared {
+        #[serde(default)]
+        parameters: Option<ParameterDefinitions>,
+    }
+
+    let declared: Declared =
+        serde_yaml::from_str(content).context("Failed to parse workflow parameters")?;
+    let params = match &declared.parameters {
+        Some(definitions) => {
+            reject_unknown_parameters(definitions, cli_params)?;
+            let mut provided = cli_params.clone();
+            let secrets = resolve_secret_parameters(
+                definitions,
+                &mut provided,
+                |name| std::env::var(name).ok(),
+                |param| {
+                    if definitions.required.iter().all(|p| p.name != param.name) {
+                        return Ok(None);
+                    }
+                    anyhow::bail!(
+                        "MapReduce workflows read secret parameters from the environment; set {} for '{}'",
+                        param.secret_env_var(),
+                        param.name
+                    )
+                },
+            )?;
+            if let Some(env_var) = secrets.env.keys().next() {
+                anyhow::bail!(
+                    "MapReduce workflows read secret parameters from the environment; set {} instead of passing a value",
+                    env_var
+                );
+            }
+            provided.extend(secrets.placeholders);
+            definitions
+                .resolve_and_validate(&provided)
+                .context("Parameter validation failed")?
+        }
+        None => cli_params.clone(),
+    };
+
+    let mut missing = Vec::new();
+    let substituted = PARAMS_REFERENCE_REGEX.replace_all(content, |caps: &regex::Captures| {
+        match params.get(&caps[1]) {
+            Some(Value::String(s)) => s.clone(),
+            Some(value) => value.to_string(),
+            None => {
+                missing.push(caps[1].to_string());
+                caps[0].to_string()
+            }
+        }
+    });
+
+    if !missing.is_empty() {
+        let mut available: Vec<&str> = params.keys().map(String::as_str).collect();
+        available.sort_unstable();
+        anyhow::bail!(
+            "Parameter '{}' not found (available: {})",
+            missing.join("', '"),
+            available.join(", ")
+        );
+    }
+
+    Ok(substituted.into_owned())
 }
 
 /// Convert composed workflow to executable WorkflowConfig
@@ -178,6 +292,13 @@ fn convert_composed_to_config(composed: ComposedWorkflow) -> Result<WorkflowConf
         env_files: workflow.config.env_files,
         profiles: workflow.config.profiles,
         merge: workflow.config.merge,
+        concurrency: workflow.config.concurrency,
+        claude_permissions: workflow.config.claude_permissions,
+        always: workflow.config.always,
+        strict_variables: workflow.config.strict_variables,
+        defaults: None,
+        protected_paths: None,
+        score_gate: None,
     })
 }
 
@@ -195,6 +316,139 @@ mod tests {
         assert!(!is_composable_workflow("commands:\n  - shell: test"));
     }
 
+    #[test]
+    fn test_apply_parameters_to_content() {
+        let content = r#"
+name: deploy
+mode: mapreduce
+parameters:
+  required:
+    - name: target
+      type: string
+  optional:
+    - name: workers
+      type: number
+      default: 4
+map:
+  max_parallel: ${params.workers}
+  agent_template:
+    - shell: "deploy ${params.target} ${item.id}"
+"#;
+        let mut cli_params = HashMap::new();
+        cli_params.insert("target".to_string(), Value::from("staging"));
+
+        let result = apply_parameters_to_content(content, &cli_params).unwrap();
+        assert!(result.contains("max_parallel: 4"));
+        assert!(result.contains("deploy staging ${item.id}"));
+
+        cli_params.insert("tagret".to_string(), Value::from("typo"));
+        let err = apply_parameters_to_content(content, &cli_params).unwrap_err();
+        assert!(err.to_string().starts_with("Unknown parameter 'tagret'"));
+        assert!(err.to_string().contains("  target (string, required)"));
+
+        let err = apply_parameters_to_content(content, &HashMap::new()).unwrap_err();
+        assert!(format!("{:#}", err).contains("Required parameter 'target' not provided"));
+    }
+
+    #[test]
+    fn test_mapreduce_secret_parameters_stay_out_of_content() {
+        let content = r#"
+name: release
+mode: mapreduce
+parameters:
+  optional:
+    - name: token
+      type: string
+      secret: true
+      env: PRODIGY_TEST_UNSET_RELEASE_TOKEN
+map:
+  agent_template:
+    - shell: "publish --token ${params.token}"
+"#;
+        let result = apply_parameters_to_content(content, &HashMap::new()).unwrap();
+        assert!(result.contains("publish --token ${PRODIGY_TEST_UNSET_RELEASE_TOKEN}"));
+
+        let mut cli_params = HashMap::new();
+        cli_params.insert("token".to_string(), Value::from("hunter2"));
+        let err = apply_parameters_to_content(content, &cli_params).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("read secret parameters from the environment"));
+    }
+
+    #[tokio::test]
+    async fn test_composable_secret_parameter_is_passed_through_env() {
+        let content = r#"
+name: deploy
+parameters:
+  required:
+    - name: api_token
+      type: string
+      secret: true
+commands:
+  - shell: "deploy --token ${params.api_token}"
+"#;
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("deploy.yml");
+        std::fs::write(&path, content).unwrap();
+
+        let mut cli_params = HashMap::new();
+        cli_params.insert("api_token".to_string(), Value::from("hunter2"));
+        let (config, _) = parse_composable_workflow(&path, content, cli_params)
+            .await
+            .unwrap();
+
+        let commands = serde_json::to_string(&config.commands).unwrap();
+        assert!(commands.contains("${PRODIGY_PARAM_API_TOKEN}"));
+        assert!(!commands.contains("hunter2"));
+        assert_eq!(
+            config.env.unwrap().get("PRODIGY_PARAM_API_TOKEN"),
+            Some(&"hunter2".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_secret_parameter_is_defined_under_strict_variables() {
+        let content = r#"
+name: deploy
+strict_variables: true
+parameters:
+  required:
+    - name: api_token
+      type: string
+      secret: true
+commands:
+  - shell: "deploy --token ${params.api_token}"
+"#;
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("deploy.yml");
+        std::fs::write(&path, content).unwrap();
+
+        let mut cli_params = HashMap::new();
+        cli_params.insert("api_token".to_string(), Value::from("hunter2"));
+        let (config, _) = parse_composable_workflow(&path, content, cli_params)
+            .await
+            .unwrap();
+        assert!(config.strict_variables_enabled());
+
+        // The workflow env block reaches every step's environment
+        let ctx = crate::cook::workflow::WorkflowContext {
+            strict_variables: true,
+            ..Default::default()
+        };
+        let env_vars = config.env.clone().unwrap();
+        let shell = match &config.commands[0] {
+            crate::config::command::WorkflowCommand::WorkflowStep(step) => {
+                step.shell.clone().unwrap()
+            }
+            other => panic!("unexpected command {:?}", other),
+        };
+        assert!(ctx.ensure_variables_defined(&shell, &env_vars).is_ok());
+        assert!(ctx
+            .ensure_variables_defined(&shell, &HashMap::new())
+            .is_err());
+    }
+
     #[test]
     fn test_extract_workflow_parameters() {
         let mut composable = ComposableWorkflow::from_config(WorkflowConfig {
@@ -205,6 +459,13 @@ mod tests {
             env_files: None,
             profiles: None,
             merge: None,
+            concurrency: None,
+            claude_permissions: None,
+            always: Vec::new(),
+            strict_variables: None,
+            defaults: None,
+            protected_paths: None,
+            score_gate: None,
         });
 
         let mut defaults = HashMap::new();
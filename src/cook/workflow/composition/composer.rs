@@ -72,9 +72,10 @@ impl WorkflowComposer {
         }
 
         // Validate and apply parameters
-        workflow
-            .validate_parameters(&params)
+        let params = workflow
+            .resolve_parameters(&params)
             .context("Parameter validation failed")?;
+        metadata.parameters = params.clone();
         self.apply_parameters(&mut workflow, &params)?;
 
         // Resolve sub-workflows
@@ -818,6 +819,10 @@ impl CommandArgExt for crate::config::CommandArg {
 }
 
 /// Substitute parameter references in a string
+///
+/// `${name}` and `${params.name}` refer to parameters. Other dotted
+/// references such as `${shell.output}` are runtime variables and are left
+/// for the executor to interpolate.
 fn substitute_params(
     regex: &regex::Regex,
     text: &str,
@@ -826,8 +831,15 @@ fn substitute_params(
     // Collect all parameter substitutions (or errors)
     let substitutions: Vec<Result<(String, String)>> = regex
         .captures_iter(text)
-        .map(|cap| {
-            let param_name = cap[1].to_string();
+        .filter_map(|cap| {
+            let expression = cap[1].to_string();
+            match expression.strip_prefix("params.") {
+                Some(name) => Some((expression.clone(), name.to_string())),
+                None if expression.contains('.') => None,
+                None => Some((expression.clone(), expression)),
+            }
+        })
+        .map(|(expression, param_name)| {
             params
                 .get(&param_name)
                 .map(|value| {
@@ -840,9 +852,17 @@ fn substitute_params(
                         }
                         Value::Null => String::new(),
                     };
-                    (format!("${{{}}}", param_name), value_str)
+                    (format!("${{{}}}", expression), value_str)
+                })
+                .ok_or_else(|| {
+                    let mut available: Vec<&str> = params.keys().map(String::as_str).collect();
+                    available.sort_unstable();
+                    anyhow::anyhow!(
+                        "Parameter '{}' not found (available: {})",
+                        param_name,
+                        available.join(", ")
+                    )
                 })
-                .ok_or_else(|| anyhow::anyhow!("Parameter '{}' not found", param_name))
         })
         .collect();
 
@@ -956,6 +976,16 @@ mod tests {
         // Test missing parameter
         let result = substitute_params(&regex, "Missing: ${missing}", &params);
         assert!(result.is_err());
+
+        // Test params. prefix, and runtime variables left in place
+        let result = substitute_params(&regex, "${params.target} then ${shell.output}", &params);
+        assert_eq!(result.unwrap(), "app.js then ${shell.output}");
+
+        let result = substitute_params(&regex, "${params.missing}", &params);
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("available: count, enabled, target"));
     }
 
     #[test]
@@ -11,10 +11,12 @@ use std::path::PathBuf;
 
 pub mod composer;
 pub mod registry;
+pub mod secrets;
 pub mod sub_workflow;
 
 pub use composer::WorkflowComposer;
 pub use registry::{TemplateRegistry, TemplateStorage};
+pub use secrets::{prompt_secret_from_terminal, resolve_secret_parameters, SecretParameters};
 pub use sub_workflow::{SubWorkflow, SubWorkflowExecutor, SubWorkflowResult};
 
 /// A composable workflow with support for imports, templates, and parameters
@@ -117,6 +119,7 @@ pub struct Parameter {
     pub type_hint: ParameterType,
 
     /// Description of the parameter
+    #[serde(default)]
     pub description: String,
 
     /// Default value for optional parameters
@@ -126,18 +129,241 @@ pub struct Parameter {
     /// Validation expression or pattern
     #[serde(skip_serializing_if = "Option::is_none")]
     pub validation: Option<String>,
+
+    /// Allowed values for `enum` parameters
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub values: Vec<Value>,
+
+    /// Keep the value out of commands, checkpoints, and events
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub secret: bool,
+
+    /// Environment variable holding a secret value
+    /// (defaults to `PRODIGY_PARAM_<NAME>`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub env: Option<String>,
 }
 
 /// Type hints for parameters
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum ParameterType {
+    #[serde(alias = "String")]
     String,
+    #[serde(alias = "Number")]
     Number,
+    #[serde(alias = "Boolean", alias = "bool")]
     Boolean,
+    #[serde(alias = "Array")]
     Array,
+    #[serde(alias = "Object")]
     Object,
+    #[serde(alias = "Any")]
     Any,
+    /// One of the parameter's `values`
+    #[serde(alias = "Enum")]
+    Enum,
+}
+
+impl ParameterType {
+    /// Convert loosely typed input (such as `--param` strings) to this type
+    ///
+    /// Values that cannot be converted are returned unchanged and rejected
+    /// by validation.
+    pub fn coerce(&self, value: Value) -> Value {
+        match (self, value) {
+            (ParameterType::String, Value::Number(n)) => Value::String(n.to_string()),
+            (ParameterType::String, Value::Bool(b)) => Value::String(b.to_string()),
+            (ParameterType::Number, Value::String(s)) => s
+                .trim()
+                .parse::<serde_json::Number>()
+                .map(Value::Number)
+                .unwrap_or(Value::String(s)),
+            (ParameterType::Boolean, Value::String(s)) => match s.as_str() {
+                "true" => Value::Bool(true),
+                "false" => Value::Bool(false),
+                _ => Value::String(s),
+            },
+            (_, value) => value,
+        }
+    }
+}
+
+impl Parameter {
+    /// One-line summary used in error messages
+    pub fn summary(&self, required: bool) -> String {
+        let mut kind = format!("{:?}", self.type_hint).to_lowercase();
+        if self.type_hint == ParameterType::Enum {
+            let values: Vec<String> = self.values.iter().map(value_to_display).collect();
+            kind = format!("{}: {}", kind, values.join("|"));
+        }
+        if self.secret {
+            kind = format!("{}, secret", kind);
+        }
+        let requirement = match (&self.default, required) {
+            (Some(_), _) if self.secret => "default: ***".to_string(),
+            (Some(default), _) => format!("default: {}", default),
+            (None, true) => "required".to_string(),
+            (None, false) => "optional".to_string(),
+        };
+
+        let mut line = format!("{} ({}, {})", self.name, kind, requirement);
+        if !self.description.is_empty() {
+            line.push_str(&format!(" - {}", self.description));
+        }
+        line
+    }
+
+    /// Environment variable that carries a secret parameter's value
+    pub fn secret_env_var(&self) -> String {
+        self.env.clone().unwrap_or_else(|| {
+            format!(
+                "PRODIGY_PARAM_{}",
+                self.name.to_uppercase().replace('-', "_")
+            )
+        })
+    }
+
+    fn validate_value(&self, value: &Value) -> Result<()> {
+        // Type validation
+        match (&self.type_hint, value) {
+            (ParameterType::String, Value::String(_)) => {}
+            (ParameterType::Number, Value::Number(_)) => {}
+            (ParameterType::Boolean, Value::Bool(_)) => {}
+            (ParameterType::Array, Value::Array(_)) => {}
+            (ParameterType::Object, Value::Object(_)) => {}
+            (ParameterType::Any, _) => {}
+            (ParameterType::Enum, value) => {
+                let display = value_to_display(value);
+                if !self.values.iter().any(|v| value_to_display(v) == display) {
+                    let allowed: Vec<String> = self.values.iter().map(value_to_display).collect();
+                    anyhow::bail!(
+                        "'{}' is not one of the allowed values: {}",
+                        display,
+                        allowed.join(", ")
+                    );
+                }
+            }
+            _ => anyhow::bail!(
+                "Type mismatch: expected {:?}, got {}",
+                self.type_hint,
+                value
+            ),
+        }
+
+        // Custom validation
+        if let Some(validation) = &self.validation {
+            // TODO: Implement custom validation expression evaluation
+            tracing::debug!(
+                "Custom validation for parameter '{}': {}",
+                self.name,
+                validation
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl ParameterDefinitions {
+    /// Find a declared parameter by name
+    pub fn find(&self, name: &str) -> Option<&Parameter> {
+        self.required
+            .iter()
+            .chain(self.optional.iter())
+            .find(|p| p.name == name)
+    }
+
+    /// List of declared parameters, one per line, for error messages
+    pub fn describe(&self) -> String {
+        let required = self.required.iter().map(|p| p.summary(true));
+        let optional = self.optional.iter().map(|p| p.summary(false));
+        required
+            .chain(optional)
+            .map(|line| format!("  {}", line))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Coerce provided values to their declared types and fill in defaults
+    pub fn resolve(&self, provided: &HashMap<String, Value>) -> HashMap<String, Value> {
+        let mut resolved: HashMap<String, Value> = provided
+            .iter()
+            .map(|(name, value)| {
+                let value = match self.find(name) {
+                    Some(param) => param.type_hint.coerce(value.clone()),
+                    None => value.clone(),
+                };
+                (name.clone(), value)
+            })
+            .collect();
+
+        for param in self.required.iter().chain(self.optional.iter()) {
+            if let (false, Some(default)) = (resolved.contains_key(&param.name), &param.default) {
+                resolved.insert(param.name.clone(), default.clone());
+            }
+        }
+
+        resolved
+    }
+
+    /// Names in `provided` that are not declared
+    pub fn unknown_parameters<'a>(
+        &self,
+        provided: impl IntoIterator<Item = &'a String>,
+    ) -> Vec<String> {
+        let mut unknown: Vec<String> = provided
+            .into_iter()
+            .filter(|name| self.find(name).is_none())
+            .cloned()
+            .collect();
+        unknown.sort();
+        unknown
+    }
+    /// Coerce and default provided parameters, then validate them
+    pub fn resolve_and_validate(
+        &self,
+        provided: &HashMap<String, Value>,
+    ) -> Result<HashMap<String, Value>> {
+        let resolved = self.resolve(provided);
+        self.validate(&resolved)?;
+        Ok(resolved)
+    }
+
+    /// Validate provided parameters against these definitions
+    pub fn validate(&self, provided: &HashMap<String, Value>) -> Result<()> {
+        // Check all required parameters are provided
+        for param in &self.required {
+            if !provided.contains_key(&param.name) && param.default.is_none() {
+                anyhow::bail!(
+                    "Required parameter '{}' not provided (set it with --param {}=<value>)\n\nExpected parameters:\n{}",
+                    param.name,
+                    param.name,
+                    self.describe()
+                );
+            }
+        }
+
+        // Validate parameter types and constraints (secret values are
+        // validated when resolved and only their placeholders appear here)
+        for (name, value) in provided {
+            if let Some(param) = self.find(name).filter(|p| !p.secret) {
+                param
+                    .validate_value(value)
+                    .with_context(|| format!("Invalid value for parameter '{}'", name))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Display form of a parameter value (strings without quotes)
+fn value_to_display(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
 }
 
 /// Result of workflow composition
@@ -222,60 +448,23 @@ impl ComposableWorkflow {
             .unwrap_or_default()
     }
 
-    /// Validate provided parameters against definitions
-    pub fn validate_parameters(&self, provided: &HashMap<String, Value>) -> Result<()> {
-        if let Some(params) = &self.parameters {
-            // Check all required parameters are provided
-            for param in &params.required {
-                if !provided.contains_key(&param.name) && param.default.is_none() {
-                    anyhow::bail!("Required parameter '{}' not provided", param.name);
-                }
-            }
-
-            // Validate parameter types and constraints
-            for (name, value) in provided {
-                if let Some(param) = params
-                    .required
-                    .iter()
-                    .chain(params.optional.iter())
-                    .find(|p| p.name == *name)
-                {
-                    self.validate_parameter_value(param, value)
-                        .with_context(|| format!("Invalid value for parameter '{}'", name))?;
-                }
-            }
+    /// Coerce and default provided parameters, then validate them
+    pub fn resolve_parameters(
+        &self,
+        provided: &HashMap<String, Value>,
+    ) -> Result<HashMap<String, Value>> {
+        match &self.parameters {
+            Some(params) => params.resolve_and_validate(provided),
+            None => Ok(provided.clone()),
         }
-
-        Ok(())
     }
 
-    fn validate_parameter_value(&self, param: &Parameter, value: &Value) -> Result<()> {
-        // Type validation
-        match (&param.type_hint, value) {
-            (ParameterType::String, Value::String(_)) => {}
-            (ParameterType::Number, Value::Number(_)) => {}
-            (ParameterType::Boolean, Value::Bool(_)) => {}
-            (ParameterType::Array, Value::Array(_)) => {}
-            (ParameterType::Object, Value::Object(_)) => {}
-            (ParameterType::Any, _) => {}
-            _ => anyhow::bail!(
-                "Type mismatch: expected {:?}, got {}",
-                param.type_hint,
-                value
-            ),
-        }
-
-        // Custom validation
-        if let Some(validation) = &param.validation {
-            // TODO: Implement custom validation expression evaluation
-            tracing::debug!(
-                "Custom validation for parameter '{}': {}",
-                param.name,
-                validation
-            );
+    /// Validate provided parameters against definitions
+    pub fn validate_parameters(&self, provided: &HashMap<String, Value>) -> Result<()> {
+        match &self.parameters {
+            Some(params) => params.validate(provided),
+            None => Ok(()),
         }
-
-        Ok(())
     }
 }
 
@@ -293,6 +482,13 @@ mod tests {
             env_files: None,
             profiles: None,
             merge: None,
+            concurrency: None,
+            claude_permissions: None,
+            always: Vec::new(),
+            strict_variables: None,
+            defaults: None,
+            protected_paths: None,
+            score_gate: None,
         };
 
         let workflow = ComposableWorkflow::from_config(config);
@@ -309,6 +505,13 @@ mod tests {
             env_files: None,
             profiles: None,
             merge: None,
+            concurrency: None,
+            claude_permissions: None,
+            always: Vec::new(),
+            strict_variables: None,
+            defaults: None,
+            protected_paths: None,
+            score_gate: None,
         });
 
         workflow.parameters = Some(ParameterDefinitions {
@@ -318,6 +521,9 @@ mod tests {
                 description: "Target file".to_string(),
                 default: None,
                 validation: None,
+                values: vec![],
+                secret: false,
+                env: None,
             }],
             optional: vec![],
         });
@@ -330,4 +536,46 @@ mod tests {
         let empty_params = HashMap::new();
         assert!(workflow.validate_parameters(&empty_params).is_err());
     }
+
+    #[test]
+    fn test_resolve_parameters_coerces_and_defaults() {
+        let yaml = r#"
+commands: []
+parameters:
+  required:
+    - name: environment
+      type: enum
+      values: [dev, staging, prod]
+    - name: version
+      type: string
+  optional:
+    - name: replicas
+      type: number
+      default: 2
+    - name: dry_run
+      type: bool
+      default: false
+"#;
+        let workflow: ComposableWorkflow = serde_yaml::from_str(yaml).unwrap();
+
+        let mut provided = HashMap::new();
+        provided.insert("environment".to_string(), Value::from("prod"));
+        provided.insert("version".to_string(), Value::from(2));
+        provided.insert("dry_run".to_string(), Value::from("true"));
+
+        let resolved = workflow.resolve_parameters(&provided).unwrap();
+        assert_eq!(resolved["version"], Value::from("2"));
+        assert_eq!(resolved["replicas"], Value::from(2));
+        assert_eq!(resolved["dry_run"], Value::Bool(true));
+
+        provided.insert("environment".to_string(), Value::from("qa"));
+        let err = workflow.resolve_parameters(&provided).unwrap_err();
+        assert!(format!("{:#}", err).contains("not one of the allowed values: dev, staging, prod"));
+
+        let err = workflow.resolve_parameters(&HashMap::new()).unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("Required parameter 'environment' not provided"));
+        assert!(message.contains("  environment (enum: dev|staging|prod, required)"));
+        assert!(message.contains("  replicas (number, default: 2)"));
+    }
 }
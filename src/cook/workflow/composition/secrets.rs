@@ -0,0 +1,196 @@
+//! Secret workflow parameters
+//!
+//! Parameters marked `secret: true` are never substituted into workflow
+//! commands. Each reference is replaced with a `${PRODIGY_PARAM_<NAME>}`
+//! environment variable reference that the shell expands when the step runs,
+//! so the value stays out of checkpoints, events, and session state.
+
+use super::{value_to_display, Parameter, ParameterDefinitions};
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::io::IsTerminal;
+
+/// Placeholders and values for a workflow's secret parameters
+#[derive(Debug, Default)]
+pub struct SecretParameters {
+    /// Environment variable reference substituted for each secret parameter
+    pub placeholders: HashMap<String, Value>,
+    /// Values that must be added to the step environment because they are
+    /// not already in the process environment
+    pub env: HashMap<String, String>,
+}
+
+/// Resolve secret parameters without exposing their values
+///
+/// Each value is taken from the first of: `--param`, the parameter's
+/// environment variable, `prompt`, and the default. Secret entries are removed
+/// from `provided` so they are never substituted into commands.
+pub fn resolve_secret_parameters(
+    definitions: &ParameterDefinitions,
+    provided: &mut HashMap<String, Value>,
+    env_lookup: impl Fn(&str) -> Option<String>,
+    mut prompt: impl FnMut(&Parameter) -> Result<Option<String>>,
+) -> Result<SecretParameters> {
+    let mut secrets = SecretParameters::default();
+    let declared = definitions
+        .required
+        .iter()
+        .map(|p| (p, true))
+        .chain(definitions.optional.iter().map(|p| (p, false)));
+
+    for (param, required) in declared.filter(|(p, _)| p.secret) {
+        let env_var = param.secret_env_var();
+
+        let value = if let Some(value) = provided.remove(&param.name) {
+            tracing::warn!(
+                "Secret parameter '{}' was passed with --param and may be visible in shell history; set {} instead",
+                param.name,
+                env_var
+            );
+            Some((value_to_display(&value), true))
+        } else if let Some(value) = env_lookup(&env_var) {
+            Some((value, false))
+        } else if let Some(value) = prompt(param)? {
+            Some((value, true))
+        } else {
+            param
+                .default
+                .as_ref()
+                .map(|default| (value_to_display(default), true))
+        };
+
+        match value {
+            Some((value, needs_env)) => {
+                param
+                    .validate_value(&param.type_hint.coerce(Value::String(value.clone())))
+                    .with_context(|| format!("Invalid value for parameter '{}'", param.name))?;
+                if needs_env {
+                    secrets.env.insert(env_var.clone(), value);
+                }
+            }
+            None if required => {
+                anyhow::bail!(
+                    "Required secret parameter '{}' not provided (set {} or run interactively)\n\nExpected parameters:\n{}",
+                    param.name,
+                    env_var,
+                    definitions.describe()
+                );
+            }
+            None => {}
+        }
+
+        secrets.placeholders.insert(
+            param.name.clone(),
+            Value::String(format!("${{{}}}", env_var)),
+        );
+    }
+
+    Ok(secrets)
+}
+
+/// Ask for a secret on the terminal with hidden input
+///
+/// Returns `None` without prompting when not attached to a terminal or when
+/// running in automation mode.
+pub fn prompt_secret_from_terminal(param: &Parameter) -> Result<Option<String>> {
+    if std::env::var("PRODIGY_AUTOMATION").is_ok_and(|v| v == "true") {
+        return Ok(None);
+    }
+
+    let term = console::Term::stderr();
+    if !term.is_term() || !std::io::stdin().is_terminal() {
+        return Ok(None);
+    }
+
+    let label = if param.description.is_empty() {
+        param.name.clone()
+    } else {
+        format!("{} ({})", param.name, param.description)
+    };
+    term.write_str(&format!("Enter {}: ", label))?;
+    let value = term
+        .read_secure_line()
+        .with_context(|| format!("Failed to read secret parameter '{}'", param.name))?;
+
+    Ok(Some(value).filter(|v| !v.is_empty()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cook::workflow::composition::ParameterType;
+
+    fn secret(name: &str, env: Option<&str>) -> Parameter {
+        Parameter {
+            name: name.to_string(),
+            type_hint: ParameterType::String,
+            description: String::new(),
+            default: None,
+            validation: None,
+            values: vec![],
+            secret: true,
+            env: env.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_secrets_resolve_to_env_placeholders() {
+        let definitions = ParameterDefinitions {
+            required: vec![
+                secret("api-token", None),
+                secret("deploy_key", Some("DEPLOY_KEY")),
+            ],
+            optional: vec![secret("extra", None)],
+        };
+        let mut provided = HashMap::new();
+        provided.insert("api-token".to_string(), Value::from("from-cli"));
+        provided.insert("region".to_string(), Value::from("eu"));
+
+        let env = |name: &str| (name == "DEPLOY_KEY").then(|| "from-env".to_string());
+        let secrets =
+            resolve_secret_parameters(&definitions, &mut provided, env, |_| Ok(None)).unwrap();
+
+        assert!(!provided.contains_key("api-token"));
+        assert!(provided.contains_key("region"));
+        assert_eq!(
+            secrets.placeholders["api-token"],
+            Value::from("${PRODIGY_PARAM_API_TOKEN}")
+        );
+        assert_eq!(
+            secrets.placeholders["deploy_key"],
+            Value::from("${DEPLOY_KEY}")
+        );
+        assert_eq!(
+            secrets.placeholders["extra"],
+            Value::from("${PRODIGY_PARAM_EXTRA}")
+        );
+        assert_eq!(secrets.env.len(), 1);
+        assert_eq!(secrets.env["PRODIGY_PARAM_API_TOKEN"], "from-cli");
+    }
+
+    #[test]
+    fn test_missing_required_secret_prompts_then_fails() {
+        let definitions = ParameterDefinitions {
+            required: vec![secret("token", None)],
+            optional: vec![],
+        };
+
+        let secrets = resolve_secret_parameters(
+            &definitions,
+            &mut HashMap::new(),
+            |_| None,
+            |_| Ok(Some("typed".to_string())),
+        )
+        .unwrap();
+        assert_eq!(secrets.env["PRODIGY_PARAM_TOKEN"], "typed");
+
+        let err =
+            resolve_secret_parameters(&definitions, &mut HashMap::new(), |_| None, |_| Ok(None))
+                .unwrap_err();
+        assert!(err.to_string().starts_with(
+            "Required secret parameter 'token' not provided (set PRODIGY_PARAM_TOKEN"
+        ));
+        assert!(err.to_string().contains("token (string, secret, required)"));
+    }
+}
@@ -10,6 +10,9 @@ mod tests {
         // Test that when clause is properly parsed and stored
         let step = WorkflowStepCommand {
             claude: None,
+            model: None,
+            context: None,
+            validate_output: None,
             shell: Some("echo 'test'".to_string()),
             analyze: None,
             test: None,
@@ -28,6 +31,15 @@ mod tests {
             capture_format: None,
             capture_streams: None,
             output_file: None,
+            assert: None,
+            summarize_commits: None,
+            claude_chat: None,
+            script: None,
+            snapshot: false,
+            required: false,
+            commit_policy: None,
+            cwd: None,
+            env: std::collections::HashMap::new(),
         };
 
         assert_eq!(step.when, Some("${condition} == true".to_string()));
@@ -37,6 +49,9 @@ mod tests {
     async fn test_when_clause_with_complex_expression() {
         let step = WorkflowStepCommand {
             claude: Some("/prodigy-test".to_string()),
+            model: None,
+            context: None,
+            validate_output: None,
             shell: None,
             analyze: None,
             test: None,
@@ -55,6 +70,15 @@ mod tests {
             capture_format: None,
             capture_streams: None,
             output_file: None,
+            assert: None,
+            summarize_commits: None,
+            claude_chat: None,
+            script: None,
+            snapshot: false,
+            required: false,
+            commit_policy: None,
+            cwd: None,
+            env: std::collections::HashMap::new(),
         };
 
         assert!(step.when.is_some());
@@ -138,6 +162,9 @@ mod tests {
     fn test_workflow_step_with_when_serialization() {
         let step = WorkflowStepCommand {
             claude: Some("/prodigy-test".to_string()),
+            model: None,
+            context: None,
+            validate_output: None,
             shell: None,
             analyze: None,
             test: None,
@@ -156,6 +183,15 @@ mod tests {
             capture_format: None,
             capture_streams: None,
             output_file: None,
+            assert: None,
+            summarize_commits: None,
+            claude_chat: None,
+            script: None,
+            snapshot: false,
+            required: false,
+            commit_policy: None,
+            cwd: None,
+            env: std::collections::HashMap::new(),
         };
 
         let json = serde_json::to_string(&step).unwrap();
@@ -0,0 +1,396 @@
+//! Context packs for Claude steps
+//!
+//! A Claude step can list repository files or glob patterns under `context:`.
+//! Before the command runs, the executor reads the matching files and appends
+//! them to the prompt, so commands no longer rely on Claude rediscovering the
+//! same files on every step.
+//!
+//! Packs are bounded by a total byte budget and a per-file limit. Files that
+//! do not fit are cut down according to the configured [`TruncationStrategy`].
+//!
+//! ```yaml
+//! - claude: "/prodigy-implement-spec ${spec}"
+//!   context:
+//!     files: ["ARCHITECTURE.md", "src/config/*.rs"]
+//!     max_bytes: 40000
+//!     truncation: head
+//! ```
+
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::Path;
+
+/// Default total size of a context pack in bytes
+pub const DEFAULT_CONTEXT_MAX_BYTES: usize = 100_000;
+
+/// Default size limit for a single file in a context pack in bytes
+pub const DEFAULT_CONTEXT_MAX_FILE_BYTES: usize = 20_000;
+
+/// Context pack attached to a Claude step
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ContextPackConfig {
+    /// Files or glob patterns using the default budgets
+    Files(Vec<String>),
+    /// Files or glob patterns with explicit budgets
+    Detailed(ContextPackSpec),
+}
+
+impl ContextPackConfig {
+    /// Resolve the configuration into a full specification
+    pub fn spec(&self) -> ContextPackSpec {
+        match self {
+            ContextPackConfig::Files(files) => ContextPackSpec {
+                files: files.clone(),
+                ..ContextPackSpec::default()
+            },
+            ContextPackConfig::Detailed(spec) => spec.clone(),
+        }
+    }
+}
+
+/// Files to include and the budgets that bound them
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContextPackSpec {
+    /// Files or glob patterns, relative to the working directory
+    pub files: Vec<String>,
+
+    /// Maximum total bytes of file content in the pack
+    #[serde(default = "default_max_bytes")]
+    pub max_bytes: usize,
+
+    /// Maximum bytes included from any single file
+    #[serde(default = "default_max_file_bytes")]
+    pub max_file_bytes: usize,
+
+    /// How files that exceed a budget are cut down
+    #[serde(default)]
+    pub truncation: TruncationStrategy,
+}
+
+impl Default for ContextPackSpec {
+    fn default() -> Self {
+        Self {
+            files: Vec::new(),
+            max_bytes: DEFAULT_CONTEXT_MAX_BYTES,
+            max_file_bytes: DEFAULT_CONTEXT_MAX_FILE_BYTES,
+            truncation: TruncationStrategy::default(),
+        }
+    }
+}
+
+fn default_max_bytes() -> usize {
+    DEFAULT_CONTEXT_MAX_BYTES
+}
+
+fn default_max_file_bytes() -> usize {
+    DEFAULT_CONTEXT_MAX_FILE_BYTES
+}
+
+/// How a file that exceeds a budget is included
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TruncationStrategy {
+    /// Keep the beginning of the file
+    #[default]
+    Head,
+    /// Keep the end of the file
+    Tail,
+    /// Leave the file out entirely
+    Skip,
+}
+
+/// A file included in a context pack
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContextFile {
+    /// Path relative to the working directory
+    pub path: String,
+    /// Included content, possibly truncated
+    pub content: String,
+    /// Whether the content was cut to fit a budget
+    pub truncated: bool,
+}
+
+/// Files selected for a Claude command
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ContextPack {
+    /// Files included in the prompt
+    pub files: Vec<ContextFile>,
+    /// Files left out to stay within the budget, or because they are not text
+    pub omitted: Vec<String>,
+}
+
+impl ContextPack {
+    /// Whether the pack contributes nothing to the prompt
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty() && self.omitted.is_empty()
+    }
+
+    /// Render the pack as a prompt section
+    pub fn render(&self) -> String {
+        let mut out = String::from("<context>\n");
+        for file in &self.files {
+            if file.truncated {
+                out.push_str(&format!(
+                    "<file path=\"{}\" truncated=\"true\">\n",
+                    file.path
+                ));
+            } else {
+                out.push_str(&format!("<file path=\"{}\">\n", file.path));
+            }
+            out.push_str(&file.content);
+            if !file.content.ends_with('\n') {
+                out.push('\n');
+            }
+            out.push_str("</file>\n");
+        }
+        if !self.omitted.is_empty() {
+            out.push_str(&format!("<omitted>{}</omitted>\n", self.omitted.join(", ")));
+        }
+        out.push_str("</context>");
+        out
+    }
+}
+
+/// Select files for a pack, applying the per-file and total budgets (pure function)
+///
+/// `files` are `(path, content)` pairs in inclusion order.
+pub fn fit_to_budget(files: Vec<(String, String)>, spec: &ContextPackSpec) -> ContextPack {
+    let mut pack = ContextPack::default();
+    let mut remaining = spec.max_bytes;
+
+    for (path, content) in files {
+        let limit = spec.max_file_bytes.min(remaining);
+        if content.len() <= limit {
+            remaining -= content.len();
+            pack.files.push(ContextFile {
+                path,
+                content,
+                truncated: false,
+            });
+            continue;
+        }
+
+        if spec.truncation == TruncationStrategy::Skip || limit == 0 {
+            pack.omitted.push(path);
+            continue;
+        }
+
+        let content = truncate(&content, limit, spec.truncation);
+        remaining -= content.len();
+        pack.files.push(ContextFile {
+            path,
+            content,
+            truncated: true,
+        });
+    }
+
+    pack
+}
+
+/// Cut `content` to at most `limit` bytes on a character boundary
+fn truncate(content: &str, limit: usize, strategy: TruncationStrategy) -> String {
+    match strategy {
+        TruncationStrategy::Tail => {
+            let mut start = content.len() - limit;
+            while !content.is_char_boundary(start) {
+                start += 1;
+            }
+            content[start..].to_string()
+        }
+        TruncationStrategy::Head | TruncationStrategy::Skip => {
+            let mut end = limit;
+            while !content.is_char_boundary(end) {
+                end -= 1;
+            }
+            content[..end].to_string()
+        }
+    }
+}
+
+/// Expand the spec's patterns and read the matching files
+///
+/// Patterns are resolved relative to `working_dir`. Each file is included
+/// once, in pattern order; files that are not valid UTF-8 are omitted.
+pub fn collect_context_pack(spec: &ContextPackSpec, working_dir: &Path) -> Result<ContextPack> {
+    let mut seen = HashSet::new();
+    let mut files = Vec::new();
+    let mut unreadable = Vec::new();
+
+    for pattern in &spec.files {
+        let full_pattern = working_dir.join(pattern);
+        let full_pattern = full_pattern.to_string_lossy();
+        let entries = glob::glob(&full_pattern)
+            .map_err(|e| anyhow!("Invalid context pattern '{}': {}", pattern, e))?;
+
+        let mut matched = false;
+        for entry in entries {
+            let path = entry.with_context(|| format!("Failed to read context '{pattern}'"))?;
+            if !path.is_file() {
+                continue;
+            }
+            matched = true;
+
+            let relative = path
+                .strip_prefix(working_dir)
+                .unwrap_or(&path)
+                .to_string_lossy()
+                .to_string();
+            if !seen.insert(relative.clone()) {
+                continue;
+            }
+
+            match std::fs::read(&path) {
+                Ok(bytes) => match String::from_utf8(bytes) {
+                    Ok(content) => files.push((relative, content)),
+                    Err(_) => unreadable.push(relative),
+                },
+                Err(e) => {
+                    tracing::warn!("Skipping context file {}: {}", relative, e);
+                    unreadable.push(relative);
+                }
+            }
+        }
+
+        if !matched {
+            tracing::warn!("Context pattern '{}' matched no files", pattern);
+        }
+    }
+
+    let mut pack = fit_to_budget(files, spec);
+    pack.omitted.extend(unreadable);
+    Ok(pack)
+}
+
+/// Append the step's context pack to a Claude command
+///
+/// The command is returned unchanged when no files match.
+pub fn inject_context(command: &str, spec: &ContextPackSpec, working_dir: &Path) -> Result<String> {
+    let pack = collect_context_pack(spec, working_dir)?;
+    if pack.is_empty() {
+        return Ok(command.to_string());
+    }
+
+    tracing::debug!(
+        "Injecting {} context file(s) into Claude command ({} omitted)",
+        pack.files.len(),
+        pack.omitted.len()
+    );
+    Ok(format!("{}\n\n{}", command, pack.render()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn spec(
+        max_bytes: usize,
+        max_file_bytes: usize,
+        truncation: TruncationStrategy,
+    ) -> ContextPackSpec {
+        ContextPackSpec {
+            files: Vec::new(),
+            max_bytes,
+            max_file_bytes,
+            truncation,
+        }
+    }
+
+    fn file(path: &str, content: &str) -> (String, String) {
+        (path.to_string(), content.to_string())
+    }
+
+    #[test]
+    fn test_parse_config_forms() {
+        let short: ContextPackConfig = serde_yaml::from_str("[\"src/*.rs\", README.md]").unwrap();
+        let short = short.spec();
+        assert_eq!(short.files, vec!["src/*.rs", "README.md"]);
+        assert_eq!(short.max_bytes, DEFAULT_CONTEXT_MAX_BYTES);
+
+        let detailed: ContextPackConfig =
+            serde_yaml::from_str("files: [docs/*.md]\nmax_bytes: 500\ntruncation: tail\n").unwrap();
+        let detailed = detailed.spec();
+        assert_eq!(detailed.max_bytes, 500);
+        assert_eq!(detailed.max_file_bytes, DEFAULT_CONTEXT_MAX_FILE_BYTES);
+        assert_eq!(detailed.truncation, TruncationStrategy::Tail);
+    }
+
+    #[test]
+    fn test_fit_to_budget_truncates_per_file() {
+        let pack = fit_to_budget(
+            vec![file("a", "0123456789"), file("b", "abc")],
+            &spec(100, 4, TruncationStrategy::Head),
+        );
+        assert_eq!(pack.files[0].content, "0123");
+        assert!(pack.files[0].truncated);
+        assert_eq!(pack.files[1].content, "abc");
+        assert!(!pack.files[1].truncated);
+
+        let pack = fit_to_budget(
+            vec![file("a", "0123456789")],
+            &spec(100, 4, TruncationStrategy::Tail),
+        );
+        assert_eq!(pack.files[0].content, "6789");
+    }
+
+    #[test]
+    fn test_fit_to_budget_respects_total() {
+        let pack = fit_to_budget(
+            vec![file("a", "123456"), file("b", "abcdef"), file("c", "x")],
+            &spec(8, 100, TruncationStrategy::Head),
+        );
+        assert_eq!(pack.files.len(), 2);
+        assert_eq!(pack.files[1].content, "ab");
+        assert_eq!(pack.omitted, vec!["c"]);
+
+        let pack = fit_to_budget(
+            vec![file("a", "123456"), file("b", "abcdef"), file("c", "x")],
+            &spec(8, 100, TruncationStrategy::Skip),
+        );
+        let paths: Vec<_> = pack.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, vec!["a", "c"]);
+        assert_eq!(pack.omitted, vec!["b"]);
+    }
+
+    #[test]
+    fn test_truncate_keeps_char_boundaries() {
+        assert_eq!(truncate("héllo", 2, TruncationStrategy::Head), "h");
+        assert_eq!(truncate("hellé", 2, TruncationStrategy::Tail), "é");
+    }
+
+    #[test]
+    fn test_inject_context_reads_globs() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir(temp_dir.path().join("src")).unwrap();
+        std::fs::write(temp_dir.path().join("src/a.rs"), "fn a() {}\n").unwrap();
+        std::fs::write(temp_dir.path().join("src/b.rs"), "fn b() {}\n").unwrap();
+        std::fs::write(temp_dir.path().join("blob.bin"), [0xff, 0xfe]).unwrap();
+
+        let spec = ContextPackSpec {
+            files: vec!["src/*.rs".into(), "src/a.rs".into(), "blob.bin".into()],
+            ..ContextPackSpec::default()
+        };
+        let command = inject_context("/implement", &spec, temp_dir.path()).unwrap();
+
+        assert!(command.starts_with("/implement\n\n<context>\n"));
+        assert!(command.contains("<file path=\"src/a.rs\">\nfn a() {}\n</file>"));
+        assert!(command.contains("<file path=\"src/b.rs\">"));
+        assert_eq!(command.matches("src/a.rs").count(), 1);
+        assert!(command.contains("<omitted>blob.bin</omitted>"));
+    }
+
+    #[test]
+    fn test_inject_context_without_matches_leaves_command() {
+        let temp_dir = TempDir::new().unwrap();
+        let spec = ContextPackSpec {
+            files: vec!["missing/*.md".into()],
+            ..ContextPackSpec::default()
+        };
+        assert_eq!(
+            inject_context("/implement", &spec, temp_dir.path()).unwrap(),
+            "/implement"
+        );
+    }
+}
@@ -0,0 +1,813 @@
+//! Markdown documentation generated from a workflow file
+//!
+//! `prodigy docs <workflow.yml>` describes a workflow from its parsed
+//! configuration: the steps of each phase, the parameters it declares, the
+//! variables its steps produce and read, the Claude commands it needs and the
+//! external tools its shell commands invoke. Regenerating the page whenever
+//! the YAML changes keeps runbooks in sync with the workflow.
+
+use super::composition::{Parameter, ParameterDefinitions, ParameterType};
+use super::{CaptureOutput, WorkflowStep};
+use crate::config::mapreduce::MapReduceWorkflowConfig;
+use crate::config::{WorkflowCommand, WorkflowConfig};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+
+static VARIABLE_REFERENCE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\$\{([^}]+)\}").expect("Valid regex pattern"));
+
+const UNKNOWN_STEP: &str = "unknown step";
+
+/// Shell keywords and builtins that are not external tools
+const SHELL_BUILTINS: &[&str] = &[
+    "!", ".", ":", "[", "[[", "{", "}", "(", ")", "alias", "break", "case", "cd", "command",
+    "continue", "declare", "do", "done", "echo", "elif", "else", "esac", "eval", "exec", "exit",
+    "export", "false", "fi", "for", "function", "if", "in", "local", "printf", "pwd", "read",
+    "readonly", "return", "set", "shift", "source", "test", "then", "trap", "true", "type",
+    "unset", "until", "wait", "while",
+];
+
+/// Everything documented about a workflow
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkflowDoc {
+    pub name: String,
+    pub mapreduce: bool,
+    pub parameters: Vec<ParameterDoc>,
+    /// Environment variables with their values; secrets are not shown
+    pub env: Vec<(String, String)>,
+    pub map_input: Option<MapInputDoc>,
+    pub sections: Vec<SectionDoc>,
+}
+
+/// A declared workflow parameter
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParameterDoc {
+    pub name: String,
+    pub type_name: String,
+    pub required: bool,
+    pub default: Option<String>,
+    pub description: String,
+}
+
+/// Where a MapReduce job's work items come from
+#[derive(Debug, Clone, PartialEq)]
+pub struct MapInputDoc {
+    pub input: String,
+    pub json_path: String,
+    pub max_parallel: String,
+    pub filter: Option<String>,
+}
+
+/// Steps of one phase, e.g. setup or the agent template
+#[derive(Debug, Clone, PartialEq)]
+pub struct SectionDoc {
+    pub title: String,
+    /// Prefix for step references, e.g. `setup` in "setup 2"
+    pub label: String,
+    pub steps: Vec<StepDoc>,
+}
+
+/// What one step does and which variables, commands and tools it involves
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StepDoc {
+    pub description: String,
+    pub when: Option<String>,
+    pub handlers: Vec<String>,
+    pub produces: Vec<String>,
+    pub reads: Vec<String>,
+    pub claude_commands: Vec<String>,
+    pub tools: Vec<String>,
+}
+
+/// Document a standard workflow
+pub fn standard_doc(
+    name: &str,
+    config: &WorkflowConfig,
+    parameters: Option<&ParameterDefinitions>,
+) -> WorkflowDoc {
+    let convert = |commands: &[WorkflowCommand]| -> Vec<StepDoc> {
+        commands.iter().map(command_doc).collect()
+    };
+
+    let mut sections = vec![SectionDoc {
+        title: "Steps".to_string(),
+        label: "step".to_string(),
+        steps: convert(&config.commands),
+    }];
+    if !config.always.is_empty() {
+        sections.push(SectionDoc {
+            title: "Always".to_string(),
+            label: "always".to_string(),
+            steps: convert(&config.always),
+        });
+    }
+
+    WorkflowDoc {
+        name: config.name.clone().unwrap_or_else(|| name.to_string()),
+        mapreduce: false,
+        parameters: parameter_docs(parameters),
+        env: env_docs(config.env.as_ref(), config.secrets.as_ref()),
+        map_input: None,
+        sections,
+    }
+}
+
+/// Document a standard workflow command
+fn command_doc(cmd: &WorkflowCommand) -> StepDoc {
+    let mut doc = step_doc(&command_step(cmd));
+    if let WorkflowCommand::WorkflowStep(command) = cmd {
+        if command.analyze.is_some() && doc.description == UNKNOWN_STEP {
+            doc.description = "analyze".to_string();
+        }
+        if let (Some(id), Some(outputs)) = (&command.id, &command.outputs) {
+            doc.produces
+                .extend(outputs.keys().map(|output| format!("{}.{}", id, output)));
+            doc.produces.sort();
+        }
+    }
+    doc
+}
+
+/// Step a standard workflow command describes
+///
+/// Step conversion drops fields that only the YAML step format has, so they
+/// are copied back from the command.
+fn command_step(cmd: &WorkflowCommand) -> WorkflowStep {
+    let mut step = crate::cook::orchestrator::convert_command_to_step(cmd);
+    if let WorkflowCommand::WorkflowStep(command) = cmd {
+        step.when = command.when.clone();
+        step.write_file = command.write_file.clone();
+        step.on_success = command
+            .on_success
+            .as_ref()
+            .map(|next| Box::new(command_step(&WorkflowCommand::WorkflowStep(next.clone()))));
+    }
+    step
+}
+
+/// Document a MapReduce workflow
+pub fn mapreduce_doc(
+    config: &MapReduceWorkflowConfig,
+    parameters: Option<&ParameterDefinitions>,
+) -> WorkflowDoc {
+    let section = |title: &str, label: &str, steps: &[WorkflowStep]| SectionDoc {
+        title: title.to_string(),
+        label: label.to_string(),
+        steps: steps.iter().map(step_doc).collect(),
+    };
+
+    let mut sections = Vec::new();
+    if let Some(setup) = &config.setup {
+        let mut setup_section = section("Setup", "setup", &setup.commands);
+        if let Some(last) = setup_section.steps.last_mut() {
+            last.produces.extend(setup.capture_outputs.keys().cloned());
+            last.produces.sort();
+        }
+        sections.push(setup_section);
+    }
+    sections.push(section("Map", "map", &config.map.agent_template.commands));
+    if let Some(reduce) = &config.reduce {
+        sections.push(section("Reduce", "reduce", &reduce.commands));
+    }
+    if !config.always.is_empty() {
+        sections.push(section("Always", "always", &config.always));
+    }
+
+    WorkflowDoc {
+        name: config.name.clone(),
+        mapreduce: true,
+        parameters: parameter_docs(parameters),
+        env: env_docs(config.env.as_ref(), config.secrets.as_ref()),
+        map_input: Some(MapInputDoc {
+            input: config.map.input.clone(),
+            json_path: config.map.json_path.clone(),
+            max_parallel: config
+                .map
+                .max_parallel
+                .clone()
+                .unwrap_or_else(|| crate::config::mapreduce::DEFAULT_MAX_PARALLEL.to_string()),
+            filter: config.map.filter.clone(),
+        }),
+        sections,
+    }
+}
+
+fn parameter_docs(parameters: Option<&ParameterDefinitions>) -> Vec<ParameterDoc> {
+    let Some(parameters) = parameters else {
+        return Vec::new();
+    };
+    let display = |value: &serde_json::Value| match value {
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    };
+    let describe = |param: &Parameter, required: bool| {
+        let mut type_name = format!("{:?}", param.type_hint).to_lowercase();
+        if param.type_hint == ParameterType::Enum {
+            let values: Vec<String> = param.values.iter().map(display).collect();
+            type_name = format!("{}: {}", type_name, values.join(", "));
+        }
+        if param.secret {
+            type_name.push_str(", secret");
+        }
+        ParameterDoc {
+            name: param.name.clone(),
+            type_name,
+            required,
+            default: param.default.as_ref().map(|value| {
+                if param.secret {
+                    "***".to_string()
+                } else {
+                    display(value)
+                }
+            }),
+            description: param.description.clone(),
+        }
+    };
+    parameters
+        .required
+        .iter()
+        .map(|p| describe(p, true))
+        .chain(parameters.optional.iter().map(|p| describe(p, false)))
+        .collect()
+}
+
+fn env_docs<S>(
+    env: Option<&std::collections::HashMap<String, String>>,
+    secrets: Option<&std::collections::HashMap<String, S>>,
+) -> Vec<(String, String)> {
+    let mut entries: Vec<(String, String)> = env
+        .into_iter()
+        .flatten()
+        .map(|(name, value)| (name.clone(), value.clone()))
+        .chain(
+            secrets
+                .into_iter()
+                .flat_map(|secrets| secrets.keys())
+                .map(|name| (name.clone(), "(secret)".to_string())),
+        )
+        .collect();
+    entries.sort();
+    entries
+}
+
+/// Document a single step (pure function)
+pub fn step_doc(step: &WorkflowStep) -> StepDoc {
+    let mut doc = StepDoc {
+        description: describe_step(step),
+        when: step.when.clone(),
+        ..Default::default()
+    };
+
+    let mut claude: Vec<&str> = Vec::new();
+    let mut shell: Vec<&str> = Vec::new();
+    let mut templates: Vec<&str> = Vec::new();
+
+    claude.extend(step.claude.as_deref());
+    claude.extend(step.command.as_deref());
+    shell.extend(step.shell.as_deref());
+    if let Some(test) = &step.test {
+        shell.push(&test.command);
+    }
+    if let Some(write_file) = &step.write_file {
+        templates.push(&write_file.path);
+        templates.push(&write_file.content);
+    }
+    if let Some(script) = &step.script {
+        templates.push(&script.code);
+    }
+    if let Some(chat) = &step.claude_chat {
+        claude.extend(chat.messages.iter().map(String::as_str));
+        doc.produces.push(chat.variable_name().to_string());
+    }
+    if let Some(summary) = &step.summarize_commits {
+        claude.extend(summary.claude.as_deref());
+        doc.produces.push(summary.variable_name().to_string());
+    }
+    if let Some(validate) = &step.validate {
+        claude.extend(validate.claude.as_deref());
+        shell.extend(validate.shell.as_deref());
+        if let Some(on_incomplete) = &validate.on_incomplete {
+            claude.extend(on_incomplete.claude.as_deref());
+            shell.extend(on_incomplete.shell.as_deref());
+        }
+    }
+
+    let mut handlers: Vec<(Option<String>, Option<String>)> = Vec::new();
+    if let Some(on_failure) = &step.on_failure {
+        handlers.extend(
+            on_failure
+                .handler_commands()
+                .into_iter()
+                .map(|handler| (handler.claude, handler.shell)),
+        );
+    }
+    if let Some(debug) = step.test.as_ref().and_then(|t| t.on_failure.as_ref()) {
+        handlers.push((debug.debug_command(), None));
+    }
+    for (handler_claude, handler_shell) in &handlers {
+        if let Some(cmd) = handler_claude {
+            doc.handlers.push(format!("on failure: claude: {}", cmd));
+            claude.push(cmd);
+        }
+        if let Some(cmd) = handler_shell {
+            doc.handlers
+                .push(format!("on failure: shell: {}", first_line(cmd)));
+            shell.push(cmd);
+        }
+    }
+
+    if let Some(capture) = &step.capture {
+        doc.produces.push(capture.clone());
+    }
+    if let CaptureOutput::Variable(name) = &step.capture_output {
+        doc.produces.push(name.clone());
+    }
+
+    let mut reads = BTreeSet::new();
+    for text in claude.iter().chain(&shell).chain(&templates) {
+        reads.extend(variable_references(text));
+    }
+    reads.extend(step.when.iter().flat_map(|w| variable_references(w)));
+    doc.reads = reads.into_iter().collect();
+
+    let mut claude_commands: BTreeSet<String> = claude
+        .iter()
+        .filter_map(|c| claude_command_name(c))
+        .collect();
+    let mut tools: BTreeSet<String> = shell.iter().flat_map(|s| shell_tools(s)).collect();
+
+    if let Some(on_success) = &step.on_success {
+        let nested = step_doc(on_success);
+        doc.handlers
+            .push(format!("on success: {}", nested.description));
+        doc.produces.extend(nested.produces);
+        doc.reads.extend(nested.reads);
+        claude_commands.extend(nested.claude_commands);
+        tools.extend(nested.tools);
+        doc.reads.sort();
+        doc.reads.dedup();
+    }
+
+    doc.produces.sort();
+    doc.produces.dedup();
+    doc.claude_commands = claude_commands.into_iter().collect();
+    doc.tools = tools.into_iter().collect();
+    doc
+}
+
+/// One-line description of a step (pure function)
+fn describe_step(step: &WorkflowStep) -> String {
+    if let Some(cmd) = &step.claude {
+        format!("claude: {}", first_line(cmd))
+    } else if let Some(cmd) = &step.shell {
+        format!("shell: {}", first_line(cmd))
+    } else if let Some(test) = &step.test {
+        format!("shell: {}", first_line(&test.command))
+    } else if let Some(chat) = &step.claude_chat {
+        format!("claude_chat: {} messages", chat.messages.len())
+    } else if let Some(script) = &step.script {
+        format!("script: {}", script.lang)
+    } else if let Some(write_file) = &step.write_file {
+        format!("write_file: {}", write_file.path)
+    } else if step.foreach.is_some() {
+        "foreach".to_string()
+    } else if let Some(handler) = &step.handler {
+        format!("handler: {}", handler.name)
+    } else if step.assert.is_some() {
+        "assert".to_string()
+    } else if step.summarize_commits.is_some() {
+        "summarize_commits".to_string()
+    } else if let Some(command) = &step.command {
+        format!("claude: {}", command)
+    } else if let Some(name) = &step.name {
+        name.clone()
+    } else {
+        UNKNOWN_STEP.to_string()
+    }
+}
+
+fn first_line(text: &str) -> String {
+    let mut lines = text.trim().lines();
+    let first = lines.next().unwrap_or_default().trim().to_string();
+    if lines.next().is_some() {
+        format!("{} ...", first)
+    } else {
+        first
+    }
+}
+
+/// Variables a template reads through `${...}` (pure function)
+///
+/// Defaults (`${name:-value}`) and filters (`${name | json}`) are stripped.
+pub fn variable_references(template: &str) -> Vec<String> {
+    VARIABLE_REFERENCE_REGEX
+        .captures_iter(template)
+        .filter_map(|cap| {
+            let name = cap[1].split([':', '|', ' ']).next()?.trim();
+            (!name.is_empty()).then(|| name.to_string())
+        })
+        .collect()
+}
+
+/// Slash command a Claude step invokes, if any (pure function)
+pub fn claude_command_name(command: &str) -> Option<String> {
+    command
+        .split_whitespace()
+        .next()
+        .filter(|name| name.starts_with('/') && name.len() > 1)
+        .map(String::from)
+}
+
+/// External programs a shell command invokes (pure function)
+///
+/// Looks at the first word of every pipeline stage and command list entry,
+/// skipping variable assignments, shell keywords, builtins, quoted text and
+/// heredoc bodies.
+pub fn shell_tools(command: &str) -> Vec<String> {
+    command_segments(&strip_heredocs(command))
+        .iter()
+        .filter_map(|segment| {
+            segment
+                .split_whitespace()
+                .map(|w| w.trim_start_matches(['(', '{', '!']))
+                .find(|w| !w.is_empty() && !is_assignment(w) && !is_keyword(w))
+                .filter(|w| !w.starts_with(['$', '"', '\'', '`', '-', '#']) && !is_builtin(w))
+                .map(String::from)
+        })
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+/// Remove the bodies of `<<EOF` heredocs, keeping the commands around them
+fn strip_heredocs(command: &str) -> String {
+    let mut kept = Vec::new();
+    let mut delimiter: Option<String> = None;
+    for line in command.lines() {
+        if let Some(end) = &delimiter {
+            if line.trim() == end {
+                delimiter = None;
+            }
+            continue;
+        }
+        delimiter = line.split_once("<<").and_then(|(_, rest)| {
+            let word = rest
+                .trim_start_matches(['-', '~'])
+                .split_whitespace()
+                .next()?;
+            let word = word.trim_matches(['\'', '"']);
+            (!word.is_empty() && !word.starts_with('<')).then(|| word.to_string())
+        });
+        kept.push(line);
+    }
+    kept.join("\n")
+}
+
+/// Split a command on separators outside quotes, dropping comments
+fn command_segments(command: &str) -> Vec<String> {
+    let mut segments = Vec::new();
+    let mut current = String::new();
+    let mut quote: Option<char> = None;
+    let mut comment = false;
+    for c in command.chars() {
+        if comment {
+            if c == '\n' {
+                comment = false;
+                segments.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"' | '`') => quote = Some(c),
+            (None, '#') if current.trim().is_empty() || current.ends_with(char::is_whitespace) => {
+                comment = true;
+                continue;
+            }
+            (None, '|' | ';' | '&' | '\n') => {
+                segments.push(std::mem::take(&mut current));
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    segments.push(current);
+    segments
+}
+
+fn is_assignment(word: &str) -> bool {
+    word.split_once('=').is_some_and(|(name, _)| {
+        !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    })
+}
+
+fn is_keyword(word: &str) -> bool {
+    matches!(
+        word,
+        "if" | "then" | "else" | "elif" | "do" | "while" | "until" | "time"
+    )
+}
+
+fn is_builtin(word: &str) -> bool {
+    SHELL_BUILTINS.contains(&word)
+}
+
+/// Step reference used in the variable and command tables
+fn step_ref(section: &SectionDoc, index: usize) -> String {
+    format!("{} {}", section.label, index + 1)
+}
+
+/// Render a workflow's documentation as markdown (pure function)
+pub fn render_markdown(doc: &WorkflowDoc, source: &str) -> String {
+    let mut out = String::new();
+    let kind = if doc.mapreduce {
+        "MapReduce workflow"
+    } else {
+        "Workflow"
+    };
+    let _ = writeln!(out, "# {}\n", doc.name);
+    let _ = writeln!(
+        out,
+        "{} generated by `prodigy docs` from `{}`. Regenerate it after changing the workflow.\n",
+        kind, source
+    );
+
+    if !doc.parameters.is_empty() {
+        out.push_str("## Parameters\n\n");
+        out.push_str("| Name | Type | Required | Default | Description |\n");
+        out.push_str("|------|------|----------|---------|-------------|\n");
+        for param in &doc.parameters {
+            let _ = writeln!(
+                out,
+                "| `{}` | {} | {} | {} | {} |",
+                param.name,
+                param.type_name,
+                if param.required { "yes" } else { "no" },
+                param
+                    .default
+                    .as_deref()
+                    .map(|d| format!("`{}`", d))
+                    .unwrap_or_default(),
+                table_cell(&param.description)
+            );
+        }
+        out.push('\n');
+    }
+
+    if !doc.env.is_empty() {
+        out.push_str("## Environment\n\n");
+        out.push_str("| Variable | Value |\n|----------|-------|\n");
+        for (name, value) in &doc.env {
+            let _ = writeln!(out, "| `{}` | `{}` |", name, table_cell(value));
+        }
+        out.push('\n');
+    }
+
+    for section in &doc.sections {
+        let _ = writeln!(out, "## {}\n", section.title);
+        if section.label == "map" {
+            if let Some(input) = &doc.map_input {
+                let _ = write!(out, "One agent per item of `{}`", input.input);
+                if !input.json_path.is_empty() {
+                    let _ = write!(out, " at `{}`", input.json_path);
+                }
+                if let Some(filter) = &input.filter {
+                    let _ = write!(out, " matching `{}`", filter);
+                }
+                let _ = writeln!(out, ", up to {} in parallel.\n", input.max_parallel);
+            }
+        }
+        if section.steps.is_empty() {
+            out.push_str("No steps.\n\n");
+            continue;
+        }
+        for (index, step) in section.steps.iter().enumerate() {
+            let _ = writeln!(out, "{}. `{}`", index + 1, step.description);
+            if let Some(when) = &step.when {
+                let _ = writeln!(out, "   - Runs when `{}`", when);
+            }
+            for handler in &step.handlers {
+                let _ = writeln!(out, "   - {}", capitalize(handler));
+            }
+            if !step.produces.is_empty() {
+                let _ = writeln!(out, "   - Sets {}", code_list(&step.produces));
+            }
+            if !step.reads.is_empty() {
+                let _ = writeln!(out, "   - Reads {}", code_list(&step.reads));
+            }
+        }
+        out.push('\n');
+    }
+
+    render_variables(&mut out, doc);
+    render_usage(
+        &mut out,
+        doc,
+        "Claude Commands",
+        "Each command must exist in `.claude/commands`.",
+        |step| &step.claude_commands,
+    );
+    render_usage(
+        &mut out,
+        doc,
+        "External Tools",
+        "Programs the shell commands invoke, which must be installed where the workflow runs.",
+        |step| &step.tools,
+    );
+    out.truncate(out.trim_end().len());
+    out.push('\n');
+    out
+}
+
+fn render_variables(out: &mut String, doc: &WorkflowDoc) {
+    let mut produced: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+    let mut read: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+    for section in &doc.sections {
+        for (index, step) in section.steps.iter().enumerate() {
+            for name in &step.produces {
+                produced
+                    .entry(name)
+                    .or_default()
+                    .push(step_ref(section, index));
+            }
+            for name in &step.reads {
+                read.entry(name).or_default().push(step_ref(section, index));
+            }
+        }
+    }
+    if produced.is_empty() && read.is_empty() {
+        return;
+    }
+
+    out.push_str("## Variables\n\n");
+    out.push_str("| Variable | Set by | Read by |\n|----------|--------|---------|\n");
+    let names: BTreeSet<&str> = produced.keys().chain(read.keys()).copied().collect();
+    for name in names {
+        let set_by = produced
+            .get(name)
+            .map(|steps| steps.join(", "))
+            .unwrap_or_else(|| external_source(name, doc).to_string());
+        let read_by = read.get(name).map(|steps| steps.join(", "));
+        let _ = writeln!(
+            out,
+            "| `{}` | {} | {} |",
+            name,
+            set_by,
+            read_by.unwrap_or_default()
+        );
+    }
+    out.push('\n');
+}
+
+/// Where a variable no step sets comes from (pure function)
+fn external_source(name: &str, doc: &WorkflowDoc) -> &'static str {
+    let root = name.split(['.', '[']).next().unwrap_or(name);
+    if doc.parameters.iter().any(|p| p.name == root) || root == "params" {
+        "parameter"
+    } else if root == "env" || doc.env.iter().any(|(env, _)| env == root) {
+        "environment"
+    } else if root == "shell" || root == "claude" {
+        "previous step"
+    } else if doc.mapreduce && root == "item" {
+        "work item"
+    } else if doc.mapreduce && root == "map" {
+        "map phase"
+    } else {
+        "Prodigy or caller"
+    }
+}
+
+fn render_usage(
+    out: &mut String,
+    doc: &WorkflowDoc,
+    title: &str,
+    intro: &str,
+    items: impl Fn(&StepDoc) -> &Vec<String>,
+) {
+    let mut used: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+    for section in &doc.sections {
+        for (index, step) in section.steps.iter().enumerate() {
+            for item in items(step) {
+                used.entry(item).or_default().push(step_ref(section, index));
+            }
+        }
+    }
+    if used.is_empty() {
+        return;
+    }
+
+    let _ = writeln!(out, "## {}\n\n{}\n", title, intro);
+    for (item, steps) in used {
+        let _ = writeln!(out, "- `{}` ({})", item, steps.join(", "));
+    }
+    out.push('\n');
+}
+
+fn code_list(names: &[String]) -> String {
+    names
+        .iter()
+        .map(|name| format!("`{}`", name))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn table_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shell_tools_skips_builtins_and_assignments() {
+        let tools = shell_tools(
+            "RUST_LOG=debug cargo test --all | tee out.txt && echo done # not; rm\n\
+             if [ -f x ]; then jq '.items |\n  length' x; fi\n\
+             cat > out.json << 'EOF'\n\
+             {\"key\": true}\n\
+             EOF",
+        );
+        assert_eq!(tools, vec!["cargo", "cat", "jq", "tee"]);
+    }
+
+    #[test]
+    fn test_variable_references_strip_defaults() {
+        assert_eq!(
+            variable_references("echo ${item.path} ${mode:-fast} ${map.results | json}"),
+            vec!["item.path", "mode", "map.results"]
+        );
+    }
+
+    #[test]
+    fn test_standard_workflow_markdown() {
+        let yaml = r#"
+name: lint-and-fix
+env:
+  PROFILE: ci
+commands:
+  - shell: "cargo clippy --message-format json"
+    capture_output: lint_report
+  - claude: "/prodigy-fix-lints ${lint_report}"
+    when: "${PROFILE} == 'ci'"
+  - shell: "cargo test"
+    on_failure:
+      claude: "/prodigy-debug-test-failure --output ${shell.output}"
+"#;
+        let config: WorkflowConfig = serde_yaml::from_str(yaml).unwrap();
+        let doc = standard_doc("fallback", &config, None);
+        let markdown = render_markdown(&doc, "workflows/lint.yml");
+
+        assert!(markdown.starts_with("# lint-and-fix\n"));
+        assert!(markdown
+            .contains("1. `shell: cargo clippy --message-format json`\n   - Sets `lint_report`"));
+        assert!(markdown.contains("| `lint_report` | step 1 | step 2 |"));
+        assert!(markdown.contains("| `PROFILE` | environment | step 2 |"));
+        assert!(markdown.contains("- `/prodigy-debug-test-failure` (step 3)"));
+        assert!(markdown.contains("- `/prodigy-fix-lints` (step 2)"));
+        assert!(markdown.contains("- `cargo` (step 1, step 3)"));
+    }
+
+    #[test]
+    fn test_mapreduce_workflow_sections() {
+        let yaml = r#"
+name: fix-debt
+mode: mapreduce
+setup:
+  - shell: "debtmap analyze . --output items.json"
+map:
+  input: items.json
+  json_path: "$.items[*]"
+  max_parallel: 4
+  agent_template:
+    - claude: "/prodigy-fix-debt ${item.location}"
+reduce:
+  - shell: "echo ${map.successful} fixed"
+"#;
+        let config: MapReduceWorkflowConfig = serde_yaml::from_str(yaml).unwrap();
+        let markdown = render_markdown(&mapreduce_doc(&config, None), "debt.yml");
+
+        let titles: Vec<&str> = ["## Setup", "## Map", "## Reduce"]
+            .into_iter()
+            .filter(|t| markdown.contains(t))
+            .collect();
+        assert_eq!(titles.len(), 3);
+        assert!(markdown
+            .contains("One agent per item of `items.json` at `$.items[*]`, up to 4 in parallel."));
+        assert!(markdown.contains("| `item.location` | work item | map 1 |"));
+        assert!(markdown.contains("| `map.successful` | map phase | reduce 1 |"));
+        assert!(markdown.contains("- `debtmap` (setup 1)"));
+    }
+}
@@ -146,29 +146,67 @@ impl Default for DefaultShellRunner {
     }
 }
 
-#[async_trait]
-impl ShellRunner for DefaultShellRunner {
-    async fn run(
+impl DefaultShellRunner {
+    /// Run a shell command, passing each stdout line to `on_line` as it is produced
+    pub async fn run_with_line_handler(
         &self,
         command: &str,
         working_dir: &Path,
         env_vars: HashMap<String, String>,
         timeout: Option<u64>,
+        on_line: &(dyn Fn(&str) + Send + Sync),
     ) -> anyhow::Result<RunnerOutput> {
+        use std::process::Stdio;
+        use tokio::io::{AsyncBufReadExt, AsyncReadExt, BufReader};
         use tokio::process::Command;
         use tokio::time::{timeout as tokio_timeout, Duration};
 
         let mut cmd = Command::new("sh");
         cmd.args(["-c", command]);
         cmd.current_dir(working_dir);
+        cmd.stdin(Stdio::null());
+        cmd.stdout(Stdio::piped());
+        cmd.stderr(Stdio::piped());
 
         for (key, value) in env_vars {
             cmd.env(key, value);
         }
 
-        let output = if let Some(timeout_secs) = timeout {
+        let mut child = cmd.spawn()?;
+        let stdout = child.stdout.take();
+        let stderr = child.stderr.take();
+
+        let collect = async {
+            let read_stdout = async {
+                let mut collected = Vec::new();
+                if let Some(stdout) = stdout {
+                    let mut reader = BufReader::new(stdout);
+                    loop {
+                        let start = collected.len();
+                        if reader.read_until(b'\n', &mut collected).await? == 0 {
+                            break;
+                        }
+                        let line = String::from_utf8_lossy(&collected[start..]);
+                        on_line(line.trim_end_matches(['\n', '\r']));
+                    }
+                }
+                Ok::<_, std::io::Error>(collected)
+            };
+            let read_stderr = async {
+                let mut collected = Vec::new();
+                if let Some(mut stderr) = stderr {
+                    stderr.read_to_end(&mut collected).await?;
+                }
+                Ok::<_, std::io::Error>(collected)
+            };
+            let (stdout, stderr) = tokio::try_join!(read_stdout, read_stderr)?;
+            let status = child.wait().await?;
+            Ok::<_, std::io::Error>((stdout, stderr, status))
+        };
+
+        let (stdout, stderr, status) = if let Some(timeout_secs) = timeout {
             let duration = Duration::from_secs(timeout_secs);
-            match tokio_timeout(duration, cmd.output()).await {
+            match tokio_timeout(duration, collect).await {
                 Ok(result) => result?,
                 Err(_) => {
                     return Ok(RunnerOutput {
@@ -181,19 +219,33 @@ impl ShellRunner for DefaultShellRunner {
                 }
             }
         } else {
-            cmd.output().await?
+            collect.await?
         };
 
         Ok(RunnerOutput {
-            success: output.status.success(),
-            exit_code: output.status.code(),
-            stdout: String::from_utf8_lossy(&output.stdout).to_string(),
-            stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+            success: status.success(),
+            exit_code: status.code(),
+            stdout: String::from_utf8_lossy(&stdout).to_string(),
+            stderr: String::from_utf8_lossy(&stderr).to_string(),
             json_log_location: None,
         })
     }
 }
 
+#[async_trait]
+impl ShellRunner for DefaultShellRunner {
+    async fn run(
+        &self,
+        command: &str,
+        working_dir: &Path,
+        env_vars: HashMap<String, String>,
+        timeout: Option<u64>,
+    ) -> anyhow::Result<RunnerOutput> {
+        self.run_with_line_handler(command, working_dir, env_vars, timeout, &|_| {})
+            .await
+    }
+}
+
 /// Environment for workflow command execution
 ///
 /// Provides dependencies needed for executing workflow commands including
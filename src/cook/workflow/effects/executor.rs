@@ -255,6 +255,7 @@ mod tests {
             outputs: None,
             commit_required: false,
             when: None,
+            commit_policy: None,
         };
 
         let variables = HashMap::new();
@@ -162,6 +162,7 @@ mod tests {
             fail_workflow: false,
             retry_original: true,
             max_retries: 3,
+            escalate_model: None,
         };
 
         let handler = on_failure_to_error_handler(&on_failure, 1).unwrap();
@@ -360,6 +361,9 @@ mod tests {
             error_recovery_state: None,
             retry_checkpoint_state: None,
             variable_checkpoint_state: None,
+            failure_chains: Vec::new(),
+            repo: None,
+            step_selection: None,
         }
     }
 }
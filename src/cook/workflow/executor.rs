@@ -17,9 +17,14 @@
 //! - [`builder`]: Workflow executor builder pattern
 //! - [`types`]: Type definitions and utilities
 //! - [`failure_handler`]: Failure handling and recovery
+//! - [`always_steps`]: Cleanup steps that run whatever the workflow outcome
+//! - [`failure_chain`]: Ordered `on_failure` handler chains
+//! - [`output_limits`]: Step output size limits and truncation
 //!
 //! This organization separates concerns and makes the codebase easier to maintain and test.
 
+#[path = "executor/always_steps.rs"]
+pub(crate) mod always_steps;
 #[path = "executor/builder.rs"]
 mod builder;
 #[path = "executor/commands.rs"]
@@ -30,12 +35,16 @@ mod commit_handler;
 mod context;
 #[path = "executor/data_structures.rs"]
 mod data_structures;
+#[path = "executor/failure_chain.rs"]
+pub(crate) mod failure_chain;
 #[path = "executor/failure_handler.rs"]
 mod failure_handler;
 #[path = "executor/git_support.rs"]
 mod git_support;
 #[path = "executor/orchestration.rs"]
 mod orchestration;
+#[path = "executor/output_limits.rs"]
+pub(crate) mod output_limits;
 #[path = "executor/pure.rs"]
 mod pure;
 #[path = "executor/retry_logic.rs"]
@@ -57,6 +66,9 @@ use crate::abstractions::git::GitOperations;
 use crate::commands::AttributeValue;
 use crate::commands::CommandRegistry;
 use crate::cook::execution::interpolation::InterpolationContext;
+use crate::cook::execution::mapreduce::shared_context::{
+    self, SharedContextCache, SharedContextConfig,
+};
 use crate::cook::execution::ClaudeExecutor;
 use crate::cook::interaction::UserInteraction;
 use crate::cook::orchestrator::ExecutionEnvironment;
@@ -68,9 +80,10 @@ use crate::cook::workflow::checkpoint::{
 use crate::cook::workflow::normalized;
 use crate::cook::workflow::normalized::NormalizedWorkflow;
 use crate::cook::workflow::on_failure::OnFailureConfig;
+use crate::cook::workflow::score_gate::{self, ScoreDropAction, ScoreTracker};
 use crate::testing::config::TestConfiguration;
 use crate::unified_session::{format_duration, TimingTracker};
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, Context, Result};
 use once_cell::sync::Lazy;
 use regex::Regex;
 use std::collections::HashMap;
@@ -91,7 +104,7 @@ static UNBRACED_VAR_REGEX: Lazy<Regex> = Lazy::new(|| {
 use pure::IterationContinuation;
 
 // Re-export core types from types and context modules
-pub use context::WorkflowContext;
+pub use context::{format_undefined_variables, without_environment_variables, WorkflowContext};
 pub use types::{CaptureOutput, CommandType, StepResult, VariableResolution};
 
 // Re-export data structures for backward compatibility
@@ -125,6 +138,12 @@ pub struct WorkflowExecutor {
     current_workflow: Option<NormalizedWorkflow>,
     /// Current step index being executed (for checkpoint context)
     current_step_index: Option<usize>,
+    /// Name and step count of the workflow being executed (for mid-step checkpoints)
+    current_workflow_shape: Option<(String, usize)>,
+    /// `on_failure` chains in flight, outermost first
+    failure_chains: Vec<checkpoint::FailureChainState>,
+    /// `on_failure` chains recorded in the checkpoint being resumed
+    resumed_failure_chains: Vec<checkpoint::FailureChainState>,
     /// Git operations abstraction for testing
     git_operations: Arc<dyn GitOperations>,
     /// Resume context for handling interrupted workflows with error recovery state
@@ -147,6 +166,38 @@ pub struct WorkflowExecutor {
     positional_args: Option<Vec<String>>,
     /// Shutdown signal for graceful interruption handling (Spec 184)
     shutdown_signal: Arc<AtomicBool>,
+    /// Read-only mode - reject steps that would create commits
+    read_only: bool,
+    /// Keep-going mode - report failed steps as warnings and run the rest
+    keep_going: bool,
+    /// Steps selected with `--only-steps` and `--skip-steps` (all if empty)
+    step_selection: super::step_selection::StepSelection,
+    /// Record/replay session for Claude and shell command outputs
+    replay_session: Option<Arc<crate::cook::execution::ReplaySession>>,
+    /// Fault injector for Claude and shell commands (`--chaos`)
+    chaos: Option<Arc<crate::cook::execution::chaos::ChaosMonkey>>,
+    /// Recorder for JUnit XML output
+    junit: Option<Arc<crate::cook::execution::junit::JunitRecorder>>,
+    /// Step profiler for `--profile-steps`
+    step_profiler: Option<Arc<crate::cook::execution::step_profile::StepProfiler>>,
+    /// Paths no step may change
+    protected_paths: Option<Arc<crate::cook::protected_paths::ProtectedPaths>>,
+    /// Seed of a deterministic run (`--seed`)
+    seed: Option<u64>,
+    /// Health score measured between iterations, from the workflow's `score_gate:`
+    score_gate: Option<Arc<crate::cook::workflow::score_gate::ScoreGate>>,
+    /// Receives the step whose failure ends the run
+    failures: Option<Arc<super::failure_report::FailureRecorder>>,
+    /// Tool permissions for Claude commands, from the workflow's `claude_permissions:`
+    claude_permissions: Option<crate::cook::execution::claude_permissions::ClaudePermissions>,
+    /// Model a Claude step escalated to, recorded with the step result
+    last_claude_model: Option<String>,
+    /// Git changes of the step that just ran, recorded with its checkpoint
+    last_step_changes: Option<crate::cook::workflow::git_context::StepChanges>,
+    /// Result of the step that just failed, for the failure report
+    failed_step_result: Option<StepResult>,
+    /// Size limits for step output kept in variables and checkpoints
+    output_settings: crate::config::OutputSettings,
 }
 
 impl WorkflowExecutor {
@@ -170,9 +221,13 @@ impl WorkflowExecutor {
             ctx.variables.insert(key, value);
         }
 
-        // 2. Execute handler (new or legacy)
+        // 2. Execute handler (chain, new or legacy)
         let handler_commands = on_failure_config.handler_commands();
-        if !handler_commands.is_empty() {
+        if let Some(chain) = on_failure_config.chain() {
+            result = self
+                .execute_failure_chain(step, result, chain, env, ctx)
+                .await?;
+        } else if !handler_commands.is_empty() {
             result = self
                 .handle_new_style_failure(
                     step,
@@ -392,6 +447,14 @@ impl WorkflowExecutor {
             Ok(CommandType::Foreach(foreach_config.clone()))
         } else if let Some(write_file_config) = &step.write_file {
             Ok(CommandType::WriteFile(write_file_config.clone()))
+        } else if let Some(assert_config) = &step.assert {
+            Ok(CommandType::Assert(assert_config.clone()))
+        } else if let Some(summary_config) = &step.summarize_commits {
+            Ok(CommandType::SummarizeCommits(summary_config.clone()))
+        } else if let Some(chat_config) = &step.claude_chat {
+            Ok(CommandType::ClaudeChat(chat_config.clone()))
+        } else if let Some(script_config) = &step.script {
+            Ok(CommandType::Script(script_config.clone()))
         } else if let Some(name) = &step.name {
             Ok(CommandType::Legacy(pure::normalize_legacy_command(name)))
         } else if let Some(command) = &step.command {
@@ -428,6 +491,14 @@ impl WorkflowExecutor {
             format!("handler: {}", handler_step.name)
         } else if let Some(write_file_config) = &step.write_file {
             format!("write_file: {}", write_file_config.path)
+        } else if step.assert.is_some() {
+            "assert".to_string()
+        } else if step.summarize_commits.is_some() {
+            "summarize_commits".to_string()
+        } else if step.claude_chat.is_some() {
+            "claude_chat".to_string()
+        } else if let Some(script) = &step.script {
+            format!("script: {}", script.lang)
         } else if let Some(name) = &step.name {
             name.clone()
         } else if let Some(command) = &step.command {
@@ -457,6 +528,14 @@ impl WorkflowExecutor {
         } else if let Some(write_file_config) = &step.write_file {
             let (interpolated, _) = ctx.interpolate_with_tracking(&write_file_config.path);
             format!("write_file: {interpolated}")
+        } else if step.assert.is_some() {
+            "assert".to_string()
+        } else if step.summarize_commits.is_some() {
+            "summarize_commits".to_string()
+        } else if step.claude_chat.is_some() {
+            "claude_chat".to_string()
+        } else if let Some(script) = &step.script {
+            format!("script: {}", script.lang)
         } else if let Some(name) = &step.name {
             let (interpolated, _) = ctx.interpolate_with_tracking(name);
             interpolated
@@ -497,7 +576,8 @@ impl WorkflowExecutor {
     /// Verifies that commits were created after a step execution. If no commits
     /// were created, determines the appropriate action based on step configuration:
     /// - Create auto-commit if changes exist and auto_commit is enabled
-    /// - Fail if commit_required is true
+    /// - Fail if commit_required is true, unless the step reported that no
+    ///   changes were needed (see [`crate::cook::no_changes`])
     /// - Continue silently otherwise
     ///
     /// The function uses extracted pure logic for decision-making, reducing
@@ -510,8 +590,11 @@ impl WorkflowExecutor {
         head_before: &str,
         step: &WorkflowStep,
         step_display: &str,
+        step_output: &str,
         workflow_context: &mut WorkflowContext,
     ) -> Result<bool> {
+        // Taken before the change check so the report file is not auto-committed
+        let no_changes = crate::cook::no_changes::take(working_dir, step_output);
         let head_after = self.get_current_head(working_dir).await?;
         let commit_handler = commit_handler::CommitHandler::new(
             Arc::clone(&self.git_operations),
@@ -537,9 +620,14 @@ impl WorkflowExecutor {
                         .await?;
                     return Ok(commit_created);
                 }
-                pure::CommitVerificationAction::RequireCommitError => {
-                    self.handle_no_commits_error(step)?;
-                }
+                pure::CommitVerificationAction::RequireCommitError => match no_changes {
+                    Some(no_changes) => self.user_interaction.display_info(&format!(
+                        "{}: {}",
+                        step_display,
+                        no_changes.describe()
+                    )),
+                    None => self.handle_no_commits_error(step)?,
+                },
                 pure::CommitVerificationAction::NoAction => {}
             }
             return Ok(false);
@@ -623,12 +711,23 @@ impl WorkflowExecutor {
     ) -> Result<()> {
         // Initialize workflow context early for checkpoint saving
         let mut workflow_context = self.init_workflow_context(env);
+        workflow_context.strict_variables = workflow.strict_variables;
 
         // Execute workflow and capture result
         let execution_result = self
             .execute_internal(workflow, env, &mut workflow_context)
             .await;
 
+        // Run cleanup steps whatever the outcome
+        let execution_result = self
+            .execute_always_steps(
+                &workflow.always_steps,
+                env,
+                &mut workflow_context,
+                execution_result,
+            )
+            .await;
+
         // Save checkpoint based on execution result (success or failure)
         if let Some(ref checkpoint_manager) = self.checkpoint_manager {
             if let Some(ref workflow_id) = self.workflow_id {
@@ -655,6 +754,7 @@ impl WorkflowExecutor {
                             if let Some(ref path) = self.workflow_path {
                                 cp.workflow_path = Some(path.clone());
                             }
+                            cp.step_selection = self.recorded_step_selection();
                             cp
                         });
 
@@ -667,6 +767,7 @@ impl WorkflowExecutor {
                     Err(error) => {
                         // Failure: save error recovery checkpoint
                         let failed_step_index = self.current_step_index.unwrap_or(0);
+                        let retry_state = self.retry_checkpoint_state().await;
                         let checkpoint_create_result = checkpoint::create_error_checkpoint(
                             workflow_id.clone(),
                             &normalized_workflow,
@@ -681,6 +782,9 @@ impl WorkflowExecutor {
                             if let Some(ref path) = self.workflow_path {
                                 cp.workflow_path = Some(path.clone());
                             }
+                            cp.step_selection = self.recorded_step_selection();
+                            cp.failure_chains = self.failure_chains.clone();
+                            cp.retry_checkpoint_state = retry_state;
                             cp
                         });
 
@@ -753,6 +857,7 @@ impl WorkflowExecutor {
         execution_flags: &pure::ExecutionFlags,
     ) -> Result<bool> {
         let mut any_changes = false;
+        let mut outcomes = Vec::new();
 
         for (step_index, step) in workflow.steps.iter().enumerate() {
             // Check for shutdown signal before each step (Spec 184)
@@ -792,6 +897,7 @@ impl WorkflowExecutor {
                     if let Some(ref path) = self.workflow_path {
                         checkpoint.workflow_path = Some(path.clone());
                     }
+                    checkpoint.step_selection = self.recorded_step_selection();
 
                     // Save checkpoint
                     if let Err(e) = checkpoint_manager.save_checkpoint(&checkpoint).await {
@@ -819,11 +925,23 @@ impl WorkflowExecutor {
                 continue;
             }
 
+            // Skip steps left out by --only-steps or --skip-steps
+            if !self.step_selection.includes(step_index) {
+                self.user_interaction.display_info(&format!(
+                    "Skipping step {}/{} (not selected): {}",
+                    step_index + 1,
+                    workflow.steps.len(),
+                    self.get_step_display_name(step)
+                ));
+                continue;
+            }
+
             // Restore error recovery state if needed
             self.restore_error_recovery_state(step_index, workflow_context);
 
             // Execute single step and update changes flag
-            let step_had_commits = self
+            let step_start = Instant::now();
+            let step_outcome = self
                 .execute_step_with_tracking(
                     step,
                     step_index,
@@ -832,11 +950,58 @@ impl WorkflowExecutor {
                     workflow_context,
                     execution_flags,
                 )
-                .await?;
+                .await;
+            let failed_result = self.failed_step_result.take();
+            if self.keep_going {
+                outcomes.push(super::step_outcomes::StepOutcome::new(
+                    step_index,
+                    self.get_step_display_name(step),
+                    step.required,
+                    step_start.elapsed(),
+                    step_outcome.as_ref().err(),
+                ));
+            }
+            let step_had_commits = match step_outcome {
+                Ok(had_commits) => had_commits,
+                Err(e) if self.keep_going && !step.required => {
+                    self.user_interaction.display_warning(&format!(
+                        "Step {} failed, continuing (--keep-going): {}",
+                        step_index + 1,
+                        e
+                    ));
+                    false
+                }
+                Err(e) => {
+                    if let Some(failures) = &self.failures {
+                        failures.record_step(super::failure_report::FailedStep::new(
+                            step_index,
+                            self.get_step_display_name(step),
+                            failed_result.as_ref(),
+                        ));
+                    }
+                    if !self.keep_going {
+                        return Err(e);
+                    }
+                    self.user_interaction.display_warning(&format!(
+                        "Required step {} failed, continuing (--keep-going): {}",
+                        step_index + 1,
+                        e
+                    ));
+                    false
+                }
+            };
 
             any_changes = step_had_commits || any_changes;
         }
 
+        if self.keep_going && !outcomes.is_empty() {
+            self.user_interaction.display_info(&format!(
+                "Step outcomes:\n{}",
+                super::step_outcomes::format_table(&outcomes)
+            ));
+            super::step_outcomes::check_required(&outcomes)?;
+        }
+
         Ok(any_changes)
     }
 
@@ -851,6 +1016,7 @@ impl WorkflowExecutor {
         execution_flags: &pure::ExecutionFlags,
     ) -> Result<bool> {
         self.current_step_index = Some(step_index);
+        self.current_workflow_shape = Some((workflow.name.clone(), workflow.steps.len()));
 
         // Use interpolated display name for user-facing logging
         let step_display_interpolated =
@@ -865,6 +1031,11 @@ impl WorkflowExecutor {
         // Keep non-interpolated version for tracking (used in timing, checkpoints, etc.)
         let step_display = self.get_step_display_name(step);
 
+        if step.snapshot && !self.dry_run {
+            self.snapshot_working_tree(step_index, &step_display, env)
+                .await?;
+        }
+
         // Get HEAD before command execution if needed
         let head_before = if !execution_flags.skip_validation
             && step.commit_required
@@ -881,7 +1052,68 @@ impl WorkflowExecutor {
         let step_started_at = chrono::Utc::now();
 
         // Execute the step
-        let step_result = self.execute_step(step, env, workflow_context).await?;
+        let _in_flight = crate::cook::signal_handler::track_work(format!(
+            "step {}/{}: {}",
+            step_index + 1,
+            workflow.steps.len(),
+            step_display_interpolated
+        ));
+        let tracker = crate::cook::execution::step_progress::StepTracker::start(
+            &env.session_id,
+            "steps",
+            &step_display_interpolated,
+        );
+        let measurement = crate::cook::execution::step_profile::start(
+            self.step_profiler.as_ref(),
+            &step_display_interpolated,
+        );
+        let metadata = crate::subprocess::ProcessMetadata::new(env.session_id.to_string())
+            .with_worktree(env.worktree_name.as_deref())
+            .with_step_index(step_index);
+        let step_result = match crate::subprocess::metadata::scope(
+            metadata,
+            self.execute_step(step, env, workflow_context),
+        )
+        .await
+        {
+            Ok(result) => result,
+            Err(e) => {
+                if let Some(measurement) = measurement {
+                    measurement.finish(false);
+                }
+                crate::cook::execution::junit::record(
+                    self.junit.as_deref(),
+                    crate::cook::execution::junit::TestCase::from_error(
+                        &workflow.name,
+                        &step_display_interpolated,
+                        command_start.elapsed(),
+                        &e,
+                    ),
+                );
+                return Err(e);
+            }
+        };
+        crate::cook::execution::junit::record(
+            self.junit.as_deref(),
+            crate::cook::execution::junit::TestCase::from_step(
+                &workflow.name,
+                &step_display_interpolated,
+                command_start.elapsed(),
+                &step_result,
+            ),
+        );
+        if let Some(measurement) = measurement {
+            measurement.finish(step_result.success);
+        }
+        let step_elapsed = tracker.finish(step_result.success);
+        if step_result.success {
+            self.user_interaction
+                .display_success(&orchestration::format_step_completed(
+                    step_index,
+                    workflow.steps.len(),
+                    step_elapsed,
+                ));
+        }
 
         // Display output
         self.log_step_output(&step_result);
@@ -899,7 +1131,7 @@ impl WorkflowExecutor {
         }
 
         // Track completed steps
-        let completed_step = orchestration::build_session_step_result(
+        let mut completed_step = orchestration::build_session_step_result(
             step_index,
             step_display.clone(),
             step,
@@ -908,9 +1140,12 @@ impl WorkflowExecutor {
             step_started_at,
             step_completed_at,
         );
+        if let Some(model) = self.last_claude_model.take() {
+            completed_step.model = Some(model);
+        }
         self.completed_steps.push(completed_step.clone());
 
-        let checkpoint_step = orchestration::build_checkpoint_step(
+        let mut checkpoint_step = orchestration::build_checkpoint_step(
             step_index,
             step_display.clone(),
             step,
@@ -919,7 +1154,12 @@ impl WorkflowExecutor {
             command_duration,
             step_completed_at,
         );
+        checkpoint_step.changes = self.last_step_changes.take();
+        let record_audit = checkpoint_step.changes.is_some();
         self.checkpoint_completed_steps.push(checkpoint_step);
+        if record_audit {
+            self.record_step_audit().await;
+        }
 
         // Save checkpoint if available
         self.save_step_checkpoint(workflow, workflow_context, step_index)
@@ -936,6 +1176,7 @@ impl WorkflowExecutor {
                     &before,
                     step,
                     &step_display,
+                    &step_result.stdout,
                     workflow_context,
                 )
                 .await?
@@ -949,6 +1190,65 @@ impl WorkflowExecutor {
         Ok(had_commits)
     }
 
+    /// Store the changes of every checkpointed step with the session
+    async fn record_step_audit(&self) {
+        use crate::cook::workflow::step_audit;
+
+        let steps = step_audit::from_checkpoint_steps(&self.checkpoint_completed_steps);
+        let update = SessionUpdate::SetMetadata(
+            step_audit::STEP_AUDIT_METADATA_KEY.to_string(),
+            step_audit::to_metadata_value(&steps),
+        );
+        if let Err(e) = self.session_manager.update_session(update).await {
+            tracing::warn!("Failed to store step audit: {}", e);
+        }
+    }
+
+    /// Record a snapshot of the working tree before a `snapshot: true` step
+    async fn snapshot_working_tree(
+        &self,
+        step_index: usize,
+        step_display: &str,
+        env: &ExecutionEnvironment,
+    ) -> Result<()> {
+        use crate::cook::workflow::snapshots;
+
+        let step_number = step_index + 1;
+        let snapshot = snapshots::take_snapshot(
+            self.git_operations.as_ref(),
+            &env.working_dir,
+            &env.session_id,
+            step_number,
+            step_display,
+        )
+        .await
+        .with_context(|| {
+            format!(
+                "Failed to snapshot the working tree before step {}",
+                step_number
+            )
+        })?;
+        snapshots::append_snapshot(&snapshots::snapshots_file_path(&env.session_id)?, &snapshot)
+            .await?;
+
+        self.user_interaction.display_info(&format!(
+            "Snapshot saved, restore with: prodigy rollback --to-step {} --session {}",
+            step_number, env.session_id
+        ));
+        Ok(())
+    }
+
+    /// Retry state to store with a checkpoint, if any step was retried
+    async fn retry_checkpoint_state(
+        &self,
+    ) -> Option<crate::cook::retry_state::RetryCheckpointState> {
+        self.retry_state_manager
+            .create_checkpoint_state()
+            .await
+            .ok()
+            .filter(|state| !state.command_retry_states.is_empty())
+    }
+
     /// Save step checkpoint if manager is available
     async fn save_step_checkpoint(
         &self,
@@ -976,6 +1276,8 @@ impl WorkflowExecutor {
                 if let Some(ref path) = self.workflow_path {
                     checkpoint.workflow_path = Some(path.clone());
                 }
+                checkpoint.step_selection = self.recorded_step_selection();
+                checkpoint.retry_checkpoint_state = self.retry_checkpoint_state().await;
 
                 if let Err(e) = checkpoint_manager.save_checkpoint(&checkpoint).await {
                     tracing::warn!("Failed to save checkpoint: {}", e);
@@ -1033,6 +1335,16 @@ impl WorkflowExecutor {
             .update_session(SessionUpdate::StartWorkflow)
             .await?;
 
+        // Baseline health score before the first iteration changes anything
+        let mut score_tracker = match &self.score_gate {
+            Some(gate) if !self.dry_run => {
+                let mut tracker = ScoreTracker::new(Arc::clone(gate));
+                self.record_iteration_score(&mut tracker, env, 0).await?;
+                Some(tracker)
+            }
+            _ => None,
+        };
+
         while should_continue && iteration < effective_max_iterations {
             iteration += 1;
 
@@ -1067,6 +1379,10 @@ impl WorkflowExecutor {
 
             any_changes = iteration_had_changes || any_changes;
 
+            if let Some(tracker) = score_tracker.as_mut() {
+                self.record_iteration_score(tracker, env, iteration).await?;
+            }
+
             // Determine continuation using pure function
             let continuation = pure::determine_iteration_continuation(
                 workflow,
@@ -1128,6 +1444,44 @@ impl WorkflowExecutor {
         Ok(())
     }
 
+    /// Measure the health score after `iteration` and apply the score gate
+    async fn record_iteration_score(
+        &self,
+        tracker: &mut ScoreTracker,
+        env: &ExecutionEnvironment,
+        iteration: u32,
+    ) -> Result<()> {
+        let score_env = HashMap::from([("PRODIGY_ITERATION".to_string(), iteration.to_string())]);
+        let score = tracker
+            .gate()
+            .measure(&env.working_dir, &score_env)
+            .await
+            .context("Failed to measure health score")?;
+        let dropped = tracker.record(iteration, score);
+
+        self.session_manager
+            .update_session(SessionUpdate::SetMetadata(
+                score_gate::SCORES_METADATA_KEY.to_string(),
+                score_gate::to_metadata_value(tracker.scores()),
+            ))
+            .await?;
+
+        match dropped {
+            None => {
+                self.user_interaction
+                    .display_metric("Health score", &score.to_string());
+                Ok(())
+            }
+            Some(drop) => match tracker.gate().on_drop {
+                ScoreDropAction::Fail => Err(anyhow!("{}", drop)),
+                ScoreDropAction::Warn => {
+                    self.user_interaction.display_warning(&drop.to_string());
+                    Ok(())
+                }
+            },
+        }
+    }
+
     /// Prepare environment variables for step execution
     /// Safely format environment variable value for logging (delegated to pure module)
     fn format_env_var_for_logging(key: &str, value: &str) -> String {
@@ -1183,35 +1537,38 @@ impl WorkflowExecutor {
         Option<PathBuf>,
         ExecutionEnvironment,
     )> {
-        // Set up environment for this step
-        let (env_vars, working_dir_override) =
-            if let Some(ref mut env_manager) = self.environment_manager {
-                // Use environment manager to set up step environment
-                let env_context = env_manager
-                    .setup_step_environment(
-                        step,
-                        self.global_environment_config.as_ref(),
-                        &ctx.variables,
-                    )
-                    .await?;
-
-                // Only override working directory if step explicitly set it
-                // This respects ExecutionEnvironment (e.g., MapReduce worktrees)
-                // while allowing legitimate per-step directory overrides
-                let working_dir_override =
-                    if step.working_dir.is_some() && env_context.working_dir != **env.working_dir {
-                        Some(env_context.working_dir.clone())
-                    } else {
-                        None
-                    };
+        // A step's working directory is resolved against the worktree, not the
+        // directory prodigy was started from
+        let working_dir_override = step
+            .working_dir
+            .as_ref()
+            .map(|dir| {
+                let dir = ctx.interpolate(&dir.to_string_lossy());
+                pure::resolve_step_working_dir(std::path::Path::new(&dir), &env.working_dir)
+            })
+            .filter(|dir| dir != &**env.working_dir);
 
-                (env_context.env, working_dir_override)
-            } else {
-                // Fall back to traditional environment preparation
-                let env_vars = self.prepare_env_vars(step, env, ctx);
-                let working_dir_override = step.working_dir.clone();
-                (env_vars, working_dir_override)
-            };
+        // Set up environment for this step
+        let env_vars = if let Some(ref mut env_manager) = self.environment_manager {
+            // Use environment manager to set up step environment
+            let env_context = env_manager
+                .setup_step_environment(
+                    step,
+                    self.global_environment_config.as_ref(),
+                    &ctx.variables,
+                )
+                .await?;
+            let mut env_vars = env_context.env;
+            // Let steps record progress with `prodigy checkpoint-mark` and label Claude logs
+            env_vars.insert(
+                crate::cook::workflow::checkpoint_marks::SESSION_ID_ENV.to_string(),
+                env.session_id.to_string(),
+            );
+            env_vars
+        } else {
+            // Fall back to traditional environment preparation
+            self.prepare_env_vars(step, env, ctx)
+        };
 
         // Update execution environment if working directory is overridden
         let mut actual_env = env.clone();
@@ -1248,6 +1605,15 @@ impl WorkflowExecutor {
     ) -> Result<()> {
         let step_name = self.get_step_display_name(step);
 
+        // Replayed commands cannot reproduce the commits of the recorded run
+        if self
+            .replay_session
+            .as_ref()
+            .is_some_and(|session| session.is_replaying())
+        {
+            return Ok(());
+        }
+
         // Validate commit requirements using pure function
         Self::validate_commit_requirement(
             step,
@@ -1356,6 +1722,8 @@ impl WorkflowExecutor {
                     commands: workflow.steps.clone(),
                     timeout: None,                   // No timeout by default
                     capture_outputs: HashMap::new(), // No variables to capture by default
+                    artifacts: Vec::new(),
+                    shared_context: None,
                 }
             } else {
                 // No setup phase
@@ -1363,9 +1731,16 @@ impl WorkflowExecutor {
                     commands: vec![],
                     timeout: None, // No timeout by default
                     capture_outputs: HashMap::new(),
+                    artifacts: Vec::new(),
+                    shared_context: None,
                 }
             };
 
+            if let Some(shared) = setup_phase.shared_context.as_ref() {
+                self.prepare_shared_context(shared, worktree_env, workflow_context)
+                    .await?;
+            }
+
             if !setup_phase.commands.is_empty() {
                 // SPEC 128: Immutable Environment Context Pattern
                 // The setup phase executor uses worktree_env (ExecutionEnvironment) which already
@@ -1397,6 +1772,46 @@ impl WorkflowExecutor {
         Ok((generated_input_file, captured_variables))
     }
 
+    /// Restore the shared context from cache or compute it for the current commit
+    async fn prepare_shared_context(
+        &mut self,
+        shared: &SharedContextConfig,
+        worktree_env: &ExecutionEnvironment,
+        workflow_context: &mut WorkflowContext,
+    ) -> Result<()> {
+        use crate::cook::execution::setup_executor::SetupPhaseExecutor;
+        use crate::cook::execution::SetupPhase;
+
+        let cache = SharedContextCache::open(
+            shared,
+            shared_context::cache_dir(&worktree_env.project_dir)?,
+            &worktree_env.working_dir,
+        )?;
+        if cache.restore(&worktree_env.working_dir, &shared.file)? {
+            self.user_interaction.display_info(&format!(
+                "Reusing shared context {} cached for {}",
+                shared.file,
+                &cache.head()[..cache.head().len().min(8)]
+            ));
+            return Ok(());
+        }
+
+        self.user_interaction
+            .display_progress(&format!("Computing shared context {}...", shared.file));
+        let phase = SetupPhase {
+            commands: shared.commands.clone(),
+            timeout: None,
+            capture_outputs: HashMap::new(),
+            artifacts: Vec::new(),
+            shared_context: None,
+        };
+        SetupPhaseExecutor::new(&phase)
+            .execute_with_file_detection(&phase.commands, self, worktree_env, workflow_context)
+            .await
+            .map_err(|e| anyhow!("Shared context commands failed: {}", e))?;
+        cache.store(&worktree_env.working_dir, &shared.file)
+    }
+
     /// Execute a MapReduce workflow
     ///
     /// High-level orchestration of MapReduce workflow execution:
@@ -1411,6 +1826,7 @@ impl WorkflowExecutor {
         workflow: &ExtendedWorkflowConfig,
         env: &ExecutionEnvironment,
     ) -> Result<()> {
+        use crate::cook::execution::mapreduce::setup_artifacts::SetupArtifacts;
         use crate::cook::execution::MapReduceExecutor;
 
         let workflow_start = Instant::now();
@@ -1443,9 +1859,24 @@ impl WorkflowExecutor {
             .await?;
 
         // Configure map phase with input interpolation and environment variables
-        let map_phase =
+        let mut map_phase =
             orchestration::configure_map_phase(workflow, generated_input_file, &workflow_context)?;
 
+        map_phase.chaos = self.chaos.clone();
+        map_phase.junit = self.junit.clone();
+        map_phase.protected_paths = self.protected_paths.clone();
+        map_phase.seed = self.seed;
+        map_phase.failures = self.failures.clone();
+
+        // Snapshot declared setup outputs before agents start merging
+        if let Some(setup) = workflow.setup_phase.as_ref() {
+            let patterns = setup.artifact_patterns();
+            if !patterns.is_empty() {
+                let artifacts = SetupArtifacts::capture(&patterns, &worktree_env.working_dir)?;
+                map_phase.setup_artifacts = Some(Arc::new(artifacts));
+            }
+        }
+
         // Create MapReduce executor
         // Use the parent worktree as the base for map phase agent worktrees
         // Convert VerbosityLevel to u8 for merge operation verbosity control
@@ -1460,7 +1891,7 @@ impl WorkflowExecutor {
         // SPEC 134: Create WorktreeManager for agent worktrees using parent worktree as base
         use crate::worktree::WorktreeManager;
         let worktree_manager = Arc::new(WorktreeManager::new(
-            env.working_dir.to_path_buf(),
+            env.checkout_root(),
             self.subprocess.clone(),
         )?);
 
@@ -1469,7 +1900,7 @@ impl WorkflowExecutor {
             self.session_manager.clone(),
             self.user_interaction.clone(),
             worktree_manager,
-            env.working_dir.to_path_buf(), // Use parent worktree path as base for agents
+            env.checkout_root(), // Use parent worktree path as base for agents
             verbosity_u8,
         )
         .await;
@@ -1565,6 +1996,8 @@ impl crate::cook::execution::CommandExecutor for StepValidationCommandExecutor {
         let step = match command_type {
             "claude" => WorkflowStep {
                 claude: Some(args.join(" ")),
+                context: None,
+                validate_output: None,
                 ..Default::default()
             },
             "shell" => WorkflowStep {
@@ -0,0 +1,150 @@
+//! Declarative cleanup steps (`always:`)
+//!
+//! Steps listed under `always:` (or its alias `finally:`) run after the
+//! workflow's own steps whatever their outcome: success, failure, or a
+//! shutdown request. They see `${workflow.status}` (`success` or `failure`)
+//! and, after a failure, `${workflow.error}`.
+//!
+//! Every cleanup step runs even when an earlier one fails. A failed cleanup
+//! step fails a workflow that otherwise succeeded; when the workflow had
+//! already failed, its original error is kept.
+
+use super::{StepResult, WorkflowContext, WorkflowExecutor, WorkflowStep};
+use crate::cook::orchestrator::ExecutionEnvironment;
+use anyhow::{anyhow, Result};
+
+/// Variable holding `success` or `failure` while cleanup steps run
+pub const STATUS_VARIABLE: &str = "workflow.status";
+
+/// Variable holding the workflow error while cleanup steps run
+pub const ERROR_VARIABLE: &str = "workflow.error";
+
+/// Final outcome of a workflow and its cleanup steps (pure function)
+///
+/// The workflow's own error wins; otherwise the first cleanup failure
+/// becomes the result.
+pub fn combine_outcome(workflow_result: Result<()>, cleanup_failures: Vec<String>) -> Result<()> {
+    workflow_result?;
+    match cleanup_failures.into_iter().next() {
+        Some(failure) => Err(anyhow!("Cleanup step failed: {}", failure)),
+        None => Ok(()),
+    }
+}
+
+/// Failure message of a cleanup step, if it failed (pure function)
+fn step_failure(step_name: &str, result: &Result<StepResult>) -> Option<String> {
+    match result {
+        Ok(result) if result.success => None,
+        Ok(result) => Some(format!(
+            "{} exited with {:?}: {}",
+            step_name,
+            result.exit_code,
+            result.stderr.trim()
+        )),
+        Err(e) => Some(format!("{}: {}", step_name, e)),
+    }
+}
+
+impl WorkflowExecutor {
+    /// Run `always_steps` after a workflow that finished with `workflow_result`
+    pub(crate) async fn execute_always_steps(
+        &mut self,
+        always_steps: &[WorkflowStep],
+        env: &ExecutionEnvironment,
+        ctx: &mut WorkflowContext,
+        workflow_result: Result<()>,
+    ) -> Result<()> {
+        if always_steps.is_empty() {
+            return workflow_result;
+        }
+
+        let status = if workflow_result.is_ok() {
+            "success"
+        } else {
+            "failure"
+        };
+        ctx.variables
+            .insert(STATUS_VARIABLE.to_string(), status.to_string());
+        if let Err(ref e) = workflow_result {
+            ctx.variables
+                .insert(ERROR_VARIABLE.to_string(), e.to_string());
+        }
+
+        self.user_interaction.display_progress(&format!(
+            "Running {} cleanup step{} (workflow {})",
+            always_steps.len(),
+            if always_steps.len() == 1 { "" } else { "s" },
+            status
+        ));
+
+        let mut failures = Vec::new();
+        for step in always_steps {
+            let step_name = self.get_step_display_name(step);
+            let result = self.execute_step(step, env, ctx).await;
+            if let Some(failure) = step_failure(&step_name, &result) {
+                self.user_interaction
+                    .display_error(&format!("Cleanup step failed: {}", failure));
+                failures.push(failure);
+            }
+        }
+
+        if let Err(ref e) = workflow_result {
+            for failure in &failures {
+                tracing::warn!(
+                    "Cleanup step failed after workflow error ({}): {}",
+                    e,
+                    failure
+                );
+            }
+        }
+        combine_outcome(workflow_result, failures)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step_result(success: bool, stderr: &str) -> StepResult {
+        StepResult {
+            success,
+            exit_code: Some(if success { 0 } else { 2 }),
+            stdout: String::new(),
+            stderr: stderr.to_string(),
+            json_log_location: None,
+        }
+    }
+
+    #[test]
+    fn test_combine_outcome_keeps_workflow_error() {
+        let result = combine_outcome(
+            Err(anyhow!("tests failed")),
+            vec!["shell: rm -rf tmp: denied".to_string()],
+        );
+        assert_eq!(result.unwrap_err().to_string(), "tests failed");
+    }
+
+    #[test]
+    fn test_combine_outcome_fails_on_cleanup_failure() {
+        assert!(combine_outcome(Ok(()), vec![]).is_ok());
+        let err =
+            combine_outcome(Ok(()), vec!["first".to_string(), "second".to_string()]).unwrap_err();
+        assert_eq!(err.to_string(), "Cleanup step failed: first");
+    }
+
+    #[test]
+    fn test_step_failure_messages() {
+        assert_eq!(
+            step_failure("shell: true", &Ok(step_result(true, ""))),
+            None
+        );
+        assert_eq!(
+            step_failure("shell: false", &Ok(step_result(false, "boom\n"))),
+            Some("shell: false exited with Some(2): boom".to_string())
+        );
+        assert_eq!(
+            step_failure("shell: x", &Err(anyhow!("timed out"))),
+            Some("shell: x: timed out".to_string())
+        );
+    }
+}
@@ -4,7 +4,7 @@
 
 use super::super::checkpoint::{
     create_checkpoint_with_total_steps, CheckpointManager,
-    CompletedStep as CheckpointCompletedStep, ResumeContext, RetryState,
+    CompletedStep as CheckpointCompletedStep, FailureChainState, ResumeContext, RetryState,
 };
 use super::super::error_recovery::ErrorRecoveryState;
 use super::super::normalized;
@@ -55,6 +55,9 @@ impl WorkflowExecutor {
             global_environment_config: None,
             current_workflow: None,
             current_step_index: None,
+            current_workflow_shape: None,
+            failure_chains: Vec::new(),
+            resumed_failure_chains: Vec::new(),
             git_operations: Arc::new(RealGitOperations::new()),
             resume_context: None,
             retry_state_manager: Arc::new(RetryStateManager::new()),
@@ -66,6 +69,22 @@ impl WorkflowExecutor {
             dry_run_potential_handlers: Vec::new(),
             positional_args: None,
             shutdown_signal: Arc::new(AtomicBool::new(false)),
+            read_only: false,
+            keep_going: false,
+            step_selection: Default::default(),
+            replay_session: None,
+            chaos: None,
+            junit: None,
+            step_profiler: None,
+            protected_paths: None,
+            seed: None,
+            score_gate: None,
+            failures: None,
+            claude_permissions: None,
+            last_claude_model: None,
+            output_settings: crate::config::OutputSettings::default(),
+            last_step_changes: None,
+            failed_step_result: None,
         }
     }
 
@@ -95,12 +114,25 @@ impl WorkflowExecutor {
                     }
                 });
             }
+            self.resumed_failure_chains = checkpoint.failure_chains.clone();
         }
 
         self.resume_context = Some(context);
         self
     }
 
+    /// Set the size limits for step output
+    pub fn with_output_settings(mut self, output_settings: crate::config::OutputSettings) -> Self {
+        self.output_settings = output_settings;
+        self
+    }
+
+    /// Set the `on_failure` chains recorded in a checkpoint being resumed
+    pub fn with_failure_chains(mut self, failure_chains: Vec<FailureChainState>) -> Self {
+        self.resumed_failure_chains = failure_chains;
+        self
+    }
+
     /// Set the workflow file path (for checkpoint resume)
     pub fn with_workflow_path(mut self, path: PathBuf) -> Self {
         self.workflow_path = Some(path);
@@ -113,6 +145,113 @@ impl WorkflowExecutor {
         self
     }
 
+    /// Enable read-only mode, rejecting steps that require or auto-create commits
+    pub fn with_read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Enable keep-going mode, running every step even if some fail
+    pub fn with_keep_going(mut self, keep_going: bool) -> Self {
+        self.keep_going = keep_going;
+        self
+    }
+
+    /// Run only the steps in a resolved `--only-steps`/`--skip-steps` selection
+    pub fn with_step_selection(
+        mut self,
+        step_selection: crate::cook::workflow::step_selection::StepSelection,
+    ) -> Self {
+        self.step_selection = step_selection;
+        self
+    }
+
+    /// The step selection to record in checkpoints, if steps were selected
+    pub(crate) fn recorded_step_selection(
+        &self,
+    ) -> Option<crate::cook::workflow::step_selection::StepSelection> {
+        (!self.step_selection.is_empty()).then(|| self.step_selection.clone())
+    }
+
+    /// Record or replay Claude and shell command outputs
+    pub fn with_replay_session(
+        mut self,
+        session: Option<Arc<crate::cook::execution::ReplaySession>>,
+    ) -> Self {
+        self.replay_session = session;
+        self
+    }
+
+    /// Inject faults into Claude and shell commands (`--chaos`)
+    pub fn with_chaos(
+        mut self,
+        chaos: Option<Arc<crate::cook::execution::chaos::ChaosMonkey>>,
+    ) -> Self {
+        self.chaos = chaos;
+        self
+    }
+
+    /// Record step results for JUnit XML output
+    pub fn with_junit(
+        mut self,
+        junit: Option<Arc<crate::cook::execution::junit::JunitRecorder>>,
+    ) -> Self {
+        self.junit = junit;
+        self
+    }
+
+    /// Measure each step for `--profile-steps`
+    pub fn with_step_profiler(
+        mut self,
+        profiler: Option<Arc<crate::cook::execution::step_profile::StepProfiler>>,
+    ) -> Self {
+        self.step_profiler = profiler;
+        self
+    }
+
+    /// Guard protected paths after every step
+    pub fn with_protected_paths(
+        mut self,
+        protected_paths: Option<Arc<crate::cook::protected_paths::ProtectedPaths>>,
+    ) -> Self {
+        self.protected_paths = protected_paths;
+        self
+    }
+
+    /// Make retry jitter and MapReduce slot order repeatable (`--seed`)
+    pub fn with_seed(mut self, seed: Option<u64>) -> Self {
+        self.seed = seed;
+        self.retry_state_manager = Arc::new(RetryStateManager::new().with_seed(seed));
+        self
+    }
+
+    /// Stop iterating when the health score drops (`score_gate:`)
+    pub fn with_score_gate(
+        mut self,
+        gate: Option<crate::cook::workflow::score_gate::ScoreGate>,
+    ) -> Self {
+        self.score_gate = gate.map(Arc::new);
+        self
+    }
+
+    /// Record the step that fails the run for its failure report
+    pub fn with_failure_recorder(
+        mut self,
+        failures: Option<Arc<crate::cook::workflow::failure_report::FailureRecorder>>,
+    ) -> Self {
+        self.failures = failures;
+        self
+    }
+
+    /// Run Claude commands with the workflow's tool permissions
+    pub fn with_claude_permissions(
+        mut self,
+        permissions: Option<crate::cook::execution::claude_permissions::ClaudePermissions>,
+    ) -> Self {
+        self.claude_permissions = permissions;
+        self
+    }
+
     /// Set the environment configuration for the workflow
     pub fn with_environment_config(
         mut self,
@@ -191,9 +330,12 @@ impl WorkflowExecutor {
     }
 
     /// Check if shutdown was requested (Spec 184)
+    ///
+    /// Also true once the process received SIGTERM or SIGHUP.
     pub fn is_shutdown_requested(&self) -> bool {
         use std::sync::atomic::Ordering;
         self.shutdown_signal.load(Ordering::Acquire)
+            || crate::cook::signal_handler::shutdown_requested().is_some()
     }
 
     /// Set the checkpoint manager for workflow resumption
@@ -238,6 +380,9 @@ impl WorkflowExecutor {
             global_environment_config: None,
             current_workflow: None,
             current_step_index: None,
+            current_workflow_shape: None,
+            failure_chains: Vec::new(),
+            resumed_failure_chains: Vec::new(),
             git_operations: Arc::new(RealGitOperations::new()),
             resume_context: None,
             retry_state_manager: Arc::new(RetryStateManager::new()),
@@ -249,6 +394,22 @@ impl WorkflowExecutor {
             dry_run_potential_handlers: Vec::new(),
             positional_args: None,
             shutdown_signal: Arc::new(AtomicBool::new(false)),
+            read_only: false,
+            keep_going: false,
+            step_selection: Default::default(),
+            replay_session: None,
+            chaos: None,
+            junit: None,
+            step_profiler: None,
+            protected_paths: None,
+            seed: None,
+            score_gate: None,
+            failures: None,
+            claude_permissions: None,
+            last_claude_model: None,
+            output_settings: crate::config::OutputSettings::default(),
+            last_step_changes: None,
+            failed_step_result: None,
         }
     }
 
@@ -279,6 +440,9 @@ impl WorkflowExecutor {
             global_environment_config: None,
             current_workflow: None,
             current_step_index: None,
+            current_workflow_shape: None,
+            failure_chains: Vec::new(),
+            resumed_failure_chains: Vec::new(),
             git_operations,
             resume_context: None,
             retry_state_manager: Arc::new(RetryStateManager::new()),
@@ -290,6 +454,22 @@ impl WorkflowExecutor {
             dry_run_potential_handlers: Vec::new(),
             positional_args: None,
             shutdown_signal: Arc::new(AtomicBool::new(false)),
+            read_only: false,
+            keep_going: false,
+            step_selection: Default::default(),
+            replay_session: None,
+            chaos: None,
+            junit: None,
+            step_profiler: None,
+            protected_paths: None,
+            seed: None,
+            score_gate: None,
+            failures: None,
+            claude_permissions: None,
+            last_claude_model: None,
+            output_settings: crate::config::OutputSettings::default(),
+            last_step_changes: None,
+            failed_step_result: None,
         }
     }
 
@@ -306,6 +486,9 @@ impl WorkflowExecutor {
             Some(WorkflowStep {
                 name: None,
                 claude: on_incomplete.claude.clone(),
+                model: None,
+                context: None,
+                validate_output: None,
                 shell: on_incomplete.shell.clone(),
                 test: None,
                 foreach: None,
@@ -333,6 +516,13 @@ impl WorkflowExecutor {
                 validation_timeout: None,
                 ignore_validation_failure: false,
                 when: None,
+                assert: None,
+                summarize_commits: None,
+                claude_chat: None,
+                script: None,
+                snapshot: false,
+                required: false,
+                commit_policy: None,
             })
         } else {
             None
@@ -411,6 +601,7 @@ impl WorkflowExecutor {
                         duration: Duration::from_secs(0),
                         completed_at: chrono::Utc::now(),
                         retry_state: Some(retry_state),
+                        changes: None,
                     };
 
                     // Remove any existing entry for this step and add the new one
@@ -432,6 +623,7 @@ impl WorkflowExecutor {
                 if let Some(ref path) = self.workflow_path {
                     checkpoint.workflow_path = Some(path.clone());
                 }
+                checkpoint.step_selection = self.recorded_step_selection();
 
                 // Add retry state from RetryStateManager
                 if let Ok(retry_checkpoint_state) =
@@ -18,17 +18,31 @@
 
 use crate::commands::{AttributeValue, ExecutionContext};
 use crate::cook::error::ResultExt;
-use crate::cook::execution::{ClaudeExecutor, ExecutionResult};
+use crate::cook::execution::chaos::{run_with_chaos, ChaosTarget};
+use crate::cook::execution::claude_permissions::PERMISSIONS_ENV;
+use crate::cook::execution::step_progress::StepProgressReporter;
+use crate::cook::execution::{
+    ClaudeExecutor, CommandFixture, ExecutionResult, FixtureKind, CLAUDE_MODEL_ENV,
+};
 use crate::cook::orchestrator::ExecutionEnvironment;
-use crate::cook::workflow::effects::environment::{DefaultShellRunner, ShellRunner};
+use crate::cook::workflow::context_pack::inject_context;
+use crate::cook::workflow::effects::environment::DefaultShellRunner;
 use crate::cook::workflow::on_failure::OnFailureConfig;
+use crate::cook::workflow::output_validation::{
+    build_reprompt, response_text, OutputValidationConfig, CLAUDE_OUTPUT_ENV,
+};
 use crate::cook::workflow::pure::build_command;
+use crate::cook::workflow::script::{self, ScriptLang};
 use anyhow::{anyhow, Result};
 use std::collections::HashMap;
 use std::path::Path;
 use std::sync::Arc;
 
-use super::specialized_commands::{execute_foreach_command, execute_write_file_command};
+use super::retry_logic::with_escalated_model;
+use super::specialized_commands::{
+    execute_assert_command, execute_foreach_command, execute_summarize_commits_command,
+    execute_write_file_command,
+};
 use super::{CommandType, StepResult, WorkflowContext, WorkflowExecutor, WorkflowStep};
 
 // ============================================================================
@@ -65,16 +79,20 @@ fn convert_execution_result(result: ExecutionResult) -> StepResult {
 
 /// Execute a shell command with optional timeout
 ///
-/// Delegates to `DefaultShellRunner` from the effects module (spec 174d).
+/// Each stdout line is passed to `on_line` as it is produced. Delegates to
+/// `DefaultShellRunner` from the effects module (spec 174d).
 pub async fn execute_shell_command(
     command: &str,
     working_dir: &Path,
     env_vars: HashMap<String, String>,
     timeout: Option<u64>,
+    on_line: &(dyn Fn(&str) + Send + Sync),
 ) -> Result<StepResult> {
     tracing::info!("Executing shell: {}", command);
     let runner = DefaultShellRunner::new();
-    let output = runner.run(command, working_dir, env_vars, timeout).await?;
+    let output = runner
+        .run_with_line_handler(command, working_dir, env_vars, timeout, on_line)
+        .await?;
     Ok(convert_runner_output_to_step_result(output))
 }
 
@@ -91,6 +109,42 @@ fn convert_runner_output_to_step_result(
     }
 }
 
+/// Convert a recorded fixture into a StepResult
+fn fixture_to_step_result(fixture: CommandFixture) -> StepResult {
+    StepResult {
+        success: fixture.success,
+        exit_code: fixture.exit_code,
+        stdout: fixture.stdout,
+        stderr: fixture.stderr,
+        json_log_location: None,
+    }
+}
+
+/// Convert a StepResult into a fixture for recording
+fn step_result_to_fixture(kind: FixtureKind, command: &str, result: &StepResult) -> CommandFixture {
+    CommandFixture {
+        kind,
+        command: command.to_string(),
+        success: result.success,
+        exit_code: result.exit_code,
+        stdout: result.stdout.clone(),
+        stderr: result.stderr.clone(),
+    }
+}
+
+/// Templates a command interpolates before it runs (pure function)
+pub fn command_templates(command_type: &CommandType) -> Vec<&str> {
+    match command_type {
+        CommandType::Claude(cmd) | CommandType::Legacy(cmd) | CommandType::Shell(cmd) => {
+            vec![cmd.as_str()]
+        }
+        CommandType::Test(test_cmd) => vec![test_cmd.command.as_str()],
+        CommandType::WriteFile(cfg) => vec![cfg.path.as_str(), cfg.content.as_str()],
+        CommandType::Script(cfg) => vec![cfg.code.as_str()],
+        _ => Vec::new(),
+    }
+}
+
 /// Format command description for logging
 pub fn format_command_description(command_type: &CommandType) -> String {
     match command_type {
@@ -100,6 +154,10 @@ pub fn format_command_description(command_type: &CommandType) -> String {
         CommandType::Handler { handler_name, .. } => format!("handler: {}", handler_name),
         CommandType::Foreach(cfg) => format!("foreach: {:?}", cfg.input),
         CommandType::WriteFile(cfg) => format!("write_file: {}", cfg.path),
+        CommandType::Assert(_) => "assert".to_string(),
+        CommandType::SummarizeCommits(_) => "summarize_commits".to_string(),
+        CommandType::ClaudeChat(cfg) => format!("claude_chat: {} messages", cfg.messages.len()),
+        CommandType::Script(cfg) => format!("script: {}", cfg.lang),
     }
 }
 
@@ -125,6 +183,14 @@ impl WorkflowExecutor {
             env_vars.insert("PRODIGY_COMMAND_TIMEOUT".to_string(), timeout.to_string());
         }
 
+        if self.read_only {
+            env_vars.insert("PRODIGY_READ_ONLY".to_string(), "true".to_string());
+        }
+
+        if let Some(model) = &step.model {
+            env_vars.insert(CLAUDE_MODEL_ENV.to_string(), model.clone());
+        }
+
         self.dispatch_command(command_type.clone(), step, env, ctx, env_vars)
             .await
     }
@@ -137,12 +203,32 @@ impl WorkflowExecutor {
         ctx: &mut WorkflowContext,
         env_vars: HashMap<String, String>,
     ) -> Result<StepResult> {
+        for template in command_templates(&command_type) {
+            ctx.ensure_variables_defined(template, &env_vars)?;
+        }
+
         match command_type {
             CommandType::Claude(cmd) | CommandType::Legacy(cmd) => {
                 let (interpolated, resolutions) = ctx.interpolate_with_tracking(&cmd);
                 self.log_variable_resolutions(&resolutions);
-                self.execute_claude_command(&interpolated, env, env_vars)
-                    .await
+                let command = match &step.context {
+                    Some(context) => {
+                        let mut spec = context.spec();
+                        spec.files = spec.files.iter().map(|f| ctx.interpolate(f)).collect();
+                        inject_context(&interpolated, &spec, &env.working_dir)?
+                    }
+                    None => interpolated,
+                };
+                match &step.validate_output {
+                    Some(contract) => {
+                        self.execute_claude_validated(&command, step, contract, env, ctx, env_vars)
+                            .await
+                    }
+                    None => {
+                        self.execute_claude_with_escalation(&command, step, env, env_vars)
+                            .await
+                    }
+                }
             }
             CommandType::Shell(cmd) => {
                 let (interpolated, resolutions) = ctx.interpolate_with_tracking(&cmd);
@@ -171,16 +257,318 @@ impl WorkflowExecutor {
                 config.content = content;
                 execute_write_file_command(&config, &env.working_dir).await
             }
+            CommandType::Assert(config) => {
+                let config = config.interpolated(|s| ctx.interpolate(s));
+                execute_assert_command(&config, &env.working_dir, self.git_operations.as_ref())
+                    .await
+            }
+            CommandType::SummarizeCommits(config) => {
+                let config = config.interpolated(|s| ctx.interpolate(s));
+                self.execute_summarize_commits(&config, env, ctx, env_vars)
+                    .await
+            }
+            CommandType::ClaudeChat(config) => {
+                self.execute_claude_chat(&config, env, ctx, env_vars).await
+            }
+            CommandType::Script(config) => {
+                let (code, resolutions) = ctx.interpolate_with_tracking(&config.code);
+                self.log_variable_resolutions(&resolutions);
+                self.execute_script(config.lang, &code, step, env, env_vars)
+                    .await
+            }
         }
     }
 
-    pub(crate) async fn execute_claude_command(
+    /// Run a script step's code with its interpreter
+    ///
+    /// The code is written to a temporary file that lives until the
+    /// interpreter exits.
+    async fn execute_script(
         &self,
+        lang: ScriptLang,
+        code: &str,
+        step: &WorkflowStep,
+        env: &ExecutionEnvironment,
+        env_vars: HashMap<String, String>,
+    ) -> Result<StepResult> {
+        let key = script::fixture_key(lang, code);
+        if let Some(replayed) = self.replay_fixture(FixtureKind::Shell, &key)? {
+            return Ok(replayed);
+        }
+        let file = script::write_script(lang, code)?;
+        let command = script::interpreter_command(lang, file.path());
+        let result = self
+            .run_shell(
+                &command,
+                format!("script: {}", lang),
+                env,
+                env_vars,
+                step.timeout,
+            )
+            .await?;
+        self.record_fixture(FixtureKind::Shell, &key, &result)?;
+        Ok(result)
+    }
+
+    /// Run a claude_chat step, sending its messages in one Claude conversation
+    ///
+    /// Each message is interpolated just before it is sent so it can refer to
+    /// earlier replies, and every message after the first resumes the session
+    /// the previous reply reported. Stops at the first failing message.
+    async fn execute_claude_chat(
+        &mut self,
+        config: &crate::cook::workflow::claude_chat::ClaudeChatConfig,
+        env: &ExecutionEnvironment,
+        ctx: &mut WorkflowContext,
+        env_vars: HashMap<String, String>,
+    ) -> Result<StepResult> {
+        use crate::cook::execution::claude_sessions::{parse_session_id, RESUME_SESSION_ENV};
+        use crate::cook::workflow::claude_chat::{reply_text, reply_variable};
+
+        if config.messages.is_empty() {
+            return Err(anyhow!("claude_chat needs at least one message"));
+        }
+        let variable = ctx.interpolate(config.variable_name());
+
+        let mut session_id: Option<String> = None;
+        let mut last_reply = None;
+        for (index, message) in config.messages.iter().enumerate() {
+            ctx.ensure_variables_defined(message, &env_vars)?;
+            let (command, resolutions) = ctx.interpolate_with_tracking(message);
+            self.log_variable_resolutions(&resolutions);
+
+            let mut message_env = env_vars.clone();
+            if let Some(id) = &session_id {
+                message_env.insert(RESUME_SESSION_ENV.to_string(), id.clone());
+            }
+            let result = self
+                .execute_claude_command(&command, env, message_env)
+                .await?;
+            if !result.success {
+                return Ok(result);
+            }
+
+            session_id = parse_session_id(&result.stdout);
+            if session_id.is_none() && index + 1 < config.messages.len() {
+                return Err(anyhow!(
+                    "claude_chat message {} reported no session to continue; claude_chat requires Claude's JSON streaming",
+                    index + 1
+                ));
+            }
+
+            let reply = reply_text(&result.stdout);
+            ctx.variables
+                .insert(reply_variable(&variable, index), reply.clone());
+            last_reply = Some(StepResult {
+                stdout: reply,
+                ..result
+            });
+        }
+
+        let result = last_reply.expect("claude_chat has at least one message");
+        ctx.variables
+            .insert(variable.clone(), result.stdout.clone());
+        if let Some(id) = session_id {
+            ctx.variables.insert(format!("{}.session_id", variable), id);
+        }
+        Ok(result)
+    }
+
+    /// Run a summarize_commits step and store the summary in its variable
+    ///
+    /// Without an explicit `since`, commits are counted from the commit the
+    /// workflow started from. A configured Claude command is given the
+    /// artifact path and may rewrite it before it is read back.
+    async fn execute_summarize_commits(
+        &mut self,
+        config: &crate::cook::workflow::commit_summary::SummarizeCommitsConfig,
+        env: &ExecutionEnvironment,
+        ctx: &mut WorkflowContext,
+        env_vars: HashMap<String, String>,
+    ) -> Result<StepResult> {
+        let since = match &config.since {
+            Some(since) => since.clone(),
+            None => ctx
+                .git_tracker
+                .as_ref()
+                .and_then(|tracker| tracker.lock().ok()?.workflow_start_commit.clone())
+                .ok_or_else(|| {
+                    anyhow!("summarize_commits needs 'since' when the workflow's starting commit is unknown")
+                })?,
+        };
+
+        let mut result = execute_summarize_commits_command(
+            config,
+            &since,
+            &env.working_dir,
+            Arc::clone(&self.git_operations),
+        )
+        .await?;
+
+        if let Some(claude) = &config.claude {
+            let output = config.output_path();
+            let refined = self
+                .execute_claude_command(&format!("{} {}", claude, output), env, env_vars)
+                .await?;
+            if !refined.success {
+                return Ok(refined);
+            }
+            result.stdout = tokio::fs::read_to_string(env.working_dir.join(&output))
+                .await
+                .map_err(|e| anyhow!("Failed to read commit summary {}: {}", output, e))?;
+        }
+
+        ctx.variables
+            .insert(config.variable_name().to_string(), result.stdout.clone());
+        Ok(result)
+    }
+
+    /// Run a Claude step, retrying once with `on_failure.escalate_model` if it fails
+    async fn execute_claude_with_escalation(
+        &mut self,
+        command: &str,
+        step: &WorkflowStep,
+        env: &ExecutionEnvironment,
+        env_vars: HashMap<String, String>,
+    ) -> Result<StepResult> {
+        let escalate_model = step
+            .on_failure
+            .as_ref()
+            .and_then(|config| config.escalate_model());
+        let Some(model) = escalate_model else {
+            return self.execute_claude_command(command, env, env_vars).await;
+        };
+
+        let failure = match self
+            .execute_claude_command(command, env, env_vars.clone())
+            .await
+        {
+            Ok(result) if result.success => return Ok(result),
+            Ok(result) => result.stderr,
+            Err(e) => format!("{e:#}"),
+        };
+
+        tracing::warn!("Claude command '{}' failed: {}", command, failure);
+        self.user_interaction.display_info(&format!(
+            "Claude command failed, retrying with model '{model}'"
+        ));
+        self.last_claude_model = Some(model.to_string());
+        self.execute_claude_command(command, env, with_escalated_model(&env_vars, Some(model)))
+            .await
+    }
+
+    /// Run a Claude step until its output meets `validate_output`
+    ///
+    /// Each failed check re-runs the command with the validation errors
+    /// appended, up to `max_attempts` runs in total.
+    async fn execute_claude_validated(
+        &mut self,
         command: &str,
+        step: &WorkflowStep,
+        contract: &OutputValidationConfig,
         env: &ExecutionEnvironment,
+        ctx: &WorkflowContext,
         env_vars: HashMap<String, String>,
     ) -> Result<StepResult> {
-        execute_claude_command(&self.claude_executor, command, &env.working_dir, env_vars).await
+        let max_attempts = contract.max_attempts.max(1);
+        let mut prompt = command.to_string();
+        let mut attempt = 1;
+
+        loop {
+            let result = self
+                .execute_claude_with_escalation(&prompt, step, env, env_vars.clone())
+                .await?;
+            if !result.success {
+                return Ok(result);
+            }
+
+            let errors = self
+                .check_claude_output(contract, &result, env, ctx, &env_vars)
+                .await?;
+            if errors.is_empty() {
+                return Ok(result);
+            }
+
+            if attempt >= max_attempts {
+                return Ok(StepResult {
+                    success: false,
+                    stderr: format!(
+                        "Output validation failed after {} attempt(s):\n{}",
+                        max_attempts,
+                        errors.join("\n")
+                    ),
+                    ..result
+                });
+            }
+
+            self.user_interaction.display_warning(&format!(
+                "Output validation failed (attempt {attempt}/{max_attempts}), re-prompting: {}",
+                errors.join("; ")
+            ));
+            prompt = build_reprompt(command, &errors);
+            attempt += 1;
+        }
+    }
+
+    /// Validation errors for a Claude response, including the shell validator
+    async fn check_claude_output(
+        &self,
+        contract: &OutputValidationConfig,
+        result: &StepResult,
+        env: &ExecutionEnvironment,
+        ctx: &WorkflowContext,
+        env_vars: &HashMap<String, String>,
+    ) -> Result<Vec<String>> {
+        let response = response_text(&result.stdout);
+        let mut errors = contract.check_response(&response);
+
+        if let Some(validator) = &contract.shell {
+            let output_file = tempfile::NamedTempFile::new()?;
+            std::fs::write(output_file.path(), &response)?;
+
+            let mut validator_env = env_vars.clone();
+            validator_env.insert(
+                CLAUDE_OUTPUT_ENV.to_string(),
+                output_file.path().to_string_lossy().to_string(),
+            );
+            let validator = ctx.interpolate(validator);
+            let outcome = self
+                .execute_shell_command(&validator, env, validator_env, None)
+                .await?;
+            if !outcome.success {
+                let detail = [outcome.stderr.trim(), outcome.stdout.trim()]
+                    .into_iter()
+                    .find(|s| !s.is_empty())
+                    .map(String::from)
+                    .unwrap_or_else(|| format!("exit code {:?}", outcome.exit_code));
+                errors.push(format!("Validator `{validator}` failed: {detail}"));
+            }
+        }
+
+        Ok(errors)
+    }
+
+    pub(crate) async fn execute_claude_command(
+        &self,
+        command: &str,
+        env: &ExecutionEnvironment,
+        mut env_vars: HashMap<String, String>,
+    ) -> Result<StepResult> {
+        if let Some(replayed) = self.replay_fixture(FixtureKind::Claude, command)? {
+            return Ok(replayed);
+        }
+        if let Some(permissions) = &self.claude_permissions {
+            env_vars.insert(PERMISSIONS_ENV.to_string(), permissions.to_env_value());
+        }
+        let result = run_with_chaos(
+            self.chaos.as_deref(),
+            ChaosTarget::Claude,
+            command,
+            execute_claude_command(&self.claude_executor, command, &env.working_dir, env_vars),
+        )
+        .await?;
+        self.record_fixture(FixtureKind::Claude, command, &result)?;
+        Ok(result)
     }
 
     pub(crate) async fn execute_shell_command(
@@ -190,7 +578,59 @@ impl WorkflowExecutor {
         env_vars: HashMap<String, String>,
         timeout: Option<u64>,
     ) -> Result<StepResult> {
-        execute_shell_command(command, &env.working_dir, env_vars, timeout).await
+        if let Some(replayed) = self.replay_fixture(FixtureKind::Shell, command)? {
+            return Ok(replayed);
+        }
+        let source = match command.char_indices().nth(57) {
+            Some((end, _)) => format!("shell: {}...", &command[..end]),
+            None => format!("shell: {}", command),
+        };
+        let result = self
+            .run_shell(command, source, env, env_vars, timeout)
+            .await?;
+        self.record_fixture(FixtureKind::Shell, command, &result)?;
+        Ok(result)
+    }
+
+    /// Run a shell command, reporting its progress under `source`
+    async fn run_shell(
+        &self,
+        command: &str,
+        source: String,
+        env: &ExecutionEnvironment,
+        env_vars: HashMap<String, String>,
+        timeout: Option<u64>,
+    ) -> Result<StepResult> {
+        let progress = StepProgressReporter::new(&env.session_id, source, command);
+        run_with_chaos(
+            self.chaos.as_deref(),
+            ChaosTarget::Shell,
+            command,
+            execute_shell_command(command, &env.working_dir, env_vars, timeout, &|line| {
+                progress.observe_line(line)
+            }),
+        )
+        .await
+    }
+
+    /// Serve a recorded output instead of executing the command when replaying
+    fn replay_fixture(&self, kind: FixtureKind, command: &str) -> Result<Option<StepResult>> {
+        match &self.replay_session {
+            Some(session) if session.is_replaying() => {
+                let fixture = session.next_fixture(kind, command)?;
+                tracing::info!("Replaying recorded output for {}: {}", kind, command);
+                Ok(Some(fixture_to_step_result(fixture)))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Record a command output when recording is enabled
+    fn record_fixture(&self, kind: FixtureKind, command: &str, result: &StepResult) -> Result<()> {
+        match &self.replay_session {
+            Some(session) => session.record_fixture(step_result_to_fixture(kind, command, result)),
+            None => Ok(()),
+        }
     }
 
     async fn execute_shell_for_step(
@@ -415,6 +855,7 @@ mod tests {
             std::path::Path::new("/tmp"),
             HashMap::new(),
             None,
+            &|_| {},
         )
         .await
         .unwrap();
@@ -429,6 +870,7 @@ mod tests {
             std::path::Path::new("/tmp"),
             HashMap::new(),
             Some(1),
+            &|_| {},
         )
         .await
         .unwrap();
@@ -3,7 +3,10 @@
 //! This module handles building interpolation contexts, managing variables,
 //! and formatting variable values for display with masking support.
 
-use crate::cook::execution::interpolation::InterpolationContext;
+use crate::cook::execution::claude_capabilities::PERMISSION_MODE_ENV;
+use crate::cook::execution::claude_sessions::SESSION_REUSE_ENV;
+use crate::cook::execution::interpolation::{InterpolationContext, InterpolationEngine};
+use crate::cook::execution::CLAUDE_MODEL_ENV;
 use crate::cook::orchestrator::ExecutionEnvironment;
 use crate::cook::workflow::executor::{
     VariableResolution, WorkflowExecutor, WorkflowStep, BRACED_VAR_REGEX, UNBRACED_VAR_REGEX,
@@ -33,6 +36,8 @@ pub struct WorkflowContext {
     pub variable_store: Arc<crate::cook::workflow::variables::VariableStore>,
     /// Git change tracker for file and commit tracking variables
     pub git_tracker: Option<Arc<std::sync::Mutex<GitChangeTracker>>>,
+    /// Fail steps that reference undefined `${...}` variables
+    pub strict_variables: bool,
 }
 
 impl Default for WorkflowContext {
@@ -44,6 +49,7 @@ impl Default for WorkflowContext {
             validation_results: HashMap::new(),
             variable_store: Arc::new(crate::cook::workflow::variables::VariableStore::new()),
             git_tracker: None,
+            strict_variables: false,
         }
     }
 }
@@ -53,6 +59,17 @@ impl WorkflowContext {
     pub fn build_interpolation_context(&self) -> InterpolationContext {
         let mut context = InterpolationContext::new();
 
+        // Add git.* metadata first so user-defined variables can override it
+        if let Some(ref git_tracker) = self.git_tracker {
+            if let Ok(tracker) = git_tracker.lock() {
+                if let Some(metadata) = tracker.metadata() {
+                    for (key, value) in metadata.variables() {
+                        context.set(key, Value::String(value));
+                    }
+                }
+            }
+        }
+
         // Add variables as strings
         for (key, value) in &self.variables {
             context.set(key.clone(), Value::String(value.clone()));
@@ -325,6 +342,29 @@ impl WorkflowContext {
         })
     }
 
+    /// Check that a template references only defined variables
+    ///
+    /// Does nothing unless `strict_variables` is set. Unbraced `$VAR`
+    /// references and `${var:-default}` references are always accepted, as
+    /// are names set in `env_vars` or the process environment.
+    pub fn ensure_variables_defined(
+        &self,
+        template: &str,
+        env_vars: &HashMap<String, String>,
+    ) -> anyhow::Result<()> {
+        if !self.strict_variables {
+            return Ok(());
+        }
+
+        let context = self.build_interpolation_context();
+        let undefined = InterpolationEngine::new(false).undefined_references(template, &context)?;
+        let undefined = without_environment_variables(undefined, env_vars);
+        match format_undefined_variables(&undefined, template) {
+            Some(message) => Err(anyhow::anyhow!(message)),
+            None => Ok(()),
+        }
+    }
+
     /// Resolve a variable path from the store (async)
     ///
     /// Queries the variable store for a value at the given path. Paths can be
@@ -338,6 +378,41 @@ impl WorkflowContext {
     }
 }
 
+/// Drop names that the step environment or the process environment defines
+///
+/// The step environment holds the step's `env:` and the workflow's `env:`
+/// block, and the shell expands these references when the step runs. This
+/// covers names like `${HOME}` and secret parameter placeholders such as
+/// `${PRODIGY_PARAM_TOKEN}`.
+pub fn without_environment_variables(
+    undefined: Vec<String>,
+    env_vars: &HashMap<String, String>,
+) -> Vec<String> {
+    undefined
+        .into_iter()
+        .filter(|name| !env_vars.contains_key(name) && std::env::var_os(name).is_none())
+        .collect()
+}
+
+/// Error message for undefined variables, `None` if there are none (pure function)
+pub fn format_undefined_variables(undefined: &[String], template: &str) -> Option<String> {
+    if undefined.is_empty() {
+        return None;
+    }
+    let names: Vec<String> = undefined
+        .iter()
+        .map(|name| format!("${{{}}}", name))
+        .collect();
+    Some(format!(
+        "Undefined variable{} {} in '{}'. Define {}, or write ${{{}:-}} to allow an empty value",
+        if undefined.len() == 1 { "" } else { "s" },
+        names.join(", "),
+        template,
+        if undefined.len() == 1 { "it" } else { "them" },
+        undefined[0]
+    ))
+}
+
 impl WorkflowExecutor {
     /// Log variable resolutions in debug mode
     pub fn log_variable_resolutions(&self, resolutions: &[VariableResolution]) {
@@ -504,7 +579,7 @@ impl WorkflowExecutor {
     pub fn prepare_env_vars(
         &self,
         step: &WorkflowStep,
-        _env: &ExecutionEnvironment,
+        env: &ExecutionEnvironment,
         ctx: &mut WorkflowContext,
     ) -> HashMap<String, String> {
         let mut env_vars = HashMap::new();
@@ -512,11 +587,32 @@ impl WorkflowExecutor {
         // Add automation flag
         env_vars.insert("PRODIGY_AUTOMATION".to_string(), "true".to_string());
 
+        // Let steps record progress with `prodigy checkpoint-mark`
+        env_vars.insert(
+            crate::cook::workflow::checkpoint_marks::SESSION_ID_ENV.to_string(),
+            env.session_id.to_string(),
+        );
+
         // Propagate PRODIGY_CLAUDE_STREAMING environment variable if set (spec 129)
         if let Ok(streaming_val) = std::env::var("PRODIGY_CLAUDE_STREAMING") {
             env_vars.insert("PRODIGY_CLAUDE_STREAMING".to_string(), streaming_val);
         }
 
+        // Propagate warm Claude session reuse if requested
+        if let Ok(reuse) = std::env::var(SESSION_REUSE_ENV) {
+            env_vars.insert(SESSION_REUSE_ENV.to_string(), reuse);
+        }
+
+        // Propagate the requested Claude permission mode if set
+        if let Ok(mode) = std::env::var(PERMISSION_MODE_ENV) {
+            env_vars.insert(PERMISSION_MODE_ENV.to_string(), mode);
+        }
+
+        // Propagate the default Claude model if set; a step's `model:` overrides it
+        if let Ok(model) = std::env::var(CLAUDE_MODEL_ENV) {
+            env_vars.insert(CLAUDE_MODEL_ENV.to_string(), model);
+        }
+
         // Add step-specific environment variables with interpolation
         for (key, value) in &step.env {
             let (interpolated_value, resolutions) = ctx.interpolate_with_tracking(value);
@@ -537,6 +633,7 @@ mod tests {
     use crate::cook::workflow::executor::WorkflowContext;
     use crate::cook::workflow::variables::{CapturedValue, VariableStore};
     use serde_json::json;
+    use std::collections::HashMap;
     use std::sync::Arc;
 
     #[tokio::test]
@@ -616,4 +713,49 @@ mod tests {
 
         assert_eq!(result, "Processed 10.0: 8.0 ok, 2.0 failed");
     }
+
+    #[test]
+    fn test_ensure_variables_defined() {
+        let mut ctx = WorkflowContext::default();
+        ctx.variables
+            .insert("build_dir".to_string(), "target".to_string());
+        let template = "rm -rf ${build_dir}/${cache_dir} $HOME ${opt:-}";
+
+        let env_vars = HashMap::new();
+
+        // Lenient by default
+        assert!(ctx.ensure_variables_defined(template, &env_vars).is_ok());
+
+        ctx.strict_variables = true;
+        let err = ctx
+            .ensure_variables_defined(template, &env_vars)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Undefined variable ${cache_dir} in 'rm -rf ${build_dir}/${cache_dir} $HOME ${opt:-}'. \
+             Define it, or write ${cache_dir:-} to allow an empty value"
+        );
+        assert!(ctx
+            .ensure_variables_defined("echo ${build_dir} ${cache_dir:-}", &env_vars)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_environment_variables_are_defined() {
+        let ctx = WorkflowContext {
+            strict_variables: true,
+            ..Default::default()
+        };
+        let template = "deploy --token ${PRODIGY_PARAM_TOKEN} --path ${PATH}";
+
+        let err = ctx
+            .ensure_variables_defined(template, &HashMap::new())
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("Undefined variable ${PRODIGY_PARAM_TOKEN} in"));
+
+        let env_vars = HashMap::from([("PRODIGY_PARAM_TOKEN".to_string(), "secret".to_string())]);
+        assert!(ctx.ensure_variables_defined(template, &env_vars).is_ok());
+    }
 }
@@ -42,6 +42,18 @@ pub struct WorkflowStep {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub claude: Option<String>,
 
+    /// Model passed to the Claude CLI for this step
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+
+    /// Repository files injected into the Claude command
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context: Option<crate::cook::workflow::context_pack::ContextPackConfig>,
+
+    /// Contract the Claude response must satisfy, re-prompting on failure
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub validate_output: Option<crate::cook::workflow::output_validation::OutputValidationConfig>,
+
     /// Shell command to execute
     #[serde(skip_serializing_if = "Option::is_none")]
     pub shell: Option<String>,
@@ -58,6 +70,30 @@ pub struct WorkflowStep {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub write_file: Option<crate::config::command::WriteFileConfig>,
 
+    /// Git and filesystem state assertions
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assert: Option<crate::cook::workflow::assertions::AssertConfig>,
+
+    /// Summary of the commits made during the workflow
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summarize_commits: Option<crate::cook::workflow::commit_summary::SummarizeCommitsConfig>,
+
+    /// Messages sent to Claude within one conversation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub claude_chat: Option<crate::cook::workflow::claude_chat::ClaudeChatConfig>,
+
+    /// Inline script run with the selected interpreter
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub script: Option<crate::cook::workflow::script::ScriptConfig>,
+
+    /// Record a snapshot of the working tree before the step runs
+    #[serde(default)]
+    pub snapshot: bool,
+
+    /// Fail a `--keep-going` run if this step fails
+    #[serde(default)]
+    pub required: bool,
+
     /// Legacy command field (for backward compatibility)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub command: Option<String>,
@@ -90,8 +126,8 @@ pub struct WorkflowStep {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub timeout: Option<u64>,
 
-    /// Working directory for the command
-    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Working directory for the command, relative to the worktree
+    #[serde(alias = "cwd", skip_serializing_if = "Option::is_none")]
     pub working_dir: Option<PathBuf>,
 
     /// Environment variables
@@ -122,6 +158,10 @@ pub struct WorkflowStep {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub commit_config: Option<crate::cook::commit_tracker::CommitConfig>,
 
+    /// Commit counts, paths and messages verified after the step runs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit_policy: Option<crate::cook::commit_tracker::CommitPolicy>,
+
     /// Auto-commit if changes detected
     #[serde(default)]
     #[serde(skip_serializing_if = "is_false")]
@@ -249,5 +289,9 @@ pub struct ExtendedWorkflowConfig {
     pub retry_defaults: Option<crate::cook::retry_v2::RetryConfig>,
     /// Global environment configuration
     pub environment: Option<crate::cook::environment::EnvironmentConfig>,
+    /// Cleanup steps run after `steps` whatever their outcome
+    pub always_steps: Vec<WorkflowStep>,
+    /// Fail steps that reference undefined `${...}` variables
+    pub strict_variables: bool,
     // collect_metrics removed - MMM focuses on orchestration, not metrics
 }
@@ -0,0 +1,302 @@
+//! Ordered `on_failure` handler chains
+//!
+//! A chain tries its handlers in order, retrying the original step after each
+//! handler that succeeds, until the step recovers, a handler with an explicit
+//! `outcome` fails, or `max_total_attempts` (handler runs plus retries) is
+//! used up. Handlers may carry their own `on_failure`, chains included.
+//!
+//! Outcomes decide how far a failure spreads: `continue` contains it,
+//! `fail_step` fails only the step (an enclosing chain moves on to its next
+//! handler), and `fail_workflow` escapes every enclosing chain.
+//!
+//! Chain progress is kept as a stack of [`FailureChainState`], outermost
+//! first, and written to checkpoints before each handler runs so a resumed
+//! run picks up at the handler that was in flight.
+
+use super::{
+    checkpoint, failure_handler, orchestration, StepResult, WorkflowContext, WorkflowExecutor,
+    WorkflowStep,
+};
+use crate::cook::orchestrator::ExecutionEnvironment;
+use crate::cook::workflow::checkpoint::FailureChainState;
+use crate::cook::workflow::on_failure::{
+    ChainHandler, FailureChainConfig, FailureOutcome, HandlerCommand,
+};
+use anyhow::Result;
+
+/// Error raised by a `fail_workflow` outcome; enclosing chains let it through
+#[derive(Debug, thiserror::Error)]
+#[error("on_failure chain for '{step}' failed the workflow")]
+pub struct FailureChainAbort {
+    /// Display name of the step whose chain gave up
+    pub step: String,
+}
+
+/// Whether the attempt budget allows one more attempt (pure function)
+pub fn attempts_left(max_total_attempts: Option<u32>, attempts: u32) -> bool {
+    max_total_attempts.is_none_or(|max| attempts < max)
+}
+
+/// Handler index and attempts to resume a chain from, if the checkpoint
+/// recorded it in flight at `depth` for `step` (pure function)
+pub fn resume_point(
+    resumed: &[FailureChainState],
+    depth: usize,
+    step: &str,
+) -> Option<(usize, u32)> {
+    resumed
+        .get(depth)
+        .filter(|state| state.step == step && state.outcome.is_none())
+        .map(|state| (state.handler_index, state.attempts))
+}
+
+/// Build the step that runs a chain handler (pure function)
+fn chain_handler_step(handler: &ChainHandler) -> WorkflowStep {
+    let command = HandlerCommand {
+        shell: handler.shell.clone(),
+        claude: handler.claude.clone(),
+        continue_on_error: false,
+    };
+    let mut step = failure_handler::create_handler_step(&command, handler.timeout);
+    step.on_failure = handler.on_failure.as_deref().cloned();
+    step
+}
+
+impl WorkflowExecutor {
+    /// Run an `on_failure` chain for a failed step
+    pub(crate) async fn execute_failure_chain(
+        &mut self,
+        step: &WorkflowStep,
+        mut result: StepResult,
+        chain: &FailureChainConfig,
+        env: &ExecutionEnvironment,
+        ctx: &mut WorkflowContext,
+    ) -> Result<StepResult> {
+        let step_name = self.get_step_display_name(step);
+
+        // Chains that already finished are only kept for the error checkpoint
+        self.failure_chains.retain(|state| state.outcome.is_none());
+        let depth = self.failure_chains.len();
+        let (mut handler_index, mut attempts) =
+            match resume_point(&self.resumed_failure_chains, depth, &step_name) {
+                Some(point) => {
+                    self.user_interaction.display_info(&format!(
+                        "Resuming on_failure chain at handler {}/{}",
+                        point.0 + 1,
+                        chain.handlers.len()
+                    ));
+                    point
+                }
+                None => (0, 0),
+            };
+        self.failure_chains.push(FailureChainState {
+            step: step_name.clone(),
+            handler_index,
+            attempts,
+            outcome: None,
+        });
+
+        // `None` means the step recovered
+        let outcome = loop {
+            let Some(handler) = chain.handlers.get(handler_index) else {
+                break Some(chain.outcome);
+            };
+            if !attempts_left(chain.max_total_attempts, attempts) {
+                self.user_interaction
+                    .display_warning(&format!("on_failure chain used all {} attempts", attempts));
+                break Some(chain.outcome);
+            }
+
+            self.record_failure_chain_progress(depth, handler_index, attempts, ctx)
+                .await;
+            self.user_interaction.display_info(&format!(
+                "Executing on_failure handler {}/{}...",
+                handler_index + 1,
+                chain.handlers.len()
+            ));
+            attempts += 1;
+
+            let handler_step = chain_handler_step(handler);
+            let handler_success = match Box::pin(self.execute_step(&handler_step, env, ctx)).await {
+                Ok(handler_result) => {
+                    result = failure_handler::append_handler_output(
+                        result,
+                        std::slice::from_ref(&handler_result.stdout),
+                    );
+                    handler_result.success
+                }
+                Err(e) if e.is::<FailureChainAbort>() => {
+                    self.failure_chains.truncate(depth);
+                    return Err(e);
+                }
+                Err(e) => {
+                    self.user_interaction.display_error(&format!(
+                        "on_failure handler {} failed: {}",
+                        handler_index + 1,
+                        e
+                    ));
+                    false
+                }
+            };
+
+            if !handler_success {
+                if let Some(outcome) = handler.outcome {
+                    break Some(outcome);
+                }
+                handler_index += 1;
+                continue;
+            }
+
+            if !chain.retry_original {
+                result = failure_handler::mark_step_recovered(result);
+                break None;
+            }
+            if !attempts_left(chain.max_total_attempts, attempts) {
+                break Some(chain.outcome);
+            }
+
+            attempts += 1;
+            self.user_interaction
+                .display_info("Retrying original command after on_failure handler");
+            let mut retry_step = step.clone();
+            retry_step.on_failure = None;
+            match Box::pin(self.execute_step(&retry_step, env, ctx)).await {
+                Ok(retry_result) if retry_result.success => {
+                    result = retry_result;
+                    break None;
+                }
+                Err(e) if e.is::<FailureChainAbort>() => {
+                    self.failure_chains.truncate(depth);
+                    return Err(e);
+                }
+                _ => handler_index += 1,
+            }
+        };
+
+        if depth == 0 {
+            self.resumed_failure_chains.clear();
+        }
+
+        match outcome {
+            None => {
+                self.failure_chains.truncate(depth);
+                self.user_interaction
+                    .display_success("Step recovered through on_failure chain");
+                Ok(result)
+            }
+            Some(FailureOutcome::Continue) => {
+                self.failure_chains.truncate(depth);
+                result.success = true;
+                result.stdout.push_str(
+                    "\n[Note: Command failed but workflow continues due to on_failure outcome]",
+                );
+                Ok(result)
+            }
+            Some(outcome) => {
+                // Keep the finished chain so the error checkpoint records how it ended
+                self.failure_chains.truncate(depth + 1);
+                if let Some(state) = self.failure_chains.get_mut(depth) {
+                    state.handler_index = handler_index;
+                    state.attempts = attempts;
+                    state.outcome = Some(outcome);
+                }
+                if outcome == FailureOutcome::FailWorkflow {
+                    return Err(FailureChainAbort { step: step_name }.into());
+                }
+                Ok(result)
+            }
+        }
+    }
+
+    /// Record the handler about to run and checkpoint it
+    async fn record_failure_chain_progress(
+        &mut self,
+        depth: usize,
+        handler_index: usize,
+        attempts: u32,
+        ctx: &WorkflowContext,
+    ) {
+        self.failure_chains.truncate(depth + 1);
+        if let Some(state) = self.failure_chains.get_mut(depth) {
+            state.handler_index = handler_index;
+            state.attempts = attempts;
+        }
+
+        let (
+            Some(checkpoint_manager),
+            Some(workflow_id),
+            Some((workflow_name, total_steps)),
+            Some(step_index),
+        ) = (
+            &self.checkpoint_manager,
+            &self.workflow_id,
+            &self.current_workflow_shape,
+            self.current_step_index,
+        )
+        else {
+            return;
+        };
+
+        let normalized_workflow = orchestration::create_normalized_workflow(workflow_name, ctx);
+        let mut checkpoint = checkpoint::create_checkpoint_with_total_steps(
+            workflow_id.clone(),
+            &normalized_workflow,
+            ctx,
+            self.checkpoint_completed_steps.clone(),
+            step_index,
+            orchestration::create_workflow_hash(workflow_name, *total_steps),
+            *total_steps,
+        );
+        checkpoint.workflow_path = self.workflow_path.clone();
+        checkpoint.step_selection = self.recorded_step_selection();
+        checkpoint.failure_chains = self.failure_chains.clone();
+
+        if let Err(e) = checkpoint_manager.save_checkpoint(&checkpoint).await {
+            tracing::warn!("Failed to save on_failure chain checkpoint: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(
+        step: &str,
+        handler_index: usize,
+        outcome: Option<FailureOutcome>,
+    ) -> FailureChainState {
+        FailureChainState {
+            step: step.to_string(),
+            handler_index,
+            attempts: 3,
+            outcome,
+        }
+    }
+
+    #[test]
+    fn test_attempts_left() {
+        assert!(attempts_left(None, 100));
+        assert!(attempts_left(Some(3), 2));
+        assert!(!attempts_left(Some(3), 3));
+        assert!(!attempts_left(Some(0), 0));
+    }
+
+    #[test]
+    fn test_resume_point_matches_in_flight_chain() {
+        let resumed = vec![
+            state("shell: make", 1, None),
+            state("claude: /fix", 2, None),
+        ];
+        assert_eq!(resume_point(&resumed, 0, "shell: make"), Some((1, 3)));
+        assert_eq!(resume_point(&resumed, 1, "claude: /fix"), Some((2, 3)));
+        assert_eq!(resume_point(&resumed, 0, "shell: other"), None);
+        assert_eq!(resume_point(&resumed, 2, "shell: make"), None);
+    }
+
+    #[test]
+    fn test_resume_point_ignores_finished_chain() {
+        let resumed = vec![state("shell: make", 1, Some(FailureOutcome::FailStep))];
+        assert_eq!(resume_point(&resumed, 0, "shell: make"), None);
+    }
+}
@@ -40,11 +40,15 @@ pub fn calculate_retry_delay(retry_config: &RetryConfig, attempt: u32) -> Durati
 
 /// Apply jitter to a delay duration
 ///
-/// Adds randomness to prevent thundering herd problems.
-#[allow(deprecated)] // rand::thread_rng deprecated in favor of rand::rng
-pub fn apply_jitter(delay: Duration, jitter_factor: f64) -> Duration {
+/// Adds randomness to prevent thundering herd problems; repeatable when
+/// `seed` is given.
+pub fn apply_jitter(delay: Duration, jitter_factor: f64, seed: Option<u64>) -> Duration {
     use rand::Rng;
-    let mut rng = rand::rng();
+    let mut rng = crate::cook::execution::determinism::rng(
+        seed,
+        "step-retry-jitter",
+        delay.as_nanos() as u64,
+    );
     let jitter_range = delay.as_secs_f64() * jitter_factor;
     let jitter = rng.random_range(-jitter_range..=jitter_range);
     let adjusted = delay.as_secs_f64() + jitter;
@@ -276,6 +280,9 @@ pub fn create_handler_step(
         name: None,
         shell: cmd.shell.clone(),
         claude: cmd.claude.clone(),
+        model: None,
+        context: None,
+        validate_output: None,
         test: None,
         foreach: None,
         write_file: None,
@@ -302,6 +309,13 @@ pub fn create_handler_step(
         validation_timeout: None,
         ignore_validation_failure: false,
         when: None,
+        assert: None,
+        summarize_commits: None,
+        claude_chat: None,
+        script: None,
+        snapshot: false,
+        required: false,
+        commit_policy: None,
     }
 }
 
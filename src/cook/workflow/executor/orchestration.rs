@@ -24,6 +24,7 @@ use anyhow::{anyhow, Context as AnyhowContext, Result};
 use crate::cook::environment::EnvironmentConfig;
 use crate::cook::execution::MapPhase;
 use crate::cook::workflow::{ExtendedWorkflowConfig, WorkflowStep};
+use crate::styled_println;
 
 use super::{
     normalized, CheckpointCompletedStep, ExecutionEnvironment, StepResult, WorkflowContext,
@@ -76,7 +77,7 @@ pub async fn validate_mapreduce_dry_run(workflow: &ExtendedWorkflowConfig) -> Re
         Ok(report) => {
             // Display the validation report
             let formatter = OutputFormatter::new();
-            println!("{}", formatter.format_human(&report));
+            styled_println!("{}", formatter.format_human(&report));
 
             if report.errors.is_empty() {
                 println!("\n[DRY RUN] Validation successful! Workflow is ready to execute.");
@@ -239,6 +240,7 @@ pub fn build_session_step_result(
         started_at: step_started_at,
         completed_at: step_completed_at,
         exit_code: step_result.exit_code,
+        model: step.model.clone(),
     }
 }
 
@@ -267,6 +269,7 @@ pub fn build_checkpoint_step(
         duration: command_duration,
         completed_at: step_completed_at,
         retry_state: None,
+        changes: None,
     }
 }
 
@@ -286,6 +289,16 @@ pub fn format_step_progress(step_index: usize, total_steps: usize, step_display:
     )
 }
 
+/// Format a step completion message with its elapsed time
+pub fn format_step_completed(step_index: usize, total_steps: usize, elapsed: Duration) -> String {
+    format!(
+        "Step {}/{} completed in {}",
+        step_index + 1,
+        total_steps,
+        crate::cook::execution::progress::format_duration(elapsed)
+    )
+}
+
 /// Format an iteration progress message
 ///
 /// Creates a consistent progress message showing current iteration.
@@ -408,6 +421,9 @@ mod tests {
 
         let msg = format_step_progress(4, 5, "last step");
         assert_eq!(msg, "Executing step 5/5: last step");
+
+        let msg = format_step_completed(1, 5, Duration::from_secs(75));
+        assert_eq!(msg, "Step 2/5 completed in 1m 15s");
     }
 
     #[test]
@@ -503,6 +519,7 @@ mod tests {
             validation_results: HashMap::new(),
             variable_store: Arc::new(VariableStore::new()),
             git_tracker: None,
+            strict_variables: false,
         };
 
         let normalized = create_normalized_workflow("test-workflow", &context);
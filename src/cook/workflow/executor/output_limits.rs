@@ -0,0 +1,235 @@
+//! Step output size limits
+//!
+//! Outputs larger than `output.max_capture_bytes` are cut down before they
+//! reach variables such as `${shell.output}`, checkpoints and Claude prompts.
+//! The full output is first written to the log artifact store and its path
+//! exposed as `${shell.output_file}` (`${test.output_file}`, ... for other
+//! command types).
+
+use super::retry_logic::format_shell_output;
+use super::{CommandType, StepResult, WorkflowContext, WorkflowExecutor, WorkflowStep};
+use crate::config::TruncationStrategy;
+use crate::cook::orchestrator::ExecutionEnvironment;
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+
+/// Variable holding the path of a step's full output (pure function)
+pub fn output_file_variable(command_type: &CommandType) -> &'static str {
+    match command_type {
+        CommandType::Claude(_) | CommandType::Legacy(_) => "claude.output_file",
+        CommandType::Shell(_) => "shell.output_file",
+        CommandType::Handler { .. } => "handler.output_file",
+        CommandType::Test(_) => "test.output_file",
+        CommandType::Foreach(_) => "foreach.output_file",
+        CommandType::WriteFile(_) => "write_file.output_file",
+        CommandType::Assert(_) => "assert.output_file",
+        CommandType::SummarizeCommits(_) => "summarize_commits.output_file",
+        CommandType::ClaudeChat(_) => "claude_chat.output_file",
+        CommandType::Script(_) => "script.output_file",
+    }
+}
+
+/// Largest char boundary of `text` not after `index` (pure function)
+fn floor_char_boundary(text: &str, index: usize) -> usize {
+    let mut index = index.min(text.len());
+    while !text.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+/// Smallest char boundary of `text` not before `index` (pure function)
+fn ceil_char_boundary(text: &str, index: usize) -> usize {
+    let mut index = index.min(text.len());
+    while !text.is_char_boundary(index) {
+        index += 1;
+    }
+    index
+}
+
+/// Cut `output` down to about `max_bytes`, or `None` if it already fits (pure function)
+///
+/// A marker line records how much was dropped and where the full output is.
+pub fn truncate_output(
+    output: &str,
+    max_bytes: usize,
+    strategy: TruncationStrategy,
+    full_output: Option<&Path>,
+) -> Option<String> {
+    if output.len() <= max_bytes {
+        return None;
+    }
+
+    let (head_bytes, tail_bytes) = match strategy {
+        TruncationStrategy::Head => (max_bytes, 0),
+        TruncationStrategy::Tail => (0, max_bytes),
+        TruncationStrategy::HeadTail => (max_bytes / 4, max_bytes - max_bytes / 4),
+    };
+    let head_end = floor_char_boundary(output, head_bytes);
+    let tail_start = ceil_char_boundary(output, output.len() - tail_bytes);
+
+    let marker = match full_output {
+        Some(path) => format!(
+            "... [{} bytes truncated, full output: {}] ...",
+            tail_start - head_end,
+            path.display()
+        ),
+        None => format!("... [{} bytes truncated] ...", tail_start - head_end),
+    };
+
+    let mut truncated = String::with_capacity(max_bytes + marker.len() + 2);
+    truncated.push_str(&output[..head_end]);
+    if head_end > 0 && !truncated.ends_with('\n') {
+        truncated.push('\n');
+    }
+    truncated.push_str(&marker);
+    if tail_start < output.len() {
+        truncated.push('\n');
+        truncated.push_str(&output[tail_start..]);
+    }
+    Some(truncated)
+}
+
+/// File name for a step's full output (pure function)
+fn artifact_file_name(step_name: &str, timestamp: chrono::DateTime<chrono::Utc>) -> String {
+    let slug: String = step_name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .take(40)
+        .collect();
+    format!(
+        "{}-{}.log",
+        timestamp.format("%Y%m%dT%H%M%S%3f"),
+        slug.trim_matches('-')
+    )
+}
+
+impl WorkflowExecutor {
+    /// Truncate oversized step output, saving the full output first
+    pub(crate) async fn limit_step_output(
+        &self,
+        step: &WorkflowStep,
+        command_type: &CommandType,
+        result: &mut StepResult,
+        env: &ExecutionEnvironment,
+        ctx: &mut WorkflowContext,
+    ) {
+        let variable = output_file_variable(command_type);
+        ctx.variables.remove(variable);
+
+        let Some(max_bytes) = self.output_settings.max_capture_bytes else {
+            return;
+        };
+        if result.stdout.len() <= max_bytes && result.stderr.len() <= max_bytes {
+            return;
+        }
+
+        let step_name = self.get_step_display_name(step);
+        let full_output = match self.save_full_output(&step_name, result, env).await {
+            Ok(path) => {
+                ctx.variables
+                    .insert(variable.to_string(), path.display().to_string());
+                Some(path)
+            }
+            Err(e) => {
+                tracing::warn!("Failed to save full output of {}: {}", step_name, e);
+                None
+            }
+        };
+
+        let strategy = self.output_settings.truncation;
+        let original_len = result.stdout.len() + result.stderr.len();
+        if let Some(stdout) =
+            truncate_output(&result.stdout, max_bytes, strategy, full_output.as_deref())
+        {
+            result.stdout = stdout;
+        }
+        if let Some(stderr) =
+            truncate_output(&result.stderr, max_bytes, strategy, full_output.as_deref())
+        {
+            result.stderr = stderr;
+        }
+        tracing::info!(
+            "Truncated {} bytes of output from {} to {} bytes",
+            original_len,
+            step_name,
+            result.stdout.len() + result.stderr.len()
+        );
+    }
+
+    /// Write a step's full output to the log artifact store
+    async fn save_full_output(
+        &self,
+        step_name: &str,
+        result: &StepResult,
+        env: &ExecutionEnvironment,
+    ) -> Result<PathBuf> {
+        let base_dir = match &self.output_settings.artifact_dir {
+            Some(dir) => dir.clone(),
+            None => {
+                let repo_name = crate::storage::extract_repo_name(&env.project_dir)?;
+                crate::storage::GlobalStorage::new()?
+                    .get_logs_dir(&repo_name)
+                    .await?
+                    .join("outputs")
+            }
+        };
+        let dir = base_dir.join(env.session_id.as_ref());
+        tokio::fs::create_dir_all(&dir).await?;
+
+        let path = dir.join(artifact_file_name(step_name, chrono::Utc::now()));
+        tokio::fs::write(&path, format_shell_output(&result.stdout, &result.stderr)).await?;
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate_output_within_limit() {
+        assert_eq!(
+            truncate_output("short", 10, TruncationStrategy::HeadTail, None),
+            None
+        );
+    }
+
+    #[test]
+    fn test_truncate_output_strategies() {
+        let output = "0123456789abcdefghij";
+        let path = Path::new("/tmp/full.log");
+
+        let head = truncate_output(output, 8, TruncationStrategy::Head, Some(path)).unwrap();
+        assert_eq!(
+            head,
+            "01234567\n... [12 bytes truncated, full output: /tmp/full.log] ..."
+        );
+
+        let tail = truncate_output(output, 8, TruncationStrategy::Tail, None).unwrap();
+        assert_eq!(tail, "... [12 bytes truncated] ...\ncdefghij");
+
+        let both = truncate_output(output, 8, TruncationStrategy::HeadTail, None).unwrap();
+        assert_eq!(both, "01\n... [12 bytes truncated] ...\nefghij");
+    }
+
+    #[test]
+    fn test_truncate_output_respects_char_boundaries() {
+        let output = "ééééé"; // 2 bytes per char
+        let head = truncate_output(output, 5, TruncationStrategy::Head, None).unwrap();
+        assert!(head.starts_with("éé\n"));
+        let tail = truncate_output(output, 5, TruncationStrategy::Tail, None).unwrap();
+        assert!(tail.ends_with("\néé"));
+    }
+
+    #[test]
+    fn test_artifact_file_name() {
+        let timestamp = chrono::DateTime::parse_from_rfc3339("2025-01-02T03:04:05.678Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        assert_eq!(
+            artifact_file_name("shell: cargo test --all", timestamp),
+            "20250102T030405678-shell--cargo-test---all.log"
+        );
+    }
+}
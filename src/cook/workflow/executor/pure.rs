@@ -13,6 +13,7 @@ use crate::cook::execution::interpolation::InterpolationContext;
 use crate::cook::workflow::{WorkflowMode, WorkflowStep};
 use anyhow::Result;
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
 // Import parent module types
 use super::{ExtendedWorkflowConfig, StepResult};
@@ -148,6 +149,14 @@ pub fn get_step_display_name(step: &WorkflowStep) -> String {
         format!("test: {}", test_cmd.command)
     } else if let Some(handler_step) = &step.handler {
         format!("handler: {}", handler_step.name)
+    } else if step.assert.is_some() {
+        "assert".to_string()
+    } else if step.summarize_commits.is_some() {
+        "summarize_commits".to_string()
+    } else if step.claude_chat.is_some() {
+        "claude_chat".to_string()
+    } else if let Some(script) = &step.script {
+        format!("script: {}", script.lang)
     } else if let Some(name) = &step.name {
         name.clone()
     } else if let Some(command) = &step.command {
@@ -246,7 +255,8 @@ pub fn validate_commit_requirement(
     assumed_commits: &[String],
     json_log_location: Option<&str>,
 ) -> Result<()> {
-    if !step.commit_required {
+    // A commit policy checks the commit count itself, routing violations to on_failure
+    if !step.commit_required || step.commit_policy.is_some() {
         return Ok(());
     }
 
@@ -284,6 +294,28 @@ pub fn validate_commit_requirement(
     Err(anyhow::anyhow!(error_msg))
 }
 
+/// Validate that a step may run in read-only mode
+///
+/// Steps that require commits or auto-commit their changes cannot succeed
+/// without modifying the repository, so they are rejected up front.
+pub fn validate_read_only_step(step: &WorkflowStep, step_name: &str) -> Result<()> {
+    if step.commit_required {
+        return Err(anyhow::anyhow!(
+            "Step '{}' has commit_required=true, which is not allowed in read-only mode",
+            step_name
+        ));
+    }
+
+    if step.auto_commit {
+        return Err(anyhow::anyhow!(
+            "Step '{}' has auto_commit=true, which is not allowed in read-only mode",
+            step_name
+        ));
+    }
+
+    Ok(())
+}
+
 /// Build step commit variables from tracked commits
 pub fn build_commit_variables(
     tracked_commits: &[crate::cook::commit_tracker::TrackedCommit],
@@ -390,9 +422,12 @@ pub fn determine_no_commit_action(
     step: &WorkflowStep,
     has_changes: Result<bool>,
 ) -> CommitVerificationAction {
+    // Steps with a commit policy report missing commits as policy violations
+    let require_commit = step.commit_required && step.commit_policy.is_none();
+
     if !step.auto_commit {
         // Auto-commit disabled
-        return if step.commit_required {
+        return if require_commit {
             CommitVerificationAction::RequireCommitError
         } else {
             CommitVerificationAction::NoAction
@@ -407,7 +442,7 @@ pub fn determine_no_commit_action(
         }
         Ok(false) => {
             // No changes
-            if step.commit_required {
+            if require_commit {
                 CommitVerificationAction::RequireCommitError
             } else {
                 CommitVerificationAction::NoAction
@@ -415,7 +450,7 @@ pub fn determine_no_commit_action(
         }
         Err(_) => {
             // Failed to check changes
-            if step.commit_required {
+            if require_commit {
                 CommitVerificationAction::RequireCommitError
             } else {
                 CommitVerificationAction::NoAction
@@ -424,6 +459,17 @@ pub fn determine_no_commit_action(
     }
 }
 
+/// Resolve a step's working directory against the worktree (pure function)
+///
+/// Absolute directories are used as given.
+pub fn resolve_step_working_dir(dir: &Path, worktree: &Path) -> PathBuf {
+    if dir.is_absolute() {
+        dir.to_path_buf()
+    } else {
+        worktree.join(dir)
+    }
+}
+
 // ============================================================================
 // Command Type Determination Functions
 // ============================================================================
@@ -449,6 +495,18 @@ pub fn count_specified_commands(step: &WorkflowStep) -> usize {
     if step.write_file.is_some() {
         count += 1;
     }
+    if step.assert.is_some() {
+        count += 1;
+    }
+    if step.summarize_commits.is_some() {
+        count += 1;
+    }
+    if step.claude_chat.is_some() {
+        count += 1;
+    }
+    if step.script.is_some() {
+        count += 1;
+    }
     if step.name.is_some() || step.command.is_some() {
         count += 1;
     }
@@ -459,12 +517,12 @@ pub fn count_specified_commands(step: &WorkflowStep) -> usize {
 pub fn validate_single_command_type(count: usize) -> Result<()> {
     if count > 1 {
         return Err(anyhow::anyhow!(
-            "Multiple command types specified. Use only one of: claude, shell, test, handler, foreach, write_file, or name/command"
+            "Multiple command types specified. Use only one of: claude, shell, test, handler, foreach, write_file, assert, summarize_commits, claude_chat, script, or name/command"
         ));
     }
     if count == 0 {
         return Err(anyhow::anyhow!(
-            "No command specified. Use one of: claude, shell, test, handler, foreach, write_file, or name/command"
+            "No command specified. Use one of: claude, shell, test, handler, foreach, write_file, assert, summarize_commits, claude_chat, script, or name/command"
         ));
     }
     Ok(())
@@ -541,6 +599,10 @@ pub fn extract_command_name(command_type: &super::CommandType) -> &str {
         super::CommandType::Handler { handler_name, .. } => handler_name,
         super::CommandType::Foreach(_) => "foreach",
         super::CommandType::WriteFile(config) => &config.path,
+        super::CommandType::Assert(_) => "assert",
+        super::CommandType::SummarizeCommits(_) => "summarize_commits",
+        super::CommandType::ClaudeChat(_) => "claude_chat",
+        super::CommandType::Script(_) => "script",
     }
 }
 
@@ -594,6 +656,8 @@ mod tests {
             iterate: false,
             retry_defaults: None,
             environment: None,
+            always_steps: Vec::new(),
+            strict_variables: false,
         }
     }
 
@@ -654,6 +718,7 @@ mod tests {
             started_at: chrono::Utc::now(),
             completed_at: chrono::Utc::now(),
             error: None,
+            model: None,
         }
     }
 
@@ -747,6 +812,8 @@ mod tests {
     fn test_get_step_display_name_claude() {
         let step = WorkflowStep {
             claude: Some("test command".to_string()),
+            context: None,
+            validate_output: None,
             ..Default::default()
         };
         assert_eq!(get_step_display_name(&step), "claude: test command");
@@ -839,6 +906,8 @@ mod tests {
         let step = WorkflowStep {
             commit_required: true,
             claude: Some("test".to_string()),
+            context: None,
+            validate_output: None,
             ..Default::default()
         };
 
@@ -848,6 +917,29 @@ mod tests {
         assert!(result.unwrap_err().to_string().contains("no commits"));
     }
 
+    #[test]
+    fn test_validate_read_only_step() {
+        let step = WorkflowStep {
+            shell: Some("cargo test".to_string()),
+            ..Default::default()
+        };
+        assert!(validate_read_only_step(&step, "shell: cargo test").is_ok());
+
+        let step = WorkflowStep {
+            commit_required: true,
+            ..Default::default()
+        };
+        let err = validate_read_only_step(&step, "claude: /fix").unwrap_err();
+        assert!(err.to_string().contains("commit_required"));
+
+        let step = WorkflowStep {
+            auto_commit: true,
+            ..Default::default()
+        };
+        let err = validate_read_only_step(&step, "claude: /fix").unwrap_err();
+        assert!(err.to_string().contains("auto_commit"));
+    }
+
     #[test]
     fn test_build_commit_variables_empty() {
         let result = build_commit_variables(&[]);
@@ -954,4 +1046,17 @@ mod tests {
         let action = determine_no_commit_action(&step, Err(anyhow::anyhow!("error")));
         assert_eq!(action, CommitVerificationAction::NoAction);
     }
+
+    #[test]
+    fn test_resolve_step_working_dir() {
+        let worktree = Path::new("/worktrees/session-1");
+        assert_eq!(
+            resolve_step_working_dir(Path::new("frontend"), worktree),
+            PathBuf::from("/worktrees/session-1/frontend")
+        );
+        assert_eq!(
+            resolve_step_working_dir(Path::new("/tmp/build"), worktree),
+            PathBuf::from("/tmp/build")
+        );
+    }
 }
@@ -4,6 +4,7 @@
 //! extracted from commands.rs to reduce its size and improve separation of concerns.
 
 use crate::config::command::TestDebugConfig;
+use crate::cook::execution::CLAUDE_MODEL_ENV;
 use crate::cook::orchestrator::ExecutionEnvironment;
 use crate::cook::workflow::checkpoint;
 use crate::cook::workflow::NormalizedWorkflow;
@@ -59,6 +60,18 @@ pub fn should_fail_workflow_on_max_attempts(fail_workflow: bool) -> bool {
     fail_workflow
 }
 
+/// Environment for a Claude run, selecting `model` when one is given (pure function)
+pub fn with_escalated_model(
+    env_vars: &HashMap<String, String>,
+    model: Option<&str>,
+) -> HashMap<String, String> {
+    let mut env_vars = env_vars.clone();
+    if let Some(model) = model.map(str::trim).filter(|m| !m.is_empty()) {
+        env_vars.insert(CLAUDE_MODEL_ENV.to_string(), model.to_string());
+    }
+    env_vars
+}
+
 // ============================================================================
 // I/O Helper Functions
 // ============================================================================
@@ -125,6 +138,11 @@ impl WorkflowExecutor {
                     }
                 }
 
+                // Escalation-only configs retry without a debug command
+                let Some(mut debug_cmd) = debug_config.debug_command() else {
+                    continue;
+                };
+
                 let temp_file =
                     if should_use_temp_file(shell_result.stdout.len(), shell_result.stderr.len()) {
                         Some(create_output_temp_file(
@@ -139,8 +157,6 @@ impl WorkflowExecutor {
                     .as_ref()
                     .map(|f| f.path().to_string_lossy().to_string());
 
-                let mut debug_cmd = debug_config.claude.clone();
-
                 let output = if let Some(output_file) = output_path {
                     output_file
                 } else {
@@ -163,7 +179,11 @@ impl WorkflowExecutor {
                 ));
 
                 let debug_result = self
-                    .execute_claude_command(&debug_cmd, env, env_vars.clone())
+                    .execute_claude_command(
+                        &debug_cmd,
+                        env,
+                        with_escalated_model(&env_vars, debug_config.escalate_model.as_deref()),
+                    )
                     .await?;
 
                 if !debug_result.success {
@@ -235,6 +255,11 @@ impl WorkflowExecutor {
                     }
                 }
 
+                // Escalation-only configs retry without a debug command
+                let Some(mut debug_cmd) = debug_config.debug_command() else {
+                    continue;
+                };
+
                 // Save checkpoint after test failure but before retry
                 if let (Some(workflow), Some(step_index)) =
                     (&self.current_workflow, self.current_step_index)
@@ -268,8 +293,6 @@ impl WorkflowExecutor {
                     .as_ref()
                     .map(|f| f.path().to_string_lossy().to_string());
 
-                let mut debug_cmd = debug_config.claude.clone();
-
                 ctx.variables
                     .insert("test.attempt".to_string(), attempt.to_string());
                 ctx.variables.insert(
@@ -297,7 +320,11 @@ impl WorkflowExecutor {
                 ));
 
                 let debug_result = self
-                    .execute_claude_command(&debug_cmd, env, env_vars.clone())
+                    .execute_claude_command(
+                        &debug_cmd,
+                        env,
+                        with_escalated_model(&env_vars, debug_config.escalate_model.as_deref()),
+                    )
                     .await?;
 
                 if !debug_result.success {
@@ -336,6 +363,17 @@ mod tests {
         assert!(output.contains("stderr content"));
     }
 
+    #[test]
+    fn test_with_escalated_model() {
+        let base = HashMap::from([("A".to_string(), "1".to_string())]);
+        assert_eq!(with_escalated_model(&base, None), base);
+        assert_eq!(with_escalated_model(&base, Some(" ")), base);
+
+        let escalated = with_escalated_model(&base, Some("opus"));
+        assert_eq!(escalated.get(CLAUDE_MODEL_ENV), Some(&"opus".to_string()));
+        assert_eq!(escalated.get("A"), Some(&"1".to_string()));
+    }
+
     #[test]
     fn test_format_inline_output() {
         let output = format_inline_output("stdout", "stderr");
@@ -3,6 +3,8 @@
 //! This module contains execution logic for specialized command types:
 //! - Foreach: Parallel/sequential iteration over collections
 //! - WriteFile: File writing with format support
+//! - Assert: Git and filesystem state assertions
+//! - SummarizeCommits: Summary of the commits made during the workflow
 //!
 //! These commands were extracted from commands.rs to reduce LOC and improve
 //! separation of concerns (spec 174f refactor).
@@ -107,6 +109,80 @@ pub async fn execute_write_file_command(
     })
 }
 
+// ============================================================================
+// Assert Command
+// ============================================================================
+
+/// Execute an assert command
+///
+/// Fails the step with every failed check listed in stderr.
+pub async fn execute_assert_command(
+    config: &crate::cook::workflow::assertions::AssertConfig,
+    working_dir: &Path,
+    git: &dyn crate::abstractions::git::GitOperations,
+) -> Result<StepResult> {
+    use crate::cook::workflow::assertions::{format_failures, run_assertions};
+
+    let failures = run_assertions(config, working_dir, git).await?;
+    if failures.is_empty() {
+        return Ok(StepResult {
+            success: true,
+            exit_code: Some(0),
+            stdout: format!("All {} assertions passed", config.check_count()),
+            stderr: String::new(),
+            json_log_location: None,
+        });
+    }
+
+    Ok(StepResult {
+        success: false,
+        exit_code: Some(1),
+        stdout: String::new(),
+        stderr: format_failures(config, &failures),
+        json_log_location: None,
+    })
+}
+
+// ============================================================================
+// Summarize Commits Command
+// ============================================================================
+
+/// Execute a summarize_commits command
+///
+/// Summarizes the commits made on HEAD since `since`, writes the summary to
+/// the configured artifact and returns it as stdout.
+pub async fn execute_summarize_commits_command(
+    config: &crate::cook::workflow::commit_summary::SummarizeCommitsConfig,
+    since: &str,
+    working_dir: &Path,
+    git: std::sync::Arc<dyn crate::abstractions::git::GitOperations>,
+) -> Result<StepResult> {
+    use crate::cook::workflow::commit_summary::{collect_commits, render, summarize};
+
+    let commits = collect_commits(git, working_dir.to_path_buf(), since).await?;
+    let content = render(&summarize(&commits), config.format);
+
+    let path = working_dir.join(config.output_path());
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    tokio::fs::write(&path, &content).await.map_err(|e| {
+        anyhow!(
+            "Failed to write commit summary to {}: {}",
+            path.display(),
+            e
+        )
+    })?;
+
+    Ok(StepResult {
+        success: true,
+        exit_code: Some(0),
+        stdout: content,
+        stderr: String::new(),
+        json_log_location: None,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -208,4 +284,44 @@ mod tests {
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("Invalid JSON"));
     }
+
+    #[tokio::test]
+    async fn test_execute_summarize_commits_writes_artifact() {
+        use crate::abstractions::git::MockGitOperations;
+        use crate::cook::workflow::commit_summary::SummarizeCommitsConfig;
+
+        let temp_dir = TempDir::new().unwrap();
+        let git = MockGitOperations::new();
+        git.add_success_response(
+            "bbbbbbbbbb|fix: handle tabs|Ada|2026-01-02T00:00:00Z\nsrc/parser.rs\n\n\
+             aaaaaaaaaa|feat(cli): add --repos|Ada|2026-01-01T00:00:00Z\nsrc/cli.rs\n",
+        )
+        .await;
+        git.add_success_response(" 1 file changed, 3 insertions(+)\n")
+            .await;
+        git.add_success_response(" 1 file changed, 5 insertions(+), 1 deletion(-)\n")
+            .await;
+
+        let config = SummarizeCommitsConfig::default();
+        let result = execute_summarize_commits_command(
+            &config,
+            "main",
+            temp_dir.path(),
+            std::sync::Arc::new(git),
+        )
+        .await
+        .unwrap();
+
+        assert!(result.success);
+        assert!(result
+            .stdout
+            .starts_with("## Summary\n\n2 commits, 2 files changed, +8 -1\n"));
+        let features = result.stdout.find("### Features").unwrap();
+        let fixes = result.stdout.find("### Bug Fixes").unwrap();
+        assert!(features < fixes);
+
+        let written =
+            std::fs::read_to_string(temp_dir.path().join(".prodigy/commit-summary.md")).unwrap();
+        assert_eq!(written, result.stdout);
+    }
 }
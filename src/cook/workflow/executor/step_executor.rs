@@ -41,6 +41,7 @@ impl WorkflowExecutor {
             project_dir: Arc::new(std::env::current_dir()?),
             worktree_name: None,
             session_id: Arc::from("resume-session"),
+            package: None,
         };
 
         // Execute the step
@@ -58,6 +59,9 @@ impl WorkflowExecutor {
         let mut workflow_step = WorkflowStep {
             name: Some(step.id.to_string()),
             claude: None,
+            model: None,
+            context: None,
+            validate_output: None,
             shell: None,
             test: None,
             foreach: None,
@@ -102,6 +106,13 @@ impl WorkflowExecutor {
             validation_timeout: None,
             ignore_validation_failure: false,
             when: step.when.as_ref().map(|w| w.to_string()),
+            assert: None,
+            summarize_commits: None,
+            claude_chat: None,
+            script: None,
+            snapshot: false,
+            required: false,
+            commit_policy: None,
         };
 
         // Set command based on step type
@@ -184,6 +195,11 @@ impl WorkflowExecutor {
         // Get step name for logging
         let step_name = self.get_step_display_name(step);
 
+        // Read-only runs must never create commits
+        if self.read_only {
+            super::pure::validate_read_only_step(step, &step_name)?;
+        }
+
         // Log execution context (progress display, tracing)
         self.log_step_execution_context(&step_name, env, ctx);
 
@@ -232,10 +248,17 @@ impl WorkflowExecutor {
             &after_head,
             json_log_location,
         )?;
+        self.check_commit_policy(step, &tracked_commits, &mut result)?;
+        self.enforce_protected_paths(step, &before_head, env, &mut result)
+            .await?;
 
-        // Capture output to variables and files
-        self.capture_step_output(step, &result, ctx).await?;
+        // Write the full output to file, then keep oversized output out of variables
         self.write_output_to_file(step, &result, &actual_env)?;
+        self.limit_step_output(step, &command_type, &mut result, &actual_env, ctx)
+            .await;
+
+        // Capture output to variables
+        self.capture_step_output(step, &result, ctx).await?;
         self.handle_legacy_capture(step, &command_type, &result, ctx);
 
         // Execute validation checks if configured
@@ -249,6 +272,9 @@ impl WorkflowExecutor {
 
         // === PHASE 4: Finalization ===
         // Determine if step failure should fail the workflow
+        if !result.success {
+            self.failed_step_result = Some(result.clone());
+        }
         let result = self.finalize_step_result(step, result)?;
 
         // Update session state with git changes
@@ -271,10 +297,19 @@ impl WorkflowExecutor {
                 Ok(WorkflowStep {
                     name: None,
                     claude: step.claude.clone(),
+                    model: step.model.clone(),
+                    context: step.context.clone(),
+                    validate_output: step.validate_output.clone(),
                     shell: step.shell.clone(),
                     test: step.test.clone(),
                     foreach: step.foreach.clone(),
                     write_file: None,
+                    assert: step.assert.clone(),
+                    summarize_commits: step.summarize_commits.clone(),
+                    claude_chat: step.claude_chat.clone(),
+                    script: step.script.clone(),
+                    snapshot: step.snapshot,
+                    required: step.required,
                     command: None,
                     handler: None,
                     capture: None,
@@ -302,6 +337,7 @@ impl WorkflowExecutor {
                     validation_timeout: None,
                     ignore_validation_failure: false,
                     when: step.when.clone(),
+                    commit_policy: None,
                 })
             }
             WorkflowCommand::Simple(cmd_str) => {
@@ -309,6 +345,9 @@ impl WorkflowExecutor {
                 Ok(WorkflowStep {
                     name: None,
                     claude: None,
+                    model: None,
+                    context: None,
+                    validate_output: None,
                     shell: None,
                     test: None,
                     foreach: None,
@@ -336,6 +375,13 @@ impl WorkflowExecutor {
                     validation_timeout: None,
                     ignore_validation_failure: false,
                     when: None,
+                    assert: None,
+                    summarize_commits: None,
+                    claude_chat: None,
+                    script: None,
+                    snapshot: false,
+                    required: false,
+                    commit_policy: None,
                 })
             }
             WorkflowCommand::Structured(cmd) => {
@@ -343,6 +389,9 @@ impl WorkflowExecutor {
                 Ok(WorkflowStep {
                     name: Some(cmd.name.clone()),
                     claude: None,
+                    model: None,
+                    context: None,
+                    validate_output: None,
                     shell: None,
                     test: None,
                     foreach: None,
@@ -370,6 +419,13 @@ impl WorkflowExecutor {
                     validation_timeout: None,
                     ignore_validation_failure: false,
                     when: None,
+                    assert: None,
+                    summarize_commits: None,
+                    claude_chat: None,
+                    script: None,
+                    snapshot: false,
+                    required: false,
+                    commit_policy: None,
                 })
             }
             WorkflowCommand::SimpleObject(simple) => {
@@ -377,6 +433,9 @@ impl WorkflowExecutor {
                 Ok(WorkflowStep {
                     name: Some(simple.name.clone()),
                     claude: None,
+                    model: None,
+                    context: None,
+                    validate_output: None,
                     shell: None,
                     test: None,
                     foreach: None,
@@ -404,6 +463,13 @@ impl WorkflowExecutor {
                     validation_timeout: None,
                     ignore_validation_failure: false,
                     when: None,
+                    assert: None,
+                    summarize_commits: None,
+                    claude_chat: None,
+                    script: None,
+                    snapshot: false,
+                    required: false,
+                    commit_policy: None,
                 })
             }
         }
@@ -481,6 +547,88 @@ impl WorkflowExecutor {
         Ok(tracked_commits)
     }
 
+    /// Fail the step result when its commits break the step's commit policy
+    fn check_commit_policy(
+        &self,
+        step: &WorkflowStep,
+        tracked_commits: &[TrackedCommit],
+        result: &mut StepResult,
+    ) -> Result<()> {
+        let Some(policy) = &step.commit_policy else {
+            return Ok(());
+        };
+        let replaying = self
+            .replay_session
+            .as_ref()
+            .is_some_and(|session| session.is_replaying());
+        if !result.success || self.dry_run || replaying {
+            return Ok(());
+        }
+
+        let violations = policy.violations(tracked_commits)?;
+        if violations.is_empty() {
+            return Ok(());
+        }
+
+        let step_name = self.get_step_display_name(step);
+        let message = format!(
+            "Step '{}' violated its commit policy:\n  - {}",
+            step_name,
+            violations.join("\n  - ")
+        );
+        self.user_interaction.display_warning(&message);
+        result.success = false;
+        if !result.stderr.is_empty() && !result.stderr.ends_with('\n') {
+            result.stderr.push('\n');
+        }
+        result.stderr.push_str(&message);
+        if result.exit_code == Some(0) {
+            result.exit_code = Some(1);
+        }
+        Ok(())
+    }
+
+    /// Revert or fail on changes the step made to protected paths
+    async fn enforce_protected_paths(
+        &self,
+        step: &WorkflowStep,
+        before_head: &str,
+        env: &ExecutionEnvironment,
+        result: &mut StepResult,
+    ) -> Result<()> {
+        let Some(protected) = self.protected_paths.as_deref() else {
+            return Ok(());
+        };
+        let replaying = self
+            .replay_session
+            .as_ref()
+            .is_some_and(|session| session.is_replaying());
+        if self.dry_run || replaying {
+            return Ok(());
+        }
+
+        let Some(violation) =
+            crate::cook::protected_paths::enforce(protected, &env.working_dir, before_head).await?
+        else {
+            return Ok(());
+        };
+
+        let message = violation.describe(&self.get_step_display_name(step));
+        self.user_interaction.display_warning(&message);
+        if violation.reverted || !result.success {
+            return Ok(());
+        }
+        result.success = false;
+        if !result.stderr.is_empty() && !result.stderr.ends_with('\n') {
+            result.stderr.push('\n');
+        }
+        result.stderr.push_str(&message);
+        if result.exit_code == Some(0) {
+            result.exit_code = Some(1);
+        }
+        Ok(())
+    }
+
     /// Track git changes and update session state
     async fn track_and_update_session(&mut self, ctx: &WorkflowContext) -> Result<()> {
         // Track git changes - complete step
@@ -498,6 +646,7 @@ impl WorkflowExecutor {
 
                     // Count actual files changed
                     let count = changes.files_changed().len();
+                    self.last_step_changes = Some(changes);
                     if count > 0 {
                         count
                     } else {
@@ -777,8 +926,11 @@ impl WorkflowExecutor {
                     &retry_config,
                     retry_ctx.attempt - 1,
                 );
-                let jittered_delay =
-                    super::failure_handler::apply_jitter(delay, retry_config.jitter_factor);
+                let jittered_delay = super::failure_handler::apply_jitter(
+                    delay,
+                    retry_config.jitter_factor,
+                    self.seed,
+                );
 
                 let retry_msg = super::failure_handler::format_retry_message(
                     step_name,
@@ -63,6 +63,10 @@ impl CaptureOutput {
                     CommandType::Test(_) => "test.output".to_string(),
                     CommandType::Foreach(_) => "foreach.output".to_string(),
                     CommandType::WriteFile(_) => "write_file.output".to_string(),
+                    CommandType::Assert(_) => "assert.output".to_string(),
+                    CommandType::SummarizeCommits(_) => "summarize_commits.output".to_string(),
+                    CommandType::ClaudeChat(_) => "claude_chat.output".to_string(),
+                    CommandType::Script(_) => "script.output".to_string(),
                 })
             }
             CaptureOutput::Variable(name) => Some(name.clone()),
@@ -112,6 +116,13 @@ pub enum CommandType {
     Foreach(crate::config::command::ForeachConfig),
     /// Write file command with formatting and validation
     WriteFile(crate::config::command::WriteFileConfig),
+    Assert(crate::cook::workflow::assertions::AssertConfig),
+    /// Summary of the commits made during the workflow
+    SummarizeCommits(crate::cook::workflow::commit_summary::SummarizeCommitsConfig),
+    /// Messages sent to Claude within one conversation
+    ClaudeChat(crate::cook::workflow::claude_chat::ClaudeChatConfig),
+    /// Inline script run with the selected interpreter
+    Script(crate::cook::workflow::script::ScriptConfig),
     /// Legacy name-based approach (deprecated)
     Legacy(String),
     /// Modular command handler with dynamic attributes
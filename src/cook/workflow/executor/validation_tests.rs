@@ -652,6 +652,8 @@ fn test_determine_step_name_with_explicit_name() {
     let step = WorkflowStep {
         name: Some("my-custom-step".to_string()),
         claude: Some("/prodigy-lint".to_string()),
+        context: None,
+        validate_output: None,
         shell: Some("cargo test".to_string()),
         ..Default::default()
     };
@@ -664,6 +666,8 @@ fn test_determine_step_name_with_claude_no_name() {
     let step = WorkflowStep {
         name: None,
         claude: Some("/prodigy-code-review".to_string()),
+        context: None,
+        validate_output: None,
         shell: None,
         ..Default::default()
     };
@@ -676,6 +680,8 @@ fn test_determine_step_name_with_shell_no_name() {
     let step = WorkflowStep {
         name: None,
         claude: None,
+        context: None,
+        validate_output: None,
         shell: Some("cargo build --release".to_string()),
         ..Default::default()
     };
@@ -688,6 +694,8 @@ fn test_determine_step_name_with_neither_fallback() {
     let step = WorkflowStep {
         name: None,
         claude: None,
+        context: None,
+        validate_output: None,
         shell: None,
         ..Default::default()
     };
@@ -701,6 +709,8 @@ fn test_determine_step_name_empty_name_uses_fallback() {
     let step = WorkflowStep {
         name: None,
         claude: Some("/command".to_string()),
+        context: None,
+        validate_output: None,
         ..Default::default()
     };
 
@@ -25,7 +25,10 @@ pub use pure::{
 pub use commands::{execute_claude_command, execute_shell_command, format_command_description};
 
 // Re-export specialized command functions
-pub use specialized_commands::{execute_foreach_command, execute_write_file_command};
+pub use specialized_commands::{
+    execute_assert_command, execute_foreach_command, execute_summarize_commits_command,
+    execute_write_file_command,
+};
 
 // Re-export commonly used items from failure_handler module
 pub use failure_handler::{
@@ -39,5 +42,6 @@ pub use failure_handler::{
 pub use orchestration::{
     build_checkpoint_step, build_session_step_result, calculate_progress_percentage,
     create_normalized_workflow, create_workflow_hash, format_iteration_progress,
-    format_skip_step, format_step_progress, format_workflow_start, should_continue_iteration,
+    format_skip_step, format_step_completed, format_step_progress, format_workflow_start,
+    should_continue_iteration,
 };
@@ -576,6 +576,8 @@ mod tests {
 
         let step = WorkflowStep {
             claude: Some("/prodigy-code-review".to_string()),
+            context: None,
+            validate_output: None,
             commit_required: true,
             ..Default::default()
         };
@@ -628,6 +630,9 @@ mod tests {
             parallel: crate::config::command::ParallelConfig::Count(2),
             do_block: vec![Box::new(crate::config::command::WorkflowStepCommand {
                 claude: None,
+                model: None,
+                context: None,
+                validate_output: None,
                 shell: Some("echo Processing item".to_string()),
                 analyze: None,
                 test: None,
@@ -646,6 +651,15 @@ mod tests {
                 capture_format: None,
                 capture_streams: None,
                 output_file: None,
+                assert: None,
+                summarize_commits: None,
+                claude_chat: None,
+                script: None,
+                snapshot: false,
+                required: false,
+                commit_policy: None,
+                cwd: None,
+                env: HashMap::new(),
             })],
             continue_on_error: false,
             max_items: None,
@@ -680,6 +694,8 @@ mod tests {
 
         let step = WorkflowStep {
             claude: Some("/prodigy-code-review".to_string()),
+            context: None,
+            validate_output: None,
             shell: Some("cargo test".to_string()),
             ..Default::default()
         };
@@ -714,6 +730,8 @@ mod tests {
 
         let step = WorkflowStep {
             claude: Some("/prodigy-code-review --strict".to_string()),
+            context: None,
+            validate_output: None,
             commit_required: true,
             ..Default::default()
         };
@@ -772,6 +790,8 @@ mod tests {
 
         let step = WorkflowStep {
             claude: Some("/prodigy-code-review".to_string()),
+            context: None,
+            validate_output: None,
             shell: Some("cargo test".to_string()),
             ..Default::default()
         };
@@ -864,6 +884,8 @@ mod tests {
 
         let step = WorkflowStep {
             claude: Some("/prodigy-implement-spec".to_string()),
+            context: None,
+            validate_output: None,
             commit_required: true,
             ..Default::default()
         };
@@ -886,6 +908,7 @@ mod tests {
             project_dir: Arc::new(working_dir.to_path_buf()),
             worktree_name: None,
             session_id: Arc::from("test"),
+            package: None,
         };
 
         let mut env_vars = HashMap::new();
@@ -916,6 +939,129 @@ mod tests {
         assert!(calls[0].2.contains_key("PRODIGY_AUTOMATION"));
     }
 
+    #[tokio::test]
+    async fn test_execute_claude_command_passes_workflow_permissions() {
+        use crate::cook::execution::claude_permissions::{
+            ClaudePermissions, PermissionProfile, PERMISSIONS_ENV,
+        };
+
+        let (executor, claude_mock, _, _, _) = create_test_executor_with_git_mock().await;
+        let permissions = ClaudePermissions::profile(PermissionProfile::NoShell);
+        let executor = executor.with_claude_permissions(Some(permissions.clone()));
+        let temp_dir = TempDir::new().unwrap();
+        let env = ExecutionEnvironment {
+            working_dir: Arc::new(temp_dir.path().to_path_buf()),
+            project_dir: Arc::new(temp_dir.path().to_path_buf()),
+            worktree_name: None,
+            session_id: Arc::from("test"),
+            package: None,
+        };
+        claude_mock.add_response(ExecutionResult {
+            success: true,
+            exit_code: Some(0),
+            stdout: String::new(),
+            stderr: String::new(),
+            metadata: HashMap::new(),
+        });
+
+        executor
+            .execute_claude_command("/prodigy-code-review", &env, HashMap::new())
+            .await
+            .unwrap();
+
+        let calls = claude_mock.get_calls();
+        assert_eq!(
+            calls[0].2.get(PERMISSIONS_ENV),
+            Some(&permissions.to_env_value())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_execute_commands_replay_fixtures() {
+        use crate::cook::execution::{CommandFixture, FixtureKind, ReplaySession};
+
+        let (executor, claude_mock, _, _, _) = create_test_executor_with_git_mock().await;
+        let session = ReplaySession::from_fixtures(
+            "fixtures.json",
+            vec![
+                CommandFixture {
+                    kind: FixtureKind::Claude,
+                    command: "/prodigy-code-review".to_string(),
+                    success: true,
+                    exit_code: Some(0),
+                    stdout: "recorded review".to_string(),
+                    stderr: String::new(),
+                },
+                CommandFixture {
+                    kind: FixtureKind::Shell,
+                    command: "exit 1".to_string(),
+                    success: true,
+                    exit_code: Some(0),
+                    stdout: "recorded shell".to_string(),
+                    stderr: String::new(),
+                },
+            ],
+        );
+        let executor = executor.with_replay_session(Some(Arc::new(session)));
+
+        let temp_dir = TempDir::new().unwrap();
+        let env = ExecutionEnvironment {
+            working_dir: Arc::new(temp_dir.path().to_path_buf()),
+            project_dir: Arc::new(temp_dir.path().to_path_buf()),
+            worktree_name: None,
+            session_id: Arc::from("test"),
+            package: None,
+        };
+
+        let claude = executor
+            .execute_claude_command("/prodigy-code-review", &env, HashMap::new())
+            .await
+            .unwrap();
+        assert_eq!(claude.stdout, "recorded review");
+        assert!(claude_mock.get_calls().is_empty());
+
+        // The recorded output is used instead of actually running the command
+        let shell = executor
+            .execute_shell_command("exit 1", &env, HashMap::new(), None)
+            .await
+            .unwrap();
+        assert!(shell.success);
+        assert_eq!(shell.stdout, "recorded shell");
+
+        // Unrecorded commands fail instead of executing
+        assert!(executor
+            .execute_shell_command("echo 'new'", &env, HashMap::new(), None)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_execute_shell_command_records_fixture() {
+        use crate::cook::execution::{ReplayMode, ReplaySession};
+
+        let (executor, _, _, _, _) = create_test_executor_with_git_mock().await;
+        let temp_dir = TempDir::new().unwrap();
+        let fixture_path = temp_dir.path().join("fixtures.json");
+        let executor =
+            executor.with_replay_session(Some(Arc::new(ReplaySession::record(&fixture_path))));
+
+        let env = ExecutionEnvironment {
+            working_dir: Arc::new(temp_dir.path().to_path_buf()),
+            project_dir: Arc::new(temp_dir.path().to_path_buf()),
+            worktree_name: None,
+            session_id: Arc::from("test"),
+            package: None,
+        };
+        executor
+            .execute_shell_command("echo 'recorded'", &env, HashMap::new(), None)
+            .await
+            .unwrap();
+
+        let replay = ReplaySession::replay(&fixture_path).unwrap();
+        assert_eq!(replay.mode(), ReplayMode::Replay);
+        assert_eq!(replay.remaining(), 1);
+    }
+
     #[tokio::test]
     async fn test_execute_shell_command_success() {
         let (executor, _, _, _, _) = create_test_executor_with_git_mock().await;
@@ -926,6 +1072,7 @@ mod tests {
             project_dir: Arc::new(temp_dir.path().to_path_buf()),
             worktree_name: None,
             session_id: Arc::from("test"),
+            package: None,
         };
 
         let env_vars = HashMap::new();
@@ -961,13 +1108,15 @@ mod tests {
             ..Default::default()
         });
 
+        let recorder = Arc::new(crate::cook::execution::junit::JunitRecorder::new());
         let mut executor = WorkflowExecutor::with_test_config_and_git(
             claude_executor.clone() as Arc<dyn ClaudeExecutor>,
             session_manager.clone() as Arc<dyn SessionManager>,
             user_interaction.clone() as Arc<dyn UserInteraction>,
             test_config,
             git_operations.clone(),
-        );
+        )
+        .with_junit(Some(recorder.clone()));
 
         let temp_dir = TempDir::new().unwrap();
         let env = ExecutionEnvironment {
@@ -975,6 +1124,7 @@ mod tests {
             project_dir: Arc::new(temp_dir.path().to_path_buf()),
             worktree_name: None,
             session_id: Arc::from("test"),
+            package: None,
         };
 
         // Set up workflow
@@ -983,6 +1133,8 @@ mod tests {
             mode: WorkflowMode::Sequential,
             steps: vec![WorkflowStep {
                 claude: Some("/prodigy-code-review".to_string()),
+                context: None,
+                validate_output: None,
                 ..Default::default()
             }],
             setup_phase: None,
@@ -992,7 +1144,9 @@ mod tests {
             iterate: false,
             environment: None,
             retry_defaults: None,
+            always_steps: Vec::new(),
             // collect_metrics removed - MMM focuses on orchestration
+            strict_variables: false,
         };
 
         // Execute workflow
@@ -1013,6 +1167,84 @@ mod tests {
         assert!(messages
             .iter()
             .any(|(t, m)| t == "info" && m.contains("Test Workflow")));
+
+        // The step is recorded for the run's JUnit report
+        let cases = recorder.cases();
+        assert_eq!(cases.len(), 1);
+        assert_eq!(cases[0].suite, "Test Workflow");
+    }
+
+    #[tokio::test]
+    async fn test_failed_step_is_recorded_only_for_its_own_run() {
+        use crate::cook::workflow::failure_report::FailureRecorder;
+
+        // Runs one single-step job with its own failure recorder; the step
+        // fails when it must commit but its command makes no changes
+        async fn run_job(
+            command: &str,
+            commit_required: bool,
+            failures: Arc<FailureRecorder>,
+        ) -> Result<()> {
+            let git_operations = Arc::new(MockGitOperations::new());
+            for _ in 0..20 {
+                git_operations.add_success_response("abc123def456").await;
+            }
+            let test_config = Arc::new(TestConfiguration {
+                test_mode: true,
+                no_changes_commands: vec!["prodigy-fail".to_string()],
+                ..Default::default()
+            });
+            let mut executor = WorkflowExecutor::with_test_config_and_git(
+                Arc::new(MockClaudeExecutor::new()) as Arc<dyn ClaudeExecutor>,
+                Arc::new(MockSessionManager::new()) as Arc<dyn SessionManager>,
+                Arc::new(MockUserInteraction::new()) as Arc<dyn UserInteraction>,
+                test_config,
+                git_operations,
+            )
+            .with_failure_recorder(Some(failures));
+            let temp_dir = TempDir::new().unwrap();
+            let env = ExecutionEnvironment {
+                working_dir: Arc::new(temp_dir.path().to_path_buf()),
+                project_dir: Arc::new(temp_dir.path().to_path_buf()),
+                worktree_name: None,
+                session_id: Arc::from("test"),
+                package: None,
+            };
+            let workflow = ExtendedWorkflowConfig {
+                name: "Job".to_string(),
+                mode: WorkflowMode::Sequential,
+                steps: vec![WorkflowStep {
+                    claude: Some(command.to_string()),
+                    commit_required,
+                    ..Default::default()
+                }],
+                setup_phase: None,
+                map_phase: None,
+                reduce_phase: None,
+                max_iterations: 1,
+                iterate: false,
+                environment: None,
+                retry_defaults: None,
+                always_steps: Vec::new(),
+                strict_variables: false,
+            };
+            executor.execute(&workflow, &env).await
+        }
+
+        let first = Arc::new(FailureRecorder::new());
+        assert!(run_job("/prodigy-fail", true, first.clone()).await.is_err());
+        let second = Arc::new(FailureRecorder::new());
+        assert!(run_job("/prodigy-code-review", false, second.clone())
+            .await
+            .is_ok());
+
+        assert!(second.take().step.is_none());
+        let failed = first
+            .take()
+            .step
+            .expect("first job records its failed step");
+        assert_eq!(failed.step_index, 0);
+        assert!(failed.step.contains("/prodigy-fail"));
     }
 
     #[tokio::test]
@@ -1025,6 +1257,7 @@ mod tests {
             project_dir: Arc::new(temp_dir.path().to_path_buf()),
             worktree_name: None,
             session_id: Arc::from("test"),
+            package: None,
         };
 
         let mut context = WorkflowContext::default();
@@ -1045,6 +1278,102 @@ mod tests {
         assert!(context.captured_outputs["CAPTURED_OUTPUT"].contains("captured output"));
     }
 
+    #[tokio::test]
+    async fn test_execute_script_step_interpolates_code() {
+        use crate::cook::workflow::script::{ScriptConfig, ScriptLang};
+
+        let (mut executor, _, _, _, _) = create_test_executor_with_git_mock().await;
+
+        let temp_dir = TempDir::new().unwrap();
+        let env = ExecutionEnvironment {
+            working_dir: Arc::new(temp_dir.path().to_path_buf()),
+            project_dir: Arc::new(temp_dir.path().to_path_buf()),
+            worktree_name: None,
+            session_id: Arc::from("test"),
+            package: None,
+        };
+
+        let mut context = WorkflowContext::default();
+        context
+            .variables
+            .insert("name".to_string(), "world".to_string());
+        let step = WorkflowStep {
+            script: Some(ScriptConfig {
+                lang: ScriptLang::Bash,
+                code: "greeting=hello\necho \"$greeting ${name}\"\n".to_string(),
+            }),
+            ..Default::default()
+        };
+
+        let result = executor
+            .execute_step(&step, &env, &mut context)
+            .await
+            .unwrap();
+
+        assert!(result.success);
+        assert_eq!(result.stdout.trim(), "hello world");
+    }
+
+    #[tokio::test]
+    async fn test_execute_claude_chat_step_continues_conversation() {
+        use crate::cook::execution::claude_sessions::RESUME_SESSION_ENV;
+        use crate::cook::workflow::claude_chat::ClaudeChatConfig;
+
+        let (mut executor, claude_mock, _, _, _) = create_test_executor_with_git_mock().await;
+
+        let temp_dir = TempDir::new().unwrap();
+        let env = ExecutionEnvironment {
+            working_dir: Arc::new(temp_dir.path().to_path_buf()),
+            project_dir: Arc::new(temp_dir.path().to_path_buf()),
+            worktree_name: None,
+            session_id: Arc::from("test"),
+            package: None,
+        };
+
+        // Responses are served last-in first-out
+        for (session, reply) in [("s-2", "Fixed it"), ("s-1", "Found a bug")] {
+            claude_mock.add_response(ExecutionResult {
+                success: true,
+                exit_code: Some(0),
+                stdout: format!(
+                    r#"{{"type":"result","result":"{}","session_id":"{}"}}"#,
+                    reply, session
+                ),
+                stderr: String::new(),
+                metadata: HashMap::new(),
+            });
+        }
+
+        let mut context = WorkflowContext::default();
+        let step = WorkflowStep {
+            claude_chat: Some(ClaudeChatConfig {
+                messages: vec![
+                    "/prodigy-review".to_string(),
+                    "Fix this: ${review.1}".to_string(),
+                ],
+                variable: Some("review".to_string()),
+            }),
+            ..Default::default()
+        };
+
+        let result = executor
+            .execute_step(&step, &env, &mut context)
+            .await
+            .unwrap();
+
+        assert!(result.success);
+        assert_eq!(result.stdout, "Fixed it");
+        assert_eq!(context.variables["review.1"], "Found a bug");
+        assert_eq!(context.variables["review"], "Fixed it");
+        assert_eq!(context.variables["review.session_id"], "s-2");
+
+        let calls = claude_mock.get_calls();
+        assert_eq!(calls.len(), 2);
+        assert!(!calls[0].2.contains_key(RESUME_SESSION_ENV));
+        assert_eq!(calls[1].0, "Fix this: Found a bug");
+        assert_eq!(calls[1].2[RESUME_SESSION_ENV], "s-1");
+    }
+
     #[tokio::test]
     async fn test_execute_step_with_env_interpolation() {
         let (mut executor, _, _, _, _) = create_test_executor_with_git_mock().await;
@@ -1055,6 +1384,7 @@ mod tests {
             project_dir: Arc::new(temp_dir.path().to_path_buf()),
             worktree_name: None,
             session_id: Arc::from("test"),
+            package: None,
         };
 
         let mut context = WorkflowContext::default();
@@ -1099,6 +1429,7 @@ mod tests {
             project_dir: Arc::new(temp_dir.path().to_path_buf()),
             session_id: Arc::from("test-session"),
             worktree_name: None,
+            package: None,
         };
 
         let mut context = WorkflowContext::default();
@@ -1114,6 +1445,7 @@ mod tests {
                     max_attempts: 2,
                     fail_workflow: false,
                     commit_required: true,
+                    escalate_model: None,
                 }),
             }),
             ..Default::default()
@@ -1139,6 +1471,173 @@ mod tests {
         assert!(calls[0].0.contains("/prodigy-debug-test-failure"));
     }
 
+    #[tokio::test]
+    async fn test_claude_step_escalates_model_on_failure() {
+        let (mut executor, claude_mock, _, _, _) = create_test_executor_with_git_mock().await;
+
+        // Responses are popped from the end: the first run fails, the retry succeeds
+        claude_mock.add_response(ExecutionResult {
+            stdout: "Implemented".to_string(),
+            stderr: String::new(),
+            exit_code: Some(0),
+            success: true,
+            metadata: HashMap::new(),
+        });
+        claude_mock.add_response(ExecutionResult {
+            stdout: String::new(),
+            stderr: "model gave up".to_string(),
+            exit_code: Some(1),
+            success: false,
+            metadata: HashMap::new(),
+        });
+
+        let temp_dir = TempDir::new().unwrap();
+        let env = ExecutionEnvironment {
+            working_dir: Arc::new(temp_dir.path().to_path_buf()),
+            project_dir: Arc::new(temp_dir.path().to_path_buf()),
+            session_id: Arc::from("test-session"),
+            worktree_name: None,
+            package: None,
+        };
+        let mut context = WorkflowContext::default();
+
+        let step = WorkflowStep {
+            claude: Some("/implement".to_string()),
+            model: Some("sonnet".to_string()),
+            context: None,
+            validate_output: None,
+            on_failure: Some(OnFailureConfig::Advanced {
+                shell: None,
+                claude: None,
+                fail_workflow: true,
+                retry_original: false,
+                max_retries: 0,
+                escalate_model: Some("opus".to_string()),
+            }),
+            ..Default::default()
+        };
+
+        let result = executor
+            .execute_step(&step, &env, &mut context)
+            .await
+            .unwrap();
+        assert!(result.success);
+
+        let calls = claude_mock.get_calls();
+        assert_eq!(calls.len(), 2);
+        assert_eq!(
+            calls[0].2.get(crate::cook::execution::CLAUDE_MODEL_ENV),
+            Some(&"sonnet".to_string())
+        );
+        assert_eq!(
+            calls[1].2.get(crate::cook::execution::CLAUDE_MODEL_ENV),
+            Some(&"opus".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_claude_step_injects_context_pack() {
+        let (mut executor, claude_mock, _, _, _) = create_test_executor_with_git_mock().await;
+        claude_mock.add_response(ExecutionResult {
+            stdout: "Done".to_string(),
+            stderr: String::new(),
+            exit_code: Some(0),
+            success: true,
+            metadata: HashMap::new(),
+        });
+
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("NOTES.md"), "remember the invariant\n").unwrap();
+        let env = ExecutionEnvironment {
+            working_dir: Arc::new(temp_dir.path().to_path_buf()),
+            project_dir: Arc::new(temp_dir.path().to_path_buf()),
+            session_id: Arc::from("test-session"),
+            worktree_name: None,
+            package: None,
+        };
+        let mut context = WorkflowContext::default();
+        context
+            .variables
+            .insert("doc".to_string(), "NOTES".to_string());
+
+        let step = WorkflowStep {
+            claude: Some("/implement".to_string()),
+            context: Some(
+                crate::cook::workflow::context_pack::ContextPackConfig::Files(vec![
+                    "${doc}.md".to_string()
+                ]),
+            ),
+            ..Default::default()
+        };
+
+        let result = executor
+            .execute_step(&step, &env, &mut context)
+            .await
+            .unwrap();
+        assert!(result.success);
+
+        let calls = claude_mock.get_calls();
+        assert_eq!(calls.len(), 1);
+        assert!(calls[0].0.starts_with("/implement\n\n<context>"));
+        assert!(calls[0]
+            .0
+            .contains("<file path=\"NOTES.md\">\nremember the invariant"));
+    }
+
+    #[tokio::test]
+    async fn test_claude_step_reprompts_until_output_validates() {
+        let (mut executor, claude_mock, _, _, _) = create_test_executor_with_git_mock().await;
+        for stdout in ["STATUS: done", "still thinking"] {
+            claude_mock.add_response(ExecutionResult {
+                stdout: stdout.to_string(),
+                stderr: String::new(),
+                exit_code: Some(0),
+                success: true,
+                metadata: HashMap::new(),
+            });
+        }
+
+        let temp_dir = TempDir::new().unwrap();
+        let env = ExecutionEnvironment {
+            working_dir: Arc::new(temp_dir.path().to_path_buf()),
+            project_dir: Arc::new(temp_dir.path().to_path_buf()),
+            session_id: Arc::from("test-session"),
+            worktree_name: None,
+            package: None,
+        };
+        let mut context = WorkflowContext::default();
+
+        let step = WorkflowStep {
+            claude: Some("/plan".to_string()),
+            validate_output: Some(
+                crate::cook::workflow::output_validation::OutputValidationConfig {
+                    regex: Some("^STATUS: done".to_string()),
+                    json_schema: None,
+                    shell: None,
+                    max_attempts: 2,
+                },
+            ),
+            ..Default::default()
+        };
+
+        let result = executor
+            .execute_step(&step, &env, &mut context)
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert_eq!(result.stdout, "STATUS: done");
+
+        let calls = claude_mock.get_calls();
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0].0, "/plan");
+        assert!(calls[1]
+            .0
+            .starts_with("/plan\n\nYour previous response did not pass validation:"));
+        assert!(calls[1]
+            .0
+            .contains("does not match the pattern `^STATUS: done`"));
+    }
+
     #[tokio::test]
     async fn test_shell_command_with_on_failure_fail_workflow() {
         let (mut executor, claude_mock, _, _, _) = create_test_executor_with_git_mock().await;
@@ -1158,6 +1657,7 @@ mod tests {
             project_dir: Arc::new(temp_dir.path().to_path_buf()),
             session_id: Arc::from("test-session"),
             worktree_name: None,
+            package: None,
         };
 
         let mut context = WorkflowContext::default();
@@ -1171,6 +1671,7 @@ mod tests {
                     max_attempts: 1,
                     fail_workflow: true,
                     commit_required: true,
+                    escalate_model: None,
                 }),
             }),
             ..Default::default()
@@ -1201,6 +1702,7 @@ mod tests {
             project_dir: Arc::new(temp_dir.path().to_path_buf()),
             session_id: Arc::from("test-session"),
             worktree_name: None,
+            package: None,
         };
 
         let mut context = WorkflowContext::default();
@@ -1236,6 +1738,7 @@ mod tests {
             project_dir: Arc::new(temp_dir.path().to_path_buf()),
             session_id: Arc::from("test-session"),
             worktree_name: None,
+            package: None,
         };
 
         let mut context = WorkflowContext::default();
@@ -1314,6 +1817,7 @@ mod tests {
             project_dir: Arc::new(temp_dir.path().to_path_buf()),
             worktree_name: None,
             session_id: Arc::from("test"),
+            package: None,
         };
 
         // Create a workflow with conditional steps
@@ -1342,6 +1846,8 @@ mod tests {
             iterate: false,
             retry_defaults: None,
             environment: None,
+            always_steps: Vec::new(),
+            strict_variables: false,
         };
 
         // Set test mode to avoid actual command execution
@@ -1359,6 +1865,256 @@ mod tests {
         std::env::remove_var("PRODIGY_TEST_MODE");
     }
 
+    #[tokio::test]
+    async fn test_always_steps_run_after_failure() {
+        let (mut executor, _, _, _, git_mock) = create_test_executor_with_git_mock().await;
+        for _ in 0..10 {
+            git_mock.add_success_response("abc123def456").await;
+        }
+
+        let temp_dir = TempDir::new().unwrap();
+        let env = ExecutionEnvironment {
+            working_dir: Arc::new(temp_dir.path().to_path_buf()),
+            project_dir: Arc::new(temp_dir.path().to_path_buf()),
+            worktree_name: None,
+            session_id: Arc::from("test"),
+            package: None,
+        };
+
+        let workflow = ExtendedWorkflowConfig {
+            name: "Cleanup Workflow".to_string(),
+            mode: WorkflowMode::Sequential,
+            steps: vec![WorkflowStep {
+                shell: Some("exit 3".to_string()),
+                ..Default::default()
+            }],
+            setup_phase: None,
+            map_phase: None,
+            reduce_phase: None,
+            max_iterations: 1,
+            iterate: false,
+            retry_defaults: None,
+            environment: None,
+            always_steps: vec![
+                WorkflowStep {
+                    shell: Some("exit 1".to_string()),
+                    ..Default::default()
+                },
+                WorkflowStep {
+                    shell: Some("echo ${workflow.status} > status.txt".to_string()),
+                    ..Default::default()
+                },
+            ],
+            strict_variables: false,
+        };
+
+        let result = executor.execute(&workflow, &env).await;
+
+        // The workflow's own failure is reported, not the cleanup failure
+        let err = result.unwrap_err().to_string();
+        assert!(!err.contains("Cleanup step failed"), "{}", err);
+        let status = std::fs::read_to_string(temp_dir.path().join("status.txt")).unwrap();
+        assert_eq!(status.trim(), "failure");
+    }
+
+    fn chain_step(shell: &str, on_failure: &str) -> WorkflowStep {
+        WorkflowStep {
+            shell: Some(shell.to_string()),
+            on_failure: Some(serde_yaml::from_str(on_failure).unwrap()),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failure_chain_tries_handlers_in_order() {
+        let (mut executor, _, _, _, _) = create_test_executor_with_git_mock().await;
+        let temp_dir = TempDir::new().unwrap();
+        let env = ExecutionEnvironment {
+            working_dir: Arc::new(temp_dir.path().to_path_buf()),
+            project_dir: Arc::new(temp_dir.path().to_path_buf()),
+            session_id: Arc::from("test-session"),
+            worktree_name: None,
+            package: None,
+        };
+        let mut context = WorkflowContext::default();
+
+        let step = chain_step(
+            "test -f fixed",
+            r#"
+handlers:
+  - shell: "exit 2"
+  - shell: "touch fixed"
+  - shell: "touch unused"
+"#,
+        );
+
+        let result = executor
+            .execute_step(&step, &env, &mut context)
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert!(!temp_dir.path().join("unused").exists());
+    }
+
+    #[tokio::test]
+    async fn test_failure_chain_resumes_at_checkpointed_handler() {
+        use crate::cook::workflow::checkpoint::FailureChainState;
+
+        let (executor, _, _, _, _) = create_test_executor_with_git_mock().await;
+        let mut executor = executor.with_failure_chains(vec![FailureChainState {
+            step: "shell: test -f fixed".to_string(),
+            handler_index: 1,
+            attempts: 1,
+            outcome: None,
+        }]);
+        let temp_dir = TempDir::new().unwrap();
+        let env = ExecutionEnvironment {
+            working_dir: Arc::new(temp_dir.path().to_path_buf()),
+            project_dir: Arc::new(temp_dir.path().to_path_buf()),
+            session_id: Arc::from("test-session"),
+            worktree_name: None,
+            package: None,
+        };
+        let mut context = WorkflowContext::default();
+
+        let step = chain_step(
+            "test -f fixed",
+            "{handlers: [{shell: 'touch first'}, {shell: 'touch fixed'}]}",
+        );
+        let result = executor.execute_step(&step, &env, &mut context).await;
+        assert!(result.unwrap().success);
+        assert!(!temp_dir.path().join("first").exists());
+    }
+
+    #[tokio::test]
+    async fn test_failure_chain_outcomes() {
+        let (mut executor, _, _, _, _) = create_test_executor_with_git_mock().await;
+        let temp_dir = TempDir::new().unwrap();
+        let env = ExecutionEnvironment {
+            working_dir: Arc::new(temp_dir.path().to_path_buf()),
+            project_dir: Arc::new(temp_dir.path().to_path_buf()),
+            session_id: Arc::from("test-session"),
+            worktree_name: None,
+            package: None,
+        };
+        let mut context = WorkflowContext::default();
+
+        // `continue` contains the failure
+        let step = chain_step(
+            "exit 1",
+            "{handlers: [{shell: 'exit 1'}], outcome: continue}",
+        );
+        let result = executor.execute_step(&step, &env, &mut context).await;
+        assert!(result.unwrap().success);
+
+        // The attempt budget stops the chain before the second handler
+        let step = chain_step(
+            "exit 1",
+            "{handlers: [{shell: 'true'}, {shell: 'touch second'}], max_total_attempts: 2}",
+        );
+        let result = executor.execute_step(&step, &env, &mut context).await;
+        assert!(result.is_err());
+        assert!(!temp_dir.path().join("second").exists());
+    }
+
+    #[tokio::test]
+    async fn test_failure_chain_fail_workflow_escapes_enclosing_chain() {
+        use crate::cook::workflow::executor::failure_chain::FailureChainAbort;
+
+        let (mut executor, _, _, _, git_mock) = create_test_executor_with_git_mock().await;
+        for _ in 0..20 {
+            git_mock.add_success_response("abc123def456").await;
+        }
+        let temp_dir = TempDir::new().unwrap();
+        let env = ExecutionEnvironment {
+            working_dir: Arc::new(temp_dir.path().to_path_buf()),
+            project_dir: Arc::new(temp_dir.path().to_path_buf()),
+            session_id: Arc::from("test-session"),
+            worktree_name: None,
+            package: None,
+        };
+        let mut context = WorkflowContext::default();
+
+        // A `fail_step` handler chain lets the outer chain move on...
+        let step = chain_step(
+            "test -f recovered",
+            r#"
+handlers:
+  - shell: "exit 1"
+    on_failure:
+      handlers: [{shell: "exit 1"}]
+      outcome: fail_step
+  - shell: "touch recovered"
+"#,
+        );
+        let result = executor.execute_step(&step, &env, &mut context).await;
+        assert!(result.unwrap().success);
+
+        // ...while `fail_workflow` stops it
+        let step = chain_step(
+            "exit 1",
+            r#"
+handlers:
+  - shell: "exit 1"
+    on_failure:
+      handlers: [{shell: "exit 1"}]
+      outcome: fail_workflow
+  - shell: "touch should-not-run"
+outcome: continue
+"#,
+        );
+        let err = executor
+            .execute_step(&step, &env, &mut context)
+            .await
+            .unwrap_err();
+        assert!(err.is::<FailureChainAbort>(), "{}", err);
+        assert!(!temp_dir.path().join("should-not-run").exists());
+    }
+
+    #[tokio::test]
+    async fn test_oversized_output_is_truncated_and_saved() {
+        let (executor, _, _, _, git_mock) = create_test_executor_with_git_mock().await;
+        for _ in 0..5 {
+            git_mock.add_success_response("abc123def456").await;
+        }
+        let temp_dir = TempDir::new().unwrap();
+        let artifact_dir = TempDir::new().unwrap();
+        let mut executor = executor.with_output_settings(crate::config::OutputSettings {
+            max_capture_bytes: Some(1024),
+            truncation: crate::config::TruncationStrategy::Tail,
+            artifact_dir: Some(artifact_dir.path().to_path_buf()),
+        });
+        let env = ExecutionEnvironment {
+            working_dir: Arc::new(temp_dir.path().to_path_buf()),
+            project_dir: Arc::new(temp_dir.path().to_path_buf()),
+            session_id: Arc::from("test-session"),
+            worktree_name: None,
+            package: None,
+        };
+        let mut context = WorkflowContext::default();
+
+        let step = WorkflowStep {
+            shell: Some("seq 1 2000; echo last-line".to_string()),
+            capture_output: crate::cook::workflow::CaptureOutput::Default,
+            ..Default::default()
+        };
+        executor
+            .execute_step(&step, &env, &mut context)
+            .await
+            .unwrap();
+
+        let output = &context.captured_outputs["shell.output"];
+        assert!(output.len() < 1200, "{} bytes kept", output.len());
+        assert!(output.trim_end().ends_with("last-line"));
+        assert!(output.contains("bytes truncated, full output:"));
+
+        let output_file = &context.variables["shell.output_file"];
+        assert!(output_file.starts_with(artifact_dir.path().to_str().unwrap()));
+        let full = std::fs::read_to_string(output_file).unwrap();
+        assert!(full.contains("\n1\n2\n3\n"));
+        assert!(full.contains("last-line"));
+    }
+
     // ==================== ERROR HANDLING TESTS ====================
 
     #[tokio::test]
@@ -1371,6 +2127,7 @@ mod tests {
             project_dir: Arc::new(temp_dir.path().to_path_buf()),
             session_id: Arc::from("test-session"),
             worktree_name: None,
+            package: None,
         };
 
         let mut context = WorkflowContext::default();
@@ -1399,6 +2156,7 @@ mod tests {
             project_dir: Arc::new(temp_dir.path().to_path_buf()),
             session_id: Arc::from("test-session"),
             worktree_name: None,
+            package: None,
         };
 
         let mut context = WorkflowContext::default();
@@ -1425,6 +2183,7 @@ mod tests {
             project_dir: Arc::new(temp_dir.path().to_path_buf()),
             session_id: Arc::from("test-session"),
             worktree_name: None,
+            package: None,
         };
 
         let mut context = WorkflowContext::default();
@@ -1454,6 +2213,7 @@ mod tests {
             project_dir: Arc::new(temp_dir.path().to_path_buf()),
             session_id: Arc::from("test-session"),
             worktree_name: None,
+            package: None,
         };
 
         let _context = WorkflowContext::default();
@@ -1506,6 +2266,7 @@ mod tests {
             project_dir: Arc::new(temp_dir.path().to_path_buf()),
             session_id: Arc::from("test-session"),
             worktree_name: None,
+            package: None,
         };
 
         let mut context = WorkflowContext::default();
@@ -1519,6 +2280,7 @@ mod tests {
                     max_attempts: 2,
                     fail_workflow: false,
                     commit_required: false,
+                    escalate_model: None,
                 }),
             }),
             ..Default::default()
@@ -1545,6 +2307,7 @@ mod tests {
             project_dir: Arc::new(temp_dir.path().to_path_buf()),
             worktree_name: None,
             session_id: Arc::from("test"),
+            package: None,
         };
 
         // Create a workflow with error recovery
@@ -1564,6 +2327,7 @@ mod tests {
                         fail_workflow: false,
                         retry_original: true,
                         max_retries: 1,
+                        escalate_model: None,
                     }),
                     ..Default::default()
                 },
@@ -1579,6 +2343,8 @@ mod tests {
             iterate: false,
             retry_defaults: None,
             environment: None,
+            always_steps: Vec::new(),
+            strict_variables: false,
         };
 
         // Set test mode
@@ -1604,6 +2370,7 @@ mod tests {
             project_dir: Arc::new(temp_dir.path().to_path_buf()),
             worktree_name: None,
             session_id: Arc::from("test"),
+            package: None,
         };
 
         // Save current directory and change to temp dir for test
@@ -1622,6 +2389,9 @@ mod tests {
             parallel: crate::config::command::ParallelConfig::Boolean(false),
             do_block: vec![Box::new(crate::config::command::WorkflowStepCommand {
                 claude: None,
+                model: None,
+                context: None,
+                validate_output: None,
                 shell: Some("echo Processing item".to_string()),
                 analyze: None,
                 test: None,
@@ -1640,6 +2410,15 @@ mod tests {
                 capture_format: None,
                 capture_streams: None,
                 output_file: None,
+                assert: None,
+                summarize_commits: None,
+                claude_chat: None,
+                script: None,
+                snapshot: false,
+                required: false,
+                commit_policy: None,
+                cwd: None,
+                env: HashMap::new(),
             })],
             continue_on_error: false,
             max_items: None,
@@ -1698,6 +2477,7 @@ mod tests {
             project_dir: Arc::new(temp_dir.path().to_path_buf()),
             worktree_name: Some(Arc::from("test-worktree")),
             session_id: Arc::from("integration-test"),
+            package: None,
         };
 
         // Create a comprehensive workflow
@@ -1713,6 +2493,8 @@ mod tests {
                 // Step 2: Claude command with capture
                 WorkflowStep {
                     claude: Some("/prodigy-analyze".to_string()),
+                    context: None,
+                    validate_output: None,
                     capture_output: CaptureOutput::Variable("analysis_result".to_string()),
                     ..Default::default()
                 },
@@ -1725,6 +2507,8 @@ mod tests {
                 // Step 4: Claude command
                 WorkflowStep {
                     claude: Some("/prodigy-code-review".to_string()),
+                    context: None,
+                    validate_output: None,
                     commit_required: false,
                     ..Default::default()
                 },
@@ -1741,6 +2525,8 @@ mod tests {
             iterate: false,
             retry_defaults: None,
             environment: None,
+            always_steps: Vec::new(),
+            strict_variables: false,
         };
 
         // Execute the workflow
@@ -1820,6 +2606,7 @@ mod tests {
             project_dir: Arc::new(temp_dir.path().to_path_buf()),
             worktree_name: None,
             session_id: Arc::from("iterative-test"),
+            package: None,
         };
 
         // Create an iterative workflow
@@ -1828,6 +2615,8 @@ mod tests {
             mode: WorkflowMode::Sequential,
             steps: vec![WorkflowStep {
                 claude: Some("/prodigy-improve".to_string()),
+                context: None,
+                validate_output: None,
                 ..Default::default()
             }],
             setup_phase: None,
@@ -1837,6 +2626,8 @@ mod tests {
             iterate: true,
             retry_defaults: None,
             environment: None,
+            always_steps: Vec::new(),
+            strict_variables: false,
         };
 
         // Execute the workflow
@@ -1862,6 +2653,7 @@ mod tests {
             project_dir: Arc::new(temp_dir.path().to_path_buf()),
             worktree_name: None,
             session_id: Arc::from("env-test"),
+            package: None,
         };
 
         // Create workflow with environment configuration
@@ -1884,6 +2676,8 @@ mod tests {
             iterate: false,
             retry_defaults: None,
             environment: None, // Environment config not needed for this test
+            always_steps: Vec::new(),
+            strict_variables: false,
         };
 
         let result = executor.execute(&workflow, &env).await;
@@ -1915,6 +2709,7 @@ mod tests {
             project_dir: Arc::new(temp_dir.path().to_path_buf()),
             worktree_name: None,
             session_id: Arc::from("validation-test"),
+            package: None,
         };
 
         // Create workflow with validation
@@ -1923,6 +2718,8 @@ mod tests {
             mode: WorkflowMode::Sequential,
             steps: vec![WorkflowStep {
                 claude: Some("/prodigy-implement-spec 01".to_string()),
+                context: None,
+                validate_output: None,
                 // Validation configuration
                 validate: None,
                 ..Default::default()
@@ -1934,6 +2731,8 @@ mod tests {
             iterate: false,
             retry_defaults: None,
             environment: None,
+            always_steps: Vec::new(),
+            strict_variables: false,
         };
 
         // This test verifies the workflow structure is correct
@@ -1978,6 +2777,7 @@ mod tests {
             project_dir: Arc::new(temp_dir.path().to_path_buf()),
             worktree_name: None,
             session_id: Arc::from("validation-streaming-test"),
+            package: None,
         };
 
         // Create workflow with validation step
@@ -1986,6 +2786,8 @@ mod tests {
             mode: WorkflowMode::Sequential,
             steps: vec![WorkflowStep {
                 claude: Some("/prodigy-implement-spec 01".to_string()),
+                context: None,
+                validate_output: None,
                 validate: Some(crate::cook::workflow::validation::ValidationConfig {
                     claude: Some("/prodigy-validate-spec 01".to_string()),
                     shell: None,
@@ -2006,6 +2808,8 @@ mod tests {
             iterate: false,
             retry_defaults: None,
             environment: None,
+            always_steps: Vec::new(),
+            strict_variables: false,
         };
 
         // Execute workflow
@@ -2044,6 +2848,7 @@ mod tests {
             project_dir: Arc::new(temp_dir.path().to_path_buf()),
             worktree_name: None,
             session_id: Arc::from("resume-test"),
+            package: None,
         };
 
         // Create workflow that tracks completed steps
@@ -2071,6 +2876,8 @@ mod tests {
             iterate: false,
             retry_defaults: None,
             environment: None,
+            always_steps: Vec::new(),
+            strict_variables: false,
         };
 
         // Execute workflow
@@ -2169,6 +2976,7 @@ pub(crate) mod timeout_tests {
             project_dir: Arc::new(temp_dir.path().to_path_buf()),
             worktree_name: None,
             session_id: Arc::from("test"),
+            package: None,
         };
 
         // Create a workflow with a timeout
@@ -2187,6 +2995,8 @@ pub(crate) mod timeout_tests {
             iterate: false,
             retry_defaults: None,
             environment: None,
+            always_steps: Vec::new(),
+            strict_variables: false,
         };
 
         let result = executor.execute(&workflow, &env).await;
@@ -2212,6 +3022,7 @@ pub(crate) mod timeout_tests {
             project_dir: Arc::new(temp_dir.path().to_path_buf()),
             worktree_name: None,
             session_id: Arc::from("test"),
+            package: None,
         };
 
         // Test shell command with timeout
@@ -2234,6 +3045,7 @@ pub(crate) mod timeout_tests {
             project_dir: Arc::new(temp_dir.path().to_path_buf()),
             worktree_name: None,
             session_id: Arc::from("test"),
+            package: None,
         };
 
         // Test shell command that completes within timeout
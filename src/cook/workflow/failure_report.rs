@@ -0,0 +1,267 @@
+//! Failure summaries for failed runs
+//!
+//! When a run fails, the bare error chain rarely says which step failed or
+//! how to continue. The run's [`FailureRecorder`] receives the failing step,
+//! its exit code and the tail of its output from the workflow executor while
+//! the run unwinds; the execution pipeline adds the resume command and the
+//! dead-lettered items of the run's MapReduce job, stores the resulting
+//! [`FailureReport`] in the session metadata and, with `--explain-failure`,
+//! prints it as a short panel.
+
+use super::StepResult;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Session metadata key holding the failure report
+pub const FAILURE_METADATA_KEY: &str = "failure";
+
+/// Output lines kept from the failing step
+pub const OUTPUT_TAIL_LINES: usize = 20;
+
+/// Dead-lettered items listed in a report
+pub const MAX_DLQ_ITEMS: usize = 10;
+
+/// Details gathered while a run executes, consumed when it finishes
+#[derive(Debug, Default)]
+pub struct PendingFailure {
+    /// The step whose failure ended the run
+    pub step: Option<FailedStep>,
+    /// MapReduce job started by the run
+    pub mapreduce_job: Option<String>,
+}
+
+/// The step that failed a run
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FailedStep {
+    pub step_index: usize,
+    pub step: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit_code: Option<i32>,
+    /// Last lines of stderr, or of stdout when stderr is empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub output_tail: Vec<String>,
+}
+
+impl FailedStep {
+    /// Describe a failed step from its result, if it produced one (pure function)
+    pub fn new(step_index: usize, step: String, result: Option<&StepResult>) -> Self {
+        let (exit_code, output_tail) = match result {
+            Some(result) => {
+                let output = if result.stderr.trim().is_empty() {
+                    &result.stdout
+                } else {
+                    &result.stderr
+                };
+                (result.exit_code, output_tail(output, OUTPUT_TAIL_LINES))
+            }
+            None => (None, Vec::new()),
+        };
+        Self {
+            step_index,
+            step,
+            exit_code,
+            output_tail,
+        }
+    }
+}
+
+/// Summary of a failed run
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FailureReport {
+    pub session_id: String,
+    /// First line of the error that ended the run
+    pub error: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub step: Option<FailedStep>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resume_command: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dlq_job_id: Option<String>,
+    /// IDs of the job's dead-lettered items, at most [`MAX_DLQ_ITEMS`]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dlq_items: Vec<String>,
+    #[serde(default)]
+    pub dlq_total: usize,
+}
+
+impl FailureReport {
+    /// Report the error of a run and the step that caused it (pure function)
+    pub fn new(session_id: &str, error: &anyhow::Error, step: Option<FailedStep>) -> Self {
+        Self {
+            session_id: session_id.to_string(),
+            error: error.to_string().lines().next().unwrap_or("").to_string(),
+            step,
+            resume_command: None,
+            dlq_job_id: None,
+            dlq_items: Vec::new(),
+            dlq_total: 0,
+        }
+    }
+}
+
+/// Last `lines` non-empty lines of `output` (pure function)
+pub fn output_tail(output: &str, lines: usize) -> Vec<String> {
+    let all: Vec<&str> = output
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .collect();
+    all[all.len().saturating_sub(lines)..]
+        .iter()
+        .map(|line| line.to_string())
+        .collect()
+}
+
+/// Collects the failure details of one run
+#[derive(Debug, Default)]
+pub struct FailureRecorder {
+    pending: Mutex<PendingFailure>,
+}
+
+impl FailureRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn pending(&self) -> std::sync::MutexGuard<'_, PendingFailure> {
+        match self.pending.lock() {
+            Ok(pending) => pending,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    /// Remember the step whose failure is ending the run
+    pub fn record_step(&self, step: FailedStep) {
+        self.pending().step = Some(step);
+    }
+
+    /// Remember the MapReduce job started by the run
+    pub fn record_mapreduce_job(&self, job_id: &str) {
+        self.pending().mapreduce_job = Some(job_id.to_string());
+    }
+
+    /// Details recorded since the last call
+    pub fn take(&self) -> PendingFailure {
+        std::mem::take(&mut *self.pending())
+    }
+}
+
+pub fn to_metadata_value(report: &FailureReport) -> serde_json::Value {
+    serde_json::to_value(report).unwrap_or_default()
+}
+
+/// Failure report recorded in session metadata, if any
+pub fn from_metadata(metadata: &HashMap<String, serde_json::Value>) -> Option<FailureReport> {
+    metadata
+        .get(FAILURE_METADATA_KEY)
+        .and_then(|value| serde_json::from_value(value.clone()).ok())
+}
+
+/// Human-readable lines of a failure report (pure function)
+pub fn format_report(report: &FailureReport) -> Vec<String> {
+    let mut lines = Vec::new();
+    if let Some(step) = &report.step {
+        let exit = step
+            .exit_code
+            .map(|code| format!(" (exit code {})", code))
+            .unwrap_or_default();
+        lines.push(format!(
+            "Failed step: [{}] {}{}",
+            step.step_index, step.step, exit
+        ));
+    }
+    lines.push(format!("Error: {}", report.error));
+    if let Some(step) = report.step.as_ref().filter(|s| !s.output_tail.is_empty()) {
+        lines.push(format!("Last {} lines of output:", step.output_tail.len()));
+        lines.extend(step.output_tail.iter().map(|line| format!("  {}", line)));
+    }
+    if let Some(job_id) = &report.dlq_job_id {
+        if report.dlq_total > 0 {
+            let more = report.dlq_total.saturating_sub(report.dlq_items.len());
+            let suffix = if more > 0 {
+                format!(" (+{} more)", more)
+            } else {
+                String::new()
+            };
+            lines.push(format!(
+                "Dead-lettered items in {}: {}{}",
+                job_id,
+                report.dlq_items.join(", "),
+                suffix
+            ));
+        }
+    }
+    if let Some(command) = &report.resume_command {
+        lines.push(format!("Resume with: {}", command));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(stdout: &str, stderr: &str) -> StepResult {
+        StepResult {
+            success: false,
+            exit_code: Some(101),
+            stdout: stdout.to_string(),
+            stderr: stderr.to_string(),
+            json_log_location: None,
+        }
+    }
+
+    #[test]
+    fn test_failed_step_keeps_output_tail() {
+        let stderr = (1..=30)
+            .map(|n| format!("error {}", n))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let step = FailedStep::new(
+            2,
+            "shell: cargo test".to_string(),
+            Some(&result("ignored", &stderr)),
+        );
+        assert_eq!(step.exit_code, Some(101));
+        assert_eq!(step.output_tail.len(), OUTPUT_TAIL_LINES);
+        assert_eq!(step.output_tail[0], "error 11");
+
+        let stdout_only =
+            FailedStep::new(0, "shell: make".to_string(), Some(&result("a\n\nb", "")));
+        assert_eq!(stdout_only.output_tail, vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_report_round_trips_and_formats() {
+        let error = anyhow::anyhow!("Step 'shell: cargo test' failed with exit code 101\n\nmore");
+        let mut report = FailureReport::new(
+            "session-1",
+            &error,
+            Some(FailedStep::new(
+                1,
+                "shell: cargo test".to_string(),
+                Some(&result("", "test foo ... FAILED")),
+            )),
+        );
+        report.resume_command = Some("prodigy resume session-1".to_string());
+        report.dlq_job_id = Some("mapreduce-1".to_string());
+        report.dlq_items = vec!["item-1".to_string(), "item-2".to_string()];
+        report.dlq_total = 5;
+
+        let metadata =
+            HashMap::from([(FAILURE_METADATA_KEY.to_string(), to_metadata_value(&report))]);
+        let restored = from_metadata(&metadata).unwrap();
+        assert_eq!(restored, report);
+        assert_eq!(
+            format_report(&restored),
+            vec![
+                "Failed step: [1] shell: cargo test (exit code 101)",
+                "Error: Step 'shell: cargo test' failed with exit code 101",
+                "Last 1 lines of output:",
+                "  test foo ... FAILED",
+                "Dead-lettered items in mapreduce-1: item-1, item-2 (+3 more)",
+                "Resume with: prodigy resume session-1",
+            ]
+        );
+    }
+}
@@ -0,0 +1,684 @@
+//! Convert GitHub Actions workflows into Prodigy workflows
+//!
+//! `prodigy import gha` translates the parts of a GitHub Actions workflow
+//! that have a Prodigy equivalent:
+//!
+//! - `run` steps become `shell` steps, keeping `id`, `env`,
+//!   `working-directory` (as `cwd`) and `timeout-minutes`
+//! - workflow `env` becomes the workflow's `env`; job `env` is added to each
+//!   of the job's steps
+//! - jobs run one after another, ordered so each comes after its `needs`
+//! - a one-dimensional `strategy.matrix` becomes a `foreach` over its values;
+//!   larger matrices are expanded into one copy of the steps per combination
+//! - `${{ matrix.* }}`, `${{ env.* }}` and `${{ secrets.* }}` expressions are
+//!   rewritten to the item, the literal value or a shell variable
+//!
+//! `actions/checkout` steps are dropped, since Prodigy runs in the repository.
+//! Everything else, such as triggers, other actions, `if:` conditions and
+//! other expressions, is reported as a warning and left out or kept verbatim.
+
+use anyhow::{anyhow, bail, Context, Result};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde_yaml::{Mapping, Value};
+use std::collections::BTreeMap;
+
+static EXPRESSION_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\$\{\{\s*(.*?)\s*\}\}").expect("valid regex"));
+
+/// A converted workflow and what could not be converted
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conversion {
+    /// Prodigy workflow YAML, headed by comments listing the warnings
+    pub workflow: String,
+    /// Features that were left out or kept verbatim
+    pub warnings: Vec<String>,
+}
+
+/// How `${{ matrix.* }}` expressions are rewritten in a job's steps
+enum MatrixBinding<'a> {
+    /// The job has no matrix
+    None,
+    /// One-dimensional matrix converted to `foreach`: every key is `${item}`
+    Item,
+    /// One expanded combination
+    Values(&'a BTreeMap<String, String>),
+}
+
+/// Convert a GitHub Actions workflow, optionally only one of its jobs (pure function)
+pub fn convert(source: &str, only_job: Option<&str>) -> Result<Conversion> {
+    let doc: Value = serde_yaml::from_str(source).context("Invalid GitHub Actions YAML")?;
+    let root = doc
+        .as_mapping()
+        .ok_or_else(|| anyhow!("GitHub Actions workflow must be a mapping"))?;
+    let jobs = root
+        .get("jobs")
+        .and_then(Value::as_mapping)
+        .ok_or_else(|| anyhow!("GitHub Actions workflow has no jobs"))?;
+
+    let mut warnings = Vec::new();
+    for key in root.keys().filter_map(Value::as_str) {
+        match key {
+            "name" | "jobs" | "env" | "defaults" => {}
+            "on" => warn(
+                &mut warnings,
+                "Triggers (`on:`) are not converted; start the workflow with `prodigy run`",
+            ),
+            other => warn(
+                &mut warnings,
+                &format!("Workflow `{}` is not supported", other),
+            ),
+        }
+    }
+    let default_cwd = run_defaults(root.get("defaults"), "Workflow", &mut warnings);
+
+    let mut commands = Vec::new();
+    for job_id in job_order(jobs, only_job, &mut warnings)? {
+        let job = jobs
+            .get(job_id.as_str())
+            .and_then(Value::as_mapping)
+            .ok_or_else(|| anyhow!("Job `{}` must be a mapping", job_id))?;
+        commands.extend(convert_job(
+            &job_id,
+            job,
+            default_cwd.as_deref(),
+            &mut warnings,
+        ));
+    }
+    if commands.is_empty() {
+        bail!("No steps could be converted; Prodigy workflows need at least one command");
+    }
+
+    let mut workflow = Mapping::new();
+    if let Some(name) = root.get("name") {
+        workflow.insert("name".into(), Value::String(scalar(name)));
+    }
+    if let Some(env) = root.get("env").and_then(Value::as_mapping) {
+        let env = convert_env(env, &MatrixBinding::None, "Workflow env", &mut warnings);
+        workflow.insert("env".into(), Value::Mapping(env));
+    }
+    workflow.insert("commands".into(), Value::Sequence(commands));
+
+    let mut header =
+        String::from("# Converted from a GitHub Actions workflow by `prodigy import gha`\n");
+    if !warnings.is_empty() {
+        header.push_str("# Review before running:\n");
+        for warning in &warnings {
+            header.push_str(&format!("#   - {}\n", warning));
+        }
+    }
+    Ok(Conversion {
+        workflow: header + &serde_yaml::to_string(&Value::Mapping(workflow))?,
+        warnings,
+    })
+}
+
+/// Job IDs in an order where each job follows the jobs it needs
+fn job_order(
+    jobs: &Mapping,
+    only_job: Option<&str>,
+    warnings: &mut Vec<String>,
+) -> Result<Vec<String>> {
+    let ids: Vec<String> = jobs.keys().map(scalar).collect();
+    if let Some(job) = only_job {
+        if !ids.iter().any(|id| id == job) {
+            bail!(
+                "No job `{}` in the workflow (jobs: {})",
+                job,
+                ids.join(", ")
+            );
+        }
+        if !needs(jobs, job).is_empty() {
+            warn(
+                warnings,
+                &format!(
+                    "Job `{}` needs jobs that were not converted: {}",
+                    job,
+                    needs(jobs, job).join(", ")
+                ),
+            );
+        }
+        return Ok(vec![job.to_string()]);
+    }
+
+    let mut ordered: Vec<String> = Vec::new();
+    while ordered.len() < ids.len() {
+        let next = ids.iter().find(|id| {
+            !ordered.contains(id)
+                && needs(jobs, id)
+                    .iter()
+                    .all(|need| ordered.contains(need) || !ids.contains(need))
+        });
+        match next {
+            Some(id) => ordered.push(id.clone()),
+            None => bail!("Jobs have circular `needs`"),
+        }
+    }
+    Ok(ordered)
+}
+
+/// Jobs listed in a job's `needs` (pure function)
+fn needs(jobs: &Mapping, job: &str) -> Vec<String> {
+    match jobs.get(job).and_then(|job| job.get("needs")) {
+        Some(Value::Sequence(items)) => items.iter().map(scalar).collect(),
+        Some(value) => vec![scalar(value)],
+        None => Vec::new(),
+    }
+}
+
+/// Default working directory from a `defaults:` block
+fn run_defaults(
+    defaults: Option<&Value>,
+    owner: &str,
+    warnings: &mut Vec<String>,
+) -> Option<String> {
+    let run = defaults?.get("run")?;
+    if let Some(shell) = run
+        .get("shell")
+        .map(scalar)
+        .filter(|shell| !is_posix_shell(shell))
+    {
+        warn(
+            warnings,
+            &format!(
+                "{} default shell `{}` is not supported; steps run with sh",
+                owner, shell
+            ),
+        );
+    }
+    run.get("working-directory").map(scalar)
+}
+
+/// Convert a job's steps, expanding its matrix
+fn convert_job(
+    job_id: &str,
+    job: &Mapping,
+    workflow_cwd: Option<&str>,
+    warnings: &mut Vec<String>,
+) -> Vec<Value> {
+    let owner = format!("Job `{}`", job_id);
+    for key in job.keys().filter_map(Value::as_str) {
+        match key {
+            "steps" | "env" | "strategy" | "defaults" | "name" | "needs" | "runs-on" => {}
+            "timeout-minutes" => warn(
+                warnings,
+                &format!(
+                    "{}: `timeout-minutes` is not converted; set `timeout` on its steps",
+                    owner
+                ),
+            ),
+            other => warn(
+                warnings,
+                &format!("{}: `{}` is not supported", owner, other),
+            ),
+        }
+    }
+    let cwd =
+        run_defaults(job.get("defaults"), &owner, warnings).or(workflow_cwd.map(str::to_string));
+    let steps: Vec<&Mapping> = job
+        .get("steps")
+        .and_then(Value::as_sequence)
+        .map(|steps| steps.iter().filter_map(Value::as_mapping).collect())
+        .unwrap_or_default();
+    if steps.is_empty() {
+        warn(warnings, &format!("{} has no steps", owner));
+    }
+
+    let strategy = job.get("strategy");
+    let combinations = strategy
+        .and_then(|strategy| strategy.get("matrix"))
+        .map(|matrix| matrix_combinations(matrix, &owner, warnings))
+        .unwrap_or_default();
+    let job_steps = |binding: &MatrixBinding, warnings: &mut Vec<String>| -> Vec<Value> {
+        let env = job
+            .get("env")
+            .and_then(Value::as_mapping)
+            .map(|env| convert_env(env, binding, &owner, warnings))
+            .unwrap_or_default();
+        steps
+            .iter()
+            .enumerate()
+            .filter_map(|(index, step)| {
+                convert_step(step, index, &owner, &env, cwd.as_deref(), binding, warnings)
+            })
+            .collect()
+    };
+
+    let dimensions = combinations.first().map(BTreeMap::len).unwrap_or(0);
+    if dimensions == 1 {
+        let key = combinations[0].keys().next().cloned().unwrap_or_default();
+        let items: Vec<Value> = combinations
+            .iter()
+            .map(|combination| Value::String(combination[&key].clone()))
+            .collect();
+        let mut foreach = Mapping::new();
+        foreach.insert("foreach".into(), Value::Sequence(items));
+        if let Some(parallel) = strategy.and_then(|s| s.get("max-parallel")) {
+            foreach.insert("parallel".into(), parallel.clone());
+        }
+        if strategy
+            .and_then(|s| s.get("fail-fast"))
+            .and_then(Value::as_bool)
+            == Some(false)
+        {
+            foreach.insert("continue_on_error".into(), Value::Bool(true));
+        }
+        foreach.insert(
+            "do".into(),
+            Value::Sequence(job_steps(&MatrixBinding::Item, warnings)),
+        );
+        let mut step = Mapping::new();
+        step.insert("foreach".into(), Value::Mapping(foreach));
+        return vec![Value::Mapping(step)];
+    }
+    if combinations.is_empty() {
+        return job_steps(&MatrixBinding::None, warnings);
+    }
+    for key in ["max-parallel", "fail-fast"] {
+        if strategy.and_then(|s| s.get(key)).is_some() {
+            warn(
+                warnings,
+                &format!(
+                    "{}: `strategy.{}` is not converted; combinations run one after another",
+                    owner, key
+                ),
+            );
+        }
+    }
+    combinations
+        .iter()
+        .flat_map(|combination| job_steps(&MatrixBinding::Values(combination), warnings))
+        .collect()
+}
+
+/// Every combination of a matrix's values, minus its exclusions
+fn matrix_combinations(
+    matrix: &Value,
+    owner: &str,
+    warnings: &mut Vec<String>,
+) -> Vec<BTreeMap<String, String>> {
+    let Some(matrix) = matrix.as_mapping() else {
+        warn(
+            warnings,
+            &format!(
+                "{}: matrix `{}` is not a list of values and is not converted",
+                owner,
+                scalar(matrix)
+            ),
+        );
+        return Vec::new();
+    };
+
+    let mut combinations = vec![BTreeMap::new()];
+    for (key, values) in matrix {
+        let key = scalar(key);
+        if key == "exclude" {
+            continue;
+        }
+        if key == "include" {
+            warn(
+                warnings,
+                &format!("{}: matrix `include` is not supported", owner),
+            );
+            continue;
+        }
+        let Some(values) = values.as_sequence() else {
+            warn(
+                warnings,
+                &format!(
+                    "{}: matrix `{}` is not a list of values and is not converted",
+                    owner, key
+                ),
+            );
+            continue;
+        };
+        combinations = combinations
+            .into_iter()
+            .flat_map(|combination| {
+                let key = &key;
+                values.iter().map(move |value| {
+                    let mut combination = combination.clone();
+                    combination.insert(key.clone(), scalar(value));
+                    combination
+                })
+            })
+            .collect();
+    }
+
+    let excluded: Vec<BTreeMap<String, String>> = matrix
+        .get("exclude")
+        .and_then(Value::as_sequence)
+        .map(|rules| {
+            rules
+                .iter()
+                .filter_map(Value::as_mapping)
+                .map(|rule| rule.iter().map(|(k, v)| (scalar(k), scalar(v))).collect())
+                .collect()
+        })
+        .unwrap_or_default();
+    combinations
+        .into_iter()
+        .filter(|combination| !combination.is_empty())
+        .filter(|combination| {
+            !excluded.iter().any(|rule| {
+                rule.iter()
+                    .all(|(key, value)| combination.get(key) == Some(value))
+            })
+        })
+        .collect()
+}
+
+/// Convert one step, or `None` if it has no Prodigy equivalent
+fn convert_step(
+    step: &Mapping,
+    index: usize,
+    owner: &str,
+    job_env: &Mapping,
+    default_cwd: Option<&str>,
+    binding: &MatrixBinding,
+    warnings: &mut Vec<String>,
+) -> Option<Value> {
+    let label = match step.get("name").or(step.get("id")) {
+        Some(name) => format!("{}, step `{}`", owner, scalar(name)),
+        None => format!("{}, step {}", owner, index + 1),
+    };
+    if let Some(action) = step.get("uses").map(scalar) {
+        if !action.starts_with("actions/checkout") {
+            warn(
+                warnings,
+                &format!(
+                    "{}: action `{}` is not supported; step skipped",
+                    label, action
+                ),
+            );
+        }
+        return None;
+    }
+    let Some(run) = step.get("run").map(scalar) else {
+        warn(warnings, &format!("{} has no `run` and is skipped", label));
+        return None;
+    };
+
+    let mut converted = Mapping::new();
+    if let Some(id) = step.get("id") {
+        converted.insert("id".into(), Value::String(scalar(id)));
+    }
+    converted.insert(
+        "shell".into(),
+        Value::String(translate(&run, binding, &label, warnings)),
+    );
+    let mut env = job_env.clone();
+    if let Some(step_env) = step.get("env").and_then(Value::as_mapping) {
+        env.extend(convert_env(step_env, binding, &label, warnings));
+    }
+    if !env.is_empty() {
+        converted.insert("env".into(), Value::Mapping(env));
+    }
+    if let Some(cwd) = step
+        .get("working-directory")
+        .map(scalar)
+        .or(default_cwd.map(str::to_string))
+    {
+        converted.insert(
+            "cwd".into(),
+            Value::String(translate(&cwd, binding, &label, warnings)),
+        );
+    }
+    if let Some(minutes) = step.get("timeout-minutes").and_then(Value::as_u64) {
+        converted.insert("timeout".into(), Value::Number((minutes * 60).into()));
+    }
+
+    for key in step.keys().filter_map(Value::as_str) {
+        match key {
+            "name" | "id" | "run" | "env" | "working-directory" | "timeout-minutes" => {}
+            "shell" => {
+                let shell = scalar(&step[key]);
+                if !is_posix_shell(&shell) {
+                    warn(
+                        warnings,
+                        &format!(
+                            "{}: shell `{}` is not supported; the step runs with sh",
+                            label, shell
+                        ),
+                    );
+                }
+            }
+            "if" => warn(
+                warnings,
+                &format!(
+                    "{}: condition `{}` is not converted; the step always runs",
+                    label,
+                    scalar(&step[key])
+                ),
+            ),
+            other => warn(
+                warnings,
+                &format!("{}: `{}` is not supported", label, other),
+            ),
+        }
+    }
+    Some(Value::Mapping(converted))
+}
+
+/// Environment variables with their values translated
+fn convert_env(
+    env: &Mapping,
+    binding: &MatrixBinding,
+    owner: &str,
+    warnings: &mut Vec<String>,
+) -> Mapping {
+    env.iter()
+        .map(|(key, value)| {
+            (
+                Value::String(scalar(key)),
+                Value::String(translate(&scalar(value), binding, owner, warnings)),
+            )
+        })
+        .collect()
+}
+
+/// Rewrite `${{ ... }}` expressions that have a Prodigy equivalent
+fn translate(
+    text: &str,
+    binding: &MatrixBinding,
+    owner: &str,
+    warnings: &mut Vec<String>,
+) -> String {
+    let mut out = String::new();
+    let mut last = 0;
+    for captures in EXPRESSION_RE.captures_iter(text) {
+        let whole = captures.get(0).expect("match");
+        out.push_str(&text[last..whole.start()]);
+        last = whole.end();
+        let expression = &captures[1];
+        let follows_word = text[last..]
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphanumeric() || c == '_');
+        let shell_var = |name: &str| {
+            if follows_word {
+                format!("${{{}}}", name)
+            } else {
+                format!("${}", name)
+            }
+        };
+
+        let replacement = if let Some(key) = expression.strip_prefix("matrix.") {
+            match binding {
+                MatrixBinding::Item => Some("${item}".to_string()),
+                MatrixBinding::Values(values) => values.get(key).cloned(),
+                MatrixBinding::None => None,
+            }
+        } else if let Some(name) = expression.strip_prefix("env.") {
+            Some(shell_var(name))
+        } else if let Some(name) = expression.strip_prefix("secrets.") {
+            warn(
+                warnings,
+                &format!(
+                    "Secret `{}` must be set in the environment before running",
+                    name
+                ),
+            );
+            Some(shell_var(name))
+        } else {
+            None
+        };
+        match replacement {
+            Some(replacement) => out.push_str(&replacement),
+            None => {
+                warn(
+                    warnings,
+                    &format!(
+                        "{}: expression `{}` has no Prodigy equivalent and is kept verbatim",
+                        owner,
+                        whole.as_str()
+                    ),
+                );
+                out.push_str(whole.as_str());
+            }
+        }
+    }
+    out.push_str(&text[last..]);
+    out
+}
+
+fn is_posix_shell(shell: &str) -> bool {
+    matches!(shell.split_whitespace().next(), Some("bash" | "sh"))
+}
+
+/// A YAML scalar as text (pure function)
+fn scalar(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Number(n) => n.to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::Null => String::new(),
+        other => serde_yaml::to_string(other)
+            .unwrap_or_default()
+            .trim()
+            .to_string(),
+    }
+}
+
+/// Add a warning unless it was already reported
+fn warn(warnings: &mut Vec<String>, warning: &str) {
+    if !warnings.iter().any(|w| w == warning) {
+        warnings.push(warning.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::WorkflowConfig;
+
+    const CI: &str = r#"
+name: CI
+on: [push, pull_request]
+env:
+  CARGO_TERM_COLOR: always
+jobs:
+  test:
+    needs: lint
+    runs-on: ubuntu-latest
+    env:
+      RUST_BACKTRACE: "1"
+    strategy:
+      fail-fast: false
+      matrix:
+        toolchain: [stable, beta]
+    steps:
+      - uses: actions/checkout@v4
+      - uses: dtolnay/rust-toolchain@stable
+      - name: Test
+        run: cargo +${{ matrix.toolchain }} test --color ${{ env.CARGO_TERM_COLOR }}
+        timeout-minutes: 10
+  lint:
+    runs-on: ubuntu-latest
+    steps:
+      - id: clippy
+        run: cargo clippy -- -D warnings
+        working-directory: crates/core
+      - name: Upload
+        if: github.event_name == 'push'
+        run: ./upload.sh ${{ secrets.TOKEN }}_v2
+"#;
+
+    #[test]
+    fn test_convert_orders_jobs_and_maps_single_matrix_to_foreach() {
+        let conversion = convert(CI, None).unwrap();
+        let workflow: WorkflowConfig = serde_yaml::from_str(&conversion.workflow).unwrap();
+        assert_eq!(workflow.name.as_deref(), Some("CI"));
+        assert_eq!(workflow.env.as_ref().unwrap()["CARGO_TERM_COLOR"], "always");
+
+        let steps: Vec<Value> = serde_yaml::from_str::<Value>(&conversion.workflow).unwrap()
+            ["commands"]
+            .as_sequence()
+            .unwrap()
+            .clone();
+        assert_eq!(steps.len(), 3);
+        assert_eq!(steps[0]["id"], "clippy");
+        assert_eq!(steps[0]["cwd"], "crates/core");
+        assert_eq!(steps[1]["shell"], "./upload.sh ${TOKEN}_v2");
+
+        let foreach = &steps[2]["foreach"];
+        assert_eq!(
+            foreach["foreach"],
+            serde_yaml::from_str::<Value>("[stable, beta]").unwrap()
+        );
+        assert_eq!(foreach["continue_on_error"], true);
+        let test = &foreach["do"][0];
+        assert_eq!(
+            test["shell"],
+            "cargo +${item} test --color $CARGO_TERM_COLOR"
+        );
+        assert_eq!(test["env"]["RUST_BACKTRACE"], "1");
+        assert_eq!(test["timeout"], 600);
+
+        let warnings = conversion.warnings.join("\n");
+        assert!(warnings.contains("Triggers"), "{}", warnings);
+        assert!(warnings.contains("action `dtolnay/rust-toolchain@stable`"));
+        assert!(warnings.contains("condition `github.event_name == 'push'`"));
+        assert!(warnings.contains("Secret `TOKEN`"));
+        assert!(!warnings.contains("checkout"));
+        assert!(conversion.workflow.contains("#   - Triggers"));
+    }
+
+    #[test]
+    fn test_convert_expands_multi_dimensional_matrix() {
+        let yaml = r#"
+jobs:
+  build:
+    strategy:
+      matrix:
+        os: [linux, macos]
+        features: [default, full]
+        exclude:
+          - os: macos
+            features: full
+    steps:
+      - run: make ${{ matrix.os }}-${{ matrix.features }} ${{ github.sha }}
+"#;
+        let conversion = convert(yaml, Some("build")).unwrap();
+        let doc: Value = serde_yaml::from_str(&conversion.workflow).unwrap();
+        let shells: Vec<&str> = doc["commands"]
+            .as_sequence()
+            .unwrap()
+            .iter()
+            .map(|step| step["shell"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            shells,
+            vec![
+                "make linux-default ${{ github.sha }}",
+                "make linux-full ${{ github.sha }}",
+                "make macos-default ${{ github.sha }}"
+            ]
+        );
+        assert_eq!(conversion.warnings.len(), 1);
+        assert!(conversion.warnings[0].contains("`${{ github.sha }}`"));
+
+        let err = convert(yaml, Some("deploy")).unwrap_err();
+        assert!(err.to_string().contains("jobs: build"));
+    }
+}
@@ -38,6 +38,7 @@
 //! ${step.files_added:json}     # JSON array format
 //! ${step.files_added:*.rs}     # Filter by glob pattern
 //! ${workflow.commit_count}     # Scalar values
+//! ${git.branch}                # Repository metadata, refreshed per step
 //! ```
 //!
 //! # Example
@@ -72,7 +73,7 @@
 //! - **Phase 3**: Diff statistics and file changes (10 tests)
 
 use anyhow::{Context, Result};
-use git2::{DiffOptions, Oid, Repository, StatusOptions};
+use git2::{DescribeFormatOptions, DescribeOptions, DiffOptions, Oid, Repository, StatusOptions};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
@@ -96,6 +97,73 @@ pub struct GitChangeTracker {
     pub(crate) current_step_id: Option<String>,
     /// Last known commit before current step
     pub(crate) last_commit: Option<String>,
+    /// Repository metadata read when the current step began
+    pub(crate) metadata: Option<GitMetadata>,
+}
+
+/// Repository metadata exposed as `${git.*}` variables
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GitMetadata {
+    /// Current branch, or `HEAD` when detached
+    pub branch: String,
+    /// Full SHA of HEAD
+    pub sha: String,
+    /// Abbreviated SHA of HEAD
+    pub short_sha: String,
+    /// Author name of the HEAD commit
+    pub author: String,
+    /// Author email of the HEAD commit
+    pub author_email: String,
+    /// Most recent tag reachable from HEAD, empty if there is none
+    pub last_tag: String,
+    /// Files with uncommitted changes, including untracked files
+    pub changed_files: Vec<String>,
+}
+
+impl GitMetadata {
+    /// Read metadata for the repository's current HEAD
+    pub fn read(repo: &Repository) -> Result<Self> {
+        let head = repo.head().context("Failed to read HEAD")?;
+        let commit = head.peel_to_commit()?;
+        let branch = if repo.head_detached()? {
+            "HEAD".to_string()
+        } else {
+            head.shorthand().unwrap_or("HEAD").to_string()
+        };
+        let sha = commit.id().to_string();
+        let author = commit.author();
+
+        Ok(Self {
+            branch,
+            short_sha: sha.chars().take(7).collect(),
+            sha,
+            author: author.name().unwrap_or_default().to_string(),
+            author_email: author.email().unwrap_or_default().to_string(),
+            last_tag: Self::last_tag(repo).unwrap_or_default(),
+            changed_files: GitChangeTracker::collect_uncommitted_changes(repo)?.files_changed(),
+        })
+    }
+
+    /// Most recent tag reachable from HEAD
+    fn last_tag(repo: &Repository) -> Option<String> {
+        let describe = repo.describe(DescribeOptions::new().describe_tags()).ok()?;
+        describe
+            .format(Some(DescribeFormatOptions::new().abbreviated_size(0)))
+            .ok()
+    }
+
+    /// Variables as `(name, value)` pairs, file lists space-separated (pure function)
+    pub fn variables(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("git.branch", self.branch.clone()),
+            ("git.sha", self.sha.clone()),
+            ("git.short_sha", self.short_sha.clone()),
+            ("git.author", self.author.clone()),
+            ("git.author_email", self.author_email.clone()),
+            ("git.last_tag", self.last_tag.clone()),
+            ("git.changed_files", self.changed_files.join(" ")),
+        ]
+    }
 }
 
 /// Changes tracked for a single step
@@ -142,6 +210,27 @@ impl StepChanges {
         self.commits.len()
     }
 
+    /// One-line diff stat, e.g. `2 files changed (+1 ~1 -0), +10 -2 lines, 1 commit`
+    pub fn diff_stat(&self) -> String {
+        let files = self.files_changed().len();
+        if files == 0 && self.commits.is_empty() {
+            return "no changes".to_string();
+        }
+        let commits = self.commit_count();
+        format!(
+            "{} file{} changed (+{} ~{} -{}), +{} -{} lines, {} commit{}",
+            files,
+            if files == 1 { "" } else { "s" },
+            self.files_added.len(),
+            self.files_modified.len(),
+            self.files_deleted.len(),
+            self.insertions,
+            self.deletions,
+            commits,
+            if commits == 1 { "" } else { "s" }
+        )
+    }
+
     /// Merge changes from another StepChanges
     pub fn merge(&mut self, other: &StepChanges) {
         self.files_added.extend(other.files_added.clone());
@@ -249,6 +338,7 @@ impl GitChangeTracker {
                 step_changes: HashMap::new(),
                 current_step_id: None,
                 last_commit: head_commit,
+                metadata: GitMetadata::read(&repo).ok(),
             })
         } else {
             // Not a git repository, tracker will be inactive
@@ -259,6 +349,7 @@ impl GitChangeTracker {
                 step_changes: HashMap::new(),
                 current_step_id: None,
                 last_commit: None,
+                metadata: None,
             })
         }
     }
@@ -289,6 +380,7 @@ impl GitChangeTracker {
         // Save current commit as baseline for this step
         if let Ok(repo) = Repository::open(&self.repo_path) {
             self.last_commit = Self::get_head_commit(&repo)?;
+            self.metadata = GitMetadata::read(&repo).ok();
         }
 
         self.current_step_id = Some(step_id.clone());
@@ -500,6 +592,22 @@ impl GitChangeTracker {
                 let changes = self.get_workflow_changes();
                 self.resolve_step_variable(&changes, var_name, format, pattern)
             }
+            ["git", "changed_files"] => {
+                let files = self
+                    .metadata
+                    .as_ref()
+                    .map(|m| m.changed_files.as_slice())
+                    .unwrap_or_default();
+                Ok(Self::format_file_list(files, format))
+            }
+            ["git", var_name] => {
+                let key = format!("git.{}", var_name);
+                self.metadata
+                    .as_ref()
+                    .and_then(|m| m.variables().into_iter().find(|(k, _)| *k == key))
+                    .map(|(_, value)| value)
+                    .ok_or_else(|| anyhow::anyhow!("Unknown git variable: {}", var_name))
+            }
             _ => Err(anyhow::anyhow!("Unknown git variable path: {}", var_path)),
         }
     }
@@ -559,6 +667,11 @@ impl GitChangeTracker {
         Self::format_file_list(&filtered, format)
     }
 
+    /// Repository metadata read when the current step began
+    pub fn metadata(&self) -> Option<&GitMetadata> {
+        self.metadata.as_ref()
+    }
+
     /// Check if tracker is active (in a git repository)
     pub fn is_active(&self) -> bool {
         self.workflow_start_commit.is_some()
@@ -28,6 +28,11 @@ mod tests {
 
         assert_eq!(changes.files_changed().len(), 4);
         assert_eq!(changes.commit_count(), 1);
+        assert_eq!(
+            changes.diff_stat(),
+            "4 files changed (+2 ~1 -1), +10 -5 lines, 1 commit"
+        );
+        assert_eq!(StepChanges::default().diff_stat(), "no changes");
     }
 
     #[test]
@@ -94,4 +99,64 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_git_metadata_refreshed_per_step() -> Result<()> {
+        let dir = init_test_repo()?;
+        let mut tracker = GitChangeTracker::new(dir.path())?;
+
+        let metadata = tracker.metadata().cloned().expect("metadata");
+        assert_eq!(metadata.sha.len(), 40);
+        assert_eq!(metadata.short_sha, metadata.sha[..7]);
+        assert_eq!(metadata.author, "Test");
+        assert_eq!(metadata.author_email, "test@example.com");
+        assert_eq!(metadata.last_tag, "");
+        assert!(metadata.changed_files.is_empty());
+
+        {
+            let repo = git2::Repository::open(dir.path())?;
+            let head = repo.head()?.peel_to_commit()?;
+            let sig = git2::Signature::now("Test", "test@example.com")?;
+            repo.tag("v1.2.0", head.as_object(), &sig, "release", false)?;
+        }
+        std::fs::write(dir.path().join("notes.txt"), "draft")?;
+        tracker.begin_step("step1")?;
+
+        assert_eq!(tracker.resolve_variable("git.last_tag")?, "v1.2.0");
+        assert_eq!(tracker.resolve_variable("git.changed_files")?, "notes.txt");
+        assert_eq!(
+            tracker.resolve_variable("git.changed_files:json")?,
+            r#"["notes.txt"]"#
+        );
+        assert_eq!(tracker.resolve_variable("git.branch")?, metadata.branch);
+        assert!(tracker.resolve_variable("git.unknown").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_git_metadata_detached_head() -> Result<()> {
+        let dir = init_test_repo()?;
+        {
+            let repo = git2::Repository::open(dir.path())?;
+            let head = repo.head()?.peel_to_commit()?.id();
+            repo.set_head_detached(head)?;
+        }
+
+        let tracker = GitChangeTracker::new(dir.path())?;
+        assert_eq!(tracker.resolve_variable("git.branch")?, "HEAD");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_non_git_directory_has_no_metadata() -> Result<()> {
+        let dir = TempDir::new()?;
+        let tracker = GitChangeTracker::new(dir.path())?;
+
+        assert!(tracker.metadata().is_none());
+        assert!(tracker.resolve_variable("git.sha").is_err());
+
+        Ok(())
+    }
 }
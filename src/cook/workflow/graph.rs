@@ -0,0 +1,515 @@
+//! Diagrams of a workflow's structure
+//!
+//! `prodigy graph <workflow.yml>` draws a workflow as a Mermaid flowchart or a
+//! Graphviz digraph, for embedding in docs and in pull requests that change
+//! automation. The diagram is built from the same model as `prodigy docs`:
+//!
+//! - Steps run top to bottom in the order they are declared, grouped by phase
+//! - A step with a `when:` condition is preceded by a decision node whose
+//!   "no" branch skips it
+//! - Failure and success handlers branch off the step they belong to
+//! - A MapReduce workflow fans out from its input into the agent template and
+//!   back in, once every agent has finished, to the reduce phase
+
+use super::docs::{SectionDoc, StepDoc, WorkflowDoc};
+use std::fmt::Write;
+use std::str::FromStr;
+
+/// Longest node label before it is shortened
+const MAX_LABEL_CHARS: usize = 60;
+
+/// Diagram output format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphFormat {
+    Mermaid,
+    Dot,
+}
+
+impl FromStr for GraphFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "mermaid" => Ok(Self::Mermaid),
+            "dot" | "graphviz" => Ok(Self::Dot),
+            other => anyhow::bail!(
+                "Unsupported graph format '{}' (expected mermaid or dot)",
+                other
+            ),
+        }
+    }
+}
+
+/// How a node is drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeShape {
+    Step,
+    Condition,
+    Handler,
+    /// Work items entering the map phase
+    Input,
+    /// Agent results merged back after the map phase
+    Merge,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphNode {
+    pub id: String,
+    pub label: String,
+    pub shape: NodeShape,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphEdge {
+    pub from: String,
+    pub to: String,
+    pub label: Option<String>,
+    /// Handler branches are drawn dashed
+    pub dashed: bool,
+}
+
+/// Nodes of one workflow phase
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphCluster {
+    pub id: String,
+    pub title: String,
+    pub nodes: Vec<GraphNode>,
+}
+
+/// A workflow as nodes and edges, independent of output format
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkflowGraph {
+    pub title: String,
+    pub clusters: Vec<GraphCluster>,
+    /// Nodes outside any phase, e.g. the map input
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+}
+
+/// Points the next step is reached from, with the label of that edge
+type Exits = Vec<(String, Option<String>)>;
+
+/// Build the graph of a documented workflow (pure function)
+pub fn build_graph(doc: &WorkflowDoc) -> WorkflowGraph {
+    let mut graph = WorkflowGraph {
+        title: doc.name.clone(),
+        clusters: Vec::new(),
+        nodes: Vec::new(),
+        edges: Vec::new(),
+    };
+    let mut exits: Exits = Vec::new();
+
+    for section in doc.sections.iter().filter(|s| !s.steps.is_empty()) {
+        let mut entry_label = None;
+        match section.label.as_str() {
+            "map" => {
+                let node = GraphNode {
+                    id: "input".to_string(),
+                    label: input_label(doc),
+                    shape: NodeShape::Input,
+                };
+                connect(&mut graph, &exits, &node.id);
+                exits = vec![(node.id.clone(), None)];
+                graph.nodes.push(node);
+                entry_label = Some(fan_out_label(doc));
+            }
+            "always" => entry_label = Some("always".to_string()),
+            _ => {}
+        }
+        if let Some(label) = entry_label {
+            for exit in &mut exits {
+                exit.1 = Some(label.clone());
+            }
+        }
+
+        exits = add_section(&mut graph, section, exits);
+
+        if section.label == "map" {
+            let node = GraphNode {
+                id: "merge".to_string(),
+                label: "merge agent results".to_string(),
+                shape: NodeShape::Merge,
+            };
+            let fan_in: Exits = exits
+                .into_iter()
+                .map(|(from, label)| (from, label.or_else(|| Some("all agents".to_string()))))
+                .collect();
+            connect(&mut graph, &fan_in, &node.id);
+            exits = vec![(node.id.clone(), None)];
+            graph.nodes.push(node);
+        }
+    }
+    graph
+}
+
+/// Label of the map input node, including the item filter
+fn input_label(doc: &WorkflowDoc) -> String {
+    match &doc.map_input {
+        Some(input) => match &input.filter {
+            Some(filter) => format!("input: {} where {}", input.input, filter),
+            None => format!("input: {}", input.input),
+        },
+        None => "input".to_string(),
+    }
+}
+
+/// Edge label describing how work items are distributed to agents
+fn fan_out_label(doc: &WorkflowDoc) -> String {
+    match &doc.map_input {
+        Some(input) => format!("each item, max {} parallel", input.max_parallel),
+        None => "each item".to_string(),
+    }
+}
+
+/// Add a phase's steps, returning the points the next phase is reached from
+fn add_section(graph: &mut WorkflowGraph, section: &SectionDoc, mut exits: Exits) -> Exits {
+    let mut cluster = GraphCluster {
+        id: section.label.clone(),
+        title: section.title.clone(),
+        nodes: Vec::new(),
+    };
+
+    for (index, step) in section.steps.iter().enumerate() {
+        let id = format!("{}{}", section.label, index + 1);
+
+        if let Some(condition) = &step.when {
+            let decision = format!("{}_when", id);
+            cluster.nodes.push(GraphNode {
+                id: decision.clone(),
+                label: condition.clone(),
+                shape: NodeShape::Condition,
+            });
+            connect(graph, &exits, &decision);
+            exits = vec![(decision, Some("yes".to_string()))];
+        }
+
+        cluster.nodes.push(GraphNode {
+            id: id.clone(),
+            label: step.description.clone(),
+            shape: NodeShape::Step,
+        });
+        connect(graph, &exits, &id);
+        add_handlers(graph, &mut cluster, &id, step);
+
+        exits = vec![(id.clone(), None)];
+        if step.when.is_some() {
+            exits.push((format!("{}_when", id), Some("no".to_string())));
+        }
+    }
+
+    graph.clusters.push(cluster);
+    exits
+}
+
+/// Branch a step's failure and success handlers off it
+fn add_handlers(graph: &mut WorkflowGraph, cluster: &mut GraphCluster, id: &str, step: &StepDoc) {
+    for (index, handler) in step.handlers.iter().enumerate() {
+        let (trigger, action) = handler.split_once(": ").unwrap_or(("handler", handler));
+        let handler_id = format!("{}_handler{}", id, index + 1);
+        cluster.nodes.push(GraphNode {
+            id: handler_id.clone(),
+            label: action.to_string(),
+            shape: NodeShape::Handler,
+        });
+        graph.edges.push(GraphEdge {
+            from: id.to_string(),
+            to: handler_id,
+            label: Some(trigger.to_string()),
+            dashed: true,
+        });
+    }
+}
+
+fn connect(graph: &mut WorkflowGraph, exits: &Exits, to: &str) {
+    graph
+        .edges
+        .extend(exits.iter().map(|(from, label)| GraphEdge {
+            from: from.clone(),
+            to: to.to_string(),
+            label: label.clone(),
+            dashed: false,
+        }));
+}
+
+/// Render a graph in the given format (pure function)
+pub fn render(graph: &WorkflowGraph, format: GraphFormat) -> String {
+    match format {
+        GraphFormat::Mermaid => render_mermaid(graph),
+        GraphFormat::Dot => render_dot(graph),
+    }
+}
+
+/// Render a graph as a Mermaid flowchart (pure function)
+pub fn render_mermaid(graph: &WorkflowGraph) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "---");
+    let _ = writeln!(out, "title: {}", mermaid_text(&graph.title));
+    let _ = writeln!(out, "---");
+    let _ = writeln!(out, "flowchart TD");
+
+    for cluster in &graph.clusters {
+        let _ = writeln!(
+            out,
+            "    subgraph {}[\"{}\"]",
+            cluster.id,
+            mermaid_text(&cluster.title)
+        );
+        for node in &cluster.nodes {
+            let _ = writeln!(out, "        {}", mermaid_node(node));
+        }
+        let _ = writeln!(out, "    end");
+    }
+    for node in &graph.nodes {
+        let _ = writeln!(out, "    {}", mermaid_node(node));
+    }
+    for edge in &graph.edges {
+        let arrow = if edge.dashed { "-.->" } else { "-->" };
+        match &edge.label {
+            Some(label) => {
+                let _ = writeln!(
+                    out,
+                    "    {} {}|\"{}\"| {}",
+                    edge.from,
+                    arrow,
+                    mermaid_text(label),
+                    edge.to
+                );
+            }
+            None => {
+                let _ = writeln!(out, "    {} {} {}", edge.from, arrow, edge.to);
+            }
+        }
+    }
+    out
+}
+
+fn mermaid_node(node: &GraphNode) -> String {
+    let label = mermaid_text(&node.label);
+    match node.shape {
+        NodeShape::Step => format!("{}[\"{}\"]", node.id, label),
+        NodeShape::Condition => format!("{}{{\"{}\"}}", node.id, label),
+        NodeShape::Handler => format!("{}(\"{}\")", node.id, label),
+        NodeShape::Input => format!("{}[/\"{}\"/]", node.id, label),
+        NodeShape::Merge => format!("{}([\"{}\"])", node.id, label),
+    }
+}
+
+/// Label text with the characters Mermaid treats as markup escaped
+fn mermaid_text(text: &str) -> String {
+    let mut escaped = String::new();
+    for c in shorten(text).chars() {
+        match c {
+            '"' => escaped.push_str("#quot;"),
+            '<' => escaped.push_str("#lt;"),
+            '>' => escaped.push_str("#gt;"),
+            '#' => escaped.push_str("#35;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Render a graph as a Graphviz digraph (pure function)
+pub fn render_dot(graph: &WorkflowGraph) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "digraph workflow {{");
+    let _ = writeln!(out, "    label=\"{}\";", dot_text(&graph.title));
+    let _ = writeln!(out, "    labelloc=t;");
+    let _ = writeln!(out, "    node [shape=box];");
+
+    for cluster in &graph.clusters {
+        let _ = writeln!(out, "    subgraph cluster_{} {{", cluster.id);
+        let _ = writeln!(out, "        label=\"{}\";", dot_text(&cluster.title));
+        for node in &cluster.nodes {
+            let _ = writeln!(out, "        {}", dot_node(node));
+        }
+        let _ = writeln!(out, "    }}");
+    }
+    for node in &graph.nodes {
+        let _ = writeln!(out, "    {}", dot_node(node));
+    }
+    for edge in &graph.edges {
+        let mut attributes = Vec::new();
+        if let Some(label) = &edge.label {
+            attributes.push(format!("label=\"{}\"", dot_text(label)));
+        }
+        if edge.dashed {
+            attributes.push("style=dashed".to_string());
+        }
+        if attributes.is_empty() {
+            let _ = writeln!(out, "    {} -> {};", edge.from, edge.to);
+        } else {
+            let _ = writeln!(
+                out,
+                "    {} -> {} [{}];",
+                edge.from,
+                edge.to,
+                attributes.join(", ")
+            );
+        }
+    }
+    let _ = writeln!(out, "}}");
+    out
+}
+
+fn dot_node(node: &GraphNode) -> String {
+    let shape = match node.shape {
+        NodeShape::Step => "",
+        NodeShape::Condition => ", shape=diamond",
+        NodeShape::Handler => ", style=rounded",
+        NodeShape::Input => ", shape=parallelogram",
+        NodeShape::Merge => ", shape=oval",
+    };
+    format!(
+        "{} [label=\"{}\"{}];",
+        node.id,
+        dot_text(&node.label),
+        shape
+    )
+}
+
+/// Label text escaped for a double-quoted DOT string
+fn dot_text(text: &str) -> String {
+    shorten(text).replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Shorten long labels so nodes stay readable
+fn shorten(text: &str) -> String {
+    if text.chars().count() <= MAX_LABEL_CHARS {
+        return text.to_string();
+    }
+    let mut short: String = text.chars().take(MAX_LABEL_CHARS - 3).collect();
+    short.push_str("...");
+    short
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cook::workflow::docs::MapInputDoc;
+
+    fn step(description: &str) -> StepDoc {
+        StepDoc {
+            description: description.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn section(label: &str, title: &str, steps: Vec<StepDoc>) -> SectionDoc {
+        SectionDoc {
+            title: title.to_string(),
+            label: label.to_string(),
+            steps,
+        }
+    }
+
+    fn workflow(sections: Vec<SectionDoc>, map_input: Option<MapInputDoc>) -> WorkflowDoc {
+        WorkflowDoc {
+            name: "example".to_string(),
+            mapreduce: map_input.is_some(),
+            parameters: Vec::new(),
+            env: Vec::new(),
+            map_input,
+            sections,
+        }
+    }
+
+    fn edge(graph: &WorkflowGraph, from: &str, to: &str) -> Option<GraphEdge> {
+        graph
+            .edges
+            .iter()
+            .find(|e| e.from == from && e.to == to)
+            .cloned()
+    }
+
+    #[test]
+    fn test_conditional_step_can_be_skipped() {
+        let mut deploy = step("shell: ./deploy.sh");
+        deploy.when = Some("${tests_passed}".to_string());
+        deploy.handlers = vec!["on failure: claude: /fix-deploy".to_string()];
+        let doc = workflow(
+            vec![section(
+                "step",
+                "Steps",
+                vec![step("shell: cargo test"), deploy, step("shell: notify")],
+            )],
+            None,
+        );
+
+        let graph = build_graph(&doc);
+        assert!(edge(&graph, "step1", "step2_when").is_some());
+        assert_eq!(
+            edge(&graph, "step2_when", "step2")
+                .unwrap()
+                .label
+                .as_deref(),
+            Some("yes")
+        );
+        assert_eq!(
+            edge(&graph, "step2_when", "step3")
+                .unwrap()
+                .label
+                .as_deref(),
+            Some("no")
+        );
+        assert!(edge(&graph, "step2", "step3").is_some());
+
+        let handler = edge(&graph, "step2", "step2_handler1").unwrap();
+        assert!(handler.dashed);
+        assert_eq!(handler.label.as_deref(), Some("on failure"));
+    }
+
+    #[test]
+    fn test_mapreduce_fans_out_and_in() {
+        let doc = workflow(
+            vec![
+                section("setup", "Setup", vec![step("shell: generate items")]),
+                section("map", "Map", vec![step("claude: /fix ${item.path}")]),
+                section("reduce", "Reduce", vec![step("shell: summarize")]),
+            ],
+            Some(MapInputDoc {
+                input: "items.json".to_string(),
+                json_path: "$.items[*]".to_string(),
+                max_parallel: "4".to_string(),
+                filter: None,
+            }),
+        );
+
+        let graph = build_graph(&doc);
+        assert!(edge(&graph, "setup1", "input").is_some());
+        assert_eq!(
+            edge(&graph, "input", "map1").unwrap().label.as_deref(),
+            Some("each item, max 4 parallel")
+        );
+        assert_eq!(
+            edge(&graph, "map1", "merge").unwrap().label.as_deref(),
+            Some("all agents")
+        );
+        assert!(edge(&graph, "merge", "reduce1").is_some());
+    }
+
+    #[test]
+    fn test_render_formats_escape_labels() {
+        let doc = workflow(
+            vec![section(
+                "step",
+                "Steps",
+                vec![step("shell: echo \"a > b\" # note")],
+            )],
+            None,
+        );
+        let graph = build_graph(&doc);
+
+        let mermaid = render(&graph, GraphFormat::Mermaid);
+        assert!(mermaid.contains("flowchart TD"));
+        assert!(mermaid.contains("step1[\"shell: echo #quot;a #gt; b#quot; #35; note\"]"));
+
+        let dot = render(&graph, GraphFormat::Dot);
+        assert!(dot.starts_with("digraph workflow {"));
+        assert!(dot.contains("subgraph cluster_step {"));
+        assert!(dot.contains("step1 [label=\"shell: echo \\\"a > b\\\" # note\"];"));
+
+        assert!("graphviz".parse::<GraphFormat>().is_ok());
+        assert!("svg".parse::<GraphFormat>().is_err());
+    }
+}
@@ -370,6 +370,8 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
         let step = WorkflowStep {
             claude: Some("/test-command".to_string()),
+            context: None,
+            validate_output: None,
             ..Default::default()
         };
         let mut context = WorkflowContext::default();
@@ -380,6 +382,7 @@ mod tests {
                 "abc123def456", // head_before
                 &step,
                 "test-step",
+                "",
                 &mut context,
             )
             .await;
@@ -427,6 +430,7 @@ mod tests {
                 "abc123def456",
                 &step,
                 "test-step",
+                "",
                 &mut context,
             )
             .await;
@@ -464,6 +468,7 @@ mod tests {
                 "abc123def456",
                 &step,
                 "test-step",
+                "",
                 &mut context,
             )
             .await;
@@ -483,6 +488,8 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
         let step = WorkflowStep {
             claude: Some("/test-command".to_string()),
+            context: None,
+            validate_output: None,
             auto_commit: false,
             commit_required: true,
             ..Default::default()
@@ -495,6 +502,7 @@ mod tests {
                 "abc123def456",
                 &step,
                 "test-step",
+                "",
                 &mut context,
             )
             .await;
@@ -510,4 +518,38 @@ mod tests {
                 || err_msg.contains("required")
         );
     }
+
+    #[tokio::test]
+    async fn test_handle_commit_verification_commit_required_no_changes_reported() {
+        let (mut executor, _, _, user_mock, git_mock) = create_test_executor_with_git_mock().await;
+
+        // Scenario: No commits created, commit_required, agent reported no changes needed
+        git_mock.add_success_response("abc123def456").await; // HEAD same as before
+
+        let temp_dir = TempDir::new().unwrap();
+        let step = WorkflowStep {
+            claude: Some("/test-command".to_string()),
+            commit_required: true,
+            ..Default::default()
+        };
+        let mut context = WorkflowContext::default();
+
+        let result = executor
+            .handle_commit_verification(
+                temp_dir.path(),
+                "abc123def456",
+                &step,
+                "test-step",
+                "Checked the code\nPRODIGY_NO_CHANGES: already handled\n",
+                &mut context,
+            )
+            .await;
+
+        // Should succeed without commits
+        assert!(!result.unwrap());
+        let messages = user_mock.get_messages();
+        assert!(messages
+            .iter()
+            .any(|(t, m)| t == "info" && m == "test-step: no changes needed: already handled"));
+    }
 }
@@ -2,17 +2,24 @@
 //!
 //! Handles command execution with git commit verification and iteration logic.
 
+pub mod assertions;
 pub mod checkpoint;
 pub mod checkpoint_errors;
+pub mod checkpoint_marks;
 pub mod checkpoint_path;
 #[cfg(test)]
 mod checkpoint_tests;
+pub mod claude_chat;
+pub mod command_check;
+pub mod commit_summary;
 #[cfg(test)]
 mod commit_tracking_tests;
 pub mod composer_integration;
 pub mod composition;
 #[cfg(test)]
 mod conditional_tests;
+pub mod context_pack;
+pub mod docs;
 pub mod effects;
 pub mod error_policy;
 #[cfg(test)]
@@ -23,6 +30,8 @@ mod error_recovery_tests;
 mod executor;
 #[cfg(test)]
 mod executor_tests;
+pub mod failure_report;
+pub mod gha_import;
 pub mod git_context;
 #[cfg(test)]
 mod git_context_commit_tests;
@@ -35,12 +44,21 @@ mod git_context_tests;
 #[cfg(test)]
 mod git_context_uncommitted_tests;
 mod git_utils;
+pub mod graph;
 pub mod normalized;
 mod on_failure;
+pub mod output_validation;
 pub mod progress;
 pub mod progress_config;
 pub mod pure;
+pub mod relocate;
 pub mod resume;
+pub mod score_gate;
+pub mod script;
+pub mod snapshots;
+pub mod step_audit;
+pub mod step_outcomes;
+pub mod step_selection;
 pub mod step_validation;
 #[cfg(test)]
 mod step_validation_tests;
@@ -56,7 +74,8 @@ pub use checkpoint::{CheckpointManager, WorkflowCheckpoint};
 pub use checkpoint_errors::{format_path, mask_secret, CheckpointError};
 pub use checkpoint_path::{resolve_global_base_dir, CheckpointStorage};
 pub use composer_integration::{
-    is_composable_workflow, parse_composable_workflow, CompositionError,
+    apply_parameters_to_content, is_composable_workflow, parse_composable_workflow,
+    CompositionError,
 };
 pub use composition::{
     ComposableWorkflow, ComposedWorkflow, CompositionMetadata, Parameter, ParameterDefinitions,
@@ -69,11 +88,15 @@ pub use error_policy::{
     RetryConfig, WorkflowErrorPolicy,
 };
 pub use executor::{
-    specialized_commands::execute_write_file_command, CaptureOutput, CommandType,
-    ExtendedWorkflowConfig, HandlerStep, StepResult, WorkflowContext,
-    WorkflowExecutor as WorkflowExecutorImpl, WorkflowMode, WorkflowStep,
+    format_undefined_variables,
+    specialized_commands::{
+        execute_assert_command, execute_summarize_commits_command, execute_write_file_command,
+    },
+    without_environment_variables, CaptureOutput, CommandType, ExtendedWorkflowConfig, HandlerStep,
+    StepResult, WorkflowContext, WorkflowExecutor as WorkflowExecutorImpl, WorkflowMode,
+    WorkflowStep,
 };
-pub use git_context::{GitChangeTracker, StepChanges, VariableFormat};
+pub use git_context::{GitChangeTracker, GitMetadata, StepChanges, VariableFormat};
 pub use normalized::{
     ExecutionMode, MapReduceConfig, NormalizedStep, NormalizedWorkflow, StepCommand, StepHandlers,
     WorkflowType,
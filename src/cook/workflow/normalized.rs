@@ -36,6 +36,7 @@ pub struct NormalizedStep {
     pub env: Arc<HashMap<String, String>>,
     pub outputs: Option<Arc<HashMap<String, OutputDeclaration>>>,
     pub commit_required: bool,
+    pub commit_policy: Option<crate::cook::commit_tracker::CommitPolicy>,
     pub when: Option<Arc<str>>,
 }
 
@@ -148,11 +149,12 @@ impl NormalizedWorkflow {
                 // Convert on_failure from TestDebugConfig to OnFailureConfig if needed
                 let on_failure = step.on_failure.as_ref().map(|tf| {
                     Arc::new(OnFailureConfig::Advanced {
-                        claude: Some(tf.claude.clone()),
+                        claude: tf.debug_command(),
                         shell: None,
                         fail_workflow: tf.fail_workflow,
                         retry_original: false,
                         max_retries: tf.max_attempts,
+                        escalate_model: tf.escalate_model.clone(),
                     })
                 });
 
@@ -170,11 +172,12 @@ impl NormalizedWorkflow {
                             step.test.as_ref().and_then(|t| {
                                 t.on_failure.as_ref().map(|tf| {
                                     Arc::new(OnFailureConfig::Advanced {
-                                        claude: Some(tf.claude.clone()),
+                                        claude: tf.debug_command(),
                                         shell: None,
                                         fail_workflow: tf.fail_workflow,
                                         retry_original: false,
                                         max_retries: tf.max_attempts,
+                                        escalate_model: tf.escalate_model.clone(),
                                     })
                                 })
                             })
@@ -186,10 +189,11 @@ impl NormalizedWorkflow {
                         on_exit_code: Arc::new(HashMap::new()), // WorkflowStepCommand doesn't have on_exit_code
                     },
                     timeout: step.timeout.map(Duration::from_secs),
-                    working_dir: None, // WorkflowStepCommand doesn't have working_dir field
-                    env: Arc::new(HashMap::new()), // WorkflowStepCommand doesn't have env field
+                    working_dir: step.cwd.as_ref().map(PathBuf::from),
+                    env: Arc::new(step.env.clone()),
                     outputs: step.outputs.as_ref().map(|o| Arc::new(o.clone())),
                     commit_required: step.commit_required,
+                    commit_policy: step.commit_policy.clone(),
                     when: step.when.as_ref().map(|w| Arc::from(w.as_str())),
                 })
             }
@@ -213,6 +217,7 @@ impl NormalizedWorkflow {
                     outputs: cmd.outputs.as_ref().map(|o| Arc::new(o.clone())),
                     commit_required: cmd.metadata.commit_required,
                     when: None, // Structured commands don't have when clauses
+                    commit_policy: None,
                 })
             }
             WorkflowCommand::SimpleObject(cmd) => {
@@ -228,6 +233,7 @@ impl NormalizedWorkflow {
                     outputs: None,
                     commit_required: cmd.commit_required.unwrap_or(false),
                     when: None, // SimpleObject commands don't have when clauses
+                    commit_policy: None,
                 })
             }
             WorkflowCommand::Simple(cmd) => {
@@ -243,6 +249,7 @@ impl NormalizedWorkflow {
                     outputs: None,
                     commit_required: false,
                     when: None, // Simple commands don't have when clauses
+                    commit_policy: None,
                 })
             }
         }
@@ -253,10 +260,19 @@ impl NormalizedWorkflow {
         WorkflowStep {
             name: cmd.id.clone(),
             claude: cmd.claude.clone(),
+            model: cmd.model.clone(),
+            context: cmd.context.clone(),
+            validate_output: cmd.validate_output.clone(),
             shell: cmd.shell.clone(),
             test: cmd.test.clone(),
             foreach: cmd.foreach.clone(),
             write_file: None,
+            assert: cmd.assert.clone(),
+            summarize_commits: cmd.summarize_commits.clone(),
+            claude_chat: cmd.claude_chat.clone(),
+            script: cmd.script.clone(),
+            snapshot: cmd.snapshot,
+            required: cmd.required,
             command: None,
             handler: None,
             capture: None,
@@ -267,14 +283,15 @@ impl NormalizedWorkflow {
             auto_commit: false,
             commit_config: None,
             timeout: None,
-            working_dir: None,
-            env: HashMap::new(),
+            working_dir: cmd.cwd.as_ref().map(PathBuf::from),
+            env: cmd.env.clone(),
             on_failure: cmd.on_failure.as_ref().map(|tf| OnFailureConfig::Advanced {
-                claude: Some(tf.claude.clone()),
+                claude: tf.debug_command(),
                 shell: None,
                 fail_workflow: tf.fail_workflow,
                 retry_original: false,
                 max_retries: tf.max_attempts,
+                escalate_model: tf.escalate_model.clone(),
             }),
             retry: None,
             on_success: cmd
@@ -289,6 +306,7 @@ impl NormalizedWorkflow {
             validation_timeout: None,
             ignore_validation_failure: false,
             when: cmd.when.clone(),
+            commit_policy: cmd.commit_policy.clone(),
         }
     }
 
@@ -325,6 +343,8 @@ impl NormalizedWorkflow {
             iterate: self.extract_max_iterations()? > 1,
             retry_defaults: None, // Would need to be set from workflow config
             environment: None,    // Would need to be set from workflow config
+            always_steps: Vec::new(),
+            strict_variables: false,
         })
     }
 
@@ -355,6 +375,9 @@ impl NormalizedWorkflow {
                 return Ok(WorkflowStep {
                     name: Some(step.id.to_string()),
                     claude: None,
+                    model: None,
+                    context: None,
+                    validate_output: None,
                     shell: None,
                     test: None,
                     foreach: None,
@@ -395,6 +418,13 @@ impl NormalizedWorkflow {
                     validation_timeout: None,
                     ignore_validation_failure: false,
                     when: step.when.as_ref().map(|w| w.to_string()), // PRESERVED!
+                    assert: None,
+                    summarize_commits: None,
+                    claude_chat: None,
+                    script: None,
+                    snapshot: false,
+                    required: false,
+                    commit_policy: step.commit_policy.clone(),
                 });
             }
             StepCommand::Simple(cmd) => {
@@ -402,6 +432,9 @@ impl NormalizedWorkflow {
                 return Ok(WorkflowStep {
                     name: Some(step.id.to_string()),
                     claude: None,
+                    model: None,
+                    context: None,
+                    validate_output: None,
                     shell: None,
                     test: None,
                     foreach: None,
@@ -438,6 +471,13 @@ impl NormalizedWorkflow {
                     validation_timeout: None,
                     ignore_validation_failure: false,
                     when: step.when.as_ref().map(|w| w.to_string()), // PRESERVED!
+                    assert: None,
+                    summarize_commits: None,
+                    claude_chat: None,
+                    script: None,
+                    snapshot: false,
+                    required: false,
+                    commit_policy: step.commit_policy.clone(),
                 });
             }
         };
@@ -445,6 +485,9 @@ impl NormalizedWorkflow {
         Ok(WorkflowStep {
             name: Some(step.id.to_string()),
             claude,
+            model: None,
+            context: None,
+            validate_output: None,
             shell,
             test,
             foreach,
@@ -481,6 +524,13 @@ impl NormalizedWorkflow {
             validation_timeout: None,
             ignore_validation_failure: false,
             when: step.when.as_ref().map(|w| w.to_string()), // PRESERVED!
+            assert: None,
+            summarize_commits: None,
+            claude_chat: None,
+            script: None,
+            snapshot: false,
+            required: false,
+            commit_policy: step.commit_policy.clone(),
         })
     }
 
@@ -571,6 +621,13 @@ mod tests {
             env_files: None,
             profiles: None,
             merge: None,
+            concurrency: None,
+            claude_permissions: None,
+            always: Vec::new(),
+            strict_variables: None,
+            defaults: None,
+            protected_paths: None,
+            score_gate: None,
         };
 
         let normalized =
@@ -597,6 +654,9 @@ mod tests {
 
         let step_cmd = WorkflowStepCommand {
             claude: Some("test command".to_string()),
+            model: None,
+            context: None,
+            validate_output: None,
             shell: None,
             analyze: None,
             test: None,
@@ -615,6 +675,15 @@ mod tests {
             capture_format: None,
             capture_streams: None,
             output_file: None,
+            assert: None,
+            summarize_commits: None,
+            claude_chat: None,
+            script: None,
+            snapshot: false,
+            required: false,
+            commit_policy: None,
+            cwd: None,
+            env: HashMap::new(),
         };
 
         let config = WorkflowConfig {
@@ -625,6 +694,13 @@ mod tests {
             env_files: None,
             profiles: None,
             merge: None,
+            concurrency: None,
+            claude_permissions: None,
+            always: Vec::new(),
+            strict_variables: None,
+            defaults: None,
+            protected_paths: None,
+            score_gate: None,
         };
 
         let normalized =
@@ -650,6 +726,13 @@ mod tests {
             env_files: None,
             profiles: None,
             merge: None,
+            concurrency: None,
+            claude_permissions: None,
+            always: Vec::new(),
+            strict_variables: None,
+            defaults: None,
+            protected_paths: None,
+            score_gate: None,
         };
         assert_eq!(
             NormalizedWorkflow::classify_workflow_type(&standard),
@@ -678,6 +761,13 @@ mod tests {
             env_files: None,
             profiles: None,
             merge: None,
+            concurrency: None,
+            claude_permissions: None,
+            always: Vec::new(),
+            strict_variables: None,
+            defaults: None,
+            protected_paths: None,
+            score_gate: None,
         };
         assert_eq!(
             NormalizedWorkflow::classify_workflow_type(&with_outputs),
@@ -706,6 +796,7 @@ mod tests {
             outputs: None,
             commit_required: false,
             when: None,
+            commit_policy: None,
         };
         assert!(workflow.validate_step(&invalid_step).is_err());
 
@@ -721,6 +812,7 @@ mod tests {
             outputs: None,
             commit_required: false,
             when: None,
+            commit_policy: None,
         };
         assert!(workflow.validate_step(&invalid_timeout).is_err());
 
@@ -736,6 +828,7 @@ mod tests {
             outputs: None,
             commit_required: false,
             when: None,
+            commit_policy: None,
         };
         assert!(workflow.validate_step(&valid_step).is_ok());
     }
@@ -64,6 +64,62 @@ pub struct HandlerCommand {
     pub continue_on_error: bool,
 }
 
+/// What a failure chain does with the failed step once it gives up
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FailureOutcome {
+    /// Treat the failure as handled and move on to the next step
+    Continue,
+    /// Fail this step; an enclosing chain moves on to its next handler
+    #[default]
+    FailStep,
+    /// Fail the workflow, escaping any enclosing chain
+    FailWorkflow,
+}
+
+/// One handler in an `on_failure` chain
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChainHandler {
+    /// Shell command to execute
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shell: Option<String>,
+
+    /// Claude command to execute
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub claude: Option<String>,
+
+    /// Maximum handler execution time in seconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<u64>,
+
+    /// Failure handling for the handler itself
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_failure: Option<Box<OnFailureConfig>>,
+
+    /// Outcome applied as soon as this handler fails, instead of trying the next one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub outcome: Option<FailureOutcome>,
+}
+
+/// Ordered chain of failure handlers sharing one attempt budget
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FailureChainConfig {
+    /// Handlers tried in order until the step recovers
+    pub handlers: Vec<ChainHandler>,
+
+    /// Handler runs plus retries of the original step allowed across the chain
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_total_attempts: Option<u32>,
+
+    /// Whether to retry the original step after each successful handler
+    #[serde(default = "default_chain_retry_original")]
+    pub retry_original: bool,
+
+    /// Outcome once every handler was tried or the attempts ran out
+    #[serde(default)]
+    pub outcome: FailureOutcome,
+}
+
 /// Configuration for handling command failures
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -80,6 +136,9 @@ pub enum OnFailureConfig {
     /// Detailed handler configuration
     Detailed(FailureHandlerConfig),
 
+    /// Ordered handler chain with explicit outcomes
+    Chain(FailureChainConfig),
+
     /// Advanced configuration with handler and control flags
     /// This must come before FailControl because it has more specific fields
     Advanced {
@@ -102,6 +161,10 @@ pub enum OnFailureConfig {
         /// Maximum retry attempts (supports both max_retries and max_attempts)
         #[serde(default = "default_retries", alias = "max_attempts")]
         max_retries: u32,
+
+        /// Model to retry a failed Claude step with
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        escalate_model: Option<String>,
     },
 
     /// Just control whether to fail the workflow
@@ -118,6 +181,10 @@ fn default_fail() -> bool {
     false // By default, don't fail if there's a handler
 }
 
+fn default_chain_retry_original() -> bool {
+    true
+}
+
 fn default_retries() -> u32 {
     1
 }
@@ -138,6 +205,7 @@ impl OnFailureConfig {
             OnFailureConfig::Advanced { fail_workflow, .. } => *fail_workflow,
             OnFailureConfig::FailControl { fail_workflow } => *fail_workflow,
             OnFailureConfig::Handler(_) => false, // If there's a handler, don't fail by default
+            OnFailureConfig::Chain(_) => true,    // `continue` outcomes are applied by the chain
         }
     }
 
@@ -210,6 +278,9 @@ impl OnFailureConfig {
                         name: None,
                         shell: shell.clone(),
                         claude: claude.clone(),
+                        model: None,
+                        context: None,
+                        validate_output: None,
                         test: None,
                         foreach: None,
                         write_file: None,
@@ -236,6 +307,13 @@ impl OnFailureConfig {
                         validation_timeout: None,
                         ignore_validation_failure: false,
                         when: None,
+                        assert: None,
+                        summarize_commits: None,
+                        claude_chat: None,
+                        script: None,
+                        snapshot: false,
+                        required: false,
+                        commit_policy: None,
                     })
                 } else {
                     None
@@ -262,6 +340,17 @@ impl OnFailureConfig {
         }
     }
 
+    /// Model to retry a failed Claude step with, if escalation is configured
+    pub fn escalate_model(&self) -> Option<&str> {
+        match self {
+            OnFailureConfig::Advanced { escalate_model, .. } => escalate_model
+                .as_deref()
+                .map(str::trim)
+                .filter(|m| !m.is_empty()),
+            _ => None,
+        }
+    }
+
     /// Get the handler strategy
     pub fn strategy(&self) -> HandlerStrategy {
         match self {
@@ -285,6 +374,14 @@ impl OnFailureConfig {
             _ => None,
         }
     }
+
+    /// Get the handler chain, if this is a chain configuration
+    pub fn chain(&self) -> Option<&FailureChainConfig> {
+        match self {
+            OnFailureConfig::Chain(chain) => Some(chain),
+            _ => None,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -340,6 +437,20 @@ max_retries: 3
         assert_eq!(config.max_retries(), 3);
     }
 
+    #[test]
+    fn test_parse_escalate_model() {
+        let yaml = r#"
+escalate_model: opus
+max_attempts: 1
+"#;
+        let config: OnFailureConfig = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(config.escalate_model(), Some("opus"));
+        assert!(config.handler().is_none());
+
+        let config: OnFailureConfig = serde_yaml::from_str("claude: /fix").unwrap();
+        assert_eq!(config.escalate_model(), None);
+    }
+
     #[test]
     fn test_max_attempts_implies_retry() {
         // Test that max_attempts > 0 implies retry without retry_original
@@ -407,4 +518,36 @@ handler_failure_fatal: true
         assert_eq!(commands.len(), 2);
         assert!(commands[0].continue_on_error);
     }
+
+    #[test]
+    fn test_chain_config() {
+        let yaml = r#"
+max_total_attempts: 4
+outcome: continue
+handlers:
+  - shell: "cargo clean"
+  - claude: "/fix-build"
+    on_failure:
+      handlers:
+        - shell: "git checkout ."
+      outcome: fail_workflow
+  - shell: "notify.sh"
+    outcome: fail_step
+"#;
+        let config: OnFailureConfig = serde_yaml::from_str(yaml).unwrap();
+        let chain = config.chain().expect("chain config");
+        assert_eq!(chain.handlers.len(), 3);
+        assert_eq!(chain.max_total_attempts, Some(4));
+        assert!(chain.retry_original);
+        assert_eq!(chain.outcome, FailureOutcome::Continue);
+        assert_eq!(chain.handlers[2].outcome, Some(FailureOutcome::FailStep));
+        assert!(config.handler_commands().is_empty());
+        assert!(config.handler().is_none());
+
+        let nested = chain.handlers[1].on_failure.as_deref().unwrap();
+        assert_eq!(
+            nested.chain().unwrap().outcome,
+            FailureOutcome::FailWorkflow
+        );
+    }
 }
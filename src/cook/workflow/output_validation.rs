@@ -0,0 +1,297 @@
+//! Output contracts for Claude steps
+//!
+//! A Claude step can declare a `validate_output:` block describing what its
+//! response must look like. After each run the executor checks the response;
+//! when it does not meet the contract, the command is re-run with the
+//! validation errors appended so Claude can correct itself. The step fails
+//! once `max_attempts` runs have been used without passing.
+//!
+//! ```yaml
+//! - claude: "/prodigy-plan ${spec}"
+//!   validate_output:
+//!     json_schema:
+//!       type: object
+//!       required: [steps]
+//!     shell: "test -n \"$(git log -1 --format=%H origin/main..HEAD)\""
+//!     max_attempts: 3
+//! ```
+//!
+//! Regex and JSON schema checks look at Claude's response text. The shell
+//! validator runs in the working directory with the response saved to the
+//! file named by `PRODIGY_CLAUDE_OUTPUT`, so it can also check commits or
+//! generated files; it passes when it exits with status 0.
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Environment variable naming the file holding the response being validated
+pub const CLAUDE_OUTPUT_ENV: &str = "PRODIGY_CLAUDE_OUTPUT";
+
+/// Contract a Claude step's response must satisfy
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OutputValidationConfig {
+    /// Regular expression the response must match
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub regex: Option<String>,
+
+    /// JSON schema the response must satisfy
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub json_schema: Option<Value>,
+
+    /// Shell command that must exit successfully
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shell: Option<String>,
+
+    /// Total runs allowed, including the first
+    #[serde(default = "default_max_attempts")]
+    pub max_attempts: u32,
+}
+
+fn default_max_attempts() -> u32 {
+    3
+}
+
+impl OutputValidationConfig {
+    /// Check the response against the regex and JSON schema (pure function)
+    ///
+    /// The shell validator is run separately by the executor.
+    pub fn check_response(&self, response: &str) -> Vec<String> {
+        let mut errors = Vec::new();
+
+        if let Some(pattern) = &self.regex {
+            match Regex::new(pattern) {
+                Ok(re) if re.is_match(response) => {}
+                Ok(_) => errors.push(format!("Output does not match the pattern `{pattern}`")),
+                Err(e) => errors.push(format!("Invalid validation pattern `{pattern}`: {e}")),
+            }
+        }
+
+        if let Some(schema) = &self.json_schema {
+            match extract_json(response) {
+                Some(value) => check_schema(&value, schema, "$", &mut errors),
+                None => errors.push("Output is not valid JSON".to_string()),
+            }
+        }
+
+        errors
+    }
+}
+
+/// Text of Claude's response, taken from the final `result` event when the
+/// output is a stream-json log (pure function)
+pub fn response_text(stdout: &str) -> String {
+    stdout
+        .lines()
+        .rev()
+        .filter_map(|line| serde_json::from_str::<Value>(line.trim()).ok())
+        .find(|event| event.get("type").and_then(Value::as_str) == Some("result"))
+        .and_then(|event| {
+            event
+                .get("result")
+                .and_then(Value::as_str)
+                .map(String::from)
+        })
+        .unwrap_or_else(|| stdout.to_string())
+}
+
+/// Parse the response as JSON, falling back to its last fenced code block
+fn extract_json(response: &str) -> Option<Value> {
+    if let Ok(value) = serde_json::from_str(response.trim()) {
+        return Some(value);
+    }
+
+    let mut blocks = response.split("```").skip(1).step_by(2).collect::<Vec<_>>();
+    blocks.reverse();
+    blocks.into_iter().find_map(|block| {
+        let body = block.strip_prefix("json").unwrap_or(block);
+        serde_json::from_str(body.trim()).ok()
+    })
+}
+
+/// Validate `value` against the supported JSON schema keywords
+///
+/// Supports `type`, `enum`, `required`, `properties`, `items`, `minItems`
+/// and `minLength`; other keywords are ignored.
+fn check_schema(value: &Value, schema: &Value, path: &str, errors: &mut Vec<String>) {
+    if let Some(expected) = schema.get("type") {
+        let types: Vec<&str> = match expected {
+            Value::String(t) => vec![t.as_str()],
+            Value::Array(ts) => ts.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        if !types.is_empty() && !types.iter().any(|t| has_type(value, t)) {
+            errors.push(format!("{path}: expected {}", types.join(" or ")));
+            return;
+        }
+    }
+
+    if let Some(allowed) = schema.get("enum").and_then(Value::as_array) {
+        if !allowed.contains(value) {
+            errors.push(format!("{path}: value is not one of the allowed values"));
+        }
+    }
+
+    if let Some(obj) = value.as_object() {
+        if let Some(required) = schema.get("required").and_then(Value::as_array) {
+            for field in required.iter().filter_map(Value::as_str) {
+                if !obj.contains_key(field) {
+                    errors.push(format!("{path}: missing required field `{field}`"));
+                }
+            }
+        }
+        if let Some(properties) = schema.get("properties").and_then(Value::as_object) {
+            for (name, property_schema) in properties {
+                if let Some(property) = obj.get(name) {
+                    check_schema(property, property_schema, &format!("{path}.{name}"), errors);
+                }
+            }
+        }
+    }
+
+    if let Some(items) = value.as_array() {
+        if let Some(min) = schema.get("minItems").and_then(Value::as_u64) {
+            if (items.len() as u64) < min {
+                errors.push(format!("{path}: expected at least {min} items"));
+            }
+        }
+        if let Some(item_schema) = schema.get("items") {
+            for (i, item) in items.iter().enumerate() {
+                check_schema(item, item_schema, &format!("{path}[{i}]"), errors);
+            }
+        }
+    }
+
+    if let (Some(s), Some(min)) = (
+        value.as_str(),
+        schema.get("minLength").and_then(Value::as_u64),
+    ) {
+        if (s.chars().count() as u64) < min {
+            errors.push(format!("{path}: expected at least {min} characters"));
+        }
+    }
+}
+
+fn has_type(value: &Value, expected: &str) -> bool {
+    match expected {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        _ => true,
+    }
+}
+
+/// Command for the next attempt, with the validation errors appended (pure function)
+pub fn build_reprompt(command: &str, errors: &[String]) -> String {
+    let mut prompt = format!("{command}\n\nYour previous response did not pass validation:\n");
+    for error in errors {
+        prompt.push_str(&format!("- {error}\n"));
+    }
+    prompt.push_str("Fix these problems and respond again.");
+    prompt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn config(regex: Option<&str>, json_schema: Option<Value>) -> OutputValidationConfig {
+        OutputValidationConfig {
+            regex: regex.map(String::from),
+            json_schema,
+            shell: None,
+            max_attempts: 3,
+        }
+    }
+
+    #[test]
+    fn test_parse_config() {
+        let config: OutputValidationConfig = serde_yaml::from_str(
+            "regex: 'DONE'\njson_schema:\n  type: object\n  required: [summary]\n",
+        )
+        .unwrap();
+        assert_eq!(config.regex.as_deref(), Some("DONE"));
+        assert_eq!(
+            config.json_schema,
+            Some(json!({"type": "object", "required": ["summary"]}))
+        );
+        assert_eq!(config.max_attempts, 3);
+    }
+
+    #[test]
+    fn test_regex_check() {
+        let config = config(Some(r"^STATUS: (ok|done)"), None);
+        assert!(config.check_response("STATUS: ok\nmore").is_empty());
+        let errors = config.check_response("nothing to report");
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("does not match"));
+    }
+
+    #[test]
+    fn test_json_schema_check() {
+        let schema = json!({
+            "type": "object",
+            "required": ["summary", "files"],
+            "properties": {
+                "summary": {"type": "string", "minLength": 3},
+                "files": {"type": "array", "items": {"type": "string"}, "minItems": 1},
+                "status": {"enum": ["ok", "partial"]}
+            }
+        });
+        let config = config(None, Some(schema));
+
+        assert!(config
+            .check_response(r#"{"summary": "Fixed it", "files": ["a.rs"], "status": "ok"}"#)
+            .is_empty());
+
+        let mut errors =
+            config.check_response(r#"{"summary": "x", "files": [1], "status": "bad"}"#);
+        errors.sort();
+        assert_eq!(
+            errors,
+            vec![
+                "$.files[0]: expected string",
+                "$.status: value is not one of the allowed values",
+                "$.summary: expected at least 3 characters",
+            ]
+        );
+
+        let errors = config.check_response("{}");
+        assert_eq!(errors.len(), 2);
+        assert_eq!(
+            config.check_response("not json"),
+            vec!["Output is not valid JSON"]
+        );
+    }
+
+    #[test]
+    fn test_json_in_fenced_block() {
+        let config = config(None, Some(json!({"type": "object", "required": ["ok"]})));
+        let response = "Here is the plan:\n```json\n{\"ok\": true}\n```\nDone.";
+        assert!(config.check_response(response).is_empty());
+    }
+
+    #[test]
+    fn test_response_text_from_stream_json() {
+        let stream = concat!(
+            r#"{"type":"system","subtype":"init"}"#,
+            "\n",
+            r#"{"type":"result","subtype":"success","result":"STATUS: ok"}"#,
+            "\n"
+        );
+        assert_eq!(response_text(stream), "STATUS: ok");
+        assert_eq!(response_text("plain output"), "plain output");
+    }
+
+    #[test]
+    fn test_build_reprompt() {
+        let prompt = build_reprompt("/plan", &["missing `steps`".to_string()]);
+        assert!(prompt.starts_with("/plan\n\nYour previous response did not pass validation:\n"));
+        assert!(prompt.contains("- missing `steps`\n"));
+    }
+}
@@ -3,6 +3,7 @@
 //! Provides real-time progress monitoring for sequential workflows,
 //! including support for resumed workflows with accurate step tracking.
 
+use crate::styled_println;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
@@ -492,14 +493,14 @@ impl ProgressDisplay {
     /// Display progress update if enough time has passed
     pub fn update(&mut self, message: &str) {
         if self.last_update.elapsed() >= self.min_update_interval {
-            println!("🔄 {}", message);
+            styled_println!("🔄 {}", message);
             self.last_update = Instant::now();
         }
     }
 
     /// Force display a message regardless of timing
     pub fn force_update(&mut self, message: &str) {
-        println!("🔄 {}", message);
+        styled_println!("🔄 {}", message);
         self.last_update = Instant::now();
     }
 }
@@ -117,6 +117,7 @@ mod tests {
                 duration: Duration::from_millis(100),
                 completed_at: Utc::now(),
                 retry_state: None,
+                changes: None,
             })
             .collect();
 
@@ -143,6 +144,9 @@ mod tests {
             error_recovery_state: None,
             retry_checkpoint_state: None,
             variable_checkpoint_state: None,
+            failure_chains: Vec::new(),
+            repo: None,
+            step_selection: None,
         }
     }
 
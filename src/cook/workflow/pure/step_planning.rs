@@ -102,6 +102,7 @@ mod tests {
             outputs: None,
             commit_required,
             when: None,
+            commit_policy: None,
         }
     }
 
@@ -0,0 +1,648 @@
+//! Resuming a session from a different checkout
+//!
+//! A session's worktree lives under `~/.prodigy/worktrees`, but it is linked
+//! to the checkout it was created from, and its checkpoints name the workflow
+//! file by path. Moving or copying that checkout breaks resume. Checkpoints
+//! therefore record a [`RepoIdentity`]: the checkout root, its `origin` URL
+//! and the workflow path relative to the root.
+//!
+//! `prodigy resume <session> --relocate <new_path>` uses it to move the
+//! session: it checks that `new_path` is the same repository, repairs the
+//! worktree's git links with `git worktree repair`, and only then rewrites
+//! the paths under the old root in the checkpoints and the worktree's session
+//! state. `prodigy resume-job <job> --relocate <new_path>` does the same for
+//! a MapReduce job's parent worktree and state directory. The checkout must
+//! be moved or copied together with its `.git` directory; a fresh clone does
+//! not know the session's worktree.
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use tokio::process::Command;
+
+/// Checkout a session runs from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RepoIdentity {
+    /// Absolute root of the checkout
+    pub root: PathBuf,
+    /// URL of the `origin` remote, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin_url: Option<String>,
+    /// Workflow file relative to `root`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workflow_path: Option<PathBuf>,
+}
+
+impl RepoIdentity {
+    /// Identify the checkout containing `dir`, which may be one of its worktrees
+    ///
+    /// Returns `None` outside a git repository.
+    pub async fn detect(dir: &Path) -> Option<Self> {
+        let common_dir = git(
+            dir,
+            &["rev-parse", "--path-format=absolute", "--git-common-dir"],
+        )
+        .await
+        .ok()?;
+        let root = checkout_root(Path::new(common_dir.trim()))?;
+        let origin_url = git(&root, &["remote", "get-url", "origin"])
+            .await
+            .ok()
+            .map(|url| url.trim().to_string())
+            .filter(|url| !url.is_empty());
+        Some(Self {
+            root,
+            origin_url,
+            workflow_path: None,
+        })
+    }
+
+    /// This identity with `workflow` recorded relative to the root (pure function)
+    ///
+    /// Relative workflow paths are taken to be relative to the root already.
+    pub fn with_workflow(&self, workflow: Option<&Path>) -> Self {
+        let workflow_path = workflow.map(|path| {
+            path.strip_prefix(&self.root)
+                .map(Path::to_path_buf)
+                .unwrap_or_else(|_| path.to_path_buf())
+        });
+        Self {
+            workflow_path: workflow_path.filter(|path| path.is_relative()),
+            ..self.clone()
+        }
+    }
+}
+
+/// Result of moving a session to a new checkout
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Relocation {
+    pub old_root: PathBuf,
+    pub new_root: PathBuf,
+    /// Files whose paths were rewritten
+    pub files: usize,
+    /// Paths rewritten across those files
+    pub paths: usize,
+}
+
+/// Checkout root for a git common directory such as `/repo/.git` (pure function)
+fn checkout_root(common_dir: &Path) -> Option<PathBuf> {
+    if common_dir.file_name()? == ".git" {
+        common_dir.parent().map(Path::to_path_buf)
+    } else {
+        // Bare repositories have no checkout to relocate
+        None
+    }
+}
+
+/// Checkout root named by a worktree's `.git` file (pure function)
+///
+/// The file reads `gitdir: /repo/.git/worktrees/<name>`.
+pub fn root_from_gitdir_file(contents: &str) -> Option<PathBuf> {
+    let gitdir = Path::new(contents.trim().strip_prefix("gitdir:")?.trim());
+    let worktrees = gitdir.parent()?;
+    if worktrees.file_name()? != "worktrees" {
+        return None;
+    }
+    checkout_root(worktrees.parent()?)
+}
+
+/// Whether two remote URLs name the same repository (pure function)
+///
+/// Ignores a trailing slash or `.git` suffix.
+pub fn same_remote(a: &str, b: &str) -> bool {
+    fn normalize(url: &str) -> &str {
+        let url = url.trim().trim_end_matches('/');
+        url.strip_suffix(".git").unwrap_or(url)
+    }
+    normalize(a) == normalize(b)
+}
+
+/// `path` moved from under `old_root` to under `new_root` (pure function)
+///
+/// Returns `None` for paths outside `old_root`.
+pub fn rebase_path(path: &Path, old_root: &Path, new_root: &Path) -> Option<PathBuf> {
+    let relative = path.strip_prefix(old_root).ok()?;
+    Some(if relative.as_os_str().is_empty() {
+        new_root.to_path_buf()
+    } else {
+        new_root.join(relative)
+    })
+}
+
+/// Rewrite every string in `value` that is a path under `old_root` (pure function)
+///
+/// Returns the number of strings rewritten. Only whole path components match,
+/// so `/repo-old` is not under `/repo`.
+pub fn rebase_json_paths(value: &mut Value, old_root: &Path, new_root: &Path) -> usize {
+    match value {
+        Value::String(s) => match rebase_path(Path::new(s.as_str()), old_root, new_root) {
+            Some(rebased) => {
+                *s = rebased.to_string_lossy().into_owned();
+                1
+            }
+            None => 0,
+        },
+        Value::Array(items) => items
+            .iter_mut()
+            .map(|item| rebase_json_paths(item, old_root, new_root))
+            .sum(),
+        Value::Object(fields) => fields
+            .values_mut()
+            .map(|field| rebase_json_paths(field, old_root, new_root))
+            .sum(),
+        _ => 0,
+    }
+}
+
+/// Point a checkpoint at `new_root` (pure function)
+///
+/// Rebases absolute paths and resolves the root-relative workflow path the
+/// checkpoint recorded. Returns the number of paths rewritten.
+pub fn relocate_checkpoint(checkpoint: &mut Value, old_root: &Path, new_root: &Path) -> usize {
+    let mut rewritten = rebase_json_paths(checkpoint, old_root, new_root);
+    let relative_workflow = checkpoint
+        .pointer("/repo/workflow_path")
+        .and_then(Value::as_str)
+        .map(|path| new_root.join(path).to_string_lossy().into_owned());
+    if let (Some(workflow), Some(fields)) = (relative_workflow, checkpoint.as_object_mut()) {
+        if fields.get("workflow_path").and_then(Value::as_str) != Some(workflow.as_str()) {
+            fields.insert("workflow_path".to_string(), Value::String(workflow));
+            rewritten += 1;
+        }
+    }
+    rewritten
+}
+
+/// Move a session's worktree and checkpoints to the checkout at `new_path`
+///
+/// Nothing is rewritten unless `new_path` is the same repository and the
+/// worktree's git links could be repaired.
+pub async fn relocate_session(
+    worktree: &Path,
+    checkpoint_dir: &Path,
+    new_path: &Path,
+) -> Result<Relocation> {
+    let new_identity = new_checkout(new_path).await?;
+    let new_root = new_identity.root.clone();
+
+    let checkpoint_files = checkpoint_files(checkpoint_dir).await?;
+    let mut checkpoints = Vec::new();
+    for path in checkpoint_files {
+        let contents = fs::read_to_string(&path)
+            .await
+            .with_context(|| format!("Failed to read checkpoint {}", path.display()))?;
+        let json: Value = serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse checkpoint {}", path.display()))?;
+        checkpoints.push((path, json));
+    }
+    let recorded: Option<RepoIdentity> = checkpoints
+        .iter()
+        .filter_map(|(_, json)| json.get("repo").cloned())
+        .find_map(|repo| serde_json::from_value(repo).ok());
+
+    // Older checkpoints have no identity; the worktree still names its checkout
+    let old_root = match &recorded {
+        Some(repo) => repo.root.clone(),
+        None => linked_root(worktree).await?,
+    };
+
+    if let Some(expected) = recorded.as_ref().and_then(|r| r.origin_url.as_deref()) {
+        match new_identity.origin_url.as_deref() {
+            Some(actual) if same_remote(expected, actual) => {}
+            actual => bail!(
+                "{} is not the same repository: expected origin {}, found {}",
+                new_root.display(),
+                expected,
+                actual.unwrap_or("no origin remote")
+            ),
+        }
+    }
+
+    // Repair the links between the checkout and the worktree before touching state
+    repair_worktree(worktree, &new_root).await?;
+
+    let mut relocation = Relocation {
+        old_root: old_root.clone(),
+        new_root: new_root.clone(),
+        files: 0,
+        paths: 0,
+    };
+    for (path, mut json) in checkpoints {
+        let rewritten = relocate_checkpoint(&mut json, &old_root, &new_root);
+        if rewritten > 0 {
+            write_json_atomically(&path, &json).await?;
+            relocation.files += 1;
+            relocation.paths += rewritten;
+        }
+    }
+
+    rebase_session_state(worktree, &mut relocation).await?;
+    Ok(relocation)
+}
+
+/// Move a MapReduce job's parent worktree and state to the checkout at `new_path`
+///
+/// Like [`relocate_session`], but rewrites every JSON file in the job's state
+/// directory (`~/.prodigy/state/<repo>/mapreduce/jobs/<id>`). MapReduce
+/// checkpoints record no [`RepoIdentity`], so the old checkout is the one the
+/// parent worktree is linked to.
+pub async fn relocate_job(
+    parent_worktree: &Path,
+    job_dir: &Path,
+    new_path: &Path,
+) -> Result<Relocation> {
+    let new_root = new_checkout(new_path).await?.root;
+    let old_root = linked_root(parent_worktree).await?;
+    repair_worktree(parent_worktree, &new_root).await?;
+
+    let mut relocation = Relocation {
+        old_root: old_root.clone(),
+        new_root: new_root.clone(),
+        files: 0,
+        paths: 0,
+    };
+    for path in json_files(job_dir).await? {
+        let contents = fs::read_to_string(&path)
+            .await
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        // Files that are not JSON documents have no paths to rebase
+        let Ok(mut json) = serde_json::from_str::<Value>(&contents) else {
+            continue;
+        };
+        let rewritten = rebase_json_paths(&mut json, &old_root, &new_root);
+        if rewritten > 0 {
+            write_json_atomically(&path, &json).await?;
+            relocation.files += 1;
+            relocation.paths += rewritten;
+        }
+    }
+
+    rebase_session_state(parent_worktree, &mut relocation).await?;
+    Ok(relocation)
+}
+
+/// Identity of the checkout at `new_path`, which must be its root
+async fn new_checkout(new_path: &Path) -> Result<RepoIdentity> {
+    let new_root = fs::canonicalize(new_path)
+        .await
+        .with_context(|| format!("Checkout {} does not exist", new_path.display()))?;
+    let identity = RepoIdentity::detect(&new_root)
+        .await
+        .ok_or_else(|| anyhow!("{} is not a git checkout", new_root.display()))?;
+    if identity.root != new_root {
+        bail!(
+            "{} is inside the checkout {}; pass the checkout root",
+            new_root.display(),
+            identity.root.display()
+        );
+    }
+    Ok(identity)
+}
+
+/// Checkout a worktree's `.git` file links it to
+async fn linked_root(worktree: &Path) -> Result<PathBuf> {
+    let git_file = fs::read_to_string(worktree.join(".git"))
+        .await
+        .with_context(|| format!("{} is not a git worktree", worktree.display()))?;
+    root_from_gitdir_file(&git_file).ok_or_else(|| {
+        anyhow!(
+            "Cannot tell which checkout {} belonged to",
+            worktree.display()
+        )
+    })
+}
+
+/// Link `worktree` to the checkout at `new_root` and check that it took
+async fn repair_worktree(worktree: &Path, new_root: &Path) -> Result<()> {
+    git(
+        new_root,
+        &["worktree", "repair", &worktree.to_string_lossy()],
+    )
+    .await
+    .with_context(|| {
+        format!(
+            "Failed to repair worktree {} from {}",
+            worktree.display(),
+            new_root.display()
+        )
+    })?;
+    let linked = RepoIdentity::detect(worktree).await.map(|repo| repo.root);
+    if linked.as_deref() != Some(new_root) {
+        bail!(
+            "Worktree {} is not registered in {}. Relocation needs the checkout \
+             moved or copied together with its .git directory, not a fresh clone.",
+            worktree.display(),
+            new_root.display()
+        );
+    }
+    Ok(())
+}
+
+/// Rebase the paths in a worktree's session state, if it has one
+async fn rebase_session_state(worktree: &Path, relocation: &mut Relocation) -> Result<()> {
+    let session_state = worktree.join(".prodigy").join("session_state.json");
+    if let Ok(contents) = fs::read_to_string(&session_state).await {
+        let mut json: Value = serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse {}", session_state.display()))?;
+        let rewritten = rebase_json_paths(&mut json, &relocation.old_root, &relocation.new_root);
+        if rewritten > 0 {
+            write_json_atomically(&session_state, &json).await?;
+            relocation.files += 1;
+            relocation.paths += rewritten;
+        }
+    }
+    Ok(())
+}
+
+/// JSON files under `dir`, including its subdirectories
+async fn json_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let mut entries = fs::read_dir(&dir)
+            .await
+            .with_context(|| format!("Failed to read {}", dir.display()))?;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if entry.file_type().await?.is_dir() {
+                pending.push(path);
+            } else if path.extension().is_some_and(|ext| ext == "json") {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Checkpoint files in `dir`, oldest first
+///
+/// Resume picks the most recently modified checkpoint, so rewriting them in
+/// this order keeps the latest one latest.
+async fn checkpoint_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut entries = fs::read_dir(dir)
+        .await
+        .with_context(|| format!("Failed to read checkpoints in {}", dir.display()))?;
+    while let Some(entry) = entries.next_entry().await? {
+        let path = entry.path();
+        let is_checkpoint = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.ends_with(".checkpoint.json"));
+        if is_checkpoint {
+            let modified = entry.metadata().await?.modified()?;
+            files.push((modified, path));
+        }
+    }
+    files.sort();
+    Ok(files.into_iter().map(|(_, path)| path).collect())
+}
+
+/// Replace `path` with `json` via a temp file and rename
+async fn write_json_atomically(path: &Path, json: &Value) -> Result<()> {
+    let temp = path.with_extension("relocate.tmp");
+    fs::write(&temp, serde_json::to_string_pretty(json)?)
+        .await
+        .with_context(|| format!("Failed to write {}", temp.display()))?;
+    fs::rename(&temp, path)
+        .await
+        .with_context(|| format!("Failed to replace {}", path.display()))
+}
+
+/// Run git in `dir`, returning stdout
+async fn git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .await
+        .map_err(|e| anyhow!("Failed to run git {}: {}", args.join(" "), e))?;
+    if !output.status.success() {
+        bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_rebase_path_matches_whole_components() {
+        let old = Path::new("/src/repo");
+        let new = Path::new("/work/repo");
+
+        assert_eq!(
+            rebase_path(Path::new("/src/repo/flows/ci.yml"), old, new),
+            Some(PathBuf::from("/work/repo/flows/ci.yml"))
+        );
+        assert_eq!(
+            rebase_path(Path::new("/src/repo"), old, new),
+            Some(PathBuf::from("/work/repo"))
+        );
+        assert_eq!(rebase_path(Path::new("/src/repo-old/a"), old, new), None);
+        assert_eq!(rebase_path(Path::new("flows/ci.yml"), old, new), None);
+    }
+
+    #[test]
+    fn test_relocate_checkpoint() {
+        let mut checkpoint = json!({
+            "workflow_path": "flows/ci.yml",
+            "variable_state": {"PROJECT_ROOT": "/src/repo", "note": "/src/repository"},
+            "repo": {
+                "root": "/src/repo",
+                "origin_url": "git@example.com:org/repo.git",
+                "workflow_path": "flows/ci.yml"
+            }
+        });
+
+        let rewritten = relocate_checkpoint(
+            &mut checkpoint,
+            Path::new("/src/repo"),
+            Path::new("/work/r"),
+        );
+
+        assert_eq!(rewritten, 3);
+        assert_eq!(checkpoint["workflow_path"], "/work/r/flows/ci.yml");
+        assert_eq!(checkpoint["variable_state"]["PROJECT_ROOT"], "/work/r");
+        assert_eq!(checkpoint["variable_state"]["note"], "/src/repository");
+        assert_eq!(checkpoint["repo"]["root"], "/work/r");
+    }
+
+    #[test]
+    fn test_with_workflow_records_root_relative_path() {
+        let repo = RepoIdentity {
+            root: PathBuf::from("/src/repo"),
+            origin_url: None,
+            workflow_path: None,
+        };
+
+        let absolute = repo.with_workflow(Some(Path::new("/src/repo/flows/ci.yml")));
+        assert_eq!(absolute.workflow_path, Some(PathBuf::from("flows/ci.yml")));
+
+        let outside = repo.with_workflow(Some(Path::new("/tmp/ci.yml")));
+        assert_eq!(outside.workflow_path, None);
+    }
+
+    #[test]
+    fn test_root_from_gitdir_file() {
+        assert_eq!(
+            root_from_gitdir_file("gitdir: /src/repo/.git/worktrees/session-1\n"),
+            Some(PathBuf::from("/src/repo"))
+        );
+        assert_eq!(root_from_gitdir_file("gitdir: /src/repo.git"), None);
+    }
+
+    /// Repository at `root` with `worktree` checked out on branch `session-1`
+    async fn init_repo_with_worktree(root: &Path, worktree: &Path) {
+        git(root, &["init", "-q"]).await.unwrap();
+        git(
+            root,
+            &[
+                "remote",
+                "add",
+                "origin",
+                "https://example.com/org/repo.git",
+            ],
+        )
+        .await
+        .unwrap();
+        git(
+            root,
+            &[
+                "-c",
+                "user.name=Test",
+                "-c",
+                "user.email=test@example.com",
+                "commit",
+                "-q",
+                "--allow-empty",
+                "-m",
+                "init",
+            ],
+        )
+        .await
+        .unwrap();
+        git(
+            root,
+            &[
+                "worktree",
+                "add",
+                "-q",
+                "-b",
+                "session-1",
+                &worktree.to_string_lossy(),
+            ],
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_relocate_session_after_moving_checkout() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let base = fs::canonicalize(temp.path()).await.unwrap();
+        let old_root = base.join("old");
+        let new_root = base.join("new");
+        let worktree = base.join("session-1");
+        let checkpoints = base.join("checkpoints");
+        fs::create_dir_all(&old_root).await.unwrap();
+        fs::create_dir_all(&checkpoints).await.unwrap();
+
+        init_repo_with_worktree(&old_root, &worktree).await;
+
+        let repo = RepoIdentity::detect(&worktree).await.unwrap();
+        assert_eq!(repo.root, old_root);
+        let checkpoint = serde_json::json!({
+            "workflow_path": old_root.join("flow.yml"),
+            "repo": repo.with_workflow(Some(&old_root.join("flow.yml"))),
+        });
+        let checkpoint_file = checkpoints.join("workflow-1.checkpoint.json");
+        fs::write(&checkpoint_file, checkpoint.to_string())
+            .await
+            .unwrap();
+
+        fs::rename(&old_root, &new_root).await.unwrap();
+        let relocation = relocate_session(&worktree, &checkpoints, &new_root)
+            .await
+            .unwrap();
+
+        assert_eq!(relocation.old_root, old_root);
+        assert_eq!(relocation.files, 1);
+        let rewritten: Value =
+            serde_json::from_str(&fs::read_to_string(&checkpoint_file).await.unwrap()).unwrap();
+        assert_eq!(
+            rewritten["workflow_path"],
+            new_root.join("flow.yml").to_string_lossy().as_ref()
+        );
+        assert_eq!(
+            RepoIdentity::detect(&worktree).await.unwrap().root,
+            new_root
+        );
+    }
+
+    #[tokio::test]
+    async fn test_relocate_job_after_moving_checkout() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let base = fs::canonicalize(temp.path()).await.unwrap();
+        let old_root = base.join("old");
+        let new_root = base.join("new");
+        let worktree = base.join("session-1");
+        let job_dir = base.join("jobs").join("mapreduce-1");
+        fs::create_dir_all(&old_root).await.unwrap();
+        fs::create_dir_all(job_dir.join("agents")).await.unwrap();
+        init_repo_with_worktree(&old_root, &worktree).await;
+
+        let checkpoint_file = job_dir.join("checkpoint-v0.json");
+        let checkpoint = serde_json::json!({
+            "parent_worktree": worktree,
+            "config": {"input": old_root.join("items.json")},
+        });
+        fs::write(&checkpoint_file, checkpoint.to_string())
+            .await
+            .unwrap();
+        let agent_file = job_dir.join("agents").join("agent-0.json");
+        let agent = serde_json::json!({"output": old_root.join("out.txt")});
+        fs::write(&agent_file, agent.to_string()).await.unwrap();
+
+        fs::rename(&old_root, &new_root).await.unwrap();
+        let relocation = relocate_job(&worktree, &job_dir, &new_root).await.unwrap();
+
+        assert_eq!(relocation.old_root, old_root);
+        assert_eq!(relocation.files, 2);
+        let rewritten: Value =
+            serde_json::from_str(&fs::read_to_string(&checkpoint_file).await.unwrap()).unwrap();
+        assert_eq!(
+            rewritten["config"]["input"],
+            new_root.join("items.json").to_string_lossy().as_ref()
+        );
+        assert_eq!(
+            rewritten["parent_worktree"],
+            worktree.to_string_lossy().as_ref()
+        );
+        assert_eq!(
+            RepoIdentity::detect(&worktree).await.unwrap().root,
+            new_root
+        );
+    }
+
+    #[test]
+    fn test_same_remote() {
+        assert!(same_remote(
+            "https://example.com/org/repo.git",
+            "https://example.com/org/repo/"
+        ));
+        assert!(!same_remote(
+            "https://example.com/org/repo",
+            "https://example.com/org/fork"
+        ));
+    }
+}
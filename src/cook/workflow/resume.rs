@@ -19,6 +19,7 @@ use crate::cook::workflow::executor::{
 };
 use crate::cook::workflow::normalized::NormalizedWorkflow;
 use crate::cook::workflow::progress::{ExecutionPhase, ProgressDisplay, SequentialProgressTracker};
+use crate::styled_println;
 use anyhow::{anyhow, Context, Result};
 use serde_json::Value;
 use std::collections::HashMap;
@@ -435,6 +436,9 @@ impl ResumeExecutor {
                 let mut step = WorkflowStep {
                     name: None,
                     claude: None,
+                    model: None,
+                    context: None,
+                    validate_output: None,
                     shell: None,
                     test: None,
                     foreach: None,
@@ -462,6 +466,13 @@ impl ResumeExecutor {
                     validation_timeout: None,
                     ignore_validation_failure: false,
                     when: None,
+                    assert: None,
+                    summarize_commits: None,
+                    claude_chat: None,
+                    script: None,
+                    snapshot: false,
+                    required: false,
+                    commit_policy: None,
                 };
 
                 // Parse command based on enum variant
@@ -483,19 +494,26 @@ impl ResumeExecutor {
                     }
                     crate::config::WorkflowCommand::WorkflowStep(wf_step) => {
                         step.claude = wf_step.claude;
+                        step.model = wf_step.model;
+                        step.context = wf_step.context;
+                        step.validate_output = wf_step.validate_output;
                         step.shell = wf_step.shell;
                         // Convert TestDebugConfig to OnFailureConfig
                         if let Some(test_debug) = wf_step.on_failure {
                             // Create a HandlerCommand from the TestDebugConfig
-                            let handler_cmd = crate::cook::workflow::on_failure::HandlerCommand {
-                                claude: Some(test_debug.claude),
-                                shell: None,
-                                continue_on_error: false,
-                            };
+                            let handler_cmds: Vec<_> = test_debug
+                                .debug_command()
+                                .map(|claude| crate::cook::workflow::on_failure::HandlerCommand {
+                                    claude: Some(claude),
+                                    shell: None,
+                                    continue_on_error: false,
+                                })
+                                .into_iter()
+                                .collect();
 
                             step.on_failure = Some(crate::cook::workflow::on_failure::OnFailureConfig::Detailed(
                                 crate::cook::workflow::on_failure::FailureHandlerConfig {
-                                    commands: vec![handler_cmd],
+                                    commands: handler_cmds,
                                     strategy: crate::cook::workflow::on_failure::HandlerStrategy::default(),
                                     timeout: None,
                                     capture: std::collections::HashMap::new(),
@@ -536,6 +554,8 @@ impl ResumeExecutor {
             reduce_phase: None,   // Not a MapReduce workflow
             retry_defaults: None, // Would need to be loaded from checkpoint
             environment: None,    // Would need to be loaded from checkpoint
+            always_steps: Vec::new(),
+            strict_variables: false,
         }
     }
 
@@ -594,6 +614,7 @@ impl ResumeExecutor {
             ),
             worktree_name: None,
             session_id: Arc::from(format!("resume-{}", workflow_id)),
+            package: None,
         }
     }
 
@@ -629,7 +650,7 @@ impl ResumeExecutor {
         start_time: std::time::Instant,
     ) {
         let total_duration = start_time.elapsed();
-        println!("\n✅ Workflow Resume Complete!");
+        styled_println!("\n✅ Workflow Resume Complete!");
         println!("   Total steps: {}", total_steps);
         println!("   Steps skipped (already completed): {}", skipped_steps);
         println!("   Steps executed in this session: {}", steps_executed);
@@ -891,7 +912,7 @@ impl ResumeExecutor {
         // Set progress callback to display updates
         progress_tracker.set_callback(move |update| {
             if let Some(ref msg) = update.message {
-                println!("📊 {}", msg);
+                styled_println!("📊 {}", msg);
             }
         });
 
@@ -910,6 +931,18 @@ impl ResumeExecutor {
                 continue;
             }
 
+            if checkpoint
+                .step_selection
+                .as_ref()
+                .is_some_and(|selection| !selection.includes(step_index))
+            {
+                info!("Skipping step {} (not selected)", step_index + 1);
+                progress_tracker
+                    .skip_step(step_index, "Not selected".to_string())
+                    .await;
+                continue;
+            }
+
             // Execute the step with progress tracking
             match Self::execute_single_step(
                 executor,
@@ -1069,12 +1102,16 @@ impl ResumeExecutor {
         progress_display.force_update("Loading workflow file and restoring state...");
 
         let workflow_config = Self::load_workflow_file(workflow_path).await?;
+        let strict_variables = workflow_config.strict_variables_enabled();
         let steps = Self::convert_commands_to_steps(workflow_config.commands);
-        let extended_workflow = Self::build_extended_workflow(&checkpoint, steps);
+        let mut extended_workflow = Self::build_extended_workflow(&checkpoint, steps);
+        extended_workflow.strict_variables = strict_variables;
+        extended_workflow.always_steps = Self::convert_commands_to_steps(workflow_config.always);
         let env = Self::build_execution_environment(workflow_path, workflow_id);
 
         // Restore workflow context
         let mut workflow_context = self.restore_workflow_context(&checkpoint)?;
+        workflow_context.strict_variables = extended_workflow.strict_variables;
 
         // Create workflow executor with checkpoint support
         let mut executor = WorkflowExecutorImpl::new(
@@ -1083,7 +1120,9 @@ impl ResumeExecutor {
             user_interaction.clone(),
         )
         .with_workflow_path(workflow_path.clone())
-        .with_checkpoint_manager(self.checkpoint_manager.clone(), workflow_id.to_string());
+        .with_checkpoint_manager(self.checkpoint_manager.clone(), workflow_id.to_string())
+        .with_failure_chains(checkpoint.failure_chains.clone())
+        .with_step_selection(checkpoint.step_selection.clone().unwrap_or_default());
 
         // Execute remaining steps
         let start_from = checkpoint.execution_state.current_step_index;
@@ -1100,6 +1139,20 @@ impl ResumeExecutor {
                 &checkpoint,
                 workflow_id,
             )
+            .await;
+
+        // Cleanup steps run on resume too, since a crashed run never reached them
+        let (steps_result, steps_executed) = match steps_executed {
+            Ok(count) => (Ok(()), count),
+            Err(e) => (Err(e), 0),
+        };
+        executor
+            .execute_always_steps(
+                &extended_workflow.always_steps,
+                &env,
+                &mut workflow_context,
+                steps_result,
+            )
             .await?;
 
         // Update progress to completed
@@ -1234,6 +1287,9 @@ fn build_cleanup_step(action: &crate::cook::workflow::on_failure::HandlerCommand
         name: Some("cleanup".to_string()),
         shell: action.shell.clone(),
         claude: action.claude.clone(),
+        model: None,
+        context: None,
+        validate_output: None,
         test: None,
         foreach: None,
         write_file: None,
@@ -1260,6 +1316,13 @@ fn build_cleanup_step(action: &crate::cook::workflow::on_failure::HandlerCommand
         validation_timeout: None,
         ignore_validation_failure: false,
         when: None,
+        assert: None,
+        summarize_commits: None,
+        claude_chat: None,
+        script: None,
+        snapshot: false,
+        required: false,
+        commit_policy: None,
     }
 }
 
@@ -1305,6 +1368,9 @@ mod tests {
             workflow_name: Some("test".to_string()),
             error_recovery_state: None,
             retry_checkpoint_state: None,
+            failure_chains: Vec::new(),
+            repo: None,
+            step_selection: None,
         };
 
         // Save checkpoint
@@ -1361,6 +1427,9 @@ mod tests {
             workflow_name: Some("test".to_string()),
             error_recovery_state: None,
             retry_checkpoint_state: None,
+            failure_chains: Vec::new(),
+            repo: None,
+            step_selection: None,
         };
 
         // Save checkpoint
@@ -1418,7 +1487,10 @@ mod tests {
             total_steps: 1,
             workflow_name: Some("test".to_string()),
             error_recovery_state: None,
+            repo: None,
+            step_selection: None,
             retry_checkpoint_state: None,
+            failure_chains: Vec::new(),
         };
 
         // Save checkpoint
@@ -0,0 +1,290 @@
+//! Health score gate between iterations
+//!
+//! An iterating workflow can "improve" a codebase into a worse state, one
+//! plausible change at a time. A `score_gate` measures the project's health
+//! with a command before the first iteration and after each one, and stops
+//! the run when the score falls too far below the best score seen so far:
+//!
+//! ```yaml
+//! score_gate:
+//!   command: "scripts/health-score.sh"  # prints a number, e.g. 87.5
+//!   max_drop: 2.0                       # tolerated drop from the best score
+//!   on_drop: fail                       # or warn
+//! ```
+//!
+//! The last number printed by the command is the score; higher is better
+//! unless `lower_is_better` is set (for example when counting warnings).
+//! With `on_drop: warn` the run continues and the drop is only reported.
+//! Scores are stored in the session metadata either way.
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use tokio::process::Command;
+
+/// Session metadata key holding the measured scores
+pub const SCORES_METADATA_KEY: &str = "iteration_scores";
+
+/// What to do when the score drops beyond `max_drop`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScoreDropAction {
+    /// Stop the run with an error
+    #[default]
+    Fail,
+    /// Report the drop and keep iterating
+    Warn,
+}
+
+/// Health score check run between iterations
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScoreGate {
+    /// Shell command printing the score
+    pub command: String,
+
+    /// Largest tolerated drop below the best score so far
+    #[serde(default)]
+    pub max_drop: f64,
+
+    /// What to do when the score drops further
+    #[serde(default)]
+    pub on_drop: ScoreDropAction,
+
+    /// Treat a lower score as healthier
+    #[serde(default)]
+    pub lower_is_better: bool,
+}
+
+/// Score measured before the first iteration (iteration 0) or after one
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IterationScore {
+    pub iteration: u32,
+    pub score: f64,
+    /// Whether the score dropped beyond the gate's `max_drop`
+    #[serde(default)]
+    pub dropped: bool,
+}
+
+impl ScoreGate {
+    /// Check that the gate can be applied
+    pub fn validate(&self) -> Result<()> {
+        if self.command.trim().is_empty() {
+            bail!("score_gate.command cannot be empty");
+        }
+        if !self.max_drop.is_finite() || self.max_drop < 0.0 {
+            bail!("score_gate.max_drop must be a non-negative number");
+        }
+        Ok(())
+    }
+
+    /// How far `score` is below `best`, in the gate's direction (pure function)
+    pub fn drop_from(&self, best: f64, score: f64) -> f64 {
+        if self.lower_is_better {
+            score - best
+        } else {
+            best - score
+        }
+    }
+
+    /// The healthier of two scores (pure function)
+    pub fn better(&self, a: f64, b: f64) -> f64 {
+        if self.lower_is_better {
+            a.min(b)
+        } else {
+            a.max(b)
+        }
+    }
+
+    /// Run the score command in `working_dir` and parse its output
+    pub async fn measure(&self, working_dir: &Path, env: &HashMap<String, String>) -> Result<f64> {
+        let output = Command::new("sh")
+            .arg("-c")
+            .arg(&self.command)
+            .current_dir(working_dir)
+            .envs(env)
+            .output()
+            .await
+            .with_context(|| format!("Failed to run score command: {}", self.command))?;
+        if !output.status.success() {
+            bail!(
+                "Score command failed ({}): {}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        parse_score(&stdout)
+            .ok_or_else(|| anyhow!("Score command printed no number: {}", stdout.trim()))
+    }
+}
+
+/// Tracks the scores of a run against its gate
+#[derive(Debug)]
+pub struct ScoreTracker {
+    gate: Arc<ScoreGate>,
+    best: Option<f64>,
+    scores: Vec<IterationScore>,
+}
+
+impl ScoreTracker {
+    pub fn new(gate: Arc<ScoreGate>) -> Self {
+        Self {
+            gate,
+            best: None,
+            scores: Vec::new(),
+        }
+    }
+
+    pub fn gate(&self) -> &ScoreGate {
+        &self.gate
+    }
+
+    /// Record the score after `iteration`, returning the drop when it exceeds `max_drop`
+    pub fn record(&mut self, iteration: u32, score: f64) -> Option<ScoreDrop> {
+        let exceeded = self.best.and_then(|best| {
+            let drop = self.gate.drop_from(best, score);
+            (drop > self.gate.max_drop).then_some(ScoreDrop {
+                iteration,
+                best,
+                score,
+                max_drop: self.gate.max_drop,
+            })
+        });
+        self.best = Some(match self.best {
+            Some(best) => self.gate.better(best, score),
+            None => score,
+        });
+        self.scores.push(IterationScore {
+            iteration,
+            score,
+            dropped: exceeded.is_some(),
+        });
+        exceeded
+    }
+
+    /// Scores recorded so far
+    pub fn scores(&self) -> &[IterationScore] {
+        &self.scores
+    }
+}
+
+/// A score that fell too far below the best score so far
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScoreDrop {
+    pub iteration: u32,
+    pub best: f64,
+    pub score: f64,
+    pub max_drop: f64,
+}
+
+impl std::fmt::Display for ScoreDrop {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Health score dropped to {} after iteration {} (best so far {}, max drop {})",
+            self.score, self.iteration, self.best, self.max_drop
+        )
+    }
+}
+
+/// Last number in the score command's output (pure function)
+pub fn parse_score(output: &str) -> Option<f64> {
+    output
+        .split_whitespace()
+        .rev()
+        .find_map(|token| token.parse::<f64>().ok().filter(|score| score.is_finite()))
+}
+
+/// Scores as a session metadata value
+pub fn to_metadata_value(scores: &[IterationScore]) -> serde_json::Value {
+    serde_json::to_value(scores).unwrap_or_default()
+}
+
+/// Scores recorded in session metadata, if any
+pub fn from_metadata(metadata: &HashMap<String, serde_json::Value>) -> Option<Vec<IterationScore>> {
+    metadata
+        .get(SCORES_METADATA_KEY)
+        .and_then(|value| serde_json::from_value(value.clone()).ok())
+}
+
+/// One line per measured score (pure function)
+pub fn format_scores(scores: &[IterationScore]) -> Vec<String> {
+    scores
+        .iter()
+        .map(|entry| {
+            let label = if entry.iteration == 0 {
+                "baseline".to_string()
+            } else {
+                format!("iteration {}", entry.iteration)
+            };
+            let flag = if entry.dropped { "  (dropped)" } else { "" };
+            format!("{}: {}{}", label, entry.score, flag)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gate(max_drop: f64, lower_is_better: bool) -> Arc<ScoreGate> {
+        Arc::new(ScoreGate {
+            command: "echo 1".to_string(),
+            max_drop,
+            on_drop: ScoreDropAction::Fail,
+            lower_is_better,
+        })
+    }
+
+    #[test]
+    fn test_parse_score_takes_last_number() {
+        assert_eq!(parse_score("coverage 81.5%\nscore: 87.25\n"), Some(87.25));
+        assert_eq!(parse_score("42"), Some(42.0));
+        assert_eq!(parse_score("no score here"), None);
+    }
+
+    #[test]
+    fn test_tracker_compares_against_best_score() {
+        let mut tracker = ScoreTracker::new(gate(2.0, false));
+        assert_eq!(tracker.record(0, 80.0), None);
+        assert_eq!(tracker.record(1, 85.0), None);
+        // Small drops add up against the best score, not the previous one
+        assert_eq!(tracker.record(2, 83.5), None);
+        let drop = tracker.record(3, 82.0).unwrap();
+        assert_eq!(drop.best, 85.0);
+        assert_eq!(drop.iteration, 3);
+        assert!(tracker.scores()[3].dropped);
+    }
+
+    #[test]
+    fn test_tracker_lower_is_better() {
+        let mut tracker = ScoreTracker::new(gate(0.0, true));
+        assert_eq!(tracker.record(0, 12.0), None);
+        assert_eq!(tracker.record(1, 9.0), None);
+        assert!(tracker.record(2, 10.0).is_some());
+    }
+
+    #[test]
+    fn test_validate_rejects_negative_max_drop() {
+        let mut invalid = (*gate(1.0, false)).clone();
+        invalid.max_drop = -1.0;
+        assert!(invalid.validate().is_err());
+        assert!(gate(0.0, false).validate().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_measure_runs_command() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let gate = ScoreGate {
+            command: "echo \"score $SCORE\"".to_string(),
+            max_drop: 0.0,
+            on_drop: ScoreDropAction::Warn,
+            lower_is_better: false,
+        };
+        let env = HashMap::from([("SCORE".to_string(), "7.5".to_string())]);
+        assert_eq!(gate.measure(dir.path(), &env).await.unwrap(), 7.5);
+    }
+}
@@ -0,0 +1,138 @@
+//! `script:` steps
+//!
+//! A script step runs a multi-line program with the selected interpreter,
+//! which reads much better than a one-line `shell:` command for munging data
+//! between steps:
+//!
+//! ```yaml
+//! - script:
+//!     lang: python
+//!     code: |
+//!       import json
+//!       results = json.load(open("${results_file}"))
+//!       print(sum(1 for r in results if r["status"] == "failed"), end="")
+//!   capture_output: failed_count
+//! ```
+//!
+//! Workflow variables are interpolated into `code` like in a shell command.
+//! The code is written to a temporary file that is removed after the step,
+//! and the interpreter runs it from the working directory with the step's
+//! environment and timeout. Output is captured like a shell step's.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io::Write;
+use std::path::Path;
+use tempfile::NamedTempFile;
+
+/// Language of a script step
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScriptLang {
+    Python,
+    Bash,
+    Node,
+}
+
+impl ScriptLang {
+    /// Interpreter executable run with the script file
+    pub fn interpreter(self) -> &'static str {
+        match self {
+            ScriptLang::Python => "python3",
+            ScriptLang::Bash => "bash",
+            ScriptLang::Node => "node",
+        }
+    }
+
+    /// File extension of the temporary script file
+    pub fn extension(self) -> &'static str {
+        match self {
+            ScriptLang::Python => ".py",
+            ScriptLang::Bash => ".sh",
+            ScriptLang::Node => ".js",
+        }
+    }
+}
+
+impl fmt::Display for ScriptLang {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ScriptLang::Python => "python",
+            ScriptLang::Bash => "bash",
+            ScriptLang::Node => "node",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Configuration of a `script:` step
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScriptConfig {
+    /// Interpreter to run the code with
+    pub lang: ScriptLang,
+
+    /// Source code of the script
+    pub code: String,
+}
+
+/// Shell command running the script at `path` (pure function)
+pub fn interpreter_command(lang: ScriptLang, path: &Path) -> String {
+    format!(
+        "{} {}",
+        lang.interpreter(),
+        shell_words::quote(&path.to_string_lossy())
+    )
+}
+
+/// Key a script's output is recorded and replayed under (pure function)
+///
+/// The temporary file name differs between runs, so recordings are keyed
+/// by the language and the interpolated code instead.
+pub fn fixture_key(lang: ScriptLang, code: &str) -> String {
+    format!("script ({}):\n{}", lang, code)
+}
+
+/// Write `code` to a temporary file, removed when the returned handle drops
+pub fn write_script(lang: ScriptLang, code: &str) -> Result<NamedTempFile> {
+    let mut file = tempfile::Builder::new()
+        .prefix("prodigy-script-")
+        .suffix(lang.extension())
+        .tempfile()
+        .context("Failed to create temporary script file")?;
+    file.write_all(code.as_bytes())
+        .and_then(|_| file.flush())
+        .context("Failed to write temporary script file")?;
+    Ok(file)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_script_config() {
+        let yaml = "lang: python\ncode: |\n  print('hi')\n";
+        let config: ScriptConfig = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(config.lang, ScriptLang::Python);
+        assert_eq!(config.code, "print('hi')\n");
+
+        assert!(serde_yaml::from_str::<ScriptConfig>("lang: ruby\ncode: x").is_err());
+    }
+
+    #[test]
+    fn test_interpreter_command_quotes_path() {
+        assert_eq!(
+            interpreter_command(ScriptLang::Node, Path::new("/tmp/my script.js")),
+            "node '/tmp/my script.js'"
+        );
+    }
+
+    #[test]
+    fn test_write_script_uses_language_extension() {
+        let file = write_script(ScriptLang::Bash, "echo hi\n").unwrap();
+        assert!(file.path().to_string_lossy().ends_with(".sh"));
+        assert_eq!(std::fs::read_to_string(file.path()).unwrap(), "echo hi\n");
+    }
+}
@@ -0,0 +1,398 @@
+//! Repository snapshots taken before risky steps
+//!
+//! A step with `snapshot: true` records the state of its working tree before
+//! it runs: the HEAD commit and a commit of uncommitted and untracked files
+//! on top of it. That commit is built through a temporary index, so the
+//! worktree, its index and the stash list shared by all worktrees of the
+//! repository are left alone. It (or HEAD when the tree is clean) is pinned under
+//! `refs/prodigy/snapshots/<session>/step-<n>` so it survives garbage
+//! collection, and the snapshot is appended to a JSON Lines file next to the
+//! session's workflow checkpoints.
+//!
+//! `prodigy rollback --to-step <n>` restores the latest snapshot of step `n`:
+//! the worktree is reset to the recorded HEAD, files created since are
+//! removed and the recorded changes are checked out again, unstaged. Ignored
+//! files are left alone.
+
+use super::checkpoint_path::CheckpointStorage;
+use crate::abstractions::git::GitOperations;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tokio::io::AsyncWriteExt;
+
+const SNAPSHOTS_FILE: &str = "snapshots.jsonl";
+
+/// Working tree state recorded before a step ran
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Snapshot {
+    /// Step number, counted from 1 as in progress output
+    pub step: usize,
+    pub step_name: String,
+    pub working_dir: PathBuf,
+    /// Commit checked out before the step
+    pub head: String,
+    /// Commit of uncommitted and untracked files on top of `head`, if there
+    /// were any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub working_tree: Option<String>,
+    /// Ref keeping the snapshot's commits reachable
+    pub git_ref: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Location of the snapshots file for a session
+pub fn snapshots_file_path(session_id: &str) -> Result<PathBuf> {
+    let storage = CheckpointStorage::Session {
+        session_id: session_id.to_string(),
+    };
+    Ok(storage.resolve_base_dir()?.join(SNAPSHOTS_FILE))
+}
+
+/// Ref pinning the snapshot of a session's step (pure function)
+pub fn snapshot_ref(session_id: &str, step: usize) -> String {
+    format!("refs/prodigy/snapshots/{}/step-{}", session_id, step)
+}
+
+async fn git(
+    git: &dyn GitOperations,
+    args: &[&str],
+    description: &str,
+    working_dir: &Path,
+) -> Result<String> {
+    let output = git
+        .git_command_in_dir(args, description, working_dir)
+        .await?;
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Write the uncommitted and untracked files of `working_dir` to a commit on
+/// top of `head`
+///
+/// `git add` runs against a copy of the worktree's index, so the real index
+/// keeps what the user staged and unchanged files are not hashed again.
+async fn commit_working_tree(
+    git_ops: &dyn GitOperations,
+    working_dir: &Path,
+    head: &str,
+    step: usize,
+) -> Result<String> {
+    let index_dir = tempfile::tempdir().context("Failed to create snapshot index directory")?;
+    let index_file = index_dir.path().join("index");
+    let repo_index = working_dir.join(
+        git(
+            git_ops,
+            &["rev-parse", "--git-path", "index"],
+            "locate index",
+            working_dir,
+        )
+        .await?,
+    );
+    if repo_index.exists() {
+        tokio::fs::copy(&repo_index, &index_file)
+            .await
+            .with_context(|| format!("Failed to copy {}", repo_index.display()))?;
+    }
+
+    git_ops
+        .git_command_with_index(
+            &["add", "--all"],
+            "stage snapshot",
+            working_dir,
+            &index_file,
+        )
+        .await?;
+    let output = git_ops
+        .git_command_with_index(
+            &["write-tree"],
+            "write snapshot tree",
+            working_dir,
+            &index_file,
+        )
+        .await?;
+    let tree = String::from_utf8_lossy(&output.stdout).trim().to_string();
+
+    let message = format!("prodigy snapshot before step {}", step);
+    git(
+        git_ops,
+        &["commit-tree", &tree, "-p", head, "-m", &message],
+        "commit snapshot",
+        working_dir,
+    )
+    .await
+}
+
+/// Record the working tree of `working_dir` before step `step` runs
+///
+/// The worktree, its index and the stash list are not modified.
+pub async fn take_snapshot(
+    git_ops: &dyn GitOperations,
+    working_dir: &Path,
+    session_id: &str,
+    step: usize,
+    step_name: &str,
+) -> Result<Snapshot> {
+    let head = git(git_ops, &["rev-parse", "HEAD"], "get HEAD", working_dir).await?;
+    let dirty = !git(
+        git_ops,
+        &["status", "--porcelain"],
+        "check status",
+        working_dir,
+    )
+    .await?
+    .is_empty();
+
+    let working_tree = if dirty {
+        Some(commit_working_tree(git_ops, working_dir, &head, step).await?)
+    } else {
+        None
+    };
+
+    let git_ref = snapshot_ref(session_id, step);
+    let target = working_tree.as_deref().unwrap_or(&head);
+    git(
+        git_ops,
+        &["update-ref", &git_ref, target],
+        "pin snapshot",
+        working_dir,
+    )
+    .await?;
+
+    Ok(Snapshot {
+        step,
+        step_name: step_name.to_string(),
+        working_dir: working_dir.to_path_buf(),
+        head,
+        working_tree,
+        git_ref,
+        created_at: Utc::now(),
+    })
+}
+
+/// Restore the working tree a snapshot recorded
+pub async fn restore_snapshot(git_ops: &dyn GitOperations, snapshot: &Snapshot) -> Result<()> {
+    let dir = snapshot.working_dir.as_path();
+    git(
+        git_ops,
+        &["reset", "--hard", &snapshot.head],
+        "reset to snapshot",
+        dir,
+    )
+    .await?;
+    git(git_ops, &["clean", "-fd"], "remove new files", dir).await?;
+    if let Some(working_tree) = &snapshot.working_tree {
+        // Move the clean tree from `head` to the recorded changes, then
+        // unstage them so HEAD and the index match the snapshot's commit
+        git(
+            git_ops,
+            &["read-tree", "-u", "-m", &snapshot.head, working_tree],
+            "check out snapshot changes",
+            dir,
+        )
+        .await?;
+        git(git_ops, &["reset", "-q"], "unstage snapshot changes", dir).await?;
+    }
+    Ok(())
+}
+
+/// Append a snapshot to the snapshots file
+pub async fn append_snapshot(path: &Path, snapshot: &Snapshot) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+
+    let mut line = serde_json::to_string(snapshot)?;
+    line.push('\n');
+
+    let mut file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .await
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    file.write_all(line.as_bytes()).await?;
+    file.flush().await?;
+    Ok(())
+}
+
+/// Load all snapshots in the order they were taken
+///
+/// A missing file means no snapshots; unreadable lines are skipped.
+pub async fn load_snapshots(path: &Path) -> Result<Vec<Snapshot>> {
+    let content = match tokio::fs::read_to_string(path).await {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => {
+            return Err(e).with_context(|| format!("Failed to read {}", path.display()));
+        }
+    };
+
+    Ok(content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| match serde_json::from_str(line) {
+            Ok(snapshot) => Some(snapshot),
+            Err(e) => {
+                tracing::warn!("Skipping malformed snapshot: {}", e);
+                None
+            }
+        })
+        .collect())
+}
+
+/// Latest snapshot taken before step `step` (pure function)
+pub fn find_snapshot(snapshots: &[Snapshot], step: usize) -> Option<&Snapshot> {
+    snapshots
+        .iter()
+        .rev()
+        .find(|snapshot| snapshot.step == step)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::abstractions::git::RealGitOperations;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    fn init_repo(dir: &Path) {
+        for args in [
+            vec!["init", "-q"],
+            vec!["config", "user.email", "test@example.com"],
+            vec!["config", "user.name", "Test"],
+        ] {
+            Command::new("git")
+                .args(&args)
+                .current_dir(dir)
+                .output()
+                .unwrap();
+        }
+        std::fs::write(dir.join("tracked.txt"), "original\n").unwrap();
+        Command::new("git")
+            .args(["add", "."])
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        Command::new("git")
+            .args(["commit", "-q", "-m", "initial"])
+            .current_dir(dir)
+            .output()
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_and_restore_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        init_repo(dir);
+        let git_ops = RealGitOperations::new();
+
+        std::fs::write(dir.join("tracked.txt"), "edited\n").unwrap();
+        std::fs::write(dir.join("notes.txt"), "untracked\n").unwrap();
+
+        let snapshot = take_snapshot(&git_ops, dir, "session-1", 2, "claude: /refactor")
+            .await
+            .unwrap();
+        assert!(snapshot.working_tree.is_some());
+        assert_eq!(
+            std::fs::read_to_string(dir.join("tracked.txt")).unwrap(),
+            "edited\n"
+        );
+        assert!(dir.join("notes.txt").exists());
+
+        // A destructive step: commit, delete and create files
+        std::fs::remove_file(dir.join("notes.txt")).unwrap();
+        std::fs::write(dir.join("tracked.txt"), "broken\n").unwrap();
+        std::fs::write(dir.join("junk.txt"), "junk\n").unwrap();
+        Command::new("git")
+            .args(["commit", "-q", "-am", "destroy"])
+            .current_dir(dir)
+            .output()
+            .unwrap();
+
+        restore_snapshot(&git_ops, &snapshot).await.unwrap();
+
+        let head = Command::new("git")
+            .args(["rev-parse", "HEAD"])
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert_eq!(String::from_utf8_lossy(&head.stdout).trim(), snapshot.head);
+        assert_eq!(
+            std::fs::read_to_string(dir.join("tracked.txt")).unwrap(),
+            "edited\n"
+        );
+        assert!(dir.join("notes.txt").exists());
+        assert!(!dir.join("junk.txt").exists());
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_leaves_stash_and_index_alone() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        init_repo(dir);
+        let git_ops = RealGitOperations::new();
+        let git_output = |args: &[&str]| {
+            let output = Command::new("git")
+                .args(args)
+                .current_dir(dir)
+                .output()
+                .unwrap();
+            String::from_utf8_lossy(&output.stdout).trim().to_string()
+        };
+
+        std::fs::write(dir.join("tracked.txt"), "stashed\n").unwrap();
+        git_output(&["stash", "push", "-q", "-m", "user work"]);
+        let stash_list = git_output(&["stash", "list"]);
+
+        std::fs::write(dir.join("tracked.txt"), "staged\n").unwrap();
+        git_output(&["add", "tracked.txt"]);
+        std::fs::write(dir.join("notes.txt"), "untracked\n").unwrap();
+        let status = git_output(&["status", "--porcelain"]);
+
+        let snapshot = take_snapshot(&git_ops, dir, "session-1", 1, "shell: ./migrate.sh")
+            .await
+            .unwrap();
+
+        assert_eq!(git_output(&["stash", "list"]), stash_list);
+        assert_eq!(git_output(&["status", "--porcelain"]), status);
+        let working_tree = snapshot.working_tree.unwrap();
+        assert_eq!(
+            git_output(&["rev-parse", &format!("{}^", working_tree)]),
+            snapshot.head
+        );
+        assert_eq!(
+            git_output(&["show", &format!("{}:notes.txt", working_tree)]),
+            "untracked"
+        );
+        assert_eq!(git_output(&["rev-parse", &snapshot.git_ref]), working_tree);
+    }
+
+    #[tokio::test]
+    async fn test_append_and_find_latest_snapshot() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("checkpoints").join(SNAPSHOTS_FILE);
+        assert!(load_snapshots(&path).await.unwrap().is_empty());
+
+        for head in ["aaa", "bbb"] {
+            let snapshot = Snapshot {
+                step: 3,
+                step_name: "shell: ./migrate.sh".to_string(),
+                working_dir: temp_dir.path().to_path_buf(),
+                head: head.to_string(),
+                working_tree: None,
+                git_ref: snapshot_ref("session-1", 3),
+                created_at: Utc::now(),
+            };
+            append_snapshot(&path, &snapshot).await.unwrap();
+        }
+
+        let snapshots = load_snapshots(&path).await.unwrap();
+        assert_eq!(snapshots.len(), 2);
+        assert_eq!(find_snapshot(&snapshots, 3).unwrap().head, "bbb");
+        assert!(find_snapshot(&snapshots, 1).is_none());
+    }
+}
@@ -0,0 +1,115 @@
+//! Per-step change audit
+//!
+//! After every step the executor records the git changes it made (files
+//! added, modified and deleted, line counts and commits) with the step's
+//! checkpoint record. The same audit is stored in the session metadata so
+//! `prodigy sessions show` can display what each step changed after the
+//! checkpoint is gone.
+
+use crate::cook::workflow::checkpoint::CompletedStep;
+use crate::cook::workflow::git_context::StepChanges;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Session metadata key holding the recorded audit
+pub const STEP_AUDIT_METADATA_KEY: &str = "step_audit";
+
+/// Changes made by one executed step
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StepAudit {
+    pub step_index: usize,
+    pub step: String,
+    pub changes: StepChanges,
+}
+
+/// Audit entries of the checkpointed steps that recorded changes (pure function)
+pub fn from_checkpoint_steps(steps: &[CompletedStep]) -> Vec<StepAudit> {
+    steps
+        .iter()
+        .filter_map(|step| {
+            step.changes.as_ref().map(|changes| StepAudit {
+                step_index: step.step_index,
+                step: step.command.clone(),
+                changes: changes.clone(),
+            })
+        })
+        .collect()
+}
+
+pub fn to_metadata_value(steps: &[StepAudit]) -> serde_json::Value {
+    serde_json::to_value(steps).unwrap_or_default()
+}
+
+/// Audit recorded in session metadata, if any
+pub fn from_metadata(metadata: &HashMap<String, serde_json::Value>) -> Option<Vec<StepAudit>> {
+    metadata
+        .get(STEP_AUDIT_METADATA_KEY)
+        .and_then(|value| serde_json::from_value(value.clone()).ok())
+}
+
+/// One line per step with its diff stat, in execution order (pure function)
+pub fn format_audit(steps: &[StepAudit]) -> Vec<String> {
+    steps
+        .iter()
+        .map(|audit| {
+            format!(
+                "[{}] {}: {}",
+                audit.step_index,
+                audit.step,
+                audit.changes.diff_stat()
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use std::time::Duration;
+
+    fn completed(step_index: usize, changes: Option<StepChanges>) -> CompletedStep {
+        CompletedStep {
+            step_index,
+            command: format!("shell: step {}", step_index),
+            success: true,
+            output: None,
+            captured_variables: HashMap::new(),
+            duration: Duration::from_secs(1),
+            completed_at: Utc::now(),
+            retry_state: None,
+            changes,
+        }
+    }
+
+    #[test]
+    fn test_audit_round_trips_through_metadata() {
+        let changes = StepChanges {
+            files_modified: vec!["src/lib.rs".into()],
+            commits: vec!["abc123".into()],
+            insertions: 12,
+            deletions: 3,
+            ..Default::default()
+        };
+        let steps = from_checkpoint_steps(&[
+            completed(0, Some(changes)),
+            completed(1, None),
+            completed(2, Some(StepChanges::default())),
+        ]);
+        assert_eq!(steps.len(), 2);
+
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            STEP_AUDIT_METADATA_KEY.to_string(),
+            to_metadata_value(&steps),
+        );
+        let restored = from_metadata(&metadata).unwrap();
+        assert_eq!(
+            format_audit(&restored),
+            vec![
+                "[0] shell: step 0: 1 file changed (+0 ~1 -0), +12 -3 lines, 1 commit".to_string(),
+                "[2] shell: step 2: no changes".to_string(),
+            ]
+        );
+    }
+}
@@ -0,0 +1,140 @@
+//! Step outcomes of a `--keep-going` run
+//!
+//! `prodigy run --keep-going` reports a failing step as a warning and runs the
+//! remaining steps anyway. Each step's outcome is collected here and printed
+//! as a table once the steps finish. The run fails only if a step marked
+//! `required: true` failed.
+
+use crate::cook::execution::progress::format_duration;
+use anyhow::{anyhow, Result};
+use std::time::Duration;
+
+/// How one step of a `--keep-going` run ended
+#[derive(Debug, Clone, PartialEq)]
+pub struct StepOutcome {
+    /// Zero-based position of the step in the workflow
+    pub index: usize,
+    pub name: String,
+    pub required: bool,
+    pub duration: Duration,
+    /// First line of the error, if the step failed
+    pub error: Option<String>,
+}
+
+impl StepOutcome {
+    pub fn new(
+        index: usize,
+        name: String,
+        required: bool,
+        duration: Duration,
+        error: Option<&anyhow::Error>,
+    ) -> Self {
+        Self {
+            index,
+            name,
+            required,
+            duration,
+            error: error.map(|e| e.to_string().lines().next().unwrap_or_default().to_string()),
+        }
+    }
+
+    pub fn failed(&self) -> bool {
+        self.error.is_some()
+    }
+}
+
+/// Table with one row per step and the error of each failed step (pure function)
+pub fn format_table(outcomes: &[StepOutcome]) -> String {
+    let name_width = outcomes
+        .iter()
+        .map(|outcome| outcome.name.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("Step".len());
+    let mut table = format!(
+        "{:>4}  {:<name_width$}  {:<17}  {:>8}\n",
+        "#", "Step", "Result", "Time"
+    );
+    for outcome in outcomes {
+        let result = match (outcome.failed(), outcome.required) {
+            (false, _) => "ok",
+            (true, false) => "failed",
+            (true, true) => "failed (required)",
+        };
+        table.push_str(&format!(
+            "{:>4}  {:<name_width$}  {:<17}  {:>8}\n",
+            outcome.index + 1,
+            outcome.name,
+            result,
+            format_duration(outcome.duration),
+        ));
+        if let Some(error) = &outcome.error {
+            table.push_str(&format!("{:>4}  {}\n", "", error));
+        }
+    }
+    let failed = outcomes.iter().filter(|outcome| outcome.failed()).count();
+    table.push_str(&format!("{} of {} steps failed", failed, outcomes.len()));
+    table
+}
+
+/// Error naming the required steps that failed, if any (pure function)
+pub fn check_required(outcomes: &[StepOutcome]) -> Result<()> {
+    let failed: Vec<String> = outcomes
+        .iter()
+        .filter(|outcome| outcome.required && outcome.failed())
+        .map(|outcome| format!("step {} ({})", outcome.index + 1, outcome.name))
+        .collect();
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(anyhow!("Required steps failed: {}", failed.join(", ")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outcome(index: usize, name: &str, required: bool, error: Option<&str>) -> StepOutcome {
+        StepOutcome::new(
+            index,
+            name.to_string(),
+            required,
+            Duration::from_secs(3),
+            error.map(|e| anyhow!("{}\nmore detail", e)).as_ref(),
+        )
+    }
+
+    #[test]
+    fn test_only_required_failures_fail_the_run() {
+        let outcomes = vec![
+            outcome(0, "shell: cargo fmt --check", false, Some("exit code 1")),
+            outcome(1, "shell: cargo test", true, None),
+        ];
+        assert!(check_required(&outcomes).is_ok());
+
+        let outcomes = vec![
+            outcome(0, "shell: cargo fmt --check", false, Some("exit code 1")),
+            outcome(1, "shell: cargo test", true, Some("exit code 101")),
+        ];
+        let err = check_required(&outcomes).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Required steps failed: step 2 (shell: cargo test)"
+        );
+    }
+
+    #[test]
+    fn test_format_table() {
+        let table = format_table(&[
+            outcome(0, "shell: cargo fmt --check", false, Some("exit code 1")),
+            outcome(1, "shell: cargo test", true, None),
+        ]);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert!(lines[1].starts_with("   1  shell: cargo fmt --check  failed"));
+        assert_eq!(lines[2].trim(), "exit code 1");
+        assert!(lines[3].starts_with("   2  shell: cargo test         ok"));
+        assert_eq!(lines[4], "1 of 2 steps failed");
+    }
+}
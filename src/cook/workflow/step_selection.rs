@@ -0,0 +1,281 @@
+//! Step selection for partial workflow runs
+//!
+//! `prodigy run --only-steps 3,5-7 --skip-steps lint` runs a subset of a
+//! workflow's steps, e.g. to re-run the failing tail of a long workflow while
+//! developing it. Steps are named by their 1-based position, as shown in the
+//! progress output, or by their `id`. The selection is resolved to positions
+//! before the run starts and recorded in checkpoints, so a resumed run skips
+//! the same steps.
+
+use crate::config::command::WorkflowCommand;
+use anyhow::{anyhow, bail, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// A step, or an inclusive range of steps, named on the command line
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum StepRef {
+    /// 1-based positions `first..=last`
+    Range { first: usize, last: usize },
+    /// Step `id`
+    Id(String),
+}
+
+impl StepRef {
+    fn contains(&self, position: usize) -> bool {
+        matches!(self, StepRef::Range { first, last } if (*first..=*last).contains(&position))
+    }
+}
+
+impl FromStr for StepRef {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        if s.is_empty() {
+            bail!("Empty step in step list");
+        }
+        if !s.starts_with(|c: char| c.is_ascii_digit()) {
+            return Ok(StepRef::Id(s.to_string()));
+        }
+        let (first, last) = s.split_once('-').unwrap_or((s, s));
+        let parse = |n: &str| {
+            n.trim()
+                .parse::<usize>()
+                .ok()
+                .filter(|n| *n > 0)
+                .ok_or_else(|| anyhow!("Invalid step '{}': steps are numbered from 1", s))
+        };
+        let (first, last) = (parse(first)?, parse(last)?);
+        if first > last {
+            bail!("Invalid step range '{}': start is after end", s);
+        }
+        Ok(StepRef::Range { first, last })
+    }
+}
+
+impl fmt::Display for StepRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StepRef::Range { first, last } if first == last => write!(f, "{first}"),
+            StepRef::Range { first, last } => write!(f, "{first}-{last}"),
+            StepRef::Id(id) => f.write_str(id),
+        }
+    }
+}
+
+impl TryFrom<String> for StepRef {
+    type Error = anyhow::Error;
+
+    fn try_from(s: String) -> Result<Self> {
+        s.parse()
+    }
+}
+
+impl From<StepRef> for String {
+    fn from(step: StepRef) -> Self {
+        step.to_string()
+    }
+}
+
+/// Steps given to `--only-steps` and `--skip-steps`
+///
+/// An empty selection runs every step.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StepSelection {
+    /// Run only these steps (all steps if empty)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub only: Vec<StepRef>,
+    /// Never run these steps
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skip: Vec<StepRef>,
+}
+
+impl StepSelection {
+    /// Selection from comma-separated step lists such as `3,5-7` or `lint,test`
+    pub fn parse(only: Option<&str>, skip: Option<&str>) -> Result<Self> {
+        let parse_list = |list: Option<&str>| -> Result<Vec<StepRef>> {
+            list.map(|list| list.split(',').map(str::parse).collect())
+                .unwrap_or_else(|| Ok(Vec::new()))
+        };
+        Ok(Self {
+            only: parse_list(only)?,
+            skip: parse_list(skip)?,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.only.is_empty() && self.skip.is_empty()
+    }
+
+    /// Check the selection against a workflow and replace step IDs by positions
+    ///
+    /// `step_ids` holds the `id` of each step, in order. Fails if a position is
+    /// past the last step, an ID matches no step, or no step would run.
+    pub fn resolve(&self, step_ids: &[Option<String>]) -> Result<Self> {
+        let resolve_list = |refs: &[StepRef]| -> Result<Vec<StepRef>> {
+            let mut resolved = Vec::new();
+            for step in refs {
+                match step {
+                    StepRef::Range { last, .. } if *last > step_ids.len() => bail!(
+                        "Step {} is out of range: the workflow has {} step(s)",
+                        step,
+                        step_ids.len()
+                    ),
+                    StepRef::Range { .. } => resolved.push(step.clone()),
+                    StepRef::Id(id) => {
+                        let positions: Vec<usize> = step_ids
+                            .iter()
+                            .enumerate()
+                            .filter(|(_, step_id)| step_id.as_deref() == Some(id.as_str()))
+                            .map(|(index, _)| index + 1)
+                            .collect();
+                        if positions.is_empty() {
+                            bail!("No step has id '{}'{}", id, format_known_ids(step_ids));
+                        }
+                        resolved.extend(positions.into_iter().map(|position| StepRef::Range {
+                            first: position,
+                            last: position,
+                        }));
+                    }
+                }
+            }
+            Ok(resolved)
+        };
+
+        let resolved = Self {
+            only: resolve_list(&self.only)?,
+            skip: resolve_list(&self.skip)?,
+        };
+        if !self.is_empty() && (0..step_ids.len()).all(|index| !resolved.includes(index)) {
+            bail!("The step selection ({}) leaves no steps to run", self);
+        }
+        Ok(resolved)
+    }
+
+    /// Whether the step at zero-based `index` runs
+    ///
+    /// Only meaningful for a resolved selection; step IDs never match.
+    pub fn includes(&self, index: usize) -> bool {
+        let position = index + 1;
+        (self.only.is_empty() || self.only.iter().any(|step| step.contains(position)))
+            && !self.skip.iter().any(|step| step.contains(position))
+    }
+}
+
+impl fmt::Display for StepSelection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let join = |refs: &[StepRef]| {
+            refs.iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        match (self.only.is_empty(), self.skip.is_empty()) {
+            (true, true) => f.write_str("all steps"),
+            (false, true) => write!(f, "only steps {}", join(&self.only)),
+            (true, false) => write!(f, "skipping steps {}", join(&self.skip)),
+            (false, false) => write!(
+                f,
+                "only steps {}, skipping {}",
+                join(&self.only),
+                join(&self.skip)
+            ),
+        }
+    }
+}
+
+/// The `id` of each workflow command, in order
+pub fn step_ids(commands: &[WorkflowCommand]) -> Vec<Option<String>> {
+    commands
+        .iter()
+        .map(|command| match command {
+            WorkflowCommand::Structured(command) => command.id.clone(),
+            WorkflowCommand::WorkflowStep(step) => step.id.clone(),
+            WorkflowCommand::Simple(_) | WorkflowCommand::SimpleObject(_) => None,
+        })
+        .collect()
+}
+
+fn format_known_ids(step_ids: &[Option<String>]) -> String {
+    let known: Vec<&str> = step_ids.iter().flatten().map(String::as_str).collect();
+    if known.is_empty() {
+        " (no step has an id; use step numbers)".to_string()
+    } else {
+        format!(" (known ids: {})", known.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(ids: &[Option<&str>]) -> Vec<Option<String>> {
+        ids.iter().map(|id| id.map(str::to_string)).collect()
+    }
+
+    #[test]
+    fn test_parse_step_lists() {
+        let selection = StepSelection::parse(Some("3, 5-7,lint"), Some("2")).unwrap();
+        assert_eq!(
+            selection.only,
+            vec![
+                StepRef::Range { first: 3, last: 3 },
+                StepRef::Range { first: 5, last: 7 },
+                StepRef::Id("lint".to_string()),
+            ]
+        );
+        assert_eq!(selection.to_string(), "only steps 3,5-7,lint, skipping 2");
+
+        assert!(StepSelection::parse(Some("0"), None).is_err());
+        assert!(StepSelection::parse(Some("7-5"), None).is_err());
+        assert!(StepSelection::parse(Some("3,"), None).is_err());
+    }
+
+    #[test]
+    fn test_resolve_and_include() {
+        let step_ids = ids(&[None, Some("build"), None, Some("lint"), None]);
+        let selection = StepSelection::parse(Some("2-5"), Some("lint"))
+            .unwrap()
+            .resolve(&step_ids)
+            .unwrap();
+
+        let included: Vec<usize> = (0..5).filter(|i| selection.includes(*i)).collect();
+        assert_eq!(included, vec![1, 2, 4]);
+    }
+
+    #[test]
+    fn test_resolve_rejects_invalid_selections() {
+        let step_ids = ids(&[None, Some("build")]);
+
+        let out_of_range = StepSelection::parse(Some("3"), None).unwrap();
+        assert!(out_of_range
+            .resolve(&step_ids)
+            .unwrap_err()
+            .to_string()
+            .contains("out of range"));
+
+        let unknown = StepSelection::parse(None, Some("test")).unwrap();
+        assert!(unknown
+            .resolve(&step_ids)
+            .unwrap_err()
+            .to_string()
+            .contains("known ids: build"));
+
+        let nothing = StepSelection::parse(Some("1"), Some("1")).unwrap();
+        assert!(nothing.resolve(&step_ids).is_err());
+    }
+
+    #[test]
+    fn test_selection_round_trips_through_json() {
+        let selection = StepSelection::parse(Some("1,3-4"), None).unwrap();
+        let json = serde_json::to_value(&selection).unwrap();
+        assert_eq!(json, serde_json::json!({"only": ["1", "3-4"]}));
+        assert_eq!(
+            serde_json::from_value::<StepSelection>(json).unwrap(),
+            selection
+        );
+    }
+}
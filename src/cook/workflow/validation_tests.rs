@@ -239,6 +239,9 @@ mod tests {
         let step = WorkflowStep {
             name: None,
             claude: Some("/prodigy-implement-spec 01".to_string()),
+            model: None,
+            context: None,
+            validate_output: None,
             shell: None,
             test: None,
             command: None,
@@ -291,6 +294,9 @@ mod tests {
         let step = WorkflowStep {
             name: None,
             claude: Some("/test-command".to_string()),
+            model: None,
+            context: None,
+            validate_output: None,
             shell: None,
             test: None,
             command: None,
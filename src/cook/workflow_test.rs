@@ -0,0 +1,466 @@
+//! Testing workflows against fixture repositories
+//!
+//! `prodigy test-workflow workflow.yml` runs the cases declared in the
+//! workflow's `tests:` block. Each case gets a fresh git repository holding
+//! its fixture files, runs the workflow there without a worktree, and checks
+//! the resulting files and commits. Claude is never called: every Claude
+//! command is served by the case's mocks (see [`claude_mock`]).
+//!
+//! ```yaml
+//! tests:
+//!   - name: lint fixes are committed
+//!     files:
+//!       src/lib.rs: "fn main() {}\n"
+//!     claude:
+//!       - command: /prodigy-lint
+//!         run: 'echo "// linted" >> src/lib.rs && git commit -qam "style: lint"'
+//!     expect:
+//!       commits: 1
+//!       files:
+//!         - path: src/lib.rs
+//!           contains: "// linted"
+//! ```
+//!
+//! Shell steps run for real inside the fixture repository.
+//!
+//! [`claude_mock`]: crate::cook::execution::claude_mock
+
+use super::command::CookCommand;
+use super::execution::claude_mock::{ClaudeMock, ClaudeMocks};
+use crate::styled_println;
+use anyhow::{anyhow, Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Arc;
+
+fn default_true() -> bool {
+    true
+}
+
+/// One test case of a workflow
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WorkflowTestCase {
+    pub name: String,
+
+    /// Fixture files committed to the repository before the run, by path
+    #[serde(default)]
+    pub files: BTreeMap<String, String>,
+
+    /// Mocks serving the Claude commands the workflow runs
+    #[serde(default)]
+    pub claude: Vec<ClaudeMock>,
+
+    /// Positional arguments, as passed with `--args`
+    #[serde(default)]
+    pub args: Vec<String>,
+
+    #[serde(default)]
+    pub expect: TestExpectations,
+}
+
+/// What a test case checks after the run
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TestExpectations {
+    /// Whether the workflow should succeed
+    #[serde(default = "default_true")]
+    pub success: bool,
+
+    /// Number of commits the run should create
+    #[serde(default)]
+    pub commits: Option<usize>,
+
+    /// Text each expected commit message contains, one commit per entry
+    #[serde(default)]
+    pub commit_messages: Vec<String>,
+
+    #[serde(default)]
+    pub files: Vec<FileExpectation>,
+}
+
+impl Default for TestExpectations {
+    fn default() -> Self {
+        Self {
+            success: true,
+            commits: None,
+            commit_messages: Vec::new(),
+            files: Vec::new(),
+        }
+    }
+}
+
+/// Expected state of one file after the run
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FileExpectation {
+    pub path: String,
+
+    /// Whether the file should exist
+    #[serde(default = "default_true")]
+    pub exists: bool,
+
+    /// Text the file should contain
+    #[serde(default)]
+    pub contains: Option<String>,
+
+    /// Exact content the file should have
+    #[serde(default)]
+    pub equals: Option<String>,
+}
+
+/// What a test run produced
+#[derive(Debug, Clone, Default)]
+pub struct RunOutcome {
+    /// Why the workflow failed, `None` on success
+    pub error: Option<String>,
+    /// Messages of the commits the run created, oldest first
+    pub commit_messages: Vec<String>,
+}
+
+/// Result of one test case
+#[derive(Debug, Clone)]
+pub struct CaseResult {
+    pub name: String,
+    /// Unmet expectations, empty when the case passed
+    pub failures: Vec<String>,
+}
+
+/// Parse the `tests:` block of a workflow file (pure function)
+pub fn parse_tests(content: &str) -> Result<Vec<WorkflowTestCase>> {
+    let document: serde_yaml::Value =
+        serde_yaml::from_str(content).context("Failed to parse workflow YAML")?;
+    let tests = document
+        .get("tests")
+        .ok_or_else(|| anyhow!("Workflow has no `tests:` block"))?;
+    let cases: Vec<WorkflowTestCase> =
+        serde_yaml::from_value(tests.clone()).context("Invalid `tests:` block")?;
+    if cases.is_empty() {
+        return Err(anyhow!("Workflow `tests:` block is empty"));
+    }
+    Ok(cases)
+}
+
+/// Unmet expectations of a run (pure function)
+///
+/// `read_file` returns the content of a repository file, or `None` if it
+/// does not exist.
+pub fn check_expectations(
+    expect: &TestExpectations,
+    outcome: &RunOutcome,
+    read_file: impl Fn(&str) -> Option<String>,
+) -> Vec<String> {
+    let mut failures = Vec::new();
+    match (&outcome.error, expect.success) {
+        (Some(error), true) => failures.push(format!(
+            "workflow failed: {}",
+            error.lines().next().unwrap_or_default()
+        )),
+        (None, false) => failures.push("workflow succeeded but was expected to fail".to_string()),
+        _ => {}
+    }
+
+    let commits = outcome.commit_messages.len();
+    if let Some(expected) = expect.commits.filter(|expected| *expected != commits) {
+        failures.push(format!(
+            "expected {} commit(s), found {}",
+            expected, commits
+        ));
+    }
+    let mut unmatched: Vec<&String> = outcome.commit_messages.iter().collect();
+    for text in &expect.commit_messages {
+        match unmatched.iter().position(|message| message.contains(text)) {
+            Some(index) => {
+                unmatched.remove(index);
+            }
+            None => failures.push(format!("no commit message contains '{}'", text)),
+        }
+    }
+
+    for file in &expect.files {
+        match (read_file(&file.path), file.exists) {
+            (None, true) => failures.push(format!("{} does not exist", file.path)),
+            (Some(_), false) => failures.push(format!("{} exists", file.path)),
+            (None, false) => {}
+            (Some(content), true) => {
+                if let Some(text) = file.contains.as_ref().filter(|t| !content.contains(*t)) {
+                    failures.push(format!("{} does not contain '{}'", file.path, text));
+                }
+                if file.equals.as_ref().is_some_and(|e| *e != content) {
+                    failures.push(format!("{} content differs from expected", file.path));
+                }
+            }
+        }
+    }
+    failures
+}
+
+/// Create a git repository holding the fixture files in one commit
+fn create_fixture_repo(dir: &Path, files: &BTreeMap<String, String>) -> Result<git2::Oid> {
+    let repo = git2::Repository::init(dir).context("Failed to create fixture repository")?;
+    let mut config = repo.config()?;
+    config.set_str("user.name", "Prodigy Test")?;
+    config.set_str("user.email", "test@prodigy.local")?;
+
+    for (path, content) in files {
+        let target = dir.join(path);
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&target, content)
+            .with_context(|| format!("Failed to write fixture file {}", path))?;
+    }
+
+    let mut index = repo.index()?;
+    index.add_all(["*"], git2::IndexAddOption::DEFAULT, None)?;
+    index.write()?;
+    let tree = repo.find_tree(index.write_tree()?)?;
+    let signature = repo.signature()?;
+    repo.commit(Some("HEAD"), &signature, &signature, "Fixture", &tree, &[])
+        .context("Failed to commit fixture files")
+}
+
+/// Messages of the commits made after `base`, oldest first
+fn commits_since(dir: &Path, base: git2::Oid) -> Result<Vec<String>> {
+    let repo = git2::Repository::open(dir)?;
+    let mut walk = repo.revwalk()?;
+    walk.push_head()?;
+    walk.hide(base)?;
+    walk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::REVERSE)?;
+    walk.map(|oid| {
+        let commit = repo.find_commit(oid?)?;
+        Ok(commit.message().unwrap_or_default().trim().to_string())
+    })
+    .collect()
+}
+
+/// Run one test case in a fresh fixture repository
+async fn run_case(playbook: &Path, case: &WorkflowTestCase) -> Result<CaseResult> {
+    let temp = tempfile::TempDir::new()?;
+    let dir = temp.path();
+    let base = create_fixture_repo(dir, &case.files)?;
+
+    let cmd = CookCommand {
+        playbook: playbook.to_path_buf(),
+        path: Some(dir.to_path_buf()),
+        max_iterations: 1,
+        map: vec![],
+        args: case.args.clone(),
+        fail_fast: false,
+        auto_accept: true,
+        resume: None,
+        quiet: true,
+        verbosity: 0,
+        dry_run: false,
+        read_only: false,
+        record: None,
+        junit_output: None,
+        profile_steps: false,
+        replay: None,
+        chaos: None,
+        seed: None,
+        answers_file: None,
+        environment: None,
+        no_worktree: true,
+        params: std::collections::HashMap::new(),
+        package: None,
+        changed_only: None,
+        explain_failure: false,
+        keep_going: false,
+        steps: Default::default(),
+        explicit_flags: Default::default(),
+    };
+
+    let mocks = Arc::new(ClaudeMocks::new(case.claude.clone()));
+    let result = super::cook_with_claude(cmd, mocks).await;
+
+    let outcome = RunOutcome {
+        error: result.err().map(|e| format!("{:#}", e)),
+        commit_messages: commits_since(dir, base).unwrap_or_default(),
+    };
+    let failures = check_expectations(&case.expect, &outcome, |path| {
+        std::fs::read_to_string(dir.join(path)).ok()
+    });
+    Ok(CaseResult {
+        name: case.name.clone(),
+        failures,
+    })
+}
+
+/// Format the results of a test run (pure function)
+pub fn format_results(results: &[CaseResult]) -> Vec<String> {
+    let mut lines = Vec::new();
+    for result in results {
+        if result.failures.is_empty() {
+            lines.push(format!("✅ {}", result.name));
+        } else {
+            lines.push(format!("❌ {}", result.name));
+            lines.extend(result.failures.iter().map(|f| format!("   - {}", f)));
+        }
+    }
+    let failed = results.iter().filter(|r| !r.failures.is_empty()).count();
+    lines.push(format!(
+        "\n{} passed, {} failed",
+        results.len() - failed,
+        failed
+    ));
+    lines
+}
+
+/// Run the test cases of a workflow, optionally only those whose name contains `filter`
+pub async fn run_workflow_tests(playbook: &Path, filter: Option<&str>) -> Result<()> {
+    // Pin the playbook path, since each run changes the current directory
+    let original_dir = std::env::current_dir()?;
+    let playbook = original_dir.join(playbook);
+    let content = tokio::fs::read_to_string(&playbook)
+        .await
+        .with_context(|| format!("Failed to read workflow {}", playbook.display()))?;
+    let cases: Vec<WorkflowTestCase> = parse_tests(&content)?
+        .into_iter()
+        .filter(|case| filter.is_none_or(|f| case.name.contains(f)))
+        .collect();
+    if cases.is_empty() {
+        return Err(anyhow!("No test cases match the filter"));
+    }
+
+    let mut results = Vec::with_capacity(cases.len());
+    for case in &cases {
+        styled_println!("▶ {}", case.name);
+        let result = run_case(&playbook, case).await;
+        std::env::set_current_dir(&original_dir)?;
+        results.push(result.unwrap_or_else(|e| CaseResult {
+            name: case.name.clone(),
+            failures: vec![format!("could not run: {:#}", e)],
+        }));
+    }
+
+    styled_println!();
+    for line in format_results(&results) {
+        styled_println!("{}", line);
+    }
+
+    let failed = results.iter().filter(|r| !r.failures.is_empty()).count();
+    if failed > 0 {
+        return Err(anyhow!(
+            "{} of {} workflow tests failed",
+            failed,
+            results.len()
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORKFLOW: &str = r#"
+commands:
+  - claude: "/prodigy-lint"
+tests:
+  - name: lint
+    files:
+      src/lib.rs: "fn main() {}\n"
+    claude:
+      - command: /prodigy-lint
+        run: echo linted >> src/lib.rs
+    expect:
+      commits: 1
+      files:
+        - path: src/lib.rs
+          contains: linted
+        - path: debug.log
+          exists: false
+"#;
+
+    #[test]
+    fn test_parse_tests() {
+        let cases = parse_tests(WORKFLOW).unwrap();
+        assert_eq!(cases.len(), 1);
+        assert_eq!(cases[0].files["src/lib.rs"], "fn main() {}\n");
+        assert_eq!(cases[0].claude[0].command, "/prodigy-lint");
+        assert!(cases[0].claude[0].success);
+        assert!(cases[0].expect.success);
+        assert_eq!(cases[0].expect.commits, Some(1));
+        assert!(!cases[0].expect.files[1].exists);
+
+        assert!(parse_tests("commands: []").is_err());
+        assert!(parse_tests("tests:\n  - name: x\n    expected: {}\n").is_err());
+    }
+
+    #[test]
+    fn test_check_expectations() {
+        let expect = parse_tests(WORKFLOW).unwrap().remove(0).expect;
+        let read =
+            |path: &str| (path == "src/lib.rs").then(|| "fn main() {}\nlinted\n".to_string());
+        let passed = RunOutcome {
+            error: None,
+            commit_messages: vec!["style: lint".to_string()],
+        };
+        assert!(check_expectations(&expect, &passed, read).is_empty());
+
+        let failed = RunOutcome {
+            error: Some("Step 1 failed\ncaused by".to_string()),
+            commit_messages: vec![],
+        };
+        let failures = check_expectations(&expect, &failed, |_| None);
+        assert_eq!(
+            failures,
+            vec![
+                "workflow failed: Step 1 failed",
+                "expected 1 commit(s), found 0",
+                "src/lib.rs does not exist",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_commit_messages_and_failure() {
+        let expect = TestExpectations {
+            success: false,
+            commit_messages: vec!["fix".to_string(), "fix".to_string()],
+            ..Default::default()
+        };
+        let outcome = RunOutcome {
+            error: None,
+            commit_messages: vec!["fix: a".to_string(), "docs: b".to_string()],
+        };
+        assert_eq!(
+            check_expectations(&expect, &outcome, |_| None),
+            vec![
+                "workflow succeeded but was expected to fail",
+                "no commit message contains 'fix'",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fixture_repo_and_commits_since() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let files = BTreeMap::from([("a/b.txt".to_string(), "hello".to_string())]);
+        let base = create_fixture_repo(temp.path(), &files).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(temp.path().join("a/b.txt")).unwrap(),
+            "hello"
+        );
+        assert!(commits_since(temp.path(), base).unwrap().is_empty());
+
+        let repo = git2::Repository::open(temp.path()).unwrap();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        let signature = repo.signature().unwrap();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            "feat: next",
+            &head.tree().unwrap(),
+            &[&head],
+        )
+        .unwrap();
+        assert_eq!(
+            commits_since(temp.path(), base).unwrap(),
+            vec!["feat: next"]
+        );
+    }
+}
@@ -211,7 +211,12 @@ fn determine_mapreduce_phases(config: &CookConfig) -> Vec<Phase> {
         }
 
         // Map phase (always present in MapReduce)
-        let max_parallel = mr_config.map.max_parallel.parse::<usize>().unwrap_or(10);
+        let max_parallel = mr_config
+            .map
+            .max_parallel
+            .as_deref()
+            .and_then(|max_parallel| max_parallel.parse::<usize>().ok())
+            .unwrap_or(10);
         phases.push(Phase::Map {
             max_parallel,
             has_filter: mr_config.map.filter.is_some(),
@@ -257,6 +262,13 @@ mod tests {
             env_files: None,
             profiles: None,
             merge: None,
+            concurrency: None,
+            claude_permissions: None,
+            always: Vec::new(),
+            strict_variables: None,
+            defaults: None,
+            protected_paths: None,
+            score_gate: None,
         }
     }
 
@@ -274,11 +286,35 @@ mod tests {
                 verbosity: 0,
                 quiet: false,
                 dry_run: false,
+                read_only: false,
+                record: None,
+                junit_output: None,
+                profile_steps: false,
+                replay: None,
+                chaos: None,
+                seed: None,
+                answers_file: None,
+                environment: None,
+                no_worktree: false,
                 params: Default::default(),
+                package: None,
+                changed_only: None,
+                explain_failure: false,
+                keep_going: false,
+                steps: Default::default(),
+                explicit_flags: Default::default(),
             },
             project_path: Arc::new(PathBuf::from(".")),
             workflow: Arc::new(create_default_workflow_config()),
             mapreduce_config: None,
+            replay: None,
+            chaos: None,
+            junit: None,
+            profiler: None,
+            protected_paths: None,
+            answers: None,
+            failures: None,
+            settings: Default::default(),
         }
     }
 
@@ -294,8 +330,11 @@ mod tests {
             map: MapPhaseYaml {
                 input: "items.json".to_string(),
                 json_path: "$.items[*]".to_string(),
-                agent_template: AgentTemplate { commands: vec![] },
-                max_parallel: max_parallel.to_string(),
+                agent_template: AgentTemplate {
+                    commands: vec![],
+                    idempotency: None,
+                },
+                max_parallel: Some(max_parallel.to_string()),
                 filter: None,
                 sort_by: None,
                 max_items: None,
@@ -303,6 +342,16 @@ mod tests {
                 distinct: None,
                 agent_timeout_secs: None,
                 timeout_config: None,
+                rebase_onto: None,
+                speculative: None,
+                item_key: None,
+                affinity: Vec::new(),
+                build_cache: Vec::new(),
+                claude_permissions: None,
+                network: Default::default(),
+                changed_since: None,
+                changed_path: None,
+                batch_size: None,
             },
             reduce: None,
             error_policy: Default::default(),
@@ -312,6 +361,13 @@ mod tests {
             failure_threshold: None,
             error_collection: None,
             merge: None,
+            notifications: None,
+            concurrency: None,
+            claude_permissions: None,
+            always: Vec::new(),
+            defaults: None,
+            protected_paths: None,
+            strict_variables: None,
         }
     }
 
@@ -337,12 +393,17 @@ mod tests {
                 commands: vec![shell_step("echo setup"), shell_step("echo setup2")],
                 timeout: Some("300".to_string()),
                 capture_outputs: Default::default(),
+                artifacts: Vec::new(),
+                shared_context: None,
             }),
             map: MapPhaseYaml {
                 input: "items.json".to_string(),
                 json_path: "$.items[*]".to_string(),
-                agent_template: AgentTemplate { commands: vec![] },
-                max_parallel: "10".to_string(),
+                agent_template: AgentTemplate {
+                    commands: vec![],
+                    idempotency: None,
+                },
+                max_parallel: Some("10".to_string()),
                 filter: Some("status == 'active'".to_string()),
                 sort_by: Some("priority DESC".to_string()),
                 max_items: None,
@@ -350,9 +411,20 @@ mod tests {
                 distinct: None,
                 agent_timeout_secs: None,
                 timeout_config: None,
+                rebase_onto: None,
+                speculative: None,
+                item_key: None,
+                affinity: Vec::new(),
+                build_cache: Vec::new(),
+                claude_permissions: None,
+                network: Default::default(),
+                changed_since: None,
+                changed_path: None,
+                batch_size: None,
             },
             reduce: Some(ReducePhaseYaml {
                 commands: vec![shell_step("echo reduce")],
+                results_file: None,
             }),
             error_policy: Default::default(),
             on_item_failure: None,
@@ -361,6 +433,13 @@ mod tests {
             failure_threshold: None,
             error_collection: None,
             merge: None,
+            notifications: None,
+            concurrency: None,
+            claude_permissions: None,
+            always: Vec::new(),
+            defaults: None,
+            protected_paths: None,
+            strict_variables: None,
         }
     }
 
@@ -96,6 +96,13 @@ mod tests {
             env_files: None,
             profiles: None,
             merge: None,
+            concurrency: None,
+            claude_permissions: None,
+            always: Vec::new(),
+            strict_variables: None,
+            defaults: None,
+            protected_paths: None,
+            score_gate: None,
         }
     }
 
@@ -111,8 +118,11 @@ mod tests {
             map: MapPhaseYaml {
                 input: "items.json".to_string(),
                 json_path: "$.items[*]".to_string(),
-                agent_template: AgentTemplate { commands: vec![] },
-                max_parallel: "10".to_string(),
+                agent_template: AgentTemplate {
+                    commands: vec![],
+                    idempotency: None,
+                },
+                max_parallel: Some("10".to_string()),
                 filter: None,
                 sort_by: None,
                 max_items: None,
@@ -120,6 +130,16 @@ mod tests {
                 distinct: None,
                 agent_timeout_secs: None,
                 timeout_config: None,
+                rebase_onto: None,
+                speculative: None,
+                item_key: None,
+                affinity: Vec::new(),
+                build_cache: Vec::new(),
+                claude_permissions: None,
+                network: Default::default(),
+                changed_since: None,
+                changed_path: None,
+                batch_size: None,
             },
             reduce: None,
             error_policy: Default::default(),
@@ -129,6 +149,13 @@ mod tests {
             failure_threshold: None,
             error_collection: None,
             merge: None,
+            notifications: None,
+            concurrency: None,
+            claude_permissions: None,
+            always: Vec::new(),
+            defaults: None,
+            protected_paths: None,
+            strict_variables: None,
         }
     }
 
@@ -146,11 +173,35 @@ mod tests {
                 verbosity: 0,
                 quiet: false,
                 dry_run: false,
+                read_only: false,
+                record: None,
+                junit_output: None,
+                profile_steps: false,
+                replay: None,
+                chaos: None,
+                seed: None,
+                answers_file: None,
+                environment: None,
+                no_worktree: false,
                 params: Default::default(),
+                package: None,
+                changed_only: None,
+                explain_failure: false,
+                keep_going: false,
+                steps: Default::default(),
+                explicit_flags: Default::default(),
             },
             project_path: Arc::new(PathBuf::from(".")),
             workflow: Arc::new(create_default_workflow_config()),
             mapreduce_config: None,
+            replay: None,
+            chaos: None,
+            junit: None,
+            profiler: None,
+            protected_paths: None,
+            answers: None,
+            failures: None,
+            settings: Default::default(),
         }
     }
 
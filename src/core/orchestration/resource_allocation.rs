@@ -114,10 +114,9 @@ fn get_max_parallel(config: &CookConfig) -> usize {
     config
         .mapreduce_config
         .as_ref()
-        .map(|mr| {
-            // Parse max_parallel as usize, defaulting to 10
-            mr.map.max_parallel.parse::<usize>().unwrap_or(10)
-        })
+        // Parse max_parallel as usize, defaulting to 10
+        .and_then(|mr| mr.map.max_parallel.as_deref())
+        .and_then(|max_parallel| max_parallel.parse::<usize>().ok())
         .unwrap_or(10)
 }
 
@@ -169,6 +168,13 @@ mod tests {
             env_files: None,
             profiles: None,
             merge: None,
+            concurrency: None,
+            claude_permissions: None,
+            always: Vec::new(),
+            strict_variables: None,
+            defaults: None,
+            protected_paths: None,
+            score_gate: None,
         }
     }
 
@@ -186,11 +192,35 @@ mod tests {
                 verbosity: 0,
                 quiet: false,
                 dry_run: false,
+                read_only: false,
+                record: None,
+                junit_output: None,
+                profile_steps: false,
+                replay: None,
+                chaos: None,
+                seed: None,
+                answers_file: None,
+                environment: None,
+                no_worktree: false,
                 params: Default::default(),
+                package: None,
+                changed_only: None,
+                explain_failure: false,
+                keep_going: false,
+                steps: Default::default(),
+                explicit_flags: Default::default(),
             },
             project_path: Arc::new(PathBuf::from(".")),
             workflow: Arc::new(create_default_workflow_config()),
             mapreduce_config: None,
+            replay: None,
+            chaos: None,
+            junit: None,
+            profiler: None,
+            protected_paths: None,
+            answers: None,
+            failures: None,
+            settings: Default::default(),
         }
     }
 
@@ -206,8 +236,11 @@ mod tests {
             map: MapPhaseYaml {
                 input: "items.json".to_string(),
                 json_path: "$.items[*]".to_string(),
-                agent_template: AgentTemplate { commands: vec![] },
-                max_parallel: max_parallel.to_string(),
+                agent_template: AgentTemplate {
+                    commands: vec![],
+                    idempotency: None,
+                },
+                max_parallel: Some(max_parallel.to_string()),
                 filter: None,
                 sort_by: None,
                 max_items: None,
@@ -215,6 +248,16 @@ mod tests {
                 distinct: None,
                 agent_timeout_secs: None,
                 timeout_config: None,
+                rebase_onto: None,
+                speculative: None,
+                item_key: None,
+                affinity: Vec::new(),
+                build_cache: Vec::new(),
+                claude_permissions: None,
+                network: Default::default(),
+                changed_since: None,
+                changed_path: None,
+                batch_size: None,
             },
             reduce: None,
             error_policy: Default::default(),
@@ -224,6 +267,13 @@ mod tests {
             failure_threshold: None,
             error_collection: None,
             merge: None,
+            notifications: None,
+            concurrency: None,
+            claude_permissions: None,
+            always: Vec::new(),
+            defaults: None,
+            protected_paths: None,
+            strict_variables: None,
         }
     }
 
@@ -0,0 +1,362 @@
+//! C ABI for driving Prodigy workflows in-process
+//!
+//! Enabled with the `prodigy-ffi` feature. Build a shared library with:
+//!
+//! ```bash
+//! cargo rustc --release --lib --features prodigy-ffi --crate-type cdylib
+//! ```
+//!
+//! The exported functions are declared in `bindings/c/prodigy.h`, and
+//! `bindings/python/prodigy_ffi.py` wraps them with `ctypes`.
+//!
+//! # Lifecycle
+//!
+//! 1. [`prodigy_job_start`] starts a workflow on a background thread and
+//!    returns a job handle.
+//! 2. [`prodigy_job_poll`] reports the job status and elapsed time without
+//!    blocking.
+//! 3. [`prodigy_job_cancel`] requests cancellation. The workflow stops as a
+//!    CLI run does on SIGTERM: no new steps or agents start, running ones
+//!    finish, checkpoints are saved and the session is marked interrupted so
+//!    it can be resumed. A job still running after the shutdown grace period
+//!    is abandoned.
+//! 4. [`prodigy_job_free`] releases the handle. Freeing a running job cancels it.
+//!
+//! Workflows change the process working directory while they run, and
+//! cancellation applies to the whole process, so only one job should run per
+//! process at a time.
+
+use crate::api::WorkflowRunner;
+use crate::cook::signal_handler::{self, ShutdownSignal};
+use std::ffi::{c_char, CStr, CString};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::Instant;
+use tokio::sync::Notify;
+
+/// Status of a job started with [`prodigy_job_start`]
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProdigyJobStatus {
+    /// The workflow is still running
+    Running = 0,
+    /// The workflow completed successfully
+    Succeeded = 1,
+    /// The workflow failed; see [`prodigy_job_error`]
+    Failed = 2,
+    /// The workflow was cancelled; see [`prodigy_job_error`] if it had to be
+    /// abandoned
+    Cancelled = 3,
+}
+
+/// Progress snapshot returned by [`prodigy_job_poll`]
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProdigyJobProgress {
+    /// Current job status
+    pub status: ProdigyJobStatus,
+    /// Milliseconds since the job started
+    pub elapsed_ms: u64,
+}
+
+#[derive(Debug)]
+struct JobState {
+    status: ProdigyJobStatus,
+    error: Option<CString>,
+}
+
+/// Opaque handle to a running or finished workflow job
+pub struct ProdigyJob {
+    state: Arc<Mutex<JobState>>,
+    cancel: Arc<Notify>,
+    started: Instant,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl ProdigyJob {
+    fn start(runner: WorkflowRunner) -> std::io::Result<Self> {
+        let state = Arc::new(Mutex::new(JobState {
+            status: ProdigyJobStatus::Running,
+            error: None,
+        }));
+        let cancel = Arc::new(Notify::new());
+
+        let thread_state = Arc::clone(&state);
+        let thread_cancel = Arc::clone(&cancel);
+        let thread = std::thread::Builder::new()
+            .name("prodigy-ffi-job".to_string())
+            .spawn(move || {
+                let (status, error) = run_to_completion(runner, &thread_cancel);
+                if let Ok(mut state) = thread_state.lock() {
+                    state.status = status;
+                    state.error = error.map(to_c_string);
+                }
+            })?;
+
+        Ok(Self {
+            state,
+            cancel,
+            started: Instant::now(),
+            thread: Some(thread),
+        })
+    }
+
+    fn progress(&self) -> ProdigyJobProgress {
+        let status = self
+            .state
+            .lock()
+            .map(|state| state.status)
+            .unwrap_or(ProdigyJobStatus::Failed);
+        ProdigyJobProgress {
+            status,
+            elapsed_ms: u64::try_from(self.started.elapsed().as_millis()).unwrap_or(u64::MAX),
+        }
+    }
+}
+
+impl Drop for ProdigyJob {
+    fn drop(&mut self) {
+        self.cancel.notify_one();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Run the workflow on a dedicated runtime until it finishes or is cancelled
+fn run_to_completion(
+    runner: WorkflowRunner,
+    cancel: &Notify,
+) -> (ProdigyJobStatus, Option<String>) {
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            return (
+                ProdigyJobStatus::Failed,
+                Some(format!("Failed to start runtime: {e}")),
+            )
+        }
+    };
+
+    // A job cancelled earlier in this process must not stop this one
+    signal_handler::clear_shutdown_request();
+
+    runtime.block_on(async {
+        let run = runner.run();
+        tokio::pin!(run);
+        tokio::select! {
+            result = &mut run => return match result {
+                Ok(()) => (ProdigyJobStatus::Succeeded, None),
+                Err(e) => (ProdigyJobStatus::Failed, Some(format!("{e:#}"))),
+            },
+            _ = cancel.notified() => {}
+        }
+
+        // Let the run unwind so it saves checkpoints and marks the session
+        // interrupted, instead of dropping it mid-step
+        signal_handler::request_shutdown(ShutdownSignal::Terminate);
+        let result = match signal_handler::shutdown_grace_period() {
+            Some(grace_period) => match tokio::time::timeout(grace_period, run).await {
+                Ok(result) => result,
+                Err(_) => {
+                    return (
+                        ProdigyJobStatus::Cancelled,
+                        Some(format!(
+                            "Cancellation timed out after {}s, running steps were abandoned",
+                            grace_period.as_secs()
+                        )),
+                    )
+                }
+            },
+            None => run.await,
+        };
+        match result {
+            Ok(()) => (ProdigyJobStatus::Succeeded, None),
+            Err(_) => (ProdigyJobStatus::Cancelled, None),
+        }
+    })
+}
+
+fn to_c_string(message: String) -> CString {
+    CString::new(message.replace('\0', " ")).unwrap_or_default()
+}
+
+/// Read an optional UTF-8 path argument
+///
+/// # Safety
+/// `ptr` must be null or point to a valid NUL-terminated string.
+unsafe fn optional_path(ptr: *const c_char) -> Option<Result<PathBuf, ()>> {
+    if ptr.is_null() {
+        return None;
+    }
+    // SAFETY: the caller guarantees `ptr` is a valid NUL-terminated string
+    let value = unsafe { CStr::from_ptr(ptr) };
+    Some(value.to_str().map(PathBuf::from).map_err(|_| ()))
+}
+
+/// Start running a workflow file
+///
+/// `workflow_path` is required. `repo_path` may be null to use the current
+/// directory. When `auto_accept` is true all prompts (such as merging the
+/// worktree) are answered with yes.
+///
+/// Returns null if the arguments are invalid or the job thread cannot be
+/// started.
+///
+/// # Safety
+/// `workflow_path` must point to a valid NUL-terminated UTF-8 string, and
+/// `repo_path` must be null or point to one.
+#[no_mangle]
+pub unsafe extern "C" fn prodigy_job_start(
+    workflow_path: *const c_char,
+    repo_path: *const c_char,
+    auto_accept: bool,
+) -> *mut ProdigyJob {
+    // SAFETY: forwarded from the caller's guarantees
+    let Some(Ok(workflow)) = (unsafe { optional_path(workflow_path) }) else {
+        return std::ptr::null_mut();
+    };
+
+    let mut builder = WorkflowRunner::builder()
+        .workflow(workflow)
+        .auto_accept(auto_accept);
+    // SAFETY: forwarded from the caller's guarantees
+    match unsafe { optional_path(repo_path) } {
+        Some(Ok(path)) => builder = builder.path(path),
+        Some(Err(())) => return std::ptr::null_mut(),
+        None => {}
+    }
+
+    let Ok(runner) = builder.build() else {
+        return std::ptr::null_mut();
+    };
+    match ProdigyJob::start(runner) {
+        Ok(job) => Box::into_raw(Box::new(job)),
+        Err(_) => std::ptr::null_mut(),
+    }
+}
+
+/// Report the status and elapsed time of a job without blocking
+///
+/// Returns a `Failed` snapshot if `job` is null.
+///
+/// # Safety
+/// `job` must be null or a handle returned by [`prodigy_job_start`] that has
+/// not been freed.
+#[no_mangle]
+pub unsafe extern "C" fn prodigy_job_poll(job: *const ProdigyJob) -> ProdigyJobProgress {
+    // SAFETY: the caller guarantees `job` is null or a live handle
+    match unsafe { job.as_ref() } {
+        Some(job) => job.progress(),
+        None => ProdigyJobProgress {
+            status: ProdigyJobStatus::Failed,
+            elapsed_ms: 0,
+        },
+    }
+}
+
+/// Request cancellation of a running job
+///
+/// Returns at once; the job reports `Running` until it has stopped. Has no
+/// effect on finished jobs.
+///
+/// # Safety
+/// `job` must be null or a handle returned by [`prodigy_job_start`] that has
+/// not been freed.
+#[no_mangle]
+pub unsafe extern "C" fn prodigy_job_cancel(job: *const ProdigyJob) {
+    // SAFETY: the caller guarantees `job` is null or a live handle
+    if let Some(job) = unsafe { job.as_ref() } {
+        job.cancel.notify_one();
+    }
+}
+
+/// Error message of a job that failed or was abandoned while cancelling, or
+/// null if there is none
+///
+/// The string is owned by the job and stays valid until [`prodigy_job_free`].
+///
+/// # Safety
+/// `job` must be null or a handle returned by [`prodigy_job_start`] that has
+/// not been freed.
+#[no_mangle]
+pub unsafe extern "C" fn prodigy_job_error(job: *const ProdigyJob) -> *const c_char {
+    // SAFETY: the caller guarantees `job` is null or a live handle
+    let Some(job) = (unsafe { job.as_ref() }) else {
+        return std::ptr::null();
+    };
+    job.state
+        .lock()
+        .ok()
+        .and_then(|state| state.error.as_ref().map(|error| error.as_ptr()))
+        .unwrap_or(std::ptr::null())
+}
+
+/// Release a job handle, cancelling the job if it is still running
+///
+/// Blocks until the job thread has exited.
+///
+/// # Safety
+/// `job` must be null or a handle returned by [`prodigy_job_start`] that has
+/// not already been freed.
+#[no_mangle]
+pub unsafe extern "C" fn prodigy_job_free(job: *mut ProdigyJob) {
+    if !job.is_null() {
+        // SAFETY: the caller guarantees `job` came from `Box::into_raw` in
+        // `prodigy_job_start` and has not been freed
+        drop(unsafe { Box::from_raw(job) });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn wait_for_finish(job: *const ProdigyJob) -> ProdigyJobProgress {
+        for _ in 0..200 {
+            let progress = unsafe { prodigy_job_poll(job) };
+            if progress.status != ProdigyJobStatus::Running {
+                return progress;
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        }
+        unsafe { prodigy_job_poll(job) }
+    }
+
+    #[test]
+    fn test_start_rejects_null_workflow() {
+        let job = unsafe { prodigy_job_start(std::ptr::null(), std::ptr::null(), false) };
+        assert!(job.is_null());
+    }
+
+    #[test]
+    fn test_null_handles_are_ignored() {
+        let progress = unsafe { prodigy_job_poll(std::ptr::null()) };
+        assert_eq!(progress.status, ProdigyJobStatus::Failed);
+        assert!(unsafe { prodigy_job_error(std::ptr::null()) }.is_null());
+        unsafe {
+            prodigy_job_cancel(std::ptr::null());
+            prodigy_job_free(std::ptr::null_mut());
+        }
+    }
+
+    #[test]
+    fn test_failed_job_reports_error() {
+        let workflow = CString::new("workflow.yml").unwrap();
+        let repo = CString::new("/nonexistent/prodigy-ffi-repo").unwrap();
+        let job = unsafe { prodigy_job_start(workflow.as_ptr(), repo.as_ptr(), true) };
+        assert!(!job.is_null());
+
+        let progress = wait_for_finish(job);
+        assert_eq!(progress.status, ProdigyJobStatus::Failed);
+
+        let error = unsafe { prodigy_job_error(job) };
+        assert!(!error.is_null());
+        let message = unsafe { CStr::from_ptr(error) }.to_string_lossy();
+        assert!(message.contains("/nonexistent/prodigy-ffi-repo"));
+
+        unsafe { prodigy_job_free(job) };
+    }
+}
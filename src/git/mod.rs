@@ -40,6 +40,9 @@ pub trait GitReader: Send + Sync {
     /// Get diff between two references
     async fn get_diff(&self, path: &Path, from: &str, to: &str) -> LibResult<GitDiff>;
 
+    /// List files changed on HEAD since it diverged from `base`, relative to `path`
+    async fn get_changed_files(&self, path: &Path, base: &str) -> LibResult<Vec<PathBuf>>;
+
     /// Get the last commit message
     async fn get_last_commit_message(&self, path: &Path) -> LibResult<String>;
 
@@ -201,6 +204,28 @@ impl GitReader for GitCommandRunner {
         parsers::parse_diff_output(&output.stdout)
     }
 
+    async fn get_changed_files(&self, path: &Path, base: &str) -> LibResult<Vec<PathBuf>> {
+        let range = format!("{base}...HEAD");
+        let output = self
+            .run_git_command(path, &["diff", "--name-only", "--relative", &range])
+            .await?;
+
+        if !output.status.success() {
+            return Err(GitError::CommandFailed(format!(
+                "git diff against {base} failed: {}",
+                output.stderr.trim()
+            ))
+            .into());
+        }
+
+        Ok(output
+            .stdout
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| PathBuf::from(line.trim()))
+            .collect())
+    }
+
     async fn get_last_commit_message(&self, path: &Path) -> LibResult<String> {
         self.get_commit_message(path, "HEAD").await
     }
@@ -620,6 +645,38 @@ mod tests {
         assert_eq!(files[2], PathBuf::from("Cargo.toml"));
     }
 
+    #[tokio::test]
+    async fn test_get_changed_files() {
+        let (git, mock) = create_test_runner();
+        let temp_dir = TempDir::new().expect("Failed to create temporary directory for test");
+
+        mock.add_response(
+            "git",
+            Ok(crate::subprocess::ProcessOutput {
+                status: crate::subprocess::ExitStatusHelper::success(),
+                stdout: "src/lib.rs\ndocs/guide.md\n".to_string(),
+                stderr: String::new(),
+                duration: std::time::Duration::from_millis(10),
+            }),
+        )
+        .await;
+
+        let files = git
+            .get_changed_files(temp_dir.path(), "origin/main")
+            .await
+            .expect("get_changed_files should succeed for test");
+        assert_eq!(
+            files,
+            vec![PathBuf::from("src/lib.rs"), PathBuf::from("docs/guide.md")]
+        );
+
+        let calls = mock.get_calls().await;
+        assert_eq!(
+            calls[0].args,
+            vec!["diff", "--name-only", "--relative", "origin/main...HEAD"]
+        );
+    }
+
     #[tokio::test]
     async fn test_is_clean() {
         let (git, mock) = create_test_runner();
@@ -413,6 +413,18 @@ impl GitReader for GitScenarioMock {
         super::parsers::parse_diff_output(&output)
     }
 
+    async fn get_changed_files(&self, path: &Path, base: &str) -> LibResult<Vec<PathBuf>> {
+        let range = format!("{base}...HEAD");
+        let output = self
+            .execute_command(path, &["diff", "--name-only", "--relative", &range])
+            .await?;
+        Ok(output
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| PathBuf::from(line.trim()))
+            .collect())
+    }
+
     async fn get_last_commit_message(&self, path: &Path) -> LibResult<String> {
         self.get_commit_message(path, "HEAD").await
     }
@@ -65,6 +65,7 @@
 pub mod command;
 pub mod templates;
 
+use crate::{styled_eprintln, styled_println};
 use anyhow::{Context, Result};
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -144,12 +145,12 @@ async fn initialize_git_repository(path: &Path, subprocess: &SubprocessManager)
 fn select_templates(cmd: &InitCommand) -> Result<Vec<templates::CommandTemplate>> {
     if let Some(ref command_names) = cmd.commands {
         if command_names.is_empty() {
-            println!("⚠️  No commands specified. Use --commands or omit to install all.");
+            styled_println!("⚠️  No commands specified. Use --commands or omit to install all.");
             return Ok(vec![]);
         }
         let selected = templates::get_templates_by_names(command_names);
         if selected.is_empty() {
-            println!("❌ No matching commands found for: {command_names:?}");
+            styled_println!("❌ No matching commands found for: {command_names:?}");
             println!("   Available commands: prodigy-code-review, prodigy-implement-spec, prodigy-lint, prodigy-product-enhance, prodigy-merge-worktree, prodigy-cleanup-tech-debt");
             return Ok(vec![]);
         }
@@ -185,7 +186,7 @@ fn is_test_environment() -> bool {
 
 /// Display warning about existing commands
 fn display_existing_commands_warning(existing: &[&str]) {
-    println!("\n⚠️  The following commands already exist:");
+    styled_println!("\n⚠️  The following commands already exist:");
     for name in existing {
         println!("   - {name}");
     }
@@ -207,12 +208,12 @@ fn get_user_confirmation() -> Result<bool> {
         io::stdin().read_line(&mut input)?;
 
         if input.trim().to_lowercase() != "y" {
-            println!("❌ Installation cancelled.");
+            styled_println!("❌ Installation cancelled.");
             return Ok(false);
         }
     } else {
         // Non-interactive mode - skip existing by default
-        println!("ℹ️  Skipping existing commands (non-interactive mode).");
+        styled_println!("ℹ️  Skipping existing commands (non-interactive mode).");
     }
 
     Ok(true)
@@ -294,7 +295,7 @@ fn install_templates(
     templates: &[templates::CommandTemplate],
     force: bool,
 ) -> Result<(usize, usize)> {
-    println!("\n📦 Installing {} command(s)...", templates.len());
+    styled_println!("\n📦 Installing {} command(s)...", templates.len());
     let mut installed = 0;
     let mut skipped = 0;
 
@@ -302,7 +303,7 @@ fn install_templates(
         match install_command(commands_dir, template, force) {
             Ok(_) => installed += 1,
             Err(e) => {
-                eprintln!("❌ Failed to install '{}': {}", template.name, e);
+                styled_eprintln!("❌ Failed to install '{}': {}", template.name, e);
                 skipped += 1;
             }
         }
@@ -313,7 +314,7 @@ fn install_templates(
 
 /// Display installation summary and next steps
 fn display_installation_summary(installed: usize, skipped: usize, commands_dir: &Path) {
-    println!("\n✨ Installation complete!");
+    styled_println!("\n✨ Installation complete!");
     println!("   - {installed} command(s) installed");
     if skipped > 0 {
         println!("   - {skipped} command(s) skipped");
@@ -321,14 +322,16 @@ fn display_installation_summary(installed: usize, skipped: usize, commands_dir:
 
     // Show next steps
     if installed > 0 {
-        println!("\n📚 Next steps:");
+        styled_println!("\n📚 Next steps:");
         println!(
             "   1. Review installed commands in: {}",
             commands_dir.display()
         );
         println!("   2. Customize commands as needed for your project");
         println!("   3. Run 'prodigy cook' to start improving your code");
-        println!("\n💡 Tip: You can always reinstall default commands with 'prodigy init --force'");
+        styled_println!(
+            "\n💡 Tip: You can always reinstall default commands with 'prodigy init --force'"
+        );
     }
 }
 
@@ -341,7 +344,7 @@ fn install_command(
     let file_path = commands_dir.join(format!("{}.md", template.name));
 
     if file_path.exists() && !force {
-        println!(
+        styled_println!(
             "⚠️  Command '{}' already exists. Use --force to overwrite.",
             template.name
         );
@@ -351,7 +354,7 @@ fn install_command(
     fs::write(&file_path, template.content)
         .with_context(|| format!("Failed to write command file: {}", file_path.display()))?;
 
-    println!("✅ Installed command: {}", template.name);
+    styled_println!("✅ Installed command: {}", template.name);
     Ok(())
 }
 
@@ -366,7 +369,7 @@ async fn validate_project_structure(
         .canonicalize()
         .with_context(|| format!("Failed to resolve path: {}", target_dir.display()))?;
 
-    println!(
+    styled_println!(
         "🚀 Initializing Prodigy commands in: {}",
         target_dir.display()
     );
@@ -383,9 +386,9 @@ async fn validate_project_structure(
         }
 
         // Initialize git repository automatically
-        println!("📦 Directory is not a git repository. Initializing git...");
+        styled_println!("📦 Directory is not a git repository. Initializing git...");
         initialize_git_repository(&target_dir, subprocess).await?;
-        println!("✅ Git repository initialized successfully.");
+        styled_println!("✅ Git repository initialized successfully.");
     }
 
     let commands_dir = initialize_directories(&target_dir)?;
@@ -400,7 +403,7 @@ fn initialize_directories(target_dir: &Path) -> Result<PathBuf> {
     if !commands_dir.exists() {
         fs::create_dir_all(&commands_dir)
             .with_context(|| format!("Failed to create directory: {}", commands_dir.display()))?;
-        println!("📁 Created directory: {}", commands_dir.display());
+        styled_println!("📁 Created directory: {}", commands_dir.display());
     }
 
     Ok(commands_dir)
@@ -10,12 +10,14 @@
 //!
 //! ## Modules
 //!
+//! - `api` - Stable facade for embedding Prodigy in other Rust tools
 //! - `abstractions` - Trait-based abstractions for external dependencies (git, Claude CLI)
 //! - `commands` - Modular command handler architecture for extensible workflow commands
 //! - `config` - Configuration management for the tool
 //! - `cook` - Core cooking command implementation with mapping support
 //! - `core` - Pure business logic functions without I/O operations
 //! - `effects` - Effect-based operations for composable I/O
+//! - `ffi` - C ABI for running workflows from other languages (`prodigy-ffi` feature)
 //! - `env` - Environment abstraction layer for dependency injection and testing
 //! - `git` - Granular, testable git operations layer
 //! - `init` - Initialize Prodigy commands in projects
@@ -25,6 +27,7 @@
 //! - `unified_session` - Unified session management with event-driven architecture
 //! - `testing` - Testing utilities and fixtures for comprehensive testing
 pub mod abstractions;
+pub mod api;
 pub mod app;
 pub mod cli;
 pub mod commands;
@@ -34,8 +37,11 @@ pub mod core;
 pub mod effects;
 pub mod env;
 pub mod error;
+#[cfg(feature = "prodigy-ffi")]
+pub mod ffi;
 pub mod git;
 pub mod init;
+pub mod output;
 pub mod resume_logic;
 pub mod storage;
 pub mod subprocess;
@@ -13,12 +13,47 @@ use tracing::error;
 // Import the modularized components
 use prodigy::app::{handle_fatal_error, initialize_app, AppConfig};
 use prodigy::cli::{execute_command, Cli};
+use prodigy::output;
 
-#[tokio::main]
-async fn main() {
+fn main() {
     // Parse command line arguments
     let cli = Cli::parse();
 
+    // The environment is only modified here, before the runtime starts its
+    // worker threads
+
+    // Decide on colors and emoji before anything is printed. `--plain` is
+    // exported so styled output and subprocesses follow it
+    if cli.plain {
+        std::env::set_var(output::PLAIN_ENV, "1");
+    }
+    output::configure_console();
+
+    // Export the storage directory so config loading and subprocesses see it
+    if let Some(dir) = &cli.storage_dir {
+        match std::path::absolute(dir) {
+            Ok(dir) => std::env::set_var(prodigy::storage::STORAGE_DIR_ENV, dir),
+            Err(e) => {
+                eprintln!("Invalid --storage-dir {}: {}", dir.display(), e);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let runtime = match tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+    {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            eprintln!("Failed to start the async runtime: {}", e);
+            std::process::exit(1);
+        }
+    };
+    runtime.block_on(run(cli));
+}
+
+async fn run(cli: Cli) {
     // Create application configuration
     let app_config = match AppConfig::new(cli.verbose) {
         Ok(config) => config,
@@ -0,0 +1,356 @@
+//! Terminal output styling
+//!
+//! Prodigy decorates its console output with emoji, box-drawing characters
+//! and ANSI colors. These look fine in an interactive UTF-8 terminal but
+//! corrupt CI logs and confuse terminals without Unicode support, so every
+//! user-facing message goes through this module:
+//!
+//! - [`OutputStyle::detect`] decides from the environment whether stdout
+//!   is an interactive terminal that can show colors and Unicode.
+//! - `--plain` (alias `--no-color`) forces plain output for one run by
+//!   setting [`PLAIN_ENV`], which subprocesses inherit.
+//! - [`styled`] and the `styled_println!` family of macros rewrite
+//!   decorations into ASCII when Unicode is disabled (`✅` becomes `[ok]`,
+//!   `→` becomes `->`, other pictographs are dropped).
+//!
+//! Detection honors, in order: `PRODIGY_PLAIN` (plain output),
+//! `CLICOLOR_FORCE` (decorated output even when piped), `NO_COLOR` (no
+//! colors), `TERM=dumb` and non-terminal stdout (neither), and the locale
+//! (`LC_ALL`, `LC_CTYPE`, `LANG`) for Unicode.
+//!
+//! A live progress bar registered with [`set_live_bar`] is hidden while
+//! styled output is printed, so messages never land in the middle of it.
+
+use indicatif::ProgressBar;
+use std::borrow::Cow;
+use std::io::{IsTerminal, Write};
+use std::sync::Mutex;
+
+static LIVE_BAR: Mutex<Option<ProgressBar>> = Mutex::new(None);
+
+/// Environment variable forcing plain output
+pub const PLAIN_ENV: &str = "PRODIGY_PLAIN";
+
+/// What the console output may contain
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutputStyle {
+    /// ANSI colors and other escape sequences
+    pub color: bool,
+    /// Emoji and box-drawing characters
+    pub unicode: bool,
+}
+
+impl OutputStyle {
+    /// ASCII only, no colors
+    pub fn plain() -> Self {
+        Self {
+            color: false,
+            unicode: false,
+        }
+    }
+
+    /// Colors and Unicode decorations
+    pub fn decorated() -> Self {
+        Self {
+            color: true,
+            unicode: true,
+        }
+    }
+
+    /// Style supported by the current process's stdout and environment
+    pub fn detect() -> Self {
+        Self::detect_from(
+            |name| std::env::var(name).ok(),
+            std::io::stdout().is_terminal(),
+        )
+    }
+
+    /// Style for the given environment lookup and terminal state
+    pub fn detect_from(var: impl Fn(&str) -> Option<String>, is_terminal: bool) -> Self {
+        let enabled = |name: &str| {
+            var(name).is_some_and(|value| {
+                let value = value.trim().to_ascii_lowercase();
+                !value.is_empty() && value != "0" && value != "false"
+            })
+        };
+
+        if enabled(PLAIN_ENV) {
+            return Self::plain();
+        }
+
+        let unicode = locale_is_utf8(&var);
+        if enabled("CLICOLOR_FORCE") {
+            return Self {
+                color: true,
+                unicode,
+            };
+        }
+
+        let interactive = is_terminal && var("TERM").as_deref() != Some("dumb");
+        let no_color = var("NO_COLOR").is_some_and(|value| !value.is_empty());
+        Self {
+            color: interactive && !no_color,
+            unicode: interactive && unicode,
+        }
+    }
+}
+
+/// Whether the locale uses UTF-8, following the POSIX precedence
+fn locale_is_utf8(var: &impl Fn(&str) -> Option<String>) -> bool {
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .find_map(|name| var(name).filter(|value| !value.is_empty()));
+    match locale {
+        Some(locale) => {
+            let locale = locale.to_ascii_lowercase();
+            locale.contains("utf-8") || locale.contains("utf8")
+        }
+        // No locale configured: modern Unix terminals default to UTF-8
+        None => !cfg!(windows),
+    }
+}
+
+/// Apply the current style's color setting to `console` and `indicatif` output
+pub fn configure_console() {
+    let style = current();
+    console::set_colors_enabled(style.color);
+    console::set_colors_enabled_stderr(style.color);
+}
+
+/// The output style for this process's environment, including `--plain`
+pub fn current() -> OutputStyle {
+    OutputStyle::detect()
+}
+
+/// Keep `bar` below styled output until it is replaced or cleared
+pub fn set_live_bar(bar: Option<ProgressBar>) {
+    let mut live = match LIVE_BAR.lock() {
+        Ok(live) => live,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    *live = bar;
+}
+
+/// Run `print` with the live progress bar, if any, hidden
+pub fn suspend<R>(print: impl FnOnce() -> R) -> R {
+    let bar = match LIVE_BAR.lock() {
+        Ok(live) => live.clone(),
+        Err(poisoned) => poisoned.into_inner().clone(),
+    };
+    match bar {
+        Some(bar) if !bar.is_hidden() => bar.suspend(print),
+        _ => print(),
+    }
+}
+
+/// Text as it should be printed with the current style
+pub fn styled(text: &str) -> Cow<'_, str> {
+    if current().unicode || text.is_ascii() {
+        Cow::Borrowed(text)
+    } else {
+        Cow::Owned(to_ascii(text))
+    }
+}
+
+/// Replace emoji and box-drawing characters with ASCII equivalents
+///
+/// Status symbols become bracketed labels, lines and arrows become their
+/// ASCII lookalikes, and other pictographs are removed together with the
+/// spacing that followed them. Text in other scripts is left unchanged.
+pub fn to_ascii(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if let Some(replacement) = ascii_replacement(c) {
+            // `[✓ done]` becomes `[ok done]`, not `[[ok] done]`
+            match replacement.strip_prefix('[') {
+                Some(label) if result.ends_with('[') => {
+                    result.push_str(label.trim_end_matches(']'))
+                }
+                _ => result.push_str(replacement),
+            }
+        } else if is_pictograph(c) {
+            while chars.peek().is_some_and(|next| is_modifier(*next)) {
+                chars.next();
+            }
+            // Drop the separating space, unless the symbol stood between words
+            if result.is_empty() || result.ends_with(char::is_whitespace) || result.ends_with('[') {
+                while chars.peek() == Some(&' ') {
+                    chars.next();
+                }
+            }
+        } else if !is_modifier(c) {
+            result.push(c);
+        }
+    }
+    result
+}
+
+fn ascii_replacement(c: char) -> Option<&'static str> {
+    Some(match c {
+        '✅' | '✓' | '✔' => "[ok]",
+        '❌' | '✗' | '✘' | '⛔' => "[error]",
+        '⚠' => "[warn]",
+        'ℹ' => "[info]",
+        '💡' => "[hint]",
+        '⏳' | '…' => "...",
+        '→' | '⟶' | '➜' | '➡' => "->",
+        '←' => "<-",
+        '▶' | '▸' => ">",
+        '•' | '·' => "-",
+        '═' | '━' => "=",
+        '─' | '╌' => "-",
+        '║' | '│' | '┃' => "|",
+        '┌' | '┐' | '└' | '┘' | '├' | '┤' | '┬' | '┴' | '┼' | '╔' | '╗' | '╚' | '╝' | '╠' | '╣'
+        | '╦' | '╩' | '╬' => "+",
+        '█' | '▓' => "#",
+        '▒' | '░' => "-",
+        _ => return None,
+    })
+}
+
+fn is_pictograph(c: char) -> bool {
+    matches!(c,
+        '\u{1F000}'..='\u{1FAFF}'
+        | '\u{2300}'..='\u{23FF}'
+        | '\u{2600}'..='\u{27BF}'
+        | '\u{2B00}'..='\u{2BFF}'
+        | '\u{2190}'..='\u{21FF}')
+}
+
+/// Variation selectors, joiners and keycaps that only modify a pictograph
+fn is_modifier(c: char) -> bool {
+    matches!(c, '\u{FE0E}' | '\u{FE0F}' | '\u{200D}' | '\u{20E3}')
+}
+
+/// Writer applying [`styled`] to everything written through it
+///
+/// Used for log output, whose messages are formatted before they reach
+/// the console. Each `write` call must contain whole characters, which
+/// holds for the line-buffered tracing formatter.
+pub struct StyledWriter<W> {
+    inner: W,
+}
+
+impl<W: Write> StyledWriter<W> {
+    pub fn new(inner: W) -> Self {
+        Self { inner }
+    }
+}
+
+impl<W: Write> Write for StyledWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let inner = &mut self.inner;
+        suspend(|| match std::str::from_utf8(buf) {
+            Ok(text) if !current().unicode && !text.is_ascii() => {
+                inner.write_all(to_ascii(text).as_bytes())
+            }
+            _ => inner.write_all(buf),
+        })?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// `println!` that follows the current [`OutputStyle`]
+#[macro_export]
+macro_rules! styled_println {
+    () => {
+        $crate::output::suspend(|| println!())
+    };
+    ($($arg:tt)*) => {{
+        let text = format!($($arg)*);
+        $crate::output::suspend(|| println!("{}", $crate::output::styled(&text)))
+    }};
+}
+
+/// `print!` that follows the current [`OutputStyle`]
+#[macro_export]
+macro_rules! styled_print {
+    ($($arg:tt)*) => {{
+        let text = format!($($arg)*);
+        $crate::output::suspend(|| print!("{}", $crate::output::styled(&text)))
+    }};
+}
+
+/// `eprintln!` that follows the current [`OutputStyle`]
+#[macro_export]
+macro_rules! styled_eprintln {
+    () => {
+        $crate::output::suspend(|| eprintln!())
+    };
+    ($($arg:tt)*) => {{
+        let text = format!($($arg)*);
+        $crate::output::suspend(|| eprintln!("{}", $crate::output::styled(&text)))
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn detect(vars: &[(&str, &str)], is_terminal: bool) -> OutputStyle {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        OutputStyle::detect_from(|name| vars.get(name).cloned(), is_terminal)
+    }
+
+    #[test]
+    fn test_detect_style() {
+        let utf8 = ("LANG", "en_US.UTF-8");
+        assert_eq!(detect(&[utf8], true), OutputStyle::decorated());
+        assert_eq!(detect(&[utf8], false), OutputStyle::plain());
+        assert_eq!(
+            detect(&[utf8, ("TERM", "dumb")], true),
+            OutputStyle::plain()
+        );
+        assert_eq!(
+            detect(&[utf8, ("NO_COLOR", "1")], true),
+            OutputStyle {
+                color: false,
+                unicode: true
+            }
+        );
+        assert_eq!(
+            detect(&[utf8, ("LC_ALL", "C")], true),
+            OutputStyle {
+                color: true,
+                unicode: false
+            }
+        );
+        assert_eq!(
+            detect(&[utf8, ("CLICOLOR_FORCE", "1")], false),
+            OutputStyle::decorated()
+        );
+        assert_eq!(
+            detect(&[utf8, ("CLICOLOR_FORCE", "1"), (PLAIN_ENV, "1")], true),
+            OutputStyle::plain()
+        );
+        assert_eq!(
+            detect(&[utf8, (PLAIN_ENV, "0")], true),
+            OutputStyle::decorated()
+        );
+    }
+
+    #[test]
+    fn test_to_ascii() {
+        assert_eq!(to_ascii("✅ Done"), "[ok] Done");
+        assert_eq!(to_ascii("⚠️  Careful"), "[warn]  Careful");
+        assert_eq!(to_ascii("🔄 Executing step 1"), "Executing step 1");
+        assert_eq!(to_ascii("\n📝 Claude log: x"), "\nClaude log: x");
+        assert_eq!(to_ascii("  🚀 Start"), "  Start");
+        assert_eq!(to_ascii("[1] 📊 Tokens"), "[1] Tokens");
+        assert_eq!(to_ascii("a → b • c"), "a -> b - c");
+        assert_eq!(to_ascii("▶ case"), "> case");
+        assert_eq!(to_ascii("[✓ COMPLETED]"), "[ok COMPLETED]");
+        assert_eq!(to_ascii("┌─ Box ─┐\n│ x │"), "+- Box -+\n| x |");
+        assert_eq!(to_ascii("👩‍💻 coder"), "coder");
+        assert_eq!(to_ascii("naïve 日本"), "naïve 日本");
+    }
+}
@@ -204,6 +204,9 @@ mod tests {
             error_recovery_state: None,
             retry_checkpoint_state: None,
             variable_checkpoint_state: None,
+            failure_chains: Vec::new(),
+            repo: None,
+            step_selection: None,
         };
 
         let messages = format_checkpoint_status(&checkpoint);
@@ -266,6 +269,9 @@ mod tests {
             error_recovery_state: None,
             retry_checkpoint_state: None,
             variable_checkpoint_state: None,
+            failure_chains: Vec::new(),
+            repo: None,
+            step_selection: None,
         };
 
         assert_eq!(calculate_skip_count(&checkpoint, false), 5);
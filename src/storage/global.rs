@@ -79,6 +79,28 @@ impl GlobalStorage {
         Ok(path)
     }
 
+    /// Get the global locks directory for a repository
+    pub async fn get_locks_dir(&self, repo_name: &str) -> Result<PathBuf> {
+        let path = self.base_dir.join("locks").join(repo_name);
+
+        fs::create_dir_all(&path)
+            .await
+            .context("Failed to create global locks directory")?;
+
+        Ok(path)
+    }
+
+    /// Get the event trigger directory for a repository
+    pub async fn get_triggers_dir(&self, repo_name: &str) -> Result<PathBuf> {
+        let path = self.base_dir.join("triggers").join(repo_name);
+
+        fs::create_dir_all(&path)
+            .await
+            .context("Failed to create global triggers directory")?;
+
+        Ok(path)
+    }
+
     /// List all job IDs with DLQ data for a repository
     pub async fn list_dlq_job_ids(&self, repo_name: &str) -> Result<Vec<String>> {
         let dlq_repo_dir = self.base_dir.join("dlq").join(repo_name);
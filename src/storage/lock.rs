@@ -3,6 +3,7 @@
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 use uuid::Uuid;
@@ -50,6 +51,15 @@ impl StorageLock {
         }
     }
 
+    /// Extension that moves the expiry to `ttl` after `now` (pure function)
+    ///
+    /// Extending by a fixed interval would let late renewals eat into the
+    /// margin until the lock expires under a live holder.
+    pub fn renewal(&self, ttl: Duration, now: DateTime<Utc>) -> Duration {
+        let held = (now - self.acquired_at).to_std().unwrap_or_default();
+        (held + ttl).saturating_sub(self.ttl)
+    }
+
     /// Remaining time before lock expires
     pub fn remaining_ttl(&self) -> Option<Duration> {
         match chrono::Duration::from_std(self.ttl) {
@@ -90,14 +100,27 @@ pub trait StorageLockGuard: Send + Sync {
 /// File-based lock guard implementation
 pub struct FileLockGuard {
     lock: StorageLock,
-    lock_file: std::path::PathBuf,
+    lock_file: PathBuf,
 }
 
 impl FileLockGuard {
     /// Create a new file lock guard
-    pub fn new(lock: StorageLock, lock_file: std::path::PathBuf) -> Self {
+    pub fn new(lock: StorageLock, lock_file: PathBuf) -> Self {
         Self { lock, lock_file }
     }
+
+    /// Whether the lock file still exists and was not taken over by another holder
+    ///
+    /// Files that are not lock JSON are treated as owned, so plain marker
+    /// files keep their remove-on-release behavior.
+    fn owns_lock_file(&self) -> bool {
+        match std::fs::read_to_string(&self.lock_file) {
+            Ok(content) => serde_json::from_str::<StorageLock>(&content)
+                .map(|lock| lock.token == self.lock.token)
+                .unwrap_or(true),
+            Err(_) => false,
+        }
+    }
 }
 
 #[async_trait]
@@ -107,6 +130,9 @@ impl StorageLockGuard for FileLockGuard {
     }
 
     async fn release(self: Box<Self>) -> StorageResult<()> {
+        if !self.owns_lock_file() {
+            return Ok(());
+        }
         tokio::fs::remove_file(&self.lock_file)
             .await
             .map_err(|e| StorageError::lock(format!("Failed to release lock: {}", e)))?;
@@ -114,15 +140,17 @@ impl StorageLockGuard for FileLockGuard {
     }
 
     async fn extend(&mut self, additional_ttl: Duration) -> StorageResult<()> {
-        self.lock.ttl += additional_ttl;
-
-        // Update lock file modification time
-        let _metadata = tokio::fs::metadata(&self.lock_file).await?;
-        let _modified = std::time::SystemTime::now();
+        // Refuse to extend a lock that was taken over after expiring
+        let current = read_lock_file(&self.lock_file).await?;
+        if current.as_ref().map(|lock| lock.token.as_str()) != Some(self.lock.token.as_str()) {
+            return Err(StorageError::lock(format!(
+                "Lock '{}' is no longer held by {}",
+                self.lock.key, self.lock.holder
+            )));
+        }
 
-        // Note: Setting modification time is platform-specific
-        // For now, we'll just update the lock info
-        Ok(())
+        self.lock.ttl += additional_ttl;
+        write_lock_file(&self.lock_file, &self.lock).await
     }
 
     async fn is_valid(&self) -> StorageResult<bool> {
@@ -138,7 +166,7 @@ impl StorageLockGuard for FileLockGuard {
 impl Drop for FileLockGuard {
     fn drop(&mut self) {
         // Best-effort cleanup in drop
-        if self.lock_file.exists() {
+        if self.owns_lock_file() {
             let _ = std::fs::remove_file(&self.lock_file);
         }
     }
@@ -223,3 +251,226 @@ pub trait LockBackend: Send + Sync {
     /// List all active locks
     async fn list_locks(&self) -> StorageResult<Vec<StorageLock>>;
 }
+
+/// Lock backend storing one JSON file per key in a shared directory
+///
+/// Files are created atomically, so processes sharing the directory (for
+/// example through `~/.prodigy`) exclude each other. A lock whose TTL has
+/// passed is treated as abandoned and can be taken over.
+pub struct FileLockBackend {
+    locks_dir: PathBuf,
+}
+
+impl FileLockBackend {
+    /// Create a backend storing lock files in `locks_dir`
+    pub fn new(locks_dir: PathBuf) -> StorageResult<Self> {
+        std::fs::create_dir_all(&locks_dir)?;
+        Ok(Self { locks_dir })
+    }
+
+    /// Path of the lock file for a key
+    pub fn lock_path(&self, key: &str) -> PathBuf {
+        let name: String = key
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        self.locks_dir.join(format!("{}.lock", name))
+    }
+
+    /// Current holder of a lock, if it exists and has not expired
+    pub async fn current(&self, key: &str) -> StorageResult<Option<StorageLock>> {
+        Ok(read_lock_file(&self.lock_path(key))
+            .await?
+            .filter(|lock| !lock.is_expired()))
+    }
+}
+
+#[async_trait]
+impl LockBackend for FileLockBackend {
+    async fn try_acquire(
+        &self,
+        key: &str,
+        holder: &str,
+        ttl: Duration,
+    ) -> StorageResult<Box<dyn StorageLockGuard>> {
+        let path = self.lock_path(key);
+        let lock = StorageLock::new(key.to_string(), holder.to_string(), ttl);
+
+        // Two attempts: the second one follows removal of an abandoned lock
+        for _ in 0..2 {
+            match tokio::fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&path)
+                .await
+            {
+                Ok(file) => {
+                    drop(file);
+                    write_lock_file(&path, &lock).await?;
+                    return Ok(Box::new(FileLockGuard::new(lock, path)));
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                    let existing = read_lock_file(&path).await?;
+                    let abandoned = match &existing {
+                        Some(existing) => existing.is_expired(),
+                        // Created but never written, e.g. the creator crashed
+                        None => file_age(&path).await > LOCK_WRITE_GRACE,
+                    };
+                    if !abandoned {
+                        let holder = existing
+                            .map(|l| l.holder)
+                            .unwrap_or_else(|| "another process".to_string());
+                        return Err(StorageError::conflict(format!(
+                            "Lock '{}' is held by {}",
+                            key, holder
+                        )));
+                    }
+                    remove_abandoned(&path, existing.as_ref(), &lock.token).await?;
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        Err(StorageError::conflict(format!(
+            "Lock '{}' was taken over",
+            key
+        )))
+    }
+
+    async fn exists(&self, key: &str) -> StorageResult<bool> {
+        Ok(self.current(key).await?.is_some())
+    }
+
+    async fn force_release(&self, key: &str) -> StorageResult<()> {
+        match tokio::fs::remove_file(self.lock_path(key)).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(StorageError::lock(format!("Failed to release lock: {}", e))),
+        }
+    }
+
+    async fn list_locks(&self) -> StorageResult<Vec<StorageLock>> {
+        let mut locks = Vec::new();
+        let mut entries = tokio::fs::read_dir(&self.locks_dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("lock") {
+                continue;
+            }
+            if let Some(lock) = read_lock_file(&path).await? {
+                if !lock.is_expired() {
+                    locks.push(lock);
+                }
+            }
+        }
+        Ok(locks)
+    }
+}
+
+/// How long a newly created, still empty lock file is left to its creator
+const LOCK_WRITE_GRACE: Duration = Duration::from_secs(5);
+
+/// Time since a file was last modified
+async fn file_age(path: &Path) -> Duration {
+    tokio::fs::metadata(path)
+        .await
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok())
+        .unwrap_or_default()
+}
+
+/// Remove an abandoned lock file unless another process replaced it first
+///
+/// The file is moved aside before checking it, so two processes taking over
+/// the same abandoned lock cannot delete each other's fresh lock.
+async fn remove_abandoned(
+    path: &Path,
+    abandoned: Option<&StorageLock>,
+    token: &str,
+) -> StorageResult<()> {
+    let aside = path.with_extension(format!("stale.{}", token));
+    match tokio::fs::rename(path, &aside).await {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e.into()),
+    }
+
+    let moved = read_lock_file(&aside).await?;
+    if moved.as_ref().map(|l| &l.token) != abandoned.map(|l| &l.token) {
+        // A fresh lock replaced the abandoned one; put it back
+        let _ = tokio::fs::rename(&aside, path).await;
+        return Err(StorageError::conflict(format!(
+            "Lock file {} was replaced concurrently",
+            path.display()
+        )));
+    }
+    let _ = tokio::fs::remove_file(&aside).await;
+    Ok(())
+}
+
+/// Read a lock file, returning `None` if it is missing or not fully written
+async fn read_lock_file(path: &Path) -> StorageResult<Option<StorageLock>> {
+    match tokio::fs::read_to_string(path).await {
+        Ok(content) => Ok(serde_json::from_str(&content).ok()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Replace a lock file's contents atomically
+async fn write_lock_file(path: &Path, lock: &StorageLock) -> StorageResult<()> {
+    let temp = path.with_extension(format!("lock.{}", lock.token));
+    tokio::fs::write(&temp, serde_json::to_vec(lock)?).await?;
+    tokio::fs::rename(&temp, path).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_file_lock_backend_excludes_and_expires() {
+        let dir = TempDir::new().unwrap();
+        let backend = FileLockBackend::new(dir.path().to_path_buf()).unwrap();
+
+        let mut guard = backend
+            .try_acquire("deploy/prod", "first", Duration::from_millis(200))
+            .await
+            .unwrap();
+        let err = backend
+            .try_acquire("deploy/prod", "second", Duration::from_secs(30))
+            .await
+            .err()
+            .unwrap();
+        assert!(err.is_conflict());
+        assert!(err.to_string().contains("held by first"));
+
+        guard.extend(Duration::from_millis(100)).await.unwrap();
+        let persisted = backend.current("deploy/prod").await.unwrap().unwrap();
+        assert_eq!(persisted.ttl, Duration::from_millis(300));
+        assert_eq!(backend.list_locks().await.unwrap().len(), 1);
+
+        // An expired lock is taken over, and its old guard can no longer extend it
+        tokio::time::sleep(Duration::from_millis(400)).await;
+        let second = backend
+            .try_acquire("deploy/prod", "second", Duration::from_secs(30))
+            .await
+            .unwrap();
+        assert_eq!(second.lock_info().holder, "second");
+        assert!(guard.extend(Duration::from_secs(1)).await.is_err());
+        drop(guard);
+        assert!(backend.exists("deploy/prod").await.unwrap());
+
+        second.release().await.unwrap();
+        assert!(!backend.exists("deploy/prod").await.unwrap());
+    }
+}
@@ -0,0 +1,262 @@
+//! Moving the storage directory
+//!
+//! `prodigy storage migrate <new_path>` moves events, DLQ items, checkpoints
+//! and session state to a new storage directory, e.g. off a small or
+//! network-mounted home disk. Everything is copied before anything is
+//! removed, so a failed migration leaves the old directory intact.
+
+use anyhow::{anyhow, bail, Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Entries that stay behind: the global config file is always read from
+/// `~/.prodigy/config.yml`, wherever storage lives.
+const KEPT_ENTRIES: &[&str] = &["config.yml"];
+
+/// What a migration will move
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationPlan {
+    /// Current storage directory
+    pub from: PathBuf,
+    /// New storage directory
+    pub to: PathBuf,
+    /// Top-level entries of `from` to move, by name
+    pub entries: Vec<String>,
+}
+
+/// Totals of a completed migration
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MigrationReport {
+    pub files: u64,
+    pub bytes: u64,
+}
+
+/// Check that storage can move from `from` to `to` and list what will move
+///
+/// Fails if the directories overlap, `to` already holds data, or `from` has
+/// worktrees: git records worktrees by path, so they must be merged or
+/// cleaned up first.
+pub fn plan_migration(from: &Path, to: &Path) -> Result<MigrationPlan> {
+    let from = std::path::absolute(from)?;
+    let to = std::path::absolute(to)?;
+
+    if !from.is_dir() {
+        bail!("Storage directory {} does not exist", from.display());
+    }
+    if to.starts_with(&from) || from.starts_with(&to) {
+        bail!(
+            "{} and {} overlap; choose a directory outside the current storage",
+            from.display(),
+            to.display()
+        );
+    }
+    if to.exists() {
+        let mut contents = fs::read_dir(&to)
+            .with_context(|| format!("{} is not a readable directory", to.display()))?;
+        if contents.next().is_some() {
+            bail!("{} is not empty", to.display());
+        }
+    }
+
+    let worktrees = active_worktrees(&from.join("worktrees"))?;
+    if !worktrees.is_empty() {
+        bail!(
+            "{} worktree(s) still exist under {}:\n  {}\n\
+             Merge or remove them (`prodigy worktree clean`) before migrating",
+            worktrees.len(),
+            from.join("worktrees").display(),
+            worktrees.join("\n  ")
+        );
+    }
+
+    let mut entries = Vec::new();
+    for entry in fs::read_dir(&from)? {
+        let name = entry?.file_name().to_string_lossy().into_owned();
+        if !KEPT_ENTRIES.contains(&name.as_str()) {
+            entries.push(name);
+        }
+    }
+    entries.sort();
+
+    Ok(MigrationPlan { from, to, entries })
+}
+
+/// Copy every planned entry to the new directory, then remove the originals
+///
+/// If copying fails, the partial copy is removed and the old directory is
+/// left untouched.
+pub fn execute_migration(plan: &MigrationPlan) -> Result<MigrationReport> {
+    fs::create_dir_all(&plan.to)
+        .with_context(|| format!("Failed to create {}", plan.to.display()))?;
+
+    let mut report = MigrationReport::default();
+    for name in &plan.entries {
+        let copied = copy_entry(&plan.from.join(name), &plan.to.join(name), &mut report);
+        if let Err(e) = copied {
+            for name in &plan.entries {
+                let _ = remove_entry(&plan.to.join(name));
+            }
+            return Err(e.context(format!(
+                "Migration aborted; {} is unchanged",
+                plan.from.display()
+            )));
+        }
+    }
+
+    for name in &plan.entries {
+        let original = plan.from.join(name);
+        remove_entry(&original).with_context(|| {
+            format!(
+                "Copied to {}, but failed to remove {}",
+                plan.to.display(),
+                original.display()
+            )
+        })?;
+    }
+    Ok(report)
+}
+
+/// Worktree directories under `worktrees/<repo>/`, ignoring Prodigy's own
+/// metadata files
+fn active_worktrees(worktrees_dir: &Path) -> Result<Vec<String>> {
+    let mut found = Vec::new();
+    let Ok(repos) = fs::read_dir(worktrees_dir) else {
+        return Ok(found);
+    };
+    for repo in repos {
+        let repo = repo?;
+        if !repo.file_type()?.is_dir() {
+            continue;
+        }
+        for worktree in fs::read_dir(repo.path())? {
+            let worktree = worktree?;
+            let name = worktree.file_name().to_string_lossy().into_owned();
+            if worktree.file_type()?.is_dir() && !name.starts_with('.') {
+                found.push(worktree.path().display().to_string());
+            }
+        }
+    }
+    found.sort();
+    Ok(found)
+}
+
+fn copy_entry(from: &Path, to: &Path, report: &mut MigrationReport) -> Result<()> {
+    let file_type = fs::symlink_metadata(from)?.file_type();
+    if file_type.is_dir() {
+        fs::create_dir_all(to)?;
+        for entry in fs::read_dir(from)? {
+            let entry = entry?;
+            copy_entry(&entry.path(), &to.join(entry.file_name()), report)?;
+        }
+    } else if file_type.is_symlink() {
+        copy_symlink(from, to)?;
+    } else {
+        let expected = fs::metadata(from)?.len();
+        let copied =
+            fs::copy(from, to).with_context(|| format!("Failed to copy {}", from.display()))?;
+        if copied != expected {
+            return Err(anyhow!(
+                "Incomplete copy of {}: {} of {} bytes",
+                from.display(),
+                copied,
+                expected
+            ));
+        }
+        report.files += 1;
+        report.bytes += copied;
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn copy_symlink(from: &Path, to: &Path) -> Result<()> {
+    std::os::unix::fs::symlink(fs::read_link(from)?, to)?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn copy_symlink(from: &Path, to: &Path) -> Result<()> {
+    fs::copy(from, to)?;
+    Ok(())
+}
+
+fn remove_entry(path: &Path) -> std::io::Result<()> {
+    match fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.is_dir() => fs::remove_dir_all(path),
+        Ok(_) => fs::remove_file(path),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn storage_with_data(root: &Path) -> PathBuf {
+        let storage = root.join("old");
+        fs::create_dir_all(storage.join("events/repo/job-1")).unwrap();
+        fs::write(storage.join("events/repo/job-1/events-1.jsonl"), "{}\n").unwrap();
+        fs::create_dir_all(storage.join("dlq/repo")).unwrap();
+        fs::write(storage.join("dlq/repo/item.json"), "{\"id\":1}").unwrap();
+        fs::write(storage.join("config.yml"), "log_level: info\n").unwrap();
+        storage
+    }
+
+    #[test]
+    fn test_migration_moves_data_and_keeps_config() {
+        let temp = TempDir::new().unwrap();
+        let from = storage_with_data(temp.path());
+        let to = temp.path().join("new");
+
+        let plan = plan_migration(&from, &to).unwrap();
+        assert_eq!(plan.entries, vec!["dlq", "events"]);
+
+        let report = execute_migration(&plan).unwrap();
+        assert_eq!(report.files, 2);
+        assert_eq!(
+            fs::read_to_string(to.join("events/repo/job-1/events-1.jsonl")).unwrap(),
+            "{}\n"
+        );
+        assert!(to.join("dlq/repo/item.json").exists());
+        assert!(!from.join("events").exists());
+        assert!(!from.join("dlq").exists());
+        assert!(from.join("config.yml").exists());
+        assert!(!to.join("config.yml").exists());
+    }
+
+    #[test]
+    fn test_plan_rejects_unsafe_targets() {
+        let temp = TempDir::new().unwrap();
+        let from = storage_with_data(temp.path());
+
+        assert!(plan_migration(&from, &from.join("nested")).is_err());
+        assert!(plan_migration(&from, temp.path()).is_err());
+
+        let occupied = temp.path().join("occupied");
+        fs::create_dir_all(&occupied).unwrap();
+        fs::write(occupied.join("file"), "x").unwrap();
+        assert!(plan_migration(&from, &occupied)
+            .unwrap_err()
+            .to_string()
+            .contains("not empty"));
+
+        let empty = temp.path().join("empty");
+        fs::create_dir_all(&empty).unwrap();
+        assert!(plan_migration(&from, &empty).is_ok());
+    }
+
+    #[test]
+    fn test_plan_refuses_to_move_worktrees() {
+        let temp = TempDir::new().unwrap();
+        let from = storage_with_data(temp.path());
+        fs::create_dir_all(from.join("worktrees/repo/.metadata")).unwrap();
+        fs::write(from.join("worktrees/repo/.gitignore"), ".metadata/\n").unwrap();
+        assert!(plan_migration(&from, &temp.path().join("new")).is_ok());
+
+        fs::create_dir_all(from.join("worktrees/repo/session-1")).unwrap();
+        let err = plan_migration(&from, &temp.path().join("new")).unwrap_err();
+        assert!(err.to_string().contains("session-1"));
+    }
+}
@@ -9,6 +9,7 @@ pub mod error;
 pub mod factory;
 pub mod global;
 pub mod lock;
+pub mod migrate;
 pub mod session_job_mapping;
 pub mod types;
 
@@ -58,9 +59,31 @@ pub fn extract_repo_name(repo_path: &Path) -> Result<String> {
         })
 }
 
+/// Environment variable that overrides the storage directory
+///
+/// `prodigy --storage-dir <DIR>` sets it, so subprocesses use the same storage.
+pub const STORAGE_DIR_ENV: &str = "PRODIGY_STORAGE_DIR";
+
+/// Storage directory chosen by the user, if any
+///
+/// `PRODIGY_STORAGE_DIR` takes precedence over `storage.base_path` in the
+/// global or project configuration, which is read on each call.
+pub fn configured_storage_dir() -> Option<PathBuf> {
+    std::env::var_os(STORAGE_DIR_ENV)
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| {
+            crate::config::load_prodigy_config()
+                .ok()
+                .and_then(|config| config.storage.base_path.clone())
+        })
+}
+
 /// Get the default storage directory (~/.prodigy)
 ///
-/// Can be overridden with the PRODIGY_HOME environment variable for testing.
+/// Can be overridden with the PRODIGY_HOME environment variable for testing,
+/// or with `--storage-dir`, `PRODIGY_STORAGE_DIR` or `storage.base_path` in
+/// the configuration.
 pub fn get_default_storage_dir() -> Result<PathBuf> {
     // Check for PRODIGY_HOME environment variable first
     if let Ok(prodigy_home) = std::env::var("PRODIGY_HOME") {
@@ -93,6 +116,16 @@ pub fn get_default_storage_dir() -> Result<PathBuf> {
 
     #[cfg(not(test))]
     {
+        if let Some(base_path) = configured_storage_dir() {
+            std::fs::create_dir_all(&base_path).with_context(|| {
+                format!(
+                    "Failed to create storage directory: {}",
+                    base_path.display()
+                )
+            })?;
+            return Ok(base_path);
+        }
+
         directories::BaseDirs::new()
             .ok_or_else(|| anyhow!("Could not determine base directories"))
             .map(|dirs| dirs.home_dir().join(".prodigy"))
@@ -0,0 +1,167 @@
+//! Prodigy job and session metadata exported to every subprocess
+//!
+//! External scripts and Claude commands need to correlate their own logs
+//! with Prodigy state. The executors run each step inside [`scope`] with the
+//! session, MapReduce job, work item, worktree and step index it belongs to,
+//! and the process runners add them to the environment of every command they
+//! spawn:
+//!
+//! | Variable | Set for |
+//! |----------|---------|
+//! | `PRODIGY_SESSION_ID` | every workflow step |
+//! | `PRODIGY_JOB_ID` | MapReduce setup, agent and reduce steps |
+//! | `PRODIGY_ITEM_ID` | MapReduce agent steps |
+//! | `PRODIGY_WORKTREE` | steps running in a Prodigy worktree |
+//! | `PRODIGY_STEP_INDEX` | every step, counting from 0 within its phase |
+//!
+//! A command's own environment takes precedence over these values.
+
+use std::collections::HashMap;
+use std::future::Future;
+
+/// Environment variable naming the Prodigy session
+pub const SESSION_ID_ENV: &str = "PRODIGY_SESSION_ID";
+
+/// Environment variable naming the MapReduce job
+pub const JOB_ID_ENV: &str = "PRODIGY_JOB_ID";
+
+/// Environment variable naming the work item being processed
+pub const ITEM_ID_ENV: &str = "PRODIGY_ITEM_ID";
+
+/// Environment variable naming the worktree the step runs in
+pub const WORKTREE_ENV: &str = "PRODIGY_WORKTREE";
+
+/// Environment variable holding the index of the running step
+pub const STEP_INDEX_ENV: &str = "PRODIGY_STEP_INDEX";
+
+tokio::task_local! {
+    static METADATA: ProcessMetadata;
+}
+
+/// What a subprocess is running on behalf of
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProcessMetadata {
+    pub session_id: Option<String>,
+    pub job_id: Option<String>,
+    pub item_id: Option<String>,
+    pub worktree: Option<String>,
+    pub step_index: Option<usize>,
+}
+
+impl ProcessMetadata {
+    /// Metadata for a step of the given session
+    pub fn new(session_id: impl Into<String>) -> Self {
+        Self {
+            session_id: Some(session_id.into()),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_job_id(mut self, job_id: Option<impl Into<String>>) -> Self {
+        self.job_id = job_id.map(Into::into);
+        self
+    }
+
+    pub fn with_item_id(mut self, item_id: impl Into<String>) -> Self {
+        self.item_id = Some(item_id.into());
+        self
+    }
+
+    pub fn with_worktree(mut self, worktree: Option<impl Into<String>>) -> Self {
+        self.worktree = worktree.map(Into::into);
+        self
+    }
+
+    pub fn with_step_index(mut self, step_index: usize) -> Self {
+        self.step_index = Some(step_index);
+        self
+    }
+
+    /// The environment variables for this metadata (pure function)
+    pub fn env_vars(&self) -> Vec<(&'static str, String)> {
+        [
+            (SESSION_ID_ENV, self.session_id.clone()),
+            (JOB_ID_ENV, self.job_id.clone()),
+            (ITEM_ID_ENV, self.item_id.clone()),
+            (WORKTREE_ENV, self.worktree.clone()),
+            (STEP_INDEX_ENV, self.step_index.map(|i| i.to_string())),
+        ]
+        .into_iter()
+        .filter_map(|(key, value)| value.filter(|v| !v.is_empty()).map(|v| (key, v)))
+        .collect()
+    }
+
+    /// Add the metadata to `env` without overriding variables it already sets
+    pub fn fill_env(&self, env: &mut HashMap<String, String>) {
+        for (key, value) in self.env_vars() {
+            env.entry(key.to_string()).or_insert(value);
+        }
+    }
+}
+
+/// Run `future` with `metadata` exported to the subprocesses it spawns
+pub async fn scope<F: Future>(metadata: ProcessMetadata, future: F) -> F::Output {
+    METADATA.scope(metadata, future).await
+}
+
+/// The metadata of the step currently running, if any
+pub fn current() -> Option<ProcessMetadata> {
+    METADATA.try_with(Clone::clone).ok()
+}
+
+/// `env` with the current step's metadata filled in
+pub fn with_current(env: &HashMap<String, String>) -> HashMap<String, String> {
+    let mut env = env.clone();
+    if let Some(metadata) = current() {
+        metadata.fill_env(&mut env);
+    }
+    env
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_env_vars_skip_unset_fields() {
+        let metadata = ProcessMetadata::new("session-1")
+            .with_job_id(Some("mapreduce-1"))
+            .with_worktree(None::<String>)
+            .with_step_index(2);
+
+        assert_eq!(
+            metadata.env_vars(),
+            vec![
+                (SESSION_ID_ENV, "session-1".to_string()),
+                (JOB_ID_ENV, "mapreduce-1".to_string()),
+                (STEP_INDEX_ENV, "2".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fill_env_keeps_explicit_values() {
+        let mut env = HashMap::from([(ITEM_ID_ENV.to_string(), "explicit".to_string())]);
+        ProcessMetadata::new("session-1")
+            .with_item_id("item-0")
+            .fill_env(&mut env);
+
+        assert_eq!(env[ITEM_ID_ENV], "explicit");
+        assert_eq!(env[SESSION_ID_ENV], "session-1");
+    }
+
+    #[tokio::test]
+    async fn test_scope_sets_current() {
+        assert_eq!(current(), None);
+
+        let metadata = ProcessMetadata::new("session-1").with_step_index(0);
+        let env = scope(metadata.clone(), async {
+            assert_eq!(current(), Some(metadata));
+            with_current(&HashMap::new())
+        })
+        .await;
+
+        assert_eq!(env[STEP_INDEX_ENV], "0");
+        assert_eq!(current(), None);
+    }
+}
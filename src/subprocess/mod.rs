@@ -47,9 +47,11 @@ pub mod builder;
 pub mod claude;
 pub mod error;
 pub mod git;
+pub mod metadata;
 pub mod mock;
 pub mod runner;
 pub mod streaming;
+pub mod usage;
 
 #[cfg(test)]
 mod tests;
@@ -67,6 +69,7 @@ pub use builder::ProcessCommandBuilder;
 pub use claude::ClaudeRunner;
 pub use error::ProcessError;
 pub use git::GitRunner;
+pub use metadata::ProcessMetadata;
 pub use mock::{MockCommandConfig, MockProcessRunner};
 pub use runner::ProcessCommand;
 pub use runner::{ExitStatusHelper, ProcessOutput, ProcessRunner, ProcessStream};
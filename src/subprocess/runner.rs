@@ -76,6 +76,22 @@ pub struct ProcessStream {
 pub trait ProcessRunner: Send + Sync {
     async fn run(&self, command: ProcessCommand) -> Result<ProcessOutput, ProcessError>;
     async fn run_streaming(&self, command: ProcessCommand) -> Result<ProcessStream, ProcessError>;
+
+    /// Run a command, passing each stdout line to `on_line` as it is produced
+    ///
+    /// The default implementation runs the command to completion and then
+    /// replays its stdout, so runners without live output still see every line.
+    async fn run_with_line_handler(
+        &self,
+        command: ProcessCommand,
+        on_line: &(dyn for<'a> Fn(&'a str) + Send + Sync),
+    ) -> Result<ProcessOutput, ProcessError> {
+        let output = self.run(command).await?;
+        for line in output.stdout.lines() {
+            on_line(line);
+        }
+        Ok(output)
+    }
 }
 
 pub struct TokioProcessRunner;
@@ -222,6 +238,10 @@ impl TokioProcessRunner {
 
         cmd.args(&command.args);
 
+        // Cancelled commands (timeouts, chaos kills, losing speculative agents)
+        // must not outlive the future that spawned them
+        cmd.kill_on_drop(true);
+
         // Clear inherited environment to prevent "Argument list too long" errors
         // when parent process has accumulated many environment variables from MapReduce
         cmd.env_clear();
@@ -230,6 +250,13 @@ impl TokioProcessRunner {
         // This MUST succeed - if PATH is missing, fail loudly rather than spawn with no PATH
         Self::preserve_essential_env(&mut cmd, &command.program)?;
 
+        // Export the running step's session, job and item metadata
+        if let Some(metadata) = super::metadata::current() {
+            for (key, value) in metadata.env_vars() {
+                cmd.env(key, value);
+            }
+        }
+
         // Add explicitly specified environment variables (these take precedence)
         for (key, value) in &command.env {
             cmd.env(key, value);
@@ -626,6 +653,83 @@ impl ProcessRunner for TokioProcessRunner {
         Ok(result)
     }
 
+    async fn run_with_line_handler(
+        &self,
+        command: ProcessCommand,
+        on_line: &(dyn for<'a> Fn(&'a str) + Send + Sync),
+    ) -> Result<ProcessOutput, ProcessError> {
+        use tokio::io::{AsyncBufReadExt, AsyncReadExt, BufReader};
+
+        let start = std::time::Instant::now();
+        Self::log_command_start(&command);
+
+        let mut cmd = Self::configure_command(&command)?;
+        let mut child = cmd
+            .spawn()
+            .map_err(|e| Self::map_spawn_error(e, &command.program))?;
+
+        if let Some(stdin_data) = &command.stdin {
+            Self::write_stdin(&mut child, stdin_data).await?;
+        }
+
+        let stdout = Self::extract_stream(child.stdout.take(), "stdout")?;
+        let stderr = child.stderr.take();
+
+        let collect = async {
+            let read_stdout = async {
+                let mut reader = BufReader::new(stdout);
+                let mut collected = Vec::new();
+                loop {
+                    let start = collected.len();
+                    let read = reader
+                        .read_until(b'\n', &mut collected)
+                        .await
+                        .map_err(ProcessError::Io)?;
+                    if read == 0 {
+                        break;
+                    }
+                    let line = String::from_utf8_lossy(&collected[start..]);
+                    on_line(line.trim_end_matches(['\n', '\r']));
+                }
+                Ok::<_, ProcessError>(collected)
+            };
+            let read_stderr = async {
+                let mut collected = Vec::new();
+                if let Some(mut stderr) = stderr {
+                    stderr
+                        .read_to_end(&mut collected)
+                        .await
+                        .map_err(ProcessError::Io)?;
+                }
+                Ok::<_, ProcessError>(collected)
+            };
+            let (stdout, stderr) = tokio::try_join!(read_stdout, read_stderr)?;
+            let status = child.wait().await.map_err(ProcessError::Io)?;
+            Ok::<_, ProcessError>((stdout, stderr, status))
+        };
+
+        let (stdout, stderr, status) = match command.timeout {
+            Some(duration) => tokio::time::timeout(duration, collect)
+                .await
+                .map_err(|_| ProcessError::Timeout(duration))??,
+            None => collect.await?,
+        };
+
+        let result = ProcessOutput {
+            status: Self::parse_exit_status(status),
+            stdout: String::from_utf8_lossy(&stdout).to_string(),
+            stderr: if command.suppress_stderr {
+                String::new()
+            } else {
+                String::from_utf8_lossy(&stderr).to_string()
+            },
+            duration: start.elapsed(),
+        };
+        Self::log_result(&result, &command);
+
+        Ok(result)
+    }
+
     async fn run_streaming(&self, command: ProcessCommand) -> Result<ProcessStream, ProcessError> {
         // Log command execution
         Self::log_command_start(&command);
@@ -801,4 +905,25 @@ mod tests {
             _ => panic!("Expected Error status"),
         }
     }
+
+    #[tokio::test]
+    async fn test_run_with_line_handler_sees_each_stdout_line() {
+        let mut command = test_command();
+        command.program = "sh".to_string();
+        command.args = vec![
+            "-c".to_string(),
+            "echo one; echo oops >&2; printf 'two'; exit 3".to_string(),
+        ];
+
+        let seen = std::sync::Mutex::new(Vec::new());
+        let output = TokioProcessRunner
+            .run_with_line_handler(command, &|line| seen.lock().unwrap().push(line.to_string()))
+            .await
+            .unwrap();
+
+        assert_eq!(*seen.lock().unwrap(), vec!["one", "two"]);
+        assert_eq!(output.stdout, "one\ntwo");
+        assert_eq!(output.stderr, "oops\n");
+        assert_eq!(output.status, super::ExitStatus::Error(3));
+    }
 }
@@ -33,8 +33,8 @@ impl StreamingCommandRunner {
         let mut cmd = Command::new(&command.program);
         cmd.args(&command.args);
 
-        // Set environment variables
-        for (key, value) in &command.env {
+        // Set environment variables, including the running step's metadata
+        for (key, value) in &crate::subprocess::metadata::with_current(&command.env) {
             cmd.env(key, value);
         }
 
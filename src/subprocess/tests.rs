@@ -204,6 +204,28 @@ mod subprocess_tests {
         std::env::remove_var("PRODIGY_TEST_BLOATED_VAR");
     }
 
+    #[tokio::test]
+    async fn test_step_metadata_exported() {
+        let runner = runner::TokioProcessRunner;
+        let command = ProcessCommandBuilder::new("sh")
+            .args([
+                "-c",
+                "echo $PRODIGY_JOB_ID $PRODIGY_ITEM_ID $PRODIGY_STEP_INDEX",
+            ])
+            .env("PRODIGY_ITEM_ID", "explicit")
+            .build();
+
+        let metadata = ProcessMetadata::new("session-1")
+            .with_job_id(Some("mapreduce-1"))
+            .with_item_id("item-0")
+            .with_step_index(3);
+        let output = metadata::scope(metadata, runner.run(command))
+            .await
+            .unwrap();
+
+        assert_eq!(output.stdout.trim(), "mapreduce-1 explicit 3");
+    }
+
     #[tokio::test]
     async fn test_essential_env_vars_preserved() {
         let runner = runner::TokioProcessRunner;
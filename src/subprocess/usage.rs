@@ -0,0 +1,184 @@
+//! Resource usage of subprocesses
+//!
+//! [`UsageMonitor`] measures what the child processes started while it is
+//! open consume:
+//!
+//! - CPU time is the growth of `getrusage(RUSAGE_CHILDREN)`, which counts
+//!   every descendant once it has been waited for.
+//! - Peak resident memory is sampled from the live process tree. A new
+//!   `RUSAGE_CHILDREN` high-water mark also counts, so short-lived processes
+//!   that peak between samples are still seen.
+//!
+//! Usage is process-wide, so monitors that overlap in time each see the
+//! usage of both. This makes the measurements exact for sequential steps and
+//! an upper bound when commands run in parallel.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+
+/// How often the process tree is sampled for its memory
+pub const SAMPLE_INTERVAL: Duration = Duration::from_millis(250);
+
+/// CPU time and peak memory used by subprocesses
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ResourceUsage {
+    /// User plus system CPU time
+    pub cpu_time: Duration,
+    /// Largest resident set size seen, in bytes
+    pub peak_rss_bytes: u64,
+}
+
+/// Measures subprocess resource usage from creation until [`UsageMonitor::finish`]
+pub struct UsageMonitor {
+    start: ChildrenUsage,
+    peak: Arc<AtomicU64>,
+    sampler: Option<tokio::task::JoinHandle<()>>,
+}
+
+impl UsageMonitor {
+    /// Start measuring, sampling memory when a Tokio runtime is available
+    pub fn start() -> Self {
+        let peak = Arc::new(AtomicU64::new(0));
+        let sampler = tokio::runtime::Handle::try_current()
+            .ok()
+            .map(|handle| handle.spawn(sample_tree_memory(Arc::clone(&peak))));
+        Self {
+            start: ChildrenUsage::now(),
+            peak,
+            sampler,
+        }
+    }
+
+    /// Stop measuring and return the usage since the monitor started
+    pub fn finish(mut self) -> ResourceUsage {
+        self.stop_sampler();
+        let end = ChildrenUsage::now();
+        let mut peak_rss_bytes = self.peak.load(Ordering::Relaxed);
+        if end.max_rss_bytes > self.start.max_rss_bytes {
+            peak_rss_bytes = peak_rss_bytes.max(end.max_rss_bytes);
+        }
+        ResourceUsage {
+            cpu_time: end.cpu_time.saturating_sub(self.start.cpu_time),
+            peak_rss_bytes,
+        }
+    }
+
+    fn stop_sampler(&mut self) {
+        if let Some(sampler) = self.sampler.take() {
+            sampler.abort();
+        }
+    }
+}
+
+impl Drop for UsageMonitor {
+    fn drop(&mut self) {
+        self.stop_sampler();
+    }
+}
+
+/// Cumulative usage of the children waited for so far
+#[derive(Debug, Clone, Copy, Default)]
+struct ChildrenUsage {
+    cpu_time: Duration,
+    max_rss_bytes: u64,
+}
+
+impl ChildrenUsage {
+    fn now() -> Self {
+        use nix::sys::resource::{getrusage, UsageWho};
+
+        let Ok(usage) = getrusage(UsageWho::RUSAGE_CHILDREN) else {
+            return Self::default();
+        };
+        let to_duration = |tv: nix::sys::time::TimeVal| {
+            Duration::from_secs(tv.tv_sec().max(0) as u64)
+                + Duration::from_micros(tv.tv_usec().max(0) as u64)
+        };
+        // Linux reports the maximum RSS in kilobytes, macOS in bytes
+        let max_rss = usage.max_rss().max(0) as u64;
+        let max_rss_bytes = if cfg!(target_os = "macos") {
+            max_rss
+        } else {
+            max_rss * 1024
+        };
+        Self {
+            cpu_time: to_duration(usage.user_time()) + to_duration(usage.system_time()),
+            max_rss_bytes,
+        }
+    }
+}
+
+/// Record the largest memory of this process's descendants until aborted
+async fn sample_tree_memory(peak: Arc<AtomicU64>) {
+    let root = Pid::from_u32(std::process::id());
+    let mut system = System::new();
+    let mut interval = tokio::time::interval(SAMPLE_INTERVAL);
+    loop {
+        interval.tick().await;
+        system.refresh_processes_specifics(
+            ProcessesToUpdate::All,
+            true,
+            ProcessRefreshKind::nothing().with_memory(),
+        );
+        let parents: HashMap<Pid, Pid> = system
+            .processes()
+            .iter()
+            .filter_map(|(pid, process)| Some((*pid, process.parent()?)))
+            .collect();
+        let total: u64 = descendants(&parents, root)
+            .iter()
+            .filter_map(|pid| system.process(*pid))
+            .map(|process| process.memory())
+            .sum();
+        peak.fetch_max(total, Ordering::Relaxed);
+    }
+}
+
+/// All processes below `root` in a child-to-parent map (pure function)
+fn descendants(parents: &HashMap<Pid, Pid>, root: Pid) -> HashSet<Pid> {
+    let mut found = HashSet::new();
+    let mut frontier = vec![root];
+    while let Some(parent) = frontier.pop() {
+        for (child, _) in parents.iter().filter(|(_, p)| **p == parent) {
+            if found.insert(*child) {
+                frontier.push(*child);
+            }
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_descendants_walks_process_tree() {
+        let pid = Pid::from_u32;
+        let parents: HashMap<Pid, Pid> = [(2, 1), (3, 2), (4, 3), (5, 9), (6, 1)]
+            .into_iter()
+            .map(|(child, parent)| (pid(child), pid(parent)))
+            .collect();
+
+        let found = descendants(&parents, pid(1));
+        let expected: HashSet<Pid> = [2, 3, 4, 6].into_iter().map(pid).collect();
+        assert_eq!(found, expected);
+        assert!(descendants(&parents, pid(4)).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_monitor_measures_child_cpu_time() {
+        let monitor = UsageMonitor::start();
+        let status = tokio::process::Command::new("sh")
+            .args(["-c", "i=0; while [ $i -lt 100000 ]; do i=$((i+1)); done"])
+            .status()
+            .await
+            .unwrap();
+        assert!(status.success());
+
+        assert!(monitor.finish().cpu_time > Duration::ZERO);
+    }
+}
@@ -191,6 +191,16 @@ impl GitOperations for MockGitOperations {
         self.git_command(args, description).await
     }
 
+    async fn git_command_with_index(
+        &self,
+        args: &[&str],
+        description: &str,
+        _working_dir: &std::path::Path,
+        _index_file: &std::path::Path,
+    ) -> Result<std::process::Output> {
+        self.git_command(args, description).await
+    }
+
     async fn get_last_commit_message(&self) -> Result<String> {
         let messages = self.commit_messages.lock().unwrap();
         Ok(messages
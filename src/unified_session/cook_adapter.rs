@@ -1,6 +1,8 @@
 //! Adapter to bridge cook module with unified session management
 
 use super::{
+    environment::{EnvironmentSnapshot, ENVIRONMENT_METADATA_KEY},
+    heartbeat::{self, HeartbeatTask, SessionLock},
     manager::{SessionManager as UnifiedSessionManager, SessionUpdate as UnifiedSessionUpdate},
     state::{SessionConfig, SessionId, SessionStatus, SessionType, UnifiedSession},
 };
@@ -22,6 +24,12 @@ pub struct CookSessionAdapter {
     working_dir: std::path::PathBuf,
     /// Cached session state for synchronous access
     cached_state: Arc<Mutex<Option<CookSessionState>>>,
+    /// Heartbeats for the current session while it is running
+    heartbeat: Mutex<Option<HeartbeatTask>>,
+    /// Claim on the current session while it is running
+    session_lock: Mutex<Option<SessionLock>>,
+    /// Directory holding session locks
+    locks_dir: std::path::PathBuf,
 }
 
 impl CookSessionAdapter {
@@ -30,15 +38,53 @@ impl CookSessionAdapter {
         working_dir: std::path::PathBuf,
         storage: crate::storage::GlobalStorage,
     ) -> Result<Self> {
+        let locks_dir = heartbeat::session_locks_dir(storage.base_dir());
         let unified_manager = Arc::new(UnifiedSessionManager::new(storage).await?);
         Ok(Self {
             unified_manager,
             current_session: Mutex::new(None),
             working_dir,
             cached_state: Arc::new(Mutex::new(None)),
+            heartbeat: Mutex::new(None),
+            session_lock: Mutex::new(None),
+            locks_dir,
         })
     }
 
+    /// Claim the session before it starts running, failing if another process runs it
+    async fn lock_session(&self, id: &SessionId) -> Result<()> {
+        let mut session_lock = self.session_lock.lock().await;
+        if session_lock.is_none() {
+            *session_lock = Some(SessionLock::acquire(self.locks_dir.clone(), id.as_str()).await?);
+        }
+        Ok(())
+    }
+
+    /// Start or stop heartbeats as the session starts or stops running
+    async fn set_heartbeat(&self, id: &SessionId, running: bool) {
+        let mut heartbeat = self.heartbeat.lock().await;
+        if !running {
+            *heartbeat = None;
+            *self.session_lock.lock().await = None;
+        } else if heartbeat.is_none() {
+            *heartbeat = Some(heartbeat::spawn(
+                Arc::clone(&self.unified_manager),
+                id.clone(),
+            ));
+        }
+    }
+
+    /// Whether an update starts (`Some(true)`) or stops (`Some(false)`) the session running
+    fn running_after(update: &CookSessionUpdate) -> Option<bool> {
+        match update {
+            CookSessionUpdate::UpdateStatus(status) => {
+                Some(*status == CookSessionStatus::InProgress)
+            }
+            CookSessionUpdate::MarkInterrupted => Some(false),
+            _ => None,
+        }
+    }
+
     /// Update the cached state
     async fn update_cached_state(&self) -> Result<()> {
         if let Some(id) = &*self.current_session.lock().await {
@@ -156,6 +202,10 @@ impl CookSessionAdapter {
             CookSessionUpdate::AddError(error) => {
                 vec![UnifiedSessionUpdate::Error(error)]
             }
+            CookSessionUpdate::SetMetadata(key, value) => {
+                let metadata = std::collections::HashMap::from([(key, value)]);
+                vec![UnifiedSessionUpdate::Metadata(metadata)]
+            }
             _ => vec![],
         }
     }
@@ -186,18 +236,25 @@ impl CookSessionManager for CookSessionAdapter {
             id
         } else {
             // Create new session if it doesn't exist
+            let mut metadata = std::collections::HashMap::new();
+            metadata.insert(
+                ENVIRONMENT_METADATA_KEY.to_string(),
+                EnvironmentSnapshot::capture().await.to_metadata_value(),
+            );
             let config = SessionConfig {
                 session_type: SessionType::Workflow,
                 workflow_id: Some(session_id.to_string()),
                 workflow_name: None,
                 job_id: None,
-                metadata: Default::default(),
+                metadata,
             };
             self.unified_manager.create_session(config).await?
         };
 
+        self.lock_session(&final_id).await?;
         *self.current_session.lock().await = Some(final_id.clone());
         self.unified_manager.start_session(&final_id).await?;
+        self.set_heartbeat(&final_id, true).await;
 
         // Update cached state
         self.update_cached_state().await?;
@@ -210,12 +267,21 @@ impl CookSessionManager for CookSessionAdapter {
             return Ok(());
         };
 
+        let running = Self::running_after(&update);
         let unified_updates = Self::cook_update_to_unified(update);
-        self.apply_unified_updates(id, unified_updates).await
+        self.apply_unified_updates(id, unified_updates).await?;
+        if let Some(running) = running {
+            if running {
+                self.lock_session(id).await?;
+            }
+            self.set_heartbeat(id, running).await;
+        }
+        Ok(())
     }
 
     async fn complete_session(&self) -> Result<CookSessionSummary> {
         if let Some(id) = &*self.current_session.lock().await {
+            self.set_heartbeat(id, false).await;
             let session = self.unified_manager.load_session(id).await?;
             let _ = self.unified_manager.complete_session(id, true).await?;
 
@@ -499,4 +565,31 @@ mod tests {
             .await
             .unwrap();
     }
+
+    #[tokio::test]
+    async fn test_heartbeat_while_running() {
+        let (adapter, _temp) = create_test_adapter().await;
+        adapter.start_session("session-test-789").await.unwrap();
+        assert!(adapter.heartbeat.lock().await.is_some());
+        assert!(adapter.session_lock.lock().await.is_some());
+
+        let id = SessionId::from_string("session-test-789".to_string());
+        let mut recorded = None;
+        for _ in 0..50 {
+            let session = adapter.unified_manager.load_session(&id).await.unwrap();
+            recorded = heartbeat::Heartbeat::from_metadata(&session.metadata);
+            if recorded.is_some() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+        assert_eq!(recorded.unwrap().pid, std::process::id());
+
+        adapter
+            .update_session(CookSessionUpdate::MarkInterrupted)
+            .await
+            .unwrap();
+        assert!(adapter.heartbeat.lock().await.is_none());
+        assert!(adapter.session_lock.lock().await.is_none());
+    }
 }
@@ -0,0 +1,223 @@
+//! Environment snapshots for session reproducibility
+//!
+//! A snapshot of the tool versions, platform, and relevant environment
+//! variables is stored in session metadata when a run starts. It is shown by
+//! `prodigy sessions show` and compared against the current environment on
+//! resume, so "works on my machine" failures can be traced to a changed
+//! toolchain.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
+
+/// Session metadata key holding the snapshot
+pub const ENVIRONMENT_METADATA_KEY: &str = "environment";
+
+/// Tools whose `--version` output is recorded
+const TRACKED_TOOLS: &[&str] = &["rustc", "cargo", "node", "claude"];
+
+/// Environment variables recorded alongside tool versions
+const TRACKED_ENV_VARS: &[&str] = &[
+    "PRODIGY_CLAUDE_MODEL",
+    "PRODIGY_AUTOMATION",
+    "RUSTFLAGS",
+    "RUSTUP_TOOLCHAIN",
+    "CARGO_TARGET_DIR",
+    "NODE_ENV",
+    "SHELL",
+];
+
+/// How long to wait for a tool to report its version
+const VERSION_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Tool versions and platform details captured at session start
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EnvironmentSnapshot {
+    /// Operating system (e.g. `linux`, `macos`)
+    pub os: String,
+    /// CPU architecture (e.g. `x86_64`, `aarch64`)
+    pub arch: String,
+    /// First line of `<tool> --version`, for tools that are installed
+    #[serde(default)]
+    pub tools: BTreeMap<String, String>,
+    /// Values of tracked environment variables that are set
+    #[serde(default)]
+    pub env_vars: BTreeMap<String, String>,
+}
+
+impl EnvironmentSnapshot {
+    /// Capture the current environment
+    pub async fn capture() -> Self {
+        let mut tools = BTreeMap::new();
+        for tool in TRACKED_TOOLS {
+            if let Some(version) = tool_version(tool).await {
+                tools.insert(tool.to_string(), version);
+            }
+        }
+
+        let env_vars = TRACKED_ENV_VARS
+            .iter()
+            .filter_map(|name| {
+                std::env::var(name)
+                    .ok()
+                    .map(|value| (name.to_string(), value))
+            })
+            .collect();
+
+        Self {
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            tools,
+            env_vars,
+        }
+    }
+
+    /// Read a snapshot from session metadata
+    pub fn from_metadata(metadata: &HashMap<String, serde_json::Value>) -> Option<Self> {
+        metadata
+            .get(ENVIRONMENT_METADATA_KEY)
+            .and_then(|value| serde_json::from_value(value.clone()).ok())
+    }
+
+    /// Metadata value for this snapshot
+    pub fn to_metadata_value(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or_default()
+    }
+
+    /// Material differences from `current`, one message per change (pure function)
+    ///
+    /// Platform and tool version changes are material; environment variables
+    /// are recorded for reference only.
+    pub fn differences(&self, current: &Self) -> Vec<String> {
+        let mut differences = Vec::new();
+
+        if self.os != current.os || self.arch != current.arch {
+            differences.push(format!(
+                "platform: {}/{} -> {}/{}",
+                self.os, self.arch, current.os, current.arch
+            ));
+        }
+
+        for tool in TRACKED_TOOLS {
+            match (self.tools.get(*tool), current.tools.get(*tool)) {
+                (Some(before), Some(after)) if before != after => {
+                    differences.push(format!("{tool}: {before} -> {after}"));
+                }
+                (Some(before), None) => {
+                    differences.push(format!("{tool}: {before} -> not installed"));
+                }
+                (None, Some(after)) => {
+                    differences.push(format!("{tool}: not installed -> {after}"));
+                }
+                _ => {}
+            }
+        }
+
+        differences
+    }
+
+    /// Lines describing the snapshot for display
+    pub fn display_lines(&self) -> Vec<String> {
+        let mut lines = vec![format!("Platform: {}/{}", self.os, self.arch)];
+        for tool in TRACKED_TOOLS {
+            let version = self
+                .tools
+                .get(*tool)
+                .map(String::as_str)
+                .unwrap_or("not installed");
+            lines.push(format!("{tool}: {version}"));
+        }
+        for (name, value) in &self.env_vars {
+            lines.push(format!("{name}={value}"));
+        }
+        lines
+    }
+}
+
+/// First line of `<tool> --version`, or `None` if the tool is unavailable
+async fn tool_version(tool: &str) -> Option<String> {
+    let output = tokio::time::timeout(
+        VERSION_TIMEOUT,
+        tokio::process::Command::new(tool)
+            .arg("--version")
+            .kill_on_drop(true)
+            .output(),
+    )
+    .await
+    .ok()?
+    .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .next()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(tools: &[(&str, &str)]) -> EnvironmentSnapshot {
+        EnvironmentSnapshot {
+            os: "linux".to_string(),
+            arch: "x86_64".to_string(),
+            tools: tools
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            env_vars: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_identical_environments_have_no_differences() {
+        let before = snapshot(&[("rustc", "rustc 1.80.0"), ("claude", "1.0.0")]);
+        assert!(before.differences(&before.clone()).is_empty());
+    }
+
+    #[test]
+    fn test_tool_and_platform_changes_are_reported() {
+        let before = snapshot(&[("rustc", "rustc 1.80.0"), ("node", "v20.1.0")]);
+        let mut after = snapshot(&[("rustc", "rustc 1.82.0"), ("claude", "1.0.0")]);
+        after.os = "macos".to_string();
+        after.env_vars.insert("SHELL".into(), "/bin/zsh".into());
+
+        assert_eq!(
+            before.differences(&after),
+            vec![
+                "platform: linux/x86_64 -> macos/x86_64",
+                "rustc: rustc 1.80.0 -> rustc 1.82.0",
+                "node: v20.1.0 -> not installed",
+                "claude: not installed -> 1.0.0",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_metadata_round_trip() {
+        let snapshot = snapshot(&[("cargo", "cargo 1.80.0")]);
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            ENVIRONMENT_METADATA_KEY.to_string(),
+            snapshot.to_metadata_value(),
+        );
+
+        assert_eq!(
+            EnvironmentSnapshot::from_metadata(&metadata),
+            Some(snapshot)
+        );
+        assert_eq!(EnvironmentSnapshot::from_metadata(&HashMap::new()), None);
+    }
+
+    #[tokio::test]
+    async fn test_capture_records_platform() {
+        let snapshot = EnvironmentSnapshot::capture().await;
+        assert_eq!(snapshot.os, std::env::consts::OS);
+        assert_eq!(snapshot.arch, std::env::consts::ARCH);
+    }
+}
@@ -0,0 +1,349 @@
+//! Session heartbeats and liveness
+//!
+//! A running orchestrator records a heartbeat in session metadata every
+//! [`HEARTBEAT_INTERVAL`]. A running session whose last heartbeat is older
+//! than [`STALE_AFTER`] has most likely lost its process, and `prodigy
+//! sessions ls` shows it as stale. Heartbeats are for display only: the
+//! process driving a session holds a [`SessionLock`], so two terminals can
+//! never resume the same session.
+
+use super::manager::{SessionManager, SessionUpdate};
+use super::state::{SessionId, SessionStatus};
+use crate::storage::lock::{FileLockBackend, LockBackend, StorageLockGuard};
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{oneshot, Mutex};
+use tokio::task::JoinHandle;
+
+/// Session metadata key holding the last heartbeat
+pub const HEARTBEAT_METADATA_KEY: &str = "heartbeat";
+
+/// How often a running orchestrator records a heartbeat
+pub const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How long a running session may go without a heartbeat before it is stale
+pub const STALE_AFTER: Duration = Duration::from_secs(120);
+
+/// How long a session lock survives without renewal
+const SESSION_LOCK_TTL: Duration = Duration::from_secs(30);
+
+/// How often a held session lock is renewed
+const SESSION_LOCK_RENEWAL: Duration = Duration::from_secs(10);
+
+/// Last sign of life from the process running a session
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Heartbeat {
+    pub at: DateTime<Utc>,
+    pub pid: u32,
+    pub hostname: String,
+}
+
+impl Heartbeat {
+    /// Heartbeat for the current process
+    pub fn now() -> Self {
+        Self {
+            at: Utc::now(),
+            pid: std::process::id(),
+            hostname: crate::worktree::state::current_hostname(),
+        }
+    }
+
+    /// Read the last heartbeat from session metadata
+    pub fn from_metadata(metadata: &HashMap<String, serde_json::Value>) -> Option<Self> {
+        metadata
+            .get(HEARTBEAT_METADATA_KEY)
+            .and_then(|value| serde_json::from_value(value.clone()).ok())
+    }
+
+    /// Convert to a metadata value
+    pub fn to_metadata_value(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or(serde_json::Value::Null)
+    }
+}
+
+/// Whether a session's process is still alive
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Liveness {
+    /// Running with a recent heartbeat
+    Live,
+    /// Running, but silent for the given time
+    Stale(chrono::Duration),
+    /// Not running
+    Inactive,
+}
+
+/// Determine liveness from status and the last heartbeat (pure function)
+///
+/// Sessions from before heartbeats were recorded fall back to their last
+/// update time.
+pub fn liveness(
+    status: &SessionStatus,
+    metadata: &HashMap<String, serde_json::Value>,
+    updated_at: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> Liveness {
+    if !matches!(status, SessionStatus::Running | SessionStatus::Initializing) {
+        return Liveness::Inactive;
+    }
+
+    let last_seen = Heartbeat::from_metadata(metadata)
+        .map(|heartbeat| heartbeat.at)
+        .unwrap_or(updated_at);
+    let silent = now - last_seen;
+    let stale_after = chrono::Duration::from_std(STALE_AFTER).unwrap_or(chrono::Duration::MAX);
+    if silent > stale_after {
+        Liveness::Stale(silent)
+    } else {
+        Liveness::Live
+    }
+}
+
+/// Format a status with its staleness, e.g. `running (stale 2h)` (pure function)
+pub fn format_status(status: &SessionStatus, liveness: &Liveness) -> String {
+    let name = format!("{status:?}").to_lowercase();
+    match liveness {
+        Liveness::Stale(silent) => format!("{name} (stale {})", format_age(*silent)),
+        Liveness::Live | Liveness::Inactive => name,
+    }
+}
+
+/// Format an age in its largest whole unit, e.g. `2h` (pure function)
+pub fn format_age(age: chrono::Duration) -> String {
+    let seconds = age.num_seconds().max(0);
+    match seconds {
+        s if s >= 86_400 => format!("{}d", s / 86_400),
+        s if s >= 3_600 => format!("{}h", s / 3_600),
+        s if s >= 60 => format!("{}m", s / 60),
+        s => format!("{s}s"),
+    }
+}
+
+/// Directory holding session locks under the Prodigy storage directory
+pub fn session_locks_dir(storage_dir: &std::path::Path) -> PathBuf {
+    storage_dir.join("locks").join("sessions")
+}
+
+/// Exclusive claim on driving a session; released when dropped
+///
+/// The lock is taken atomically and renewed in the background, so unlike
+/// checking the heartbeat there is no window in which two processes can
+/// both decide to resume the same session.
+pub struct SessionLock {
+    renewal: Option<JoinHandle<()>>,
+}
+
+impl SessionLock {
+    /// Claim a session, failing if another process is driving it
+    ///
+    /// Succeeds without a new claim when this process already holds the
+    /// lock, e.g. when `prodigy resume` starts the session it locked.
+    pub async fn acquire(locks_dir: PathBuf, session_id: &str) -> Result<Self> {
+        let backend = FileLockBackend::new(locks_dir)?;
+        let holder = Self::holder();
+        let guard = match backend
+            .try_acquire(session_id, &holder, SESSION_LOCK_TTL)
+            .await
+        {
+            Ok(guard) => guard,
+            Err(e) => {
+                let current = backend.current(session_id).await.ok().flatten();
+                return match current {
+                    Some(lock) if lock.holder == holder => Ok(Self { renewal: None }),
+                    Some(lock) => Err(anyhow!(
+                        "Session {} is being run by {}.\n\
+                         Resume it after that process exits.",
+                        session_id,
+                        lock.holder
+                    )),
+                    None => Err(anyhow!("Failed to lock session {}: {}", session_id, e)),
+                };
+            }
+        };
+
+        let guard = Arc::new(Mutex::new(guard));
+        let renewal = tokio::spawn(renew(session_id.to_string(), guard));
+        Ok(Self {
+            renewal: Some(renewal),
+        })
+    }
+
+    /// Holder recorded in the lock, identifying this process
+    fn holder() -> String {
+        format!(
+            "process {} on {}",
+            std::process::id(),
+            crate::worktree::state::current_hostname()
+        )
+    }
+}
+
+impl Drop for SessionLock {
+    fn drop(&mut self) {
+        // Aborting drops the task's guard, which removes the lock file
+        if let Some(renewal) = self.renewal.take() {
+            renewal.abort();
+        }
+    }
+}
+
+/// Keep a session lock alive until the task is aborted
+async fn renew(session_id: String, guard: Arc<Mutex<Box<dyn StorageLockGuard>>>) {
+    loop {
+        tokio::time::sleep(SESSION_LOCK_RENEWAL).await;
+        let mut guard = guard.lock().await;
+        let renewal = guard.lock_info().renewal(SESSION_LOCK_TTL, Utc::now());
+        if let Err(e) = guard.extend(renewal).await {
+            tracing::warn!("Lost the lock on session {}: {}", session_id, e);
+            return;
+        }
+    }
+}
+
+/// Background task recording heartbeats for a session; stops when dropped
+pub struct HeartbeatTask {
+    _stop: oneshot::Sender<()>,
+}
+
+/// Start recording heartbeats for a session every [`HEARTBEAT_INTERVAL`]
+pub fn spawn(manager: Arc<SessionManager>, id: SessionId) -> HeartbeatTask {
+    let (stop, mut stopped) = oneshot::channel::<()>();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(HEARTBEAT_INTERVAL);
+        loop {
+            tokio::select! {
+                _ = &mut stopped => break,
+                _ = interval.tick() => {
+                    let metadata = HashMap::from([(
+                        HEARTBEAT_METADATA_KEY.to_string(),
+                        Heartbeat::now().to_metadata_value(),
+                    )]);
+                    if let Err(e) = manager.update_session(&id, SessionUpdate::Metadata(metadata)).await {
+                        tracing::debug!("Failed to record heartbeat for {}: {}", id.as_str(), e);
+                    }
+                }
+            }
+        }
+    });
+    HeartbeatTask { _stop: stop }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata_with_heartbeat(at: DateTime<Utc>) -> HashMap<String, serde_json::Value> {
+        let heartbeat = Heartbeat {
+            at,
+            pid: 4242,
+            hostname: "build-host".to_string(),
+        };
+        HashMap::from([(
+            HEARTBEAT_METADATA_KEY.to_string(),
+            heartbeat.to_metadata_value(),
+        )])
+    }
+
+    #[test]
+    fn test_liveness_uses_heartbeat() {
+        let now = Utc::now();
+        let old = now - chrono::Duration::hours(3);
+
+        let fresh = metadata_with_heartbeat(now - chrono::Duration::seconds(10));
+        assert_eq!(
+            liveness(&SessionStatus::Running, &fresh, old, now),
+            Liveness::Live
+        );
+
+        let silent = metadata_with_heartbeat(now - chrono::Duration::hours(2));
+        assert_eq!(
+            liveness(&SessionStatus::Running, &silent, now, now),
+            Liveness::Stale(chrono::Duration::hours(2))
+        );
+
+        assert_eq!(
+            liveness(&SessionStatus::Paused, &silent, now, now),
+            Liveness::Inactive
+        );
+    }
+
+    #[test]
+    fn test_liveness_falls_back_to_updated_at() {
+        let now = Utc::now();
+        let metadata = HashMap::new();
+        assert_eq!(
+            liveness(&SessionStatus::Running, &metadata, now, now),
+            Liveness::Live
+        );
+        assert!(matches!(
+            liveness(
+                &SessionStatus::Running,
+                &metadata,
+                now - chrono::Duration::minutes(5),
+                now
+            ),
+            Liveness::Stale(_)
+        ));
+    }
+
+    #[test]
+    fn test_format_status() {
+        assert_eq!(
+            format_status(
+                &SessionStatus::Running,
+                &Liveness::Stale(chrono::Duration::minutes(150))
+            ),
+            "running (stale 2h)"
+        );
+        assert_eq!(
+            format_status(&SessionStatus::Running, &Liveness::Live),
+            "running"
+        );
+        assert_eq!(
+            format_status(&SessionStatus::Failed, &Liveness::Inactive),
+            "failed"
+        );
+        assert_eq!(format_age(chrono::Duration::seconds(45)), "45s");
+        assert_eq!(format_age(chrono::Duration::days(3)), "3d");
+    }
+
+    #[tokio::test]
+    async fn test_session_lock_excludes_other_processes() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let locks_dir = session_locks_dir(dir.path());
+
+        let held = SessionLock::acquire(locks_dir.clone(), "session-1")
+            .await
+            .unwrap();
+        assert!(held.renewal.is_some());
+
+        // The holding process may claim it again, e.g. resume starting the session
+        let nested = SessionLock::acquire(locks_dir.clone(), "session-1")
+            .await
+            .unwrap();
+        assert!(nested.renewal.is_none());
+
+        // Another process is refused
+        let backend = FileLockBackend::new(locks_dir.clone()).unwrap();
+        let mut lock = backend.current("session-1").await.unwrap().unwrap();
+        lock.holder = "process 4242 on build-host".to_string();
+        std::fs::write(
+            backend.lock_path("session-1"),
+            serde_json::to_vec(&lock).unwrap(),
+        )
+        .unwrap();
+        let err = SessionLock::acquire(locks_dir.clone(), "session-1")
+            .await
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(err.contains("being run by process 4242 on build-host"));
+
+        // Other sessions are unaffected
+        assert!(SessionLock::acquire(locks_dir, "session-2").await.is_ok());
+    }
+}
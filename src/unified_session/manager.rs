@@ -32,6 +32,8 @@ pub enum SessionUpdate {
 pub struct SessionManager {
     storage: SessionStorage,
     active_sessions: Arc<RwLock<HashMap<SessionId, UnifiedSession>>>,
+    /// Serializes read-modify-write updates, e.g. heartbeats racing status changes
+    update_lock: tokio::sync::Mutex<()>,
 }
 
 impl SessionManager {
@@ -40,6 +42,7 @@ impl SessionManager {
         Ok(Self {
             storage: SessionStorage::new(storage),
             active_sessions: Arc::new(RwLock::new(HashMap::new())),
+            update_lock: tokio::sync::Mutex::new(()),
         })
     }
 
@@ -111,6 +114,7 @@ impl SessionManager {
 
     /// Update a session
     pub async fn update_session(&self, id: &SessionId, update: SessionUpdate) -> Result<()> {
+        let _guard = self.update_lock.lock().await;
         let mut session = self.load_session(id).await?;
         session.updated_at = chrono::Utc::now();
 
@@ -3,10 +3,13 @@
 //! This module provides a single, consolidated session management system
 //! that handles all session-related functionality consistently across the application.
 
+pub mod bundle;
 mod checkpoints;
 mod cook_adapter;
 pub mod effects;
+pub mod environment;
 mod filters;
+pub mod heartbeat;
 mod lifecycle;
 mod manager;
 pub mod migration;
@@ -20,6 +23,7 @@ pub mod timing;
 mod updates;
 
 pub use cook_adapter::CookSessionAdapter;
+pub use environment::EnvironmentSnapshot;
 pub use manager::{SessionManager, SessionUpdate};
 pub use state::{
     Checkpoint, CheckpointId, MapReducePhase, MapReduceSession, SessionConfig, SessionFilter,
@@ -0,0 +1,431 @@
+//! Shared build caches for MapReduce agent worktrees
+//!
+//! Every agent starts from a fresh checkout, so Rust and Node projects rebuild
+//! `target/` or reinstall `node_modules/` for every item. `map.build_cache`
+//! mounts these directories into each agent worktree instead:
+//!
+//! ```yaml
+//! map:
+//!   build_cache:
+//!     - path: target
+//!       mode: slot           # reuse a warm directory per parallel slot
+//!       key: [Cargo.lock]
+//!     - path: node_modules
+//!       mode: symlink        # share the parent's copy, read-only use
+//! ```
+//!
+//! - `symlink` links to the directory in the parent worktree, typically
+//!   populated by the setup phase. All agents share one copy.
+//! - `clone` hardlinks the parent's directory into the agent worktree, so each
+//!   agent starts warm but writes its own files.
+//! - `slot` links to a persistent directory under `~/.prodigy/cache`. Each
+//!   running agent leases its own slot, so no two agents build into the same
+//!   directory at once, and slots stay warm across items and jobs.
+//!
+//! Mounting is skipped, with a warning, when the path already exists in the
+//! worktree, when the `key` files differ from the parent worktree, or when git
+//! does not ignore the mounted path. A skipped cache only costs build time.
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
+use std::sync::Mutex;
+use tracing::{info, warn};
+use walkdir::WalkDir;
+
+/// Slot directories currently leased by running agents in this process
+static LEASED_SLOTS: Mutex<BTreeSet<PathBuf>> = Mutex::new(BTreeSet::new());
+
+/// How a cached directory is provided to an agent worktree
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BuildCacheMode {
+    /// Symlink to the parent worktree's directory
+    Symlink,
+    /// Hardlink clone of the parent worktree's directory
+    Clone,
+    /// Symlink to a persistent directory leased by one agent at a time
+    #[default]
+    Slot,
+}
+
+/// A directory shared across agent worktrees
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BuildCacheEntry {
+    /// Directory relative to the worktree root, e.g. `target`
+    pub path: String,
+    /// How the directory is provided
+    #[serde(default)]
+    pub mode: BuildCacheMode,
+    /// Files whose contents must match before the cache is used, e.g. `Cargo.lock`
+    #[serde(default)]
+    pub key: Vec<String>,
+}
+
+impl BuildCacheEntry {
+    /// Check that the path is a plain relative path inside the worktree
+    pub fn validate(&self) -> Result<()> {
+        let path = Path::new(&self.path);
+        if self.path.trim().is_empty() {
+            bail!("build_cache path cannot be empty");
+        }
+        if !path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+        {
+            bail!(
+                "build_cache path '{}' must be relative and stay inside the worktree",
+                self.path
+            );
+        }
+        if path.starts_with(".git") {
+            bail!("build_cache path '{}' cannot be inside .git", self.path);
+        }
+        Ok(())
+    }
+}
+
+/// Exclusive use of a slot directory, released on drop
+#[derive(Debug)]
+pub struct SlotLease {
+    path: PathBuf,
+}
+
+impl SlotLease {
+    /// The leased slot directory
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for SlotLease {
+    fn drop(&mut self) {
+        let mut leased = match LEASED_SLOTS.lock() {
+            Ok(leased) => leased,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        leased.remove(&self.path);
+    }
+}
+
+/// Lease the lowest-numbered free slot under `cache_dir`, creating it if needed
+pub fn lease_slot(cache_dir: &Path) -> Result<SlotLease> {
+    let mut leased = match LEASED_SLOTS.lock() {
+        Ok(leased) => leased,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    let path = (0..)
+        .map(|n| cache_dir.join(format!("slot-{}", n)))
+        .find(|path| !leased.contains(path))
+        .ok_or_else(|| anyhow!("No free build cache slot"))?;
+    fs::create_dir_all(&path)
+        .with_context(|| format!("Failed to create build cache slot {}", path.display()))?;
+    leased.insert(path.clone());
+    Ok(SlotLease { path })
+}
+
+/// Short hash of the key files under `root`; missing files hash as absent
+pub fn cache_key(root: &Path, key_files: &[String]) -> String {
+    let mut hasher = Sha256::new();
+    for file in key_files {
+        hasher.update(file.as_bytes());
+        match fs::read(root.join(file)) {
+            Ok(contents) => {
+                hasher.update([1]);
+                hasher.update(&contents);
+            }
+            Err(_) => hasher.update([0]),
+        }
+    }
+    format!("{:x}", hasher.finalize())[..16].to_string()
+}
+
+/// Copy a directory tree, hardlinking files where possible
+///
+/// Falls back to a regular copy when hardlinks are not supported, for example
+/// across filesystems.
+pub fn clone_tree(source: &Path, target: &Path) -> Result<()> {
+    for entry in WalkDir::new(source).follow_links(false) {
+        let entry = entry?;
+        let relative = entry.path().strip_prefix(source)?;
+        let destination = target.join(relative);
+        let file_type = entry.file_type();
+        if file_type.is_dir() {
+            fs::create_dir_all(&destination)?;
+        } else if file_type.is_symlink() {
+            symlink(&fs::read_link(entry.path())?, &destination)?;
+        } else if fs::hard_link(entry.path(), &destination).is_err() {
+            fs::copy(entry.path(), &destination)
+                .with_context(|| format!("Failed to copy {}", entry.path().display()))?;
+        }
+    }
+    Ok(())
+}
+
+/// Build caches mounted into one agent worktree
+#[derive(Debug, Default)]
+pub struct BuildCacheMount {
+    /// Paths that were mounted
+    pub mounted: Vec<String>,
+    /// Slot leases held until the agent is dropped
+    leases: Vec<SlotLease>,
+}
+
+impl BuildCacheMount {
+    /// Slot directories leased for this worktree
+    pub fn slots(&self) -> impl Iterator<Item = &Path> {
+        self.leases.iter().map(SlotLease::path)
+    }
+}
+
+/// Mount build caches into an agent worktree
+///
+/// `parent` is the worktree the agent was created from and `cache_root` holds
+/// slot directories. Entries that cannot be mounted are skipped with a warning.
+pub fn mount_build_cache(
+    entries: &[BuildCacheEntry],
+    parent: &Path,
+    worktree: &Path,
+    cache_root: &Path,
+) -> BuildCacheMount {
+    let mut mount = BuildCacheMount::default();
+    for entry in entries {
+        match mount_entry(entry, parent, worktree, cache_root) {
+            Ok(lease) => {
+                info!(
+                    "Mounted build cache {} ({:?}) in {}",
+                    entry.path,
+                    entry.mode,
+                    worktree.display()
+                );
+                mount.mounted.push(entry.path.clone());
+                mount.leases.extend(lease);
+            }
+            Err(e) => warn!("Skipping build cache {}: {}", entry.path, e),
+        }
+    }
+    mount
+}
+
+fn mount_entry(
+    entry: &BuildCacheEntry,
+    parent: &Path,
+    worktree: &Path,
+    cache_root: &Path,
+) -> Result<Option<SlotLease>> {
+    let target = worktree.join(&entry.path);
+    if target.symlink_metadata().is_ok() {
+        bail!("{} already exists in the worktree", entry.path);
+    }
+    if let Some(dir) = target.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let lease = match entry.mode {
+        BuildCacheMode::Symlink | BuildCacheMode::Clone => {
+            if !entry.key.is_empty()
+                && cache_key(parent, &entry.key) != cache_key(worktree, &entry.key)
+            {
+                bail!("{} differs from the parent worktree", entry.key.join(", "));
+            }
+            let source = parent.join(&entry.path);
+            if !source.is_dir() {
+                bail!("{} does not exist in the parent worktree", source.display());
+            }
+            if entry.mode == BuildCacheMode::Symlink {
+                symlink(&source, &target)?;
+            } else {
+                clone_tree(&source, &target)?;
+            }
+            None
+        }
+        BuildCacheMode::Slot => {
+            let cache_dir = cache_root.join(format!(
+                "{}-{}",
+                entry.path.replace(['/', '\\'], "_"),
+                cache_key(worktree, &entry.key)
+            ));
+            let lease = lease_slot(&cache_dir)?;
+            symlink(lease.path(), &target)?;
+            Some(lease)
+        }
+    };
+
+    if let Err(e) = ensure_ignored(worktree, &entry.path) {
+        let _ = if target.is_symlink() {
+            fs::remove_file(&target)
+        } else {
+            fs::remove_dir_all(&target)
+        };
+        return Err(e);
+    }
+    Ok(lease)
+}
+
+/// Fail unless git ignores `path`, so cached files never end up in agent commits
+fn ensure_ignored(worktree: &Path, path: &str) -> Result<()> {
+    let status = Command::new("git")
+        .args(["check-ignore", "-q", path])
+        .current_dir(worktree)
+        .status()
+        .context("Failed to run git check-ignore")?;
+    if !status.success() {
+        bail!(
+            "{} is not ignored by git; add '{}' to .gitignore (no trailing slash, since it may be a symlink)",
+            path,
+            path
+        );
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn symlink(source: &Path, target: &Path) -> Result<()> {
+    std::os::unix::fs::symlink(source, target)
+        .with_context(|| format!("Failed to link {}", target.display()))
+}
+
+#[cfg(not(unix))]
+fn symlink(_source: &Path, target: &Path) -> Result<()> {
+    bail!(
+        "Cannot link {}: symlinks are only supported on Unix",
+        target.display()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn entry(path: &str, mode: BuildCacheMode) -> BuildCacheEntry {
+        BuildCacheEntry {
+            path: path.to_string(),
+            mode,
+            key: vec!["Cargo.lock".to_string()],
+        }
+    }
+
+    fn git_repo(path: &Path, gitignore: &str) {
+        fs::create_dir_all(path).unwrap();
+        Command::new("git")
+            .args(["init", "-q"])
+            .current_dir(path)
+            .status()
+            .unwrap();
+        fs::write(path.join(".gitignore"), gitignore).unwrap();
+        fs::write(path.join("Cargo.lock"), "version = 3").unwrap();
+    }
+
+    #[test]
+    fn test_validate_path() {
+        assert!(entry("target", BuildCacheMode::Slot).validate().is_ok());
+        assert!(entry("web/node_modules", BuildCacheMode::Symlink)
+            .validate()
+            .is_ok());
+        assert!(entry("", BuildCacheMode::Slot).validate().is_err());
+        assert!(entry("../target", BuildCacheMode::Slot).validate().is_err());
+        assert!(entry("/tmp/target", BuildCacheMode::Slot)
+            .validate()
+            .is_err());
+        assert!(entry(".git/objects", BuildCacheMode::Slot)
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn test_lease_slot_is_exclusive_until_dropped() {
+        let temp = TempDir::new().unwrap();
+        let first = lease_slot(temp.path()).unwrap();
+        let second = lease_slot(temp.path()).unwrap();
+        assert_eq!(first.path(), temp.path().join("slot-0"));
+        assert_eq!(second.path(), temp.path().join("slot-1"));
+
+        drop(first);
+        let third = lease_slot(temp.path()).unwrap();
+        assert_eq!(third.path(), temp.path().join("slot-0"));
+    }
+
+    #[test]
+    fn test_cache_key_tracks_file_contents() {
+        let temp = TempDir::new().unwrap();
+        let keys = vec!["Cargo.lock".to_string()];
+        let missing = cache_key(temp.path(), &keys);
+        fs::write(temp.path().join("Cargo.lock"), "a").unwrap();
+        let first = cache_key(temp.path(), &keys);
+        fs::write(temp.path().join("Cargo.lock"), "b").unwrap();
+        let second = cache_key(temp.path(), &keys);
+
+        assert_eq!(first.len(), 16);
+        assert_ne!(missing, first);
+        assert_ne!(first, second);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_mount_build_cache_modes() {
+        let temp = TempDir::new().unwrap();
+        let parent = temp.path().join("parent");
+        let agent = temp.path().join("agent");
+        let cache_root = temp.path().join("cache");
+        git_repo(&parent, "/target\n/deps\n");
+        git_repo(&agent, "/target\n/deps\n");
+        fs::create_dir_all(parent.join("deps/pkg")).unwrap();
+        fs::write(parent.join("deps/pkg/index.js"), "x").unwrap();
+
+        let mount = mount_build_cache(
+            &[
+                entry("target", BuildCacheMode::Slot),
+                entry("deps", BuildCacheMode::Clone),
+            ],
+            &parent,
+            &agent,
+            &cache_root,
+        );
+
+        assert_eq!(mount.mounted, vec!["target", "deps"]);
+        let slot = mount.slots().next().unwrap().to_path_buf();
+        assert_eq!(fs::read_link(agent.join("target")).unwrap(), slot);
+        assert!(slot.starts_with(&cache_root));
+        assert_eq!(
+            fs::read_to_string(agent.join("deps/pkg/index.js")).unwrap(),
+            "x"
+        );
+        assert!(!agent.join("deps").is_symlink());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_mount_build_cache_guards() {
+        let temp = TempDir::new().unwrap();
+        let parent = temp.path().join("parent");
+        let agent = temp.path().join("agent");
+        let cache_root = temp.path().join("cache");
+        // `node_modules/` only matches directories, not a symlink
+        git_repo(&parent, "node_modules/\n/vendor\n");
+        git_repo(&agent, "node_modules/\n/vendor\n");
+        fs::create_dir_all(parent.join("node_modules")).unwrap();
+        fs::create_dir_all(parent.join("vendor")).unwrap();
+        fs::write(agent.join("Cargo.lock"), "version = 4").unwrap();
+
+        let mount = mount_build_cache(
+            &[
+                entry("node_modules", BuildCacheMode::Symlink),
+                entry("vendor", BuildCacheMode::Symlink),
+            ],
+            &parent,
+            &agent,
+            &cache_root,
+        );
+
+        assert!(mount.mounted.is_empty());
+        // Not ignored: the link is removed again
+        assert!(agent.join("node_modules").symlink_metadata().is_err());
+        // Key files differ from the parent: never linked
+        assert!(agent.join("vendor").symlink_metadata().is_err());
+    }
+}
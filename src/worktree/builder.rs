@@ -15,7 +15,8 @@ use std::path::{Path, PathBuf};
 use tracing::{info, warn};
 use uuid::Uuid;
 
-use super::state::{IterationInfo, WorktreeStats};
+use super::state::{IterationInfo, SessionOwner, WorktreeStats};
+use super::submodules::load_checkout_options;
 use super::{WorktreeManager, WorktreeSession, WorktreeState, WorktreeStatus};
 
 /// Builder for constructing WorktreeManager instances
@@ -74,7 +75,7 @@ impl WorktreeBuilder {
                 )
             })?;
 
-        // Use home directory for worktrees (or temp dir during tests)
+        // Use the storage directory for worktrees (or temp dir during tests)
         let base_dir = {
             #[cfg(test)]
             {
@@ -90,11 +91,14 @@ impl WorktreeBuilder {
             }
             #[cfg(not(test))]
             {
-                let home_dir = directories::BaseDirs::new()
-                    .ok_or_else(|| anyhow!("Could not determine base directories"))?
-                    .home_dir()
-                    .to_path_buf();
-                home_dir.join(".prodigy").join("worktrees").join(repo_name)
+                let storage_dir = match crate::storage::configured_storage_dir() {
+                    Some(base_path) => base_path,
+                    None => directories::BaseDirs::new()
+                        .ok_or_else(|| anyhow!("Could not determine base directories"))?
+                        .home_dir()
+                        .join(".prodigy"),
+                };
+                storage_dir.join("worktrees").join(repo_name)
             }
         };
 
@@ -148,8 +152,10 @@ impl WorktreeManager {
     /// * `Result<WorktreeSession>` - The created worktree session
     ///
     /// # Errors
-    /// Returns error if worktree creation fails
+    /// Returns error if worktree creation fails or the enforced disk quota is exceeded
     pub async fn create_session_with_id(&self, session_id: &str) -> Result<WorktreeSession> {
+        self.check_disk_quota()?;
+
         // Capture current branch BEFORE creating worktree
         let mut original_branch = self.get_current_branch().await.unwrap_or_else(|e| {
             warn!(
@@ -179,11 +185,15 @@ impl WorktreeManager {
         let worktree_path = self.base_dir.join(&name);
 
         // Create worktree
-        let command = ProcessCommandBuilder::new("git")
+        let checkout = load_checkout_options();
+        let mut command = ProcessCommandBuilder::new("git")
             .current_dir(&self.repo_path)
             .args(["worktree", "add", "-b", &branch])
-            .arg(worktree_path.to_string_lossy().as_ref())
-            .build();
+            .arg(worktree_path.to_string_lossy().as_ref());
+        if self.skip_lfs_smudge(checkout) {
+            command = command.env("GIT_LFS_SKIP_SMUDGE", "1");
+        }
+        let command = command.build();
 
         let output = self
             .subprocess
@@ -195,6 +205,7 @@ impl WorktreeManager {
         if !output.status.success() {
             anyhow::bail!("Failed to create worktree: {}", output.stderr);
         }
+        self.prepare_checkout(&worktree_path, checkout).await;
 
         // Create session
         let session = WorktreeSession::new(name.clone(), branch, worktree_path);
@@ -205,6 +216,70 @@ impl WorktreeManager {
         Ok(session)
     }
 
+    /// Create a detached, throwaway worktree at the current HEAD
+    ///
+    /// Unlike [`create_session_with_id`](Self::create_session_with_id), no branch
+    /// is created and no session state is recorded, so the worktree can never be
+    /// merged back. Used by read-only runs.
+    ///
+    /// # Errors
+    /// Returns error if `git worktree add --detach` fails
+    pub async fn create_detached_worktree(&self, name: &str) -> Result<PathBuf> {
+        self.check_disk_quota()?;
+
+        let worktree_path = self.base_dir.join(name);
+
+        let checkout = load_checkout_options();
+        let mut command = ProcessCommandBuilder::new("git")
+            .current_dir(&self.repo_path)
+            .args(["worktree", "add", "--detach"])
+            .arg(worktree_path.to_string_lossy().as_ref())
+            .arg("HEAD");
+        if self.skip_lfs_smudge(checkout) {
+            command = command.env("GIT_LFS_SKIP_SMUDGE", "1");
+        }
+        let command = command.build();
+
+        let output = self
+            .subprocess
+            .runner()
+            .run(command)
+            .await
+            .context("Failed to execute git worktree add --detach")?;
+
+        if !output.status.success() {
+            anyhow::bail!("Failed to create detached worktree: {}", output.stderr);
+        }
+        self.prepare_checkout(&worktree_path, checkout).await;
+
+        Ok(worktree_path)
+    }
+
+    /// Remove a worktree created by [`create_detached_worktree`](Self::create_detached_worktree)
+    ///
+    /// # Errors
+    /// Returns error if `git worktree remove` fails
+    pub async fn remove_detached_worktree(&self, worktree_path: &Path) -> Result<()> {
+        let command = ProcessCommandBuilder::new("git")
+            .current_dir(&self.repo_path)
+            .args(["worktree", "remove", "--force"])
+            .arg(worktree_path.to_string_lossy().as_ref())
+            .build();
+
+        let output = self
+            .subprocess
+            .runner()
+            .run(command)
+            .await
+            .context("Failed to execute git worktree remove")?;
+
+        if !output.status.success() {
+            anyhow::bail!("Failed to remove detached worktree: {}", output.stderr);
+        }
+
+        Ok(())
+    }
+
     /// Save session state with original branch tracking
     pub(crate) fn save_session_state_with_original_branch(
         &self,
@@ -237,6 +312,7 @@ impl WorktreeManager {
             interruption_type: None,
             last_checkpoint: None,
             resumable: false,
+            owner: Some(SessionOwner::current()),
         };
 
         let json = serde_json::to_string_pretty(&state)?;
@@ -431,6 +507,7 @@ pub(crate) mod test_helpers {
                 command_output: None,
             }),
             resumable: true,
+            owner: None,
         }
     }
 
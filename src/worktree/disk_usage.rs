@@ -0,0 +1,288 @@
+//! Disk usage measurement and quota enforcement for Prodigy worktrees
+//!
+//! Every agent in a MapReduce job gets its own checkout, so parallel jobs can
+//! fill a disk without anyone noticing. This module measures worktree sizes
+//! for `prodigy worktree ls --detailed` and checks the total under
+//! `~/.prodigy/worktrees` against the quota configured in `config.yml`:
+//!
+//! ```yaml
+//! worktree:
+//!   disk_quota: 50GB
+//!   enforce_disk_quota: true  # refuse new worktrees instead of warning
+//! ```
+
+use anyhow::{anyhow, Result};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::warn;
+use walkdir::WalkDir;
+
+use super::display::DetailedWorktreeList;
+use super::WorktreeManager;
+use crate::config::WorktreeSettings;
+
+/// How long a measurement of the worktree root is reused before walking again
+const MEASUREMENT_TTL: Duration = Duration::from_secs(30);
+
+/// Last measurement of a worktree root, shared by all managers in the process
+static LAST_MEASUREMENT: Mutex<Option<(PathBuf, Instant, u64)>> = Mutex::new(None);
+
+/// Configured limit on the total size of Prodigy worktrees
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiskQuota {
+    /// Maximum total size in bytes
+    pub limit_bytes: u64,
+    /// Refuse to create worktrees over the limit instead of warning
+    pub enforce: bool,
+}
+
+impl DiskQuota {
+    /// Build a quota from worktree settings, or `None` when no quota is set
+    pub fn from_settings(settings: &WorktreeSettings) -> Result<Option<Self>> {
+        settings
+            .disk_quota
+            .as_deref()
+            .map(|quota| {
+                Ok(Self {
+                    limit_bytes: parse_size(quota)?,
+                    enforce: settings.enforce_disk_quota,
+                })
+            })
+            .transpose()
+    }
+
+    /// Whether `used_bytes` is over the limit
+    pub fn is_exceeded(&self, used_bytes: u64) -> bool {
+        used_bytes > self.limit_bytes
+    }
+}
+
+/// Load the disk quota from Prodigy configuration
+///
+/// Returns `None` when no quota is configured or the configuration cannot be
+/// loaded, so a broken config file never blocks worktree creation.
+pub fn load_disk_quota() -> Option<DiskQuota> {
+    match crate::config::load_prodigy_config() {
+        Ok(config) => DiskQuota::from_settings(&config.worktree).unwrap_or_else(|e| {
+            warn!("Ignoring worktree disk quota: {}", e);
+            None
+        }),
+        Err(errors) => {
+            warn!("Ignoring worktree disk quota: {}", errors);
+            None
+        }
+    }
+}
+
+/// Quota checks run before a worktree is created
+impl WorktreeManager {
+    /// Directory holding the worktrees of every repository
+    pub fn worktrees_root(&self) -> &Path {
+        self.base_dir.parent().unwrap_or(&self.base_dir)
+    }
+
+    /// Check the configured disk quota before creating a worktree
+    ///
+    /// Logs a warning when Prodigy worktrees are over the quota, or fails when
+    /// `enforce_disk_quota` is set.
+    ///
+    /// # Errors
+    /// Returns error if the quota is exceeded and enforced
+    pub fn check_disk_quota(&self) -> Result<()> {
+        let Some(quota) = load_disk_quota() else {
+            return Ok(());
+        };
+        let used = cached_directory_size(self.worktrees_root());
+        if !quota.is_exceeded(used) {
+            return Ok(());
+        }
+        let message = quota_exceeded_message(used, &quota);
+        if quota.enforce {
+            anyhow::bail!("Refusing to create worktree: {}", message);
+        }
+        warn!("{}", message);
+        Ok(())
+    }
+}
+
+/// Total size in bytes of the files under `path`
+///
+/// Symlinks are not followed, and entries that cannot be read (for example
+/// files removed mid-walk) are skipped. A missing path has size zero.
+pub fn directory_size(path: &Path) -> u64 {
+    WalkDir::new(path)
+        .follow_links(false)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| entry.metadata().ok())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+/// Measure each session's worktree and the total, filling in the size fields
+pub fn apply_disk_usage(list: &mut DetailedWorktreeList) {
+    let mut total = 0;
+    for session in &mut list.sessions {
+        let size = directory_size(&session.worktree_path);
+        session.disk_usage_bytes = Some(size);
+        total += size;
+    }
+    list.summary.disk_usage_bytes = Some(total);
+}
+
+/// Size of everything under `root`, reusing a recent measurement if there is one
+pub fn cached_directory_size(root: &Path) -> u64 {
+    let mut last = match LAST_MEASUREMENT.lock() {
+        Ok(last) => last,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    if let Some((path, measured_at, size)) = last.as_ref() {
+        if path == root && measured_at.elapsed() < MEASUREMENT_TTL {
+            return *size;
+        }
+    }
+    let size = directory_size(root);
+    *last = Some((root.to_path_buf(), Instant::now(), size));
+    size
+}
+
+/// Parse a size such as `50GB`, `512 MiB` or `1073741824`
+///
+/// Units are binary (`1GB` = 1024³ bytes) and case-insensitive.
+pub fn parse_size(value: &str) -> Result<u64> {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+    let number: f64 = number
+        .parse()
+        .map_err(|_| anyhow!("Invalid size '{}': expected a number and a unit", value))?;
+    let multiplier: u64 = match unit.trim().to_ascii_uppercase().as_str() {
+        "" | "B" => 1,
+        "K" | "KB" | "KIB" => 1 << 10,
+        "M" | "MB" | "MIB" => 1 << 20,
+        "G" | "GB" | "GIB" => 1 << 30,
+        "T" | "TB" | "TIB" => 1 << 40,
+        other => {
+            return Err(anyhow!(
+                "Invalid size '{}': unknown unit '{}' (use B, KB, MB, GB or TB)",
+                value,
+                other
+            ))
+        }
+    };
+    Ok((number * multiplier as f64) as u64)
+}
+
+/// Format a byte count for display, e.g. `1.5 GB`
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+/// Message shown when worktrees use more space than the quota allows
+pub fn quota_exceeded_message(used_bytes: u64, quota: &DiskQuota) -> String {
+    format!(
+        "Prodigy worktrees use {} (quota {}). Run 'prodigy worktree clean' to free space.",
+        format_size(used_bytes),
+        format_size(quota.limit_bytes)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::worktree::display::WorktreeSummary;
+    use crate::worktree::{EnhancedSessionInfo, WorktreeStatus};
+    use chrono::Utc;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(parse_size("1024").unwrap(), 1024);
+        assert_eq!(parse_size("2KB").unwrap(), 2048);
+        assert_eq!(parse_size("50GB").unwrap(), 50 << 30);
+        assert_eq!(parse_size("512 mib").unwrap(), 512 << 20);
+        assert_eq!(parse_size("1.5G").unwrap(), 3 << 29);
+        assert!(parse_size("lots").is_err());
+        assert!(parse_size("10PB").is_err());
+    }
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(1536), "1.5 KB");
+        assert_eq!(format_size(50 << 30), "50.0 GB");
+    }
+
+    #[test]
+    fn test_quota_from_settings() {
+        let unset = WorktreeSettings::default();
+        assert_eq!(DiskQuota::from_settings(&unset).unwrap(), None);
+
+        let settings = WorktreeSettings {
+            disk_quota: Some("1GB".to_string()),
+            enforce_disk_quota: true,
+            ..Default::default()
+        };
+        let quota = DiskQuota::from_settings(&settings).unwrap().unwrap();
+        assert!(quota.enforce);
+        assert!(!quota.is_exceeded(1 << 30));
+        assert!(quota.is_exceeded((1 << 30) + 1));
+    }
+
+    #[test]
+    fn test_apply_disk_usage() {
+        let temp = TempDir::new().unwrap();
+        let first = temp.path().join("session-a");
+        let second = temp.path().join("session-b");
+        std::fs::create_dir_all(first.join("src")).unwrap();
+        std::fs::create_dir_all(&second).unwrap();
+        std::fs::write(first.join("src/main.rs"), vec![0u8; 300]).unwrap();
+        std::fs::write(second.join("README.md"), vec![0u8; 200]).unwrap();
+
+        let session = |path: &Path| EnhancedSessionInfo {
+            session_id: "session".to_string(),
+            status: WorktreeStatus::InProgress,
+            workflow_path: None,
+            workflow_args: vec![],
+            started_at: Utc::now(),
+            last_activity: Utc::now(),
+            current_step: 0,
+            total_steps: None,
+            error_summary: None,
+            branch_name: "prodigy-session".to_string(),
+            parent_branch: None,
+            worktree_path: path.to_path_buf(),
+            files_changed: 0,
+            commits: 0,
+            items_processed: None,
+            total_items: None,
+            disk_usage_bytes: None,
+        };
+        let mut list = DetailedWorktreeList {
+            sessions: vec![session(&first), session(&second)],
+            summary: WorktreeSummary::default(),
+        };
+
+        apply_disk_usage(&mut list);
+
+        assert_eq!(list.sessions[0].disk_usage_bytes, Some(300));
+        assert_eq!(list.sessions[1].disk_usage_bytes, Some(200));
+        assert_eq!(list.summary.disk_usage_bytes, Some(500));
+        assert_eq!(directory_size(&temp.path().join("missing")), 0);
+    }
+}
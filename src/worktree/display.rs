@@ -2,6 +2,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
+use super::disk_usage::format_size;
 use super::{WorktreeState, WorktreeStatus};
 
 /// Enhanced session information for display
@@ -39,6 +40,9 @@ pub struct EnhancedSessionInfo {
     pub items_processed: Option<u32>,
     /// For MapReduce jobs, total items
     pub total_items: Option<u32>,
+    /// Size of the worktree on disk, when measured
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disk_usage_bytes: Option<u64>,
 }
 
 /// Summary of all worktree sessions
@@ -49,6 +53,9 @@ pub struct WorktreeSummary {
     pub interrupted: usize,
     pub failed: usize,
     pub completed: usize,
+    /// Combined size of all listed worktrees, when measured
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disk_usage_bytes: Option<u64>,
 }
 
 /// Detailed worktree list with enhanced information
@@ -129,6 +136,9 @@ impl SessionDisplay for EnhancedSessionInfo {
             "\n     Files changed: {} • Commits: {}",
             self.files_changed, self.commits
         ));
+        if let Some(bytes) = self.disk_usage_bytes {
+            output.push_str(&format!(" • Size: {}", format_size(bytes)));
+        }
         output.push_str(&format!(
             "\n     Worktree: {}",
             self.worktree_path.display()
@@ -187,6 +197,10 @@ impl SessionDisplay for DetailedWorktreeList {
             ));
         }
 
+        if let Some(bytes) = self.summary.disk_usage_bytes {
+            output.push_str(&format!("\nDisk usage: {} total", format_size(bytes)));
+        }
+
         output
     }
 
@@ -252,6 +266,7 @@ impl From<&WorktreeState> for EnhancedSessionInfo {
             commits: state.stats.commits,
             items_processed: None,
             total_items: None,
+            disk_usage_bytes: None,
         }
     }
 }
@@ -335,6 +350,7 @@ mod tests {
             commits: 2,
             items_processed: None,
             total_items: None,
+            disk_usage_bytes: None,
         };
 
         let output = session.format_default();
@@ -364,6 +380,7 @@ mod tests {
             commits: 0,
             items_processed: Some(25),
             total_items: Some(100),
+            disk_usage_bytes: None,
         };
 
         let output = session.format_default();
@@ -389,6 +406,7 @@ mod tests {
             commits: 1,
             items_processed: None,
             total_items: None,
+            disk_usage_bytes: None,
         };
 
         let output = session.format_default();
@@ -415,9 +433,11 @@ mod tests {
             commits: 5,
             items_processed: None,
             total_items: None,
+            disk_usage_bytes: Some(3 << 20),
         };
 
         let output = session.format_verbose();
+        assert!(output.contains("Size: 3.0 MB"));
         assert!(output.contains("Files changed: 15"));
         assert!(output.contains("Commits: 5"));
         assert!(output.contains("Worktree: /home/user/worktree"));
@@ -442,6 +462,7 @@ mod tests {
             commits: 1,
             items_processed: None,
             total_items: None,
+            disk_usage_bytes: None,
         };
 
         let json = session.format_json();
@@ -483,6 +504,7 @@ mod tests {
             commits: 1,
             items_processed: None,
             total_items: None,
+            disk_usage_bytes: None,
         };
 
         let session2 = EnhancedSessionInfo {
@@ -502,6 +524,7 @@ mod tests {
             commits: 5,
             items_processed: None,
             total_items: None,
+            disk_usage_bytes: None,
         };
 
         let list = DetailedWorktreeList {
@@ -512,6 +535,7 @@ mod tests {
                 interrupted: 0,
                 failed: 0,
                 completed: 1,
+                disk_usage_bytes: None,
             },
         };
 
@@ -532,6 +556,7 @@ mod tests {
                 interrupted: 1,
                 failed: 0,
                 completed: 1,
+                disk_usage_bytes: None,
             },
         };
 
@@ -597,6 +622,7 @@ mod tests {
             commits: state.stats.commits,
             items_processed: None,
             total_items: None,
+            disk_usage_bytes: None,
         };
 
         assert_eq!(session_info.session_id, "convert-test");
@@ -391,6 +391,7 @@ mod tests {
             commits: 0,
             items_processed: None,
             total_items: None,
+            disk_usage_bytes: None,
         }
     }
 
@@ -31,6 +31,7 @@
 
 use crate::config::mapreduce::MergeWorkflow;
 use crate::subprocess::{ProcessCommandBuilder, SubprocessManager};
+use crate::{styled_eprintln, styled_println};
 use anyhow::{Context, Result};
 use chrono::Utc;
 use serde_json;
@@ -362,9 +363,10 @@ impl WorktreeManager {
             self.verify_merge_completion(worktree_branch, &target_branch, &merge_output)
                 .await?;
         } else {
-            println!(
+            styled_println!(
                 "ℹ️  No new commits in worktree '{}', skipping merge (already in sync with '{}')",
-                name, target_branch
+                name,
+                target_branch
             );
         }
 
@@ -374,6 +376,152 @@ impl WorktreeManager {
         Ok(())
     }
 
+    /// Merge a worktree session, keeping the merge only if verification passes
+    ///
+    /// After the merge, `verify_command` runs in a temporary checkout of the
+    /// merged target branch. If it fails, the target branch is reset to its
+    /// pre-merge commit and the session is left unmerged.
+    ///
+    /// # Returns
+    /// * `Ok(None)` - The merge was kept
+    /// * `Ok(Some(output))` - Verification failed and the merge was rolled back
+    ///
+    /// # Errors
+    /// Returns error if the merge fails, the session is not found, or the
+    /// rollback fails
+    pub async fn merge_session_verified(
+        &self,
+        name: &str,
+        verify_command: &str,
+    ) -> Result<Option<String>> {
+        let session = self.find_session_by_name(name).await?;
+        let worktree_branch = &session.branch;
+        let target_branch = self.get_merge_target(name).await?;
+
+        let should_merge = self
+            .validate_merge_preconditions(name, worktree_branch, &target_branch)
+            .await?;
+        if !should_merge {
+            styled_println!(
+                "ℹ️  No new commits in worktree '{}', skipping merge (already in sync with '{}')",
+                name,
+                target_branch
+            );
+            self.finalize_merge_session(name).await?;
+            return Ok(None);
+        }
+
+        let pre_merge = self.run_git(&["rev-parse", &target_branch]).await?;
+
+        let merge_output = self
+            .execute_merge_workflow(name, worktree_branch, &target_branch)
+            .await?;
+        self.verify_merge_completion(worktree_branch, &target_branch, &merge_output)
+            .await?;
+
+        styled_println!("🧪 Verifying '{}' with: {}", target_branch, verify_command);
+        if let Some(failure) = self
+            .run_merge_verification(&target_branch, verify_command)
+            .await?
+        {
+            self.reset_branch(&target_branch, &pre_merge).await?;
+            return Ok(Some(failure));
+        }
+
+        self.finalize_merge_session(name).await?;
+        Ok(None)
+    }
+
+    /// Whether the repository checkout has uncommitted changes
+    pub async fn has_uncommitted_changes(&self) -> Result<bool> {
+        let status = self.run_git(&["status", "--porcelain"]).await?;
+        Ok(!status.is_empty())
+    }
+
+    /// Whether `git status` reports no changes in a worktree, including its submodules
+    async fn is_worktree_clean(&self, worktree_path: &std::path::Path) -> bool {
+        let status_command = ProcessCommandBuilder::new("git")
+            .current_dir(worktree_path)
+            .args(["status", "--porcelain"])
+            .build();
+        match self.subprocess.runner().run(status_command).await {
+            Ok(output) => output.status.success() && output.stdout.trim().is_empty(),
+            Err(_) => false,
+        }
+    }
+
+    /// Run the verification command against a temporary checkout of `branch`
+    ///
+    /// Returns the summarized output when the command fails.
+    async fn run_merge_verification(
+        &self,
+        branch: &str,
+        verify_command: &str,
+    ) -> Result<Option<String>> {
+        let temp_dir =
+            tempfile::tempdir().context("Failed to create merge verification directory")?;
+        let checkout = temp_dir.path().join("verify");
+        let checkout_str = checkout.to_string_lossy().to_string();
+
+        self.run_git(&["worktree", "add", "--detach", &checkout_str, branch])
+            .await?;
+
+        let command = ProcessCommandBuilder::new("sh")
+            .current_dir(&checkout)
+            .args(["-c", verify_command])
+            .build();
+        let result = self.subprocess.runner().run(command).await;
+
+        // Remove the checkout even if the command could not be started
+        let removed = self
+            .run_git(&["worktree", "remove", "--force", &checkout_str])
+            .await;
+        let output = result.context("Failed to run merge verification command")?;
+        removed?;
+
+        if output.status.success() {
+            Ok(None)
+        } else {
+            Ok(Some(manager_validation::summarize_verification_output(
+                &output.stdout,
+                &output.stderr,
+            )))
+        }
+    }
+
+    /// Move `branch` back to `commit`, updating the checkout if it is current
+    async fn reset_branch(&self, branch: &str, commit: &str) -> Result<()> {
+        if self.get_current_branch().await? == branch {
+            self.run_git(&["reset", "--hard", commit]).await?;
+        } else {
+            self.run_git(&["update-ref", &format!("refs/heads/{branch}"), commit])
+                .await?;
+        }
+        info!("Reset {} to {} after failed verification", branch, commit);
+        Ok(())
+    }
+
+    /// Run a git command in the repository and return its trimmed stdout
+    async fn run_git(&self, args: &[&str]) -> Result<String> {
+        let command = ProcessCommandBuilder::new("git")
+            .current_dir(&self.repo_path)
+            .args(args)
+            .build();
+
+        let output = self
+            .subprocess
+            .runner()
+            .run(command)
+            .await
+            .with_context(|| format!("Failed to execute git {}", args.join(" ")))?;
+
+        if !output.status.success() {
+            anyhow::bail!("git {} failed: {}", args.join(" "), output.stderr.trim());
+        }
+
+        Ok(output.stdout.trim().to_string())
+    }
+
     /// Find session by name - pure function that extracts session lookup logic
     async fn find_session_by_name(&self, name: &str) -> Result<WorktreeSession> {
         let sessions = self.list_sessions().await?;
@@ -469,18 +617,18 @@ impl WorktreeManager {
 
     /// Perform auto cleanup - I/O operation
     async fn perform_auto_cleanup(&self, name: &str) -> Result<()> {
-        println!("🧹 Auto-cleanup is enabled, checking if session can be cleaned up...");
+        styled_println!("🧹 Auto-cleanup is enabled, checking if session can be cleaned up...");
 
         // Give a moment for the merge to propagate
         tokio::time::sleep(std::time::Duration::from_millis(500)).await;
 
         match self.cleanup_session_after_merge(name).await {
             Ok(()) => {
-                println!("✅ Successfully cleaned up merged session: {name}");
+                styled_println!("✅ Successfully cleaned up merged session: {name}");
                 Ok(())
             }
             Err(e) => {
-                eprintln!("⚠️  Auto-cleanup failed for session {name}: {e}");
+                styled_eprintln!("⚠️  Auto-cleanup failed for session {name}: {e}");
                 self.show_cleanup_diagnostics(name).await;
                 eprintln!(
                     "   You can manually clean up later with: prodigy worktree cleanup {name}"
@@ -501,7 +649,7 @@ impl WorktreeManager {
 
             if let Ok(status_output) = self.subprocess.runner().run(status_command).await {
                 if status_output.status.success() && !status_output.stdout.trim().is_empty() {
-                    eprintln!("📝 Current worktree status:");
+                    styled_eprintln!("📝 Current worktree status:");
                     eprintln!("{}", status_output.stdout.trim());
                 }
             }
@@ -528,6 +676,12 @@ impl WorktreeManager {
         let worktree_path = self.base_dir.join(name);
         let worktree_path_str = worktree_path.to_string_lossy();
 
+        // git refuses to remove a worktree with submodules unless forced, so
+        // force it once the worktree is known to be clean
+        let force = force
+            || (worktree_path.join(".gitmodules").is_file()
+                && self.is_worktree_clean(&worktree_path).await);
+
         let mut args = vec!["worktree", "remove"];
         if force {
             args.push("--force");
@@ -756,11 +910,11 @@ impl WorktreeManager {
             // Perform the cleanup
             match self.cleanup_session_after_merge(&session_name).await {
                 Ok(()) => {
-                    println!("✅ Cleaned up merged session: {session_name}");
+                    styled_println!("✅ Cleaned up merged session: {session_name}");
                     cleaned_up.push(session_name);
                 }
                 Err(e) => {
-                    eprintln!("❌ Failed to cleanup session {session_name}: {e}");
+                    styled_eprintln!("❌ Failed to cleanup session {session_name}: {e}");
                 }
             }
         }
@@ -798,9 +952,9 @@ impl WorktreeManager {
             if status_output.status.success() && !status_output.stdout.trim().is_empty() {
                 // Worktree has uncommitted changes, but since it's already merged,
                 // we can safely force cleanup
-                println!("📝 Worktree has uncommitted changes after merge:");
+                styled_println!("📝 Worktree has uncommitted changes after merge:");
                 println!("{}", status_output.stdout.trim());
-                println!("🔧 Using force cleanup since changes are already merged...");
+                styled_println!("🔧 Using force cleanup since changes are already merged...");
                 self.cleanup_session(name, true).await?;
             } else {
                 // No uncommitted changes, regular cleanup
@@ -981,6 +1135,7 @@ mod tests {
                 command_output: None,
             }),
             resumable: true,
+            owner: None,
         };
         let state_file = metadata_dir.join(format!("{session_name}.json"));
         std::fs::write(&state_file, serde_json::to_string(&state).unwrap()).unwrap();
@@ -1031,6 +1186,7 @@ mod tests {
             interruption_type: None,
             last_checkpoint: None,
             resumable: true,
+            owner: None,
         };
         let state_file = metadata_dir.join(format!("{session_name}.json"));
         std::fs::write(&state_file, serde_json::to_string(&state).unwrap()).unwrap();
@@ -1080,6 +1236,7 @@ mod tests {
             interruption_type: None,
             last_checkpoint: None,
             resumable: true,
+            owner: None,
         };
         let state_file = metadata_dir.join(format!("{session_name}.json"));
         std::fs::write(&state_file, serde_json::to_string(&state).unwrap()).unwrap();
@@ -1128,6 +1285,7 @@ mod tests {
             interruption_type: None,
             last_checkpoint: None,
             resumable: true,
+            owner: None,
         };
 
         state.last_checkpoint = Some(Checkpoint {
@@ -1234,6 +1392,7 @@ mod tests {
             interruption_type: None,
             last_checkpoint: None,
             resumable: true,
+            owner: None,
         };
 
         let state_file = metadata_dir.join(format!("{session_name}.json"));
@@ -1279,6 +1438,7 @@ mod tests {
             interruption_type: None,
             last_checkpoint: None,
             resumable: true,
+            owner: None,
         };
         let state_file = metadata_dir.join(format!("{session_name}.json"));
         std::fs::write(&state_file, serde_json::to_string(&state).unwrap()).unwrap();
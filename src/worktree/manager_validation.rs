@@ -21,6 +21,7 @@
 //! - Stateless validation logic
 //! - Clear error messages with context
 
+use crate::styled_eprintln;
 use anyhow::Result;
 
 /// Determine if merge should proceed based on commit count between branches
@@ -70,7 +71,7 @@ pub fn should_proceed_with_merge(commit_count: &str) -> bool {
 /// - Error output indicates failure
 pub fn validate_claude_result(result: &crate::cook::execution::ExecutionResult) -> Result<()> {
     if !result.success {
-        eprintln!("❌ Claude merge failed:");
+        styled_eprintln!("❌ Claude merge failed:");
         if !result.stderr.is_empty() {
             eprintln!("Error output: {}", result.stderr);
         }
@@ -291,3 +292,40 @@ pub fn is_permission_denied(output: &str) -> bool {
 pub fn check_if_branch_merged(branch: &str, merged_branches_output: &str) -> bool {
     merged_branches_output.contains(branch)
 }
+
+/// Number of output lines kept when a merge verification fails
+const VERIFICATION_OUTPUT_LINES: usize = 20;
+
+/// Summarize the output of a failed merge verification command
+///
+/// Keeps the last lines of combined stdout and stderr, where test runners
+/// usually report failures.
+///
+/// # Arguments
+///
+/// * `stdout` - Standard output of the verification command
+/// * `stderr` - Standard error of the verification command
+///
+/// # Examples
+///
+/// ```
+/// use prodigy::worktree::manager_validation::summarize_verification_output;
+///
+/// let summary = summarize_verification_output("running 3 tests\n", "test failed\n");
+/// assert_eq!(summary, "running 3 tests\ntest failed");
+/// assert_eq!(summarize_verification_output("", ""), "verification command failed");
+/// ```
+pub fn summarize_verification_output(stdout: &str, stderr: &str) -> String {
+    let lines: Vec<&str> = stdout
+        .lines()
+        .chain(stderr.lines())
+        .filter(|line| !line.trim().is_empty())
+        .collect();
+
+    if lines.is_empty() {
+        return "verification command failed".to_string();
+    }
+
+    let start = lines.len().saturating_sub(VERIFICATION_OUTPUT_LINES);
+    lines[start..].join("\n")
+}
@@ -23,6 +23,7 @@
 
 use crate::config::mapreduce::MergeWorkflow;
 use crate::cook::execution::{ClaudeExecutor, ClaudeExecutorImpl};
+use crate::styled_println;
 use crate::subprocess::{ProcessCommandBuilder, SubprocessManager};
 use anyhow::{Context, Result};
 use std::collections::HashMap;
@@ -110,7 +111,7 @@ impl MergeOrchestrator {
     {
         match &self.custom_merge_workflow {
             Some(merge_workflow) => {
-                println!(
+                styled_println!(
                     "🔄 Executing custom merge workflow for '{name}' into '{target_branch}'..."
                 );
                 self.execute_custom_merge_workflow(
@@ -123,7 +124,7 @@ impl MergeOrchestrator {
                 .await
             }
             None => {
-                println!("🔄 Merging worktree '{name}' into '{target_branch}' using Claude-assisted merge...");
+                styled_println!("🔄 Merging worktree '{name}' into '{target_branch}' using Claude-assisted merge...");
                 self.execute_claude_merge(name, worktree_branch, target_branch)
                     .await
             }
@@ -258,6 +259,8 @@ impl MergeOrchestrator {
                 }
                 crate::cook::workflow::WorkflowStep {
                     claude: Some(claude_cmd),
+                    context: None,
+                    validate_output: None,
                     ..
                 } => {
                     let cmd_output = self
@@ -461,7 +464,7 @@ impl MergeOrchestrator {
         let shell_cmd_interpolated = self.interpolate_merge_variables(shell_cmd, variables);
 
         let step_name = format!("shell: {}", shell_cmd_interpolated);
-        println!(
+        styled_println!(
             "🔄 Executing step {}/{}: {}",
             step_index + 1,
             total_steps,
@@ -515,7 +518,7 @@ impl MergeOrchestrator {
         let claude_cmd_interpolated = self.interpolate_merge_variables(claude_cmd, variables);
 
         let step_name = format!("claude: {}", claude_cmd_interpolated);
-        println!(
+        styled_println!(
             "🔄 Executing step {}/{}: {}",
             step_index + 1,
             total_steps,
@@ -681,6 +684,9 @@ impl MergeOrchestrator {
             error_recovery_state: None,
             retry_checkpoint_state: None,
             variable_checkpoint_state: None,
+            failure_chains: Vec::new(),
+            repo: None,
+            step_selection: None,
         };
         checkpoint_manager.save_checkpoint(&checkpoint).await?;
         tracing::info!("Saved checkpoint for merge workflow at step {}", step_index);
@@ -63,7 +63,9 @@
 use chrono::{DateTime, Utc};
 use std::path::PathBuf;
 
+pub mod build_cache;
 pub mod builder;
+pub mod disk_usage;
 pub mod display;
 pub mod list_detailed_pure;
 pub mod manager;
@@ -74,7 +76,9 @@ pub mod manager_validation;
 pub mod merge_orchestrator;
 pub mod parsing;
 pub mod pool;
+pub mod recovery;
 pub mod state;
+pub mod submodules;
 #[cfg(test)]
 mod test_state;
 #[cfg(test)]
@@ -90,8 +94,8 @@ pub use pool::{
     WorktreePoolConfig, WorktreeRequest, WorktreeStatus as PoolWorktrStatus,
 };
 pub use state::{
-    Checkpoint, CommandType, InterruptionType, IterationInfo, WorktreeState, WorktreeStats,
-    WorktreeStatus,
+    Checkpoint, CommandType, InterruptionType, IterationInfo, SessionOwner, WorktreeState,
+    WorktreeStats, WorktreeStatus,
 };
 
 /// Represents an active git worktree session for MMM operations
@@ -0,0 +1,207 @@
+//! Recovery of worktree sessions orphaned by a crash
+//!
+//! When the machine or the prodigy process dies without a chance to record the
+//! interruption (host crash, OOM kill, SIGKILL), the session's state file is
+//! left `InProgress` with no process behind it. These functions detect such
+//! sessions by checking the recorded owner process, mark them interrupted, and
+//! claim them for a resume that continues in the existing worktree.
+
+use anyhow::{anyhow, Context, Result};
+use chrono::Utc;
+
+use super::manager_queries::collect_all_states;
+use super::state::{current_hostname, InterruptionType, SessionOwner};
+use super::{WorktreeManager, WorktreeState, WorktreeStatus};
+use crate::cook::execution::is_process_running;
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/// Record that a session's process died without cleaning up
+pub(crate) fn mark_interrupted(state: &mut WorktreeState) {
+    state.status = WorktreeStatus::Interrupted;
+    state.interrupted_at = Some(Utc::now());
+    state.interruption_type = Some(InterruptionType::ProcessKill);
+    state.resumable = true;
+}
+
+/// Check that a session can be recovered, explaining why not otherwise
+pub(crate) fn check_recoverable(
+    state: &WorktreeState,
+    hostname: &str,
+    is_running: impl Fn(u32) -> bool,
+) -> Result<()> {
+    if state.status == WorktreeStatus::Interrupted || state.is_orphaned(hostname, &is_running) {
+        return Ok(());
+    }
+
+    match (&state.status, &state.owner) {
+        (WorktreeStatus::InProgress, Some(owner)) if owner.hostname != hostname => Err(anyhow!(
+            "Session {} is owned by process {} on host {}; recover it from that host",
+            state.worktree_name,
+            owner.pid,
+            owner.hostname
+        )),
+        (WorktreeStatus::InProgress, Some(owner)) => Err(anyhow!(
+            "Session {} is still running in process {}",
+            state.worktree_name,
+            owner.pid
+        )),
+        (status, _) => Err(anyhow!(
+            "Session {} is {:?}, only interrupted sessions can be recovered",
+            state.worktree_name,
+            status
+        )),
+    }
+}
+
+// ============================================================================
+// Recovery Methods
+// ============================================================================
+
+impl WorktreeManager {
+    /// Mark in-progress sessions whose owner process is gone as interrupted
+    ///
+    /// Returns the sessions that were marked, in their updated state.
+    pub fn mark_orphaned_sessions(&self) -> Result<Vec<WorktreeState>> {
+        let hostname = current_hostname();
+        let metadata_dir = self.base_dir.join(".metadata");
+
+        let mut marked = Vec::new();
+        for mut state in collect_all_states(&metadata_dir)? {
+            if !state.is_orphaned(&hostname, is_process_running) {
+                continue;
+            }
+            self.update_session_state(&state.worktree_name, mark_interrupted)?;
+            mark_interrupted(&mut state);
+            marked.push(state);
+        }
+
+        Ok(marked)
+    }
+
+    /// Claim an interrupted or orphaned session for the current process
+    ///
+    /// The session is set back to in progress and owned by this process, so
+    /// a crash during the resumed run is detected like the original one.
+    pub fn claim_for_recovery(&self, name: &str) -> Result<WorktreeState> {
+        let state = self
+            .get_session_state(name)
+            .with_context(|| format!("No worktree session named {name}"))?;
+        check_recoverable(&state, &current_hostname(), is_process_running)?;
+
+        self.update_session_state(name, |state| {
+            state.status = WorktreeStatus::InProgress;
+            state.owner = Some(SessionOwner::current());
+        })?;
+        self.get_session_state(name)
+    }
+
+    /// Return a claimed session to interrupted after a failed recovery
+    ///
+    /// Sessions the resumed run already moved on from (failed, interrupted,
+    /// completed) are left as they are.
+    pub fn release_recovery(&self, name: &str) -> Result<()> {
+        self.update_session_state(name, |state| {
+            if state.status == WorktreeStatus::InProgress {
+                mark_interrupted(state);
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::worktree::{IterationInfo, WorktreeStats};
+
+    fn state(status: WorktreeStatus, owner: Option<(u32, &str)>) -> WorktreeState {
+        WorktreeState {
+            session_id: "session-1".to_string(),
+            worktree_name: "session-1".to_string(),
+            branch: "prodigy-session-1".to_string(),
+            original_branch: "main".to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            status,
+            iterations: IterationInfo {
+                completed: 0,
+                max: 10,
+            },
+            stats: WorktreeStats::default(),
+            merged: false,
+            merged_at: None,
+            error: None,
+            merge_prompt_shown: false,
+            merge_prompt_response: None,
+            interrupted_at: None,
+            interruption_type: None,
+            last_checkpoint: None,
+            resumable: false,
+            owner: owner.map(|(pid, hostname)| SessionOwner {
+                pid,
+                hostname: hostname.to_string(),
+            }),
+        }
+    }
+
+    #[test]
+    fn test_is_orphaned_requires_dead_local_owner() {
+        let dead = |_| false;
+        let alive = |_| true;
+
+        assert!(state(WorktreeStatus::InProgress, Some((42, "host"))).is_orphaned("host", dead));
+        assert!(!state(WorktreeStatus::InProgress, Some((42, "host"))).is_orphaned("host", alive));
+        assert!(!state(WorktreeStatus::InProgress, Some((42, "other"))).is_orphaned("host", dead));
+        assert!(!state(WorktreeStatus::InProgress, None).is_orphaned("host", dead));
+        assert!(!state(WorktreeStatus::Completed, Some((42, "host"))).is_orphaned("host", dead));
+    }
+
+    #[test]
+    fn test_mark_interrupted() {
+        let mut session = state(WorktreeStatus::InProgress, Some((42, "host")));
+        mark_interrupted(&mut session);
+
+        assert_eq!(session.status, WorktreeStatus::Interrupted);
+        assert_eq!(
+            session.interruption_type,
+            Some(InterruptionType::ProcessKill)
+        );
+        assert!(session.interrupted_at.is_some());
+        assert!(session.resumable);
+    }
+
+    #[test]
+    fn test_check_recoverable() {
+        let dead = |_| false;
+        let alive = |_| true;
+
+        assert!(
+            check_recoverable(&state(WorktreeStatus::Interrupted, None), "host", alive).is_ok()
+        );
+        assert!(check_recoverable(
+            &state(WorktreeStatus::InProgress, Some((42, "host"))),
+            "host",
+            dead
+        )
+        .is_ok());
+
+        let running = check_recoverable(
+            &state(WorktreeStatus::InProgress, Some((42, "host"))),
+            "host",
+            alive,
+        );
+        assert!(running.unwrap_err().to_string().contains("still running"));
+
+        let remote = check_recoverable(
+            &state(WorktreeStatus::InProgress, Some((42, "other"))),
+            "host",
+            dead,
+        );
+        assert!(remote.unwrap_err().to_string().contains("host other"));
+
+        let merged = check_recoverable(&state(WorktreeStatus::Merged, None), "host", dead);
+        assert!(merged.unwrap_err().to_string().contains("Merged"));
+    }
+}
@@ -30,6 +30,35 @@ pub struct WorktreeState {
     pub interruption_type: Option<InterruptionType>,
     pub last_checkpoint: Option<Checkpoint>,
     pub resumable: bool,
+    /// Process running the session, used to detect sessions orphaned by a
+    /// crash. Absent for sessions created before owners were recorded.
+    #[serde(default)]
+    pub owner: Option<SessionOwner>,
+}
+
+/// Process that owns an in-progress worktree session
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SessionOwner {
+    pub pid: u32,
+    pub hostname: String,
+}
+
+impl SessionOwner {
+    /// Owner record for the current process
+    pub fn current() -> Self {
+        Self {
+            pid: std::process::id(),
+            hostname: current_hostname(),
+        }
+    }
+}
+
+/// Hostname of this machine, or `unknown` if it cannot be determined
+pub fn current_hostname() -> String {
+    hostname::get()
+        .ok()
+        .and_then(|h| h.into_string().ok())
+        .unwrap_or_else(|| "unknown".to_string())
 }
 
 /// Status of a worktree session
@@ -124,4 +153,17 @@ impl WorktreeState {
             && self.merged_at.is_none()
             && self.error.is_none()
     }
+
+    /// Whether this session is in progress but its owner process is gone
+    ///
+    /// Only sessions owned by a process on `hostname` can be checked; sessions
+    /// from other hosts or without a recorded owner are never orphaned.
+    pub fn is_orphaned(&self, hostname: &str, is_running: impl Fn(u32) -> bool) -> bool {
+        match &self.owner {
+            Some(owner) if self.status == WorktreeStatus::InProgress => {
+                owner.hostname == hostname && !is_running(owner.pid)
+            }
+            _ => false,
+        }
+    }
 }
@@ -0,0 +1,485 @@
+//! Submodules and Git LFS objects in new worktrees
+//!
+//! `git worktree add` checks out neither submodules nor LFS objects (without
+//! git-lfs hooks it leaves pointer files), so builds fail in repositories
+//! that need them. After creating a worktree, Prodigy initializes its
+//! submodules and checks out its LFS objects, controlled in `config.yml`:
+//!
+//! ```yaml
+//! worktree:
+//!   submodules: true  # default
+//!   lfs: true         # default
+//! ```
+//!
+//! Downloads are cached so that a MapReduce job does not fetch the same
+//! objects once per agent:
+//!
+//! - A submodule is cloned with `--reference` to the main checkout's copy in
+//!   `.git/modules`, or, when the main checkout never initialized it, to a
+//!   mirror under `~/.prodigy/cache/submodules` that is cloned once and
+//!   fetched at most once per process.
+//! - LFS objects live in the repository's shared LFS storage. They are
+//!   fetched once per commit and process, then each worktree only runs
+//!   `git lfs checkout`, which copies them from local storage.
+//!
+//! Both steps are skipped when the repository has no `.gitmodules` or no LFS
+//! patterns in `.gitattributes`. A failure is logged and the worktree is
+//! still created.
+
+use anyhow::{bail, Context, Result};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::{Mutex, OnceLock};
+use tracing::{info, warn};
+
+use super::WorktreeManager;
+use crate::config::WorktreeSettings;
+
+/// Submodule mirrors already fetched by this process
+static REFRESHED_MIRRORS: Mutex<BTreeSet<String>> = Mutex::new(BTreeSet::new());
+
+/// LFS fetches (common git dir and commit) already done by this process
+static FETCHED_LFS: Mutex<BTreeSet<String>> = Mutex::new(BTreeSet::new());
+
+/// What to prepare in a new worktree besides the checkout itself
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CheckoutOptions {
+    /// Initialize and update submodules
+    pub submodules: bool,
+    /// Check out Git LFS objects
+    pub lfs: bool,
+}
+
+impl Default for CheckoutOptions {
+    fn default() -> Self {
+        Self {
+            submodules: true,
+            lfs: true,
+        }
+    }
+}
+
+impl CheckoutOptions {
+    /// Options from worktree settings
+    pub fn from_settings(settings: &WorktreeSettings) -> Self {
+        Self {
+            submodules: settings.submodules,
+            lfs: settings.lfs,
+        }
+    }
+}
+
+/// Load the checkout options from Prodigy configuration
+///
+/// Falls back to the defaults when the configuration cannot be loaded.
+pub fn load_checkout_options() -> CheckoutOptions {
+    match crate::config::load_prodigy_config() {
+        Ok(config) => CheckoutOptions::from_settings(&config.worktree),
+        Err(errors) => {
+            warn!("Using default worktree checkout options: {}", errors);
+            CheckoutOptions::default()
+        }
+    }
+}
+
+/// Submodule names and paths from `git config --get-regexp` output (pure function)
+///
+/// Lines look like `submodule.libs/foo.path libs/foo`.
+pub fn parse_submodule_paths(output: &str) -> Vec<(String, String)> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (key, path) = line.split_once(' ')?;
+            let name = key.strip_prefix("submodule.")?.strip_suffix(".path")?;
+            Some((name.to_string(), path.trim().to_string()))
+        })
+        .collect()
+}
+
+/// Whether `.gitattributes` content routes any files through LFS (pure function)
+pub fn declares_lfs(gitattributes: &str) -> bool {
+    gitattributes
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#'))
+        .any(|line| line.split_whitespace().any(|attr| attr == "filter=lfs"))
+}
+
+/// Directory of the mirror caching the submodule at `url` (pure function)
+pub fn mirror_dir(cache_dir: &Path, url: &str) -> PathBuf {
+    let digest = Sha256::digest(url.as_bytes());
+    let hash: String = digest[..8].iter().map(|b| format!("{:02x}", b)).collect();
+    let name = url
+        .trim_end_matches('/')
+        .trim_end_matches(".git")
+        .rsplit(['/', ':'])
+        .next()
+        .filter(|name| !name.is_empty())
+        .unwrap_or("submodule");
+    cache_dir.join(format!("{}-{}.git", name, hash))
+}
+
+/// Whether the checkout at `dir` uses Git LFS and git-lfs is installed
+pub fn uses_lfs(dir: &Path) -> bool {
+    static LFS_INSTALLED: OnceLock<bool> = OnceLock::new();
+    let declared = std::fs::read_to_string(dir.join(".gitattributes"))
+        .map(|content| declares_lfs(&content))
+        .unwrap_or(false);
+    declared
+        && *LFS_INSTALLED.get_or_init(|| {
+            let installed = git(Path::new("."), &["lfs", "version"]).is_ok();
+            if !installed {
+                warn!("This repository uses Git LFS, but git-lfs is not installed");
+            }
+            installed
+        })
+}
+
+/// Initialize and update the submodules of a new worktree
+///
+/// `repo` is the main checkout whose `.git/modules` is used as a reference
+/// when present; mirrors of other submodules are kept under `cache_dir`.
+pub fn init_submodules(repo: &Path, worktree: &Path, cache_dir: &Path) -> Result<()> {
+    if !worktree.join(".gitmodules").is_file() {
+        return Ok(());
+    }
+    let listed = git(
+        worktree,
+        &[
+            "config",
+            "-f",
+            ".gitmodules",
+            "--get-regexp",
+            r"^submodule\..*\.path$",
+        ],
+    )?;
+    let common_dir = common_git_dir(repo)?;
+
+    for (name, path) in parse_submodule_paths(&listed) {
+        // Resolves relative URLs against the superproject's remote
+        git(worktree, &["submodule", "init", "--", &path])?;
+        let url = git(
+            worktree,
+            &["config", "--get", &format!("submodule.{}.url", name)],
+        )?;
+        let reference = match reference_repo(&common_dir, &name, url.trim(), cache_dir) {
+            Ok(reference) => Some(reference),
+            Err(e) => {
+                warn!("Cloning submodule {} without a cache: {}", path, e);
+                None
+            }
+        };
+        let mut args = vec!["submodule", "update", "--init"];
+        let reference_str = reference.map(|r| r.to_string_lossy().to_string());
+        if let Some(reference) = &reference_str {
+            args.extend(["--reference", reference.as_str()]);
+        }
+        args.extend(["--", path.as_str()]);
+        git(worktree, &args)?;
+    }
+
+    // Nested submodules, cloned without a cache
+    git(worktree, &["submodule", "update", "--init", "--recursive"])?;
+    Ok(())
+}
+
+/// Local repository the submodule `name` can borrow objects from
+fn reference_repo(common_dir: &Path, name: &str, url: &str, cache_dir: &Path) -> Result<PathBuf> {
+    let main_copy = common_dir.join("modules").join(name);
+    if main_copy.join("objects").is_dir() {
+        return Ok(main_copy);
+    }
+
+    let mirror = mirror_dir(cache_dir, url);
+    let mut refreshed = match REFRESHED_MIRRORS.lock() {
+        Ok(refreshed) => refreshed,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    let key = mirror.to_string_lossy().to_string();
+    if !refreshed.contains(&key) {
+        if mirror.join("objects").is_dir() {
+            git(&mirror, &["fetch", "--prune", "--quiet"])?;
+        } else {
+            std::fs::create_dir_all(cache_dir)?;
+            info!("Caching submodule {} in {}", url, mirror.display());
+            git(
+                cache_dir,
+                &[
+                    "clone",
+                    "--mirror",
+                    "--quiet",
+                    url,
+                    &mirror.to_string_lossy(),
+                ],
+            )?;
+        }
+        refreshed.insert(key);
+    }
+    Ok(mirror)
+}
+
+/// Check out the LFS objects of a new worktree, fetching them once per commit
+pub fn checkout_lfs_objects(worktree: &Path) -> Result<()> {
+    let key = format!(
+        "{}@{}",
+        common_git_dir(worktree)?.display(),
+        git(worktree, &["rev-parse", "HEAD"])?.trim()
+    );
+    {
+        let mut fetched = match FETCHED_LFS.lock() {
+            Ok(fetched) => fetched,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        if !fetched.contains(&key) {
+            git(worktree, &["lfs", "fetch"])?;
+            fetched.insert(key);
+        }
+    }
+    git(worktree, &["lfs", "checkout"])?;
+    Ok(())
+}
+
+/// Git directory shared by the main checkout and all its worktrees
+fn common_git_dir(dir: &Path) -> Result<PathBuf> {
+    let output = git(dir, &["rev-parse", "--git-common-dir"])?;
+    let common = PathBuf::from(output.trim());
+    Ok(if common.is_absolute() {
+        common
+    } else {
+        dir.join(common)
+    })
+}
+
+/// Run git in `dir`, returning stdout
+fn git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .with_context(|| format!("Failed to run git {}", args.join(" ")))?;
+    if !output.status.success() {
+        bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Checkout preparation run after a worktree is created
+impl WorktreeManager {
+    /// Whether `git worktree add` should skip LFS downloads for this repository
+    ///
+    /// The objects are checked out afterwards from shared storage instead.
+    pub(crate) fn skip_lfs_smudge(&self, options: CheckoutOptions) -> bool {
+        options.lfs && uses_lfs(&self.repo_path)
+    }
+
+    /// Initialize submodules and check out LFS objects in a new worktree
+    ///
+    /// Failures are logged; the worktree is usable for anything that does
+    /// not need them.
+    pub(crate) async fn prepare_checkout(&self, worktree_path: &Path, options: CheckoutOptions) {
+        let repo = self.repo_path.clone();
+        let worktree = worktree_path.to_path_buf();
+        let result = tokio::task::spawn_blocking(move || {
+            if options.submodules && worktree.join(".gitmodules").is_file() {
+                match crate::storage::get_default_storage_dir() {
+                    Ok(storage) => {
+                        let cache_dir = storage.join("cache").join("submodules");
+                        if let Err(e) = init_submodules(&repo, &worktree, &cache_dir) {
+                            warn!(
+                                "Failed to initialize submodules in {}: {}",
+                                worktree.display(),
+                                e
+                            );
+                        }
+                    }
+                    Err(e) => warn!("Skipping submodules: {}", e),
+                }
+            }
+            if options.lfs && uses_lfs(&worktree) {
+                if let Err(e) = checkout_lfs_objects(&worktree) {
+                    warn!(
+                        "Failed to check out LFS objects in {}: {}",
+                        worktree.display(),
+                        e
+                    );
+                }
+            }
+        })
+        .await;
+        if let Err(e) = result {
+            warn!("Worktree checkout preparation failed: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn run(dir: &Path, args: &[&str]) {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "git {:?}: {}",
+            args,
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    fn init_repo(dir: &Path) {
+        std::fs::create_dir_all(dir).unwrap();
+        run(dir, &["init", "-q", "-b", "main"]);
+        run(dir, &["config", "user.email", "test@example.com"]);
+        run(dir, &["config", "user.name", "Test"]);
+    }
+
+    #[test]
+    fn test_parse_submodule_paths() {
+        let output = "submodule.libs/foo.path libs/foo\nsubmodule.bar.path vendor/bar\n";
+        assert_eq!(
+            parse_submodule_paths(output),
+            vec![
+                ("libs/foo".to_string(), "libs/foo".to_string()),
+                ("bar".to_string(), "vendor/bar".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_declares_lfs() {
+        assert!(declares_lfs(
+            "*.psd filter=lfs diff=lfs merge=lfs -text\n*.rs text\n"
+        ));
+        assert!(!declares_lfs("# *.psd filter=lfs\n*.rs text\n"));
+    }
+
+    #[test]
+    fn test_mirror_dir_is_stable_per_url() {
+        let cache = Path::new("/cache");
+        let a = mirror_dir(cache, "https://example.com/org/lib.git");
+        assert_eq!(a, mirror_dir(cache, "https://example.com/org/lib.git"));
+        assert_ne!(a, mirror_dir(cache, "https://example.com/other/lib.git"));
+        assert!(a.file_name().unwrap().to_string_lossy().starts_with("lib-"));
+    }
+
+    #[test]
+    #[serial_test::serial] // Modifies global env vars
+    fn test_init_submodules_uses_main_checkout_copy() {
+        // Local submodule URLs need the file transport, which git disables by default
+        std::env::set_var("GIT_CONFIG_COUNT", "1");
+        std::env::set_var("GIT_CONFIG_KEY_0", "protocol.file.allow");
+        std::env::set_var("GIT_CONFIG_VALUE_0", "always");
+
+        let temp = TempDir::new().unwrap();
+        let sub = temp.path().join("sub");
+        init_repo(&sub);
+        std::fs::write(sub.join("lib.txt"), "lib").unwrap();
+        run(&sub, &["add", "lib.txt"]);
+        run(&sub, &["commit", "-q", "-m", "lib"]);
+
+        let repo = temp.path().join("repo");
+        init_repo(&repo);
+        run(
+            &repo,
+            &["submodule", "add", "-q", &sub.to_string_lossy(), "libs/sub"],
+        );
+        run(&repo, &["commit", "-q", "-m", "add submodule"]);
+        let worktree = temp.path().join("wt");
+        run(
+            &repo,
+            &[
+                "worktree",
+                "add",
+                "-q",
+                "-b",
+                "wt",
+                &worktree.to_string_lossy(),
+            ],
+        );
+
+        let cache = temp.path().join("cache");
+        let result = init_submodules(&repo, &worktree, &cache);
+
+        std::env::remove_var("GIT_CONFIG_COUNT");
+        std::env::remove_var("GIT_CONFIG_KEY_0");
+        std::env::remove_var("GIT_CONFIG_VALUE_0");
+
+        result.unwrap();
+        assert_eq!(
+            std::fs::read_to_string(worktree.join("libs/sub/lib.txt")).unwrap(),
+            "lib"
+        );
+        // The main checkout already has the submodule, so no mirror is created
+        assert!(!cache.exists());
+    }
+
+    #[test]
+    #[serial_test::serial] // Modifies global env vars
+    fn test_init_submodules_falls_back_to_mirror() {
+        std::env::set_var("GIT_CONFIG_COUNT", "1");
+        std::env::set_var("GIT_CONFIG_KEY_0", "protocol.file.allow");
+        std::env::set_var("GIT_CONFIG_VALUE_0", "always");
+
+        let temp = TempDir::new().unwrap();
+        let sub = temp.path().join("sub");
+        init_repo(&sub);
+        std::fs::write(sub.join("lib.txt"), "lib").unwrap();
+        run(&sub, &["add", "lib.txt"]);
+        run(&sub, &["commit", "-q", "-m", "lib"]);
+
+        let repo = temp.path().join("repo");
+        init_repo(&repo);
+        run(
+            &repo,
+            &["submodule", "add", "-q", &sub.to_string_lossy(), "libs/sub"],
+        );
+        run(&repo, &["commit", "-q", "-m", "add submodule"]);
+        // A clone that never initialized its submodules
+        let clone = temp.path().join("clone");
+        run(
+            temp.path(),
+            &[
+                "clone",
+                "-q",
+                &repo.to_string_lossy(),
+                &clone.to_string_lossy(),
+            ],
+        );
+        let worktree = temp.path().join("wt");
+        run(
+            &clone,
+            &[
+                "worktree",
+                "add",
+                "-q",
+                "-b",
+                "wt",
+                &worktree.to_string_lossy(),
+            ],
+        );
+
+        let cache = temp.path().join("cache");
+        let result = init_submodules(&clone, &worktree, &cache);
+
+        std::env::remove_var("GIT_CONFIG_COUNT");
+        std::env::remove_var("GIT_CONFIG_KEY_0");
+        std::env::remove_var("GIT_CONFIG_VALUE_0");
+
+        result.unwrap();
+        assert!(worktree.join("libs/sub/lib.txt").is_file());
+        assert!(mirror_dir(&cache, &sub.to_string_lossy())
+            .join("objects")
+            .is_dir());
+    }
+}
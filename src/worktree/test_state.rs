@@ -234,6 +234,7 @@ fn test_validate_initial_state_valid() {
         interruption_type: None,
         last_checkpoint: None,
         resumable: false,
+        owner: None,
     };
 
     assert!(state.validate_initial_state("test-session", "test-branch"));
@@ -263,6 +264,7 @@ fn test_validate_initial_state_wrong_session_id() {
         interruption_type: None,
         last_checkpoint: None,
         resumable: false,
+        owner: None,
     };
 
     assert!(!state.validate_initial_state("test-session", "test-branch"));
@@ -292,6 +294,7 @@ fn test_validate_initial_state_wrong_status() {
         interruption_type: None,
         last_checkpoint: None,
         resumable: false,
+        owner: None,
     };
 
     assert!(!state.validate_initial_state("test-session", "test-branch"));
@@ -321,6 +324,7 @@ fn test_validate_initial_state_non_zero_iterations() {
         interruption_type: None,
         last_checkpoint: None,
         resumable: false,
+        owner: None,
     };
 
     assert!(!state.validate_initial_state("test-session", "test-branch"));
@@ -350,6 +354,7 @@ fn test_validate_initial_state_already_merged() {
         interruption_type: None,
         last_checkpoint: None,
         resumable: false,
+        owner: None,
     };
 
     assert!(!state.validate_initial_state("test-session", "test-branch"));
@@ -379,6 +384,7 @@ fn test_validate_initial_state_has_error() {
         interruption_type: None,
         last_checkpoint: None,
         resumable: false,
+        owner: None,
     };
 
     assert!(!state.validate_initial_state("test-session", "test-branch"));
@@ -408,6 +414,7 @@ fn test_validate_initial_state_wrong_branch() {
         interruption_type: None,
         last_checkpoint: None,
         resumable: false,
+        owner: None,
     };
 
     assert!(!state.validate_initial_state("test-session", "test-branch"));
@@ -437,6 +444,7 @@ fn test_validate_initial_state_wrong_worktree_name() {
         interruption_type: None,
         last_checkpoint: None,
         resumable: false,
+        owner: None,
     };
 
     assert!(!state.validate_initial_state("test-session", "test-branch"));
@@ -466,6 +474,7 @@ fn test_validate_initial_state_wrong_max_iterations() {
         interruption_type: None,
         last_checkpoint: None,
         resumable: false,
+        owner: None,
     };
 
     assert!(!state.validate_initial_state("test-session", "test-branch"));
@@ -356,6 +356,7 @@ fn test_filter_sessions_by_status() {
             interruption_type: None,
             last_checkpoint: None,
             resumable: true,
+            owner: None,
         },
         WorktreeState {
             session_id: "session2".to_string(),
@@ -379,6 +380,7 @@ fn test_filter_sessions_by_status() {
             interruption_type: None,
             last_checkpoint: None,
             resumable: true,
+            owner: None,
         },
         WorktreeState {
             session_id: "session3".to_string(),
@@ -402,6 +404,7 @@ fn test_filter_sessions_by_status() {
             interruption_type: None,
             last_checkpoint: None,
             resumable: true,
+            owner: None,
         },
     ];
 
@@ -454,6 +457,7 @@ fn test_load_state_from_file() {
         interruption_type: None,
         last_checkpoint: None,
         resumable: true,
+        owner: None,
     };
 
     // Write valid JSON file
@@ -124,6 +124,7 @@ async fn test_list_sessions_with_metadata_fallback() -> Result<()> {
         interruption_type: None,
         last_checkpoint: None,
         resumable: false,
+        owner: None,
     };
 
     // Save the orphaned state with correct filename format (must start with "session-")
@@ -195,6 +196,7 @@ async fn test_metadata_sessions_exclude_cleaned_up() -> Result<()> {
         interruption_type: None,
         last_checkpoint: None,
         resumable: false,
+        owner: None,
     };
 
     // Save the cleaned up state
@@ -52,6 +52,7 @@ fn create_test_checkpoint(
                 worktree_session_id: Some(format!("session-{}", i)),
                 json_log_location: None,
                 cleanup_status: None,
+                no_changes: None,
             },
             completed_at: Utc::now(),
         });
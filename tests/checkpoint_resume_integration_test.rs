@@ -50,6 +50,9 @@ async fn test_checkpoint_includes_workflow_path() -> Result<()> {
         workflow_name: Some("test".to_string()),
         error_recovery_state: None,
         retry_checkpoint_state: None,
+        failure_chains: Vec::new(),
+        repo: None,
+        step_selection: None,
     };
 
     // Save checkpoint
@@ -111,6 +114,7 @@ async fn test_checkpoint_roundtrip() -> Result<()> {
                 duration: std::time::Duration::from_secs(1),
                 completed_at: chrono::Utc::now(),
                 retry_state: None,
+                changes: None,
             },
             prodigy::cook::workflow::checkpoint::CompletedStep {
                 step_index: 1,
@@ -121,6 +125,7 @@ async fn test_checkpoint_roundtrip() -> Result<()> {
                 duration: std::time::Duration::from_secs(1),
                 completed_at: chrono::Utc::now(),
                 retry_state: None,
+                changes: None,
             },
         ],
         variable_state: {
@@ -137,6 +142,9 @@ async fn test_checkpoint_roundtrip() -> Result<()> {
         workflow_name: Some("roundtrip".to_string()),
         error_recovery_state: None,
         retry_checkpoint_state: None,
+        failure_chains: Vec::new(),
+        repo: None,
+        step_selection: None,
     };
 
     // Save checkpoint
@@ -197,7 +205,10 @@ async fn test_resume_fails_without_workflow_path() -> Result<()> {
         total_steps: 2,
         workflow_name: Some("legacy".to_string()),
         error_recovery_state: None,
+        repo: None,
+        step_selection: None,
         retry_checkpoint_state: None,
+        failure_chains: Vec::new(),
     };
 
     // Save checkpoint
@@ -303,8 +314,11 @@ async fn test_checkpoint_preserves_variables() -> Result<()> {
         workflow_hash: "vars-hash".to_string(),
         total_steps: 1,
         workflow_name: Some("vars".to_string()),
+        repo: None,
+        step_selection: None,
         error_recovery_state: None,
         retry_checkpoint_state: None,
+        failure_chains: Vec::new(),
     };
 
     // Save and reload
@@ -378,6 +392,7 @@ async fn test_checkpoint_execution_progress() -> Result<()> {
                     duration: std::time::Duration::from_secs(1),
                     completed_at: chrono::Utc::now(),
                     retry_state: None,
+                    changes: None,
                 })
                 .collect(),
             variable_state: std::collections::HashMap::new(),
@@ -387,9 +402,12 @@ async fn test_checkpoint_execution_progress() -> Result<()> {
             version: prodigy::cook::workflow::checkpoint::CHECKPOINT_VERSION,
             workflow_hash: "progress-hash".to_string(),
             total_steps: 5,
+            repo: None,
+            step_selection: None,
             workflow_name: Some("progress".to_string()),
             error_recovery_state: None,
             retry_checkpoint_state: None,
+            failure_chains: Vec::new(),
         };
 
         #[allow(deprecated)]
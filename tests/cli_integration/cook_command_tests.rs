@@ -1 +1,80 @@
+// Tests for the 'run' command
 
+use super::test_utils::*;
+use std::path::Path;
+use std::process::Command;
+
+fn init_repo(dir: &Path, config: Option<&str>) {
+    std::fs::create_dir_all(dir).unwrap();
+    std::fs::write(dir.join("README.md"), "# repo\n").unwrap();
+    if let Some(config) = config {
+        std::fs::create_dir_all(dir.join(".prodigy")).unwrap();
+        std::fs::write(dir.join(".prodigy/config.yml"), config).unwrap();
+    }
+    for args in [
+        vec!["init", "-q"],
+        vec!["add", "."],
+        vec![
+            "-c",
+            "user.email=test@example.com",
+            "-c",
+            "user.name=Test User",
+            "commit",
+            "-q",
+            "-m",
+            "Initial commit",
+        ],
+    ] {
+        let status = Command::new("git")
+            .args(&args)
+            .current_dir(dir)
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {:?} failed", args);
+    }
+}
+
+/// Each repository of a multi-repo run uses its own project config, and the
+/// report follows `PRODIGY_PLAIN`
+#[test]
+fn test_run_across_repos_loads_each_repo_config() {
+    let test = CliTest::new();
+    init_repo(
+        &test.temp_path().join("strict"),
+        Some("strict_variables: true\n"),
+    );
+    init_repo(&test.temp_path().join("lenient"), None);
+    std::fs::write(test.temp_path().join("repos.txt"), "strict\nlenient\n").unwrap();
+    let (test, workflow_path) = test.with_workflow(
+        "undefined",
+        r#"
+commands:
+  - shell: "echo ${undefined_variable}"
+"#,
+    );
+
+    let output = test
+        .env("PRODIGY_PLAIN", "1")
+        .arg("run")
+        .arg(workflow_path.to_str().unwrap())
+        .arg("--repos")
+        .arg("repos.txt")
+        .run();
+
+    assert_eq!(output.exit_code, exit_codes::GENERAL_ERROR);
+    assert!(
+        output.stdout_contains("1 succeeded, 1 failed, 2 total"),
+        "{}",
+        output.stdout
+    );
+    let report_line = |name: &str| {
+        output
+            .stdout
+            .lines()
+            .find(|line| line.trim_start().starts_with(name))
+            .unwrap_or_default()
+            .to_string()
+    };
+    assert!(report_line("strict").contains("Undefined variable"));
+    assert!(report_line("lenient").ends_with("[ok]"));
+}
@@ -0,0 +1,42 @@
+// Tests for the 'logs' command
+
+use super::test_utils::*;
+
+#[test]
+fn test_logs_claude_reads_relocated_storage() {
+    let storage = tempfile::TempDir::new().unwrap();
+    let log_dir = storage.path().join("logs/claude-streaming");
+    std::fs::create_dir_all(&log_dir).unwrap();
+    let log = log_dir.join("20250101_000000-run.jsonl");
+    std::fs::write(
+        &log,
+        r#"{"type":"assistant","message":{"content":[{"type":"text","text":"Fixing the test"}]}}
+"#,
+    )
+    .unwrap();
+    std::fs::write(
+        log_dir.join("index.jsonl"),
+        format!(
+            "{}\n",
+            serde_json::json!({
+                "log": log,
+                "started_at": "2025-01-01T00:00:00Z",
+                "command": "/prodigy-fix",
+                "session_id": "session-relocated",
+            })
+        ),
+    )
+    .unwrap();
+
+    let mut test = CliTest::new()
+        .env_remove("PRODIGY_HOME")
+        .env("PRODIGY_STORAGE_DIR", storage.path().to_str().unwrap())
+        .arg("logs")
+        .arg("claude")
+        .arg("session-relocated");
+
+    let output = test.run();
+
+    assert_eq!(output.exit_code, exit_codes::SUCCESS, "{}", output.stderr);
+    assert!(output.stdout_contains("assistant: Fixing the test"));
+}
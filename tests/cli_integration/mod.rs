@@ -12,6 +12,7 @@ pub mod dlq_command_tests;
 pub mod dry_run_tests;
 pub mod events_command_tests;
 pub mod exec_command_tests;
+pub mod logs_command_tests;
 pub mod resume_command_tests;
 pub mod resume_integration_tests;
 pub mod signal_handling_tests;
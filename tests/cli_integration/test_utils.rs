@@ -122,6 +122,12 @@ impl CliTest {
         self
     }
 
+    /// Unset an environment variable, such as the isolating `PRODIGY_HOME`
+    pub fn env_remove(mut self, key: &str) -> Self {
+        self.command.env_remove(key);
+        self
+    }
+
     /// Set configuration content
     pub fn with_config(mut self, content: &str) -> Self {
         self.config_content = Some(content.to_string());
@@ -376,6 +376,8 @@ async fn test_resume_manager_dlq_integration() {
         skip_validation: false,
         from_checkpoint: None,
         max_parallel: None,
+        max_items: None,
+        agent_timeout_secs: None,
         force_recreation: false,
         validate_environment: false,
     };
@@ -388,6 +390,8 @@ async fn test_resume_manager_dlq_integration() {
         skip_validation: false,
         from_checkpoint: None,
         max_parallel: None,
+        max_items: None,
+        agent_timeout_secs: None,
         force_recreation: false,
         validate_environment: false,
     };
@@ -445,6 +449,7 @@ async fn test_agent_result_to_dlq_item_conversion() {
         worktree_session_id: None,
         json_log_location: Some("/path/to/log.json".to_string()),
         cleanup_status: None,
+        no_changes: None,
     };
 
     let dlq_item = agent_result_to_dlq_item(&failed_result, &work_item, 1);
@@ -476,6 +481,7 @@ async fn test_agent_result_to_dlq_item_conversion() {
         worktree_session_id: None,
         json_log_location: Some("/path/to/timeout-log.json".to_string()),
         cleanup_status: None,
+        no_changes: None,
     };
 
     let timeout_dlq_item = agent_result_to_dlq_item(&timeout_result, &work_item, 1);
@@ -509,6 +515,7 @@ async fn test_agent_result_to_dlq_item_conversion() {
         worktree_session_id: None,
         json_log_location: Some("/path/to/success-log.json".to_string()),
         cleanup_status: None,
+        no_changes: None,
     };
 
     let success_dlq_item = agent_result_to_dlq_item(&success_result, &work_item, 1);
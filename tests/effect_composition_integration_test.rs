@@ -37,6 +37,13 @@ fn create_workflow_config() -> WorkflowConfig {
         env_files: None,
         profiles: None,
         merge: None,
+        concurrency: None,
+        claude_permissions: None,
+        always: Vec::new(),
+        strict_variables: None,
+        defaults: None,
+        protected_paths: None,
+        score_gate: None,
     }
 }
 
@@ -52,8 +59,11 @@ fn create_mapreduce_config(max_parallel: usize) -> MapReduceWorkflowConfig {
         map: MapPhaseYaml {
             input: "items.json".to_string(),
             json_path: "$.items[*]".to_string(),
-            agent_template: AgentTemplate { commands: vec![] },
-            max_parallel: max_parallel.to_string(),
+            agent_template: AgentTemplate {
+                commands: vec![],
+                idempotency: None,
+            },
+            max_parallel: Some(max_parallel.to_string()),
             filter: None,
             sort_by: None,
             max_items: None,
@@ -61,6 +71,16 @@ fn create_mapreduce_config(max_parallel: usize) -> MapReduceWorkflowConfig {
             distinct: None,
             agent_timeout_secs: None,
             timeout_config: None,
+            rebase_onto: None,
+            speculative: None,
+            item_key: None,
+            affinity: Vec::new(),
+            build_cache: Vec::new(),
+            claude_permissions: None,
+            network: Default::default(),
+            changed_since: None,
+            changed_path: None,
+            batch_size: None,
         },
         reduce: None,
         error_policy: Default::default(),
@@ -70,6 +90,13 @@ fn create_mapreduce_config(max_parallel: usize) -> MapReduceWorkflowConfig {
         failure_threshold: None,
         error_collection: None,
         merge: None,
+        notifications: None,
+        concurrency: None,
+        claude_permissions: None,
+        always: Vec::new(),
+        defaults: None,
+        protected_paths: None,
+        strict_variables: None,
     }
 }
 
@@ -93,11 +120,35 @@ fn create_cook_config(mapreduce: bool, max_parallel: usize, dry_run: bool) -> Co
             verbosity: 0,
             quiet: false,
             dry_run,
+            read_only: false,
+            record: None,
+            junit_output: None,
+            profile_steps: false,
+            replay: None,
+            chaos: None,
+            seed: None,
+            answers_file: None,
+            environment: None,
+            no_worktree: false,
             params: Default::default(),
+            package: None,
+            changed_only: None,
+            explain_failure: false,
+            keep_going: false,
+            steps: Default::default(),
+            explicit_flags: Default::default(),
         },
         project_path: Arc::new(PathBuf::from(".")),
         workflow: Arc::new(create_workflow_config()),
         mapreduce_config,
+        replay: None,
+        chaos: None,
+        junit: None,
+        profiler: None,
+        protected_paths: None,
+        answers: None,
+        failures: None,
+        settings: Default::default(),
     }
 }
 
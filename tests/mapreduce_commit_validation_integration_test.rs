@@ -87,6 +87,7 @@ fn test_dlq_captures_commit_validation_failures() {
         worktree_session_id: Some("session-123".to_string()),
         json_log_location: Some("/path/to/log.json".to_string()),
         cleanup_status: None,
+        no_changes: None,
     };
 
     let work_item = json!({"id": 1, "file": "test.txt"});
@@ -136,6 +137,7 @@ fn test_dlq_classification_from_error_message() {
             worktree_session_id: None,
             json_log_location: None,
             cleanup_status: None,
+            no_changes: None,
         };
 
         let work_item = json!({"test": true});
@@ -174,6 +176,7 @@ fn test_successful_agents_not_added_to_dlq() {
         worktree_session_id: None,
         json_log_location: Some("/path/to/log.json".to_string()),
         cleanup_status: None,
+        no_changes: None,
     };
 
     let work_item = json!({"test": true});
@@ -36,7 +36,7 @@ map:
     assert_eq!(env.get("TIMEOUT_SECONDS"), Some(&"900".to_string()));
 
     // Verify numeric fields accept string values (for env var references)
-    assert_eq!(config.map.max_parallel, "${MAX_PARALLEL}");
+    assert_eq!(config.map.max_parallel.as_deref(), Some("${MAX_PARALLEL}"));
     assert_eq!(
         config.map.agent_timeout_secs,
         Some("${TIMEOUT_SECONDS}".to_string())
@@ -72,7 +72,7 @@ map:
     let config = parse_mapreduce_workflow(workflow_yaml)?;
 
     // Verify numeric fields accept direct numeric values too
-    assert_eq!(config.map.max_parallel, "3");
+    assert_eq!(config.map.max_parallel.as_deref(), Some("3"));
     assert_eq!(config.map.agent_timeout_secs, Some("600".to_string()));
 
     // Verify we can convert to MapPhase
@@ -107,7 +107,7 @@ map:
     let config = parse_mapreduce_workflow(workflow_yaml)?;
 
     // Should parse successfully
-    assert_eq!(config.map.max_parallel, "${MISSING_VAR}");
+    assert_eq!(config.map.max_parallel.as_deref(), Some("${MISSING_VAR}"));
 
     // But conversion to MapPhase should fail with clear error
     let result = config.to_map_phase();
@@ -178,7 +178,7 @@ map:
     let config = parse_mapreduce_workflow(workflow_yaml)?;
 
     // Both syntaxes should be accepted
-    assert_eq!(config.map.max_parallel, "$VAR1");
+    assert_eq!(config.map.max_parallel.as_deref(), Some("$VAR1"));
     assert_eq!(config.map.agent_timeout_secs, Some("${VAR2}".to_string()));
 
     // Both should resolve correctly
@@ -23,6 +23,7 @@ fn create_test_env(temp_dir: &TempDir) -> ExecutionEnvironment {
         project_dir: Arc::new(temp_dir.path().to_path_buf()),
         worktree_name: Some(Arc::from("test-worktree")),
         session_id: Arc::from("test-session"),
+        package: None,
     }
 }
 
@@ -52,6 +53,8 @@ async fn test_setup_phase_execution() {
         ],
         timeout: Some(30),
         capture_outputs: HashMap::new(),
+        artifacts: Vec::new(),
+        shared_context: None,
     };
 
     // Create a minimal map phase (required)
@@ -69,6 +72,24 @@ async fn test_setup_phase_execution() {
         distinct: None,
         timeout_config: None,
         workflow_env: HashMap::new(),
+        rebase_onto: None,
+        speculative: None,
+        item_key: None,
+        affinity: Vec::new(),
+        build_cache: Vec::new(),
+        network: Default::default(),
+        strict_variables: false,
+        setup_artifacts: None,
+        chaos: None,
+        junit: None,
+        protected_paths: None,
+        seed: None,
+        failures: None,
+        idempotency: None,
+        notifications: None,
+        claude_permissions: None,
+        changed_path: None,
+        changed_since: None,
     };
 
     // Create coordinator
@@ -110,6 +131,8 @@ async fn test_setup_phase_failure() {
         }],
         timeout: Some(30),
         capture_outputs: HashMap::new(),
+        artifacts: Vec::new(),
+        shared_context: None,
     };
 
     let map_phase = MapPhase {
@@ -126,6 +149,24 @@ async fn test_setup_phase_failure() {
         distinct: None,
         timeout_config: None,
         workflow_env: HashMap::new(),
+        rebase_onto: None,
+        speculative: None,
+        item_key: None,
+        affinity: Vec::new(),
+        build_cache: Vec::new(),
+        network: Default::default(),
+        strict_variables: false,
+        setup_artifacts: None,
+        chaos: None,
+        junit: None,
+        protected_paths: None,
+        seed: None,
+        failures: None,
+        idempotency: None,
+        notifications: None,
+        claude_permissions: None,
+        changed_path: None,
+        changed_since: None,
     };
 
     let coordinator = PhaseCoordinator::new(Some(setup_phase), map_phase, None, subprocess.clone());
@@ -172,6 +213,24 @@ async fn test_map_phase_with_json_input() {
         distinct: None,
         timeout_config: None,
         workflow_env: HashMap::new(),
+        rebase_onto: None,
+        speculative: None,
+        item_key: None,
+        affinity: Vec::new(),
+        build_cache: Vec::new(),
+        network: Default::default(),
+        strict_variables: false,
+        setup_artifacts: None,
+        chaos: None,
+        junit: None,
+        protected_paths: None,
+        seed: None,
+        failures: None,
+        idempotency: None,
+        notifications: None,
+        claude_permissions: None,
+        changed_path: None,
+        changed_since: None,
     };
 
     let coordinator = PhaseCoordinator::new(None, map_phase, None, subprocess.clone());
@@ -206,6 +265,24 @@ async fn test_reduce_phase_aggregation() {
         distinct: None,
         timeout_config: None,
         workflow_env: HashMap::new(),
+        rebase_onto: None,
+        speculative: None,
+        item_key: None,
+        affinity: Vec::new(),
+        build_cache: Vec::new(),
+        network: Default::default(),
+        strict_variables: false,
+        setup_artifacts: None,
+        chaos: None,
+        junit: None,
+        protected_paths: None,
+        seed: None,
+        failures: None,
+        idempotency: None,
+        notifications: None,
+        claude_permissions: None,
+        changed_path: None,
+        changed_since: None,
     };
 
     // Create reduce phase
@@ -215,6 +292,8 @@ async fn test_reduce_phase_aggregation() {
             ..Default::default()
         }],
         timeout_secs: Some(30),
+        results_file: None,
+        claude_permissions: None,
     };
 
     let coordinator =
@@ -241,6 +320,8 @@ async fn test_coordinator_state_machine() {
         }],
         timeout: Some(30),
         capture_outputs: HashMap::new(),
+        artifacts: Vec::new(),
+        shared_context: None,
     };
 
     let map_phase = MapPhase {
@@ -257,6 +338,24 @@ async fn test_coordinator_state_machine() {
         distinct: None,
         timeout_config: None,
         workflow_env: HashMap::new(),
+        rebase_onto: None,
+        speculative: None,
+        item_key: None,
+        affinity: Vec::new(),
+        build_cache: Vec::new(),
+        network: Default::default(),
+        strict_variables: false,
+        setup_artifacts: None,
+        chaos: None,
+        junit: None,
+        protected_paths: None,
+        seed: None,
+        failures: None,
+        idempotency: None,
+        notifications: None,
+        claude_permissions: None,
+        changed_path: None,
+        changed_since: None,
     };
 
     let reduce_phase = ReducePhase {
@@ -265,6 +364,8 @@ async fn test_coordinator_state_machine() {
             ..Default::default()
         }],
         timeout_secs: Some(30),
+        results_file: None,
+        claude_permissions: None,
     };
 
     let coordinator = PhaseCoordinator::new(
@@ -306,6 +407,8 @@ async fn test_phase_context_variable_passing() {
         }],
         timeout: Some(30),
         capture_outputs,
+        artifacts: Vec::new(),
+        shared_context: None,
     };
 
     let map_phase = MapPhase {
@@ -322,6 +425,24 @@ async fn test_phase_context_variable_passing() {
         distinct: None,
         timeout_config: None,
         workflow_env: HashMap::new(),
+        rebase_onto: None,
+        speculative: None,
+        item_key: None,
+        affinity: Vec::new(),
+        build_cache: Vec::new(),
+        network: Default::default(),
+        strict_variables: false,
+        setup_artifacts: None,
+        chaos: None,
+        junit: None,
+        protected_paths: None,
+        seed: None,
+        failures: None,
+        idempotency: None,
+        notifications: None,
+        claude_permissions: None,
+        changed_path: None,
+        changed_since: None,
     };
 
     let coordinator = PhaseCoordinator::new(Some(setup_phase), map_phase, None, subprocess.clone());
@@ -346,6 +467,8 @@ async fn test_phase_executor_trait_implementation() {
         }],
         timeout: Some(30),
         capture_outputs: HashMap::new(),
+        artifacts: Vec::new(),
+        shared_context: None,
     };
 
     let executor =
@@ -387,6 +510,24 @@ async fn test_empty_workflow_completes() {
         distinct: None,
         timeout_config: None,
         workflow_env: HashMap::new(),
+        rebase_onto: None,
+        speculative: None,
+        item_key: None,
+        affinity: Vec::new(),
+        build_cache: Vec::new(),
+        network: Default::default(),
+        strict_variables: false,
+        setup_artifacts: None,
+        chaos: None,
+        junit: None,
+        protected_paths: None,
+        seed: None,
+        failures: None,
+        idempotency: None,
+        notifications: None,
+        claude_permissions: None,
+        changed_path: None,
+        changed_since: None,
     };
 
     let coordinator = PhaseCoordinator::new(None, map_phase, None, subprocess.clone());
@@ -182,6 +182,7 @@ async fn create_partial_job_state(
                 worktree_session_id: None,
                 json_log_location: None,
                 cleanup_status: None,
+                no_changes: None,
             },
         );
     }
@@ -216,6 +217,7 @@ async fn create_partial_job_state(
                 worktree_session_id: None,
                 json_log_location: None,
                 cleanup_status: None,
+                no_changes: None,
             },
         );
     }
@@ -223,6 +225,9 @@ async fn create_partial_job_state(
     let agent_template = vec![WorkflowStep {
         name: None,
         claude: Some("/process ${item}".to_string()),
+        model: None,
+        context: None,
+        validate_output: None,
         shell: None,
         test: None,
         foreach: None,
@@ -250,12 +255,22 @@ async fn create_partial_job_state(
         validation_timeout: None,
         ignore_validation_failure: false,
         when: None,
+        assert: None,
+        summarize_commits: None,
+        claude_chat: None,
+        script: None,
+        snapshot: false,
+        required: false,
+        commit_policy: None,
     }];
 
     let reduce_commands = if total > 0 {
         Some(vec![WorkflowStep {
             name: None,
             claude: Some("/summarize ${map.results}".to_string()),
+            model: None,
+            context: None,
+            validate_output: None,
             shell: None,
             test: None,
             foreach: None,
@@ -283,6 +298,13 @@ async fn create_partial_job_state(
             validation_timeout: None,
             ignore_validation_failure: false,
             when: None,
+            assert: None,
+            summarize_commits: None,
+            claude_chat: None,
+            script: None,
+            snapshot: false,
+            required: false,
+            commit_policy: None,
         }])
     } else {
         None
@@ -390,6 +412,7 @@ async fn test_resume_workflow_from_checkpoint() {
         project_dir: Arc::new(project_root.clone()),
         worktree_name: None,
         session_id: Arc::from("test-session"),
+        package: None,
     };
     let result = resume_manager
         .resume_job(job_id, options, &env)
@@ -505,6 +528,7 @@ async fn test_resume_with_dlq_recovery() {
         project_dir: Arc::new(project_root.clone()),
         worktree_name: None,
         session_id: Arc::from("test-session"),
+        package: None,
     };
     let result = resume_manager
         .resume_job(job_id, options, &env)
@@ -574,6 +598,7 @@ async fn test_resume_completed_workflow() {
         project_dir: Arc::new(project_root.clone()),
         worktree_name: None,
         session_id: Arc::from("test-session"),
+        package: None,
     };
     let result = resume_manager
         .resume_job(job_id, options, &env)
@@ -633,6 +658,7 @@ async fn test_cross_worktree_coordination() {
                 worktree_session_id: None,
                 json_log_location: None,
                 cleanup_status: None,
+                no_changes: None,
             },
         );
         state1.successful_count += 1;
@@ -667,6 +693,7 @@ async fn test_cross_worktree_coordination() {
                 worktree_session_id: None,
                 json_log_location: None,
                 cleanup_status: None,
+                no_changes: None,
             },
         );
         state2.successful_count += 1;
@@ -755,6 +782,7 @@ async fn test_resume_with_environment_validation() {
         project_dir: Arc::new(project_root.clone()),
         worktree_name: None,
         session_id: Arc::from("test-session"),
+        package: None,
     };
     let result = resume_manager.resume_job(job_id, options, &env).await;
 
@@ -805,6 +833,7 @@ async fn test_force_resume_completed_job() {
         project_dir: Arc::new(project_root.clone()),
         worktree_name: None,
         session_id: Arc::from("test-session"),
+        package: None,
     };
     let result = resume_manager
         .resume_job(job_id, options, &env)
@@ -33,6 +33,13 @@ mod execution_planning {
             env_files: None,
             profiles: None,
             merge: None,
+            concurrency: None,
+            claude_permissions: None,
+            always: Vec::new(),
+            strict_variables: None,
+            defaults: None,
+            protected_paths: None,
+            score_gate: None,
         }
     }
 
@@ -48,8 +55,11 @@ mod execution_planning {
             map: MapPhaseYaml {
                 input: "items.json".to_string(),
                 json_path: "$.items[*]".to_string(),
-                agent_template: AgentTemplate { commands: vec![] },
-                max_parallel: max_parallel.to_string(),
+                agent_template: AgentTemplate {
+                    commands: vec![],
+                    idempotency: None,
+                },
+                max_parallel: Some(max_parallel.to_string()),
                 filter: None,
                 sort_by: None,
                 max_items: None,
@@ -57,6 +67,16 @@ mod execution_planning {
                 distinct: None,
                 agent_timeout_secs: None,
                 timeout_config: None,
+                rebase_onto: None,
+                speculative: None,
+                item_key: None,
+                affinity: Vec::new(),
+                build_cache: Vec::new(),
+                claude_permissions: None,
+                network: Default::default(),
+                changed_path: None,
+                changed_since: None,
+                batch_size: None,
             },
             reduce: None,
             error_policy: Default::default(),
@@ -66,6 +86,13 @@ mod execution_planning {
             failure_threshold: None,
             error_collection: None,
             merge: None,
+            notifications: None,
+            concurrency: None,
+            claude_permissions: None,
+            always: Vec::new(),
+            defaults: None,
+            protected_paths: None,
+            strict_variables: None,
         };
 
         CookConfig {
@@ -81,11 +108,35 @@ mod execution_planning {
                 verbosity: 0,
                 quiet: false,
                 dry_run,
+                read_only: false,
+                record: None,
+                junit_output: None,
+                profile_steps: false,
+                replay: None,
+                chaos: None,
+                seed: None,
+                answers_file: None,
+                environment: None,
+                no_worktree: false,
                 params: Default::default(),
+                package: None,
+                changed_only: None,
+                explain_failure: false,
+                keep_going: false,
+                steps: Default::default(),
+                explicit_flags: Default::default(),
             },
             project_path: Arc::new(PathBuf::from(".")),
             workflow: Arc::new(create_default_workflow_config()),
             mapreduce_config: Some(Arc::new(mr_config)),
+            replay: None,
+            chaos: None,
+            junit: None,
+            profiler: None,
+            protected_paths: None,
+            answers: None,
+            failures: None,
+            settings: Default::default(),
         }
     }
 
@@ -172,7 +223,23 @@ mod mode_detection {
                 verbosity: 0,
                 quiet: false,
                 dry_run: false,
+                read_only: false,
+                record: None,
+                junit_output: None,
+                profile_steps: false,
+                replay: None,
+                chaos: None,
+                seed: None,
+                answers_file: None,
+                environment: None,
+                no_worktree: false,
                 params: Default::default(),
+                package: None,
+                changed_only: None,
+                explain_failure: false,
+                keep_going: false,
+                steps: Default::default(),
+                explicit_flags: Default::default(),
             },
             project_path: Arc::new(PathBuf::from(".")),
             workflow: Arc::new(WorkflowConfig {
@@ -183,8 +250,23 @@ mod mode_detection {
                 env_files: None,
                 profiles: None,
                 merge: None,
+                concurrency: None,
+                claude_permissions: None,
+                always: Vec::new(),
+                strict_variables: None,
+                defaults: None,
+                protected_paths: None,
+                score_gate: None,
             }),
             mapreduce_config: None,
+            replay: None,
+            chaos: None,
+            junit: None,
+            profiler: None,
+            protected_paths: None,
+            answers: None,
+            failures: None,
+            settings: Default::default(),
         }
     }
 
@@ -254,8 +336,11 @@ mod resource_allocation {
             map: MapPhaseYaml {
                 input: "items.json".to_string(),
                 json_path: "$.items[*]".to_string(),
-                agent_template: AgentTemplate { commands: vec![] },
-                max_parallel: max_parallel.to_string(),
+                agent_template: AgentTemplate {
+                    commands: vec![],
+                    idempotency: None,
+                },
+                max_parallel: Some(max_parallel.to_string()),
                 filter: None,
                 sort_by: None,
                 max_items: None,
@@ -263,6 +348,16 @@ mod resource_allocation {
                 distinct: None,
                 agent_timeout_secs: None,
                 timeout_config: None,
+                rebase_onto: None,
+                speculative: None,
+                item_key: None,
+                affinity: Vec::new(),
+                build_cache: Vec::new(),
+                claude_permissions: None,
+                network: Default::default(),
+                changed_path: None,
+                changed_since: None,
+                batch_size: None,
             },
             reduce: None,
             error_policy: Default::default(),
@@ -272,6 +367,13 @@ mod resource_allocation {
             failure_threshold: None,
             error_collection: None,
             merge: None,
+            notifications: None,
+            concurrency: None,
+            claude_permissions: None,
+            always: Vec::new(),
+            defaults: None,
+            protected_paths: None,
+            strict_variables: None,
         };
 
         CookConfig {
@@ -287,7 +389,23 @@ mod resource_allocation {
                 verbosity: 0,
                 quiet: false,
                 dry_run: false,
+                read_only: false,
+                record: None,
+                junit_output: None,
+                profile_steps: false,
+                replay: None,
+                chaos: None,
+                seed: None,
+                answers_file: None,
+                environment: None,
+                no_worktree: false,
                 params: Default::default(),
+                package: None,
+                changed_only: None,
+                explain_failure: false,
+                keep_going: false,
+                steps: Default::default(),
+                explicit_flags: Default::default(),
             },
             project_path: Arc::new(PathBuf::from(".")),
             workflow: Arc::new(WorkflowConfig {
@@ -298,8 +416,23 @@ mod resource_allocation {
                 env_files: None,
                 profiles: None,
                 merge: None,
+                concurrency: None,
+                claude_permissions: None,
+                always: Vec::new(),
+                strict_variables: None,
+                defaults: None,
+                protected_paths: None,
+                score_gate: None,
             }),
             mapreduce_config: Some(Arc::new(mr_config)),
+            replay: None,
+            chaos: None,
+            junit: None,
+            profiler: None,
+            protected_paths: None,
+            answers: None,
+            failures: None,
+            settings: Default::default(),
         }
     }
 
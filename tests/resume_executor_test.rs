@@ -221,6 +221,9 @@ fn create_test_checkpoint(workflow_id: &str, workflow_path: PathBuf) -> Workflow
         workflow_name: Some("test-workflow".to_string()),
         error_recovery_state: None,
         retry_checkpoint_state: None,
+        failure_chains: Vec::new(),
+        repo: None,
+        step_selection: None,
     }
 }
 
@@ -530,6 +533,7 @@ async fn test_execute_from_checkpoint_restore_context() -> Result<()> {
         duration: std::time::Duration::from_secs(1),
         completed_at: chrono::Utc::now(),
         retry_state: None,
+        changes: None,
     });
     checkpoint.execution_state.current_step_index = 1;
 
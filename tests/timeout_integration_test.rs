@@ -104,6 +104,9 @@ fn test_timeout_field_serialization() {
         shell: Some("npm test".to_string()),
         timeout: Some(300),
         claude: None,
+        model: None,
+        context: None,
+        validate_output: None,
         analyze: None,
         test: None,
         foreach: None,
@@ -120,6 +123,15 @@ fn test_timeout_field_serialization() {
         on_success: None,
         validate: None,
         when: None,
+        assert: None,
+        summarize_commits: None,
+        claude_chat: None,
+        script: None,
+        snapshot: false,
+        required: false,
+        commit_policy: None,
+        cwd: None,
+        env: std::collections::HashMap::new(),
     };
 
     let yaml = serde_yaml::to_string(&step).unwrap();
@@ -27,6 +27,13 @@ async fn test_basic_workflow_composition() -> Result<()> {
         env_files: None,
         profiles: None,
         merge: None,
+        concurrency: None,
+        claude_permissions: None,
+        always: Vec::new(),
+        strict_variables: None,
+        defaults: None,
+        protected_paths: None,
+        score_gate: None,
     });
 
     assert!(!workflow.uses_composition());
@@ -43,6 +50,13 @@ async fn test_workflow_with_parameters() -> Result<()> {
         env_files: None,
         profiles: None,
         merge: None,
+        concurrency: None,
+        claude_permissions: None,
+        always: Vec::new(),
+        strict_variables: None,
+        defaults: None,
+        protected_paths: None,
+        score_gate: None,
     });
 
     // Add parameter definitions
@@ -53,6 +67,9 @@ async fn test_workflow_with_parameters() -> Result<()> {
             description: "File to process".to_string(),
             default: None,
             validation: None,
+            values: vec![],
+            secret: false,
+            env: None,
         }],
         optional: vec![Parameter {
             name: "style".to_string(),
@@ -60,6 +77,9 @@ async fn test_workflow_with_parameters() -> Result<()> {
             description: "Processing style".to_string(),
             default: Some(Value::String("functional".to_string())),
             validation: None,
+            values: vec![],
+            secret: false,
+            env: None,
         }],
     });
 
@@ -89,6 +109,13 @@ async fn test_workflow_with_imports() -> Result<()> {
         env_files: None,
         profiles: None,
         merge: None,
+        concurrency: None,
+        claude_permissions: None,
+        always: Vec::new(),
+        strict_variables: None,
+        defaults: None,
+        protected_paths: None,
+        score_gate: None,
     });
 
     // Add imports
@@ -119,6 +146,13 @@ async fn test_workflow_with_template() -> Result<()> {
         env_files: None,
         profiles: None,
         merge: None,
+        concurrency: None,
+        claude_permissions: None,
+        always: Vec::new(),
+        strict_variables: None,
+        defaults: None,
+        protected_paths: None,
+        score_gate: None,
     });
 
     // Add template usage
@@ -146,6 +180,13 @@ async fn test_workflow_with_sub_workflows() -> Result<()> {
         env_files: None,
         profiles: None,
         merge: None,
+        concurrency: None,
+        claude_permissions: None,
+        always: Vec::new(),
+        strict_variables: None,
+        defaults: None,
+        protected_paths: None,
+        score_gate: None,
     });
 
     // Add sub-workflows
@@ -191,6 +232,13 @@ async fn test_template_registry() -> Result<()> {
         env_files: None,
         profiles: None,
         merge: None,
+        concurrency: None,
+        claude_permissions: None,
+        always: Vec::new(),
+        strict_variables: None,
+        defaults: None,
+        protected_paths: None,
+        score_gate: None,
     });
 
     // Register template
@@ -229,6 +277,13 @@ async fn test_parameter_type_validation() -> Result<()> {
         env_files: None,
         profiles: None,
         merge: None,
+        concurrency: None,
+        claude_permissions: None,
+        always: Vec::new(),
+        strict_variables: None,
+        defaults: None,
+        protected_paths: None,
+        score_gate: None,
     });
 
     workflow.parameters = Some(ParameterDefinitions {
@@ -239,6 +294,9 @@ async fn test_parameter_type_validation() -> Result<()> {
                 description: "Number of iterations".to_string(),
                 default: None,
                 validation: None,
+                values: vec![],
+                secret: false,
+                env: None,
             },
             Parameter {
                 name: "enabled".to_string(),
@@ -246,6 +304,9 @@ async fn test_parameter_type_validation() -> Result<()> {
                 description: "Enable feature".to_string(),
                 default: None,
                 validation: None,
+                values: vec![],
+                secret: false,
+                env: None,
             },
         ],
         optional: vec![],
@@ -279,6 +340,13 @@ async fn test_workflow_defaults() -> Result<()> {
         env_files: None,
         profiles: None,
         merge: None,
+        concurrency: None,
+        claude_permissions: None,
+        always: Vec::new(),
+        strict_variables: None,
+        defaults: None,
+        protected_paths: None,
+        score_gate: None,
     });
 
     // Add defaults
@@ -308,6 +376,13 @@ async fn test_workflow_inheritance() -> Result<()> {
         env_files: None,
         profiles: None,
         merge: None,
+        concurrency: None,
+        claude_permissions: None,
+        always: Vec::new(),
+        strict_variables: None,
+        defaults: None,
+        protected_paths: None,
+        score_gate: None,
     });
 
     // Set up inheritance
@@ -327,6 +402,13 @@ async fn test_required_parameters() -> Result<()> {
         env_files: None,
         profiles: None,
         merge: None,
+        concurrency: None,
+        claude_permissions: None,
+        always: Vec::new(),
+        strict_variables: None,
+        defaults: None,
+        protected_paths: None,
+        score_gate: None,
     });
 
     workflow.parameters = Some(ParameterDefinitions {
@@ -337,6 +419,9 @@ async fn test_required_parameters() -> Result<()> {
                 description: "Input file".to_string(),
                 default: None,
                 validation: None,
+                values: vec![],
+                secret: false,
+                env: None,
             },
             Parameter {
                 name: "output".to_string(),
@@ -344,6 +429,9 @@ async fn test_required_parameters() -> Result<()> {
                 description: "Output file".to_string(),
                 default: None,
                 validation: None,
+                values: vec![],
+                secret: false,
+                env: None,
             },
         ],
         optional: vec![],
@@ -376,6 +464,13 @@ async fn test_template_metadata() -> Result<()> {
         env_files: None,
         profiles: None,
         merge: None,
+        concurrency: None,
+        claude_permissions: None,
+        always: Vec::new(),
+        strict_variables: None,
+        defaults: None,
+        protected_paths: None,
+        score_gate: None,
     });
 
     let metadata = TemplateMetadata {
@@ -420,6 +515,13 @@ async fn test_template_search_by_tags() -> Result<()> {
         env_files: None,
         profiles: None,
         merge: None,
+        concurrency: None,
+        claude_permissions: None,
+        always: Vec::new(),
+        strict_variables: None,
+        defaults: None,
+        protected_paths: None,
+        score_gate: None,
     });
     let metadata1 = TemplateMetadata {
         description: Some("Refactoring template".to_string()),
@@ -442,6 +544,13 @@ async fn test_template_search_by_tags() -> Result<()> {
         env_files: None,
         profiles: None,
         merge: None,
+        concurrency: None,
+        claude_permissions: None,
+        always: Vec::new(),
+        strict_variables: None,
+        defaults: None,
+        protected_paths: None,
+        score_gate: None,
     });
     let metadata2 = TemplateMetadata {
         description: Some("Testing template".to_string()),
@@ -27,11 +27,35 @@ fn create_test_config(workflow: WorkflowConfig) -> CookConfig {
             verbosity: 0,
             quiet: false,
             dry_run: false,
+            read_only: false,
+            record: None,
+            junit_output: None,
+            profile_steps: false,
+            replay: None,
+            chaos: None,
+            seed: None,
+            answers_file: None,
+            environment: None,
+            no_worktree: false,
             params: HashMap::new(),
+            package: None,
+            changed_only: None,
+            explain_failure: false,
+            keep_going: false,
+            steps: Default::default(),
+            explicit_flags: Default::default(),
         },
         project_path: Arc::new(PathBuf::from("/tmp/test")),
         workflow: Arc::new(workflow),
         mapreduce_config: None,
+        replay: None,
+        chaos: None,
+        junit: None,
+        profiler: None,
+        protected_paths: None,
+        answers: None,
+        failures: None,
+        settings: Default::default(),
     }
 }
 
@@ -49,6 +73,13 @@ mod standard_workflow {
             profiles: None,
             merge: None,
             commands: vec![WorkflowCommand::Simple("test-command".to_string())],
+            concurrency: None,
+            claude_permissions: None,
+            always: Vec::new(),
+            strict_variables: None,
+            defaults: None,
+            protected_paths: None,
+            score_gate: None,
         };
 
         let config = create_test_config(workflow);
@@ -69,6 +100,13 @@ mod standard_workflow {
             profiles: None,
             merge: None,
             commands: vec![WorkflowCommand::Simple("test-command".to_string())],
+            concurrency: None,
+            claude_permissions: None,
+            always: Vec::new(),
+            strict_variables: None,
+            defaults: None,
+            protected_paths: None,
+            score_gate: None,
         };
 
         let config = create_test_config(workflow);
@@ -89,6 +127,13 @@ mod standard_workflow {
             profiles: None,
             merge: None,
             commands: vec![WorkflowCommand::Simple("test-command".to_string())],
+            concurrency: None,
+            claude_permissions: None,
+            always: Vec::new(),
+            strict_variables: None,
+            defaults: None,
+            protected_paths: None,
+            score_gate: None,
         };
 
         let config = create_test_config(workflow);
@@ -116,6 +161,13 @@ mod structured_workflow {
                 // WorkflowCommand doesn't have WithOutput variant in actual code
                 WorkflowCommand::Simple("test-command".to_string()),
             ],
+            concurrency: None,
+            claude_permissions: None,
+            always: Vec::new(),
+            strict_variables: None,
+            defaults: None,
+            protected_paths: None,
+            score_gate: None,
         };
 
         let config = create_test_config(workflow);
@@ -139,6 +191,13 @@ mod args_workflow {
             profiles: None,
             merge: None,
             commands: vec![WorkflowCommand::Simple("test-command".to_string())],
+            concurrency: None,
+            claude_permissions: None,
+            always: Vec::new(),
+            strict_variables: None,
+            defaults: None,
+            protected_paths: None,
+            score_gate: None,
         };
 
         let mut config = create_test_config(workflow);
@@ -163,6 +222,13 @@ mod mapreduce_workflow {
             env_files: None,
             profiles: None,
             merge: None,
+            concurrency: None,
+            claude_permissions: None,
+            always: Vec::new(),
+            strict_variables: None,
+            defaults: None,
+            protected_paths: None,
+            score_gate: None,
         };
 
         // MapReduceWorkflowConfig would be in a separate field
@@ -179,11 +245,35 @@ mod mapreduce_workflow {
                 verbosity: 0,
                 quiet: false,
                 dry_run: false,
+                read_only: false,
+                record: None,
+                junit_output: None,
+                profile_steps: false,
+                replay: None,
+                chaos: None,
+                seed: None,
+                answers_file: None,
+                environment: None,
+                no_worktree: false,
                 params: HashMap::new(),
+                package: None,
+                changed_only: None,
+                explain_failure: false,
+                keep_going: false,
+                steps: Default::default(),
+                explicit_flags: Default::default(),
             },
             project_path: Arc::new(PathBuf::from("/tmp/test")),
             workflow: Arc::new(workflow),
             mapreduce_config: None,
+            replay: None,
+            chaos: None,
+            junit: None,
+            profiler: None,
+            protected_paths: None,
+            answers: None,
+            failures: None,
+            settings: Default::default(),
         };
 
         // This test documents the MapReduce workflow type
@@ -223,6 +313,13 @@ mod feature_matrix {
             profiles: None,
             merge: None,
             commands: vec![WorkflowCommand::Simple("test".to_string())],
+            concurrency: None,
+            claude_permissions: None,
+            always: Vec::new(),
+            strict_variables: None,
+            defaults: None,
+            protected_paths: None,
+            score_gate: None,
         };
 
         // These classifications should be preserved during migration
@@ -248,6 +345,13 @@ mod migration_tests {
             env_files: None,
             profiles: None,
             merge: None,
+            concurrency: None,
+            claude_permissions: None,
+            always: Vec::new(),
+            strict_variables: None,
+            defaults: None,
+            protected_paths: None,
+            score_gate: None,
         };
         let config = create_test_config(workflow);
 
@@ -149,6 +149,7 @@ commands:
                 duration: std::time::Duration::from_secs(1),
                 completed_at: chrono::Utc::now(),
                 retry_state: None,
+                changes: None,
             },
             prodigy::cook::workflow::checkpoint::CompletedStep {
                 step_index: 1,
@@ -159,6 +160,7 @@ commands:
                 duration: std::time::Duration::from_secs(1),
                 completed_at: chrono::Utc::now(),
                 retry_state: None,
+                changes: None,
             },
         ],
         variable_state: std::collections::HashMap::new(),
@@ -171,6 +173,9 @@ commands:
         workflow_name: Some("test-workflow".to_string()),
         error_recovery_state: None,
         retry_checkpoint_state: None,
+        failure_chains: Vec::new(),
+        repo: None,
+        step_selection: None,
     };
 
     #[allow(deprecated)]
@@ -63,6 +63,7 @@ steps:
             duration: Duration::from_millis(100),
             completed_at: chrono::Utc::now(),
             retry_state: None,
+            changes: None,
         }],
         variable_state: std::collections::HashMap::new(),
         mapreduce_state: None,
@@ -84,6 +85,9 @@ steps:
             },
         ),
         retry_checkpoint_state: None,
+        failure_chains: Vec::new(),
+        repo: None,
+        step_selection: None,
     };
 
     // Save the checkpoint
@@ -198,6 +202,9 @@ async fn test_custom_workflow_filename_in_checkpoint() -> Result<()> {
             workflow_name: Some("test".to_string()),
             error_recovery_state: None,
             retry_checkpoint_state: None,
+            failure_chains: Vec::new(),
+            repo: None,
+            step_selection: None,
         };
 
         // Save and reload